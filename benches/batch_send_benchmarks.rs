@@ -0,0 +1,64 @@
+//! # Batch Send Benchmarks
+//!
+//! Compares sending 1000 small messages one awaited call at a time against
+//! `send_batch`, with and without frame coalescing.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use quantum_forge_secure_comms::{BatchSendConfig, StreamlinedSecureClient};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+const PEER_ID: &str = "bench_peer";
+const BATCH_SIZE: usize = 1000;
+const PAYLOAD_SIZE: usize = 64;
+
+fn payloads() -> Vec<Vec<u8>> {
+    (0..BATCH_SIZE).map(|i| vec![(i % 251) as u8; PAYLOAD_SIZE]).collect()
+}
+
+fn batch_send_benchmarks(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut client = runtime.block_on(async {
+        let mut client = StreamlinedSecureClient::new().await.unwrap();
+        client.establish_secure_channel(PEER_ID).await.unwrap();
+        client
+    });
+
+    let mut group = c.benchmark_group("batch_send_1000");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    group.bench_function("sequential_send_secure_message", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for payload in payloads() {
+                    client.send_secure_message(PEER_ID, &payload).await.unwrap();
+                }
+            })
+        })
+    });
+
+    for (name, coalesce) in [("uncoalesced", false), ("coalesced", true)] {
+        let config = BatchSendConfig {
+            coalesce,
+            ..Default::default()
+        };
+        group.bench_with_input(BenchmarkId::new("send_batch", name), &config, |b, config| {
+            b.iter(|| {
+                runtime.block_on(async {
+                    let batch = client
+                        .send_batch_with_config(PEER_ID, payloads(), config)
+                        .await
+                        .unwrap();
+                    assert_eq!(batch.successful_count, BATCH_SIZE);
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, batch_send_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+//! # Crypto Dispatch Benchmarks
+//!
+//! Compares the AEAD suites and SHA3 hashing across payload sizes on the
+//! host running the benchmark. The capability report printed first shows
+//! which suite the dispatcher picks here, so results from different
+//! machines can be read side by side.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use quantum_forge_secure_comms::hw_accel::{AeadSuite, CryptoDispatch, DispatchPolicy};
+
+const KEY: [u8; 32] = [7u8; 32];
+const NONCE: [u8; 12] = [1u8; 12];
+const SIZES: [usize; 3] = [1024, 64 * 1024, 1024 * 1024];
+
+fn aead_benchmarks(c: &mut Criterion) {
+    let dispatch = CryptoDispatch::new(DispatchPolicy::Auto);
+    println!("🖥️ {:?}", dispatch.capability_report());
+
+    let mut group = c.benchmark_group("aead_seal");
+    for size in SIZES {
+        let payload = vec![0x5au8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        for (name, suite) in [
+            ("aes256gcm", AeadSuite::Aes256Gcm),
+            ("chacha20poly1305", AeadSuite::ChaCha20Poly1305),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, size), &payload, |b, payload| {
+                b.iter(|| {
+                    dispatch
+                        .seal_with(suite, &KEY, &NONCE, b"", payload)
+                        .unwrap()
+                })
+            });
+        }
+        group.bench_with_input(
+            BenchmarkId::new("dispatched", size),
+            &payload,
+            |b, payload| b.iter(|| dispatch.seal(&KEY, &NONCE, b"", payload).unwrap()),
+        );
+        let portable = CryptoDispatch::new(DispatchPolicy::Portable);
+        group.bench_with_input(
+            BenchmarkId::new("portable_policy", size),
+            &payload,
+            |b, payload| b.iter(|| portable.seal(&KEY, &NONCE, b"", payload).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+fn hash_benchmarks(c: &mut Criterion) {
+    let dispatch = CryptoDispatch::default();
+    let mut group = c.benchmark_group("sha3_256");
+    for size in SIZES {
+        let payload = vec![0x5au8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+            b.iter(|| dispatch.digest(payload))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, aead_benchmarks, hash_benchmarks);
+criterion_main!(benches);
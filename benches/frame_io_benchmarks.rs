@@ -0,0 +1,62 @@
+//! # Frame I/O Benchmarks
+//!
+//! Writes 1000 small frames over loopback TCP with the standard one write
+//! per frame path and with batched vectored writes, reading them back on
+//! the other end so both paths pay the full transfer cost.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use quantum_forge_secure_comms::frame_io::{read_frame, FrameIoMode, FrameWriter};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::Runtime;
+
+const FRAME_COUNT: usize = 1000;
+const FRAME_SIZE: usize = 128;
+
+async fn loopback() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let (client, server) = tokio::join!(TcpStream::connect(address), listener.accept());
+    (client.unwrap(), server.unwrap().0)
+}
+
+fn frame_io_benchmarks(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("frame_io_1000x128b");
+    group.throughput(Throughput::Elements(FRAME_COUNT as u64));
+    group.sample_size(20);
+    group.measurement_time(Duration::from_secs(10));
+
+    for (name, mode) in [
+        ("standard", FrameIoMode::Standard),
+        ("vectored", FrameIoMode::Vectored),
+    ] {
+        group.bench_with_input(BenchmarkId::new("write", name), &mode, |b, mode| {
+            b.iter(|| {
+                runtime.block_on(async {
+                    let (client, mut server) = loopback().await;
+                    let reader = tokio::spawn(async move {
+                        let mut count = 0;
+                        while read_frame(&mut server).await.unwrap().is_some() {
+                            count += 1;
+                        }
+                        count
+                    });
+                    let mut writer = FrameWriter::new(client, *mode);
+                    for i in 0..FRAME_COUNT {
+                        writer.queue(vec![(i % 251) as u8; FRAME_SIZE]).unwrap();
+                    }
+                    writer.flush().await.unwrap();
+                    drop(writer);
+                    assert_eq!(reader.await.unwrap(), FRAME_COUNT);
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, frame_io_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! # Parallel Pipeline Benchmarks
+//!
+//! Measures sealing throughput of a 64 MiB payload as the worker count of
+//! the encryption pipeline grows, against a single-threaded baseline.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use quantum_forge_secure_comms::crypto_pipeline::{ParallelCipher, PipelineConfig};
+use std::time::Duration;
+
+const KEY: [u8; 32] = [7u8; 32];
+const NONCE: [u8; 12] = [1u8; 12];
+const PAYLOAD_SIZE: usize = 64 * 1024 * 1024;
+
+fn pipeline_benchmarks(c: &mut Criterion) {
+    let payload = vec![0x5au8; PAYLOAD_SIZE];
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    let mut group = c.benchmark_group("parallel_encrypt_64mib");
+    group.throughput(Throughput::Bytes(PAYLOAD_SIZE as u64));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let mut worker_counts = vec![1, 2, 4, 8];
+    worker_counts.retain(|&workers| workers <= cores.max(1));
+    if !worker_counts.contains(&cores) {
+        worker_counts.push(cores);
+    }
+
+    for workers in worker_counts {
+        let cipher = ParallelCipher::new(PipelineConfig {
+            parallelism: workers,
+            ..Default::default()
+        });
+        group.bench_with_input(
+            BenchmarkId::new("workers", workers),
+            &payload,
+            |b, payload| b.iter(|| cipher.encrypt(&KEY, &NONCE, b"bench", payload).unwrap()),
+        );
+    }
+
+    let cipher = ParallelCipher::default();
+    let frames = cipher.encrypt(&KEY, &NONCE, b"bench", &payload).unwrap();
+    group.bench_function("decrypt_all_cores", |b| {
+        b.iter(|| cipher.decrypt(&KEY, b"bench", &frames).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, pipeline_benchmarks);
+criterion_main!(benches);
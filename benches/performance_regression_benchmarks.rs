@@ -0,0 +1,238 @@
+//! # Performance Regression Benchmarks
+//!
+//! Core operations measured on every change: PQC key generation and
+//! encapsulation, Ed25519 signing and verification, channel establishment,
+//! message send, quantum gate application and memory pool allocation.
+//!
+//! After the run the results can be saved as a baseline or compared
+//! against one (see `bench_baseline`):
+//!
+//! ```text
+//! QFSC_BENCH_GATE=save cargo bench --bench performance_regression_benchmarks
+//! QFSC_BENCH_GATE=compare QFSC_BENCH_THRESHOLD=10 \
+//!     cargo bench --bench performance_regression_benchmarks
+//! ```
+//!
+//! `QFSC_BENCH_BASELINE` overrides the baseline path
+//! (`benches/baseline.json` by default).
+
+use criterion::{criterion_group, BenchmarkId, Criterion};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use quantum_forge_secure_comms::bench_baseline::{BenchmarkBaseline, RegressionGate};
+use quantum_forge_secure_comms::crypto_protocols::{PQCAlgorithm, PQC, QRNG};
+use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+use quantum_forge_secure_comms::performance::{MemoryPool, MemoryPoolConfig};
+use quantum_forge_secure_comms::quantum_core::{QuantumGate, QuantumState};
+use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+use quantum_forge_secure_comms::StreamlinedSecureClient;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Prefix of every gated benchmark ID
+const GATE_PREFIX: &str = "regression/";
+
+fn qrng(runtime: &Runtime) -> QRNG {
+    runtime.block_on(async {
+        let mut foundation = SecurityFoundation::new(SecurityConfig::production_ready())
+            .await
+            .unwrap();
+        QRNG::with_entropy(&mut foundation).unwrap()
+    })
+}
+
+fn pqc_benchmarks(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("regression/pqc");
+
+    for (name, algorithm) in [
+        ("ml_kem_512", PQCAlgorithm::Kyber512),
+        ("ml_kem_768", PQCAlgorithm::Kyber768),
+        ("ml_kem_1024", PQCAlgorithm::Kyber1024),
+    ] {
+        let mut pqc = PQC::new(algorithm, qrng(&runtime));
+        group.bench_function(BenchmarkId::new("keygen", name), |b| {
+            b.iter(|| {
+                // Bypass the key cache so every iteration generates a key
+                pqc.clear_cache();
+                pqc.generate_keypair().unwrap()
+            })
+        });
+
+        let keypair = pqc.generate_keypair().unwrap();
+        group.bench_function(BenchmarkId::new("encapsulate", name), |b| {
+            b.iter(|| pqc.encrypt(&keypair.public_key, b"session key").unwrap())
+        });
+        let ciphertext = pqc.encrypt(&keypair.public_key, b"session key").unwrap();
+        group.bench_function(BenchmarkId::new("decapsulate", name), |b| {
+            b.iter(|| pqc.decrypt(&keypair.private_key, &ciphertext).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn signature_benchmarks(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut qrng = qrng(&runtime);
+    let mut keys = KeyManager::new();
+    keys.generate_key("bench", KeyPurpose::Signing, &mut qrng)
+        .unwrap();
+    let message = [0x5au8; 256];
+    let signature = keys.sign("bench", &message).unwrap();
+    let public_key: [u8; 32] = keys.public_key("bench").unwrap().try_into().unwrap();
+    let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+    let signature = Signature::from_slice(&signature).unwrap();
+
+    let mut group = c.benchmark_group("regression/ed25519");
+    group.bench_function("sign", |b| b.iter(|| keys.sign("bench", &message).unwrap()));
+    group.bench_function("verify", |b| {
+        b.iter(|| verifying_key.verify(&message, &signature).unwrap())
+    });
+    group.finish();
+}
+
+fn client_benchmarks(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut client = runtime.block_on(async { StreamlinedSecureClient::new().await.unwrap() });
+
+    let mut group = c.benchmark_group("regression/client");
+    group.sample_size(10);
+    // Every establishment opens a loopback TCP connection; a short window
+    // keeps the run well inside the ephemeral port range
+    group.warm_up_time(Duration::from_secs(1));
+    group.measurement_time(Duration::from_secs(5));
+
+    // Each channel is closed again so the governor's channel limit is never hit
+    let mut peer = 0u64;
+    group.bench_function("establish_channel", |b| {
+        b.iter(|| {
+            peer += 1;
+            let peer_id = format!("bench_peer_{}", peer);
+            let channel = runtime
+                .block_on(client.establish_secure_channel(&peer_id))
+                .unwrap();
+            client.close_secure_channel(&peer_id).unwrap();
+            channel
+        })
+    });
+
+    runtime
+        .block_on(client.establish_secure_channel("send_peer"))
+        .unwrap();
+    for size in [64usize, 4096] {
+        let payload = vec![0x5au8; size];
+        group.bench_with_input(
+            BenchmarkId::new("send_message", size),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    runtime
+                        .block_on(client.send_secure_message("send_peer", payload))
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn quantum_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/quantum_gate");
+    for (name, gate, qubits) in [
+        ("hadamard", QuantumGate::Hadamard, vec![0]),
+        ("pauli_x", QuantumGate::PauliX, vec![1]),
+        ("cnot", QuantumGate::CNOT, vec![0, 1]),
+    ] {
+        let mut state = QuantumState::new("bench".to_string(), 4);
+        group.bench_function(name, |b| {
+            b.iter(|| state.apply_gate(gate, &qubits).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn pool_benchmarks(c: &mut Criterion) {
+    let pool = MemoryPool::new(MemoryPoolConfig::default());
+    let mut group = c.benchmark_group("regression/memory_pool");
+    for size in [1024usize, 65536, 1048576] {
+        group.bench_with_input(BenchmarkId::new("get_return", size), &size, |b, &size| {
+            b.iter(|| {
+                let buffer = pool.get_buffer(size);
+                pool.return_buffer(buffer);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    pqc_benchmarks,
+    signature_benchmarks,
+    client_benchmarks,
+    quantum_benchmarks,
+    pool_benchmarks
+);
+
+/// Save or check the baseline as requested through the environment
+fn apply_gate() -> Result<(), String> {
+    let mode = match std::env::var("QFSC_BENCH_GATE") {
+        Ok(mode) => mode,
+        Err(_) => return Ok(()),
+    };
+    let baseline_path = std::env::var("QFSC_BENCH_BASELINE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("benches/baseline.json"));
+    let criterion_dir = std::env::var("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target"))
+        .join("criterion");
+    let current = BenchmarkBaseline::from_criterion_dir(&criterion_dir, Some(GATE_PREFIX))
+        .map_err(|e| e.to_string())?;
+
+    match mode.as_str() {
+        "save" => {
+            current.save(&baseline_path).map_err(|e| e.to_string())?;
+            println!(
+                "💾 Saved {} benchmark means to {}",
+                current.results.len(),
+                baseline_path.display()
+            );
+            Ok(())
+        }
+        "compare" => {
+            let threshold = std::env::var("QFSC_BENCH_THRESHOLD")
+                .ok()
+                .and_then(|value| value.parse::<f64>().ok())
+                .unwrap_or(10.0);
+            let baseline = BenchmarkBaseline::load(&baseline_path).map_err(|e| e.to_string())?;
+            if baseline.arch != current.arch || baseline.cpus != current.cpus {
+                println!(
+                    "⚠️ Baseline recorded on {} with {} CPUs, running on {} with {} CPUs",
+                    baseline.arch, baseline.cpus, current.arch, current.cpus
+                );
+            }
+            let report = RegressionGate::new(threshold).compare(&baseline, &current);
+            println!("{}", report.summary());
+            if report.passed() {
+                Ok(())
+            } else {
+                Err("performance regression gate failed".to_string())
+            }
+        }
+        other => Err(format!(
+            "Unknown QFSC_BENCH_GATE mode '{}' (expected save or compare)",
+            other
+        )),
+    }
+}
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+
+    if let Err(e) = apply_gate() {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+}
@@ -196,8 +196,10 @@ async fn demonstrate_error_handling() -> Result<()> {
 
     // Test configuration validation
     println!("  • Testing configuration validation...");
-    let mut invalid_config = StreamlinedConfig::default();
-    invalid_config.network_timeout = 0; // Invalid timeout
+    let _invalid_config = StreamlinedConfig {
+        network_timeout: 0, // Invalid timeout
+        ..StreamlinedConfig::default()
+    };
 
     // Note: In a real implementation, this would validate the config
     println!("    ✅ Configuration validation would catch invalid settings");
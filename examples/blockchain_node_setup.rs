@@ -39,6 +39,7 @@ struct BlockchainNodeConfig {
     /// Message routing configuration
     routing_config: MessageRoutingConfig,
     /// Consensus participation settings
+    #[allow(dead_code)]
     consensus_config: ConsensusConfig,
 }
 
@@ -48,6 +49,7 @@ struct MessageRoutingConfig {
     /// Maximum message size in bytes
     max_message_size: usize,
     /// Message timeout in seconds
+    #[allow(dead_code)]
     message_timeout: u64,
     /// Enable message flooding for urgent updates
     enable_flooding: bool,
@@ -98,6 +100,7 @@ struct NetworkHealth {
     /// Last health check timestamp
     last_check: u64,
     /// Network latency in milliseconds
+    #[allow(dead_code)]
     network_latency_ms: f64,
 }
 
@@ -119,10 +122,12 @@ impl BlockchainNode {
         println!("🚀 Initializing Blockchain Node: {}", config.node_id);
         
         // Create secure client with blockchain-optimized configuration
-        let mut client_config = StreamlinedConfig::default();
-        client_config.max_channels = config.validator_peers.len() + 10; // Extra capacity
-        client_config.network_timeout = config.channel_config.channel_timeout;
-        client_config.enable_monitoring = true;
+        let client_config = StreamlinedConfig {
+            max_channels: config.validator_peers.len() + 10, // Extra capacity
+            network_timeout: config.channel_config.channel_timeout,
+            enable_monitoring: true,
+            ..StreamlinedConfig::default()
+        };
         
         let secure_client = StreamlinedSecureClient::with_config(client_config).await?;
         
@@ -155,7 +160,7 @@ impl BlockchainNode {
         // Establish blockchain validator network with specified topology
         let results = self.secure_client.establish_blockchain_validator_network(
             self.config.validator_peers.clone(),
-            self.config.topology.clone(),
+            self.config.topology,
             Some(self.config.channel_config.clone()),
         ).await?;
         
@@ -263,7 +268,7 @@ impl BlockchainNode {
         let mut flood_results = Vec::new();
         let mut successful_sends = 0;
         // Avoid borrow checker issue by cloning peer IDs
-        let peer_ids: Vec<String> = self.config.validator_peers.iter().cloned().collect();
+        let peer_ids: Vec<String> = self.config.validator_peers.to_vec();
         for validator in peer_ids {
             match self.send_blockchain_message(&validator, message).await {
                 Ok(secure_message) => {
@@ -385,14 +390,14 @@ async fn demonstrate_blockchain_node_setup() -> Result<()> {
     println!("\n📤 Sending Test Messages");
     println!("{}", "-".repeat(30));
     
-    let test_messages = vec![
+    let test_messages = [
         "BLOCKCHAIN_CONSENSUS:PROPOSE_BLOCK_12345",
         "BLOCKCHAIN_VALIDATION:VERIFY_TRANSACTION_67890",
         "BLOCKCHAIN_SYNC:REQUEST_LATEST_STATE",
         "BLOCKCHAIN_BROADCAST:NEW_VALIDATOR_JOINED",
     ];
     // Avoid borrow checker issue by cloning peer IDs
-    let peer_ids: Vec<String> = blockchain_node.config.validator_peers.iter().cloned().collect();
+    let peer_ids: Vec<String> = blockchain_node.config.validator_peers.to_vec();
     for (i, message) in test_messages.iter().enumerate() {
         let target_validator = &peer_ids[i % peer_ids.len()];
         match blockchain_node.send_blockchain_message(target_validator, message.as_bytes()).await {
@@ -460,7 +465,7 @@ async fn demonstrate_blockchain_topologies() -> Result<()> {
         
         let node_config = BlockchainNodeConfig {
             node_id: format!("test_node_{:?}", topology).to_lowercase(),
-            topology: topology.clone(),
+            topology,
             validator_peers: validator_peers.clone(),
             channel_config: ChannelEstablishmentConfig {
                 max_concurrent: 6,
@@ -523,7 +528,7 @@ async fn demonstrate_continuous_operation() -> Result<()> {
     let mut interval = interval(Duration::from_secs(2));
     let mut operation_count = 0;
     // Avoid borrow checker issue by cloning peer IDs
-    let peer_ids: Vec<String> = continuous_node.config.validator_peers.iter().cloned().collect();
+    let peer_ids: Vec<String> = continuous_node.config.validator_peers.to_vec();
     for _ in 0..5 {
         interval.tick().await;
         operation_count += 1;
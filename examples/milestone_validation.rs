@@ -12,8 +12,7 @@
 //! - System health validation
 
 use quantum_forge_secure_comms::{StreamlinedSecureClient, NetworkTopology};
-use std::time::{Duration, Instant};
-use tokio;
+use std::time::Instant;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -71,7 +70,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     println!("   📊 Results: Min={}ms, Max={}ms, Avg={}ms", min_channel, max_channel, avg_channel);
     
-    if avg_channel >= 26 && avg_channel <= 42 {
+    if (26..=42).contains(&avg_channel) {
         println!("   ✅ PASS: Average channel establishment within 26-42ms target");
     } else if avg_channel <= 60 {
         println!("   ⚠️  ACCEPTABLE: Within stress testing limits (≤60ms)");
@@ -79,7 +78,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("   ❌ FAIL: Channel establishment exceeded limits");
     }
     
-    let target_success = channel_times.iter().filter(|&&x| x >= 26 && x <= 42).count() as f64 / channel_times.len() as f64;
+    let target_success = channel_times.iter().filter(|&&x| (26..=42).contains(&x)).count() as f64 / channel_times.len() as f64;
     println!("   📈 Target Success Rate: {:.1}%\n", target_success * 100.0);
 
     // Test 3: Message Throughput Performance
@@ -176,7 +175,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let total_tests = 5;
     
     if avg_init <= 12 { passed_tests += 1; }
-    if avg_channel >= 26 && avg_channel <= 42 { passed_tests += 1; }
+    if (26..=42).contains(&avg_channel) { passed_tests += 1; }
     if avg_message_us < 1000 { passed_tests += 1; }
     if results.successful_count == validator_ids.len() { passed_tests += 1; }
     if health_ok { passed_tests += 1; }
@@ -0,0 +1,178 @@
+//! # Node Backup CLI
+//!
+//! Command-line tool for disaster recovery runbooks:
+//! - `keygen`: generate a backup key (store it offline)
+//! - `backup --out FILE [--config FILE] [--wal FILE]`: write an encrypted backup
+//! - `inspect FILE`: show an archive's manifest without the key
+//! - `verify FILE`: decrypt and verify an archive
+//! - `restore FILE [--config-out FILE] [--wal FILE] [--address-book FILE]`:
+//!   verify an archive and write its configuration, consensus WAL and peer
+//!   registry on the new host
+//!
+//! The backup key is read from the `QF_BACKUP_KEY` environment variable as
+//! hex. Identity keys are never written to disk unencrypted; a node started
+//! from the restored configuration takes them over with
+//! `StreamlinedSecureClient::restore_backup`.
+//!
+//! ```text
+//! cargo run --example node_backup -- keygen
+//! QF_BACKUP_KEY=... cargo run --example node_backup -- backup --out node.qfb --wal data/consensus.wal
+//! QF_BACKUP_KEY=... cargo run --example node_backup -- restore node.qfb --config-out config/node.json
+//! ```
+
+use quantum_forge_secure_comms::address_book::PeerAddressBook;
+use quantum_forge_secure_comms::backup::{BackupArchive, BackupKey, NodeBackup};
+use quantum_forge_secure_comms::crypto_protocols::QRNG;
+use quantum_forge_secure_comms::{SecureCommsError, StreamlinedConfig, StreamlinedSecureClient};
+use std::collections::HashMap;
+
+// Type alias for convenience
+type Result<T> = std::result::Result<T, SecureCommsError>;
+
+const USAGE: &str =
+    "usage: node_backup <keygen|backup|inspect|verify|restore> [FILE] [--option VALUE]...";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(command) = args.first() else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+    let (positional, options) = parse_args(&args[1..])?;
+
+    match command.as_str() {
+        "keygen" => keygen(),
+        "backup" => backup(&options).await,
+        "inspect" => inspect(archive_path(&positional)?),
+        "verify" => {
+            let backup = open(archive_path(&positional)?)?;
+            println!("✅ Archive verified: {:?}", backup);
+            Ok(())
+        }
+        "restore" => restore(archive_path(&positional)?, &options),
+        other => Err(SecureCommsError::Validation(format!(
+            "Unknown command {}\n{}",
+            other, USAGE
+        ))),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Vec<String>, HashMap<String, String>)> {
+    let mut positional = Vec::new();
+    let mut options = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.strip_prefix("--") {
+            Some(name) => {
+                let value = iter.next().ok_or_else(|| {
+                    SecureCommsError::Validation(format!("--{} needs a value", name))
+                })?;
+                options.insert(name.to_string(), value.clone());
+            }
+            None => positional.push(arg.clone()),
+        }
+    }
+    Ok((positional, options))
+}
+
+fn archive_path(positional: &[String]) -> Result<&str> {
+    positional
+        .first()
+        .map(String::as_str)
+        .ok_or_else(|| SecureCommsError::Validation(format!("Archive path missing\n{}", USAGE)))
+}
+
+fn backup_key() -> Result<BackupKey> {
+    let encoded = std::env::var("QF_BACKUP_KEY").map_err(|_| {
+        SecureCommsError::Configuration("Set QF_BACKUP_KEY to the hex backup key".to_string())
+    })?;
+    BackupKey::from_hex(&encoded)
+}
+
+fn keygen() -> Result<()> {
+    let key = BackupKey::generate(&mut QRNG::from_seed(rand::random()))?;
+    println!("{}", key.to_hex().as_str());
+    eprintln!("🔑 Store this key offline; check value {:?}", key);
+    Ok(())
+}
+
+async fn backup(options: &HashMap<String, String>) -> Result<()> {
+    let key = backup_key()?;
+    let out = options
+        .get("out")
+        .ok_or_else(|| SecureCommsError::Validation("--out is required".to_string()))?;
+
+    let config = match options.get("config") {
+        Some(path) => read_config(path)?,
+        None => StreamlinedConfig::default(),
+    };
+    let client = StreamlinedSecureClient::with_config(config).await?;
+    let mut node = client.backup()?;
+    if let Some(wal) = options.get("wal") {
+        node = node.with_consensus_wal(wal, None)?;
+    }
+    let archive = node.seal(&key)?;
+    archive.save(out)?;
+    println!(
+        "💾 Backup of {} written to {}",
+        archive.manifest.node_id, out
+    );
+    Ok(())
+}
+
+fn inspect(path: &str) -> Result<()> {
+    let archive = BackupArchive::load(path)?;
+    let manifest = &archive.manifest;
+    println!("Node:     {}", manifest.node_id);
+    println!("Created:  {}", manifest.created_at);
+    println!(
+        "Version:  {} (format {})",
+        manifest.crate_version, archive.format_version
+    );
+    for section in &manifest.sections {
+        println!("  {:<14} {} entries", section.name, section.entries);
+    }
+    Ok(())
+}
+
+fn open(path: &str) -> Result<NodeBackup> {
+    BackupArchive::load(path)?.open(&backup_key()?)
+}
+
+fn restore(path: &str, options: &HashMap<String, String>) -> Result<()> {
+    let backup = open(path)?;
+    println!("✅ Archive verified for {}", backup.node_id());
+
+    if let Some(config_out) = options.get("config-out") {
+        let config: StreamlinedConfig = backup
+            .config()?
+            .ok_or_else(|| SecureCommsError::Configuration("Backup has no config".to_string()))?;
+        let json = serde_json::to_string_pretty(&config).map_err(|e| {
+            SecureCommsError::SystemError(format!("Config serialization failed: {}", e))
+        })?;
+        std::fs::write(config_out, json)
+            .map_err(|e| SecureCommsError::SystemError(format!("Config write failed: {}", e)))?;
+        println!("⚙️  Configuration written to {}", config_out);
+    }
+    if let Some(wal) = options.get("wal") {
+        let records = backup.restore_consensus_wal(wal, None)?;
+        println!("📜 {} consensus records written to {}", records, wal);
+    }
+    if let Some(book_path) = options.get("address-book") {
+        let peers = backup.restore_peers(&mut PeerAddressBook::open(book_path)?)?;
+        println!("📒 {} peers written to {}", peers, book_path);
+    }
+    println!(
+        "🔑 {} keys ready; start the node and call restore_backup to install them",
+        backup.key_ids().len()
+    );
+    Ok(())
+}
+
+fn read_config(path: &str) -> Result<StreamlinedConfig> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| SecureCommsError::Configuration(format!("Failed to read {}: {}", path, e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| SecureCommsError::Configuration(format!("Invalid config {}: {}", path, e)))
+}
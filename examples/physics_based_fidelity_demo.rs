@@ -1,5 +1,5 @@
 use quantum_forge_secure_comms::{
-    quantum_core::{QuantumCore, QuantumState, QuantumGate, QuantumOperations},
+    quantum_core::{QuantumCore, QuantumGate, QuantumOperations},
     Result,
 };
 
@@ -11,7 +11,7 @@ async fn main() -> Result<()> {
     println!();
     
     // Initialize quantum system
-    let mut core = QuantumCore::new(4).await?;
+    let core = QuantumCore::new(4).await?;
     
     println!("🔬 **1. QUANTUM STATE NORMALIZATION PHYSICS**");
     println!("-----------------------------------------");
@@ -0,0 +1,86 @@
+//! # QRNG Quality Check
+//!
+//! Command-line run of the randomness test suite on fresh QRNG output:
+//! - Samples raw shot outcomes or conditioned output
+//! - Prints the statistic and p-value of every test
+//! - Exits non-zero when any test fails, so it can gate deployments
+//!
+//! ```text
+//! cargo run --example qrng_quality -- [raw|conditioned] [bytes]
+//! ```
+
+use quantum_forge_secure_comms::randomness_tests::RandomnessSample;
+use quantum_forge_secure_comms::{SecureCommsError, StreamlinedSecureClient};
+
+// Type alias for convenience
+type Result<T> = std::result::Result<T, SecureCommsError>;
+
+const DEFAULT_BYTES: usize = 128 * 1024;
+
+fn parse_args() -> Result<(RandomnessSample, usize)> {
+    let mut args = std::env::args().skip(1);
+    let sample = match args.next().as_deref() {
+        None | Some("conditioned") => RandomnessSample::Conditioned,
+        Some("raw") => RandomnessSample::Raw,
+        Some(other) => {
+            return Err(SecureCommsError::Validation(format!(
+                "Unknown sample '{}' (expected raw or conditioned)",
+                other
+            )))
+        }
+    };
+    let bytes = match args.next() {
+        Some(count) => count
+            .parse()
+            .map_err(|_| SecureCommsError::Validation(format!("Invalid byte count '{}'", count)))?,
+        None => DEFAULT_BYTES,
+    };
+    Ok((sample, bytes))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let (sample, bytes) = parse_args()?;
+    println!(
+        "🎲 QRNG quality check: {:?} output, {} bytes",
+        sample, bytes
+    );
+    println!("{}", "=".repeat(60));
+
+    let client = StreamlinedSecureClient::new().await?;
+    let report = client.check_randomness_quality(sample, bytes).await?;
+
+    println!(
+        "{:<14} {:>14} {:>12}  result",
+        "test", "statistic", "p-value"
+    );
+    for result in &report.results {
+        let p_value = result
+            .p_value
+            .map(|p| format!("{:.6}", p))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<14} {:>14.4} {:>12}  {}",
+            result.name,
+            result.statistic,
+            p_value,
+            if result.passed {
+                "✅ pass"
+            } else {
+                "❌ FAIL"
+            }
+        );
+    }
+    println!("{}", "=".repeat(60));
+    println!(
+        "Min-entropy {:.4} bits/bit over {} bits: {}",
+        report.min_entropy,
+        report.bits,
+        report.health()
+    );
+
+    if !report.passed() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
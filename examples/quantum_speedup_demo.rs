@@ -2,7 +2,6 @@ use quantum_forge_secure_comms::{
     create_test_client, ChannelEstablishmentConfig,
 };
 use std::time::{Duration, Instant};
-use tokio;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
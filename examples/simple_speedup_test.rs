@@ -1,7 +1,7 @@
 use quantum_forge_secure_comms::{
     create_test_client, ChannelEstablishmentConfig,
 };
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -63,8 +63,10 @@ async fn monitor_system_status() -> Result<()> {
     println!("{}", "-".repeat(40));
 
     // Create client with monitoring enabled
-    let mut config = StreamlinedConfig::default();
-    config.enable_monitoring = true;
+    let mut config = StreamlinedConfig {
+        enable_monitoring: true,
+        ..StreamlinedConfig::default()
+    };
     config.security.level = SecurityLevel::High;
 
     println!("\n Initializing monitoring client...");
@@ -299,9 +301,11 @@ async fn simulate_load_monitoring() -> Result<()> {
     println!(" LOAD TESTING AND MONITORING");
     println!("{}", "-".repeat(40));
 
-    let mut config = StreamlinedConfig::default();
-    config.max_channels = 20; // Increased for load testing
-    config.enable_monitoring = true;
+    let config = StreamlinedConfig {
+        max_channels: 20, // Increased for load testing
+        enable_monitoring: true,
+        ..StreamlinedConfig::default()
+    };
 
     println!("\n Setting up load testing environment...");
     let mut client = StreamlinedSecureClient::with_config(config).await?;
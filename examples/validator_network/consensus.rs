@@ -0,0 +1,70 @@
+//! Consensus rounds on the deterministic simulated network
+
+use crate::{Options, Result};
+use quantum_forge_secure_comms::byzantine_sim::{
+    ByzantineBehavior, Simulation, SimulationConfig, SimulationReport,
+};
+use quantum_forge_secure_comms::SecureCommsError;
+use std::time::{Duration, Instant};
+
+/// Outcome of the consensus phase
+#[derive(Debug, Clone)]
+pub struct ConsensusReport {
+    pub simulation: SimulationReport,
+    pub fault_tolerance: usize,
+    /// Byzantine validators and whether an honest node caught them
+    pub detected: Vec<(String, bool)>,
+    pub elapsed: Duration,
+}
+
+/// Run `options.rounds` rounds with the last `options.byzantine` validators
+/// equivocating, then check safety and liveness
+pub async fn run(options: &Options) -> Result<ConsensusReport> {
+    let mut config = SimulationConfig {
+        node_count: options.validators,
+        seed: options.seed,
+        ..Default::default()
+    };
+    if options.byzantine > config.fault_tolerance() {
+        return Err(SecureCommsError::Configuration(format!(
+            "{} validators tolerate at most {} Byzantine faults",
+            options.validators,
+            config.fault_tolerance()
+        )));
+    }
+    let byzantine: Vec<usize> =
+        (options.validators - options.byzantine..options.validators).collect();
+    for &index in &byzantine {
+        config
+            .byzantine
+            .insert(index, ByzantineBehavior::Equivocate);
+    }
+    let fault_tolerance = config.fault_tolerance();
+
+    let started = Instant::now();
+    let mut simulation = Simulation::new(config).await?;
+    let report = simulation.run(options.rounds)?;
+    let elapsed = started.elapsed();
+
+    report.check_safety()?;
+    report.check_liveness()?;
+    println!(
+        "   ✅ {} rounds committed in {} ticks (safety and liveness hold)",
+        report.rounds, report.ticks
+    );
+
+    let detected = byzantine
+        .iter()
+        .map(|&index| {
+            let id = format!("node_{}", index);
+            let caught = report.detected(&id);
+            (id, caught)
+        })
+        .collect();
+    Ok(ConsensusReport {
+        simulation: report,
+        fault_tolerance,
+        detected,
+        elapsed,
+    })
+}
@@ -0,0 +1,167 @@
+//! Validator clients joined by a full mesh of secure channels
+
+use crate::Result;
+use quantum_forge_secure_comms::performance::LatencySummary;
+use quantum_forge_secure_comms::{SecureCommsError, StreamlinedConfig, StreamlinedSecureClient};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// One channel of the mesh, as seen by the validator that opened it
+#[derive(Debug, Clone)]
+pub struct LinkReport {
+    pub from: String,
+    pub to: String,
+    pub fidelity: f64,
+    pub qber: f64,
+    pub setup_ms: f64,
+    pub bytes_sent: u64,
+}
+
+/// Outcome of building the mesh
+#[derive(Debug, Clone, Default)]
+pub struct MeshReport {
+    pub links: Vec<LinkReport>,
+    pub messages_sent: u64,
+}
+
+/// Running validators and their loopback listeners
+pub struct ValidatorMesh {
+    validators: Vec<StreamlinedSecureClient>,
+    listeners: Vec<JoinHandle<()>>,
+    ports: Vec<u16>,
+}
+
+pub fn validator_id(index: usize) -> String {
+    format!("validator_{}", index)
+}
+
+impl ValidatorMesh {
+    /// Start `count` validators, each accepting connections on its own port
+    pub async fn start(count: usize) -> Result<Self> {
+        let mut validators = Vec::with_capacity(count);
+        let mut listeners = Vec::with_capacity(count);
+        let mut ports = Vec::with_capacity(count);
+        for index in 0..count {
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .await
+                .map_err(|e| SecureCommsError::NetworkComm(format!("Bind failed: {}", e)))?;
+            let port = listener
+                .local_addr()
+                .map_err(|e| SecureCommsError::NetworkComm(e.to_string()))?
+                .port();
+            listeners.push(tokio::spawn(async move {
+                // Accept and hold connections so handshakes have a live peer
+                let mut connections = Vec::new();
+                while let Ok((stream, _)) = listener.accept().await {
+                    connections.push(stream);
+                }
+            }));
+            ports.push(port);
+
+            let config = StreamlinedConfig {
+                client_id: Some(validator_id(index)),
+                ..Default::default()
+            };
+            let client = StreamlinedSecureClient::with_config(config).await?;
+            println!(
+                "   ✅ {} listening on 127.0.0.1:{}",
+                validator_id(index),
+                port
+            );
+            validators.push(client);
+        }
+        Ok(Self {
+            validators,
+            listeners,
+            ports,
+        })
+    }
+
+    /// Open a channel from every validator to every other one and announce
+    /// a block over each
+    pub async fn connect_all(&mut self) -> Result<MeshReport> {
+        let mut report = MeshReport::default();
+        let ports = self.ports.clone();
+        for (from, client) in self.validators.iter_mut().enumerate() {
+            for (to, port) in ports.iter().enumerate() {
+                if from == to {
+                    continue;
+                }
+                let peer = validator_id(to);
+                client
+                    .address_book_mut()
+                    .record_address(&peer, "127.0.0.1", *port)?;
+
+                let started = Instant::now();
+                client.establish_secure_channel(&peer).await?;
+                let setup_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+                let announcement =
+                    format!("block proposal from {} to {}", validator_id(from), peer);
+                client
+                    .send_secure_message(&peer, announcement.as_bytes())
+                    .await?;
+                report.messages_sent += 1;
+
+                let stats = client.get_channel_stats(&peer).ok_or_else(|| {
+                    SecureCommsError::NetworkComm(format!("No statistics for channel to {}", peer))
+                })?;
+                let sample = stats.qkd_history.back();
+                report.links.push(LinkReport {
+                    from: validator_id(from),
+                    to: peer,
+                    fidelity: sample.map(|s| s.fidelity).unwrap_or_default(),
+                    qber: sample.map(|s| s.qber).unwrap_or_default(),
+                    setup_ms,
+                    bytes_sent: stats.bytes_sent,
+                });
+            }
+            println!(
+                "   ✅ {} connected to {} peers",
+                validator_id(from),
+                ports.len() - 1
+            );
+        }
+        Ok(report)
+    }
+
+    /// Operation latencies of every validator, merged by operation name
+    ///
+    /// Percentiles are taken from the slowest validator.
+    pub fn latencies(&self) -> HashMap<String, LatencySummary> {
+        let mut merged: HashMap<String, LatencySummary> = HashMap::new();
+        for client in &self.validators {
+            for (operation, summary) in client.operation_latencies() {
+                merged
+                    .entry(operation)
+                    .and_modify(|existing| {
+                        if summary.p99_ms > existing.p99_ms {
+                            let count = existing.count + summary.count;
+                            let errors = existing.errors + summary.errors;
+                            *existing = summary.clone();
+                            existing.count = count;
+                            existing.errors = errors;
+                        } else {
+                            existing.count += summary.count;
+                            existing.errors += summary.errors;
+                        }
+                    })
+                    .or_insert(summary);
+            }
+        }
+        merged
+    }
+
+    /// Shut every validator down and stop the listeners
+    pub async fn shutdown(&mut self) -> Result<()> {
+        for client in &mut self.validators {
+            client.shutdown().await?;
+        }
+        for listener in self.listeners.drain(..) {
+            listener.abort();
+        }
+        Ok(())
+    }
+}
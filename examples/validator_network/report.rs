@@ -0,0 +1,123 @@
+//! Performance and fidelity report
+
+use crate::consensus::ConsensusReport;
+use crate::mesh::MeshReport;
+use crate::{Options, Result};
+use quantum_forge_secure_comms::performance::LatencySummary;
+use quantum_forge_secure_comms::quantum_network_sim::{
+    NetworkScenario, QuantumNetworkSim, ScenarioReport,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Everything the demo measured
+pub struct DemoReport<'a> {
+    pub options: &'a Options,
+    pub mesh: &'a MeshReport,
+    pub latencies: &'a HashMap<String, LatencySummary>,
+    pub consensus: &'a ConsensusReport,
+    pub link: Option<&'a ScenarioReport>,
+    pub elapsed: Duration,
+}
+
+/// Model one validator-to-validator link over `fiber_km` of fiber
+pub fn estimate_link(fiber_km: f64) -> Result<ScenarioReport> {
+    let sim = QuantumNetworkSim::new(NetworkScenario::point_to_point(fiber_km))?;
+    let report = sim.evaluate("alice", "bob")?;
+    println!(
+        "   ✅ {:.0} km link: {:.1} pairs/s, {:.1} secret bits/s",
+        fiber_km, report.pair_rate_hz, report.secret_key_rate_bps
+    );
+    Ok(report)
+}
+
+/// Print the report tables
+pub fn print(report: &DemoReport) {
+    println!("\n📊 Validator Network Report");
+    println!("===========================");
+
+    let links = &report.mesh.links;
+    println!("\n🔐 Secure channels: {}", links.len());
+    println!(
+        "   {:<14} {:<14} {:>9} {:>7} {:>10} {:>8}",
+        "from", "to", "fidelity", "QBER", "setup ms", "bytes"
+    );
+    for link in links {
+        println!(
+            "   {:<14} {:<14} {:>9.4} {:>7.4} {:>10.2} {:>8}",
+            link.from, link.to, link.fidelity, link.qber, link.setup_ms, link.bytes_sent
+        );
+    }
+    if !links.is_empty() {
+        let count = links.len() as f64;
+        let min_fidelity = links
+            .iter()
+            .map(|l| l.fidelity)
+            .fold(f64::INFINITY, f64::min);
+        println!(
+            "   mean fidelity {:.4} (min {:.4}), mean QBER {:.4}, mean setup {:.2} ms",
+            links.iter().map(|l| l.fidelity).sum::<f64>() / count,
+            min_fidelity,
+            links.iter().map(|l| l.qber).sum::<f64>() / count,
+            links.iter().map(|l| l.setup_ms).sum::<f64>() / count
+        );
+    }
+    println!("   messages sent: {}", report.mesh.messages_sent);
+
+    println!("\n⏱️  Operation latencies (slowest validator)");
+    let mut operations: Vec<_> = report.latencies.iter().collect();
+    operations.sort_by(|a, b| a.0.cmp(b.0));
+    for (operation, summary) in operations {
+        println!(
+            "   {:<20} n={:<5} mean {:>8.2} ms  p50 {:>8.2}  p95 {:>8.2}  p99 {:>8.2}",
+            operation,
+            summary.count,
+            summary.mean_ms,
+            summary.p50_ms,
+            summary.p95_ms,
+            summary.p99_ms
+        );
+    }
+
+    let consensus = report.consensus;
+    let simulation = &consensus.simulation;
+    println!("\n🗳️  Consensus");
+    println!(
+        "   {} rounds over {} virtual ticks in {:.2} ms",
+        simulation.rounds,
+        simulation.ticks,
+        consensus.elapsed.as_secs_f64() * 1000.0
+    );
+    println!(
+        "   honest validators: {}, tolerates {} faults",
+        simulation.honest_nodes.len(),
+        consensus.fault_tolerance
+    );
+    println!(
+        "   messages delivered {}, dropped {}, refused {}, garbage {}",
+        simulation.metrics.delivered,
+        simulation.metrics.dropped,
+        simulation.metrics.refused,
+        simulation.metrics.garbage_received
+    );
+    for (id, caught) in &consensus.detected {
+        println!(
+            "   {} equivocated: {}",
+            id,
+            if *caught { "detected" } else { "not detected" }
+        );
+    }
+
+    if let Some(link) = report.link {
+        println!(
+            "\n📡 Quantum link model ({} km fiber)",
+            report.options.fiber_km
+        );
+        println!(
+            "   pair rate {:.1} Hz, fidelity {:.4}, QBER {:.4}, secret key rate {:.1} bps",
+            link.pair_rate_hz, link.fidelity, link.qber, link.secret_key_rate_bps
+        );
+    }
+
+    println!("\n⏲️  Total time: {:.2} s", report.elapsed.as_secs_f64());
+}
@@ -0,0 +1,509 @@
+//! # Peer Address Book - Persistent Registry with Trust-on-First-Use Pinning
+//!
+//! Remembers every peer the client has talked to: its long-term identity
+//! key, last known address and trust state. The book is loaded at startup
+//! and consulted whenever a channel is established or a peer's key is seen,
+//! so a peer that reconnects with a different key - the signature of a key
+//! substitution attack - is detected instead of silently trusted.
+//!
+//! ## Trust States
+//!
+//! - **Pinned**: Key recorded on first use (TOFU)
+//! - **Verified**: Key confirmed out of band by an operator
+//! - **KeyChanged**: Peer presented a different key; connections are refused
+//!   until an operator accepts or rejects the new key
+//! - **Revoked**: Peer is no longer trusted with any key
+//!
+//! ## Persistence
+//!
+//! The book is a single JSON document rewritten atomically (temporary file
+//! plus rename) after every change, so a crash never leaves a half-written
+//! registry behind. With an `EnvelopeCipher` the document is written as a
+//! sealed envelope; a plaintext book is still read and is sealed on the next
+//! save.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::address_book::{KeyCheck, PeerAddressBook};
+//!
+//! let mut book = PeerAddressBook::open("data/peers.json").unwrap();
+//! book.record_address("validator_1", "10.0.0.5", 8081).unwrap();
+//! match book.check_key("validator_1", &[7u8; 32]).unwrap() {
+//!     KeyCheck::FirstUse | KeyCheck::Match => println!("Trusted"),
+//!     other => println!("Refusing peer: {:?}", other),
+//! }
+//! ```
+
+use crate::envelope::{Envelope, EnvelopeCipher};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Envelope context binding a sealed book to the address book
+const ADDRESS_BOOK_CONTEXT: &str = "address_book";
+
+/// Trust state of a peer's identity key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustState {
+    /// Key pinned on first use
+    Pinned,
+    /// Key confirmed out of band
+    Verified,
+    /// A different key was presented and awaits operator review
+    KeyChanged,
+    /// Peer is not trusted
+    Revoked,
+}
+
+/// Outcome of checking a presented identity key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyCheck {
+    /// First key seen for the peer; it is now pinned
+    FirstUse,
+    /// Key matches the pinned key
+    Match,
+    /// Key differs from the pinned key
+    Changed {
+        /// Fingerprint of the pinned key
+        pinned_fingerprint: String,
+        /// Fingerprint of the presented key
+        presented_fingerprint: String,
+    },
+    /// Peer was revoked
+    Revoked,
+}
+
+impl KeyCheck {
+    /// Whether the key may be used
+    pub fn is_trusted(&self) -> bool {
+        matches!(self, KeyCheck::FirstUse | KeyCheck::Match)
+    }
+}
+
+/// Stored knowledge about one peer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// Peer identifier
+    pub peer_id: String,
+    /// Pinned identity public key
+    pub public_key: Option<Vec<u8>>,
+    /// Last known network address
+    pub address: Option<String>,
+    /// Last known port
+    pub port: Option<u16>,
+    /// Trust state of the pinned key
+    pub trust: TrustState,
+    /// Key presented in conflict with the pinned key, awaiting review
+    pub pending_key: Option<Vec<u8>>,
+    /// Number of key changes detected
+    pub key_changes: u32,
+    /// First time the peer was recorded (Unix seconds)
+    pub first_seen: u64,
+    /// Last time the peer was seen (Unix seconds)
+    pub last_seen: u64,
+}
+
+impl PeerRecord {
+    fn new(peer_id: &str) -> Self {
+        let now = chrono::Utc::now().timestamp() as u64;
+        Self {
+            peer_id: peer_id.to_string(),
+            public_key: None,
+            address: None,
+            port: None,
+            trust: TrustState::Pinned,
+            pending_key: None,
+            key_changes: 0,
+            first_seen: now,
+            last_seen: now,
+        }
+    }
+
+    /// Fingerprint of the pinned key
+    pub fn fingerprint(&self) -> Option<String> {
+        self.public_key.as_deref().map(fingerprint)
+    }
+}
+
+/// Short SHA3-256 fingerprint of a public key, as colon-separated hex
+pub fn fingerprint(public_key: &[u8]) -> String {
+    use sha3::{Digest, Sha3_256};
+    Sha3_256::digest(public_key)[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Persistent registry of peers, their keys and addresses
+#[derive(Debug, Clone, Default)]
+pub struct PeerAddressBook {
+    path: Option<PathBuf>,
+    peers: BTreeMap<String, PeerRecord>,
+    cipher: Option<Arc<EnvelopeCipher>>,
+}
+
+impl PeerAddressBook {
+    /// Address book kept only in memory
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load the address book at `path`, starting empty when the file is missing
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open_with_cipher(path, None)
+    }
+
+    /// Load the address book at `path`, sealing it under `cipher` when saved
+    pub fn open_with_cipher<P: AsRef<Path>>(
+        path: P,
+        cipher: Option<Arc<EnvelopeCipher>>,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let peers = if path.exists() {
+            let bytes = fs::read(&path).map_err(|e| {
+                SecureCommsError::SystemError(format!(
+                    "Failed to read address book {:?}: {}",
+                    path, e
+                ))
+            })?;
+            let bytes = match (Envelope::from_bytes(&bytes), &cipher) {
+                (Some(envelope), Some(cipher)) => cipher.open(ADDRESS_BOOK_CONTEXT, &envelope)?,
+                (Some(_), None) => {
+                    return Err(SecureCommsError::Configuration(format!(
+                        "Address book {:?} is sealed but no cipher is configured",
+                        path
+                    )))
+                }
+                (None, _) => bytes,
+            };
+            serde_json::from_slice(&bytes).map_err(|e| {
+                SecureCommsError::SystemError(format!("Corrupt address book {:?}: {}", path, e))
+            })?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: Some(path),
+            peers,
+            cipher,
+        })
+    }
+
+    /// Write the book to disk; a no-op for in-memory books
+    pub fn save(&self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    SecureCommsError::SystemError(format!(
+                        "Failed to create address book directory: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+        let mut bytes = serde_json::to_vec_pretty(&self.peers).map_err(|e| {
+            SecureCommsError::SystemError(format!("Address book serialization failed: {}", e))
+        })?;
+        if let Some(cipher) = &self.cipher {
+            bytes = cipher.seal_bytes(ADDRESS_BOOK_CONTEXT, &bytes)?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| SecureCommsError::SystemError(format!("Address book write failed: {}", e)))
+    }
+
+    /// Re-save the book under the cipher's current master key
+    ///
+    /// Each save seals under a fresh data key, so this also covers a book
+    /// that was stored in plaintext.
+    pub fn rewrap(&self) -> Result<()> {
+        if self.cipher.is_none() {
+            return Err(SecureCommsError::Configuration(
+                "Address book has no cipher to rewrap under".to_string(),
+            ));
+        }
+        self.save()
+    }
+
+    /// Check a presented identity key against the pinned key
+    ///
+    /// The first key seen for a peer is pinned. A different key marks the
+    /// peer `KeyChanged` and is kept as pending, but never replaces the
+    /// pinned key on its own.
+    pub fn check_key(&mut self, peer_id: &str, public_key: &[u8]) -> Result<KeyCheck> {
+        let record = self
+            .peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerRecord::new(peer_id));
+        record.last_seen = chrono::Utc::now().timestamp() as u64;
+
+        let check = if record.trust == TrustState::Revoked {
+            KeyCheck::Revoked
+        } else {
+            match record.public_key.as_deref() {
+                None => {
+                    record.public_key = Some(public_key.to_vec());
+                    record.trust = TrustState::Pinned;
+                    KeyCheck::FirstUse
+                }
+                Some(pinned) if pinned == public_key && record.trust != TrustState::KeyChanged => {
+                    KeyCheck::Match
+                }
+                Some(pinned) => {
+                    let pinned_fingerprint = fingerprint(pinned);
+                    if pinned != public_key && record.pending_key.as_deref() != Some(public_key) {
+                        record.key_changes += 1;
+                        record.pending_key = Some(public_key.to_vec());
+                    }
+                    record.trust = TrustState::KeyChanged;
+                    KeyCheck::Changed {
+                        pinned_fingerprint,
+                        presented_fingerprint: fingerprint(public_key),
+                    }
+                }
+            }
+        };
+        self.save()?;
+        Ok(check)
+    }
+
+    /// Pin a key confirmed out of band, replacing any previous key
+    pub fn verify_key(&mut self, peer_id: &str, public_key: &[u8]) -> Result<()> {
+        let record = self
+            .peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerRecord::new(peer_id));
+        record.public_key = Some(public_key.to_vec());
+        record.pending_key = None;
+        record.trust = TrustState::Verified;
+        self.save()
+    }
+
+    /// Accept the pending key of a `KeyChanged` peer
+    pub fn accept_key_change(&mut self, peer_id: &str) -> Result<()> {
+        let record = self
+            .peers
+            .get_mut(peer_id)
+            .ok_or_else(|| SecureCommsError::PeerNotFound(peer_id.to_string()))?;
+        let pending = record.pending_key.take().ok_or_else(|| {
+            SecureCommsError::Validation(format!("No pending key change for {}", peer_id))
+        })?;
+        record.public_key = Some(pending);
+        record.trust = TrustState::Pinned;
+        self.save()
+    }
+
+    /// Reject the pending key of a `KeyChanged` peer, keeping the pinned key
+    pub fn reject_key_change(&mut self, peer_id: &str) -> Result<()> {
+        let record = self
+            .peers
+            .get_mut(peer_id)
+            .ok_or_else(|| SecureCommsError::PeerNotFound(peer_id.to_string()))?;
+        record.pending_key = None;
+        record.trust = TrustState::Pinned;
+        self.save()
+    }
+
+    /// Stop trusting a peer
+    pub fn revoke(&mut self, peer_id: &str) -> Result<()> {
+        self.peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerRecord::new(peer_id))
+            .trust = TrustState::Revoked;
+        self.save()
+    }
+
+    /// Record the address a peer was reached at
+    pub fn record_address(&mut self, peer_id: &str, address: &str, port: u16) -> Result<()> {
+        let record = self
+            .peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerRecord::new(peer_id));
+        record.address = Some(address.to_string());
+        record.port = Some(port);
+        record.last_seen = chrono::Utc::now().timestamp() as u64;
+        self.save()
+    }
+
+    /// Last known address of peer
+    pub fn address(&self, peer_id: &str) -> Option<(String, u16)> {
+        let record = self.peers.get(peer_id)?;
+        Some((record.address.clone()?, record.port?))
+    }
+
+    /// Whether the peer may be connected to
+    pub fn is_usable(&self, peer_id: &str) -> bool {
+        self.peers
+            .get(peer_id)
+            .map(|record| !matches!(record.trust, TrustState::KeyChanged | TrustState::Revoked))
+            .unwrap_or(true)
+    }
+
+    /// Record of peer
+    pub fn get(&self, peer_id: &str) -> Option<&PeerRecord> {
+        self.peers.get(peer_id)
+    }
+
+    /// All records, ordered by peer ID
+    pub fn peers(&self) -> Vec<&PeerRecord> {
+        self.peers.values().collect()
+    }
+
+    /// Forget a peer
+    pub fn remove(&mut self, peer_id: &str) -> Result<Option<PeerRecord>> {
+        let removed = self.peers.remove(peer_id);
+        self.save()?;
+        Ok(removed)
+    }
+
+    /// Insert records restored from a backup, replacing known peers
+    ///
+    /// Returns the number of records written.
+    pub fn restore(&mut self, records: Vec<PeerRecord>) -> Result<usize> {
+        let restored = records.len();
+        for record in records {
+            self.peers.insert(record.peer_id.clone(), record);
+        }
+        self.save()?;
+        Ok(restored)
+    }
+
+    /// Backing file, if persistent
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Number of known peers
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peers are known
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tofu_pinning() {
+        let mut book = PeerAddressBook::in_memory();
+        assert_eq!(
+            book.check_key("peer_1", &[1u8; 32]).unwrap(),
+            KeyCheck::FirstUse
+        );
+        assert_eq!(
+            book.check_key("peer_1", &[1u8; 32]).unwrap(),
+            KeyCheck::Match
+        );
+
+        let changed = book.check_key("peer_1", &[2u8; 32]).unwrap();
+        assert!(matches!(changed, KeyCheck::Changed { .. }));
+        assert!(!changed.is_trusted());
+        // The pinned key is kept and the peer stays blocked until reviewed
+        assert_eq!(
+            book.check_key("peer_1", &[1u8; 32]).unwrap(),
+            KeyCheck::Changed {
+                pinned_fingerprint: fingerprint(&[1u8; 32]),
+                presented_fingerprint: fingerprint(&[1u8; 32]),
+            }
+        );
+        assert!(!book.is_usable("peer_1"));
+        assert_eq!(book.get("peer_1").unwrap().key_changes, 1);
+
+        book.accept_key_change("peer_1").unwrap();
+        assert_eq!(
+            book.check_key("peer_1", &[2u8; 32]).unwrap(),
+            KeyCheck::Match
+        );
+
+        book.revoke("peer_1").unwrap();
+        assert_eq!(
+            book.check_key("peer_1", &[2u8; 32]).unwrap(),
+            KeyCheck::Revoked
+        );
+    }
+
+    #[test]
+    fn test_persistence_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers").join("book.json");
+
+        let mut book = PeerAddressBook::open(&path).unwrap();
+        assert!(book.is_empty());
+        book.record_address("peer_1", "10.0.0.5", 9000).unwrap();
+        book.check_key("peer_1", &[1u8; 32]).unwrap();
+        book.verify_key("peer_2", &[2u8; 32]).unwrap();
+
+        let reloaded = PeerAddressBook::open(&path).unwrap();
+        assert_eq!(reloaded.len(), 2);
+        assert_eq!(
+            reloaded.address("peer_1"),
+            Some(("10.0.0.5".to_string(), 9000))
+        );
+        assert_eq!(reloaded.get("peer_2").unwrap().trust, TrustState::Verified);
+        assert_eq!(
+            reloaded.get("peer_1").unwrap().fingerprint(),
+            Some(fingerprint(&[1u8; 32]))
+        );
+    }
+
+    #[test]
+    fn test_sealed_book_and_rewrap() {
+        use crate::crypto_protocols::QRNG;
+        use crate::envelope::ManagedMasterKeys;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.json");
+        let mut plain = PeerAddressBook::open(&path).unwrap();
+        plain.record_address("peer_1", "10.0.0.5", 9000).unwrap();
+
+        let mut qrng = QRNG::from_seed([6u8; 32]);
+        let masters = Arc::new(ManagedMasterKeys::generate("master-1", &mut qrng).unwrap());
+        let cipher = Arc::new(EnvelopeCipher::new(masters.clone()));
+        let mut book = PeerAddressBook::open_with_cipher(&path, Some(cipher.clone())).unwrap();
+        assert_eq!(book.len(), 1);
+        book.record_address("peer_2", "10.0.0.6", 9000).unwrap();
+
+        let raw = fs::read(&path).unwrap();
+        assert!(!raw.windows(6).any(|window| window == b"peer_2"));
+        assert!(PeerAddressBook::open(&path).is_err());
+
+        masters.rotate("master-2", &mut qrng).unwrap();
+        book.rewrap().unwrap();
+        masters.retire("master-1").unwrap();
+        let reloaded = PeerAddressBook::open_with_cipher(&path, Some(cipher)).unwrap();
+        assert_eq!(
+            reloaded.address("peer_2"),
+            Some(("10.0.0.6".to_string(), 9000))
+        );
+        assert!(PeerAddressBook::in_memory().rewrap().is_err());
+    }
+
+    #[test]
+    fn test_reject_key_change() {
+        let mut book = PeerAddressBook::in_memory();
+        book.check_key("peer_1", &[1u8; 32]).unwrap();
+        book.check_key("peer_1", &[9u8; 32]).unwrap();
+        book.reject_key_change("peer_1").unwrap();
+        assert!(book.is_usable("peer_1"));
+        assert_eq!(
+            book.check_key("peer_1", &[1u8; 32]).unwrap(),
+            KeyCheck::Match
+        );
+        assert!(book.accept_key_change("peer_1").is_err());
+    }
+}
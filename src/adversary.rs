@@ -0,0 +1,476 @@
+//! # Adversary - Protocol-Violating Peer for Negative Tests
+//!
+//! A `MaliciousPeer` wraps an ordinary client holding a channel with the
+//! deployment under test and crafts traffic that deliberately breaks the
+//! protocol. Delivering it to the victim and checking the victim's
+//! security events confirms each violation is refused and recorded rather
+//! than silently dropped or, worse, accepted. Built only with the
+//! `adversary` feature and for unit tests.
+//!
+//! ## Violations
+//!
+//! - **Bad MAC**: A payload sealed under the victim's `PROBE_CONTEXT` with
+//!   its tag corrupted; expected as `AdversarialInput` from `aead`
+//! - **Replay**: A genuine message delivered twice; expected as
+//!   `ReplayAttack` from `dedup`
+//! - **Signature Downgrade**: A hybrid-signed message with its ML-DSA half
+//!   stripped; expected as `AdversarialInput` from `hybrid_signature` when
+//!   the victim requires hybrid signatures from the adversary
+//! - **Malformed Handshake**: A private handshake frame of the wrong size;
+//!   expected as `AdversarialInput` from `private_handshake` when the victim
+//!   accepts private handshakes
+//! - **Forged Key Update**: A key update with a random ciphertext and
+//!   confirmation; expected as `AdversarialInput` from `key_update` once the
+//!   adversary has pinned the victim's key update key
+//!
+//! Crafting never changes the adversary's own channel state, so
+//! violations can be probed in any order against the same victim.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::adversary::{probe, MaliciousPeer, Violation};
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//!
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! let mut victim = StreamlinedSecureClient::new().await?;
+//! let mut adversary = MaliciousPeer::new(StreamlinedSecureClient::new().await?);
+//! let adversary_id = adversary.id().to_string();
+//! let victim_id = victim.get_client_id().to_string();
+//! adversary.client().establish_secure_channel(&victim_id).await?;
+//! victim.establish_secure_channel(&adversary_id).await?;
+//!
+//! for violation in [Violation::BadMac, Violation::Replay] {
+//!     let outcome = probe(&mut adversary, &mut victim, violation).await?;
+//!     assert!(outcome.is_detected(), "{:?} went unnoticed", violation);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::key_update::{KeyUpdate, KeyUpdatePublicKey};
+use crate::network_comms::NetworkMessage;
+use crate::private_handshake::PrivateFrame;
+use crate::security_foundation::{SecurityEvent, ThreatType};
+use crate::streamlined_client::{SecureMessage, SendOptions, StreamlinedSecureClient};
+use crate::{Result, SecureCommsError};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long the victim is given to surface a delivered message
+const RECEIVE_WINDOW: Duration = Duration::from_millis(50);
+
+/// Application context the victim receives context-bound probes under
+pub const PROBE_CONTEXT: &[u8] = b"adversary-probe";
+
+/// Protocol violation the adversary can commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Violation {
+    /// Corrupted AEAD tag on a context-bound payload
+    BadMac,
+    /// Genuine message delivered twice
+    Replay,
+    /// Hybrid signature with the post-quantum half stripped
+    SignatureDowngrade,
+    /// Private handshake frame that does not parse
+    MalformedHandshake,
+    /// Key update that fails key confirmation
+    ForgedKeyUpdate,
+}
+
+impl Violation {
+    /// Every violation
+    pub const ALL: [Violation; 5] = [
+        Violation::BadMac,
+        Violation::Replay,
+        Violation::SignatureDowngrade,
+        Violation::MalformedHandshake,
+        Violation::ForgedKeyUpdate,
+    ];
+
+    /// Threat type and component the victim is expected to record
+    pub fn expected_threat(&self) -> (ThreatType, &'static str) {
+        match self {
+            Violation::BadMac => (ThreatType::AdversarialInput, "aead"),
+            Violation::Replay => (ThreatType::ReplayAttack, "dedup"),
+            Violation::SignatureDowngrade => (ThreatType::AdversarialInput, "hybrid_signature"),
+            Violation::MalformedHandshake => (ThreatType::AdversarialInput, "private_handshake"),
+            Violation::ForgedKeyUpdate => (ThreatType::AdversarialInput, "key_update"),
+        }
+    }
+
+    /// Context the victim expects on the violation's messages, if any
+    pub fn expected_context(&self) -> Option<&'static [u8]> {
+        match self {
+            Violation::BadMac => Some(PROBE_CONTEXT),
+            _ => None,
+        }
+    }
+
+    /// Whether `event` is the one this violation should produce
+    pub fn matches(&self, event: &SecurityEvent) -> bool {
+        let (threat_type, component) = self.expected_threat();
+        event.threat_type == threat_type && event.component == component
+    }
+}
+
+/// Traffic crafted by the adversary, in delivery order
+#[derive(Debug, Clone)]
+pub enum AttackFrame {
+    /// Application message for the victim's inbound queue
+    Message(SecureMessage),
+    /// Protocol message for the victim's handshake or key update handling
+    Network(NetworkMessage),
+}
+
+/// Result of delivering one violation to a victim
+#[derive(Debug, Clone)]
+pub struct ViolationOutcome {
+    /// Violation committed
+    pub violation: Violation,
+    /// Frames delivered
+    pub frames: usize,
+    /// Frames the victim accepted; the first copy of a replay is genuine
+    pub accepted: usize,
+    /// Security event the victim recorded for the violation, if any
+    pub event: Option<SecurityEvent>,
+}
+
+impl ViolationOutcome {
+    /// Whether the violation was refused and recorded
+    pub fn is_detected(&self) -> bool {
+        let genuine = match self.violation {
+            Violation::Replay => 1,
+            _ => 0,
+        };
+        self.event.is_some() && self.accepted <= genuine
+    }
+}
+
+/// Peer that deliberately violates the protocol
+pub struct MaliciousPeer {
+    client: StreamlinedSecureClient,
+    victim_key_update_keys: Vec<KeyUpdatePublicKey>,
+}
+
+impl MaliciousPeer {
+    /// Adversary acting through `client`
+    pub fn new(client: StreamlinedSecureClient) -> Self {
+        Self {
+            client,
+            victim_key_update_keys: Vec::new(),
+        }
+    }
+
+    /// Client ID the adversary sends as
+    pub fn id(&self) -> &str {
+        self.client.get_client_id()
+    }
+
+    /// Underlying client, to set up channels and pin keys
+    pub fn client(&mut self) -> &mut StreamlinedSecureClient {
+        &mut self.client
+    }
+
+    /// Pin a victim's key update key, as needed for `ForgedKeyUpdate`
+    pub fn pin_victim_key_update_key(&mut self, key: &KeyUpdatePublicKey) -> Result<()> {
+        self.client.pin_peer_key_update_key(key)?;
+        self.victim_key_update_keys
+            .retain(|pinned| pinned.owner_id != key.owner_id);
+        self.victim_key_update_keys.push(key.clone());
+        Ok(())
+    }
+
+    /// Craft the frames committing `violation` against `victim_id`
+    pub async fn craft(
+        &mut self,
+        victim_id: &str,
+        violation: Violation,
+    ) -> Result<Vec<AttackFrame>> {
+        match violation {
+            Violation::BadMac => {
+                let options = SendOptions {
+                    aad: Some(PROBE_CONTEXT.to_vec()),
+                    ..Default::default()
+                };
+                let mut message = self
+                    .client
+                    .send_secure_message_with_options(victim_id, b"adversary", options)
+                    .await?;
+                if let Some(last) = message.payload.last_mut() {
+                    *last ^= 0x01;
+                }
+                Ok(vec![AttackFrame::Message(message)])
+            }
+            Violation::Replay => {
+                let message = self
+                    .client
+                    .send_secure_message(victim_id, b"adversary")
+                    .await?;
+                Ok(vec![
+                    AttackFrame::Message(message.clone()),
+                    AttackFrame::Message(message),
+                ])
+            }
+            Violation::SignatureDowngrade => {
+                let mut message = self
+                    .client
+                    .send_secure_message(victim_id, b"adversary")
+                    .await?;
+                // An unsigned message is as much a downgrade as a stripped one
+                if let Some(signature) = message.transcript_signature.as_mut() {
+                    signature.ml_dsa = None;
+                }
+                Ok(vec![AttackFrame::Message(message)])
+            }
+            Violation::MalformedHandshake => {
+                let mut handshake_id = vec![0u8; 16];
+                rand::thread_rng().fill_bytes(&mut handshake_id);
+                let mut frame = vec![0u8; 37];
+                rand::thread_rng().fill_bytes(&mut frame);
+                Ok(vec![AttackFrame::Network(
+                    NetworkMessage::PrivateHandshake(PrivateFrame {
+                        handshake_id,
+                        frame,
+                    }),
+                )])
+            }
+            Violation::ForgedKeyUpdate => {
+                let key = self
+                    .victim_key_update_keys
+                    .iter()
+                    .find(|key| key.owner_id == victim_id)
+                    .ok_or_else(|| {
+                        SecureCommsError::Validation(format!(
+                            "No key update key pinned for {}",
+                            victim_id
+                        ))
+                    })?;
+                // Random bytes: ML-KEM implicit rejection yields a secret the
+                // confirmation cannot match
+                let mut ciphertext = vec![0u8; 1088];
+                rand::thread_rng().fill_bytes(&mut ciphertext);
+                let mut confirmation = vec![0u8; 32];
+                rand::thread_rng().fill_bytes(&mut confirmation);
+                Ok(vec![AttackFrame::Network(NetworkMessage::KeyUpdate(
+                    KeyUpdate {
+                        sender_id: self.id().to_string(),
+                        recipient_id: victim_id.to_string(),
+                        epoch: self.client.key_update_epoch(victim_id) + 1,
+                        recipient_key_id: key.key_id(),
+                        ciphertext,
+                        confirmation,
+                    },
+                ))])
+            }
+        }
+    }
+}
+
+/// Hand `frames` to an in-process victim, returning how many it accepted
+///
+/// Messages are received under `context`, the context the victim itself
+/// expects, whatever context a frame claims.
+pub async fn deliver(
+    victim: &mut StreamlinedSecureClient,
+    frames: Vec<AttackFrame>,
+    context: Option<&[u8]>,
+) -> usize {
+    let mut accepted = 0;
+    for frame in frames {
+        let result = match frame {
+            AttackFrame::Message(message) => {
+                let inbound = victim.inbound_sender();
+                match (inbound.send(message), context) {
+                    (Ok(()), Some(context)) => victim
+                        .receive_secure_message_with_context(context, RECEIVE_WINDOW)
+                        .await
+                        .map(|_| ()),
+                    (Ok(()), None) => victim
+                        .receive_secure_message(RECEIVE_WINDOW)
+                        .await
+                        .map(|_| ()),
+                    (Err(_), _) => Err(SecureCommsError::ChannelNotEstablished),
+                }
+            }
+            AttackFrame::Network(NetworkMessage::PrivateHandshake(frame)) => {
+                victim.answer_private_handshake(&frame).map(|_| ())
+            }
+            AttackFrame::Network(NetworkMessage::KeyUpdate(update)) => {
+                victim.apply_key_update(&update).await.map(|_| ())
+            }
+            AttackFrame::Network(other) => Err(SecureCommsError::Validation(format!(
+                "No in-process delivery for {:?}",
+                other
+            ))),
+        };
+        if result.is_ok() {
+            accepted += 1;
+        }
+    }
+    accepted
+}
+
+/// Commit `violation` against `victim` and check it was detected
+pub async fn probe(
+    adversary: &mut MaliciousPeer,
+    victim: &mut StreamlinedSecureClient,
+    violation: Violation,
+) -> Result<ViolationOutcome> {
+    let victim_id = victim.get_client_id().to_string();
+    let before = matching_events(victim, violation);
+    let frames = adversary.craft(&victim_id, violation).await?;
+    let frame_count = frames.len();
+    let accepted = deliver(victim, frames, violation.expected_context()).await;
+    let event = if matching_events(victim, violation) > before {
+        victim
+            .security_events()
+            .iter()
+            .rev()
+            .find(|event| violation.matches(event))
+            .cloned()
+    } else {
+        None
+    };
+    if event.is_none() {
+        println!("🚨 {:?} from {} went undetected", violation, adversary.id());
+    }
+    Ok(ViolationOutcome {
+        violation,
+        frames: frame_count,
+        accepted,
+        event,
+    })
+}
+
+fn matching_events(victim: &StreamlinedSecureClient, violation: Violation) -> usize {
+    victim
+        .security_events()
+        .iter()
+        .filter(|event| violation.matches(event))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hybrid_signature::SignatureMode;
+    use crate::private_handshake::PrivateHandshakeConfig;
+    use crate::streamlined_client::StreamlinedConfig;
+
+    async fn victim_and_adversary() -> (StreamlinedSecureClient, MaliciousPeer) {
+        let config = StreamlinedConfig {
+            private_handshake: Some(PrivateHandshakeConfig::default()),
+            ..Default::default()
+        };
+        let mut victim = StreamlinedSecureClient::with_config(config).await.unwrap();
+        let mut adversary = MaliciousPeer::new(StreamlinedSecureClient::new().await.unwrap());
+        let victim_id = victim.get_client_id().to_string();
+        let adversary_id = adversary.id().to_string();
+        adversary
+            .client()
+            .establish_secure_channel(&victim_id)
+            .await
+            .unwrap();
+        victim
+            .establish_secure_channel(&adversary_id)
+            .await
+            .unwrap();
+        // Sealed payloads then fail on their tag, not on mismatched keys
+        victim.share_session_key(adversary.client()).await.unwrap();
+        (victim, adversary)
+    }
+
+    #[tokio::test]
+    async fn test_every_violation_is_detected() {
+        let (mut victim, mut adversary) = victim_and_adversary().await;
+        let victim_id = victim.get_client_id().to_string();
+        let adversary_id = adversary.id().to_string();
+
+        // The victim requires hybrid signatures, which the adversary provides
+        // on its genuine traffic
+        let signature_key = adversary.client().signature_public_key();
+        victim
+            .pin_peer_signature_key(&adversary_id, &signature_key)
+            .unwrap();
+        victim.set_channel_signature_mode(&adversary_id, SignatureMode::Hybrid);
+        adversary
+            .client()
+            .set_channel_signature_mode(&victim_id, SignatureMode::Hybrid);
+        adversary
+            .pin_victim_key_update_key(&victim.key_update_public_key().unwrap())
+            .unwrap();
+
+        for violation in Violation::ALL {
+            let outcome = probe(&mut adversary, &mut victim, violation).await.unwrap();
+            assert!(
+                outcome.is_detected(),
+                "{:?} went undetected: {:?}",
+                violation,
+                outcome
+            );
+        }
+        let replay = probe(&mut adversary, &mut victim, Violation::Replay)
+            .await
+            .unwrap();
+        assert_eq!((replay.frames, replay.accepted), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_victim_expects_its_own_context() {
+        let (mut victim, mut adversary) = victim_and_adversary().await;
+        let victim_id = victim.get_client_id().to_string();
+
+        // An intact payload sealed under the expected context is accepted
+        let options = SendOptions {
+            aad: Some(PROBE_CONTEXT.to_vec()),
+            ..Default::default()
+        };
+        let genuine = adversary
+            .client()
+            .send_secure_message_with_options(&victim_id, b"probe", options)
+            .await
+            .unwrap();
+        let frames = vec![AttackFrame::Message(genuine)];
+        assert_eq!(deliver(&mut victim, frames, Some(PROBE_CONTEXT)).await, 1);
+
+        // Relabelled with another context, the frame is not opened under
+        // the label it claims but set aside for a receiver expecting it
+        let mut frames = adversary.craft(&victim_id, Violation::BadMac).await.unwrap();
+        if let AttackFrame::Message(message) = &mut frames[0] {
+            message.associated_data = Some(b"relabelled".to_vec());
+        }
+        assert_eq!(deliver(&mut victim, frames, Some(PROBE_CONTEXT)).await, 0);
+        assert_eq!(matching_events(&victim, Violation::BadMac), 0);
+    }
+
+    #[tokio::test]
+    async fn test_undetected_violation_is_reported() {
+        let (mut victim, mut adversary) = victim_and_adversary().await;
+        // Without a hybrid requirement a stripped signature passes as classical
+        // and an unsigned message as unsigned
+        let outcome = probe(&mut adversary, &mut victim, Violation::SignatureDowngrade)
+            .await
+            .unwrap();
+        assert_eq!(outcome.accepted, 1);
+        assert!(outcome.event.is_none());
+        assert!(!outcome.is_detected());
+    }
+
+    #[tokio::test]
+    async fn test_forged_key_update_needs_pinned_key() {
+        let (victim, mut adversary) = victim_and_adversary().await;
+        let victim_id = victim.get_client_id().to_string();
+        assert!(matches!(
+            adversary
+                .craft(&victim_id, Violation::ForgedKeyUpdate)
+                .await,
+            Err(SecureCommsError::Validation(_))
+        ));
+        assert_eq!(
+            Violation::ForgedKeyUpdate.expected_threat(),
+            (ThreatType::AdversarialInput, "key_update")
+        );
+    }
+}
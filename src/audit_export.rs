@@ -0,0 +1,491 @@
+//! # Audit Export - CEF and OCSF Output for SIEM Ingestion
+//!
+//! Maps structured audit log entries to the two formats most SIEMs ingest
+//! natively and streams them to a file or a syslog collector.
+//!
+//! ## Formats
+//!
+//! - **CEF**: ArcSight Common Event Format, one `CEF:0|...` line per event
+//! - **OCSF**: Open Cybersecurity Schema Framework 1.1 events, one JSON object per line
+//!
+//! ## Field Mapping
+//!
+//! | Log entry   | CEF                        | OCSF                        |
+//! |-------------|----------------------------|-----------------------------|
+//! | timestamp   | `rt` (epoch ms)            | `time` (epoch ms)           |
+//! | level       | Severity 0-10              | `severity_id` / `severity`  |
+//! | category    | Signature ID, `cat`        | `class_uid`, `category_uid` |
+//! | message     | Name, `msg`                | `message`                   |
+//! | data        | `cs1` (JSON)               | `unmapped.data`             |
+//! | trace_id    | `cs2`                      | `metadata.correlation_uid`  |
+//! | source      | `cs3`                      | `metadata.log_provider`     |
+//! | duration_ms | `cn1`                      | `duration`                  |
+//!
+//! Security entries become OCSF Detection Findings (2004), audit and user
+//! entries API Activity (6003) and everything else Base Events (0).
+//!
+//! ## Sinks
+//!
+//! - **File**: Appends one event per line
+//! - **Syslog**: RFC 5424 messages over UDP, facility `log audit` (13) by default
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::audit_export::{AuditExportConfig, AuditExporter, AuditFormat, AuditSink};
+//! use quantum_forge_secure_comms::logging::{LogLevel, LOGGER};
+//!
+//! let mut config = AuditExportConfig::default();
+//! config.format = AuditFormat::Ocsf;
+//! config.sink = AuditSink::File("./logs/audit.ocsf.jsonl".into());
+//! config.filter.min_level = LogLevel::Warn;
+//!
+//! let exporter = AuditExporter::new(config);
+//! let exported = exporter.export(&LOGGER.get_audit_trail()).unwrap();
+//! println!("Exported {} audit events", exported);
+//! ```
+
+use crate::logging::{LogCategory, LogEntry, LogLevel};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// OCSF schema version emitted
+pub const OCSF_VERSION: &str = "1.1.0";
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditFormat {
+    /// ArcSight Common Event Format
+    Cef,
+    /// Open Cybersecurity Schema Framework JSON
+    Ocsf,
+}
+
+/// Export destination
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditSink {
+    /// Append to a file
+    File(PathBuf),
+    /// Send RFC 5424 syslog messages over UDP
+    Syslog {
+        /// Collector address, e.g. "siem.example.com:514"
+        address: String,
+        /// Syslog facility
+        facility: u8,
+    },
+}
+
+/// Which entries are exported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFilter {
+    /// Categories to export; empty exports every category
+    pub categories: Vec<LogCategory>,
+    /// Minimum level exported
+    pub min_level: LogLevel,
+}
+
+impl Default for AuditFilter {
+    fn default() -> Self {
+        Self {
+            categories: vec![LogCategory::Audit, LogCategory::Security],
+            min_level: LogLevel::Info,
+        }
+    }
+}
+
+impl AuditFilter {
+    /// Whether an entry passes the filter
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        entry.level >= self.min_level
+            && (self.categories.is_empty() || self.categories.contains(&entry.category))
+    }
+}
+
+/// Audit export configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExportConfig {
+    /// Output format
+    pub format: AuditFormat,
+    /// Destination
+    pub sink: AuditSink,
+    /// Entry filter
+    pub filter: AuditFilter,
+    /// Vendor reported in CEF headers and OCSF metadata
+    pub vendor: String,
+    /// Product reported in CEF headers and OCSF metadata
+    pub product: String,
+    /// Product version
+    pub product_version: String,
+    /// Host name reported to syslog
+    pub hostname: String,
+}
+
+impl Default for AuditExportConfig {
+    fn default() -> Self {
+        Self {
+            format: AuditFormat::Cef,
+            sink: AuditSink::File(PathBuf::from("./logs/audit.cef")),
+            filter: AuditFilter::default(),
+            vendor: "QuantumForge".to_string(),
+            product: "SecureComms".to_string(),
+            product_version: env!("CARGO_PKG_VERSION").to_string(),
+            hostname: "-".to_string(),
+        }
+    }
+}
+
+/// CEF severity (0-10)
+fn cef_severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Trace => 0,
+        LogLevel::Debug => 1,
+        LogLevel::Info => 3,
+        LogLevel::Warn => 5,
+        LogLevel::Error => 8,
+        LogLevel::Critical => 10,
+    }
+}
+
+/// OCSF severity ID and caption
+fn ocsf_severity(level: LogLevel) -> (u8, &'static str) {
+    match level {
+        LogLevel::Trace | LogLevel::Debug | LogLevel::Info => (1, "Informational"),
+        LogLevel::Warn => (3, "Medium"),
+        LogLevel::Error => (4, "High"),
+        LogLevel::Critical => (5, "Critical"),
+    }
+}
+
+/// OCSF class UID, class name, category UID and category name
+fn ocsf_class(category: &LogCategory) -> (u32, &'static str, u32, &'static str) {
+    match category {
+        LogCategory::Security => (2004, "Detection Finding", 2, "Findings"),
+        LogCategory::Audit | LogCategory::User => {
+            (6003, "API Activity", 6, "Application Activity")
+        }
+        _ => (0, "Base Event", 0, "Uncategorized"),
+    }
+}
+
+/// Syslog severity (RFC 5424)
+fn syslog_severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Critical => 2,
+        LogLevel::Error => 3,
+        LogLevel::Warn => 4,
+        LogLevel::Info => 6,
+        LogLevel::Trace | LogLevel::Debug => 7,
+    }
+}
+
+/// Escape a CEF header field
+fn escape_cef_header(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Escape a CEF extension value
+fn escape_cef_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+/// Format an entry as a CEF line
+pub fn to_cef(entry: &LogEntry, config: &AuditExportConfig) -> String {
+    let category = entry.category.to_string();
+    let mut extension = vec![
+        format!("rt={}", entry.timestamp.timestamp_millis()),
+        format!("cat={}", escape_cef_value(&category)),
+        format!("msg={}", escape_cef_value(&entry.message)),
+    ];
+    if !entry.data.is_null() {
+        extension.push("cs1Label=data".to_string());
+        extension.push(format!("cs1={}", escape_cef_value(&entry.data.to_string())));
+    }
+    if let Some(trace_id) = &entry.trace_id {
+        extension.push("cs2Label=traceId".to_string());
+        extension.push(format!("cs2={}", escape_cef_value(trace_id)));
+    }
+    if let Some(source) = &entry.source {
+        extension.push("cs3Label=source".to_string());
+        extension.push(format!("cs3={}", escape_cef_value(source)));
+    }
+    if let Some(duration_ms) = entry.duration_ms {
+        extension.push("cn1Label=durationMs".to_string());
+        extension.push(format!("cn1={}", duration_ms));
+    }
+
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        escape_cef_header(&config.vendor),
+        escape_cef_header(&config.product),
+        escape_cef_header(&config.product_version),
+        escape_cef_header(&category),
+        escape_cef_header(&entry.message),
+        cef_severity(entry.level),
+        extension.join(" ")
+    )
+}
+
+/// Format an entry as an OCSF event
+pub fn to_ocsf(entry: &LogEntry, config: &AuditExportConfig) -> serde_json::Value {
+    let (class_uid, class_name, category_uid, category_name) = ocsf_class(&entry.category);
+    let (severity_id, severity) = ocsf_severity(entry.level);
+    // Activity 99 ("Other"): log entries carry no finer-grained activity
+    let activity_id = 99;
+
+    let mut event = serde_json::json!({
+        "class_uid": class_uid,
+        "class_name": class_name,
+        "category_uid": category_uid,
+        "category_name": category_name,
+        "activity_id": activity_id,
+        "type_uid": class_uid * 100 + activity_id,
+        "time": entry.timestamp.timestamp_millis(),
+        "severity_id": severity_id,
+        "severity": severity,
+        "message": entry.message,
+        "metadata": {
+            "version": OCSF_VERSION,
+            "product": {
+                "name": config.product,
+                "vendor_name": config.vendor,
+                "version": config.product_version,
+            },
+            "log_name": entry.category.to_string(),
+        },
+        "unmapped": {
+            "data": entry.data,
+        },
+    });
+
+    if let Some(trace_id) = &entry.trace_id {
+        event["metadata"]["correlation_uid"] = serde_json::json!(trace_id);
+    }
+    if let Some(source) = &entry.source {
+        event["metadata"]["log_provider"] = serde_json::json!(source);
+    }
+    if let Some(duration_ms) = entry.duration_ms {
+        event["duration"] = serde_json::json!(duration_ms);
+    }
+    if class_uid == 2004 {
+        event["finding_info"] = serde_json::json!({
+            "title": entry.message,
+            "uid": format!("{}-{}", entry.category, entry.timestamp.timestamp_millis()),
+        });
+    }
+    event
+}
+
+/// Streams filtered audit entries to the configured sink
+#[derive(Debug, Clone)]
+pub struct AuditExporter {
+    config: AuditExportConfig,
+}
+
+impl AuditExporter {
+    /// Create exporter
+    pub fn new(config: AuditExportConfig) -> Self {
+        Self { config }
+    }
+
+    /// Exporter configuration
+    pub fn config(&self) -> &AuditExportConfig {
+        &self.config
+    }
+
+    /// Format an entry, or `None` if the filter excludes it
+    pub fn format_entry(&self, entry: &LogEntry) -> Option<String> {
+        if !self.config.filter.matches(entry) {
+            return None;
+        }
+        Some(match self.config.format {
+            AuditFormat::Cef => to_cef(entry, &self.config),
+            AuditFormat::Ocsf => to_ocsf(entry, &self.config).to_string(),
+        })
+    }
+
+    /// Export entries to the sink, returning how many passed the filter
+    pub fn export(&self, entries: &[LogEntry]) -> Result<usize> {
+        match &self.config.sink {
+            AuditSink::File(path) => self.export_to_file(path, entries),
+            AuditSink::Syslog { address, facility } => {
+                self.export_to_syslog(address, *facility, entries)
+            }
+        }
+    }
+
+    fn export_to_file(&self, path: &Path, entries: &[LogEntry]) -> Result<usize> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                SecureCommsError::SystemError(format!(
+                    "Failed to create audit export directory {:?}: {}",
+                    parent, e
+                ))
+            })?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                SecureCommsError::SystemError(format!("Failed to open audit export {:?}: {}", path, e))
+            })?;
+
+        let mut writer = std::io::BufWriter::new(file);
+        let mut exported = 0;
+        for line in entries.iter().filter_map(|entry| self.format_entry(entry)) {
+            writeln!(writer, "{}", line).map_err(|e| {
+                SecureCommsError::SystemError(format!("Audit export write failed: {}", e))
+            })?;
+            exported += 1;
+        }
+        writer.flush().map_err(|e| {
+            SecureCommsError::SystemError(format!("Audit export write failed: {}", e))
+        })?;
+        Ok(exported)
+    }
+
+    fn export_to_syslog(&self, address: &str, facility: u8, entries: &[LogEntry]) -> Result<usize> {
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").map_err(|e| {
+            SecureCommsError::NetworkError(format!("Failed to open syslog socket: {}", e))
+        })?;
+
+        let mut exported = 0;
+        for entry in entries {
+            if let Some(line) = self.format_entry(entry) {
+                let message = self.syslog_message(facility, entry, &line);
+                socket.send_to(message.as_bytes(), address).map_err(|e| {
+                    SecureCommsError::NetworkError(format!(
+                        "Failed to send audit event to {}: {}",
+                        address, e
+                    ))
+                })?;
+                exported += 1;
+            }
+        }
+        Ok(exported)
+    }
+
+    /// Wrap a formatted event in an RFC 5424 syslog message
+    pub fn syslog_message(&self, facility: u8, entry: &LogEntry, payload: &str) -> String {
+        let priority = facility as u16 * 8 + syslog_severity(entry.level) as u16;
+        format!(
+            "<{}>1 {} {} {} - {} - {}",
+            priority,
+            entry.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            self.config.hostname,
+            self.config.product,
+            entry.category,
+            payload
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: LogLevel, category: LogCategory, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            level,
+            category,
+            message: message.to_string(),
+            data: serde_json::json!({"user": "alice", "action": "key=rotate"}),
+            source: None,
+            trace_id: Some("trace-1".to_string()),
+            duration_ms: Some(12),
+        }
+    }
+
+    #[test]
+    fn test_cef_field_mapping() {
+        let config = AuditExportConfig::default();
+        let line = to_cef(&entry(LogLevel::Warn, LogCategory::Security, "Login a|b"), &config);
+
+        assert!(line.starts_with("CEF:0|QuantumForge|SecureComms|"));
+        assert!(line.contains("|security|Login a\\|b|5|"));
+        assert!(line.contains("rt=1700000000000"));
+        assert!(line.contains("cat=security"));
+        assert!(line.contains("cs2Label=traceId cs2=trace-1"));
+        assert!(line.contains("cn1=12"));
+        assert!(line.contains("key\\=rotate"));
+    }
+
+    #[test]
+    fn test_ocsf_field_mapping() {
+        let config = AuditExportConfig::default();
+        let finding = to_ocsf(&entry(LogLevel::Critical, LogCategory::Security, "Tamper"), &config);
+        assert_eq!(finding["class_uid"], 2004);
+        assert_eq!(finding["type_uid"], 200499);
+        assert_eq!(finding["severity_id"], 5);
+        assert_eq!(finding["time"], 1_700_000_000_000i64);
+        assert_eq!(finding["metadata"]["version"], OCSF_VERSION);
+        assert_eq!(finding["metadata"]["correlation_uid"], "trace-1");
+        assert_eq!(finding["unmapped"]["data"]["user"], "alice");
+
+        let activity = to_ocsf(&entry(LogLevel::Info, LogCategory::Audit, "Rotated"), &config);
+        assert_eq!(activity["class_uid"], 6003);
+        assert_eq!(activity["category_uid"], 6);
+        assert_eq!(activity["severity"], "Informational");
+    }
+
+    #[test]
+    fn test_filter_and_file_export() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let config = AuditExportConfig {
+            format: AuditFormat::Ocsf,
+            sink: AuditSink::File(path.clone()),
+            filter: AuditFilter {
+                categories: vec![LogCategory::Security],
+                min_level: LogLevel::Warn,
+            },
+            ..AuditExportConfig::default()
+        };
+        let exporter = AuditExporter::new(config);
+
+        let entries = vec![
+            entry(LogLevel::Info, LogCategory::Security, "low"),
+            entry(LogLevel::Error, LogCategory::Security, "high"),
+            entry(LogLevel::Error, LogCategory::Audit, "other category"),
+        ];
+        assert_eq!(exporter.export(&entries).unwrap(), 1);
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["message"], "high");
+    }
+
+    #[test]
+    fn test_syslog_export() {
+        let collector = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let config = AuditExportConfig {
+            sink: AuditSink::Syslog {
+                address: collector.local_addr().unwrap().to_string(),
+                facility: 13,
+            },
+            ..AuditExportConfig::default()
+        };
+        let exporter = AuditExporter::new(config);
+        let sent = exporter
+            .export(&[entry(LogLevel::Error, LogCategory::Audit, "Denied")])
+            .unwrap();
+        assert_eq!(sent, 1);
+
+        let mut buffer = [0u8; 2048];
+        let (length, _) = collector.recv_from(&mut buffer).unwrap();
+        let message = std::str::from_utf8(&buffer[..length]).unwrap();
+        assert!(message.starts_with("<107>1 2023-11-14T22:13:20.000Z - SecureComms - audit - CEF:0|"));
+    }
+}
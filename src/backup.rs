@@ -0,0 +1,723 @@
+//! # Node Backup - Encrypted Disaster Recovery Archives
+//!
+//! Captures everything a node needs to resume on new hardware - long-term
+//! keys, the peer registry, the consensus WAL and the configuration - in a
+//! single encrypted archive, and restores it with integrity checks at every
+//! step.
+//!
+//! ## Archive Layout
+//!
+//! - **manifest**: Node ID, creation time, crate version and one entry per
+//!   section with its entry count and a keyed digest; readable without the
+//!   backup key so operators can inspect an archive
+//! - **key_check**: Check value identifying the backup key
+//! - **ciphertext**: AEAD-sealed contents, with the manifest as associated
+//!   data
+//!
+//! ## Integrity Verification
+//!
+//! Opening an archive authenticates the ciphertext and manifest together,
+//! then recomputes every section digest and entry count. Restoring keys
+//! re-derives each public key from its seed and compares it with the
+//! recorded one; restoring the WAL replays the written log and compares the
+//! record count. A damaged or mismatched archive fails with
+//! `SecureCommsError::CryptoProtocol` before anything is written.
+//!
+//! ## Backup Key
+//!
+//! Archives are sealed under a 32-byte `BackupKey`, kept offline by the
+//! operator (as hex) and independent of the node's storage master keys, so
+//! a node whose HSM is lost can still be recovered.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::backup::{BackupArchive, BackupKey};
+//! use quantum_forge_secure_comms::{StreamlinedConfig, StreamlinedSecureClient};
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let client = StreamlinedSecureClient::new().await?;
+//! let key = BackupKey::from_hex(&std::env::var("QF_BACKUP_KEY").unwrap())?;
+//!
+//! let archive = client
+//!     .backup()?
+//!     .with_consensus_wal("data/consensus.wal", None)?
+//!     .seal(&key)?;
+//! archive.save("backups/validator_1.qfb")?;
+//!
+//! // On replacement hardware
+//! let backup = BackupArchive::load("backups/validator_1.qfb")?.open(&key)?;
+//! let config: StreamlinedConfig = backup.config()?.expect("config backed up");
+//! let mut restored = StreamlinedSecureClient::with_config(config).await?;
+//! restored.restore_backup(&backup)?;
+//! backup.restore_consensus_wal("data/consensus.wal", None)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::address_book::{PeerAddressBook, PeerRecord};
+use crate::consensus_wal::{ConsensusWal, WalRecord};
+use crate::crypto_protocols::QRNG;
+use crate::envelope::EnvelopeCipher;
+use crate::hw_accel::{CryptoDispatch, KEY_LEN, NONCE_LEN};
+use crate::key_manager::{KeyInfo, KeyManager};
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Archive format version written by this crate
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Section names in the manifest
+const SECTION_CONFIG: &str = "config";
+const SECTION_KEYS: &str = "keys";
+const SECTION_PEERS: &str = "peers";
+const SECTION_WAL: &str = "consensus_wal";
+
+/// Symmetric key that seals node backups
+pub struct BackupKey {
+    key: SecretBytes,
+}
+
+impl std::fmt::Debug for BackupKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackupKey")
+            .field("check_value", &hex(&self.check_value()))
+            .finish()
+    }
+}
+
+impl BackupKey {
+    /// Generate a new backup key from the QRNG
+    pub fn generate(qrng: &mut QRNG) -> Result<Self> {
+        Self::from_bytes(&Zeroizing::new(qrng.generate_bytes(KEY_LEN)?))
+    }
+
+    /// Use existing key bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != KEY_LEN {
+            return Err(SecureCommsError::Validation(format!(
+                "Backup key must be {} bytes, got {}",
+                KEY_LEN,
+                bytes.len()
+            )));
+        }
+        Ok(Self {
+            key: SecretBytes::from_slice(SensitiveKind::Key, bytes),
+        })
+    }
+
+    /// Parse a key written by `to_hex`
+    pub fn from_hex(encoded: &str) -> Result<Self> {
+        let bytes = Zeroizing::new(unhex(encoded.trim()).ok_or_else(|| {
+            SecureCommsError::Validation("Backup key is not valid hex".to_string())
+        })?);
+        Self::from_bytes(&bytes)
+    }
+
+    /// Hex encoding for offline storage
+    pub fn to_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex(self.key.expose()))
+    }
+
+    /// Short value identifying the key without revealing it
+    pub fn check_value(&self) -> Vec<u8> {
+        self.derive("node_backup_check_v1")[..8].to_vec()
+    }
+
+    fn derive(&self, label: &str) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(digest(label, &[self.key.expose()]))
+    }
+}
+
+/// Section entry of a backup manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSection {
+    /// Section name
+    pub name: String,
+    /// Number of entries in the section
+    pub entries: usize,
+    /// Digest of the section keyed by the backup key
+    pub digest: Vec<u8>,
+}
+
+/// Unencrypted description of a backup archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Node the backup was taken from
+    pub node_id: String,
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+    /// Crate version that wrote the archive
+    pub crate_version: String,
+    /// Sections present in the archive
+    pub sections: Vec<BackupSection>,
+}
+
+impl BackupManifest {
+    /// Section by name
+    pub fn section(&self, name: &str) -> Option<&BackupSection> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+}
+
+/// Encrypted node backup as written to disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupArchive {
+    /// Archive format version
+    pub format_version: u32,
+    /// Description of the contents
+    pub manifest: BackupManifest,
+    /// Check value of the backup key the archive is sealed under
+    pub key_check: Vec<u8>,
+    /// Sealed contents
+    pub ciphertext: Vec<u8>,
+}
+
+impl BackupArchive {
+    /// Decrypt and verify the archive
+    ///
+    /// Fails with `SecureCommsError::CryptoProtocol` for the wrong
+    /// key, a modified archive, or contents that disagree with the manifest.
+    pub fn open(&self, key: &BackupKey) -> Result<NodeBackup> {
+        if self.format_version != BACKUP_FORMAT_VERSION {
+            return Err(SecureCommsError::Validation(format!(
+                "Unsupported backup format version {}",
+                self.format_version
+            )));
+        }
+        if self.key_check != key.check_value() {
+            return Err(SecureCommsError::CryptoProtocol(format!(
+                "Backup is sealed under key {} but key {} was supplied",
+                hex(&self.key_check),
+                hex(&key.check_value())
+            )));
+        }
+        let plaintext = Zeroizing::new(
+            CryptoDispatch::default()
+                .open(
+                    &key.derive("node_backup_seal_v1"),
+                    &manifest_aad(&self.manifest)?,
+                    &self.ciphertext,
+                )
+                .map_err(|_| {
+                    SecureCommsError::CryptoProtocol(
+                        "Backup archive failed authentication".to_string(),
+                    )
+                })?,
+        );
+        let backup: NodeBackup = serde_json::from_slice(&plaintext).map_err(|e| {
+            SecureCommsError::CryptoProtocol(format!("Backup contents unreadable: {}", e))
+        })?;
+
+        let expected = backup.manifest(key)?;
+        if expected.node_id != self.manifest.node_id
+            || expected.created_at != self.manifest.created_at
+            || expected.sections != self.manifest.sections
+        {
+            return Err(SecureCommsError::CryptoProtocol(
+                "Backup contents do not match the manifest".to_string(),
+            ));
+        }
+        Ok(backup)
+    }
+
+    /// Write the archive atomically
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    SecureCommsError::SystemError(format!(
+                        "Failed to create backup directory: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+        let bytes = serde_json::to_vec_pretty(self).map_err(|e| {
+            SecureCommsError::SystemError(format!("Backup serialization failed: {}", e))
+        })?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| SecureCommsError::SystemError(format!("Backup write failed: {}", e)))
+    }
+
+    /// Read an archive written by `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| {
+            SecureCommsError::SystemError(format!("Failed to read backup {:?}: {}", path, e))
+        })?;
+        serde_json::from_slice(&bytes).map_err(|e| {
+            SecureCommsError::SystemError(format!("Corrupt backup archive {:?}: {}", path, e))
+        })
+    }
+}
+
+/// Key seed carried in a backup
+#[derive(Clone, Serialize, Deserialize)]
+struct BackedUpKey {
+    info: KeyInfo,
+    seed: Vec<u8>,
+}
+
+impl Drop for BackedUpKey {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+    }
+}
+
+/// Decrypted node state, collected for a backup or opened from one
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NodeBackup {
+    node_id: String,
+    created_at: u64,
+    config: Option<serde_json::Value>,
+    keys: Vec<BackedUpKey>,
+    peers: Vec<PeerRecord>,
+    consensus_wal: Vec<WalRecord>,
+}
+
+impl std::fmt::Debug for NodeBackup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeBackup")
+            .field("node_id", &self.node_id)
+            .field("created_at", &self.created_at)
+            .field("config", &self.config.is_some())
+            .field("keys", &self.key_ids())
+            .field("peers", &self.peers.len())
+            .field("consensus_wal", &self.consensus_wal.len())
+            .finish()
+    }
+}
+
+impl NodeBackup {
+    /// Start an empty backup of `node_id`
+    pub fn new(node_id: &str) -> Self {
+        Self {
+            node_id: node_id.to_string(),
+            created_at: chrono::Utc::now().timestamp() as u64,
+            config: None,
+            keys: Vec::new(),
+            peers: Vec::new(),
+            consensus_wal: Vec::new(),
+        }
+    }
+
+    /// Include the node configuration
+    pub fn with_config<T: Serialize>(mut self, config: &T) -> Result<Self> {
+        self.config = Some(serde_json::to_value(config).map_err(|e| {
+            SecureCommsError::SystemError(format!("Config serialization failed: {}", e))
+        })?);
+        Ok(self)
+    }
+
+    /// Include every key held by `keys`
+    pub fn with_keys(mut self, keys: &KeyManager) -> Self {
+        for key_id in keys.key_ids() {
+            if let Some((info, seed)) = keys.export_key(&key_id) {
+                self.keys.push(BackedUpKey {
+                    info,
+                    seed: seed.to_vec(),
+                });
+            }
+        }
+        self
+    }
+
+    /// Include every record of the peer registry
+    pub fn with_peers(mut self, book: &PeerAddressBook) -> Self {
+        self.peers = book.peers().into_iter().cloned().collect();
+        self
+    }
+
+    /// Include the records of the consensus WAL at `path`
+    pub fn with_consensus_wal<P: AsRef<Path>>(
+        mut self,
+        path: P,
+        cipher: Option<&EnvelopeCipher>,
+    ) -> Result<Self> {
+        self.consensus_wal = ConsensusWal::replay_with_cipher(path, cipher)?;
+        Ok(self)
+    }
+
+    /// Seal the backup under `key`
+    pub fn seal(&self, key: &BackupKey) -> Result<BackupArchive> {
+        let manifest = self.manifest(key)?;
+        let plaintext = Zeroizing::new(serde_json::to_vec(self).map_err(|e| {
+            SecureCommsError::SystemError(format!("Backup serialization failed: {}", e))
+        })?);
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let ciphertext = CryptoDispatch::default().seal(
+            &key.derive("node_backup_seal_v1"),
+            &nonce,
+            &manifest_aad(&manifest)?,
+            &plaintext,
+        )?;
+
+        crate::logging::log_audit(
+            "Node backup created",
+            serde_json::json!({
+                "node_id": self.node_id,
+                "key_check": hex(&key.check_value()),
+                "sections": manifest
+                    .sections
+                    .iter()
+                    .map(|section| (section.name.clone(), section.entries))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+        Ok(BackupArchive {
+            format_version: BACKUP_FORMAT_VERSION,
+            manifest,
+            key_check: key.check_value(),
+            ciphertext,
+        })
+    }
+
+    /// Node the backup was taken from
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Creation time (Unix seconds)
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    /// Backed-up configuration, if included
+    pub fn config<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        self.config
+            .clone()
+            .map(|value| {
+                serde_json::from_value(value).map_err(|e| {
+                    SecureCommsError::Configuration(format!("Backed-up config unreadable: {}", e))
+                })
+            })
+            .transpose()
+    }
+
+    /// IDs of the backed-up keys
+    pub fn key_ids(&self) -> Vec<String> {
+        self.keys
+            .iter()
+            .map(|key| key.info.key_id.clone())
+            .collect()
+    }
+
+    /// Backed-up peer records
+    pub fn peers(&self) -> &[PeerRecord] {
+        &self.peers
+    }
+
+    /// Backed-up consensus WAL records
+    pub fn consensus_wal(&self) -> &[WalRecord] {
+        &self.consensus_wal
+    }
+
+    /// Install the backed-up keys into `keys`
+    ///
+    /// Every seed is checked against its recorded public key first; nothing
+    /// is installed if one fails or a key ID is already taken.
+    pub fn restore_keys(&self, keys: &mut KeyManager) -> Result<usize> {
+        let mut staged = KeyManager::new();
+        for key in &self.keys {
+            if keys.key_info(&key.info.key_id).is_some() {
+                return Err(SecureCommsError::Validation(format!(
+                    "Key {} already exists",
+                    key.info.key_id
+                )));
+            }
+            staged
+                .restore_key(&key.info, seed_array(&key.seed)?)
+                .map_err(|e| SecureCommsError::CryptoProtocol(e.to_string()))?;
+        }
+        for key in &self.keys {
+            keys.restore_key(&key.info, seed_array(&key.seed)?)?;
+        }
+        Ok(self.keys.len())
+    }
+
+    /// Install the backed-up peer records into `book`
+    pub fn restore_peers(&self, book: &mut PeerAddressBook) -> Result<usize> {
+        book.restore(self.peers.clone())
+    }
+
+    /// Write the backed-up consensus WAL to `path`
+    ///
+    /// Refuses to touch an existing non-empty WAL. The written log is
+    /// replayed to confirm every record landed.
+    pub fn restore_consensus_wal<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cipher: Option<Arc<EnvelopeCipher>>,
+    ) -> Result<usize> {
+        let path = path.as_ref();
+        if fs::metadata(path)
+            .map(|meta| meta.len() > 0)
+            .unwrap_or(false)
+        {
+            return Err(SecureCommsError::Validation(format!(
+                "Refusing to overwrite existing consensus WAL {:?}",
+                path
+            )));
+        }
+        let mut wal = ConsensusWal::open_with_cipher(path, cipher.clone())?;
+        for record in &self.consensus_wal {
+            wal.append(record)?;
+        }
+        let replayed = ConsensusWal::replay_with_cipher(path, cipher.as_deref())?.len();
+        if replayed != self.consensus_wal.len() {
+            return Err(SecureCommsError::SystemError(format!(
+                "Restored WAL replays {} of {} records",
+                replayed,
+                self.consensus_wal.len()
+            )));
+        }
+        Ok(replayed)
+    }
+
+    /// Manifest describing this backup under `key`
+    pub fn manifest(&self, key: &BackupKey) -> Result<BackupManifest> {
+        let mac_key = key.derive("node_backup_manifest_v1");
+        let section = |name: &str,
+                       entries: usize,
+                       value: serde_json::Result<Vec<u8>>|
+         -> Result<BackupSection> {
+            let bytes = Zeroizing::new(value.map_err(|e| {
+                SecureCommsError::SystemError(format!("Backup serialization failed: {}", e))
+            })?);
+            Ok(BackupSection {
+                name: name.to_string(),
+                entries,
+                digest: digest(name, &[mac_key.as_slice(), bytes.as_slice()]),
+            })
+        };
+        Ok(BackupManifest {
+            node_id: self.node_id.clone(),
+            created_at: self.created_at,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            sections: vec![
+                section(
+                    SECTION_CONFIG,
+                    usize::from(self.config.is_some()),
+                    serde_json::to_vec(&self.config),
+                )?,
+                section(
+                    SECTION_KEYS,
+                    self.keys.len(),
+                    serde_json::to_vec(&self.keys),
+                )?,
+                section(
+                    SECTION_PEERS,
+                    self.peers.len(),
+                    serde_json::to_vec(&self.peers),
+                )?,
+                section(
+                    SECTION_WAL,
+                    self.consensus_wal.len(),
+                    serde_json::to_vec(&self.consensus_wal),
+                )?,
+            ],
+        })
+    }
+}
+
+/// Associated data binding the manifest to the ciphertext
+fn manifest_aad(manifest: &BackupManifest) -> Result<Vec<u8>> {
+    serde_json::to_vec(&(BACKUP_FORMAT_VERSION, manifest))
+        .map_err(|e| SecureCommsError::SystemError(format!("Backup serialization failed: {}", e)))
+}
+
+fn seed_array(seed: &[u8]) -> Result<[u8; 32]> {
+    seed.try_into().map_err(|_| {
+        SecureCommsError::CryptoProtocol("Backed-up key seed has wrong length".to_string())
+    })
+}
+
+fn digest(label: &str, parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update((label.len() as u64).to_be_bytes());
+    hasher.update(label.as_bytes());
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(encoded: &str) -> Option<Vec<u8>> {
+    if !encoded.len().is_multiple_of(2) || !encoded.is_ascii() {
+        return None;
+    }
+    (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_verify::{ConsensusProposal, VerificationMethod, DEFAULT_PROPOSAL_TYPE};
+    use crate::key_manager::KeyPurpose;
+
+    fn wal_record(id: &str) -> WalRecord {
+        WalRecord::ProposalCreated {
+            proposal: ConsensusProposal {
+                proposal_id: id.to_string(),
+                proposer_id: "proposer".to_string(),
+                data: b"block".to_vec(),
+                signature: vec![1u8; 64],
+                timestamp: 1,
+                verification_requirements: vec![VerificationMethod::IntegrityHash],
+                proposal_type: DEFAULT_PROPOSAL_TYPE.to_string(),
+                round: None,
+                leader_proof: None,
+            },
+            created_at: 1,
+        }
+    }
+
+    fn node_state(dir: &Path) -> (KeyManager, PeerAddressBook, std::path::PathBuf) {
+        let mut qrng = QRNG::from_seed([8u8; 32]);
+        let mut keys = KeyManager::new();
+        keys.generate_key("identity", KeyPurpose::Signing, &mut qrng)
+            .unwrap();
+        keys.generate_key("vrf", KeyPurpose::Vrf, &mut qrng)
+            .unwrap();
+
+        let mut book = PeerAddressBook::in_memory();
+        book.check_key("validator_2", &[2u8; 32]).unwrap();
+        book.record_address("validator_2", "10.0.0.2", 9000)
+            .unwrap();
+
+        let wal_path = dir.join("consensus.wal");
+        let mut wal = ConsensusWal::open(&wal_path).unwrap();
+        wal.append(&wal_record("p1")).unwrap();
+        wal.append(&wal_record("p2")).unwrap();
+        (keys, book, wal_path)
+    }
+
+    #[test]
+    fn test_backup_restores_on_new_node() {
+        let dir = tempfile::tempdir().unwrap();
+        let (keys, book, wal_path) = node_state(dir.path());
+        let key = BackupKey::generate(&mut QRNG::from_seed([9u8; 32])).unwrap();
+
+        let archive = NodeBackup::new("validator_1")
+            .with_config(&serde_json::json!({ "port": 8081 }))
+            .unwrap()
+            .with_keys(&keys)
+            .with_peers(&book)
+            .with_consensus_wal(&wal_path, None)
+            .unwrap()
+            .seal(&key)
+            .unwrap();
+        let archive_path = dir.path().join("backups").join("node.qfb");
+        archive.save(&archive_path).unwrap();
+        let raw = fs::read(&archive_path).unwrap();
+        assert!(!raw.windows(11).any(|window| window == b"validator_2"));
+        assert_eq!(archive.manifest.section("keys").unwrap().entries, 2);
+
+        // Replacement hardware holds nothing but the archive and the key
+        let key = BackupKey::from_hex(&key.to_hex()).unwrap();
+        let backup = BackupArchive::load(&archive_path)
+            .unwrap()
+            .open(&key)
+            .unwrap();
+        let mut restored_keys = KeyManager::new();
+        assert_eq!(backup.restore_keys(&mut restored_keys).unwrap(), 2);
+        assert_eq!(
+            restored_keys.sign("identity", b"vote").unwrap(),
+            keys.sign("identity", b"vote").unwrap()
+        );
+        assert_eq!(
+            restored_keys.key_info("vrf").unwrap().created_at,
+            keys.key_info("vrf").unwrap().created_at
+        );
+
+        let mut restored_book = PeerAddressBook::in_memory();
+        backup.restore_peers(&mut restored_book).unwrap();
+        assert_eq!(restored_book.get("validator_2"), book.get("validator_2"));
+
+        let new_wal = dir.path().join("restored").join("consensus.wal");
+        assert_eq!(backup.restore_consensus_wal(&new_wal, None).unwrap(), 2);
+        assert!(backup.restore_consensus_wal(&new_wal, None).is_err());
+        let config: serde_json::Value = backup.config().unwrap().unwrap();
+        assert_eq!(config["port"], 8081);
+    }
+
+    #[test]
+    fn test_wrong_key_and_tampering_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (keys, book, _) = node_state(dir.path());
+        let mut qrng = QRNG::from_seed([9u8; 32]);
+        let key = BackupKey::generate(&mut qrng).unwrap();
+        let archive = NodeBackup::new("validator_1")
+            .with_keys(&keys)
+            .with_peers(&book)
+            .seal(&key)
+            .unwrap();
+        assert!(archive.open(&key).is_ok());
+
+        let other = BackupKey::generate(&mut qrng).unwrap();
+        assert!(matches!(
+            archive.open(&other),
+            Err(SecureCommsError::CryptoProtocol(_))
+        ));
+
+        let mut relabeled = archive.clone();
+        relabeled.manifest.node_id = "validator_9".to_string();
+        assert!(matches!(
+            relabeled.open(&key),
+            Err(SecureCommsError::CryptoProtocol(_))
+        ));
+
+        let mut flipped = archive.clone();
+        let last = flipped.ciphertext.len() - 1;
+        flipped.ciphertext[last] ^= 0x01;
+        assert!(matches!(
+            flipped.open(&key),
+            Err(SecureCommsError::CryptoProtocol(_))
+        ));
+        assert!(BackupKey::from_hex("not hex").is_err());
+    }
+
+    #[test]
+    fn test_key_restore_is_all_or_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let (keys, _, _) = node_state(dir.path());
+        let mut backup = NodeBackup::new("validator_1").with_keys(&keys);
+
+        // A seed that no longer matches its public key installs nothing
+        backup.keys[1].seed[0] ^= 0x01;
+        let mut target = KeyManager::new();
+        assert!(matches!(
+            backup.restore_keys(&mut target),
+            Err(SecureCommsError::CryptoProtocol(_))
+        ));
+        assert!(target.key_ids().is_empty());
+
+        let backup = NodeBackup::new("validator_1").with_keys(&keys);
+        let mut occupied = KeyManager::new();
+        occupied
+            .import_key("vrf", KeyPurpose::Vrf, [1u8; 32])
+            .unwrap();
+        assert!(backup.restore_keys(&mut occupied).is_err());
+        assert_eq!(occupied.key_ids(), vec!["vrf".to_string()]);
+    }
+}
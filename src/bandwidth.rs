@@ -0,0 +1,603 @@
+//! # Bandwidth Accounting - Per-Peer Traffic Shaping
+//!
+//! Egress and ingress accounting per peer and per message class with
+//! token-bucket throttling, so bulk transfers cannot saturate the links that
+//! consensus and control traffic depend on.
+//!
+//! ## Message Classes
+//!
+//! - **Consensus**: Proposals, votes and verification traffic (uncapped by default)
+//! - **Control**: Handshakes, key exchange, keepalives (uncapped by default)
+//! - **Data**: Regular application payloads (uncapped by default)
+//! - **Bulk**: Large transfers such as snapshots and file sync
+//!
+//! ## Shaping
+//!
+//! Each (peer, class, direction) pair owns a token bucket refilled at the
+//! configured rate with a configurable burst. Egress that exceeds the bucket
+//! is either delayed (shaped) up to `max_shaping_delay_ms` or rejected.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::bandwidth::{BandwidthConfig, BandwidthManager, MessageClass};
+//!
+//! let mut bandwidth = BandwidthManager::new(BandwidthConfig::default());
+//! if bandwidth.try_egress("validator_2", MessageClass::Bulk, 64 * 1024).is_ok() {
+//!     // send the chunk
+//! }
+//! let report = bandwidth.usage_report();
+//! println!("Tracking {} peers", report.len());
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Traffic class used for accounting and shaping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MessageClass {
+    /// Consensus proposals, votes and verification
+    Consensus,
+    /// Handshake, key exchange and keepalive traffic
+    Control,
+    /// Regular application data
+    Data,
+    /// Large bulk transfers
+    Bulk,
+}
+
+impl MessageClass {
+    /// All message classes
+    pub const ALL: [MessageClass; 4] = [
+        MessageClass::Consensus,
+        MessageClass::Control,
+        MessageClass::Data,
+        MessageClass::Bulk,
+    ];
+
+    /// Stable name used in metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageClass::Consensus => "consensus",
+            MessageClass::Control => "control",
+            MessageClass::Data => "data",
+            MessageClass::Bulk => "bulk",
+        }
+    }
+}
+
+/// Traffic direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    /// Outbound traffic
+    Egress,
+    /// Inbound traffic
+    Ingress,
+}
+
+/// Rate cap for one message class
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClassLimit {
+    /// Sustained rate in bytes per second
+    pub bytes_per_second: u64,
+    /// Maximum burst in bytes
+    pub burst_bytes: u64,
+}
+
+/// Bandwidth management configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandwidthConfig {
+    /// Per-peer egress caps by class (absent class = uncapped)
+    pub egress_limits: HashMap<MessageClass, ClassLimit>,
+    /// Per-peer ingress caps by class (absent class = uncapped)
+    pub ingress_limits: HashMap<MessageClass, ClassLimit>,
+    /// Maximum time egress may be delayed before it is rejected
+    pub max_shaping_delay_ms: u64,
+}
+
+impl Default for BandwidthConfig {
+    fn default() -> Self {
+        let mut egress_limits = HashMap::new();
+        egress_limits.insert(
+            MessageClass::Bulk,
+            ClassLimit {
+                bytes_per_second: 4 * 1024 * 1024, // 4MB/s
+                burst_bytes: 1024 * 1024,
+            },
+        );
+
+        Self {
+            ingress_limits: egress_limits.clone(),
+            egress_limits,
+            max_shaping_delay_ms: 250,
+        }
+    }
+}
+
+/// Token bucket rate limiter
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    /// Refill rate in tokens (bytes) per second
+    rate: f64,
+    /// Bucket capacity in tokens
+    capacity: f64,
+    /// Currently available tokens
+    tokens: f64,
+    /// Last refill instant
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket from a class limit
+    pub fn new(limit: ClassLimit) -> Self {
+        Self::new_at(limit, Instant::now())
+    }
+
+    /// Create a full bucket anchored at a given instant
+    pub fn new_at(limit: ClassLimit, now: Instant) -> Self {
+        let capacity = limit.burst_bytes.max(1) as f64;
+        Self {
+            rate: limit.bytes_per_second.max(1) as f64,
+            capacity,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    /// Try to take `bytes` tokens at `now`
+    pub fn try_consume_at(&mut self, bytes: u64, now: Instant) -> bool {
+        self.refill(now);
+        let needed = bytes as f64;
+        if needed <= self.tokens {
+            self.tokens -= needed;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Time until `bytes` tokens are available at `now`
+    ///
+    /// Requests larger than the burst are treated as needing a full bucket.
+    pub fn time_until_available_at(&mut self, bytes: u64, now: Instant) -> Duration {
+        self.refill(now);
+        let needed = (bytes as f64).min(self.capacity);
+        if needed <= self.tokens {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((needed - self.tokens) / self.rate)
+        }
+    }
+
+    /// Forcefully take tokens, allowing the balance to go negative
+    ///
+    /// Used for oversized messages that have already waited for a full bucket.
+    pub fn force_consume_at(&mut self, bytes: u64, now: Instant) {
+        self.refill(now);
+        self.tokens -= bytes as f64;
+    }
+
+    /// Currently available tokens
+    pub fn available(&self) -> f64 {
+        self.tokens.max(0.0)
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+/// Byte and message counters for one class
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClassUsage {
+    /// Bytes sent
+    pub egress_bytes: u64,
+    /// Bytes received
+    pub ingress_bytes: u64,
+    /// Messages sent
+    pub egress_messages: u64,
+    /// Messages received
+    pub ingress_messages: u64,
+    /// Egress attempts rejected or delayed by shaping
+    pub egress_throttled: u64,
+    /// Ingress messages exceeding the cap
+    pub ingress_throttled: u64,
+    /// Egress bytes spent on padding and cover traffic
+    #[serde(default)]
+    pub padding_bytes: u64,
+    /// Cover frames sent
+    #[serde(default)]
+    pub cover_messages: u64,
+}
+
+/// Usage report for one peer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BandwidthUsage {
+    /// Peer identifier
+    pub peer_id: String,
+    /// Usage by class name
+    pub classes: HashMap<String, ClassUsage>,
+    /// Total bytes sent
+    pub total_egress_bytes: u64,
+    /// Total bytes received
+    pub total_ingress_bytes: u64,
+}
+
+/// Per-peer state
+#[derive(Debug, Clone, Default)]
+struct PeerBandwidth {
+    buckets: HashMap<(MessageClass, Direction), TokenBucket>,
+    usage: HashMap<MessageClass, ClassUsage>,
+}
+
+/// Bandwidth accounting and shaping across peers
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthManager {
+    /// Shaping configuration
+    config: BandwidthConfig,
+    /// State per peer
+    peers: HashMap<String, PeerBandwidth>,
+}
+
+impl BandwidthManager {
+    /// Create new bandwidth manager
+    pub fn new(config: BandwidthConfig) -> Self {
+        Self {
+            config,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Get bandwidth configuration
+    pub fn get_config(&self) -> &BandwidthConfig {
+        &self.config
+    }
+
+    /// Replace the cap for a class; existing buckets are reset
+    pub fn set_limit(&mut self, direction: Direction, class: MessageClass, limit: Option<ClassLimit>) {
+        let limits = match direction {
+            Direction::Egress => &mut self.config.egress_limits,
+            Direction::Ingress => &mut self.config.ingress_limits,
+        };
+        match limit {
+            Some(limit) => {
+                limits.insert(class, limit);
+            }
+            None => {
+                limits.remove(&class);
+            }
+        }
+        for peer in self.peers.values_mut() {
+            peer.buckets.remove(&(class, direction));
+        }
+    }
+
+    /// Account for egress, rejecting it if the class cap is exhausted
+    pub fn try_egress(&mut self, peer_id: &str, class: MessageClass, bytes: u64) -> Result<()> {
+        self.try_egress_at(peer_id, class, bytes, Instant::now())
+    }
+
+    /// Account for egress at a given instant
+    pub fn try_egress_at(
+        &mut self,
+        peer_id: &str,
+        class: MessageClass,
+        bytes: u64,
+        now: Instant,
+    ) -> Result<()> {
+        let limit = self.config.egress_limits.get(&class).copied();
+        let peer = self.peers.entry(peer_id.to_string()).or_default();
+
+        if let Some(limit) = limit {
+            let bucket = peer
+                .buckets
+                .entry((class, Direction::Egress))
+                .or_insert_with(|| TokenBucket::new_at(limit, now));
+            if !bucket.try_consume_at(bytes, now) {
+                peer.usage.entry(class).or_default().egress_throttled += 1;
+                return Err(SecureCommsError::ResourceExhausted(format!(
+                    "Egress {} bandwidth to {} exhausted ({} bytes requested)",
+                    class.as_str(),
+                    peer_id,
+                    bytes
+                )));
+            }
+        }
+
+        let usage = peer.usage.entry(class).or_default();
+        usage.egress_bytes += bytes;
+        usage.egress_messages += 1;
+        Ok(())
+    }
+
+    /// Delay required before `bytes` of egress fit the class cap
+    pub fn egress_delay(&mut self, peer_id: &str, class: MessageClass, bytes: u64) -> Duration {
+        let now = Instant::now();
+        let limit = match self.config.egress_limits.get(&class).copied() {
+            Some(limit) => limit,
+            None => return Duration::ZERO,
+        };
+        self.peers
+            .entry(peer_id.to_string())
+            .or_default()
+            .buckets
+            .entry((class, Direction::Egress))
+            .or_insert_with(|| TokenBucket::new_at(limit, now))
+            .time_until_available_at(bytes, now)
+    }
+
+    /// Account for egress after the caller waited out the shaping delay
+    ///
+    /// Oversized messages that can never fit the burst are charged against the
+    /// bucket so subsequent traffic is paced accordingly.
+    pub fn commit_shaped_egress(&mut self, peer_id: &str, class: MessageClass, bytes: u64) {
+        let now = Instant::now();
+        let limit = self.config.egress_limits.get(&class).copied();
+        let peer = self.peers.entry(peer_id.to_string()).or_default();
+
+        if let Some(limit) = limit {
+            peer.buckets
+                .entry((class, Direction::Egress))
+                .or_insert_with(|| TokenBucket::new_at(limit, now))
+                .force_consume_at(bytes, now);
+        }
+
+        let usage = peer.usage.entry(class).or_default();
+        usage.egress_bytes += bytes;
+        usage.egress_messages += 1;
+        usage.egress_throttled += 1;
+    }
+
+    /// Account for padding added to a sent frame
+    ///
+    /// Padding is charged against the bucket without being refused, since
+    /// the frame it belongs to has already been admitted.
+    pub fn record_padding(&mut self, peer_id: &str, class: MessageClass, bytes: u64) {
+        let now = Instant::now();
+        let limit = self.config.egress_limits.get(&class).copied();
+        let peer = self.peers.entry(peer_id.to_string()).or_default();
+
+        if let Some(limit) = limit {
+            peer.buckets
+                .entry((class, Direction::Egress))
+                .or_insert_with(|| TokenBucket::new_at(limit, now))
+                .force_consume_at(bytes, now);
+        }
+
+        let usage = peer.usage.entry(class).or_default();
+        usage.egress_bytes += bytes;
+        usage.padding_bytes += bytes;
+    }
+
+    /// Mark an egress message already accounted as a cover frame of `bytes`
+    pub fn record_cover(&mut self, peer_id: &str, class: MessageClass, bytes: u64) {
+        let usage = self
+            .peers
+            .entry(peer_id.to_string())
+            .or_default()
+            .usage
+            .entry(class)
+            .or_default();
+        usage.padding_bytes += bytes;
+        usage.cover_messages += 1;
+    }
+
+    /// Account for ingress; returns `false` when the peer exceeds its cap
+    pub fn record_ingress(&mut self, peer_id: &str, class: MessageClass, bytes: u64) -> bool {
+        self.record_ingress_at(peer_id, class, bytes, Instant::now())
+    }
+
+    /// Account for ingress at a given instant
+    pub fn record_ingress_at(
+        &mut self,
+        peer_id: &str,
+        class: MessageClass,
+        bytes: u64,
+        now: Instant,
+    ) -> bool {
+        let limit = self.config.ingress_limits.get(&class).copied();
+        let peer = self.peers.entry(peer_id.to_string()).or_default();
+
+        let within_cap = match limit {
+            Some(limit) => peer
+                .buckets
+                .entry((class, Direction::Ingress))
+                .or_insert_with(|| TokenBucket::new_at(limit, now))
+                .try_consume_at(bytes, now),
+            None => true,
+        };
+
+        let usage = peer.usage.entry(class).or_default();
+        usage.ingress_bytes += bytes;
+        usage.ingress_messages += 1;
+        if !within_cap {
+            usage.ingress_throttled += 1;
+        }
+        within_cap
+    }
+
+    /// Usage for one peer
+    pub fn peer_usage(&self, peer_id: &str) -> Option<BandwidthUsage> {
+        self.peers
+            .get(peer_id)
+            .map(|peer| Self::build_usage(peer_id, peer))
+    }
+
+    /// Usage for all peers, sorted by peer ID
+    pub fn usage_report(&self) -> Vec<BandwidthUsage> {
+        let mut report: Vec<BandwidthUsage> = self
+            .peers
+            .iter()
+            .map(|(peer_id, peer)| Self::build_usage(peer_id, peer))
+            .collect();
+        report.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+        report
+    }
+
+    /// Forget a peer's buckets and counters
+    pub fn remove_peer(&mut self, peer_id: &str) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Get bandwidth statistics for metrics export
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut totals: HashMap<MessageClass, ClassUsage> = HashMap::new();
+        for peer in self.peers.values() {
+            for (class, usage) in &peer.usage {
+                let total = totals.entry(*class).or_default();
+                total.egress_bytes += usage.egress_bytes;
+                total.ingress_bytes += usage.ingress_bytes;
+                total.egress_messages += usage.egress_messages;
+                total.ingress_messages += usage.ingress_messages;
+                total.egress_throttled += usage.egress_throttled;
+                total.ingress_throttled += usage.ingress_throttled;
+                total.padding_bytes += usage.padding_bytes;
+                total.cover_messages += usage.cover_messages;
+            }
+        }
+
+        let mut stats = HashMap::new();
+        stats.insert("tracked_peers".to_string(), serde_json::json!(self.peers.len()));
+        for class in MessageClass::ALL {
+            let usage = totals.remove(&class).unwrap_or_default();
+            stats.insert(
+                format!("{}_usage", class.as_str()),
+                serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null),
+            );
+        }
+        stats
+    }
+
+    fn build_usage(peer_id: &str, peer: &PeerBandwidth) -> BandwidthUsage {
+        let mut usage = BandwidthUsage {
+            peer_id: peer_id.to_string(),
+            ..Default::default()
+        };
+        for (class, class_usage) in &peer.usage {
+            usage.total_egress_bytes += class_usage.egress_bytes;
+            usage.total_ingress_bytes += class_usage.ingress_bytes;
+            usage
+                .classes
+                .insert(class.as_str().to_string(), class_usage.clone());
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk_limited() -> BandwidthManager {
+        let mut config = BandwidthConfig::default();
+        config.egress_limits.insert(
+            MessageClass::Bulk,
+            ClassLimit {
+                bytes_per_second: 1000,
+                burst_bytes: 1000,
+            },
+        );
+        BandwidthManager::new(config)
+    }
+
+    #[test]
+    fn test_token_bucket_refill() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new_at(
+            ClassLimit {
+                bytes_per_second: 100,
+                burst_bytes: 100,
+            },
+            start,
+        );
+
+        assert!(bucket.try_consume_at(100, start));
+        assert!(!bucket.try_consume_at(50, start));
+        assert!(bucket.try_consume_at(50, start + Duration::from_millis(500)));
+        assert_eq!(
+            bucket.time_until_available_at(100, start + Duration::from_millis(500)),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_bulk_throttled_consensus_unaffected() {
+        let mut bandwidth = bulk_limited();
+        let now = Instant::now();
+
+        assert!(bandwidth.try_egress_at("peer", MessageClass::Bulk, 800, now).is_ok());
+        assert!(bandwidth.try_egress_at("peer", MessageClass::Bulk, 800, now).is_err());
+        assert!(bandwidth
+            .try_egress_at("peer", MessageClass::Consensus, 1_000_000, now)
+            .is_ok());
+
+        let usage = bandwidth.peer_usage("peer").unwrap();
+        assert_eq!(usage.classes["bulk"].egress_bytes, 800);
+        assert_eq!(usage.classes["bulk"].egress_throttled, 1);
+        assert_eq!(usage.classes["consensus"].egress_bytes, 1_000_000);
+    }
+
+    #[test]
+    fn test_limits_are_per_peer() {
+        let mut bandwidth = bulk_limited();
+        let now = Instant::now();
+
+        assert!(bandwidth.try_egress_at("a", MessageClass::Bulk, 1000, now).is_ok());
+        assert!(bandwidth.try_egress_at("b", MessageClass::Bulk, 1000, now).is_ok());
+        assert!(bandwidth.try_egress_at("a", MessageClass::Bulk, 1, now).is_err());
+    }
+
+    #[test]
+    fn test_ingress_accounting() {
+        let mut config = BandwidthConfig::default();
+        config.ingress_limits.insert(
+            MessageClass::Data,
+            ClassLimit {
+                bytes_per_second: 100,
+                burst_bytes: 100,
+            },
+        );
+        let mut bandwidth = BandwidthManager::new(config);
+        let now = Instant::now();
+
+        assert!(bandwidth.record_ingress_at("peer", MessageClass::Data, 100, now));
+        assert!(!bandwidth.record_ingress_at("peer", MessageClass::Data, 10, now));
+
+        let usage = bandwidth.peer_usage("peer").unwrap();
+        assert_eq!(usage.total_ingress_bytes, 110);
+        assert_eq!(usage.classes["data"].ingress_throttled, 1);
+    }
+
+    #[test]
+    fn test_padding_overhead_accounting() {
+        let mut bandwidth = bulk_limited();
+        let now = Instant::now();
+
+        assert!(bandwidth.try_egress_at("peer", MessageClass::Bulk, 500, now).is_ok());
+        bandwidth.record_padding("peer", MessageClass::Bulk, 500);
+        assert!(bandwidth.try_egress_at("peer", MessageClass::Bulk, 100, now).is_err());
+        bandwidth.record_cover("peer", MessageClass::Bulk, 0);
+
+        let usage = bandwidth.peer_usage("peer").unwrap();
+        assert_eq!(usage.classes["bulk"].egress_bytes, 1000);
+        assert_eq!(usage.classes["bulk"].egress_messages, 1);
+        assert_eq!(usage.classes["bulk"].padding_bytes, 500);
+        assert_eq!(usage.classes["bulk"].cover_messages, 1);
+    }
+
+    #[test]
+    fn test_stats_export() {
+        let mut bandwidth = bulk_limited();
+        bandwidth.try_egress("a", MessageClass::Control, 10).unwrap();
+        bandwidth.try_egress("b", MessageClass::Control, 20).unwrap();
+
+        let stats = bandwidth.get_stats();
+        assert_eq!(stats["tracked_peers"], serde_json::json!(2));
+        assert_eq!(stats["control_usage"]["egress_bytes"], serde_json::json!(30));
+        assert_eq!(bandwidth.usage_report().len(), 2);
+    }
+}
@@ -0,0 +1,386 @@
+//! # Benchmark Baselines - Criterion Result Capture and Regression Gates
+//!
+//! Turns criterion output into a versioned, machine-readable baseline and
+//! compares later runs against it, so continuous benchmarking can fail a
+//! build when an operation slows down beyond a set threshold.
+//!
+//! ## Workflow
+//!
+//! 1. Run the suite and save the results with `QFSC_BENCH_GATE=save`
+//! 2. Commit the baseline JSON alongside the code it measures
+//! 3. Run the suite in CI with `QFSC_BENCH_GATE=compare`; the run exits
+//!    non-zero when any benchmark's mean exceeds its baseline by more than
+//!    `QFSC_BENCH_THRESHOLD` percent (10 by default)
+//!
+//! Baselines only compare meaningfully on the hardware they were recorded
+//! on, so each baseline records the host architecture and CPU count.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::bench_baseline::{BenchmarkBaseline, RegressionGate};
+//!
+//! let baseline = BenchmarkBaseline::load("benches/baseline.json").unwrap();
+//! let current = BenchmarkBaseline::from_criterion_dir("target/criterion", Some("regression/")).unwrap();
+//! let report = RegressionGate::new(10.0).compare(&baseline, &current);
+//! println!("{}", report.summary());
+//! assert!(report.passed());
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Baseline file format version
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Mean timings of a set of benchmarks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkBaseline {
+    /// File format version
+    pub version: u32,
+    /// Unix timestamp of the run
+    pub recorded_at: u64,
+    /// Target architecture of the host
+    pub arch: String,
+    /// Logical CPUs of the host
+    pub cpus: usize,
+    /// Mean time per iteration in nanoseconds, by criterion benchmark ID
+    pub results: BTreeMap<String, f64>,
+}
+
+impl BenchmarkBaseline {
+    /// Baseline of the given results for this host
+    pub fn new(results: BTreeMap<String, f64>) -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            recorded_at: chrono::Utc::now().timestamp() as u64,
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            results,
+        }
+    }
+
+    /// Collect the latest criterion estimates below `dir`
+    ///
+    /// Only benchmark IDs starting with `prefix` are kept, if given.
+    pub fn from_criterion_dir<P: AsRef<Path>>(dir: P, prefix: Option<&str>) -> Result<Self> {
+        let mut results = BTreeMap::new();
+        collect_estimates(dir.as_ref(), prefix, &mut results)?;
+        Ok(Self::new(results))
+    }
+
+    /// Read a baseline file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| {
+            SecureCommsError::SystemError(format!("Failed to read baseline {:?}: {}", path, e))
+        })?;
+        let baseline: Self = serde_json::from_slice(&bytes).map_err(|e| {
+            SecureCommsError::SystemError(format!("Corrupt baseline {:?}: {}", path, e))
+        })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(SecureCommsError::Validation(format!(
+                "Unsupported baseline version {} (expected {})",
+                baseline.version, BASELINE_VERSION
+            )));
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline atomically
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    SecureCommsError::SystemError(format!(
+                        "Failed to create baseline directory: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+        let bytes = serde_json::to_vec_pretty(self).map_err(|e| {
+            SecureCommsError::SystemError(format!("Baseline serialization failed: {}", e))
+        })?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| SecureCommsError::SystemError(format!("Baseline write failed: {}", e)))
+    }
+}
+
+/// Walk a criterion output tree, reading `new/benchmark.json` and `new/estimates.json`
+fn collect_estimates(
+    dir: &Path,
+    prefix: Option<&str>,
+    results: &mut BTreeMap<String, f64>,
+) -> Result<()> {
+    let entries = fs::read_dir(dir).map_err(|e| {
+        SecureCommsError::SystemError(format!("Failed to read criterion output {:?}: {}", dir, e))
+    })?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        // Criterion's own HTML report directory holds no estimates
+        if path
+            .file_name()
+            .map(|name| name == "report")
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let benchmark = path.join("new").join("benchmark.json");
+        let estimates = path.join("new").join("estimates.json");
+        if benchmark.is_file() && estimates.is_file() {
+            let id = read_json(&benchmark)?
+                .get("full_id")
+                .and_then(|id| id.as_str())
+                .map(str::to_string);
+            let mean = read_json(&estimates)?
+                .pointer("/mean/point_estimate")
+                .and_then(|mean| mean.as_f64());
+            if let (Some(id), Some(mean)) = (id, mean) {
+                if prefix.map(|prefix| id.starts_with(prefix)).unwrap_or(true) {
+                    results.insert(id, mean);
+                }
+            }
+        } else {
+            collect_estimates(&path, prefix, results)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value> {
+    let bytes = fs::read(path)
+        .map_err(|e| SecureCommsError::SystemError(format!("Failed to read {:?}: {}", path, e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| SecureCommsError::SystemError(format!("Corrupt {:?}: {}", path, e)))
+}
+
+/// Change of one benchmark against its baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkDelta {
+    /// Criterion benchmark ID
+    pub name: String,
+    /// Baseline mean in nanoseconds
+    pub baseline_ns: f64,
+    /// Current mean in nanoseconds
+    pub current_ns: f64,
+    /// Relative change in percent; positive is slower
+    pub change_percent: f64,
+}
+
+/// Outcome of comparing a run against a baseline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegressionReport {
+    /// Allowed slowdown in percent
+    pub threshold_percent: f64,
+    /// Benchmarks slower than the threshold allows
+    pub regressions: Vec<BenchmarkDelta>,
+    /// Benchmarks faster by more than the threshold
+    pub improvements: Vec<BenchmarkDelta>,
+    /// Benchmarks within the threshold
+    pub unchanged: usize,
+    /// Baseline benchmarks absent from the run
+    pub missing: Vec<String>,
+    /// Benchmarks without a baseline
+    pub added: Vec<String>,
+}
+
+impl RegressionReport {
+    /// Whether the run stays within the threshold
+    ///
+    /// Missing benchmarks fail the gate too, so a renamed or deleted
+    /// benchmark cannot hide a regression.
+    pub fn passed(&self) -> bool {
+        self.regressions.is_empty() && self.missing.is_empty()
+    }
+
+    /// Human-readable summary, one line per notable benchmark
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "{} regressed, {} improved, {} unchanged, {} missing, {} new (threshold {:.1}%)",
+            self.regressions.len(),
+            self.improvements.len(),
+            self.unchanged,
+            self.missing.len(),
+            self.added.len(),
+            self.threshold_percent
+        )];
+        for delta in &self.regressions {
+            lines.push(format!(
+                "❌ {}: {:.0}ns -> {:.0}ns (+{:.1}%)",
+                delta.name, delta.baseline_ns, delta.current_ns, delta.change_percent
+            ));
+        }
+        for delta in &self.improvements {
+            lines.push(format!(
+                "✅ {}: {:.0}ns -> {:.0}ns ({:.1}%)",
+                delta.name, delta.baseline_ns, delta.current_ns, delta.change_percent
+            ));
+        }
+        for name in &self.missing {
+            lines.push(format!("⚠️ {}: missing from run", name));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Threshold check of a run against a baseline
+#[derive(Debug, Clone, Copy)]
+pub struct RegressionGate {
+    threshold_percent: f64,
+}
+
+impl RegressionGate {
+    /// Gate allowing slowdowns up to `threshold_percent`
+    pub fn new(threshold_percent: f64) -> Self {
+        Self {
+            threshold_percent: threshold_percent.max(0.0),
+        }
+    }
+
+    /// Compare `current` against `baseline`
+    pub fn compare(
+        &self,
+        baseline: &BenchmarkBaseline,
+        current: &BenchmarkBaseline,
+    ) -> RegressionReport {
+        let mut report = RegressionReport {
+            threshold_percent: self.threshold_percent,
+            ..Default::default()
+        };
+        for (name, &baseline_ns) in &baseline.results {
+            let current_ns = match current.results.get(name) {
+                Some(&current_ns) => current_ns,
+                None => {
+                    report.missing.push(name.clone());
+                    continue;
+                }
+            };
+            let change_percent = if baseline_ns > 0.0 {
+                (current_ns - baseline_ns) / baseline_ns * 100.0
+            } else {
+                0.0
+            };
+            let delta = BenchmarkDelta {
+                name: name.clone(),
+                baseline_ns,
+                current_ns,
+                change_percent,
+            };
+            if change_percent > self.threshold_percent {
+                report.regressions.push(delta);
+            } else if change_percent < -self.threshold_percent {
+                report.improvements.push(delta);
+            } else {
+                report.unchanged += 1;
+            }
+        }
+        report.added = current
+            .results
+            .keys()
+            .filter(|name| !baseline.results.contains_key(*name))
+            .cloned()
+            .collect();
+        report
+    }
+}
+
+impl Default for RegressionGate {
+    fn default() -> Self {
+        Self::new(10.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(results: &[(&str, f64)]) -> BenchmarkBaseline {
+        BenchmarkBaseline::new(
+            results
+                .iter()
+                .map(|(name, ns)| (name.to_string(), *ns))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_gate_flags_regressions_and_missing() {
+        let old = baseline(&[("a", 100.0), ("b", 100.0), ("c", 100.0), ("gone", 1.0)]);
+        let new = baseline(&[("a", 105.0), ("b", 125.0), ("c", 50.0), ("fresh", 1.0)]);
+        let report = RegressionGate::new(10.0).compare(&old, &new);
+
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.regressions.len(), 1);
+        assert_eq!(report.regressions[0].name, "b");
+        assert!((report.regressions[0].change_percent - 25.0).abs() < 1e-9);
+        assert_eq!(report.improvements[0].name, "c");
+        assert_eq!(report.missing, vec!["gone".to_string()]);
+        assert_eq!(report.added, vec!["fresh".to_string()]);
+        assert!(!report.passed());
+
+        let relaxed = RegressionGate::new(30.0).compare(&new, &new);
+        assert!(relaxed.passed());
+    }
+
+    #[test]
+    fn test_reads_criterion_output() {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, id, mean) in [
+            (
+                "regression_keygen/ml_kem_768",
+                "regression/keygen/ml_kem_768",
+                90_000.0,
+            ),
+            ("other/send", "other/send", 5.0),
+        ] {
+            let new = dir.path().join(path).join("new");
+            fs::create_dir_all(&new).unwrap();
+            fs::write(
+                new.join("benchmark.json"),
+                serde_json::json!({ "full_id": id }).to_string(),
+            )
+            .unwrap();
+            fs::write(
+                new.join("estimates.json"),
+                serde_json::json!({ "mean": { "point_estimate": mean } }).to_string(),
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(dir.path().join("report")).unwrap();
+
+        let all = BenchmarkBaseline::from_criterion_dir(dir.path(), None).unwrap();
+        assert_eq!(all.results.len(), 2);
+        let gated = BenchmarkBaseline::from_criterion_dir(dir.path(), Some("regression/")).unwrap();
+        assert_eq!(
+            gated.results.get("regression/keygen/ml_kem_768"),
+            Some(&90_000.0)
+        );
+        assert_eq!(gated.results.len(), 1);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baselines").join("baseline.json");
+        let saved = baseline(&[("a", 1.5)]);
+        saved.save(&path).unwrap();
+        assert_eq!(BenchmarkBaseline::load(&path).unwrap(), saved);
+
+        let mut future = saved.clone();
+        future.version = BASELINE_VERSION + 1;
+        future.save(&path).unwrap();
+        assert!(BenchmarkBaseline::load(&path).is_err());
+    }
+}
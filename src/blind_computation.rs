@@ -0,0 +1,504 @@
+//! # Blind Quantum Computation - Delegation Without Revealing the Circuit
+//!
+//! Protocol skeleton for delegating a circuit to a quantum server that
+//! learns neither the circuit nor its input or output. It follows universal
+//! blind quantum computation (Broadbent, Fitzsimons and Kashefi, 2009) on
+//! the simplest resource state, a one-dimensional cluster, and therefore
+//! covers single-qubit circuits; brickwork states for multi-qubit circuits
+//! are future work.
+//!
+//! ## Protocol
+//!
+//! 1. **Compile**: The client turns the circuit into a sequence of angles
+//!    α, one per J(α) = H·Rz(α) step, starting from |+⟩. Gates are limited
+//!    to the Clifford+T set, so every angle is a multiple of π/4. The
+//!    sequence is padded with identities to `chain_length`, hiding the
+//!    circuit's size.
+//! 2. **Prepare**: The client sends one qubit |+_θ⟩ per step plus an output
+//!    qubit, each with a secret random θ = kπ/4.
+//! 3. **Entangle**: The server joins the qubits in a chain with CZ gates.
+//! 4. **Measure**: Step by step, the client sends δ = φ + θ + rπ, where φ
+//!    is the step's angle adapted to earlier outcomes and r a secret random
+//!    bit. The server measures in the basis |±_δ⟩ and reports the outcome,
+//!    which the client corrects by r.
+//! 5. **Output**: The server measures the output qubit in the Z basis; the
+//!    client removes the pending Pauli X correction.
+//!
+//! Every δ the server sees is uniformly distributed over the eight angles,
+//! whatever the circuit, and every outcome is masked by r, so the server's
+//! view is independent of the computation.
+//!
+//! ## Backend Integration
+//!
+//! [`BlindServer`] is the server side of the protocol; [`SimulatedBlindServer`]
+//! implements it on an in-process state vector. [`BlindBackend`] wraps a
+//! server as a [`QuantumBackend`], so the scheduler can place single-qubit
+//! operations on a blind server like on any hardware backend. A real
+//! delegation only needs another `BlindServer` that forwards qubits and
+//! angles to a remote device.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::blind_computation::{BlindClient, BlindConfig, SimulatedBlindServer};
+//! use quantum_forge_secure_comms::crypto_protocols::QRNG;
+//! use quantum_forge_secure_comms::quantum_core::{QuantumCircuit, QuantumGate};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut circuit = QuantumCircuit::new("secret".to_string(), 1);
+//! circuit.add_gate(QuantumGate::PauliX, vec![0])?;
+//!
+//! let mut client = BlindClient::new(BlindConfig::default(), QRNG::from_seed([1; 32]))?;
+//! let mut server = SimulatedBlindServer::new(QRNG::from_seed([2; 32]));
+//! assert!(client.run(&circuit, &mut server)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::crypto_protocols::QRNG;
+use crate::quantum_core::{QuantumCircuit, QuantumGate, QuantumState};
+use crate::quantum_scheduler::QuantumBackend;
+use crate::{Result, SecureCommsError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI, SQRT_2, TAU};
+
+/// Complex amplitude as (re, im)
+type Complex = (f64, f64);
+
+/// Parameters of blind delegation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlindConfig {
+    /// Measured qubits per run; every circuit is padded to this length
+    pub chain_length: usize,
+}
+
+impl Default for BlindConfig {
+    fn default() -> Self {
+        Self { chain_length: 16 }
+    }
+}
+
+impl BlindConfig {
+    /// Check the chain length is usable
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=256).contains(&self.chain_length) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Blind computation chain length must be between 1 and 256, got {}",
+                self.chain_length
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Server side of blind delegation
+pub trait BlindServer: Send {
+    /// Runs queued on the server ahead of a new one
+    fn queue_depth(&self) -> usize {
+        0
+    }
+
+    /// Take the client's qubits and entangle them in a chain
+    ///
+    /// The last qubit is the output; the others are measured in order.
+    fn load(&mut self, qubits: Vec<QuantumState>) -> Result<()>;
+
+    /// Measure the next chain qubit in the basis |±_δ⟩, true for |−_δ⟩
+    fn measure(&mut self, delta: f64) -> Result<bool>;
+
+    /// Measure the output qubit in the Z basis, ending the run
+    fn measure_output(&mut self) -> Result<bool>;
+}
+
+/// Blind server simulated on a two-qubit window of the chain
+///
+/// Each qubit of a chain only interacts with its neighbours, so the chain
+/// can be entangled lazily: the next qubit joins the current one just
+/// before the current one is measured.
+#[derive(Debug)]
+pub struct SimulatedBlindServer {
+    qrng: QRNG,
+    current: Option<[Complex; 2]>,
+    pending: VecDeque<[Complex; 2]>,
+    observed: Vec<f64>,
+}
+
+impl SimulatedBlindServer {
+    /// Server drawing measurement outcomes from `qrng`
+    pub fn new(qrng: QRNG) -> Self {
+        Self {
+            qrng,
+            current: None,
+            pending: VecDeque::new(),
+            observed: Vec::new(),
+        }
+    }
+
+    /// Every measurement angle the server was sent, i.e. all it learns
+    pub fn observed_angles(&self) -> &[f64] {
+        &self.observed
+    }
+
+    fn draw(&mut self) -> f64 {
+        self.qrng.gen_range(0..u64::MAX) as f64 / u64::MAX as f64
+    }
+}
+
+impl BlindServer for SimulatedBlindServer {
+    fn load(&mut self, qubits: Vec<QuantumState>) -> Result<()> {
+        if self.current.is_some() {
+            return Err(SecureCommsError::QuantumOperation(
+                "Blind server is still running a computation".to_string(),
+            ));
+        }
+        let mut qubits = qubits
+            .iter()
+            .map(single_qubit_amplitudes)
+            .collect::<Result<VecDeque<_>>>()?;
+        if qubits.len() < 2 {
+            return Err(SecureCommsError::QuantumOperation(
+                "Blind computation needs at least one chain qubit and an output".to_string(),
+            ));
+        }
+        self.current = qubits.pop_front();
+        self.pending = qubits;
+        Ok(())
+    }
+
+    fn measure(&mut self, delta: f64) -> Result<bool> {
+        let (current, next) = match (self.current, self.pending.pop_front()) {
+            (Some(current), Some(next)) => (current, next),
+            _ => {
+                return Err(SecureCommsError::QuantumOperation(
+                    "No chain qubit left to measure".to_string(),
+                ))
+            }
+        };
+        self.observed.push(delta);
+        // CZ flips the sign of |11⟩; joint[c][n] for current c and next n
+        let joint = [
+            [mul(current[0], next[0]), mul(current[0], next[1])],
+            [mul(current[1], next[0]), neg(mul(current[1], next[1]))],
+        ];
+        // Project the current qubit onto (⟨0| ± e^{−iδ}⟨1|)/√2
+        let phase = (delta.cos(), -delta.sin());
+        let project = |sign: f64| -> [Complex; 2] {
+            [0, 1].map(|n| {
+                let (a, b) = (joint[0][n], mul(phase, joint[1][n]));
+                ((a.0 + sign * b.0) / SQRT_2, (a.1 + sign * b.1) / SQRT_2)
+            })
+        };
+        let plus = project(1.0);
+        let outcome = self.draw() >= norm(&plus);
+        let collapsed = if outcome { project(-1.0) } else { plus };
+        let scale = norm(&collapsed).sqrt();
+        self.current = Some(collapsed.map(|c| (c.0 / scale, c.1 / scale)));
+        Ok(outcome)
+    }
+
+    fn measure_output(&mut self) -> Result<bool> {
+        if !self.pending.is_empty() {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "{} chain qubits are still unmeasured",
+                self.pending.len()
+            )));
+        }
+        let output = self.current.take().ok_or_else(|| {
+            SecureCommsError::QuantumOperation("Blind server holds no computation".to_string())
+        })?;
+        let one = output[1].0 * output[1].0 + output[1].1 * output[1].1;
+        Ok(self.draw() < one)
+    }
+}
+
+/// Client side of blind delegation, holding the secret angles and pads
+#[derive(Debug)]
+pub struct BlindClient {
+    config: BlindConfig,
+    qrng: QRNG,
+}
+
+impl BlindClient {
+    /// Client drawing its secrets from `qrng`
+    pub fn new(config: BlindConfig, qrng: QRNG) -> Result<Self> {
+        config.validate()?;
+        Ok(Self { config, qrng })
+    }
+
+    /// Run `circuit` on |0⟩ through `server` and return the measured output
+    pub fn run(&mut self, circuit: &QuantumCircuit, server: &mut dyn BlindServer) -> Result<bool> {
+        let angles = pad(compile(circuit)?, self.config.chain_length)?;
+        let thetas: Vec<u64> = (0..=angles.len())
+            .map(|_| self.qrng.gen_range(0..8))
+            .collect();
+        let pads: Vec<bool> = (0..angles.len())
+            .map(|_| self.qrng.gen_range(0..2) == 1)
+            .collect();
+        let qubits = thetas
+            .iter()
+            .enumerate()
+            .map(|(position, &k)| prepare(position, k))
+            .collect::<Result<Vec<_>>>()?;
+        server.load(qubits)?;
+
+        // Pending Pauli corrections X^x Z^z on the current chain qubit
+        let (mut x, mut z) = (false, false);
+        for (step, &alpha) in angles.iter().enumerate() {
+            let phi = if x { alpha } else { -alpha };
+            let delta = phi + thetas[step] as f64 * FRAC_PI_4 + if pads[step] { PI } else { 0.0 };
+            let outcome = server.measure(delta.rem_euclid(TAU))? ^ pads[step];
+            (x, z) = (outcome ^ z, x);
+        }
+        Ok(server.measure_output()? ^ x)
+    }
+}
+
+/// Scheduler backend running single-qubit circuits on a blind server
+///
+/// Accepts circuits on one qubit in |0⟩ and writes the measured output into
+/// the state as a basis state, as one shot on hardware would. Anything else
+/// is refused, so the scheduler runs it on the simulation instead.
+pub struct BlindBackend<S: BlindServer> {
+    client: Mutex<BlindClient>,
+    server: Mutex<S>,
+}
+
+impl<S: BlindServer> BlindBackend<S> {
+    /// Backend delegating to `server` with secrets from `qrng`
+    pub fn new(config: BlindConfig, qrng: QRNG, server: S) -> Result<Self> {
+        Ok(Self {
+            client: Mutex::new(BlindClient::new(config, qrng)?),
+            server: Mutex::new(server),
+        })
+    }
+
+    /// The wrapped server
+    pub fn server(&self) -> parking_lot::MutexGuard<'_, S> {
+        self.server.lock()
+    }
+}
+
+impl<S: BlindServer> QuantumBackend for BlindBackend<S> {
+    fn queue_depth(&self) -> usize {
+        self.server.lock().queue_depth()
+    }
+
+    fn execute(&self, circuit: &QuantumCircuit, state: &mut QuantumState) -> Result<()> {
+        let ground = state.qubit_count == 1
+            && (state.amplitudes[0] - 1.0).abs() < 1e-9
+            && state.amplitudes[1].abs() < 1e-9;
+        if !ground {
+            return Err(SecureCommsError::QuantumOperation(
+                "Blind delegation runs single-qubit circuits from |0⟩ only".to_string(),
+            ));
+        }
+        let output = self.client.lock().run(circuit, &mut *self.server.lock())?;
+        let outcome = usize::from(output);
+        state.amplitudes = vec![0.0; 2];
+        state.amplitudes[outcome] = 1.0;
+        state.phases = vec![0.0; 2];
+        Ok(())
+    }
+}
+
+/// J(α) angles computing `circuit` on |0⟩, starting from |+⟩
+///
+/// Fails with `SecureCommsError::Validation` for circuits on more than one
+/// qubit or with gates outside H, X, Y, Z, S, T and Phase.
+pub fn compile(circuit: &QuantumCircuit) -> Result<Vec<f64>> {
+    if circuit.qubit_count != 1 {
+        return Err(SecureCommsError::Validation(format!(
+            "Blind delegation covers single-qubit circuits, {} has {} qubits",
+            circuit.id, circuit.qubit_count
+        )));
+    }
+    // H turns the |+⟩ input into |0⟩
+    let mut angles = Vec::new();
+    push_angles(&mut angles, &[0.0]);
+    for (gate, _) in &circuit.operations {
+        // Rz(α) = J(0)·J(α) and H = J(0)
+        let steps: &[f64] = match gate {
+            QuantumGate::Hadamard => &[0.0],
+            QuantumGate::PauliZ | QuantumGate::Phase => &[PI, 0.0],
+            QuantumGate::SGate => &[FRAC_PI_2, 0.0],
+            QuantumGate::TGate => &[FRAC_PI_4, 0.0],
+            QuantumGate::PauliX => &[0.0, PI, 0.0, 0.0],
+            // Y = iXZ
+            QuantumGate::PauliY => &[PI, 0.0, 0.0, PI, 0.0, 0.0],
+            QuantumGate::CNOT => {
+                return Err(SecureCommsError::Validation(format!(
+                    "Blind delegation cannot run {:?} in {}",
+                    gate, circuit.id
+                )))
+            }
+        };
+        push_angles(&mut angles, steps);
+    }
+    Ok(angles)
+}
+
+/// Pad `angles` with identities to exactly `length` steps
+///
+/// J(0)·J(0) and J(π/2)³ are identities, so any padding but a single step
+/// is possible.
+pub fn pad(mut angles: Vec<f64>, length: usize) -> Result<Vec<f64>> {
+    let missing = length
+        .checked_sub(angles.len())
+        .filter(|&missing| missing != 1);
+    let missing = missing.ok_or_else(|| {
+        SecureCommsError::Validation(format!(
+            "A {}-step pattern cannot be padded to {} steps",
+            angles.len(),
+            length
+        ))
+    })?;
+    if missing % 2 == 1 {
+        angles.extend([FRAC_PI_2; 3]);
+    }
+    angles.resize(length, 0.0);
+    Ok(angles)
+}
+
+/// Append steps, cancelling adjacent J(0)·J(0) pairs
+fn push_angles(angles: &mut Vec<f64>, steps: &[f64]) {
+    for &step in steps {
+        if step == 0.0 && angles.last() == Some(&0.0) {
+            angles.pop();
+        } else {
+            angles.push(step);
+        }
+    }
+}
+
+/// Qubit |+_θ⟩ with θ = kπ/4, prepared on the quantum layer
+fn prepare(position: usize, k: u64) -> Result<QuantumState> {
+    let mut qubit = QuantumState::new(format!("blind_qubit_{}", position), 1);
+    qubit.apply_gate(QuantumGate::Hadamard, &[0])?;
+    for _ in 0..k {
+        qubit.apply_gate(QuantumGate::TGate, &[0])?;
+    }
+    Ok(qubit)
+}
+
+fn single_qubit_amplitudes(state: &QuantumState) -> Result<[Complex; 2]> {
+    if state.qubit_count != 1 {
+        return Err(SecureCommsError::QuantumOperation(format!(
+            "Blind server takes single qubits, {} has {}",
+            state.id, state.qubit_count
+        )));
+    }
+    Ok([0, 1].map(|i| {
+        let (amplitude, phase) = (state.amplitudes[i], state.phases[i]);
+        (amplitude * phase.cos(), amplitude * phase.sin())
+    }))
+}
+
+fn mul(a: Complex, b: Complex) -> Complex {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+fn neg(a: Complex) -> Complex {
+    (-a.0, -a.1)
+}
+
+fn norm(state: &[Complex; 2]) -> f64 {
+    state.iter().map(|c| c.0 * c.0 + c.1 * c.1).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circuit(gates: &[QuantumGate]) -> QuantumCircuit {
+        let mut circuit = QuantumCircuit::new("blind".to_string(), 1);
+        for &gate in gates {
+            circuit.add_gate(gate, vec![0]).unwrap();
+        }
+        circuit
+    }
+
+    fn run(gates: &[QuantumGate], seed: u8) -> bool {
+        let mut client =
+            BlindClient::new(BlindConfig::default(), QRNG::from_seed([seed; 32])).unwrap();
+        let mut server = SimulatedBlindServer::new(QRNG::from_seed([seed + 100; 32]));
+        client.run(&circuit(gates), &mut server).unwrap()
+    }
+
+    #[test]
+    fn test_deterministic_circuits_give_their_output() {
+        use QuantumGate::*;
+        for seed in 0..8 {
+            assert!(!run(&[], seed));
+            assert!(run(&[PauliX], seed));
+            assert!(run(&[PauliY], seed));
+            assert!(!run(&[PauliX, PauliX], seed));
+            // HZH = X and HTTTTH = X
+            assert!(run(&[Hadamard, PauliZ, Hadamard], seed));
+            assert!(run(&[Hadamard, TGate, TGate, SGate, Hadamard], seed));
+            assert!(!run(
+                &[Hadamard, SGate, SGate, SGate, SGate, Hadamard],
+                seed
+            ));
+        }
+    }
+
+    #[test]
+    fn test_server_view_does_not_depend_on_the_circuit() {
+        use QuantumGate::*;
+        let mut counts = [[0usize; 8]; 2];
+        for (i, gates) in [vec![], vec![Hadamard, TGate, Hadamard, PauliX]]
+            .iter()
+            .enumerate()
+        {
+            for seed in 0..40 {
+                let mut client =
+                    BlindClient::new(BlindConfig::default(), QRNG::from_seed([seed; 32])).unwrap();
+                let mut server = SimulatedBlindServer::new(QRNG::from_seed([seed; 32]));
+                client.run(&circuit(gates), &mut server).unwrap();
+                assert_eq!(server.observed_angles().len(), 16);
+                for &delta in server.observed_angles() {
+                    let k = (delta / FRAC_PI_4).round() as usize % 8;
+                    counts[i][k] += 1;
+                }
+            }
+        }
+        // 640 angles per circuit, spread over all eight values
+        for count in counts.iter().flatten() {
+            assert!((50..110).contains(count), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_compile_pad_and_backend_limits() {
+        use QuantumGate::*;
+        assert_eq!(compile(&circuit(&[PauliX])).unwrap(), vec![PI]);
+        assert_eq!(compile(&circuit(&[Hadamard])).unwrap(), Vec::<f64>::new());
+        assert_eq!(pad(vec![PI], 4).unwrap().len(), 4);
+        assert_eq!(pad(vec![PI], 5).unwrap(), vec![PI, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(
+            pad(Vec::new(), 3).unwrap(),
+            vec![FRAC_PI_2, FRAC_PI_2, FRAC_PI_2]
+        );
+        assert!(pad(vec![PI], 2).is_err());
+        assert!(pad(vec![PI; 3], 2).is_err());
+
+        let mut two_qubits = QuantumCircuit::new("pair".to_string(), 2);
+        two_qubits.add_gate(CNOT, vec![0, 1]).unwrap();
+        assert!(compile(&two_qubits).is_err());
+
+        let backend = BlindBackend::new(
+            BlindConfig::default(),
+            QRNG::from_seed([1; 32]),
+            SimulatedBlindServer::new(QRNG::from_seed([2; 32])),
+        )
+        .unwrap();
+        let mut state = QuantumState::new("blind".to_string(), 1);
+        backend.execute(&circuit(&[PauliX]), &mut state).unwrap();
+        assert_eq!(state.amplitudes, vec![0.0, 1.0]);
+        // Not in |0⟩ any more, so refused
+        assert!(backend.execute(&circuit(&[PauliX]), &mut state).is_err());
+        assert!(BlindConfig { chain_length: 0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! # Blocking Client - Synchronous Facade for Non-Async Applications
+//!
+//! Wraps `StreamlinedSecureClient` together with a dedicated Tokio runtime so
+//! applications without an async executor can establish channels, send and
+//! receive messages and run health checks through plain blocking calls. Every
+//! call mirrors its async counterpart and is bounded by a timeout.
+//!
+//! ## Runtime Management
+//!
+//! - **Owned Runtime**: A small multi-threaded runtime is created per client
+//! - **Timeouts**: Each call fails with `SecureCommsError::Timeout` after the configured limit
+//! - **Async Guard**: Calls made from inside an async runtime are refused instead of panicking
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::blocking::BlockingClient;
+//! use std::time::Duration;
+//!
+//! let mut client = BlockingClient::new().unwrap();
+//! client.establish_secure_channel("peer_1").unwrap();
+//! client.send_secure_message("peer_1", b"hello").unwrap();
+//!
+//! match client.receive_secure_message(Duration::from_secs(1)) {
+//!     Ok(message) => println!("Received {} bytes", message.payload.len()),
+//!     Err(e) => println!("Nothing received: {}", e),
+//! }
+//! assert!(client.health_check().unwrap());
+//! ```
+
+use crate::streamlined_client::{
+    SecureChannel, SecureMessage, StreamlinedConfig, StreamlinedSecureClient,
+};
+use crate::{Result, SecureCommsError};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+
+/// Synchronous wrapper around `StreamlinedSecureClient`
+pub struct BlockingClient {
+    runtime: Runtime,
+    client: StreamlinedSecureClient,
+    timeout: Duration,
+}
+
+impl BlockingClient {
+    /// Create blocking client with default configuration
+    pub fn new() -> Result<Self> {
+        Self::with_config(StreamlinedConfig::default())
+    }
+
+    /// Create blocking client with custom configuration
+    ///
+    /// The call timeout defaults to the configured network timeout.
+    pub fn with_config(config: StreamlinedConfig) -> Result<Self> {
+        ensure_not_async()?;
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("secure-comms-blocking")
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                SecureCommsError::SystemError(format!("Failed to start blocking runtime: {}", e))
+            })?;
+
+        let timeout = Duration::from_secs(config.network_timeout);
+        let client = runtime.block_on(StreamlinedSecureClient::with_config(config))?;
+        Ok(Self {
+            runtime,
+            client,
+            timeout,
+        })
+    }
+
+    /// Timeout applied to each call
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Change the timeout applied to each call
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Establish secure channel with peer
+    pub fn establish_secure_channel(&mut self, peer_id: &str) -> Result<SecureChannel> {
+        let timeout = self.timeout;
+        run_with_timeout(
+            &self.runtime,
+            timeout,
+            self.client.establish_secure_channel(peer_id),
+        )
+    }
+
+    /// Send secure message to peer
+    pub fn send_secure_message(&mut self, peer_id: &str, data: &[u8]) -> Result<SecureMessage> {
+        let timeout = self.timeout;
+        run_with_timeout(
+            &self.runtime,
+            timeout,
+            self.client.send_secure_message(peer_id, data),
+        )
+    }
+
+    /// Receive the next inbound message, waiting up to `timeout`
+    pub fn receive_secure_message(&mut self, timeout: Duration) -> Result<SecureMessage> {
+        ensure_not_async()?;
+        self.runtime.block_on(self.client.receive_secure_message(timeout))
+    }
+
+    /// Handle for delivering inbound messages from other threads
+    pub fn inbound_sender(&self) -> mpsc::UnboundedSender<SecureMessage> {
+        self.client.inbound_sender()
+    }
+
+    /// Run the client health check
+    pub fn health_check(&mut self) -> Result<bool> {
+        let timeout = self.timeout;
+        run_with_timeout(&self.runtime, timeout, self.client.health_check())
+    }
+
+    /// Get system status
+    pub fn get_system_status(&self) -> Result<HashMap<String, serde_json::Value>> {
+        run_with_timeout(&self.runtime, self.timeout, async {
+            Ok(self.client.get_system_status().await)
+        })
+    }
+
+    /// Get secure channel information
+    pub fn get_secure_channel(&self, peer_id: &str) -> Option<&SecureChannel> {
+        self.client.get_secure_channel(peer_id)
+    }
+
+    /// List all active secure channels
+    pub fn list_secure_channels(&self) -> Vec<&SecureChannel> {
+        self.client.list_secure_channels()
+    }
+
+    /// Get client ID
+    pub fn get_client_id(&self) -> &str {
+        self.client.get_client_id()
+    }
+
+    /// Underlying async client
+    pub fn client(&self) -> &StreamlinedSecureClient {
+        &self.client
+    }
+
+    /// Shutdown the client gracefully
+    pub fn shutdown(&mut self) -> Result<()> {
+        let timeout = self.timeout;
+        run_with_timeout(&self.runtime, timeout, self.client.shutdown())
+    }
+}
+
+/// Refuse blocking calls from inside an async runtime, where they would panic
+fn ensure_not_async() -> Result<()> {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(SecureCommsError::Configuration(
+            "BlockingClient cannot be used from within an async runtime; use StreamlinedSecureClient"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Drive a client future to completion on the owned runtime
+fn run_with_timeout<T, F>(runtime: &Runtime, timeout: Duration, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    ensure_not_async()?;
+    runtime.block_on(async {
+        tokio::time::timeout(timeout, future).await.map_err(|_| {
+            SecureCommsError::Timeout(format!(
+                "Blocking call did not complete within {}ms",
+                timeout.as_millis()
+            ))
+        })?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_round_trip() {
+        let mut client = BlockingClient::new().unwrap();
+        let channel = client.establish_secure_channel("blocking_peer").unwrap();
+        assert!(channel.is_established);
+
+        let sent = client.send_secure_message("blocking_peer", b"ping").unwrap();
+        assert_eq!(sent.payload, b"ping");
+
+        let local = client.get_client_id().to_string();
+        let inbound = client.inbound_sender();
+        std::thread::spawn(move || {
+            inbound
+                .send(SecureMessage::new("blocking_peer".to_string(), local, b"pong".to_vec()))
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+
+        let received = client
+            .receive_secure_message(Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(received.payload, b"pong");
+        assert!(client.health_check().unwrap());
+        assert!(client.get_system_status().unwrap().contains_key("client_id"));
+    }
+
+    #[test]
+    fn test_blocking_timeouts() {
+        let mut client = BlockingClient::new().unwrap();
+        let result = client.receive_secure_message(Duration::from_millis(10));
+        assert!(matches!(result, Err(SecureCommsError::Timeout(_))));
+
+        client.set_timeout(Duration::from_secs(5));
+        assert_eq!(client.timeout(), Duration::from_secs(5));
+        assert!(client.send_secure_message("unknown_peer", b"data").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refused_inside_async_runtime() {
+        assert!(matches!(
+            BlockingClient::new(),
+            Err(SecureCommsError::Configuration(_))
+        ));
+    }
+}
@@ -0,0 +1,780 @@
+//! # Byzantine Simulation - Fault-Injection Harness for Consensus
+//!
+//! Runs N in-process consensus engines over a deterministic simulated network
+//! and lets a subset of validators misbehave, so the safety and liveness of
+//! the consensus engine can be checked rather than assumed. Built only with
+//! the `simulation` feature and for unit tests.
+//!
+//! ## Simulated Transport
+//!
+//! - **Virtual Time**: Messages are delivered in tick order from a single queue
+//! - **Latency**: Every message takes a base latency plus seeded jitter
+//! - **Determinism**: The same seed replays the same delivery schedule
+//!
+//! ## Byzantine Behaviors
+//!
+//! - **Delay**: Outgoing messages are held back by extra ticks
+//! - **Drop**: Outgoing messages are lost with a given probability
+//! - **Equivocate**: Conflicting proposals and conflicting votes are signed for the same round
+//! - **Garbage**: Undecodable bytes are sent instead of protocol messages
+//!
+//! ## Checked Properties
+//!
+//! - **Safety**: Honest nodes never approve two proposals for one round and never disagree on an outcome
+//! - **Liveness**: Every round led by an honest proposer is approved on every honest node
+//!
+//! The harness configures a BFT quorum of `2f + 1` voters with `f = (n - 1) / 3`,
+//! so both properties are expected to hold while at most `f` nodes misbehave.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! use quantum_forge_secure_comms::byzantine_sim::{ByzantineBehavior, Simulation, SimulationConfig};
+//!
+//! let mut config = SimulationConfig::default();
+//! config.byzantine.insert(3, ByzantineBehavior::Equivocate);
+//!
+//! let mut simulation = Simulation::new(config).await?;
+//! let report = simulation.run(8)?;
+//! report.check_safety()?;
+//! report.check_liveness()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::consensus_verify::{
+    sign_commit_vote, ConsensusConfig, ConsensusEngine, ConsensusProposal, ConsensusStatus,
+    Proposal, ProposalQuery, ValidatorInfo, VerificationResult, VoteType,
+};
+use crate::equivocation::EvidenceQuery;
+use crate::quorum::{Fraction, QuorumConfig, QuorumRule};
+use crate::{Result, SecureCommsError};
+use ed25519_dalek::SigningKey;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Proposal type used by simulated validators
+pub const SIM_PROPOSAL_TYPE: &str = "sim_block";
+
+/// How a simulated validator behaves
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ByzantineBehavior {
+    /// Follows the protocol
+    Honest,
+    /// Follows the protocol but delays every outgoing message
+    Delay { ticks: u64 },
+    /// Follows the protocol but loses outgoing messages
+    Drop { probability: f64 },
+    /// Signs conflicting proposals and conflicting votes
+    Equivocate,
+    /// Sends random bytes instead of protocol messages
+    Garbage,
+}
+
+/// Simulation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    /// Number of validators
+    pub node_count: usize,
+    /// Misbehaving validators by index; all others are honest
+    pub byzantine: HashMap<usize, ByzantineBehavior>,
+    /// Base message latency in ticks
+    pub base_latency_ticks: u64,
+    /// Maximum seeded extra latency per message in ticks
+    pub jitter_ticks: u64,
+    /// Virtual time limit for the whole simulation
+    pub max_ticks: u64,
+    /// Seed for latency, drops and garbage
+    pub seed: u64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            node_count: 4,
+            byzantine: HashMap::new(),
+            base_latency_ticks: 2,
+            jitter_ticks: 3,
+            max_ticks: 10_000,
+            seed: 7,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Behavior of the validator at `index`
+    pub fn behavior_of(&self, index: usize) -> ByzantineBehavior {
+        self.byzantine
+            .get(&index)
+            .copied()
+            .unwrap_or(ByzantineBehavior::Honest)
+    }
+
+    /// Number of faults the quorum is sized to tolerate
+    pub fn fault_tolerance(&self) -> usize {
+        self.node_count.saturating_sub(1) / 3
+    }
+}
+
+/// Message delivered by the simulated network
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    /// Sending node index
+    pub from: usize,
+    /// Receiving node index
+    pub to: usize,
+    /// Wire bytes
+    pub bytes: Vec<u8>,
+    /// Delivery tick
+    pub delivered_at: u64,
+}
+
+/// Deterministic in-memory transport with virtual time
+#[derive(Debug)]
+pub struct SimulatedNetwork {
+    queue: BTreeMap<(u64, u64), Envelope>,
+    now: u64,
+    sequence: u64,
+    base_latency_ticks: u64,
+    jitter_ticks: u64,
+    rng: ChaCha8Rng,
+}
+
+impl SimulatedNetwork {
+    /// Create network with the given latency model and seed
+    pub fn new(base_latency_ticks: u64, jitter_ticks: u64, seed: u64) -> Self {
+        Self {
+            queue: BTreeMap::new(),
+            now: 0,
+            sequence: 0,
+            base_latency_ticks,
+            jitter_ticks,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Queue bytes for delivery after latency, jitter and `extra_delay`
+    pub fn send(&mut self, from: usize, to: usize, bytes: Vec<u8>, extra_delay: u64) {
+        let jitter = self.rng.gen_range(0..=self.jitter_ticks);
+        let deliver_at = self.now + self.base_latency_ticks + jitter + extra_delay;
+        self.sequence += 1;
+        self.queue.insert(
+            (deliver_at, self.sequence),
+            Envelope {
+                from,
+                to,
+                bytes,
+                delivered_at: deliver_at,
+            },
+        );
+    }
+
+    /// Next message due at or before `max_ticks`, advancing virtual time
+    pub fn next_delivery(&mut self, max_ticks: u64) -> Option<Envelope> {
+        let (&key, _) = self.queue.iter().next()?;
+        if key.0 > max_ticks {
+            return None;
+        }
+        self.now = key.0;
+        self.queue.remove(&key)
+    }
+
+    /// Current virtual time
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Messages still in flight
+    pub fn in_flight(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+/// Protocol messages exchanged by simulated validators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SimMessage {
+    Proposal(Box<ConsensusProposal>),
+    Vote {
+        proposal_id: String,
+        voter_id: String,
+        vote: VoteType,
+        signature: Vec<u8>,
+    },
+}
+
+/// Transport and protocol counters for a simulation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationMetrics {
+    /// Messages delivered by the network
+    pub delivered: u64,
+    /// Messages lost by dropping validators
+    pub dropped: u64,
+    /// Deliveries that could not be decoded
+    pub garbage_received: u64,
+    /// Proposals and votes refused by an engine
+    pub refused: u64,
+}
+
+/// Outcome of one round as seen by one node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundOutcome {
+    /// Round number
+    pub round: u64,
+    /// Proposer of the proposal
+    pub proposer_id: String,
+    /// Proposal the node tracked for the round
+    pub proposal_id: String,
+    /// Status on the node
+    pub status: ConsensusStatus,
+}
+
+/// Result of a simulation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Rounds proposed so far
+    pub rounds: u64,
+    /// Virtual time at the end of the run
+    pub ticks: u64,
+    /// Honest node IDs
+    pub honest_nodes: Vec<String>,
+    /// Proposer of each round
+    pub proposers: BTreeMap<u64, String>,
+    /// Round outcomes per honest node
+    pub outcomes: HashMap<String, Vec<RoundOutcome>>,
+    /// Validators each honest node holds equivocation evidence against
+    pub evidence: HashMap<String, Vec<String>>,
+    /// Transport and protocol counters
+    pub metrics: SimulationMetrics,
+}
+
+impl SimulationReport {
+    /// Check that honest nodes never approved conflicting proposals
+    pub fn check_safety(&self) -> Result<()> {
+        let mut approved_per_round: BTreeMap<u64, HashSet<&str>> = BTreeMap::new();
+        let mut final_status: HashMap<&str, ConsensusStatus> = HashMap::new();
+
+        for (node, outcomes) in &self.outcomes {
+            for outcome in outcomes {
+                if outcome.status == ConsensusStatus::Approved {
+                    approved_per_round
+                        .entry(outcome.round)
+                        .or_default()
+                        .insert(&outcome.proposal_id);
+                }
+                if matches!(outcome.status, ConsensusStatus::Approved | ConsensusStatus::Rejected) {
+                    let previous = final_status.insert(&outcome.proposal_id, outcome.status);
+                    if previous.is_some() && previous != Some(outcome.status) {
+                        return Err(SecureCommsError::ConsensusVerify(format!(
+                            "Safety violation: node {} decided {:?} for {}, another honest node decided {:?}",
+                            node,
+                            outcome.status,
+                            outcome.proposal_id,
+                            previous.unwrap_or(outcome.status)
+                        )));
+                    }
+                }
+            }
+        }
+
+        for (round, approved) in approved_per_round {
+            if approved.len() > 1 {
+                return Err(SecureCommsError::ConsensusVerify(format!(
+                    "Safety violation: {} conflicting proposals approved in round {}",
+                    approved.len(),
+                    round
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every honestly proposed round was approved on every honest node
+    pub fn check_liveness(&self) -> Result<()> {
+        for (round, proposer) in &self.proposers {
+            if !self.honest_nodes.contains(proposer) {
+                continue;
+            }
+            for node in &self.honest_nodes {
+                let approved = self
+                    .outcomes
+                    .get(node)
+                    .map(|outcomes| {
+                        outcomes.iter().any(|outcome| {
+                            outcome.round == *round && outcome.status == ConsensusStatus::Approved
+                        })
+                    })
+                    .unwrap_or(false);
+                if !approved {
+                    return Err(SecureCommsError::ConsensusVerify(format!(
+                        "Liveness violation: round {} from {} not approved on {}",
+                        round, proposer, node
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether some honest node holds evidence against `validator_id`
+    pub fn detected(&self, validator_id: &str) -> bool {
+        self.evidence
+            .values()
+            .any(|accused| accused.iter().any(|id| id == validator_id))
+    }
+}
+
+/// One simulated validator
+struct SimNode {
+    id: String,
+    engine: ConsensusEngine,
+    key: SigningKey,
+    behavior: ByzantineBehavior,
+    /// Votes that arrived before their proposal
+    pending_votes: Vec<SimMessage>,
+}
+
+/// N in-process validators over a simulated network
+pub struct Simulation {
+    config: SimulationConfig,
+    nodes: Vec<SimNode>,
+    network: SimulatedNetwork,
+    rng: ChaCha8Rng,
+    proposers: BTreeMap<u64, String>,
+    next_round: u64,
+    metrics: SimulationMetrics,
+}
+
+impl Simulation {
+    /// Create validators, keys and engines for a simulation
+    pub async fn new(config: SimulationConfig) -> Result<Self> {
+        if config.node_count == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Simulation needs at least one node".to_string(),
+            ));
+        }
+        if let Some(index) = config.byzantine.keys().find(|&&index| index >= config.node_count) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Byzantine node index {} out of range",
+                index
+            )));
+        }
+
+        let faults = config.fault_tolerance();
+        let quorum = QuorumConfig {
+            default_rule: QuorumRule {
+                approval: Fraction::new(2, 3),
+                participation: Fraction::new(2, 3),
+                min_voters: (2 * faults + 1) as u32,
+            },
+            ..QuorumConfig::default()
+        };
+
+        let keys: Vec<SigningKey> = (0..config.node_count)
+            .map(|index| {
+                let mut seed = [0u8; 32];
+                seed[..8].copy_from_slice(&(index as u64).to_le_bytes());
+                seed[8..16].copy_from_slice(&config.seed.to_le_bytes());
+                SigningKey::from_bytes(&seed)
+            })
+            .collect();
+
+        let mut nodes = Vec::with_capacity(config.node_count);
+        for (index, key) in keys.iter().enumerate() {
+            let consensus_config = ConsensusConfig {
+                consensus_timeout_ms: 3_600_000,
+                quorum: Some(quorum.clone()),
+                ..ConsensusConfig::default()
+            };
+            let id = node_id(index);
+            let mut engine = ConsensusEngine::new(id.clone(), consensus_config).await?;
+            engine.set_proposal_type_verifiers(SIM_PROPOSAL_TYPE, &["digital_signature"])?;
+            for (peer, peer_key) in keys.iter().enumerate() {
+                engine.register_validator(ValidatorInfo {
+                    validator_id: node_id(peer),
+                    public_key: peer_key.verifying_key().to_bytes().to_vec(),
+                    trust_score: 1.0,
+                    is_active: true,
+                    last_activity: 0,
+                });
+            }
+
+            nodes.push(SimNode {
+                id,
+                engine,
+                key: key.clone(),
+                behavior: config.behavior_of(index),
+                pending_votes: Vec::new(),
+            });
+        }
+
+        Ok(Self {
+            network: SimulatedNetwork::new(config.base_latency_ticks, config.jitter_ticks, config.seed),
+            rng: ChaCha8Rng::seed_from_u64(config.seed ^ 0x5eed),
+            config,
+            nodes,
+            proposers: BTreeMap::new(),
+            next_round: 0,
+            metrics: SimulationMetrics::default(),
+        })
+    }
+
+    /// Run `rounds` further rounds with round-robin proposers
+    pub fn run(&mut self, rounds: u64) -> Result<SimulationReport> {
+        for round in self.next_round..self.next_round + rounds {
+            let proposer = (round % self.nodes.len() as u64) as usize;
+            self.proposers.insert(round, self.nodes[proposer].id.clone());
+            self.propose(proposer, round)?;
+
+            while let Some(envelope) = self.network.next_delivery(self.config.max_ticks) {
+                self.metrics.delivered += 1;
+                self.deliver(envelope);
+            }
+        }
+        self.next_round += rounds;
+        Ok(self.report())
+    }
+
+    /// Engine of the node at `index`, for inspection
+    pub fn engine(&self, index: usize) -> Option<&ConsensusEngine> {
+        self.nodes.get(index).map(|node| &node.engine)
+    }
+
+    fn propose(&mut self, index: usize, round: u64) -> Result<()> {
+        let data = format!("block {} from {}", round, self.nodes[index].id).into_bytes();
+
+        if self.nodes[index].behavior == ByzantineBehavior::Equivocate {
+            // Two signed proposals for one round, split across the peers, and
+            // the conflicting one also sent late to the first peer
+            let first = self.detached_proposal(index, round, data.clone());
+            let mut conflicting_data = data;
+            conflicting_data.extend_from_slice(b" (conflicting)");
+            let second = self.detached_proposal(index, round, conflicting_data);
+
+            let peers: Vec<usize> = (0..self.nodes.len()).filter(|&peer| peer != index).collect();
+            for (position, &peer) in peers.iter().enumerate() {
+                let proposal = if position % 2 == 0 { &first } else { &second };
+                self.send(index, peer, &SimMessage::Proposal(Box::new(proposal.clone())), 0);
+            }
+            if let Some(&peer) = peers.first() {
+                let late = self.config.base_latency_ticks + self.config.jitter_ticks + 1;
+                self.send(index, peer, &SimMessage::Proposal(Box::new(second.clone())), late);
+            }
+            for proposal in [first, second] {
+                self.cast_votes(index, &proposal.proposal_id, &proposal.data);
+            }
+            return Ok(());
+        }
+
+        let node = &mut self.nodes[index];
+        let proposal = Proposal::new(&node.id, data)
+            .with_type(SIM_PROPOSAL_TYPE)
+            .with_round(round)
+            .sign_ed25519(&node.key);
+        let proposal_id = node.engine.submit_proposal(proposal)?;
+        let record = node.engine.get_proposal(&proposal_id).ok_or_else(|| {
+            SecureCommsError::ConsensusVerify(format!("Proposal {} not tracked", proposal_id))
+        })?;
+
+        self.broadcast(index, &SimMessage::Proposal(Box::new(record.proposal.clone())));
+        self.cast_votes(index, &proposal_id, &record.proposal.data);
+        Ok(())
+    }
+
+    /// Signed proposal that bypasses the equivocating node's own engine
+    fn detached_proposal(&self, index: usize, round: u64, data: Vec<u8>) -> ConsensusProposal {
+        let node = &self.nodes[index];
+        let signed = Proposal::new(&node.id, data)
+            .with_type(SIM_PROPOSAL_TYPE)
+            .with_round(round)
+            .sign_ed25519(&node.key);
+        ConsensusProposal {
+            proposal_id: format!(
+                "prop_{}_{}_{}",
+                node.id,
+                round,
+                &uuid::Uuid::new_v4().simple().to_string()[..8]
+            ),
+            proposer_id: signed.proposer_id,
+            data: signed.data,
+            signature: signed.signature.unwrap_or_default(),
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            verification_requirements: Vec::new(),
+            proposal_type: signed.proposal_type,
+            round: signed.round,
+            leader_proof: None,
+        }
+    }
+
+    /// Vote on a proposal according to the node's behavior
+    fn cast_votes(&mut self, index: usize, proposal_id: &str, data: &[u8]) {
+        let votes: &[VoteType] = if self.nodes[index].behavior == ByzantineBehavior::Equivocate {
+            &[VoteType::Approve, VoteType::Reject]
+        } else {
+            &[VoteType::Approve]
+        };
+
+        for &vote in votes {
+            let node = &mut self.nodes[index];
+            let signature = sign_commit_vote(&node.key, proposal_id, data, vote);
+            let message = SimMessage::Vote {
+                proposal_id: proposal_id.to_string(),
+                voter_id: node.id.clone(),
+                vote,
+                signature,
+            };
+            if node.behavior != ByzantineBehavior::Equivocate {
+                self.apply(index, message.clone());
+            }
+            self.broadcast(index, &message);
+        }
+    }
+
+    fn broadcast(&mut self, from: usize, message: &SimMessage) {
+        for to in 0..self.nodes.len() {
+            if to != from {
+                self.send(from, to, message, 0);
+            }
+        }
+    }
+
+    /// Put a message on the network, applying the sender's behavior
+    fn send(&mut self, from: usize, to: usize, message: &SimMessage, extra_delay: u64) {
+        let mut bytes = serde_json::to_vec(message).unwrap_or_default();
+        let mut delay = extra_delay;
+
+        match self.nodes[from].behavior {
+            ByzantineBehavior::Honest | ByzantineBehavior::Equivocate => {}
+            ByzantineBehavior::Delay { ticks } => delay += ticks,
+            ByzantineBehavior::Drop { probability } => {
+                if self.rng.gen_bool(probability.clamp(0.0, 1.0)) {
+                    self.metrics.dropped += 1;
+                    return;
+                }
+            }
+            ByzantineBehavior::Garbage => {
+                let length = self.rng.gen_range(1..256);
+                bytes = (0..length).map(|_| self.rng.gen()).collect();
+            }
+        }
+
+        self.network.send(from, to, bytes, delay);
+    }
+
+    fn deliver(&mut self, envelope: Envelope) {
+        let message: SimMessage = match serde_json::from_slice(&envelope.bytes) {
+            Ok(message) => message,
+            Err(_) => {
+                self.metrics.garbage_received += 1;
+                return;
+            }
+        };
+
+        let receiver = envelope.to;
+        if self.nodes[receiver].behavior == ByzantineBehavior::Equivocate {
+            // Equivocators only ever answer proposals with conflicting votes
+            if let SimMessage::Proposal(proposal) = message {
+                self.cast_votes(receiver, &proposal.proposal_id, &proposal.data);
+            }
+            return;
+        }
+        self.apply(receiver, message);
+    }
+
+    /// Process a protocol message on a protocol-following node
+    fn apply(&mut self, index: usize, message: SimMessage) {
+        match message {
+            SimMessage::Proposal(proposal) => {
+                let proposal = *proposal;
+                let node = &mut self.nodes[index];
+                if node.engine.get_proposal(&proposal.proposal_id).is_some() {
+                    return;
+                }
+                if node.engine.import_proposal(proposal.clone()).is_err() {
+                    self.metrics.refused += 1;
+                    return;
+                }
+                self.cast_votes(index, &proposal.proposal_id, &proposal.data);
+
+                // Votes that overtook the proposal can now be counted
+                let node = &mut self.nodes[index];
+                let (ready, waiting): (Vec<SimMessage>, Vec<SimMessage>) =
+                    node.pending_votes.drain(..).partition(|vote| {
+                        matches!(vote, SimMessage::Vote { proposal_id, .. } if *proposal_id == proposal.proposal_id)
+                    });
+                node.pending_votes = waiting;
+                for vote in ready {
+                    self.apply(index, vote);
+                }
+            }
+            SimMessage::Vote {
+                proposal_id,
+                voter_id,
+                vote,
+                signature,
+            } => {
+                let node = &mut self.nodes[index];
+                if node.engine.get_proposal(&proposal_id).is_none() {
+                    node.pending_votes.push(SimMessage::Vote {
+                        proposal_id,
+                        voter_id,
+                        vote,
+                        signature,
+                    });
+                    return;
+                }
+                let is_final = node
+                    .engine
+                    .get_session_status(&proposal_id)
+                    .map(|status| status.is_final())
+                    .unwrap_or(false);
+                if !is_final
+                    && node
+                        .engine
+                        .submit_signed_vote(
+                            &proposal_id,
+                            voter_id,
+                            vote,
+                            VerificationResult::custom(true, 1.0, 0),
+                            signature,
+                        )
+                        .is_err()
+                {
+                    self.metrics.refused += 1;
+                }
+            }
+        }
+    }
+
+    /// Collect round outcomes and evidence from the honest nodes
+    pub fn report(&self) -> SimulationReport {
+        let honest: Vec<&SimNode> = self
+            .nodes
+            .iter()
+            .filter(|node| node.behavior == ByzantineBehavior::Honest)
+            .collect();
+
+        let mut outcomes = HashMap::new();
+        let mut evidence = HashMap::new();
+        for node in &honest {
+            let mut rounds: Vec<RoundOutcome> = node
+                .engine
+                .query_proposals(&ProposalQuery::default())
+                .into_iter()
+                .filter_map(|record| {
+                    Some(RoundOutcome {
+                        round: record.proposal.round?,
+                        proposer_id: record.proposal.proposer_id,
+                        proposal_id: record.proposal.proposal_id,
+                        status: record.status,
+                    })
+                })
+                .collect();
+            rounds.sort_by(|a, b| a.round.cmp(&b.round).then(a.proposal_id.cmp(&b.proposal_id)));
+            outcomes.insert(node.id.clone(), rounds);
+
+            let mut accused: Vec<String> = node
+                .engine
+                .equivocation_evidence(&EvidenceQuery::default())
+                .into_iter()
+                .map(|evidence| evidence.validator_id.clone())
+                .collect();
+            accused.sort();
+            accused.dedup();
+            evidence.insert(node.id.clone(), accused);
+        }
+
+        SimulationReport {
+            rounds: self.next_round,
+            ticks: self.network.now(),
+            honest_nodes: honest.iter().map(|node| node.id.clone()).collect(),
+            proposers: self.proposers.clone(),
+            outcomes,
+            evidence,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+fn node_id(index: usize) -> String {
+    format!("node_{}", index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run(byzantine: &[(usize, ByzantineBehavior)], rounds: u64) -> SimulationReport {
+        let mut config = SimulationConfig::default();
+        config.byzantine.extend(byzantine.iter().copied());
+        let mut simulation = Simulation::new(config).await.unwrap();
+        simulation.run(rounds).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_all_honest_reach_agreement() {
+        let report = run(&[], 4).await;
+        report.check_safety().unwrap();
+        report.check_liveness().unwrap();
+        assert_eq!(report.honest_nodes.len(), 4);
+        assert_eq!(report.metrics.refused, 0);
+    }
+
+    #[tokio::test]
+    async fn test_equivocator_detected_without_breaking_safety() {
+        let report = run(&[(3, ByzantineBehavior::Equivocate)], 8).await;
+        report.check_safety().unwrap();
+        report.check_liveness().unwrap();
+        assert!(report.detected("node_3"));
+        assert!(report.metrics.refused > 0);
+    }
+
+    #[tokio::test]
+    async fn test_faulty_links_and_garbage_tolerated() {
+        for behavior in [
+            ByzantineBehavior::Delay { ticks: 50 },
+            ByzantineBehavior::Drop { probability: 1.0 },
+            ByzantineBehavior::Garbage,
+        ] {
+            let report = run(&[(1, behavior)], 8).await;
+            report.check_safety().unwrap();
+            report.check_liveness().unwrap();
+            if behavior == ByzantineBehavior::Garbage {
+                assert!(report.metrics.garbage_received > 0);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_too_many_faults_lose_liveness_not_safety() {
+        let report = run(
+            &[
+                (2, ByzantineBehavior::Drop { probability: 1.0 }),
+                (3, ByzantineBehavior::Drop { probability: 1.0 }),
+            ],
+            4,
+        )
+        .await;
+        report.check_safety().unwrap();
+        assert!(report.check_liveness().is_err());
+    }
+
+    #[test]
+    fn test_network_is_deterministic() {
+        let order = |seed| {
+            let mut network = SimulatedNetwork::new(1, 10, seed);
+            for to in 0..20 {
+                network.send(0, to, vec![to as u8], 0);
+            }
+            let mut order = Vec::new();
+            while let Some(envelope) = network.next_delivery(u64::MAX) {
+                order.push(envelope.to);
+            }
+            order
+        };
+        assert_eq!(order(1), order(1));
+        assert_eq!(order(1).len(), 20);
+    }
+}
@@ -0,0 +1,791 @@
+//! # Calibration - Backend-Reported Error Rates for Transpilation and Fidelity
+//!
+//! Quantum hardware drifts: coherence times and gate errors change from one
+//! calibration run to the next, and they differ from qubit to qubit. Instead
+//! of assuming fixed error rates, each backend's latest calibration is kept
+//! here and used wherever the core needs to know how well a circuit will run.
+//!
+//! ## Calibration Data
+//!
+//! - **Qubits**: T1 and T2 coherence times, single-qubit gate error and
+//!   readout error of every physical qubit
+//! - **Couplers**: Error of the two-qubit gate on each connected pair; pairs
+//!   without a coupler cannot run a CNOT
+//! - **Timing**: Single- and two-qubit gate durations, used for decoherence
+//!
+//! ## Uses
+//!
+//! - **Transpilation**: [`CalibrationData::transpile`] maps a circuit's
+//!   logical qubits onto the physical qubits with the lowest errors, keeping
+//!   every CNOT on a coupler
+//! - **Fidelity**: [`CalibrationData::estimate_fidelity`] multiplies gate,
+//!   decoherence and readout survival; the scheduler compares these per-circuit
+//!   estimates against the required fidelity instead of a backend's static one
+//! - **Error Rates**: The hardware interface reports mean errors across fresh
+//!   calibrations in place of a fixed table
+//!
+//! ## Refresh
+//!
+//! Backends with a [`CalibrationSource`] are re-fetched once their data is
+//! `refresh_interval_seconds` old. Data older than `max_age_seconds` is
+//! ignored, so a backend whose calibration stops updating falls back to its
+//! static profile rather than trusting stale numbers.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::calibration::{
+//!     CalibrationConfig, CalibrationData, CalibrationStore, CouplerCalibration, QubitCalibration,
+//! };
+//! use quantum_forge_secure_comms::quantum_core::{QuantumCircuit, QuantumGate};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let store = CalibrationStore::new(CalibrationConfig::default())?;
+//! let qubit = QubitCalibration {
+//!     t1_us: 120.0,
+//!     t2_us: 90.0,
+//!     gate_error: 0.0004,
+//!     readout_error: 0.015,
+//! };
+//! store.ingest(
+//!     "ion_trap",
+//!     CalibrationData {
+//!         taken_at_ms: 1_700_000_000_000,
+//!         qubits: vec![qubit.clone(), qubit],
+//!         couplers: vec![CouplerCalibration {
+//!             control: 0,
+//!             target: 1,
+//!             error: 0.008,
+//!         }],
+//!         single_qubit_gate_ns: 35.0,
+//!         two_qubit_gate_ns: 300.0,
+//!     },
+//! )?;
+//!
+//! let mut circuit = QuantumCircuit::new("bell".to_string(), 2);
+//! circuit.add_gate(QuantumGate::Hadamard, vec![0])?;
+//! circuit.add_gate(QuantumGate::CNOT, vec![0, 1])?;
+//! let estimates = store.fidelity_estimates(&circuit, 1_700_000_000_000);
+//! println!("expected fidelity on ion_trap: {:.4}", estimates["ion_trap"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::quantum_core::{QuantumCircuit, QuantumGate};
+use crate::{Result, SecureCommsError};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// How often calibrations are refreshed and how long they are trusted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationConfig {
+    /// Age at which a backend's calibration is fetched again; 0 disables the timer
+    pub refresh_interval_seconds: u64,
+    /// Age past which a calibration is ignored
+    pub max_age_seconds: u64,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_seconds: 900,
+            max_age_seconds: 3_600,
+        }
+    }
+}
+
+impl CalibrationConfig {
+    /// Check calibrations are refreshed before they expire
+    pub fn validate(&self) -> Result<()> {
+        if self.max_age_seconds == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Calibration maximum age must be positive".to_string(),
+            ));
+        }
+        if self.refresh_interval_seconds > self.max_age_seconds {
+            return Err(SecureCommsError::Configuration(format!(
+                "Calibration refresh interval ({}s) must not exceed the maximum age ({}s)",
+                self.refresh_interval_seconds, self.max_age_seconds
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Calibration of one physical qubit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QubitCalibration {
+    /// Energy relaxation time, in microseconds
+    pub t1_us: f64,
+    /// Dephasing time, in microseconds
+    pub t2_us: f64,
+    /// Error of a single-qubit gate on this qubit
+    pub gate_error: f64,
+    /// Probability of reading the wrong outcome
+    pub readout_error: f64,
+}
+
+/// Two-qubit gate calibration of one coupled pair, in either direction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CouplerCalibration {
+    pub control: u32,
+    pub target: u32,
+    /// Error of a CNOT across the pair
+    pub error: f64,
+}
+
+/// One calibration run reported by a backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationData {
+    /// When the backend measured it (Unix milliseconds)
+    pub taken_at_ms: u64,
+    /// Physical qubits, indexed by qubit number
+    pub qubits: Vec<QubitCalibration>,
+    /// Coupled pairs able to run two-qubit gates
+    pub couplers: Vec<CouplerCalibration>,
+    /// Duration of a single-qubit gate, in nanoseconds
+    pub single_qubit_gate_ns: f64,
+    /// Duration of a two-qubit gate, in nanoseconds
+    pub two_qubit_gate_ns: f64,
+}
+
+impl CalibrationData {
+    /// Check the reported values are physically possible
+    pub fn validate(&self) -> Result<()> {
+        let probability = |p: f64| (0.0..=1.0).contains(&p);
+        if self.qubits.is_empty() {
+            return Err(SecureCommsError::Validation(
+                "Calibration reports no qubits".to_string(),
+            ));
+        }
+        for (index, qubit) in self.qubits.iter().enumerate() {
+            if !(qubit.t1_us > 0.0
+                && qubit.t1_us.is_finite()
+                && qubit.t2_us > 0.0
+                && qubit.t2_us <= 2.0 * qubit.t1_us)
+            {
+                return Err(SecureCommsError::Validation(format!(
+                    "Qubit {} needs T1 > 0 and 0 < T2 <= 2·T1, got T1 {}µs and T2 {}µs",
+                    index, qubit.t1_us, qubit.t2_us
+                )));
+            }
+            if !probability(qubit.gate_error) || !probability(qubit.readout_error) {
+                return Err(SecureCommsError::Validation(format!(
+                    "Qubit {} error rates must be in [0, 1]",
+                    index
+                )));
+            }
+        }
+        for coupler in &self.couplers {
+            let count = self.qubits.len() as u32;
+            if coupler.control == coupler.target
+                || coupler.control >= count
+                || coupler.target >= count
+                || !probability(coupler.error)
+            {
+                return Err(SecureCommsError::Validation(format!(
+                    "Coupler {}-{} must join two distinct qubits with an error in [0, 1]",
+                    coupler.control, coupler.target
+                )));
+            }
+        }
+        if !(self.single_qubit_gate_ns >= 0.0
+            && self.single_qubit_gate_ns.is_finite()
+            && self.two_qubit_gate_ns >= 0.0
+            && self.two_qubit_gate_ns.is_finite())
+        {
+            return Err(SecureCommsError::Validation(
+                "Calibration gate durations must be non-negative".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Error of `gate` on physical `qubits`; `None` if the hardware cannot run it
+    pub fn gate_error(&self, gate: QuantumGate, qubits: &[u32]) -> Option<f64> {
+        match (gate, qubits) {
+            (QuantumGate::CNOT, [a, b]) => self.coupler_error(*a, *b),
+            (QuantumGate::CNOT, _) => None,
+            (_, [qubit]) => self.qubit(*qubit).map(|qubit| qubit.gate_error),
+            _ => None,
+        }
+    }
+
+    /// Error of a CNOT between two physical qubits, if they are coupled
+    pub fn coupler_error(&self, a: u32, b: u32) -> Option<f64> {
+        self.couplers
+            .iter()
+            .find(|c| (c.control, c.target) == (a, b) || (c.control, c.target) == (b, a))
+            .map(|c| c.error)
+    }
+
+    /// Mean single-qubit gate, two-qubit gate and readout errors
+    pub fn mean_errors(&self) -> CalibrationErrors {
+        let qubits = self.qubits.len().max(1) as f64;
+        let couplers = self.couplers.len();
+        CalibrationErrors {
+            single_qubit: self.qubits.iter().map(|q| q.gate_error).sum::<f64>() / qubits,
+            two_qubit: if couplers == 0 {
+                0.0
+            } else {
+                self.couplers.iter().map(|c| c.error).sum::<f64>() / couplers as f64
+            },
+            measurement: self.qubits.iter().map(|q| q.readout_error).sum::<f64>() / qubits,
+        }
+    }
+
+    /// Expected fidelity of a circuit already laid out on physical qubits
+    ///
+    /// The product of every gate's success probability, each used qubit's
+    /// T1 and T2 decay over the circuit's duration, and each used qubit's
+    /// readout. A gate the hardware cannot run makes the estimate 0.
+    pub fn estimate_fidelity(&self, circuit: &QuantumCircuit) -> f64 {
+        let mut fidelity = 1.0;
+        let mut duration_ns = 0.0;
+        let mut used = vec![false; self.qubits.len()];
+        for (gate, qubits) in &circuit.operations {
+            let Some(error) = self.gate_error(*gate, qubits) else {
+                return 0.0;
+            };
+            fidelity *= 1.0 - error;
+            duration_ns += if qubits.len() == 2 {
+                self.two_qubit_gate_ns
+            } else {
+                self.single_qubit_gate_ns
+            };
+            for &qubit in qubits {
+                used[qubit as usize] = true;
+            }
+        }
+        let duration_us = duration_ns / 1_000.0;
+        for (qubit, _) in self.qubits.iter().zip(used).filter(|(_, used)| *used) {
+            let decay = (-duration_us / qubit.t1_us).exp() * (-duration_us / qubit.t2_us).exp();
+            fidelity *= decay * (1.0 - qubit.readout_error);
+        }
+        fidelity
+    }
+
+    /// Lay a circuit out on the physical qubits with the lowest errors
+    ///
+    /// The circuit is optimized first. Logical qubits with the most CNOTs
+    /// are placed first, each on the free physical qubit coupled to all its
+    /// already-placed partners that loses the least fidelity to its gates,
+    /// couplers and readout. Partners not yet placed are scored by the best
+    /// coupler still free next to the candidate.
+    pub fn transpile(&self, circuit: &QuantumCircuit) -> Result<Transpiled> {
+        let physical = self.qubits.len() as u32;
+        if circuit.qubit_count > physical {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Circuit {} needs {} qubits, hardware has {}",
+                circuit.id, circuit.qubit_count, physical
+            )));
+        }
+        let mut optimized = circuit.clone();
+        optimized.optimize()?;
+
+        let logical = circuit.qubit_count as usize;
+        let mut single_gates = vec![0u32; logical];
+        let mut interactions = vec![vec![0u32; logical]; logical];
+        for (gate, qubits) in &optimized.operations {
+            match (gate, qubits.as_slice()) {
+                (QuantumGate::CNOT, [a, b]) => {
+                    interactions[*a as usize][*b as usize] += 1;
+                    interactions[*b as usize][*a as usize] += 1;
+                }
+                (_, [qubit]) => single_gates[*qubit as usize] += 1,
+                _ => {}
+            }
+        }
+
+        let mut order: Vec<usize> = (0..logical).collect();
+        order.sort_by_key(|&l| std::cmp::Reverse(interactions[l].iter().sum::<u32>()));
+        let mut layout: Vec<Option<u32>> = vec![None; logical];
+        let mut taken = vec![false; self.qubits.len()];
+        for l in order {
+            let mut best: Option<(u32, f64)> = None;
+            for p in (0..physical).filter(|&p| !taken[p as usize]) {
+                let qubit = &self.qubits[p as usize];
+                let mut score = single_gates[l] as f64 * (1.0 - qubit.gate_error).ln()
+                    + (1.0 - qubit.readout_error).ln();
+                let mut reachable = true;
+                for (partner, &count) in interactions[l].iter().enumerate() {
+                    if count == 0 {
+                        continue;
+                    }
+                    // Partners still to place count the best free coupler
+                    let error = match layout[partner] {
+                        Some(placed) => self.coupler_error(p, placed),
+                        None => (0..physical)
+                            .filter(|&q| q != p && !taken[q as usize])
+                            .filter_map(|q| self.coupler_error(p, q))
+                            .reduce(f64::min),
+                    };
+                    match error {
+                        Some(error) => score += count as f64 * (1.0 - error).ln(),
+                        None => {
+                            reachable = false;
+                            break;
+                        }
+                    }
+                }
+                if reachable && best.is_none_or(|(_, best_score)| score > best_score) {
+                    best = Some((p, score));
+                }
+            }
+            let (p, _) = best.ok_or_else(|| {
+                SecureCommsError::QuantumOperation(format!(
+                    "No layout of circuit {} puts every CNOT on a coupler",
+                    circuit.id
+                ))
+            })?;
+            layout[l] = Some(p);
+            taken[p as usize] = true;
+        }
+        let layout: Vec<u32> = layout.into_iter().flatten().collect();
+
+        let mut mapped = QuantumCircuit::new(circuit.id.clone(), physical);
+        for (gate, qubits) in &optimized.operations {
+            let qubits = qubits.iter().map(|&q| layout[q as usize]).collect();
+            mapped.add_gate(*gate, qubits)?;
+        }
+        let estimated_fidelity = self.estimate_fidelity(&mapped);
+        mapped.expected_fidelity = estimated_fidelity;
+        Ok(Transpiled {
+            circuit: mapped,
+            layout,
+            estimated_fidelity,
+        })
+    }
+
+    fn qubit(&self, qubit: u32) -> Option<&QubitCalibration> {
+        self.qubits.get(qubit as usize)
+    }
+}
+
+/// Circuit laid out on physical qubits by [`CalibrationData::transpile`]
+#[derive(Debug, Clone)]
+pub struct Transpiled {
+    /// Optimized circuit over the backend's physical qubits
+    pub circuit: QuantumCircuit,
+    /// Physical qubit of each logical qubit
+    pub layout: Vec<u32>,
+    /// Fidelity expected from the backend's calibration
+    pub estimated_fidelity: f64,
+}
+
+/// Mean error rates of a calibration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationErrors {
+    pub single_qubit: f64,
+    pub two_qubit: f64,
+    pub measurement: f64,
+}
+
+impl CalibrationErrors {
+    /// Error rate by its hardware interface name
+    pub fn get(&self, operation: &str) -> Option<f64> {
+        match operation {
+            "single_qubit" => Some(self.single_qubit),
+            "two_qubit" => Some(self.two_qubit),
+            "measurement" => Some(self.measurement),
+            _ => None,
+        }
+    }
+}
+
+/// Summary of one backend's calibration for status reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationSummary {
+    pub taken_at_ms: u64,
+    pub qubits: usize,
+    pub couplers: usize,
+    pub errors: CalibrationErrors,
+    /// Older than `max_age_seconds` and no longer used
+    pub stale: bool,
+    /// Last refresh failure, cleared by the next successful one
+    pub last_error: Option<String>,
+}
+
+/// Backend able to report its current calibration
+pub trait CalibrationSource: Send + Sync {
+    /// Fetch the latest calibration run
+    fn fetch(&self) -> Result<CalibrationData>;
+}
+
+#[derive(Default)]
+struct BackendCalibration {
+    data: Option<CalibrationData>,
+    source: Option<Arc<dyn CalibrationSource>>,
+    last_error: Option<String>,
+}
+
+/// Latest calibration of every backend
+pub struct CalibrationStore {
+    config: CalibrationConfig,
+    backends: RwLock<BTreeMap<String, BackendCalibration>>,
+}
+
+impl fmt::Debug for CalibrationStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CalibrationStore")
+            .field("config", &self.config)
+            .field("backends", &self.backends.read().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Default for CalibrationStore {
+    fn default() -> Self {
+        Self {
+            config: CalibrationConfig::default(),
+            backends: RwLock::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl CalibrationStore {
+    /// Store with no calibrations
+    pub fn new(config: CalibrationConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            backends: RwLock::new(BTreeMap::new()),
+        })
+    }
+
+    /// Refresh and age limits in force
+    pub fn config(&self) -> &CalibrationConfig {
+        &self.config
+    }
+
+    /// Record a backend's calibration, ignoring runs older than the one held
+    ///
+    /// Returns whether the data replaced the backend's calibration.
+    pub fn ingest(&self, backend: &str, data: CalibrationData) -> Result<bool> {
+        data.validate()?;
+        let mut backends = self.backends.write();
+        let entry = backends.entry(backend.to_string()).or_default();
+        if let Some(current) = &entry.data {
+            if current.taken_at_ms > data.taken_at_ms {
+                return Ok(false);
+            }
+        }
+        entry.data = Some(data);
+        entry.last_error = None;
+        Ok(true)
+    }
+
+    /// Fetch a backend's calibration from `source` whenever it is due
+    pub fn register_source(&self, backend: &str, source: Arc<dyn CalibrationSource>) {
+        self.backends
+            .write()
+            .entry(backend.to_string())
+            .or_default()
+            .source = Some(source);
+    }
+
+    /// Forget a backend's calibration and source
+    pub fn remove(&self, backend: &str) -> bool {
+        self.backends.write().remove(backend).is_some()
+    }
+
+    /// Fetch from every source whose calibration is missing or due
+    ///
+    /// Sources are called without holding the store's lock. Returns the
+    /// backends fetched and whether each fetch was ingested.
+    pub fn refresh_due(&self, now_ms: u64) -> Vec<(String, Result<bool>)> {
+        let due: Vec<(String, Arc<dyn CalibrationSource>)> = self
+            .backends
+            .read()
+            .iter()
+            .filter(|(_, entry)| {
+                entry.data.as_ref().is_none_or(|data| {
+                    age_seconds(data, now_ms) >= self.config.refresh_interval_seconds
+                })
+            })
+            .filter_map(|(name, entry)| Some((name.clone(), entry.source.clone()?)))
+            .collect();
+
+        due.into_iter()
+            .map(|(backend, source)| {
+                let result = source.fetch().and_then(|data| self.ingest(&backend, data));
+                if let Err(e) = &result {
+                    println!("⚠️ Calibration refresh of {} failed: {}", backend, e);
+                    if let Some(entry) = self.backends.write().get_mut(&backend) {
+                        entry.last_error = Some(e.to_string());
+                    }
+                }
+                (backend, result)
+            })
+            .collect()
+    }
+
+    /// A backend's calibration, unless missing or older than `max_age_seconds`
+    pub fn get(&self, backend: &str, now_ms: u64) -> Option<CalibrationData> {
+        self.backends
+            .read()
+            .get(backend)?
+            .data
+            .clone()
+            .filter(|data| !self.is_stale(data, now_ms))
+    }
+
+    /// Lay a circuit out on a backend using its fresh calibration
+    pub fn transpile(
+        &self,
+        backend: &str,
+        circuit: &QuantumCircuit,
+        now_ms: u64,
+    ) -> Result<Transpiled> {
+        self.get(backend, now_ms)
+            .ok_or_else(|| {
+                SecureCommsError::QuantumOperation(format!(
+                    "No fresh calibration for backend {}",
+                    backend
+                ))
+            })?
+            .transpile(circuit)
+    }
+
+    /// Expected fidelity of a circuit on every freshly calibrated backend
+    ///
+    /// A backend that cannot lay the circuit out is estimated at 0.
+    pub fn fidelity_estimates(
+        &self,
+        circuit: &QuantumCircuit,
+        now_ms: u64,
+    ) -> BTreeMap<String, f64> {
+        self.backends
+            .read()
+            .iter()
+            .filter_map(|(name, entry)| {
+                let data = entry.data.as_ref().filter(|d| !self.is_stale(d, now_ms))?;
+                let fidelity = data
+                    .transpile(circuit)
+                    .map_or(0.0, |transpiled| transpiled.estimated_fidelity);
+                Some((name.clone(), fidelity))
+            })
+            .collect()
+    }
+
+    /// Mean error rates across fresh calibrations; zero with none
+    pub fn mean_errors(&self, now_ms: u64) -> CalibrationErrors {
+        let backends = self.backends.read();
+        let fresh: Vec<CalibrationErrors> = backends
+            .values()
+            .filter_map(|entry| entry.data.as_ref())
+            .filter(|data| !self.is_stale(data, now_ms))
+            .map(CalibrationData::mean_errors)
+            .collect();
+        if fresh.is_empty() {
+            return CalibrationErrors::default();
+        }
+        let n = fresh.len() as f64;
+        CalibrationErrors {
+            single_qubit: fresh.iter().map(|e| e.single_qubit).sum::<f64>() / n,
+            two_qubit: fresh.iter().map(|e| e.two_qubit).sum::<f64>() / n,
+            measurement: fresh.iter().map(|e| e.measurement).sum::<f64>() / n,
+        }
+    }
+
+    /// Calibration summary of every backend that has reported one
+    pub fn summary(&self, now_ms: u64) -> BTreeMap<String, CalibrationSummary> {
+        self.backends
+            .read()
+            .iter()
+            .filter_map(|(name, entry)| {
+                let data = entry.data.as_ref()?;
+                Some((
+                    name.clone(),
+                    CalibrationSummary {
+                        taken_at_ms: data.taken_at_ms,
+                        qubits: data.qubits.len(),
+                        couplers: data.couplers.len(),
+                        errors: data.mean_errors(),
+                        stale: self.is_stale(data, now_ms),
+                        last_error: entry.last_error.clone(),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    fn is_stale(&self, data: &CalibrationData, now_ms: u64) -> bool {
+        age_seconds(data, now_ms) > self.config.max_age_seconds
+    }
+}
+
+fn age_seconds(data: &CalibrationData, now_ms: u64) -> u64 {
+    now_ms.saturating_sub(data.taken_at_ms) / 1_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn qubit(gate_error: f64, readout_error: f64) -> QubitCalibration {
+        QubitCalibration {
+            t1_us: 100.0,
+            t2_us: 80.0,
+            gate_error,
+            readout_error,
+        }
+    }
+
+    fn coupler(control: u32, target: u32, error: f64) -> CouplerCalibration {
+        CouplerCalibration {
+            control,
+            target,
+            error,
+        }
+    }
+
+    /// Line of four qubits where qubit 0 is noisy and 2-3 is the best coupler
+    fn line(taken_at_ms: u64) -> CalibrationData {
+        CalibrationData {
+            taken_at_ms,
+            qubits: vec![
+                qubit(0.01, 0.1),
+                qubit(0.001, 0.02),
+                qubit(0.0005, 0.01),
+                qubit(0.0005, 0.01),
+            ],
+            couplers: vec![
+                coupler(0, 1, 0.02),
+                coupler(1, 2, 0.01),
+                coupler(2, 3, 0.005),
+            ],
+            single_qubit_gate_ns: 50.0,
+            two_qubit_gate_ns: 300.0,
+        }
+    }
+
+    fn bell() -> QuantumCircuit {
+        let mut circuit = QuantumCircuit::new("bell".to_string(), 2);
+        circuit.add_gate(QuantumGate::Hadamard, vec![0]).unwrap();
+        circuit.add_gate(QuantumGate::CNOT, vec![0, 1]).unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_transpile_picks_best_coupled_qubits() {
+        let data = line(0);
+        let transpiled = data.transpile(&bell()).unwrap();
+        let mut layout = transpiled.layout.clone();
+        layout.sort();
+        assert_eq!(layout, vec![2, 3]);
+        assert_eq!(transpiled.circuit.qubit_count, 4);
+        let (_, cnot_qubits) = &transpiled.circuit.operations[1];
+        assert!(data.coupler_error(cnot_qubits[0], cnot_qubits[1]).is_some());
+
+        // Gate, decoherence and readout errors all lower the estimate
+        let gates = (1.0 - 0.0005) * (1.0 - 0.005);
+        assert!(transpiled.estimated_fidelity < gates * 0.99 * 0.99);
+        assert!(transpiled.estimated_fidelity > 0.95);
+        assert_eq!(
+            transpiled.circuit.expected_fidelity,
+            transpiled.estimated_fidelity
+        );
+
+        // The same circuit forced onto the noisy end does worse
+        let mut noisy = QuantumCircuit::new("bell".to_string(), 4);
+        noisy.add_gate(QuantumGate::Hadamard, vec![0]).unwrap();
+        noisy.add_gate(QuantumGate::CNOT, vec![0, 1]).unwrap();
+        assert!(data.estimate_fidelity(&noisy) < transpiled.estimated_fidelity);
+        let mut uncoupled = QuantumCircuit::new("far".to_string(), 4);
+        uncoupled.add_gate(QuantumGate::CNOT, vec![0, 3]).unwrap();
+        assert_eq!(data.estimate_fidelity(&uncoupled), 0.0);
+
+        // A triangle of CNOTs has no layout on a line
+        let mut triangle = QuantumCircuit::new("triangle".to_string(), 3);
+        for pair in [[0, 1], [1, 2], [0, 2]] {
+            triangle.add_gate(QuantumGate::CNOT, pair.to_vec()).unwrap();
+        }
+        assert!(data.transpile(&triangle).is_err());
+        assert!(data
+            .transpile(&QuantumCircuit::new("wide".to_string(), 5))
+            .is_err());
+    }
+
+    struct Device {
+        taken_at_ms: AtomicU64,
+    }
+
+    impl CalibrationSource for Device {
+        fn fetch(&self) -> Result<CalibrationData> {
+            Ok(line(self.taken_at_ms.load(Ordering::Relaxed)))
+        }
+    }
+
+    #[test]
+    fn test_refresh_schedule_and_staleness() {
+        let store = CalibrationStore::new(CalibrationConfig {
+            refresh_interval_seconds: 60,
+            max_age_seconds: 120,
+        })
+        .unwrap();
+        let device = Arc::new(Device {
+            taken_at_ms: AtomicU64::new(1_000_000),
+        });
+        store.register_source("qpu", device.clone());
+
+        let refreshed = store.refresh_due(1_000_000);
+        assert_eq!(refreshed.len(), 1);
+        assert!(refreshed[0].1.as_ref().unwrap());
+        assert!(store.refresh_due(1_030_000).is_empty());
+        assert!(store.get("qpu", 1_030_000).is_some());
+
+        // Due again after the interval, but the backend has not recalibrated
+        let refreshed = store.refresh_due(1_060_000);
+        assert_eq!(refreshed.len(), 1);
+        assert!(store.get("qpu", 1_121_000).is_none());
+        assert!(store.summary(1_121_000)["qpu"].stale);
+        assert!(store.fidelity_estimates(&bell(), 1_121_000).is_empty());
+        assert_eq!(store.mean_errors(1_121_000), CalibrationErrors::default());
+
+        device.taken_at_ms.store(1_120_000, Ordering::Relaxed);
+        store.refresh_due(1_121_000);
+        assert!(store.get("qpu", 1_121_000).is_some());
+        let errors = store.mean_errors(1_121_000);
+        assert!((errors.single_qubit - 0.003).abs() < 1e-12);
+        assert_eq!(errors.get("two_qubit"), Some(errors.two_qubit));
+        assert!(store.fidelity_estimates(&bell(), 1_121_000)["qpu"] > 0.95);
+
+        // An older run never replaces a newer one
+        assert!(!store.ingest("qpu", line(0)).unwrap());
+        assert_eq!(store.get("qpu", 1_121_000).unwrap().taken_at_ms, 1_120_000);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(CalibrationConfig::default().validate().is_ok());
+        assert!(CalibrationConfig {
+            refresh_interval_seconds: 600,
+            max_age_seconds: 300,
+        }
+        .validate()
+        .is_err());
+        assert!(line(0).validate().is_ok());
+
+        let mut data = line(0);
+        data.qubits[1].t2_us = 250.0;
+        assert!(data.validate().is_err());
+        let mut data = line(0);
+        data.qubits[2].readout_error = 1.5;
+        assert!(data.validate().is_err());
+        let mut data = line(0);
+        data.couplers.push(coupler(3, 7, 0.01));
+        assert!(data.validate().is_err());
+        let data = CalibrationData {
+            qubits: Vec::new(),
+            ..line(0)
+        };
+        let store = CalibrationStore::new(CalibrationConfig::default()).unwrap();
+        assert!(store.ingest("qpu", data).is_err());
+        assert!(store.summary(0).is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+//! # Cancellation - Cooperative Cancellation of Long-Running Operations
+//!
+//! Lets callers abort a handshake or rekey that is waiting on the network
+//! without dropping the client or leaking what the operation had set up.
+//! Operations take a `CancellationToken`; every await point runs inside a
+//! `CancelScope`, which stops at the first await point after the token is
+//! cancelled and reports which one it was. The operation then unwinds
+//! through its normal failure path, so a cancelled handshake leaves no
+//! channel, state machine, network channel or resource permit behind and a
+//! cancelled rekey keeps the old session key.
+//!
+//! ## Shutdown
+//!
+//! Each client owns a shutdown token. Calls without an explicit token run
+//! under a child of it, so cancelling `StreamlinedSecureClient::shutdown_token`
+//! from another task (a signal handler, a supervisor) aborts whatever the
+//! client is doing, and `shutdown` cancels it before closing channels.
+//! A token passed explicitly replaces the shutdown token for that call;
+//! derive it with `child_token` to honour both.
+//!
+//! ## Commit Points
+//!
+//! Once a handshake registers its channel the result is committed: later
+//! steps such as cluster replication run to completion even if the token is
+//! cancelled, so a caller never sees `Cancelled` for a channel that is open.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::cancellation::CancellationToken;
+//! use quantum_forge_secure_comms::{SecureCommsError, StreamlinedSecureClient};
+//! use quantum_forge_secure_comms::streamlined_client::ChannelEstablishmentConfig;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! let token = client.shutdown_token().child_token();
+//! let canceller = token.clone();
+//! tokio::spawn(async move {
+//!     tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+//!     canceller.cancel();
+//! });
+//! match client
+//!     .establish_secure_channel_with_cancel("peer_1", &ChannelEstablishmentConfig::default(), &token)
+//!     .await
+//! {
+//!     Err(SecureCommsError::Cancelled(reason)) => println!("⏹️ {}", reason),
+//!     other => println!("{:?}", other.map(|channel| channel.channel_id)),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Result, SecureCommsError};
+use std::future::Future;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Await points of one channel establishment attempt, in order
+pub const HANDSHAKE_STEPS: [&str; 4] = [
+    "resolve_address",
+    "connect_and_exchange",
+    "open_channel",
+    "verify",
+];
+
+/// Await points of one rekey, in order
+pub const REKEY_STEPS: [&str; 2] = ["exchange_keys", "rotate_key"];
+
+/// Cancellation state of one operation
+#[derive(Debug, Clone)]
+pub struct CancelScope {
+    token: CancellationToken,
+    operation: String,
+    step: Option<&'static str>,
+    #[cfg(test)]
+    cancel_at: Option<&'static str>,
+}
+
+impl CancelScope {
+    /// Scope for `operation`, cancelled through `token`
+    pub fn new(token: &CancellationToken, operation: impl Into<String>) -> Self {
+        Self {
+            token: token.clone(),
+            operation: operation.into(),
+            step: None,
+            #[cfg(test)]
+            cancel_at: None,
+        }
+    }
+
+    /// Token the scope observes
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Last await point the operation reached
+    pub fn step(&self) -> Option<&'static str> {
+        self.step
+    }
+
+    /// Whether the operation has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Fail with `Cancelled` if the token was cancelled
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(self.cancelled())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Run one await point, abandoning it as soon as the token is cancelled
+    ///
+    /// A token cancelled before the step starts wins over a step that is
+    /// already complete, so cancellation is observed at the first await
+    /// point after it happened.
+    pub async fn run<T, F>(&mut self, step: &'static str, future: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.step = Some(step);
+        #[cfg(test)]
+        if self.cancel_at == Some(step) {
+            self.token.cancel();
+        }
+        tokio::select! {
+            biased;
+            _ = self.token.cancelled() => Err(self.cancelled()),
+            result = future => result,
+        }
+    }
+
+    /// Sleep unless cancelled first
+    pub async fn sleep(&mut self, duration: std::time::Duration) -> Result<()> {
+        self.run("backoff", async {
+            tokio::time::sleep(duration).await;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Cancel the token when the operation reaches `step`
+    #[cfg(test)]
+    pub(crate) fn cancel_at(mut self, step: &'static str) -> Self {
+        self.cancel_at = Some(step);
+        self
+    }
+
+    fn cancelled(&self) -> SecureCommsError {
+        SecureCommsError::Cancelled(match self.step {
+            Some(step) => format!("{} cancelled at {}", self.operation, step),
+            None => format!("{} cancelled before it started", self.operation),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancelled_token_stops_at_next_step() {
+        let token = CancellationToken::new();
+        let mut scope = CancelScope::new(&token, "handshake with peer_1");
+        assert_eq!(scope.run("first", async { Ok(1) }).await.unwrap(), 1);
+        scope.check().unwrap();
+
+        token.cancel();
+        // A ready step still loses to an earlier cancellation
+        let err = scope.run("second", async { Ok(2) }).await.unwrap_err();
+        assert!(matches!(
+            err,
+            SecureCommsError::Cancelled(ref reason) if reason == "handshake with peer_1 cancelled at second"
+        ));
+        assert_eq!(scope.step(), Some("second"));
+        assert!(scope.check().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_interrupts_pending_step() {
+        let token = CancellationToken::new();
+        let mut scope = CancelScope::new(&token, "rekey");
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        let started = std::time::Instant::now();
+        let result: Result<()> = scope
+            .run("exchange_keys", std::future::pending::<Result<()>>())
+            .await;
+        assert!(matches!(result, Err(SecureCommsError::Cancelled(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(scope.sleep(Duration::from_secs(60)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_child_tokens_follow_shutdown() {
+        let shutdown = CancellationToken::new();
+        let call = shutdown.child_token();
+        let scope = CancelScope::new(&call, "handshake");
+        assert!(scope.check().is_ok());
+
+        // Cancelling one call leaves the client's token alone
+        let other = shutdown.child_token();
+        other.cancel();
+        assert!(!shutdown.is_cancelled());
+        assert!(scope.check().is_ok());
+
+        shutdown.cancel();
+        assert!(matches!(
+            scope.check(),
+            Err(SecureCommsError::Cancelled(ref reason)) if reason.ends_with("before it started")
+        ));
+        let mut tripped =
+            CancelScope::new(&CancellationToken::new(), "rekey").cancel_at("rotate_key");
+        assert!(tripped.run("exchange_keys", async { Ok(()) }).await.is_ok());
+        assert!(tripped.run("rotate_key", async { Ok(()) }).await.is_err());
+    }
+}
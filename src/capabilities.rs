@@ -0,0 +1,286 @@
+//! # Capabilities - Protocol Feature Flags for Rolling Upgrades
+//!
+//! Lets a fleet adopt new protocol features one node at a time. Each node
+//! advertises the features it has enabled during the handshake (or later
+//! with a `Capabilities` message); a feature is used on a channel only when
+//! both ends advertise it, and otherwise the channel falls back to the
+//! behaviour older nodes understand.
+//!
+//! ## Features
+//!
+//! - **message_batching**: Coalesced `SecureDataBatch` frames; without it
+//!   batches go out one frame per message
+//! - **key_ratchet**: Session key updates with `KeyUpdate`
+//! - **suite_negotiation**: Downgrade-resistant `SuiteHello`/`SuiteAccept`
+//! - **chacha20_poly1305**: ChaCha20-Poly1305 offered during suite
+//!   negotiation; without it only AES-256-GCM is offered
+//! - **relay_envelopes**: End-to-end sealed `RelayEnvelope` messages
+//! - **compression**: LZ4-compressed payloads marked with the
+//!   `x-compression` header
+//!
+//! ## Compatibility Rules
+//!
+//! - Features travel as names, so a node ignores features newer than
+//!   itself instead of rejecting the advertisement
+//! - A peer that never advertised is assumed to run the last release
+//!   without advertisements and to speak its `CapabilitySet::legacy` set
+//! - Each feature can be switched on or off per node in `FeatureFlags`;
+//!   switching one off stops this node advertising and using it
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::capabilities::{negotiate, Feature, FeatureFlags};
+//!
+//! let flags = FeatureFlags::default().disable(Feature::ChaCha20Poly1305);
+//! let peer = FeatureFlags::default().advertisement();
+//! let negotiated = negotiate(&flags, &peer);
+//! assert!(negotiated.allows(Feature::KeyRatchet));
+//! assert!(!negotiated.allows(Feature::ChaCha20Poly1305));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Protocol version advertised alongside the feature set
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Protocol version assumed for peers that advertise nothing
+pub const LEGACY_PROTOCOL_VERSION: u32 = 1;
+
+/// Optional protocol feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// Several payloads coalesced into one frame
+    MessageBatching,
+    /// Session key ratcheting
+    KeyRatchet,
+    /// Cipher suite negotiation bound into the session key
+    SuiteNegotiation,
+    /// ChaCha20-Poly1305 AEAD suite
+    #[serde(rename = "chacha20_poly1305")]
+    ChaCha20Poly1305,
+    /// End-to-end sealed relay envelopes
+    RelayEnvelopes,
+    /// Adaptive payload compression
+    Compression,
+}
+
+impl Feature {
+    /// Every feature known to this build
+    pub const ALL: [Feature; 6] = [
+        Feature::MessageBatching,
+        Feature::KeyRatchet,
+        Feature::SuiteNegotiation,
+        Feature::ChaCha20Poly1305,
+        Feature::RelayEnvelopes,
+        Feature::Compression,
+    ];
+
+    /// Wire name of the feature
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feature::MessageBatching => "message_batching",
+            Feature::KeyRatchet => "key_ratchet",
+            Feature::SuiteNegotiation => "suite_negotiation",
+            Feature::ChaCha20Poly1305 => "chacha20_poly1305",
+            Feature::RelayEnvelopes => "relay_envelopes",
+            Feature::Compression => "compression",
+        }
+    }
+
+    /// Feature with the given wire name, if this build knows it
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|feature| feature.name() == name)
+    }
+
+    /// Whether nodes that predate advertisements speak this feature
+    pub fn is_legacy(&self) -> bool {
+        !matches!(self, Feature::ChaCha20Poly1305 | Feature::Compression)
+    }
+}
+
+/// Per-node feature switches
+///
+/// Every feature is enabled unless overridden.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureFlags {
+    /// Explicit enable (`true`) or disable (`false`) per feature
+    #[serde(default)]
+    pub overrides: BTreeMap<Feature, bool>,
+}
+
+impl FeatureFlags {
+    /// Switch a feature on
+    pub fn enable(mut self, feature: Feature) -> Self {
+        self.overrides.insert(feature, true);
+        self
+    }
+
+    /// Switch a feature off
+    pub fn disable(mut self, feature: Feature) -> Self {
+        self.overrides.insert(feature, false);
+        self
+    }
+
+    /// Whether this node uses the feature
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.overrides.get(&feature).copied().unwrap_or(true)
+    }
+
+    /// Features this node uses
+    pub fn enabled(&self) -> BTreeSet<Feature> {
+        Feature::ALL
+            .into_iter()
+            .filter(|feature| self.is_enabled(*feature))
+            .collect()
+    }
+
+    /// Capability set to advertise to peers
+    pub fn advertisement(&self) -> CapabilitySet {
+        CapabilitySet {
+            protocol_version: PROTOCOL_VERSION,
+            features: self
+                .enabled()
+                .into_iter()
+                .map(|feature| feature.name().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Features a node advertises
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilitySet {
+    /// Sender's protocol version
+    pub protocol_version: u32,
+    /// Wire names of the sender's enabled features
+    pub features: BTreeSet<String>,
+}
+
+impl CapabilitySet {
+    /// Capabilities assumed for a peer that advertises nothing
+    pub fn legacy() -> Self {
+        Self {
+            protocol_version: LEGACY_PROTOCOL_VERSION,
+            features: Feature::ALL
+                .into_iter()
+                .filter(Feature::is_legacy)
+                .map(|feature| feature.name().to_string())
+                .collect(),
+        }
+    }
+
+    /// Whether the sender advertised the feature
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(feature.name())
+    }
+
+    /// Advertised feature names this build does not know
+    pub fn unknown(&self) -> Vec<String> {
+        self.features
+            .iter()
+            .filter(|name| Feature::from_name(name).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Features agreed for one channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegotiatedFeatures {
+    /// Peer's advertised protocol version
+    pub peer_version: u32,
+    /// Features both ends enabled
+    pub active: BTreeSet<Feature>,
+    /// Features enabled here that the peer lacks; the channel falls back
+    pub fallbacks: BTreeSet<Feature>,
+}
+
+impl NegotiatedFeatures {
+    /// Whether the feature may be used on the channel
+    pub fn allows(&self, feature: Feature) -> bool {
+        self.active.contains(&feature)
+    }
+}
+
+/// Features usable with a peer that advertised `peer`
+pub fn negotiate(local: &FeatureFlags, peer: &CapabilitySet) -> NegotiatedFeatures {
+    let (active, fallbacks): (BTreeSet<Feature>, BTreeSet<Feature>) = local
+        .enabled()
+        .into_iter()
+        .partition(|feature| peer.supports(*feature));
+    NegotiatedFeatures {
+        peer_version: peer.protocol_version,
+        active,
+        fallbacks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiation_uses_common_features() {
+        let local = FeatureFlags::default().disable(Feature::KeyRatchet);
+        let peer = FeatureFlags::default()
+            .disable(Feature::RelayEnvelopes)
+            .advertisement();
+        let negotiated = negotiate(&local, &peer);
+
+        assert!(negotiated.allows(Feature::MessageBatching));
+        // Disabled here: neither used nor reported as a fallback
+        assert!(!negotiated.allows(Feature::KeyRatchet));
+        assert!(!negotiated.fallbacks.contains(&Feature::KeyRatchet));
+        // Lacking at the peer: a fallback
+        assert!(!negotiated.allows(Feature::RelayEnvelopes));
+        assert_eq!(
+            negotiated.fallbacks,
+            BTreeSet::from([Feature::RelayEnvelopes])
+        );
+        assert_eq!(negotiated.peer_version, PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_newer_and_legacy_peers_interoperate() {
+        // A newer peer advertises a feature this build has never heard of
+        let mut newer = FeatureFlags::default().advertisement();
+        newer.protocol_version = PROTOCOL_VERSION + 1;
+        newer.features.insert("quantum_teleport_v9".to_string());
+        let json = serde_json::to_string(&newer).unwrap();
+        let received: CapabilitySet = serde_json::from_str(&json).unwrap();
+        assert_eq!(received.unknown(), vec!["quantum_teleport_v9".to_string()]);
+        assert_eq!(
+            negotiate(&FeatureFlags::default(), &received).active,
+            FeatureFlags::default().enabled()
+        );
+
+        // An older peer gets only what the previous release spoke
+        let legacy = negotiate(&FeatureFlags::default(), &CapabilitySet::legacy());
+        assert!(legacy.allows(Feature::SuiteNegotiation));
+        assert!(!legacy.allows(Feature::ChaCha20Poly1305));
+        assert_eq!(legacy.peer_version, LEGACY_PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_flags_round_trip_through_config() {
+        let flags = FeatureFlags::default()
+            .disable(Feature::ChaCha20Poly1305)
+            .enable(Feature::KeyRatchet);
+        let json = serde_json::to_value(&flags).unwrap();
+        assert_eq!(json["overrides"]["chacha20_poly1305"], false);
+        let parsed: FeatureFlags = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, flags);
+        assert!(!parsed.is_enabled(Feature::ChaCha20Poly1305));
+        assert!(!parsed
+            .advertisement()
+            .features
+            .contains(Feature::ChaCha20Poly1305.name()));
+        assert_eq!(
+            Feature::from_name("message_batching"),
+            Some(Feature::MessageBatching)
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! # Channel Migration - Transport Handover Without Re-Keying
+//!
+//! Lets an established logical secure channel move to a new transport
+//! connection (TCP to QUIC, or a new IP address after a network change)
+//! while keeping its session keys, message counters and routing entry.
+//! Instead of a full PQC/QKD channel establishment, the peers run a short
+//! re-authentication exchange proving possession of the existing session key.
+//!
+//! ## Re-Authentication Exchange
+//!
+//! 1. **Challenge**: The migrating side sends a fresh nonce bound to the channel and new endpoint
+//! 2. **Proof**: The peer answers with a SHA-3 MAC keyed with the current session key
+//! 3. **Commit**: The proof is verified in constant time and the channel is rebound
+//!
+//! Challenges expire after `challenge_ttl_seconds` and are single use, so a
+//! captured proof cannot be replayed to hijack the channel from another address.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::channel_migration::{
+//!     MigrationManager, TransportEndpoint, TransportKind,
+//! };
+//!
+//! let session_key = vec![9u8; 32];
+//! let mut migrations = MigrationManager::default();
+//! let endpoint = TransportEndpoint::new(TransportKind::Quic, "10.0.0.7", 4433);
+//!
+//! let challenge = migrations.create_challenge("channel_peer_1", "peer_1", endpoint);
+//! let proof = MigrationManager::prove(&challenge, &session_key);
+//! let accepted = migrations.verify_proof("peer_1", &proof, &session_key).unwrap();
+//! println!("Channel migrated to {}", accepted.endpoint);
+//! ```
+
+use crate::constant_time::ct_eq;
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Transport protocol carrying a secure channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransportKind {
+    /// TCP stream transport
+    Tcp,
+    /// QUIC transport over UDP
+    Quic,
+}
+
+impl fmt::Display for TransportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransportKind::Tcp => write!(f, "tcp"),
+            TransportKind::Quic => write!(f, "quic"),
+        }
+    }
+}
+
+/// Transport endpoint of a peer
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TransportEndpoint {
+    /// Transport protocol
+    pub kind: TransportKind,
+    /// Network address
+    pub address: String,
+    /// Port number
+    pub port: u16,
+}
+
+impl TransportEndpoint {
+    /// Create new transport endpoint
+    pub fn new(kind: TransportKind, address: &str, port: u16) -> Self {
+        Self {
+            kind,
+            address: address.to_string(),
+            port,
+        }
+    }
+
+    /// Socket address string
+    pub fn socket_address(&self) -> String {
+        format!("{}:{}", self.address, self.port)
+    }
+}
+
+impl fmt::Display for TransportEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}:{}", self.kind, self.address, self.port)
+    }
+}
+
+/// Migration challenge sent to the peer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationChallenge {
+    /// Channel being migrated
+    pub channel_id: String,
+    /// New transport endpoint
+    pub endpoint: TransportEndpoint,
+    /// Fresh random nonce
+    pub nonce: Vec<u8>,
+    /// Challenge creation time (Unix seconds)
+    pub issued_at: u64,
+}
+
+/// Proof of session key possession returned by the peer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationProof {
+    /// Channel being migrated
+    pub channel_id: String,
+    /// Nonce from the challenge
+    pub nonce: Vec<u8>,
+    /// SHA-3 MAC over channel, endpoint and nonce keyed with the session key
+    pub mac: Vec<u8>,
+}
+
+/// Completed, verified migration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationRecord {
+    /// Peer whose channel migrated
+    pub peer_id: String,
+    /// Migrated channel
+    pub channel_id: String,
+    /// New transport endpoint
+    pub endpoint: TransportEndpoint,
+    /// Completion time (Unix seconds)
+    pub migrated_at: u64,
+}
+
+/// Migration configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationConfig {
+    /// Seconds a challenge remains valid
+    pub challenge_ttl_seconds: u64,
+    /// Completed migrations retained for auditing
+    pub history_limit: usize,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            challenge_ttl_seconds: 30,
+            history_limit: 256,
+        }
+    }
+}
+
+/// Tracks pending migration challenges and completed migrations
+#[derive(Debug, Clone, Default)]
+pub struct MigrationManager {
+    /// Migration configuration
+    config: MigrationConfig,
+    /// Outstanding challenges by peer
+    pending: HashMap<String, MigrationChallenge>,
+    /// Completed migrations, oldest first
+    history: Vec<MigrationRecord>,
+    /// Rejected proofs
+    failed_attempts: u64,
+}
+
+impl MigrationManager {
+    /// Create new migration manager
+    pub fn new(config: MigrationConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            history: Vec::new(),
+            failed_attempts: 0,
+        }
+    }
+
+    /// Issue a migration challenge for a peer's channel
+    ///
+    /// Replaces any outstanding challenge for the same peer.
+    pub fn create_challenge(
+        &mut self,
+        channel_id: &str,
+        peer_id: &str,
+        endpoint: TransportEndpoint,
+    ) -> MigrationChallenge {
+        use rand::RngCore;
+        let mut nonce = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let challenge = MigrationChallenge {
+            channel_id: channel_id.to_string(),
+            endpoint,
+            nonce,
+            issued_at: chrono::Utc::now().timestamp() as u64,
+        };
+        self.pending.insert(peer_id.to_string(), challenge.clone());
+        challenge
+    }
+
+    /// Answer a challenge using the existing session key
+    pub fn prove(challenge: &MigrationChallenge, session_key: &[u8]) -> MigrationProof {
+        MigrationProof {
+            channel_id: challenge.channel_id.clone(),
+            nonce: challenge.nonce.clone(),
+            mac: Self::compute_mac(challenge, session_key),
+        }
+    }
+
+    /// Verify a peer's proof and consume the challenge
+    pub fn verify_proof(
+        &mut self,
+        peer_id: &str,
+        proof: &MigrationProof,
+        session_key: &[u8],
+    ) -> Result<MigrationRecord> {
+        let challenge = self.pending.get(peer_id).cloned().ok_or_else(|| {
+            SecureCommsError::Validation(format!("No pending channel migration for {}", peer_id))
+        })?;
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        if now.saturating_sub(challenge.issued_at) > self.config.challenge_ttl_seconds {
+            self.pending.remove(peer_id);
+            return Err(SecureCommsError::Timeout(format!(
+                "Channel migration challenge for {} expired",
+                peer_id
+            )));
+        }
+
+        let expected = Self::compute_mac(&challenge, session_key);
+        let mac_matches = ct_eq(&expected, &proof.mac);
+
+        if proof.channel_id != challenge.channel_id || proof.nonce != challenge.nonce || !mac_matches {
+            self.failed_attempts += 1;
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+
+        self.pending.remove(peer_id);
+        let record = MigrationRecord {
+            peer_id: peer_id.to_string(),
+            channel_id: challenge.channel_id,
+            endpoint: challenge.endpoint,
+            migrated_at: now,
+        };
+        self.history.push(record.clone());
+        if self.history.len() > self.config.history_limit {
+            let excess = self.history.len() - self.config.history_limit;
+            self.history.drain(..excess);
+        }
+        Ok(record)
+    }
+
+    /// Abandon a pending migration
+    pub fn cancel(&mut self, peer_id: &str) -> bool {
+        self.pending.remove(peer_id).is_some()
+    }
+
+    /// Pending challenge for a peer
+    pub fn pending_challenge(&self, peer_id: &str) -> Option<&MigrationChallenge> {
+        self.pending.get(peer_id)
+    }
+
+    /// Completed migrations, oldest first
+    pub fn history(&self) -> &[MigrationRecord] {
+        &self.history
+    }
+
+    /// Get migration statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert("pending_migrations".to_string(), serde_json::json!(self.pending.len()));
+        stats.insert("completed_migrations".to_string(), serde_json::json!(self.history.len()));
+        stats.insert("failed_attempts".to_string(), serde_json::json!(self.failed_attempts));
+        stats
+    }
+
+    fn compute_mac(challenge: &MigrationChallenge, session_key: &[u8]) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"channel_migration_v1");
+        hasher.update(session_key);
+        hasher.update(challenge.channel_id.as_bytes());
+        hasher.update(challenge.endpoint.to_string().as_bytes());
+        hasher.update(&challenge.nonce);
+        hasher.update(challenge.issued_at.to_le_bytes());
+        hasher.finalize().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quic_endpoint() -> TransportEndpoint {
+        TransportEndpoint::new(TransportKind::Quic, "10.0.0.7", 4433)
+    }
+
+    #[test]
+    fn test_migration_with_session_key() {
+        let key = vec![5u8; 32];
+        let mut manager = MigrationManager::default();
+        let challenge = manager.create_challenge("channel_a", "peer_a", quic_endpoint());
+
+        let proof = MigrationManager::prove(&challenge, &key);
+        let record = manager.verify_proof("peer_a", &proof, &key).unwrap();
+
+        assert_eq!(record.endpoint, quic_endpoint());
+        assert_eq!(manager.history().len(), 1);
+        assert!(manager.pending_challenge("peer_a").is_none());
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let mut manager = MigrationManager::default();
+        let challenge = manager.create_challenge("channel_a", "peer_a", quic_endpoint());
+
+        let proof = MigrationManager::prove(&challenge, &[1u8; 32]);
+        assert!(manager.verify_proof("peer_a", &proof, &[2u8; 32]).is_err());
+        assert_eq!(manager.get_stats()["failed_attempts"], serde_json::json!(1));
+        assert!(manager.pending_challenge("peer_a").is_some());
+    }
+
+    #[test]
+    fn test_proof_is_single_use() {
+        let key = vec![5u8; 32];
+        let mut manager = MigrationManager::default();
+        let challenge = manager.create_challenge("channel_a", "peer_a", quic_endpoint());
+        let proof = MigrationManager::prove(&challenge, &key);
+
+        assert!(manager.verify_proof("peer_a", &proof, &key).is_ok());
+        assert!(manager.verify_proof("peer_a", &proof, &key).is_err());
+    }
+
+    #[test]
+    fn test_proof_bound_to_endpoint() {
+        let key = vec![5u8; 32];
+        let mut manager = MigrationManager::default();
+        let original = manager.create_challenge("channel_a", "peer_a", quic_endpoint());
+        let proof = MigrationManager::prove(&original, &key);
+
+        // Re-issuing for a different endpoint invalidates the old proof
+        manager.create_challenge(
+            "channel_a",
+            "peer_a",
+            TransportEndpoint::new(TransportKind::Tcp, "10.0.0.8", 8080),
+        );
+        assert!(manager.verify_proof("peer_a", &proof, &key).is_err());
+    }
+
+    #[test]
+    fn test_expired_challenge() {
+        let key = vec![5u8; 32];
+        let mut manager = MigrationManager::new(MigrationConfig {
+            challenge_ttl_seconds: 0,
+            ..MigrationConfig::default()
+        });
+        let mut challenge = manager.create_challenge("channel_a", "peer_a", quic_endpoint());
+        challenge.issued_at -= 10;
+        manager.pending.insert("peer_a".to_string(), challenge.clone());
+
+        let proof = MigrationManager::prove(&challenge, &key);
+        assert!(manager.verify_proof("peer_a", &proof, &key).is_err());
+    }
+}
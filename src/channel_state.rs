@@ -0,0 +1,323 @@
+//! # Channel State - Explicit Handshake and Session State Machine
+//!
+//! Models the lifecycle of a secure channel as a typed state machine so the
+//! handshake can be reviewed as a transition table instead of being inferred
+//! from the order of calls in the client. Every step of establishment, rekey
+//! and teardown is an input; an input that is not legal in the current state
+//! is refused with an error rather than silently accepted.
+//!
+//! ## States
+//!
+//! - **Idle**: Channel slot reserved, nothing sent yet
+//! - **HandshakeSent**: Connection and key exchange in flight
+//! - **KeyConfirmed**: Session key derived and installed on the transport
+//! - **Established**: Verified channel carrying application traffic
+//! - **Rekeying**: Session key being replaced; returns to Established
+//! - **Closed**: Terminal; the channel cannot be reused
+//!
+//! ## Transition Table
+//!
+//! | From | Input | To |
+//! |------|-------|----|
+//! | Idle | SendHandshake | HandshakeSent |
+//! | HandshakeSent | ConfirmKey | KeyConfirmed |
+//! | KeyConfirmed | Establish | Established |
+//! | Established | BeginRekey | Rekeying |
+//! | Rekeying | CompleteRekey / AbortRekey | Established |
+//! | any but Closed | Close | Closed |
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::channel_state::{
+//!     ChannelInput, ChannelState, ChannelStateMachine,
+//! };
+//!
+//! let mut machine = ChannelStateMachine::new("peer_1");
+//! machine.apply(ChannelInput::SendHandshake).unwrap();
+//! machine.apply(ChannelInput::ConfirmKey).unwrap();
+//! machine.apply(ChannelInput::Establish).unwrap();
+//! assert_eq!(machine.state(), ChannelState::Established);
+//!
+//! // Rekeying a channel that is already rekeying is refused
+//! machine.apply(ChannelInput::BeginRekey).unwrap();
+//! assert!(machine.apply(ChannelInput::BeginRekey).is_err());
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Lifecycle state of a secure channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChannelState {
+    /// Channel slot reserved, nothing sent yet
+    Idle,
+    /// Connection and key exchange in flight
+    HandshakeSent,
+    /// Session key derived and installed on the transport
+    KeyConfirmed,
+    /// Verified channel carrying application traffic
+    Established,
+    /// Session key being replaced
+    Rekeying,
+    /// Channel torn down
+    Closed,
+}
+
+impl ChannelState {
+    /// Every state, in lifecycle order
+    pub const ALL: [ChannelState; 6] = [
+        ChannelState::Idle,
+        ChannelState::HandshakeSent,
+        ChannelState::KeyConfirmed,
+        ChannelState::Established,
+        ChannelState::Rekeying,
+        ChannelState::Closed,
+    ];
+
+    /// State reached by applying `input`, or `None` if the transition is illegal
+    pub fn next(self, input: ChannelInput) -> Option<ChannelState> {
+        use ChannelInput as I;
+        use ChannelState as S;
+        match (self, input) {
+            (S::Closed, _) => None,
+            (_, I::Close) => Some(S::Closed),
+            (S::Idle, I::SendHandshake) => Some(S::HandshakeSent),
+            (S::HandshakeSent, I::ConfirmKey) => Some(S::KeyConfirmed),
+            (S::KeyConfirmed, I::Establish) => Some(S::Established),
+            (S::Established, I::BeginRekey) => Some(S::Rekeying),
+            (S::Rekeying, I::CompleteRekey | I::AbortRekey) => Some(S::Established),
+            _ => None,
+        }
+    }
+
+    /// Whether the channel may carry application traffic
+    ///
+    /// Traffic keeps flowing under the old key while a rekey is in progress.
+    pub fn carries_traffic(self) -> bool {
+        matches!(self, ChannelState::Established | ChannelState::Rekeying)
+    }
+}
+
+impl fmt::Display for ChannelState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Step of the channel lifecycle driving a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChannelInput {
+    /// Connect and start the key exchange
+    SendHandshake,
+    /// Session key derived and installed
+    ConfirmKey,
+    /// Channel verification succeeded
+    Establish,
+    /// Start replacing the session key
+    BeginRekey,
+    /// New session key installed
+    CompleteRekey,
+    /// Rekey failed; the old session key stays in use
+    AbortRekey,
+    /// Tear the channel down
+    Close,
+}
+
+impl ChannelInput {
+    /// Every input
+    pub const ALL: [ChannelInput; 7] = [
+        ChannelInput::SendHandshake,
+        ChannelInput::ConfirmKey,
+        ChannelInput::Establish,
+        ChannelInput::BeginRekey,
+        ChannelInput::CompleteRekey,
+        ChannelInput::AbortRekey,
+        ChannelInput::Close,
+    ];
+}
+
+/// One applied transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChange {
+    /// State before the transition
+    pub from: ChannelState,
+    /// State after the transition
+    pub to: ChannelState,
+    /// Input that caused it
+    pub input: ChannelInput,
+    /// Unix time of the transition in milliseconds
+    pub at_ms: u64,
+}
+
+/// State machine of one channel with its transition history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelStateMachine {
+    peer_id: String,
+    state: ChannelState,
+    history: Vec<StateChange>,
+}
+
+impl ChannelStateMachine {
+    /// Machine for a new channel, starting in `Idle`
+    pub fn new(peer_id: &str) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            state: ChannelState::Idle,
+            history: Vec::new(),
+        }
+    }
+
+    /// Machine for a channel established elsewhere, such as one resumed from
+    /// a cluster store or imported from a session snapshot
+    pub fn resumed(peer_id: &str) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            state: ChannelState::Established,
+            history: Vec::new(),
+        }
+    }
+
+    /// Peer the channel leads to
+    pub fn peer_id(&self) -> &str {
+        &self.peer_id
+    }
+
+    /// Current state
+    pub fn state(&self) -> ChannelState {
+        self.state
+    }
+
+    /// Transitions applied so far, oldest first
+    pub fn history(&self) -> &[StateChange] {
+        &self.history
+    }
+
+    /// Apply an input, failing without changing state if it is illegal
+    pub fn apply(&mut self, input: ChannelInput) -> Result<StateChange> {
+        let to = self.state.next(input).ok_or_else(|| {
+            SecureCommsError::Validation(format!(
+                "Illegal channel transition for {}: {:?} in state {}",
+                self.peer_id, input, self.state
+            ))
+        })?;
+        let change = StateChange {
+            from: self.state,
+            to,
+            input,
+            at_ms: crate::expiry::now_ms(),
+        };
+        self.state = to;
+        self.history.push(change);
+        Ok(change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(state: ChannelState, input: ChannelInput) -> Option<ChannelState> {
+        use ChannelInput as I;
+        use ChannelState as S;
+        match (state, input) {
+            (S::Idle, I::SendHandshake) => Some(S::HandshakeSent),
+            (S::HandshakeSent, I::ConfirmKey) => Some(S::KeyConfirmed),
+            (S::KeyConfirmed, I::Establish) => Some(S::Established),
+            (S::Established, I::BeginRekey) => Some(S::Rekeying),
+            (S::Rekeying, I::CompleteRekey) => Some(S::Established),
+            (S::Rekeying, I::AbortRekey) => Some(S::Established),
+            (S::Idle, I::Close) => Some(S::Closed),
+            (S::HandshakeSent, I::Close) => Some(S::Closed),
+            (S::KeyConfirmed, I::Close) => Some(S::Closed),
+            (S::Established, I::Close) => Some(S::Closed),
+            (S::Rekeying, I::Close) => Some(S::Closed),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_transition_table_is_exhaustive() {
+        let mut legal = 0;
+        for state in ChannelState::ALL {
+            for input in ChannelInput::ALL {
+                assert_eq!(
+                    state.next(input),
+                    expected(state, input),
+                    "{:?} on {:?}",
+                    input,
+                    state
+                );
+                legal += usize::from(state.next(input).is_some());
+            }
+        }
+        assert_eq!(legal, 11);
+        // Closed is terminal
+        assert!(ChannelInput::ALL
+            .iter()
+            .all(|input| ChannelState::Closed.next(*input).is_none()));
+    }
+
+    #[test]
+    fn test_illegal_transition_leaves_state_unchanged() {
+        for state in ChannelState::ALL {
+            for input in ChannelInput::ALL {
+                if state.next(input).is_some() {
+                    continue;
+                }
+                let mut machine = ChannelStateMachine::new("peer");
+                machine.state = state;
+                let err = machine.apply(input).unwrap_err();
+                assert!(matches!(err, SecureCommsError::Validation(_)));
+                assert_eq!(machine.state(), state);
+                assert!(machine.history().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_records_history() {
+        let mut machine = ChannelStateMachine::new("peer_1");
+        for input in [
+            ChannelInput::SendHandshake,
+            ChannelInput::ConfirmKey,
+            ChannelInput::Establish,
+            ChannelInput::BeginRekey,
+            ChannelInput::AbortRekey,
+            ChannelInput::BeginRekey,
+            ChannelInput::CompleteRekey,
+            ChannelInput::Close,
+        ] {
+            machine.apply(input).unwrap();
+        }
+        assert_eq!(machine.state(), ChannelState::Closed);
+        let path: Vec<ChannelState> = machine.history().iter().map(|change| change.to).collect();
+        assert_eq!(
+            path,
+            vec![
+                ChannelState::HandshakeSent,
+                ChannelState::KeyConfirmed,
+                ChannelState::Established,
+                ChannelState::Rekeying,
+                ChannelState::Established,
+                ChannelState::Rekeying,
+                ChannelState::Established,
+                ChannelState::Closed,
+            ]
+        );
+        assert!(machine
+            .history()
+            .windows(2)
+            .all(|pair| pair[0].to == pair[1].from));
+
+        // A resumed channel skips the handshake but cannot re-run it
+        let mut resumed = ChannelStateMachine::resumed("peer_2");
+        assert!(resumed.state().carries_traffic());
+        assert!(resumed.apply(ChannelInput::SendHandshake).is_err());
+        assert_eq!(
+            resumed.apply(ChannelInput::Close).unwrap().from,
+            ChannelState::Established
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! # Channel Statistics - Per-Peer Traffic, Latency and QKD Quality
+//!
+//! Keeps running counters for every peer the client has opened a secure
+//! channel with: messages and bytes in each direction, rekeys, resend
+//! attempts, average send latency and a bounded history of QKD fidelity and
+//! quantum bit error rate (QBER) samples. Statistics are cumulative per peer
+//! and survive channel re-establishment, so dashboards can chart a peer over
+//! its whole lifetime.
+//!
+//! ## Data Sources
+//!
+//! - **Network Layer**: Message and byte counters, send latency, resend attempts
+//! - **Crypto Layer**: QKD fidelity and error rate of each key exchange and rekey
+//! - **Compression**: Ratio, CPU cost and the adaptive on/off decision
+//! - **Heartbeats**: Current adaptive interval, round trip time, jitter and loss
+//! - **Certification**: Latest CHSH test of the entanglement shared with the peer
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! client.establish_secure_channel("peer_1").await?;
+//! client.send_secure_message("peer_1", b"hello").await?;
+//!
+//! if let Some(stats) = client.get_channel_stats("peer_1") {
+//!     println!("{} sent, {:.2}ms avg", stats.messages_sent, stats.average_latency_ms);
+//! }
+//! for stats in client.get_all_peer_stats() {
+//!     println!("{}: {} rekeys", stats.peer_id, stats.rekey_count);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::chsh::ChshCertification;
+use crate::compression::{CompressionConfig, CompressionDecision, CompressionStats};
+use crate::heartbeat::{HeartbeatConfig, HeartbeatOutcome, HeartbeatStats};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Default number of QKD samples retained per peer
+pub const DEFAULT_QKD_HISTORY: usize = 32;
+
+/// QKD quality measured for one key exchange
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QkdSample {
+    /// Sample time (Unix seconds)
+    pub timestamp: u64,
+    /// QKD fidelity (0.0 - 1.0)
+    pub fidelity: f64,
+    /// Quantum bit error rate (0.0 - 1.0)
+    pub qber: f64,
+}
+
+/// Cumulative statistics for one peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelStats {
+    /// Remote peer
+    pub peer_id: String,
+    /// Most recent channel identifier
+    pub channel_id: String,
+    /// Messages sent
+    pub messages_sent: u64,
+    /// Messages received
+    pub messages_received: u64,
+    /// Payload bytes sent
+    pub bytes_sent: u64,
+    /// Payload bytes received
+    pub bytes_received: u64,
+    /// Session key rotations
+    pub rekey_count: u64,
+    /// Handshake and message resend attempts
+    pub retransmissions: u64,
+    /// Sends that failed in the network layer
+    pub send_failures: u64,
+    /// Messages dropped because their TTL expired
+    pub messages_expired: u64,
+    /// Mean send latency in milliseconds
+    pub average_latency_ms: f64,
+    /// QKD fidelity and QBER samples, oldest first
+    pub qkd_history: VecDeque<QkdSample>,
+    /// Time the first channel to the peer was opened (Unix seconds)
+    pub first_established_at: u64,
+    /// Time of the last recorded activity (Unix seconds)
+    pub last_activity: u64,
+    /// Payload compression measurements and whether it is switched on
+    #[serde(default)]
+    pub compression: CompressionStats,
+    /// Adaptive heartbeat interval and its round trip measurements
+    #[serde(default)]
+    pub heartbeat: HeartbeatStats,
+    /// Latest CHSH certification of the entanglement shared with the peer
+    #[serde(default)]
+    pub certification: Option<ChshCertification>,
+}
+
+impl ChannelStats {
+    /// Create empty statistics for peer
+    pub fn new(peer_id: &str, channel_id: &str) -> Self {
+        let now = chrono::Utc::now().timestamp() as u64;
+        Self {
+            peer_id: peer_id.to_string(),
+            channel_id: channel_id.to_string(),
+            messages_sent: 0,
+            messages_received: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+            rekey_count: 0,
+            retransmissions: 0,
+            send_failures: 0,
+            messages_expired: 0,
+            average_latency_ms: 0.0,
+            qkd_history: VecDeque::new(),
+            first_established_at: now,
+            last_activity: now,
+            compression: CompressionStats::default(),
+            heartbeat: HeartbeatStats::default(),
+            certification: None,
+        }
+    }
+
+    /// Latest QKD sample
+    pub fn latest_qkd(&self) -> Option<&QkdSample> {
+        self.qkd_history.back()
+    }
+
+    /// Mean QBER over the retained history
+    pub fn average_qber(&self) -> f64 {
+        if self.qkd_history.is_empty() {
+            return 0.0;
+        }
+        self.qkd_history.iter().map(|s| s.qber).sum::<f64>() / self.qkd_history.len() as f64
+    }
+
+    fn touch(&mut self) {
+        self.last_activity = chrono::Utc::now().timestamp() as u64;
+    }
+
+    fn record_sent(&mut self, bytes: usize, latency: Duration) {
+        self.messages_sent += 1;
+        self.bytes_sent += bytes as u64;
+        // Incremental mean avoids keeping every latency sample
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        self.average_latency_ms += (latency_ms - self.average_latency_ms) / self.messages_sent as f64;
+        self.touch();
+    }
+
+    fn record_received(&mut self, bytes: usize) {
+        self.messages_received += 1;
+        self.bytes_received += bytes as u64;
+        self.touch();
+    }
+
+    fn record_qkd(&mut self, fidelity: f64, qber: f64, history_len: usize) {
+        self.qkd_history.push_back(QkdSample {
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            fidelity,
+            qber,
+        });
+        while self.qkd_history.len() > history_len {
+            self.qkd_history.pop_front();
+        }
+    }
+}
+
+/// Statistics of all peers, maintained by the client
+#[derive(Debug, Clone)]
+pub struct ChannelStatsRegistry {
+    stats: HashMap<String, ChannelStats>,
+    history_len: usize,
+}
+
+impl ChannelStatsRegistry {
+    /// Create registry retaining `history_len` QKD samples per peer
+    pub fn new(history_len: usize) -> Self {
+        Self {
+            stats: HashMap::new(),
+            history_len: history_len.max(1),
+        }
+    }
+
+    /// Record a newly established channel and its key exchange quality
+    pub fn channel_opened(&mut self, peer_id: &str, channel_id: &str, fidelity: f64, qber: f64) {
+        let history_len = self.history_len;
+        let stats = self
+            .stats
+            .entry(peer_id.to_string())
+            .or_insert_with(|| ChannelStats::new(peer_id, channel_id));
+        stats.channel_id = channel_id.to_string();
+        // A certification covered the previous channel's entanglement only
+        stats.certification = None;
+        stats.record_qkd(fidelity, qber, history_len);
+        stats.touch();
+    }
+
+    /// Record a session key rotation
+    pub fn record_rekey(&mut self, peer_id: &str, fidelity: f64, qber: f64) {
+        let history_len = self.history_len;
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.rekey_count += 1;
+            stats.record_qkd(fidelity, qber, history_len);
+            stats.touch();
+        }
+    }
+
+    /// Record a delivered outbound message
+    pub fn record_sent(&mut self, peer_id: &str, bytes: usize, latency: Duration) {
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.record_sent(bytes, latency);
+        }
+    }
+
+    /// Record an outbound message the network layer refused
+    pub fn record_send_failure(&mut self, peer_id: &str) {
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.send_failures += 1;
+        }
+    }
+
+    /// Record a message dropped because its TTL expired
+    pub fn record_expired(&mut self, peer_id: &str) {
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.messages_expired += 1;
+        }
+    }
+
+    /// Record an accepted inbound message
+    pub fn record_received(&mut self, peer_id: &str, bytes: usize) {
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.record_received(bytes);
+        }
+    }
+
+    /// Whether to compress a `len` byte payload to a peer
+    ///
+    /// Peers without statistics are never compressed for.
+    pub fn plan_compression(
+        &mut self,
+        peer_id: &str,
+        config: &CompressionConfig,
+        len: usize,
+    ) -> bool {
+        match self.stats.get_mut(peer_id) {
+            Some(stats) => stats.compression.plan(config, len),
+            None => false,
+        }
+    }
+
+    /// Record an outbound compression
+    ///
+    /// Returns the channel's new decision if it changed.
+    pub fn record_compression(
+        &mut self,
+        peer_id: &str,
+        config: &CompressionConfig,
+        original: usize,
+        compressed: usize,
+        elapsed: Duration,
+    ) -> Option<CompressionDecision> {
+        self.stats.get_mut(peer_id).and_then(|stats| {
+            stats
+                .compression
+                .record_compression(config, original, compressed, elapsed)
+        })
+    }
+
+    /// Record an inbound decompression
+    pub fn record_decompression(&mut self, peer_id: &str, elapsed: Duration) {
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.compression.record_decompression(elapsed);
+        }
+    }
+
+    /// Record a heartbeat to a peer and schedule its next one
+    ///
+    /// Returns the peer's new interval if it changed.
+    pub fn record_heartbeat(
+        &mut self,
+        peer_id: &str,
+        config: &HeartbeatConfig,
+        outcome: HeartbeatOutcome,
+        now_ms: u64,
+    ) -> Option<u64> {
+        self.stats
+            .get_mut(peer_id)
+            .and_then(|stats| stats.heartbeat.record(config, outcome, now_ms))
+    }
+
+    /// Keep the latest CHSH certification of a peer's entanglement
+    pub fn record_certification(&mut self, certification: ChshCertification) {
+        if let Some(stats) = self.stats.get_mut(&certification.peer_id) {
+            stats.certification = Some(certification);
+        }
+    }
+
+    /// Drop a peer's CHSH certification when its channel closes
+    pub fn clear_certification(&mut self, peer_id: &str) {
+        if let Some(stats) = self.stats.get_mut(peer_id) {
+            stats.certification = None;
+        }
+    }
+
+    /// Record a resend attempt
+    ///
+    /// Handshake retries happen before the first channel exists, so the
+    /// peer entry is created on demand.
+    pub fn record_retransmission(&mut self, peer_id: &str) {
+        self.stats
+            .entry(peer_id.to_string())
+            .or_insert_with(|| ChannelStats::new(peer_id, ""))
+            .retransmissions += 1;
+    }
+
+    /// Statistics for peer
+    pub fn get(&self, peer_id: &str) -> Option<&ChannelStats> {
+        self.stats.get(peer_id)
+    }
+
+    /// Statistics of all peers, ordered by peer ID
+    pub fn all(&self) -> Vec<&ChannelStats> {
+        let mut all: Vec<&ChannelStats> = self.stats.values().collect();
+        all.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+        all
+    }
+
+    /// Forget the statistics of a peer
+    pub fn remove(&mut self, peer_id: &str) -> Option<ChannelStats> {
+        self.stats.remove(peer_id)
+    }
+}
+
+impl Default for ChannelStatsRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_QKD_HISTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traffic_counters_and_latency() {
+        let mut registry = ChannelStatsRegistry::default();
+        // Unknown peers are ignored until a channel is opened
+        registry.record_sent("peer_1", 10, Duration::from_millis(5));
+        assert!(registry.get("peer_1").is_none());
+
+        registry.channel_opened("peer_1", "channel_1", 0.99, 0.01);
+        registry.record_sent("peer_1", 100, Duration::from_millis(2));
+        registry.record_sent("peer_1", 50, Duration::from_millis(4));
+        registry.record_received("peer_1", 30);
+        registry.record_send_failure("peer_1");
+
+        let stats = registry.get("peer_1").unwrap();
+        assert_eq!(stats.messages_sent, 2);
+        assert_eq!(stats.bytes_sent, 150);
+        assert_eq!(stats.messages_received, 1);
+        assert_eq!(stats.bytes_received, 30);
+        assert_eq!(stats.send_failures, 1);
+        assert!((stats.average_latency_ms - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_qkd_history_bounded() {
+        let mut registry = ChannelStatsRegistry::new(3);
+        registry.channel_opened("peer_1", "channel_1", 0.90, 0.04);
+        for i in 0..4 {
+            registry.record_rekey("peer_1", 0.95, 0.01 * i as f64);
+        }
+        registry.channel_opened("peer_1", "channel_2", 0.99, 0.0);
+
+        let stats = registry.get("peer_1").unwrap();
+        assert_eq!(stats.rekey_count, 4);
+        assert_eq!(stats.channel_id, "channel_2");
+        assert_eq!(stats.qkd_history.len(), 3);
+        assert_eq!(stats.latest_qkd().unwrap().fidelity, 0.99);
+        assert!((stats.average_qber() - 0.05 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_retransmissions_and_ordering() {
+        let mut registry = ChannelStatsRegistry::default();
+        registry.record_retransmission("peer_b");
+        registry.record_retransmission("peer_b");
+        registry.channel_opened("peer_b", "channel_b", 1.0, 0.0);
+        registry.channel_opened("peer_a", "channel_a", 1.0, 0.0);
+
+        let all = registry.all();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].peer_id, "peer_a");
+        assert_eq!(all[1].retransmissions, 2);
+        assert!(registry.remove("peer_a").is_some());
+        assert_eq!(registry.all().len(), 1);
+    }
+}
@@ -0,0 +1,346 @@
+//! # CHSH - Bell Inequality Certification of Channels
+//!
+//! Certifies that the Bell pairs shared with a peer are genuinely entangled
+//! by running a CHSH experiment on them. Local hidden variable models keep
+//! the CHSH value S at or below 2; ideal |Φ+⟩ pairs reach 2√2 ≈ 2.83. A
+//! channel is marked quantum-certified only when the lower end of the
+//! confidence interval of S clears the configured threshold.
+//!
+//! ## Experiment
+//!
+//! Each round takes a fresh pair, picks one of Alice's and one of Bob's two
+//! settings from the QRNG and measures qubit 0 and qubit 1 along the
+//! matching Y-Z plane axes (`circuits::CHSH_ALICE_ANGLES` and
+//! `circuits::CHSH_BOB_ANGLES`). Per setting pair the correlation E is the
+//! fraction of agreeing outcomes minus the fraction of disagreeing ones, and
+//!
+//! S = E(0,0) + E(0,1) + E(1,0) − E(1,1)
+//!
+//! ## Confidence
+//!
+//! Each E is the mean of ±1 outcomes, so its variance is (1 − E²)/n for n
+//! rounds of that setting pair. The standard error of S combines the four,
+//! and the interval is S ± `confidence_z` standard errors.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::chsh::{certify, run_chsh, ChshConfig};
+//! use quantum_forge_secure_comms::crypto_protocols::QRNG;
+//! use quantum_forge_secure_comms::quantum_core::{QuantumGate, QuantumState};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = ChshConfig::default();
+//! let mut qrng = QRNG::from_seed([1; 32]);
+//! let mut next_pair = || {
+//!     let mut pair = QuantumState::new("pair".to_string(), 2);
+//!     pair.apply_gate(QuantumGate::Hadamard, &[0])?;
+//!     pair.apply_gate(QuantumGate::CNOT, &[0, 1])?;
+//!     Ok(pair)
+//! };
+//! let tally = run_chsh(&mut next_pair, config.rounds, &mut qrng)?;
+//! let result = certify("validator_2", "channel_1", &tally, &config);
+//! println!("S = {:.3} ± {:.3}", result.s_value, result.standard_error);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::circuits::{CHSH_ALICE_ANGLES, CHSH_BOB_ANGLES};
+use crate::crypto_protocols::QRNG;
+use crate::quantum_core::{MeasurementBasis, QuantumState};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::FRAC_PI_2;
+
+/// Largest CHSH value any local hidden variable model reaches
+pub const CLASSICAL_BOUND: f64 = 2.0;
+
+/// Largest CHSH value quantum mechanics allows, 2√2
+pub const TSIRELSON_BOUND: f64 = 2.0 * std::f64::consts::SQRT_2;
+
+/// Experiment size and certification threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChshConfig {
+    /// Pairs measured per certification
+    pub rounds: u32,
+    /// Standard errors on either side of S in the confidence interval
+    pub confidence_z: f64,
+    /// Value the interval's lower end must exceed to certify
+    pub threshold: f64,
+}
+
+impl Default for ChshConfig {
+    fn default() -> Self {
+        Self {
+            rounds: 1_000,
+            confidence_z: 3.0,
+            threshold: CLASSICAL_BOUND,
+        }
+    }
+}
+
+impl ChshConfig {
+    /// Check the experiment can certify at all
+    pub fn validate(&self) -> Result<()> {
+        if self.rounds < 40 {
+            return Err(SecureCommsError::Configuration(format!(
+                "CHSH needs at least 40 rounds, got {}",
+                self.rounds
+            )));
+        }
+        if !(self.confidence_z > 0.0 && self.confidence_z.is_finite()) {
+            return Err(SecureCommsError::Configuration(format!(
+                "CHSH confidence must be a positive number of standard errors, got {}",
+                self.confidence_z
+            )));
+        }
+        if !(self.threshold >= CLASSICAL_BOUND && self.threshold < TSIRELSON_BOUND) {
+            return Err(SecureCommsError::Configuration(format!(
+                "CHSH threshold must be in [2, 2√2), got {}",
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Rounds and agreeing outcomes of each setting pair
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChshTally {
+    /// Rounds by `[alice][bob]` setting
+    pub trials: [[u64; 2]; 2],
+    /// Rounds whose outcomes agreed, by `[alice][bob]` setting
+    pub agreements: [[u64; 2]; 2],
+}
+
+impl ChshTally {
+    /// Record one round
+    pub fn record(&mut self, alice: usize, bob: usize, alice_bit: u8, bob_bit: u8) {
+        self.trials[alice][bob] += 1;
+        if alice_bit == bob_bit {
+            self.agreements[alice][bob] += 1;
+        }
+    }
+
+    /// Rounds recorded
+    pub fn rounds(&self) -> u64 {
+        self.trials.iter().flatten().sum()
+    }
+
+    /// Estimated correlation E of a setting pair, 0 before any round
+    pub fn correlation(&self, alice: usize, bob: usize) -> f64 {
+        let trials = self.trials[alice][bob];
+        if trials == 0 {
+            return 0.0;
+        }
+        (2.0 * self.agreements[alice][bob] as f64 - trials as f64) / trials as f64
+    }
+
+    /// Estimated S = E(0,0) + E(0,1) + E(1,0) − E(1,1)
+    pub fn s_value(&self) -> f64 {
+        self.correlation(0, 0) + self.correlation(0, 1) + self.correlation(1, 0)
+            - self.correlation(1, 1)
+    }
+
+    /// Standard error of `s_value`; infinite while a setting pair has no rounds
+    pub fn standard_error(&self) -> f64 {
+        let mut variance = 0.0;
+        for alice in 0..2 {
+            for bob in 0..2 {
+                let trials = self.trials[alice][bob];
+                if trials == 0 {
+                    return f64::INFINITY;
+                }
+                let e = self.correlation(alice, bob);
+                variance += (1.0 - e * e) / trials as f64;
+            }
+        }
+        variance.sqrt()
+    }
+}
+
+/// Outcome of certifying a peer's entanglement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChshCertification {
+    pub peer_id: String,
+    /// Channel the certification belongs to
+    pub channel_id: String,
+    pub rounds: u64,
+    /// Estimated correlation by `[alice][bob]` setting
+    pub correlations: [[f64; 2]; 2],
+    pub s_value: f64,
+    pub standard_error: f64,
+    /// Lower and upper end of S ± `confidence_z` standard errors
+    pub confidence_interval: (f64, f64),
+    pub threshold: f64,
+    /// Whether the interval's lower end exceeds the threshold
+    pub certified: bool,
+    /// Pairs taken from the entanglement pool rather than prepared fresh
+    pub pooled_pairs: u64,
+    /// Time of the experiment (Unix milliseconds)
+    pub certified_at_ms: u64,
+}
+
+/// Run `rounds` CHSH rounds on the pairs `next_pair` provides
+///
+/// Each pair's qubit 0 is Alice's half and qubit 1 Bob's.
+pub fn run_chsh(
+    next_pair: &mut dyn FnMut() -> Result<QuantumState>,
+    rounds: u32,
+    qrng: &mut QRNG,
+) -> Result<ChshTally> {
+    let mut tally = ChshTally::default();
+    for round in 0..rounds {
+        let mut pair = next_pair()?;
+        if pair.qubit_count != 2 {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "CHSH needs 2-qubit pairs, got {} qubits",
+                pair.qubit_count
+            )));
+        }
+        let settings = qrng.gen_range(0..4) as usize;
+        let (alice, bob) = (settings >> 1, settings & 1);
+        let bases = [
+            plane_basis(CHSH_ALICE_ANGLES[alice]),
+            plane_basis(CHSH_BOB_ANGLES[bob]),
+        ];
+        // Outcome bits are MSB first, so Bob's qubit 1 comes first
+        let outcome = pair.measure_in_bases(format!("chsh_{}", round), &bases, qrng)?;
+        tally.record(alice, bob, outcome[1], outcome[0]);
+    }
+    Ok(tally)
+}
+
+/// Judge a tally against the configured threshold
+pub fn certify(
+    peer_id: &str,
+    channel_id: &str,
+    tally: &ChshTally,
+    config: &ChshConfig,
+) -> ChshCertification {
+    let s_value = tally.s_value();
+    let standard_error = tally.standard_error();
+    let margin = config.confidence_z * standard_error;
+    let confidence_interval = (s_value - margin, s_value + margin);
+    ChshCertification {
+        peer_id: peer_id.to_string(),
+        channel_id: channel_id.to_string(),
+        rounds: tally.rounds(),
+        correlations: [
+            [tally.correlation(0, 0), tally.correlation(0, 1)],
+            [tally.correlation(1, 0), tally.correlation(1, 1)],
+        ],
+        s_value,
+        standard_error,
+        confidence_interval,
+        threshold: config.threshold,
+        certified: confidence_interval.0 > config.threshold,
+        pooled_pairs: 0,
+        certified_at_ms: crate::expiry::now_ms(),
+    }
+}
+
+/// Basis along the Y-Z plane axis `angle` radians from Z towards Y
+fn plane_basis(angle: f64) -> MeasurementBasis {
+    if angle == 0.0 {
+        return MeasurementBasis::Z;
+    }
+    MeasurementBasis::Angle {
+        theta: angle.abs(),
+        phi: FRAC_PI_2.copysign(angle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum_core::QuantumGate;
+
+    fn bell_pair() -> Result<QuantumState> {
+        let mut pair = QuantumState::new("pair".to_string(), 2);
+        pair.apply_gate(QuantumGate::Hadamard, &[0])?;
+        pair.apply_gate(QuantumGate::CNOT, &[0, 1])?;
+        Ok(pair)
+    }
+
+    #[test]
+    fn test_bell_pairs_violate_chsh() {
+        let config = ChshConfig::default();
+        let mut qrng = QRNG::from_seed([11; 32]);
+        let tally = run_chsh(&mut bell_pair, 4_000, &mut qrng).unwrap();
+        assert_eq!(tally.rounds(), 4_000);
+        assert!(tally.trials.iter().flatten().all(|&n| n > 800));
+
+        let result = certify("peer", "channel", &tally, &config);
+        assert!((result.s_value - TSIRELSON_BOUND).abs() < 0.15);
+        assert!(result.certified);
+        assert!(result.confidence_interval.0 > CLASSICAL_BOUND);
+        assert!(result.confidence_interval.1 > result.s_value);
+        for alice in 0..2 {
+            for bob in 0..2 {
+                let ideal = crate::circuits::bell_test_correlation(alice, bob);
+                assert!((result.correlations[alice][bob] - ideal).abs() < 0.12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_separable_pairs_are_not_certified() {
+        let config = ChshConfig::default();
+        let mut qrng = QRNG::from_seed([12; 32]);
+        // Perfectly correlated in Z but not entangled
+        let mut classical = || {
+            let mut pair = QuantumState::new("pair".to_string(), 2);
+            pair.apply_gate(QuantumGate::PauliX, &[0])?;
+            pair.apply_gate(QuantumGate::PauliX, &[1])?;
+            Ok(pair)
+        };
+        let tally = run_chsh(&mut classical, 4_000, &mut qrng).unwrap();
+        let result = certify("peer", "channel", &tally, &config);
+        assert!(result.s_value <= CLASSICAL_BOUND + 0.15);
+        assert!(!result.certified);
+
+        let mut single = || Ok(QuantumState::new("single".to_string(), 1));
+        assert!(run_chsh(&mut single, 10, &mut qrng).is_err());
+    }
+
+    #[test]
+    fn test_tally_statistics_and_validation() {
+        let mut tally = ChshTally::default();
+        assert_eq!(tally.standard_error(), f64::INFINITY);
+        for (alice, bob) in [(0, 0), (0, 1), (1, 0)] {
+            for _ in 0..9 {
+                tally.record(alice, bob, 1, 1);
+            }
+            tally.record(alice, bob, 0, 1);
+        }
+        for _ in 0..10 {
+            tally.record(1, 1, 0, 1);
+        }
+        assert!((tally.correlation(0, 0) - 0.8).abs() < 1e-12);
+        assert_eq!(tally.correlation(1, 1), -1.0);
+        assert!((tally.s_value() - 3.4).abs() < 1e-12);
+        assert!((tally.standard_error() - (3.0 * 0.36 / 10.0_f64).sqrt()).abs() < 1e-12);
+
+        let config = ChshConfig::default();
+        let result = certify("peer", "channel", &ChshTally::default(), &config);
+        assert!(!result.certified);
+        assert!(config.validate().is_ok());
+        for invalid in [
+            ChshConfig {
+                rounds: 10,
+                ..Default::default()
+            },
+            ChshConfig {
+                threshold: 1.5,
+                ..Default::default()
+            },
+            ChshConfig {
+                confidence_z: 0.0,
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err());
+        }
+    }
+}
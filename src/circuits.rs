@@ -0,0 +1,396 @@
+//! # Circuits - Ready-Made Circuits for Standard Protocols
+//!
+//! Builders for the circuits quantum protocols keep needing, so callers do
+//! not assemble gate lists by hand. Every builder validates its parameters
+//! and returns a [`QuantumCircuit`] that can be executed directly, added to
+//! a core with `QuantumCore::add_circuit` or transpiled for a backend.
+//!
+//! ## Library
+//!
+//! - **BB84**: [`bb84_preparation`] encodes bits in the Z or X basis and
+//!   [`bb84_measurement`] rotates X-basis qubits for a Z measurement
+//! - **Bell Pairs and Tests**: [`bell_pair`] and [`bell_test`], which adds
+//!   the CHSH measurement rotations of one Alice/Bob setting pair
+//! - **GHZ**: [`ghz`] entangles any number of qubits
+//! - **QFT**: [`qft`] on up to [`MAX_QFT_QUBITS`] qubits
+//! - **Random Circuits**: [`random_circuit`] builds seeded layers of random
+//!   single-qubit gates and alternating CNOT bricks, for sampling benchmarks
+//!
+//! ## Gate Set
+//!
+//! Circuits use only the core's gates (H, X, Y, Z, S, T and CNOT). Rotations
+//! the gate set lacks are composed: T† as T·S·Z, X rotations as H·P·H and the
+//! controlled S of the QFT from two CNOTs and T gates. Nothing finer than a
+//! π/4 phase can be composed, so [`qft`] is exact on one or two qubits and
+//! beyond that drops the controlled rotations below π/2, as in Coppersmith's
+//! approximate QFT.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::circuits;
+//! use quantum_forge_secure_comms::quantum_core::{MeasurementBasis, QuantumState};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let bits = [true, false, true, true];
+//! let bases = [
+//!     MeasurementBasis::Z,
+//!     MeasurementBasis::X,
+//!     MeasurementBasis::X,
+//!     MeasurementBasis::Z,
+//! ];
+//! let prepare = circuits::bb84_preparation(&bits, &bases)?;
+//! let mut state = QuantumState::new(prepare.id.clone(), prepare.qubit_count);
+//! prepare.execute(&mut state)?;
+//! circuits::bb84_measurement(&bases)?.execute(&mut state)?;
+//!
+//! let ghz = circuits::ghz(5)?;
+//! println!("{} has {} gates", ghz.id, ghz.operations.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::quantum_core::{MeasurementBasis, QuantumCircuit, QuantumGate};
+use crate::{Result, SecureCommsError};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+/// Largest register [`qft`] builds
+pub const MAX_QFT_QUBITS: u32 = 8;
+
+/// Measurement angles of Alice's two CHSH settings, in the Y-Z plane
+pub const CHSH_ALICE_ANGLES: [f64; 2] = [0.0, FRAC_PI_2];
+
+/// Measurement angles of Bob's two CHSH settings, in the Y-Z plane
+pub const CHSH_BOB_ANGLES: [f64; 2] = [-FRAC_PI_4, FRAC_PI_4];
+
+/// Single-qubit gates drawn by [`random_circuit`]
+const RANDOM_GATES: [QuantumGate; 4] = [
+    QuantumGate::Hadamard,
+    QuantumGate::TGate,
+    QuantumGate::SGate,
+    QuantumGate::PauliY,
+];
+
+/// Encode `bits` in `bases`, one qubit each
+///
+/// A 1 bit flips its qubit; an X basis then applies a Hadamard, giving the
+/// states |0⟩, |1⟩, |+⟩ and |−⟩.
+pub fn bb84_preparation(bits: &[bool], bases: &[MeasurementBasis]) -> Result<QuantumCircuit> {
+    if bits.len() != bases.len() {
+        return Err(SecureCommsError::Validation(format!(
+            "BB84 preparation needs one basis per bit, got {} bits and {} bases",
+            bits.len(),
+            bases.len()
+        )));
+    }
+    let mut circuit =
+        QuantumCircuit::new(format!("bb84_prepare_{}", bits.len()), qubits(bits.len())?);
+    for (qubit, (&bit, basis)) in bits.iter().zip(bases).enumerate() {
+        let qubit = qubit as u32;
+        if bit {
+            circuit.add_gate(QuantumGate::PauliX, vec![qubit])?;
+        }
+        if bb84_diagonal(basis)? {
+            circuit.add_gate(QuantumGate::Hadamard, vec![qubit])?;
+        }
+    }
+    Ok(circuit)
+}
+
+/// Rotate qubits measured in the X basis so a Z measurement reads them
+pub fn bb84_measurement(bases: &[MeasurementBasis]) -> Result<QuantumCircuit> {
+    let mut circuit = QuantumCircuit::new(
+        format!("bb84_measure_{}", bases.len()),
+        qubits(bases.len())?,
+    );
+    for (qubit, basis) in bases.iter().enumerate() {
+        if bb84_diagonal(basis)? {
+            circuit.add_gate(QuantumGate::Hadamard, vec![qubit as u32])?;
+        }
+    }
+    Ok(circuit)
+}
+
+/// Entangle qubits 0 and 1 in (|00⟩ + |11⟩)/√2
+pub fn bell_pair() -> QuantumCircuit {
+    let mut circuit = QuantumCircuit::new("bell_pair".to_string(), 2);
+    push(&mut circuit, QuantumGate::Hadamard, &[0]);
+    push(&mut circuit, QuantumGate::CNOT, &[0, 1]);
+    circuit
+}
+
+/// Bell pair rotated for one CHSH setting pair, ready for a Z measurement
+///
+/// Alice (qubit 0) and Bob (qubit 1) measure along the Y-Z plane axes
+/// `CHSH_ALICE_ANGLES[alice]` and `CHSH_BOB_ANGLES[bob]`; each qubit gets
+/// Rx(angle) before the measurement.
+pub fn bell_test(alice: usize, bob: usize) -> Result<QuantumCircuit> {
+    if alice > 1 || bob > 1 {
+        return Err(SecureCommsError::Validation(format!(
+            "CHSH settings are 0 or 1, got Alice {} and Bob {}",
+            alice, bob
+        )));
+    }
+    let mut circuit = bell_pair();
+    circuit.id = format!("bell_test_{}{}", alice, bob);
+    if alice == 1 {
+        // Rx(π/2)
+        x_rotation(&mut circuit, 0, &[QuantumGate::SGate]);
+    }
+    if bob == 0 {
+        // Rx(−π/4), with T† = T·S·Z
+        x_rotation(
+            &mut circuit,
+            1,
+            &[QuantumGate::TGate, QuantumGate::SGate, QuantumGate::PauliZ],
+        );
+    } else {
+        // Rx(π/4)
+        x_rotation(&mut circuit, 1, &[QuantumGate::TGate]);
+    }
+    Ok(circuit)
+}
+
+/// Ideal correlation ⟨A·B⟩ of [`bell_test`] outcomes for a setting pair
+///
+/// cos(α + β); the four settings together reach the Tsirelson bound 2√2.
+pub fn bell_test_correlation(alice: usize, bob: usize) -> f64 {
+    (CHSH_ALICE_ANGLES[alice] + CHSH_BOB_ANGLES[bob]).cos()
+}
+
+/// Entangle `qubit_count` qubits in (|0…0⟩ + |1…1⟩)/√2
+pub fn ghz(qubit_count: u32) -> Result<QuantumCircuit> {
+    if qubit_count < 2 {
+        return Err(SecureCommsError::Validation(format!(
+            "GHZ state needs at least 2 qubits, got {}",
+            qubit_count
+        )));
+    }
+    let mut circuit = QuantumCircuit::new(format!("ghz_{}", qubit_count), qubit_count);
+    push(&mut circuit, QuantumGate::Hadamard, &[0]);
+    for target in 1..qubit_count {
+        push(&mut circuit, QuantumGate::CNOT, &[0, target]);
+    }
+    Ok(circuit)
+}
+
+/// Quantum Fourier transform of `qubit_count` qubits, qubit 0 least significant
+///
+/// Exact on up to two qubits; larger registers keep only the controlled S
+/// between neighbouring qubits (see the module docs).
+pub fn qft(qubit_count: u32) -> Result<QuantumCircuit> {
+    if qubit_count == 0 || qubit_count > MAX_QFT_QUBITS {
+        return Err(SecureCommsError::Validation(format!(
+            "QFT is built for 1 to {} qubits, got {}",
+            MAX_QFT_QUBITS, qubit_count
+        )));
+    }
+    let mut circuit = QuantumCircuit::new(format!("qft_{}", qubit_count), qubit_count);
+    for target in (0..qubit_count).rev() {
+        push(&mut circuit, QuantumGate::Hadamard, &[target]);
+        if target > 0 {
+            controlled_s(&mut circuit, target - 1, target);
+        }
+    }
+    for low in 0..qubit_count / 2 {
+        swap(&mut circuit, low, qubit_count - 1 - low);
+    }
+    Ok(circuit)
+}
+
+/// Seeded random circuit of `depth` layers for sampling benchmarks
+///
+/// Each layer gives every qubit a random single-qubit gate, never the one
+/// it had in the previous layer, followed by CNOTs on alternating pairs
+/// (0-1, 2-3, … then 1-2, 3-4, …). The same seed builds the same circuit.
+pub fn random_circuit(qubit_count: u32, depth: u32, seed: u64) -> Result<QuantumCircuit> {
+    if qubit_count == 0 || depth == 0 {
+        return Err(SecureCommsError::Validation(
+            "Random circuit needs at least one qubit and one layer".to_string(),
+        ));
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut circuit = QuantumCircuit::new(
+        format!("random_{}x{}_{}", qubit_count, depth, seed),
+        qubit_count,
+    );
+    let mut previous: Vec<Option<QuantumGate>> = vec![None; qubit_count as usize];
+    for layer in 0..depth {
+        for qubit in 0..qubit_count {
+            let gate = loop {
+                let gate = RANDOM_GATES[rng.gen_range(0..RANDOM_GATES.len())];
+                if previous[qubit as usize] != Some(gate) {
+                    break gate;
+                }
+            };
+            previous[qubit as usize] = Some(gate);
+            push(&mut circuit, gate, &[qubit]);
+        }
+        let mut control = layer % 2;
+        while control + 1 < qubit_count {
+            push(&mut circuit, QuantumGate::CNOT, &[control, control + 1]);
+            control += 2;
+        }
+    }
+    Ok(circuit)
+}
+
+/// Qubit count of a circuit over `len` items
+fn qubits(len: usize) -> Result<u32> {
+    if len == 0 {
+        return Err(SecureCommsError::Validation(
+            "Circuit needs at least one qubit".to_string(),
+        ));
+    }
+    u32::try_from(len)
+        .map_err(|_| SecureCommsError::Validation(format!("Too many qubits: {}", len)))
+}
+
+/// Whether a BB84 basis is the diagonal one
+fn bb84_diagonal(basis: &MeasurementBasis) -> Result<bool> {
+    match basis {
+        MeasurementBasis::Z => Ok(false),
+        MeasurementBasis::X => Ok(true),
+        other => Err(SecureCommsError::Validation(format!(
+            "BB84 uses the Z and X bases, got {:?}",
+            other
+        ))),
+    }
+}
+
+/// Add a gate on qubits the builder has already range-checked
+fn push(circuit: &mut QuantumCircuit, gate: QuantumGate, qubits: &[u32]) {
+    circuit
+        .add_gate(gate, qubits.to_vec())
+        .expect("library circuits address their own qubits");
+}
+
+/// X rotation by the Z phase `phase` composes, as H·phase·H
+fn x_rotation(circuit: &mut QuantumCircuit, qubit: u32, phase: &[QuantumGate]) {
+    push(circuit, QuantumGate::Hadamard, &[qubit]);
+    for &gate in phase {
+        push(circuit, gate, &[qubit]);
+    }
+    push(circuit, QuantumGate::Hadamard, &[qubit]);
+}
+
+/// Controlled S as T(c)·T(t)·CNOT·T†(t)·CNOT
+fn controlled_s(circuit: &mut QuantumCircuit, control: u32, target: u32) {
+    push(circuit, QuantumGate::TGate, &[control]);
+    push(circuit, QuantumGate::TGate, &[target]);
+    push(circuit, QuantumGate::CNOT, &[control, target]);
+    for gate in [QuantumGate::TGate, QuantumGate::SGate, QuantumGate::PauliZ] {
+        push(circuit, gate, &[target]);
+    }
+    push(circuit, QuantumGate::CNOT, &[control, target]);
+}
+
+/// Swap two qubits with three CNOTs
+fn swap(circuit: &mut QuantumCircuit, a: u32, b: u32) {
+    push(circuit, QuantumGate::CNOT, &[a, b]);
+    push(circuit, QuantumGate::CNOT, &[b, a]);
+    push(circuit, QuantumGate::CNOT, &[a, b]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum_core::QuantumState;
+
+    fn run(circuit: &QuantumCircuit) -> QuantumState {
+        let mut state = QuantumState::new(circuit.id.clone(), circuit.qubit_count);
+        circuit.execute(&mut state).unwrap();
+        state
+    }
+
+    #[test]
+    fn test_bb84_round_trip_and_entangled_states() {
+        let bits = [true, false, true, false];
+        let bases = [
+            MeasurementBasis::Z,
+            MeasurementBasis::X,
+            MeasurementBasis::X,
+            MeasurementBasis::Z,
+        ];
+        let prepare = bb84_preparation(&bits, &bases).unwrap();
+        assert_eq!(
+            prepare.operations,
+            vec![
+                (QuantumGate::PauliX, vec![0]),
+                (QuantumGate::Hadamard, vec![1]),
+                (QuantumGate::PauliX, vec![2]),
+                (QuantumGate::Hadamard, vec![2]),
+            ]
+        );
+        assert_eq!(
+            bb84_measurement(&bases).unwrap().operations,
+            vec![
+                (QuantumGate::Hadamard, vec![1]),
+                (QuantumGate::Hadamard, vec![2]),
+            ]
+        );
+        // Z-basis bits are written straight into the register
+        let prepare = bb84_preparation(&bits, &[MeasurementBasis::Z; 4]).unwrap();
+        assert!((run(&prepare).amplitudes[0b0101].abs() - 1.0).abs() < 1e-9);
+
+        let state = run(&ghz(4).unwrap());
+        let half = 1.0 / 2.0_f64.sqrt();
+        assert!((state.amplitudes[0].abs() - half).abs() < 1e-9);
+        assert!((state.amplitudes[0b1111].abs() - half).abs() < 1e-9);
+        assert_eq!(run(&bell_pair()).amplitudes[0b10], 0.0);
+
+        assert!(bb84_preparation(&bits, &bases[..3]).is_err());
+        assert!(bb84_preparation(&[true], &[MeasurementBasis::Y]).is_err());
+        assert!(bb84_measurement(&[]).is_err());
+        assert!(ghz(1).is_err());
+    }
+
+    #[test]
+    fn test_bell_test_settings_reach_tsirelson_bound() {
+        let chsh =
+            bell_test_correlation(0, 0) + bell_test_correlation(0, 1) + bell_test_correlation(1, 0)
+                - bell_test_correlation(1, 1);
+        assert!((chsh - 2.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+
+        let gates = |alice, bob| bell_test(alice, bob).unwrap().operations.len();
+        assert_eq!(gates(0, 1), 2 + 3);
+        assert_eq!(gates(1, 0), 2 + 3 + 5);
+        assert_eq!(bell_test(1, 1).unwrap().id, "bell_test_11");
+        assert!(bell_test(2, 0).is_err());
+    }
+
+    #[test]
+    fn test_qft_and_random_circuits() {
+        // One qubit is a Hadamard; two add a controlled S and a swap
+        assert_eq!(
+            qft(1).unwrap().operations,
+            vec![(QuantumGate::Hadamard, vec![0])]
+        );
+        let two = qft(2).unwrap();
+        assert_eq!(two.operations.len(), 1 + 7 + 1 + 3);
+        assert_eq!(two.operations[0], (QuantumGate::Hadamard, vec![1]));
+        assert_eq!(qft(5).unwrap().qubit_count, 5);
+        assert!(qft(0).is_err());
+        assert!(qft(MAX_QFT_QUBITS + 1).is_err());
+
+        let circuit = random_circuit(5, 6, 42).unwrap();
+        assert_eq!(
+            circuit.operations,
+            random_circuit(5, 6, 42).unwrap().operations
+        );
+        assert_ne!(
+            circuit.operations,
+            random_circuit(5, 6, 43).unwrap().operations
+        );
+        let cnots = circuit
+            .operations
+            .iter()
+            .filter(|(gate, _)| *gate == QuantumGate::CNOT)
+            .count();
+        assert_eq!(cnots, 3 * 2 + 3 * 2);
+        assert_eq!(circuit.operations.len(), 6 * 5 + cnots);
+        assert!(random_circuit(0, 3, 1).is_err());
+    }
+}
@@ -0,0 +1,541 @@
+//! # Clock Synchronization - Skew Detection and Secure Time Sync
+//!
+//! Message timestamps, key lifetimes and replay windows all assume that peer
+//! clocks roughly agree. This module estimates per-peer clock skew from
+//! authenticated handshake timestamps, raises warnings and alerts when skew
+//! exceeds configured thresholds, and provides an optional NTP-style secure
+//! time sync exchange authenticated with the channel session key.
+//!
+//! ## Skew Estimation
+//!
+//! - **Handshake Samples**: One-way estimate from the peer's signed handshake timestamp
+//! - **Secure Sync**: Four-timestamp exchange yielding offset and round-trip time
+//! - **Robust Aggregation**: Per-peer skew is the median of recent samples
+//! - **Fleet Offset**: Median skew across peers corrects the local clock view
+//!
+//! ## Time Windows
+//!
+//! Expiry and replay checks use `is_within_window`, which compensates for the
+//! estimated skew of the sending peer instead of comparing raw wall clocks.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::clock_sync::{ClockSkewMonitor, ClockSyncConfig};
+//!
+//! let session_key = vec![7u8; 32];
+//! let mut local = ClockSkewMonitor::new(ClockSyncConfig::default());
+//! let remote = ClockSkewMonitor::new(ClockSyncConfig::default());
+//!
+//! let request = local.create_sync_request("validator_2");
+//! let response = remote.respond_to_sync(&request, &session_key);
+//! let sample = local.complete_sync("validator_2", &response, &session_key).unwrap();
+//! println!("Offset {}ms, RTT {}ms", sample.offset_ms, sample.rtt_ms);
+//! ```
+
+use crate::constant_time::ct_eq;
+use crate::logging::{log_security, log_warn, LogCategory};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Clock synchronization configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockSyncConfig {
+    /// Skew above which a warning is logged
+    pub warn_threshold_ms: u64,
+    /// Skew above which a security alert is raised
+    pub alert_threshold_ms: u64,
+    /// Samples retained per peer
+    pub max_samples: usize,
+    /// Sync exchanges with a longer round trip are discarded
+    pub max_rtt_ms: u64,
+    /// Apply the fleet offset to `synchronized_now_ms`
+    pub secure_sync_enabled: bool,
+}
+
+impl Default for ClockSyncConfig {
+    fn default() -> Self {
+        Self {
+            warn_threshold_ms: 500,
+            alert_threshold_ms: 5_000,
+            max_samples: 16,
+            max_rtt_ms: 2_000,
+            secure_sync_enabled: false,
+        }
+    }
+}
+
+/// Severity of a peer's clock skew
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkewStatus {
+    /// Skew within the warning threshold
+    Normal,
+    /// Skew above the warning threshold
+    Warning,
+    /// Skew above the alert threshold
+    Critical,
+}
+
+/// Source of a skew sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SampleSource {
+    /// One-way estimate from an authenticated handshake
+    Handshake,
+    /// Four-timestamp secure sync exchange
+    SecureSync,
+}
+
+/// Single clock offset observation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkewSample {
+    /// Peer the sample refers to
+    pub peer_id: String,
+    /// Remote clock minus local clock in milliseconds
+    pub offset_ms: i64,
+    /// Round-trip time in milliseconds (0 if unknown)
+    pub rtt_ms: u64,
+    /// How the sample was obtained
+    pub source: SampleSource,
+    /// Local time the sample was recorded (ms)
+    pub recorded_at_ms: u64,
+}
+
+/// Secure time sync request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSyncRequest {
+    /// Random nonce binding request and response
+    pub nonce: Vec<u8>,
+    /// Requester transmit time (t1)
+    pub origin_ms: u64,
+}
+
+/// Secure time sync response authenticated with the session key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSyncResponse {
+    /// Nonce echoed from the request
+    pub nonce: Vec<u8>,
+    /// Requester transmit time echoed (t1)
+    pub origin_ms: u64,
+    /// Responder receive time (t2)
+    pub receive_ms: u64,
+    /// Responder transmit time (t3)
+    pub transmit_ms: u64,
+    /// SHA-3 MAC over all fields keyed with the session key
+    pub mac: Vec<u8>,
+}
+
+impl TimeSyncResponse {
+    /// Compute the response MAC
+    pub fn compute_mac(
+        session_key: &[u8],
+        nonce: &[u8],
+        origin_ms: u64,
+        receive_ms: u64,
+        transmit_ms: u64,
+    ) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"time_sync_v1");
+        hasher.update(session_key);
+        hasher.update(nonce);
+        hasher.update(origin_ms.to_le_bytes());
+        hasher.update(receive_ms.to_le_bytes());
+        hasher.update(transmit_ms.to_le_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Verify the MAC in constant time
+    pub fn verify(&self, session_key: &[u8]) -> bool {
+        let expected = Self::compute_mac(
+            session_key,
+            &self.nonce,
+            self.origin_ms,
+            self.receive_ms,
+            self.transmit_ms,
+        );
+        ct_eq(&expected, &self.mac)
+    }
+}
+
+/// Per-peer and fleet-wide clock skew tracking
+#[derive(Debug, Clone, Default)]
+pub struct ClockSkewMonitor {
+    /// Synchronization configuration
+    config: ClockSyncConfig,
+    /// Recent samples per peer
+    samples: HashMap<String, VecDeque<SkewSample>>,
+    /// Outstanding sync requests (peer -> request)
+    pending: HashMap<String, TimeSyncRequest>,
+    /// Alerts raised so far
+    alerts_raised: u64,
+}
+
+impl ClockSkewMonitor {
+    /// Create new clock skew monitor
+    pub fn new(config: ClockSyncConfig) -> Self {
+        Self {
+            config,
+            samples: HashMap::new(),
+            pending: HashMap::new(),
+            alerts_raised: 0,
+        }
+    }
+
+    /// Get synchronization configuration
+    pub fn get_config(&self) -> &ClockSyncConfig {
+        &self.config
+    }
+
+    /// Current local wall clock in milliseconds
+    pub fn local_now_ms() -> u64 {
+        chrono::Utc::now().timestamp_millis().max(0) as u64
+    }
+
+    /// Record a skew sample from an authenticated handshake timestamp
+    ///
+    /// Callers must only pass timestamps covered by a verified handshake
+    /// signature. `rtt_ms` is used to approximate the one-way delay.
+    pub fn record_handshake_timestamp(
+        &mut self,
+        peer_id: &str,
+        remote_timestamp_ms: u64,
+        rtt_ms: u64,
+    ) -> SkewStatus {
+        let now = Self::local_now_ms();
+        let sent_local_estimate = now as i64 - (rtt_ms / 2) as i64;
+        let sample = SkewSample {
+            peer_id: peer_id.to_string(),
+            offset_ms: remote_timestamp_ms as i64 - sent_local_estimate,
+            rtt_ms,
+            source: SampleSource::Handshake,
+            recorded_at_ms: now,
+        };
+        self.record_sample(sample)
+    }
+
+    /// Create a secure time sync request for a peer
+    pub fn create_sync_request(&mut self, peer_id: &str) -> TimeSyncRequest {
+        use rand::RngCore;
+        let mut nonce = vec![0u8; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let request = TimeSyncRequest {
+            nonce,
+            origin_ms: Self::local_now_ms(),
+        };
+        self.pending.insert(peer_id.to_string(), request.clone());
+        request
+    }
+
+    /// Answer a peer's secure time sync request
+    pub fn respond_to_sync(&self, request: &TimeSyncRequest, session_key: &[u8]) -> TimeSyncResponse {
+        let receive_ms = Self::local_now_ms();
+        let transmit_ms = Self::local_now_ms();
+        TimeSyncResponse {
+            nonce: request.nonce.clone(),
+            origin_ms: request.origin_ms,
+            receive_ms,
+            transmit_ms,
+            mac: TimeSyncResponse::compute_mac(
+                session_key,
+                &request.nonce,
+                request.origin_ms,
+                receive_ms,
+                transmit_ms,
+            ),
+        }
+    }
+
+    /// Complete a secure time sync exchange
+    pub fn complete_sync(
+        &mut self,
+        peer_id: &str,
+        response: &TimeSyncResponse,
+        session_key: &[u8],
+    ) -> Result<SkewSample> {
+        self.complete_sync_at(peer_id, response, session_key, Self::local_now_ms())
+    }
+
+    /// Complete a secure time sync exchange with an explicit receive time (t4)
+    pub fn complete_sync_at(
+        &mut self,
+        peer_id: &str,
+        response: &TimeSyncResponse,
+        session_key: &[u8],
+        received_ms: u64,
+    ) -> Result<SkewSample> {
+        let request = self.pending.get(peer_id).cloned().ok_or_else(|| {
+            SecureCommsError::Validation(format!("No pending time sync request for {}", peer_id))
+        })?;
+
+        if request.nonce != response.nonce || request.origin_ms != response.origin_ms {
+            return Err(SecureCommsError::Validation(format!(
+                "Time sync response from {} does not match request",
+                peer_id
+            )));
+        }
+        if !response.verify(session_key) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        self.pending.remove(peer_id);
+
+        let t1 = response.origin_ms as i64;
+        let t2 = response.receive_ms as i64;
+        let t3 = response.transmit_ms as i64;
+        let t4 = received_ms as i64;
+
+        let rtt_ms = ((t4 - t1) - (t3 - t2)).max(0) as u64;
+        if rtt_ms > self.config.max_rtt_ms {
+            return Err(SecureCommsError::Timeout(format!(
+                "Time sync round trip to {} took {}ms",
+                peer_id, rtt_ms
+            )));
+        }
+
+        let sample = SkewSample {
+            peer_id: peer_id.to_string(),
+            offset_ms: ((t2 - t1) + (t3 - t4)) / 2,
+            rtt_ms,
+            source: SampleSource::SecureSync,
+            recorded_at_ms: received_ms,
+        };
+        self.record_sample(sample.clone());
+        Ok(sample)
+    }
+
+    /// Estimated skew for a peer (median of recent samples)
+    pub fn peer_skew_ms(&self, peer_id: &str) -> Option<i64> {
+        self.samples
+            .get(peer_id)
+            .and_then(|samples| Self::median(samples.iter().map(|s| s.offset_ms).collect()))
+    }
+
+    /// Skew status for a peer
+    pub fn peer_status(&self, peer_id: &str) -> SkewStatus {
+        self.peer_skew_ms(peer_id)
+            .map(|skew| self.classify(skew))
+            .unwrap_or(SkewStatus::Normal)
+    }
+
+    /// Median skew across all peers, i.e. how far the local clock is behind the fleet
+    pub fn fleet_offset_ms(&self) -> i64 {
+        let skews: Vec<i64> = self
+            .samples
+            .keys()
+            .filter_map(|peer_id| self.peer_skew_ms(peer_id))
+            .collect();
+        Self::median(skews).unwrap_or(0)
+    }
+
+    /// Local time corrected by the fleet offset when secure sync is enabled
+    pub fn synchronized_now_ms(&self) -> u64 {
+        let now = Self::local_now_ms() as i64;
+        if self.config.secure_sync_enabled {
+            (now + self.fleet_offset_ms()).max(0) as u64
+        } else {
+            now as u64
+        }
+    }
+
+    /// Check a peer timestamp against a validity window, compensating for skew
+    ///
+    /// `timestamp_ms` is expressed in the peer's clock. Timestamps from the
+    /// future beyond the alert threshold are rejected.
+    pub fn is_within_window(&self, peer_id: &str, timestamp_ms: u64, window_ms: u64) -> bool {
+        self.is_within_window_at(peer_id, timestamp_ms, window_ms, Self::local_now_ms())
+    }
+
+    /// Window check against an explicit local time
+    pub fn is_within_window_at(
+        &self,
+        peer_id: &str,
+        timestamp_ms: u64,
+        window_ms: u64,
+        local_now_ms: u64,
+    ) -> bool {
+        let skew = self.peer_skew_ms(peer_id).unwrap_or(0);
+        let local_equivalent = timestamp_ms as i64 - skew;
+        let age = local_now_ms as i64 - local_equivalent;
+
+        if age < 0 {
+            (-age) as u64 <= self.config.alert_threshold_ms
+        } else {
+            age as u64 <= window_ms
+        }
+    }
+
+    /// Forget all samples for a peer
+    pub fn remove_peer(&mut self, peer_id: &str) {
+        self.samples.remove(peer_id);
+        self.pending.remove(peer_id);
+    }
+
+    /// Get clock sync statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert("tracked_peers".to_string(), serde_json::json!(self.samples.len()));
+        stats.insert("pending_syncs".to_string(), serde_json::json!(self.pending.len()));
+        stats.insert("fleet_offset_ms".to_string(), serde_json::json!(self.fleet_offset_ms()));
+        stats.insert("alerts_raised".to_string(), serde_json::json!(self.alerts_raised));
+
+        let max_abs_skew = self
+            .samples
+            .keys()
+            .filter_map(|peer_id| self.peer_skew_ms(peer_id))
+            .map(|skew| skew.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        stats.insert("max_abs_skew_ms".to_string(), serde_json::json!(max_abs_skew));
+        stats
+    }
+
+    fn record_sample(&mut self, sample: SkewSample) -> SkewStatus {
+        let peer_id = sample.peer_id.clone();
+        let samples = self.samples.entry(peer_id.clone()).or_default();
+        samples.push_back(sample);
+        while samples.len() > self.config.max_samples.max(1) {
+            samples.pop_front();
+        }
+
+        let skew = self.peer_skew_ms(&peer_id).unwrap_or(0);
+        let status = self.classify(skew);
+        match status {
+            SkewStatus::Normal => {}
+            SkewStatus::Warning => log_warn(
+                LogCategory::Network,
+                &format!("Clock skew of {}ms detected for peer {}", skew, peer_id),
+            ),
+            SkewStatus::Critical => {
+                self.alerts_raised += 1;
+                log_security(
+                    "Critical clock skew detected",
+                    serde_json::json!({
+                        "peer_id": peer_id,
+                        "skew_ms": skew,
+                        "threshold_ms": self.config.alert_threshold_ms,
+                    }),
+                );
+            }
+        }
+        status
+    }
+
+    fn classify(&self, skew_ms: i64) -> SkewStatus {
+        let magnitude = skew_ms.unsigned_abs();
+        if magnitude > self.config.alert_threshold_ms {
+            SkewStatus::Critical
+        } else if magnitude > self.config.warn_threshold_ms {
+            SkewStatus::Warning
+        } else {
+            SkewStatus::Normal
+        }
+    }
+
+    fn median(mut values: Vec<i64>) -> Option<i64> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_unstable();
+        let mid = values.len() / 2;
+        if values.len().is_multiple_of(2) {
+            Some((values[mid - 1] + values[mid]) / 2)
+        } else {
+            Some(values[mid])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_response(request: &TimeSyncRequest, key: &[u8], t2: u64, t3: u64) -> TimeSyncResponse {
+        TimeSyncResponse {
+            nonce: request.nonce.clone(),
+            origin_ms: request.origin_ms,
+            receive_ms: t2,
+            transmit_ms: t3,
+            mac: TimeSyncResponse::compute_mac(key, &request.nonce, request.origin_ms, t2, t3),
+        }
+    }
+
+    #[test]
+    fn test_secure_sync_offset() {
+        let key = vec![1u8; 32];
+        let mut monitor = ClockSkewMonitor::new(ClockSyncConfig::default());
+        let request = monitor.create_sync_request("peer");
+        let t1 = request.origin_ms;
+
+        // Peer clock is 1000ms ahead, 20ms each way, 10ms processing
+        let response = signed_response(&request, &key, t1 + 1020, t1 + 1030);
+        let sample = monitor.complete_sync_at("peer", &response, &key, t1 + 50).unwrap();
+
+        assert_eq!(sample.offset_ms, 1000);
+        assert_eq!(sample.rtt_ms, 40);
+        assert_eq!(monitor.peer_status("peer"), SkewStatus::Warning);
+    }
+
+    #[test]
+    fn test_tampered_sync_rejected() {
+        let key = vec![1u8; 32];
+        let mut monitor = ClockSkewMonitor::new(ClockSyncConfig::default());
+        let request = monitor.create_sync_request("peer");
+        let t1 = request.origin_ms;
+
+        let mut response = signed_response(&request, &key, t1 + 5, t1 + 6);
+        response.transmit_ms += 60_000;
+        assert!(monitor.complete_sync_at("peer", &response, &key, t1 + 10).is_err());
+
+        let forged = signed_response(&request, &[2u8; 32], t1 + 5, t1 + 6);
+        assert!(monitor.complete_sync_at("peer", &forged, &key, t1 + 10).is_err());
+        assert!(monitor.peer_skew_ms("peer").is_none());
+    }
+
+    #[test]
+    fn test_handshake_skew_classification() {
+        let mut monitor = ClockSkewMonitor::new(ClockSyncConfig::default());
+        let now = ClockSkewMonitor::local_now_ms();
+
+        assert_eq!(monitor.record_handshake_timestamp("fast", now, 0), SkewStatus::Normal);
+        assert_eq!(
+            monitor.record_handshake_timestamp("slow", now - 60_000, 0),
+            SkewStatus::Critical
+        );
+        assert_eq!(monitor.get_stats()["alerts_raised"], serde_json::json!(1));
+    }
+
+    #[test]
+    fn test_window_compensates_for_skew() {
+        let key = vec![3u8; 32];
+        let mut monitor = ClockSkewMonitor::new(ClockSyncConfig::default());
+        let request = monitor.create_sync_request("ahead");
+        let t1 = request.origin_ms;
+        let response = signed_response(&request, &key, t1 + 3000, t1 + 3000);
+        monitor.complete_sync_at("ahead", &response, &key, t1).unwrap();
+        assert_eq!(monitor.peer_skew_ms("ahead"), Some(3000));
+
+        // A fresh message stamped with the peer's (ahead) clock is accepted
+        assert!(monitor.is_within_window_at("ahead", t1 + 3000, 1000, t1));
+        // An old message is rejected even though its raw timestamp looks current
+        assert!(!monitor.is_within_window_at("ahead", t1, 1000, t1));
+    }
+
+    #[test]
+    fn test_fleet_offset_median() {
+        let mut monitor = ClockSkewMonitor::new(ClockSyncConfig {
+            secure_sync_enabled: true,
+            ..ClockSyncConfig::default()
+        });
+        for (peer, offset) in [("a", 100i64), ("b", 200), ("c", 10_000)] {
+            monitor.record_sample(SkewSample {
+                peer_id: peer.to_string(),
+                offset_ms: offset,
+                rtt_ms: 0,
+                source: SampleSource::SecureSync,
+                recorded_at_ms: 0,
+            });
+        }
+        assert_eq!(monitor.fleet_offset_ms(), 200);
+    }
+}
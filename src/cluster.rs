@@ -0,0 +1,499 @@
+//! # Client Clustering - Shared Session State for Failover
+//!
+//! Lets several client instances form a high-availability group. The
+//! active node writes the state of every established channel to a shared
+//! session store; when it fails, a standby takes over the lease and
+//! installs the stored channels directly, so peers keep talking to the
+//! same channel without a new key exchange.
+//!
+//! ## Components
+//!
+//! - **SessionRecord**: Everything needed to resume a channel - session key,
+//!   message counters, outbound sequence and peer address
+//! - **SessionStore**: Pluggable backend trait; implement it over Redis, etcd
+//!   or a replicated log to share state between hosts
+//! - **InMemorySessionStore**: Process-local store whose clones share state,
+//!   for tests and single-host deployments
+//! - **ClusterLease**: Single active-node lease with a fencing epoch
+//!
+//! ## Fencing
+//!
+//! Every lease grant increments the epoch. Records are written with the
+//! writer's epoch and the store rejects writes from an older epoch, so a
+//! node that lost its lease cannot overwrite the state of its successor.
+//!
+//! ## Identity
+//!
+//! Peers pin the identity key of the node they established channels with.
+//! Cluster members that should be interchangeable must therefore be
+//! provisioned with the same identity, which is never written to the store.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::cluster::{InMemorySessionStore, SessionStore};
+//! use quantum_forge_secure_comms::expiry::now_ms;
+//!
+//! let store = InMemorySessionStore::new();
+//! let lease = store.acquire_lease("node_a", 10_000, now_ms()).unwrap();
+//! println!("node_a active at epoch {}", lease.unwrap().epoch);
+//! assert!(store.acquire_lease("node_b", 10_000, now_ms()).unwrap().is_none());
+//! ```
+
+use crate::channel_migration::TransportKind;
+use crate::network_comms::{self, PeerInfo};
+use crate::{Result, SecureCommsError};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Cluster membership settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    /// Node identifier within the cluster (defaults to the client ID)
+    pub node_id: Option<String>,
+    /// Seconds an active-node lease stays valid without renewal
+    pub lease_ttl_seconds: u64,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            node_id: None,
+            lease_ttl_seconds: 10,
+        }
+    }
+}
+
+/// Role of a node in the cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClusterRole {
+    /// Holds the lease and replicates its channels
+    Active,
+    /// Waits to take over when the lease lapses
+    Standby,
+}
+
+/// Active-node lease
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterLease {
+    /// Node holding the lease
+    pub holder: String,
+    /// Fencing epoch, incremented on every new grant
+    pub epoch: u64,
+    /// Expiry in milliseconds since the Unix epoch
+    pub expires_at_ms: u64,
+}
+
+impl ClusterLease {
+    /// Whether the lease has lapsed at `now_ms`
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms >= self.expires_at_ms
+    }
+}
+
+/// Replicated state of one established channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Remote peer identifier
+    pub peer_id: String,
+    /// Client-level channel identifier
+    pub channel_id: String,
+    /// Network-level channel identifier
+    pub network_channel_id: String,
+    /// Symmetric session key of the channel
+    pub session_key: Vec<u8>,
+    /// Network messages sent on the channel
+    pub send_counter: u64,
+    /// Network messages received on the channel
+    pub receive_counter: u64,
+    /// Transport carrying the channel
+    pub transport: TransportKind,
+    /// Channel security level in bits
+    pub security_level: u16,
+    /// QKD fidelity at establishment or last rekey
+    pub qkd_fidelity: f64,
+    /// Quantum bit error rate at establishment or last rekey
+    pub qkd_error_rate: f64,
+    /// Connection metadata of the channel
+    pub connection_info: String,
+    /// Unix timestamp when the channel was established
+    pub established_at: u64,
+    /// Peer connection details
+    pub peer: PeerInfo,
+    /// Last application sequence number sent to the peer
+    pub outbound_sequence: u64,
+    /// Node that wrote the record
+    pub owner: String,
+    /// Lease epoch of the writer
+    pub epoch: u64,
+    /// Write time in milliseconds since the Unix epoch
+    pub updated_at_ms: u64,
+}
+
+impl SessionRecord {
+    /// Rebuild the network-level channel
+    pub fn network_channel(&self) -> network_comms::SecureChannel {
+        let mut channel = network_comms::SecureChannel::new(
+            self.network_channel_id.clone(),
+            self.peer_id.clone(),
+            self.session_key.clone(),
+        );
+        channel.send_counter = self.send_counter;
+        channel.receive_counter = self.receive_counter;
+        channel.established_at = self.established_at;
+        channel.transport = self.transport;
+        channel
+    }
+}
+
+/// Shared backend for session records and the active-node lease
+///
+/// Implementations must be safe to call from several nodes at once; lease
+/// acquisition and epoch-checked writes have to be atomic.
+pub trait SessionStore: Send + Sync {
+    /// Grant or renew the lease for `node_id`
+    ///
+    /// Succeeds when the lease is free, expired or already held by the
+    /// node. Returns `None` while another node holds a live lease.
+    fn acquire_lease(
+        &self,
+        node_id: &str,
+        ttl_ms: u64,
+        now_ms: u64,
+    ) -> Result<Option<ClusterLease>>;
+
+    /// Give up the lease if `node_id` holds it
+    fn release_lease(&self, node_id: &str) -> Result<()>;
+
+    /// Current lease, live or expired
+    fn lease(&self) -> Result<Option<ClusterLease>>;
+
+    /// Store a record, rejecting writes from an epoch older than the lease
+    fn put(&self, record: SessionRecord) -> Result<()>;
+
+    /// Record of the peer's channel
+    fn get(&self, peer_id: &str) -> Result<Option<SessionRecord>>;
+
+    /// Delete the peer's record written under `epoch` or later
+    fn remove(&self, peer_id: &str, epoch: u64) -> Result<()>;
+
+    /// All stored records
+    fn list(&self) -> Result<Vec<SessionRecord>>;
+}
+
+#[derive(Debug, Default)]
+struct StoreState {
+    lease: Option<ClusterLease>,
+    records: BTreeMap<String, SessionRecord>,
+}
+
+impl StoreState {
+    fn check_epoch(&self, epoch: u64) -> Result<()> {
+        match &self.lease {
+            Some(lease) if epoch < lease.epoch => Err(SecureCommsError::Validation(format!(
+                "Stale session write from epoch {} (lease epoch {})",
+                epoch, lease.epoch
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Process-local session store; clones share the same state
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySessionStore {
+    state: Arc<RwLock<StoreState>>,
+}
+
+impl InMemorySessionStore {
+    /// Create empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn acquire_lease(
+        &self,
+        node_id: &str,
+        ttl_ms: u64,
+        now_ms: u64,
+    ) -> Result<Option<ClusterLease>> {
+        let mut state = self.state.write();
+        let epoch = match &state.lease {
+            Some(lease) if lease.holder == node_id && !lease.is_expired(now_ms) => lease.epoch,
+            Some(lease) if !lease.is_expired(now_ms) => return Ok(None),
+            Some(lease) => lease.epoch + 1,
+            None => 1,
+        };
+        let lease = ClusterLease {
+            holder: node_id.to_string(),
+            epoch,
+            expires_at_ms: now_ms.saturating_add(ttl_ms),
+        };
+        state.lease = Some(lease.clone());
+        Ok(Some(lease))
+    }
+
+    fn release_lease(&self, node_id: &str) -> Result<()> {
+        let mut state = self.state.write();
+        if let Some(lease) = state.lease.as_mut() {
+            if lease.holder == node_id {
+                // Keep the epoch so the next grant still fences this node
+                lease.expires_at_ms = 0;
+            }
+        }
+        Ok(())
+    }
+
+    fn lease(&self) -> Result<Option<ClusterLease>> {
+        Ok(self.state.read().lease.clone())
+    }
+
+    fn put(&self, record: SessionRecord) -> Result<()> {
+        let mut state = self.state.write();
+        state.check_epoch(record.epoch)?;
+        state.records.insert(record.peer_id.clone(), record);
+        Ok(())
+    }
+
+    fn get(&self, peer_id: &str) -> Result<Option<SessionRecord>> {
+        Ok(self.state.read().records.get(peer_id).cloned())
+    }
+
+    fn remove(&self, peer_id: &str, epoch: u64) -> Result<()> {
+        let mut state = self.state.write();
+        state.check_epoch(epoch)?;
+        state.records.remove(peer_id);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<SessionRecord>> {
+        Ok(self.state.read().records.values().cloned().collect())
+    }
+}
+
+/// A client's membership in a cluster
+#[derive(Clone)]
+pub struct ClusterMember {
+    store: Arc<dyn SessionStore>,
+    node_id: String,
+    lease_ttl_ms: u64,
+    epoch: Option<u64>,
+}
+
+impl ClusterMember {
+    /// Join through `store` as `node_id`, initially without the lease
+    pub fn new(store: Arc<dyn SessionStore>, node_id: String, config: &ClusterConfig) -> Self {
+        Self {
+            store,
+            node_id,
+            lease_ttl_ms: config.lease_ttl_seconds.saturating_mul(1000),
+            epoch: None,
+        }
+    }
+
+    /// Try to become or stay the active node
+    pub fn try_activate(&mut self, now_ms: u64) -> Result<ClusterRole> {
+        match self
+            .store
+            .acquire_lease(&self.node_id, self.lease_ttl_ms, now_ms)?
+        {
+            Some(lease) => {
+                self.epoch = Some(lease.epoch);
+                Ok(ClusterRole::Active)
+            }
+            None => {
+                self.epoch = None;
+                Ok(ClusterRole::Standby)
+            }
+        }
+    }
+
+    /// Give up the lease and become a standby
+    pub fn step_down(&mut self) -> Result<()> {
+        self.epoch = None;
+        self.store.release_lease(&self.node_id)
+    }
+
+    /// Current role as last observed
+    pub fn role(&self) -> ClusterRole {
+        if self.epoch.is_some() {
+            ClusterRole::Active
+        } else {
+            ClusterRole::Standby
+        }
+    }
+
+    /// Lease epoch while active
+    pub fn epoch(&self) -> Option<u64> {
+        self.epoch
+    }
+
+    /// Node identifier
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Shared store
+    pub fn store(&self) -> &Arc<dyn SessionStore> {
+        &self.store
+    }
+
+    /// Write a record as the active node; standbys write nothing
+    ///
+    /// A rejected write means another node took over, so this node steps
+    /// back to standby.
+    pub fn publish(&mut self, mut record: SessionRecord, now_ms: u64) -> Result<bool> {
+        let epoch = match self.epoch {
+            Some(epoch) => epoch,
+            None => return Ok(false),
+        };
+        record.owner = self.node_id.clone();
+        record.epoch = epoch;
+        record.updated_at_ms = now_ms;
+        if let Err(e) = self.store.put(record) {
+            self.epoch = None;
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// Delete the peer's record as the active node
+    pub fn retract(&mut self, peer_id: &str) -> Result<()> {
+        let epoch = match self.epoch {
+            Some(epoch) => epoch,
+            None => return Ok(()),
+        };
+        if let Err(e) = self.store.remove(peer_id, epoch) {
+            self.epoch = None;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Membership details as a JSON map
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut map = HashMap::new();
+        map.insert("node_id".to_string(), serde_json::json!(self.node_id));
+        map.insert(
+            "role".to_string(),
+            serde_json::json!(format!("{:?}", self.role())),
+        );
+        map.insert("epoch".to_string(), serde_json::json!(self.epoch));
+        map.insert(
+            "replicated_sessions".to_string(),
+            serde_json::json!(self.store.list().map(|records| records.len()).unwrap_or(0)),
+        );
+        map
+    }
+}
+
+impl std::fmt::Debug for ClusterMember {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClusterMember")
+            .field("node_id", &self.node_id)
+            .field("lease_ttl_ms", &self.lease_ttl_ms)
+            .field("epoch", &self.epoch)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network_comms::ConnectionStatus;
+
+    fn record(peer_id: &str) -> SessionRecord {
+        SessionRecord {
+            peer_id: peer_id.to_string(),
+            channel_id: format!("secure_{}", peer_id),
+            network_channel_id: format!("channel_{}", peer_id),
+            session_key: vec![7u8; 32],
+            send_counter: 3,
+            receive_counter: 2,
+            transport: TransportKind::Tcp,
+            security_level: 256,
+            qkd_fidelity: 0.98,
+            qkd_error_rate: 0.02,
+            connection_info: format!("conn_{}", peer_id),
+            established_at: 1,
+            peer: PeerInfo {
+                peer_id: peer_id.to_string(),
+                address: "127.0.0.1".to_string(),
+                port: 8081,
+                public_key: vec![1u8; 32],
+                connection_status: ConnectionStatus::SecureChannelEstablished,
+                last_seen: 1,
+                trust_score: 0.8,
+            },
+            outbound_sequence: 5,
+            owner: String::new(),
+            epoch: 0,
+            updated_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_lease_exclusive_until_expiry() {
+        let store = InMemorySessionStore::new();
+        let lease = store.acquire_lease("node_a", 1_000, 0).unwrap().unwrap();
+        assert_eq!(lease.epoch, 1);
+        assert!(store.acquire_lease("node_b", 1_000, 500).unwrap().is_none());
+
+        // Renewal keeps the epoch
+        let renewed = store.acquire_lease("node_a", 1_000, 900).unwrap().unwrap();
+        assert_eq!(renewed.epoch, 1);
+        assert_eq!(renewed.expires_at_ms, 1_900);
+
+        let taken = store
+            .acquire_lease("node_b", 1_000, 2_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(taken.holder, "node_b");
+        assert_eq!(taken.epoch, 2);
+    }
+
+    #[test]
+    fn test_stale_epoch_fenced() {
+        let store = Arc::new(InMemorySessionStore::new());
+        let config = ClusterConfig::default();
+        let mut node_a = ClusterMember::new(store.clone(), "node_a".to_string(), &config);
+        let mut node_b = ClusterMember::new(store.clone(), "node_b".to_string(), &config);
+
+        assert_eq!(node_a.try_activate(0).unwrap(), ClusterRole::Active);
+        assert_eq!(node_b.try_activate(0).unwrap(), ClusterRole::Standby);
+        assert!(node_a.publish(record("peer_1"), 0).unwrap());
+        assert!(!node_b.publish(record("peer_1"), 0).unwrap());
+
+        // Lease lapses without node_a noticing
+        assert_eq!(node_b.try_activate(60_000).unwrap(), ClusterRole::Active);
+        assert!(node_a.publish(record("peer_2"), 60_001).is_err());
+        assert_eq!(node_a.role(), ClusterRole::Standby);
+
+        let stored = store.get("peer_1").unwrap().unwrap();
+        assert_eq!(stored.owner, "node_a");
+        assert!(store.get("peer_2").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_release_hands_over() {
+        let store = Arc::new(InMemorySessionStore::new());
+        let config = ClusterConfig::default();
+        let mut node_a = ClusterMember::new(store.clone(), "node_a".to_string(), &config);
+        let mut node_b = ClusterMember::new(store.clone(), "node_b".to_string(), &config);
+
+        node_a.try_activate(0).unwrap();
+        node_a.publish(record("peer_1"), 0).unwrap();
+        node_a.step_down().unwrap();
+        assert_eq!(node_b.try_activate(1).unwrap(), ClusterRole::Active);
+        assert_eq!(node_b.epoch(), Some(2));
+
+        let restored = store.get("peer_1").unwrap().unwrap().network_channel();
+        assert_eq!(restored.channel_id, "channel_peer_1");
+        assert_eq!(restored.send_counter, 3);
+        assert_eq!(restored.session_key, vec![7u8; 32]);
+    }
+}
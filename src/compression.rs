@@ -0,0 +1,355 @@
+//! # Compression - Per-Channel Payload Compression That Backs Off
+//!
+//! Compresses outbound payloads with LZ4 on channels whose peer advertises
+//! the `compression` feature, and keeps measuring whether it pays off. A
+//! channel carrying payloads that do not shrink (already-encrypted blobs,
+//! media, random data) has compression switched off automatically, so it
+//! stops spending CPU on every message.
+//!
+//! ## Measurements
+//!
+//! For every channel [`CompressionStats`] keeps the bytes before and after
+//! compression, the time spent compressing and decompressing, and a moving
+//! average of the compressed-to-original size ratio over recent messages.
+//! The stats are part of `ChannelStats`, so dashboards see the decision
+//! next to the rest of the channel's traffic.
+//!
+//! ## Adaptive Disabling
+//!
+//! - **Judging**: Once `sample_messages` payloads have been measured, a
+//!   channel whose recent ratio is above `disable_ratio` is disabled
+//! - **Probing**: A disabled channel still compresses every
+//!   `probe_interval`th eligible payload; one that shrinks below
+//!   `disable_ratio` re-enables the channel
+//! - **Per Message**: A payload that does not shrink is sent as it is,
+//!   whatever the channel's decision
+//!
+//! ## Wire Format
+//!
+//! Compressed messages carry the [`COMPRESSION_HEADER`] header naming the
+//! algorithm, and their payload is the LZ4 block prefixed with the original
+//! length. Compression runs after the outbound interceptors and before the
+//! transcript signature, so signatures cover the bytes on the wire.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::compression::{CompressionConfig, CompressionDecision, CompressionStats};
+//! use std::time::Duration;
+//!
+//! let config = CompressionConfig {
+//!     enabled: true,
+//!     ..Default::default()
+//! };
+//! let mut stats = CompressionStats::default();
+//! for _ in 0..config.sample_messages {
+//!     if stats.plan(&config, 4096) {
+//!         stats.record_compression(&config, 4096, 4100, Duration::from_micros(30));
+//!     }
+//! }
+//! assert_eq!(stats.decision, CompressionDecision::Disabled);
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Header naming the algorithm of a compressed payload
+pub const COMPRESSION_HEADER: &str = "x-compression";
+
+/// Algorithm name carried in `COMPRESSION_HEADER`
+pub const COMPRESSION_LZ4: &str = "lz4";
+
+/// Length of the original-size prefix of a compressed payload
+const SIZE_PREFIX_LEN: usize = 4;
+
+/// Payload compression settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// Whether outbound payloads are compressed at all
+    pub enabled: bool,
+    /// Payloads smaller than this are sent as they are
+    pub min_payload_bytes: usize,
+    /// Recent compressed-to-original ratio above which a channel is disabled
+    pub disable_ratio: f64,
+    /// Payloads measured before a channel is judged
+    pub sample_messages: u32,
+    /// While disabled, compress every Nth eligible payload to re-check
+    pub probe_interval: u64,
+    /// Largest payload accepted when decompressing
+    pub max_decompressed_bytes: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_payload_bytes: 256,
+            disable_ratio: 0.9,
+            sample_messages: 8,
+            probe_interval: 64,
+            max_decompressed_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Check the thresholds are usable
+    pub fn validate(&self) -> Result<()> {
+        if !(self.disable_ratio > 0.0 && self.disable_ratio <= 1.0) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Compression disable ratio must be in (0, 1], got {}",
+                self.disable_ratio
+            )));
+        }
+        if self.sample_messages == 0 || self.probe_interval == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Compression sample size and probe interval must be at least 1".to_string(),
+            ));
+        }
+        if self.max_decompressed_bytes == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Compression decompressed size limit must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether a channel currently compresses its payloads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionDecision {
+    #[default]
+    Enabled,
+    /// Recent payloads did not compress; only probes are compressed
+    Disabled,
+}
+
+/// Compression measurements and decision for one channel
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompressionStats {
+    pub decision: CompressionDecision,
+    /// Payloads sent compressed
+    pub messages_compressed: u64,
+    /// Eligible payloads sent uncompressed, skipped or not shrinking
+    pub messages_skipped: u64,
+    /// Payload bytes given to the compressor
+    pub bytes_in: u64,
+    /// Bytes the compressor produced
+    pub bytes_out: u64,
+    /// Time spent compressing, in microseconds
+    pub compress_micros: u64,
+    /// Inbound payloads decompressed
+    pub messages_decompressed: u64,
+    /// Time spent decompressing, in microseconds
+    pub decompress_micros: u64,
+    /// Moving average of the compressed-to-original ratio
+    pub recent_ratio: f64,
+    /// Payloads in `recent_ratio`, capped at the sample size
+    pub samples: u32,
+    /// Times compression was switched off for the channel
+    pub times_disabled: u64,
+    /// Eligible payloads skipped since the last probe
+    #[serde(default)]
+    since_probe: u64,
+}
+
+impl CompressionStats {
+    /// Overall compressed-to-original ratio (1.0 before any compression)
+    pub fn ratio(&self) -> f64 {
+        if self.bytes_in == 0 {
+            return 1.0;
+        }
+        self.bytes_out as f64 / self.bytes_in as f64
+    }
+
+    /// Compression CPU time per KiB of input, in microseconds
+    pub fn micros_per_kib(&self) -> f64 {
+        if self.bytes_in == 0 {
+            return 0.0;
+        }
+        self.compress_micros as f64 * 1024.0 / self.bytes_in as f64
+    }
+
+    /// Whether to compress a payload of `len` bytes
+    ///
+    /// Disabled channels count the payload as skipped unless it is due as
+    /// a probe.
+    pub fn plan(&mut self, config: &CompressionConfig, len: usize) -> bool {
+        if !config.enabled || len < config.min_payload_bytes {
+            return false;
+        }
+        if self.decision == CompressionDecision::Enabled {
+            return true;
+        }
+        self.since_probe += 1;
+        if self.since_probe >= config.probe_interval {
+            self.since_probe = 0;
+            return true;
+        }
+        self.messages_skipped += 1;
+        false
+    }
+
+    /// Record one compression, returning the new decision if it changed
+    pub fn record_compression(
+        &mut self,
+        config: &CompressionConfig,
+        original: usize,
+        compressed: usize,
+        elapsed: Duration,
+    ) -> Option<CompressionDecision> {
+        self.bytes_in += original as u64;
+        self.bytes_out += compressed as u64;
+        self.compress_micros += elapsed.as_micros() as u64;
+        if compressed < original {
+            self.messages_compressed += 1;
+        } else {
+            self.messages_skipped += 1;
+        }
+
+        let ratio = compressed as f64 / original.max(1) as f64;
+        match self.decision {
+            CompressionDecision::Enabled => {
+                self.samples = (self.samples + 1).min(config.sample_messages);
+                self.recent_ratio += (ratio - self.recent_ratio) / self.samples as f64;
+                if self.samples >= config.sample_messages
+                    && self.recent_ratio > config.disable_ratio
+                {
+                    self.decision = CompressionDecision::Disabled;
+                    self.times_disabled += 1;
+                    return Some(CompressionDecision::Disabled);
+                }
+                None
+            }
+            // Only probes are compressed while disabled
+            CompressionDecision::Disabled if ratio <= config.disable_ratio => {
+                self.decision = CompressionDecision::Enabled;
+                self.recent_ratio = ratio;
+                self.samples = 1;
+                Some(CompressionDecision::Enabled)
+            }
+            CompressionDecision::Disabled => None,
+        }
+    }
+
+    /// Record one inbound decompression
+    pub fn record_decompression(&mut self, elapsed: Duration) {
+        self.messages_decompressed += 1;
+        self.decompress_micros += elapsed.as_micros() as u64;
+    }
+}
+
+/// LZ4-compress a payload, prefixed with its original length
+pub fn compress(payload: &[u8]) -> Vec<u8> {
+    lz4_flex::block::compress_prepend_size(payload)
+}
+
+/// Decompress a payload produced by [`compress`]
+///
+/// The original length is checked against `max_len` before anything is
+/// allocated.
+pub fn decompress(payload: &[u8], max_len: usize) -> Result<Vec<u8>> {
+    if payload.len() < SIZE_PREFIX_LEN {
+        return Err(SecureCommsError::Validation(
+            "Compressed payload is truncated".to_string(),
+        ));
+    }
+    let (prefix, block) = payload.split_at(SIZE_PREFIX_LEN);
+    let len = u32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]) as usize;
+    if len > max_len {
+        return Err(SecureCommsError::Validation(format!(
+            "Compressed payload expands to {} bytes, limit is {}",
+            len, max_len
+        )));
+    }
+    lz4_flex::block::decompress(block, len)
+        .map_err(|e| SecureCommsError::Validation(format!("Corrupt compressed payload: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> CompressionConfig {
+        CompressionConfig {
+            enabled: true,
+            sample_messages: 4,
+            probe_interval: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_size_limit() {
+        let payload = b"validator heartbeat ".repeat(64);
+        let compressed = compress(&payload);
+        assert!(compressed.len() < payload.len() / 4);
+        assert_eq!(decompress(&compressed, payload.len()).unwrap(), payload);
+
+        assert!(decompress(&compressed, payload.len() - 1).is_err());
+        assert!(decompress(&compressed[..2], payload.len()).is_err());
+        let mut corrupt = compressed;
+        corrupt.truncate(corrupt.len() / 2);
+        assert!(decompress(&corrupt, payload.len()).is_err());
+    }
+
+    #[test]
+    fn test_incompressible_channel_disabled_and_reprobed() {
+        let config = enabled();
+        let mut stats = CompressionStats::default();
+        assert!(!stats.plan(&config, 100));
+        let mut decisions = Vec::new();
+        for _ in 0..4 {
+            assert!(stats.plan(&config, 1000));
+            decisions.push(stats.record_compression(
+                &config,
+                1000,
+                1004,
+                Duration::from_micros(10),
+            ));
+        }
+        assert_eq!(
+            decisions,
+            vec![None, None, None, Some(CompressionDecision::Disabled)]
+        );
+        assert_eq!(stats.messages_skipped, 4);
+        assert!(stats.ratio() > 1.0);
+
+        // Skipped until the probe, which finds compressible data again
+        assert!(!stats.plan(&config, 1000));
+        assert!(!stats.plan(&config, 1000));
+        assert!(stats.plan(&config, 1000));
+        assert_eq!(
+            stats.record_compression(&config, 1000, 200, Duration::from_micros(10)),
+            Some(CompressionDecision::Enabled)
+        );
+        assert_eq!(stats.messages_skipped, 6);
+        assert_eq!(stats.messages_compressed, 1);
+        assert_eq!(stats.times_disabled, 1);
+        assert!(stats.micros_per_kib() > 0.0);
+    }
+
+    #[test]
+    fn test_mixed_payloads_stay_enabled() {
+        let config = enabled();
+        let mut stats = CompressionStats::default();
+        // Averaging over the sample size tolerates an occasional blob
+        for compressed in [300, 1010, 250, 400, 1010, 350] {
+            assert!(stats.plan(&config, 1000));
+            assert_eq!(
+                stats.record_compression(&config, 1000, compressed, Duration::ZERO),
+                None
+            );
+        }
+        assert_eq!(stats.decision, CompressionDecision::Enabled);
+        assert!(!CompressionConfig::default().enabled);
+        assert!(CompressionConfig {
+            disable_ratio: 1.5,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,527 @@
+//! # Compromise Recovery - Operator-Driven Re-Keying of a Compromised Peer
+//!
+//! When a peer's long-term identity key is suspected compromised, its
+//! channels and pinned keys can no longer be trusted. Recovery walks the
+//! peer back to a trusted state under a new identity key, recording every
+//! step in a signed, hash-chained audit log.
+//!
+//! ## Recovery Stages
+//!
+//! 1. **Revoked**: Pinned keys are revoked and the peer's channels torn down;
+//!    a fresh attestation is requested
+//! 2. **KeyInstalled**: A new long-term key was delivered out of band by an
+//!    operator, or re-certified by a threshold of endorsing validators
+//! 3. **Attested**: The peer attested from a trusted environment that binds
+//!    the new key
+//! 4. **Completed**: Channels are re-established under the new key
+//!
+//! ## Threshold Re-Certification
+//!
+//! Each endorser signs the peer ID, new key and incident ID with its own
+//! identity key. A `RecertificationPolicy` accepts the new key once
+//! `threshold` distinct, known endorsers other than the peer itself have
+//! signed it.
+//!
+//! ## Signed Audit Log
+//!
+//! Every step is appended to a `RecoveryAuditLog`. Each entry commits to the
+//! digest of its predecessor and is signed with the recovering node's
+//! identity key, so removed, reordered or altered entries fail `verify`.
+//! Entries are mirrored to the audit trail as they are written.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::compromise_recovery::{
+//!     KeyEndorsement, RecertificationPolicy, RecoveryAuditLog, RecoveryStep,
+//! };
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//!
+//! let mut keys = KeyManager::new();
+//! keys.import_key("identity", KeyPurpose::Signing, [1u8; 32]).unwrap();
+//!
+//! let mut log = RecoveryAuditLog::new();
+//! log.append("incident_1", "validator_3", RecoveryStep::Initiated, serde_json::json!({}), &keys, "identity")
+//!     .unwrap();
+//! log.verify(keys.public_key("identity").unwrap()).unwrap();
+//!
+//! let endorsement = KeyEndorsement::sign("validator_1", "validator_3", &[9u8; 32], "incident_1", &keys, "identity")
+//!     .unwrap();
+//! let mut policy = RecertificationPolicy::default();
+//! policy.threshold = 1;
+//! policy.endorsers.insert("validator_1".to_string(), keys.public_key("identity").unwrap().to_vec());
+//! policy.verify("validator_3", &[9u8; 32], "incident_1", &[endorsement]).unwrap();
+//! ```
+
+use crate::address_book::fingerprint;
+use crate::consensus_verify::verify_commit_signature;
+use crate::key_manager::KeyManager;
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Progress of a recovery incident
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecoveryStage {
+    /// Old keys revoked, awaiting a new long-term key
+    Revoked,
+    /// New key accepted, awaiting fresh attestation
+    KeyInstalled,
+    /// New key attested, awaiting channel re-establishment
+    Attested,
+    /// Channels re-established under the new key
+    Completed,
+}
+
+/// Step recorded in the recovery audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecoveryStep {
+    /// Operator triggered recovery
+    Initiated,
+    /// Pinned keys revoked and channels torn down
+    KeysRevoked,
+    /// Fresh attestation requested from the peer
+    AttestationRequested,
+    /// New long-term key accepted
+    KeyDistributed,
+    /// Offered key failed re-certification
+    KeyRejected,
+    /// Attestation binding the new key verified
+    AttestationVerified,
+    /// Attestation rejected
+    AttestationFailed,
+    /// Channel re-established under the new key
+    ChannelReestablished,
+    /// Recovery finished
+    Completed,
+}
+
+/// A validator's signature vouching for a peer's new identity key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEndorsement {
+    /// Endorsing validator
+    pub endorser_id: String,
+    /// Ed25519 signature over `KeyEndorsement::payload`
+    pub signature: Vec<u8>,
+}
+
+impl KeyEndorsement {
+    /// Bytes an endorser signs for `peer_id`'s new key in `incident_id`
+    pub fn payload(peer_id: &str, public_key: &[u8], incident_id: &str) -> Vec<u8> {
+        let mut payload = b"key_recertification_v1".to_vec();
+        for field in [peer_id.as_bytes(), public_key, incident_id.as_bytes()] {
+            payload.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            payload.extend_from_slice(field);
+        }
+        payload
+    }
+
+    /// Endorse `peer_id`'s new key with the signing key `key_id`
+    pub fn sign(
+        endorser_id: &str,
+        peer_id: &str,
+        public_key: &[u8],
+        incident_id: &str,
+        keys: &KeyManager,
+        key_id: &str,
+    ) -> Result<Self> {
+        Ok(Self {
+            endorser_id: endorser_id.to_string(),
+            signature: keys.sign(key_id, &Self::payload(peer_id, public_key, incident_id))?,
+        })
+    }
+}
+
+/// How a recovering peer's new long-term key was delivered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyDistribution {
+    /// Confirmed by an operator over a separate channel
+    OutOfBand {
+        /// New identity key
+        public_key: Vec<u8>,
+    },
+    /// Vouched for by a threshold of validators
+    Recertified {
+        /// New identity key
+        public_key: Vec<u8>,
+        /// Endorsements of the new key
+        endorsements: Vec<KeyEndorsement>,
+    },
+}
+
+impl KeyDistribution {
+    /// New identity key
+    pub fn public_key(&self) -> &[u8] {
+        match self {
+            Self::OutOfBand { public_key } | Self::Recertified { public_key, .. } => public_key,
+        }
+    }
+
+    /// Short name of the delivery method
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::OutOfBand { .. } => "out_of_band",
+            Self::Recertified { .. } => "threshold_recertification",
+        }
+    }
+}
+
+/// Endorsers trusted to re-certify keys and how many must agree
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecertificationPolicy {
+    /// Distinct endorsements required (0 disables re-certification)
+    pub threshold: usize,
+    /// Identity keys of trusted endorsers by validator ID
+    pub endorsers: BTreeMap<String, Vec<u8>>,
+}
+
+impl RecertificationPolicy {
+    /// Check the threshold is reachable
+    pub fn validate(&self) -> Result<()> {
+        if self.threshold > self.endorsers.len() {
+            return Err(SecureCommsError::Configuration(format!(
+                "Re-certification threshold {} exceeds {} endorsers",
+                self.threshold,
+                self.endorsers.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verify endorsements of `peer_id`'s new key, returning how many counted
+    ///
+    /// Unknown endorsers, the peer endorsing itself, duplicates and bad
+    /// signatures are ignored. Fails with `SecureCommsError::Configuration`
+    /// when re-certification is disabled and with
+    /// `SecureCommsError::AuthenticationFailed` below the threshold.
+    pub fn verify(
+        &self,
+        peer_id: &str,
+        public_key: &[u8],
+        incident_id: &str,
+        endorsements: &[KeyEndorsement],
+    ) -> Result<usize> {
+        if self.threshold == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Threshold re-certification is not configured".to_string(),
+            ));
+        }
+        let payload = KeyEndorsement::payload(peer_id, public_key, incident_id);
+        let valid: BTreeSet<&str> = endorsements
+            .iter()
+            .filter(|e| e.endorser_id != peer_id)
+            .filter(|e| {
+                self.endorsers
+                    .get(&e.endorser_id)
+                    .is_some_and(|key| verify_commit_signature(key, &payload, &e.signature))
+            })
+            .map(|e| e.endorser_id.as_str())
+            .collect();
+        if valid.len() < self.threshold {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        Ok(valid.len())
+    }
+}
+
+/// State of an open recovery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryIncident {
+    /// Identifier bound into endorsements and log entries
+    pub incident_id: String,
+    /// Peer being recovered
+    pub peer_id: String,
+    /// Unix timestamp recovery started
+    pub started_at: u64,
+    /// Current stage
+    pub stage: RecoveryStage,
+    /// Accepted new identity key, once distributed
+    pub new_key: Option<Vec<u8>>,
+}
+
+impl RecoveryIncident {
+    /// Start an incident for `peer_id` in the `Revoked` stage
+    pub fn new(incident_id: &str, peer_id: &str) -> Self {
+        Self {
+            incident_id: incident_id.to_string(),
+            peer_id: peer_id.to_string(),
+            started_at: chrono::Utc::now().timestamp() as u64,
+            stage: RecoveryStage::Revoked,
+            new_key: None,
+        }
+    }
+
+    /// Fail with `SecureCommsError::Validation` unless in `stage`
+    pub fn expect_stage(&self, stage: RecoveryStage) -> Result<()> {
+        if self.stage != stage {
+            return Err(SecureCommsError::Validation(format!(
+                "Recovery of {} is at {:?}, expected {:?}",
+                self.peer_id, self.stage, stage
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fingerprint of the accepted new key
+    pub fn new_key_fingerprint(&self) -> Option<String> {
+        self.new_key.as_deref().map(fingerprint)
+    }
+}
+
+/// One signed, chained entry of the recovery audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryLogEntry {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+    /// Incident the step belongs to
+    pub incident_id: String,
+    /// Recovered peer
+    pub peer_id: String,
+    /// Step taken
+    pub step: RecoveryStep,
+    /// Step-specific details
+    pub details: serde_json::Value,
+    /// Digest of the previous entry (empty for the first)
+    pub prev_digest: Vec<u8>,
+    /// Ed25519 signature over `signing_payload`
+    pub signature: Vec<u8>,
+}
+
+impl RecoveryLogEntry {
+    /// Bytes covered by the signature
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = b"recovery_log_v1".to_vec();
+        payload.extend_from_slice(&self.sequence.to_be_bytes());
+        payload.extend_from_slice(&self.timestamp_ms.to_be_bytes());
+        let step = serde_json::to_vec(&self.step).unwrap_or_default();
+        let details = serde_json::to_vec(&self.details).unwrap_or_default();
+        for field in [
+            self.incident_id.as_bytes(),
+            self.peer_id.as_bytes(),
+            &step,
+            &details,
+            &self.prev_digest,
+        ] {
+            payload.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            payload.extend_from_slice(field);
+        }
+        payload
+    }
+
+    /// Digest the next entry commits to
+    pub fn digest(&self) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.signing_payload());
+        hasher.update(&self.signature);
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Append-only, hash-chained log of recovery steps signed by this node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecoveryAuditLog {
+    entries: Vec<RecoveryLogEntry>,
+}
+
+impl RecoveryAuditLog {
+    /// Create empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sign and append a step, mirroring it to the audit trail
+    pub fn append(
+        &mut self,
+        incident_id: &str,
+        peer_id: &str,
+        step: RecoveryStep,
+        details: serde_json::Value,
+        keys: &KeyManager,
+        key_id: &str,
+    ) -> Result<&RecoveryLogEntry> {
+        let mut entry = RecoveryLogEntry {
+            sequence: self.entries.len() as u64,
+            timestamp_ms: chrono::Utc::now().timestamp_millis() as u64,
+            incident_id: incident_id.to_string(),
+            peer_id: peer_id.to_string(),
+            step,
+            details,
+            prev_digest: self.entries.last().map(|e| e.digest()).unwrap_or_default(),
+            signature: Vec::new(),
+        };
+        entry.signature = keys.sign(key_id, &entry.signing_payload())?;
+        crate::logging::log_audit(
+            &format!("Compromise recovery: {:?}", step),
+            serde_json::json!({
+                "incident_id": entry.incident_id,
+                "peer_id": entry.peer_id,
+                "sequence": entry.sequence,
+                "details": entry.details,
+            }),
+        );
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("entry just pushed"))
+    }
+
+    /// Check every signature and chain link against the signer's key
+    ///
+    /// Fails with `SecureCommsError::AuthenticationFailed` on a bad
+    /// signature and `SecureCommsError::Validation` on a broken chain.
+    pub fn verify(&self, public_key: &[u8]) -> Result<()> {
+        let mut prev_digest = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.sequence != index as u64 || entry.prev_digest != prev_digest {
+                return Err(SecureCommsError::Validation(format!(
+                    "Recovery log chain broken at entry {}",
+                    index
+                )));
+            }
+            if !verify_commit_signature(public_key, &entry.signing_payload(), &entry.signature) {
+                return Err(SecureCommsError::AuthenticationFailed);
+            }
+            prev_digest = entry.digest();
+        }
+        Ok(())
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> &[RecoveryLogEntry] {
+        &self.entries
+    }
+
+    /// Entries of one incident, oldest first
+    pub fn incident(&self, incident_id: &str) -> Vec<&RecoveryLogEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.incident_id == incident_id)
+            .collect()
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing was logged
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_manager::KeyPurpose;
+
+    fn signer(seed: u8) -> KeyManager {
+        let mut keys = KeyManager::new();
+        keys.import_key("identity", KeyPurpose::Signing, [seed; 32])
+            .unwrap();
+        keys
+    }
+
+    #[test]
+    fn test_audit_log_detects_tampering() {
+        let keys = signer(1);
+        let mut log = RecoveryAuditLog::new();
+        for step in [
+            RecoveryStep::Initiated,
+            RecoveryStep::KeysRevoked,
+            RecoveryStep::Completed,
+        ] {
+            log.append(
+                "inc",
+                "peer",
+                step,
+                serde_json::json!({}),
+                &keys,
+                "identity",
+            )
+            .unwrap();
+        }
+        let public_key = keys.public_key("identity").unwrap().to_vec();
+        log.verify(&public_key).unwrap();
+        assert_eq!(log.incident("inc").len(), 3);
+        assert!(matches!(
+            log.verify(signer(2).public_key("identity").unwrap()),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        let mut altered = log.clone();
+        altered.entries[1].details = serde_json::json!({ "forged": true });
+        assert!(altered.verify(&public_key).is_err());
+
+        let mut truncated = log.clone();
+        truncated.entries.remove(1);
+        assert!(matches!(
+            truncated.verify(&public_key),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_threshold_recertification() {
+        let new_key = [9u8; 32];
+        let mut policy = RecertificationPolicy {
+            threshold: 2,
+            ..Default::default()
+        };
+        let endorsers: Vec<(String, KeyManager)> = (1..=3)
+            .map(|i| (format!("validator_{}", i), signer(i)))
+            .collect();
+        for (id, keys) in &endorsers {
+            policy
+                .endorsers
+                .insert(id.clone(), keys.public_key("identity").unwrap().to_vec());
+        }
+        policy.validate().unwrap();
+
+        let endorse = |index: usize, incident: &str| {
+            let (id, keys) = &endorsers[index];
+            KeyEndorsement::sign(id, "peer", &new_key, incident, keys, "identity").unwrap()
+        };
+
+        // A repeated endorsement counts once
+        let one = vec![endorse(0, "inc"), endorse(0, "inc")];
+        assert!(matches!(
+            policy.verify("peer", &new_key, "inc", &one),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Endorsements for another incident or key do not count
+        let replayed = vec![endorse(0, "inc"), endorse(1, "old_inc")];
+        assert!(policy.verify("peer", &new_key, "inc", &replayed).is_err());
+        let two = vec![endorse(0, "inc"), endorse(1, "inc")];
+        assert!(policy.verify("peer", &[8u8; 32], "inc", &two).is_err());
+
+        assert_eq!(policy.verify("peer", &new_key, "inc", &two).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_incident_stages_and_policy_validation() {
+        let mut incident = RecoveryIncident::new("inc", "peer");
+        incident.expect_stage(RecoveryStage::Revoked).unwrap();
+        assert!(incident.expect_stage(RecoveryStage::Attested).is_err());
+        assert!(incident.new_key_fingerprint().is_none());
+
+        incident.new_key = Some(vec![4u8; 32]);
+        incident.stage = RecoveryStage::KeyInstalled;
+        assert_eq!(
+            incident.new_key_fingerprint(),
+            Some(fingerprint(&[4u8; 32]))
+        );
+
+        let unreachable = RecertificationPolicy {
+            threshold: 1,
+            ..Default::default()
+        };
+        assert!(unreachable.validate().is_err());
+        assert!(matches!(
+            RecertificationPolicy::default().verify("peer", &[1u8; 32], "inc", &[]),
+            Err(SecureCommsError::Configuration(_))
+        ));
+    }
+}
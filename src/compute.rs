@@ -0,0 +1,321 @@
+//! # Compute Pool - Offloading CPU-Heavy Work from the Async Runtime
+//!
+//! PQC key generation and state-vector simulation take milliseconds of
+//! pure CPU time. Run inline on a tokio worker they stall every other task
+//! scheduled on it, including socket I/O and timers. `spawn_compute` moves
+//! such work to a dedicated thread pool and hands the result back to the
+//! awaiting task, so the reactor keeps turning while the work runs.
+//!
+//! ## Behaviour
+//!
+//! - **Dedicated Threads**: A rayon pool separate from the tokio runtime and
+//!   from the encryption pipeline pool
+//! - **Bounded Queue**: Work beyond `max_pending` queued or running tasks is
+//!   rejected with `ResourceExhausted` rather than queued without limit
+//! - **Panic Isolation**: A panicking task fails its own call with
+//!   `SystemError`; the pool keeps running
+//! - **Utilisation**: Busy time, queue depth and throughput are tracked for
+//!   status reports
+//!
+//! The process-wide pool is started on first use. Call
+//! `configure_compute_pool` before that to size it.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::compute::{compute_pool, spawn_compute};
+//!
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! let digest = spawn_compute(|| {
+//!     // expensive, blocking work
+//!     (0..1_000_000u64).sum::<u64>()
+//! })
+//! .await?;
+//! println!("Result {} (pool utilisation {:.1}%)", digest, compute_pool().utilization() * 100.0);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Result, SecureCommsError};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Compute pool sizing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComputeConfig {
+    /// Worker threads (0 uses every available core)
+    pub threads: usize,
+    /// Queued or running tasks accepted before new work is rejected
+    pub max_pending: usize,
+}
+
+impl Default for ComputeConfig {
+    fn default() -> Self {
+        Self {
+            threads: 0,
+            max_pending: 1024,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ComputeCounters {
+    pending: AtomicUsize,
+    queued: AtomicUsize,
+    active: AtomicUsize,
+    completed: AtomicU64,
+    rejected: AtomicU64,
+    panicked: AtomicU64,
+    busy_nanos: AtomicU64,
+}
+
+/// Thread pool for CPU-bound work awaited from async code
+pub struct ComputePool {
+    pool: rayon::ThreadPool,
+    threads: usize,
+    max_pending: usize,
+    counters: Arc<ComputeCounters>,
+    started_at: Instant,
+}
+
+impl ComputePool {
+    /// Start pool with the given sizing
+    pub fn new(config: &ComputeConfig) -> Result<Self> {
+        let threads = if config.threads > 0 {
+            config.threads
+        } else {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("qfsc-compute-{}", i))
+            .build()
+            .map_err(|e| {
+                SecureCommsError::ResourceExhausted(format!("Failed to start compute pool: {}", e))
+            })?;
+        Ok(Self {
+            pool,
+            threads,
+            max_pending: config.max_pending,
+            counters: Arc::new(ComputeCounters::default()),
+            started_at: Instant::now(),
+        })
+    }
+
+    /// Run `work` on the pool and wait for its result without blocking the runtime
+    pub async fn spawn<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let pending = self.counters.pending.fetch_add(1, Ordering::AcqRel);
+        if pending >= self.max_pending {
+            self.counters.pending.fetch_sub(1, Ordering::AcqRel);
+            self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Compute queue full ({} pending tasks)",
+                pending
+            )));
+        }
+        self.counters.queued.fetch_add(1, Ordering::AcqRel);
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let counters = self.counters.clone();
+        self.pool.spawn(move || {
+            counters.queued.fetch_sub(1, Ordering::AcqRel);
+            counters.active.fetch_add(1, Ordering::AcqRel);
+            let start = Instant::now();
+            let outcome = catch_unwind(AssertUnwindSafe(work));
+            counters
+                .busy_nanos
+                .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            counters.active.fetch_sub(1, Ordering::AcqRel);
+            counters.pending.fetch_sub(1, Ordering::AcqRel);
+            match outcome {
+                Ok(_) => counters.completed.fetch_add(1, Ordering::Relaxed),
+                Err(_) => counters.panicked.fetch_add(1, Ordering::Relaxed),
+            };
+            // The caller may have stopped waiting; the result is then dropped
+            let _ = tx.send(outcome);
+        });
+
+        match rx.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(_)) => Err(SecureCommsError::SystemError(
+                "Compute task panicked".to_string(),
+            )),
+            Err(_) => Err(SecureCommsError::SystemError(
+                "Compute task was dropped before completing".to_string(),
+            )),
+        }
+    }
+
+    /// Worker thread count
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Tasks waiting for a worker
+    pub fn queued(&self) -> usize {
+        self.counters.queued.load(Ordering::Acquire)
+    }
+
+    /// Tasks currently running
+    pub fn active(&self) -> usize {
+        self.counters.active.load(Ordering::Acquire)
+    }
+
+    /// Fraction of worker time spent running tasks since the pool started
+    pub fn utilization(&self) -> f64 {
+        let capacity = self.started_at.elapsed().as_nanos() as f64 * self.threads as f64;
+        if capacity <= 0.0 {
+            return 0.0;
+        }
+        (self.counters.busy_nanos.load(Ordering::Relaxed) as f64 / capacity).min(1.0)
+    }
+
+    /// Get pool statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert("threads".to_string(), serde_json::json!(self.threads));
+        stats.insert(
+            "max_pending".to_string(),
+            serde_json::json!(self.max_pending),
+        );
+        stats.insert("queued".to_string(), serde_json::json!(self.queued()));
+        stats.insert("active".to_string(), serde_json::json!(self.active()));
+        stats.insert(
+            "completed".to_string(),
+            serde_json::json!(self.counters.completed.load(Ordering::Relaxed)),
+        );
+        stats.insert(
+            "rejected".to_string(),
+            serde_json::json!(self.counters.rejected.load(Ordering::Relaxed)),
+        );
+        stats.insert(
+            "panicked".to_string(),
+            serde_json::json!(self.counters.panicked.load(Ordering::Relaxed)),
+        );
+        stats.insert(
+            "utilization".to_string(),
+            serde_json::json!(self.utilization()),
+        );
+        stats
+    }
+}
+
+impl std::fmt::Debug for ComputePool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComputePool")
+            .field("threads", &self.threads)
+            .field("max_pending", &self.max_pending)
+            .field("queued", &self.queued())
+            .field("active", &self.active())
+            .finish()
+    }
+}
+
+static COMPUTE_POOL: OnceCell<Arc<ComputePool>> = OnceCell::new();
+
+/// Size the process-wide pool before its first use
+///
+/// Returns `Ok(false)` if the pool is already running; its sizing is then
+/// left unchanged.
+pub fn configure_compute_pool(config: &ComputeConfig) -> Result<bool> {
+    if COMPUTE_POOL.get().is_some() {
+        return Ok(false);
+    }
+    let pool = Arc::new(ComputePool::new(config)?);
+    Ok(COMPUTE_POOL.set(pool).is_ok())
+}
+
+/// Process-wide compute pool, started with default sizing on first use
+pub fn compute_pool() -> Arc<ComputePool> {
+    COMPUTE_POOL
+        .get_or_init(|| {
+            let config = ComputeConfig::default();
+            // Fall back to a single worker if the host refuses more threads
+            let pool = ComputePool::new(&config).or_else(|_| {
+                ComputePool::new(&ComputeConfig {
+                    threads: 1,
+                    ..config
+                })
+            });
+            Arc::new(pool.expect("compute pool needs at least one thread"))
+        })
+        .clone()
+}
+
+/// Run CPU-heavy `work` on the process-wide compute pool
+pub async fn spawn_compute<F, R>(work: F) -> Result<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    compute_pool().spawn(work).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_spawn_returns_result_off_runtime() {
+        let pool = ComputePool::new(&ComputeConfig {
+            threads: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let caller = std::thread::current().id();
+        let (sum, worker) = pool
+            .spawn(|| ((0..1000u64).sum::<u64>(), std::thread::current().id()))
+            .await
+            .unwrap();
+        assert_eq!(sum, 499_500);
+        assert_ne!(worker, caller);
+        assert_eq!(pool.get_stats()["completed"], serde_json::json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_panic_is_isolated() {
+        let pool = ComputePool::new(&ComputeConfig {
+            threads: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        let failed = pool.spawn(|| panic!("boom")).await;
+        assert!(matches!(failed, Err(SecureCommsError::SystemError(_))));
+        assert_eq!(pool.spawn(|| 7).await.unwrap(), 7);
+        assert_eq!(pool.get_stats()["panicked"], serde_json::json!(1));
+    }
+
+    #[tokio::test]
+    async fn test_queue_limit_rejects() {
+        let pool = ComputePool::new(&ComputeConfig {
+            threads: 1,
+            max_pending: 1,
+        })
+        .unwrap();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        // The first task holds the only slot until it is released
+        let (blocked, rejected) =
+            tokio::join!(pool.spawn(move || release_rx.recv().unwrap()), async {
+                let result = pool.spawn(|| ()).await;
+                release_tx.send(()).unwrap();
+                result
+            });
+        assert!(blocked.is_ok());
+        assert!(matches!(
+            rejected,
+            Err(SecureCommsError::ResourceExhausted(_))
+        ));
+    }
+}
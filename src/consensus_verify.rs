@@ -12,12 +12,20 @@
 //! - **Integrity Hash**: SHA-3 based message integrity verification
 //! - **Multi-Factor Verification**: Combined cryptographic and quantum validation
 //! - **Consensus Validation**: Streamlined consensus for critical operations
+//! - **Pluggable Verifiers**: `Verifier` trait and registry selecting verifiers per proposal type
 //!
 //! ### Consensus Engine
 //! - **Streamlined Consensus**: Simplified consensus for single-peer operations
 //! - **Validator Management**: Dynamic validator registration and trust scoring
 //! - **Proposal Tracking**: Comprehensive proposal lifecycle management
 //! - **Vote Collection**: Secure vote aggregation and validation
+//! - **Proposal Lifecycle API**: Create, sign, submit, query and audit proposals and decisions
+//! - **Write-Ahead Log**: Proposals, votes and decisions persisted and restored after restarts
+//! - **Commit Certificates**: Ed25519-signed votes bundled for light-client verification
+//! - **Equivocation Detection**: Conflicting signed votes or proposals recorded as evidence
+//! - **VRF Leader Election**: Round proposers proven eligible with VRF proofs over the quantum beacon
+//! - **State Sync Support**: Certified decisions exported and applied for lagging validators
+//! - **Weighted Quorum**: Validator weights and per-proposal-type supermajority rules
 //!
 //! ### Performance Characteristics
 //! - **Verification Time**: <10ms for cryptographic signatures
@@ -49,7 +57,7 @@
 //!
 //! ### Basic Consensus Engine Setup
 //! ```rust,no_run
-//! use streamlined_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig};
+//! use quantum_forge_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -62,7 +70,7 @@
 //!
 //! ### Data Verification
 //! ```rust,no_run
-//! # use streamlined_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig, VerificationMethod};
+//! # use quantum_forge_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig, VerificationMethod};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = ConsensusConfig::default();
@@ -79,7 +87,7 @@
 //!
 //! ### Consensus Proposal
 //! ```rust,no_run
-//! # use streamlined_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig, VoteType};
+//! # use quantum_forge_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig, VerificationMethod, VoteType};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = ConsensusConfig::default();
@@ -88,7 +96,7 @@
 //! let data = b"proposal data";
 //! let signature = vec![0u8; 64]; // Mock signature
 //! 
-//! let proposal_id = consensus.create_proposal("proposer".to_string(), data.to_vec(), signature)?;
+//! let proposal_id = consensus.create_proposal("proposer".to_string(), data.to_vec(), signature.clone())?;
 //! 
 //! // Submit a vote on the proposal
 //! let verification_result = consensus.verify_data(data, &signature, VerificationMethod::CryptographicSignature).await?;
@@ -99,7 +107,7 @@
 //!
 //! ### Comprehensive Verification
 //! ```rust,no_run
-//! # use streamlined_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig};
+//! # use quantum_forge_secure_comms::consensus_verify::{ConsensusEngine, ConsensusConfig};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = ConsensusConfig::default();
@@ -114,6 +122,68 @@
 //! # }
 //! ```
 //!
+//! ### Proposal Lifecycle
+//! ```rust,no_run
+//! # use quantum_forge_secure_comms::consensus_verify::*;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut consensus =
+//!     ConsensusEngine::with_wal("validator".to_string(), ConsensusConfig::default(), "data/consensus.wal").await?;
+//!
+//! let proposal = Proposal::new("validator", b"block #42".to_vec()).with_signature(vec![7u8; 64]);
+//! let proposal_id = consensus.submit_proposal(proposal)?;
+//!
+//! // After finalization (possibly after a restart)
+//! if let Some(decision) = consensus.get_decision(&proposal_id) {
+//!     println!("{:?} with {} approvals", decision.status, decision.approve_count);
+//! }
+//! let approved = consensus.query_proposals(&ProposalQuery {
+//!     status: Some(ConsensusStatus::Approved),
+//!     ..ProposalQuery::default()
+//! });
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### Light-Client Verification
+//! ```rust,no_run
+//! # use quantum_forge_secure_comms::consensus_verify::*;
+//! # fn check(certificate_bytes: &[u8], validators: &[ValidatorInfo]) -> quantum_forge_secure_comms::Result<()> {
+//! // A light client only needs the validator set to check a decision
+//! let validator_set = ValidatorSet::from_validators(validators);
+//! let certificate = CommitCertificate::from_bytes(certificate_bytes)?;
+//! certificate.verify(&validator_set)?;
+//! println!("{} is {:?}", certificate.proposal_id, certificate.status);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### Custom Verifiers
+//! ```rust,no_run
+//! # use quantum_forge_secure_comms::consensus_verify::*;
+//! # use quantum_forge_secure_comms::Result;
+//! struct ZkProofVerifier;
+//!
+//! impl Verifier for ZkProofVerifier {
+//!     fn name(&self) -> &str {
+//!         "zk_proof"
+//!     }
+//!
+//!     fn verify(&self, data: &[u8], proof: &[u8]) -> Result<VerificationResult> {
+//!         let verified = !data.is_empty() && proof.len() == 192;
+//!         Ok(VerificationResult::custom(verified, if verified { 1.0 } else { 0.0 }, 0))
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! let mut consensus = ConsensusEngine::new("validator".to_string(), ConsensusConfig::default()).await?;
+//! consensus.register_verifier(std::sync::Arc::new(ZkProofVerifier));
+//! consensus.set_proposal_type_verifiers("rollup_batch", &["zk_proof", "hash_based"])?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Security Architecture
 //!
 //! ### Verification Methods
@@ -148,10 +218,25 @@
 //! - **Consensus Decision**: <10ms for threshold calculation
 //! - **Memory Usage**: <1MB for complete consensus state
 
+use crate::consensus_wal::{ConsensusWal, WalRecord};
+use crate::constant_time::ct_eq;
+use crate::envelope::EnvelopeCipher;
+use crate::equivocation::{
+    EquivocationDetector, EquivocationEvidence, EquivocationKind, EvidenceQuery, SignedStatement,
+};
+use crate::events::{ClientEvent, EventBus};
+use crate::leader_election::{LeaderElection, LeaderProof};
+use crate::state_sync::SyncEntry;
+use crate::crypto_protocols::PQC;
+use crate::peer_reputation::ReputationManager;
 use crate::performance::PerformanceMetrics;
+use crate::quorum::{QuorumConfig, QuorumOutcome, QuorumRule, QuorumTally};
 use crate::{Result, SecureCommsError};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 
 /// Verification result for messages or operations
@@ -176,6 +261,23 @@ impl std::fmt::Display for VerificationResult {
     }
 }
 
+impl VerificationResult {
+    /// Build a result produced by an application-defined verifier
+    pub fn custom(verified: bool, confidence: f64, verification_time_ms: u64) -> Self {
+        Self {
+            verified,
+            confidence: confidence.clamp(0.0, 1.0),
+            verification_time_ms,
+            verification_method: VerificationMethod::Custom,
+            error_details: if verified {
+                None
+            } else {
+                Some("Custom verification failed".to_string())
+            },
+        }
+    }
+}
+
 impl std::ops::Not for VerificationResult {
     type Output = bool;
 
@@ -201,6 +303,281 @@ pub enum VerificationMethod {
     QuantumResistant,
     /// Integrity check
     IntegrityCheck,
+    /// Application-defined verifier from the registry
+    Custom,
+}
+
+/// Proposal type used when none is specified
+pub const DEFAULT_PROPOSAL_TYPE: &str = "default";
+
+/// Pluggable verification method for the consensus pipeline
+///
+/// Implementations must be deterministic for a given input so that all
+/// validators reach the same verdict.
+pub trait Verifier: Send + Sync {
+    /// Unique registry name
+    fn name(&self) -> &str;
+
+    /// Verify data against its signature or proof
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<VerificationResult>;
+}
+
+/// Digital signature verifier
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DigitalSignatureVerifier;
+
+impl Verifier for DigitalSignatureVerifier {
+    fn name(&self) -> &str {
+        "digital_signature"
+    }
+
+    fn verify(&self, _data: &[u8], signature: &[u8]) -> Result<VerificationResult> {
+        Ok(signature_check(signature, Instant::now()))
+    }
+}
+
+/// SHA-3 hash-based integrity verifier
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashBasedVerifier;
+
+impl Verifier for HashBasedVerifier {
+    fn name(&self) -> &str {
+        "hash_based"
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<VerificationResult> {
+        Ok(integrity_hash_check(data, signature, Instant::now()))
+    }
+}
+
+/// Quantum-enhanced verifier combining signature and quantum state validation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuantumEnhancedVerifier;
+
+impl Verifier for QuantumEnhancedVerifier {
+    fn name(&self) -> &str {
+        "quantum_enhanced"
+    }
+
+    fn verify(&self, data: &[u8], signature: &[u8]) -> Result<VerificationResult> {
+        let start_time = Instant::now();
+        let signature_result = signature_check(signature, start_time);
+        let verified = signature_result.verified && !data.is_empty();
+        Ok(VerificationResult {
+            verified,
+            confidence: if verified { signature_result.confidence } else { 0.0 },
+            verification_time_ms: start_time.elapsed().as_millis() as u64,
+            verification_method: VerificationMethod::QuantumState,
+            error_details: signature_result.error_details,
+        })
+    }
+}
+
+/// Registry of verifiers and their assignment to proposal types
+#[derive(Clone)]
+pub struct VerifierRegistry {
+    /// Registered verifiers by name
+    verifiers: HashMap<String, Arc<dyn Verifier>>,
+    /// Verifier names required per proposal type
+    proposal_types: HashMap<String, Vec<String>>,
+}
+
+impl std::fmt::Debug for VerifierRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerifierRegistry")
+            .field("verifiers", &self.verifier_names())
+            .field("proposal_types", &self.proposal_types)
+            .finish()
+    }
+}
+
+impl Default for VerifierRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VerifierRegistry {
+    /// Create registry with the built-in verifiers
+    ///
+    /// The default proposal type requires `digital_signature` and `hash_based`.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            verifiers: HashMap::new(),
+            proposal_types: HashMap::new(),
+        };
+        registry.register(Arc::new(DigitalSignatureVerifier));
+        registry.register(Arc::new(HashBasedVerifier));
+        registry.register(Arc::new(QuantumEnhancedVerifier));
+        registry.proposal_types.insert(
+            DEFAULT_PROPOSAL_TYPE.to_string(),
+            vec!["digital_signature".to_string(), "hash_based".to_string()],
+        );
+        registry
+    }
+
+    /// Register or replace a verifier
+    pub fn register(&mut self, verifier: Arc<dyn Verifier>) {
+        self.verifiers.insert(verifier.name().to_string(), verifier);
+    }
+
+    /// Remove a verifier; fails while a proposal type still requires it
+    pub fn unregister(&mut self, name: &str) -> Result<()> {
+        if let Some((proposal_type, _)) = self
+            .proposal_types
+            .iter()
+            .find(|(_, names)| names.iter().any(|n| n == name))
+        {
+            return Err(SecureCommsError::Configuration(format!(
+                "Verifier {} is required by proposal type {}",
+                name, proposal_type
+            )));
+        }
+        self.verifiers.remove(name);
+        Ok(())
+    }
+
+    /// Get a verifier by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Verifier>> {
+        self.verifiers.get(name).cloned()
+    }
+
+    /// Names of registered verifiers, sorted
+    pub fn verifier_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.verifiers.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Assign the verifiers required for a proposal type
+    pub fn set_proposal_type(&mut self, proposal_type: &str, verifier_names: &[&str]) -> Result<()> {
+        if verifier_names.is_empty() {
+            return Err(SecureCommsError::Configuration(format!(
+                "Proposal type {} needs at least one verifier",
+                proposal_type
+            )));
+        }
+        if let Some(unknown) = verifier_names
+            .iter()
+            .find(|name| !self.verifiers.contains_key(**name))
+        {
+            return Err(SecureCommsError::Configuration(format!(
+                "Unknown verifier {}",
+                unknown
+            )));
+        }
+
+        self.proposal_types.insert(
+            proposal_type.to_string(),
+            verifier_names.iter().map(|name| name.to_string()).collect(),
+        );
+        Ok(())
+    }
+
+    /// Verifier names used for a proposal type (falls back to the default type)
+    pub fn verifiers_for(&self, proposal_type: &str) -> Vec<String> {
+        self.proposal_types
+            .get(proposal_type)
+            .or_else(|| self.proposal_types.get(DEFAULT_PROPOSAL_TYPE))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Run every verifier required by a proposal type
+    ///
+    /// The combined result passes only if all verifiers pass; its confidence
+    /// is the lowest individual confidence.
+    pub fn verify(&self, proposal_type: &str, data: &[u8], signature: &[u8]) -> Result<VerificationResult> {
+        let start_time = Instant::now();
+        let names = self.verifiers_for(proposal_type);
+        if names.is_empty() {
+            return Err(SecureCommsError::Configuration(format!(
+                "No verifiers configured for proposal type {}",
+                proposal_type
+            )));
+        }
+
+        let mut verified = true;
+        let mut confidence: f64 = 1.0;
+        let mut failures = Vec::new();
+        for name in &names {
+            let verifier = self.verifiers.get(name).ok_or_else(|| {
+                SecureCommsError::Configuration(format!("Unknown verifier {}", name))
+            })?;
+            let result = verifier.verify(data, signature)?;
+            if !result.verified {
+                verified = false;
+                failures.push(name.clone());
+            }
+            confidence = confidence.min(result.confidence);
+        }
+
+        Ok(VerificationResult {
+            verified,
+            confidence: if verified { confidence } else { 0.0 },
+            verification_time_ms: start_time.elapsed().as_millis() as u64,
+            verification_method: if names.len() > 1 {
+                VerificationMethod::MultiFactor
+            } else {
+                VerificationMethod::Custom
+            },
+            error_details: if failures.is_empty() {
+                None
+            } else {
+                Some(format!("Failed verifiers: {}", failures.join(", ")))
+            },
+        })
+    }
+}
+
+/// Signature shape check shared by `verify_data` and the signature verifier
+fn signature_check(signature: &[u8], start_time: Instant) -> VerificationResult {
+    let method = VerificationMethod::CryptographicSignature;
+    // Fast-path optimization for signatures with benchmark/test support
+    if signature.len() < 32 {
+        VerificationResult {
+            verified: false,
+            confidence: 0.0,
+            verification_time_ms: start_time.elapsed().as_millis() as u64,
+            verification_method: method,
+            error_details: Some("Signature too short".to_string()),
+        }
+    } else {
+        // Optimized signature verification - more permissive for benchmarks/tests
+        let is_valid = !signature.iter().all(|&b| b == 0) ||
+            signature.len() == 64; // Standard signature length
+
+        VerificationResult {
+            verified: is_valid,
+            confidence: if is_valid { 0.95 } else { 0.0 },
+            verification_time_ms: start_time.elapsed().as_millis() as u64,
+            verification_method: method,
+            error_details: None,
+        }
+    }
+}
+
+/// SHA-3 prefix check shared by `verify_data` and the hash-based verifier
+fn integrity_hash_check(data: &[u8], signature: &[u8], start_time: Instant) -> VerificationResult {
+    use sha3::{Digest, Sha3_256};
+    let mut hasher = Sha3_256::new();
+    hasher.update(data);
+    let computed_hash = hasher.finalize();
+
+    // Compare first 8 bytes for fast verification
+    let is_valid = if signature.len() >= 8 && computed_hash.len() >= 8 {
+        ct_eq(&signature[..8], &computed_hash[..8])
+    } else {
+        false
+    };
+
+    VerificationResult {
+        verified: is_valid,
+        confidence: if is_valid { 0.99 } else { 0.0 },
+        verification_time_ms: start_time.elapsed().as_millis() as u64,
+        verification_method: VerificationMethod::IntegrityHash,
+        error_details: None,
+    }
 }
 
 /// Consensus configuration for the streamlined system
@@ -216,6 +593,10 @@ pub struct ConsensusConfig {
     pub fast_consensus: bool,
     /// Verification methods to use
     pub verification_methods: Vec<VerificationMethod>,
+    /// Explicit weighted quorum rules; derived from `min_validators` and
+    /// `consensus_threshold` when unset
+    #[serde(default)]
+    pub quorum: Option<QuorumConfig>,
 }
 
 impl Default for ConsensusConfig {
@@ -229,10 +610,38 @@ impl Default for ConsensusConfig {
                 VerificationMethod::CryptographicSignature,
                 VerificationMethod::IntegrityHash,
             ],
+            quorum: None,
         }
     }
 }
 
+impl ConsensusConfig {
+    /// Quorum rules in force, derived from the legacy threshold if not set
+    pub fn quorum_config(&self) -> QuorumConfig {
+        match &self.quorum {
+            Some(quorum) => quorum.clone(),
+            None => QuorumConfig {
+                default_rule: QuorumRule::from_threshold(
+                    self.min_validators,
+                    self.consensus_threshold,
+                ),
+                ..QuorumConfig::default()
+            },
+        }
+    }
+
+    /// Validate the configuration, including quorum rules
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.consensus_threshold) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Consensus threshold {} outside 0.0..=1.0",
+                self.consensus_threshold
+            )));
+        }
+        self.quorum_config().validate()
+    }
+}
+
 /// Consensus proposal for validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusProposal {
@@ -242,6 +651,19 @@ pub struct ConsensusProposal {
     pub signature: Vec<u8>,
     pub timestamp: u64,
     pub verification_requirements: Vec<VerificationMethod>,
+    /// Proposal type selecting the verifiers from the registry
+    #[serde(default = "default_proposal_type")]
+    pub proposal_type: String,
+    /// Proposal round, when the proposer is bound to one proposal per round
+    #[serde(default)]
+    pub round: Option<u64>,
+    /// VRF proof of the proposer's eligibility for `round`
+    #[serde(default)]
+    pub leader_proof: Option<LeaderProof>,
+}
+
+fn default_proposal_type() -> String {
+    DEFAULT_PROPOSAL_TYPE.to_string()
 }
 
 /// Vote on a consensus proposal
@@ -252,6 +674,9 @@ pub struct ConsensusVote {
     pub vote: VoteType,
     pub verification_result: VerificationResult,
     pub timestamp: u64,
+    /// Ed25519 signature over the commit vote message, if the vote was signed
+    #[serde(default)]
+    pub signature: Option<Vec<u8>>,
 }
 
 /// Type of vote
@@ -284,6 +709,417 @@ pub enum ConsensusStatus {
     Failed,
 }
 
+impl ConsensusStatus {
+    /// Whether the status is final
+    pub fn is_final(&self) -> bool {
+        !matches!(self, ConsensusStatus::Pending | ConsensusStatus::InProgress)
+    }
+}
+
+/// Proposal under construction, signed before submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    /// Proposing validator
+    pub proposer_id: String,
+    /// Proposal type selecting the verifier pipeline
+    pub proposal_type: String,
+    /// Proposal payload
+    pub data: Vec<u8>,
+    /// Proposal round; proposers may sign only one proposal per type and round
+    #[serde(default)]
+    pub round: Option<u64>,
+    /// VRF proof of the proposer's eligibility for `round`
+    #[serde(default)]
+    pub leader_proof: Option<LeaderProof>,
+    /// Signature over `signing_payload`, set by `sign` or `with_signature`
+    pub signature: Option<Vec<u8>>,
+}
+
+impl Proposal {
+    /// Create unsigned proposal of the default type
+    pub fn new(proposer_id: &str, data: Vec<u8>) -> Self {
+        Self {
+            proposer_id: proposer_id.to_string(),
+            proposal_type: DEFAULT_PROPOSAL_TYPE.to_string(),
+            data,
+            round: None,
+            leader_proof: None,
+            signature: None,
+        }
+    }
+
+    /// Set the proposal round
+    pub fn with_round(mut self, round: u64) -> Self {
+        self.round = Some(round);
+        self
+    }
+
+    /// Attach the proposer's leader election claim for the proposal round
+    pub fn with_leader_proof(mut self, leader_proof: LeaderProof) -> Self {
+        self.round = Some(leader_proof.round);
+        self.leader_proof = Some(leader_proof);
+        self
+    }
+
+    /// Set the proposal type
+    pub fn with_type(mut self, proposal_type: &str) -> Self {
+        self.proposal_type = proposal_type.to_string();
+        self
+    }
+
+    /// Attach an externally produced signature
+    pub fn with_signature(mut self, signature: Vec<u8>) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Bytes covered by the proposal signature
+    pub fn signing_payload(&self) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"consensus_proposal_v1");
+        hasher.update(self.proposer_id.as_bytes());
+        hasher.update(self.proposal_type.as_bytes());
+        if let Some(round) = self.round {
+            hasher.update(round.to_le_bytes());
+        }
+        if let Some(leader_proof) = &self.leader_proof {
+            hasher.update(&leader_proof.output);
+        }
+        hasher.update(&self.data);
+        hasher.finalize().to_vec()
+    }
+
+    /// Sign the proposal with the proposer's PQC private key
+    pub fn sign(mut self, pqc: &mut PQC, private_key: &[u8]) -> Result<Self> {
+        self.signature = Some(pqc.sign(private_key, &self.signing_payload())?);
+        Ok(self)
+    }
+
+    /// Sign the proposal with the proposer's Ed25519 validator key
+    ///
+    /// Ed25519-signed proposals with a round can be checked for equivocation.
+    pub fn sign_ed25519(mut self, signing_key: &SigningKey) -> Self {
+        self.signature = Some(signing_key.sign(&self.signing_payload()).to_bytes().to_vec());
+        self
+    }
+}
+
+/// Evidence of the votes behind a decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteProof {
+    /// Proposal the votes refer to
+    pub proposal_id: String,
+    /// SHA-3 hash of the proposal data
+    pub data_hash: Vec<u8>,
+    /// Votes sorted by voter ID
+    pub votes: Vec<ConsensusVote>,
+    /// SHA-3 digest binding proposal and votes
+    pub digest: Vec<u8>,
+}
+
+impl VoteProof {
+    /// Build a proof from a proposal's votes
+    pub fn build(proposal: &ConsensusProposal, votes: &HashMap<String, ConsensusVote>) -> Self {
+        use sha3::{Digest, Sha3_256};
+        let data_hash = Sha3_256::digest(&proposal.data).to_vec();
+        let mut votes: Vec<ConsensusVote> = votes.values().cloned().collect();
+        votes.sort_by(|a, b| a.voter_id.cmp(&b.voter_id));
+        let digest = Self::compute_digest(&proposal.proposal_id, &data_hash, &votes);
+        Self {
+            proposal_id: proposal.proposal_id.clone(),
+            data_hash,
+            votes,
+            digest,
+        }
+    }
+
+    /// Check that the digest matches the contained votes
+    pub fn verify(&self) -> bool {
+        Self::compute_digest(&self.proposal_id, &self.data_hash, &self.votes) == self.digest
+    }
+
+    fn compute_digest(proposal_id: &str, data_hash: &[u8], votes: &[ConsensusVote]) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(proposal_id.as_bytes());
+        hasher.update(data_hash);
+        for vote in votes {
+            hasher.update(vote.voter_id.as_bytes());
+            hasher.update([vote.vote as u8]);
+            hasher.update([vote.verification_result.verified as u8]);
+            hasher.update(vote.timestamp.to_le_bytes());
+        }
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Final outcome of a proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalDecision {
+    /// Decided proposal
+    pub proposal_id: String,
+    /// Final status
+    pub status: ConsensusStatus,
+    /// Approving votes
+    pub approve_count: usize,
+    /// Rejecting votes
+    pub reject_count: usize,
+    /// Abstaining votes
+    pub abstain_count: usize,
+    /// Threshold in force when the decision was made
+    pub threshold: f64,
+    /// Decision time (Unix seconds)
+    pub finalized_at: u64,
+    /// Votes behind the decision
+    pub vote_proof: VoteProof,
+}
+
+/// Proposal with its votes and outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalRecord {
+    /// Submitted proposal
+    pub proposal: ConsensusProposal,
+    /// Current status
+    pub status: ConsensusStatus,
+    /// Votes sorted by voter ID
+    pub votes: Vec<ConsensusVote>,
+    /// Session creation time (Unix seconds)
+    pub created_at: u64,
+    /// Final decision, once reached
+    pub decision: Option<ProposalDecision>,
+}
+
+/// Filter for proposal queries; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProposalQuery {
+    /// Match status
+    pub status: Option<ConsensusStatus>,
+    /// Match proposer
+    pub proposer_id: Option<String>,
+    /// Match proposal type
+    pub proposal_type: Option<String>,
+    /// Created at or after (Unix seconds)
+    pub since: Option<u64>,
+    /// Maximum results, newest first
+    pub limit: Option<usize>,
+}
+
+/// Message a validator signs when voting on a proposal
+pub fn commit_vote_message(proposal_id: &str, data_hash: &[u8], vote: VoteType) -> Vec<u8> {
+    use sha3::{Digest, Sha3_256};
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"commit_vote_v1");
+    hasher.update(proposal_id.as_bytes());
+    hasher.update(data_hash);
+    hasher.update([vote as u8]);
+    hasher.finalize().to_vec()
+}
+
+/// Sign a commit vote with a validator's Ed25519 key
+pub fn sign_commit_vote(
+    signing_key: &SigningKey,
+    proposal_id: &str,
+    proposal_data: &[u8],
+    vote: VoteType,
+) -> Vec<u8> {
+    use sha3::{Digest, Sha3_256};
+    let data_hash = Sha3_256::digest(proposal_data);
+    let message = commit_vote_message(proposal_id, &data_hash, vote);
+    signing_key.sign(&message).to_bytes().to_vec()
+}
+
+/// Verify an Ed25519 signature against a raw public key
+pub(crate) fn verify_commit_signature(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let key_bytes: [u8; 32] = match public_key.try_into() {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    let verifying_key = match VerifyingKey::from_bytes(&key_bytes) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let signature = match Signature::from_slice(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    verifying_key.verify_strict(message, &signature).is_ok()
+}
+
+/// Validator public keys known to a light client
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    /// Ed25519 public keys by validator ID
+    members: BTreeMap<String, Vec<u8>>,
+}
+
+impl ValidatorSet {
+    /// Create empty validator set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from active validators
+    pub fn from_validators<'a, I>(validators: I) -> Self
+    where
+        I: IntoIterator<Item = &'a ValidatorInfo>,
+    {
+        let mut set = Self::new();
+        for validator in validators.into_iter().filter(|v| v.is_active) {
+            set.add(&validator.validator_id, validator.public_key.clone());
+        }
+        set
+    }
+
+    /// Add or replace a validator key
+    pub fn add(&mut self, validator_id: &str, public_key: Vec<u8>) {
+        self.members.insert(validator_id.to_string(), public_key);
+    }
+
+    /// Public key of a validator
+    pub fn public_key(&self, validator_id: &str) -> Option<&[u8]> {
+        self.members.get(validator_id).map(|key| key.as_slice())
+    }
+
+    /// Number of validators
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// SHA-3 commitment to the set, binding certificates to it
+    pub fn hash(&self) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        for (validator_id, public_key) in &self.members {
+            hasher.update((validator_id.len() as u32).to_le_bytes());
+            hasher.update(validator_id.as_bytes());
+            hasher.update(public_key);
+        }
+        hasher.finalize().to_vec()
+    }
+}
+
+/// One validator's signed vote inside a certificate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitSignature {
+    /// Signing validator
+    pub validator_id: String,
+    /// Vote cast
+    pub vote: VoteType,
+    /// Ed25519 signature over `commit_vote_message`
+    pub signature: Vec<u8>,
+}
+
+/// Compact, self-contained proof of a consensus decision
+///
+/// Light clients verify it with nothing but the validator set: every
+/// signature is checked against the set and the signed votes alone must
+/// meet the threshold relative to the full set size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitCertificate {
+    /// Decided proposal
+    pub proposal_id: String,
+    /// SHA-3 hash of the proposal data
+    pub data_hash: Vec<u8>,
+    /// Final status (Approved or Rejected)
+    pub status: ConsensusStatus,
+    /// Consensus threshold used
+    pub threshold: f64,
+    /// Hash of the validator set the certificate was built for
+    pub validator_set_hash: Vec<u8>,
+    /// Signed votes, sorted by validator ID
+    pub signatures: Vec<CommitSignature>,
+}
+
+impl CommitCertificate {
+    /// Verify the certificate against a validator set
+    pub fn verify(&self, validator_set: &ValidatorSet) -> Result<()> {
+        if validator_set.is_empty() {
+            return Err(SecureCommsError::ConsensusVerify(
+                "Empty validator set".to_string(),
+            ));
+        }
+        if self.validator_set_hash != validator_set.hash() {
+            return Err(SecureCommsError::ConsensusVerify(
+                "Certificate was issued for a different validator set".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut approvals = 0usize;
+        let mut rejections = 0usize;
+        for entry in &self.signatures {
+            if !seen.insert(entry.validator_id.as_str()) {
+                return Err(SecureCommsError::ConsensusVerify(format!(
+                    "Duplicate signature from {}",
+                    entry.validator_id
+                )));
+            }
+            let public_key = validator_set.public_key(&entry.validator_id).ok_or_else(|| {
+                SecureCommsError::ConsensusVerify(format!(
+                    "{} is not in the validator set",
+                    entry.validator_id
+                ))
+            })?;
+            let message = commit_vote_message(&self.proposal_id, &self.data_hash, entry.vote);
+            if !verify_commit_signature(public_key, &message, &entry.signature) {
+                return Err(SecureCommsError::ConsensusVerify(format!(
+                    "Invalid signature from {}",
+                    entry.validator_id
+                )));
+            }
+            match entry.vote {
+                VoteType::Approve => approvals += 1,
+                VoteType::Reject => rejections += 1,
+                VoteType::Abstain => {}
+            }
+        }
+
+        let set_size = validator_set.len() as f64;
+        let reached = match self.status {
+            ConsensusStatus::Approved => approvals as f64 / set_size >= self.threshold,
+            ConsensusStatus::Rejected => rejections as f64 / set_size > 1.0 - self.threshold,
+            _ => false,
+        };
+        if !reached {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Certificate for {} does not reach the {:?} threshold",
+                self.proposal_id, self.status
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verify that the certificate covers the given proposal data
+    pub fn verify_data(&self, proposal_data: &[u8], validator_set: &ValidatorSet) -> Result<()> {
+        use sha3::{Digest, Sha3_256};
+        if Sha3_256::digest(proposal_data).as_slice() != self.data_hash.as_slice() {
+            return Err(SecureCommsError::ConsensusVerify(
+                "Proposal data does not match certificate".to_string(),
+            ));
+        }
+        self.verify(validator_set)
+    }
+
+    /// Serialize for transport to light clients
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| {
+            SecureCommsError::ConsensusVerify(format!("Certificate serialization failed: {}", e))
+        })
+    }
+
+    /// Deserialize a certificate
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| SecureCommsError::ConsensusVerify(format!("Invalid certificate: {}", e)))
+    }
+}
+
 /// Validator information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorInfo {
@@ -294,81 +1130,717 @@ pub struct ValidatorInfo {
     pub last_activity: u64,
 }
 
-/// Main consensus engine for streamlined verification
-pub struct ConsensusEngine {
-    /// Active consensus sessions
-    sessions: HashMap<String, ConsensusSession>,
-    /// Registered validators
-    validators: HashMap<String, ValidatorInfo>,
-    /// Consensus configuration
-    config: ConsensusConfig,
-    /// Performance metrics
-    metrics: PerformanceMetrics,
-    /// Local validator ID
-    local_validator_id: String,
-}
+/// Main consensus engine for streamlined verification
+pub struct ConsensusEngine {
+    /// Active consensus sessions
+    sessions: HashMap<String, ConsensusSession>,
+    /// Registered validators
+    validators: HashMap<String, ValidatorInfo>,
+    quorum: QuorumConfig,
+    /// Consensus configuration
+    config: ConsensusConfig,
+    /// Performance metrics
+    metrics: PerformanceMetrics,
+    /// Local validator ID
+    local_validator_id: String,
+    /// Pluggable verifiers selected per proposal type
+    verifiers: VerifierRegistry,
+    /// Final decisions, retained after session cleanup
+    decisions: HashMap<String, ProposalDecision>,
+    /// Optional write-ahead log for durable history
+    wal: Option<ConsensusWal>,
+    /// Conflicting signed statements and their evidence
+    equivocation: EquivocationDetector,
+    /// VRF proposer selection, when round proposers are elected
+    leader_election: Option<LeaderElection>,
+    /// Event bus notified when proposals are committed
+    events: Option<EventBus>,
+}
+
+impl ConsensusEngine {
+    /// Create new consensus engine
+    pub async fn new(local_validator_id: String, config: ConsensusConfig) -> Result<Self> {
+        let start_time = Instant::now();
+        config.validate()?;
+        let quorum = config.quorum_config();
+
+        let mut metrics = PerformanceMetrics::new();
+        metrics.consensus_verify_ms = start_time.elapsed().as_millis() as u64;
+
+        Ok(Self {
+            sessions: HashMap::new(),
+            validators: HashMap::new(),
+            config,
+            quorum,
+            metrics,
+            local_validator_id,
+            verifiers: VerifierRegistry::new(),
+            decisions: HashMap::new(),
+            wal: None,
+            equivocation: EquivocationDetector::default(),
+            leader_election: None,
+            events: None,
+        })
+    }
+
+    /// Create consensus engine backed by a write-ahead log
+    ///
+    /// Existing WAL records are replayed so proposals, votes and decisions
+    /// survive restarts.
+    pub async fn with_wal<P: AsRef<Path>>(
+        local_validator_id: String,
+        config: ConsensusConfig,
+        wal_path: P,
+    ) -> Result<Self> {
+        Self::with_wal_cipher(local_validator_id, config, wal_path, None).await
+    }
+
+    /// Create consensus engine backed by a WAL sealed under `cipher`
+    ///
+    /// Plaintext records from before encryption was enabled are replayed;
+    /// `rewrap_wal` seals them.
+    pub async fn with_encrypted_wal<P: AsRef<Path>>(
+        local_validator_id: String,
+        config: ConsensusConfig,
+        wal_path: P,
+        cipher: Arc<EnvelopeCipher>,
+    ) -> Result<Self> {
+        Self::with_wal_cipher(local_validator_id, config, wal_path, Some(cipher)).await
+    }
+
+    async fn with_wal_cipher<P: AsRef<Path>>(
+        local_validator_id: String,
+        config: ConsensusConfig,
+        wal_path: P,
+        cipher: Option<Arc<EnvelopeCipher>>,
+    ) -> Result<Self> {
+        let mut engine = Self::new(local_validator_id, config).await?;
+        let records = ConsensusWal::replay_with_cipher(&wal_path, cipher.as_deref())?;
+        let restored = records.len();
+        for record in records {
+            engine.apply_wal_record(record);
+        }
+        engine.wal = Some(ConsensusWal::open_with_cipher(&wal_path, cipher)?);
+
+        if restored > 0 {
+            println!(
+                "📜 Restored {} consensus records ({} proposals, {} decisions)",
+                restored,
+                engine.sessions.len(),
+                engine.decisions.len()
+            );
+        }
+        Ok(engine)
+    }
+
+    /// Apply a replayed WAL record to in-memory state
+    fn apply_wal_record(&mut self, record: WalRecord) {
+        match record {
+            WalRecord::ProposalCreated {
+                proposal,
+                created_at,
+            } => {
+                let proposal_id = proposal.proposal_id.clone();
+                self.sessions.insert(
+                    proposal_id.clone(),
+                    ConsensusSession {
+                        session_id: proposal_id,
+                        proposal,
+                        votes: HashMap::new(),
+                        status: ConsensusStatus::Pending,
+                        created_at,
+                        finalized_at: None,
+                    },
+                );
+            }
+            WalRecord::VoteCast(vote) => {
+                if let Some(session) = self.sessions.get_mut(&vote.proposal_id) {
+                    session.status = ConsensusStatus::InProgress;
+                    session.votes.insert(vote.voter_id.clone(), vote);
+                }
+            }
+            WalRecord::Finalized(decision) => {
+                if let Some(session) = self.sessions.get_mut(&decision.proposal_id) {
+                    session.status = decision.status;
+                    session.finalized_at = Some(decision.finalized_at);
+                }
+                self.decisions.insert(decision.proposal_id.clone(), decision);
+            }
+        }
+    }
+
+    /// Move the WAL to the cipher's current master key
+    ///
+    /// Returns the number of records rewritten; engines without an
+    /// encrypted WAL have nothing to rewrap.
+    pub fn rewrap_wal(&mut self) -> Result<usize> {
+        match self.wal.as_mut() {
+            Some(wal) => wal.rewrap(),
+            None => Ok(0),
+        }
+    }
+
+    /// Append to the WAL when one is configured
+    fn persist(&mut self, record: WalRecord) -> Result<()> {
+        match self.wal.as_mut() {
+            Some(wal) => wal.append(&record),
+            None => Ok(()),
+        }
+    }
+
+    /// Publish `ConsensusCommitted` events on the given bus
+    pub fn set_event_bus(&mut self, events: EventBus) {
+        self.events = Some(events);
+    }
+
+    /// Register a validator
+    pub fn register_validator(&mut self, validator_info: ValidatorInfo) {
+        self.validators
+            .insert(validator_info.validator_id.clone(), validator_info);
+    }
+
+    /// Create consensus proposal
+    pub fn create_proposal(
+        &mut self,
+        proposer_id: String,
+        data: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<String> {
+        self.insert_proposal(proposer_id, DEFAULT_PROPOSAL_TYPE, data, signature, None, None)
+    }
+
+    /// Create, persist and track a proposal session
+    fn insert_proposal(
+        &mut self,
+        proposer_id: String,
+        proposal_type: &str,
+        data: Vec<u8>,
+        signature: Vec<u8>,
+        round: Option<u64>,
+        leader_proof: Option<LeaderProof>,
+    ) -> Result<String> {
+        let proposal_id = format!(
+            "prop_{}_{}_{}",
+            proposer_id,
+            chrono::Utc::now().timestamp(),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+
+        let proposal = ConsensusProposal {
+            proposal_id: proposal_id.clone(),
+            proposer_id,
+            data,
+            signature,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            verification_requirements: self.config.verification_methods.clone(),
+            proposal_type: proposal_type.to_string(),
+            round,
+            leader_proof,
+        };
+        let created_at = chrono::Utc::now().timestamp() as u64;
+
+        // Persist before acknowledging the proposal
+        self.persist(WalRecord::ProposalCreated {
+            proposal: proposal.clone(),
+            created_at,
+        })?;
+
+        let session = ConsensusSession {
+            session_id: proposal_id.clone(),
+            proposal,
+            votes: HashMap::new(),
+            status: ConsensusStatus::Pending,
+            created_at,
+            finalized_at: None,
+        };
+
+        self.sessions.insert(proposal_id.clone(), session);
+        Ok(proposal_id)
+    }
+
+    /// Create consensus proposal on behalf of a remote peer
+    ///
+    /// Proposals from peers whose reputation is below the configured
+    /// proposal threshold are refused before a session is created.
+    pub fn create_proposal_from_peer(
+        &mut self,
+        proposer_id: String,
+        data: Vec<u8>,
+        signature: Vec<u8>,
+        reputation: &ReputationManager,
+    ) -> Result<String> {
+        if !reputation.accepts_proposals_from(&proposer_id) {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Proposal from {} refused: reputation {:.2} below threshold",
+                proposer_id,
+                reputation.score(&proposer_id)
+            )));
+        }
+
+        self.create_proposal(proposer_id, data, signature)
+    }
+
+    /// Create a typed proposal after running its type's verifier pipeline
+    ///
+    /// Proposals failing any required verifier are refused.
+    pub fn create_typed_proposal(
+        &mut self,
+        proposer_id: String,
+        proposal_type: &str,
+        data: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<String> {
+        self.verify_typed_proposal(proposal_type, &data, &signature)?;
+        self.insert_proposal(proposer_id, proposal_type, data, signature, None, None)
+    }
+
+    /// Run a proposal type's verifier pipeline, refusing failures
+    fn verify_typed_proposal(
+        &self,
+        proposal_type: &str,
+        data: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        let result = self.verifiers.verify(proposal_type, data, signature)?;
+        if !result.verified {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Proposal of type {} failed verification: {}",
+                proposal_type,
+                result.error_details.unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Submit a signed proposal through its type's verifier pipeline
+    ///
+    /// Round-bound proposals signed with the proposer's Ed25519 validator key
+    /// are checked for equivocation; a second, different proposal for the
+    /// same type and round is refused and recorded as evidence. When leader
+    /// election is enabled, round-bound proposals must carry a valid VRF
+    /// claim showing the proposer is eligible for that round.
+    pub fn submit_proposal(&mut self, proposal: Proposal) -> Result<String> {
+        let signature = proposal.signature.clone().ok_or_else(|| {
+            SecureCommsError::Validation("Proposal must be signed before submission".to_string())
+        })?;
+        self.admit_proposal(&proposal, &signature)?;
+
+        self.insert_proposal(
+            proposal.proposer_id,
+            &proposal.proposal_type,
+            proposal.data,
+            signature,
+            proposal.round,
+            proposal.leader_proof,
+        )
+    }
+
+    /// Track a proposal created by another validator under its original ID
+    ///
+    /// The proposal passes the same checks as `submit_proposal`, so votes
+    /// signed by remote validators refer to the same proposal on every node.
+    /// Importing an already known proposal is a no-op.
+    pub fn import_proposal(&mut self, proposal: ConsensusProposal) -> Result<()> {
+        if self.sessions.contains_key(&proposal.proposal_id) {
+            return Ok(());
+        }
+
+        let signed = Proposal {
+            proposer_id: proposal.proposer_id.clone(),
+            proposal_type: proposal.proposal_type.clone(),
+            data: proposal.data.clone(),
+            round: proposal.round,
+            leader_proof: proposal.leader_proof.clone(),
+            signature: Some(proposal.signature.clone()),
+        };
+        self.admit_proposal(&signed, &proposal.signature)?;
+
+        let created_at = chrono::Utc::now().timestamp() as u64;
+        self.persist(WalRecord::ProposalCreated {
+            proposal: proposal.clone(),
+            created_at,
+        })?;
+        self.apply_wal_record(WalRecord::ProposalCreated {
+            proposal,
+            created_at,
+        });
+        Ok(())
+    }
+
+    /// Verifier pipeline, leader proof and equivocation checks for a proposal
+    fn admit_proposal(&mut self, proposal: &Proposal, signature: &[u8]) -> Result<()> {
+        self.verify_typed_proposal(&proposal.proposal_type, &proposal.data, signature)?;
+
+        if let (Some(election), Some(round)) = (&self.leader_election, proposal.round) {
+            let leader_proof = proposal.leader_proof.as_ref().ok_or_else(|| {
+                SecureCommsError::ConsensusVerify(format!(
+                    "Proposal for round {} carries no leader proof",
+                    round
+                ))
+            })?;
+            if leader_proof.round != round {
+                return Err(SecureCommsError::ConsensusVerify(format!(
+                    "Leader proof is for round {}, proposal for round {}",
+                    leader_proof.round, round
+                )));
+            }
+            election.verify_claim(&proposal.proposer_id, leader_proof)?;
+        }
+
+        if let Some(round) = proposal.round {
+            let payload = proposal.signing_payload();
+            let verifiable = self
+                .validators
+                .get(&proposal.proposer_id)
+                .map(|validator| verify_commit_signature(&validator.public_key, &payload, signature))
+                .unwrap_or(false);
+            if verifiable {
+                let statement = SignedStatement::new(
+                    payload,
+                    signature.to_vec(),
+                    &format!("proposal of {} bytes", proposal.data.len()),
+                );
+                let round_key = format!("{}/{}", proposal.proposal_type, round);
+                if let Some(evidence) = self.equivocation.observe(
+                    &proposal.proposer_id,
+                    &round_key,
+                    EquivocationKind::ConflictingProposals,
+                    statement,
+                ) {
+                    return Err(SecureCommsError::ConsensusVerify(format!(
+                        "Validator {} equivocated in round {} (evidence {})",
+                        proposal.proposer_id, round_key, evidence.evidence_id
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a proposal with its votes and decision
+    pub fn get_proposal(&self, proposal_id: &str) -> Option<ProposalRecord> {
+        self.sessions
+            .get(proposal_id)
+            .map(|session| self.build_record(session))
+    }
+
+    /// Get the final decision for a proposal
+    ///
+    /// Decisions remain available after their session is cleaned up.
+    pub fn get_decision(&self, proposal_id: &str) -> Option<&ProposalDecision> {
+        self.decisions.get(proposal_id)
+    }
+
+    /// Query proposals, newest first
+    pub fn query_proposals(&self, query: &ProposalQuery) -> Vec<ProposalRecord> {
+        let mut sessions: Vec<&ConsensusSession> = self
+            .sessions
+            .values()
+            .filter(|session| query.status.is_none() || query.status == Some(session.status))
+            .filter(|session| {
+                query.proposer_id.is_none()
+                    || query.proposer_id.as_deref() == Some(session.proposal.proposer_id.as_str())
+            })
+            .filter(|session| {
+                query.proposal_type.is_none()
+                    || query.proposal_type.as_deref() == Some(session.proposal.proposal_type.as_str())
+            })
+            .filter(|session| query.since.is_none() || query.since <= Some(session.created_at))
+            .collect();
+
+        sessions.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| b.session_id.cmp(&a.session_id))
+        });
+        if let Some(limit) = query.limit {
+            sessions.truncate(limit);
+        }
+
+        sessions
+            .into_iter()
+            .map(|session| self.build_record(session))
+            .collect()
+    }
+
+    /// All final decisions, newest first
+    pub fn decision_history(&self) -> Vec<&ProposalDecision> {
+        let mut decisions: Vec<&ProposalDecision> = self.decisions.values().collect();
+        decisions.sort_by(|a, b| {
+            b.finalized_at
+                .cmp(&a.finalized_at)
+                .then_with(|| b.proposal_id.cmp(&a.proposal_id))
+        });
+        decisions
+    }
+
+    fn build_record(&self, session: &ConsensusSession) -> ProposalRecord {
+        let mut votes: Vec<ConsensusVote> = session.votes.values().cloned().collect();
+        votes.sort_by(|a, b| a.voter_id.cmp(&b.voter_id));
+        ProposalRecord {
+            proposal: session.proposal.clone(),
+            status: session.status,
+            votes,
+            created_at: session.created_at,
+            decision: self.decisions.get(&session.session_id).cloned(),
+        }
+    }
+
+    /// Verify a stored proposal with the verifiers of its type
+    pub fn verify_proposal(&self, proposal_id: &str) -> Result<VerificationResult> {
+        let session = self
+            .sessions
+            .get(proposal_id)
+            .ok_or_else(|| SecureCommsError::ConsensusVerify("Proposal not found".to_string()))?;
+        let proposal = &session.proposal;
+        self.verifiers
+            .verify(&proposal.proposal_type, &proposal.data, &proposal.signature)
+    }
+
+    /// Register or replace a verifier
+    pub fn register_verifier(&mut self, verifier: Arc<dyn Verifier>) {
+        self.verifiers.register(verifier);
+    }
+
+    /// Assign the verifiers required for a proposal type
+    pub fn set_proposal_type_verifiers(&mut self, proposal_type: &str, verifier_names: &[&str]) -> Result<()> {
+        self.verifiers.set_proposal_type(proposal_type, verifier_names)
+    }
+
+    /// Get verifier registry
+    pub fn verifier_registry(&self) -> &VerifierRegistry {
+        &self.verifiers
+    }
+
+    /// Submit vote on proposal
+    pub fn submit_vote(
+        &mut self,
+        proposal_id: &str,
+        voter_id: String,
+        vote: VoteType,
+        verification_result: VerificationResult,
+    ) -> Result<()> {
+        self.record_vote(proposal_id, voter_id, vote, verification_result, None)
+    }
+
+    /// Submit a vote signed with the validator's Ed25519 key
+    ///
+    /// Signed votes are checked against the registered validator key and
+    /// are included in commit certificates.
+    pub fn submit_signed_vote(
+        &mut self,
+        proposal_id: &str,
+        voter_id: String,
+        vote: VoteType,
+        verification_result: VerificationResult,
+        signature: Vec<u8>,
+    ) -> Result<()> {
+        use sha3::{Digest, Sha3_256};
+        let validator = self.validators.get(&voter_id).ok_or_else(|| {
+            SecureCommsError::ConsensusVerify(format!("Unknown validator {}", voter_id))
+        })?;
+        let session = self
+            .sessions
+            .get(proposal_id)
+            .ok_or_else(|| SecureCommsError::ConsensusVerify("Proposal not found".to_string()))?;
+
+        let data_hash = Sha3_256::digest(&session.proposal.data);
+        let message = commit_vote_message(proposal_id, &data_hash, vote);
+        if !verify_commit_signature(&validator.public_key, &message, &signature) {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Invalid vote signature from {}",
+                voter_id
+            )));
+        }
+
+        // A second, different signed vote on the same proposal is equivocation
+        let statement = SignedStatement::new(message, signature.clone(), &format!("{:?}", vote));
+        if let Some(evidence) = self.equivocation.observe(
+            &voter_id,
+            proposal_id,
+            EquivocationKind::ConflictingVotes,
+            statement,
+        ) {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Validator {} equivocated on {} (evidence {})",
+                voter_id, proposal_id, evidence.evidence_id
+            )));
+        }
+
+        self.record_vote(proposal_id, voter_id, vote, verification_result, Some(signature))
+    }
+
+    /// Enable VRF leader election for round-bound proposals
+    pub fn set_leader_election(&mut self, election: LeaderElection) {
+        self.leader_election = Some(election);
+    }
+
+    /// Leader election, if enabled
+    pub fn leader_election(&self) -> Option<&LeaderElection> {
+        self.leader_election.as_ref()
+    }
 
-impl ConsensusEngine {
-    /// Create new consensus engine
-    pub async fn new(local_validator_id: String, config: ConsensusConfig) -> Result<Self> {
-        let start_time = Instant::now();
+    /// Mutable leader election, for registering VRF keys and beacon values
+    pub fn leader_election_mut(&mut self) -> Option<&mut LeaderElection> {
+        self.leader_election.as_mut()
+    }
 
-        let mut metrics = PerformanceMetrics::new();
-        metrics.consensus_verify_ms = start_time.elapsed().as_millis() as u64;
+    /// Query recorded equivocation evidence, newest first
+    pub fn equivocation_evidence(&self, query: &EvidenceQuery) -> Vec<&EquivocationEvidence> {
+        self.equivocation.query(query)
+    }
 
-        Ok(Self {
-            sessions: HashMap::new(),
-            validators: HashMap::new(),
-            config,
-            metrics,
-            local_validator_id,
+    /// Whether evidence of equivocation exists against a validator
+    pub fn has_equivocated(&self, validator_id: &str) -> bool {
+        self.equivocation.has_equivocated(validator_id)
+    }
+
+    /// Build a commit certificate for a decided proposal
+    ///
+    /// Only signed votes are included; the certificate is bound to the
+    /// current set of active validators.
+    pub fn build_commit_certificate(&self, proposal_id: &str) -> Result<CommitCertificate> {
+        let decision = self.decisions.get(proposal_id).ok_or_else(|| {
+            SecureCommsError::ConsensusVerify(format!("Proposal {} is not decided", proposal_id))
+        })?;
+        if !matches!(
+            decision.status,
+            ConsensusStatus::Approved | ConsensusStatus::Rejected
+        ) {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Proposal {} ended as {:?}; no certificate available",
+                proposal_id, decision.status
+            )));
+        }
+
+        let signatures = decision
+            .vote_proof
+            .votes
+            .iter()
+            .filter_map(|vote| {
+                vote.signature.as_ref().map(|signature| CommitSignature {
+                    validator_id: vote.voter_id.clone(),
+                    vote: vote.vote,
+                    signature: signature.clone(),
+                })
+            })
+            .collect();
+
+        Ok(CommitCertificate {
+            proposal_id: proposal_id.to_string(),
+            data_hash: decision.vote_proof.data_hash.clone(),
+            status: decision.status,
+            threshold: decision.threshold,
+            validator_set_hash: self.validator_set().hash(),
+            signatures,
         })
     }
 
-    /// Register a validator
-    pub fn register_validator(&mut self, validator_info: ValidatorInfo) {
-        self.validators
-            .insert(validator_info.validator_id.clone(), validator_info);
+    /// Current set of active validators
+    pub fn validator_set(&self) -> ValidatorSet {
+        ValidatorSet::from_validators(self.validators.values())
     }
 
-    /// Create consensus proposal
-    pub fn create_proposal(
-        &mut self,
-        proposer_id: String,
-        data: Vec<u8>,
-        signature: Vec<u8>,
-    ) -> Result<String> {
-        let proposal_id = format!("prop_{}_{}", proposer_id, chrono::Utc::now().timestamp());
+    /// Approved and rejected decisions in ledger order
+    ///
+    /// Ledger order is by proposal timestamp, then proposal ID, which every
+    /// node holding the same proposals agrees on.
+    fn ledger(&self) -> Vec<&ProposalDecision> {
+        let mut ledger: Vec<(&ConsensusProposal, &ProposalDecision)> = self
+            .decisions
+            .values()
+            .filter(|d| {
+                matches!(d.status, ConsensusStatus::Approved | ConsensusStatus::Rejected)
+            })
+            .filter_map(|d| {
+                self.sessions
+                    .get(&d.proposal_id)
+                    .map(|session| (&session.proposal, d))
+            })
+            .collect();
+        ledger.sort_by(|a, b| {
+            a.0.timestamp
+                .cmp(&b.0.timestamp)
+                .then_with(|| a.0.proposal_id.cmp(&b.0.proposal_id))
+        });
+        ledger.into_iter().map(|(_, decision)| decision).collect()
+    }
 
-        let proposal = ConsensusProposal {
-            proposal_id: proposal_id.clone(),
-            proposer_id,
-            data,
-            signature,
-            timestamp: chrono::Utc::now().timestamp() as u64,
-            verification_requirements: self.config.verification_methods.clone(),
-        };
+    /// Number of approved or rejected decisions held locally
+    pub fn sync_height(&self) -> u64 {
+        self.ledger().len() as u64
+    }
 
-        let session = ConsensusSession {
-            session_id: proposal_id.clone(),
-            proposal,
-            votes: HashMap::new(),
-            status: ConsensusStatus::Pending,
-            created_at: chrono::Utc::now().timestamp() as u64,
-            finalized_at: None,
-        };
+    /// Export certified decisions from `from_height` for state sync
+    pub fn export_sync_entries(&self, from_height: u64, max_entries: usize) -> Vec<SyncEntry> {
+        self.ledger()
+            .into_iter()
+            .skip(from_height as usize)
+            .take(max_entries)
+            .filter_map(|decision| {
+                let session = self.sessions.get(&decision.proposal_id)?;
+                let certificate = self.build_commit_certificate(&decision.proposal_id).ok()?;
+                Some(SyncEntry {
+                    proposal: session.proposal.clone(),
+                    decision: decision.clone(),
+                    certificate,
+                })
+            })
+            .collect()
+    }
 
-        self.sessions.insert(proposal_id.clone(), session);
-        Ok(proposal_id)
+    /// Apply certified decisions received through state sync
+    ///
+    /// Every entry is verified against the validator set before anything is
+    /// applied; already known decisions are skipped. Applied entries are
+    /// persisted to the WAL. Returns the number of new decisions.
+    pub fn apply_sync_entries(
+        &mut self,
+        entries: &[SyncEntry],
+        validator_set: &ValidatorSet,
+    ) -> Result<usize> {
+        for entry in entries {
+            entry.verify(validator_set)?;
+        }
+
+        let mut applied = 0;
+        for entry in entries {
+            if self.decisions.contains_key(&entry.proposal.proposal_id) {
+                continue;
+            }
+
+            let mut records = vec![WalRecord::ProposalCreated {
+                proposal: entry.proposal.clone(),
+                created_at: entry.proposal.timestamp,
+            }];
+            records.extend(
+                entry
+                    .decision
+                    .vote_proof
+                    .votes
+                    .iter()
+                    .cloned()
+                    .map(WalRecord::VoteCast),
+            );
+            records.push(WalRecord::Finalized(entry.decision.clone()));
+
+            for record in records {
+                self.persist(record.clone())?;
+                self.apply_wal_record(record);
+            }
+            applied += 1;
+        }
+        Ok(applied)
     }
 
-    /// Submit vote on proposal
-    pub fn submit_vote(
+    /// Record a vote, persisting it before acknowledging
+    fn record_vote(
         &mut self,
         proposal_id: &str,
         voter_id: String,
         vote: VoteType,
         verification_result: VerificationResult,
+        signature: Option<Vec<u8>>,
     ) -> Result<()> {
         let session = self
             .sessions
@@ -389,8 +1861,14 @@ impl ConsensusEngine {
             vote,
             verification_result,
             timestamp: chrono::Utc::now().timestamp() as u64,
+            signature,
         };
 
+        // Persist before acknowledging the vote
+        if let Some(wal) = self.wal.as_mut() {
+            wal.append(&WalRecord::VoteCast(consensus_vote.clone()))?;
+        }
+
         session.votes.insert(voter_id, consensus_vote);
         session.status = ConsensusStatus::InProgress;
 
@@ -410,35 +1888,7 @@ impl ConsensusEngine {
         let start_time = Instant::now();
 
         let result = match method {
-            VerificationMethod::CryptographicSignature => {
-                // Fast-path optimization for signatures with benchmark/test support
-                if signature.len() < 32 {
-                    VerificationResult {
-                        verified: false,
-                        confidence: 0.0,
-                        verification_time_ms: start_time.elapsed().as_millis() as u64,
-                        verification_method: method,
-                        error_details: Some("Signature too short".to_string()),
-                    }
-                } else {
-                    // Optimized signature verification - more permissive for benchmarks/tests
-                    let is_valid = if signature.len() >= 32 {
-                        // For benchmark/test scenarios, accept non-zero signatures
-                        !signature.iter().all(|&b| b == 0) || 
-                        signature.len() == 64  // Standard signature length
-                    } else {
-                        false
-                    };
-                    
-                    VerificationResult {
-                        verified: is_valid,
-                        confidence: if is_valid { 0.95 } else { 0.0 },
-                        verification_time_ms: start_time.elapsed().as_millis() as u64,
-                        verification_method: method,
-                        error_details: None,
-                    }
-                }
-            }
+            VerificationMethod::CryptographicSignature => signature_check(signature, start_time),
             VerificationMethod::QuantumState => {
                 // Perfect fidelity quantum verification - instant for benchmarks
                 VerificationResult {
@@ -449,28 +1899,7 @@ impl ConsensusEngine {
                     error_details: None,
                 }
             }
-            VerificationMethod::IntegrityHash => {
-                // Fast hash-based verification
-                use sha3::{Digest, Sha3_256};
-                let mut hasher = Sha3_256::new();
-                hasher.update(data);
-                let computed_hash = hasher.finalize();
-                
-                // Compare first 8 bytes for fast verification
-                let is_valid = if signature.len() >= 8 && computed_hash.len() >= 8 {
-                    signature[..8] == computed_hash[..8]
-                } else {
-                    false
-                };
-                
-                VerificationResult {
-                    verified: is_valid,
-                    confidence: if is_valid { 0.99 } else { 0.0 },
-                    verification_time_ms: start_time.elapsed().as_millis() as u64,
-                    verification_method: method,
-                    error_details: None,
-                }
-            }
+            VerificationMethod::IntegrityHash => integrity_hash_check(data, signature, start_time),
             VerificationMethod::MultiFactor => {
                 // Production multi-factor verification with comprehensive security checks
                 let crypto_valid = signature.len() >= 32 && !signature.iter().all(|&b| b == 0);
@@ -501,7 +1930,7 @@ impl ConsensusEngine {
                 
                 // Verify signature against quantum hash
                 let hash_matches = if signature.len() >= quantum_hash.len() {
-                    &signature[..quantum_hash.len()] == quantum_hash.as_slice()
+                    ct_eq(&signature[..quantum_hash.len()], &quantum_hash)
                 } else {
                     false
                 };
@@ -527,7 +1956,7 @@ impl ConsensusEngine {
                 
                 // Cross-reference signature with integrity hash
                 let integrity_match = if signature.len() >= 16 && integrity_hash.len() >= 16 {
-                    signature[..16] == integrity_hash[..16]
+                    ct_eq(&signature[..16], &integrity_hash[..16])
                 } else {
                     false
                 };
@@ -542,6 +1971,15 @@ impl ConsensusEngine {
                     } else { None },
                 }
             }
+            VerificationMethod::Custom => VerificationResult {
+                verified: false,
+                confidence: 0.0,
+                verification_time_ms: start_time.elapsed().as_millis() as u64,
+                verification_method: method,
+                error_details: Some(
+                    "Custom verification requires a registered verifier".to_string(),
+                ),
+            },
         };
 
         Ok(result)
@@ -554,37 +1992,77 @@ impl ConsensusEngine {
             .get_mut(proposal_id)
             .ok_or_else(|| SecureCommsError::ConsensusVerify("Session not found".to_string()))?;
 
-        let total_votes = session.votes.len();
-        let approve_votes = session
-            .votes
-            .values()
-            .filter(|vote| vote.vote == VoteType::Approve)
-            .count();
-
-        let approval_ratio = if total_votes > 0 {
-            approve_votes as f64 / total_votes as f64
-        } else {
-            0.0
+        let mut tally = QuorumTally {
+            voters: session.votes.len(),
+            ..QuorumTally::default()
         };
+        for vote in session.votes.values() {
+            let weight = self.quorum.weight_of(&vote.voter_id);
+            match vote.vote {
+                VoteType::Approve => tally.approve_weight += weight,
+                VoteType::Reject => tally.reject_weight += weight,
+                VoteType::Abstain => tally.abstain_weight += weight,
+            }
+        }
 
-        // Check if we have enough votes and meet threshold
-        if total_votes >= self.config.min_validators as usize {
-            if approval_ratio >= self.config.consensus_threshold {
+        // Weighted quorum for this proposal type, against active validator weight
+        let rule = *self.quorum.rule_for(&session.proposal.proposal_type);
+        let total_weight = self.quorum.total_weight(
+            self.validators
+                .values()
+                .filter(|validator| validator.is_active)
+                .map(|validator| validator.validator_id.as_str()),
+        );
+        match rule.evaluate(&tally, total_weight) {
+            Some(QuorumOutcome::Approved) => {
                 session.status = ConsensusStatus::Approved;
                 session.finalized_at = Some(chrono::Utc::now().timestamp() as u64);
-            } else if approval_ratio < (1.0 - self.config.consensus_threshold) {
+            }
+            Some(QuorumOutcome::Rejected) => {
                 session.status = ConsensusStatus::Rejected;
                 session.finalized_at = Some(chrono::Utc::now().timestamp() as u64);
             }
+            None => {}
         }
 
         // Check for timeout
         let current_time = chrono::Utc::now().timestamp() as u64;
-        if current_time - session.created_at > (self.config.consensus_timeout_ms / 1000) {
+        if current_time.saturating_sub(session.created_at) > (self.config.consensus_timeout_ms / 1000) {
             session.status = ConsensusStatus::Timeout;
             session.finalized_at = Some(current_time);
         }
 
+        if session.status.is_final() && !self.decisions.contains_key(proposal_id) {
+            let count = |vote_type: VoteType| {
+                session
+                    .votes
+                    .values()
+                    .filter(|vote| vote.vote == vote_type)
+                    .count()
+            };
+            let decision = ProposalDecision {
+                proposal_id: proposal_id.to_string(),
+                status: session.status,
+                approve_count: count(VoteType::Approve),
+                reject_count: count(VoteType::Reject),
+                abstain_count: count(VoteType::Abstain),
+                threshold: rule.approval.as_f64(),
+                finalized_at: session.finalized_at.unwrap_or(current_time),
+                vote_proof: VoteProof::build(&session.proposal, &session.votes),
+            };
+
+            self.persist(WalRecord::Finalized(decision.clone()))?;
+            if let Some(events) = &self.events {
+                events.emit(ClientEvent::ConsensusCommitted {
+                    proposal_id: decision.proposal_id.clone(),
+                    status: decision.status,
+                    approve_count: decision.approve_count,
+                    reject_count: decision.reject_count,
+                });
+            }
+            self.decisions.insert(proposal_id.to_string(), decision);
+        }
+
         Ok(())
     }
 
@@ -751,6 +2229,10 @@ impl ConsensusEngine {
             "approved_sessions".to_string(),
             serde_json::Value::Number(approved_sessions.into()),
         );
+        stats.insert(
+            "equivocation".to_string(),
+            serde_json::to_value(self.equivocation.get_stats()).unwrap_or_default(),
+        );
 
         stats
     }
@@ -936,11 +2418,21 @@ mod tests {
         assert!(sig_result.verified);
         assert!(sig_result.confidence > 0.9);
 
+        // Integrity checks compare against the data's own SHA3-256 digest
+        let digest = {
+            use sha3::{Digest, Sha3_256};
+            Sha3_256::digest(data).to_vec()
+        };
         let hash_result = engine
-            .verify_data(data, &signature, VerificationMethod::IntegrityHash)
+            .verify_data(data, &digest, VerificationMethod::IntegrityHash)
             .await
             .unwrap();
         assert!(hash_result.verified);
+        let hash_result = engine
+            .verify_data(data, &signature, VerificationMethod::IntegrityHash)
+            .await
+            .unwrap();
+        assert!(!hash_result.verified);
 
         let multi_result = engine
             .verify_data(data, &signature, VerificationMethod::MultiFactor)
@@ -1037,6 +2529,24 @@ mod tests {
         assert_eq!(status, ConsensusStatus::Approved);
     }
 
+    #[tokio::test]
+    async fn test_proposal_reputation_gate() {
+        let mut engine = ConsensusEngine::new("local".to_string(), ConsensusConfig::default())
+            .await
+            .unwrap();
+
+        let mut reputation = ReputationManager::default();
+        reputation.record_success("honest", 10);
+        reputation.set_override("byzantine", 0.1).unwrap();
+
+        assert!(engine
+            .create_proposal_from_peer("honest".to_string(), vec![1, 2, 3], vec![9; 64], &reputation)
+            .is_ok());
+        assert!(engine
+            .create_proposal_from_peer("byzantine".to_string(), vec![1, 2, 3], vec![9; 64], &reputation)
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_comprehensive_verification() {
         let config = ConsensusConfig::default();
@@ -1073,4 +2583,431 @@ mod tests {
         assert_eq!(result.verification_method, VerificationMethod::MultiFactor);
         assert!(result.confidence > 0.8);
     }
+
+    struct LengthProofVerifier;
+
+    impl Verifier for LengthProofVerifier {
+        fn name(&self) -> &str {
+            "length_proof"
+        }
+
+        fn verify(&self, data: &[u8], proof: &[u8]) -> Result<VerificationResult> {
+            Ok(VerificationResult::custom(proof.len() == data.len(), 1.0, 0))
+        }
+    }
+
+    #[test]
+    fn test_verifier_registry_selection() {
+        let mut registry = VerifierRegistry::new();
+        assert!(registry.set_proposal_type("rollup", &["length_proof"]).is_err());
+
+        registry.register(Arc::new(LengthProofVerifier));
+        registry.set_proposal_type("rollup", &["length_proof"]).unwrap();
+
+        assert_eq!(registry.verifiers_for("rollup"), vec!["length_proof".to_string()]);
+        assert_eq!(
+            registry.verifiers_for("unknown_type"),
+            registry.verifiers_for(DEFAULT_PROPOSAL_TYPE)
+        );
+        assert!(registry.verify("rollup", b"abcd", b"1234").unwrap().verified);
+        assert!(!registry.verify("rollup", b"abcd", b"12").unwrap().verified);
+        assert!(registry.unregister("length_proof").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_typed_proposal_pipeline() {
+        let mut engine = ConsensusEngine::new("validator".to_string(), ConsensusConfig::default())
+            .await
+            .unwrap();
+        engine.register_verifier(Arc::new(LengthProofVerifier));
+        engine
+            .set_proposal_type_verifiers("rollup", &["length_proof"])
+            .unwrap();
+
+        assert!(engine
+            .create_typed_proposal("peer".to_string(), "rollup", b"batch".to_vec(), b"bad".to_vec())
+            .is_err());
+
+        let proposal_id = engine
+            .create_typed_proposal("peer".to_string(), "rollup", b"batch".to_vec(), b"proof".to_vec())
+            .unwrap();
+        assert!(engine.verify_proposal(&proposal_id).unwrap().verified);
+        assert_eq!(
+            engine.sessions[&proposal_id].proposal.proposal_type,
+            "rollup".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_publishes_event() {
+        use sha3::{Digest, Sha3_256};
+
+        let mut engine = ConsensusEngine::new("validator".to_string(), ConsensusConfig::default())
+            .await
+            .unwrap();
+        let events = EventBus::new();
+        let mut stream = events.subscribe();
+        engine.set_event_bus(events);
+        engine.register_validator(ValidatorInfo {
+            validator_id: "validator_1".to_string(),
+            public_key: vec![1u8; 32],
+            trust_score: 1.0,
+            is_active: true,
+            last_activity: chrono::Utc::now().timestamp() as u64,
+        });
+        engine
+            .set_proposal_type_verifiers("block", &["hash_based"])
+            .unwrap();
+
+        let data = b"block 1".to_vec();
+        let signature = Sha3_256::digest(&data).to_vec();
+        let proposal_id = engine
+            .create_typed_proposal("validator".to_string(), "block", data, signature)
+            .unwrap();
+        engine
+            .submit_vote(
+                &proposal_id,
+                "validator_1".to_string(),
+                VoteType::Approve,
+                VerificationResult::custom(true, 0.95, 1),
+            )
+            .unwrap();
+
+        match stream.try_recv().unwrap() {
+            ClientEvent::ConsensusCommitted {
+                proposal_id: committed,
+                status,
+                approve_count,
+                ..
+            } => {
+                assert_eq!(committed, proposal_id);
+                assert_eq!(status, ConsensusStatus::Approved);
+                assert_eq!(approve_count, 1);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert!(stream.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proposal_history_survives_restart() {
+        use sha3::{Digest, Sha3_256};
+        let dir = tempfile::tempdir().unwrap();
+        let wal_path = dir.path().join("consensus.wal");
+
+        let data = b"block #42".to_vec();
+        let mut signature = Sha3_256::digest(&data).to_vec();
+        signature.extend_from_slice(&[7u8; 32]);
+
+        let proposal_id = {
+            let mut engine =
+                ConsensusEngine::with_wal("validator".to_string(), ConsensusConfig::default(), &wal_path)
+                    .await
+                    .unwrap();
+            assert!(engine
+                .submit_proposal(Proposal::new("validator", data.clone()))
+                .is_err());
+
+            let proposal_id = engine
+                .submit_proposal(Proposal::new("validator", data.clone()).with_signature(signature))
+                .unwrap();
+            engine
+                .submit_vote(
+                    &proposal_id,
+                    "validator".to_string(),
+                    VoteType::Approve,
+                    VerificationResult::custom(true, 1.0, 0),
+                )
+                .unwrap();
+            assert_eq!(
+                engine.get_session_status(&proposal_id),
+                Some(ConsensusStatus::Approved)
+            );
+            proposal_id
+        };
+
+        let restored =
+            ConsensusEngine::with_wal("validator".to_string(), ConsensusConfig::default(), &wal_path)
+                .await
+                .unwrap();
+        let decision = restored.get_decision(&proposal_id).unwrap();
+        assert_eq!(decision.status, ConsensusStatus::Approved);
+        assert_eq!(decision.approve_count, 1);
+        assert!(decision.vote_proof.verify());
+
+        let approved = restored.query_proposals(&ProposalQuery {
+            status: Some(ConsensusStatus::Approved),
+            ..ProposalQuery::default()
+        });
+        assert_eq!(approved.len(), 1);
+        assert_eq!(approved[0].votes.len(), 1);
+        assert_eq!(restored.decision_history().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_commit_certificate_light_client() {
+        let config = ConsensusConfig {
+            min_validators: 2,
+            consensus_threshold: 0.6,
+            ..ConsensusConfig::default()
+        };
+        let mut engine = ConsensusEngine::new("v0".to_string(), config).await.unwrap();
+
+        let keys: Vec<SigningKey> = (1u8..=3).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        for (i, key) in keys.iter().enumerate() {
+            engine.register_validator(ValidatorInfo {
+                validator_id: format!("v{}", i),
+                public_key: key.verifying_key().to_bytes().to_vec(),
+                trust_score: 1.0,
+                is_active: true,
+                last_activity: 0,
+            });
+        }
+
+        let data = b"block #7".to_vec();
+        let proposal_id = engine
+            .create_proposal("v0".to_string(), data.clone(), vec![1u8; 64])
+            .unwrap();
+
+        // A signature from the wrong key is refused
+        let forged = sign_commit_vote(&keys[2], &proposal_id, &data, VoteType::Approve);
+        assert!(engine
+            .submit_signed_vote(
+                &proposal_id,
+                "v1".to_string(),
+                VoteType::Approve,
+                VerificationResult::custom(true, 1.0, 0),
+                forged,
+            )
+            .is_err());
+
+        for (i, key) in keys.iter().enumerate().take(2) {
+            let signature = sign_commit_vote(key, &proposal_id, &data, VoteType::Approve);
+            engine
+                .submit_signed_vote(
+                    &proposal_id,
+                    format!("v{}", i),
+                    VoteType::Approve,
+                    VerificationResult::custom(true, 1.0, 0),
+                    signature,
+                )
+                .unwrap();
+        }
+
+        let certificate = engine.build_commit_certificate(&proposal_id).unwrap();
+        let bytes = certificate.to_bytes().unwrap();
+        let received = CommitCertificate::from_bytes(&bytes).unwrap();
+
+        let validator_set = engine.validator_set();
+        assert!(received.verify_data(&data, &validator_set).is_ok());
+        assert!(received.verify_data(b"other block", &validator_set).is_err());
+
+        let mut tampered = received.clone();
+        tampered.status = ConsensusStatus::Rejected;
+        assert!(tampered.verify(&validator_set).is_err());
+
+        let mut smaller_set = ValidatorSet::new();
+        smaller_set.add("v0", keys[0].verifying_key().to_bytes().to_vec());
+        assert!(received.verify(&smaller_set).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_equivocation_evidence_recorded() {
+        let config = ConsensusConfig {
+            min_validators: 3,
+            ..ConsensusConfig::default()
+        };
+        let mut engine = ConsensusEngine::new("v0".to_string(), config).await.unwrap();
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let public_key = key.verifying_key().to_bytes().to_vec();
+        engine.register_validator(ValidatorInfo {
+            validator_id: "v1".to_string(),
+            public_key: public_key.clone(),
+            trust_score: 1.0,
+            is_active: true,
+            last_activity: 0,
+        });
+
+        let data = b"block #9".to_vec();
+        let proposal_id = engine
+            .create_proposal("v0".to_string(), data.clone(), vec![1u8; 64])
+            .unwrap();
+
+        // Repeating the same vote is harmless; switching it is equivocation
+        let votes = [
+            (VoteType::Approve, true),
+            (VoteType::Approve, true),
+            (VoteType::Reject, false),
+        ];
+        for (vote, accepted) in votes {
+            let signature = sign_commit_vote(&key, &proposal_id, &data, vote);
+            let result = engine.submit_signed_vote(
+                &proposal_id,
+                "v1".to_string(),
+                vote,
+                VerificationResult::custom(true, 1.0, 0),
+                signature,
+            );
+            assert_eq!(result.is_ok(), accepted);
+        }
+
+        let evidence = engine.equivocation_evidence(&EvidenceQuery::default());
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].kind, EquivocationKind::ConflictingVotes);
+        assert!(evidence[0].verify(&public_key));
+
+        // Two different proposals for the same round
+        engine
+            .set_proposal_type_verifiers("block", &["digital_signature"])
+            .unwrap();
+        let first = Proposal::new("v1", b"block A".to_vec())
+            .with_type("block")
+            .with_round(4)
+            .sign_ed25519(&key);
+        let second = Proposal::new("v1", b"block B".to_vec())
+            .with_type("block")
+            .with_round(4)
+            .sign_ed25519(&key);
+        assert!(engine.submit_proposal(first).is_ok());
+        assert!(engine.submit_proposal(second).is_err());
+
+        let query = EvidenceQuery {
+            kind: Some(EquivocationKind::ConflictingProposals),
+            ..EvidenceQuery::default()
+        };
+        let evidence = engine.equivocation_evidence(&query);
+        assert_eq!(evidence.len(), 1);
+        assert!(evidence[0].verify(&public_key));
+        assert!(engine.has_equivocated("v1"));
+    }
+
+    #[tokio::test]
+    async fn test_round_proposals_require_leader_proof() {
+        use crate::key_manager::{KeyManager, KeyPurpose};
+        use crate::leader_election::{BeaconValue, LeaderElectionConfig};
+
+        let mut engine = ConsensusEngine::new("v0".to_string(), ConsensusConfig::default())
+            .await
+            .unwrap();
+        let mut keys = KeyManager::new();
+        let vrf = keys.import_key("vrf", KeyPurpose::Vrf, [6u8; 32]).unwrap();
+
+        let mut election = LeaderElection::new(LeaderElectionConfig {
+            expected_proposers: 1.0, // a single validator is always eligible
+            ..LeaderElectionConfig::default()
+        });
+        election.register_validator("v0", &vrf.public_key).unwrap();
+        election
+            .beacon_mut()
+            .accept(BeaconValue::genesis(vec![1u8; 32]))
+            .unwrap();
+        engine.set_leader_election(election);
+        engine
+            .set_proposal_type_verifiers("block", &["digital_signature"])
+            .unwrap();
+
+        let unproven = Proposal::new("v0", b"block".to_vec())
+            .with_type("block")
+            .with_round(5)
+            .with_signature(vec![1u8; 64]);
+        assert!(engine.submit_proposal(unproven).is_err());
+
+        let claim = engine
+            .leader_election()
+            .unwrap()
+            .claim(&keys, "vrf", 5)
+            .unwrap()
+            .unwrap();
+        let proposal = Proposal::new("v0", b"block".to_vec())
+            .with_type("block")
+            .with_leader_proof(claim)
+            .with_signature(vec![1u8; 64]);
+        let proposal_id = engine.submit_proposal(proposal).unwrap();
+
+        let record = engine.get_proposal(&proposal_id).unwrap();
+        assert_eq!(record.proposal.round, Some(5));
+        assert!(record.proposal.leader_proof.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_weighted_quorum_rules() {
+        use crate::quorum::Fraction;
+
+        // Half the weight must vote before a default proposal is decided
+        let mut quorum = QuorumConfig {
+            default_rule: QuorumRule {
+                participation: Fraction::new(1, 2),
+                ..QuorumRule::default()
+            },
+            ..QuorumConfig::default()
+        };
+        quorum.weights.insert("heavy".to_string(), 3);
+        quorum.type_rules.insert(
+            "rollup".to_string(),
+            QuorumRule {
+                approval: Fraction::new(9, 10),
+                participation: Fraction::new(0, 1),
+                min_voters: 2,
+            },
+        );
+
+        let invalid = ConsensusConfig {
+            quorum: Some(QuorumConfig {
+                default_rule: QuorumRule {
+                    approval: Fraction::new(1, 2),
+                    ..QuorumRule::default()
+                },
+                ..quorum.clone()
+            }),
+            ..ConsensusConfig::default()
+        };
+        assert!(ConsensusEngine::new("v0".to_string(), invalid).await.is_err());
+
+        let config = ConsensusConfig {
+            quorum: Some(quorum),
+            ..ConsensusConfig::default()
+        };
+        let mut engine = ConsensusEngine::new("v0".to_string(), config).await.unwrap();
+        engine.register_verifier(Arc::new(LengthProofVerifier));
+        engine
+            .set_proposal_type_verifiers("rollup", &["length_proof"])
+            .unwrap();
+        for id in ["heavy", "a", "b"] {
+            engine.register_validator(ValidatorInfo {
+                validator_id: id.to_string(),
+                public_key: vec![0u8; 32],
+                trust_score: 1.0,
+                is_active: true,
+                last_activity: 0,
+            });
+        }
+
+        // 3 of 4 cast weight approves the default 2/3 rule
+        let block = engine
+            .create_proposal("v0".to_string(), b"block".to_vec(), vec![1u8; 64])
+            .unwrap();
+        let ok = VerificationResult::custom(true, 1.0, 0);
+        engine
+            .submit_vote(&block, "a".to_string(), VoteType::Reject, ok.clone())
+            .unwrap();
+        assert_eq!(engine.get_session_status(&block), Some(ConsensusStatus::InProgress));
+        engine
+            .submit_vote(&block, "heavy".to_string(), VoteType::Approve, ok.clone())
+            .unwrap();
+        let decision = engine.get_decision(&block).unwrap();
+        assert_eq!(decision.status, ConsensusStatus::Approved);
+        assert!((decision.threshold - 2.0 / 3.0).abs() < 1e-9);
+
+        // The same votes stay open under the stricter rollup rule
+        let rollup = engine
+            .create_typed_proposal("v0".to_string(), "rollup", b"batch".to_vec(), b"proof".to_vec())
+            .unwrap();
+        engine
+            .submit_vote(&rollup, "a".to_string(), VoteType::Reject, ok.clone())
+            .unwrap();
+        engine
+            .submit_vote(&rollup, "heavy".to_string(), VoteType::Approve, ok.clone())
+            .unwrap();
+        assert_eq!(engine.get_session_status(&rollup), Some(ConsensusStatus::InProgress));
+    }
 }
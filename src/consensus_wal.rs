@@ -0,0 +1,396 @@
+//! # Consensus Write-Ahead Log - Durable Proposal History
+//!
+//! Append-only JSON-lines log of consensus lifecycle events. Every proposal,
+//! vote and final decision is written (and synced) before the in-memory
+//! consensus state changes are acknowledged, so past decisions and their vote
+//! proofs can be audited programmatically and restored after restarts.
+//!
+//! ## Record Types
+//!
+//! - **ProposalCreated**: Full proposal as submitted
+//! - **VoteCast**: Individual validator vote with its verification result
+//! - **Finalized**: Final decision with vote counts and vote proof
+//!
+//! ## Crash Safety
+//!
+//! Records are written one per line and flushed with `sync_data`. A torn
+//! final line left by a crash is ignored on replay; corruption anywhere
+//! else is reported as an error rather than silently dropping history.
+//!
+//! ## Encryption at Rest
+//!
+//! A WAL opened with an `EnvelopeCipher` writes each record as a sealed
+//! envelope line. Plaintext lines written before encryption was enabled are
+//! still replayed, and `rewrap` seals them along with moving older envelopes
+//! to the current master key.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::consensus_wal::ConsensusWal;
+//!
+//! let records = ConsensusWal::replay("data/consensus.wal").unwrap();
+//! println!("Recovered {} consensus records", records.len());
+//! ```
+
+use crate::consensus_verify::{ConsensusProposal, ConsensusVote, ProposalDecision};
+use crate::envelope::{Envelope, EnvelopeCipher};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Envelope context binding sealed records to the WAL
+const WAL_CONTEXT: &str = "consensus_wal";
+
+/// Consensus lifecycle event persisted in the WAL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WalRecord {
+    /// Proposal accepted into the consensus engine
+    ProposalCreated {
+        /// Submitted proposal
+        proposal: ConsensusProposal,
+        /// Session creation time (Unix seconds)
+        created_at: u64,
+    },
+    /// Vote recorded for a proposal
+    VoteCast(ConsensusVote),
+    /// Proposal reached a final status
+    Finalized(ProposalDecision),
+}
+
+impl WalRecord {
+    /// Proposal the record belongs to
+    pub fn proposal_id(&self) -> &str {
+        match self {
+            WalRecord::ProposalCreated { proposal, .. } => &proposal.proposal_id,
+            WalRecord::VoteCast(vote) => &vote.proposal_id,
+            WalRecord::Finalized(decision) => &decision.proposal_id,
+        }
+    }
+}
+
+/// Append-only consensus write-ahead log
+#[derive(Debug)]
+pub struct ConsensusWal {
+    /// Log file location
+    path: PathBuf,
+    /// Open append handle
+    file: File,
+    /// Records appended through this handle
+    records_written: u64,
+    /// Seals records before they are written
+    cipher: Option<Arc<EnvelopeCipher>>,
+}
+
+impl ConsensusWal {
+    /// Open (or create) a WAL for appending
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open_with_cipher(path, None)
+    }
+
+    /// Open (or create) a WAL, sealing appended records when a cipher is given
+    pub fn open_with_cipher<P: AsRef<Path>>(
+        path: P,
+        cipher: Option<Arc<EnvelopeCipher>>,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    SecureCommsError::SystemError(format!("Failed to create WAL directory: {}", e))
+                })?;
+            }
+        }
+
+        let file = Self::open_append(&path)?;
+
+        Ok(Self {
+            path,
+            file,
+            records_written: 0,
+            cipher,
+        })
+    }
+
+    fn open_append(path: &Path) -> Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| SecureCommsError::SystemError(format!("Failed to open WAL {:?}: {}", path, e)))
+    }
+
+    /// Durably append a record
+    pub fn append(&mut self, record: &WalRecord) -> Result<()> {
+        let mut line = serde_json::to_vec(record)
+            .map_err(|e| SecureCommsError::SystemError(format!("WAL serialization failed: {}", e)))?;
+        if let Some(cipher) = &self.cipher {
+            line = cipher.seal_bytes(WAL_CONTEXT, &line)?;
+        }
+        line.push(b'\n');
+
+        self.file
+            .write_all(&line)
+            .and_then(|_| self.file.sync_data())
+            .map_err(|e| SecureCommsError::SystemError(format!("WAL write failed: {}", e)))?;
+
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Read every record from a WAL file
+    ///
+    /// A missing file yields no records. A torn final line is skipped.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Vec<WalRecord>> {
+        Self::replay_with_cipher(path, None)
+    }
+
+    /// Read every record from a WAL file, opening sealed records with `cipher`
+    ///
+    /// Sealed records in a WAL replayed without a cipher are an error.
+    pub fn replay_with_cipher<P: AsRef<Path>>(
+        path: P,
+        cipher: Option<&EnvelopeCipher>,
+    ) -> Result<Vec<WalRecord>> {
+        let lines = Self::read_lines(path.as_ref())?;
+
+        let mut records = Vec::with_capacity(lines.len());
+        let last_index = lines.len().saturating_sub(1);
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(envelope) = Envelope::from_bytes(line.as_bytes()) {
+                records.push(Self::open_record(&envelope, cipher, index)?);
+                continue;
+            }
+            match serde_json::from_str::<WalRecord>(line) {
+                Ok(record) => records.push(record),
+                Err(_) if index == last_index => break, // torn write from a crash
+                Err(e) => {
+                    return Err(SecureCommsError::SystemError(format!(
+                        "Corrupt WAL record at line {}: {}",
+                        index + 1,
+                        e
+                    )))
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Rewrite the log under the cipher's current master key
+    ///
+    /// Envelopes wrapped under an older master key are rewrapped and
+    /// plaintext records are sealed; a torn final line is dropped. The file
+    /// is replaced atomically. Returns the number of records rewritten.
+    pub fn rewrap(&mut self) -> Result<usize> {
+        let cipher = self.cipher.clone().ok_or_else(|| {
+            SecureCommsError::Configuration("WAL has no cipher to rewrap under".to_string())
+        })?;
+        let lines = Self::read_lines(&self.path)?;
+
+        let mut contents = Vec::new();
+        let mut rewritten = 0;
+        let last_index = lines.len().saturating_sub(1);
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let sealed = match Envelope::from_bytes(line.as_bytes()) {
+                Some(envelope) if cipher.needs_rewrap(&envelope) => {
+                    rewritten += 1;
+                    cipher.rewrap(WAL_CONTEXT, &envelope)?.to_bytes()?
+                }
+                Some(_) => line.as_bytes().to_vec(),
+                None => match serde_json::from_str::<WalRecord>(line) {
+                    Ok(_) => {
+                        rewritten += 1;
+                        cipher.seal_bytes(WAL_CONTEXT, line.as_bytes())?
+                    }
+                    Err(_) if index == last_index => break, // torn write from a crash
+                    Err(e) => {
+                        return Err(SecureCommsError::SystemError(format!(
+                            "Corrupt WAL record at line {}: {}",
+                            index + 1,
+                            e
+                        )))
+                    }
+                },
+            };
+            contents.extend_from_slice(&sealed);
+            contents.push(b'\n');
+        }
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".rewrap");
+        let tmp_path = PathBuf::from(tmp_path);
+        File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(&contents)?;
+                file.sync_data()
+            })
+            .and_then(|_| fs::rename(&tmp_path, &self.path))
+            .map_err(|e| SecureCommsError::SystemError(format!("WAL rewrite failed: {}", e)))?;
+        self.file = Self::open_append(&self.path)?;
+
+        Ok(rewritten)
+    }
+
+    fn read_lines(path: &Path) -> Result<Vec<String>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(path)
+            .map_err(|e| SecureCommsError::SystemError(format!("Failed to open WAL {:?}: {}", path, e)))?;
+        BufReader::new(file)
+            .lines()
+            .collect::<std::io::Result<_>>()
+            .map_err(|e| SecureCommsError::SystemError(format!("WAL read failed: {}", e)))
+    }
+
+    fn open_record(
+        envelope: &Envelope,
+        cipher: Option<&EnvelopeCipher>,
+        index: usize,
+    ) -> Result<WalRecord> {
+        let cipher = cipher.ok_or_else(|| {
+            SecureCommsError::Configuration(format!(
+                "Sealed WAL record at line {} but no cipher configured",
+                index + 1
+            ))
+        })?;
+        let plaintext = cipher.open(WAL_CONTEXT, envelope)?;
+        serde_json::from_slice(&plaintext).map_err(|e| {
+            SecureCommsError::SystemError(format!("Corrupt WAL record at line {}: {}", index + 1, e))
+        })
+    }
+
+    /// Log file location
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records appended through this handle
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_verify::{
+        VerificationMethod, VerificationResult, VoteType, DEFAULT_PROPOSAL_TYPE,
+    };
+
+    fn proposal(id: &str) -> ConsensusProposal {
+        ConsensusProposal {
+            proposal_id: id.to_string(),
+            proposer_id: "proposer".to_string(),
+            data: b"block".to_vec(),
+            signature: vec![1u8; 64],
+            timestamp: 1,
+            verification_requirements: vec![VerificationMethod::IntegrityHash],
+            proposal_type: DEFAULT_PROPOSAL_TYPE.to_string(),
+            round: None,
+            leader_proof: None,
+        }
+    }
+
+    #[test]
+    fn test_append_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus.wal");
+
+        let mut wal = ConsensusWal::open(&path).unwrap();
+        wal.append(&WalRecord::ProposalCreated {
+            proposal: proposal("p1"),
+            created_at: 1,
+        })
+        .unwrap();
+        wal.append(&WalRecord::VoteCast(ConsensusVote {
+            proposal_id: "p1".to_string(),
+            voter_id: "v1".to_string(),
+            vote: VoteType::Approve,
+            verification_result: VerificationResult::custom(true, 1.0, 0),
+            timestamp: 2,
+            signature: None,
+        }))
+        .unwrap();
+        assert_eq!(wal.records_written(), 2);
+
+        let records = ConsensusWal::replay(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].proposal_id(), "p1");
+    }
+
+    #[test]
+    fn test_torn_tail_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus.wal");
+
+        let mut wal = ConsensusWal::open(&path).unwrap();
+        wal.append(&WalRecord::ProposalCreated {
+            proposal: proposal("p1"),
+            created_at: 1,
+        })
+        .unwrap();
+        wal.file.write_all(b"{\"VoteCast\":{\"propo").unwrap();
+
+        assert_eq!(ConsensusWal::replay(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_encrypted_wal_and_rewrap() {
+        use crate::crypto_protocols::QRNG;
+        use crate::envelope::ManagedMasterKeys;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus.wal");
+        let created = |id: &str| WalRecord::ProposalCreated {
+            proposal: proposal(id),
+            created_at: 1,
+        };
+
+        // A plaintext record from before encryption was enabled
+        ConsensusWal::open(&path).unwrap().append(&created("p1")).unwrap();
+
+        let mut qrng = QRNG::from_seed([5u8; 32]);
+        let masters = Arc::new(ManagedMasterKeys::generate("master-1", &mut qrng).unwrap());
+        let cipher = Arc::new(EnvelopeCipher::new(masters.clone()));
+        let mut wal = ConsensusWal::open_with_cipher(&path, Some(cipher.clone())).unwrap();
+        wal.append(&created("p2")).unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("\"p1\"") && !raw.contains("\"p2\""));
+        assert!(ConsensusWal::replay(&path).is_err());
+        let records = ConsensusWal::replay_with_cipher(&path, Some(&cipher)).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].proposal_id(), "p2");
+
+        // Rotate, rewrap everything, then drop the old master key
+        masters.rotate("master-2", &mut qrng).unwrap();
+        assert_eq!(wal.rewrap().unwrap(), 2);
+        wal.append(&created("p3")).unwrap();
+        masters.retire("master-1").unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("\"p1\""));
+        let records = ConsensusWal::replay_with_cipher(&path, Some(&cipher)).unwrap();
+        let ids: Vec<&str> = records.iter().map(|record| record.proposal_id()).collect();
+        assert_eq!(ids, vec!["p1", "p2", "p3"]);
+    }
+
+    #[test]
+    fn test_missing_wal_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ConsensusWal::replay(dir.path().join("absent.wal"))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+//! # Constant Time - Side-Channel Safe Comparison and Selection
+//!
+//! Comparison and selection primitives whose running time depends only on
+//! input lengths, never on contents. Every MAC, tag, token and key check in
+//! the crate goes through `ct_eq`, so an attacker timing verification
+//! failures learns nothing about how many leading bytes were right.
+//!
+//! ## Primitives
+//!
+//! - **ct_eq**: Byte slice equality; only the lengths may leak
+//! - **ct_select / ct_copy_if**: Branch-free choice between two buffers
+//! - **ct_is_zero**: Whether a buffer is all zero bytes
+//!
+//! ## Timing Harness
+//!
+//! With the `timing-harness` feature, `TimingHarness` runs a dudect-style
+//! leak test: an operation is timed on two input classes in random order and
+//! Welch's t-test decides whether the timing distributions differ. A
+//! t-statistic above `LEAK_T_THRESHOLD` is treated as a leak.
+//!
+//! ```text
+//! cargo test --features timing-harness constant_time
+//! ```
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::constant_time::{ct_eq, ct_select};
+//!
+//! let expected = [7u8; 32];
+//! let presented = [7u8; 32];
+//! assert!(ct_eq(&expected, &presented));
+//! assert_eq!(ct_select(true, &[1, 2], &[3, 4]), vec![1, 2]);
+//! ```
+
+use std::hint::black_box;
+
+/// All-ones mask when `choice` is set, zero otherwise
+fn mask(choice: bool) -> u8 {
+    (black_box(choice) as u8).wrapping_neg()
+}
+
+/// Whether two byte slices are equal, in time independent of their contents
+///
+/// Slices of different length compare unequal immediately; lengths of
+/// MACs and tags are public.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let difference = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| black_box(acc | (x ^ y)));
+    ct_is_zero(&[difference])
+}
+
+/// Whether a buffer is all zero bytes, in time independent of its contents
+pub fn ct_is_zero(bytes: &[u8]) -> bool {
+    let folded = bytes.iter().fold(0u8, |acc, byte| black_box(acc | byte));
+    // Maps 0 to 1 and anything else to 0 without branching
+    (((folded as u16).wrapping_sub(1) >> 8) & 1) == 1
+}
+
+/// `a` when `choice` is set, `b` otherwise, without branching on `choice`
+///
+/// Both inputs must have the same length.
+pub fn ct_select(choice: bool, a: &[u8], b: &[u8]) -> Vec<u8> {
+    assert_eq!(a.len(), b.len(), "ct_select inputs must have equal length");
+    let mask = mask(choice);
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x & mask) | (y & !mask))
+        .collect()
+}
+
+/// Overwrite `dst` with `src` when `choice` is set, without branching on it
+///
+/// Both buffers must have the same length.
+pub fn ct_copy_if(choice: bool, dst: &mut [u8], src: &[u8]) {
+    assert_eq!(
+        dst.len(),
+        src.len(),
+        "ct_copy_if inputs must have equal length"
+    );
+    let mask = mask(choice);
+    for (d, s) in dst.iter_mut().zip(src.iter()) {
+        *d = (*d & !mask) | (s & mask);
+    }
+}
+
+#[cfg(feature = "timing-harness")]
+mod timing {
+    use rand::Rng;
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    /// |t| above which timing distributions are taken to differ
+    pub const LEAK_T_THRESHOLD: f64 = 10.0;
+
+    /// Input class of one measurement
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum InputClass {
+        /// Fixed input, usually the case expected to take a different path
+        Fixed,
+        /// Random input
+        Random,
+    }
+
+    /// Timing leak test settings
+    #[derive(Debug, Clone)]
+    pub struct TimingConfig {
+        /// Measurements per class
+        pub samples: usize,
+        /// Operation calls timed together per measurement
+        pub batch: usize,
+        /// Fraction of the slowest measurements dropped as system noise
+        pub crop_fraction: f64,
+    }
+
+    impl Default for TimingConfig {
+        fn default() -> Self {
+            Self {
+                samples: 20_000,
+                batch: 16,
+                crop_fraction: 0.05,
+            }
+        }
+    }
+
+    /// Outcome of a timing leak test
+    #[derive(Debug, Clone)]
+    pub struct TimingReport {
+        /// Measurements kept per class after cropping
+        pub samples: usize,
+        /// Mean nanoseconds per batch of the fixed class
+        pub fixed_mean_ns: f64,
+        /// Mean nanoseconds per batch of the random class
+        pub random_mean_ns: f64,
+        /// Welch's t-statistic between the classes
+        pub t_statistic: f64,
+    }
+
+    impl TimingReport {
+        /// Whether the classes are distinguishable by timing
+        pub fn leaks(&self) -> bool {
+            self.t_statistic.abs() > LEAK_T_THRESHOLD
+        }
+    }
+
+    /// Dudect-style statistical timing test
+    #[derive(Debug, Clone, Default)]
+    pub struct TimingHarness {
+        config: TimingConfig,
+    }
+
+    impl TimingHarness {
+        /// Create a harness
+        pub fn new(config: TimingConfig) -> Self {
+            Self { config }
+        }
+
+        /// Time `operation` on inputs from `prepare`, classes in random order
+        ///
+        /// Inputs are prepared before timing starts so only `operation`
+        /// is measured.
+        pub fn run<T, P, F>(&self, mut prepare: P, mut operation: F) -> TimingReport
+        where
+            P: FnMut(InputClass) -> T,
+            F: FnMut(&T),
+        {
+            let mut rng = rand::thread_rng();
+            let total = self.config.samples * 2;
+            let classes: Vec<InputClass> = (0..total)
+                .map(|_| {
+                    if rng.gen::<bool>() {
+                        InputClass::Fixed
+                    } else {
+                        InputClass::Random
+                    }
+                })
+                .collect();
+            let inputs: Vec<T> = classes.iter().map(|class| prepare(*class)).collect();
+
+            let mut fixed = Vec::with_capacity(total);
+            let mut random = Vec::with_capacity(total);
+            for (class, input) in classes.iter().zip(inputs.iter()) {
+                let start = Instant::now();
+                for _ in 0..self.config.batch {
+                    operation(black_box(input));
+                }
+                let elapsed = start.elapsed().as_nanos() as f64;
+                match class {
+                    InputClass::Fixed => fixed.push(elapsed),
+                    InputClass::Random => random.push(elapsed),
+                }
+            }
+
+            let fixed = crop(fixed, self.config.crop_fraction);
+            let random = crop(random, self.config.crop_fraction);
+            let (fixed_mean, fixed_var) = mean_variance(&fixed);
+            let (random_mean, random_var) = mean_variance(&random);
+            let standard_error =
+                (fixed_var / fixed.len() as f64 + random_var / random.len() as f64).sqrt();
+            let t_statistic = if standard_error > 0.0 {
+                (fixed_mean - random_mean) / standard_error
+            } else {
+                0.0
+            };
+            TimingReport {
+                samples: fixed.len().min(random.len()),
+                fixed_mean_ns: fixed_mean,
+                random_mean_ns: random_mean,
+                t_statistic,
+            }
+        }
+    }
+
+    /// Drop the slowest measurements, which mostly reflect interrupts
+    fn crop(mut samples: Vec<f64>, fraction: f64) -> Vec<f64> {
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let keep = ((samples.len() as f64) * (1.0 - fraction)).ceil() as usize;
+        samples.truncate(keep.max(2).min(samples.len()));
+        samples
+    }
+
+    fn mean_variance(samples: &[f64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, variance)
+    }
+}
+
+#[cfg(feature = "timing-harness")]
+pub use timing::{InputClass, TimingConfig, TimingHarness, TimingReport, LEAK_T_THRESHOLD};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(&[9u8; 32], &[9u8; 32]));
+        assert!(!ct_eq(&[9u8; 32], &[9u8; 31]));
+        for position in [0, 15, 31] {
+            let mut other = [9u8; 32];
+            other[position] ^= 0x80;
+            assert!(!ct_eq(&[9u8; 32], &other));
+        }
+        assert!(ct_is_zero(&[0u8; 16]));
+        assert!(!ct_is_zero(&[0, 0, 1]));
+    }
+
+    #[test]
+    fn test_ct_select_and_copy() {
+        assert_eq!(ct_select(true, &[1, 2, 3], &[4, 5, 6]), vec![1, 2, 3]);
+        assert_eq!(ct_select(false, &[1, 2, 3], &[4, 5, 6]), vec![4, 5, 6]);
+
+        let mut dst = [0u8; 3];
+        ct_copy_if(false, &mut dst, &[7, 8, 9]);
+        assert_eq!(dst, [0, 0, 0]);
+        ct_copy_if(true, &mut dst, &[7, 8, 9]);
+        assert_eq!(dst, [7, 8, 9]);
+    }
+
+    #[cfg(feature = "timing-harness")]
+    #[test]
+    fn test_timing_harness_detects_early_exit() {
+        // Slice equality returns at the first differing byte
+        let secret = vec![0x5au8; 4096];
+        let report = TimingHarness::default().run(
+            |class| {
+                let mut input = secret.clone();
+                if class == InputClass::Random {
+                    input[0] ^= rand::random::<u8>() | 1;
+                }
+                input
+            },
+            |input| {
+                black_box(black_box(input.as_slice()) == black_box(secret.as_slice()));
+            },
+        );
+        assert!(report.leaks(), "t = {:.2}", report.t_statistic);
+    }
+
+    #[cfg(feature = "timing-harness")]
+    #[test]
+    fn test_comparison_is_constant_time() {
+        // Fixed: mismatch in the first byte; random: mismatch in the last
+        let secret = vec![0x5au8; 4096];
+        let report = TimingHarness::default().run(
+            |class| {
+                let mut input = secret.clone();
+                match class {
+                    InputClass::Fixed => input[0] ^= 1,
+                    InputClass::Random => input[4095] ^= rand::random::<u8>() | 1,
+                }
+                input
+            },
+            |input| {
+                black_box(ct_eq(input, &secret));
+            },
+        );
+        assert!(!report.leaks(), "t = {:.2}", report.t_statistic);
+    }
+
+    #[cfg(feature = "timing-harness")]
+    #[test]
+    fn test_decryption_failure_is_constant_time() {
+        use crate::hw_accel::{CryptoDispatch, DispatchPolicy};
+
+        // Fixed: tag corrupted; random: ciphertext corrupted at a random byte
+        let dispatch = CryptoDispatch::new(DispatchPolicy::default());
+        let key = [3u8; 32];
+        let sealed = dispatch
+            .seal(&key, &[4u8; 12], b"aad", &[0u8; 1024])
+            .unwrap();
+        let report = TimingHarness::new(TimingConfig {
+            samples: 5_000,
+            batch: 4,
+            ..Default::default()
+        })
+        .run(
+            |class| {
+                let mut input = sealed.clone();
+                let position = match class {
+                    InputClass::Fixed => input.len() - 1,
+                    InputClass::Random => rand::random::<usize>() % (input.len() - 13) + 13,
+                };
+                input[position] ^= 1;
+                input
+            },
+            |input| {
+                assert!(dispatch.open(&key, b"aad", input).is_err());
+            },
+        );
+        assert!(!report.leaks(), "t = {:.2}", report.t_statistic);
+    }
+}
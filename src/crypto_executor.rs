@@ -0,0 +1,727 @@
+//! # Crypto Executor - Prioritised Workers for Sign, Verify and KEM Jobs
+//!
+//! Signature verification and KEM operations are CPU-bound. Run on the
+//! async runtime or on the shared compute pool, a burst of inbound
+//! signature checks queues up in front of the one encapsulation a peer's
+//! handshake is waiting on. The crypto executor runs these jobs on its own
+//! threads and orders them by priority class, so handshakes complete while
+//! data traffic is still being verified.
+//!
+//! ## Scheduling
+//!
+//! - **Priority Classes**: `Handshake` jobs (channel setup, key updates)
+//!   are always taken before `Data` jobs (per-message signatures)
+//! - **Reserved Workers**: `handshake_workers` threads only ever run
+//!   handshake jobs, so a handshake never waits behind a data job that is
+//!   already running
+//! - **Bounded Queues**: Each class queues at most `max_queued` jobs;
+//!   further jobs are rejected with `ResourceExhausted`, and a data backlog
+//!   never consumes handshake capacity
+//! - **Panic Isolation**: A panicking job fails its own call with
+//!   `SystemError`; the worker keeps running
+//!
+//! ## Metrics
+//!
+//! Per class: current queue depth, submitted, completed, rejected and
+//! panicked jobs, and mean and maximum time spent waiting for a worker.
+//! Completed jobs are also counted by kind (sign, verify, encapsulate,
+//! decapsulate).
+//!
+//! The process-wide executor is started on first use. Call
+//! `configure_crypto_executor` before that to size it.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_executor::{crypto_executor, CryptoPriority};
+//! use quantum_forge_secure_comms::crypto_protocols::PQCAlgorithm;
+//! use quantum_forge_secure_comms::crypto_provider::{BuiltinKem, Kem};
+//! use std::sync::Arc;
+//!
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! let kem: Arc<dyn Kem> = Arc::new(BuiltinKem::new(PQCAlgorithm::Kyber768));
+//! let keypair = kem.keygen()?;
+//! let executor = crypto_executor();
+//! let (ciphertext, _shared) = executor
+//!     .encapsulate(CryptoPriority::Handshake, kem, keypair.public_key)
+//!     .await?;
+//! println!(
+//!     "{} byte ciphertext, handshake mean wait {}us",
+//!     ciphertext.len(),
+//!     executor.metrics().handshake.mean_wait_us
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::crypto_provider::{Kem, Signer};
+use crate::secret_memory::SecretBytes;
+use crate::{Result, SecureCommsError};
+use once_cell::sync::OnceCell;
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Scheduling class of a crypto job, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CryptoPriority {
+    /// Channel establishment and key updates
+    Handshake,
+    /// Per-message signing and verification
+    Data,
+}
+
+/// Operation a crypto job performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CryptoJobKind {
+    /// Signature generation
+    Sign,
+    /// Signature verification
+    Verify,
+    /// KEM encapsulation
+    Encapsulate,
+    /// KEM decapsulation
+    Decapsulate,
+}
+
+impl CryptoJobKind {
+    fn index(self) -> usize {
+        match self {
+            CryptoJobKind::Sign => 0,
+            CryptoJobKind::Verify => 1,
+            CryptoJobKind::Encapsulate => 2,
+            CryptoJobKind::Decapsulate => 3,
+        }
+    }
+
+    const ALL: [CryptoJobKind; 4] = [
+        CryptoJobKind::Sign,
+        CryptoJobKind::Verify,
+        CryptoJobKind::Encapsulate,
+        CryptoJobKind::Decapsulate,
+    ];
+}
+
+/// Crypto executor sizing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CryptoExecutorConfig {
+    /// Worker threads (0 uses half the available cores, at least 2)
+    pub workers: usize,
+    /// Workers that only run handshake jobs; must leave one for data jobs
+    pub handshake_workers: usize,
+    /// Jobs each priority class may queue before new ones are rejected
+    pub max_queued: usize,
+}
+
+impl Default for CryptoExecutorConfig {
+    fn default() -> Self {
+        Self {
+            workers: 0,
+            handshake_workers: 1,
+            max_queued: 4096,
+        }
+    }
+}
+
+impl CryptoExecutorConfig {
+    /// Worker count after resolving `workers: 0`
+    pub fn resolved_workers(&self) -> usize {
+        if self.workers > 0 {
+            return self.workers;
+        }
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        (cores / 2).max(2)
+    }
+
+    /// Check the sizing is usable
+    pub fn validate(&self) -> Result<()> {
+        if self.handshake_workers >= self.resolved_workers() {
+            return Err(SecureCommsError::Configuration(format!(
+                "Crypto executor needs more than {} workers to reserve {} for handshakes",
+                self.resolved_workers(),
+                self.handshake_workers
+            )));
+        }
+        if self.max_queued == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Crypto executor queue limit must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Queue metrics of one priority class
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CryptoQueueMetrics {
+    /// Jobs waiting for a worker
+    pub queued: usize,
+    /// Jobs accepted
+    pub submitted: u64,
+    /// Jobs that ran to completion
+    pub completed: u64,
+    /// Jobs refused because the queue was full
+    pub rejected: u64,
+    /// Jobs that panicked
+    pub panicked: u64,
+    /// Mean time from submission to a worker picking the job up
+    pub mean_wait_us: u64,
+    /// Longest time a job waited for a worker
+    pub max_wait_us: u64,
+}
+
+/// Crypto executor metrics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CryptoExecutorMetrics {
+    /// Worker threads
+    pub workers: usize,
+    /// Workers reserved for handshake jobs
+    pub handshake_workers: usize,
+    /// Handshake class
+    pub handshake: CryptoQueueMetrics,
+    /// Data class
+    pub data: CryptoQueueMetrics,
+    /// Completed jobs by kind
+    pub completed_by_kind: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default)]
+struct ClassCounters {
+    submitted: AtomicU64,
+    completed: AtomicU64,
+    rejected: AtomicU64,
+    panicked: AtomicU64,
+    started: AtomicU64,
+    wait_nanos: AtomicU64,
+    max_wait_nanos: AtomicU64,
+}
+
+impl ClassCounters {
+    fn record_start(&self, waited_nanos: u64) {
+        self.started.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(waited_nanos, Ordering::Relaxed);
+        self.max_wait_nanos
+            .fetch_max(waited_nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, queued: usize) -> CryptoQueueMetrics {
+        let started = self.started.load(Ordering::Relaxed);
+        let mean_wait_nanos = self
+            .wait_nanos
+            .load(Ordering::Relaxed)
+            .checked_div(started)
+            .unwrap_or(0);
+        CryptoQueueMetrics {
+            queued,
+            submitted: self.submitted.load(Ordering::Relaxed),
+            completed: self.completed.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            panicked: self.panicked.load(Ordering::Relaxed),
+            mean_wait_us: mean_wait_nanos / 1_000,
+            max_wait_us: self.max_wait_nanos.load(Ordering::Relaxed) / 1_000,
+        }
+    }
+}
+
+struct Job {
+    priority: CryptoPriority,
+    enqueued_at: Instant,
+    run: Box<dyn FnOnce() + Send>,
+}
+
+#[derive(Default)]
+struct Queues {
+    handshake: VecDeque<Job>,
+    data: VecDeque<Job>,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    queues: Mutex<Queues>,
+    /// Signalled for every job; general workers wait here
+    work_ready: Condvar,
+    /// Signalled for handshake jobs; reserved workers wait here
+    handshake_ready: Condvar,
+    handshake: ClassCounters,
+    data: ClassCounters,
+    completed_by_kind: [AtomicU64; 4],
+}
+
+impl Shared {
+    fn counters(&self, priority: CryptoPriority) -> &ClassCounters {
+        match priority {
+            CryptoPriority::Handshake => &self.handshake,
+            CryptoPriority::Data => &self.data,
+        }
+    }
+
+    /// Next job for a worker, or `None` once the executor shuts down
+    fn next_job(&self, handshake_only: bool) -> Option<Job> {
+        let mut queues = self.queues.lock();
+        loop {
+            if queues.shutdown {
+                return None;
+            }
+            if let Some(job) = queues.handshake.pop_front() {
+                return Some(job);
+            }
+            if !handshake_only {
+                if let Some(job) = queues.data.pop_front() {
+                    return Some(job);
+                }
+                self.work_ready.wait(&mut queues);
+            } else {
+                self.handshake_ready.wait(&mut queues);
+            }
+        }
+    }
+}
+
+/// Prioritised worker threads for crypto jobs
+pub struct CryptoExecutor {
+    shared: Arc<Shared>,
+    workers: usize,
+    handshake_workers: usize,
+    max_queued: usize,
+}
+
+impl CryptoExecutor {
+    /// Start workers with the given sizing
+    pub fn new(config: &CryptoExecutorConfig) -> Result<Self> {
+        config.validate()?;
+        let workers = config.resolved_workers();
+        let shared = Arc::new(Shared::default());
+        for index in 0..workers {
+            let handshake_only = index < config.handshake_workers;
+            let worker_shared = shared.clone();
+            std::thread::Builder::new()
+                .name(format!("qfsc-crypto-{}", index))
+                .spawn(move || {
+                    while let Some(job) = worker_shared.next_job(handshake_only) {
+                        let waited = job.enqueued_at.elapsed().as_nanos() as u64;
+                        worker_shared.counters(job.priority).record_start(waited);
+                        (job.run)();
+                    }
+                })
+                .map_err(|e| {
+                    shared.queues.lock().shutdown = true;
+                    shared.work_ready.notify_all();
+                    shared.handshake_ready.notify_all();
+                    SecureCommsError::ResourceExhausted(format!(
+                        "Failed to start crypto worker: {}",
+                        e
+                    ))
+                })?;
+        }
+        Ok(Self {
+            shared,
+            workers,
+            handshake_workers: config.handshake_workers,
+            max_queued: config.max_queued,
+        })
+    }
+
+    /// Run `work` on a crypto worker and wait for its result without blocking the runtime
+    pub async fn submit<F, R>(
+        &self,
+        priority: CryptoPriority,
+        kind: CryptoJobKind,
+        work: F,
+    ) -> Result<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let shared = self.shared.clone();
+        let run = Box::new(move || {
+            let outcome = catch_unwind(AssertUnwindSafe(work));
+            let counters = shared.counters(priority);
+            match outcome {
+                Ok(_) => {
+                    counters.completed.fetch_add(1, Ordering::Relaxed);
+                    shared.completed_by_kind[kind.index()].fetch_add(1, Ordering::Relaxed);
+                }
+                Err(_) => {
+                    counters.panicked.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            // The caller may have stopped waiting; the result is then dropped
+            let _ = tx.send(outcome);
+        });
+
+        {
+            let mut queues = self.shared.queues.lock();
+            let queue = match priority {
+                CryptoPriority::Handshake => &mut queues.handshake,
+                CryptoPriority::Data => &mut queues.data,
+            };
+            if queue.len() >= self.max_queued {
+                let queued = queue.len();
+                drop(queues);
+                self.shared
+                    .counters(priority)
+                    .rejected
+                    .fetch_add(1, Ordering::Relaxed);
+                return Err(SecureCommsError::ResourceExhausted(format!(
+                    "Crypto {:?} queue full ({} queued jobs)",
+                    priority, queued
+                )));
+            }
+            queue.push_back(Job {
+                priority,
+                enqueued_at: Instant::now(),
+                run,
+            });
+        }
+        self.shared
+            .counters(priority)
+            .submitted
+            .fetch_add(1, Ordering::Relaxed);
+        if priority == CryptoPriority::Handshake {
+            self.shared.handshake_ready.notify_one();
+        }
+        self.shared.work_ready.notify_one();
+
+        match rx.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(_)) => Err(SecureCommsError::SystemError(
+                "Crypto job panicked".to_string(),
+            )),
+            Err(_) => Err(SecureCommsError::SystemError(
+                "Crypto job was dropped before completing".to_string(),
+            )),
+        }
+    }
+
+    /// Sign `message` with `signer`
+    pub async fn sign(
+        &self,
+        priority: CryptoPriority,
+        signer: Arc<dyn Signer>,
+        private_key: SecretBytes,
+        message: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        self.submit(priority, CryptoJobKind::Sign, move || {
+            signer.sign(private_key.expose(), &message)
+        })
+        .await?
+    }
+
+    /// Verify `signature` over `message` with `signer`
+    pub async fn verify(
+        &self,
+        priority: CryptoPriority,
+        signer: Arc<dyn Signer>,
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<bool> {
+        self.submit(priority, CryptoJobKind::Verify, move || {
+            signer.verify(&public_key, &message, &signature)
+        })
+        .await
+    }
+
+    /// Encapsulate a fresh secret to `public_key` with `kem`
+    pub async fn encapsulate(
+        &self,
+        priority: CryptoPriority,
+        kem: Arc<dyn Kem>,
+        public_key: Vec<u8>,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.submit(priority, CryptoJobKind::Encapsulate, move || {
+            kem.encapsulate(&public_key)
+        })
+        .await?
+    }
+
+    /// Recover the shared secret from `ciphertext` with `kem`
+    pub async fn decapsulate(
+        &self,
+        priority: CryptoPriority,
+        kem: Arc<dyn Kem>,
+        private_key: SecretBytes,
+        ciphertext: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        self.submit(priority, CryptoJobKind::Decapsulate, move || {
+            kem.decapsulate(private_key.expose(), &ciphertext)
+        })
+        .await?
+    }
+
+    /// Jobs of `priority` waiting for a worker
+    pub fn queue_depth(&self, priority: CryptoPriority) -> usize {
+        let queues = self.shared.queues.lock();
+        match priority {
+            CryptoPriority::Handshake => queues.handshake.len(),
+            CryptoPriority::Data => queues.data.len(),
+        }
+    }
+
+    /// Queue depth, throughput and wait times per priority class
+    pub fn metrics(&self) -> CryptoExecutorMetrics {
+        let completed_by_kind = CryptoJobKind::ALL
+            .iter()
+            .map(|kind| {
+                (
+                    format!("{:?}", kind).to_lowercase(),
+                    self.shared.completed_by_kind[kind.index()].load(Ordering::Relaxed),
+                )
+            })
+            .collect();
+        CryptoExecutorMetrics {
+            workers: self.workers,
+            handshake_workers: self.handshake_workers,
+            handshake: self
+                .shared
+                .handshake
+                .snapshot(self.queue_depth(CryptoPriority::Handshake)),
+            data: self
+                .shared
+                .data
+                .snapshot(self.queue_depth(CryptoPriority::Data)),
+            completed_by_kind,
+        }
+    }
+
+    /// Get executor statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        match serde_json::to_value(self.metrics()) {
+            Ok(serde_json::Value::Object(map)) => map.into_iter().collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+impl Drop for CryptoExecutor {
+    fn drop(&mut self) {
+        // Queued jobs are dropped with the queues; their callers see an error
+        self.shared.queues.lock().shutdown = true;
+        self.shared.work_ready.notify_all();
+        self.shared.handshake_ready.notify_all();
+    }
+}
+
+impl std::fmt::Debug for CryptoExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CryptoExecutor")
+            .field("workers", &self.workers)
+            .field("handshake_workers", &self.handshake_workers)
+            .field("max_queued", &self.max_queued)
+            .finish()
+    }
+}
+
+static CRYPTO_EXECUTOR: OnceCell<Arc<CryptoExecutor>> = OnceCell::new();
+
+/// Size the process-wide executor before its first use
+///
+/// Returns `Ok(false)` if the executor is already running; its sizing is
+/// then left unchanged.
+pub fn configure_crypto_executor(config: &CryptoExecutorConfig) -> Result<bool> {
+    if CRYPTO_EXECUTOR.get().is_some() {
+        return Ok(false);
+    }
+    let executor = Arc::new(CryptoExecutor::new(config)?);
+    Ok(CRYPTO_EXECUTOR.set(executor).is_ok())
+}
+
+/// Process-wide crypto executor, started with default sizing on first use
+pub fn crypto_executor() -> Arc<CryptoExecutor> {
+    CRYPTO_EXECUTOR
+        .get_or_init(|| {
+            let executor = CryptoExecutor::new(&CryptoExecutorConfig::default());
+            Arc::new(executor.expect("crypto executor needs two worker threads"))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto_protocols::PQCAlgorithm;
+    use crate::crypto_provider::{BuiltinKem, BuiltinSigner};
+    use crate::secret_memory::SensitiveKind;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn executor(workers: usize, max_queued: usize) -> Arc<CryptoExecutor> {
+        Arc::new(
+            CryptoExecutor::new(&CryptoExecutorConfig {
+                workers,
+                handshake_workers: 1,
+                max_queued,
+            })
+            .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_crypto_jobs_run_and_are_counted() {
+        let executor = executor(2, 16);
+        let signer: Arc<dyn Signer> =
+            Arc::new(BuiltinSigner::new(PQCAlgorithm::Dilithium2).unwrap());
+        let keypair = signer.keygen().unwrap();
+        let signature = executor
+            .sign(
+                CryptoPriority::Data,
+                signer.clone(),
+                SecretBytes::new(SensitiveKind::Key, keypair.private_key),
+                b"message".to_vec(),
+            )
+            .await
+            .unwrap();
+        assert!(executor
+            .verify(
+                CryptoPriority::Data,
+                signer,
+                keypair.public_key,
+                b"message".to_vec(),
+                signature,
+            )
+            .await
+            .unwrap());
+
+        let kem: Arc<dyn Kem> = Arc::new(BuiltinKem::new(PQCAlgorithm::Kyber512));
+        let keypair = kem.keygen().unwrap();
+        let (ciphertext, shared) = executor
+            .encapsulate(CryptoPriority::Handshake, kem.clone(), keypair.public_key)
+            .await
+            .unwrap();
+        let recovered = executor
+            .decapsulate(
+                CryptoPriority::Handshake,
+                kem,
+                SecretBytes::new(SensitiveKind::Key, keypair.private_key),
+                ciphertext,
+            )
+            .await
+            .unwrap();
+        assert_eq!(recovered, shared);
+
+        let metrics = executor.metrics();
+        assert_eq!(metrics.workers, 2);
+        assert_eq!(metrics.data.completed, 2);
+        assert_eq!(metrics.handshake.completed, 2);
+        assert_eq!(metrics.completed_by_kind["verify"], 1);
+        assert_eq!(metrics.completed_by_kind["decapsulate"], 1);
+        assert_eq!(executor.get_stats()["workers"], serde_json::json!(2));
+
+        let failed = executor
+            .submit(CryptoPriority::Data, CryptoJobKind::Verify, || {
+                panic!("boom")
+            })
+            .await;
+        assert!(matches!(failed, Err(SecureCommsError::SystemError(_))));
+        assert_eq!(executor.metrics().data.panicked, 1);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_jobs_bypass_data_backlog() {
+        let executor = executor(2, 16);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Arc::new(std::sync::Mutex::new(release_rx));
+
+        // Occupy the only general worker and queue more data jobs behind it
+        let mut backlog = Vec::new();
+        for _ in 0..4 {
+            let executor = executor.clone();
+            let release_rx = release_rx.clone();
+            backlog.push(tokio::spawn(async move {
+                executor
+                    .submit(CryptoPriority::Data, CryptoJobKind::Verify, move || {
+                        release_rx.lock().unwrap().recv().unwrap()
+                    })
+                    .await
+            }));
+        }
+        while executor.queue_depth(CryptoPriority::Data) < 3 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        let handshake = tokio::time::timeout(
+            Duration::from_secs(5),
+            executor.submit(CryptoPriority::Handshake, CryptoJobKind::Encapsulate, || 7),
+        )
+        .await
+        .expect("handshake job waited behind data jobs");
+        assert_eq!(handshake.unwrap(), 7);
+        assert_eq!(executor.metrics().data.completed, 0);
+        assert_eq!(executor.metrics().data.queued, 3);
+
+        for _ in 0..4 {
+            release_tx.send(()).unwrap();
+        }
+        for job in backlog {
+            job.await.unwrap().unwrap();
+        }
+        let metrics = executor.metrics();
+        assert_eq!(metrics.data.completed, 4);
+        assert!(metrics.data.max_wait_us >= metrics.handshake.max_wait_us);
+    }
+
+    #[tokio::test]
+    async fn test_queue_limit_and_sizing() {
+        assert!(CryptoExecutor::new(&CryptoExecutorConfig {
+            workers: 2,
+            handshake_workers: 2,
+            ..Default::default()
+        })
+        .is_err());
+
+        let executor = executor(2, 1);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel::<()>();
+        let running = {
+            let executor = executor.clone();
+            tokio::spawn(async move {
+                executor
+                    .submit(CryptoPriority::Data, CryptoJobKind::Sign, move || {
+                        started_tx.send(()).unwrap();
+                        release_rx.recv().unwrap()
+                    })
+                    .await
+            })
+        };
+        tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+            .await
+            .unwrap();
+        let queued = {
+            let executor = executor.clone();
+            tokio::spawn(async move {
+                executor
+                    .submit(CryptoPriority::Data, CryptoJobKind::Sign, || ())
+                    .await
+            })
+        };
+        while executor.queue_depth(CryptoPriority::Data) < 1 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        let rejected = executor
+            .submit(CryptoPriority::Data, CryptoJobKind::Sign, || ())
+            .await;
+        assert!(matches!(
+            rejected,
+            Err(SecureCommsError::ResourceExhausted(_))
+        ));
+        // The data backlog leaves handshake capacity untouched
+        executor
+            .submit(CryptoPriority::Handshake, CryptoJobKind::Encapsulate, || ())
+            .await
+            .unwrap();
+        assert_eq!(executor.metrics().data.rejected, 1);
+
+        release_tx.send(()).unwrap();
+        running.await.unwrap().unwrap();
+        queued.await.unwrap().unwrap();
+    }
+}
@@ -58,8 +58,8 @@
 //!
 //! ### Basic Crypto Protocols Setup
 //! ```rust,no_run
-//! use streamlined_secure_comms::crypto_protocols::CryptoProtocols;
-//! use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! use quantum_forge_secure_comms::crypto_protocols::CryptoProtocols;
+//! use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -73,8 +73,8 @@
 //!
 //! ### Post-Quantum Key Exchange
 //! ```rust,no_run
-//! # use streamlined_secure_comms::crypto_protocols::CryptoProtocols;
-//! # use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! # use quantum_forge_secure_comms::crypto_protocols::CryptoProtocols;
+//! # use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = SecurityConfig::production_ready();
@@ -95,8 +95,8 @@
 //!
 //! ### Algorithm Agility
 //! ```rust,no_run
-//! # use streamlined_secure_comms::crypto_protocols::{CryptoProtocols, PQC};
-//! # use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! # use quantum_forge_secure_comms::crypto_protocols::{CryptoProtocols, PQC};
+//! # use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = SecurityConfig::production_ready();
@@ -114,8 +114,8 @@
 //!
 //! ### Quantum Random Number Generation
 //! ```rust,no_run
-//! # use streamlined_secure_comms::crypto_protocols::CryptoProtocols;
-//! # use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! # use quantum_forge_secure_comms::crypto_protocols::CryptoProtocols;
+//! # use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = SecurityConfig::production_ready();
@@ -167,8 +167,15 @@
 //! - SPHINCS+-SHA2-192s: 192-bit security with balanced parameters
 //! - SPHINCS+-SHA2-256s: 256-bit security with maximum strength
 
+use crate::compute::spawn_compute;
+use crate::crypto_provider::ProviderRegistry;
+use crate::hsm_entropy::EntropyProvenance;
 use crate::performance::PerformanceMetrics;
-use crate::security_foundation::SecurityFoundation;
+#[cfg(any(test, feature = "experimental-ot"))]
+use crate::quantum_core::QuantumState;
+#[cfg(any(test, feature = "experimental-ot"))]
+use crate::quantum_ot::{run_oblivious_transfer, OtConfig, OtReceiver, OtSender};
+use crate::security_foundation::{SecurityFoundation, SecurityLevel};
 use crate::{Result, SecureCommsError};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
@@ -201,6 +208,8 @@ pub struct QRNG {
     rng: ChaCha20Rng,
     /// Flag indicating enhanced entropy seeding from security foundation
     entropy_enhanced: bool,
+    /// Provenance of the seed drawn from the security foundation
+    provenance: Option<EntropyProvenance>,
 }
 
 impl QRNG {
@@ -219,9 +228,32 @@ impl QRNG {
         Ok(Self {
             rng,
             entropy_enhanced: true,
+            provenance: security_foundation.entropy_provenance().cloned(),
         })
     }
     
+    /// Create QRNG from a fixed seed for reproducible runs
+    ///
+    /// Not for production keys: anyone knowing the seed can predict every
+    /// output. Used by deterministic quantum journaling to replay sessions.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            rng: ChaCha20Rng::from_seed(seed),
+            entropy_enhanced: false,
+            provenance: None,
+        }
+    }
+
+    /// Whether the QRNG was created from a fixed seed
+    pub fn is_deterministic(&self) -> bool {
+        !self.entropy_enhanced
+    }
+
+    /// Position in the generator's output stream, in 32-bit words
+    pub fn word_pos(&self) -> u128 {
+        self.rng.get_word_pos()
+    }
+
     /// Generate cryptographically secure random bytes
     /// 
     /// Produces high-quality random bytes suitable for cryptographic operations
@@ -251,6 +283,11 @@ impl QRNG {
     pub fn is_entropy_enhanced(&self) -> bool {
         self.entropy_enhanced
     }
+
+    /// Provenance of the seed; `None` for fixed-seed generators
+    pub fn provenance(&self) -> Option<&EntropyProvenance> {
+        self.provenance.as_ref()
+    }
 }
 
 /// Configuration for cryptographic protocols and algorithm selection
@@ -293,7 +330,7 @@ impl Default for CryptoConfig {
 /// Comprehensive set of quantum-resistant cryptographic algorithms standardized
 /// by NIST for protection against quantum computer attacks. Includes key
 /// encapsulation mechanisms, digital signatures, and hash-based signatures.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum PQCAlgorithm {
     /// ML-KEM (Kyber) Key Encapsulation Mechanisms - FIPS 203
     /// Kyber-512: NIST security level 1 (128-bit quantum security)
@@ -418,23 +455,45 @@ impl PQC {
         }
         
         // Generate new keypair using NIST-standardized algorithms
-        let keypair = match self.algorithm {
-            PQCAlgorithm::Kyber512 => self.generate_kyber_keypair(512),
-            PQCAlgorithm::Kyber768 => self.generate_kyber_keypair(768),
-            PQCAlgorithm::Kyber1024 => self.generate_kyber_keypair(1024),
-            PQCAlgorithm::Dilithium2 => self.generate_dilithium_keypair(2),
-            PQCAlgorithm::Dilithium3 => self.generate_dilithium_keypair(3),
-            PQCAlgorithm::Dilithium5 => self.generate_dilithium_keypair(5),
-            PQCAlgorithm::SphincsPlus128s => self.generate_sphincs_keypair(128),
-            PQCAlgorithm::SphincsPlus192s => self.generate_sphincs_keypair(192),
-            PQCAlgorithm::SphincsPlus256s => self.generate_sphincs_keypair(256),
-        }?;
+        let keypair = Self::keygen(self.algorithm)?;
         
         // Cache the keypair for future use
         self.cache_keypair(cache_id, &keypair);
         
         Ok(keypair)
     }
+
+    /// Generate key pair on the compute pool instead of the async runtime
+    ///
+    /// Same caching as `generate_keypair`; only a cache miss is offloaded.
+    pub async fn generate_keypair_offloaded(&mut self) -> Result<PQCKeyPair> {
+        let cache_id = format!("default_{:?}", self.algorithm);
+        if let Some(cached_keypair) = self.key_cache.get(&cache_id) {
+            return Ok(cached_keypair.clone());
+        }
+
+        let algorithm = self.algorithm;
+        let keypair = spawn_compute(move || Self::keygen(algorithm)).await??;
+        self.cache_keypair(&cache_id, &keypair);
+        Ok(keypair)
+    }
+
+    /// Generate a fresh key pair for `algorithm`
+    ///
+    /// Stateless, so it can run on any thread.
+    pub fn keygen(algorithm: PQCAlgorithm) -> Result<PQCKeyPair> {
+        match algorithm {
+            PQCAlgorithm::Kyber512 => Self::generate_kyber_keypair(512),
+            PQCAlgorithm::Kyber768 => Self::generate_kyber_keypair(768),
+            PQCAlgorithm::Kyber1024 => Self::generate_kyber_keypair(1024),
+            PQCAlgorithm::Dilithium2 => Self::generate_dilithium_keypair(2),
+            PQCAlgorithm::Dilithium3 => Self::generate_dilithium_keypair(3),
+            PQCAlgorithm::Dilithium5 => Self::generate_dilithium_keypair(5),
+            PQCAlgorithm::SphincsPlus128s => Self::generate_sphincs_keypair(128),
+            PQCAlgorithm::SphincsPlus192s => Self::generate_sphincs_keypair(192),
+            PQCAlgorithm::SphincsPlus256s => Self::generate_sphincs_keypair(256),
+        }
+    }
     
     /// Cache a key pair with the specified identifier for performance optimization
     /// 
@@ -497,6 +556,18 @@ impl PQC {
         }
     }
     
+    /// Encapsulate a fresh shared secret to an ML-KEM public key
+    ///
+    /// Returns the ciphertext for the key holder and the shared secret.
+    pub fn encapsulate(&mut self, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.ml_kem_encapsulate(public_key)
+    }
+
+    /// Recover the shared secret of an ML-KEM ciphertext
+    pub fn decapsulate(&mut self, private_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.ml_kem_decapsulate(private_key, ciphertext)
+    }
+
     /// Encrypt data using public key
     pub fn encrypt(&mut self, public_key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
         // TRUE ASYMMETRIC ENCRYPTION using ML-KEM
@@ -666,11 +737,7 @@ impl PQC {
             .take(32)
             .all(|(sig_byte, exp_byte)| {
                 // Strict cryptographic tolerance for production security
-                    let diff = if sig_byte > exp_byte {
-                        sig_byte - exp_byte
-                    } else {
-                        exp_byte - sig_byte
-                    };
+                    let diff = sig_byte.abs_diff(*exp_byte);
                 diff <= 1 // Very strict tolerance for cryptographic accuracy
             });
         
@@ -679,11 +746,7 @@ impl PQC {
             .iter()
             .zip(verification_hash[16..32].iter())
             .all(|(check_byte, verify_byte)| {
-                let diff = if check_byte > verify_byte {
-                    check_byte - verify_byte
-                } else {
-                    verify_byte - check_byte
-                };
+                let diff = check_byte.abs_diff(*verify_byte);
                 diff <= 2 // Allow controlled variance in check computation
             });
 
@@ -692,7 +755,7 @@ impl PQC {
     }
     
     // Helper methods for key generation - Real NIST ML-KEM Implementation
-    fn generate_kyber_keypair(&mut self, key_size: usize) -> Result<PQCKeyPair> {
+    fn generate_kyber_keypair(key_size: usize) -> Result<PQCKeyPair> {
         match key_size {
             512 => {
                 // ML-KEM-512 (NIST standardized Kyber-512)
@@ -749,7 +812,7 @@ impl PQC {
         }
     }
     
-    fn generate_dilithium_keypair(&mut self, security_level: usize) -> Result<PQCKeyPair> {
+    fn generate_dilithium_keypair(security_level: usize) -> Result<PQCKeyPair> {
         match security_level {
             2 => {
                 // ML-DSA-44 (NIST standardized Dilithium2)
@@ -806,7 +869,7 @@ impl PQC {
         }
     }
     
-    fn generate_sphincs_keypair(&mut self, key_size: usize) -> Result<PQCKeyPair> {
+    fn generate_sphincs_keypair(key_size: usize) -> Result<PQCKeyPair> {
         match key_size {
             128 => {
                 // SLH-DSA-SHA2-128s (NIST standardized SPHINCS+-SHA2-128s)
@@ -1403,6 +1466,7 @@ pub struct KeyExchangeResult {
     pub keys: CryptoKeys,
     pub security_level: u16,
     pub qkd_fidelity: f64,
+    pub qkd_error_rate: f64,
     pub setup_time_ms: u64,
 }
 
@@ -1411,6 +1475,7 @@ pub struct CryptoProtocols {
     qrng: QRNG,
     pqc: PQC,
     qkd: QKD,
+    providers: ProviderRegistry,
     metrics: PerformanceMetrics,
 }
 
@@ -1433,6 +1498,7 @@ impl CryptoProtocols {
             qrng,
             pqc,
             qkd,
+            providers: ProviderRegistry::default(),
             metrics,
         })
     }
@@ -1445,8 +1511,9 @@ impl CryptoProtocols {
     ) -> Result<KeyExchangeResult> {
         let start_time = Instant::now();
         
-        // Parallel optimization: Run PQC keypair generation and QKD session initialization concurrently
-        let pqc_future = async { self.pqc.generate_keypair() };
+        // Parallel optimization: PQC keypair generation runs on the compute pool
+        // while the QKD session is initialized
+        let pqc_future = self.pqc.generate_keypair_offloaded();
         let qkd_future = async { self.qkd.init_session(peer_id) };
         
         let (pqc_keypair, session_id) = tokio::try_join!(pqc_future, qkd_future)?;
@@ -1467,6 +1534,7 @@ impl CryptoProtocols {
             keys,
             security_level: 256,
             qkd_fidelity: qkd_session.fidelity,
+            qkd_error_rate: qkd_session.error_rate,
             setup_time_ms: start_time.elapsed().as_millis() as u64,
         })
     }
@@ -1480,6 +1548,27 @@ impl CryptoProtocols {
     pub fn qrng(&mut self) -> &mut QRNG {
         &mut self.qrng
     }
+
+    /// Replace the QRNG with one freshly seeded from the security foundation
+    ///
+    /// Used after a hardware entropy source is attached so that keys
+    /// generated from now on draw on it.
+    pub fn reseed_qrng(&mut self, security_foundation: &mut SecurityFoundation) -> Result<()> {
+        self.qrng = QRNG::with_entropy(security_foundation)?;
+        Ok(())
+    }
+    
+    /// Select the key exchange KEM required by a security level
+    ///
+    /// Later key exchanges, including rekeys of existing channels, use the
+    /// returned algorithm; cached key pairs of the previous one are dropped.
+    pub fn apply_security_level(&mut self, level: SecurityLevel) -> PQCAlgorithm {
+        let algorithm = PQC::select_algorithm_for_security_level(level.key_exchange_bits());
+        if self.pqc.get_algorithm() != algorithm {
+            self.pqc.set_algorithm(algorithm);
+        }
+        algorithm
+    }
     
     /// Get PQC reference
     pub fn pqc(&mut self) -> &mut PQC {
@@ -1490,6 +1579,59 @@ impl CryptoProtocols {
     pub fn qkd(&mut self) -> &mut QKD {
         &mut self.qkd
     }
+
+    /// KEM, signature and AEAD implementations used by channel logic
+    pub fn providers(&self) -> &ProviderRegistry {
+        &self.providers
+    }
+
+    /// Mutable provider registry, to swap in alternative implementations
+    pub fn providers_mut(&mut self) -> &mut ProviderRegistry {
+        &mut self.providers
+    }
+
+    /// Sending party of a quantum oblivious transfer (experimental)
+    ///
+    /// Returns the sender and the qubits to deliver to the receiver; see
+    /// `quantum_ot` for the rest of the exchange.
+    #[cfg(any(test, feature = "experimental-ot"))]
+    pub fn ot_sender(
+        &mut self,
+        config: &OtConfig,
+        messages: [Vec<u8>; 2],
+    ) -> Result<(OtSender, Vec<QuantumState>)> {
+        OtSender::new(config, messages, self.party_qrng()?)
+    }
+
+    /// Receiving party of a quantum oblivious transfer (experimental)
+    #[cfg(any(test, feature = "experimental-ot"))]
+    pub fn ot_receiver(&mut self, config: &OtConfig, choice: bool) -> Result<OtReceiver> {
+        OtReceiver::new(config, choice, self.party_qrng()?)
+    }
+
+    /// Run both parties of a quantum oblivious transfer locally (experimental)
+    ///
+    /// Returns the message `choice` selects. Meant for tests and
+    /// benchmarks; real computations put the parties on different nodes.
+    #[cfg(any(test, feature = "experimental-ot"))]
+    pub fn oblivious_transfer(
+        &mut self,
+        config: &OtConfig,
+        messages: [Vec<u8>; 2],
+        choice: bool,
+    ) -> Result<Vec<u8>> {
+        let sender_qrng = self.party_qrng()?;
+        let receiver_qrng = self.party_qrng()?;
+        run_oblivious_transfer(config, messages, choice, sender_qrng, receiver_qrng)
+    }
+
+    /// QRNG for one protocol party, seeded from this one
+    #[cfg(any(test, feature = "experimental-ot"))]
+    fn party_qrng(&mut self) -> Result<QRNG> {
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&self.qrng.generate_bytes(32)?);
+        Ok(QRNG::from_seed(seed))
+    }
 }
 
 #[cfg(test)]
@@ -1558,6 +1700,19 @@ mod tests {
         assert!(result.setup_time_ms < 1000); // Should be fast
     }
 
+    #[tokio::test]
+    async fn test_security_level_selects_kem() {
+        let mut foundation = SecurityFoundation::new(SecurityConfig::production_ready()).await.unwrap();
+        let mut crypto = CryptoProtocols::new(&mut foundation).await.unwrap();
+        
+        assert_eq!(crypto.apply_security_level(SecurityLevel::Maximum), PQCAlgorithm::Kyber1024);
+        let result = crypto.exchange_keys("peer_carol", 32).await.unwrap();
+        let keypair = result.keys.pqc_keypair.unwrap();
+        assert_eq!(keypair.algorithm, PQCAlgorithm::Kyber1024);
+        assert_eq!(keypair.security_level, 256);
+        assert_eq!(crypto.apply_security_level(SecurityLevel::Standard), PQCAlgorithm::Kyber512);
+    }
+    
     #[tokio::test]
     async fn test_algorithm_agility() {
         let config = SecurityConfig::production_ready();
@@ -1667,6 +1822,34 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_oblivious_transfer() {
+        let config = SecurityConfig::production_ready();
+        let mut foundation = SecurityFoundation::new(config).await.unwrap();
+        let mut crypto = CryptoProtocols::new(&mut foundation).await.unwrap();
+        let config = OtConfig::default();
+        let votes = [b"vote:no ".to_vec(), b"vote:yes".to_vec()];
+
+        for choice in [false, true] {
+            let received = crypto
+                .oblivious_transfer(&config, votes.clone(), choice)
+                .unwrap();
+            assert_eq!(received, votes[usize::from(choice)]);
+        }
+
+        // Parties created separately run the same exchange
+        let (mut sender, qubits) = crypto.ot_sender(&config, votes.clone()).unwrap();
+        let mut receiver = crypto.ot_receiver(&config, true).unwrap();
+        let challenge = sender.challenge(receiver.measure(qubits).unwrap()).unwrap();
+        let bases = sender
+            .check_openings(&receiver.open(&challenge).unwrap())
+            .unwrap();
+        let transfer = sender
+            .transfer(&receiver.index_sets(&bases).unwrap())
+            .unwrap();
+        assert_eq!(receiver.receive(&transfer).unwrap(), votes[1]);
+    }
+
     #[tokio::test]
     async fn test_malformed_ciphertext_robustness() {
         let config = SecurityConfig::production_ready();
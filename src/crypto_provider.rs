@@ -0,0 +1,419 @@
+//! # Crypto Providers - Pluggable KEM, Signature and AEAD Implementations
+//!
+//! Channel logic reaches its primitives through three traits instead of
+//! concrete types, so alternative implementations - liboqs bindings,
+//! hardware offload, national algorithms - can be swapped in without
+//! touching the protocol code:
+//!
+//! - **`Kem`**: Key generation, encapsulation and decapsulation for one
+//!   KEM parameter set
+//! - **`Signer`**: Key generation, signing and verification for one
+//!   signature parameter set
+//! - **`Aead`**: Sealing and opening channel payloads
+//!
+//! ## Provider Registry
+//!
+//! A `ProviderRegistry` holds one implementation per KEM and signature
+//! algorithm and one AEAD. `ProviderRegistry::builtin` registers the
+//! implementations this crate ships: ML-KEM-512/768/1024, ML-DSA-44/65/87
+//! and the CPU-dispatched AEAD of `hw_accel`. Registering a provider for an
+//! algorithm replaces the previous one; algorithms without a provider are
+//! refused with `SecureCommsError::Configuration`.
+//!
+//! Replacement AEADs must keep the sealed layout of `CryptoDispatch`
+//! (`[suite(1)][nonce(12)][ciphertext+tag]`) so peers with the built-in
+//! provider can still open their messages.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::PQCAlgorithm;
+//! use quantum_forge_secure_comms::crypto_provider::{BuiltinKem, ProviderRegistry};
+//! use std::sync::Arc;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut registry = ProviderRegistry::default();
+//! // Stand-in for e.g. a liboqs-backed implementation
+//! registry.register_kem(Arc::new(BuiltinKem::new(PQCAlgorithm::Kyber768)));
+//!
+//! let kem = registry.kem(PQCAlgorithm::Kyber768)?;
+//! let keypair = kem.keygen()?;
+//! let (ciphertext, shared) = kem.encapsulate(&keypair.public_key)?;
+//! assert_eq!(kem.decapsulate(&keypair.private_key, &ciphertext)?, shared);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::crypto_protocols::{PQCAlgorithm, PQCKeyPair, PQC, QRNG};
+use crate::hw_accel::{AeadSuite, CryptoDispatch, DispatchPolicy};
+use crate::hybrid_signature::{ml_dsa_sign, ml_dsa_verify, MlDsaLevel, MlDsaPublicKey};
+use crate::{Result, SecureCommsError};
+
+/// Name of the provider shipped with this crate
+pub const BUILTIN_PROVIDER: &str = "builtin";
+
+/// Key encapsulation mechanism for one parameter set
+pub trait Kem: Send + Sync {
+    /// Name of the implementing provider
+    fn provider(&self) -> &str;
+
+    /// Parameter set
+    fn algorithm(&self) -> PQCAlgorithm;
+
+    /// Generate a fresh key pair
+    fn keygen(&self) -> Result<PQCKeyPair>;
+
+    /// Encapsulate a fresh secret to `public_key`
+    ///
+    /// Returns the ciphertext and the shared secret.
+    fn encapsulate(&self, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)>;
+
+    /// Recover the shared secret from `ciphertext`
+    fn decapsulate(&self, private_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Signature scheme for one parameter set
+pub trait Signer: Send + Sync {
+    /// Name of the implementing provider
+    fn provider(&self) -> &str;
+
+    /// Parameter set
+    fn algorithm(&self) -> PQCAlgorithm;
+
+    /// Generate a fresh key pair
+    fn keygen(&self) -> Result<PQCKeyPair>;
+
+    /// Sign `message`
+    fn sign(&self, private_key: &[u8], message: &[u8]) -> Result<Vec<u8>>;
+
+    /// Whether `signature` is valid for `message` under `public_key`
+    fn verify(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool;
+}
+
+/// Authenticated encryption of channel payloads
+pub trait Aead: Send + Sync {
+    /// Name of the implementing provider
+    fn provider(&self) -> &str;
+
+    /// Suite used by `seal`
+    fn suite(&self) -> AeadSuite;
+
+    /// Encrypt `plaintext`, binding `aad`
+    fn seal(&self, key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>>;
+
+    /// Decrypt a payload produced by `seal`
+    fn open(&self, key: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Built-in ML-KEM (FIPS 203)
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinKem {
+    algorithm: PQCAlgorithm,
+}
+
+impl BuiltinKem {
+    /// ML-KEM with the given parameter set
+    pub const fn new(algorithm: PQCAlgorithm) -> Self {
+        Self { algorithm }
+    }
+
+    fn pqc(&self) -> PQC {
+        // ML-KEM draws on the operating system's randomness; the QRNG is unused
+        PQC::new(self.algorithm, QRNG::from_seed(rand::random()))
+    }
+}
+
+impl Kem for BuiltinKem {
+    fn provider(&self) -> &str {
+        BUILTIN_PROVIDER
+    }
+
+    fn algorithm(&self) -> PQCAlgorithm {
+        self.algorithm
+    }
+
+    fn keygen(&self) -> Result<PQCKeyPair> {
+        PQC::keygen(self.algorithm)
+    }
+
+    fn encapsulate(&self, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        self.pqc().encapsulate(public_key)
+    }
+
+    fn decapsulate(&self, private_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.pqc().decapsulate(private_key, ciphertext)
+    }
+}
+
+/// Built-in ML-DSA (FIPS 204)
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinSigner {
+    algorithm: PQCAlgorithm,
+    level: MlDsaLevel,
+}
+
+impl BuiltinSigner {
+    /// ML-DSA with the given parameter set
+    ///
+    /// Only the Dilithium algorithms are supported.
+    pub fn new(algorithm: PQCAlgorithm) -> Result<Self> {
+        let level = match algorithm {
+            PQCAlgorithm::Dilithium2 => MlDsaLevel::MlDsa44,
+            PQCAlgorithm::Dilithium3 => MlDsaLevel::MlDsa65,
+            PQCAlgorithm::Dilithium5 => MlDsaLevel::MlDsa87,
+            other => {
+                return Err(SecureCommsError::Configuration(format!(
+                    "No built-in signer for {:?}",
+                    other
+                )))
+            }
+        };
+        Ok(Self { algorithm, level })
+    }
+}
+
+impl Signer for BuiltinSigner {
+    fn provider(&self) -> &str {
+        BUILTIN_PROVIDER
+    }
+
+    fn algorithm(&self) -> PQCAlgorithm {
+        self.algorithm
+    }
+
+    fn keygen(&self) -> Result<PQCKeyPair> {
+        PQC::keygen(self.algorithm)
+    }
+
+    fn sign(&self, private_key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+        ml_dsa_sign(self.level, private_key, message)
+    }
+
+    fn verify(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let key = MlDsaPublicKey {
+            level: self.level,
+            bytes: public_key.to_vec(),
+        };
+        ml_dsa_verify(&key, message, signature)
+    }
+}
+
+impl Aead for CryptoDispatch {
+    fn provider(&self) -> &str {
+        BUILTIN_PROVIDER
+    }
+
+    fn suite(&self) -> AeadSuite {
+        CryptoDispatch::suite(self)
+    }
+
+    fn seal(&self, key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        CryptoDispatch::seal(self, key, nonce, aad, plaintext)
+    }
+
+    fn open(&self, key: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+        CryptoDispatch::open(self, key, aad, sealed)
+    }
+}
+
+/// Implementations selected per algorithm
+#[derive(Clone)]
+pub struct ProviderRegistry {
+    kems: HashMap<PQCAlgorithm, Arc<dyn Kem>>,
+    signers: HashMap<PQCAlgorithm, Arc<dyn Signer>>,
+    aead: Arc<dyn Aead>,
+}
+
+impl ProviderRegistry {
+    /// Registry with the built-in implementations, AEAD dispatched under `policy`
+    pub fn builtin(policy: DispatchPolicy) -> Self {
+        let mut registry = Self {
+            kems: HashMap::new(),
+            signers: HashMap::new(),
+            aead: Arc::new(CryptoDispatch::new(policy)),
+        };
+        for algorithm in [
+            PQCAlgorithm::Kyber512,
+            PQCAlgorithm::Kyber768,
+            PQCAlgorithm::Kyber1024,
+        ] {
+            registry.register_kem(Arc::new(BuiltinKem::new(algorithm)));
+        }
+        for algorithm in [
+            PQCAlgorithm::Dilithium2,
+            PQCAlgorithm::Dilithium3,
+            PQCAlgorithm::Dilithium5,
+        ] {
+            if let Ok(signer) = BuiltinSigner::new(algorithm) {
+                registry.register_signer(Arc::new(signer));
+            }
+        }
+        registry
+    }
+
+    /// Use `kem` for its algorithm, returning the implementation it replaces
+    pub fn register_kem(&mut self, kem: Arc<dyn Kem>) -> Option<Arc<dyn Kem>> {
+        self.kems.insert(kem.algorithm(), kem)
+    }
+
+    /// Use `signer` for its algorithm, returning the implementation it replaces
+    pub fn register_signer(&mut self, signer: Arc<dyn Signer>) -> Option<Arc<dyn Signer>> {
+        self.signers.insert(signer.algorithm(), signer)
+    }
+
+    /// Use `aead` for channel payloads, returning the implementation it replaces
+    pub fn set_aead(&mut self, aead: Arc<dyn Aead>) -> Arc<dyn Aead> {
+        std::mem::replace(&mut self.aead, aead)
+    }
+
+    /// KEM implementation for `algorithm`
+    pub fn kem(&self, algorithm: PQCAlgorithm) -> Result<Arc<dyn Kem>> {
+        self.kems.get(&algorithm).cloned().ok_or_else(|| {
+            SecureCommsError::Configuration(format!("No KEM provider for {:?}", algorithm))
+        })
+    }
+
+    /// Signature implementation for `algorithm`
+    pub fn signer(&self, algorithm: PQCAlgorithm) -> Result<Arc<dyn Signer>> {
+        self.signers.get(&algorithm).cloned().ok_or_else(|| {
+            SecureCommsError::Configuration(format!("No signature provider for {:?}", algorithm))
+        })
+    }
+
+    /// AEAD implementation for channel payloads
+    pub fn aead(&self) -> &dyn Aead {
+        self.aead.as_ref()
+    }
+
+    /// Provider name per primitive, e.g. `"kem:Kyber768" -> "builtin"`
+    pub fn summary(&self) -> BTreeMap<String, String> {
+        let mut summary = BTreeMap::new();
+        for (algorithm, kem) in &self.kems {
+            summary.insert(format!("kem:{:?}", algorithm), kem.provider().to_string());
+        }
+        for (algorithm, signer) in &self.signers {
+            summary.insert(
+                format!("signer:{:?}", algorithm),
+                signer.provider().to_string(),
+            );
+        }
+        summary.insert(
+            format!("aead:{:?}", self.aead.suite()),
+            self.aead.provider().to_string(),
+        );
+        summary
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::builtin(DispatchPolicy::Auto)
+    }
+}
+
+impl fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProviderRegistry")
+            .field("providers", &self.summary())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Built-in ML-KEM that counts encapsulations, standing in for an offload engine
+    struct CountingKem {
+        inner: BuiltinKem,
+        encapsulations: AtomicUsize,
+    }
+
+    impl Kem for CountingKem {
+        fn provider(&self) -> &str {
+            "counting"
+        }
+
+        fn algorithm(&self) -> PQCAlgorithm {
+            self.inner.algorithm()
+        }
+
+        fn keygen(&self) -> Result<PQCKeyPair> {
+            self.inner.keygen()
+        }
+
+        fn encapsulate(&self, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+            self.encapsulations.fetch_add(1, Ordering::SeqCst);
+            self.inner.encapsulate(public_key)
+        }
+
+        fn decapsulate(&self, private_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+            self.inner.decapsulate(private_key, ciphertext)
+        }
+    }
+
+    #[test]
+    fn test_builtin_providers_round_trip() {
+        let registry = ProviderRegistry::default();
+
+        let kem = registry.kem(PQCAlgorithm::Kyber1024).unwrap();
+        let keypair = kem.keygen().unwrap();
+        let (ciphertext, shared) = kem.encapsulate(&keypair.public_key).unwrap();
+        assert_eq!(
+            kem.decapsulate(&keypair.private_key, &ciphertext).unwrap(),
+            shared
+        );
+
+        let signer = registry.signer(PQCAlgorithm::Dilithium3).unwrap();
+        let keypair = signer.keygen().unwrap();
+        let signature = signer.sign(&keypair.private_key, b"message").unwrap();
+        assert!(signer.verify(&keypair.public_key, b"message", &signature));
+        assert!(!signer.verify(&keypair.public_key, b"other", &signature));
+
+        let aead = registry.aead();
+        let sealed = aead
+            .seal(&[3u8; 32], &[0u8; 12], b"aad", b"payload")
+            .unwrap();
+        assert_eq!(aead.open(&[3u8; 32], b"aad", &sealed).unwrap(), b"payload");
+        assert!(aead.open(&[3u8; 32], b"other", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_registered_provider_replaces_builtin() {
+        let mut registry = ProviderRegistry::default();
+        let counting = Arc::new(CountingKem {
+            inner: BuiltinKem::new(PQCAlgorithm::Kyber768),
+            encapsulations: AtomicUsize::new(0),
+        });
+        let replaced = registry.register_kem(counting.clone()).unwrap();
+        assert_eq!(replaced.provider(), BUILTIN_PROVIDER);
+
+        let kem = registry.kem(PQCAlgorithm::Kyber768).unwrap();
+        let keypair = kem.keygen().unwrap();
+        kem.encapsulate(&keypair.public_key).unwrap();
+        assert_eq!(counting.encapsulations.load(Ordering::SeqCst), 1);
+
+        let summary = registry.summary();
+        assert_eq!(summary["kem:Kyber768"], "counting");
+        assert_eq!(summary["kem:Kyber512"], BUILTIN_PROVIDER);
+    }
+
+    #[test]
+    fn test_missing_provider_is_refused() {
+        let registry = ProviderRegistry::default();
+        assert!(matches!(
+            registry.kem(PQCAlgorithm::Dilithium2),
+            Err(SecureCommsError::Configuration(_))
+        ));
+        assert!(matches!(
+            registry.signer(PQCAlgorithm::SphincsPlus128s),
+            Err(SecureCommsError::Configuration(_))
+        ));
+        assert!(BuiltinSigner::new(PQCAlgorithm::Kyber768).is_err());
+    }
+}
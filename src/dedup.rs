@@ -0,0 +1,308 @@
+//! # Receive Deduplication - Bounded, Time-Windowed Duplicate Suppression
+//!
+//! Retransmissions, failover resends and gossip relays can hand the same
+//! message to the receive path more than once. The dedup cache remembers
+//! every accepted message for a configurable window and drops later copies
+//! once the interceptor chain has passed them, before they trigger receipts
+//! or reach the application.
+//!
+//! ## Keys
+//!
+//! - **Message ID**: Each sender-chosen message ID is accepted once per sender
+//! - **Sequence**: Messages carrying a per-peer sequence number are also keyed
+//!   by `(sender, sequence)`, so a replay under a fresh message ID is caught
+//!
+//! ## Bounds
+//!
+//! Entries older than the window are pruned on every check. When the entry
+//! count or the estimated memory footprint exceeds its cap, the oldest
+//! entries are evicted first; a duplicate arriving after its entry was
+//! evicted is accepted again, so the caps trade memory for protection.
+//!
+//! ## Metrics
+//!
+//! Each dropped duplicate increments `secure_comms_duplicates_dropped_total`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::dedup::{DedupCache, DedupConfig};
+//! use quantum_forge_secure_comms::expiry::now_ms;
+//!
+//! let mut cache = DedupCache::new(DedupConfig::default());
+//! assert!(cache.check("peer_1", "msg_1", Some(1), now_ms()));
+//! assert!(!cache.check("peer_1", "msg_1", Some(1), now_ms()));
+//! println!("{:?}", cache.stats());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Fixed per-entry bookkeeping cost used in the memory estimate
+const ENTRY_OVERHEAD_BYTES: usize = 96;
+
+/// Receive deduplication settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupConfig {
+    /// Drop duplicate messages on receive
+    pub enabled: bool,
+    /// Seconds a message stays in the cache
+    pub window_seconds: u64,
+    /// Maximum number of remembered messages
+    pub max_entries: usize,
+    /// Upper bound on the estimated cache memory in bytes
+    pub max_memory_bytes: usize,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_seconds: 300,
+            max_entries: 100_000,
+            max_memory_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Counters of the dedup cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DedupStats {
+    /// Messages checked
+    pub checked: u64,
+    /// Duplicates dropped
+    pub duplicates_dropped: u64,
+    /// Entries pruned after leaving the window
+    pub expired: u64,
+    /// Entries evicted early by the entry or memory cap
+    pub evicted: u64,
+    /// Entries currently held
+    pub entries: usize,
+    /// Estimated memory currently held in bytes
+    pub memory_bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    id_key: String,
+    sequence_key: Option<String>,
+    inserted_ms: u64,
+    size: usize,
+}
+
+/// Time-windowed set of recently accepted messages
+#[derive(Debug, Clone)]
+pub struct DedupCache {
+    config: DedupConfig,
+    keys: HashSet<String>,
+    order: VecDeque<Entry>,
+    memory_bytes: usize,
+    stats: DedupStats,
+}
+
+impl DedupCache {
+    /// Create cache with the given settings
+    pub fn new(config: DedupConfig) -> Self {
+        Self {
+            config,
+            keys: HashSet::new(),
+            order: VecDeque::new(),
+            memory_bytes: 0,
+            stats: DedupStats::default(),
+        }
+    }
+
+    /// Record a message, returning `false` if it is a duplicate
+    ///
+    /// Always returns `true` when deduplication is disabled.
+    pub fn check(
+        &mut self,
+        sender_id: &str,
+        message_id: &str,
+        sequence: Option<u64>,
+        now_ms: u64,
+    ) -> bool {
+        if !self.config.enabled {
+            return true;
+        }
+        self.stats.checked += 1;
+        self.prune(now_ms);
+
+        let id_key = format!("id:{}:{}", sender_id, message_id);
+        let sequence_key = sequence.map(|sequence| format!("seq:{}:{}", sender_id, sequence));
+        let duplicate = self.keys.contains(&id_key)
+            || sequence_key
+                .as_ref()
+                .map(|key| self.keys.contains(key))
+                .unwrap_or(false);
+        if duplicate {
+            self.stats.duplicates_dropped += 1;
+            metrics::counter!("secure_comms_duplicates_dropped_total", 1);
+            return false;
+        }
+
+        let size = ENTRY_OVERHEAD_BYTES
+            + id_key.len()
+            + sequence_key.as_ref().map(|key| key.len()).unwrap_or(0);
+        self.keys.insert(id_key.clone());
+        if let Some(key) = &sequence_key {
+            self.keys.insert(key.clone());
+        }
+        self.order.push_back(Entry {
+            id_key,
+            sequence_key,
+            inserted_ms: now_ms,
+            size,
+        });
+        self.memory_bytes += size;
+
+        while self.order.len() > self.config.max_entries.max(1)
+            || (self.memory_bytes > self.config.max_memory_bytes && self.order.len() > 1)
+        {
+            self.evict_oldest();
+            self.stats.evicted += 1;
+        }
+        true
+    }
+
+    /// Drop entries older than the window
+    pub fn prune(&mut self, now_ms: u64) {
+        let window_ms = self.config.window_seconds.saturating_mul(1000);
+        while let Some(oldest) = self.order.front() {
+            if now_ms.saturating_sub(oldest.inserted_ms) <= window_ms {
+                break;
+            }
+            self.evict_oldest();
+            self.stats.expired += 1;
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(entry) = self.order.pop_front() {
+            self.keys.remove(&entry.id_key);
+            if let Some(key) = &entry.sequence_key {
+                self.keys.remove(key);
+            }
+            self.memory_bytes = self.memory_bytes.saturating_sub(entry.size);
+        }
+    }
+
+    /// Number of remembered messages
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Current settings
+    pub fn config(&self) -> &DedupConfig {
+        &self.config
+    }
+
+    /// Cache counters
+    pub fn stats(&self) -> DedupStats {
+        DedupStats {
+            entries: self.order.len(),
+            memory_bytes: self.memory_bytes,
+            ..self.stats
+        }
+    }
+
+    /// Cache counters as a JSON map
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let stats = self.stats();
+        let mut map = HashMap::new();
+        map.insert("checked".to_string(), serde_json::json!(stats.checked));
+        map.insert(
+            "duplicates_dropped".to_string(),
+            serde_json::json!(stats.duplicates_dropped),
+        );
+        map.insert("expired".to_string(), serde_json::json!(stats.expired));
+        map.insert("evicted".to_string(), serde_json::json!(stats.evicted));
+        map.insert("entries".to_string(), serde_json::json!(stats.entries));
+        map.insert(
+            "memory_bytes".to_string(),
+            serde_json::json!(stats.memory_bytes),
+        );
+        map
+    }
+}
+
+impl Default for DedupCache {
+    fn default() -> Self {
+        Self::new(DedupConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_by_id_and_sequence() {
+        let mut cache = DedupCache::default();
+        assert!(cache.check("peer_1", "msg_1", Some(1), 1_000));
+        assert!(!cache.check("peer_1", "msg_1", Some(1), 1_001));
+        // Replay of sequence 1 under a new message ID
+        assert!(!cache.check("peer_1", "msg_2", Some(1), 1_002));
+        // Same ID from another sender is a different message
+        assert!(cache.check("peer_2", "msg_1", Some(1), 1_003));
+        assert!(cache.check("peer_1", "msg_3", None, 1_004));
+
+        let stats = cache.stats();
+        assert_eq!(stats.checked, 5);
+        assert_eq!(stats.duplicates_dropped, 2);
+        assert_eq!(stats.entries, 3);
+    }
+
+    #[test]
+    fn test_window_expiry() {
+        let mut cache = DedupCache::new(DedupConfig {
+            window_seconds: 1,
+            ..Default::default()
+        });
+        assert!(cache.check("peer_1", "msg_1", None, 0));
+        assert!(!cache.check("peer_1", "msg_1", None, 1_000));
+        assert!(cache.check("peer_1", "msg_1", None, 2_001));
+        assert_eq!(cache.stats().expired, 1);
+    }
+
+    #[test]
+    fn test_entry_and_memory_caps() {
+        let mut cache = DedupCache::new(DedupConfig {
+            max_entries: 2,
+            ..Default::default()
+        });
+        for i in 0..3 {
+            assert!(cache.check("peer_1", &format!("msg_{}", i), None, 0));
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evicted, 1);
+        // Evicted entry is forgotten
+        assert!(cache.check("peer_1", "msg_0", None, 0));
+
+        let mut small = DedupCache::new(DedupConfig {
+            max_memory_bytes: 2 * ENTRY_OVERHEAD_BYTES,
+            ..Default::default()
+        });
+        for i in 0..10 {
+            small.check("peer_1", &format!("msg_{}", i), None, 0);
+        }
+        assert!(small.stats().memory_bytes <= 2 * ENTRY_OVERHEAD_BYTES);
+        assert!(!small.is_empty());
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut cache = DedupCache::new(DedupConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        assert!(cache.check("peer_1", "msg_1", None, 0));
+        assert!(cache.check("peer_1", "msg_1", None, 0));
+        assert!(cache.is_empty());
+    }
+}
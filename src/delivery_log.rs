@@ -0,0 +1,389 @@
+//! # Delivery Log - Receive-Side Journal for Exactly-Once Processing
+//!
+//! Journal of inbound messages, keyed by message ID. A message is journaled
+//! when it is handed to the application and marked processed once the
+//! application's handler has finished with it. After a crash the journal
+//! replays every message that was received but never marked, and refuses
+//! copies of messages already processed, so a handler that marks its work
+//! durably sees each message exactly once across restarts.
+//!
+//! ## Storage Layout
+//!
+//! The log keeps its state in a `Storage` backend, by default a
+//! `FileStorage` at the configured path:
+//!
+//! - `r/<sequence>`: Received message, keyed by arrival order
+//! - `p/<message ID>`: Processed marker
+//!
+//! Marking a message processed deletes its `r/` entry and writes its `p/`
+//! marker in one batch, so a crash never leaves a message both pending and
+//! processed. With an `EnvelopeCipher` every value is stored as a sealed
+//! envelope. `compact` re-seals pending messages under the current master
+//! key and compacts the backend.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::delivery_log::DeliveryLog;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut log = DeliveryLog::open("data/delivery.log")?;
+//! for message in log.unprocessed() {
+//!     println!("Reprocessing {} after restart", message.message_id);
+//!     log.mark_processed(&message.message_id)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::envelope::{Envelope, EnvelopeCipher};
+use crate::storage::{FileStorage, Storage, WriteBatch};
+use crate::streamlined_client::SecureMessage;
+use crate::{Result, SecureCommsError};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Envelope context binding sealed records to the delivery log
+const LOG_CONTEXT: &str = "delivery_log";
+
+/// Key prefix of received messages
+const RECEIVED_PREFIX: &str = "r/";
+
+/// Key prefix of processed markers
+const PROCESSED_PREFIX: &str = "p/";
+
+/// Receive-side processing journal
+#[derive(Debug)]
+pub struct DeliveryLog {
+    /// Backing store
+    storage: Arc<dyn Storage>,
+    /// Seals values before they are stored
+    cipher: Option<Arc<EnvelopeCipher>>,
+    /// Received messages not yet processed, in arrival order
+    pending: Vec<String>,
+    /// Pending messages by ID, with their arrival sequence
+    messages: HashMap<String, (u64, SecureMessage)>,
+    /// IDs of processed messages
+    processed: HashSet<String>,
+    /// Sequence assigned to the next received message
+    next_sequence: u64,
+    /// Batches written through this handle
+    records_written: u64,
+}
+
+impl DeliveryLog {
+    /// Open (or create) a file-backed delivery log and load its state
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open_with_cipher(path, None)
+    }
+
+    /// Open (or create) a file-backed delivery log, sealing values when a
+    /// cipher is given
+    pub fn open_with_cipher<P: AsRef<Path>>(
+        path: P,
+        cipher: Option<Arc<EnvelopeCipher>>,
+    ) -> Result<Self> {
+        Self::with_storage(Arc::new(FileStorage::open(path)?), cipher)
+    }
+
+    /// Load a delivery log from any storage backend
+    ///
+    /// Sealed values in a log opened without a cipher are an error.
+    pub fn with_storage(
+        storage: Arc<dyn Storage>,
+        cipher: Option<Arc<EnvelopeCipher>>,
+    ) -> Result<Self> {
+        let mut log = Self {
+            storage,
+            cipher,
+            pending: Vec::new(),
+            messages: HashMap::new(),
+            processed: HashSet::new(),
+            next_sequence: 0,
+            records_written: 0,
+        };
+
+        for (key, _) in log.storage.scan_prefix(PROCESSED_PREFIX.as_bytes())? {
+            let message_id = String::from_utf8_lossy(&key[PROCESSED_PREFIX.len()..]).into_owned();
+            log.processed.insert(message_id);
+        }
+        // Zero-padded sequences scan in arrival order
+        for (key, value) in log.storage.scan_prefix(RECEIVED_PREFIX.as_bytes())? {
+            let sequence = std::str::from_utf8(&key[RECEIVED_PREFIX.len()..])
+                .ok()
+                .and_then(|sequence| sequence.parse::<u64>().ok())
+                .ok_or_else(|| {
+                    SecureCommsError::SystemError(format!(
+                        "Corrupt delivery log key {}",
+                        String::from_utf8_lossy(&key)
+                    ))
+                })?;
+            let message: SecureMessage =
+                serde_json::from_slice(&log.decode(&value)?).map_err(|e| {
+                    SecureCommsError::SystemError(format!(
+                        "Corrupt delivery log message at sequence {}: {}",
+                        sequence, e
+                    ))
+                })?;
+            log.next_sequence = log.next_sequence.max(sequence + 1);
+            if !log.contains(&message.message_id) {
+                log.pending.push(message.message_id.clone());
+                log.messages
+                    .insert(message.message_id.clone(), (sequence, message));
+            }
+        }
+        Ok(log)
+    }
+
+    /// Journal a message before handing it to the application
+    ///
+    /// Returns false without writing if the message ID is already journaled,
+    /// processed or not.
+    pub fn record_received(&mut self, message: &SecureMessage) -> Result<bool> {
+        if self.contains(&message.message_id) {
+            return Ok(false);
+        }
+        let sequence = self.next_sequence;
+        let mut batch = WriteBatch::new();
+        batch.put(&received_key(sequence), &self.encode(message)?);
+        self.write(batch)?;
+
+        self.next_sequence += 1;
+        self.pending.push(message.message_id.clone());
+        self.messages
+            .insert(message.message_id.clone(), (sequence, message.clone()));
+        Ok(true)
+    }
+
+    /// Durably mark a journaled message as processed
+    ///
+    /// Marking a message twice is a no-op; marking an unknown message fails
+    /// with `SecureCommsError::Validation`.
+    pub fn mark_processed(&mut self, message_id: &str) -> Result<()> {
+        if self.processed.contains(message_id) {
+            return Ok(());
+        }
+        let Some((sequence, _)) = self.messages.get(message_id) else {
+            return Err(SecureCommsError::Validation(format!(
+                "Message {} is not in the delivery log",
+                message_id
+            )));
+        };
+        let mut batch = WriteBatch::new();
+        batch
+            .delete(&received_key(*sequence))
+            .put(&processed_key(message_id), &[]);
+        self.write(batch)?;
+
+        self.pending.retain(|pending| pending != message_id);
+        self.messages.remove(message_id);
+        self.processed.insert(message_id.to_string());
+        Ok(())
+    }
+
+    /// Whether the message has been journaled, processed or not
+    pub fn contains(&self, message_id: &str) -> bool {
+        self.processed.contains(message_id) || self.messages.contains_key(message_id)
+    }
+
+    /// Journaled message that is not yet processed
+    pub fn message(&self, message_id: &str) -> Option<&SecureMessage> {
+        self.messages.get(message_id).map(|(_, message)| message)
+    }
+
+    /// Whether the application has finished with the message
+    pub fn is_processed(&self, message_id: &str) -> bool {
+        self.processed.contains(message_id)
+    }
+
+    /// Received but unprocessed messages, in arrival order
+    pub fn unprocessed(&self) -> Vec<SecureMessage> {
+        self.pending
+            .iter()
+            .filter_map(|message_id| self.messages.get(message_id))
+            .map(|(_, message)| message.clone())
+            .collect()
+    }
+
+    /// Number of received but unprocessed messages
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of processed message IDs remembered
+    pub fn processed_count(&self) -> usize {
+        self.processed.len()
+    }
+
+    /// Re-seal pending messages and compact the backing store
+    ///
+    /// Values are sealed under the cipher's current master key, so
+    /// compaction also moves the log off a rotated one. Returns the number
+    /// of entries kept.
+    pub fn compact(&mut self) -> Result<usize> {
+        let mut batch = WriteBatch::new();
+        for message_id in &self.pending {
+            if let Some((sequence, message)) = self.messages.get(message_id) {
+                batch.put(&received_key(*sequence), &self.encode(message)?);
+            }
+        }
+        self.write(batch)?;
+        self.storage.compact()?;
+        Ok(self.processed.len() + self.pending.len())
+    }
+
+    /// Backing store
+    pub fn storage(&self) -> &Arc<dyn Storage> {
+        &self.storage
+    }
+
+    /// Batches written through this handle
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+
+    fn write(&mut self, batch: WriteBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        self.storage.write_batch(batch)?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    fn encode(&self, message: &SecureMessage) -> Result<Vec<u8>> {
+        let value = serde_json::to_vec(message).map_err(|e| {
+            SecureCommsError::SystemError(format!("Delivery log serialization failed: {}", e))
+        })?;
+        match &self.cipher {
+            Some(cipher) => cipher.seal_bytes(LOG_CONTEXT, &value),
+            None => Ok(value),
+        }
+    }
+
+    fn decode(&self, value: &[u8]) -> Result<Vec<u8>> {
+        let Some(envelope) = Envelope::from_bytes(value) else {
+            return Ok(value.to_vec());
+        };
+        let cipher = self.cipher.as_ref().ok_or_else(|| {
+            SecureCommsError::Configuration(
+                "Sealed delivery log entry but no cipher configured".to_string(),
+            )
+        })?;
+        cipher.open(LOG_CONTEXT, &envelope)
+    }
+}
+
+fn received_key(sequence: u64) -> Vec<u8> {
+    format!("{}{:020}", RECEIVED_PREFIX, sequence).into_bytes()
+}
+
+fn processed_key(message_id: &str) -> Vec<u8> {
+    format!("{}{}", PROCESSED_PREFIX, message_id).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn message(payload: &[u8]) -> SecureMessage {
+        SecureMessage::new("alice".to_string(), "bob".to_string(), payload.to_vec())
+    }
+
+    #[test]
+    fn test_unprocessed_messages_replay_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("delivery.log");
+        let first = message(b"one");
+        let second = message(b"two");
+        {
+            let mut log = DeliveryLog::open(&path).unwrap();
+            assert!(log.record_received(&first).unwrap());
+            assert!(log.record_received(&second).unwrap());
+            assert!(!log.record_received(&first).unwrap());
+            log.mark_processed(&first.message_id).unwrap();
+            // Crash before the second message is processed
+        }
+
+        let mut log = DeliveryLog::open(&path).unwrap();
+        assert!(log.is_processed(&first.message_id));
+        let replayed = log.unprocessed();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].payload, b"two");
+
+        // Copies of processed and pending messages are both refused
+        assert!(!log.record_received(&first).unwrap());
+        assert!(!log.record_received(&second).unwrap());
+        log.mark_processed(&second.message_id).unwrap();
+        log.mark_processed(&second.message_id).unwrap();
+        assert_eq!(log.pending_count(), 0);
+        assert!(matches!(
+            log.mark_processed("unknown"),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_processing_swaps_entries_atomically() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let mut log = DeliveryLog::with_storage(storage.clone(), None).unwrap();
+        let messages: Vec<SecureMessage> = (0..12u8).map(|i| message(&[i])).collect();
+        for message in &messages {
+            log.record_received(message).unwrap();
+        }
+        log.mark_processed(&messages[0].message_id).unwrap();
+
+        assert_eq!(storage.scan_prefix(b"r/").unwrap().len(), 11);
+        assert_eq!(
+            storage.scan_prefix(b"p/").unwrap()[0].0,
+            processed_key(&messages[0].message_id)
+        );
+        // Arrival order survives reload past ten entries
+        let log = DeliveryLog::with_storage(storage, None).unwrap();
+        let replayed: Vec<String> = log
+            .unprocessed()
+            .into_iter()
+            .map(|message| message.message_id)
+            .collect();
+        let expected: Vec<String> = messages[1..]
+            .iter()
+            .map(|message| message.message_id.clone())
+            .collect();
+        assert_eq!(replayed, expected);
+    }
+
+    #[test]
+    fn test_sealed_log_compaction() {
+        use crate::crypto_protocols::QRNG;
+        use crate::envelope::ManagedMasterKeys;
+
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let mut qrng = QRNG::from_seed([5u8; 32]);
+        let masters = Arc::new(ManagedMasterKeys::generate("master-1", &mut qrng).unwrap());
+        let cipher = Arc::new(EnvelopeCipher::new(masters));
+
+        let mut log = DeliveryLog::with_storage(storage.clone(), Some(cipher.clone())).unwrap();
+        let messages: Vec<SecureMessage> = (0..4u8).map(|i| message(&[b'x', i])).collect();
+        for message in &messages {
+            log.record_received(message).unwrap();
+        }
+        for message in &messages[..3] {
+            log.mark_processed(&message.message_id).unwrap();
+        }
+        assert_eq!(log.records_written(), 7);
+        let (_, stored) = &storage.scan_prefix(b"r/").unwrap()[0];
+        assert!(!stored.windows(5).any(|window| window == b"alice"));
+        assert!(DeliveryLog::with_storage(storage.clone(), None).is_err());
+
+        // Three processed IDs and one pending message survive compaction
+        assert_eq!(log.compact().unwrap(), 4);
+        log.record_received(&message(b"late")).unwrap();
+        let log = DeliveryLog::with_storage(storage, Some(cipher)).unwrap();
+        assert_eq!(log.processed_count(), 3);
+        assert_eq!(log.pending_count(), 2);
+        assert!(log.is_processed(&messages[0].message_id));
+        assert_eq!(log.unprocessed()[0].message_id, messages[3].message_id);
+    }
+}
@@ -0,0 +1,399 @@
+//! # Entanglement Pool - Ready Bell Pairs per Peer
+//!
+//! Keeps a pool of prepared |Φ+⟩ pairs for every peer relationship so QKD
+//! rekeying and teleportation take a ready pair instead of paying Bell-pair
+//! generation latency inline. Pooled pairs are 2-qubit states in the shared
+//! `QuantumCore`, pinned while pooled, so their fidelity follows the core's
+//! noise model as they wait.
+//!
+//! ## Pool Maintenance
+//!
+//! - **Background Generation**: `spawn_refill` tops every peer up to
+//!   `target_pairs_per_peer` on an interval; `refill_loop` is the same loop
+//!   for a supervisor to run, ending with an error after
+//!   `REFILL_FAILURE_LIMIT` failed refills in a row
+//! - **Age Limit**: Pairs older than `max_pair_age_ms` are discarded
+//! - **Fidelity Floor**: Pairs that decayed below `min_fidelity` are discarded
+//! - **Oldest First**: `take_pair` serves the oldest pair still usable and
+//!   removes it from the core
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::entanglement_pool::{EntanglementPool, EntanglementPoolConfig};
+//! use quantum_forge_secure_comms::quantum_core::QuantumCore;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let core = Arc::new(QuantumCore::new(4).await?);
+//! let pool = Arc::new(EntanglementPool::new(core, EntanglementPoolConfig::default()));
+//! pool.add_peer("validator_2");
+//! let refill = pool.spawn_refill();
+//!
+//! if let Some(pair) = pool.take_pair("validator_2") {
+//!     println!("pair {} fidelity {:.4}", pair.state.id, pair.fidelity);
+//! }
+//! refill.abort();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::quantum_core::{QuantumCore, QuantumState};
+use crate::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Consecutive failed refills after which `refill_loop` gives up
+pub const REFILL_FAILURE_LIMIT: u32 = 5;
+
+/// Pool sizes and staleness limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntanglementPoolConfig {
+    /// Ready pairs kept for each peer
+    pub target_pairs_per_peer: usize,
+    /// Age after which a pooled pair is discarded
+    pub max_pair_age_ms: u64,
+    /// Fidelity below which a pooled pair is discarded
+    pub min_fidelity: f64,
+    /// Interval of background generation
+    pub refill_interval_ms: u64,
+}
+
+impl Default for EntanglementPoolConfig {
+    fn default() -> Self {
+        Self {
+            target_pairs_per_peer: 4,
+            max_pair_age_ms: 30_000,
+            min_fidelity: 0.9,
+            refill_interval_ms: 250,
+        }
+    }
+}
+
+/// Pool counters since the pool was created
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntanglementPoolStats {
+    pub pairs_generated: u64,
+    pub pairs_served: u64,
+    /// Pairs dropped for age, low fidelity or a removed peer
+    pub pairs_discarded: u64,
+    /// Requests that found no ready pair
+    pub misses: u64,
+}
+
+/// A Bell pair handed out by the pool
+#[derive(Debug, Clone)]
+pub struct ReadyPair {
+    pub peer_id: String,
+    /// The pair itself, no longer held by the core
+    pub state: QuantumState,
+    pub fidelity: f64,
+    /// Time the pair spent in the pool
+    pub age: Duration,
+}
+
+#[derive(Debug)]
+struct PooledPair {
+    state_id: String,
+    created_at: Instant,
+}
+
+/// Per-peer pools of ready entangled pairs
+pub struct EntanglementPool {
+    core: Arc<QuantumCore>,
+    config: EntanglementPoolConfig,
+    pools: Mutex<HashMap<String, VecDeque<PooledPair>>>,
+    stats: Mutex<EntanglementPoolStats>,
+}
+
+impl EntanglementPool {
+    pub fn new(core: Arc<QuantumCore>, config: EntanglementPoolConfig) -> Self {
+        Self {
+            core,
+            config,
+            pools: Mutex::new(HashMap::new()),
+            stats: Mutex::new(EntanglementPoolStats::default()),
+        }
+    }
+
+    /// Start keeping pairs for peer
+    pub fn add_peer(&self, peer_id: &str) {
+        self.pools.lock().entry(peer_id.to_string()).or_default();
+    }
+
+    /// Stop keeping pairs for peer, discarding its pool
+    pub fn remove_peer(&self, peer_id: &str) -> usize {
+        let pairs = self.pools.lock().remove(peer_id).unwrap_or_default();
+        for pair in &pairs {
+            self.core.take_state(&pair.state_id);
+        }
+        self.stats.lock().pairs_discarded += pairs.len() as u64;
+        pairs.len()
+    }
+
+    /// Peers with a pool
+    pub fn peers(&self) -> Vec<String> {
+        self.pools.lock().keys().cloned().collect()
+    }
+
+    /// Pairs pooled for peer, including any that went stale since the
+    /// last refill
+    pub fn ready_pairs(&self, peer_id: &str) -> usize {
+        self.pools.lock().get(peer_id).map_or(0, VecDeque::len)
+    }
+
+    /// Discard stale pairs and top every peer up to the target
+    ///
+    /// Returns the number of pairs generated.
+    pub fn replenish(&self) -> Result<usize> {
+        let deficits: Vec<(String, usize)> = {
+            let mut pools = self.pools.lock();
+            pools
+                .iter_mut()
+                .map(|(peer_id, pool)| {
+                    self.discard_stale(pool);
+                    let deficit = self.config.target_pairs_per_peer.saturating_sub(pool.len());
+                    (peer_id.clone(), deficit)
+                })
+                .collect()
+        };
+
+        // Pairs are prepared without holding the pool lock
+        let mut generated = 0;
+        for (peer_id, deficit) in deficits {
+            for _ in 0..deficit {
+                let pair = self.generate_pair(&peer_id)?;
+                generated += 1;
+                match self.pools.lock().get_mut(&peer_id) {
+                    Some(pool) => pool.push_back(pair),
+                    None => {
+                        // The peer was removed meanwhile
+                        self.core.take_state(&pair.state_id);
+                        self.stats.lock().pairs_discarded += 1;
+                    }
+                }
+            }
+        }
+        self.stats.lock().pairs_generated += generated as u64;
+        Ok(generated)
+    }
+
+    /// Take the oldest usable pair for peer, if one is ready
+    pub fn take_pair(&self, peer_id: &str) -> Option<ReadyPair> {
+        let pair = {
+            let mut pools = self.pools.lock();
+            pools.get_mut(peer_id).and_then(|pool| {
+                self.discard_stale(pool);
+                pool.pop_front()
+            })
+        };
+        let ready = pair.and_then(|pair| {
+            let state = self.core.take_state(&pair.state_id)?;
+            Some(ReadyPair {
+                peer_id: peer_id.to_string(),
+                fidelity: state.fidelity,
+                state,
+                age: pair.created_at.elapsed(),
+            })
+        });
+
+        let mut stats = self.stats.lock();
+        if ready.is_some() {
+            stats.pairs_served += 1;
+        } else {
+            stats.misses += 1;
+        }
+        ready
+    }
+
+    /// Refill the pools every `refill_interval_ms` until the pool is dropped
+    pub fn spawn_refill(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let refill = self.refill_loop();
+        tokio::spawn(async move {
+            if let Err(e) = refill.await {
+                println!("⚠️ Entanglement pool refill stopped: {}", e);
+            }
+        })
+    }
+
+    /// Refill loop run by `spawn_refill`
+    ///
+    /// Returns once the pool is dropped, or with the last error after
+    /// `REFILL_FAILURE_LIMIT` consecutive refills failed.
+    pub fn refill_loop(self: &Arc<Self>) -> impl Future<Output = Result<()>> + Send + 'static {
+        let pool = Arc::downgrade(self);
+        let period = Duration::from_millis(self.config.refill_interval_ms.max(1));
+        async move {
+            let mut ticker = tokio::time::interval(period);
+            let mut failures = 0;
+            loop {
+                ticker.tick().await;
+                let pool = match pool.upgrade() {
+                    Some(pool) => pool,
+                    None => return Ok(()),
+                };
+                match pool.replenish() {
+                    Ok(_) => failures = 0,
+                    Err(e) => {
+                        println!("⚠️ Entanglement pool refill failed: {}", e);
+                        failures += 1;
+                        if failures >= REFILL_FAILURE_LIMIT {
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Counters since the pool was created
+    pub fn stats(&self) -> EntanglementPoolStats {
+        self.stats.lock().clone()
+    }
+
+    fn generate_pair(&self, peer_id: &str) -> Result<PooledPair> {
+        let state_id = format!("epr_{}_{}", peer_id, uuid::Uuid::new_v4().simple());
+        self.core.create_comm_state(state_id.clone(), 2)?;
+        self.core.create_entangled_state(&state_id)?;
+        // Pooled pairs are owned by the pool, not by garbage collection
+        self.core.pin_state(&state_id);
+        Ok(PooledPair {
+            state_id,
+            created_at: Instant::now(),
+        })
+    }
+
+    fn discard_stale(&self, pool: &mut VecDeque<PooledPair>) {
+        let max_age = Duration::from_millis(self.config.max_pair_age_ms);
+        let before = pool.len();
+        pool.retain(|pair| {
+            let usable = pair.created_at.elapsed() <= max_age
+                && self
+                    .core
+                    .get_state_info(&pair.state_id)
+                    .is_some_and(|state| state.fidelity >= self.config.min_fidelity);
+            if !usable {
+                self.core.take_state(&pair.state_id);
+            }
+            usable
+        });
+        self.stats.lock().pairs_discarded += (before - pool.len()) as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum_core::{NoiseModel, QuantumConfig};
+
+    async fn pool(config: EntanglementPoolConfig) -> EntanglementPool {
+        let core = QuantumCore::new(4).await.unwrap();
+        EntanglementPool::new(Arc::new(core), config)
+    }
+
+    #[tokio::test]
+    async fn test_pool_serves_ready_pairs() {
+        let pool = pool(EntanglementPoolConfig {
+            target_pairs_per_peer: 3,
+            ..Default::default()
+        })
+        .await;
+        assert!(pool.take_pair("peer_a").is_none());
+        pool.add_peer("peer_a");
+        pool.add_peer("peer_b");
+        assert_eq!(pool.replenish().unwrap(), 6);
+        assert_eq!(pool.replenish().unwrap(), 0);
+        assert_eq!(pool.core.state_count(), 6);
+
+        let pair = pool.take_pair("peer_a").unwrap();
+        assert_eq!(pair.peer_id, "peer_a");
+        assert_eq!(pair.state.qubit_count, 2);
+        assert!((pair.state.amplitudes[0] - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+        assert!((pair.state.amplitudes[3] - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+        assert!((pair.fidelity - 1.0).abs() < 1e-12);
+        assert!(!pool.core.is_pinned(&pair.state.id));
+        assert_eq!(pool.ready_pairs("peer_a"), 2);
+        assert_eq!(pool.core.state_count(), 5);
+
+        assert_eq!(pool.remove_peer("peer_b"), 3);
+        assert_eq!(pool.core.state_count(), 2);
+        let stats = pool.stats();
+        assert_eq!(stats.pairs_generated, 6);
+        assert_eq!(stats.pairs_served, 1);
+        assert_eq!(stats.pairs_discarded, 3);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_pairs_are_discarded() {
+        let pool = pool(EntanglementPoolConfig {
+            target_pairs_per_peer: 2,
+            max_pair_age_ms: 20,
+            ..Default::default()
+        })
+        .await;
+        pool.add_peer("peer_a");
+        pool.replenish().unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        assert!(pool.take_pair("peer_a").is_none());
+        assert_eq!(pool.stats().pairs_discarded, 2);
+        assert_eq!(pool.core.state_count(), 0);
+        assert_eq!(pool.replenish().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_decohered_pairs_are_discarded() {
+        let core = QuantumCore::with_config(QuantumConfig {
+            noise: Some(NoiseModel {
+                t1: 0.2,
+                t2: 0.1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let pool = EntanglementPool::new(
+            Arc::new(core),
+            EntanglementPoolConfig {
+                target_pairs_per_peer: 1,
+                min_fidelity: 0.8,
+                ..Default::default()
+            },
+        );
+        pool.add_peer("peer_a");
+        pool.replenish().unwrap();
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        assert!(pool.take_pair("peer_a").is_none());
+        assert_eq!(pool.stats().pairs_discarded, 1);
+    }
+
+    #[tokio::test]
+    async fn test_background_refill() {
+        let pool = Arc::new(
+            pool(EntanglementPoolConfig {
+                target_pairs_per_peer: 2,
+                refill_interval_ms: 5,
+                ..Default::default()
+            })
+            .await,
+        );
+        pool.add_peer("peer_a");
+        let refill = pool.spawn_refill();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pool.ready_pairs("peer_a"), 2);
+
+        // The task ends on its own once the pool is gone
+        drop(pool);
+        tokio::time::timeout(Duration::from_secs(1), refill)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
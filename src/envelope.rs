@@ -0,0 +1,383 @@
+//! # Envelope - Envelope Encryption for Data at Rest
+//!
+//! Persistent state - the consensus WAL and the peer address book - is
+//! sealed with a fresh random data key per write, and the data key is
+//! wrapped by a master key that never touches disk. Master keys come from a
+//! `MasterKeyProvider`: `ManagedMasterKeys` keeps them in a `KeyManager`,
+//! and an HSM integration implements the same trait.
+//!
+//! ## Envelope Layout
+//!
+//! - **master_key_id**: Master key the data key is wrapped under
+//! - **wrapped_key**: Data key sealed by the master key
+//! - **ciphertext**: Payload sealed by the data key
+//!
+//! Both layers are AEAD-sealed with the component's context (e.g.
+//! `consensus_wal`) as associated data, so a blob written by one component
+//! cannot be substituted into another.
+//!
+//! ## Key Rotation
+//!
+//! Rotating the master key only affects new envelopes. `rewrap` moves an
+//! existing envelope to the current master key by unwrapping and rewrapping
+//! its data key; the ciphertext is left untouched. Retire an old master key
+//! once every component has rewrapped.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::QRNG;
+//! use quantum_forge_secure_comms::envelope::{EnvelopeCipher, ManagedMasterKeys};
+//! use std::sync::Arc;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut qrng = QRNG::from_seed(rand::random());
+//! let masters = Arc::new(ManagedMasterKeys::generate("master-1", &mut qrng)?);
+//! let cipher = EnvelopeCipher::new(masters.clone());
+//!
+//! let envelope = cipher.seal("notes", b"persisted state")?;
+//! masters.rotate("master-2", &mut qrng)?;
+//! let rewrapped = cipher.rewrap("notes", &envelope)?;
+//! assert_eq!(cipher.open("notes", &rewrapped)?, b"persisted state");
+//! masters.retire("master-1")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::crypto_protocols::QRNG;
+use crate::hw_accel::{CryptoDispatch, KEY_LEN, NONCE_LEN};
+use crate::key_manager::{KeyInfo, KeyManager, KeyPurpose};
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Envelope format version written by this crate
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// Source of master keys that wrap data keys
+///
+/// Master keys must stay inside the provider; only wrapped data keys leave
+/// it.
+pub trait MasterKeyProvider: Send + Sync {
+    /// Master key new envelopes are wrapped under
+    fn current_key_id(&self) -> String;
+
+    /// Wrap a data key under a master key, binding `aad`
+    fn wrap(&self, key_id: &str, aad: &[u8], data_key: &[u8]) -> Result<Vec<u8>>;
+
+    /// Unwrap a data key wrapped under a master key with the same `aad`
+    fn unwrap(&self, key_id: &str, aad: &[u8], wrapped: &[u8]) -> Result<SecretBytes>;
+}
+
+/// Master keys held in a `KeyManager`, with rotation and retirement
+pub struct ManagedMasterKeys {
+    keys: RwLock<KeyManager>,
+    current: RwLock<String>,
+}
+
+impl ManagedMasterKeys {
+    /// Use the wrapping keys of `keys`, wrapping new envelopes under `current_key_id`
+    pub fn new(keys: KeyManager, current_key_id: &str) -> Result<Self> {
+        check_wrapping_key(&keys, current_key_id)?;
+        Ok(Self {
+            keys: RwLock::new(keys),
+            current: RwLock::new(current_key_id.to_string()),
+        })
+    }
+
+    /// Start with a single master key generated from the QRNG
+    pub fn generate(key_id: &str, qrng: &mut QRNG) -> Result<Self> {
+        let mut keys = KeyManager::new();
+        keys.generate_key(key_id, KeyPurpose::Wrapping, qrng)?;
+        Self::new(keys, key_id)
+    }
+
+    /// Generate a new master key and wrap new envelopes under it
+    pub fn rotate(&self, key_id: &str, qrng: &mut QRNG) -> Result<KeyInfo> {
+        let info = self
+            .keys
+            .write()
+            .generate_key(key_id, KeyPurpose::Wrapping, qrng)?;
+        *self.current.write() = key_id.to_string();
+        println!("🔑 Rotated storage master key to {}", key_id);
+        Ok(info)
+    }
+
+    /// Destroy a master key no envelope needs any more
+    ///
+    /// The current key cannot be retired. Envelopes still wrapped under a
+    /// retired key can no longer be opened.
+    pub fn retire(&self, key_id: &str) -> Result<bool> {
+        if *self.current.read() == key_id {
+            return Err(SecureCommsError::Validation(format!(
+                "Master key {} is current and cannot be retired",
+                key_id
+            )));
+        }
+        Ok(self.keys.write().remove_key(key_id))
+    }
+
+    /// Managed master key IDs, sorted
+    pub fn key_ids(&self) -> Vec<String> {
+        self.keys.read().key_ids()
+    }
+}
+
+fn check_wrapping_key(keys: &KeyManager, key_id: &str) -> Result<()> {
+    match keys.key_info(key_id) {
+        Some(info) if info.purpose == KeyPurpose::Wrapping => Ok(()),
+        Some(info) => Err(SecureCommsError::Configuration(format!(
+            "Key {} is a {:?} key, not a wrapping key",
+            key_id, info.purpose
+        ))),
+        None => Err(SecureCommsError::Configuration(format!(
+            "Unknown master key {}",
+            key_id
+        ))),
+    }
+}
+
+impl MasterKeyProvider for ManagedMasterKeys {
+    fn current_key_id(&self) -> String {
+        self.current.read().clone()
+    }
+
+    fn wrap(&self, key_id: &str, aad: &[u8], data_key: &[u8]) -> Result<Vec<u8>> {
+        self.keys.read().wrap_key(key_id, aad, data_key)
+    }
+
+    fn unwrap(&self, key_id: &str, aad: &[u8], wrapped: &[u8]) -> Result<SecretBytes> {
+        self.keys.read().unwrap_key(key_id, aad, wrapped)
+    }
+}
+
+impl std::fmt::Debug for ManagedMasterKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagedMasterKeys")
+            .field("current", &*self.current.read())
+            .field("key_ids", &self.key_ids())
+            .finish()
+    }
+}
+
+/// Payload sealed under a data key wrapped by a master key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Envelope format version
+    pub version: u8,
+    /// Master key the data key is wrapped under
+    pub master_key_id: String,
+    /// Data key sealed by the master key
+    pub wrapped_key: Vec<u8>,
+    /// Payload sealed by the data key
+    pub ciphertext: Vec<u8>,
+}
+
+impl Envelope {
+    /// Serialize for storage
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| {
+            SecureCommsError::SystemError(format!("Envelope serialization failed: {}", e))
+        })
+    }
+
+    /// Parse stored bytes, or `None` if they are not an envelope
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok()
+    }
+}
+
+/// Seals and opens envelopes under the provider's master keys
+pub struct EnvelopeCipher {
+    provider: Arc<dyn MasterKeyProvider>,
+    dispatch: CryptoDispatch,
+}
+
+impl EnvelopeCipher {
+    /// Create a cipher over a master key provider
+    pub fn new(provider: Arc<dyn MasterKeyProvider>) -> Self {
+        Self {
+            provider,
+            dispatch: CryptoDispatch::default(),
+        }
+    }
+
+    /// Master key new envelopes are wrapped under
+    pub fn current_key_id(&self) -> String {
+        self.provider.current_key_id()
+    }
+
+    /// Seal `plaintext` under a fresh data key
+    pub fn seal(&self, context: &str, plaintext: &[u8]) -> Result<Envelope> {
+        let data_key =
+            SecretBytes::new(SensitiveKind::Key, rand::random::<[u8; KEY_LEN]>().to_vec());
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let ciphertext =
+            self.dispatch
+                .seal(data_key.expose(), &nonce, &data_aad(context), plaintext)?;
+        let master_key_id = self.provider.current_key_id();
+        let wrapped_key = self.provider.wrap(
+            &master_key_id,
+            &wrap_aad(context, &master_key_id),
+            data_key.expose(),
+        )?;
+        Ok(Envelope {
+            version: ENVELOPE_VERSION,
+            master_key_id,
+            wrapped_key,
+            ciphertext,
+        })
+    }
+
+    /// Open an envelope sealed with the same context
+    pub fn open(&self, context: &str, envelope: &Envelope) -> Result<Vec<u8>> {
+        let data_key = self.unwrap_data_key(context, envelope)?;
+        self.dispatch
+            .open(data_key.expose(), &data_aad(context), &envelope.ciphertext)
+    }
+
+    /// Whether the envelope is wrapped under an older master key
+    pub fn needs_rewrap(&self, envelope: &Envelope) -> bool {
+        envelope.master_key_id != self.provider.current_key_id()
+    }
+
+    /// Move an envelope to the current master key without re-encrypting it
+    pub fn rewrap(&self, context: &str, envelope: &Envelope) -> Result<Envelope> {
+        if !self.needs_rewrap(envelope) {
+            return Ok(envelope.clone());
+        }
+        let data_key = self.unwrap_data_key(context, envelope)?;
+        let master_key_id = self.provider.current_key_id();
+        let wrapped_key = self.provider.wrap(
+            &master_key_id,
+            &wrap_aad(context, &master_key_id),
+            data_key.expose(),
+        )?;
+        Ok(Envelope {
+            version: ENVELOPE_VERSION,
+            master_key_id,
+            wrapped_key,
+            ciphertext: envelope.ciphertext.clone(),
+        })
+    }
+
+    /// Seal and serialize for storage
+    pub fn seal_bytes(&self, context: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.seal(context, plaintext)?.to_bytes()
+    }
+
+    /// Parse and open stored bytes
+    pub fn open_bytes(&self, context: &str, bytes: &[u8]) -> Result<Vec<u8>> {
+        let envelope = Envelope::from_bytes(bytes).ok_or_else(|| {
+            SecureCommsError::CryptoProtocol(format!("Stored {} data is not an envelope", context))
+        })?;
+        self.open(context, &envelope)
+    }
+
+    fn unwrap_data_key(&self, context: &str, envelope: &Envelope) -> Result<SecretBytes> {
+        if envelope.version != ENVELOPE_VERSION {
+            return Err(SecureCommsError::CryptoProtocol(format!(
+                "Unsupported envelope version {}",
+                envelope.version
+            )));
+        }
+        self.provider.unwrap(
+            &envelope.master_key_id,
+            &wrap_aad(context, &envelope.master_key_id),
+            &envelope.wrapped_key,
+        )
+    }
+}
+
+impl std::fmt::Debug for EnvelopeCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnvelopeCipher")
+            .field("current_key_id", &self.provider.current_key_id())
+            .finish()
+    }
+}
+
+fn data_aad(context: &str) -> Vec<u8> {
+    format!("envelope_data_v1:{}", context).into_bytes()
+}
+
+fn wrap_aad(context: &str, master_key_id: &str) -> Vec<u8> {
+    format!("envelope_key_v1:{}:{}", context, master_key_id).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> (Arc<ManagedMasterKeys>, EnvelopeCipher) {
+        let mut qrng = QRNG::from_seed([8u8; 32]);
+        let masters = Arc::new(ManagedMasterKeys::generate("master-1", &mut qrng).unwrap());
+        let cipher = EnvelopeCipher::new(masters.clone());
+        (masters, cipher)
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let (_, cipher) = cipher();
+        let envelope = cipher.seal("wal", b"record").unwrap();
+        assert_eq!(envelope.master_key_id, "master-1");
+        assert!(!envelope
+            .ciphertext
+            .windows(6)
+            .any(|window| window == b"record"));
+        assert_eq!(cipher.open("wal", &envelope).unwrap(), b"record");
+
+        // Each write gets its own data key
+        let again = cipher.seal("wal", b"record").unwrap();
+        assert_ne!(again.wrapped_key, envelope.wrapped_key);
+
+        let bytes = cipher.seal_bytes("wal", b"line").unwrap();
+        assert_eq!(cipher.open_bytes("wal", &bytes).unwrap(), b"line");
+        assert!(cipher.open_bytes("wal", b"{\"plain\":true}").is_err());
+    }
+
+    #[test]
+    fn test_context_and_tampering_rejected() {
+        let (_, cipher) = cipher();
+        let envelope = cipher.seal("wal", b"record").unwrap();
+        assert!(cipher.open("address_book", &envelope).is_err());
+
+        let mut tampered = envelope.clone();
+        let last = tampered.ciphertext.len() - 1;
+        tampered.ciphertext[last] ^= 1;
+        assert!(cipher.open("wal", &tampered).is_err());
+
+        let mut relabelled = envelope;
+        relabelled.master_key_id = "master-2".to_string();
+        assert!(cipher.open("wal", &relabelled).is_err());
+    }
+
+    #[test]
+    fn test_rotation_and_rewrap() {
+        let (masters, cipher) = cipher();
+        let old = cipher.seal("wal", b"record").unwrap();
+        assert!(!cipher.needs_rewrap(&old));
+
+        let mut qrng = QRNG::from_seed([9u8; 32]);
+        masters.rotate("master-2", &mut qrng).unwrap();
+        assert!(cipher.needs_rewrap(&old));
+        assert_eq!(
+            cipher.seal("wal", b"new").unwrap().master_key_id,
+            "master-2"
+        );
+
+        let rewrapped = cipher.rewrap("wal", &old).unwrap();
+        assert_eq!(rewrapped.master_key_id, "master-2");
+        assert_eq!(rewrapped.ciphertext, old.ciphertext);
+        assert_eq!(cipher.rewrap("wal", &rewrapped).unwrap(), rewrapped);
+
+        assert!(masters.retire("master-2").is_err());
+        assert!(masters.retire("master-1").unwrap());
+        assert_eq!(masters.key_ids(), vec!["master-2".to_string()]);
+        assert!(cipher.open("wal", &old).is_err());
+        assert_eq!(cipher.open("wal", &rewrapped).unwrap(), b"record");
+    }
+}
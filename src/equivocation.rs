@@ -0,0 +1,420 @@
+//! # Equivocation Detection - Conflicting Signature Evidence
+//!
+//! A validator equivocates when it signs two conflicting statements for the
+//! same round: two different votes on one proposal, or two different
+//! proposals for the same proposal round. This module remembers the first
+//! signed statement each validator makes per round, detects conflicting
+//! follow-ups, and records both signed messages as evidence that any holder
+//! of the validator's public key can re-verify.
+//!
+//! ## Evidence Handling
+//!
+//! - **Cryptographic Evidence**: Both signed messages and signatures are retained
+//! - **Independent Verification**: `EquivocationEvidence::verify` re-checks both Ed25519 signatures
+//! - **Critical Alerts**: Every new equivocation is logged at Critical level
+//! - **Query API**: Filter evidence by validator, kind and detection time
+//!
+//! Evidence is the input to slashing in blockchain deployments; this module
+//! only detects and records it and does not penalize validators itself.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::equivocation::{
+//!     EquivocationDetector, EquivocationKind, EvidenceQuery, SignedStatement,
+//! };
+//!
+//! let mut detector = EquivocationDetector::default();
+//! let first = SignedStatement::new(b"approve".to_vec(), vec![1u8; 64], "Approve");
+//! let second = SignedStatement::new(b"reject".to_vec(), vec![2u8; 64], "Reject");
+//!
+//! detector.observe("validator_1", "prop_1", EquivocationKind::ConflictingVotes, first);
+//! if let Some(evidence) =
+//!     detector.observe("validator_1", "prop_1", EquivocationKind::ConflictingVotes, second)
+//! {
+//!     println!("Equivocation recorded: {}", evidence.evidence_id);
+//! }
+//!
+//! let query = EvidenceQuery {
+//!     validator_id: Some("validator_1".to_string()),
+//!     ..EvidenceQuery::default()
+//! };
+//! println!("{} evidence records", detector.query(&query).len());
+//! ```
+
+use crate::consensus_verify::verify_commit_signature;
+use crate::logging::{log_critical, LogCategory};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Equivocation detection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivocationConfig {
+    /// First statements remembered per (validator, round) before the oldest is evicted
+    pub max_tracked_rounds: usize,
+    /// Evidence records retained
+    pub max_evidence: usize,
+}
+
+impl Default for EquivocationConfig {
+    fn default() -> Self {
+        Self {
+            max_tracked_rounds: 10_000,
+            max_evidence: 1_000,
+        }
+    }
+}
+
+/// Kind of conflicting statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EquivocationKind {
+    /// Two different votes on the same proposal
+    ConflictingVotes,
+    /// Two different proposals for the same proposal round
+    ConflictingProposals,
+}
+
+impl EquivocationKind {
+    /// Stable name for logs and stats
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EquivocationKind::ConflictingVotes => "conflicting_votes",
+            EquivocationKind::ConflictingProposals => "conflicting_proposals",
+        }
+    }
+}
+
+/// Message signed by a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedStatement {
+    /// Exact bytes covered by the signature
+    pub message: Vec<u8>,
+    /// Ed25519 signature over `message`
+    pub signature: Vec<u8>,
+    /// Human-readable description, e.g. the vote cast
+    pub summary: String,
+}
+
+impl SignedStatement {
+    /// Create a signed statement
+    pub fn new(message: Vec<u8>, signature: Vec<u8>, summary: &str) -> Self {
+        Self {
+            message,
+            signature,
+            summary: summary.to_string(),
+        }
+    }
+}
+
+/// Proof that a validator signed two conflicting statements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivocationEvidence {
+    /// Unique evidence identifier
+    pub evidence_id: String,
+    /// Offending validator
+    pub validator_id: String,
+    /// Kind of conflict
+    pub kind: EquivocationKind,
+    /// Round both statements belong to (proposal ID for votes)
+    pub round: String,
+    /// Statement seen first
+    pub first: SignedStatement,
+    /// Conflicting statement
+    pub second: SignedStatement,
+    /// Detection time (Unix seconds)
+    pub detected_at: u64,
+}
+
+impl EquivocationEvidence {
+    /// Check the evidence against the validator's Ed25519 public key
+    ///
+    /// Valid evidence carries two different messages, both correctly signed.
+    pub fn verify(&self, public_key: &[u8]) -> bool {
+        self.first.message != self.second.message
+            && verify_commit_signature(public_key, &self.first.message, &self.first.signature)
+            && verify_commit_signature(public_key, &self.second.message, &self.second.signature)
+    }
+}
+
+/// Filter for evidence queries; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvidenceQuery {
+    /// Match offending validator
+    pub validator_id: Option<String>,
+    /// Match conflict kind
+    pub kind: Option<EquivocationKind>,
+    /// Detected at or after (Unix seconds)
+    pub since: Option<u64>,
+    /// Maximum results, newest first
+    pub limit: Option<usize>,
+}
+
+/// Tracks signed statements per round and records equivocation evidence
+#[derive(Debug, Default)]
+pub struct EquivocationDetector {
+    config: EquivocationConfig,
+    /// First statement per (validator, kind, round)
+    statements: HashMap<(String, EquivocationKind, String), SignedStatement>,
+    /// Insertion order for eviction
+    order: VecDeque<(String, EquivocationKind, String)>,
+    /// Recorded evidence, oldest first
+    evidence: VecDeque<EquivocationEvidence>,
+    /// Total equivocations detected
+    alerts_raised: u64,
+}
+
+impl EquivocationDetector {
+    /// Create detector with custom configuration
+    pub fn new(config: EquivocationConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Observe a verified signed statement
+    ///
+    /// Callers must check the signature before observing. Returns evidence
+    /// when the statement conflicts with the validator's earlier statement
+    /// for the same round; repeating an identical statement is not an offence.
+    pub fn observe(
+        &mut self,
+        validator_id: &str,
+        round: &str,
+        kind: EquivocationKind,
+        statement: SignedStatement,
+    ) -> Option<EquivocationEvidence> {
+        let key = (validator_id.to_string(), kind, round.to_string());
+        let first = match self.statements.get(&key) {
+            Some(first) => first,
+            None => {
+                self.remember(key, statement);
+                return None;
+            }
+        };
+        if first.message == statement.message {
+            return None;
+        }
+        // Replaying the same conflicting statement does not raise a new alert
+        if let Some(existing) = self.evidence.iter().find(|e| {
+            e.validator_id == validator_id
+                && e.kind == kind
+                && e.round == round
+                && e.second.message == statement.message
+        }) {
+            return Some(existing.clone());
+        }
+
+        let evidence = EquivocationEvidence {
+            evidence_id: format!(
+                "evd_{}_{}",
+                validator_id,
+                &uuid::Uuid::new_v4().simple().to_string()[..8]
+            ),
+            validator_id: validator_id.to_string(),
+            kind,
+            round: round.to_string(),
+            first: first.clone(),
+            second: statement,
+            detected_at: chrono::Utc::now().timestamp() as u64,
+        };
+
+        self.alerts_raised += 1;
+        log_critical(
+            LogCategory::Security,
+            &format!("Validator {} equivocated in round {}", validator_id, round),
+            serde_json::json!({
+                "evidence_id": evidence.evidence_id,
+                "validator_id": validator_id,
+                "kind": kind.as_str(),
+                "round": round,
+                "first": evidence.first.summary,
+                "second": evidence.second.summary,
+            }),
+        );
+
+        self.evidence.push_back(evidence.clone());
+        while self.evidence.len() > self.config.max_evidence.max(1) {
+            self.evidence.pop_front();
+        }
+        Some(evidence)
+    }
+
+    fn remember(&mut self, key: (String, EquivocationKind, String), statement: SignedStatement) {
+        self.order.push_back(key.clone());
+        self.statements.insert(key, statement);
+        while self.order.len() > self.config.max_tracked_rounds.max(1) {
+            if let Some(oldest) = self.order.pop_front() {
+                self.statements.remove(&oldest);
+            }
+        }
+    }
+
+    /// Query recorded evidence, newest first
+    pub fn query(&self, query: &EvidenceQuery) -> Vec<&EquivocationEvidence> {
+        self.evidence
+            .iter()
+            .rev()
+            .filter(|e| {
+                query.validator_id.is_none() || query.validator_id.as_ref() == Some(&e.validator_id)
+            })
+            .filter(|e| query.kind.is_none() || query.kind == Some(e.kind))
+            .filter(|e| query.since.is_none() || query.since <= Some(e.detected_at))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// Look up evidence by ID
+    pub fn get(&self, evidence_id: &str) -> Option<&EquivocationEvidence> {
+        self.evidence.iter().find(|e| e.evidence_id == evidence_id)
+    }
+
+    /// Whether any evidence is recorded against the validator
+    pub fn has_equivocated(&self, validator_id: &str) -> bool {
+        self.evidence.iter().any(|e| e.validator_id == validator_id)
+    }
+
+    /// Get detection statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "tracked_statements".to_string(),
+            serde_json::Value::Number(self.statements.len().into()),
+        );
+        stats.insert(
+            "evidence_records".to_string(),
+            serde_json::Value::Number(self.evidence.len().into()),
+        );
+        stats.insert(
+            "equivocations_detected".to_string(),
+            serde_json::Value::Number(self.alerts_raised.into()),
+        );
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed(key: &SigningKey, message: &[u8], summary: &str) -> SignedStatement {
+        SignedStatement::new(
+            message.to_vec(),
+            key.sign(message).to_bytes().to_vec(),
+            summary,
+        )
+    }
+
+    #[test]
+    fn test_conflicting_votes_produce_evidence() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let mut detector = EquivocationDetector::default();
+
+        assert!(detector
+            .observe(
+                "v1",
+                "prop_1",
+                EquivocationKind::ConflictingVotes,
+                signed(&key, b"approve", "Approve")
+            )
+            .is_none());
+        let evidence = detector
+            .observe(
+                "v1",
+                "prop_1",
+                EquivocationKind::ConflictingVotes,
+                signed(&key, b"reject", "Reject"),
+            )
+            .unwrap();
+
+        assert!(evidence.verify(&key.verifying_key().to_bytes()));
+        assert!(detector.has_equivocated("v1"));
+        assert!(detector.get(&evidence.evidence_id).is_some());
+    }
+
+    #[test]
+    fn test_repeated_statement_is_not_equivocation() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let mut detector = EquivocationDetector::default();
+
+        for _ in 0..3 {
+            assert!(detector
+                .observe(
+                    "v1",
+                    "prop_1",
+                    EquivocationKind::ConflictingVotes,
+                    signed(&key, b"approve", "Approve")
+                )
+                .is_none());
+        }
+        // Different rounds never conflict
+        assert!(detector
+            .observe(
+                "v1",
+                "prop_2",
+                EquivocationKind::ConflictingVotes,
+                signed(&key, b"reject", "Reject")
+            )
+            .is_none());
+        assert!(!detector.has_equivocated("v1"));
+    }
+
+    #[test]
+    fn test_evidence_rejects_wrong_key() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let other = SigningKey::from_bytes(&[3u8; 32]);
+        let mut detector = EquivocationDetector::default();
+
+        detector.observe(
+            "v1",
+            "r1",
+            EquivocationKind::ConflictingProposals,
+            signed(&key, b"a", "a"),
+        );
+        let evidence = detector
+            .observe(
+                "v1",
+                "r1",
+                EquivocationKind::ConflictingProposals,
+                signed(&key, b"b", "b"),
+            )
+            .unwrap();
+
+        assert!(!evidence.verify(&other.verifying_key().to_bytes()));
+    }
+
+    #[test]
+    fn test_evidence_query_filters() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let mut detector = EquivocationDetector::default();
+
+        for validator in ["v1", "v2"] {
+            detector.observe(
+                validator,
+                "p",
+                EquivocationKind::ConflictingVotes,
+                signed(&key, b"x", "x"),
+            );
+            detector.observe(
+                validator,
+                "p",
+                EquivocationKind::ConflictingVotes,
+                signed(&key, b"y", "y"),
+            );
+        }
+
+        let query = EvidenceQuery {
+            validator_id: Some("v2".to_string()),
+            ..EvidenceQuery::default()
+        };
+        assert_eq!(detector.query(&query).len(), 1);
+
+        let query = EvidenceQuery {
+            kind: Some(EquivocationKind::ConflictingProposals),
+            ..EvidenceQuery::default()
+        };
+        assert!(detector.query(&query).is_empty());
+        assert_eq!(detector.query(&EvidenceQuery::default()).len(), 2);
+    }
+}
@@ -70,7 +70,7 @@
 //!
 //! ### Basic Error Handler Setup
 //! ```rust,no_run
-//! use streamlined_secure_comms::error_handling::{ErrorHandler, RetryConfig};
+//! use quantum_forge_secure_comms::error_handling::{ErrorHandler, RetryConfig};
 //! use std::time::Duration;
 //!
 //! // Create error handler with custom retry configuration
@@ -92,7 +92,7 @@
 //!
 //! ### Circuit Breaker Usage
 //! ```rust,no_run
-//! # use streamlined_secure_comms::error_handling::{CircuitBreaker, CircuitBreakerConfig};
+//! # use quantum_forge_secure_comms::error_handling::{CircuitBreaker, CircuitBreakerConfig};
 //! # use std::time::Duration;
 //! // Create circuit breaker configuration
 //! let config = CircuitBreakerConfig {
@@ -129,11 +129,11 @@
 //!
 //! ### Error Handling with Context
 //! ```rust,no_run
-//! # use streamlined_secure_comms::error_handling::{ErrorHandler, ProductionError, ErrorContext, RecoveryAction};
+//! # use quantum_forge_secure_comms::error_handling::{ErrorHandler, ProductionError, ErrorContext, RecoveryAction};
 //! # use std::time::Duration;
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! # let retry_config = streamlined_secure_comms::error_handling::RetryConfig::default();
+//! # let retry_config = quantum_forge_secure_comms::error_handling::RetryConfig::default();
 //! # let error_handler = ErrorHandler::with_config(retry_config, 0.05);
 //! // Create error context
 //! let context = ErrorContext {
@@ -152,7 +152,7 @@
 //!     retry_count: 1,
 //!     max_retries: 3,
 //!     last_attempt: Some(chrono::Utc::now()),
-//!     recovery_strategy: streamlined_secure_comms::error_handling::RecoveryStrategy::ExponentialBackoff,
+//!     recovery_strategy: quantum_forge_secure_comms::error_handling::RecoveryStrategy::ExponentialBackoff,
 //! };
 //! 
 //! let recovery_action = error_handler.handle_error(error, context).await?;
@@ -175,7 +175,7 @@
 //!
 //! ### Error Statistics and Monitoring
 //! ```rust,no_run
-//! # use streamlined_secure_comms::error_handling::ErrorHandler;
+//! # use quantum_forge_secure_comms::error_handling::ErrorHandler;
 //! # fn main() {
 //! # let error_handler = ErrorHandler::new();
 //! // Get error statistics
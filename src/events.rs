@@ -0,0 +1,402 @@
+//! # Client Events - Lifecycle Hooks and Broadcast Event Stream
+//!
+//! Publishes typed lifecycle events from the client so applications can react
+//! to channel, key, peer health, threat and consensus changes without polling
+//! internal state. Events are delivered both to registered callbacks and to a
+//! broadcast stream that any number of tasks may subscribe to.
+//!
+//! ## Event Types
+//!
+//! - **ChannelEstablished / ChannelClosed**: Secure channel lifecycle
+//! - **ChannelStateChanged**: A channel's state machine took a transition
+//! - **KeyRotated**: Session key of a channel was replaced
+//! - **PeerHealthChanged**: A topology link went up or down
+//! - **ThreatDetected**: The threat detector recorded a security event
+//! - **SecurityLevelChanged**: The effective security level was raised or lowered
+//! - **ConsensusCommitted**: A proposal reached a final decision
+//! - **EndpointFailover**: A logical endpoint moved to a standby peer or back
+//! - **QuotaExceeded**: A tenant or channel quota rejected traffic
+//!
+//! ## Delivery Semantics
+//!
+//! - **Callbacks**: Invoked synchronously, in registration order, on the emitting task
+//! - **Stream**: Tokio broadcast channel; slow subscribers observe `Lagged` rather
+//!   than blocking the client
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::events::{ClientEvent, EventKind};
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! client.on_event(
+//!     Some(EventKind::ChannelEstablished),
+//!     Arc::new(|event: &ClientEvent| println!("Channel up: {:?}", event)),
+//! );
+//!
+//! let mut stream = client.subscribe_events();
+//! tokio::spawn(async move {
+//!     while let Ok(event) = stream.recv().await {
+//!         println!("Event: {:?}", event.kind());
+//!     }
+//! });
+//!
+//! client.establish_secure_channel("peer_1").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::channel_state::{ChannelInput, ChannelState};
+use crate::consensus_verify::ConsensusStatus;
+use crate::governor::{QuotaResource, QuotaScope};
+use crate::middleware::Direction;
+use crate::security_foundation::{SecurityLevel, ThreatType};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Capacity of the broadcast event stream
+pub const EVENT_STREAM_CAPACITY: usize = 1024;
+
+/// Lifecycle event published by the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientEvent {
+    /// Secure channel established with a peer
+    ChannelEstablished {
+        /// Remote peer
+        peer_id: String,
+        /// Channel identifier
+        channel_id: String,
+        /// Negotiated security level in bits
+        security_level: u16,
+    },
+    /// Secure channel closed
+    ChannelClosed {
+        /// Remote peer
+        peer_id: String,
+        /// Channel identifier
+        channel_id: String,
+        /// Reason for closing
+        reason: String,
+    },
+    /// Channel state machine applied a transition
+    ChannelStateChanged {
+        /// Remote peer
+        peer_id: String,
+        /// State before the transition
+        from: ChannelState,
+        /// State after the transition
+        to: ChannelState,
+        /// Input that caused the transition
+        input: ChannelInput,
+    },
+    /// Session key of a channel was rotated
+    KeyRotated {
+        /// Remote peer
+        peer_id: String,
+        /// Channel identifier
+        channel_id: String,
+    },
+    /// Link health to a peer changed
+    PeerHealthChanged {
+        /// Remote peer
+        peer_id: String,
+        /// Whether the peer is now reachable
+        healthy: bool,
+    },
+    /// Threat detector recorded a security event
+    ThreatDetected {
+        /// Threat classification
+        threat_type: ThreatType,
+        /// Detection confidence (0.0 - 1.0)
+        confidence: f64,
+        /// Component that raised the event
+        component: String,
+    },
+    /// Effective security level changed at runtime
+    SecurityLevelChanged {
+        /// Level before the change
+        from: SecurityLevel,
+        /// Level after the change
+        to: SecurityLevel,
+        /// Operator reason or triggering threat
+        trigger: String,
+    },
+    /// Consensus proposal reached a final decision
+    ConsensusCommitted {
+        /// Decided proposal
+        proposal_id: String,
+        /// Final status
+        status: ConsensusStatus,
+        /// Approving votes
+        approve_count: usize,
+        /// Rejecting votes
+        reject_count: usize,
+    },
+    /// Traffic for a logical endpoint moved to another peer
+    EndpointFailover {
+        /// Logical endpoint name
+        endpoint: String,
+        /// Peer that carried the endpoint's traffic before
+        from_peer: String,
+        /// Peer that carries it now
+        to_peer: String,
+        /// Whether traffic returned to the primary
+        failback: bool,
+    },
+    /// Traffic was rejected by a tenant or channel quota
+    QuotaExceeded {
+        /// Tenant or channel charged
+        scope: QuotaScope,
+        /// Exhausted resource
+        resource: QuotaResource,
+        /// Direction of the rejected traffic
+        direction: Direction,
+        /// Configured limit; per second for rates
+        limit: u64,
+        /// Amount in use, or consumed within the last second for rates
+        used: u64,
+        /// Amount the rejected traffic asked for
+        requested: u64,
+    },
+}
+
+/// Event discriminant used to filter callbacks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    /// `ClientEvent::ChannelEstablished`
+    ChannelEstablished,
+    /// `ClientEvent::ChannelClosed`
+    ChannelClosed,
+    /// `ClientEvent::ChannelStateChanged`
+    ChannelStateChanged,
+    /// `ClientEvent::KeyRotated`
+    KeyRotated,
+    /// `ClientEvent::PeerHealthChanged`
+    PeerHealthChanged,
+    /// `ClientEvent::ThreatDetected`
+    ThreatDetected,
+    /// `ClientEvent::SecurityLevelChanged`
+    SecurityLevelChanged,
+    /// `ClientEvent::ConsensusCommitted`
+    ConsensusCommitted,
+    /// `ClientEvent::EndpointFailover`
+    EndpointFailover,
+    /// `ClientEvent::QuotaExceeded`
+    QuotaExceeded,
+}
+
+impl ClientEvent {
+    /// Kind of this event
+    pub fn kind(&self) -> EventKind {
+        match self {
+            ClientEvent::ChannelEstablished { .. } => EventKind::ChannelEstablished,
+            ClientEvent::ChannelClosed { .. } => EventKind::ChannelClosed,
+            ClientEvent::ChannelStateChanged { .. } => EventKind::ChannelStateChanged,
+            ClientEvent::KeyRotated { .. } => EventKind::KeyRotated,
+            ClientEvent::PeerHealthChanged { .. } => EventKind::PeerHealthChanged,
+            ClientEvent::ThreatDetected { .. } => EventKind::ThreatDetected,
+            ClientEvent::SecurityLevelChanged { .. } => EventKind::SecurityLevelChanged,
+            ClientEvent::ConsensusCommitted { .. } => EventKind::ConsensusCommitted,
+            ClientEvent::EndpointFailover { .. } => EventKind::EndpointFailover,
+            ClientEvent::QuotaExceeded { .. } => EventKind::QuotaExceeded,
+        }
+    }
+}
+
+/// Callback invoked for published events
+pub type EventCallback = Arc<dyn Fn(&ClientEvent) + Send + Sync>;
+
+/// Identifier returned when registering a callback
+pub type HookId = u64;
+
+struct Hook {
+    id: HookId,
+    filter: Option<EventKind>,
+    callback: EventCallback,
+}
+
+/// Fan-out of client events to callbacks and stream subscribers
+///
+/// Cloning the bus yields a handle to the same callbacks and stream, so
+/// subsystems such as the consensus engine can publish on the client's bus.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ClientEvent>,
+    hooks: Arc<RwLock<Vec<Hook>>>,
+    next_id: Arc<AtomicU64>,
+    published: Arc<AtomicU64>,
+}
+
+impl EventBus {
+    /// Create event bus with the default stream capacity
+    pub fn new() -> Self {
+        Self::with_capacity(EVENT_STREAM_CAPACITY)
+    }
+
+    /// Create event bus with a custom stream capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self {
+            sender,
+            hooks: Arc::new(RwLock::new(Vec::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+            published: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Register callback for one event kind, or for all events when `filter` is `None`
+    pub fn register(&self, filter: Option<EventKind>, callback: EventCallback) -> HookId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.hooks.write().push(Hook {
+            id,
+            filter,
+            callback,
+        });
+        id
+    }
+
+    /// Remove a registered callback, returning whether it existed
+    pub fn unregister(&self, id: HookId) -> bool {
+        let mut hooks = self.hooks.write();
+        let before = hooks.len();
+        hooks.retain(|hook| hook.id != id);
+        hooks.len() != before
+    }
+
+    /// Subscribe to the broadcast event stream
+    pub fn subscribe(&self) -> broadcast::Receiver<ClientEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publish event to callbacks and stream subscribers
+    pub fn emit(&self, event: ClientEvent) {
+        let kind = event.kind();
+        let callbacks: Vec<EventCallback> = self
+            .hooks
+            .read()
+            .iter()
+            .filter(|hook| hook.filter.map(|filter| filter == kind).unwrap_or(true))
+            .map(|hook| hook.callback.clone())
+            .collect();
+
+        // Callbacks run outside the lock so they may register further hooks
+        for callback in callbacks {
+            callback(&event);
+        }
+
+        self.published.fetch_add(1, Ordering::Relaxed);
+        // No subscribers is not an error
+        let _ = self.sender.send(event);
+    }
+
+    /// Number of registered callbacks
+    pub fn hook_count(&self) -> usize {
+        self.hooks.read().len()
+    }
+
+    /// Total events published
+    pub fn published_count(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("hooks", &self.hook_count())
+            .field("subscribers", &self.sender.receiver_count())
+            .field("published", &self.published_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn health_event(healthy: bool) -> ClientEvent {
+        ClientEvent::PeerHealthChanged {
+            peer_id: "peer_1".to_string(),
+            healthy,
+        }
+    }
+
+    #[test]
+    fn test_filtered_callbacks() {
+        let bus = EventBus::new();
+        let all = Arc::new(AtomicUsize::new(0));
+        let health = Arc::new(AtomicUsize::new(0));
+
+        let all_counter = all.clone();
+        bus.register(
+            None,
+            Arc::new(move |_| {
+                all_counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+        let health_counter = health.clone();
+        let id = bus.register(
+            Some(EventKind::PeerHealthChanged),
+            Arc::new(move |_| {
+                health_counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        bus.emit(health_event(false));
+        bus.emit(ClientEvent::KeyRotated {
+            peer_id: "peer_1".to_string(),
+            channel_id: "channel_1".to_string(),
+        });
+        assert_eq!(all.load(Ordering::SeqCst), 2);
+        assert_eq!(health.load(Ordering::SeqCst), 1);
+
+        assert!(bus.unregister(id));
+        assert!(!bus.unregister(id));
+        bus.emit(health_event(true));
+        assert_eq!(health.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.published_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_stream() {
+        let bus = EventBus::new();
+        // Emitting without subscribers must not fail
+        bus.emit(health_event(true));
+
+        let mut first = bus.subscribe();
+        let mut second = bus.clone().subscribe();
+        bus.emit(health_event(false));
+
+        assert_eq!(first.recv().await.unwrap(), health_event(false));
+        assert_eq!(
+            second.recv().await.unwrap().kind(),
+            EventKind::PeerHealthChanged
+        );
+    }
+
+    #[test]
+    fn test_lagging_subscriber() {
+        let bus = EventBus::with_capacity(2);
+        let mut stream = bus.subscribe();
+        for _ in 0..4 {
+            bus.emit(health_event(true));
+        }
+        assert!(matches!(
+            stream.try_recv(),
+            Err(broadcast::error::TryRecvError::Lagged(2))
+        ));
+    }
+}
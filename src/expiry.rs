@@ -0,0 +1,135 @@
+//! # Message Expiry - TTL Deadlines for Queued, Relayed and Received Messages
+//!
+//! Messages may carry an absolute expiry deadline (Unix milliseconds) derived
+//! from a TTL chosen at send time. Every stage that can hold a message back -
+//! shaping queues, gossip relays and the receive path - checks the deadline and
+//! drops expired messages instead of delivering them late, so stale consensus
+//! votes or market data never reach the application.
+//!
+//! ## Enforcement Points
+//!
+//! - **Send**: Messages that expire while waiting for bandwidth are not routed
+//! - **Relay**: Gossip nodes neither deliver nor forward expired messages
+//! - **Receive**: Expired messages are discarded before reaching the application
+//!
+//! ## Metrics
+//!
+//! Each drop increments `secure_comms_messages_expired_total`, labelled with
+//! the stage, and the per-stage totals are available from [`expired_counts`].
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::expiry::{deadline_after, is_expired, now_ms};
+//! use std::time::Duration;
+//!
+//! let deadline = deadline_after(Duration::from_millis(500));
+//! assert!(!is_expired(Some(deadline), now_ms()));
+//! assert!(is_expired(Some(deadline), deadline + 1));
+//! assert!(!is_expired(None, u64::MAX));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Stage at which an expired message was dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExpiryStage {
+    /// Outbound queue or shaping delay
+    Send,
+    /// Gossip relay
+    Relay,
+    /// Inbound queue or receive path
+    Receive,
+}
+
+impl ExpiryStage {
+    /// Label used in metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpiryStage::Send => "send",
+            ExpiryStage::Relay => "relay",
+            ExpiryStage::Receive => "receive",
+        }
+    }
+}
+
+/// Process-wide totals of expired messages per stage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpiredCounts {
+    /// Dropped before or while sending
+    pub send: u64,
+    /// Dropped by gossip relays
+    pub relay: u64,
+    /// Dropped on receive
+    pub receive: u64,
+}
+
+static EXPIRED_SEND: AtomicU64 = AtomicU64::new(0);
+static EXPIRED_RELAY: AtomicU64 = AtomicU64::new(0);
+static EXPIRED_RECEIVE: AtomicU64 = AtomicU64::new(0);
+
+/// Current wall clock in Unix milliseconds
+pub fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Absolute deadline `ttl` from now
+pub fn deadline_after(ttl: Duration) -> u64 {
+    now_ms().saturating_add(ttl.as_millis().min(u64::MAX as u128) as u64)
+}
+
+/// Whether a message with the given deadline has expired at `now_ms`
+///
+/// Messages without a deadline never expire.
+pub fn is_expired(expires_at_ms: Option<u64>, now_ms: u64) -> bool {
+    expires_at_ms.map(|deadline| now_ms > deadline).unwrap_or(false)
+}
+
+/// Count an expired message dropped at `stage`
+pub fn record_expired(stage: ExpiryStage) {
+    let counter = match stage {
+        ExpiryStage::Send => &EXPIRED_SEND,
+        ExpiryStage::Relay => &EXPIRED_RELAY,
+        ExpiryStage::Receive => &EXPIRED_RECEIVE,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+    metrics::counter!("secure_comms_messages_expired_total", 1, "stage" => stage.as_str());
+}
+
+/// Totals of expired messages dropped so far
+pub fn expired_counts() -> ExpiredCounts {
+    ExpiredCounts {
+        send: EXPIRED_SEND.load(Ordering::Relaxed),
+        relay: EXPIRED_RELAY.load(Ordering::Relaxed),
+        receive: EXPIRED_RECEIVE.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadlines() {
+        let now = now_ms();
+        let deadline = deadline_after(Duration::from_secs(1));
+        assert!(deadline >= now + 1000);
+        assert!(!is_expired(Some(deadline), deadline));
+        assert!(is_expired(Some(deadline), deadline + 1));
+        assert!(!is_expired(None, u64::MAX));
+        assert_eq!(deadline_after(Duration::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_expired_counters() {
+        let before = expired_counts();
+        record_expired(ExpiryStage::Relay);
+        record_expired(ExpiryStage::Receive);
+        let after = expired_counts();
+        // Counters are process-wide, so only check they advanced
+        assert!(after.relay > before.relay);
+        assert!(after.receive > before.receive);
+    }
+}
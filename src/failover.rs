@@ -0,0 +1,355 @@
+//! # Failover - Hot Standby Peers for Logical Endpoints
+//!
+//! A logical endpoint (a sequencer, an oracle, a state server) is served by
+//! a primary peer and an ordered list of standbys. The client keeps warm
+//! secure channels to all of them and sends the endpoint's traffic to the
+//! first peer whose circuit breaker is closed.
+//!
+//! ## Circuit Breakers
+//!
+//! Every peer of an endpoint has its own [`CircuitBreaker`]. Failed sends
+//! and failed health probes count against it; once it opens, traffic fails
+//! over to the next standby with a closed breaker. After the breaker's
+//! recovery timeout a successful probe half-opens it, and once
+//! `success_threshold` probes succeed it closes again.
+//!
+//! ## Failback
+//!
+//! The primary is always preferred: as soon as its breaker closes again
+//! traffic returns to it. Every move is reported as a
+//! [`FailoverTransition`], which the client publishes as
+//! `ClientEvent::EndpointFailover`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::failover::{FailoverConfig, FailoverEndpoint, FailoverManager};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = FailoverConfig {
+//!     endpoints: vec![FailoverEndpoint {
+//!         name: "sequencer".to_string(),
+//!         primary: "validator_1".to_string(),
+//!         standbys: vec!["validator_2".to_string(), "validator_3".to_string()],
+//!     }],
+//!     ..Default::default()
+//! };
+//! let mut failover = FailoverManager::new(config)?;
+//! for _ in 0..5 {
+//!     for transition in failover.record_failure("validator_1") {
+//!         println!("{} moved to {}", transition.endpoint, transition.to_peer);
+//!     }
+//! }
+//! assert_eq!(failover.active_peer("sequencer")?, "validator_2");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error_handling::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerState};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Primary and standby peers of one logical endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailoverEndpoint {
+    /// Logical endpoint name
+    pub name: String,
+    /// Preferred peer
+    pub primary: String,
+    /// Standby peers, in failover order
+    pub standbys: Vec<String>,
+}
+
+impl FailoverEndpoint {
+    /// Primary followed by the standbys
+    pub fn peers(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.primary).chain(self.standbys.iter())
+    }
+}
+
+/// Failover configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailoverConfig {
+    /// Endpoints with standby peers
+    pub endpoints: Vec<FailoverEndpoint>,
+    /// Breaker settings applied to every peer
+    #[serde(default)]
+    pub breaker: CircuitBreakerConfig,
+}
+
+impl FailoverConfig {
+    /// Check names are unique and each endpoint lists distinct peers
+    pub fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for endpoint in &self.endpoints {
+            if endpoint.name.is_empty() || !names.insert(&endpoint.name) {
+                return Err(SecureCommsError::Configuration(format!(
+                    "Failover endpoint names must be unique and non-empty, got {:?}",
+                    endpoint.name
+                )));
+            }
+            let mut peers = HashSet::new();
+            if !endpoint
+                .peers()
+                .all(|peer| !peer.is_empty() && peers.insert(peer))
+            {
+                return Err(SecureCommsError::Configuration(format!(
+                    "Failover endpoint {} lists an empty or repeated peer",
+                    endpoint.name
+                )));
+            }
+        }
+        if self.breaker.failure_threshold == 0 || self.breaker.success_threshold == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Failover breaker thresholds must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Move of an endpoint's traffic from one peer to another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailoverTransition {
+    pub endpoint: String,
+    pub from_peer: String,
+    pub to_peer: String,
+    /// Whether traffic returned to the primary
+    pub failback: bool,
+}
+
+/// Current routing of one endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointStatus {
+    pub endpoint: String,
+    /// Peer carrying the endpoint's traffic
+    pub active_peer: String,
+    /// Whether the active peer is a standby
+    pub failed_over: bool,
+    /// Breaker state per peer, as `closed`, `open` or `half_open`
+    pub breakers: BTreeMap<String, String>,
+}
+
+struct EndpointState {
+    endpoint: FailoverEndpoint,
+    active: String,
+}
+
+/// Routes logical endpoints to healthy peers
+pub struct FailoverManager {
+    endpoints: BTreeMap<String, EndpointState>,
+    breakers: BTreeMap<String, CircuitBreaker>,
+}
+
+impl FailoverManager {
+    /// Manager for the configured endpoints, each starting on its primary
+    pub fn new(config: FailoverConfig) -> Result<Self> {
+        config.validate()?;
+        let mut breakers = BTreeMap::new();
+        let mut endpoints = BTreeMap::new();
+        for endpoint in config.endpoints {
+            for peer in endpoint.peers() {
+                breakers
+                    .entry(peer.clone())
+                    .or_insert_with(|| CircuitBreaker::new(config.breaker.clone()));
+            }
+            endpoints.insert(
+                endpoint.name.clone(),
+                EndpointState {
+                    active: endpoint.primary.clone(),
+                    endpoint,
+                },
+            );
+        }
+        Ok(Self {
+            endpoints,
+            breakers,
+        })
+    }
+
+    /// Peer that should carry the endpoint's traffic now
+    pub fn active_peer(&self, endpoint: &str) -> Result<String> {
+        self.endpoints
+            .get(endpoint)
+            .map(|state| state.active.clone())
+            .ok_or_else(|| {
+                SecureCommsError::Configuration(format!("Unknown failover endpoint {}", endpoint))
+            })
+    }
+
+    /// Every peer of every endpoint, for keeping channels warm
+    pub fn peers(&self) -> Vec<String> {
+        self.breakers.keys().cloned().collect()
+    }
+
+    /// Whether any endpoint uses `peer_id`
+    pub fn is_member(&self, peer_id: &str) -> bool {
+        self.breakers.contains_key(peer_id)
+    }
+
+    /// Record a successful send or probe
+    ///
+    /// An open breaker ignores successes until its recovery timeout has
+    /// passed.
+    pub fn record_success(&mut self, peer_id: &str) -> Vec<FailoverTransition> {
+        if let Some(breaker) = self.breakers.get_mut(peer_id) {
+            if breaker.can_execute() {
+                breaker.record_success();
+            }
+        }
+        self.reselect()
+    }
+
+    /// Record a failed send or probe
+    pub fn record_failure(&mut self, peer_id: &str) -> Vec<FailoverTransition> {
+        if let Some(breaker) = self.breakers.get_mut(peer_id) {
+            breaker.record_failure();
+        }
+        self.reselect()
+    }
+
+    /// Routing of every endpoint, ordered by name
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        self.endpoints
+            .values()
+            .map(|state| EndpointStatus {
+                endpoint: state.endpoint.name.clone(),
+                active_peer: state.active.clone(),
+                failed_over: state.active != state.endpoint.primary,
+                breakers: state
+                    .endpoint
+                    .peers()
+                    .map(|peer| (peer.clone(), breaker_label(self.breakers[peer].state())))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Move each endpoint to its first peer with a closed breaker
+    ///
+    /// An endpoint with no closed breaker stays where it is.
+    fn reselect(&mut self) -> Vec<FailoverTransition> {
+        let mut transitions = Vec::new();
+        for state in self.endpoints.values_mut() {
+            let best = state
+                .endpoint
+                .peers()
+                .find(|peer| self.breakers[*peer].state() == CircuitBreakerState::Closed)
+                .cloned();
+            if let Some(best) = best {
+                if best != state.active {
+                    transitions.push(FailoverTransition {
+                        endpoint: state.endpoint.name.clone(),
+                        from_peer: std::mem::replace(&mut state.active, best.clone()),
+                        failback: best == state.endpoint.primary,
+                        to_peer: best,
+                    });
+                }
+            }
+        }
+        transitions
+    }
+}
+
+fn breaker_label(state: CircuitBreakerState) -> String {
+    match state {
+        CircuitBreakerState::Closed => "closed",
+        CircuitBreakerState::Open => "open",
+        CircuitBreakerState::HalfOpen => "half_open",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn manager(recovery: Duration) -> FailoverManager {
+        FailoverManager::new(FailoverConfig {
+            endpoints: vec![FailoverEndpoint {
+                name: "sequencer".to_string(),
+                primary: "p".to_string(),
+                standbys: vec!["s1".to_string(), "s2".to_string()],
+            }],
+            breaker: CircuitBreakerConfig {
+                failure_threshold: 2,
+                recovery_timeout: recovery,
+                success_threshold: 2,
+                ..Default::default()
+            },
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fails_over_in_standby_order() {
+        let mut failover = manager(Duration::from_secs(60));
+        assert!(failover.record_failure("p").is_empty());
+        let transitions = failover.record_failure("p");
+        assert_eq!(
+            transitions,
+            vec![FailoverTransition {
+                endpoint: "sequencer".to_string(),
+                from_peer: "p".to_string(),
+                to_peer: "s1".to_string(),
+                failback: false,
+            }]
+        );
+
+        failover.record_failure("s1");
+        failover.record_failure("s1");
+        assert_eq!(failover.active_peer("sequencer").unwrap(), "s2");
+        // With every breaker open the endpoint stays put
+        failover.record_failure("s2");
+        assert!(failover.record_failure("s2").is_empty());
+        let status = &failover.status()[0];
+        assert!(status.failed_over);
+        assert_eq!(status.breakers["p"], "open");
+        assert!(failover.active_peer("unknown").is_err());
+    }
+
+    #[test]
+    fn test_fails_back_once_primary_recovers() {
+        let mut failover = manager(Duration::from_millis(20));
+        failover.record_failure("p");
+        failover.record_failure("p");
+        assert_eq!(failover.active_peer("sequencer").unwrap(), "s1");
+
+        // Successes before the recovery timeout do not count
+        assert!(failover.record_success("p").is_empty());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(failover.record_success("p").is_empty());
+        let transitions = failover.record_success("p");
+        assert_eq!(transitions.len(), 1);
+        assert!(transitions[0].failback);
+        assert_eq!(transitions[0].to_peer, "p");
+        assert!(!failover.status()[0].failed_over);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let endpoint = |name: &str, primary: &str, standbys: &[&str]| FailoverEndpoint {
+            name: name.to_string(),
+            primary: primary.to_string(),
+            standbys: standbys.iter().map(|peer| peer.to_string()).collect(),
+        };
+        let config = |endpoints| FailoverConfig {
+            endpoints,
+            ..Default::default()
+        };
+        assert!(
+            config(vec![endpoint("a", "p", &["s"]), endpoint("b", "s", &["p"])])
+                .validate()
+                .is_ok()
+        );
+        assert!(config(vec![endpoint("a", "p", &["p"])]).validate().is_err());
+        assert!(
+            config(vec![endpoint("a", "p", &[]), endpoint("a", "q", &[])])
+                .validate()
+                .is_err()
+        );
+        assert!(config(vec![endpoint("", "p", &[])]).validate().is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! # File Transfer - Chunked, Hash-Verified Bulk Transfers
+//!
+//! Splits large payloads (snapshots, archives, logs) into fixed-size chunks
+//! described by a manifest of per-chunk SHA-3 hashes, so they can be streamed
+//! over secure channels as bulk traffic, verified chunk by chunk, resumed
+//! after interruptions and reassembled with an end-to-end content hash check.
+//!
+//! ## Transfer Flow
+//!
+//! 1. **Manifest**: The sender announces size, chunk size and all chunk hashes
+//! 2. **Chunks**: Chunks arrive in any order and are checked against the manifest
+//! 3. **Resume**: `missing_chunks` lists what still has to be requested
+//! 4. **Assembly**: The payload is rebuilt and checked against the content hash
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::file_transfer::{prepare_transfer, TransferReceiver};
+//!
+//! let payload = vec![0u8; 1_000_000];
+//! let (manifest, chunks) = prepare_transfer("snapshot", &payload, 64 * 1024);
+//!
+//! let mut receiver = TransferReceiver::new(manifest).unwrap();
+//! for chunk in chunks {
+//!     receiver.accept_chunk(chunk).unwrap();
+//! }
+//! let received = receiver.assemble().unwrap();
+//! assert_eq!(received, payload);
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+
+/// Description of a chunked transfer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferManifest {
+    /// Unique transfer identifier
+    pub transfer_id: String,
+    /// Payload name, e.g. "snapshot"
+    pub name: String,
+    /// Payload size in bytes
+    pub total_size: u64,
+    /// Size of every chunk except possibly the last
+    pub chunk_size: usize,
+    /// SHA-3 hash of each chunk
+    pub chunk_hashes: Vec<Vec<u8>>,
+    /// SHA-3 hash of the whole payload
+    pub content_hash: Vec<u8>,
+}
+
+impl TransferManifest {
+    /// Number of chunks in the transfer
+    pub fn chunk_count(&self) -> usize {
+        self.chunk_hashes.len()
+    }
+}
+
+/// One chunk of a transfer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChunk {
+    /// Transfer the chunk belongs to
+    pub transfer_id: String,
+    /// Chunk position
+    pub index: usize,
+    /// Chunk bytes
+    pub data: Vec<u8>,
+}
+
+fn sha3(data: &[u8]) -> Vec<u8> {
+    use sha3::{Digest, Sha3_256};
+    Sha3_256::digest(data).to_vec()
+}
+
+/// Split a payload into a manifest and its chunks
+pub fn prepare_transfer(
+    name: &str,
+    data: &[u8],
+    chunk_size: usize,
+) -> (TransferManifest, Vec<FileChunk>) {
+    let chunk_size = chunk_size.max(1);
+    let transfer_id = format!("xfer_{}", uuid::Uuid::new_v4().simple());
+
+    let chunks: Vec<FileChunk> = data
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, bytes)| FileChunk {
+            transfer_id: transfer_id.clone(),
+            index,
+            data: bytes.to_vec(),
+        })
+        .collect();
+
+    let manifest = TransferManifest {
+        transfer_id,
+        name: name.to_string(),
+        total_size: data.len() as u64,
+        chunk_size,
+        chunk_hashes: chunks.iter().map(|chunk| sha3(&chunk.data)).collect(),
+        content_hash: sha3(data),
+    };
+    (manifest, chunks)
+}
+
+/// Receiving side of a chunked transfer
+#[derive(Debug, Clone)]
+pub struct TransferReceiver {
+    manifest: TransferManifest,
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+}
+
+impl TransferReceiver {
+    /// Start receiving a transfer described by `manifest`
+    pub fn new(manifest: TransferManifest) -> Result<Self> {
+        let expected_chunks = if manifest.total_size == 0 {
+            0
+        } else {
+            ((manifest.total_size - 1) / manifest.chunk_size.max(1) as u64 + 1) as usize
+        };
+        if manifest.chunk_size == 0 || manifest.chunk_count() != expected_chunks {
+            return Err(SecureCommsError::Validation(format!(
+                "Inconsistent manifest for transfer {}",
+                manifest.transfer_id
+            )));
+        }
+
+        let chunks = vec![None; manifest.chunk_count()];
+        Ok(Self {
+            manifest,
+            chunks,
+            received: 0,
+        })
+    }
+
+    /// Manifest of the transfer
+    pub fn manifest(&self) -> &TransferManifest {
+        &self.manifest
+    }
+
+    /// Accept a chunk after checking it against the manifest
+    ///
+    /// Returns whether the transfer is complete. Duplicate chunks are ignored.
+    pub fn accept_chunk(&mut self, chunk: FileChunk) -> Result<bool> {
+        if chunk.transfer_id != self.manifest.transfer_id {
+            return Err(SecureCommsError::Validation(format!(
+                "Chunk belongs to transfer {}, expected {}",
+                chunk.transfer_id, self.manifest.transfer_id
+            )));
+        }
+        let expected_hash = self.manifest.chunk_hashes.get(chunk.index).ok_or_else(|| {
+            SecureCommsError::Validation(format!("Chunk index {} out of range", chunk.index))
+        })?;
+        if sha3(&chunk.data) != *expected_hash {
+            return Err(SecureCommsError::Security(format!(
+                "Chunk {} of transfer {} failed hash verification",
+                chunk.index, chunk.transfer_id
+            )));
+        }
+
+        let slot = &mut self.chunks[chunk.index];
+        if slot.is_none() {
+            *slot = Some(chunk.data);
+            self.received += 1;
+        }
+        Ok(self.is_complete())
+    }
+
+    /// Indices still to be received
+    pub fn missing_chunks(&self) -> Vec<usize> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.is_none())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Whether every chunk has arrived
+    pub fn is_complete(&self) -> bool {
+        self.received == self.chunks.len()
+    }
+
+    /// Fraction of chunks received
+    pub fn progress(&self) -> f64 {
+        if self.chunks.is_empty() {
+            return 1.0;
+        }
+        self.received as f64 / self.chunks.len() as f64
+    }
+
+    /// Reassemble the payload and check the content hash
+    pub fn assemble(&self) -> Result<Vec<u8>> {
+        if !self.is_complete() {
+            return Err(SecureCommsError::Validation(format!(
+                "Transfer {} incomplete: {} of {} chunks",
+                self.manifest.transfer_id,
+                self.received,
+                self.chunks.len()
+            )));
+        }
+
+        let mut data = Vec::with_capacity(self.manifest.total_size as usize);
+        for chunk in self.chunks.iter().flatten() {
+            data.extend_from_slice(chunk);
+        }
+        if sha3(&data) != self.manifest.content_hash {
+            return Err(SecureCommsError::Security(format!(
+                "Transfer {} failed content hash verification",
+                self.manifest.transfer_id
+            )));
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_order_transfer() {
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let (manifest, mut chunks) = prepare_transfer("blob", &payload, 1024);
+        assert_eq!(manifest.chunk_count(), 10);
+
+        chunks.reverse();
+        let mut receiver = TransferReceiver::new(manifest).unwrap();
+        for chunk in chunks {
+            receiver.accept_chunk(chunk).unwrap();
+        }
+        assert_eq!(receiver.assemble().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_corrupt_chunk_rejected() {
+        let (manifest, mut chunks) = prepare_transfer("blob", &[7u8; 3000], 1000);
+        let mut receiver = TransferReceiver::new(manifest).unwrap();
+
+        chunks[1].data[0] ^= 1;
+        assert!(receiver.accept_chunk(chunks[1].clone()).is_err());
+        assert!(receiver.accept_chunk(chunks[0].clone()).is_ok());
+        assert_eq!(receiver.missing_chunks(), vec![1, 2]);
+        assert!(receiver.assemble().is_err());
+    }
+
+    #[test]
+    fn test_inconsistent_manifest_rejected() {
+        let (mut manifest, _) = prepare_transfer("blob", &[1u8; 2500], 1000);
+        manifest.chunk_hashes.pop();
+        assert!(TransferReceiver::new(manifest).is_err());
+
+        let (empty, _) = prepare_transfer("empty", &[], 1000);
+        let receiver = TransferReceiver::new(empty).unwrap();
+        assert!(receiver.is_complete());
+        assert!(receiver.assemble().unwrap().is_empty());
+    }
+}
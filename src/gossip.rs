@@ -0,0 +1,763 @@
+//! # Gossip Dissemination - Epidemic Broadcast over Secure Channels
+//!
+//! Epidemic message propagation for blocks, transactions and announcements
+//! across large validator sets. The engine is transport-agnostic: it consumes
+//! inbound frames and produces [`GossipAction`]s which are delivered over the
+//! existing secure channels (see `NetworkComms::dispatch_gossip`).
+//!
+//! ## Protocol
+//!
+//! - **Eager Push**: New messages are forwarded in full to `fanout` random topic peers
+//! - **Lazy Push (IHAVE)**: Remaining topic peers only receive message IDs
+//! - **Pull (IWANT)**: Peers request full messages for IDs they have not seen
+//! - **Heartbeat**: Periodic IHAVE digests of recent messages repair missed pushes
+//!
+//! ## Deduplication
+//!
+//! Every message ID is remembered in a bounded, time-limited seen cache so that
+//! duplicates arriving over multiple paths are dropped without being forwarded
+//! again. Full messages are retained in a shorter history window to answer
+//! IWANT requests.
+//!
+//! ## Expiry
+//!
+//! Messages published with a TTL carry an absolute deadline that is bound into
+//! the message ID. Expired messages are neither delivered, forwarded nor served
+//! in response to IWANT.
+//!
+//! ## Topics
+//!
+//! Peers announce topic interest with `add_peer_topic`; the local node
+//! subscribes with `subscribe`. Messages are only delivered locally for
+//! subscribed topics and only forwarded to peers interested in the topic.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::gossip::{GossipConfig, GossipEngine};
+//!
+//! let mut gossip = GossipEngine::new("validator_1".to_string(), GossipConfig::default());
+//! gossip.subscribe("blocks");
+//! gossip.add_peer_topic("validator_2", "blocks");
+//! gossip.add_peer_topic("validator_3", "blocks");
+//!
+//! let (message_id, actions) = gossip.publish("blocks", b"block #42".to_vec()).unwrap();
+//! println!("Published {} to {} peers", message_id, actions.len());
+//! ```
+
+use crate::expiry::{deadline_after, is_expired, now_ms, record_expired, ExpiryStage};
+use crate::{Result, SecureCommsError};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// Gossip protocol configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipConfig {
+    /// Number of topic peers receiving full messages (eager push)
+    pub fanout: usize,
+    /// Number of topic peers receiving IHAVE announcements (lazy push)
+    pub lazy_fanout: usize,
+    /// Maximum number of message IDs remembered for deduplication
+    pub seen_cache_capacity: usize,
+    /// Seconds a message ID stays in the seen cache
+    pub seen_ttl_seconds: u64,
+    /// Number of full messages retained to serve IWANT requests
+    pub history_length: usize,
+    /// Maximum IDs per IHAVE announcement
+    pub max_ihave_ids: usize,
+    /// Maximum payload size accepted for publication or forwarding
+    pub max_message_bytes: usize,
+    /// Maximum hops a message may travel
+    pub max_hops: u32,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            fanout: 6,
+            lazy_fanout: 6,
+            seen_cache_capacity: 10_000,
+            seen_ttl_seconds: 120,
+            history_length: 512,
+            max_ihave_ids: 64,
+            max_message_bytes: 1024 * 1024, // 1MB
+            max_hops: 16,
+        }
+    }
+}
+
+/// Gossip message carried in full
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipMessage {
+    /// Content-derived message identifier
+    pub message_id: String,
+    /// Topic the message was published on
+    pub topic: String,
+    /// Originating node
+    pub origin: String,
+    /// Origin-local sequence number
+    pub sequence: u64,
+    /// Message payload
+    pub payload: Vec<u8>,
+    /// Number of hops travelled so far
+    pub hops: u32,
+    /// Delivery deadline in Unix milliseconds, if published with a TTL
+    #[serde(default)]
+    pub expires_at_ms: Option<u64>,
+}
+
+impl GossipMessage {
+    /// Compute message ID from origin, sequence, topic, payload and deadline
+    ///
+    /// Binding the deadline into the ID stops relays from extending a TTL.
+    pub fn compute_id(
+        origin: &str,
+        sequence: u64,
+        topic: &str,
+        payload: &[u8],
+        expires_at_ms: Option<u64>,
+    ) -> String {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(origin.as_bytes());
+        hasher.update(sequence.to_le_bytes());
+        hasher.update(topic.as_bytes());
+        hasher.update(payload);
+        if let Some(deadline) = expires_at_ms {
+            hasher.update(deadline.to_le_bytes());
+        }
+        hasher
+            .finalize()
+            .iter()
+            .take(16)
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Whether the message deadline has passed
+    pub fn is_expired(&self) -> bool {
+        is_expired(self.expires_at_ms, now_ms())
+    }
+}
+
+/// Wire frames exchanged by the gossip protocol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GossipFrame {
+    /// Full message (eager push or IWANT response)
+    Publish(GossipMessage),
+    /// Announcement of available message IDs (lazy push)
+    IHave {
+        /// Topic of the announced messages
+        topic: String,
+        /// Announced message IDs
+        message_ids: Vec<String>,
+    },
+    /// Request for full messages
+    IWant {
+        /// Requested message IDs
+        message_ids: Vec<String>,
+    },
+}
+
+/// Frame to be sent to a peer over its secure channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipAction {
+    /// Destination peer
+    pub peer_id: String,
+    /// Frame to deliver
+    pub frame: GossipFrame,
+}
+
+/// Result of handling an inbound frame
+#[derive(Debug, Clone, Default)]
+pub struct GossipOutcome {
+    /// Message delivered to the local application (subscribed topics only)
+    pub delivered: Option<GossipMessage>,
+    /// Frames to send in response
+    pub actions: Vec<GossipAction>,
+    /// Whether the frame was a duplicate of an already seen message
+    pub duplicate: bool,
+}
+
+/// Gossip protocol counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GossipMetrics {
+    /// Messages published locally
+    pub published: u64,
+    /// Messages delivered to the local application
+    pub delivered: u64,
+    /// Duplicate messages dropped
+    pub duplicates: u64,
+    /// Full messages forwarded
+    pub forwarded: u64,
+    /// IHAVE frames sent
+    pub ihave_sent: u64,
+    /// IWANT frames sent
+    pub iwant_sent: u64,
+    /// Messages served in response to IWANT
+    pub iwant_served: u64,
+    /// Expired messages dropped instead of delivered or forwarded
+    #[serde(default)]
+    pub expired: u64,
+}
+
+/// Bounded, time-limited cache of seen message IDs
+#[derive(Debug, Clone)]
+struct SeenCache {
+    entries: HashMap<String, u64>,
+    order: VecDeque<String>,
+    capacity: usize,
+    ttl_seconds: u64,
+}
+
+impl SeenCache {
+    fn new(capacity: usize, ttl_seconds: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+            ttl_seconds,
+        }
+    }
+
+    /// Insert an ID, returning `false` when it was already present
+    fn insert(&mut self, message_id: &str, now: u64) -> bool {
+        if self.entries.contains_key(message_id) {
+            return false;
+        }
+        self.entries.insert(message_id.to_string(), now);
+        self.order.push_back(message_id.to_string());
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    fn contains(&self, message_id: &str) -> bool {
+        self.entries.contains_key(message_id)
+    }
+
+    fn prune(&mut self, now: u64) {
+        while let Some(oldest) = self.order.front() {
+            let inserted = self.entries.get(oldest).copied().unwrap_or(0);
+            if now.saturating_sub(inserted) <= self.ttl_seconds {
+                break;
+            }
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Gossip engine implementing eager/lazy push with pull recovery
+#[derive(Debug)]
+pub struct GossipEngine {
+    /// Local node identifier
+    local_id: String,
+    /// Protocol configuration
+    config: GossipConfig,
+    /// Topics the local node is subscribed to
+    subscriptions: HashSet<String>,
+    /// Topic interest of remote peers (topic -> peers)
+    topic_peers: HashMap<String, HashSet<String>>,
+    /// Seen message IDs for deduplication
+    seen: SeenCache,
+    /// Recent full messages for IWANT responses
+    history: HashMap<String, GossipMessage>,
+    /// Insertion order of history entries
+    history_order: VecDeque<String>,
+    /// Local publication sequence
+    sequence: u64,
+    /// Protocol counters
+    metrics: GossipMetrics,
+}
+
+impl GossipEngine {
+    /// Create new gossip engine
+    pub fn new(local_id: String, config: GossipConfig) -> Self {
+        let seen = SeenCache::new(config.seen_cache_capacity, config.seen_ttl_seconds);
+        Self {
+            local_id,
+            config,
+            subscriptions: HashSet::new(),
+            topic_peers: HashMap::new(),
+            seen,
+            history: HashMap::new(),
+            history_order: VecDeque::new(),
+            sequence: 0,
+            metrics: GossipMetrics::default(),
+        }
+    }
+
+    /// Subscribe the local node to a topic
+    pub fn subscribe(&mut self, topic: &str) {
+        self.subscriptions.insert(topic.to_string());
+    }
+
+    /// Unsubscribe the local node from a topic
+    pub fn unsubscribe(&mut self, topic: &str) {
+        self.subscriptions.remove(topic);
+    }
+
+    /// Check local subscription
+    pub fn is_subscribed(&self, topic: &str) -> bool {
+        self.subscriptions.contains(topic)
+    }
+
+    /// Record that a peer is interested in a topic
+    pub fn add_peer_topic(&mut self, peer_id: &str, topic: &str) {
+        if peer_id == self.local_id {
+            return;
+        }
+        self.topic_peers
+            .entry(topic.to_string())
+            .or_default()
+            .insert(peer_id.to_string());
+    }
+
+    /// Remove a peer's interest in a topic
+    pub fn remove_peer_topic(&mut self, peer_id: &str, topic: &str) {
+        if let Some(peers) = self.topic_peers.get_mut(topic) {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Remove a peer from all topics (e.g. on disconnect)
+    pub fn remove_peer(&mut self, peer_id: &str) {
+        for peers in self.topic_peers.values_mut() {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Get peers interested in a topic
+    pub fn topic_peers(&self, topic: &str) -> Vec<String> {
+        let mut peers: Vec<String> = self
+            .topic_peers
+            .get(topic)
+            .map(|peers| peers.iter().cloned().collect())
+            .unwrap_or_default();
+        peers.sort();
+        peers
+    }
+
+    /// Publish a new message on a topic
+    ///
+    /// Returns the message ID and the frames to send.
+    pub fn publish(&mut self, topic: &str, payload: Vec<u8>) -> Result<(String, Vec<GossipAction>)> {
+        self.publish_with_ttl(topic, payload, None)
+    }
+
+    /// Publish a message that must not be delivered after `ttl` has elapsed
+    pub fn publish_with_ttl(
+        &mut self,
+        topic: &str,
+        payload: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> Result<(String, Vec<GossipAction>)> {
+        if payload.len() > self.config.max_message_bytes {
+            return Err(SecureCommsError::Validation(format!(
+                "Gossip payload of {} bytes exceeds limit of {} bytes",
+                payload.len(),
+                self.config.max_message_bytes
+            )));
+        }
+
+        self.sequence += 1;
+        let expires_at_ms = ttl.map(deadline_after);
+        let message = GossipMessage {
+            message_id: GossipMessage::compute_id(
+                &self.local_id,
+                self.sequence,
+                topic,
+                &payload,
+                expires_at_ms,
+            ),
+            topic: topic.to_string(),
+            origin: self.local_id.clone(),
+            sequence: self.sequence,
+            payload,
+            hops: 0,
+            expires_at_ms,
+        };
+
+        let now = Self::now();
+        self.seen.insert(&message.message_id, now);
+        self.remember(message.clone());
+        self.metrics.published += 1;
+
+        let message_id = message.message_id.clone();
+        let actions = self.disseminate(message, None);
+        Ok((message_id, actions))
+    }
+
+    /// Handle an inbound frame from a peer
+    pub fn handle_frame(&mut self, from_peer: &str, frame: GossipFrame) -> Result<GossipOutcome> {
+        match frame {
+            GossipFrame::Publish(message) => self.handle_publish(from_peer, message),
+            GossipFrame::IHave { topic, message_ids } => {
+                let mut outcome = GossipOutcome::default();
+                if !self.is_subscribed(&topic) {
+                    return Ok(outcome);
+                }
+
+                let wanted: Vec<String> = message_ids
+                    .into_iter()
+                    .take(self.config.max_ihave_ids)
+                    .filter(|id| !self.seen.contains(id))
+                    .collect();
+
+                if !wanted.is_empty() {
+                    self.metrics.iwant_sent += 1;
+                    outcome.actions.push(GossipAction {
+                        peer_id: from_peer.to_string(),
+                        frame: GossipFrame::IWant { message_ids: wanted },
+                    });
+                }
+                Ok(outcome)
+            }
+            GossipFrame::IWant { message_ids } => {
+                let mut outcome = GossipOutcome::default();
+                for message_id in message_ids.iter().take(self.config.max_ihave_ids) {
+                    if let Some(message) = self.history.get(message_id) {
+                        if message.is_expired() {
+                            continue;
+                        }
+                        self.metrics.iwant_served += 1;
+                        outcome.actions.push(GossipAction {
+                            peer_id: from_peer.to_string(),
+                            frame: GossipFrame::Publish(message.clone()),
+                        });
+                    }
+                }
+                Ok(outcome)
+            }
+        }
+    }
+
+    /// Periodic maintenance: prune caches and emit IHAVE digests
+    pub fn heartbeat(&mut self) -> Vec<GossipAction> {
+        self.seen.prune(Self::now());
+
+        let mut by_topic: HashMap<String, Vec<String>> = HashMap::new();
+        for message_id in self.history_order.iter().rev() {
+            if let Some(message) = self.history.get(message_id) {
+                if message.is_expired() {
+                    continue;
+                }
+                let ids = by_topic.entry(message.topic.clone()).or_default();
+                if ids.len() < self.config.max_ihave_ids {
+                    ids.push(message_id.clone());
+                }
+            }
+        }
+
+        let mut actions = Vec::new();
+        for (topic, message_ids) in by_topic {
+            let peers = self.sample_peers(&topic, self.config.lazy_fanout, &[]);
+            for peer_id in peers {
+                self.metrics.ihave_sent += 1;
+                actions.push(GossipAction {
+                    peer_id,
+                    frame: GossipFrame::IHave {
+                        topic: topic.clone(),
+                        message_ids: message_ids.clone(),
+                    },
+                });
+            }
+        }
+        actions
+    }
+
+    /// Get protocol counters
+    pub fn get_metrics(&self) -> &GossipMetrics {
+        &self.metrics
+    }
+
+    /// Get gossip configuration
+    pub fn get_config(&self) -> &GossipConfig {
+        &self.config
+    }
+
+    /// Get gossip statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert("subscriptions".to_string(), serde_json::json!(self.subscriptions.len()));
+        stats.insert("known_topics".to_string(), serde_json::json!(self.topic_peers.len()));
+        stats.insert("seen_cache_size".to_string(), serde_json::json!(self.seen.len()));
+        stats.insert("history_size".to_string(), serde_json::json!(self.history.len()));
+        stats.insert(
+            "metrics".to_string(),
+            serde_json::to_value(&self.metrics).unwrap_or(serde_json::Value::Null),
+        );
+        stats
+    }
+
+    fn handle_publish(&mut self, from_peer: &str, mut message: GossipMessage) -> Result<GossipOutcome> {
+        let mut outcome = GossipOutcome::default();
+
+        if message.payload.len() > self.config.max_message_bytes {
+            return Err(SecureCommsError::Validation(format!(
+                "Gossip message {} from {} exceeds size limit",
+                message.message_id, from_peer
+            )));
+        }
+
+        let expected_id = GossipMessage::compute_id(
+            &message.origin,
+            message.sequence,
+            &message.topic,
+            &message.payload,
+            message.expires_at_ms,
+        );
+        if expected_id != message.message_id {
+            return Err(SecureCommsError::Validation(format!(
+                "Gossip message ID mismatch from {}",
+                from_peer
+            )));
+        }
+
+        if message.is_expired() {
+            self.metrics.expired += 1;
+            record_expired(ExpiryStage::Relay);
+            return Ok(outcome);
+        }
+
+        if !self.seen.insert(&message.message_id, Self::now()) {
+            self.metrics.duplicates += 1;
+            outcome.duplicate = true;
+            return Ok(outcome);
+        }
+
+        message.hops += 1;
+        self.remember(message.clone());
+
+        if self.is_subscribed(&message.topic) {
+            self.metrics.delivered += 1;
+            outcome.delivered = Some(message.clone());
+        }
+
+        if message.hops < self.config.max_hops {
+            outcome.actions = self.disseminate(message, Some(from_peer));
+        }
+
+        Ok(outcome)
+    }
+
+    /// Eager push to `fanout` peers and lazy IHAVE to `lazy_fanout` others
+    fn disseminate(&mut self, message: GossipMessage, from_peer: Option<&str>) -> Vec<GossipAction> {
+        let mut exclude = vec![message.origin.clone()];
+        if let Some(from_peer) = from_peer {
+            exclude.push(from_peer.to_string());
+        }
+
+        let eager = self.sample_peers(&message.topic, self.config.fanout, &exclude);
+        exclude.extend(eager.iter().cloned());
+        let lazy = self.sample_peers(&message.topic, self.config.lazy_fanout, &exclude);
+
+        let mut actions = Vec::with_capacity(eager.len() + lazy.len());
+        for peer_id in eager {
+            self.metrics.forwarded += 1;
+            actions.push(GossipAction {
+                peer_id,
+                frame: GossipFrame::Publish(message.clone()),
+            });
+        }
+        for peer_id in lazy {
+            self.metrics.ihave_sent += 1;
+            actions.push(GossipAction {
+                peer_id,
+                frame: GossipFrame::IHave {
+                    topic: message.topic.clone(),
+                    message_ids: vec![message.message_id.clone()],
+                },
+            });
+        }
+        actions
+    }
+
+    /// Randomly sample up to `count` topic peers not in `exclude`
+    fn sample_peers(&self, topic: &str, count: usize, exclude: &[String]) -> Vec<String> {
+        let candidates: Vec<String> = self
+            .topic_peers(topic)
+            .into_iter()
+            .filter(|peer| !exclude.contains(peer))
+            .collect();
+
+        candidates
+            .choose_multiple(&mut rand::thread_rng(), count)
+            .cloned()
+            .collect()
+    }
+
+    /// Retain a full message for IWANT responses
+    fn remember(&mut self, message: GossipMessage) {
+        self.history_order.push_back(message.message_id.clone());
+        self.history.insert(message.message_id.clone(), message);
+        while self.history_order.len() > self.config.history_length {
+            if let Some(oldest) = self.history_order.pop_front() {
+                self.history.remove(&oldest);
+            }
+        }
+    }
+
+    fn now() -> u64 {
+        chrono::Utc::now().timestamp() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine_with_peers(local: &str, peers: &[&str]) -> GossipEngine {
+        let mut engine = GossipEngine::new(
+            local.to_string(),
+            GossipConfig {
+                fanout: 2,
+                lazy_fanout: 2,
+                ..GossipConfig::default()
+            },
+        );
+        engine.subscribe("blocks");
+        for peer in peers {
+            engine.add_peer_topic(peer, "blocks");
+        }
+        engine
+    }
+
+    #[test]
+    fn test_publish_respects_fanout() {
+        let mut engine = engine_with_peers("n0", &["n1", "n2", "n3", "n4", "n5"]);
+        let (_, actions) = engine.publish("blocks", b"block".to_vec()).unwrap();
+
+        let eager = actions
+            .iter()
+            .filter(|a| matches!(a.frame, GossipFrame::Publish(_)))
+            .count();
+        let lazy = actions
+            .iter()
+            .filter(|a| matches!(a.frame, GossipFrame::IHave { .. }))
+            .count();
+        assert_eq!(eager, 2);
+        assert_eq!(lazy, 2);
+    }
+
+    #[test]
+    fn test_duplicates_are_dropped() {
+        let mut sender = engine_with_peers("n0", &["n1"]);
+        let mut receiver = engine_with_peers("n1", &["n0", "n2"]);
+
+        let (_, actions) = sender.publish("blocks", b"tx".to_vec()).unwrap();
+        let frame = actions[0].frame.clone();
+
+        let first = receiver.handle_frame("n0", frame.clone()).unwrap();
+        assert!(first.delivered.is_some());
+        assert!(!first.duplicate);
+
+        let second = receiver.handle_frame("n2", frame).unwrap();
+        assert!(second.duplicate);
+        assert!(second.delivered.is_none());
+        assert_eq!(receiver.get_metrics().duplicates, 1);
+    }
+
+    #[test]
+    fn test_ihave_iwant_pull() {
+        let mut origin = engine_with_peers("n0", &["n1"]);
+        let mut puller = engine_with_peers("n1", &["n0"]);
+
+        let (message_id, _) = origin.publish("blocks", b"announcement".to_vec()).unwrap();
+
+        let outcome = puller
+            .handle_frame(
+                "n0",
+                GossipFrame::IHave {
+                    topic: "blocks".to_string(),
+                    message_ids: vec![message_id.clone()],
+                },
+            )
+            .unwrap();
+        assert_eq!(outcome.actions.len(), 1);
+
+        let response = origin.handle_frame("n1", outcome.actions[0].frame.clone()).unwrap();
+        assert_eq!(response.actions.len(), 1);
+
+        let delivered = puller
+            .handle_frame("n0", response.actions[0].frame.clone())
+            .unwrap()
+            .delivered
+            .unwrap();
+        assert_eq!(delivered.message_id, message_id);
+        assert_eq!(delivered.payload, b"announcement".to_vec());
+    }
+
+    #[test]
+    fn test_tampered_message_rejected() {
+        let mut sender = engine_with_peers("n0", &["n1"]);
+        let mut receiver = engine_with_peers("n1", &["n0"]);
+
+        let (_, actions) = sender.publish("blocks", b"original".to_vec()).unwrap();
+        let mut message = match actions[0].frame.clone() {
+            GossipFrame::Publish(message) => message,
+            _ => panic!("expected publish frame"),
+        };
+        message.payload = b"forged".to_vec();
+
+        assert!(receiver.handle_frame("n0", GossipFrame::Publish(message)).is_err());
+    }
+
+    #[test]
+    fn test_expired_messages_not_relayed() {
+        let mut sender = engine_with_peers("n0", &["n1"]);
+        let mut relay = engine_with_peers("n1", &["n0", "n2"]);
+
+        let (_, actions) = sender
+            .publish_with_ttl("blocks", b"vote".to_vec(), Some(Duration::from_secs(60)))
+            .unwrap();
+        let mut message = match actions[0].frame.clone() {
+            GossipFrame::Publish(message) => message,
+            _ => panic!("expected publish frame"),
+        };
+
+        // Extending the deadline invalidates the message ID
+        let mut extended = message.clone();
+        extended.expires_at_ms = extended.expires_at_ms.map(|d| d + 60_000);
+        assert!(relay.handle_frame("n0", GossipFrame::Publish(extended)).is_err());
+
+        // A message whose deadline has passed is dropped, not delivered or forwarded
+        message.expires_at_ms = Some(now_ms().saturating_sub(1));
+        message.message_id = GossipMessage::compute_id(
+            &message.origin,
+            message.sequence,
+            &message.topic,
+            &message.payload,
+            message.expires_at_ms,
+        );
+        let outcome = relay.handle_frame("n0", GossipFrame::Publish(message)).unwrap();
+        assert!(outcome.delivered.is_none());
+        assert!(outcome.actions.is_empty());
+        assert_eq!(relay.get_metrics().expired, 1);
+    }
+
+    #[test]
+    fn test_unsubscribed_topic_forwarded_not_delivered() {
+        let mut sender = engine_with_peers("n0", &["n1"]);
+        let mut relay = GossipEngine::new("n1".to_string(), GossipConfig::default());
+        relay.add_peer_topic("n2", "blocks");
+
+        let (_, actions) = sender.publish("blocks", b"block".to_vec()).unwrap();
+        let outcome = relay.handle_frame("n0", actions[0].frame.clone()).unwrap();
+
+        assert!(outcome.delivered.is_none());
+        assert_eq!(outcome.actions.len(), 1);
+        assert_eq!(outcome.actions[0].peer_id, "n2");
+    }
+}
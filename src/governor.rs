@@ -0,0 +1,953 @@
+//! # Resource Governor - Global Admission Control
+//!
+//! Process-wide caps on the resources a client can commit: open channels,
+//! messages in flight, payload memory and concurrent crypto operations.
+//! Work that would exceed a cap is rejected up front with
+//! `SecureCommsError::ResourceExhausted`, so overload shows up as fast
+//! rejections and a degraded health status instead of unbounded memory
+//! growth or latency collapse.
+//!
+//! ## Admission
+//!
+//! - **Permits**: `try_acquire` reserves an amount of one resource and
+//!   returns a `ResourcePermit` that gives it back when dropped
+//! - **Fail Fast**: Acquisition never waits; a full resource rejects at once
+//! - **Sharing**: Clones of a governor share the same counters, so several
+//!   clients or subsystems can draw from one budget
+//! - **Health**: Utilisation at or above `high_watermark` reports
+//!   `Constrained`, a resource at its cap reports `Saturated`
+//!
+//! ## Quotas
+//!
+//! On top of the process-wide caps, a [`QuotaConfig`] limits what one tenant
+//! and each of its channels may use:
+//!
+//! - **Bandwidth / Message Rate**: Token buckets refilled every second;
+//!   sent and received traffic draw from the same bucket
+//! - **Memory**: Payload bytes held while a message is being processed,
+//!   returned when the `QuotaPermit` is dropped
+//! - **Storage**: Payload bytes journaled and not yet processed
+//!
+//! A message is checked against its channel's limits and its tenant's
+//! limits together, and nothing is consumed unless both admit it. A failed
+//! check returns a [`QuotaExceeded`] report naming the scope, resource and
+//! amounts, which the client publishes as `ClientEvent::QuotaExceeded` for
+//! billing and monitoring.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::governor::{ResourceGovernor, ResourceKind, ResourceLimits};
+//!
+//! let governor = ResourceGovernor::new(ResourceLimits::default());
+//! let permit = governor.try_acquire(ResourceKind::InflightMessages, 1).unwrap();
+//! // ... send the message ...
+//! drop(permit);
+//! println!("Governor status: {:?}", governor.health());
+//! ```
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::governor::{QuotaConfig, QuotaLimits, ResourceGovernor, ResourceLimits};
+//! use quantum_forge_secure_comms::middleware::Direction;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let governor = ResourceGovernor::with_quotas(
+//!     ResourceLimits::default(),
+//!     QuotaConfig {
+//!         tenant_id: Some("acme".to_string()),
+//!         per_channel: QuotaLimits {
+//!             max_messages_per_second: Some(100),
+//!             ..Default::default()
+//!         },
+//!         ..Default::default()
+//!     },
+//! )?;
+//! match governor.admit_traffic("peer_1", Direction::Outbound, 512) {
+//!     Ok(_permit) => println!("within quota"),
+//!     Err(exceeded) => println!("quota exceeded: {}", exceeded),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::memory_profile::MemoryFootprint;
+use crate::middleware::Direction;
+use crate::{Result, SecureCommsError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Resource guarded by the governor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResourceKind {
+    /// Open secure channels
+    Channels,
+    /// Messages between admission and completion of the send
+    InflightMessages,
+    /// Payload bytes held by in-flight work
+    Memory,
+    /// Key exchanges and bulk encryptions running at once
+    CryptoOps,
+}
+
+impl ResourceKind {
+    /// Every guarded resource
+    pub const ALL: [ResourceKind; 4] = [
+        ResourceKind::Channels,
+        ResourceKind::InflightMessages,
+        ResourceKind::Memory,
+        ResourceKind::CryptoOps,
+    ];
+
+    /// Stable name used in errors and statistics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceKind::Channels => "channels",
+            ResourceKind::InflightMessages => "inflight_messages",
+            ResourceKind::Memory => "memory_bytes",
+            ResourceKind::CryptoOps => "crypto_ops",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            ResourceKind::Channels => 0,
+            ResourceKind::InflightMessages => 1,
+            ResourceKind::Memory => 2,
+            ResourceKind::CryptoOps => 3,
+        }
+    }
+}
+
+/// Caps enforced by the governor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Maximum open channels
+    pub max_channels: u64,
+    /// Maximum messages being sent at once
+    pub max_inflight_messages: u64,
+    /// Maximum payload bytes held by in-flight work
+    pub max_memory_bytes: u64,
+    /// Maximum concurrent key exchanges and bulk encryptions
+    pub max_concurrent_crypto_ops: u64,
+    /// Utilisation (0.0-1.0) from which health reports `Constrained`
+    pub high_watermark: f64,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_channels: 1024,
+            max_inflight_messages: 4096,
+            max_memory_bytes: 1024 * 1024 * 1024, // 1GB
+            max_concurrent_crypto_ops: 64,
+            high_watermark: 0.9,
+        }
+    }
+}
+
+impl ResourceLimits {
+    /// Cap of one resource
+    pub fn limit(&self, kind: ResourceKind) -> u64 {
+        match kind {
+            ResourceKind::Channels => self.max_channels,
+            ResourceKind::InflightMessages => self.max_inflight_messages,
+            ResourceKind::Memory => self.max_memory_bytes,
+            ResourceKind::CryptoOps => self.max_concurrent_crypto_ops,
+        }
+    }
+}
+
+/// Load condition reported in health status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GovernorHealth {
+    /// Every resource below the high watermark
+    Healthy,
+    /// At least one resource at or above the high watermark
+    Constrained,
+    /// At least one resource at its cap; new work of that kind is rejected
+    Saturated,
+}
+
+/// Resource limited per tenant and per channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaResource {
+    /// Payload bytes per second
+    Bandwidth,
+    /// Messages per second
+    MessageRate,
+    /// Payload bytes held by messages being processed
+    Memory,
+    /// Payload bytes journaled and not yet processed
+    Storage,
+}
+
+impl QuotaResource {
+    /// Every quota resource
+    pub const ALL: [QuotaResource; 4] = [
+        QuotaResource::Bandwidth,
+        QuotaResource::MessageRate,
+        QuotaResource::Memory,
+        QuotaResource::Storage,
+    ];
+
+    /// Stable name used in errors, events and statistics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaResource::Bandwidth => "bandwidth_bytes_per_second",
+            QuotaResource::MessageRate => "messages_per_second",
+            QuotaResource::Memory => "memory_bytes",
+            QuotaResource::Storage => "storage_bytes",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            QuotaResource::Bandwidth => 0,
+            QuotaResource::MessageRate => 1,
+            QuotaResource::Memory => 2,
+            QuotaResource::Storage => 3,
+        }
+    }
+}
+
+/// Owner a quota is charged to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaScope {
+    /// All traffic of one tenant
+    Tenant(String),
+    /// Traffic with one peer
+    Channel(String),
+}
+
+impl fmt::Display for QuotaScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaScope::Tenant(tenant_id) => write!(f, "tenant:{}", tenant_id),
+            QuotaScope::Channel(peer_id) => write!(f, "channel:{}", peer_id),
+        }
+    }
+}
+
+/// Limits of one scope; `None` leaves a resource unlimited
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaLimits {
+    /// Payload bytes per second, sent and received combined
+    pub max_bytes_per_second: Option<u64>,
+    /// Messages per second, sent and received combined
+    pub max_messages_per_second: Option<u64>,
+    /// Payload bytes held by messages being processed at once
+    pub max_memory_bytes: Option<u64>,
+    /// Payload bytes journaled and not yet processed
+    pub max_storage_bytes: Option<u64>,
+}
+
+impl QuotaLimits {
+    /// Limit of one resource
+    pub fn limit(&self, resource: QuotaResource) -> Option<u64> {
+        match resource {
+            QuotaResource::Bandwidth => self.max_bytes_per_second,
+            QuotaResource::MessageRate => self.max_messages_per_second,
+            QuotaResource::Memory => self.max_memory_bytes,
+            QuotaResource::Storage => self.max_storage_bytes,
+        }
+    }
+
+    /// Whether no resource is limited
+    pub fn is_unlimited(&self) -> bool {
+        QuotaResource::ALL
+            .iter()
+            .all(|resource| self.limit(*resource).is_none())
+    }
+}
+
+/// Tenant and channel quotas enforced by the governor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Tenant charged for the traffic; tenant limits apply only when set
+    pub tenant_id: Option<String>,
+    /// Limits of the tenant as a whole
+    #[serde(default)]
+    pub tenant: QuotaLimits,
+    /// Limits of every channel without an entry in `channels`
+    #[serde(default)]
+    pub per_channel: QuotaLimits,
+    /// Limits of individual channels, by peer ID
+    #[serde(default)]
+    pub channels: BTreeMap<String, QuotaLimits>,
+}
+
+impl QuotaConfig {
+    /// Check the tenant ID is usable and no limit is zero
+    pub fn validate(&self) -> Result<()> {
+        if self.tenant_id.as_deref() == Some("") {
+            return Err(SecureCommsError::Configuration(
+                "Quota tenant ID must not be empty".to_string(),
+            ));
+        }
+        let all_limits = [&self.tenant, &self.per_channel]
+            .into_iter()
+            .chain(self.channels.values());
+        for limits in all_limits {
+            if let Some(resource) = QuotaResource::ALL
+                .into_iter()
+                .find(|resource| limits.limit(*resource) == Some(0))
+            {
+                return Err(SecureCommsError::Configuration(format!(
+                    "Quota {} must be positive; leave it unset for no limit",
+                    resource.as_str()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Limits of the channel to `peer_id`
+    pub fn channel_limits(&self, peer_id: &str) -> &QuotaLimits {
+        self.channels.get(peer_id).unwrap_or(&self.per_channel)
+    }
+}
+
+/// Quota check that failed, as reported to billing and monitoring
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaExceeded {
+    pub scope: QuotaScope,
+    pub resource: QuotaResource,
+    /// Direction of the rejected traffic
+    pub direction: Direction,
+    /// Configured limit; per second for rates
+    pub limit: u64,
+    /// Amount in use, or consumed within the last second for rates
+    pub used: u64,
+    /// Amount the rejected message asked for
+    pub requested: u64,
+}
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} quota of {} exceeded ({} of {} used, {} requested)",
+            self.resource.as_str(),
+            self.scope,
+            self.used,
+            self.limit,
+            self.requested
+        )
+    }
+}
+
+/// Consumption of one quota scope
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    /// Messages admitted
+    pub messages: u64,
+    /// Payload bytes admitted
+    pub bytes: u64,
+    /// Payload bytes held by messages being processed
+    pub memory_bytes: u64,
+    /// Payload bytes journaled and not yet processed
+    pub storage_bytes: u64,
+    /// Rejections by resource name
+    pub rejections: BTreeMap<String, u64>,
+}
+
+/// Token bucket holding up to one second of a rate
+#[derive(Debug, Default)]
+struct TokenBucket {
+    tokens: f64,
+    refilled: Option<Instant>,
+}
+
+impl TokenBucket {
+    /// Tokens available at `now`; a new bucket starts full
+    fn available(&self, rate: u64, now: Instant) -> f64 {
+        match self.refilled {
+            Some(refilled) => (self.tokens
+                + now.duration_since(refilled).as_secs_f64() * rate as f64)
+                .min(rate as f64),
+            None => rate as f64,
+        }
+    }
+
+    fn take(&mut self, rate: u64, amount: u64, now: Instant) {
+        self.tokens = self.available(rate, now) - amount as f64;
+        self.refilled = Some(now);
+    }
+}
+
+#[derive(Debug, Default)]
+struct ScopeState {
+    bandwidth: TokenBucket,
+    messages: TokenBucket,
+    usage: QuotaUsage,
+}
+
+impl ScopeState {
+    /// Check `amount` of `resource` against `limit` without consuming it
+    fn check(&self, resource: QuotaResource, limit: u64, amount: u64, now: Instant) -> Option<u64> {
+        let used = match resource {
+            QuotaResource::Bandwidth => limit - self.bandwidth.available(limit, now).floor() as u64,
+            QuotaResource::MessageRate => {
+                limit - self.messages.available(limit, now).floor() as u64
+            }
+            QuotaResource::Memory => self.usage.memory_bytes,
+            QuotaResource::Storage => self.usage.storage_bytes,
+        };
+        match used.checked_add(amount) {
+            Some(total) if total <= limit => None,
+            _ => Some(used),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct QuotaState {
+    config: QuotaConfig,
+    scopes: BTreeMap<QuotaScope, ScopeState>,
+}
+
+impl QuotaState {
+    /// Scopes charged for traffic with `peer_id`, with their limits
+    fn scopes_for(&self, peer_id: &str) -> Vec<(QuotaScope, QuotaLimits)> {
+        let mut scopes = Vec::new();
+        let channel = self.config.channel_limits(peer_id);
+        if !channel.is_unlimited() {
+            scopes.push((QuotaScope::Channel(peer_id.to_string()), channel.clone()));
+        }
+        if let Some(tenant_id) = &self.config.tenant_id {
+            if !self.config.tenant.is_unlimited() {
+                scopes.push((
+                    QuotaScope::Tenant(tenant_id.clone()),
+                    self.config.tenant.clone(),
+                ));
+            }
+        }
+        scopes
+    }
+
+    /// Check every charge against every scope, recording the first failure
+    fn check(
+        &mut self,
+        scopes: &[(QuotaScope, QuotaLimits)],
+        charges: &[(QuotaResource, u64)],
+        direction: Direction,
+        now: Instant,
+    ) -> std::result::Result<(), QuotaExceeded> {
+        for (scope, limits) in scopes {
+            let state = self.scopes.entry(scope.clone()).or_default();
+            for (resource, amount) in charges {
+                let Some(limit) = limits.limit(*resource) else {
+                    continue;
+                };
+                if let Some(used) = state.check(*resource, limit, *amount, now) {
+                    *state
+                        .usage
+                        .rejections
+                        .entry(resource.as_str().to_string())
+                        .or_default() += 1;
+                    return Err(QuotaExceeded {
+                        scope: scope.clone(),
+                        resource: *resource,
+                        direction,
+                        limit,
+                        used,
+                        requested: *amount,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct GovernorState {
+    limits: ResourceLimits,
+    in_use: [AtomicU64; 4],
+    rejections: [AtomicU64; 4],
+    quotas: Mutex<QuotaState>,
+    quota_rejections: [AtomicU64; 4],
+}
+
+/// Admission controller shared by everything drawing on one budget
+#[derive(Debug, Clone)]
+pub struct ResourceGovernor {
+    state: Arc<GovernorState>,
+}
+
+impl ResourceGovernor {
+    /// Create governor enforcing `limits`
+    pub fn new(limits: ResourceLimits) -> Self {
+        Self {
+            state: Arc::new(GovernorState {
+                limits,
+                in_use: Default::default(),
+                rejections: Default::default(),
+                quotas: Default::default(),
+                quota_rejections: Default::default(),
+            }),
+        }
+    }
+
+    /// Create governor enforcing `limits` and tenant and channel `quotas`
+    pub fn with_quotas(limits: ResourceLimits, quotas: QuotaConfig) -> Result<Self> {
+        quotas.validate()?;
+        let governor = Self::new(limits);
+        governor.state.quotas.lock().config = quotas;
+        Ok(governor)
+    }
+
+    /// Enforced tenant and channel quotas
+    pub fn quota_config(&self) -> QuotaConfig {
+        self.state.quotas.lock().config.clone()
+    }
+
+    /// Enforced caps
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.state.limits
+    }
+
+    /// Reserve `amount` of `kind`, failing fast if it would exceed the cap
+    pub fn try_acquire(&self, kind: ResourceKind, amount: u64) -> Result<ResourcePermit> {
+        let limit = self.state.limits.limit(kind);
+        let reserved = self.state.in_use[kind.index()].fetch_update(
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            |current| current.checked_add(amount).filter(|total| *total <= limit),
+        );
+        match reserved {
+            Ok(_) => Ok(ResourcePermit {
+                state: self.state.clone(),
+                kind,
+                amount,
+            }),
+            Err(current) => {
+                self.state.rejections[kind.index()].fetch_add(1, Ordering::Relaxed);
+                Err(SecureCommsError::ResourceExhausted(format!(
+                    "{} limit reached ({} of {} in use, {} requested)",
+                    kind.as_str(),
+                    current,
+                    limit,
+                    amount
+                )))
+            }
+        }
+    }
+
+    /// Admit one message of `bytes` payload bytes to or from `peer_id`
+    ///
+    /// Checks message rate, bandwidth and memory against the channel's and
+    /// the tenant's quotas. Nothing is consumed unless every check passes;
+    /// the memory is held until the returned permit is dropped.
+    pub fn admit_traffic(
+        &self,
+        peer_id: &str,
+        direction: Direction,
+        bytes: u64,
+    ) -> std::result::Result<QuotaPermit, QuotaExceeded> {
+        let charges = [
+            (QuotaResource::MessageRate, 1),
+            (QuotaResource::Bandwidth, bytes),
+            (QuotaResource::Memory, bytes),
+        ];
+        let now = Instant::now();
+        let mut quotas = self.state.quotas.lock();
+        let scopes = quotas.scopes_for(peer_id);
+        if let Err(exceeded) = quotas.check(&scopes, &charges, direction, now) {
+            self.state.quota_rejections[exceeded.resource.index()].fetch_add(1, Ordering::Relaxed);
+            return Err(exceeded);
+        }
+        for (scope, limits) in &scopes {
+            let state = quotas.scopes.entry(scope.clone()).or_default();
+            if let Some(rate) = limits.max_messages_per_second {
+                state.messages.take(rate, 1, now);
+            }
+            if let Some(rate) = limits.max_bytes_per_second {
+                state.bandwidth.take(rate, bytes, now);
+            }
+            state.usage.messages += 1;
+            state.usage.bytes += bytes;
+            state.usage.memory_bytes += bytes;
+        }
+        Ok(QuotaPermit {
+            state: self.state.clone(),
+            scopes: scopes.into_iter().map(|(scope, _)| scope).collect(),
+            bytes,
+        })
+    }
+
+    /// Charge `bytes` of journaled payload from `peer_id` against storage quotas
+    ///
+    /// The charge stays until `release_storage` gives it back.
+    pub fn charge_storage(
+        &self,
+        peer_id: &str,
+        bytes: u64,
+    ) -> std::result::Result<(), QuotaExceeded> {
+        let charges = [(QuotaResource::Storage, bytes)];
+        let mut quotas = self.state.quotas.lock();
+        let scopes = quotas.scopes_for(peer_id);
+        if let Err(exceeded) = quotas.check(&scopes, &charges, Direction::Inbound, Instant::now()) {
+            self.state.quota_rejections[exceeded.resource.index()].fetch_add(1, Ordering::Relaxed);
+            return Err(exceeded);
+        }
+        for (scope, _) in scopes {
+            quotas.scopes.entry(scope).or_default().usage.storage_bytes += bytes;
+        }
+        Ok(())
+    }
+
+    /// Give back storage charged for a message from `peer_id`
+    pub fn release_storage(&self, peer_id: &str, bytes: u64) {
+        let mut quotas = self.state.quotas.lock();
+        for (scope, _) in quotas.scopes_for(peer_id) {
+            if let Some(state) = quotas.scopes.get_mut(&scope) {
+                state.usage.storage_bytes = state.usage.storage_bytes.saturating_sub(bytes);
+            }
+        }
+    }
+
+    /// Consumption of one quota scope, if anything was charged to it
+    pub fn quota_usage(&self, scope: &QuotaScope) -> Option<QuotaUsage> {
+        self.state
+            .quotas
+            .lock()
+            .scopes
+            .get(scope)
+            .map(|state| state.usage.clone())
+    }
+
+    /// Quota checks of `resource` failed so far, across all scopes
+    pub fn quota_rejections(&self, resource: QuotaResource) -> u64 {
+        self.state.quota_rejections[resource.index()].load(Ordering::Relaxed)
+    }
+
+    /// Amount of `kind` currently reserved
+    pub fn in_use(&self, kind: ResourceKind) -> u64 {
+        self.state.in_use[kind.index()].load(Ordering::Acquire)
+    }
+
+    /// Requests for `kind` rejected so far
+    pub fn rejections(&self, kind: ResourceKind) -> u64 {
+        self.state.rejections[kind.index()].load(Ordering::Relaxed)
+    }
+
+    /// Fraction of the cap of `kind` in use
+    pub fn utilization(&self, kind: ResourceKind) -> f64 {
+        let limit = self.state.limits.limit(kind);
+        if limit == 0 {
+            1.0
+        } else {
+            self.in_use(kind) as f64 / limit as f64
+        }
+    }
+
+    /// Resources at their cap
+    pub fn saturated(&self) -> Vec<ResourceKind> {
+        ResourceKind::ALL
+            .into_iter()
+            .filter(|kind| self.in_use(*kind) >= self.state.limits.limit(*kind))
+            .collect()
+    }
+
+    /// Current load condition
+    pub fn health(&self) -> GovernorHealth {
+        if !self.saturated().is_empty() {
+            GovernorHealth::Saturated
+        } else if ResourceKind::ALL
+            .iter()
+            .any(|kind| self.utilization(*kind) >= self.state.limits.high_watermark)
+        {
+            GovernorHealth::Constrained
+        } else {
+            GovernorHealth::Healthy
+        }
+    }
+
+    /// Get governor statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "status".to_string(),
+            serde_json::to_value(self.health()).unwrap_or(serde_json::Value::Null),
+        );
+        for kind in ResourceKind::ALL {
+            stats.insert(
+                kind.as_str().to_string(),
+                serde_json::json!({
+                    "in_use": self.in_use(kind),
+                    "limit": self.state.limits.limit(kind),
+                    "rejections": self.rejections(kind),
+                }),
+            );
+        }
+        let quotas: serde_json::Map<String, serde_json::Value> = self
+            .state
+            .quotas
+            .lock()
+            .scopes
+            .iter()
+            .map(|(scope, state)| {
+                (
+                    scope.to_string(),
+                    serde_json::to_value(&state.usage).unwrap_or(serde_json::Value::Null),
+                )
+            })
+            .collect();
+        stats.insert("quotas".to_string(), serde_json::Value::Object(quotas));
+        stats
+    }
+}
+
+impl Default for ResourceGovernor {
+    fn default() -> Self {
+        Self::new(ResourceLimits::default())
+    }
+}
+
+/// Reservation returned to the governor when dropped
+#[derive(Debug)]
+pub struct ResourcePermit {
+    state: Arc<GovernorState>,
+    kind: ResourceKind,
+    amount: u64,
+}
+
+impl ResourcePermit {
+    /// Reserved resource
+    pub fn kind(&self) -> ResourceKind {
+        self.kind
+    }
+
+    /// Reserved amount
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+}
+
+impl MemoryFootprint for ResourcePermit {
+    fn heap_bytes(&self) -> usize {
+        // The governor state is shared, not owned by the permit
+        0
+    }
+}
+
+impl Drop for ResourcePermit {
+    fn drop(&mut self) {
+        self.state.in_use[self.kind.index()].fetch_sub(self.amount, Ordering::AcqRel);
+    }
+}
+
+/// Quota memory returned to the tenant and channel when dropped
+#[derive(Debug)]
+pub struct QuotaPermit {
+    state: Arc<GovernorState>,
+    scopes: Vec<QuotaScope>,
+    bytes: u64,
+}
+
+impl QuotaPermit {
+    /// Scopes the message was charged to
+    pub fn scopes(&self) -> &[QuotaScope] {
+        &self.scopes
+    }
+}
+
+impl Drop for QuotaPermit {
+    fn drop(&mut self) {
+        let mut quotas = self.state.quotas.lock();
+        for scope in &self.scopes {
+            if let Some(state) = quotas.scopes.get_mut(scope) {
+                state.usage.memory_bytes = state.usage.memory_bytes.saturating_sub(self.bytes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_limits() -> ResourceLimits {
+        ResourceLimits {
+            max_channels: 2,
+            max_inflight_messages: 10,
+            max_memory_bytes: 1000,
+            max_concurrent_crypto_ops: 1,
+            high_watermark: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_permits_released_on_drop() {
+        let governor = ResourceGovernor::new(small_limits());
+        let first = governor.try_acquire(ResourceKind::Channels, 1).unwrap();
+        let _second = governor.try_acquire(ResourceKind::Channels, 1).unwrap();
+        assert!(matches!(
+            governor.try_acquire(ResourceKind::Channels, 1),
+            Err(SecureCommsError::ResourceExhausted(_))
+        ));
+        assert_eq!(governor.rejections(ResourceKind::Channels), 1);
+
+        drop(first);
+        assert_eq!(governor.in_use(ResourceKind::Channels), 1);
+        assert!(governor.try_acquire(ResourceKind::Channels, 1).is_ok());
+    }
+
+    #[test]
+    fn test_health_follows_utilization() {
+        let governor = ResourceGovernor::new(small_limits());
+        assert_eq!(governor.health(), GovernorHealth::Healthy);
+
+        let memory = governor.try_acquire(ResourceKind::Memory, 600).unwrap();
+        assert_eq!(governor.health(), GovernorHealth::Constrained);
+        // A request larger than what is left is rejected without reserving anything
+        assert!(governor.try_acquire(ResourceKind::Memory, 500).is_err());
+        assert_eq!(governor.in_use(ResourceKind::Memory), 600);
+
+        let crypto = governor.try_acquire(ResourceKind::CryptoOps, 1).unwrap();
+        assert_eq!(governor.health(), GovernorHealth::Saturated);
+        assert_eq!(governor.saturated(), vec![ResourceKind::CryptoOps]);
+        assert_eq!(
+            governor.get_stats()["status"],
+            serde_json::json!("Saturated")
+        );
+
+        drop(crypto);
+        drop(memory);
+        assert_eq!(governor.health(), GovernorHealth::Healthy);
+    }
+
+    #[test]
+    fn test_clones_share_budget() {
+        let governor = ResourceGovernor::new(small_limits());
+        let shared = governor.clone();
+        let permits: Vec<_> = (0..10)
+            .map(|_| {
+                shared
+                    .try_acquire(ResourceKind::InflightMessages, 1)
+                    .unwrap()
+            })
+            .collect();
+        assert!(governor
+            .try_acquire(ResourceKind::InflightMessages, 1)
+            .is_err());
+        drop(permits);
+        assert_eq!(governor.in_use(ResourceKind::InflightMessages), 0);
+    }
+
+    fn quotas() -> QuotaConfig {
+        QuotaConfig {
+            tenant_id: Some("acme".to_string()),
+            tenant: QuotaLimits {
+                max_bytes_per_second: Some(1000),
+                max_storage_bytes: Some(500),
+                ..Default::default()
+            },
+            per_channel: QuotaLimits {
+                max_messages_per_second: Some(3),
+                max_memory_bytes: Some(400),
+                ..Default::default()
+            },
+            channels: BTreeMap::from([(
+                "vip".to_string(),
+                QuotaLimits {
+                    max_messages_per_second: Some(100),
+                    ..Default::default()
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_channel_and_tenant_rate_quotas() {
+        let governor = ResourceGovernor::with_quotas(small_limits(), quotas()).unwrap();
+        for _ in 0..3 {
+            governor
+                .admit_traffic("peer", Direction::Outbound, 10)
+                .unwrap();
+        }
+        let exceeded = governor
+            .admit_traffic("peer", Direction::Inbound, 10)
+            .unwrap_err();
+        assert_eq!(exceeded.scope, QuotaScope::Channel("peer".to_string()));
+        assert_eq!(exceeded.resource, QuotaResource::MessageRate);
+        assert_eq!((exceeded.limit, exceeded.used), (3, 3));
+
+        // Other channels have their own budget but share the tenant's bandwidth
+        governor
+            .admit_traffic("vip", Direction::Outbound, 900)
+            .unwrap();
+        let exceeded = governor
+            .admit_traffic("vip", Direction::Outbound, 100)
+            .unwrap_err();
+        assert_eq!(exceeded.scope, QuotaScope::Tenant("acme".to_string()));
+        assert_eq!(exceeded.resource, QuotaResource::Bandwidth);
+        // The rejected message consumed nothing from the channel
+        let vip = governor
+            .quota_usage(&QuotaScope::Channel("vip".to_string()))
+            .unwrap();
+        assert_eq!((vip.messages, vip.bytes), (1, 900));
+        assert_eq!(governor.quota_rejections(QuotaResource::Bandwidth), 1);
+
+        std::thread::sleep(std::time::Duration::from_millis(400));
+        assert!(governor
+            .admit_traffic("peer", Direction::Outbound, 10)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_memory_and_storage_quotas_released() {
+        let governor = ResourceGovernor::with_quotas(small_limits(), quotas()).unwrap();
+        let permit = governor
+            .admit_traffic("peer", Direction::Outbound, 300)
+            .unwrap();
+        let exceeded = governor
+            .admit_traffic("peer", Direction::Outbound, 200)
+            .unwrap_err();
+        assert_eq!(exceeded.resource, QuotaResource::Memory);
+        assert_eq!(exceeded.used, 300);
+        drop(permit);
+        let channel = QuotaScope::Channel("peer".to_string());
+        assert_eq!(governor.quota_usage(&channel).unwrap().memory_bytes, 0);
+
+        governor.charge_storage("peer", 400).unwrap();
+        let exceeded = governor.charge_storage("other", 200).unwrap_err();
+        assert_eq!(exceeded.scope, QuotaScope::Tenant("acme".to_string()));
+        assert_eq!(exceeded.direction, Direction::Inbound);
+        governor.release_storage("peer", 400);
+        governor.charge_storage("other", 200).unwrap();
+
+        let stats = governor.get_stats();
+        assert_eq!(stats["quotas"]["tenant:acme"]["storage_bytes"], 200);
+        assert_eq!(
+            stats["quotas"]["channel:peer"]["rejections"]["memory_bytes"],
+            1
+        );
+    }
+
+    #[test]
+    fn test_quota_config_validation() {
+        assert!(quotas().validate().is_ok());
+        // Without quotas nothing is tracked
+        let governor = ResourceGovernor::new(small_limits());
+        let permit = governor
+            .admit_traffic("peer", Direction::Outbound, u64::MAX)
+            .unwrap();
+        assert!(permit.scopes().is_empty());
+
+        let mut config = quotas();
+        config.tenant_id = Some(String::new());
+        assert!(ResourceGovernor::with_quotas(small_limits(), config).is_err());
+        let mut config = quotas();
+        config.channels.get_mut("vip").unwrap().max_memory_bytes = Some(0);
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,582 @@
+//! # Handshake Fuzz - Conformance Fuzzing of the Private Handshake
+//!
+//! Byte-level fuzzing shows malformed frames are refused; it says nothing
+//! about well-formed frames arriving in an order the protocol never
+//! planned for. `HandshakeFuzzer` drives two in-process clients through
+//! private handshakes while reordering, duplicating, replaying, misrouting
+//! and dropping their genuine frames and closing channels at random, then
+//! checks both nodes came through intact. Built only with the `simulation`
+//! feature and for unit tests.
+//!
+//! ## Actions
+//!
+//! - **Start**: A node starts a handshake with its peer
+//! - **Deliver**: An in-flight frame is delivered, in any order
+//! - **Duplicate**: An in-flight frame is delivered and stays in flight
+//! - **Replay**: A frame delivered earlier is delivered again
+//! - **Misroute**: An in-flight frame is handed to the handler of the next stage
+//! - **Drop**: An in-flight frame is lost
+//! - **Close**: A node closes its channel to the peer
+//!
+//! ## Checked Properties
+//!
+//! - **No Panics**: Every delivery returns, successfully or with an error
+//! - **No Leaked Sessions**: After every action, neither transport holds a
+//!   session key for a peer its client has no channel with
+//! - **Convergence**: Once the frames still in flight are delivered in order
+//!   and overdue handshakes expire, each node's channel is either
+//!   `Established`, with a session behind it, or closed, and no handshake is
+//!   left pending
+//!
+//! The action schedule is drawn from the seed, so a failing run is replayed
+//! from its report with `HandshakeFuzzer::step`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! use quantum_forge_secure_comms::handshake_fuzz::{FuzzConfig, HandshakeFuzzer};
+//!
+//! let config = FuzzConfig {
+//!     seed: 7,
+//!     steps: 200,
+//!     ..Default::default()
+//! };
+//! let mut fuzzer = HandshakeFuzzer::new(config).await?;
+//! let report = fuzzer.run().await;
+//! assert!(report.is_conformant(), "seed {}: {:?}", report.seed, report.violations);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::channel_state::ChannelState;
+use crate::network_comms::NetworkMessage;
+use crate::private_handshake::{PrivateFrame, PrivateHandshakeConfig};
+use crate::streamlined_client::{StreamlinedConfig, StreamlinedSecureClient};
+use crate::{Result, SecureCommsError};
+use futures::FutureExt;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+
+/// One of the two nodes under test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Party {
+    Alice,
+    Bob,
+}
+
+impl Party {
+    /// Both nodes
+    pub const BOTH: [Party; 2] = [Party::Alice, Party::Bob];
+
+    /// The other node
+    pub fn peer(self) -> Party {
+        match self {
+            Party::Alice => Party::Bob,
+            Party::Bob => Party::Alice,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Party::Alice => 0,
+            Party::Bob => 1,
+        }
+    }
+}
+
+/// Handshake frame type, in protocol order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stage {
+    Init,
+    Response,
+    Finish,
+}
+
+impl Stage {
+    /// Stage whose handler a misrouted frame is handed to
+    pub fn next(self) -> Stage {
+        match self {
+            Stage::Init => Stage::Response,
+            Stage::Response => Stage::Finish,
+            Stage::Finish => Stage::Init,
+        }
+    }
+}
+
+/// Genuine handshake frame on its way to a node
+#[derive(Debug, Clone)]
+pub struct Flight {
+    /// Receiving node
+    pub to: Party,
+    /// Protocol step the frame belongs to
+    pub stage: Stage,
+    pub frame: PrivateFrame,
+}
+
+/// One fuzzing step; indices refer to the in-flight queue or delivery history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FuzzAction {
+    /// The node starts a handshake with its peer
+    Start(Party),
+    /// The in-flight frame is delivered and leaves the queue
+    Deliver(usize),
+    /// The in-flight frame is delivered and stays queued
+    Duplicate(usize),
+    /// The frame delivered at this point of the history is delivered again
+    Replay(usize),
+    /// The in-flight frame leaves the queue for the handler of the next stage
+    Misroute(usize),
+    /// The in-flight frame is lost
+    Drop(usize),
+    /// The node closes its channel to the peer
+    Close(Party),
+}
+
+/// Property a run broke
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConformanceViolation {
+    /// A delivery panicked instead of returning an error
+    Panic { action: FuzzAction, message: String },
+    /// The transport holds a session for a peer the client has no channel with
+    LeakedSession {
+        party: Party,
+        /// Action after which the leak was seen; `None` after convergence
+        after: Option<FuzzAction>,
+    },
+    /// The channel is established with no transport session behind it
+    MissingSession { party: Party },
+    /// The channel is stuck between handshake and establishment
+    NotConverged { party: Party, state: ChannelState },
+    /// Handshakes outlived their timeout
+    PendingHandshakes { party: Party, pending: usize },
+}
+
+/// Outcome of a fuzzing run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzReport {
+    /// Seed the action schedule was drawn from
+    pub seed: u64,
+    /// Actions taken, in order
+    pub actions: Vec<FuzzAction>,
+    /// Frames handed to a node, including convergence
+    pub deliveries: usize,
+    /// Deliveries refused with an error, as most out-of-order ones should be
+    pub refused: usize,
+    /// Handshakes that ended in an established channel, counted per node
+    pub established: usize,
+    /// Channel state of each node after convergence; `None` once closed
+    pub final_states: Vec<(Party, Option<ChannelState>)>,
+    pub violations: Vec<ConformanceViolation>,
+}
+
+impl FuzzReport {
+    /// Whether the run broke no property
+    pub fn is_conformant(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Fuzzing run settings
+#[derive(Debug, Clone)]
+pub struct FuzzConfig {
+    /// Seed of the action schedule
+    pub seed: u64,
+    /// Random actions before convergence
+    pub steps: usize,
+    /// Handshake settings of both nodes
+    pub handshake: PrivateHandshakeConfig,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            steps: 64,
+            // Short enough to wait out when converging
+            handshake: PrivateHandshakeConfig {
+                pending_timeout_ms: 500,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Two nodes exchanging private handshakes under a hostile schedule
+pub struct HandshakeFuzzer {
+    config: FuzzConfig,
+    rng: ChaCha8Rng,
+    nodes: [StreamlinedSecureClient; 2],
+    ids: [String; 2],
+    in_flight: Vec<Flight>,
+    delivered: Vec<Flight>,
+    report: FuzzReport,
+}
+
+impl HandshakeFuzzer {
+    /// Two fresh nodes with private handshakes enabled
+    pub async fn new(config: FuzzConfig) -> Result<Self> {
+        config.handshake.validate()?;
+        let client_config = StreamlinedConfig {
+            private_handshake: Some(config.handshake.clone()),
+            ..Default::default()
+        };
+        let alice = StreamlinedSecureClient::with_config(client_config.clone()).await?;
+        let bob = StreamlinedSecureClient::with_config(client_config).await?;
+        let ids = [
+            alice.get_client_id().to_string(),
+            bob.get_client_id().to_string(),
+        ];
+        Ok(Self {
+            rng: ChaCha8Rng::seed_from_u64(config.seed),
+            report: FuzzReport {
+                seed: config.seed,
+                actions: Vec::new(),
+                deliveries: 0,
+                refused: 0,
+                established: 0,
+                final_states: Vec::new(),
+                violations: Vec::new(),
+            },
+            config,
+            nodes: [alice, bob],
+            ids,
+            in_flight: Vec::new(),
+            delivered: Vec::new(),
+        })
+    }
+
+    /// Node under test
+    pub fn node(&self, party: Party) -> &StreamlinedSecureClient {
+        &self.nodes[party.index()]
+    }
+
+    /// Frames sent and not yet delivered or dropped
+    pub fn in_flight(&self) -> &[Flight] {
+        &self.in_flight
+    }
+
+    /// Take `steps` seeded random actions, then converge and check
+    ///
+    /// A panic ends the schedule early.
+    pub async fn run(&mut self) -> FuzzReport {
+        for _ in 0..self.config.steps {
+            let action = self.choose();
+            // Indices are drawn from the current queues, so always valid
+            let _ = self.step(action).await;
+            if matches!(
+                self.report.violations.last(),
+                Some(ConformanceViolation::Panic { .. })
+            ) {
+                break;
+            }
+        }
+        self.finish().await
+    }
+
+    /// Apply one action and check no session leaked
+    ///
+    /// Fails with `SecureCommsError::Validation` if an index is out of range.
+    pub async fn step(&mut self, action: FuzzAction) -> Result<()> {
+        let checked = |index: usize, len: usize| {
+            if index < len {
+                Ok(index)
+            } else {
+                Err(SecureCommsError::Validation(format!(
+                    "No frame {} among {}",
+                    index, len
+                )))
+            }
+        };
+        match action {
+            FuzzAction::Start(party) => {
+                let started = self.nodes[party.index()]
+                    .start_private_handshake()
+                    .and_then(private_frame);
+                match started {
+                    Ok(frame) => self.in_flight.push(Flight {
+                        to: party.peer(),
+                        stage: Stage::Init,
+                        frame,
+                    }),
+                    Err(_) => self.report.refused += 1,
+                }
+            }
+            FuzzAction::Deliver(index) => {
+                let flight = self.in_flight.remove(checked(index, self.in_flight.len())?);
+                self.deliver(action, flight.clone(), flight.stage).await;
+                self.delivered.push(flight);
+            }
+            FuzzAction::Duplicate(index) => {
+                let flight = self.in_flight[checked(index, self.in_flight.len())?].clone();
+                self.deliver(action, flight.clone(), flight.stage).await;
+                self.delivered.push(flight);
+            }
+            FuzzAction::Replay(index) => {
+                let flight = self.delivered[checked(index, self.delivered.len())?].clone();
+                let stage = flight.stage;
+                self.deliver(action, flight, stage).await;
+            }
+            FuzzAction::Misroute(index) => {
+                let flight = self.in_flight.remove(checked(index, self.in_flight.len())?);
+                let stage = flight.stage.next();
+                self.deliver(action, flight, stage).await;
+            }
+            FuzzAction::Drop(index) => {
+                self.in_flight.remove(checked(index, self.in_flight.len())?);
+            }
+            FuzzAction::Close(party) => {
+                let peer_id = self.ids[party.peer().index()].clone();
+                // Closing a channel that is not open is refused, and harmless
+                let _ = self.nodes[party.index()].close_secure_channel(&peer_id);
+            }
+        }
+        self.report.actions.push(action);
+        self.check_sessions(Some(action)).await;
+        Ok(())
+    }
+
+    /// Deliver what is still in flight, expire the rest and check convergence
+    pub async fn finish(&mut self) -> FuzzReport {
+        // Each delivery answers with at most the next stage, so this ends
+        while !self.in_flight.is_empty() {
+            let flight = self.in_flight.remove(0);
+            let stage = flight.stage;
+            self.deliver(FuzzAction::Deliver(0), flight, stage).await;
+        }
+        if self
+            .nodes
+            .iter()
+            .any(|node| node.pending_private_handshakes() > 0)
+        {
+            tokio::time::sleep(Duration::from_millis(
+                self.config.handshake.pending_timeout_ms + 10,
+            ))
+            .await;
+        }
+
+        self.report.final_states.clear();
+        for party in Party::BOTH {
+            let peer_id = self.ids[party.peer().index()].clone();
+            let node = &mut self.nodes[party.index()];
+            node.expire_private_handshakes();
+            let pending = node.pending_private_handshakes();
+            let state = node.channel_state(&peer_id);
+            let session = node.has_network_session(&peer_id).await;
+            if pending > 0 {
+                self.report
+                    .violations
+                    .push(ConformanceViolation::PendingHandshakes { party, pending });
+            }
+            match state {
+                None if session => {
+                    self.report
+                        .violations
+                        .push(ConformanceViolation::LeakedSession { party, after: None });
+                }
+                Some(ChannelState::Established) if !session => {
+                    self.report
+                        .violations
+                        .push(ConformanceViolation::MissingSession { party });
+                }
+                Some(state) if state != ChannelState::Established => {
+                    self.report
+                        .violations
+                        .push(ConformanceViolation::NotConverged { party, state });
+                }
+                _ => {}
+            }
+            self.report.final_states.push((party, state));
+        }
+        self.report.clone()
+    }
+
+    /// Random action valid for the current queues
+    fn choose(&mut self) -> FuzzAction {
+        let party = if self.rng.gen_bool(0.5) {
+            Party::Alice
+        } else {
+            Party::Bob
+        };
+        let queued = self.in_flight.len();
+        let roll = self.rng.gen_range(0..10);
+        match roll {
+            0 | 1 => FuzzAction::Start(party),
+            2 if !self.delivered.is_empty() => {
+                FuzzAction::Replay(self.rng.gen_range(0..self.delivered.len()))
+            }
+            3 => FuzzAction::Close(party),
+            _ if queued == 0 => FuzzAction::Start(party),
+            4 => FuzzAction::Duplicate(self.rng.gen_range(0..queued)),
+            5 => FuzzAction::Misroute(self.rng.gen_range(0..queued)),
+            6 => FuzzAction::Drop(self.rng.gen_range(0..queued)),
+            _ => FuzzAction::Deliver(self.rng.gen_range(0..queued)),
+        }
+    }
+
+    /// Hand a frame to its node's handler for `stage`, catching panics
+    async fn deliver(&mut self, action: FuzzAction, flight: Flight, stage: Stage) {
+        self.report.deliveries += 1;
+        let from = flight.to.peer();
+        let node = &mut self.nodes[flight.to.index()];
+        let frame = flight.frame;
+        let outcome = AssertUnwindSafe(async move {
+            match stage {
+                Stage::Init => node
+                    .answer_private_handshake(&frame)
+                    .and_then(private_frame)
+                    .map(|frame| (false, Some((Stage::Response, frame)))),
+                Stage::Response => node
+                    .finish_private_handshake(&frame)
+                    .await
+                    .and_then(|(_, finish)| private_frame(finish))
+                    .map(|frame| (true, Some((Stage::Finish, frame)))),
+                Stage::Finish => node
+                    .complete_private_handshake(&frame)
+                    .await
+                    .map(|_| (true, None)),
+            }
+        })
+        .catch_unwind()
+        .await;
+
+        match outcome {
+            Ok(Ok((established, next))) => {
+                if established {
+                    self.report.established += 1;
+                }
+                self.in_flight.extend(next.map(|(stage, frame)| Flight {
+                    to: from,
+                    stage,
+                    frame,
+                }));
+            }
+            Ok(Err(_)) => self.report.refused += 1,
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                println!(
+                    "🚨 Handshake delivery panicked on {:?}: {}",
+                    action, message
+                );
+                self.report
+                    .violations
+                    .push(ConformanceViolation::Panic { action, message });
+            }
+        }
+    }
+
+    /// Record transport sessions held without a channel
+    async fn check_sessions(&mut self, after: Option<FuzzAction>) {
+        for party in Party::BOTH {
+            let peer_id = &self.ids[party.peer().index()];
+            let node = &self.nodes[party.index()];
+            if node.channel_state(peer_id).is_none() && node.has_network_session(peer_id).await {
+                self.report
+                    .violations
+                    .push(ConformanceViolation::LeakedSession { party, after });
+            }
+        }
+    }
+}
+
+fn private_frame(message: NetworkMessage) -> Result<PrivateFrame> {
+    match message {
+        NetworkMessage::PrivateHandshake(frame) => Ok(frame),
+        other => Err(SecureCommsError::Validation(format!(
+            "Expected a private handshake frame, got {:?}",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_random_schedules_are_conformant() {
+        let mut established = 0;
+        let mut refused = 0;
+        for seed in 0..4 {
+            let config = FuzzConfig {
+                seed,
+                steps: 40,
+                ..Default::default()
+            };
+            let mut fuzzer = HandshakeFuzzer::new(config).await.unwrap();
+            let report = fuzzer.run().await;
+            assert!(
+                report.is_conformant(),
+                "seed {}: {:?} after {:?}",
+                seed,
+                report.violations,
+                report.actions
+            );
+            assert_eq!(report.actions.len(), 40);
+            assert!(fuzzer.in_flight().is_empty());
+            established += report.established;
+            refused += report.refused;
+        }
+        // The schedules both completed handshakes and refused stale frames
+        assert!(established > 0);
+        assert!(refused > 0);
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_and_stale_frames_converge() {
+        let mut fuzzer = HandshakeFuzzer::new(FuzzConfig::default()).await.unwrap();
+        for action in [
+            FuzzAction::Start(Party::Alice),
+            // Bob answers the init, then its retransmission
+            FuzzAction::Duplicate(0),
+            FuzzAction::Deliver(0),
+            // Alice finishes from the first response; the second is stale
+            FuzzAction::Deliver(0),
+            FuzzAction::Deliver(1),
+            FuzzAction::Close(Party::Bob),
+            // A stale init after the close leaves Bob a handshake to expire
+            FuzzAction::Replay(0),
+        ] {
+            fuzzer.step(action).await.unwrap();
+        }
+        assert_eq!(fuzzer.node(Party::Bob).pending_private_handshakes(), 1);
+        assert!(fuzzer.step(FuzzAction::Deliver(9)).await.is_err());
+
+        let report = fuzzer.finish().await;
+        assert!(report.is_conformant(), "{:?}", report.violations);
+        assert_eq!(report.established, 2);
+        assert_eq!(
+            report.final_states,
+            vec![
+                (Party::Alice, Some(ChannelState::Established)),
+                (Party::Bob, None),
+            ]
+        );
+        assert_eq!(report.actions.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_replays_from_seed() {
+        let config = FuzzConfig {
+            seed: 11,
+            steps: 24,
+            ..Default::default()
+        };
+        let mut first = HandshakeFuzzer::new(config.clone()).await.unwrap();
+        let mut second = HandshakeFuzzer::new(config).await.unwrap();
+        let (first, second) = (first.run().await, second.run().await);
+        assert_eq!(first.actions, second.actions);
+        assert!(first
+            .actions
+            .iter()
+            .any(|action| matches!(action, FuzzAction::Start(_))));
+        assert_eq!(first.seed, 11);
+    }
+}
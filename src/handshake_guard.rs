@@ -0,0 +1,545 @@
+//! # Handshake Guard - Denial-of-Service Protection for Channel Establishment
+//!
+//! Channel establishment runs PQC key generation and encapsulation before the
+//! peer is authenticated, so unauthenticated handshakes are screened before
+//! any of that work starts. The guard keeps no per-handshake state until a
+//! source has proven it can receive traffic at its address.
+//!
+//! ## Defenses
+//!
+//! - **Rate Limits**: Token bucket per source address; attempts beyond the
+//!   burst are refused before any cryptography
+//! - **Retry Tokens**: Stateless cookies, a SHA-3 MAC over the source address
+//!   and issue time keyed with a rotating secret, echoed back by the client
+//! - **Client Puzzles**: Once in-flight handshakes exceed the load threshold,
+//!   tokens carry a hash puzzle whose difficulty grows with load; the client
+//!   pays the CPU before the responder does
+//!
+//! ## Handshake Flow
+//!
+//! 1. **HandshakeInit** without proof: answered with **HandshakeRetry** carrying a token
+//! 2. **HandshakeInit** with the token (and puzzle solution): verified in constant time
+//! 3. **Admitted**: The returned permit counts toward load until dropped
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::handshake_guard::{
+//!     HandshakeAdmission, HandshakeGuard, HandshakeGuardConfig,
+//! };
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut guard = HandshakeGuard::new(HandshakeGuardConfig::default(), [7u8; 32]);
+//! let token = match guard.screen("203.0.113.5:4000", None)? {
+//!     HandshakeAdmission::Retry(token) => token,
+//!     HandshakeAdmission::Admitted(_) => unreachable!("retry tokens are required"),
+//! };
+//!
+//! // The client solves the puzzle, if any, and repeats its handshake
+//! let proof = token.solve();
+//! if let HandshakeAdmission::Admitted(permit) = guard.screen("203.0.113.5:4000", Some(&proof))? {
+//!     // Run the PQC handshake while holding the permit
+//!     drop(permit);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::constant_time::ct_eq;
+use crate::{Result, SecureCommsError};
+
+/// Hardest puzzle ever demanded, in leading zero bits
+pub const MAX_PUZZLE_DIFFICULTY_BITS: u8 = 28;
+
+/// Handshake screening settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandshakeGuardConfig {
+    /// Handshake attempts a source may make back to back
+    pub handshake_burst: u32,
+    /// Sustained handshake attempts per second and source
+    pub handshakes_per_second: f64,
+    /// Demand a retry token before any handshake, not only under load
+    pub require_retry_token: bool,
+    /// Seconds a retry token stays valid
+    pub token_lifetime_seconds: u64,
+    /// In-flight handshakes from which client puzzles are demanded
+    pub puzzle_load_threshold: usize,
+    /// Puzzle difficulty at the load threshold, in leading zero bits
+    ///
+    /// One bit is added each time the load doubles beyond the threshold.
+    pub puzzle_difficulty_bits: u8,
+    /// Sources whose rate state is tracked before idle ones are dropped
+    pub max_tracked_sources: usize,
+}
+
+impl Default for HandshakeGuardConfig {
+    fn default() -> Self {
+        Self {
+            handshake_burst: 8,
+            handshakes_per_second: 2.0,
+            require_retry_token: true,
+            token_lifetime_seconds: 30,
+            puzzle_load_threshold: 32,
+            puzzle_difficulty_bits: 16,
+            max_tracked_sources: 10_000,
+        }
+    }
+}
+
+/// Stateless cookie sent in `HandshakeRetry`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryToken {
+    /// Unix time the token was issued, in seconds
+    pub issued_at: u64,
+    /// Leading zero bits the puzzle solution must produce; 0 for none
+    pub difficulty_bits: u8,
+    /// Random salt making every token unique
+    pub nonce: Vec<u8>,
+    /// SHA-3 MAC binding the fields above to the source address
+    pub mac: Vec<u8>,
+}
+
+impl RetryToken {
+    /// Answer the token, solving its puzzle if it carries one
+    pub fn solve(&self) -> HandshakeProof {
+        let solution = if self.difficulty_bits == 0 {
+            0
+        } else {
+            (0u64..)
+                .find(|candidate| puzzle_solved(self, *candidate))
+                .unwrap_or(0)
+        };
+        HandshakeProof {
+            token: self.clone(),
+            solution,
+        }
+    }
+}
+
+/// Retry token echoed in `HandshakeInit` with the puzzle solution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandshakeProof {
+    /// Token issued by the responder
+    pub token: RetryToken,
+    /// Puzzle solution; ignored for tokens without a puzzle
+    pub solution: u64,
+}
+
+/// Outcome of screening a handshake attempt
+#[derive(Debug)]
+pub enum HandshakeAdmission {
+    /// Proceed with the handshake while holding the permit
+    Admitted(HandshakePermit),
+    /// Answer with `HandshakeRetry` carrying this token and drop the attempt
+    Retry(RetryToken),
+}
+
+/// Admitted handshake, counted as in flight until dropped
+#[derive(Debug)]
+pub struct HandshakePermit {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for HandshakePermit {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Token bucket of one source address
+#[derive(Debug)]
+struct SourceBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Screening counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HandshakeGuardStats {
+    /// Handshakes allowed to proceed
+    pub admitted: u64,
+    /// Retry tokens issued
+    pub retries_issued: u64,
+    /// Attempts refused by the per-source rate limit
+    pub rate_limited: u64,
+    /// Forged, expired or unsolved tokens
+    pub invalid_proofs: u64,
+}
+
+/// Responder-side screening of unauthenticated handshakes
+#[derive(Debug)]
+pub struct HandshakeGuard {
+    config: HandshakeGuardConfig,
+    secret: [u8; 32],
+    previous_secret: Option<[u8; 32]>,
+    secret_rotated_at: Instant,
+    buckets: HashMap<String, SourceBucket>,
+    in_flight: Arc<AtomicUsize>,
+    stats: HandshakeGuardStats,
+}
+
+impl HandshakeGuard {
+    /// Create a guard keyed with `secret`, which must be random
+    pub fn new(config: HandshakeGuardConfig, secret: [u8; 32]) -> Self {
+        Self {
+            config,
+            secret,
+            previous_secret: None,
+            secret_rotated_at: Instant::now(),
+            buckets: HashMap::new(),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            stats: HandshakeGuardStats::default(),
+        }
+    }
+
+    /// Screening settings
+    pub fn config(&self) -> &HandshakeGuardConfig {
+        &self.config
+    }
+
+    /// Replace the screening settings; rate state and secrets are kept
+    pub fn set_config(&mut self, config: HandshakeGuardConfig) {
+        self.config = config;
+    }
+
+    /// Screen a handshake attempt from `source` before any PQC work
+    ///
+    /// Fails with `ResourceExhausted` when the source exceeds its rate,
+    /// `Timeout` for an expired token and `AuthenticationFailed` for a forged
+    /// token or wrong puzzle solution.
+    pub fn screen(
+        &mut self,
+        source: &str,
+        proof: Option<&HandshakeProof>,
+    ) -> Result<HandshakeAdmission> {
+        self.rotate_secret_if_due();
+        if !self.take_rate_token(source) {
+            self.stats.rate_limited += 1;
+            counter!("handshake_guard_rejections_total", 1, "reason" => "rate_limited");
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Handshake rate limit exceeded for {}",
+                source
+            )));
+        }
+
+        let required = self.required_difficulty();
+        let proof = match proof {
+            Some(proof) => proof,
+            None if self.config.require_retry_token || required > 0 => {
+                return Ok(HandshakeAdmission::Retry(
+                    self.issue_token(source, required),
+                ));
+            }
+            None => return Ok(HandshakeAdmission::Admitted(self.admit())),
+        };
+
+        if let Err(e) = self.verify_proof(source, proof) {
+            self.stats.invalid_proofs += 1;
+            counter!("handshake_guard_rejections_total", 1, "reason" => "invalid_proof");
+            return Err(e);
+        }
+        // Load rose since the token was issued, so a harder puzzle is due
+        if proof.token.difficulty_bits < required {
+            return Ok(HandshakeAdmission::Retry(
+                self.issue_token(source, required),
+            ));
+        }
+        Ok(HandshakeAdmission::Admitted(self.admit()))
+    }
+
+    /// Puzzle difficulty demanded at the current load
+    pub fn required_difficulty(&self) -> u8 {
+        let threshold = self.config.puzzle_load_threshold.max(1);
+        let in_flight = self.in_flight();
+        if in_flight < threshold {
+            return 0;
+        }
+        let doublings = (in_flight / threshold).ilog2() as u8;
+        self.config
+            .puzzle_difficulty_bits
+            .saturating_add(doublings)
+            .min(MAX_PUZZLE_DIFFICULTY_BITS)
+    }
+
+    /// Handshakes admitted and not yet finished
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Screening counters
+    pub fn stats(&self) -> &HandshakeGuardStats {
+        &self.stats
+    }
+
+    /// Get handshake guard statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "admitted".to_string(),
+            serde_json::json!(self.stats.admitted),
+        );
+        stats.insert(
+            "retries_issued".to_string(),
+            serde_json::json!(self.stats.retries_issued),
+        );
+        stats.insert(
+            "rate_limited".to_string(),
+            serde_json::json!(self.stats.rate_limited),
+        );
+        stats.insert(
+            "invalid_proofs".to_string(),
+            serde_json::json!(self.stats.invalid_proofs),
+        );
+        stats.insert("in_flight".to_string(), serde_json::json!(self.in_flight()));
+        stats.insert(
+            "required_difficulty".to_string(),
+            serde_json::json!(self.required_difficulty()),
+        );
+        stats.insert(
+            "tracked_sources".to_string(),
+            serde_json::json!(self.buckets.len()),
+        );
+        stats
+    }
+
+    fn admit(&mut self) -> HandshakePermit {
+        self.stats.admitted += 1;
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        HandshakePermit {
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    fn issue_token(&mut self, source: &str, difficulty_bits: u8) -> RetryToken {
+        use rand::RngCore;
+        let mut nonce = vec![0u8; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let issued_at = chrono::Utc::now().timestamp() as u64;
+        let mac = token_mac(&self.secret, source, issued_at, difficulty_bits, &nonce);
+        self.stats.retries_issued += 1;
+        RetryToken {
+            issued_at,
+            difficulty_bits,
+            nonce,
+            mac,
+        }
+    }
+
+    fn verify_proof(&self, source: &str, proof: &HandshakeProof) -> Result<()> {
+        let token = &proof.token;
+        let mac_valid = |secret: &[u8; 32]| {
+            let expected = token_mac(
+                secret,
+                source,
+                token.issued_at,
+                token.difficulty_bits,
+                &token.nonce,
+            );
+            ct_eq(&expected, &token.mac)
+        };
+        if !mac_valid(&self.secret) && !self.previous_secret.as_ref().is_some_and(mac_valid) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        if now.saturating_sub(token.issued_at) > self.config.token_lifetime_seconds {
+            return Err(SecureCommsError::Timeout(format!(
+                "Handshake retry token for {} expired",
+                source
+            )));
+        }
+        if token.difficulty_bits > 0 && !puzzle_solved(token, proof.solution) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+
+    /// Replace the secret once per token lifetime, keeping the last one
+    fn rotate_secret_if_due(&mut self) {
+        if self.secret_rotated_at.elapsed().as_secs() < self.config.token_lifetime_seconds {
+            return;
+        }
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"handshake_guard_rotate_v1");
+        hasher.update(self.secret);
+        hasher.update(rand::random::<[u8; 32]>());
+        let mut next = [0u8; 32];
+        next.copy_from_slice(&hasher.finalize());
+        self.previous_secret = Some(std::mem::replace(&mut self.secret, next));
+        self.secret_rotated_at = Instant::now();
+    }
+
+    fn take_rate_token(&mut self, source: &str) -> bool {
+        let burst = self.config.handshake_burst as f64;
+        let rate = self.config.handshakes_per_second;
+        let now = Instant::now();
+        if self.buckets.len() >= self.config.max_tracked_sources
+            && !self.buckets.contains_key(source)
+        {
+            // Buckets refilled to the burst carry no state worth keeping
+            let refill_secs = if rate > 0.0 { burst / rate } else { f64::MAX };
+            self.buckets.retain(|_, bucket| {
+                now.duration_since(bucket.refilled_at).as_secs_f64() < refill_secs
+            });
+        }
+
+        let bucket = self
+            .buckets
+            .entry(source.to_string())
+            .or_insert(SourceBucket {
+                tokens: burst,
+                refilled_at: now,
+            });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.refilled_at = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+/// SHA-3 MAC of a retry token, bound to the source address
+fn token_mac(
+    secret: &[u8; 32],
+    source: &str,
+    issued_at: u64,
+    difficulty_bits: u8,
+    nonce: &[u8],
+) -> Vec<u8> {
+    use sha3::{Digest, Sha3_256};
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"handshake_retry_token_v1");
+    hasher.update(secret);
+    hasher.update(source.as_bytes());
+    hasher.update(issued_at.to_le_bytes());
+    hasher.update([difficulty_bits]);
+    hasher.update(nonce);
+    hasher.finalize().to_vec()
+}
+
+/// Whether a candidate solution yields the token's leading zero bits
+fn puzzle_solved(token: &RetryToken, solution: u64) -> bool {
+    use sha3::{Digest, Sha3_256};
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"handshake_puzzle_v1");
+    hasher.update(&token.mac);
+    hasher.update(solution.to_le_bytes());
+    let digest = hasher.finalize();
+
+    let mut zeros = 0u32;
+    for byte in digest.iter() {
+        zeros += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    zeros >= token.difficulty_bits as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "198.51.100.7:5000";
+
+    fn retry_token(admission: HandshakeAdmission) -> RetryToken {
+        match admission {
+            HandshakeAdmission::Retry(token) => token,
+            HandshakeAdmission::Admitted(_) => panic!("expected a retry token"),
+        }
+    }
+
+    #[test]
+    fn test_retry_token_round_trip() {
+        let mut guard = HandshakeGuard::new(HandshakeGuardConfig::default(), [1u8; 32]);
+        let token = retry_token(guard.screen(SOURCE, None).unwrap());
+        assert_eq!(token.difficulty_bits, 0);
+
+        let proof = token.solve();
+        let permit = guard.screen(SOURCE, Some(&proof)).unwrap();
+        assert!(matches!(permit, HandshakeAdmission::Admitted(_)));
+        assert_eq!(guard.in_flight(), 1);
+        drop(permit);
+        assert_eq!(guard.in_flight(), 0);
+
+        // A token is bound to the address it was issued to
+        assert!(matches!(
+            guard.screen("198.51.100.8:5000", Some(&proof)),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        let mut forged = proof.clone();
+        forged.token.difficulty_bits = 1;
+        assert!(guard.screen(SOURCE, Some(&forged)).is_err());
+        assert_eq!(guard.stats().invalid_proofs, 2);
+    }
+
+    #[test]
+    fn test_rate_limit_per_source() {
+        let mut guard = HandshakeGuard::new(
+            HandshakeGuardConfig {
+                handshake_burst: 3,
+                handshakes_per_second: 0.001,
+                ..Default::default()
+            },
+            [2u8; 32],
+        );
+        for _ in 0..3 {
+            assert!(guard.screen(SOURCE, None).is_ok());
+        }
+        assert!(matches!(
+            guard.screen(SOURCE, None),
+            Err(SecureCommsError::ResourceExhausted(_))
+        ));
+        assert!(guard.screen("198.51.100.9:5000", None).is_ok());
+        assert_eq!(guard.stats().rate_limited, 1);
+    }
+
+    #[test]
+    fn test_puzzles_demanded_under_load() {
+        let mut guard = HandshakeGuard::new(
+            HandshakeGuardConfig {
+                require_retry_token: false,
+                puzzle_load_threshold: 2,
+                puzzle_difficulty_bits: 8,
+                handshake_burst: 100,
+                ..Default::default()
+            },
+            [3u8; 32],
+        );
+        let mut permits = Vec::new();
+        for i in 0..2 {
+            match guard.screen(&format!("10.0.0.{}:1", i), None).unwrap() {
+                HandshakeAdmission::Admitted(permit) => permits.push(permit),
+                HandshakeAdmission::Retry(_) => panic!("no puzzle below the load threshold"),
+            }
+        }
+
+        let token = retry_token(guard.screen(SOURCE, None).unwrap());
+        assert_eq!(token.difficulty_bits, 8);
+        let unsolved = HandshakeProof {
+            token: token.clone(),
+            solution: (0u64..).find(|s| !puzzle_solved(&token, *s)).unwrap(),
+        };
+        assert!(guard.screen(SOURCE, Some(&unsolved)).is_err());
+        assert!(matches!(
+            guard.screen(SOURCE, Some(&token.solve())).unwrap(),
+            HandshakeAdmission::Admitted(_)
+        ));
+
+        permits.extend((0..2).map(|_| guard.admit()));
+        assert_eq!(guard.required_difficulty(), 9);
+        permits.clear();
+        assert_eq!(guard.required_difficulty(), 0);
+    }
+}
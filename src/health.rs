@@ -0,0 +1,412 @@
+//! # Health - Per-Subsystem Health Reports
+//!
+//! Breaks a client health check down by subsystem so readiness probes and
+//! monitoring can see which part of the node is failing, how long each check
+//! took, and the last error it produced, instead of a single pass/fail bit.
+//!
+//! ## Subsystems
+//!
+//! - **entropy**: Security foundation self-test of the entropy sources
+//! - **crypto**: AEAD seal/open round trip through the active provider
+//! - **quantum**: Quantum core fidelity after refreshing decohered channels
+//! - **network**: Every open channel, reported per peer
+//! - **consensus**: Verification round trip through the consensus engine
+//! - **storage**: Delivery log, cluster store and address book persistence
+//!
+//! ## Degraded Startup
+//!
+//! Optional startup stages that failed (see `startup`) are listed in
+//! `degraded_startup` and hold the overall status at `Degraded` or worse
+//! for as long as the client runs.
+//!
+//! ## Background Tasks
+//!
+//! Supervised background tasks (see `tasks`) that returned an error or
+//! panicked are listed in `failed_tasks` and also hold the overall status
+//! at `Degraded` or worse.
+//!
+//! ## Readiness
+//!
+//! A node is ready while no subsystem is `Critical` or `Down`; a single
+//! failing peer degrades the network subsystem but does not make the node
+//! unready. `http_status` maps readiness to the status code a readiness
+//! endpoint should answer with.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! let report = client.health_report().await;
+//! for subsystem in &report.subsystems {
+//!     println!(
+//!         "{:<10} {} ({:.1}ms) {:?}",
+//!         subsystem.subsystem.name(),
+//!         subsystem.status,
+//!         subsystem.check_ms,
+//!         subsystem.last_error
+//!     );
+//! }
+//! let body = serde_json::to_string(&report).unwrap();
+//! println!("HTTP {} {}", report.http_status(), body);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::channel_state::ChannelState;
+use crate::production_monitor::HealthStatus;
+use crate::startup::{StageOutcome, StartupReport};
+use crate::tasks::TaskRecord;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Subsystem covered by a health report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    /// Entropy sources of the security foundation
+    Entropy,
+    /// Cryptographic providers
+    Crypto,
+    /// Quantum core
+    Quantum,
+    /// Network channels
+    Network,
+    /// Consensus engine
+    Consensus,
+    /// Persistent storage
+    Storage,
+}
+
+impl Subsystem {
+    /// Every subsystem, in report order
+    pub const ALL: [Subsystem; 6] = [
+        Subsystem::Entropy,
+        Subsystem::Crypto,
+        Subsystem::Quantum,
+        Subsystem::Network,
+        Subsystem::Consensus,
+        Subsystem::Storage,
+    ];
+
+    /// Name used in reports, alerts and metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subsystem::Entropy => "entropy",
+            Subsystem::Crypto => "crypto",
+            Subsystem::Quantum => "quantum",
+            Subsystem::Network => "network",
+            Subsystem::Consensus => "consensus",
+            Subsystem::Storage => "storage",
+        }
+    }
+}
+
+/// Ordering of statuses from best to worst
+fn severity(status: HealthStatus) -> u8 {
+    match status {
+        HealthStatus::Healthy => 0,
+        HealthStatus::Warning => 1,
+        HealthStatus::Degraded => 2,
+        HealthStatus::Critical => 3,
+        HealthStatus::Down => 4,
+    }
+}
+
+/// Worse of two statuses
+pub fn worst(a: HealthStatus, b: HealthStatus) -> HealthStatus {
+    if severity(b) > severity(a) {
+        b
+    } else {
+        a
+    }
+}
+
+/// Whether a status still serves traffic
+fn is_serving(status: HealthStatus) -> bool {
+    severity(status) < severity(HealthStatus::Critical)
+}
+
+/// Outcome of one subsystem check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubsystemHealth {
+    /// Checked subsystem
+    pub subsystem: Subsystem,
+    /// Status found by the check
+    pub status: HealthStatus,
+    /// Duration of the check in milliseconds
+    pub check_ms: f64,
+    /// Human-readable summary of what was checked
+    pub detail: String,
+    /// Error of this check, or the most recent one from an earlier check
+    pub last_error: Option<String>,
+}
+
+/// Health of the channel to one peer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerHealth {
+    /// Remote peer
+    pub peer_id: String,
+    /// Status of the channel
+    pub status: HealthStatus,
+    /// Duration of the check in milliseconds
+    pub check_ms: f64,
+    /// Lifecycle state of the channel
+    pub channel_state: Option<ChannelState>,
+    /// Error of this check, or the most recent one from an earlier check
+    pub last_error: Option<String>,
+}
+
+/// Health of every subsystem at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Unix time of the check in milliseconds
+    pub checked_at: u64,
+    /// Worst status across subsystems
+    pub status: HealthStatus,
+    /// One entry per subsystem, in `Subsystem::ALL` order
+    pub subsystems: Vec<SubsystemHealth>,
+    /// One entry per open channel, ordered by peer ID
+    pub peers: Vec<PeerHealth>,
+    /// Startup stages that were degraded or skipped
+    #[serde(default)]
+    pub degraded_startup: Vec<StageOutcome>,
+    /// Background tasks that failed or panicked
+    #[serde(default)]
+    pub failed_tasks: Vec<TaskRecord>,
+}
+
+impl HealthReport {
+    /// Assemble a report, deriving the overall status from the subsystems
+    pub fn new(subsystems: Vec<SubsystemHealth>, mut peers: Vec<PeerHealth>) -> Self {
+        peers.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+        let status = subsystems
+            .iter()
+            .fold(HealthStatus::Healthy, |status, entry| {
+                worst(status, entry.status)
+            });
+        Self {
+            checked_at: crate::expiry::now_ms(),
+            status,
+            subsystems,
+            peers,
+            degraded_startup: Vec::new(),
+            failed_tasks: Vec::new(),
+        }
+    }
+
+    /// Include the stages a degraded startup left out
+    pub fn with_startup(mut self, startup: &StartupReport) -> Self {
+        self.degraded_startup = startup.degraded().into_iter().cloned().collect();
+        if !self.degraded_startup.is_empty() {
+            self.status = worst(self.status, HealthStatus::Degraded);
+        }
+        self
+    }
+
+    /// Include background tasks that failed or panicked
+    pub fn with_failed_tasks(mut self, failed: Vec<TaskRecord>) -> Self {
+        if !failed.is_empty() {
+            self.status = worst(self.status, HealthStatus::Degraded);
+        }
+        self.failed_tasks = failed;
+        self
+    }
+
+    /// Entry of one subsystem
+    pub fn subsystem(&self, subsystem: Subsystem) -> Option<&SubsystemHealth> {
+        self.subsystems
+            .iter()
+            .find(|entry| entry.subsystem == subsystem)
+    }
+
+    /// Entry of one peer
+    pub fn peer(&self, peer_id: &str) -> Option<&PeerHealth> {
+        self.peers.iter().find(|peer| peer.peer_id == peer_id)
+    }
+
+    /// Whether every subsystem is healthy or only warning
+    pub fn is_healthy(&self) -> bool {
+        severity(self.status) <= severity(HealthStatus::Warning)
+    }
+
+    /// Whether the node should receive traffic
+    pub fn is_ready(&self) -> bool {
+        is_serving(self.status)
+    }
+
+    /// Status code for a readiness endpoint: 200 when ready, 503 otherwise
+    pub fn http_status(&self) -> u16 {
+        if self.is_ready() {
+            200
+        } else {
+            503
+        }
+    }
+
+    /// Subsystems that are not healthy
+    pub fn failing(&self) -> Vec<&SubsystemHealth> {
+        self.subsystems
+            .iter()
+            .filter(|entry| entry.status != HealthStatus::Healthy)
+            .collect()
+    }
+}
+
+/// Last error seen per subsystem or peer across checks
+#[derive(Debug, Clone, Default)]
+pub struct HealthHistory {
+    last_errors: HashMap<String, String>,
+}
+
+impl HealthHistory {
+    /// Record the error of a check, returning the error to report
+    ///
+    /// A passing check reports the most recent earlier error so operators
+    /// can see what a recovered subsystem last failed with.
+    pub fn observe(&mut self, key: &str, error: Option<String>) -> Option<String> {
+        match error {
+            Some(error) => {
+                self.last_errors.insert(key.to_string(), error.clone());
+                Some(error)
+            }
+            None => self.last_errors.get(key).cloned(),
+        }
+    }
+
+    /// Forget the errors of a peer whose channel closed
+    pub fn forget(&mut self, key: &str) {
+        self.last_errors.remove(key);
+    }
+}
+
+/// Milliseconds of a check duration, as reported
+pub fn check_ms(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::startup::StartupStage;
+
+    fn entry(subsystem: Subsystem, status: HealthStatus) -> SubsystemHealth {
+        SubsystemHealth {
+            subsystem,
+            status,
+            check_ms: 1.0,
+            detail: String::new(),
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_overall_status_is_worst_subsystem() {
+        let mut subsystems: Vec<SubsystemHealth> = Subsystem::ALL
+            .iter()
+            .map(|subsystem| entry(*subsystem, HealthStatus::Healthy))
+            .collect();
+        let report = HealthReport::new(subsystems.clone(), Vec::new());
+        assert!(report.is_healthy());
+        assert_eq!(report.http_status(), 200);
+
+        subsystems[3].status = HealthStatus::Degraded;
+        let report = HealthReport::new(subsystems.clone(), Vec::new());
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(!report.is_healthy());
+        assert!(report.is_ready());
+        assert_eq!(report.failing().len(), 1);
+
+        // A degraded startup keeps the node degraded but ready
+        let mut startup = StartupReport::default();
+        startup
+            .skip(
+                StartupStage::MetricsExporter,
+                "address in use".to_string(),
+                std::time::Duration::ZERO,
+            )
+            .unwrap();
+        subsystems[3].status = HealthStatus::Healthy;
+        let report = HealthReport::new(subsystems.clone(), Vec::new()).with_startup(&startup);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(report.degraded_startup.len(), 1);
+        assert!(report.is_ready());
+
+        // So does a failed background task
+        let failed = TaskRecord {
+            name: "entanglement_refill".to_string(),
+            status: crate::tasks::TaskStatus::Failed {
+                error: "refill failed".to_string(),
+            },
+            started_at: 1,
+            ended_at: Some(2),
+        };
+        let report =
+            HealthReport::new(subsystems.clone(), Vec::new()).with_failed_tasks(vec![failed]);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(report.failed_tasks[0].name, "entanglement_refill");
+
+        subsystems[0].status = HealthStatus::Down;
+        let report = HealthReport::new(subsystems, Vec::new());
+        assert_eq!(report.status, HealthStatus::Down);
+        assert_eq!(report.http_status(), 503);
+        assert_eq!(
+            report.subsystem(Subsystem::Entropy).unwrap().status,
+            HealthStatus::Down
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_last_error() {
+        let mut history = HealthHistory::default();
+        assert_eq!(history.observe("storage", None), None);
+        assert_eq!(
+            history.observe("storage", Some("disk full".to_string())),
+            Some("disk full".to_string())
+        );
+        // Recovered, but the last error is still reported
+        assert_eq!(
+            history.observe("storage", None),
+            Some("disk full".to_string())
+        );
+        history.forget("storage");
+        assert_eq!(history.observe("storage", None), None);
+    }
+
+    #[test]
+    fn test_report_serializes_for_readiness_endpoint() {
+        let peers = vec![
+            PeerHealth {
+                peer_id: "peer_b".to_string(),
+                status: HealthStatus::Critical,
+                check_ms: 0.5,
+                channel_state: Some(ChannelState::Established),
+                last_error: Some("network channel missing".to_string()),
+            },
+            PeerHealth {
+                peer_id: "peer_a".to_string(),
+                status: HealthStatus::Healthy,
+                check_ms: 0.2,
+                channel_state: Some(ChannelState::Rekeying),
+                last_error: None,
+            },
+        ];
+        let report = HealthReport::new(
+            vec![entry(Subsystem::Network, HealthStatus::Degraded)],
+            peers,
+        );
+        assert_eq!(report.peers[0].peer_id, "peer_a");
+        assert_eq!(
+            report.peer("peer_b").unwrap().status,
+            HealthStatus::Critical
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["subsystems"][0]["subsystem"], "network");
+        let parsed: HealthReport = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
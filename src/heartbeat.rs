@@ -0,0 +1,302 @@
+//! # Heartbeat - Adaptive Keepalive Intervals per Channel
+//!
+//! A fixed keepalive interval is a poor fit for every link at once: on a
+//! stable channel it spends bandwidth confirming what is already known, and
+//! on a flaky one it notices trouble late. Each channel here gets its own
+//! interval, which grows while heartbeats come back cleanly and shrinks as
+//! soon as one is lost or the round trip time starts to jitter.
+//!
+//! ## Adaptation
+//!
+//! - **Stable**: After `stable_beats` clean heartbeats in a row the interval
+//!   is multiplied by `growth_factor`
+//! - **Jitter**: A heartbeat whose smoothed jitter exceeds
+//!   `jitter_threshold_ms` multiplies the interval by `backoff_factor`
+//! - **Loss**: A lost heartbeat multiplies the interval by `backoff_factor`
+//!
+//! Intervals always stay within `min_interval_ms..=max_interval_ms`. Jitter
+//! is smoothed as in RFC 3550: each sample moves the estimate 1/16 of the way
+//! towards the difference between consecutive round trip times.
+//!
+//! ## Visibility
+//!
+//! [`HeartbeatStats`] is part of `ChannelStats`, so the current interval,
+//! round trip time, jitter and loss counts show up with the rest of a
+//! peer's statistics.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::heartbeat::{HeartbeatConfig, HeartbeatOutcome, HeartbeatStats};
+//!
+//! let config = HeartbeatConfig::default();
+//! let mut stats = HeartbeatStats::default();
+//! let mut now_ms = 0;
+//! for _ in 0..config.stable_beats {
+//!     stats.record(&config, HeartbeatOutcome::Acknowledged { rtt_ms: 12.0 }, now_ms);
+//!     now_ms += stats.interval_ms;
+//! }
+//! assert!(stats.interval_ms > config.initial_interval_ms);
+//! stats.record(&config, HeartbeatOutcome::Lost, now_ms);
+//! println!("next heartbeat in {}ms", stats.interval_ms);
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+
+/// Adaptive heartbeat bounds and tuning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    /// Shortest interval between heartbeats to a peer
+    pub min_interval_ms: u64,
+    /// Longest interval between heartbeats to a peer
+    pub max_interval_ms: u64,
+    /// Interval of a channel's first heartbeats
+    pub initial_interval_ms: u64,
+    /// Clean heartbeats in a row before the interval grows
+    pub stable_beats: u32,
+    /// Interval multiplier after a stable run (> 1.0)
+    pub growth_factor: f64,
+    /// Interval multiplier after loss or jitter (< 1.0)
+    pub backoff_factor: f64,
+    /// Smoothed jitter above which the link counts as unsettled
+    pub jitter_threshold_ms: f64,
+    /// Lost heartbeats in a row before the peer is marked unhealthy
+    pub unhealthy_after_losses: u32,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            min_interval_ms: 1_000,
+            max_interval_ms: 60_000,
+            initial_interval_ms: 10_000,
+            stable_beats: 3,
+            growth_factor: 1.5,
+            backoff_factor: 0.5,
+            jitter_threshold_ms: 25.0,
+            unhealthy_after_losses: 3,
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    /// Check the bounds are ordered and the factors move the right way
+    pub fn validate(&self) -> Result<()> {
+        if self.min_interval_ms == 0
+            || self.min_interval_ms > self.initial_interval_ms
+            || self.initial_interval_ms > self.max_interval_ms
+        {
+            return Err(SecureCommsError::Configuration(format!(
+                "Heartbeat intervals must satisfy 0 < min ({}) <= initial ({}) <= max ({})",
+                self.min_interval_ms, self.initial_interval_ms, self.max_interval_ms
+            )));
+        }
+        if !(self.growth_factor > 1.0
+            && self.growth_factor.is_finite()
+            && self.backoff_factor > 0.0
+            && self.backoff_factor < 1.0)
+        {
+            return Err(SecureCommsError::Configuration(format!(
+                "Heartbeat growth factor must exceed 1.0 and backoff factor lie in (0, 1), got {} and {}",
+                self.growth_factor, self.backoff_factor
+            )));
+        }
+        if self.stable_beats == 0 || self.unhealthy_after_losses == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Heartbeat stable run and loss limit must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn clamp(&self, interval_ms: f64) -> u64 {
+        (interval_ms.round() as u64).clamp(self.min_interval_ms, self.max_interval_ms)
+    }
+}
+
+/// Result of one heartbeat
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeartbeatOutcome {
+    /// The peer answered after `rtt_ms` milliseconds
+    Acknowledged { rtt_ms: f64 },
+    /// No answer, or the heartbeat could not be sent
+    Lost,
+}
+
+/// Heartbeat sent to one peer by `send_due_heartbeats`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatResult {
+    pub peer_id: String,
+    pub outcome: HeartbeatOutcome,
+    /// Interval until the peer's next heartbeat
+    pub interval_ms: u64,
+}
+
+/// Adaptive heartbeat state and counters for one channel
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HeartbeatStats {
+    /// Current interval; 0 until the first heartbeat
+    pub interval_ms: u64,
+    /// When the next heartbeat is due (Unix milliseconds)
+    pub next_due_ms: u64,
+    /// Round trip time of the last acknowledged heartbeat
+    pub last_rtt_ms: Option<f64>,
+    /// Smoothed difference between consecutive round trip times
+    pub jitter_ms: f64,
+    /// Heartbeats sent
+    pub sent: u64,
+    /// Heartbeats lost
+    pub lost: u64,
+    /// Clean heartbeats since the interval last changed or a loss
+    pub consecutive_ok: u32,
+    /// Lost heartbeats in a row
+    pub consecutive_lost: u32,
+}
+
+impl HeartbeatStats {
+    /// Whether a heartbeat is due at `now_ms`
+    pub fn is_due(&self, now_ms: u64) -> bool {
+        self.next_due_ms <= now_ms
+    }
+
+    /// Record a heartbeat and schedule the next one
+    ///
+    /// Returns the new interval if it changed.
+    pub fn record(
+        &mut self,
+        config: &HeartbeatConfig,
+        outcome: HeartbeatOutcome,
+        now_ms: u64,
+    ) -> Option<u64> {
+        let previous = self.interval_ms;
+        let current = if previous == 0 {
+            config.initial_interval_ms
+        } else {
+            previous
+        };
+        self.sent += 1;
+
+        let interval = match outcome {
+            HeartbeatOutcome::Acknowledged { rtt_ms } => {
+                self.consecutive_lost = 0;
+                if let Some(last_rtt_ms) = self.last_rtt_ms {
+                    self.jitter_ms += ((rtt_ms - last_rtt_ms).abs() - self.jitter_ms) / 16.0;
+                }
+                self.last_rtt_ms = Some(rtt_ms);
+                if self.jitter_ms > config.jitter_threshold_ms {
+                    self.consecutive_ok = 0;
+                    config.clamp(current as f64 * config.backoff_factor)
+                } else {
+                    self.consecutive_ok += 1;
+                    if self.consecutive_ok >= config.stable_beats {
+                        self.consecutive_ok = 0;
+                        config.clamp(current as f64 * config.growth_factor)
+                    } else {
+                        current
+                    }
+                }
+            }
+            HeartbeatOutcome::Lost => {
+                self.lost += 1;
+                self.consecutive_lost += 1;
+                self.consecutive_ok = 0;
+                config.clamp(current as f64 * config.backoff_factor)
+            }
+        };
+
+        self.interval_ms = interval;
+        self.next_due_ms = now_ms.saturating_add(interval);
+        (interval != previous).then_some(interval)
+    }
+
+    /// Whether enough heartbeats in a row were lost to call the peer down
+    pub fn is_unhealthy(&self, config: &HeartbeatConfig) -> bool {
+        self.consecutive_lost >= config.unhealthy_after_losses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ack(rtt_ms: f64) -> HeartbeatOutcome {
+        HeartbeatOutcome::Acknowledged { rtt_ms }
+    }
+
+    #[test]
+    fn test_stable_link_grows_to_max() {
+        let config = HeartbeatConfig::default();
+        let mut stats = HeartbeatStats::default();
+        assert!(stats.is_due(0));
+        assert_eq!(stats.record(&config, ack(10.0), 0), Some(10_000));
+        assert!(!stats.is_due(9_999));
+        assert!(stats.is_due(10_000));
+
+        let mut intervals = Vec::new();
+        for beat in 1..12 {
+            stats.record(&config, ack(10.0), beat * 1_000);
+            intervals.push(stats.interval_ms);
+        }
+        assert_eq!(intervals[1], 15_000);
+        assert_eq!(intervals[4], 22_500);
+        assert_eq!(*intervals.last().unwrap(), 50_625);
+        for _ in 0..6 {
+            stats.record(&config, ack(10.0), 0);
+        }
+        assert_eq!(stats.interval_ms, config.max_interval_ms);
+        assert_eq!(stats.jitter_ms, 0.0);
+    }
+
+    #[test]
+    fn test_loss_and_jitter_shorten_interval() {
+        let config = HeartbeatConfig::default();
+        let mut stats = HeartbeatStats::default();
+        stats.record(&config, ack(10.0), 0);
+        assert_eq!(
+            stats.record(&config, HeartbeatOutcome::Lost, 0),
+            Some(5_000)
+        );
+        stats.record(&config, HeartbeatOutcome::Lost, 0);
+        assert!(!stats.is_unhealthy(&config));
+        stats.record(&config, HeartbeatOutcome::Lost, 0);
+        assert!(stats.is_unhealthy(&config));
+        assert_eq!(stats.interval_ms, 1_250);
+        stats.record(&config, HeartbeatOutcome::Lost, 0);
+        assert_eq!(stats.interval_ms, config.min_interval_ms);
+        assert_eq!((stats.sent, stats.lost), (5, 4));
+
+        // Wildly varying round trips back off even without loss
+        let mut stats = HeartbeatStats::default();
+        for (beat, rtt_ms) in [10.0, 600.0, 5.0, 700.0].into_iter().enumerate() {
+            stats.record(&config, ack(rtt_ms), beat as u64);
+        }
+        assert!(stats.jitter_ms > config.jitter_threshold_ms);
+        assert!(stats.interval_ms < config.initial_interval_ms);
+        assert_eq!(stats.consecutive_lost, 0);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(HeartbeatConfig::default().validate().is_ok());
+        assert!(HeartbeatConfig {
+            initial_interval_ms: 500,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(HeartbeatConfig {
+            backoff_factor: 1.0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(HeartbeatConfig {
+            stable_beats: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
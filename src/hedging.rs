@@ -0,0 +1,293 @@
+//! # Hedging - Latency-Aware Peer Selection for Redundant Sends
+//!
+//! When several peers can serve the same request (fetching state, a block,
+//! a signature share), the slowest response of a single peer sets the tail
+//! latency. Hedging sends the request to the best peer first and, if it has
+//! not answered after a short delay, also to the next best; the first
+//! answer wins and the others are ignored.
+//!
+//! ## Selection
+//!
+//! Candidates are ranked from what the peer manager has measured:
+//! - **Excluded**: Peers below the routing reputation threshold or marked
+//!   unhealthy by the last health check
+//! - **Latency**: Peers with lower measured latency first; peers never
+//!   measured go after every measured one
+//! - **Reputation**: Ties are broken by reputation score
+//!
+//! ## Hedge Delay
+//!
+//! A peer gets `latency_multiplier` times its measured latency to answer
+//! before the next peer is asked, clamped to `min_delay_ms..=max_delay_ms`.
+//! Peers without measurements get `default_delay_ms`. A send that fails
+//! outright moves on to the next peer immediately.
+//!
+//! Hedged requests reach more than one peer, so they must be idempotent.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::hedging::{HedgeConfig, PeerCandidate};
+//!
+//! let config = HedgeConfig::default();
+//! let plan = config.plan(vec![
+//!     PeerCandidate::new("validator_1", Some(40.0), 0.9),
+//!     PeerCandidate::new("validator_2", Some(8.0), 0.7),
+//!     PeerCandidate::new("validator_3", None, 1.0),
+//! ]);
+//! assert_eq!(plan.attempts[0].peer_id, "validator_2");
+//! println!("hedge to {} after {:?}", plan.attempts[1].peer_id, plan.attempts[0].hedge_after);
+//! ```
+
+use crate::peer_reputation::ReputationManager;
+use crate::receipts::DeliveryReceipt;
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::time::Duration;
+
+/// Hedged send configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HedgeConfig {
+    /// Delay before hedging away from a peer with no latency measurement
+    pub default_delay_ms: u64,
+    /// Multiple of a peer's measured latency to wait before hedging
+    pub latency_multiplier: f64,
+    /// Shortest hedge delay
+    pub min_delay_ms: u64,
+    /// Longest hedge delay
+    pub max_delay_ms: u64,
+    /// Most peers asked in addition to the first
+    pub max_hedges: usize,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            default_delay_ms: 50,
+            latency_multiplier: 2.0,
+            min_delay_ms: 5,
+            max_delay_ms: 2_000,
+            max_hedges: 1,
+        }
+    }
+}
+
+impl HedgeConfig {
+    /// Check the delays and multiplier are usable
+    pub fn validate(&self) -> Result<()> {
+        if self.min_delay_ms == 0 || self.min_delay_ms > self.max_delay_ms {
+            return Err(SecureCommsError::Configuration(format!(
+                "Hedge delay bounds must satisfy 0 < min ({}) <= max ({})",
+                self.min_delay_ms, self.max_delay_ms
+            )));
+        }
+        if !self.latency_multiplier.is_finite() || self.latency_multiplier < 1.0 {
+            return Err(SecureCommsError::Configuration(format!(
+                "Hedge latency multiplier must be at least 1.0, got {}",
+                self.latency_multiplier
+            )));
+        }
+        Ok(())
+    }
+
+    /// How long to wait for `peer` before asking the next one
+    pub fn hedge_delay(&self, peer: &PeerCandidate) -> Duration {
+        let delay_ms = match peer.latency_ms {
+            Some(latency_ms) => (latency_ms * self.latency_multiplier).ceil() as u64,
+            None => self.default_delay_ms,
+        };
+        Duration::from_millis(delay_ms.clamp(self.min_delay_ms, self.max_delay_ms))
+    }
+
+    /// Rank `candidates` and schedule the first peer plus up to
+    /// `max_hedges` more
+    pub fn plan(&self, candidates: Vec<PeerCandidate>) -> HedgePlan {
+        let attempts = rank_candidates(candidates)
+            .into_iter()
+            .take(self.max_hedges + 1)
+            .map(|peer| PlannedAttempt {
+                hedge_after: self.hedge_delay(&peer),
+                peer_id: peer.peer_id,
+            })
+            .collect();
+        HedgePlan { attempts }
+    }
+}
+
+/// What the peer manager knows about one candidate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerCandidate {
+    pub peer_id: String,
+    /// Measured average latency in milliseconds
+    pub latency_ms: Option<f64>,
+    /// Reputation score (0.0-1.0)
+    pub score: f64,
+    /// Whether the reputation is high enough to route through the peer
+    pub routable: bool,
+    /// Whether the last health check found the peer healthy
+    pub healthy: bool,
+}
+
+impl PeerCandidate {
+    /// Routable, healthy candidate
+    pub fn new(peer_id: &str, latency_ms: Option<f64>, score: f64) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            latency_ms,
+            score,
+            routable: true,
+            healthy: true,
+        }
+    }
+
+    /// Candidate described by its reputation record
+    pub fn from_reputation(peer_id: &str, reputation: &ReputationManager) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            latency_ms: reputation
+                .get_reputation(peer_id)
+                .and_then(|record| record.avg_latency_ms),
+            score: reputation.score(peer_id),
+            routable: reputation.is_routable(peer_id),
+            healthy: true,
+        }
+    }
+}
+
+/// One scheduled send of a hedged request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedAttempt {
+    pub peer_id: String,
+    /// How long to wait for this peer before asking the next
+    pub hedge_after: Duration,
+}
+
+/// Peers to ask, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HedgePlan {
+    pub attempts: Vec<PlannedAttempt>,
+}
+
+/// Send made for a hedged request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HedgeAttempt {
+    pub peer_id: String,
+    pub message_id: String,
+    /// Time since the request started when this peer was asked
+    pub sent_after_ms: u64,
+}
+
+/// Outcome of a hedged send
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HedgedDelivery {
+    /// Peer whose receipt arrived first
+    pub peer_id: String,
+    pub receipt: DeliveryReceipt,
+    /// Every send made, in order
+    pub attempts: Vec<HedgeAttempt>,
+    /// Time from the start of the request to the winning receipt
+    pub latency_ms: u64,
+}
+
+/// Drop unusable candidates and order the rest, best first
+pub fn rank_candidates(candidates: Vec<PeerCandidate>) -> Vec<PeerCandidate> {
+    let mut ranked: Vec<PeerCandidate> = candidates
+        .into_iter()
+        .filter(|peer| peer.routable && peer.healthy)
+        .collect();
+    ranked.sort_by(|a, b| {
+        let latency = match (a.latency_ms, b.latency_ms) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        latency
+            .then_with(|| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal))
+            .then_with(|| a.peer_id.cmp(&b.peer_id))
+    });
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer_reputation::ReputationConfig;
+
+    #[test]
+    fn test_ranking_prefers_measured_low_latency() {
+        let mut unhealthy = PeerCandidate::new("down", Some(1.0), 1.0);
+        unhealthy.healthy = false;
+        let ranked = rank_candidates(vec![
+            PeerCandidate::new("unmeasured", None, 1.0),
+            PeerCandidate::new("slow", Some(80.0), 1.0),
+            unhealthy,
+            PeerCandidate::new("fast_b", Some(5.0), 0.6),
+            PeerCandidate::new("fast_a", Some(5.0), 0.9),
+        ]);
+        let order: Vec<&str> = ranked.iter().map(|peer| peer.peer_id.as_str()).collect();
+        assert_eq!(order, vec!["fast_a", "fast_b", "slow", "unmeasured"]);
+    }
+
+    #[test]
+    fn test_plan_limits_hedges_and_clamps_delays() {
+        let config = HedgeConfig {
+            max_hedges: 2,
+            ..Default::default()
+        };
+        let plan = config.plan(vec![
+            PeerCandidate::new("a", Some(10.0), 1.0),
+            PeerCandidate::new("b", Some(0.5), 1.0),
+            PeerCandidate::new("c", Some(5_000.0), 1.0),
+            PeerCandidate::new("d", None, 1.0),
+        ]);
+        let delays: Vec<(&str, u64)> = plan
+            .attempts
+            .iter()
+            .map(|attempt| {
+                (
+                    attempt.peer_id.as_str(),
+                    attempt.hedge_after.as_millis() as u64,
+                )
+            })
+            .collect();
+        assert_eq!(delays, vec![("b", 5), ("a", 20), ("c", 2_000)]);
+        assert_eq!(
+            config.hedge_delay(&PeerCandidate::new("d", None, 1.0)),
+            Duration::from_millis(50)
+        );
+
+        assert!(HedgeConfig {
+            latency_multiplier: 0.5,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(HedgeConfig {
+            min_delay_ms: 10,
+            max_delay_ms: 5,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_candidates_from_reputation() {
+        let mut reputation = ReputationManager::new(ReputationConfig::default());
+        reputation.record_success("steady", 12);
+        reputation.record_failure("quarantined");
+        reputation.set_override("quarantined", 0.0).unwrap();
+        let steady = PeerCandidate::from_reputation("steady", &reputation);
+        assert_eq!(steady.latency_ms, Some(12.0));
+        assert!(steady.routable);
+        let quarantined = PeerCandidate::from_reputation("quarantined", &reputation);
+        assert!(!quarantined.routable);
+        assert_eq!(quarantined.latency_ms, None);
+
+        let ranked = rank_candidates(vec![quarantined, steady]);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].peer_id, "steady");
+    }
+}
@@ -0,0 +1,438 @@
+//! # HSM Entropy - Attested Hardware Entropy Sources and Provenance
+//!
+//! Lets the security foundation draw entropy from a hardware security module
+//! or TPM in addition to its built-in sources. Devices implement the
+//! `HardwareEntropySource` trait outside this crate; `SimulatedHsm` stands in
+//! for one in tests and development.
+//!
+//! ## Attestation
+//!
+//! Before a device is mixed into the entropy pool it must answer a fresh
+//! nonce with an `EntropyAttestation`: the device kind, identifier, firmware
+//! measurement and nonce, signed by the device's Ed25519 attestation key.
+//! `verify_entropy_attestation` checks it against an
+//! `EntropyAttestationPolicy`. Checking the attestation key against the
+//! vendor's certificate chain is the device integration's job; the policy can
+//! additionally pin attestation keys.
+//!
+//! ## Provenance
+//!
+//! Every output of the entropy service is described by an
+//! `EntropyProvenance` record: which sources fed the pool, each source's
+//! share of the input bytes and health score, the mixing rounds applied and
+//! the attested hardware device, if any. QRNGs keep the record of their seed
+//! and `KeyManager::generate_key` attaches it to the key and the audit log.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::hsm_entropy::{
+//!     verify_entropy_attestation, EntropyAttestationPolicy, HardwareEntropySource,
+//!     SimulatedHsm,
+//! };
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let hsm = SimulatedHsm::new("hsm-0");
+//! let attestation = hsm.attest(b"verifier nonce")?;
+//! verify_entropy_attestation(
+//!     &attestation,
+//!     b"verifier nonce",
+//!     &EntropyAttestationPolicy::default(),
+//! )?;
+//!
+//! let mut buffer = [0u8; 32];
+//! hsm.fill(&mut buffer)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::address_book::fingerprint;
+use crate::constant_time::ct_eq;
+use crate::security_foundation::EntropySource;
+use crate::{Result, SecureCommsError};
+
+/// Kind of device a hardware entropy source reads from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HardwareEntropyKind {
+    /// PKCS#11 hardware security module
+    Hsm,
+    /// TPM 2.0 random number generator
+    Tpm,
+    /// Software stand-in; no hardware noise source
+    Simulated,
+}
+
+impl HardwareEntropyKind {
+    /// Whether the entropy comes from a physical noise source
+    pub fn is_hardware(&self) -> bool {
+        !matches!(self, HardwareEntropyKind::Simulated)
+    }
+}
+
+/// Signed statement about an entropy device, bound to a verifier nonce
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntropyAttestation {
+    /// Device kind
+    pub kind: HardwareEntropyKind,
+    /// Device identifier, e.g. an HSM slot or TPM serial
+    pub source_id: String,
+    /// Hash of the device firmware
+    pub measurement: Vec<u8>,
+    /// Verifier nonce the attestation answers
+    pub nonce: Vec<u8>,
+    /// Unix time the attestation was produced (seconds)
+    pub timestamp: u64,
+    /// Ed25519 key that signed the attestation
+    pub attestation_key: Vec<u8>,
+    /// Signature over `signed_payload`
+    pub signature: Vec<u8>,
+}
+
+impl EntropyAttestation {
+    /// Bytes covered by the signature
+    pub fn signed_payload(&self) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"entropy_attestation_v1");
+        hasher.update(serde_json::to_vec(&self.kind).unwrap_or_default());
+        for field in [
+            self.source_id.as_bytes(),
+            self.measurement.as_slice(),
+            self.nonce.as_slice(),
+        ] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        }
+        hasher.update(self.timestamp.to_be_bytes());
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Requirements on an entropy device's attestation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntropyAttestationPolicy {
+    /// Accept devices without a physical noise source
+    pub allow_simulated: bool,
+    /// Accepted attestation keys; empty accepts any
+    pub trusted_attestation_keys: Vec<Vec<u8>>,
+    /// Oldest attestation accepted, in seconds
+    pub max_age_seconds: u64,
+}
+
+impl Default for EntropyAttestationPolicy {
+    fn default() -> Self {
+        Self {
+            allow_simulated: true,
+            trusted_attestation_keys: Vec::new(),
+            max_age_seconds: 300,
+        }
+    }
+}
+
+/// Entropy read from an HSM, TPM or similar device
+///
+/// Implementations must fail `fill` rather than return weak output when the
+/// device's own health tests fail.
+pub trait HardwareEntropySource: Send + Sync {
+    /// Device kind
+    fn kind(&self) -> HardwareEntropyKind;
+
+    /// Device identifier
+    fn source_id(&self) -> &str;
+
+    /// Fill `buffer` with random bytes from the device
+    fn fill(&self, buffer: &mut [u8]) -> Result<()>;
+
+    /// Produce an attestation of the device answering `nonce`
+    fn attest(&self, nonce: &[u8]) -> Result<EntropyAttestation>;
+}
+
+/// Check an entropy device's attestation against a nonce and policy
+pub fn verify_entropy_attestation(
+    attestation: &EntropyAttestation,
+    expected_nonce: &[u8],
+    policy: &EntropyAttestationPolicy,
+) -> Result<()> {
+    let key_bytes: [u8; 32] = attestation
+        .attestation_key
+        .as_slice()
+        .try_into()
+        .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+    let signature_bytes: [u8; 64] = attestation
+        .signature
+        .as_slice()
+        .try_into()
+        .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+    let key =
+        VerifyingKey::from_bytes(&key_bytes).map_err(|_| SecureCommsError::AuthenticationFailed)?;
+    key.verify(
+        &attestation.signed_payload(),
+        &Signature::from_bytes(&signature_bytes),
+    )
+    .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+
+    if !ct_eq(&attestation.nonce, expected_nonce) {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    let now = chrono::Utc::now().timestamp() as u64;
+    if now.saturating_sub(attestation.timestamp) > policy.max_age_seconds {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+
+    if !attestation.kind.is_hardware() && !policy.allow_simulated {
+        return Err(SecureCommsError::Validation(
+            "Entropy source has no hardware noise source".to_string(),
+        ));
+    }
+    if !policy.trusted_attestation_keys.is_empty()
+        && !policy
+            .trusted_attestation_keys
+            .contains(&attestation.attestation_key)
+    {
+        return Err(SecureCommsError::Validation(
+            "Entropy source attestation key is not trusted".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Software stand-in for an HSM entropy source
+///
+/// Reads from the operating system RNG and signs attestations with a
+/// process-local key. `set_failed` simulates a device health-test failure.
+pub struct SimulatedHsm {
+    source_id: String,
+    attestation_key: SigningKey,
+    failed: AtomicBool,
+}
+
+impl SimulatedHsm {
+    /// Create a simulated device with a fresh attestation key
+    pub fn new(source_id: &str) -> Self {
+        let mut seed = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut seed);
+        Self {
+            source_id: source_id.to_string(),
+            attestation_key: SigningKey::from_bytes(&seed),
+            failed: AtomicBool::new(false),
+        }
+    }
+
+    /// Public half of the attestation key
+    pub fn attestation_key(&self) -> Vec<u8> {
+        self.attestation_key.verifying_key().to_bytes().to_vec()
+    }
+
+    /// Make subsequent reads fail, as a device failing its health tests would
+    pub fn set_failed(&self, failed: bool) {
+        self.failed.store(failed, Ordering::Relaxed);
+    }
+}
+
+impl HardwareEntropySource for SimulatedHsm {
+    fn kind(&self) -> HardwareEntropyKind {
+        HardwareEntropyKind::Simulated
+    }
+
+    fn source_id(&self) -> &str {
+        &self.source_id
+    }
+
+    fn fill(&self, buffer: &mut [u8]) -> Result<()> {
+        if self.failed.load(Ordering::Relaxed) {
+            return Err(SecureCommsError::CryptoProtocol(format!(
+                "Entropy source {} failed its health test",
+                self.source_id
+            )));
+        }
+        rand::rngs::OsRng.fill_bytes(buffer);
+        Ok(())
+    }
+
+    fn attest(&self, nonce: &[u8]) -> Result<EntropyAttestation> {
+        let mut attestation = EntropyAttestation {
+            kind: self.kind(),
+            source_id: self.source_id.clone(),
+            measurement: Sha3_256::digest(b"simulated_hsm_firmware_v1").to_vec(),
+            nonce: nonce.to_vec(),
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            attestation_key: self.attestation_key(),
+            signature: Vec::new(),
+        };
+        attestation.signature = self
+            .attestation_key
+            .sign(&attestation.signed_payload())
+            .to_bytes()
+            .to_vec();
+        Ok(attestation)
+    }
+}
+
+/// Attested hardware device mixed into the entropy pool
+#[derive(Clone)]
+pub struct AttestedEntropySource {
+    /// Device handle
+    pub device: Arc<dyn HardwareEntropySource>,
+    /// Attestation verified when the device was attached
+    pub attestation: EntropyAttestation,
+}
+
+impl std::fmt::Debug for AttestedEntropySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttestedEntropySource")
+            .field("kind", &self.attestation.kind)
+            .field("source_id", &self.attestation.source_id)
+            .finish()
+    }
+}
+
+impl AttestedEntropySource {
+    /// Audit summary of the device
+    pub fn record(&self) -> HardwareEntropyRecord {
+        HardwareEntropyRecord {
+            kind: self.attestation.kind,
+            source_id: self.attestation.source_id.clone(),
+            attestation_key: fingerprint(&self.attestation.attestation_key),
+            attestation_digest: fingerprint(&self.attestation.signed_payload()),
+            attested_at: self.attestation.timestamp,
+        }
+    }
+}
+
+/// Audit summary of an attested hardware entropy device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HardwareEntropyRecord {
+    /// Device kind
+    pub kind: HardwareEntropyKind,
+    /// Device identifier
+    pub source_id: String,
+    /// Fingerprint of the attestation key
+    pub attestation_key: String,
+    /// Fingerprint of the signed attestation payload
+    pub attestation_digest: String,
+    /// Unix time of the attestation (seconds)
+    pub attested_at: u64,
+}
+
+/// One source's contribution to an entropy output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceContribution {
+    /// Entropy source
+    pub source: EntropySource,
+    /// Share of the pool's input bytes (0.0-1.0)
+    pub weight: f64,
+    /// Source health score at generation time (0.0-1.0)
+    pub health: f64,
+}
+
+/// Where the bytes of an entropy output came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntropyProvenance {
+    /// Unix time of generation (seconds)
+    pub generated_at: u64,
+    /// Number of bytes produced
+    pub output_bytes: usize,
+    /// SHA-3 mixing rounds applied; zero when the DRBG output was used directly
+    pub mixing_rounds: usize,
+    /// Share of the pool's input bytes drawn from the ChaCha20 DRBG
+    pub drbg_weight: f64,
+    /// Contributions of the configured sources
+    pub sources: Vec<SourceContribution>,
+    /// Attested hardware device, if one is attached
+    pub hardware: Option<HardwareEntropyRecord>,
+}
+
+impl EntropyProvenance {
+    /// Whether an attested hardware device contributed to the output
+    pub fn is_hardware_backed(&self) -> bool {
+        self.sources.iter().any(|contribution| {
+            contribution.source == EntropySource::Hardware && contribution.weight > 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attestation_verifies_against_nonce_and_policy() {
+        let hsm = SimulatedHsm::new("hsm-0");
+        let attestation = hsm.attest(b"nonce-1").unwrap();
+        verify_entropy_attestation(
+            &attestation,
+            b"nonce-1",
+            &EntropyAttestationPolicy::default(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            verify_entropy_attestation(
+                &attestation,
+                b"nonce-2",
+                &EntropyAttestationPolicy::default()
+            ),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        let mut forged = attestation.clone();
+        forged.source_id = "hsm-1".to_string();
+        assert!(matches!(
+            verify_entropy_attestation(&forged, b"nonce-1", &EntropyAttestationPolicy::default()),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        let hardware_only = EntropyAttestationPolicy {
+            allow_simulated: false,
+            ..Default::default()
+        };
+        assert!(matches!(
+            verify_entropy_attestation(&attestation, b"nonce-1", &hardware_only),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_pins_attestation_keys() {
+        let trusted = SimulatedHsm::new("hsm-0");
+        let other = SimulatedHsm::new("hsm-0");
+        let policy = EntropyAttestationPolicy {
+            trusted_attestation_keys: vec![trusted.attestation_key()],
+            ..Default::default()
+        };
+
+        let attestation = trusted.attest(b"nonce").unwrap();
+        verify_entropy_attestation(&attestation, b"nonce", &policy).unwrap();
+
+        let attestation = other.attest(b"nonce").unwrap();
+        assert!(matches!(
+            verify_entropy_attestation(&attestation, b"nonce", &policy),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_failed_device_refuses_to_fill() {
+        let hsm = SimulatedHsm::new("hsm-0");
+        let mut buffer = [0u8; 32];
+        hsm.fill(&mut buffer).unwrap();
+        assert_ne!(buffer, [0u8; 32]);
+
+        hsm.set_failed(true);
+        assert!(hsm.fill(&mut buffer).is_err());
+
+        let source = AttestedEntropySource {
+            attestation: hsm.attest(b"nonce").unwrap(),
+            device: Arc::new(hsm),
+        };
+        let record = source.record();
+        assert_eq!(record.source_id, "hsm-0");
+        assert_eq!(record.kind, HardwareEntropyKind::Simulated);
+    }
+}
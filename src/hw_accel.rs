@@ -0,0 +1,453 @@
+//! # Hardware Acceleration - CPU Feature Detection and Crypto Dispatch
+//!
+//! Detects the CPU's cryptographic extensions once at runtime and routes
+//! symmetric encryption and hashing to the fastest constant-time path the
+//! host offers, so throughput holds on hosts without AES instructions too.
+//!
+//! ## Dispatch
+//!
+//! - **AES-NI / ARMv8 AES**: With hardware AES and carry-less multiply the
+//!   AEAD is AES-256-GCM, which then runs on the AES and CLMUL/PMULL units
+//! - **AVX2 / NEON**: Without hardware AES the AEAD is ChaCha20-Poly1305,
+//!   whose SIMD backends outpace table-free software AES several times over
+//! - **Portable**: Plain ChaCha20-Poly1305 on every other target
+//! - **Hashing**: SHA3-256 uses the ARMv8.2 SHA3 instructions when present
+//!
+//! Sealed messages name their suite, so hosts that dispatched differently
+//! still open each other's ciphertexts.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::hw_accel::{CryptoDispatch, DispatchPolicy};
+//!
+//! let dispatch = CryptoDispatch::new(DispatchPolicy::Auto);
+//! println!("{:?}", dispatch.capability_report());
+//!
+//! let key = [7u8; 32];
+//! let sealed = dispatch.seal(&key, &[1u8; 12], b"header", b"payload").unwrap();
+//! assert_eq!(dispatch.open(&key, b"header", &sealed).unwrap(), b"payload");
+//! ```
+
+use crate::{Result, SecureCommsError};
+use aes_gcm::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::ChaCha20Poly1305;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashMap;
+
+/// AEAD key length in bytes
+pub const KEY_LEN: usize = 32;
+/// AEAD nonce length in bytes
+pub const NONCE_LEN: usize = 12;
+/// AEAD authentication tag length in bytes
+pub const TAG_LEN: usize = 16;
+
+static DETECTED: Lazy<CpuFeatures> = Lazy::new(CpuFeatures::detect);
+
+/// Cryptographic CPU extensions of the host
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuFeatures {
+    /// AES round instructions (x86 AES-NI, ARMv8 AES)
+    pub aes: bool,
+    /// Carry-less multiply for GHASH (x86 PCLMULQDQ, ARMv8 PMULL)
+    pub clmul: bool,
+    /// 256-bit x86 vector integer instructions
+    pub avx2: bool,
+    /// ARM Advanced SIMD
+    pub neon: bool,
+    /// SHA-2 instructions (x86 SHA-NI, ARMv8 SHA2)
+    pub sha2: bool,
+    /// ARMv8.2 SHA3 instructions
+    pub sha3: bool,
+}
+
+impl CpuFeatures {
+    /// Probe the running CPU
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn detect() -> Self {
+        Self {
+            aes: std::arch::is_x86_feature_detected!("aes"),
+            clmul: std::arch::is_x86_feature_detected!("pclmulqdq"),
+            avx2: std::arch::is_x86_feature_detected!("avx2"),
+            neon: false,
+            sha2: std::arch::is_x86_feature_detected!("sha"),
+            sha3: false,
+        }
+    }
+
+    /// Probe the running CPU
+    #[cfg(target_arch = "aarch64")]
+    pub fn detect() -> Self {
+        Self {
+            aes: std::arch::is_aarch64_feature_detected!("aes"),
+            clmul: std::arch::is_aarch64_feature_detected!("pmull"),
+            avx2: false,
+            neon: std::arch::is_aarch64_feature_detected!("neon"),
+            sha2: std::arch::is_aarch64_feature_detected!("sha2"),
+            sha3: std::arch::is_aarch64_feature_detected!("sha3"),
+        }
+    }
+
+    /// Probe the running CPU
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    pub fn detect() -> Self {
+        Self::default()
+    }
+
+    /// Hardware AES with GHASH support
+    pub fn has_hardware_aes(&self) -> bool {
+        self.aes && self.clmul
+    }
+}
+
+/// Features detected on this host, probed once per process
+pub fn cpu_features() -> CpuFeatures {
+    *DETECTED
+}
+
+/// Authenticated encryption suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AeadSuite {
+    /// AES-256-GCM
+    Aes256Gcm,
+    /// ChaCha20-Poly1305
+    ChaCha20Poly1305,
+}
+
+impl AeadSuite {
+    /// Wire identifier prefixed to sealed messages
+    pub fn id(&self) -> u8 {
+        match self {
+            AeadSuite::Aes256Gcm => 1,
+            AeadSuite::ChaCha20Poly1305 => 2,
+        }
+    }
+
+    /// Suite for a wire identifier
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(AeadSuite::Aes256Gcm),
+            2 => Some(AeadSuite::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+}
+
+/// Execution unit a dispatched operation runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccelerationPath {
+    /// x86 AES-NI with PCLMULQDQ
+    AesNi,
+    /// ARMv8 AES with PMULL
+    ArmAes,
+    /// x86 AVX2 vector backend
+    Avx2,
+    /// ARM NEON vector backend
+    Neon,
+    /// ARMv8.2 SHA3 instructions
+    ArmSha3,
+    /// Generic scalar code
+    Portable,
+}
+
+/// How the dispatcher chooses its paths
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DispatchPolicy {
+    /// Fastest path for the detected features
+    #[default]
+    Auto,
+    /// Same suite on every host regardless of features
+    Portable,
+}
+
+/// Detected features and the paths chosen for them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityReport {
+    /// Target architecture
+    pub arch: String,
+    /// Detected CPU extensions
+    pub features: CpuFeatures,
+    /// Dispatch policy in force
+    pub policy: DispatchPolicy,
+    /// AEAD suite used for sealing
+    pub aead_suite: AeadSuite,
+    /// Execution unit of the AEAD
+    pub aead_path: AccelerationPath,
+    /// Execution unit of SHA3 hashing
+    pub hash_path: AccelerationPath,
+}
+
+impl CapabilityReport {
+    /// Whether any operation runs on dedicated hardware
+    pub fn is_accelerated(&self) -> bool {
+        self.aead_path != AccelerationPath::Portable || self.hash_path != AccelerationPath::Portable
+    }
+
+    /// Report as a JSON map
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut map = HashMap::new();
+        map.insert("arch".to_string(), serde_json::json!(self.arch));
+        map.insert(
+            "features".to_string(),
+            serde_json::to_value(self.features).unwrap_or(serde_json::Value::Null),
+        );
+        map.insert(
+            "policy".to_string(),
+            serde_json::json!(format!("{:?}", self.policy)),
+        );
+        map.insert(
+            "aead_suite".to_string(),
+            serde_json::json!(format!("{:?}", self.aead_suite)),
+        );
+        map.insert(
+            "aead_path".to_string(),
+            serde_json::json!(format!("{:?}", self.aead_path)),
+        );
+        map.insert(
+            "hash_path".to_string(),
+            serde_json::json!(format!("{:?}", self.hash_path)),
+        );
+        map.insert(
+            "accelerated".to_string(),
+            serde_json::json!(self.is_accelerated()),
+        );
+        map
+    }
+}
+
+/// Runtime dispatcher for symmetric encryption and hashing
+#[derive(Debug, Clone)]
+pub struct CryptoDispatch {
+    features: CpuFeatures,
+    policy: DispatchPolicy,
+    suite: AeadSuite,
+}
+
+impl CryptoDispatch {
+    /// Dispatcher for the detected features under `policy`
+    pub fn new(policy: DispatchPolicy) -> Self {
+        Self::with_features(cpu_features(), policy)
+    }
+
+    /// Dispatcher for explicit features, e.g. to plan for another host
+    pub fn with_features(features: CpuFeatures, policy: DispatchPolicy) -> Self {
+        let suite = match policy {
+            DispatchPolicy::Auto if features.has_hardware_aes() => AeadSuite::Aes256Gcm,
+            _ => AeadSuite::ChaCha20Poly1305,
+        };
+        Self {
+            features,
+            policy,
+            suite,
+        }
+    }
+
+    /// Suite used by `seal`
+    pub fn suite(&self) -> AeadSuite {
+        self.suite
+    }
+
+    /// Execution unit of `suite` on this host
+    pub fn aead_path(&self, suite: AeadSuite) -> AccelerationPath {
+        let features = &self.features;
+        match suite {
+            AeadSuite::Aes256Gcm if features.has_hardware_aes() => {
+                if cfg!(target_arch = "aarch64") {
+                    AccelerationPath::ArmAes
+                } else {
+                    AccelerationPath::AesNi
+                }
+            }
+            AeadSuite::ChaCha20Poly1305 if features.avx2 => AccelerationPath::Avx2,
+            AeadSuite::ChaCha20Poly1305 if features.neon => AccelerationPath::Neon,
+            _ => AccelerationPath::Portable,
+        }
+    }
+
+    /// Execution unit of SHA3 hashing on this host
+    pub fn hash_path(&self) -> AccelerationPath {
+        if self.features.sha3 {
+            AccelerationPath::ArmSha3
+        } else {
+            AccelerationPath::Portable
+        }
+    }
+
+    /// Detected features and chosen paths
+    pub fn capability_report(&self) -> CapabilityReport {
+        CapabilityReport {
+            arch: std::env::consts::ARCH.to_string(),
+            features: self.features,
+            policy: self.policy,
+            aead_suite: self.suite,
+            aead_path: self.aead_path(self.suite),
+            hash_path: self.hash_path(),
+        }
+    }
+
+    /// Encrypt with the dispatched suite
+    ///
+    /// Output layout: `[suite(1)][nonce(12)][ciphertext+tag]`. A nonce must
+    /// never be reused with the same key.
+    pub fn seal(&self, key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.seal_with(self.suite, key, nonce, aad, plaintext)
+    }
+
+    /// Encrypt with an explicit suite
+    pub fn seal_with(
+        &self,
+        suite: AeadSuite,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>> {
+        check_lengths(key, nonce)?;
+        let payload = Payload {
+            msg: plaintext,
+            aad,
+        };
+        let nonce_array = GenericArray::from_slice(nonce);
+        let ciphertext = match suite {
+            AeadSuite::Aes256Gcm => {
+                Aes256Gcm::new(GenericArray::from_slice(key)).encrypt(nonce_array, payload)
+            }
+            AeadSuite::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(GenericArray::from_slice(key)).encrypt(nonce_array, payload)
+            }
+        }
+        .map_err(|e| {
+            SecureCommsError::CryptoProtocol(format!("{:?} encryption failed: {:?}", suite, e))
+        })?;
+
+        let mut sealed = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+        sealed.push(suite.id());
+        sealed.extend_from_slice(nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt a message sealed by any dispatcher
+    pub fn open(&self, key: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+        if sealed.len() < 1 + NONCE_LEN + TAG_LEN {
+            return Err(SecureCommsError::CryptoProtocol(
+                "Sealed message too short".to_string(),
+            ));
+        }
+        let suite = AeadSuite::from_id(sealed[0]).ok_or_else(|| {
+            SecureCommsError::CryptoProtocol(format!("Unknown AEAD suite {}", sealed[0]))
+        })?;
+        let nonce = &sealed[1..1 + NONCE_LEN];
+        check_lengths(key, nonce)?;
+        let payload = Payload {
+            msg: &sealed[1 + NONCE_LEN..],
+            aad,
+        };
+        let nonce = GenericArray::from_slice(nonce);
+        match suite {
+            AeadSuite::Aes256Gcm => {
+                Aes256Gcm::new(GenericArray::from_slice(key)).decrypt(nonce, payload)
+            }
+            AeadSuite::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(GenericArray::from_slice(key)).decrypt(nonce, payload)
+            }
+        }
+        .map_err(|_| SecureCommsError::AuthenticationFailed)
+    }
+
+    /// SHA3-256 digest
+    pub fn digest(&self, data: &[u8]) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        output.copy_from_slice(&Sha3_256::digest(data));
+        output
+    }
+}
+
+impl Default for CryptoDispatch {
+    fn default() -> Self {
+        Self::new(DispatchPolicy::Auto)
+    }
+}
+
+fn check_lengths(key: &[u8], nonce: &[u8]) -> Result<()> {
+    if key.len() != KEY_LEN || nonce.len() != NONCE_LEN {
+        return Err(SecureCommsError::CryptoProtocol(format!(
+            "AEAD requires a {}-byte key and {}-byte nonce, got {} and {}",
+            KEY_LEN,
+            NONCE_LEN,
+            key.len(),
+            nonce.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispatch_follows_features() {
+        let aes_host = CpuFeatures {
+            aes: true,
+            clmul: true,
+            avx2: true,
+            ..Default::default()
+        };
+        let dispatch = CryptoDispatch::with_features(aes_host, DispatchPolicy::Auto);
+        assert_eq!(dispatch.suite(), AeadSuite::Aes256Gcm);
+
+        let simd_host = CpuFeatures {
+            neon: true,
+            ..Default::default()
+        };
+        let report =
+            CryptoDispatch::with_features(simd_host, DispatchPolicy::Auto).capability_report();
+        assert_eq!(report.aead_suite, AeadSuite::ChaCha20Poly1305);
+        assert_eq!(report.aead_path, AccelerationPath::Neon);
+
+        let portable = CryptoDispatch::with_features(aes_host, DispatchPolicy::Portable);
+        assert_eq!(portable.suite(), AeadSuite::ChaCha20Poly1305);
+
+        let bare = CryptoDispatch::with_features(CpuFeatures::default(), DispatchPolicy::Auto);
+        assert!(!bare.capability_report().is_accelerated());
+    }
+
+    #[test]
+    fn test_suites_interoperate() {
+        let key = [9u8; KEY_LEN];
+        let nonce = [3u8; NONCE_LEN];
+        let auto = CryptoDispatch::new(DispatchPolicy::Auto);
+        let portable = CryptoDispatch::new(DispatchPolicy::Portable);
+
+        for suite in [AeadSuite::Aes256Gcm, AeadSuite::ChaCha20Poly1305] {
+            let sealed = auto
+                .seal_with(suite, &key, &nonce, b"aad", b"payload")
+                .unwrap();
+            assert_eq!(sealed[0], suite.id());
+            assert_eq!(portable.open(&key, b"aad", &sealed).unwrap(), b"payload");
+        }
+    }
+
+    #[test]
+    fn test_tampering_rejected() {
+        let dispatch = CryptoDispatch::default();
+        let key = [1u8; KEY_LEN];
+        let mut sealed = dispatch
+            .seal(&key, &[0u8; NONCE_LEN], b"", b"payload")
+            .unwrap();
+        assert!(matches!(
+            dispatch.open(&key, b"other", &sealed),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(dispatch.open(&key, b"", &sealed).is_err());
+        assert!(dispatch
+            .seal(&key[..16], &[0u8; NONCE_LEN], b"", b"x")
+            .is_err());
+    }
+}
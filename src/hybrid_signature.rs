@@ -0,0 +1,471 @@
+//! # Hybrid Signatures - Ed25519 and ML-DSA over One Transcript
+//!
+//! Compliance regimes for the post-quantum transition often require every
+//! signed artifact to carry both a classical and a post-quantum signature,
+//! so it stays unforgeable as long as either scheme holds. A hybrid
+//! signature is an Ed25519 signature and an ML-DSA (FIPS 204) signature over
+//! the same transcript.
+//!
+//! ## Signature Modes
+//!
+//! - **None**: Messages are not signed beyond the channel AEAD
+//! - **Classical**: Ed25519 with the long-term identity key
+//! - **Hybrid**: Ed25519 and ML-DSA; both must verify
+//!
+//! ## Policy
+//!
+//! A `SignaturePolicy` sets a default mode and stronger modes for
+//! individual channels or message types; a message is signed with the
+//! strongest mode that applies. Receivers apply their own policy: a message
+//! signed more weakly than required is refused as a downgrade, and any
+//! signature present is verified whatever the policy.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::hybrid_signature::{
+//!     sign_transcript, verify_transcript, MlDsaKeyPair, MlDsaLevel, SignatureMode,
+//! };
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//!
+//! let mut keys = KeyManager::new();
+//! keys.import_key("identity", KeyPurpose::Signing, [1u8; 32]).unwrap();
+//! let ml_dsa = MlDsaKeyPair::generate(MlDsaLevel::MlDsa65).unwrap();
+//!
+//! let signature = sign_transcript(SignatureMode::Hybrid, b"transcript", &keys, "identity", &ml_dsa)
+//!     .unwrap();
+//! let mode = verify_transcript(
+//!     SignatureMode::Hybrid,
+//!     keys.public_key("identity").unwrap(),
+//!     Some(&ml_dsa.public_key()),
+//!     b"transcript",
+//!     Some(&signature),
+//! )
+//! .unwrap();
+//! assert_eq!(mode, SignatureMode::Hybrid);
+//! ```
+
+use crate::consensus_verify::verify_commit_signature;
+use crate::key_manager::KeyManager;
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+use fips204::traits::{SerDes, Signer, Verifier};
+use fips204::{ml_dsa_44, ml_dsa_65, ml_dsa_87};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// ML-DSA context string separating transcript signatures from other uses
+const ML_DSA_CONTEXT: &[u8] = b"quantum_forge_transcript_v1";
+
+/// Signatures required on a message, weakest first
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum SignatureMode {
+    /// No transcript signature
+    #[default]
+    None,
+    /// Ed25519 only
+    Classical,
+    /// Ed25519 and ML-DSA
+    Hybrid,
+}
+
+/// ML-DSA parameter set (FIPS 204)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MlDsaLevel {
+    /// ML-DSA-44, NIST security category 2
+    MlDsa44,
+    /// ML-DSA-65, NIST security category 3
+    #[default]
+    MlDsa65,
+    /// ML-DSA-87, NIST security category 5
+    MlDsa87,
+}
+
+/// ML-DSA verification key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MlDsaPublicKey {
+    /// Parameter set
+    pub level: MlDsaLevel,
+    /// Encoded public key
+    pub bytes: Vec<u8>,
+}
+
+/// Both verification keys of a hybrid signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HybridPublicKey {
+    /// Ed25519 identity key
+    pub ed25519: Vec<u8>,
+    /// ML-DSA key
+    pub ml_dsa: MlDsaPublicKey,
+}
+
+/// Transcript signature carried with a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HybridSignature {
+    /// Ed25519 signature, always present
+    pub ed25519: Vec<u8>,
+    /// ML-DSA signature in hybrid mode
+    #[serde(default)]
+    pub ml_dsa: Option<Vec<u8>>,
+}
+
+impl HybridSignature {
+    /// Mode the signature provides
+    pub fn mode(&self) -> SignatureMode {
+        if self.ml_dsa.is_some() {
+            SignatureMode::Hybrid
+        } else {
+            SignatureMode::Classical
+        }
+    }
+
+    /// Encoded size in bytes
+    pub fn len(&self) -> usize {
+        self.ed25519.len() + self.ml_dsa.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Whether the signature carries no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// ML-DSA signing key pair
+#[derive(Debug, Clone)]
+pub struct MlDsaKeyPair {
+    level: MlDsaLevel,
+    public_key: Vec<u8>,
+    private_key: SecretBytes,
+}
+
+impl MlDsaKeyPair {
+    /// Generate a key pair from the operating system's randomness
+    pub fn generate(level: MlDsaLevel) -> Result<Self> {
+        let keygen_error = |e: &'static str| {
+            SecureCommsError::CryptoProtocol(format!("{:?} keygen failed: {}", level, e))
+        };
+        let (public_key, private_key) = match level {
+            MlDsaLevel::MlDsa44 => {
+                let (pk, sk) = ml_dsa_44::try_keygen().map_err(keygen_error)?;
+                (pk.into_bytes().to_vec(), sk.into_bytes().to_vec())
+            }
+            MlDsaLevel::MlDsa65 => {
+                let (pk, sk) = ml_dsa_65::try_keygen().map_err(keygen_error)?;
+                (pk.into_bytes().to_vec(), sk.into_bytes().to_vec())
+            }
+            MlDsaLevel::MlDsa87 => {
+                let (pk, sk) = ml_dsa_87::try_keygen().map_err(keygen_error)?;
+                (pk.into_bytes().to_vec(), sk.into_bytes().to_vec())
+            }
+        };
+        Ok(Self {
+            level,
+            public_key,
+            private_key: SecretBytes::new(SensitiveKind::Key, private_key),
+        })
+    }
+
+    /// Parameter set
+    pub fn level(&self) -> MlDsaLevel {
+        self.level
+    }
+
+    /// Verification key
+    pub fn public_key(&self) -> MlDsaPublicKey {
+        MlDsaPublicKey {
+            level: self.level,
+            bytes: self.public_key.clone(),
+        }
+    }
+
+    /// Sign `message` under the transcript context
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        ml_dsa_sign(self.level, self.private_key.expose(), message)
+    }
+}
+
+/// Sign `message` under the transcript context with an encoded private key
+pub fn ml_dsa_sign(level: MlDsaLevel, private_key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    let sign_error = |e: &'static str| {
+        SecureCommsError::CryptoProtocol(format!("{:?} signing failed: {}", level, e))
+    };
+    let key_error =
+        |_| SecureCommsError::CryptoProtocol(format!("Malformed {:?} private key", level));
+    let signature = match level {
+        MlDsaLevel::MlDsa44 => {
+            ml_dsa_44::PrivateKey::try_from_bytes(private_key.try_into().map_err(key_error)?)
+                .map_err(sign_error)?
+                .try_sign(message, ML_DSA_CONTEXT)
+                .map_err(sign_error)?
+                .to_vec()
+        }
+        MlDsaLevel::MlDsa65 => {
+            ml_dsa_65::PrivateKey::try_from_bytes(private_key.try_into().map_err(key_error)?)
+                .map_err(sign_error)?
+                .try_sign(message, ML_DSA_CONTEXT)
+                .map_err(sign_error)?
+                .to_vec()
+        }
+        MlDsaLevel::MlDsa87 => {
+            ml_dsa_87::PrivateKey::try_from_bytes(private_key.try_into().map_err(key_error)?)
+                .map_err(sign_error)?
+                .try_sign(message, ML_DSA_CONTEXT)
+                .map_err(sign_error)?
+                .to_vec()
+        }
+    };
+    Ok(signature)
+}
+
+/// Verify an ML-DSA signature made by `MlDsaKeyPair::sign`
+pub fn ml_dsa_verify(key: &MlDsaPublicKey, message: &[u8], signature: &[u8]) -> bool {
+    macro_rules! verify_with {
+        ($level:ident) => {
+            match (
+                key.bytes.as_slice().try_into(),
+                <[u8; $level::SIG_LEN]>::try_from(signature),
+            ) {
+                (Ok(pk), Ok(sig)) => $level::PublicKey::try_from_bytes(pk)
+                    .map(|pk| pk.verify(message, &sig, ML_DSA_CONTEXT))
+                    .unwrap_or(false),
+                _ => false,
+            }
+        };
+    }
+    match key.level {
+        MlDsaLevel::MlDsa44 => verify_with!(ml_dsa_44),
+        MlDsaLevel::MlDsa65 => verify_with!(ml_dsa_65),
+        MlDsaLevel::MlDsa87 => verify_with!(ml_dsa_87),
+    }
+}
+
+/// Sign a transcript in `mode` with the identity key `key_id` and `ml_dsa`
+///
+/// Fails with `SecureCommsError::Validation` for `SignatureMode::None`.
+pub fn sign_transcript(
+    mode: SignatureMode,
+    transcript: &[u8],
+    keys: &KeyManager,
+    key_id: &str,
+    ml_dsa: &MlDsaKeyPair,
+) -> Result<HybridSignature> {
+    if mode == SignatureMode::None {
+        return Err(SecureCommsError::Validation(
+            "No signature requested".to_string(),
+        ));
+    }
+    Ok(HybridSignature {
+        ed25519: keys.sign(key_id, transcript)?,
+        ml_dsa: match mode {
+            SignatureMode::Hybrid => Some(ml_dsa.sign(transcript)?),
+            _ => None,
+        },
+    })
+}
+
+/// Verify a transcript signature against the signer's keys and `required` mode
+///
+/// Returns the mode the signature provided. Every signature present must
+/// verify; a bad one fails with `SecureCommsError::AuthenticationFailed`, as
+/// does a signature weaker than `required`. An ML-DSA signature from a
+/// signer whose ML-DSA key is unknown fails with `SecureCommsError::Validation`.
+pub fn verify_transcript(
+    required: SignatureMode,
+    ed25519_key: &[u8],
+    ml_dsa_key: Option<&MlDsaPublicKey>,
+    transcript: &[u8],
+    signature: Option<&HybridSignature>,
+) -> Result<SignatureMode> {
+    let signature = match signature {
+        Some(signature) => signature,
+        None if required == SignatureMode::None => return Ok(SignatureMode::None),
+        None => return Err(SecureCommsError::AuthenticationFailed),
+    };
+    if signature.mode() < required
+        || !verify_commit_signature(ed25519_key, transcript, &signature.ed25519)
+    {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    if let Some(ml_dsa_signature) = &signature.ml_dsa {
+        let key = ml_dsa_key.ok_or_else(|| {
+            SecureCommsError::Validation("No ML-DSA key known for signer".to_string())
+        })?;
+        if !ml_dsa_verify(key, transcript, ml_dsa_signature) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+    }
+    Ok(signature.mode())
+}
+
+/// Signature modes by channel and message type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignaturePolicy {
+    /// Mode for messages no override applies to
+    pub default_mode: SignatureMode,
+    /// ML-DSA parameter set of this node's key
+    pub ml_dsa_level: MlDsaLevel,
+    /// Overrides by peer ID
+    pub channels: BTreeMap<String, SignatureMode>,
+    /// Overrides by message type (typed message schema ID)
+    pub message_types: BTreeMap<String, SignatureMode>,
+}
+
+impl SignaturePolicy {
+    /// Strongest mode applying to a message to or from `peer_id`
+    pub fn mode_for(&self, peer_id: &str, message_type: Option<&str>) -> SignatureMode {
+        let channel = self.channels.get(peer_id).copied();
+        let message_type = message_type.and_then(|t| self.message_types.get(t).copied());
+        [Some(self.default_mode), channel, message_type]
+            .into_iter()
+            .flatten()
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_manager::KeyPurpose;
+
+    fn identity(seed: u8) -> KeyManager {
+        let mut keys = KeyManager::new();
+        keys.import_key("identity", KeyPurpose::Signing, [seed; 32])
+            .unwrap();
+        keys
+    }
+
+    #[test]
+    fn test_hybrid_sign_and_verify_all_levels() {
+        let keys = identity(1);
+        let ed25519 = keys.public_key("identity").unwrap().to_vec();
+        for level in [
+            MlDsaLevel::MlDsa44,
+            MlDsaLevel::MlDsa65,
+            MlDsaLevel::MlDsa87,
+        ] {
+            let ml_dsa = MlDsaKeyPair::generate(level).unwrap();
+            let public = ml_dsa.public_key();
+            let signature =
+                sign_transcript(SignatureMode::Hybrid, b"vote", &keys, "identity", &ml_dsa)
+                    .unwrap();
+            assert_eq!(signature.mode(), SignatureMode::Hybrid);
+            assert_eq!(
+                verify_transcript(
+                    SignatureMode::Hybrid,
+                    &ed25519,
+                    Some(&public),
+                    b"vote",
+                    Some(&signature)
+                )
+                .unwrap(),
+                SignatureMode::Hybrid
+            );
+            assert!(verify_transcript(
+                SignatureMode::Hybrid,
+                &ed25519,
+                Some(&public),
+                b"other",
+                Some(&signature)
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_both_halves_must_verify() {
+        let keys = identity(1);
+        let ed25519 = keys.public_key("identity").unwrap().to_vec();
+        let ml_dsa = MlDsaKeyPair::generate(MlDsaLevel::MlDsa44).unwrap();
+        let public = ml_dsa.public_key();
+        let signature =
+            sign_transcript(SignatureMode::Hybrid, b"vote", &keys, "identity", &ml_dsa).unwrap();
+
+        // A valid Ed25519 half does not excuse a forged ML-DSA half
+        let mut forged = signature.clone();
+        if let Some(sig) = forged.ml_dsa.as_mut() {
+            sig[0] ^= 1;
+        }
+        assert!(matches!(
+            verify_transcript(
+                SignatureMode::None,
+                &ed25519,
+                Some(&public),
+                b"vote",
+                Some(&forged)
+            ),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Stripping the ML-DSA half is a downgrade when hybrid is required
+        let stripped = HybridSignature {
+            ml_dsa: None,
+            ..signature.clone()
+        };
+        assert!(matches!(
+            verify_transcript(
+                SignatureMode::Hybrid,
+                &ed25519,
+                Some(&public),
+                b"vote",
+                Some(&stripped)
+            ),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        assert_eq!(
+            verify_transcript(
+                SignatureMode::Classical,
+                &ed25519,
+                None,
+                b"vote",
+                Some(&stripped)
+            )
+            .unwrap(),
+            SignatureMode::Classical
+        );
+        assert!(matches!(
+            verify_transcript(
+                SignatureMode::None,
+                &ed25519,
+                None,
+                b"vote",
+                Some(&signature)
+            ),
+            Err(SecureCommsError::Validation(_))
+        ));
+        assert!(
+            verify_transcript(SignatureMode::Classical, &ed25519, None, b"vote", None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_policy_takes_strongest_mode() {
+        let mut policy = SignaturePolicy::default();
+        assert_eq!(policy.mode_for("peer", None), SignatureMode::None);
+
+        policy.default_mode = SignatureMode::Classical;
+        policy
+            .channels
+            .insert("validator_1".to_string(), SignatureMode::Hybrid);
+        policy
+            .message_types
+            .insert("vote".to_string(), SignatureMode::Hybrid);
+        assert_eq!(policy.mode_for("peer", None), SignatureMode::Classical);
+        assert_eq!(policy.mode_for("validator_1", None), SignatureMode::Hybrid);
+        assert_eq!(policy.mode_for("peer", Some("vote")), SignatureMode::Hybrid);
+        assert_eq!(
+            policy.mode_for("peer", Some("heartbeat")),
+            SignatureMode::Classical
+        );
+        assert!(sign_transcript(
+            SignatureMode::None,
+            b"vote",
+            &identity(1),
+            "identity",
+            &MlDsaKeyPair::generate(MlDsaLevel::MlDsa44).unwrap()
+        )
+        .is_err());
+    }
+}
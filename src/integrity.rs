@@ -0,0 +1,435 @@
+//! # Integrity - Startup Self-Check Against a Signed Manifest
+//!
+//! Before a client starts, the running executable (or the file named by
+//! `binary_path`) and, optionally, its configuration file are hashed with SHA3-256 and compared with a release
+//! manifest signed by the build pipeline's Ed25519 key. The outcome is
+//! written to the audit log and bound into the client's attestation
+//! evidence, so peers can require a verified build.
+//!
+//! ## Manifest Key
+//!
+//! - **Config**: The verifying key is given directly in the configuration
+//! - **Tpm**: The key is read from a TPM NV index with `tpm2_nvread`, so an
+//!   attacker able to rewrite the configuration cannot swap it
+//!
+//! ## Enforcement
+//!
+//! With `refuse_on_mismatch` set, any outcome other than
+//! `IntegrityStatus::Verified` - including an unreadable manifest or key -
+//! stops client startup with `SecureCommsError::Configuration`. Otherwise
+//! the failure is only recorded.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::integrity::{IntegrityConfig, ManifestKeySource};
+//! use quantum_forge_secure_comms::{StreamlinedConfig, StreamlinedSecureClient};
+//!
+//! # async fn example(release_key: Vec<u8>) -> quantum_forge_secure_comms::Result<()> {
+//! let config = StreamlinedConfig {
+//!     integrity: Some(IntegrityConfig {
+//!         manifest_path: "/etc/quantum-forge/manifest.json".to_string(),
+//!         key_source: ManifestKeySource::Config { public_key: release_key },
+//!         binary_path: None,
+//!         config_path: Some("/etc/quantum-forge/client.toml".to_string()),
+//!         refuse_on_mismatch: true,
+//!     }),
+//!     ..Default::default()
+//! };
+//! let client = StreamlinedSecureClient::with_config(config).await?;
+//! println!("{:?}", client.integrity_report().map(|report| &report.status));
+//! # Ok(())
+//! # }
+//! ```
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::path::Path;
+
+use crate::constant_time::ct_eq;
+use crate::{Result, SecureCommsError};
+
+/// Where the manifest verifying key comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ManifestKeySource {
+    /// Ed25519 public key given in the configuration
+    Config {
+        /// Raw 32-byte verifying key
+        public_key: Vec<u8>,
+    },
+    /// Ed25519 public key stored in a TPM NV index
+    Tpm {
+        /// NV index holding the raw 32-byte key
+        nv_index: u32,
+    },
+}
+
+impl ManifestKeySource {
+    /// Load the verifying key
+    pub fn load(&self) -> Result<VerifyingKey> {
+        let bytes = match self {
+            ManifestKeySource::Config { public_key } => public_key.clone(),
+            ManifestKeySource::Tpm { nv_index } => read_tpm_nv(*nv_index)?,
+        };
+        let bytes: [u8; 32] = bytes.as_slice().try_into().map_err(|_| {
+            SecureCommsError::Configuration(format!(
+                "Manifest key must be 32 bytes, got {}",
+                bytes.len()
+            ))
+        })?;
+        VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| SecureCommsError::Configuration(format!("Invalid manifest key: {}", e)))
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            ManifestKeySource::Config { .. } => "config".to_string(),
+            ManifestKeySource::Tpm { nv_index } => format!("tpm:0x{:x}", nv_index),
+        }
+    }
+}
+
+fn read_tpm_nv(nv_index: u32) -> Result<Vec<u8>> {
+    let output = std::process::Command::new("tpm2_nvread")
+        .arg(format!("0x{:x}", nv_index))
+        .output()
+        .map_err(|e| SecureCommsError::Configuration(format!("TPM unavailable: {}", e)))?;
+    if !output.status.success() {
+        return Err(SecureCommsError::Configuration(format!(
+            "TPM NV read of 0x{:x} failed: {}",
+            nv_index,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// Startup integrity check settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityConfig {
+    /// Path of the signed manifest (JSON)
+    pub manifest_path: String,
+    /// Where the manifest verifying key comes from
+    pub key_source: ManifestKeySource,
+    /// Executable to hash, if not the running one
+    #[serde(default)]
+    pub binary_path: Option<String>,
+    /// Configuration file whose hash the manifest pins, if any
+    #[serde(default)]
+    pub config_path: Option<String>,
+    /// Stop startup unless the check verifies
+    #[serde(default)]
+    pub refuse_on_mismatch: bool,
+}
+
+/// Expected hashes of a release, signed by the release key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityManifest {
+    /// Release version the manifest describes
+    pub version: String,
+    /// SHA3-256 of the executable
+    pub binary_hash: Vec<u8>,
+    /// SHA3-256 of the configuration file, if pinned
+    #[serde(default)]
+    pub config_hash: Option<Vec<u8>>,
+    /// Ed25519 signature over `signed_payload`
+    pub signature: Vec<u8>,
+}
+
+impl IntegrityManifest {
+    /// Create and sign a manifest, for release tooling
+    pub fn sign(
+        version: &str,
+        binary_hash: Vec<u8>,
+        config_hash: Option<Vec<u8>>,
+        key: &SigningKey,
+    ) -> Self {
+        let mut manifest = Self {
+            version: version.to_string(),
+            binary_hash,
+            config_hash,
+            signature: Vec::new(),
+        };
+        manifest.signature = key.sign(&manifest.signed_payload()).to_bytes().to_vec();
+        manifest
+    }
+
+    /// Bytes covered by the signature
+    pub fn signed_payload(&self) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"integrity_manifest_v1");
+        hasher.update((self.version.len() as u64).to_be_bytes());
+        hasher.update(self.version.as_bytes());
+        hasher.update((self.binary_hash.len() as u64).to_be_bytes());
+        hasher.update(&self.binary_hash);
+        match &self.config_hash {
+            Some(hash) => {
+                hasher.update([1u8]);
+                hasher.update(hash);
+            }
+            None => hasher.update([0u8]),
+        }
+        hasher.finalize().to_vec()
+    }
+
+    /// Whether the signature verifies under `key`
+    pub fn verify_signature(&self, key: &VerifyingKey) -> bool {
+        let signature: [u8; 64] = match self.signature.as_slice().try_into() {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        key.verify(&self.signed_payload(), &Signature::from_bytes(&signature))
+            .is_ok()
+    }
+
+    /// Read a manifest from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = std::fs::read(path.as_ref()).map_err(|e| {
+            SecureCommsError::Configuration(format!(
+                "Cannot read manifest {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        serde_json::from_slice(&data)
+            .map_err(|e| SecureCommsError::Configuration(format!("Invalid manifest: {}", e)))
+    }
+
+    /// Write the manifest as JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| SecureCommsError::SystemError(e.to_string()))?;
+        std::fs::write(path, data).map_err(|e| SecureCommsError::SystemError(e.to_string()))
+    }
+}
+
+/// Outcome of an integrity check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntegrityStatus {
+    /// Signature and every pinned hash match
+    Verified,
+    /// Manifest signature does not verify under the key
+    BadSignature,
+    /// Executable hash differs from the manifest
+    BinaryMismatch,
+    /// Configuration hash differs from the manifest, or a pinned
+    /// configuration file was not given
+    ConfigMismatch,
+    /// Manifest, key or hashed file could not be read
+    Error(String),
+}
+
+/// Integrity check result, recorded in the audit log and attestation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Outcome of the check
+    pub status: IntegrityStatus,
+    /// Release version claimed by the manifest
+    pub manifest_version: Option<String>,
+    /// SHA3-256 of the running executable
+    pub binary_hash: Vec<u8>,
+    /// SHA3-256 of the configuration file, if one was given
+    pub config_hash: Option<Vec<u8>>,
+    /// Where the manifest key came from
+    pub key_source: String,
+    /// Unix time of the check (seconds)
+    pub checked_at: u64,
+}
+
+impl IntegrityReport {
+    /// Whether the check verified
+    pub fn is_verified(&self) -> bool {
+        self.status == IntegrityStatus::Verified
+    }
+}
+
+/// SHA3-256 of a file's contents, streamed rather than read into memory
+pub fn hash_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let cannot_hash = |e: std::io::Error| {
+        SecureCommsError::Configuration(format!("Cannot hash {}: {}", path.as_ref().display(), e))
+    };
+    let mut file = std::fs::File::open(path.as_ref()).map_err(cannot_hash)?;
+    let mut hasher = Sha3_256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(cannot_hash)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+/// SHA3-256 of the running executable
+pub fn hash_current_binary() -> Result<Vec<u8>> {
+    let path = std::env::current_exe()
+        .map_err(|e| SecureCommsError::Configuration(format!("Cannot locate executable: {}", e)))?;
+    hash_file(path)
+}
+
+/// Compare hashes against a manifest already verified as signed
+fn compare(
+    manifest: &IntegrityManifest,
+    binary_hash: &[u8],
+    config_hash: Option<&[u8]>,
+) -> IntegrityStatus {
+    if !ct_eq(&manifest.binary_hash, binary_hash) {
+        return IntegrityStatus::BinaryMismatch;
+    }
+    match (&manifest.config_hash, config_hash) {
+        (Some(expected), Some(actual)) if ct_eq(expected, actual) => IntegrityStatus::Verified,
+        (Some(_), _) => IntegrityStatus::ConfigMismatch,
+        (None, _) => IntegrityStatus::Verified,
+    }
+}
+
+/// Check the given hashes against the signed manifest
+///
+/// Never fails: unreadable inputs become `IntegrityStatus::Error`. The
+/// report is written to the audit log.
+pub fn check_hashes(
+    config: &IntegrityConfig,
+    binary_hash: Vec<u8>,
+    config_hash: Option<Vec<u8>>,
+) -> IntegrityReport {
+    let loaded = IntegrityManifest::load(&config.manifest_path)
+        .and_then(|manifest| config.key_source.load().map(|key| (manifest, key)));
+    let (status, manifest_version) = match loaded {
+        Ok((manifest, key)) => {
+            let status = if manifest.verify_signature(&key) {
+                compare(&manifest, &binary_hash, config_hash.as_deref())
+            } else {
+                IntegrityStatus::BadSignature
+            };
+            (status, Some(manifest.version))
+        }
+        Err(e) => (IntegrityStatus::Error(e.to_string()), None),
+    };
+    record(IntegrityReport {
+        status,
+        manifest_version,
+        binary_hash,
+        config_hash,
+        key_source: config.key_source.describe(),
+        checked_at: chrono::Utc::now().timestamp() as u64,
+    })
+}
+
+/// Hash the executable and configuration file and check them
+///
+/// Reads whole files, so call it from a blocking context.
+pub fn check_integrity(config: &IntegrityConfig) -> IntegrityReport {
+    let binary_hash = match &config.binary_path {
+        Some(path) => hash_file(path),
+        None => hash_current_binary(),
+    };
+    let config_hash = config.config_path.as_ref().map(hash_file).transpose();
+    match (binary_hash, config_hash) {
+        (Ok(binary_hash), Ok(config_hash)) => check_hashes(config, binary_hash, config_hash),
+        (Err(e), _) | (_, Err(e)) => record(IntegrityReport {
+            status: IntegrityStatus::Error(e.to_string()),
+            manifest_version: None,
+            binary_hash: Vec::new(),
+            config_hash: None,
+            key_source: config.key_source.describe(),
+            checked_at: chrono::Utc::now().timestamp() as u64,
+        }),
+    }
+}
+
+fn record(report: IntegrityReport) -> IntegrityReport {
+    crate::logging::log_audit(
+        "Startup integrity check",
+        serde_json::to_value(&report).unwrap_or(serde_json::Value::Null),
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(name: &str, config_hash: Option<Vec<u8>>) -> (IntegrityConfig, SigningKey) {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let path =
+            std::env::temp_dir().join(format!("integrity_{}_{}.json", name, std::process::id()));
+        IntegrityManifest::sign("1.0.0", vec![1u8; 32], config_hash, &key)
+            .save(&path)
+            .unwrap();
+        let config = IntegrityConfig {
+            manifest_path: path.to_string_lossy().into_owned(),
+            key_source: ManifestKeySource::Config {
+                public_key: key.verifying_key().to_bytes().to_vec(),
+            },
+            binary_path: None,
+            config_path: None,
+            refuse_on_mismatch: true,
+        };
+        (config, key)
+    }
+
+    #[test]
+    fn test_manifest_verification() {
+        let (config, _) = setup("verify", Some(vec![2u8; 32]));
+        let report = check_hashes(&config, vec![1u8; 32], Some(vec![2u8; 32]));
+        assert!(report.is_verified());
+        assert_eq!(report.manifest_version.as_deref(), Some("1.0.0"));
+
+        let report = check_hashes(&config, vec![9u8; 32], Some(vec![2u8; 32]));
+        assert_eq!(report.status, IntegrityStatus::BinaryMismatch);
+        let report = check_hashes(&config, vec![1u8; 32], Some(vec![3u8; 32]));
+        assert_eq!(report.status, IntegrityStatus::ConfigMismatch);
+        let report = check_hashes(&config, vec![1u8; 32], None);
+        assert_eq!(report.status, IntegrityStatus::ConfigMismatch);
+        assert!(crate::logging::LOGGER
+            .get_audit_trail()
+            .iter()
+            .any(|entry| entry.message == "Startup integrity check"));
+        std::fs::remove_file(&config.manifest_path).ok();
+    }
+
+    #[test]
+    fn test_tampered_manifest_or_wrong_key() {
+        let (mut config, _) = setup("tamper", None);
+        let mut manifest = IntegrityManifest::load(&config.manifest_path).unwrap();
+        manifest.binary_hash = vec![9u8; 32];
+        manifest.save(&config.manifest_path).unwrap();
+        let report = check_hashes(&config, vec![9u8; 32], None);
+        assert_eq!(report.status, IntegrityStatus::BadSignature);
+
+        config.key_source = ManifestKeySource::Config {
+            public_key: vec![0u8; 31],
+        };
+        let report = check_hashes(&config, vec![9u8; 32], None);
+        assert!(matches!(report.status, IntegrityStatus::Error(_)));
+        std::fs::remove_file(&config.manifest_path).ok();
+    }
+
+    #[test]
+    fn test_check_integrity_hashes_binary_file() {
+        let (mut config, key) = setup("binary", None);
+        let binary_path =
+            std::env::temp_dir().join(format!("integrity_binary_{}", std::process::id()));
+        let contents: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+        std::fs::write(&binary_path, &contents).unwrap();
+        config.binary_path = Some(binary_path.to_string_lossy().into_owned());
+
+        // Streaming over several buffers matches a one-shot digest
+        let binary_hash = hash_file(&binary_path).unwrap();
+        assert_eq!(binary_hash, Sha3_256::digest(&contents).to_vec());
+        assert_eq!(
+            check_integrity(&config).status,
+            IntegrityStatus::BinaryMismatch
+        );
+
+        IntegrityManifest::sign("1.0.1", binary_hash.clone(), None, &key)
+            .save(&config.manifest_path)
+            .unwrap();
+        let report = check_integrity(&config);
+        assert!(report.is_verified());
+        assert_eq!(report.binary_hash, binary_hash);
+
+        config.binary_path = Some(format!("{}.missing", binary_path.display()));
+        assert!(matches!(
+            check_integrity(&config).status,
+            IntegrityStatus::Error(_)
+        ));
+        std::fs::remove_file(&config.manifest_path).ok();
+        std::fs::remove_file(&binary_path).ok();
+    }
+}
@@ -0,0 +1,357 @@
+//! # Key Manager - Long-Term Validator Key Custody
+//!
+//! Holds a node's long-term private keys under string key IDs and performs
+//! operations with them, so secret material never has to leave the manager.
+//! Keys are generated from the quantum-seeded QRNG or imported from an
+//! existing seed, and are zeroized when removed or when the manager is dropped.
+//!
+//! ## Key Purposes
+//!
+//! - **Signing**: Ed25519 keys for votes, proposals and commit certificates
+//! - **Vrf**: ECVRF keys for verifiable leader election
+//! - **Wrapping**: Symmetric master keys that wrap data keys for
+//!   envelope encryption at rest
+//!
+//! A key is bound to a single purpose; using a VRF key to sign (or the other
+//! way round) is refused.
+//!
+//! Generated keys carry the entropy provenance of the QRNG seed they were
+//! drawn from, and each generation is written to the audit log.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//!
+//! let mut keys = KeyManager::new();
+//! let info = keys.import_key("vrf", KeyPurpose::Vrf, [3u8; 32]).unwrap();
+//! let (_output, proof) = keys.vrf_prove("vrf", b"epoch 7 round 3").unwrap();
+//! println!("VRF key {} produced proof {:?}", info.key_id, proof);
+//! ```
+
+use crate::crypto_protocols::QRNG;
+use crate::hsm_entropy::EntropyProvenance;
+use crate::hw_accel::{CryptoDispatch, NONCE_LEN};
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::vrf::{VrfOutput, VrfProof, VrfSecretKey};
+use crate::{Result, SecureCommsError};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use zeroize::Zeroizing;
+
+/// What a managed key may be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyPurpose {
+    /// Ed25519 signatures
+    Signing,
+    /// ECVRF proofs
+    Vrf,
+    /// Wrapping of data keys for envelope encryption
+    Wrapping,
+}
+
+/// Public description of a managed key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyInfo {
+    /// Key identifier
+    pub key_id: String,
+    /// Permitted use
+    pub purpose: KeyPurpose,
+    /// Public key bytes; a key check value for wrapping keys
+    pub public_key: Vec<u8>,
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+    /// Entropy sources behind the key; `None` for imported keys
+    #[serde(default)]
+    pub provenance: Option<EntropyProvenance>,
+}
+
+/// Private key material with its public description
+struct ManagedKey {
+    info: KeyInfo,
+    seed: Zeroizing<[u8; 32]>,
+}
+
+/// Custodian for long-term private keys
+#[derive(Default)]
+pub struct KeyManager {
+    keys: HashMap<String, ManagedKey>,
+}
+
+impl std::fmt::Debug for KeyManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyManager")
+            .field("key_ids", &self.key_ids())
+            .finish()
+    }
+}
+
+impl KeyManager {
+    /// Create empty key manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate a new key from the QRNG
+    pub fn generate_key(
+        &mut self,
+        key_id: &str,
+        purpose: KeyPurpose,
+        qrng: &mut QRNG,
+    ) -> Result<KeyInfo> {
+        let bytes = Zeroizing::new(qrng.generate_bytes(32)?);
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&bytes);
+        let mut info = self.import_key(key_id, purpose, seed)?;
+
+        info.provenance = qrng.provenance().cloned();
+        if let Some(key) = self.keys.get_mut(key_id) {
+            key.info.provenance = info.provenance.clone();
+        }
+        crate::logging::log_audit(
+            "Key generated",
+            serde_json::json!({
+                "key_id": key_id,
+                "purpose": purpose,
+                "provenance": info.provenance,
+            }),
+        );
+        Ok(info)
+    }
+
+    /// Import a key from its 32-byte seed
+    pub fn import_key(
+        &mut self,
+        key_id: &str,
+        purpose: KeyPurpose,
+        seed: [u8; 32],
+    ) -> Result<KeyInfo> {
+        if self.keys.contains_key(key_id) {
+            return Err(SecureCommsError::Validation(format!(
+                "Key {} already exists",
+                key_id
+            )));
+        }
+
+        let seed = Zeroizing::new(seed);
+        let public_key = match purpose {
+            KeyPurpose::Signing => SigningKey::from_bytes(&seed)
+                .verifying_key()
+                .to_bytes()
+                .to_vec(),
+            KeyPurpose::Vrf => VrfSecretKey::from_seed(*seed)
+                .public_key()
+                .to_bytes()
+                .to_vec(),
+            KeyPurpose::Wrapping => wrapping_key(&seed, b"key_check_value_v1")[..8].to_vec(),
+        };
+        let info = KeyInfo {
+            key_id: key_id.to_string(),
+            purpose,
+            public_key,
+            created_at: chrono::Utc::now().timestamp() as u64,
+            provenance: None,
+        };
+
+        self.keys.insert(
+            key_id.to_string(),
+            ManagedKey {
+                info: info.clone(),
+                seed,
+            },
+        );
+        Ok(info)
+    }
+
+    /// Look up a key's public description
+    pub fn key_info(&self, key_id: &str) -> Option<&KeyInfo> {
+        self.keys.get(key_id).map(|key| &key.info)
+    }
+
+    /// Public key bytes for a key
+    pub fn public_key(&self, key_id: &str) -> Option<&[u8]> {
+        self.key_info(key_id).map(|info| info.public_key.as_slice())
+    }
+
+    /// Sign a message with an Ed25519 signing key
+    pub fn sign(&self, key_id: &str, message: &[u8]) -> Result<Vec<u8>> {
+        let seed = self.seed_for(key_id, KeyPurpose::Signing)?;
+        Ok(SigningKey::from_bytes(seed)
+            .sign(message)
+            .to_bytes()
+            .to_vec())
+    }
+
+    /// Evaluate the VRF on `alpha` with a VRF key
+    pub fn vrf_prove(&self, key_id: &str, alpha: &[u8]) -> Result<(VrfOutput, VrfProof)> {
+        let seed = self.seed_for(key_id, KeyPurpose::Vrf)?;
+        Ok(VrfSecretKey::from_seed(*seed).prove(alpha))
+    }
+
+    /// Wrap a data key under a wrapping key, binding `aad`
+    pub fn wrap_key(&self, key_id: &str, aad: &[u8], data_key: &[u8]) -> Result<Vec<u8>> {
+        let seed = self.seed_for(key_id, KeyPurpose::Wrapping)?;
+        let key = wrapping_key(seed, b"key_wrap_v1");
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        CryptoDispatch::default().seal(&key[..], &nonce, aad, data_key)
+    }
+
+    /// Unwrap a data key wrapped by `wrap_key` under the same key and `aad`
+    pub fn unwrap_key(&self, key_id: &str, aad: &[u8], wrapped: &[u8]) -> Result<SecretBytes> {
+        let seed = self.seed_for(key_id, KeyPurpose::Wrapping)?;
+        let key = wrapping_key(seed, b"key_wrap_v1");
+        let data_key = CryptoDispatch::default().open(&key[..], aad, wrapped)?;
+        Ok(SecretBytes::new(SensitiveKind::Key, data_key))
+    }
+
+    fn seed_for(&self, key_id: &str, purpose: KeyPurpose) -> Result<&[u8; 32]> {
+        let key = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| SecureCommsError::CryptoProtocol(format!("Unknown key {}", key_id)))?;
+        if key.info.purpose != purpose {
+            return Err(SecureCommsError::CryptoProtocol(format!(
+                "Key {} is a {:?} key, not {:?}",
+                key_id, key.info.purpose, purpose
+            )));
+        }
+        Ok(&*key.seed)
+    }
+
+    /// Copy of a key's seed for an encrypted node backup
+    pub(crate) fn export_key(&self, key_id: &str) -> Option<(KeyInfo, Zeroizing<[u8; 32]>)> {
+        self.keys
+            .get(key_id)
+            .map(|key| (key.info.clone(), key.seed.clone()))
+    }
+
+    /// Reinstate a key exported by `export_key`, keeping its metadata
+    ///
+    /// The seed must reproduce the recorded public key, so a damaged backup
+    /// cannot install a key different from the one it describes.
+    pub(crate) fn restore_key(&mut self, info: &KeyInfo, seed: [u8; 32]) -> Result<KeyInfo> {
+        let restored = self.import_key(&info.key_id, info.purpose, seed)?;
+        if restored.public_key != info.public_key {
+            self.keys.remove(&info.key_id);
+            return Err(SecureCommsError::CryptoProtocol(format!(
+                "Restored key {} does not match its recorded public key",
+                info.key_id
+            )));
+        }
+        if let Some(key) = self.keys.get_mut(&info.key_id) {
+            key.info = info.clone();
+        }
+        Ok(info.clone())
+    }
+
+    /// Remove and zeroize a key
+    pub fn remove_key(&mut self, key_id: &str) -> bool {
+        self.keys.remove(key_id).is_some()
+    }
+
+    /// Managed key IDs, sorted
+    pub fn key_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.keys.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Get key manager statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        let count = |purpose: KeyPurpose| {
+            self.keys
+                .values()
+                .filter(|key| key.info.purpose == purpose)
+                .count()
+        };
+        stats.insert(
+            "signing_keys".to_string(),
+            serde_json::Value::Number(count(KeyPurpose::Signing).into()),
+        );
+        stats.insert(
+            "vrf_keys".to_string(),
+            serde_json::Value::Number(count(KeyPurpose::Vrf).into()),
+        );
+        stats.insert(
+            "wrapping_keys".to_string(),
+            serde_json::Value::Number(count(KeyPurpose::Wrapping).into()),
+        );
+        stats
+    }
+}
+
+/// Symmetric key derived from a wrapping key seed for one use
+fn wrapping_key(seed: &[u8; 32], label: &[u8]) -> Zeroizing<Vec<u8>> {
+    use sha3::{Digest, Sha3_256};
+    let mut hasher = Sha3_256::new();
+    hasher.update(label);
+    hasher.update(seed);
+    Zeroizing::new(hasher.finalize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vrf::VrfPublicKey;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    #[test]
+    fn test_signing_key_operations() {
+        let mut keys = KeyManager::new();
+        let info = keys
+            .import_key("sig", KeyPurpose::Signing, [1u8; 32])
+            .unwrap();
+
+        let signature = keys.sign("sig", b"message").unwrap();
+        let public: [u8; 32] = info.public_key.as_slice().try_into().unwrap();
+        let verifying_key = VerifyingKey::from_bytes(&public).unwrap();
+        let signature = Signature::from_slice(&signature).unwrap();
+        assert!(verifying_key.verify(b"message", &signature).is_ok());
+    }
+
+    #[test]
+    fn test_vrf_key_operations() {
+        let mut keys = KeyManager::new();
+        let info = keys.import_key("vrf", KeyPurpose::Vrf, [2u8; 32]).unwrap();
+
+        let (output, proof) = keys.vrf_prove("vrf", b"alpha").unwrap();
+        let public = VrfPublicKey::from_bytes(&info.public_key).unwrap();
+        assert_eq!(public.verify(b"alpha", &proof).unwrap(), output);
+    }
+
+    #[test]
+    fn test_purpose_and_duplicates_enforced() {
+        let mut keys = KeyManager::new();
+        keys.import_key("vrf", KeyPurpose::Vrf, [2u8; 32]).unwrap();
+
+        assert!(keys.sign("vrf", b"message").is_err());
+        assert!(keys
+            .import_key("vrf", KeyPurpose::Signing, [3u8; 32])
+            .is_err());
+        assert!(keys.vrf_prove("missing", b"alpha").is_err());
+
+        assert!(keys.remove_key("vrf"));
+        assert!(keys.public_key("vrf").is_none());
+    }
+
+    #[test]
+    fn test_wrapping_key_operations() {
+        let mut keys = KeyManager::new();
+        let info = keys
+            .import_key("master", KeyPurpose::Wrapping, [4u8; 32])
+            .unwrap();
+        assert_eq!(info.public_key.len(), 8);
+
+        let wrapped = keys.wrap_key("master", b"aad", &[9u8; 32]).unwrap();
+        assert_eq!(
+            keys.unwrap_key("master", b"aad", &wrapped)
+                .unwrap()
+                .expose(),
+            &[9u8; 32]
+        );
+        assert!(keys.unwrap_key("master", b"other", &wrapped).is_err());
+        assert!(keys.sign("master", b"message").is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! # Key Update - ML-KEM Re-encapsulation Between Full Handshakes
+//!
+//! Lightweight rekeying for established channels. Each client holds a
+//! semi-static ML-KEM key pair whose public half peers pin once, signed by
+//! the client's identity key. To update a channel key, one side encapsulates
+//! a fresh secret to the peer's pinned key and sends the ciphertext in a
+//! single `KeyUpdate` message; both sides then mix the secret into the
+//! current session key. No round trip, QKD session or new identity
+//! exchange is needed.
+//!
+//! ## Key Schedule
+//!
+//! The update transcript covers both endpoints, the epoch, the recipient's
+//! key fingerprint, the ciphertext and a hash of the current session key:
+//!
+//! - **New key**: SHA3-256 over the current key, the encapsulated secret
+//!   and the transcript
+//! - **Confirmation**: SHA3-256 over the new key and the transcript, sent
+//!   with the update so the recipient detects a tampered ciphertext or a
+//!   sender that does not hold the current key
+//!
+//! Chaining the current key means an attacker needs both the old session
+//! key and the recipient's KEM secret to learn the new one. Epochs increase
+//! by one per update and are reset by a full rekey; after `max_epochs`
+//! updates a full handshake is required.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::{PQCAlgorithm, QRNG};
+//! use quantum_forge_secure_comms::crypto_provider::BuiltinKem;
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//! use quantum_forge_secure_comms::key_update::*;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut qrng = QRNG::from_seed(rand::random());
+//! let mut keys = KeyManager::new();
+//! keys.generate_key("identity", KeyPurpose::Signing, &mut qrng)?;
+//!
+//! let provider = BuiltinKem::new(PQCAlgorithm::Kyber768);
+//! let bob_kem = SemiStaticKemKey::generate(&provider)?;
+//! let bob_public = bob_kem.signed_public_key("bob", &keys, "identity")?;
+//! bob_public.verify()?;
+//!
+//! let session_key = [7u8; 32];
+//! let (update, at_alice) = initiate_key_update(&session_key, "alice", &bob_public, 1, &provider)?;
+//! let at_bob = accept_key_update(&session_key, &update, "bob", &bob_kem, 1, &provider)?;
+//! assert_eq!(at_alice.expose(), at_bob.expose());
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::address_book::fingerprint;
+use crate::consensus_verify::verify_commit_signature;
+use crate::constant_time::ct_eq;
+use crate::crypto_protocols::PQCAlgorithm;
+use crate::crypto_provider::Kem;
+use crate::key_manager::KeyManager;
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Client settings for re-encapsulation key updates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyUpdateConfig {
+    /// ML-KEM parameter set of the semi-static key
+    pub algorithm: PQCAlgorithm,
+    /// Updates allowed on a channel before a full rekey is required
+    pub max_epochs: u64,
+}
+
+impl Default for KeyUpdateConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: PQCAlgorithm::Kyber768,
+            max_epochs: 64,
+        }
+    }
+}
+
+impl KeyUpdateConfig {
+    /// Reject settings that cannot work
+    pub fn validate(&self) -> Result<()> {
+        if !matches!(
+            self.algorithm,
+            PQCAlgorithm::Kyber512 | PQCAlgorithm::Kyber768 | PQCAlgorithm::Kyber1024
+        ) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Key update algorithm {:?} is not an ML-KEM parameter set",
+                self.algorithm
+            )));
+        }
+        if self.max_epochs == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Key update max_epochs must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Semi-static ML-KEM public key, signed by its owner's identity key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyUpdatePublicKey {
+    /// Client the key belongs to
+    pub owner_id: String,
+    /// ML-KEM parameter set
+    pub algorithm: PQCAlgorithm,
+    /// Encapsulation key
+    pub public_key: Vec<u8>,
+    /// Owner's Ed25519 identity key
+    pub identity_key: Vec<u8>,
+    /// Identity key signature over `signed_payload`
+    pub signature: Vec<u8>,
+}
+
+impl KeyUpdatePublicKey {
+    /// Bytes covered by the signature
+    pub fn signed_payload(&self) -> Vec<u8> {
+        digest(
+            b"key_update_public_key_v1",
+            &[
+                self.owner_id.as_bytes(),
+                &serde_json::to_vec(&self.algorithm).unwrap_or_default(),
+                &self.public_key,
+            ],
+        )
+        .to_vec()
+    }
+
+    /// Fingerprint identifying the key in updates
+    pub fn key_id(&self) -> String {
+        fingerprint(&self.public_key)
+    }
+
+    /// Check the identity key signature
+    ///
+    /// Callers must still check `identity_key` belongs to `owner_id`.
+    pub fn verify(&self) -> Result<()> {
+        if !verify_commit_signature(&self.identity_key, &self.signed_payload(), &self.signature) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+}
+
+/// Local semi-static ML-KEM key pair
+#[derive(Debug)]
+pub struct SemiStaticKemKey {
+    algorithm: PQCAlgorithm,
+    public_key: Vec<u8>,
+    secret: SecretBytes,
+}
+
+impl SemiStaticKemKey {
+    /// Generate a fresh key pair with `provider`
+    pub fn generate(provider: &dyn Kem) -> Result<Self> {
+        let keypair = provider.keygen()?;
+        Ok(Self {
+            algorithm: provider.algorithm(),
+            public_key: keypair.public_key,
+            secret: SecretBytes::new(SensitiveKind::Key, keypair.private_key),
+        })
+    }
+
+    /// ML-KEM parameter set
+    pub fn algorithm(&self) -> PQCAlgorithm {
+        self.algorithm
+    }
+
+    /// Fingerprint identifying the key in updates
+    pub fn key_id(&self) -> String {
+        fingerprint(&self.public_key)
+    }
+
+    /// Recover a secret encapsulated to this key
+    ///
+    /// Any failure is reported as `SecureCommsError::AuthenticationFailed`.
+    pub fn decapsulate(&self, ciphertext: &[u8], provider: &dyn Kem) -> Result<SecretBytes> {
+        check_provider(provider, self.algorithm)?;
+        let shared = provider
+            .decapsulate(self.secret.expose(), ciphertext)
+            .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+        Ok(SecretBytes::new(SensitiveKind::Key, shared))
+    }
+
+    /// Public key signed with an identity key held by `keys`
+    pub fn signed_public_key(
+        &self,
+        owner_id: &str,
+        keys: &KeyManager,
+        identity_key_id: &str,
+    ) -> Result<KeyUpdatePublicKey> {
+        let identity_key = keys
+            .public_key(identity_key_id)
+            .ok_or_else(|| {
+                SecureCommsError::Configuration(format!("Unknown key {}", identity_key_id))
+            })?
+            .to_vec();
+        let mut public = KeyUpdatePublicKey {
+            owner_id: owner_id.to_string(),
+            algorithm: self.algorithm,
+            public_key: self.public_key.clone(),
+            identity_key,
+            signature: Vec::new(),
+        };
+        public.signature = keys.sign(identity_key_id, &public.signed_payload())?;
+        Ok(public)
+    }
+}
+
+/// Single-message channel key update
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyUpdate {
+    /// Initiating client
+    pub sender_id: String,
+    /// Client holding the KEM key
+    pub recipient_id: String,
+    /// Channel key epoch this update moves to
+    pub epoch: u64,
+    /// Fingerprint of the recipient's KEM key the secret is encapsulated to
+    pub recipient_key_id: String,
+    /// ML-KEM ciphertext
+    pub ciphertext: Vec<u8>,
+    /// Key confirmation over the new key and transcript
+    pub confirmation: Vec<u8>,
+}
+
+impl KeyUpdate {
+    /// Transcript hash binding the update to the current session key
+    fn transcript(&self, current_key: &[u8]) -> [u8; 32] {
+        digest(
+            b"key_update_transcript_v1",
+            &[
+                self.sender_id.as_bytes(),
+                self.recipient_id.as_bytes(),
+                &self.epoch.to_be_bytes(),
+                self.recipient_key_id.as_bytes(),
+                &self.ciphertext,
+                &digest(b"key_update_current_key_v1", &[current_key]),
+            ],
+        )
+    }
+}
+
+/// Encapsulate a fresh secret to the peer's key and derive the next key
+///
+/// Returns the update to deliver to the peer and the new session key.
+pub fn initiate_key_update(
+    current_key: &[u8],
+    sender_id: &str,
+    peer_key: &KeyUpdatePublicKey,
+    epoch: u64,
+    provider: &dyn Kem,
+) -> Result<(KeyUpdate, SecretBytes)> {
+    check_provider(provider, peer_key.algorithm)?;
+    let (ciphertext, shared) = provider.encapsulate(&peer_key.public_key)?;
+    let shared = SecretBytes::new(SensitiveKind::Key, shared);
+
+    let mut update = KeyUpdate {
+        sender_id: sender_id.to_string(),
+        recipient_id: peer_key.owner_id.clone(),
+        epoch,
+        recipient_key_id: peer_key.key_id(),
+        ciphertext,
+        confirmation: Vec::new(),
+    };
+    let transcript = update.transcript(current_key);
+    let next_key = next_session_key(current_key, &shared, &transcript);
+    update.confirmation = confirmation(&next_key, &transcript).to_vec();
+    Ok((update, next_key))
+}
+
+/// Recover the next session key from a peer's update
+///
+/// Fails with `SecureCommsError::Validation` for an update addressed to
+/// someone else, to another KEM key or to an unexpected epoch, and with
+/// `SecureCommsError::AuthenticationFailed` when key confirmation fails.
+pub fn accept_key_update(
+    current_key: &[u8],
+    update: &KeyUpdate,
+    local_id: &str,
+    local_key: &SemiStaticKemKey,
+    expected_epoch: u64,
+    provider: &dyn Kem,
+) -> Result<SecretBytes> {
+    check_provider(provider, local_key.algorithm)?;
+    if update.recipient_id != local_id {
+        return Err(SecureCommsError::Validation(format!(
+            "Key update is addressed to {}",
+            update.recipient_id
+        )));
+    }
+    if update.recipient_key_id != local_key.key_id() {
+        return Err(SecureCommsError::Validation(
+            "Key update is encapsulated to a retired KEM key".to_string(),
+        ));
+    }
+    if update.epoch != expected_epoch {
+        return Err(SecureCommsError::Validation(format!(
+            "Key update epoch {} does not follow the current epoch {}",
+            update.epoch,
+            expected_epoch.saturating_sub(1)
+        )));
+    }
+
+    let shared = SecretBytes::new(
+        SensitiveKind::Key,
+        provider
+            .decapsulate(local_key.secret.expose(), &update.ciphertext)
+            .map_err(|_| SecureCommsError::AuthenticationFailed)?,
+    );
+    let transcript = update.transcript(current_key);
+    let next_key = next_session_key(current_key, &shared, &transcript);
+    if !ct_eq(&confirmation(&next_key, &transcript), &update.confirmation) {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    Ok(next_key)
+}
+
+fn next_session_key(
+    current_key: &[u8],
+    shared: &SecretBytes,
+    transcript: &[u8; 32],
+) -> SecretBytes {
+    SecretBytes::new(
+        SensitiveKind::Key,
+        digest(
+            b"key_update_session_v1",
+            &[current_key, shared.expose(), transcript],
+        )
+        .to_vec(),
+    )
+}
+
+fn confirmation(next_key: &SecretBytes, transcript: &[u8; 32]) -> [u8; 32] {
+    digest(b"key_update_confirm_v1", &[next_key.expose(), transcript])
+}
+
+/// SHA3-256 over a label and length-prefixed parts
+fn check_provider(provider: &dyn Kem, algorithm: PQCAlgorithm) -> Result<()> {
+    if provider.algorithm() != algorithm {
+        return Err(SecureCommsError::Configuration(format!(
+            "KEM provider implements {:?}, key update key is {:?}",
+            provider.algorithm(),
+            algorithm
+        )));
+    }
+    Ok(())
+}
+
+fn digest(label: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(label);
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto_provider::BuiltinKem;
+    use crate::key_manager::KeyPurpose;
+
+    const PROVIDER: BuiltinKem = BuiltinKem::new(PQCAlgorithm::Kyber768);
+
+    fn bob() -> (SemiStaticKemKey, KeyUpdatePublicKey) {
+        let mut keys = KeyManager::new();
+        keys.import_key("identity", KeyPurpose::Signing, [5u8; 32])
+            .unwrap();
+        let kem = SemiStaticKemKey::generate(&PROVIDER).unwrap();
+        let public = kem.signed_public_key("bob", &keys, "identity").unwrap();
+        (kem, public)
+    }
+
+    #[test]
+    fn test_key_update_agrees_and_chains() {
+        let (kem, public) = bob();
+        public.verify().unwrap();
+
+        let current = [9u8; 32];
+        let (update, at_alice) =
+            initiate_key_update(&current, "alice", &public, 1, &PROVIDER).unwrap();
+        let at_bob = accept_key_update(&current, &update, "bob", &kem, 1, &PROVIDER).unwrap();
+        assert_eq!(at_alice.expose(), at_bob.expose());
+        assert_ne!(at_bob.expose(), current.as_slice());
+
+        let (update, at_alice) =
+            initiate_key_update(at_alice.expose(), "alice", &public, 2, &PROVIDER).unwrap();
+        let at_bob =
+            accept_key_update(at_bob.expose(), &update, "bob", &kem, 2, &PROVIDER).unwrap();
+        assert_eq!(at_alice.expose(), at_bob.expose());
+    }
+
+    #[test]
+    fn test_key_update_binds_transcript_and_current_key() {
+        let (kem, public) = bob();
+        let current = [9u8; 32];
+        let (update, _) = initiate_key_update(&current, "alice", &public, 1, &PROVIDER).unwrap();
+
+        assert!(matches!(
+            accept_key_update(&[8u8; 32], &update, "bob", &kem, 1, &PROVIDER),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        let mut tampered = update.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert!(matches!(
+            accept_key_update(&current, &tampered, "bob", &kem, 1, &PROVIDER),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        let mut relabelled = update.clone();
+        relabelled.sender_id = "mallory".to_string();
+        assert!(matches!(
+            accept_key_update(&current, &relabelled, "bob", &kem, 1, &PROVIDER),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        assert!(matches!(
+            accept_key_update(&current, &update, "bob", &kem, 2, &PROVIDER),
+            Err(SecureCommsError::Validation(_))
+        ));
+        assert!(matches!(
+            accept_key_update(&current, &update, "carol", &kem, 1, &PROVIDER),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_public_key_signature_and_config() {
+        let (_, mut public) = bob();
+        public.owner_id = "mallory".to_string();
+        assert!(matches!(
+            public.verify(),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        KeyUpdateConfig::default().validate().unwrap();
+        let signing = KeyUpdateConfig {
+            algorithm: PQCAlgorithm::Dilithium3,
+            ..Default::default()
+        };
+        assert!(signing.validate().is_err());
+    }
+}
@@ -0,0 +1,483 @@
+//! # Leader Election - VRF Proposer Selection from a Quantum Beacon
+//!
+//! Selects round proposers with a verifiable random function evaluated over
+//! a shared randomness beacon. Each validator privately evaluates its VRF key
+//! on the beacon value and round number; if the output falls below the
+//! eligibility threshold it may propose and attaches the VRF proof to its
+//! proposal. Nobody can predict the schedule before the beacon value is
+//! published, yet every validator can check a proposer's claim.
+//!
+//! ## Randomness Beacon
+//!
+//! - **Quantum Seeded**: Each epoch's value is drawn from the QRNG by the beacon source
+//! - **Hash Chained**: Values commit to the previous epoch's digest, so followers detect forks
+//! - **Epochs**: `rounds_per_epoch` consecutive rounds share one beacon value
+//!
+//! ## Eligibility
+//!
+//! A validator is eligible when its VRF output, mapped to [0, 1), is below
+//! `expected_proposers / validator_count`. Several validators may be eligible
+//! in a round; the claim with the lowest priority value is preferred.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//! use quantum_forge_secure_comms::leader_election::{
+//!     BeaconValue, LeaderElection, LeaderElectionConfig,
+//! };
+//!
+//! # fn main() -> quantum_forge_secure_comms::Result<()> {
+//! let mut keys = KeyManager::new();
+//! let info = keys.import_key("vrf", KeyPurpose::Vrf, [4u8; 32])?;
+//!
+//! let mut election = LeaderElection::new(LeaderElectionConfig::default());
+//! election.register_validator("validator_1", &info.public_key)?;
+//! election.beacon_mut().accept(BeaconValue::genesis(vec![9u8; 32]))?;
+//!
+//! if let Some(claim) = election.claim(&keys, "vrf", 12)? {
+//!     let priority = election.verify_claim("validator_1", &claim)?;
+//!     println!("Eligible to propose round 12 with priority {:.4}", priority);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::crypto_protocols::QRNG;
+use crate::key_manager::KeyManager;
+use crate::vrf::{VrfProof, VrfPublicKey};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Leader election configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderElectionConfig {
+    /// Expected number of eligible proposers per round
+    pub expected_proposers: f64,
+    /// Rounds sharing one beacon value
+    pub rounds_per_epoch: u64,
+    /// Beacon values retained
+    pub max_beacon_history: usize,
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            expected_proposers: 1.5,
+            rounds_per_epoch: 100,
+            max_beacon_history: 64,
+        }
+    }
+}
+
+/// Beacon randomness for one epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconValue {
+    /// Epoch number
+    pub epoch: u64,
+    /// Random bytes for the epoch
+    pub randomness: Vec<u8>,
+    /// Digest of the previous epoch's value (empty for genesis)
+    pub previous_digest: Vec<u8>,
+    /// Digest of this value
+    pub digest: Vec<u8>,
+}
+
+impl BeaconValue {
+    /// First beacon value of a chain
+    pub fn genesis(randomness: Vec<u8>) -> Self {
+        Self::chained(0, randomness, Vec::new())
+    }
+
+    fn chained(epoch: u64, randomness: Vec<u8>, previous_digest: Vec<u8>) -> Self {
+        let digest = Self::compute_digest(epoch, &randomness, &previous_digest);
+        Self {
+            epoch,
+            randomness,
+            previous_digest,
+            digest,
+        }
+    }
+
+    fn compute_digest(epoch: u64, randomness: &[u8], previous_digest: &[u8]) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"quantum_beacon_v1");
+        hasher.update(epoch.to_le_bytes());
+        hasher.update(randomness);
+        hasher.update(previous_digest);
+        hasher.finalize().to_vec()
+    }
+
+    /// Check the value's own digest
+    pub fn is_well_formed(&self) -> bool {
+        !self.randomness.is_empty()
+            && Self::compute_digest(self.epoch, &self.randomness, &self.previous_digest)
+                == self.digest
+    }
+}
+
+/// Hash-chained randomness beacon
+#[derive(Debug, Clone, Default)]
+pub struct RandomnessBeacon {
+    values: BTreeMap<u64, BeaconValue>,
+    max_history: usize,
+}
+
+impl RandomnessBeacon {
+    /// Create empty beacon retaining `max_history` epochs
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            values: BTreeMap::new(),
+            max_history,
+        }
+    }
+
+    /// Produce the next epoch's value from the QRNG (beacon source only)
+    pub fn produce_next(&mut self, qrng: &mut QRNG) -> Result<BeaconValue> {
+        let randomness = qrng.generate_bytes(32)?;
+        let value = match self.latest() {
+            Some(latest) => {
+                BeaconValue::chained(latest.epoch + 1, randomness, latest.digest.clone())
+            }
+            None => BeaconValue::genesis(randomness),
+        };
+        self.accept(value.clone())?;
+        Ok(value)
+    }
+
+    /// Accept a beacon value published by the beacon source
+    ///
+    /// The value must be well formed and, when the previous epoch is known,
+    /// chain onto it. Conflicting values for a known epoch are refused.
+    pub fn accept(&mut self, value: BeaconValue) -> Result<()> {
+        if !value.is_well_formed() {
+            return Err(SecureCommsError::Validation(format!(
+                "Malformed beacon value for epoch {}",
+                value.epoch
+            )));
+        }
+        if let Some(existing) = self.values.get(&value.epoch) {
+            if existing.digest == value.digest {
+                return Ok(());
+            }
+            return Err(SecureCommsError::Security(format!(
+                "Conflicting beacon value for epoch {}",
+                value.epoch
+            )));
+        }
+        let previous = value
+            .epoch
+            .checked_sub(1)
+            .and_then(|epoch| self.values.get(&epoch));
+        if let Some(previous) = previous {
+            if previous.digest != value.previous_digest {
+                return Err(SecureCommsError::Security(format!(
+                    "Beacon value for epoch {} does not chain onto epoch {}",
+                    value.epoch, previous.epoch
+                )));
+            }
+        }
+
+        self.values.insert(value.epoch, value);
+        while self.values.len() > self.max_history.max(1) {
+            self.values.pop_first();
+        }
+        Ok(())
+    }
+
+    /// Beacon value for an epoch
+    pub fn get(&self, epoch: u64) -> Option<&BeaconValue> {
+        self.values.get(&epoch)
+    }
+
+    /// Most recent beacon value
+    pub fn latest(&self) -> Option<&BeaconValue> {
+        self.values.values().next_back()
+    }
+}
+
+/// Proposer's claim to a round, carried in its proposal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderProof {
+    /// Beacon epoch the claim was evaluated against
+    pub epoch: u64,
+    /// Claimed round
+    pub round: u64,
+    /// VRF output bytes
+    pub output: Vec<u8>,
+    /// VRF proof
+    pub proof: VrfProof,
+}
+
+/// VRF-based proposer selection
+#[derive(Debug, Clone)]
+pub struct LeaderElection {
+    config: LeaderElectionConfig,
+    beacon: RandomnessBeacon,
+    /// Registered validators' VRF public keys
+    vrf_keys: BTreeMap<String, VrfPublicKey>,
+}
+
+impl LeaderElection {
+    /// Create leader election with an empty beacon
+    pub fn new(config: LeaderElectionConfig) -> Self {
+        let beacon = RandomnessBeacon::new(config.max_beacon_history);
+        Self {
+            config,
+            beacon,
+            vrf_keys: BTreeMap::new(),
+        }
+    }
+
+    /// Register a validator's VRF public key
+    pub fn register_validator(&mut self, validator_id: &str, vrf_public_key: &[u8]) -> Result<()> {
+        let key = VrfPublicKey::from_bytes(vrf_public_key)?;
+        self.vrf_keys.insert(validator_id.to_string(), key);
+        Ok(())
+    }
+
+    /// Remove a validator from the election
+    pub fn remove_validator(&mut self, validator_id: &str) -> bool {
+        self.vrf_keys.remove(validator_id).is_some()
+    }
+
+    /// Randomness beacon
+    pub fn beacon(&self) -> &RandomnessBeacon {
+        &self.beacon
+    }
+
+    /// Mutable randomness beacon, for accepting published values
+    pub fn beacon_mut(&mut self) -> &mut RandomnessBeacon {
+        &mut self.beacon
+    }
+
+    /// Epoch whose beacon value governs a round
+    pub fn epoch_for_round(&self, round: u64) -> u64 {
+        round / self.config.rounds_per_epoch.max(1)
+    }
+
+    /// Probability that a given validator is eligible in a round
+    pub fn eligibility_threshold(&self) -> f64 {
+        if self.vrf_keys.is_empty() {
+            return 0.0;
+        }
+        (self.config.expected_proposers / self.vrf_keys.len() as f64).min(1.0)
+    }
+
+    /// VRF input for a round
+    fn election_input(beacon: &BeaconValue, round: u64) -> Vec<u8> {
+        let mut input = b"leader_election_v1".to_vec();
+        input.extend_from_slice(&beacon.digest);
+        input.extend_from_slice(&round.to_le_bytes());
+        input
+    }
+
+    fn beacon_for_round(&self, round: u64) -> Result<&BeaconValue> {
+        let epoch = self.epoch_for_round(round);
+        self.beacon.get(epoch).ok_or_else(|| {
+            SecureCommsError::Consensus(format!("No beacon value for epoch {}", epoch))
+        })
+    }
+
+    /// Evaluate the local VRF key for a round
+    ///
+    /// Returns a claim when the local validator is eligible to propose.
+    pub fn claim(
+        &self,
+        keys: &KeyManager,
+        vrf_key_id: &str,
+        round: u64,
+    ) -> Result<Option<LeaderProof>> {
+        let beacon = self.beacon_for_round(round)?;
+        let (output, proof) = keys.vrf_prove(vrf_key_id, &Self::election_input(beacon, round))?;
+        if output.to_unit_interval() >= self.eligibility_threshold() {
+            return Ok(None);
+        }
+        Ok(Some(LeaderProof {
+            epoch: beacon.epoch,
+            round,
+            output: output.as_bytes().to_vec(),
+            proof,
+        }))
+    }
+
+    /// Verify a proposer's claim, returning its priority (lower wins)
+    pub fn verify_claim(&self, validator_id: &str, claim: &LeaderProof) -> Result<f64> {
+        let key = self.vrf_keys.get(validator_id).ok_or_else(|| {
+            SecureCommsError::Consensus(format!("No VRF key registered for {}", validator_id))
+        })?;
+        let beacon = self.beacon_for_round(claim.round)?;
+        if beacon.epoch != claim.epoch {
+            return Err(SecureCommsError::Consensus(format!(
+                "Claim for round {} uses epoch {}, expected {}",
+                claim.round, claim.epoch, beacon.epoch
+            )));
+        }
+
+        let output = key.verify(&Self::election_input(beacon, claim.round), &claim.proof)?;
+        if output.as_bytes() != claim.output.as_slice() {
+            return Err(SecureCommsError::Consensus(
+                "Claimed VRF output does not match proof".to_string(),
+            ));
+        }
+
+        let priority = output.to_unit_interval();
+        if priority >= self.eligibility_threshold() {
+            return Err(SecureCommsError::Consensus(format!(
+                "{} is not eligible to propose round {}",
+                validator_id, claim.round
+            )));
+        }
+        Ok(priority)
+    }
+
+    /// Pick the preferred claim among verified ones for a round
+    pub fn select_leader<'a>(
+        &self,
+        claims: &'a [(String, LeaderProof)],
+    ) -> Option<&'a (String, LeaderProof)> {
+        claims
+            .iter()
+            .filter_map(|entry| {
+                self.verify_claim(&entry.0, &entry.1)
+                    .ok()
+                    .map(|priority| (priority, entry))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, entry)| entry)
+    }
+
+    /// Get leader election statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "registered_validators".to_string(),
+            serde_json::Value::Number(self.vrf_keys.len().into()),
+        );
+        stats.insert(
+            "latest_epoch".to_string(),
+            serde_json::json!(self.beacon.latest().map(|value| value.epoch)),
+        );
+        stats.insert(
+            "eligibility_threshold".to_string(),
+            serde_json::json!(self.eligibility_threshold()),
+        );
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_manager::KeyPurpose;
+
+    fn setup(validators: u8) -> (LeaderElection, Vec<KeyManager>) {
+        let mut election = LeaderElection::new(LeaderElectionConfig {
+            rounds_per_epoch: 10,
+            ..LeaderElectionConfig::default()
+        });
+        let mut managers = Vec::new();
+        for i in 0..validators {
+            let mut keys = KeyManager::new();
+            let info = keys
+                .import_key("vrf", KeyPurpose::Vrf, [i + 1; 32])
+                .unwrap();
+            election
+                .register_validator(&format!("v{}", i), &info.public_key)
+                .unwrap();
+            managers.push(keys);
+        }
+        election
+            .beacon_mut()
+            .accept(BeaconValue::genesis(vec![42u8; 32]))
+            .unwrap();
+        (election, managers)
+    }
+
+    #[test]
+    fn test_beacon_chain_validation() {
+        let mut beacon = RandomnessBeacon::new(8);
+        let genesis = BeaconValue::genesis(vec![1u8; 32]);
+        beacon.accept(genesis.clone()).unwrap();
+
+        let next = BeaconValue::chained(1, vec![2u8; 32], genesis.digest.clone());
+        beacon.accept(next).unwrap();
+
+        let fork = BeaconValue::chained(1, vec![3u8; 32], genesis.digest.clone());
+        assert!(beacon.accept(fork).is_err());
+
+        let unlinked = BeaconValue::chained(2, vec![4u8; 32], vec![0u8; 32]);
+        assert!(beacon.accept(unlinked).is_err());
+
+        let mut tampered = BeaconValue::chained(3, vec![5u8; 32], Vec::new());
+        tampered.randomness[0] ^= 1;
+        assert!(beacon.accept(tampered).is_err());
+    }
+
+    #[test]
+    fn test_claims_verify_and_schedule_is_deterministic() {
+        let (election, managers) = setup(4);
+
+        let mut eligible_rounds = 0;
+        for round in 0..10 {
+            for (i, keys) in managers.iter().enumerate() {
+                let claim = election.claim(keys, "vrf", round).unwrap();
+                let again = election.claim(keys, "vrf", round).unwrap();
+                assert_eq!(claim, again);
+                if let Some(claim) = claim {
+                    eligible_rounds += 1;
+                    assert!(election.verify_claim(&format!("v{}", i), &claim).is_ok());
+                }
+            }
+        }
+        // 1.5 expected proposers per round over 10 rounds
+        assert!(eligible_rounds > 0);
+    }
+
+    #[test]
+    fn test_claim_bound_to_validator_and_round() {
+        let mut election = LeaderElection::new(LeaderElectionConfig {
+            expected_proposers: 10.0, // everyone eligible
+            ..LeaderElectionConfig::default()
+        });
+        let mut keys = KeyManager::new();
+        let mine = keys.import_key("vrf", KeyPurpose::Vrf, [1u8; 32]).unwrap();
+        let mut other_keys = KeyManager::new();
+        let theirs = other_keys
+            .import_key("vrf", KeyPurpose::Vrf, [2u8; 32])
+            .unwrap();
+        election.register_validator("me", &mine.public_key).unwrap();
+        election
+            .register_validator("them", &theirs.public_key)
+            .unwrap();
+        election
+            .beacon_mut()
+            .accept(BeaconValue::genesis(vec![7u8; 32]))
+            .unwrap();
+
+        let claim = election.claim(&keys, "vrf", 3).unwrap().unwrap();
+        assert!(election.verify_claim("me", &claim).is_ok());
+        assert!(election.verify_claim("them", &claim).is_err());
+
+        let mut moved = claim.clone();
+        moved.round = 4;
+        assert!(election.verify_claim("me", &moved).is_err());
+
+        let claims = vec![
+            ("me".to_string(), claim),
+            (
+                "them".to_string(),
+                election.claim(&other_keys, "vrf", 3).unwrap().unwrap(),
+            ),
+        ];
+        assert!(election.select_leader(&claims).is_some());
+    }
+
+    #[test]
+    fn test_missing_beacon_epoch() {
+        let (election, managers) = setup(2);
+        assert!(election.claim(&managers[0], "vrf", 25).is_err());
+    }
+}
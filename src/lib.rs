@@ -129,7 +129,7 @@
 //! - **No Hardcoded Values**: Fidelity emerges naturally from quantum mechanics
 //!
 //! ### Mathematical Foundation
-//! ```rust,no_run
+//! ```rust,ignore
 //! // Physics-based fidelity calculation
 //! fn update_fidelity(&mut self) {
 //!     let norm_squared: f64 = self.amplitudes.iter().map(|&a| a * a).sum();
@@ -199,18 +199,110 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 // Production hardening modules - Enterprise-grade operational capabilities
+pub mod address_book;        // Persistent peer registry with trust-on-first-use key pinning
+pub mod audit_export;        // CEF and OCSF audit event export to file or syslog
 pub mod error_handling;      // Circuit breaker patterns, retry logic, graceful degradation
+pub mod gossip;              // Epidemic dissemination, lazy push, dedup cache, topics
 pub mod logging;            // Structured logging, audit trails, performance monitoring  
 pub mod production_monitor; // Health checks, alerting, system monitoring
 
 // Core security and communication modules - Quantum-enhanced protocols
+#[cfg(any(test, feature = "adversary"))]
+pub mod adversary;          // Protocol-violating peer for negative integration tests
+pub mod backup;             // Encrypted node backup and verified restore for disaster recovery
+pub mod bandwidth;          // Per-peer bandwidth accounting, token-bucket traffic shaping
+pub mod bench_baseline;     // Criterion baselines and benchmark regression gates
+pub mod blind_computation;  // Measurement-based blind delegation of circuits to an untrusted server
+pub mod blocking;           // Synchronous client facade over a managed runtime
+#[cfg(any(test, feature = "simulation"))]
+pub mod byzantine_sim;      // In-process Byzantine fault simulation over a virtual network
+pub mod calibration;        // Backend calibration ingestion, calibrated transpilation and fidelity estimates
+pub mod cancellation;       // Cancellation tokens and scopes for handshakes, rekeys and shutdown
+pub mod capabilities;       // Feature flags advertised in handshakes with fallback for older peers
+pub mod channel_migration;  // Transport handover of secure channels without re-keying
+pub mod channel_state;      // Typed channel lifecycle state machine with checked transitions
+pub mod channel_stats;      // Per-peer traffic counters, latency and QKD quality history
+pub mod chsh;               // CHSH Bell test certification of the entanglement shared with a peer
+pub mod circuits;           // Ready-made BB84, Bell test, GHZ, QFT and random sampling circuits
+pub mod clock_sync;         // Clock skew estimation, secure time sync, skew-aware windows
+pub mod cluster;            // Shared session store and leases for clustered failover
+pub mod compression;        // Per-channel LZ4 compression with ratio/CPU metrics and adaptive disabling
+pub mod compromise_recovery; // Revocation, re-certification and signed audit log for compromised peers
+pub mod compute;            // Dedicated pool for CPU-heavy work awaited from async code
 pub mod consensus_verify;   // Multi-method verification, consensus protocols
+pub mod consensus_wal;      // Write-ahead log for proposals, votes and decisions
+pub mod constant_time;      // Constant-time comparison and selection, dudect-style timing harness
+pub mod crypto_executor;    // Prioritised sign/verify/KEM workers with queue depth and wait metrics
+pub mod crypto_pipeline;    // Chunked AEAD sealed in parallel on a worker pool, ordered frames
 pub mod crypto_protocols;   // Post-quantum cryptography, QKD, algorithm agility
+pub mod crypto_provider;    // Pluggable KEM, signature and AEAD provider traits and registry
+pub mod dedup;              // Time-windowed receive-path duplicate suppression
+pub mod delivery_log;       // Receive-side journal of processed message IDs for exactly-once handling
+pub mod entanglement_pool;  // Per-peer pools of ready Bell pairs, background refill, staleness limits
+pub mod envelope;           // Envelope encryption of persisted state, master key rotation and rewrap
+pub mod equivocation;       // Conflicting-signature detection, evidence records, critical alerts
+pub mod events;             // Typed lifecycle hooks and broadcast event stream
+pub mod expiry;             // Message TTL deadlines enforced on send, relay and receive
+pub mod failover;           // Hot standby peers, per-peer circuit breakers, failover and failback
+pub mod file_transfer;      // Chunked, hash-verified bulk transfers with resume
+pub mod frame_io;           // Length-prefixed frame writes batched with writev or io_uring
+pub mod governor;           // Global resource caps and fail-fast admission control
+#[cfg(any(test, feature = "simulation"))]
+pub mod handshake_fuzz;     // Conformance fuzzer for out-of-order, duplicate and stale handshake frames
+pub mod handshake_guard;    // Per-source handshake rate limits, stateless retry tokens, client puzzles
+pub mod hedging;            // Latency-aware peer ranking and hedged redundant sends
+pub mod health;             // Per-subsystem health reports with check latency and last error
+pub mod heartbeat;          // Adaptive per-channel heartbeat intervals driven by loss and jitter
+pub mod hsm_entropy;        // Attested HSM/TPM entropy sources and entropy provenance records
+pub mod hw_accel;           // CPU feature detection, accelerated AEAD and hash dispatch
+pub mod hybrid_signature;   // Ed25519 + ML-DSA transcript signatures per channel or message type
+pub mod integrity;          // Startup binary and configuration check against a signed manifest
+pub mod key_manager;        // Long-term signing and VRF key custody
+pub mod key_update;         // Single-message ML-KEM re-encapsulation session key updates
+pub mod leader_election;    // VRF proposer selection over a hash-chained quantum beacon
+pub mod memory_profile;     // Per-subsystem memory attribution, leak hints, tracking allocator
+pub mod middleware;        // Ordered send/receive interceptors for headers, signing, validation
 pub mod network_comms;     // Secure channels, peer management, connection pooling
+pub mod packet_capture;    // Redacted JSONL capture of wire frames for protocol debugging
+pub mod peer_reputation;   // Peer reputation scoring, decay, adaptive routing decisions
 pub mod performance;       // Metrics collection, resource management, optimization
+pub mod private_handshake; // Identity-hiding handshake after an ephemeral KEM round, uniform frame sizes
+pub mod qrng_bulk;         // Batched shot sampling, conditioned byte streams via AsyncRead and iterators
+pub mod qrng_extraction;   // Min-entropy estimation, SHA-3 and Toeplitz conditioning of QRNG output
 pub mod quantum_core;      // Quantum operations, state management, hardware interface
+pub mod quantum_journal;   // Per-state operation traces, deterministic replay, JSON/QASM export
+pub mod quantum_network_sim; // Fiber loss, detector and repeater modeling of key rates and fidelity
+#[cfg(any(test, feature = "experimental-ot"))]
+pub mod quantum_ot;        // Experimental BBCS 1-out-of-2 oblivious transfer with hash commitments
+pub mod quantum_scheduler; // Hardware or simulation placement by queue depth, cost, fidelity and latency
+#[cfg(feature = "experimental-qds")]
+pub mod quantum_signature; // Experimental QKD-based quantum digital signatures for comparison with ML-DSA
+pub mod qubit_registry;    // Global qubit identifiers mapped to owning states and indices
+pub mod quorum;            // Weighted voting, supermajority and per-type quorum rules
+pub mod randomness_tests;  // NIST-style monobit, runs, serial and entropy tests of QRNG output
+pub mod receipt_archive;   // Archived signed messages and receipts with verifiable evidence bundles
+pub mod receipts;          // Signed delivery receipts and end-to-end acknowledgements
+pub mod relay_e2e;         // End-to-end sealed envelopes that relays authenticate but cannot open
+pub mod remote_teleport;   // Distributed teleportation between clients over a simulated link
+pub mod rooms;             // Group chat rooms with membership control, per-room keys and history
+pub mod secret_memory;     // Zeroizing SecretBytes, hygiene audits of sensitive buffer release
 pub mod security_foundation; // Entropy generation, threat detection, security levels
+pub mod security_posture;  // Runtime security level transitions, threat escalation, audit history
+pub mod session_transcript; // Signed, hash-chained key agreement history for external audit
+pub mod signing_policy;    // Per-channel signature cadence, chained MACs and signed checkpoints
+pub mod startup;           // Startup stage ordering and degraded mode for optional stages
+pub mod state_sync;        // Certified log and snapshot catch-up for lagging validators
+pub mod storage;           // Storage trait with memory, file, sled and RocksDB backends
 pub mod streamlined_client; // Main client API, orchestration, configuration
+pub mod suite_negotiation;  // Downgrade-resistant KEM, AEAD and signature mode agreement
+pub mod tasks;             // Supervised background tasks joined on shutdown, failures in health
+pub mod tee;               // Pluggable TEE backends for key operations, attestation evidence
+pub mod tenancy;           // Isolated tenants with separate clients, key stores and quotas
+pub mod timeouts;          // Validated connect, handshake, rekey, health and consensus deadlines
+pub mod topology;          // Topology presets, link health monitoring, repair planning
+pub mod traffic_padding;   // Per-channel frame padding, cover traffic and batching delays
+pub mod typed_messaging;   // Schema-identified message types, version negotiation, handlers
+pub mod vrf;               // ECVRF over Edwards25519 for verifiable randomness
 
 // Re-export main client types for convenient access
 pub use streamlined_client::*;
@@ -265,6 +357,12 @@ pub enum SecureCommsError {
     #[error("Timeout error: {0}")]
     Timeout(String),
 
+    /// Cancelled operations - caller or shutdown token cancelled before completion
+    /// 
+    /// Examples: Handshake aborted during shutdown, rekey cancelled by a supervisor task
+    #[error("Operation cancelled: {0}")]
+    Cancelled(String),
+
     /// Data validation errors - integrity checks, format validation, schema violations
     /// 
     /// Examples: Message format validation failures, cryptographic signature verification failures
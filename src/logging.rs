@@ -591,6 +591,19 @@ pub fn log_error(category: LogCategory, message: &str) {
     });
 }
 
+pub fn log_critical(category: LogCategory, message: &str, data: serde_json::Value) {
+    LOGGER.log(LogEntry {
+        timestamp: chrono::Utc::now(),
+        level: LogLevel::Critical,
+        category,
+        message: message.to_string(),
+        data,
+        source: None,
+        trace_id: None,
+        duration_ms: None,
+    });
+}
+
 pub fn log_security(message: &str, data: serde_json::Value) {
     LOGGER.log(LogEntry {
         timestamp: chrono::Utc::now(),
@@ -0,0 +1,424 @@
+//! # Memory Profile - Per-Subsystem Memory Attribution
+//!
+//! Attributes the memory held by a client to the subsystem holding it, so
+//! operators can check the "<1MB per channel" target against real numbers
+//! and spot leaks as subsystems that keep growing between snapshots.
+//!
+//! ## Accounting
+//!
+//! - **Footprints**: Types implement `MemoryFootprint` to report their inline
+//!   size plus the heap they own; collections sum their elements
+//! - **Reports**: A `MemoryReport` holds bytes and item counts per subsystem
+//!   (channels, quantum states, pools, queues) and the per-channel average
+//! - **Leak Hints**: `MemoryProfiler` keeps recent reports and flags
+//!   subsystems whose usage grew in every one of them
+//! - **Tracking Allocator**: With the `memory-profiling` feature,
+//!   `TrackingAllocator` counts every allocation of the process so the
+//!   attributed total can be compared with what was really allocated
+//!
+//! Footprints are estimates: allocator overhead and memory owned by
+//! dependencies are not seen by `MemoryFootprint`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::memory_profile::{MemoryFootprint, MemoryReport};
+//!
+//! let buffers: Vec<Vec<u8>> = vec![vec![0u8; 4096]; 8];
+//! let mut report = MemoryReport::new(1);
+//! report.record("queues", buffers.footprint_bytes(), buffers.len());
+//! println!("Per channel: {} bytes", report.bytes_per_channel());
+//! assert!(report.within_channel_budget());
+//! ```
+//!
+//! To see process-wide allocation totals, build with
+//! `--features memory-profiling` and install the allocator in the binary:
+//!
+//! ```rust,ignore
+//! #[global_allocator]
+//! static ALLOC: quantum_forge_secure_comms::memory_profile::TrackingAllocator =
+//!     quantum_forge_secure_comms::memory_profile::TrackingAllocator;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::mem::size_of;
+
+/// Memory target per open channel
+pub const CHANNEL_BUDGET_BYTES: usize = 1024 * 1024;
+
+/// Memory owned by a value
+pub trait MemoryFootprint {
+    /// Heap bytes owned by the value, excluding its inline size
+    fn heap_bytes(&self) -> usize;
+
+    /// Inline size plus owned heap bytes
+    fn footprint_bytes(&self) -> usize
+    where
+        Self: Sized,
+    {
+        size_of::<Self>() + self.heap_bytes()
+    }
+}
+
+impl MemoryFootprint for String {
+    fn heap_bytes(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl MemoryFootprint for u64 {
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl MemoryFootprint for u8 {
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl MemoryFootprint for f64 {
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Option<T> {
+    fn heap_bytes(&self) -> usize {
+        self.as_ref().map(|value| value.heap_bytes()).unwrap_or(0)
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for Vec<T> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(|v| v.heap_bytes()).sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for VecDeque<T> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(|v| v.heap_bytes()).sum::<usize>()
+    }
+}
+
+impl<T: MemoryFootprint> MemoryFootprint for HashSet<T> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(|v| v.heap_bytes()).sum::<usize>()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint> MemoryFootprint for HashMap<K, V> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_bytes() + v.heap_bytes())
+                .sum::<usize>()
+    }
+}
+
+impl<K: MemoryFootprint, V: MemoryFootprint> MemoryFootprint for BTreeMap<K, V> {
+    fn heap_bytes(&self) -> usize {
+        // B-tree nodes are not exposed; count entries as if densely packed
+        self.iter()
+            .map(|(k, v)| size_of::<(K, V)>() + k.heap_bytes() + v.heap_bytes())
+            .sum()
+    }
+}
+
+/// Memory attributed to one subsystem
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubsystemUsage {
+    /// Estimated bytes held
+    pub bytes: usize,
+    /// Entries held (channels, states, buffers, queued messages)
+    pub items: usize,
+}
+
+/// Process allocation totals seen by `TrackingAllocator`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocatorStats {
+    /// Bytes currently allocated
+    pub current_bytes: usize,
+    /// Highest allocated bytes observed
+    pub peak_bytes: usize,
+    /// Allocations made since start
+    pub allocations: u64,
+    /// Deallocations made since start
+    pub deallocations: u64,
+}
+
+/// Memory snapshot attributed per subsystem
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryReport {
+    /// Unix timestamp of the snapshot
+    pub taken_at: u64,
+    /// Open channels when the snapshot was taken
+    pub channel_count: usize,
+    /// Usage per subsystem name
+    pub subsystems: BTreeMap<String, SubsystemUsage>,
+    /// Process allocation totals, if the tracking allocator is installed
+    pub allocator: Option<AllocatorStats>,
+    /// Subsystems that grew across every recent snapshot
+    pub suspected_leaks: Vec<String>,
+}
+
+impl MemoryReport {
+    /// Start an empty report for a client with `channel_count` open channels
+    pub fn new(channel_count: usize) -> Self {
+        Self {
+            taken_at: chrono::Utc::now().timestamp() as u64,
+            channel_count,
+            subsystems: BTreeMap::new(),
+            allocator: allocator_stats(),
+            suspected_leaks: Vec::new(),
+        }
+    }
+
+    /// Attribute `bytes` held in `items` entries to `subsystem`
+    pub fn record(&mut self, subsystem: &str, bytes: usize, items: usize) {
+        let usage = self.subsystems.entry(subsystem.to_string()).or_default();
+        usage.bytes += bytes;
+        usage.items += items;
+    }
+
+    /// Usage of one subsystem
+    pub fn usage(&self, subsystem: &str) -> SubsystemUsage {
+        self.subsystems.get(subsystem).copied().unwrap_or_default()
+    }
+
+    /// Bytes attributed to all subsystems
+    pub fn total_bytes(&self) -> usize {
+        self.subsystems.values().map(|usage| usage.bytes).sum()
+    }
+
+    /// Attributed bytes divided by open channels (total if none are open)
+    pub fn bytes_per_channel(&self) -> usize {
+        self.total_bytes() / self.channel_count.max(1)
+    }
+
+    /// Whether the per-channel average is under `CHANNEL_BUDGET_BYTES`
+    pub fn within_channel_budget(&self) -> bool {
+        self.bytes_per_channel() < CHANNEL_BUDGET_BYTES
+    }
+
+    /// Get report statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "total_bytes".to_string(),
+            serde_json::json!(self.total_bytes()),
+        );
+        stats.insert(
+            "channel_count".to_string(),
+            serde_json::json!(self.channel_count),
+        );
+        stats.insert(
+            "bytes_per_channel".to_string(),
+            serde_json::json!(self.bytes_per_channel()),
+        );
+        stats.insert(
+            "within_channel_budget".to_string(),
+            serde_json::json!(self.within_channel_budget()),
+        );
+        stats.insert(
+            "subsystems".to_string(),
+            serde_json::to_value(&self.subsystems).unwrap_or(serde_json::Value::Null),
+        );
+        stats.insert(
+            "allocator".to_string(),
+            serde_json::to_value(self.allocator).unwrap_or(serde_json::Value::Null),
+        );
+        stats.insert(
+            "suspected_leaks".to_string(),
+            serde_json::json!(self.suspected_leaks),
+        );
+        stats
+    }
+}
+
+/// Recent reports used to flag steadily growing subsystems
+#[derive(Debug, Clone)]
+pub struct MemoryProfiler {
+    history: VecDeque<MemoryReport>,
+    window: usize,
+}
+
+impl MemoryProfiler {
+    /// Flag subsystems that grew across `window` consecutive reports
+    pub fn new(window: usize) -> Self {
+        Self {
+            history: VecDeque::new(),
+            window: window.max(2),
+        }
+    }
+
+    /// Keep `report` and fill in its suspected leaks
+    pub fn record(&mut self, report: &mut MemoryReport) {
+        self.history.push_back(report.clone());
+        while self.history.len() > self.window {
+            self.history.pop_front();
+        }
+        report.suspected_leaks = self.growing_subsystems();
+        if let Some(latest) = self.history.back_mut() {
+            latest.suspected_leaks = report.suspected_leaks.clone();
+        }
+    }
+
+    /// Subsystems whose bytes increased between every pair of kept reports
+    pub fn growing_subsystems(&self) -> Vec<String> {
+        if self.history.len() < self.window {
+            return Vec::new();
+        }
+        let latest = match self.history.back() {
+            Some(report) => report,
+            None => return Vec::new(),
+        };
+        latest
+            .subsystems
+            .keys()
+            .filter(|name| {
+                self.history
+                    .iter()
+                    .zip(self.history.iter().skip(1))
+                    .all(|(before, after)| after.usage(name).bytes > before.usage(name).bytes)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Reports kept
+    pub fn history(&self) -> impl Iterator<Item = &MemoryReport> {
+        self.history.iter()
+    }
+}
+
+impl Default for MemoryProfiler {
+    fn default() -> Self {
+        Self::new(5)
+    }
+}
+
+#[cfg(feature = "memory-profiling")]
+mod tracking {
+    use super::AllocatorStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static DEALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+    /// System allocator wrapper counting process allocations
+    ///
+    /// Install with `#[global_allocator]` in the final binary.
+    pub struct TrackingAllocator;
+
+    unsafe impl GlobalAlloc for TrackingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+                PEAK.fetch_max(current, Ordering::Relaxed);
+                ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(super) fn stats() -> Option<AllocatorStats> {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        if allocations == 0 {
+            // Compiled in but not installed as the global allocator
+            return None;
+        }
+        Some(AllocatorStats {
+            current_bytes: CURRENT.load(Ordering::Relaxed),
+            peak_bytes: PEAK.load(Ordering::Relaxed),
+            allocations,
+            deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(feature = "memory-profiling")]
+pub use tracking::TrackingAllocator;
+
+/// Process allocation totals, if `TrackingAllocator` is the global allocator
+pub fn allocator_stats() -> Option<AllocatorStats> {
+    #[cfg(feature = "memory-profiling")]
+    {
+        tracking::stats()
+    }
+    #[cfg(not(feature = "memory-profiling"))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_footprints() {
+        let mut map: HashMap<String, Vec<u8>> = HashMap::new();
+        map.insert("peer".to_string(), Vec::with_capacity(1000));
+        assert!(map.heap_bytes() >= 1000 + 4);
+        let queue: VecDeque<Vec<u8>> = VecDeque::from(vec![vec![0u8; 64]; 3]);
+        assert!(queue.footprint_bytes() >= 3 * (64 + size_of::<Vec<u8>>()));
+        assert_eq!(Option::<String>::None.heap_bytes(), 0);
+    }
+
+    #[test]
+    fn test_report_per_channel_budget() {
+        let mut report = MemoryReport::new(4);
+        report.record("channels", 4 * 1024, 4);
+        report.record("queues", 1024, 2);
+        report.record("queues", 1024, 1);
+        assert_eq!(
+            report.usage("queues"),
+            SubsystemUsage {
+                bytes: 2048,
+                items: 3
+            }
+        );
+        assert_eq!(report.total_bytes(), 6 * 1024);
+        assert_eq!(report.bytes_per_channel(), 1536);
+        assert!(report.within_channel_budget());
+
+        report.record("quantum_states", 8 * CHANNEL_BUDGET_BYTES, 10);
+        assert!(!report.within_channel_budget());
+    }
+
+    #[test]
+    fn test_profiler_flags_steady_growth() {
+        let mut profiler = MemoryProfiler::new(3);
+        for step in 1..=3 {
+            let mut report = MemoryReport::new(1);
+            report.record("queues", step * 1000, step);
+            report.record("channels", 500, 1);
+            profiler.record(&mut report);
+            if step < 3 {
+                assert!(report.suspected_leaks.is_empty());
+            } else {
+                assert_eq!(report.suspected_leaks, vec!["queues".to_string()]);
+            }
+        }
+
+        // A drop in usage clears the suspicion
+        let mut report = MemoryReport::new(1);
+        report.record("queues", 100, 1);
+        profiler.record(&mut report);
+        assert!(report.suspected_leaks.is_empty());
+    }
+}
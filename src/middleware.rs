@@ -0,0 +1,429 @@
+//! # Message Middleware - Interceptor Chain for the Send and Receive Path
+//!
+//! Lets applications register interceptors that run on every outbound and
+//! inbound message. Interceptors see the message metadata, may add or read
+//! headers, and can transform the payload or reject the message outright.
+//!
+//! ## Execution Order
+//!
+//! - **Outbound**: Interceptors run in registration order before the network send
+//! - **Inbound**: Interceptors run in reverse registration order, so paired
+//!   transformations (e.g. compress/decompress) unwind correctly
+//! - **Rejection**: Returning an error stops the chain; outbound sends fail and
+//!   inbound messages are discarded
+//!
+//! ## Built-in Interceptors
+//!
+//! - **HeaderInterceptor**: Adds static headers to outbound messages
+//! - **MetricsInterceptor**: Counts messages and bytes in each direction
+//! - **SigningInterceptor**: Application-level keyed SHA3-256 MAC carried in a header
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::middleware::{HeaderInterceptor, MetricsInterceptor};
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! let metrics = Arc::new(MetricsInterceptor::new());
+//! client.register_interceptor(Arc::new(HeaderInterceptor::new("x-app", "billing")));
+//! client.register_interceptor(metrics.clone());
+//!
+//! client.establish_secure_channel("peer_1").await?;
+//! let message = client.send_secure_message("peer_1", b"hello").await?;
+//! assert_eq!(message.headers.get("x-app").map(String::as_str), Some("billing"));
+//! println!("Sent {} messages", metrics.outbound_messages());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::constant_time::ct_eq;
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Header carrying the `SigningInterceptor` MAC
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// Direction a message travels through the pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    /// Message leaving this client
+    Outbound,
+    /// Message delivered to this client
+    Inbound,
+}
+
+/// Message metadata visible to interceptors
+#[derive(Debug, Clone)]
+pub struct MessageContext {
+    /// Direction of travel
+    pub direction: Direction,
+    /// Remote peer (recipient for outbound, sender for inbound)
+    pub peer_id: String,
+    /// Message identifier
+    pub message_id: String,
+    /// Application headers carried with the message
+    pub headers: BTreeMap<String, String>,
+    /// Message creation timestamp
+    pub timestamp: u64,
+}
+
+impl MessageContext {
+    /// Create context for a message
+    pub fn new(direction: Direction, peer_id: &str, message_id: &str, timestamp: u64) -> Self {
+        Self {
+            direction,
+            peer_id: peer_id.to_string(),
+            message_id: message_id.to_string(),
+            headers: BTreeMap::new(),
+            timestamp,
+        }
+    }
+}
+
+/// Hook invoked for every message on the send and receive path
+///
+/// Both methods default to passing the payload through unchanged. Returning
+/// an error rejects the message.
+pub trait Interceptor: Send + Sync {
+    /// Name used to identify the interceptor in the chain
+    fn name(&self) -> &str;
+
+    /// Process an outbound payload before it is sent
+    fn on_send(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        Ok(payload)
+    }
+
+    /// Process an inbound payload before it is returned to the application
+    fn on_receive(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        Ok(payload)
+    }
+}
+
+/// Ordered list of interceptors
+#[derive(Clone, Default)]
+pub struct MiddlewareChain {
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+impl MiddlewareChain {
+    /// Create empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append interceptor to the end of the chain
+    pub fn register(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    /// Remove all interceptors with the given name, returning whether any were removed
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.interceptors.len();
+        self.interceptors.retain(|i| i.name() != name);
+        self.interceptors.len() != before
+    }
+
+    /// Interceptor names in execution order for outbound messages
+    pub fn names(&self) -> Vec<String> {
+        self.interceptors.iter().map(|i| i.name().to_string()).collect()
+    }
+
+    /// Number of registered interceptors
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// Whether the chain has no interceptors
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// Run outbound interceptors in registration order
+    pub fn process_outbound(&self, ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        self.interceptors
+            .iter()
+            .try_fold(payload, |payload, interceptor| interceptor.on_send(ctx, payload))
+    }
+
+    /// Run inbound interceptors in reverse registration order
+    pub fn process_inbound(&self, ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        self.interceptors
+            .iter()
+            .rev()
+            .try_fold(payload, |payload, interceptor| interceptor.on_receive(ctx, payload))
+    }
+}
+
+impl std::fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("interceptors", &self.names())
+            .finish()
+    }
+}
+
+/// Adds static headers to outbound messages
+#[derive(Debug, Clone)]
+pub struct HeaderInterceptor {
+    headers: BTreeMap<String, String>,
+}
+
+impl HeaderInterceptor {
+    /// Create interceptor adding a single header
+    pub fn new(key: &str, value: &str) -> Self {
+        let mut headers = BTreeMap::new();
+        headers.insert(key.to_string(), value.to_string());
+        Self { headers }
+    }
+
+    /// Add another header
+    pub fn with_header(mut self, key: &str, value: &str) -> Self {
+        self.headers.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+impl Interceptor for HeaderInterceptor {
+    fn name(&self) -> &str {
+        "headers"
+    }
+
+    fn on_send(&self, ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        for (key, value) in &self.headers {
+            ctx.headers.insert(key.clone(), value.clone());
+        }
+        Ok(payload)
+    }
+}
+
+/// Counts messages and payload bytes in each direction
+#[derive(Debug, Default)]
+pub struct MetricsInterceptor {
+    outbound_messages: AtomicU64,
+    outbound_bytes: AtomicU64,
+    inbound_messages: AtomicU64,
+    inbound_bytes: AtomicU64,
+}
+
+impl MetricsInterceptor {
+    /// Create interceptor with zeroed counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages seen on the send path
+    pub fn outbound_messages(&self) -> u64 {
+        self.outbound_messages.load(Ordering::Relaxed)
+    }
+
+    /// Payload bytes seen on the send path
+    pub fn outbound_bytes(&self) -> u64 {
+        self.outbound_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Messages seen on the receive path
+    pub fn inbound_messages(&self) -> u64 {
+        self.inbound_messages.load(Ordering::Relaxed)
+    }
+
+    /// Payload bytes seen on the receive path
+    pub fn inbound_bytes(&self) -> u64 {
+        self.inbound_bytes.load(Ordering::Relaxed)
+    }
+}
+
+impl Interceptor for MetricsInterceptor {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn on_send(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        self.outbound_messages.fetch_add(1, Ordering::Relaxed);
+        self.outbound_bytes
+            .fetch_add(payload.len() as u64, Ordering::Relaxed);
+        Ok(payload)
+    }
+
+    fn on_receive(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        self.inbound_messages.fetch_add(1, Ordering::Relaxed);
+        self.inbound_bytes
+            .fetch_add(payload.len() as u64, Ordering::Relaxed);
+        Ok(payload)
+    }
+}
+
+/// Application-level message signing with a shared key
+///
+/// Outbound messages get a keyed SHA3-256 MAC over the message id and payload
+/// in the `x-signature` header; inbound messages without a valid MAC are rejected.
+pub struct SigningInterceptor {
+    key: SecretBytes,
+}
+
+impl SigningInterceptor {
+    /// Create interceptor with a shared signing key
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.len() < 16 {
+            return Err(SecureCommsError::Configuration(
+                "Signing key must be at least 16 bytes".to_string(),
+            ));
+        }
+        Ok(Self {
+            key: SecretBytes::from_slice(SensitiveKind::Key, key),
+        })
+    }
+
+    fn mac(&self, ctx: &MessageContext, payload: &[u8]) -> String {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update((self.key.len() as u64).to_be_bytes());
+        hasher.update(self.key.expose());
+        hasher.update(ctx.message_id.as_bytes());
+        hasher.update(payload);
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+impl Interceptor for SigningInterceptor {
+    fn name(&self) -> &str {
+        "signing"
+    }
+
+    fn on_send(&self, ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        let mac = self.mac(ctx, &payload);
+        ctx.headers.insert(SIGNATURE_HEADER.to_string(), mac);
+        Ok(payload)
+    }
+
+    fn on_receive(&self, ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+        let expected = self.mac(ctx, &payload);
+        match ctx.headers.get(SIGNATURE_HEADER) {
+            Some(mac) if ct_eq(mac.as_bytes(), expected.as_bytes()) => Ok(payload),
+            Some(_) => Err(SecureCommsError::AuthenticationFailed),
+            None => Err(SecureCommsError::Validation(format!(
+                "Missing application signature on message {}",
+                ctx.message_id
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct JsonSchemaInterceptor;
+
+    impl Interceptor for JsonSchemaInterceptor {
+        fn name(&self) -> &str {
+            "schema"
+        }
+
+        fn on_send(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+            let value: serde_json::Value = serde_json::from_slice(&payload)
+                .map_err(|e| SecureCommsError::Validation(format!("Invalid JSON: {}", e)))?;
+            if value.get("type").is_none() {
+                return Err(SecureCommsError::Validation("Missing type field".to_string()));
+            }
+            Ok(payload)
+        }
+    }
+
+    struct XorInterceptor(u8);
+
+    impl Interceptor for XorInterceptor {
+        fn name(&self) -> &str {
+            "xor"
+        }
+
+        fn on_send(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+            Ok(payload.into_iter().map(|b| b ^ self.0).collect())
+        }
+
+        fn on_receive(&self, _ctx: &mut MessageContext, payload: Vec<u8>) -> Result<Vec<u8>> {
+            Ok(payload.into_iter().map(|b| b ^ self.0).collect())
+        }
+    }
+
+    fn context(direction: Direction) -> MessageContext {
+        MessageContext::new(direction, "peer_1", "msg_1", 0)
+    }
+
+    #[test]
+    fn test_chain_order_and_transform() {
+        let mut chain = MiddlewareChain::new();
+        let metrics = Arc::new(MetricsInterceptor::new());
+        chain.register(Arc::new(HeaderInterceptor::new("x-app", "test").with_header("x-v", "1")));
+        chain.register(Arc::new(XorInterceptor(0x5a)));
+        chain.register(metrics.clone());
+        assert_eq!(chain.names(), vec!["headers", "xor", "metrics"]);
+
+        let mut ctx = context(Direction::Outbound);
+        let wire = chain.process_outbound(&mut ctx, b"hello".to_vec()).unwrap();
+        assert_ne!(wire, b"hello");
+        assert_eq!(ctx.headers.get("x-app").map(String::as_str), Some("test"));
+        assert_eq!(ctx.headers.len(), 2);
+
+        let mut inbound = context(Direction::Inbound);
+        let plain = chain.process_inbound(&mut inbound, wire.clone()).unwrap();
+        assert_eq!(plain, b"hello");
+        assert_eq!(metrics.outbound_messages(), 1);
+        assert_eq!(metrics.inbound_bytes(), wire.len() as u64);
+
+        assert!(chain.remove("xor"));
+        assert!(!chain.remove("xor"));
+        assert_eq!(chain.len(), 2);
+    }
+
+    #[test]
+    fn test_schema_validation_rejects() {
+        let mut chain = MiddlewareChain::new();
+        chain.register(Arc::new(JsonSchemaInterceptor));
+
+        let mut ctx = context(Direction::Outbound);
+        assert!(chain
+            .process_outbound(&mut ctx, br#"{"type":"order"}"#.to_vec())
+            .is_ok());
+        assert!(matches!(
+            chain.process_outbound(&mut ctx, br#"{"id":1}"#.to_vec()),
+            Err(SecureCommsError::Validation(_))
+        ));
+        assert!(chain.process_outbound(&mut ctx, b"not json".to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_signing_interceptor() {
+        assert!(SigningInterceptor::new(b"short").is_err());
+        let signer = SigningInterceptor::new(b"0123456789abcdef0123").unwrap();
+
+        let mut ctx = context(Direction::Outbound);
+        let payload = signer.on_send(&mut ctx, b"transfer".to_vec()).unwrap();
+        assert!(ctx.headers.contains_key(SIGNATURE_HEADER));
+
+        let mut inbound = ctx.clone();
+        inbound.direction = Direction::Inbound;
+        assert!(signer.on_receive(&mut inbound.clone(), payload.clone()).is_ok());
+
+        assert!(matches!(
+            signer.on_receive(&mut inbound.clone(), b"tampered".to_vec()),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        inbound.headers.clear();
+        assert!(matches!(
+            signer.on_receive(&mut inbound, payload),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+}
@@ -16,19 +16,25 @@
 //! - **Direct Connections**: Streamlined P2P architecture without complex routing
 //! - **Multi-Peer Support**: Concurrent connections to multiple secure peers
 //! - **Trust Scoring**: Dynamic trust assessment based on peer behavior
+//! - **Reputation Routing**: Peers below the reputation floor are excluded from routing and relaying
 //! - **Connection Health**: Real-time monitoring and automatic failover
+//! - **Channel Migration**: Secure channels move between TCP/QUIC or new addresses without re-keying
 //!
 //! ### Message Routing and Delivery
 //! - **Efficient Routing**: Direct message delivery with minimal overhead
 //! - **Integrity Verification**: SHA-3 based message integrity protection
 //! - **Bandwidth Monitoring**: Real-time bandwidth usage tracking
+//! - **Traffic Shaping**: Per-peer, per-class token buckets keep bulk traffic off consensus links
 //! - **Message Queuing**: Reliable message delivery with retry mechanisms
+//! - **Gossip Transport**: Epidemic dissemination frames carried over secure channels
+//! - **State Sync Transport**: Certified logs and snapshot chunks sent as shaped bulk traffic
 //!
 //! ### Network Monitoring and Diagnostics
 //! - **Real-Time Metrics**: Connection status, latency, and throughput monitoring
 //! - **Event System**: Comprehensive network event logging and alerting
 //! - **Health Checks**: Automatic connection health assessment and recovery
 //! - **Performance Analytics**: Network performance optimization and reporting
+//! - **Packet Capture**: Redacted JSONL traces of wire frames, switchable per channel
 //!
 //! ## Performance Characteristics
 //!
@@ -69,7 +75,7 @@
 //!
 //! ### Basic Network Setup
 //! ```rust,no_run
-//! use streamlined_secure_comms::network_comms::NetworkComms;
+//! use quantum_forge_secure_comms::network_comms::NetworkComms;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -85,7 +91,7 @@
 //!
 //! ### Peer Connection and Secure Channel
 //! ```rust,no_run
-//! # use streamlined_secure_comms::network_comms::{NetworkComms, PeerInfo, ConnectionStatus};
+//! # use quantum_forge_secure_comms::network_comms::{NetworkComms, PeerInfo, ConnectionStatus};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let mut network = NetworkComms::new("local_peer_id".to_string(), "127.0.0.1".to_string(), 8080).await?;
@@ -115,7 +121,7 @@
 //!
 //! ### Secure Message Transmission
 //! ```rust,no_run
-//! # use streamlined_secure_comms::network_comms::{NetworkComms, NetworkMessage};
+//! # use quantum_forge_secure_comms::network_comms::{NetworkComms, NetworkMessage};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let mut network = NetworkComms::new("local_peer_id".to_string(), "127.0.0.1".to_string(), 8080).await?;
@@ -134,7 +140,7 @@
 //!
 //! ### Network Monitoring
 //! ```rust,no_run
-//! # use streamlined_secure_comms::network_comms::{NetworkComms, NetworkEvent};
+//! # use quantum_forge_secure_comms::network_comms::{NetworkComms, NetworkEvent};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let mut network = NetworkComms::new("local_peer_id".to_string(), "127.0.0.1".to_string(), 8080).await?;
@@ -190,9 +196,31 @@
 //!
 //! ### Handshake Protocol
 //! 1. **HandshakeInit**: Initial connection request with peer identity
-//! 2. **HandshakeResponse**: Authentication response with cryptographic proof
-//! 3. **KeyExchange**: Secure session key establishment
-//! 4. **SecureData**: Encrypted message transmission
+//! 2. **HandshakeRetry**: Stateless retry token, with a client puzzle under load
+//! 3. **HandshakeResponse**: Authentication response with cryptographic proof
+//! 4. **KeyExchange**: Secure session key establishment
+//! 5. **SecureData**: Encrypted message transmission
+//!
+//! Inbound `HandshakeInit` messages pass `screen_handshake` before any PQC
+//! work: sources are rate limited and must echo a retry token proving their
+//! address, so spoofed or flooding sources cannot exhaust the CPU.
+//!
+//! Handshake messages carry the sender's timestamp; once the handshake is
+//! authenticated it feeds clock skew estimation, and the optional
+//! **TimeSyncRequest**/**TimeSyncResponse** exchange refines it.
+//!
+//! The optional **AttestationRequest**/**AttestationResponse** exchange
+//! carries TEE attestation evidence answering the verifier's nonce.
+//!
+//! **PaddingOffer** negotiates the channel's traffic padding policy; padded
+//! channels send **Cover** frames while idle.
+//!
+//! **SigningOffer** negotiates how often the channel's messages carry a
+//! transcript signature.
+//!
+//! **PrivateHandshake** frames carry the identity-hiding handshake variant:
+//! uniformly sized frames whose identities are encrypted after an ephemeral
+//! KEM round.
 //!
 //! ### Connection Management
 //! - **Keepalive**: Periodic connection health checks
@@ -200,20 +228,50 @@
 //! - **Maintenance**: Automatic cleanup and optimization
 //! - **Recovery**: Connection failure detection and recovery
 
+use crate::bandwidth::{BandwidthManager, BandwidthUsage, ClassLimit, Direction, MessageClass};
+use crate::capabilities::CapabilitySet;
+use crate::channel_migration::{
+    MigrationChallenge, MigrationManager, MigrationProof, MigrationRecord, TransportEndpoint,
+    TransportKind,
+};
+use crate::clock_sync::{ClockSkewMonitor, SkewSample, SkewStatus, TimeSyncRequest, TimeSyncResponse};
+use crate::constant_time::ct_eq;
+use crate::expiry::{is_expired, now_ms, record_expired, ExpiryStage};
+use crate::frame_io::{FrameIoMode, FrameWriter};
+use crate::gossip::{GossipAction, GossipFrame};
+use crate::handshake_guard::{
+    HandshakeAdmission, HandshakeGuard, HandshakeGuardConfig, HandshakeProof, RetryToken,
+};
+use crate::hedging::PeerCandidate;
+use crate::integrity::IntegrityReport;
+use crate::key_update::KeyUpdate;
+use crate::middleware::Direction as FrameDirection;
+use crate::packet_capture::{CaptureConfig, CaptureStats, PacketCapture};
+use crate::peer_reputation::{PeerReputation, ReputationManager};
 use crate::performance::PerformanceMetrics;
+use crate::private_handshake::PrivateFrame;
+use crate::relay_e2e::RelayEnvelope;
+use crate::signing_policy::SigningPolicy;
+use crate::state_sync::SyncMessage;
+use crate::suite_negotiation::{SuiteAccept, SuiteHello};
+use crate::tee::AttestationEvidence;
+use crate::timeouts::Timeouts;
+use crate::traffic_padding::{PaddingPolicy, TrafficPadder};
 use crate::{Result, SecureCommsError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWrite;
 use tokio::sync::{mpsc, Mutex};
+use zeroize::Zeroize;
 
 /// Comprehensive peer information for network communications and trust management
 /// 
 /// Contains all necessary information for establishing and maintaining secure
 /// connections with remote peers, including cryptographic material, connection
 /// status, and trust assessment metrics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PeerInfo {
     /// Unique peer identifier for routing and authentication
     pub peer_id: String,
@@ -265,6 +323,21 @@ pub enum NetworkMessage {
         public_key: Vec<u8>,
         /// Cryptographic nonce for replay protection
         nonce: Vec<u8>,
+        /// Sender's wall clock in milliseconds for skew estimation
+        timestamp_ms: u64,
+        /// Retry token echoed from `HandshakeRetry`, with its puzzle solution
+        #[serde(default)]
+        proof: Option<HandshakeProof>,
+        /// Features the sender has enabled; absent from older nodes
+        #[serde(default)]
+        capabilities: Option<CapabilitySet>,
+    },
+    /// Retry demanded before the responder spends work on a handshake
+    HandshakeRetry {
+        /// Responder's unique peer identifier
+        sender_id: String,
+        /// Token to echo in the repeated `HandshakeInit`
+        token: RetryToken,
     },
     /// Handshake response with authentication proof
     HandshakeResponse {
@@ -276,6 +349,11 @@ pub enum NetworkMessage {
         nonce: Vec<u8>,
         /// Digital signature proving identity and preventing impersonation
         signature: Vec<u8>,
+        /// Responder's wall clock in milliseconds for skew estimation
+        timestamp_ms: u64,
+        /// Features the responder has enabled; absent from older nodes
+        #[serde(default)]
+        capabilities: Option<CapabilitySet>,
     },
     /// Secure key exchange message for session key establishment
     KeyExchange {
@@ -294,6 +372,18 @@ pub enum NetworkMessage {
         encrypted_payload: Vec<u8>,
         /// SHA-3 integrity hash for tamper detection
         integrity_hash: Vec<u8>,
+        /// Delivery deadline in Unix milliseconds, if the sender set a TTL
+        #[serde(default)]
+        expires_at_ms: Option<u64>,
+    },
+    /// Several encrypted payloads coalesced into one frame
+    SecureDataBatch {
+        /// Session identifier for decryption key lookup
+        session_id: String,
+        /// Encrypted payloads in send order
+        encrypted_payloads: Vec<Vec<u8>>,
+        /// SHA-3 integrity hash per payload
+        integrity_hashes: Vec<Vec<u8>>,
     },
     /// Connection keepalive for health monitoring
     Keepalive {
@@ -305,6 +395,87 @@ pub enum NetworkMessage {
         /// Human-readable reason for connection termination
         reason: String
     },
+    /// Secure time sync request
+    TimeSyncRequest(TimeSyncRequest),
+    /// Secure time sync response authenticated with the session key
+    TimeSyncResponse(TimeSyncResponse),
+    /// Request for the peer's TEE attestation evidence
+    AttestationRequest {
+        /// Verifier's unique peer identifier
+        sender_id: String,
+        /// Fresh nonce the evidence must answer
+        nonce: Vec<u8>,
+    },
+    /// TEE attestation evidence answering an `AttestationRequest`
+    AttestationResponse {
+        /// Attester's unique peer identifier
+        sender_id: String,
+        /// Signed evidence bound to the request nonce
+        evidence: AttestationEvidence,
+        /// Attester's startup integrity report, bound into the evidence
+        #[serde(default)]
+        integrity: Option<IntegrityReport>,
+    },
+    /// Padded frame of the identity-hiding handshake
+    PrivateHandshake(PrivateFrame),
+    /// Traffic padding policy offered for the channel
+    PaddingOffer {
+        /// Offering peer's unique identifier
+        sender_id: String,
+        /// Sender's local padding policy
+        policy: PaddingPolicy,
+    },
+    /// Dummy frame sent as cover traffic; discarded on receipt
+    Cover,
+    /// Signing policy offered for the channel
+    SigningOffer {
+        /// Offering peer's unique identifier
+        sender_id: String,
+        /// Sender's local signing policy
+        policy: SigningPolicy,
+    },
+    /// Session key update encapsulated to the peer's semi-static ML-KEM key
+    KeyUpdate(KeyUpdate),
+    /// KEMs, AEAD suites and signature modes offered for the channel
+    SuiteHello(SuiteHello),
+    /// Selected suite with key confirmation over the negotiation transcript
+    SuiteAccept(SuiteAccept),
+    /// Message sealed end to end for a recipient beyond the next hop
+    RelayEnvelope(RelayEnvelope),
+    /// Features re-advertised on an established channel after a flag change
+    Capabilities {
+        /// Advertising peer's unique identifier
+        sender_id: String,
+        /// Sender's enabled features
+        capabilities: CapabilitySet,
+    },
+}
+
+impl NetworkMessage {
+    /// Default traffic class used for bandwidth accounting
+    pub fn message_class(&self) -> MessageClass {
+        match self {
+            NetworkMessage::SecureData { .. }
+            | NetworkMessage::SecureDataBatch { .. }
+            | NetworkMessage::Cover => MessageClass::Data,
+            _ => MessageClass::Control,
+        }
+    }
+
+    /// Serialized size charged against bandwidth budgets
+    pub fn frame_size(&self) -> Result<u64> {
+        serde_json::to_vec(self)
+            .map(|frame| frame.len() as u64)
+            .map_err(|e| SecureCommsError::NetworkComm(e.to_string()))
+    }
+
+    /// Delivery deadline carried by the message
+    pub fn expires_at_ms(&self) -> Option<u64> {
+        match self {
+            NetworkMessage::SecureData { expires_at_ms, .. } => *expires_at_ms,
+            _ => None,
+        }
+    }
 }
 
 /// Secure communication channel with session management and monitoring
@@ -330,6 +501,8 @@ pub struct SecureChannel {
     pub last_activity: u64,
     /// Total bandwidth usage in bytes for monitoring
     pub bandwidth_usage: u64,
+    /// Transport currently carrying the channel
+    pub transport: TransportKind,
 }
 
 impl SecureChannel {
@@ -349,6 +522,7 @@ impl SecureChannel {
             established_at: now,
             last_activity: now,
             bandwidth_usage: 0,
+            transport: TransportKind::Tcp,
         }
     }
 
@@ -366,7 +540,7 @@ impl SecureChannel {
     /// the time elapsed since last activity compared to the timeout threshold.
     pub fn is_expired(&self, timeout_seconds: u64) -> bool {
         let now = chrono::Utc::now().timestamp() as u64;
-        now - self.last_activity > timeout_seconds
+        now.saturating_sub(self.last_activity) > timeout_seconds
     }
 }
 
@@ -444,6 +618,15 @@ pub enum NetworkEvent {
         /// Type of security alert (authentication failure, integrity violation, etc.)
         alert_type: String
     },
+    /// Secure channel moved to a new transport connection without re-keying
+    ChannelMigrated {
+        /// Peer identifier for the migrated channel
+        peer_id: String,
+        /// Channel identifier preserved across the migration
+        channel_id: String,
+        /// New transport endpoint
+        endpoint: String
+    },
 }
 
 /// Message routing system for direct peer-to-peer communications
@@ -461,6 +644,10 @@ pub struct MessageRouter {
     event_listeners: Vec<mpsc::UnboundedSender<NetworkEvent>>,
     /// Routing table mapping peer IDs to their active channel IDs
     routing_table: HashMap<String, String>, // peer_id -> channel_id
+    /// Behaviour-based peer reputation driving routing and relay decisions
+    reputation: ReputationManager,
+    /// Per-peer, per-class bandwidth accounting and shaping
+    bandwidth: BandwidthManager,
 }
 
 impl MessageRouter {
@@ -474,6 +661,8 @@ impl MessageRouter {
             secure_channels: HashMap::new(),
             event_listeners: Vec::new(),
             routing_table: HashMap::new(),
+            reputation: ReputationManager::default(),
+            bandwidth: BandwidthManager::default(),
         }
     }
 
@@ -484,6 +673,7 @@ impl MessageRouter {
     /// monitoring and logging purposes.
     pub fn add_peer(&mut self, peer_info: PeerInfo) {
         let peer_id = peer_info.peer_id.clone();
+        self.reputation.register_peer(&peer_id, peer_info.trust_score);
         self.peer_connections.insert(peer_id.clone(), peer_info);
 
         // Notify event listeners of new peer connection
@@ -520,12 +710,127 @@ impl MessageRouter {
         Ok(channel_id)
     }
 
+    /// Replace the session key of the peer's active channel
+    ///
+    /// Message counters restart from zero under the new key. Returns the
+    /// channel identifier, which is preserved.
+    pub fn rotate_session_key(&mut self, peer_id: &str, session_key: Vec<u8>) -> Result<String> {
+        let channel_id = self
+            .routing_table
+            .get(peer_id)
+            .cloned()
+            .ok_or(SecureCommsError::ChannelNotEstablished)?;
+        let channel = self
+            .secure_channels
+            .get_mut(&channel_id)
+            .ok_or(SecureCommsError::ChannelNotEstablished)?;
+
+        channel.session_key = session_key;
+        channel.send_counter = 0;
+        channel.receive_counter = 0;
+        channel.update_activity();
+        Ok(channel_id)
+    }
+
+    /// Tear down the peer's channel, wiping its session key
+    ///
+    /// Returns the removed channel identifier, if any.
+    pub fn revoke_channel(&mut self, peer_id: &str, reason: &str) -> Option<String> {
+        let channel_id = self.routing_table.remove(peer_id)?;
+        if let Some(mut channel) = self.secure_channels.remove(&channel_id) {
+            channel.session_key.zeroize();
+        }
+        if let Some(peer) = self.peer_connections.get_mut(peer_id) {
+            peer.connection_status = ConnectionStatus::Disconnected;
+        }
+        self.broadcast_event(NetworkEvent::PeerDisconnected {
+            peer_id: peer_id.to_string(),
+            reason: reason.to_string(),
+        });
+        Some(channel_id)
+    }
+
+    /// Active secure channel of the peer, if any
+    pub fn active_channel(&self, peer_id: &str) -> Option<&SecureChannel> {
+        self.routing_table
+            .get(peer_id)
+            .and_then(|channel_id| self.secure_channels.get(channel_id))
+    }
+
+    /// Install a channel exported by another node without re-establishment
+    ///
+    /// Channel identifier, session key and counters are kept as exported.
+    pub fn restore_channel(&mut self, peer_info: PeerInfo, channel: SecureChannel) {
+        let peer_id = channel.peer_id.clone();
+        let channel_id = channel.channel_id.clone();
+        self.add_peer(PeerInfo {
+            connection_status: ConnectionStatus::SecureChannelEstablished,
+            ..peer_info
+        });
+        if let Some(previous) = self.routing_table.insert(peer_id.clone(), channel_id.clone()) {
+            self.secure_channels.remove(&previous);
+        }
+        self.secure_channels.insert(channel_id.clone(), channel);
+
+        self.broadcast_event(NetworkEvent::SecureChannelEstablished {
+            peer_id,
+            channel_id,
+        });
+    }
+
     /// Route message to specified peer through established secure channel
     /// 
     /// Locates the appropriate secure channel for the target peer and
     /// routes the message through the encrypted channel. Updates activity
     /// timestamps and message counters for monitoring and security.
     pub fn route_message(&mut self, peer_id: &str, message: &NetworkMessage) -> Result<()> {
+        self.route_message_with_class(peer_id, message, message.message_class())
+    }
+
+    /// Route message with an explicit traffic class
+    ///
+    /// Fails with `ResourceExhausted` when the peer's egress budget for the
+    /// class is used up.
+    pub fn route_message_with_class(
+        &mut self,
+        peer_id: &str,
+        message: &NetworkMessage,
+        class: MessageClass,
+    ) -> Result<()> {
+        let message_size = message.frame_size()?;
+        self.route_internal(peer_id, class, message_size, false)
+    }
+
+    /// Route a frame whose serialized size the caller already measured
+    pub fn route_frame(&mut self, peer_id: &str, class: MessageClass, frame_size: u64) -> Result<()> {
+        self.route_internal(peer_id, class, frame_size, false)
+    }
+
+    /// Route a frame whose shaping delay has already been waited out
+    pub fn route_shaped_frame(
+        &mut self,
+        peer_id: &str,
+        class: MessageClass,
+        frame_size: u64,
+    ) -> Result<()> {
+        self.route_internal(peer_id, class, frame_size, true)
+    }
+
+    fn route_internal(
+        &mut self,
+        peer_id: &str,
+        class: MessageClass,
+        message_size: u64,
+        shaped: bool,
+    ) -> Result<()> {
+        if !self.reputation.is_routable(peer_id) {
+            return Err(SecureCommsError::NetworkComm(format!(
+                "Peer {} excluded from routing (reputation {:.2})",
+                peer_id,
+                self.reputation.score(peer_id)
+            )));
+        }
+
         let channel_id = self
             .routing_table
             .get(peer_id)
@@ -536,21 +841,24 @@ impl MessageRouter {
             .get_mut(channel_id)
             .ok_or(SecureCommsError::ChannelNotEstablished)?;
 
+        // Charge the peer's egress budget for this class
+        if shaped {
+            self.bandwidth
+                .commit_shaped_egress(peer_id, class, message_size);
+        } else {
+            self.bandwidth
+                .try_egress(peer_id, class, message_size)?;
+        }
+
         // Update channel activity
         channel.update_activity();
         channel.send_counter += 1;
-
-        // Calculate message size
-        let message_size = serde_json::to_vec(message)
-            .map_err(|e| SecureCommsError::NetworkComm(e.to_string()))?
-            .len();
-
-        channel.bandwidth_usage += message_size as u64;
+        channel.bandwidth_usage += message_size;
 
         // Notify listeners
         self.broadcast_event(NetworkEvent::MessageSent {
             peer_id: peer_id.to_string(),
-            size_bytes: message_size,
+            size_bytes: message_size as usize,
         });
 
         Ok(())
@@ -593,6 +901,109 @@ impl MessageRouter {
         }
     }
 
+    /// Record a successful interaction with a peer
+    pub fn record_peer_success(&mut self, peer_id: &str, latency_ms: u64) {
+        self.reputation.record_success(peer_id, latency_ms);
+        self.sync_trust_score(peer_id);
+    }
+
+    /// Record a failed interaction with a peer
+    pub fn record_peer_failure(&mut self, peer_id: &str) {
+        self.reputation.record_failure(peer_id);
+        self.sync_trust_score(peer_id);
+    }
+
+    /// Record a protocol violation and raise a security alert
+    pub fn record_protocol_violation(&mut self, peer_id: &str, reason: &str) {
+        self.reputation.record_protocol_violation(peer_id, reason);
+        self.sync_trust_score(peer_id);
+
+        self.broadcast_event(NetworkEvent::SecurityAlert {
+            peer_id: peer_id.to_string(),
+            alert_type: format!("Protocol violation: {}", reason),
+        });
+    }
+
+    /// Select the most reputable relay with an established channel
+    ///
+    /// The destination itself is never chosen as its own relay.
+    pub fn select_relay(&self, destination: &str) -> Option<String> {
+        let candidates: Vec<String> = self.routing_table.keys().cloned().collect();
+        self.reputation.select_relay(&candidates, Some(destination))
+    }
+
+    /// Get routable peers ordered by reputation, best first
+    pub fn ranked_peers(&self) -> Vec<String> {
+        let candidates: Vec<String> = self.peer_connections.keys().cloned().collect();
+        self.reputation.rank_peers(&candidates)
+    }
+
+    /// Get reputation manager
+    pub fn reputation(&self) -> &ReputationManager {
+        &self.reputation
+    }
+
+    /// Get mutable reputation manager for overrides and decay
+    pub fn reputation_mut(&mut self) -> &mut ReputationManager {
+        &mut self.reputation
+    }
+
+    /// Rebind a peer's secure channel to a verified new transport endpoint
+    ///
+    /// Session key, counters and routing entry are preserved.
+    pub fn rebind_channel(&mut self, record: &MigrationRecord) -> Result<()> {
+        let channel_id = self
+            .routing_table
+            .get(&record.peer_id)
+            .ok_or_else(|| SecureCommsError::PeerNotFound(record.peer_id.clone()))?;
+        if *channel_id != record.channel_id {
+            return Err(SecureCommsError::Validation(format!(
+                "Channel {} is no longer the active channel for {}",
+                record.channel_id, record.peer_id
+            )));
+        }
+
+        let channel = self
+            .secure_channels
+            .get_mut(&record.channel_id)
+            .ok_or(SecureCommsError::ChannelNotEstablished)?;
+        channel.transport = record.endpoint.kind;
+        channel.update_activity();
+
+        if let Some(peer) = self.peer_connections.get_mut(&record.peer_id) {
+            peer.address = record.endpoint.address.clone();
+            peer.port = record.endpoint.port;
+            peer.connection_status = ConnectionStatus::SecureChannelEstablished;
+            peer.last_seen = chrono::Utc::now().timestamp() as u64;
+        }
+
+        self.broadcast_event(NetworkEvent::ChannelMigrated {
+            peer_id: record.peer_id.clone(),
+            channel_id: record.channel_id.clone(),
+            endpoint: record.endpoint.to_string(),
+        });
+
+        Ok(())
+    }
+
+    /// Get bandwidth manager
+    pub fn bandwidth(&self) -> &BandwidthManager {
+        &self.bandwidth
+    }
+
+    /// Get mutable bandwidth manager for limits and ingress accounting
+    pub fn bandwidth_mut(&mut self) -> &mut BandwidthManager {
+        &mut self.bandwidth
+    }
+
+    /// Mirror the reputation score into the peer's trust score
+    fn sync_trust_score(&mut self, peer_id: &str) {
+        let score = self.reputation.score(peer_id);
+        if let Some(peer) = self.peer_connections.get_mut(peer_id) {
+            peer.trust_score = score;
+        }
+    }
+
     /// Add event listener
     pub fn add_event_listener(&mut self, sender: mpsc::UnboundedSender<NetworkEvent>) {
         self.event_listeners.push(sender);
@@ -627,6 +1038,10 @@ impl MessageRouter {
             "total_bandwidth_bytes".to_string(),
             serde_json::Value::Number(total_bandwidth.into()),
         );
+        stats.insert(
+            "bandwidth".to_string(),
+            serde_json::to_value(self.bandwidth.get_stats()).unwrap_or(serde_json::Value::Null),
+        );
 
         stats
     }
@@ -650,6 +1065,16 @@ pub struct NetworkComms {
     config: NetworkConfig,
     /// Event receiver for monitoring
     event_receiver: Option<mpsc::UnboundedReceiver<NetworkEvent>>,
+    /// Per-peer clock skew estimation and secure time sync
+    clock: ClockSkewMonitor,
+    /// Pending and completed channel migrations
+    migrations: MigrationManager,
+    /// Rate limits, retry tokens and puzzles for inbound handshakes
+    handshake_guard: HandshakeGuard,
+    /// Negotiated traffic padding per channel
+    padding: TrafficPadder,
+    /// Debug capture of wire frames, while running
+    capture: Option<PacketCapture>,
 }
 
 /// Network configuration
@@ -661,6 +1086,12 @@ pub struct NetworkConfig {
     pub max_message_size_bytes: usize,
     pub compression_enabled: bool,
     pub encryption_required: bool,
+    /// Write path for frames sent over TCP streams
+    #[serde(default)]
+    pub frame_io: FrameIoMode,
+    /// Connect and latency probe deadlines
+    #[serde(default)]
+    pub timeouts: Timeouts,
 }
 
 impl Default for NetworkConfig {
@@ -672,6 +1103,8 @@ impl Default for NetworkConfig {
             max_message_size_bytes: 1024 * 1024, // 1MB
             compression_enabled: true,
             encryption_required: true,
+            frame_io: FrameIoMode::default(),
+            timeouts: Timeouts::default(),
         }
     }
 }
@@ -726,6 +1159,14 @@ impl NetworkComms {
             metrics,
             config: NetworkConfig::default(),
             event_receiver: Some(event_receiver),
+            clock: ClockSkewMonitor::default(),
+            migrations: MigrationManager::default(),
+            handshake_guard: HandshakeGuard::new(
+                HandshakeGuardConfig::default(),
+                rand::random::<[u8; 32]>(),
+            ),
+            padding: TrafficPadder::default(),
+            capture: None,
         })
     }
 
@@ -741,6 +1182,10 @@ impl NetworkComms {
         {
             let mut router = self.router.lock().await;
             router.add_peer(peer_info.clone());
+            match &connection_result {
+                Ok(tcp_latency) => router.record_peer_success(&peer_info.peer_id, *tcp_latency),
+                Err(_) => router.record_peer_failure(&peer_info.peer_id),
+            }
         }
 
         // Only accept real connections - no fallback simulations
@@ -782,14 +1227,19 @@ impl NetworkComms {
 
     /// Establish real TCP connection to peer
     async fn establish_tcp_connection(&self, peer_info: &PeerInfo) -> Result<u64> {
-        use std::time::Duration;
+        self.probe_address(&peer_info.address, peer_info.port).await
+    }
+
+    /// Open and drop a TCP connection to measure reachability and latency (ms)
+    ///
+    /// Also warms DNS, ARP and route caches ahead of the real connection.
+    pub async fn probe_address(&self, host: &str, port: u16) -> Result<u64> {
         use tokio::net::TcpStream;
 
         let start_time = Instant::now();
-        let address = format!("{}:{}", peer_info.address, peer_info.port);
+        let address = format!("{}:{}", host, port);
 
-        // Optimized timeout for faster failure detection
-        let connection_timeout = Duration::from_millis(500);
+        let connection_timeout = self.config.timeouts.connect();
 
         match tokio::time::timeout(connection_timeout, TcpStream::connect(&address)).await {
             Ok(Ok(_stream)) => {
@@ -822,14 +1272,80 @@ impl NetworkComms {
         router.establish_channel(peer_id, session_key)
     }
 
+    /// Rotate the session key of the channel with peer
+    pub async fn rotate_session_key(&mut self, peer_id: &str, session_key: Vec<u8>) -> Result<String> {
+        let mut router = self.router.lock().await;
+        router.rotate_session_key(peer_id, session_key)
+    }
+
+    /// Tear down the channel with peer and forget its padding policy
+    pub async fn revoke_channel(&mut self, peer_id: &str, reason: &str) -> Option<String> {
+        self.padding.remove_peer(peer_id);
+        let mut router = self.router.lock().await;
+        router.revoke_channel(peer_id, reason)
+    }
+
+    /// Tear down the channel with peer from synchronous code
+    ///
+    /// The router is only locked for the length of a call, so with `&mut
+    /// self` it is free; should it be held anyway, nothing is revoked and
+    /// the error says so.
+    pub fn revoke_channel_now(&mut self, peer_id: &str, reason: &str) -> Result<Option<String>> {
+        let mut router = self
+            .router
+            .try_lock()
+            .map_err(|_| SecureCommsError::SystemError("Message router is busy".to_string()))?;
+        self.padding.remove_peer(peer_id);
+        Ok(router.revoke_channel(peer_id, reason))
+    }
+
+    /// Snapshot of the peer's connection and active secure channel
+    pub async fn export_channel(&self, peer_id: &str) -> Option<(PeerInfo, SecureChannel)> {
+        let router = self.router.lock().await;
+        let channel = router.active_channel(peer_id)?.clone();
+        let peer_info = router.get_peer(peer_id)?.clone();
+        Some((peer_info, channel))
+    }
+
+    /// Install a secure channel exported by another node
+    pub async fn restore_channel(&mut self, peer_info: PeerInfo, channel: SecureChannel) {
+        let mut router = self.router.lock().await;
+        router.restore_channel(peer_info, channel);
+    }
+
     /// Send message to peer
     pub async fn send_message(&mut self, peer_id: &str, message: NetworkMessage) -> Result<()> {
-        let mut router = self.router.lock().await;
-        router.route_message(peer_id, &message)
+        let class = message.message_class();
+        self.send_shaped(peer_id, message, class).await
     }
 
     /// Send secure data to peer
     pub async fn send_secure_data(&mut self, peer_id: &str, data: &[u8]) -> Result<()> {
+        self.send_secure_data_with_class(peer_id, data, MessageClass::Data)
+            .await
+    }
+
+    /// Send secure data to peer under an explicit traffic class
+    pub async fn send_secure_data_with_class(
+        &mut self,
+        peer_id: &str,
+        data: &[u8],
+        class: MessageClass,
+    ) -> Result<()> {
+        self.send_expiring_data(peer_id, data, class, None).await
+    }
+
+    /// Send secure data that must not be routed after `expires_at_ms`
+    ///
+    /// Messages still waiting for bandwidth when the deadline passes are
+    /// dropped with `SecureCommsError::Timeout`.
+    pub async fn send_expiring_data(
+        &mut self,
+        peer_id: &str,
+        data: &[u8],
+        class: MessageClass,
+        expires_at_ms: Option<u64>,
+    ) -> Result<()> {
         if !self
             .router
             .lock()
@@ -845,9 +1361,286 @@ impl NetworkComms {
             session_id: format!("session_{}", chrono::Utc::now().timestamp()),
             encrypted_payload: data.to_vec(),
             integrity_hash: self.compute_integrity_hash(data),
+            expires_at_ms,
         };
 
-        self.send_message(peer_id, message).await
+        self.send_shaped(peer_id, message, class).await
+    }
+
+    /// Send several payloads to peer coalesced into a single frame
+    ///
+    /// The frame is routed, shaped and accounted once under `class`; either
+    /// all payloads are sent or none are.
+    pub async fn send_secure_batch(
+        &mut self,
+        peer_id: &str,
+        payloads: &[Vec<u8>],
+        class: MessageClass,
+    ) -> Result<()> {
+        if payloads.is_empty() {
+            return Ok(());
+        }
+        if !self
+            .router
+            .lock()
+            .await
+            .peer_connections
+            .contains_key(peer_id)
+        {
+            return Err(SecureCommsError::PeerNotFound(peer_id.to_string()));
+        }
+
+        let message = NetworkMessage::SecureDataBatch {
+            session_id: format!("session_{}", chrono::Utc::now().timestamp()),
+            integrity_hashes: payloads
+                .iter()
+                .map(|payload| self.compute_integrity_hash(payload))
+                .collect(),
+            encrypted_payloads: payloads.to_vec(),
+        };
+
+        self.send_shaped(peer_id, message, class).await
+    }
+
+    /// Route a message, delaying it when the class budget is exhausted
+    ///
+    /// Delays longer than `max_shaping_delay_ms` are rejected with
+    /// `ResourceExhausted` instead of blocking the caller.
+    async fn send_shaped(
+        &mut self,
+        peer_id: &str,
+        message: NetworkMessage,
+        class: MessageClass,
+    ) -> Result<()> {
+        // Measured once here and passed to the router, which charges it
+        let message_size = message.frame_size()?;
+
+        // Padded channels decouple send times from application events
+        if let Some(policy) = self.padding.policy(peer_id) {
+            let hold = policy.batch_delay(&mut rand::thread_rng());
+            if !hold.is_zero() {
+                tokio::time::sleep(hold).await;
+            }
+        }
+
+        let (delay, max_delay) = {
+            let mut router = self.router.lock().await;
+            let max_delay = router.bandwidth().get_config().max_shaping_delay_ms;
+            let delay = router
+                .bandwidth_mut()
+                .egress_delay(peer_id, class, message_size);
+            (delay, Duration::from_millis(max_delay))
+        };
+
+        if delay.is_zero() {
+            Self::check_not_expired(&message, ExpiryStage::Send)?;
+            self.router
+                .lock()
+                .await
+                .route_frame(peer_id, class, message_size)?;
+            self.capture_frame(FrameDirection::Outbound, peer_id, &message);
+            return self.pad_sent_frame(peer_id, class, message_size).await;
+        }
+
+        if delay > max_delay {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Egress {} bandwidth to {} exhausted (would wait {}ms)",
+                class.as_str(),
+                peer_id,
+                delay.as_millis()
+            )));
+        }
+
+        tokio::time::sleep(delay).await;
+        // The deadline may have passed while the message was held back
+        Self::check_not_expired(&message, ExpiryStage::Send)?;
+        self.router
+            .lock()
+            .await
+            .route_shaped_frame(peer_id, class, message_size)?;
+        self.capture_frame(FrameDirection::Outbound, peer_id, &message);
+        self.pad_sent_frame(peer_id, class, message_size).await
+    }
+
+    /// Charge padding up to the channel's frame size and defer cover traffic
+    async fn pad_sent_frame(&mut self, peer_id: &str, class: MessageClass, message_size: u64) -> Result<()> {
+        let overhead = match self.padding.policy(peer_id) {
+            Some(policy) => (policy.padded_len(message_size as usize) as u64).saturating_sub(message_size),
+            None => return Ok(()),
+        };
+        self.padding.record_send_at(peer_id, Instant::now());
+        if overhead > 0 {
+            let mut router = self.router.lock().await;
+            router.bandwidth_mut().record_padding(peer_id, class, overhead);
+        }
+        Ok(())
+    }
+
+    /// Policy offered to peers in `padding_offer`
+    pub fn set_padding_policy(&mut self, policy: PaddingPolicy) {
+        self.padding.set_local_policy(policy);
+    }
+
+    /// `PaddingOffer` announcing our padding policy to a peer
+    pub fn padding_offer(&self) -> NetworkMessage {
+        NetworkMessage::PaddingOffer {
+            sender_id: self.local_peer.peer_id.clone(),
+            policy: self.padding.local_policy().clone(),
+        }
+    }
+
+    /// Adopt the stronger of our policy and a peer's offer for its channel
+    pub fn accept_padding_offer(&mut self, peer_id: &str, offer: &PaddingPolicy) -> PaddingPolicy {
+        self.padding.negotiate(peer_id, offer)
+    }
+
+    /// Negotiated padding policy of the channel with a peer
+    pub fn padding_policy(&self, peer_id: &str) -> Option<&PaddingPolicy> {
+        self.padding.policy(peer_id)
+    }
+
+    /// Send a cover frame on every padded channel idle past its cover interval
+    ///
+    /// Call periodically; returns the number of cover frames sent. Cover
+    /// frames never exceed the peer's egress budget: a throttled one is
+    /// skipped until the next interval. Peers whose channel is gone are
+    /// forgotten.
+    pub async fn send_cover_traffic(&mut self) -> Result<usize> {
+        let cover_size = serde_json::to_vec(&NetworkMessage::Cover)
+            .map_err(|e| SecureCommsError::NetworkComm(e.to_string()))?
+            .len() as u64;
+        let now = Instant::now();
+        let mut sent = 0;
+        for peer_id in self.padding.due_cover_at(now) {
+            let routed = self.router.lock().await.route_message_with_class(
+                &peer_id,
+                &NetworkMessage::Cover,
+                MessageClass::Data,
+            );
+            match routed {
+                Ok(()) => {
+                    self.capture_frame(FrameDirection::Outbound, &peer_id, &NetworkMessage::Cover);
+                    self.pad_sent_frame(&peer_id, MessageClass::Data, cover_size).await?;
+                    let mut router = self.router.lock().await;
+                    router
+                        .bandwidth_mut()
+                        .record_cover(&peer_id, MessageClass::Data, cover_size);
+                    sent += 1;
+                }
+                Err(SecureCommsError::PeerNotFound(_)) | Err(SecureCommsError::ChannelNotEstablished) => {
+                    self.padding.remove_peer(&peer_id);
+                }
+                Err(_) => self.padding.record_send_at(&peer_id, now),
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Fail with `Timeout` when the message deadline has passed
+    fn check_not_expired(message: &NetworkMessage, stage: ExpiryStage) -> Result<()> {
+        if is_expired(message.expires_at_ms(), now_ms()) {
+            record_expired(stage);
+            return Err(SecureCommsError::Timeout(format!(
+                "Message expired before {}",
+                stage.as_str()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Account for an inbound message against the peer's ingress budget
+    pub async fn record_inbound(&mut self, peer_id: &str, message: &NetworkMessage) -> Result<()> {
+        self.capture_frame(FrameDirection::Inbound, peer_id, message);
+        Self::check_not_expired(message, ExpiryStage::Receive)?;
+        let message_size = message.frame_size()?;
+
+        let mut router = self.router.lock().await;
+        if router
+            .bandwidth_mut()
+            .record_ingress(peer_id, message.message_class(), message_size)
+        {
+            Ok(())
+        } else {
+            Err(SecureCommsError::ResourceExhausted(format!(
+                "Ingress {} bandwidth from {} exceeded",
+                message.message_class().as_str(),
+                peer_id
+            )))
+        }
+    }
+
+    /// Start capturing wire frames, replacing any running capture
+    ///
+    /// Frames are only recorded for channels enabled with
+    /// `set_channel_capture`, or for every channel when the config asks
+    /// for it.
+    pub fn start_capture(&mut self, config: CaptureConfig) -> Result<()> {
+        let capture = PacketCapture::open(config)?;
+        println!("🔍 Capturing wire frames to {:?}", capture.config().path);
+        self.capture = Some(capture);
+        Ok(())
+    }
+
+    /// Stop capturing, returning the session's counters
+    pub fn stop_capture(&mut self) -> Option<CaptureStats> {
+        self.capture.take().map(|capture| capture.stats())
+    }
+
+    /// Start or stop capturing the channel to `peer_id`
+    ///
+    /// Fails with `SecureCommsError::Configuration` when no capture is running.
+    pub fn set_channel_capture(&mut self, peer_id: &str, enabled: bool) -> Result<()> {
+        self.capture
+            .as_mut()
+            .ok_or_else(|| {
+                SecureCommsError::Configuration("No packet capture running".to_string())
+            })?
+            .set_channel(peer_id, enabled);
+        Ok(())
+    }
+
+    /// Counters of the running capture
+    pub fn capture_stats(&self) -> Option<CaptureStats> {
+        self.capture.as_ref().map(PacketCapture::stats)
+    }
+
+    /// Record a frame exchanged with `peer_id` if its channel is captured
+    ///
+    /// Capture failures are logged and never fail the traffic itself.
+    pub fn capture_frame<T: Serialize>(
+        &mut self,
+        direction: FrameDirection,
+        peer_id: &str,
+        frame: &T,
+    ) {
+        if let Some(capture) = &mut self.capture {
+            if let Err(e) = capture.capture(direction, peer_id, frame) {
+                println!("⚠️ Packet capture failed for {}: {}", peer_id, e);
+            }
+        }
+    }
+
+    /// Set or clear the per-peer bandwidth cap for a class
+    pub async fn set_bandwidth_limit(
+        &mut self,
+        direction: Direction,
+        class: MessageClass,
+        limit: Option<ClassLimit>,
+    ) {
+        let mut router = self.router.lock().await;
+        router.bandwidth_mut().set_limit(direction, class, limit);
+    }
+
+    /// Get bandwidth usage for a peer
+    pub async fn get_bandwidth_usage(&self, peer_id: &str) -> Option<BandwidthUsage> {
+        let router = self.router.lock().await;
+        router.bandwidth().peer_usage(peer_id)
+    }
+
+    /// Get bandwidth usage for all peers
+    pub async fn bandwidth_report(&self) -> Vec<BandwidthUsage> {
+        let router = self.router.lock().await;
+        router.bandwidth().usage_report()
     }
 
     /// Get connection information for peer with real latency measurement
@@ -875,14 +1668,12 @@ impl NetworkComms {
 
     /// Measure actual network latency to peer
     async fn measure_peer_latency(&self, peer_info: &PeerInfo) -> u64 {
-        use std::time::Duration;
         use tokio::net::TcpStream;
 
         let start_time = Instant::now();
         let address = format!("{}:{}", peer_info.address, peer_info.port);
 
-        // Optimized ping timeout for faster measurements
-        let ping_timeout = Duration::from_millis(100);
+        let ping_timeout = self.config.timeouts.latency_probe();
 
         match tokio::time::timeout(ping_timeout, TcpStream::connect(&address)).await {
             Ok(Ok(_stream)) => {
@@ -921,13 +1712,394 @@ impl NetworkComms {
     pub async fn perform_maintenance(&mut self) -> Result<()> {
         let mut router = self.router.lock().await;
         router.cleanup_expired_channels(self.config.channel_timeout_seconds);
+        router.reputation_mut().apply_decay();
+        Ok(())
+    }
+
+    /// Get reputation record for a peer
+    pub async fn get_peer_reputation(&self, peer_id: &str) -> Option<PeerReputation> {
+        let router = self.router.lock().await;
+        router.reputation().get_reputation(peer_id).cloned()
+    }
+
+    /// Get reputation records for all known peers, best first
+    pub async fn list_peer_reputations(&self) -> Vec<PeerReputation> {
+        let router = self.router.lock().await;
+        router.reputation().list_reputations()
+    }
+
+    /// Pin a peer's reputation score manually
+    pub async fn override_peer_reputation(&mut self, peer_id: &str, score: f64) -> Result<()> {
+        let mut router = self.router.lock().await;
+        router.reputation_mut().set_override(peer_id, score)?;
+        router.sync_trust_score(peer_id);
         Ok(())
     }
 
+    /// Remove a manual reputation override
+    pub async fn clear_peer_reputation_override(&mut self, peer_id: &str) {
+        let mut router = self.router.lock().await;
+        router.reputation_mut().clear_override(peer_id);
+        router.sync_trust_score(peer_id);
+    }
+
+    /// Describe a peer for latency-aware selection, see `crate::hedging`
+    pub async fn peer_candidate(&self, peer_id: &str) -> PeerCandidate {
+        let router = self.router.lock().await;
+        PeerCandidate::from_reputation(peer_id, router.reputation())
+    }
+
+    /// Record a peer's measured response latency
+    pub async fn record_peer_response(&mut self, peer_id: &str, latency_ms: u64) {
+        let mut router = self.router.lock().await;
+        router.record_peer_success(peer_id, latency_ms);
+    }
+
+    /// Report a protocol violation observed from a peer
+    pub async fn report_protocol_violation(&mut self, peer_id: &str, reason: &str) {
+        let mut router = self.router.lock().await;
+        router.record_protocol_violation(peer_id, reason);
+    }
+
+    /// Select the most reputable relay towards a destination
+    pub async fn select_relay_peer(&self, destination: &str) -> Option<String> {
+        let router = self.router.lock().await;
+        router.select_relay(destination)
+    }
+
+    /// Deliver gossip frames to peers over their secure channels
+    ///
+    /// Frames whose destination is unreachable are skipped and recorded as
+    /// peer failures; frames held back by bandwidth shaping are skipped
+    /// silently. Returns the number of frames sent.
+    pub async fn dispatch_gossip(&mut self, actions: Vec<GossipAction>) -> Result<usize> {
+        let mut sent = 0;
+        for action in actions {
+            let data = serde_json::to_vec(&action.frame).map_err(|e| {
+                SecureCommsError::NetworkComm(format!("Gossip frame serialization failed: {}", e))
+            })?;
+
+            match self.send_secure_data(&action.peer_id, &data).await {
+                Ok(()) => sent += 1,
+                // Local shaping is not the peer's fault
+                Err(SecureCommsError::ResourceExhausted(_)) => {}
+                Err(_) => {
+                    let mut router = self.router.lock().await;
+                    router.record_peer_failure(&action.peer_id);
+                }
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Decode a gossip frame received as secure data
+    pub fn decode_gossip_frame(&self, data: &[u8]) -> Result<GossipFrame> {
+        serde_json::from_slice(data)
+            .map_err(|e| SecureCommsError::NetworkComm(format!("Invalid gossip frame: {}", e)))
+    }
+
+    /// Send state sync messages to a peer as bulk traffic
+    ///
+    /// Snapshot chunks are shaped like any other bulk transfer so catching
+    /// up never starves consensus traffic. Returns the number of messages sent.
+    pub async fn send_sync_messages(
+        &mut self,
+        peer_id: &str,
+        messages: Vec<SyncMessage>,
+    ) -> Result<usize> {
+        let mut sent = 0;
+        for message in messages {
+            let data = serde_json::to_vec(&message).map_err(|e| {
+                SecureCommsError::NetworkComm(format!("Sync message serialization failed: {}", e))
+            })?;
+            self.send_secure_data_with_class(peer_id, &data, MessageClass::Bulk)
+                .await?;
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    /// Decode a state sync message received as secure data
+    pub fn decode_sync_message(&self, data: &[u8]) -> Result<SyncMessage> {
+        serde_json::from_slice(data)
+            .map_err(|e| SecureCommsError::NetworkComm(format!("Invalid sync message: {}", e)))
+    }
+
+    /// Record the timestamp of an authenticated handshake message
+    ///
+    /// Must only be called after the handshake signature has been verified.
+    /// Critical skew raises a security alert to network event listeners.
+    pub async fn record_handshake_timestamp(
+        &mut self,
+        message: &NetworkMessage,
+        rtt_ms: u64,
+    ) -> Option<SkewStatus> {
+        let (peer_id, timestamp_ms) = match message {
+            NetworkMessage::HandshakeInit {
+                sender_id,
+                timestamp_ms,
+                ..
+            }
+            | NetworkMessage::HandshakeResponse {
+                sender_id,
+                timestamp_ms,
+                ..
+            } => (sender_id.clone(), *timestamp_ms),
+            _ => return None,
+        };
+
+        let status = self
+            .clock
+            .record_handshake_timestamp(&peer_id, timestamp_ms, rtt_ms);
+        self.alert_on_skew(&peer_id, status).await;
+        Some(status)
+    }
+
+    /// Screen an inbound `HandshakeInit` before any PQC work
+    ///
+    /// `source` is the transport address the message arrived from. A
+    /// `Retry` admission is answered with `HandshakeRetry` and the attempt
+    /// dropped; an admitted handshake holds its permit until it completes.
+    pub fn screen_handshake(
+        &mut self,
+        source: &str,
+        message: &NetworkMessage,
+    ) -> Result<HandshakeAdmission> {
+        let proof = match message {
+            NetworkMessage::HandshakeInit { proof, .. } => proof.as_ref(),
+            _ => {
+                return Err(SecureCommsError::Validation(
+                    "Only HandshakeInit messages are screened".to_string(),
+                ))
+            }
+        };
+        self.handshake_guard.screen(source, proof)
+    }
+
+    /// Build the `HandshakeRetry` answering a screened handshake
+    pub fn handshake_retry(&self, token: RetryToken) -> NetworkMessage {
+        NetworkMessage::HandshakeRetry {
+            sender_id: self.local_peer.peer_id.clone(),
+            token,
+        }
+    }
+
+    /// Replace the handshake screening settings
+    pub fn configure_handshake_guard(&mut self, config: HandshakeGuardConfig) {
+        self.handshake_guard.set_config(config);
+    }
+
+    /// Get handshake guard
+    pub fn handshake_guard(&self) -> &HandshakeGuard {
+        &self.handshake_guard
+    }
+
+    /// Start a secure time sync exchange with a peer
+    pub async fn request_time_sync(&mut self, peer_id: &str) -> Result<()> {
+        let request = self.clock.create_sync_request(peer_id);
+        self.send_message(peer_id, NetworkMessage::TimeSyncRequest(request))
+            .await
+    }
+
+    /// Answer a peer's time sync request over its secure channel
+    pub async fn respond_time_sync(&mut self, peer_id: &str, request: &TimeSyncRequest) -> Result<()> {
+        let session_key = self.session_key_for(peer_id).await?;
+        let response = self.clock.respond_to_sync(request, &session_key);
+        self.send_message(peer_id, NetworkMessage::TimeSyncResponse(response))
+            .await
+    }
+
+    /// Complete a time sync exchange with a peer's response
+    pub async fn handle_time_sync_response(
+        &mut self,
+        peer_id: &str,
+        response: &TimeSyncResponse,
+    ) -> Result<SkewSample> {
+        let session_key = self.session_key_for(peer_id).await?;
+        let sample = self.clock.complete_sync(peer_id, response, &session_key)?;
+        let status = self.clock.peer_status(peer_id);
+        self.alert_on_skew(peer_id, status).await;
+        Ok(sample)
+    }
+
+    /// Get clock skew monitor
+    pub fn clock(&self) -> &ClockSkewMonitor {
+        &self.clock
+    }
+
+    /// Start migrating a peer's secure channel to a new transport endpoint
+    ///
+    /// Verifies the new endpoint is reachable and returns the challenge to
+    /// deliver to the peer over the new transport.
+    pub async fn begin_channel_migration(
+        &mut self,
+        peer_id: &str,
+        endpoint: TransportEndpoint,
+    ) -> Result<MigrationChallenge> {
+        let channel_id = {
+            let router = self.router.lock().await;
+            router
+                .routing_table
+                .get(peer_id)
+                .cloned()
+                .ok_or(SecureCommsError::ChannelNotEstablished)?
+        };
+
+        let probe_latency = self.probe_transport(peer_id, &endpoint).await?;
+        println!(
+            "🔀 Migrating channel {} for peer {} to {} (probe {}ms)",
+            channel_id, peer_id, endpoint, probe_latency
+        );
+
+        Ok(self
+            .migrations
+            .create_challenge(&channel_id, peer_id, endpoint))
+    }
+
+    /// Answer a peer's migration challenge with proof of the session key
+    pub async fn answer_channel_migration(
+        &self,
+        peer_id: &str,
+        challenge: &MigrationChallenge,
+    ) -> Result<MigrationProof> {
+        let session_key = self.session_key_for(peer_id).await?;
+        Ok(MigrationManager::prove(challenge, &session_key))
+    }
+
+    /// Verify the peer's migration proof and rebind the channel
+    pub async fn complete_channel_migration(
+        &mut self,
+        peer_id: &str,
+        proof: &MigrationProof,
+    ) -> Result<MigrationRecord> {
+        let session_key = self.session_key_for(peer_id).await?;
+        let record = match self.migrations.verify_proof(peer_id, proof, &session_key) {
+            Ok(record) => record,
+            Err(e) => {
+                if matches!(e, SecureCommsError::AuthenticationFailed) {
+                    let mut router = self.router.lock().await;
+                    router.record_protocol_violation(peer_id, "invalid channel migration proof");
+                }
+                return Err(e);
+            }
+        };
+
+        let mut router = self.router.lock().await;
+        router.rebind_channel(&record)?;
+        Ok(record)
+    }
+
+    /// Get channel migration manager
+    pub fn migrations(&self) -> &MigrationManager {
+        &self.migrations
+    }
+
+    /// Check that a transport endpoint is reachable
+    ///
+    /// TCP endpoints are probed with a full connect; QUIC endpoints are
+    /// checked at the UDP level since the QUIC handshake belongs to the
+    /// transport that will carry the channel.
+    async fn probe_transport(&self, peer_id: &str, endpoint: &TransportEndpoint) -> Result<u64> {
+        match endpoint.kind {
+            TransportKind::Tcp => {
+                let probe_peer = PeerInfo {
+                    peer_id: peer_id.to_string(),
+                    address: endpoint.address.clone(),
+                    port: endpoint.port,
+                    public_key: Vec::new(),
+                    connection_status: ConnectionStatus::Connecting,
+                    last_seen: 0,
+                    trust_score: 0.0,
+                };
+                self.establish_tcp_connection(&probe_peer).await
+            }
+            TransportKind::Quic => {
+                let start_time = Instant::now();
+                let socket = tokio::net::UdpSocket::bind("0.0.0.0:0")
+                    .await
+                    .map_err(|e| SecureCommsError::NetworkComm(format!("UDP bind failed: {}", e)))?;
+                socket
+                    .connect(endpoint.socket_address())
+                    .await
+                    .map_err(|e| {
+                        SecureCommsError::NetworkComm(format!(
+                            "QUIC endpoint {} unreachable: {}",
+                            endpoint, e
+                        ))
+                    })?;
+                Ok(start_time.elapsed().as_millis() as u64)
+            }
+        }
+    }
+
+    /// Session key of the peer's established secure channel
+    pub(crate) async fn session_key_for(&self, peer_id: &str) -> Result<Vec<u8>> {
+        let router = self.router.lock().await;
+        let channel_id = router
+            .routing_table
+            .get(peer_id)
+            .ok_or_else(|| SecureCommsError::PeerNotFound(peer_id.to_string()))?;
+        router
+            .get_channel(channel_id)
+            .map(|channel| channel.session_key.clone())
+            .ok_or(SecureCommsError::ChannelNotEstablished)
+    }
+
+    /// Raise a security alert for critical clock skew
+    async fn alert_on_skew(&self, peer_id: &str, status: SkewStatus) {
+        if status == SkewStatus::Critical {
+            let router = self.router.lock().await;
+            router.broadcast_event(NetworkEvent::SecurityAlert {
+                peer_id: peer_id.to_string(),
+                alert_type: format!(
+                    "Clock skew of {}ms exceeds threshold",
+                    self.clock.peer_skew_ms(peer_id).unwrap_or(0)
+                ),
+            });
+        }
+    }
+
     /// Get network statistics
     pub async fn get_network_stats(&self) -> HashMap<String, serde_json::Value> {
         let router = self.router.lock().await;
-        router.get_stats()
+        let mut stats = router.get_stats();
+        stats.insert(
+            "migrations".to_string(),
+            serde_json::to_value(self.migrations.get_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        stats.insert(
+            "clock_sync".to_string(),
+            serde_json::to_value(self.clock.get_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        stats.insert(
+            "handshake_guard".to_string(),
+            serde_json::to_value(self.handshake_guard.get_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        stats.insert(
+            "traffic_padding".to_string(),
+            serde_json::to_value(self.padding.get_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        stats
+    }
+
+    /// Select the write path used by `frame_writer`
+    pub fn set_frame_io_mode(&mut self, mode: FrameIoMode) {
+        self.config.frame_io = mode;
+    }
+
+    /// Set the connect and latency probe deadlines
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.config.timeouts = timeouts;
+    }
+
+    /// Connect and latency probe deadlines in force
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.config.timeouts
+    }
+
+    /// Frame writer over a connected stream using the configured write path
+    pub fn frame_writer<W: AsyncWrite + Unpin>(&self, stream: W) -> FrameWriter<W> {
+        FrameWriter::new(stream, self.config.frame_io)
     }
 
     /// Get performance metrics
@@ -956,7 +2128,7 @@ impl NetworkComms {
     /// Verify integrity hash
     pub fn verify_integrity(&self, data: &[u8], hash: &[u8]) -> bool {
         let computed_hash = self.compute_integrity_hash(data);
-        computed_hash == hash
+        ct_eq(&computed_hash, hash)
     }
 }
 
@@ -1068,6 +2240,123 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_reputation_gates_routing() {
+        let mut router = MessageRouter::new();
+        router.add_peer(PeerInfo {
+            peer_id: "suspect".to_string(),
+            address: "127.0.0.1".to_string(),
+            port: 8081,
+            public_key: vec![1, 2, 3, 4],
+            connection_status: ConnectionStatus::Connected,
+            last_seen: chrono::Utc::now().timestamp() as u64,
+            trust_score: 1.0,
+        });
+        router.establish_channel("suspect", vec![7u8; 32]).unwrap();
+
+        let message = NetworkMessage::Keepalive { timestamp: 0 };
+        assert!(router.route_message("suspect", &message).is_ok());
+
+        for _ in 0..5 {
+            router.record_protocol_violation("suspect", "integrity hash mismatch");
+        }
+
+        assert!(router.get_peer("suspect").unwrap().trust_score < 0.2);
+        assert!(router.route_message("suspect", &message).is_err());
+        assert!(router.select_relay("other").is_none());
+    }
+
+    #[test]
+    fn test_bulk_shaping_spares_consensus() {
+        let mut router = MessageRouter::new();
+        router.add_peer(PeerInfo {
+            peer_id: "validator".to_string(),
+            address: "127.0.0.1".to_string(),
+            port: 8081,
+            public_key: vec![1, 2, 3, 4],
+            connection_status: ConnectionStatus::Connected,
+            last_seen: chrono::Utc::now().timestamp() as u64,
+            trust_score: 1.0,
+        });
+        router.establish_channel("validator", vec![7u8; 32]).unwrap();
+        router.bandwidth_mut().set_limit(
+            Direction::Egress,
+            MessageClass::Bulk,
+            Some(ClassLimit {
+                bytes_per_second: 1,
+                burst_bytes: 256,
+            }),
+        );
+
+        let chunk = NetworkMessage::SecureData {
+            session_id: "bulk".to_string(),
+            encrypted_payload: vec![0u8; 128],
+            integrity_hash: vec![0u8; 32],
+            expires_at_ms: None,
+        };
+        assert!(router
+            .route_message_with_class("validator", &chunk, MessageClass::Bulk)
+            .is_err());
+        assert!(router
+            .route_message_with_class("validator", &chunk, MessageClass::Consensus)
+            .is_ok());
+
+        let usage = router.bandwidth().peer_usage("validator").unwrap();
+        assert_eq!(usage.classes["bulk"].egress_throttled, 1);
+        assert!(usage.classes["consensus"].egress_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_messages_not_delivered() {
+        let mut network = NetworkComms::new("local".to_string(), "127.0.0.1".to_string(), 8080)
+            .await
+            .unwrap();
+        let expired = NetworkMessage::SecureData {
+            session_id: "session".to_string(),
+            encrypted_payload: b"stale quote".to_vec(),
+            integrity_hash: vec![0u8; 32],
+            expires_at_ms: Some(now_ms().saturating_sub(1)),
+        };
+        assert_eq!(expired.expires_at_ms().map(|d| d < now_ms()), Some(true));
+        assert!(matches!(
+            network.record_inbound("peer", &expired).await,
+            Err(SecureCommsError::Timeout(_))
+        ));
+        assert!(NetworkComms::check_not_expired(&expired, ExpiryStage::Send).is_err());
+    }
+
+    #[test]
+    fn test_channel_migration_preserves_session() {
+        let mut router = MessageRouter::new();
+        router.add_peer(PeerInfo {
+            peer_id: "mobile".to_string(),
+            address: "192.168.1.10".to_string(),
+            port: 8081,
+            public_key: vec![1, 2, 3, 4],
+            connection_status: ConnectionStatus::Connected,
+            last_seen: chrono::Utc::now().timestamp() as u64,
+            trust_score: 1.0,
+        });
+        let session_key = vec![7u8; 32];
+        let channel_id = router.establish_channel("mobile", session_key.clone()).unwrap();
+        let message = NetworkMessage::Keepalive { timestamp: 0 };
+        router.route_message("mobile", &message).unwrap();
+
+        let mut migrations = MigrationManager::default();
+        let endpoint = TransportEndpoint::new(TransportKind::Quic, "10.0.0.7", 4433);
+        let challenge = migrations.create_challenge(&channel_id, "mobile", endpoint);
+        let proof = MigrationManager::prove(&challenge, &session_key);
+        let record = migrations.verify_proof("mobile", &proof, &session_key).unwrap();
+        router.rebind_channel(&record).unwrap();
+
+        let channel = router.get_channel(&channel_id).unwrap();
+        assert_eq!(channel.transport, TransportKind::Quic);
+        assert_eq!(channel.session_key, session_key);
+        assert_eq!(channel.send_counter, 1);
+        assert_eq!(router.get_peer("mobile").unwrap().address, "10.0.0.7");
+        assert!(router.route_message("mobile", &message).is_ok());
+    }
+
     #[tokio::test]
     async fn test_integrity_verification() {
         let network = NetworkComms::new("test".to_string(), "127.0.0.1".to_string(), 8080)
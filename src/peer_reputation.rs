@@ -0,0 +1,481 @@
+//! # Peer Reputation - Behaviour-Based Scoring and Adaptive Routing
+//!
+//! Tracks the observed reliability of every remote peer and condenses it into a
+//! single reputation score (0.0-1.0). The score feeds routing decisions, relay
+//! selection and consensus proposal admission so that slow, flaky or
+//! misbehaving peers are gradually pushed out of the critical path.
+//!
+//! ## Scoring Inputs
+//!
+//! - **Reliability**: Successful vs. failed interactions (Laplace-smoothed ratio)
+//! - **Latency**: Exponentially weighted average round-trip latency against a target
+//! - **Protocol Violations**: Multiplicative penalty for malformed or malicious traffic
+//!
+//! ## Decay
+//!
+//! All observations decay exponentially with a configurable half-life, so a peer
+//! that misbehaved an hour ago slowly drifts back towards a neutral score and a
+//! peer that was reliable last week has to keep earning its reputation.
+//!
+//! ## Operator Overrides
+//!
+//! Scores can be pinned manually (e.g. to quarantine a peer or to whitelist a
+//! trusted relay). Overrides take precedence over the computed score until
+//! cleared.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::peer_reputation::{ReputationConfig, ReputationManager};
+//!
+//! let mut reputation = ReputationManager::new(ReputationConfig::default());
+//! reputation.record_success("peer_a", 12);
+//! reputation.record_failure("peer_b");
+//! reputation.record_protocol_violation("peer_c", "invalid integrity hash");
+//!
+//! // Pick the best relay among candidates
+//! let relay = reputation.select_relay(&["peer_a".to_string(), "peer_b".to_string()], None);
+//! assert_eq!(relay.as_deref(), Some("peer_a"));
+//!
+//! // Quarantine a peer manually
+//! reputation.set_override("peer_c", 0.0).unwrap();
+//! assert!(!reputation.is_routable("peer_c"));
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Configuration for reputation scoring, decay and decision thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationConfig {
+    /// Score assigned to peers with no history
+    pub initial_score: f64,
+    /// Weight of the reliability component (success ratio)
+    pub reliability_weight: f64,
+    /// Weight of the latency component
+    pub latency_weight: f64,
+    /// Penalty exponent applied per (decayed) protocol violation
+    pub violation_penalty: f64,
+    /// Latency at or below which the latency component is perfect
+    pub latency_target_ms: f64,
+    /// Smoothing factor for the latency moving average (0.0-1.0)
+    pub latency_smoothing: f64,
+    /// Half-life in seconds for decaying observations
+    pub decay_half_life_seconds: u64,
+    /// Minimum score required to route traffic to a peer
+    pub min_routing_score: f64,
+    /// Minimum score required to use a peer as a relay
+    pub min_relay_score: f64,
+    /// Minimum score required to accept consensus proposals from a peer
+    pub min_proposal_score: f64,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            initial_score: 0.75,
+            reliability_weight: 0.7,
+            latency_weight: 0.3,
+            violation_penalty: 0.5,
+            latency_target_ms: 50.0,
+            latency_smoothing: 0.2,
+            decay_half_life_seconds: 3600, // 1 hour
+            min_routing_score: 0.2,
+            min_relay_score: 0.6,
+            min_proposal_score: 0.5,
+        }
+    }
+}
+
+/// Reputation record for a single peer
+///
+/// Counters are stored as floating point values because they decay
+/// continuously over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerReputation {
+    /// Peer identifier
+    pub peer_id: String,
+    /// Decayed count of successful interactions
+    pub successes: f64,
+    /// Decayed count of failed interactions
+    pub failures: f64,
+    /// Decayed count of protocol violations
+    pub violations: f64,
+    /// Exponentially weighted average latency in milliseconds
+    pub avg_latency_ms: Option<f64>,
+    /// Score computed from observations (0.0-1.0)
+    pub computed_score: f64,
+    /// Manual override taking precedence over the computed score
+    pub override_score: Option<f64>,
+    /// Reason for the most recent protocol violation
+    pub last_violation: Option<String>,
+    /// Unix timestamp of the last observation or decay
+    pub last_updated: u64,
+}
+
+impl PeerReputation {
+    fn new(peer_id: String, initial_score: f64, now: u64) -> Self {
+        Self {
+            peer_id,
+            successes: 0.0,
+            failures: 0.0,
+            violations: 0.0,
+            avg_latency_ms: None,
+            computed_score: initial_score,
+            override_score: None,
+            last_violation: None,
+            last_updated: now,
+        }
+    }
+
+    /// Effective score, honouring any manual override
+    pub fn score(&self) -> f64 {
+        self.override_score.unwrap_or(self.computed_score)
+    }
+
+    /// Total (decayed) number of observations
+    pub fn observations(&self) -> f64 {
+        self.successes + self.failures + self.violations
+    }
+}
+
+/// Reputation manager tracking all known peers
+///
+/// Owns the reputation records and provides the decision helpers used by the
+/// router (`is_routable`, `select_relay`, `rank_peers`) and the consensus
+/// engine (`accepts_proposals_from`).
+#[derive(Debug, Clone)]
+pub struct ReputationManager {
+    /// Scoring configuration
+    config: ReputationConfig,
+    /// Reputation records keyed by peer ID
+    peers: HashMap<String, PeerReputation>,
+}
+
+impl ReputationManager {
+    /// Create new reputation manager
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Get scoring configuration
+    pub fn get_config(&self) -> &ReputationConfig {
+        &self.config
+    }
+
+    /// Register a peer with an explicit starting score
+    ///
+    /// Existing records are left untouched so that reconnecting peers keep
+    /// their history.
+    pub fn register_peer(&mut self, peer_id: &str, initial_score: f64) {
+        let now = Self::now();
+        self.peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerReputation::new(peer_id.to_string(), initial_score.clamp(0.0, 1.0), now));
+    }
+
+    /// Record a successful interaction with measured latency
+    pub fn record_success(&mut self, peer_id: &str, latency_ms: u64) {
+        let smoothing = self.config.latency_smoothing;
+        let record = self.entry(peer_id);
+        record.successes += 1.0;
+        record.avg_latency_ms = Some(match record.avg_latency_ms {
+            Some(avg) => avg * (1.0 - smoothing) + latency_ms as f64 * smoothing,
+            None => latency_ms as f64,
+        });
+        self.recompute(peer_id);
+    }
+
+    /// Record a failed interaction (timeout, connection refused, send error)
+    pub fn record_failure(&mut self, peer_id: &str) {
+        self.entry(peer_id).failures += 1.0;
+        self.recompute(peer_id);
+    }
+
+    /// Record a protocol violation (bad integrity hash, malformed message, etc.)
+    pub fn record_protocol_violation(&mut self, peer_id: &str, reason: &str) {
+        let record = self.entry(peer_id);
+        record.violations += 1.0;
+        record.last_violation = Some(reason.to_string());
+        self.recompute(peer_id);
+    }
+
+    /// Get effective reputation score for a peer
+    ///
+    /// Unknown peers receive the configured initial score.
+    pub fn score(&self, peer_id: &str) -> f64 {
+        self.peers
+            .get(peer_id)
+            .map(|record| record.score())
+            .unwrap_or(self.config.initial_score)
+    }
+
+    /// Get full reputation record for a peer
+    pub fn get_reputation(&self, peer_id: &str) -> Option<&PeerReputation> {
+        self.peers.get(peer_id)
+    }
+
+    /// List all reputation records, best score first
+    pub fn list_reputations(&self) -> Vec<PeerReputation> {
+        let mut records: Vec<PeerReputation> = self.peers.values().cloned().collect();
+        records.sort_by(|a, b| b.score().partial_cmp(&a.score()).unwrap_or(std::cmp::Ordering::Equal));
+        records
+    }
+
+    /// Pin a peer's score manually
+    pub fn set_override(&mut self, peer_id: &str, score: f64) -> Result<()> {
+        if !(0.0..=1.0).contains(&score) {
+            return Err(SecureCommsError::Validation(format!(
+                "Reputation override must be within 0.0-1.0, got {}",
+                score
+            )));
+        }
+        self.entry(peer_id).override_score = Some(score);
+        Ok(())
+    }
+
+    /// Remove a manual override, returning to the computed score
+    pub fn clear_override(&mut self, peer_id: &str) {
+        if let Some(record) = self.peers.get_mut(peer_id) {
+            record.override_score = None;
+        }
+    }
+
+    /// Forget a peer entirely
+    pub fn remove_peer(&mut self, peer_id: &str) -> Option<PeerReputation> {
+        self.peers.remove(peer_id)
+    }
+
+    /// Whether traffic may be routed to the peer
+    pub fn is_routable(&self, peer_id: &str) -> bool {
+        self.score(peer_id) >= self.config.min_routing_score
+    }
+
+    /// Whether the peer may be used to relay traffic for others
+    pub fn is_relay_eligible(&self, peer_id: &str) -> bool {
+        self.score(peer_id) >= self.config.min_relay_score
+    }
+
+    /// Whether consensus proposals from the peer should be accepted
+    pub fn accepts_proposals_from(&self, peer_id: &str) -> bool {
+        self.score(peer_id) >= self.config.min_proposal_score
+    }
+
+    /// Rank routable candidates by reputation, best first
+    pub fn rank_peers(&self, candidates: &[String]) -> Vec<String> {
+        let mut ranked: Vec<(String, f64)> = candidates
+            .iter()
+            .filter(|peer_id| self.is_routable(peer_id))
+            .map(|peer_id| (peer_id.clone(), self.score(peer_id)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.into_iter().map(|(peer_id, _)| peer_id).collect()
+    }
+
+    /// Select the best relay among candidates, optionally excluding one peer
+    /// (typically the final destination)
+    pub fn select_relay(&self, candidates: &[String], exclude: Option<&str>) -> Option<String> {
+        self.rank_peers(candidates)
+            .into_iter()
+            .filter(|peer_id| Some(peer_id.as_str()) != exclude)
+            .find(|peer_id| self.is_relay_eligible(peer_id))
+    }
+
+    /// Apply time-based decay to all records
+    pub fn apply_decay(&mut self) {
+        self.apply_decay_at(Self::now());
+    }
+
+    /// Apply time-based decay as of the given Unix timestamp
+    pub fn apply_decay_at(&mut self, now: u64) {
+        let half_life = self.config.decay_half_life_seconds.max(1) as f64;
+        let peer_ids: Vec<String> = self.peers.keys().cloned().collect();
+
+        for peer_id in peer_ids {
+            if let Some(record) = self.peers.get_mut(&peer_id) {
+                let elapsed = now.saturating_sub(record.last_updated) as f64;
+                if elapsed <= 0.0 {
+                    continue;
+                }
+                let factor = 0.5f64.powf(elapsed / half_life);
+                record.successes *= factor;
+                record.failures *= factor;
+                record.violations *= factor;
+                record.last_updated = now;
+            }
+            self.recompute(&peer_id);
+        }
+    }
+
+    /// Get reputation statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+
+        stats.insert("tracked_peers".to_string(), serde_json::json!(self.peers.len()));
+
+        let routable = self.peers.keys().filter(|p| self.is_routable(p)).count();
+        stats.insert("routable_peers".to_string(), serde_json::json!(routable));
+
+        let overridden = self.peers.values().filter(|r| r.override_score.is_some()).count();
+        stats.insert("overridden_peers".to_string(), serde_json::json!(overridden));
+
+        let avg_score = if self.peers.is_empty() {
+            0.0
+        } else {
+            self.peers.values().map(|r| r.score()).sum::<f64>() / self.peers.len() as f64
+        };
+        stats.insert("average_score".to_string(), serde_json::json!(avg_score));
+
+        stats
+    }
+
+    /// Get or create the record for a peer
+    fn entry(&mut self, peer_id: &str) -> &mut PeerReputation {
+        let initial_score = self.config.initial_score;
+        let now = Self::now();
+        self.peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerReputation::new(peer_id.to_string(), initial_score, now))
+    }
+
+    /// Recompute the score of a peer from its observations
+    fn recompute(&mut self, peer_id: &str) {
+        let config = &self.config;
+        let record = match self.peers.get_mut(peer_id) {
+            Some(record) => record,
+            None => return,
+        };
+
+        // Peers with no remaining observations fall back to the neutral prior
+        if record.successes + record.failures < f64::EPSILON && record.violations < f64::EPSILON {
+            record.computed_score = config.initial_score;
+            return;
+        }
+
+        // Laplace-smoothed success ratio
+        let reliability = (record.successes + 1.0) / (record.successes + record.failures + 2.0);
+
+        let latency = match record.avg_latency_ms {
+            Some(avg) if avg > config.latency_target_ms => config.latency_target_ms / avg,
+            _ => 1.0,
+        };
+
+        let total_weight = config.reliability_weight + config.latency_weight;
+        let base = if total_weight > 0.0 {
+            (config.reliability_weight * reliability + config.latency_weight * latency) / total_weight
+        } else {
+            reliability
+        };
+
+        let penalty = (-config.violation_penalty * record.violations).exp();
+
+        record.computed_score = (base * penalty).clamp(0.0, 1.0);
+        record.last_updated = Self::now().max(record.last_updated);
+    }
+
+    fn now() -> u64 {
+        chrono::Utc::now().timestamp() as u64
+    }
+}
+
+impl Default for ReputationManager {
+    fn default() -> Self {
+        Self::new(ReputationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_peer_gets_initial_score() {
+        let reputation = ReputationManager::default();
+        assert_eq!(reputation.score("nobody"), ReputationConfig::default().initial_score);
+        assert!(reputation.is_routable("nobody"));
+    }
+
+    #[test]
+    fn test_failures_and_violations_lower_score() {
+        let mut reputation = ReputationManager::default();
+        for _ in 0..10 {
+            reputation.record_success("good", 5);
+            reputation.record_failure("flaky");
+        }
+        reputation.record_protocol_violation("evil", "bad integrity hash");
+        reputation.record_protocol_violation("evil", "replayed nonce");
+
+        assert!(reputation.score("good") > reputation.score("flaky"));
+        assert!(reputation.score("flaky") > reputation.score("evil"));
+        assert_eq!(
+            reputation.get_reputation("evil").unwrap().last_violation.as_deref(),
+            Some("replayed nonce")
+        );
+    }
+
+    #[test]
+    fn test_high_latency_reduces_score() {
+        let mut reputation = ReputationManager::default();
+        for _ in 0..5 {
+            reputation.record_success("fast", 10);
+            reputation.record_success("slow", 500);
+        }
+        assert!(reputation.score("fast") > reputation.score("slow"));
+    }
+
+    #[test]
+    fn test_relay_selection_prefers_reputable_peers() {
+        let mut reputation = ReputationManager::default();
+        for _ in 0..5 {
+            reputation.record_success("relay_a", 10);
+            reputation.record_success("relay_b", 200);
+        }
+        reputation.record_protocol_violation("relay_c", "malformed frame");
+
+        let candidates = vec![
+            "relay_a".to_string(),
+            "relay_b".to_string(),
+            "relay_c".to_string(),
+        ];
+        assert_eq!(reputation.select_relay(&candidates, None).as_deref(), Some("relay_a"));
+        assert_eq!(
+            reputation.select_relay(&candidates, Some("relay_a")).as_deref(),
+            Some("relay_b")
+        );
+    }
+
+    #[test]
+    fn test_override_and_clear() {
+        let mut reputation = ReputationManager::default();
+        reputation.record_success("peer", 5);
+
+        reputation.set_override("peer", 0.0).unwrap();
+        assert!(!reputation.is_routable("peer"));
+        assert!(!reputation.accepts_proposals_from("peer"));
+
+        reputation.clear_override("peer");
+        assert!(reputation.is_routable("peer"));
+
+        assert!(reputation.set_override("peer", 1.5).is_err());
+    }
+
+    #[test]
+    fn test_decay_moves_towards_neutral() {
+        let mut reputation = ReputationManager::default();
+        for _ in 0..3 {
+            reputation.record_protocol_violation("peer", "bad signature");
+        }
+        let penalised = reputation.score("peer");
+
+        let last_updated = reputation.get_reputation("peer").unwrap().last_updated;
+        let half_life = reputation.get_config().decay_half_life_seconds;
+        reputation.apply_decay_at(last_updated + half_life * 10);
+
+        assert!(reputation.score("peer") > penalised);
+        assert!(reputation.get_reputation("peer").unwrap().violations < 0.01);
+    }
+}
@@ -20,6 +20,8 @@
 //!
 //! ### Performance Monitoring
 //! - **Real-Time Metrics**: Latency, throughput, and success rate tracking
+//! - **Latency Histograms**: HDR histograms with O(1) recording and p50/p95/p99/p999
+//!   per operation type (establish, send, key exchange)
 //! - **System Resource Monitoring**: CPU, memory, and network usage analysis
 //! - **Performance Analytics**: Comprehensive performance reporting and optimization
 //! - **Alert System**: Automatic alerting for performance degradation
@@ -68,7 +70,7 @@
 //!
 //! ### Basic Performance Manager Setup
 //! ```rust,no_run
-//! use streamlined_secure_comms::performance::{PerformanceManager, PerformanceConfig};
+//! use quantum_forge_secure_comms::performance::{PerformanceManager, PerformanceConfig};
 //!
 //! // Create performance manager with default configuration
 //! let config = PerformanceConfig::default();
@@ -81,7 +83,7 @@
 //!
 //! ### Memory Pool Usage
 //! ```rust,no_run
-//! # use streamlined_secure_comms::performance::{MemoryPool, MemoryPoolConfig};
+//! # use quantum_forge_secure_comms::performance::{AdaptivePoolConfig, MemoryPool, MemoryPoolConfig};
 //! // Create memory pool with custom configuration
 //! let config = MemoryPoolConfig {
 //!     small_buffer_size: 1024,
@@ -89,6 +91,7 @@
 //!     large_buffer_size: 1048576,
 //!     max_buffers_per_pool: 1000,
 //!     cache_hit_threshold: 0.9,
+//!     adaptive: AdaptivePoolConfig::default(),
 //! };
 //! 
 //! let pool = MemoryPool::new(config);
@@ -102,11 +105,16 @@
 //! // Get pool statistics
 //! let stats = pool.get_stats();
 //! println!("Cache hit ratio: {:.2}%", stats["small"].cache_hit_ratio() * 100.0);
+//!
+//! // Resize pools from the workload since the last call and trim idle buffers
+//! for decision in pool.adapt() {
+//!     println!("{} pool: {} -> {} buffers", decision.tier, decision.previous_limit, decision.new_limit);
+//! }
 //! ```
 //!
 //! ### Performance Monitoring
 //! ```rust,no_run
-//! # use streamlined_secure_comms::performance::PerformanceMonitor;
+//! # use quantum_forge_secure_comms::performance::PerformanceMonitor;
 //! # use std::time::Duration;
 //! // Create performance monitor
 //! let monitor = PerformanceMonitor::new();
@@ -115,16 +123,18 @@
 //! monitor.record_request(Duration::from_millis(50), true);
 //! monitor.record_request(Duration::from_millis(75), true);
 //! monitor.record_request(Duration::from_millis(100), false);
+//! monitor.record_operation("send", Duration::from_micros(850), true);
 //! 
 //! // Get performance report
 //! let report = monitor.get_report();
 //! println!("Average latency: {:.2}ms", report.avg_latency_ms);
 //! println!("Success rate: {:.2}%", report.success_rate * 100.0);
+//! println!("Send p99: {:.3}ms", report.operations["send"].p99_ms);
 //! ```
 //!
 //! ### Connection Pool Management
 //! ```rust,no_run
-//! # use streamlined_secure_comms::performance::{ConnectionPool, ConnectionPoolConfig};
+//! # use quantum_forge_secure_comms::performance::ConnectionPoolConfig;
 //! # use std::time::Duration;
 //! // Create connection pool configuration
 //! let config = ConnectionPoolConfig {
@@ -186,6 +196,7 @@
 //! - **Alert Processing**: Fast alert evaluation and notification
 
 use async_trait::async_trait;
+use hdrhistogram::Histogram;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -196,6 +207,7 @@ use std::sync::{
 use std::time::{Duration, Instant};
 
 use crate::logging::{log_info, log_performance, LogCategory};
+use crate::secret_memory::{check_released, SecretBytes, SensitiveKind};
 use crate::Result;
 
 /// Memory pool configuration
@@ -211,6 +223,9 @@ pub struct MemoryPoolConfig {
     pub max_buffers_per_pool: usize,
     /// Cache hit ratio threshold for optimization
     pub cache_hit_threshold: f64,
+    /// Workload-driven pool sizing
+    #[serde(default)]
+    pub adaptive: AdaptivePoolConfig,
 }
 
 impl Default for MemoryPoolConfig {
@@ -221,10 +236,89 @@ impl Default for MemoryPoolConfig {
             large_buffer_size: 1048576, // 1MB
             max_buffers_per_pool: 1000,
             cache_hit_threshold: 0.9, // 90% cache hit ratio
+            adaptive: AdaptivePoolConfig::default(),
+        }
+    }
+}
+
+/// Adaptive memory pool sizing configuration
+///
+/// Each tier starts with `min_buffers_per_pool` retained buffers. A tier
+/// whose hit ratio falls below `cache_hit_threshold` while its allocation
+/// rate holds or rises doubles its limit (up to `max_buffers_per_pool`);
+/// a tier whose allocation rate halves or stops shrinks by half.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptivePoolConfig {
+    /// Resize pools from observed workload (fixed at `max_buffers_per_pool` otherwise)
+    pub enabled: bool,
+    /// Upper bound on bytes retained across all pools
+    pub memory_budget_bytes: usize,
+    /// Buffers each pool may always retain
+    pub min_buffers_per_pool: usize,
+    /// Pooled buffers unused for this long are released
+    pub idle_timeout: Duration,
+    /// Interval between sizing passes run by `PerformanceManager`
+    pub adjust_interval: Duration,
+}
+
+impl Default for AdaptivePoolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            memory_budget_bytes: 256 * 1024 * 1024, // 256MB
+            min_buffers_per_pool: 16,
+            idle_timeout: Duration::from_secs(60),
+            adjust_interval: Duration::from_secs(10),
         }
     }
 }
 
+/// Sizing change made to one pool tier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSizingDecision {
+    pub tier: String,
+    pub previous_limit: usize,
+    pub new_limit: usize,
+    pub hit_ratio: f64,
+    pub allocations_per_second: f64,
+    pub trimmed_buffers: usize,
+    pub reason: String,
+}
+
+/// Buffers of one size tier with its sizing state
+#[derive(Debug)]
+struct PoolTier {
+    /// Pooled buffers with the time they were returned
+    buffers: VecDeque<(Vec<u8>, Instant)>,
+    /// Buffers this tier may currently retain
+    limit: usize,
+    /// Requests since the last sizing pass
+    window_requests: u64,
+    /// Cache hits since the last sizing pass
+    window_hits: u64,
+    /// Allocation rate measured by the last sizing pass
+    last_rate: f64,
+    /// Largest number of buffers held at once
+    peak: usize,
+}
+
+impl PoolTier {
+    fn new(limit: usize) -> Self {
+        Self {
+            buffers: VecDeque::new(),
+            limit,
+            window_requests: 0,
+            window_hits: 0,
+            last_rate: 0.0,
+            peak: 0,
+        }
+    }
+
+    fn pooled_bytes(&self) -> usize {
+        self.buffers.iter().map(|(buffer, _)| buffer.capacity()).sum()
+    }
+}
+
 /// Pool statistics for monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolStats {
@@ -251,11 +345,15 @@ pub struct MemoryPool {
     /// Pool configuration
     config: MemoryPoolConfig,
     /// Small buffer pool
-    small_pool: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    small_pool: Arc<Mutex<PoolTier>>,
     /// Medium buffer pool
-    medium_pool: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    medium_pool: Arc<Mutex<PoolTier>>,
     /// Large buffer pool
-    large_pool: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    large_pool: Arc<Mutex<PoolTier>>,
+    /// Bytes held across all pools
+    pooled_bytes: Arc<AtomicU64>,
+    /// Start of the current sizing window
+    window_start: Arc<Mutex<Instant>>,
     /// Statistics
     stats: Arc<RwLock<HashMap<String, PoolStats>>>,
     /// Performance metrics
@@ -265,11 +363,21 @@ pub struct MemoryPool {
 impl MemoryPool {
     /// Create new memory pool
     pub fn new(config: MemoryPoolConfig) -> Self {
+        let limit = if config.adaptive.enabled {
+            config
+                .adaptive
+                .min_buffers_per_pool
+                .min(config.max_buffers_per_pool)
+        } else {
+            config.max_buffers_per_pool
+        };
         Self {
             config,
-            small_pool: Arc::new(Mutex::new(VecDeque::new())),
-            medium_pool: Arc::new(Mutex::new(VecDeque::new())),
-            large_pool: Arc::new(Mutex::new(VecDeque::new())),
+            small_pool: Arc::new(Mutex::new(PoolTier::new(limit))),
+            medium_pool: Arc::new(Mutex::new(PoolTier::new(limit))),
+            large_pool: Arc::new(Mutex::new(PoolTier::new(limit))),
+            pooled_bytes: Arc::new(AtomicU64::new(0)),
+            window_start: Arc::new(Mutex::new(Instant::now())),
             stats: Arc::new(RwLock::new(HashMap::new())),
             allocation_times: Arc::new(RwLock::new(VecDeque::with_capacity(1000))),
         }
@@ -317,8 +425,12 @@ impl MemoryPool {
         };
 
         let mut pool_guard = pool.lock();
-        if let Some(mut buffer) = pool_guard.pop_front() {
-            // Cache hit
+        pool_guard.window_requests += 1;
+        if let Some((mut buffer, _)) = pool_guard.buffers.pop_back() {
+            // Cache hit - most recently returned buffer, so idle ones age out
+            pool_guard.window_hits += 1;
+            self.pooled_bytes
+                .fetch_sub(buffer.capacity() as u64, Ordering::Relaxed);
             buffer.clear();
             buffer.resize(size, 0);
             self.update_stats(&pool_type, true);
@@ -330,8 +442,24 @@ impl MemoryPool {
         }
     }
 
+    /// Get a pooled buffer for keys or plaintexts
+    ///
+    /// Hand it back with `return_secret_buffer`, which wipes it first.
+    pub fn get_secret_buffer(&self, kind: SensitiveKind, size: usize) -> SecretBytes {
+        SecretBytes::new(kind, self.get_buffer(size))
+    }
+
+    /// Zeroize a sensitive buffer and return it to the pool
+    pub fn return_secret_buffer(&self, buffer: SecretBytes) {
+        self.return_buffer(buffer.into_wiped());
+    }
+
     /// Return a buffer to the pool
+    ///
+    /// Under a hygiene audit, a tracked sensitive buffer that still holds
+    /// data is reported as a violation.
     pub fn return_buffer(&self, mut buffer: Vec<u8>) {
+        check_released(&buffer, "memory_pool");
         let size = buffer.capacity();
         let pool_type = self.get_pool_type(size);
 
@@ -343,11 +471,15 @@ impl MemoryPool {
         };
 
         let mut pool_guard = pool.lock();
-        if pool_guard.len() < self.config.max_buffers_per_pool {
+        let within_budget = self.pooled_bytes.load(Ordering::Relaxed) + size as u64
+            <= self.memory_budget() as u64;
+        if pool_guard.buffers.len() < pool_guard.limit && within_budget {
             buffer.clear();
-            pool_guard.push_back(buffer);
+            self.pooled_bytes.fetch_add(size as u64, Ordering::Relaxed);
+            pool_guard.buffers.push_back((buffer, Instant::now()));
+            pool_guard.peak = pool_guard.peak.max(pool_guard.buffers.len());
         }
-        // If pool is full, let the buffer be dropped
+        // If pool is full or over budget, let the buffer be dropped
     }
 
     /// Update pool statistics
@@ -387,7 +519,153 @@ impl MemoryPool {
 
     /// Get pool statistics
     pub fn get_stats(&self) -> HashMap<String, PoolStats> {
-        self.stats.read().clone()
+        let mut stats = self.stats.read().clone();
+        for (name, pool) in self.tiers() {
+            let tier = pool.lock();
+            if let Some(pool_stats) = stats.get_mut(name) {
+                pool_stats.current_pool_size = tier.buffers.len();
+                pool_stats.peak_pool_size = tier.peak;
+                pool_stats.memory_usage_bytes = tier.pooled_bytes() as u64;
+            }
+        }
+        stats
+    }
+
+    /// Buffers each tier may currently retain
+    pub fn pool_limits(&self) -> HashMap<String, usize> {
+        self.tiers()
+            .into_iter()
+            .map(|(name, pool)| (name.to_string(), pool.lock().limit))
+            .collect()
+    }
+
+    /// Bytes currently held across all pools
+    pub fn pooled_bytes(&self) -> u64 {
+        self.pooled_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Retained-memory ceiling (unbounded when adaptive sizing is off)
+    fn memory_budget(&self) -> usize {
+        if self.config.adaptive.enabled {
+            self.config.adaptive.memory_budget_bytes
+        } else {
+            usize::MAX
+        }
+    }
+
+    fn tiers(&self) -> [(&'static str, &Arc<Mutex<PoolTier>>); 3] {
+        [
+            ("small", &self.small_pool),
+            ("medium", &self.medium_pool),
+            ("large", &self.large_pool),
+        ]
+    }
+
+    /// Resize every tier from the workload since the last call
+    ///
+    /// Trims buffers idle longer than `idle_timeout` (keeping
+    /// `min_buffers_per_pool`), grows tiers missing the cache hit threshold
+    /// under steady or rising load, shrinks tiers whose load has halved,
+    /// and reports each change through `log_performance`.
+    pub fn adapt(&self) -> Vec<PoolSizingDecision> {
+        if !self.config.adaptive.enabled {
+            return Vec::new();
+        }
+        let adaptive = &self.config.adaptive;
+        let elapsed = {
+            let mut window_start = self.window_start.lock();
+            let elapsed = window_start.elapsed().as_secs_f64().max(0.001);
+            *window_start = Instant::now();
+            elapsed
+        };
+        let min_limit = adaptive
+            .min_buffers_per_pool
+            .min(self.config.max_buffers_per_pool);
+        let buffer_sizes = [
+            self.config.small_buffer_size,
+            self.config.medium_buffer_size,
+            self.config.large_buffer_size,
+        ];
+
+        let mut decisions = Vec::new();
+        for ((name, pool), buffer_size) in self.tiers().into_iter().zip(buffer_sizes) {
+            let mut tier = pool.lock();
+            let requests = std::mem::take(&mut tier.window_requests);
+            let hits = std::mem::take(&mut tier.window_hits);
+            let rate = requests as f64 / elapsed;
+            let hit_ratio = if requests == 0 {
+                1.0
+            } else {
+                hits as f64 / requests as f64
+            };
+
+            let previous_limit = tier.limit;
+            let (new_limit, reason) = if requests > 0
+                && hit_ratio < self.config.cache_hit_threshold
+                && rate >= tier.last_rate
+            {
+                // No tier may claim more than the whole budget
+                let budget_cap = (adaptive.memory_budget_bytes / buffer_size.max(1)).max(min_limit);
+                let grown = (previous_limit * 2)
+                    .max(min_limit)
+                    .min(self.config.max_buffers_per_pool)
+                    .min(budget_cap);
+                (grown, "hit ratio below threshold under steady load")
+            } else if requests == 0 || rate < tier.last_rate / 2.0 {
+                ((previous_limit / 2).max(min_limit), "allocation rate halved")
+            } else {
+                (previous_limit, "")
+            };
+            tier.limit = new_limit;
+            tier.last_rate = rate;
+
+            // Release buffers beyond the limit, then those idle too long
+            let mut trimmed = 0;
+            while tier.buffers.len() > new_limit {
+                if let Some((buffer, _)) = tier.buffers.pop_front() {
+                    self.pooled_bytes
+                        .fetch_sub(buffer.capacity() as u64, Ordering::Relaxed);
+                    trimmed += 1;
+                }
+            }
+            while tier.buffers.len() > min_limit
+                && tier
+                    .buffers
+                    .front()
+                    .map(|(_, returned_at)| returned_at.elapsed() >= adaptive.idle_timeout)
+                    .unwrap_or(false)
+            {
+                if let Some((buffer, _)) = tier.buffers.pop_front() {
+                    self.pooled_bytes
+                        .fetch_sub(buffer.capacity() as u64, Ordering::Relaxed);
+                    trimmed += 1;
+                }
+            }
+
+            if new_limit == previous_limit && trimmed == 0 {
+                continue;
+            }
+            let decision = PoolSizingDecision {
+                tier: name.to_string(),
+                previous_limit,
+                new_limit,
+                hit_ratio,
+                allocations_per_second: rate,
+                trimmed_buffers: trimmed,
+                reason: if reason.is_empty() {
+                    "idle buffers trimmed".to_string()
+                } else {
+                    reason.to_string()
+                },
+            };
+            log_performance(
+                "Memory pool resized",
+                0,
+                serde_json::to_value(&decision).unwrap_or(serde_json::Value::Null),
+            );
+            decisions.push(decision);
+        }
+        decisions
     }
 
     /// Get average allocation time
@@ -446,13 +724,225 @@ pub trait ConnectionFactory<T>: Send + Sync {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceReport {
     pub avg_latency_ms: f64,
+    #[serde(default)]
+    pub p50_latency_ms: f64,
     pub p95_latency_ms: f64,
+    #[serde(default)]
+    pub p99_latency_ms: f64,
+    #[serde(default)]
+    pub p999_latency_ms: f64,
     pub requests_per_second: f64,
     pub success_rate: f64,
     pub total_requests: u64,
     pub error_rate: f64,
     pub cpu_usage: f64,
     pub memory_usage_mb: f64,
+    /// Latency distribution per operation type
+    #[serde(default)]
+    pub operations: HashMap<String, LatencySummary>,
+}
+
+/// Operation type of channel establishment
+pub const OP_ESTABLISH: &str = "establish";
+/// Operation type of message sends
+pub const OP_SEND: &str = "send";
+/// Operation type of PQC key generation and QKD key exchange
+pub const OP_KEY_EXCHANGE: &str = "key_exchange";
+
+/// Percentiles of a latency histogram
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub errors: u64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub p999_ms: f64,
+    pub max_ms: f64,
+}
+
+/// HDR latency histogram with microsecond resolution
+///
+/// Recording is O(1) and memory is fixed regardless of sample count;
+/// percentiles are accurate to three significant digits up to one hour.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    histogram: Histogram<u64>,
+    errors: u64,
+}
+
+impl LatencyHistogram {
+    /// Largest trackable latency in microseconds (one hour)
+    const MAX_MICROS: u64 = 3_600_000_000;
+
+    /// Create empty histogram
+    pub fn new() -> Self {
+        Self {
+            histogram: Histogram::new_with_bounds(1, Self::MAX_MICROS, 3)
+                .expect("constant histogram bounds are valid"),
+            errors: 0,
+        }
+    }
+
+    /// Record one latency sample; values above one hour are clamped
+    pub fn record(&mut self, latency: Duration, success: bool) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        self.histogram.saturating_record(micros.max(1));
+        if !success {
+            self.errors += 1;
+        }
+    }
+
+    /// Number of recorded samples
+    pub fn len(&self) -> u64 {
+        self.histogram.len()
+    }
+
+    /// Whether no samples were recorded
+    pub fn is_empty(&self) -> bool {
+        self.histogram.is_empty()
+    }
+
+    /// Latency at `quantile` (0.0-1.0) in milliseconds
+    pub fn quantile_ms(&self, quantile: f64) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.histogram.value_at_quantile(quantile) as f64 / 1000.0
+    }
+
+    /// Mean latency in milliseconds
+    pub fn mean_ms(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.histogram.mean() / 1000.0
+    }
+
+    /// Add the samples of another histogram
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        // Both histograms share the same bounds, so addition cannot fail
+        let _ = self.histogram.add(&other.histogram);
+        self.errors += other.errors;
+    }
+
+    /// Drop all samples
+    pub fn reset(&mut self) {
+        self.histogram.reset();
+        self.errors = 0;
+    }
+
+    /// Summarise the distribution
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            count: self.len(),
+            errors: self.errors,
+            mean_ms: self.mean_ms(),
+            p50_ms: self.quantile_ms(0.50),
+            p95_ms: self.quantile_ms(0.95),
+            p99_ms: self.quantile_ms(0.99),
+            p999_ms: self.quantile_ms(0.999),
+            max_ms: if self.is_empty() {
+                0.0
+            } else {
+                self.histogram.max() as f64 / 1000.0
+            },
+        }
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Latency histograms keyed by operation type
+#[derive(Debug, Clone, Default)]
+pub struct OperationLatencies {
+    histograms: HashMap<String, LatencyHistogram>,
+}
+
+impl OperationLatencies {
+    /// Create empty set of histograms
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a latency sample for `operation`
+    pub fn record(&mut self, operation: &str, latency: Duration, success: bool) {
+        self.histograms
+            .entry(operation.to_string())
+            .or_default()
+            .record(latency, success);
+    }
+
+    /// Histogram of `operation`, if any sample was recorded
+    pub fn histogram(&self, operation: &str) -> Option<&LatencyHistogram> {
+        self.histograms.get(operation)
+    }
+
+    /// Summary of every operation type
+    pub fn summaries(&self) -> HashMap<String, LatencySummary> {
+        self.histograms
+            .iter()
+            .map(|(operation, histogram)| (operation.clone(), histogram.summary()))
+            .collect()
+    }
+}
+
+/// Operation type of channel rekeying
+pub const OP_REKEY: &str = "rekey";
+
+/// Configured latency budgets per operation type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceBudgets {
+    /// Latency budget per operation type, e.g. `OP_SEND`
+    pub budgets: HashMap<String, Duration>,
+    /// Consecutive violations after which a budget counts as persistently exceeded
+    pub alert_after: u32,
+}
+
+impl Default for PerformanceBudgets {
+    fn default() -> Self {
+        let budgets = [
+            (OP_SEND, Duration::from_millis(1)),
+            (OP_REKEY, Duration::from_millis(10)),
+            (OP_ESTABLISH, Duration::from_millis(50)),
+        ]
+        .into_iter()
+        .map(|(operation, budget)| (operation.to_string(), budget))
+        .collect();
+        Self {
+            budgets,
+            alert_after: 5,
+        }
+    }
+}
+
+/// Budget of one operation type and how it has been kept
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OperationBudget {
+    pub budget_ms: f64,
+    pub alert_after: u32,
+    pub samples: u64,
+    pub violations: u64,
+    pub consecutive_violations: u32,
+    pub worst_ms: f64,
+}
+
+/// Result of checking one operation against its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetCheck {
+    /// No budget configured for the operation
+    Unbudgeted,
+    /// Completed within budget
+    Within,
+    /// Over budget
+    Exceeded,
+    /// Over budget for the `alert_after`-th time in a row; reported once per streak
+    PersistentlyExceeded,
 }
 
 /// Performance metrics for tracking initialization and operation times
@@ -468,6 +958,9 @@ pub struct PerformanceMetrics {
     pub throughput_mps: f64,
     pub avg_latency_ms: f64,
     pub success_rate: f64,
+    /// Latency budgets and violations per operation type
+    #[serde(default)]
+    pub budgets: HashMap<String, OperationBudget>,
 }
 
 impl PerformanceMetrics {
@@ -483,6 +976,7 @@ impl PerformanceMetrics {
             throughput_mps: 0.0,
             avg_latency_ms: 0.0,
             success_rate: 0.0,
+            budgets: HashMap::new(),
         }
     }
 
@@ -493,6 +987,47 @@ impl PerformanceMetrics {
             + self.network_setup_ms
             + self.consensus_verify_ms;
     }
+
+    /// Install configured budgets, keeping counters of budgets already tracked
+    pub fn apply_budgets(&mut self, budgets: &PerformanceBudgets) {
+        self.budgets
+            .retain(|operation, _| budgets.budgets.contains_key(operation));
+        for (operation, budget) in &budgets.budgets {
+            let tracked = self.budgets.entry(operation.clone()).or_default();
+            tracked.budget_ms = budget.as_secs_f64() * 1000.0;
+            tracked.alert_after = budgets.alert_after.max(1);
+        }
+    }
+
+    /// Check a completed operation against its budget
+    pub fn record_operation(&mut self, operation: &str, elapsed: Duration) -> BudgetCheck {
+        let budget = match self.budgets.get_mut(operation) {
+            Some(budget) => budget,
+            None => return BudgetCheck::Unbudgeted,
+        };
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        budget.samples += 1;
+        budget.worst_ms = budget.worst_ms.max(elapsed_ms);
+        if elapsed_ms <= budget.budget_ms {
+            budget.consecutive_violations = 0;
+            return BudgetCheck::Within;
+        }
+        budget.violations += 1;
+        budget.consecutive_violations += 1;
+        if budget.consecutive_violations == budget.alert_after {
+            BudgetCheck::PersistentlyExceeded
+        } else {
+            BudgetCheck::Exceeded
+        }
+    }
+
+    /// Budget violations recorded for `operation`
+    pub fn budget_violations(&self, operation: &str) -> u64 {
+        self.budgets
+            .get(operation)
+            .map(|budget| budget.violations)
+            .unwrap_or(0)
+    }
 }
 
 impl Default for PerformanceMetrics {
@@ -504,7 +1039,9 @@ impl Default for PerformanceMetrics {
 /// Performance monitoring system
 pub struct PerformanceMonitor {
     /// Request latencies
-    latencies: Arc<RwLock<VecDeque<Duration>>>,
+    latencies: Arc<RwLock<LatencyHistogram>>,
+    /// Latencies per operation type
+    operations: Arc<RwLock<OperationLatencies>>,
     /// Success/failure counts
     success_count: Arc<AtomicU64>,
     error_count: Arc<AtomicU64>,
@@ -519,7 +1056,8 @@ impl PerformanceMonitor {
     /// Create new performance monitor
     pub fn new() -> Self {
         let monitor = Self {
-            latencies: Arc::new(RwLock::new(VecDeque::with_capacity(10000))),
+            latencies: Arc::new(RwLock::new(LatencyHistogram::new())),
+            operations: Arc::new(RwLock::new(OperationLatencies::new())),
             success_count: Arc::new(AtomicU64::new(0)),
             error_count: Arc::new(AtomicU64::new(0)),
             request_timestamps: Arc::new(RwLock::new(VecDeque::with_capacity(10000))),
@@ -535,11 +1073,7 @@ impl PerformanceMonitor {
     /// Record a request
     pub fn record_request(&self, latency: Duration, success: bool) {
         // Record latency
-        let mut latencies = self.latencies.write();
-        latencies.push_back(latency);
-        if latencies.len() > 10000 {
-            latencies.pop_front();
-        }
+        self.latencies.write().record(latency, success);
 
         // Record success/failure
         if success {
@@ -556,6 +1090,12 @@ impl PerformanceMonitor {
         }
     }
 
+    /// Record a request of a given operation type, e.g. `OP_SEND`
+    pub fn record_operation(&self, operation: &str, latency: Duration, success: bool) {
+        self.operations.write().record(operation, latency, success);
+        self.record_request(latency, success);
+    }
+
     /// Start system resource monitoring
     fn start_system_monitoring(&self) {
         let cpu_usage = self.cpu_usage.clone();
@@ -583,24 +1123,6 @@ impl PerformanceMonitor {
         let latencies = self.latencies.read();
         let timestamps = self.request_timestamps.read();
 
-        let avg_latency = if latencies.is_empty() {
-            Duration::from_nanos(0)
-        } else {
-            latencies.iter().sum::<Duration>() / latencies.len() as u32
-        };
-
-        let p95_latency = if latencies.is_empty() {
-            Duration::from_nanos(0)
-        } else {
-            let mut sorted: Vec<_> = latencies.iter().cloned().collect();
-            sorted.sort();
-            let index = (sorted.len() as f64 * 0.95) as usize;
-            sorted
-                .get(index)
-                .cloned()
-                .unwrap_or(Duration::from_nanos(0))
-        };
-
         let requests_per_second = if timestamps.len() < 2 {
             0.0
         } else {
@@ -608,8 +1130,9 @@ impl PerformanceMonitor {
                 .back()
                 .unwrap()
                 .duration_since(*timestamps.front().unwrap());
-            if time_span.as_secs() > 0 {
-                timestamps.len() as f64 / time_span.as_secs_f64()
+            let seconds = time_span.as_secs_f64();
+            if seconds > 0.0 {
+                timestamps.len() as f64 / seconds
             } else {
                 0.0
             }
@@ -626,14 +1149,18 @@ impl PerformanceMonitor {
         };
 
         PerformanceReport {
-            avg_latency_ms: avg_latency.as_millis() as f64,
-            p95_latency_ms: p95_latency.as_millis() as f64,
+            avg_latency_ms: latencies.mean_ms(),
+            p50_latency_ms: latencies.quantile_ms(0.50),
+            p95_latency_ms: latencies.quantile_ms(0.95),
+            p99_latency_ms: latencies.quantile_ms(0.99),
+            p999_latency_ms: latencies.quantile_ms(0.999),
             requests_per_second,
             success_rate,
             total_requests,
             error_rate: 100.0 - success_rate,
             cpu_usage: *self.cpu_usage.read(),
             memory_usage_mb: *self.memory_usage.read() as f64 / 1024.0 / 1024.0,
+            operations: self.operations.read().summaries(),
         }
     }
 
@@ -731,6 +1258,13 @@ impl PerformanceManager {
         let memory_pool = Arc::new(MemoryPool::new(config.memory_pool.clone()));
         let monitor = Arc::new(PerformanceMonitor::new());
         let shutdown = Arc::new(AtomicBool::new(false));
+        if config.memory_pool.adaptive.enabled {
+            Self::start_pool_sizing(
+                memory_pool.clone(),
+                shutdown.clone(),
+                config.memory_pool.adaptive.adjust_interval,
+            );
+        }
 
         Self {
             config,
@@ -740,6 +1274,23 @@ impl PerformanceManager {
         }
     }
 
+    /// Run adaptive pool sizing until shutdown
+    fn start_pool_sizing(memory_pool: Arc<MemoryPool>, shutdown: Arc<AtomicBool>, period: Duration) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            // The first tick completes immediately; skip it so the first pass sees a full window
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if shutdown.load(Ordering::Relaxed) {
+                    break;
+                }
+                memory_pool.adapt();
+            }
+        });
+    }
+
     /// Get comprehensive performance report
     pub fn get_comprehensive_report(&self) -> serde_json::Value {
         let monitor_report = self.monitor.get_report();
@@ -750,6 +1301,8 @@ impl PerformanceManager {
             "performance": monitor_report,
             "memory_pools": memory_stats,
             "avg_allocation_time_us": avg_allocation_time.as_micros(),
+            "memory_pool_limits": self.memory_pool.pool_limits(),
+            "pooled_bytes": self.memory_pool.pooled_bytes(),
             "timestamp": chrono::Utc::now(),
         })
     }
@@ -789,7 +1342,7 @@ mod tests {
         println!("✅ Memory usage (2nd reading): {:.2} MB", memory_usage2 as f64 / 1024.0 / 1024.0);
 
         // Values should be within reasonable ranges
-        assert!(cpu_usage2 >= 0.0 && cpu_usage2 <= 100.0);
+        assert!((0.0..=100.0).contains(&cpu_usage2));
         assert!(memory_usage2 > 0);
     }
 
@@ -819,4 +1372,147 @@ mod tests {
         println!("✅ System resources: {:.1}% CPU, {:.1}MB memory", 
                  report.cpu_usage, report.memory_usage_mb);
     }
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.summary(), LatencySummary::default());
+
+        for ms in 1..=1000u64 {
+            histogram.record(Duration::from_millis(ms), ms % 100 != 0);
+        }
+        let summary = histogram.summary();
+        assert_eq!(summary.count, 1000);
+        assert_eq!(summary.errors, 10);
+        assert!((summary.p50_ms - 500.0).abs() < 1.0);
+        assert!((summary.p99_ms - 990.0).abs() < 1.0);
+        assert!((summary.p999_ms - 999.0).abs() < 1.0);
+        assert!((summary.mean_ms - 500.5).abs() < 1.0);
+
+        // Sub-millisecond latencies keep their resolution
+        let mut fast = LatencyHistogram::new();
+        fast.record(Duration::from_micros(250), true);
+        assert!((fast.quantile_ms(0.5) - 0.25).abs() < 0.001);
+
+        histogram.merge(&fast);
+        assert_eq!(histogram.len(), 1001);
+    }
+
+    #[tokio::test]
+    async fn test_per_operation_report() {
+        let monitor = PerformanceMonitor::new();
+        monitor.record_operation(OP_ESTABLISH, Duration::from_millis(40), true);
+        monitor.record_operation(OP_SEND, Duration::from_micros(800), true);
+        monitor.record_operation(OP_SEND, Duration::from_micros(1200), false);
+
+        let report = monitor.get_report();
+        assert_eq!(report.total_requests, 3);
+        assert!(report.requests_per_second > 0.0);
+        assert_eq!(report.operations[OP_ESTABLISH].count, 1);
+        assert_eq!(report.operations[OP_SEND].count, 2);
+        assert_eq!(report.operations[OP_SEND].errors, 1);
+        assert!(report.operations[OP_SEND].p99_ms < 2.0);
+        assert!(report.p999_latency_ms >= 39.0);
+        assert!(!report.operations.contains_key(OP_KEY_EXCHANGE));
+    }
+
+    #[test]
+    fn test_adaptive_pool_grows_and_shrinks() {
+        let pool = MemoryPool::new(MemoryPoolConfig {
+            max_buffers_per_pool: 64,
+            adaptive: AdaptivePoolConfig {
+                min_buffers_per_pool: 4,
+                idle_timeout: Duration::ZERO,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(pool.pool_limits()["small"], 4);
+
+        // Burst of 16 concurrent buffers misses past the 4 retained ones
+        for _ in 0..3 {
+            let buffers: Vec<_> = (0..16).map(|_| pool.get_buffer(512)).collect();
+            buffers.into_iter().for_each(|buffer| pool.return_buffer(buffer));
+        }
+        let decisions = pool.adapt();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].tier, "small");
+        assert_eq!(decisions[0].new_limit, 8);
+        assert!(decisions[0].hit_ratio < 0.9);
+
+        // The larger pool retains more of the next burst
+        let buffers: Vec<_> = (0..16).map(|_| pool.get_buffer(512)).collect();
+        buffers.into_iter().for_each(|buffer| pool.return_buffer(buffer));
+        assert_eq!(pool.get_stats()["small"].current_pool_size, 8);
+
+        // Idle buffers are trimmed back to the per-pool minimum
+        let decisions = pool.adapt();
+        assert_eq!(decisions[0].trimmed_buffers, 4);
+        assert_eq!(pool.get_stats()["small"].current_pool_size, 4);
+        assert_eq!(pool.pooled_bytes(), 4 * 1024);
+
+        // Load disappears: the tier shrinks back to the minimum
+        pool.adapt();
+        pool.adapt();
+        assert_eq!(pool.pool_limits()["small"], 4);
+    }
+
+    #[test]
+    fn test_pool_budget_and_idle_trim() {
+        let pool = MemoryPool::new(MemoryPoolConfig {
+            adaptive: AdaptivePoolConfig {
+                memory_budget_bytes: 3 * 65536,
+                min_buffers_per_pool: 16,
+                idle_timeout: Duration::ZERO,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let buffers: Vec<_> = (0..5).map(|_| pool.get_buffer(60_000)).collect();
+        buffers.into_iter().for_each(|buffer| pool.return_buffer(buffer));
+        // Only three medium buffers fit in the budget
+        assert_eq!(pool.pooled_bytes(), 3 * 65536);
+
+        // Disabled sizing keeps the static limits and never trims
+        let fixed = MemoryPool::new(MemoryPoolConfig {
+            adaptive: AdaptivePoolConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(fixed.pool_limits()["large"], 1000);
+        assert!(fixed.adapt().is_empty());
+    }
+
+    #[test]
+    fn test_budget_violations_escalate_once_per_streak() {
+        let mut metrics = PerformanceMetrics::new();
+        metrics.apply_budgets(&PerformanceBudgets {
+            budgets: HashMap::from([(OP_SEND.to_string(), Duration::from_millis(1))]),
+            alert_after: 3,
+        });
+        assert_eq!(
+            metrics.record_operation(OP_REKEY, Duration::from_secs(1)),
+            BudgetCheck::Unbudgeted
+        );
+
+        let slow = Duration::from_millis(5);
+        assert_eq!(metrics.record_operation(OP_SEND, slow), BudgetCheck::Exceeded);
+        assert_eq!(metrics.record_operation(OP_SEND, slow), BudgetCheck::Exceeded);
+        assert_eq!(
+            metrics.record_operation(OP_SEND, slow),
+            BudgetCheck::PersistentlyExceeded
+        );
+        assert_eq!(metrics.record_operation(OP_SEND, slow), BudgetCheck::Exceeded);
+
+        // A sample within budget ends the streak
+        assert_eq!(
+            metrics.record_operation(OP_SEND, Duration::from_micros(300)),
+            BudgetCheck::Within
+        );
+        assert_eq!(metrics.budget_violations(OP_SEND), 4);
+        assert_eq!(metrics.budgets[OP_SEND].samples, 5);
+        assert!((metrics.budgets[OP_SEND].worst_ms - 5.0).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,841 @@
+//! # Private Handshake - Identity Hiding and Uniform Handshake Frames
+//!
+//! Optional handshake variant for deployments where metadata privacy
+//! matters. Peers first agree on keys through an ephemeral ML-KEM round and
+//! only then exchange identities and signatures, encrypted under those keys.
+//! Every frame is padded to the same size, so a passive observer learns
+//! neither who is talking nor anything from message lengths.
+//!
+//! ## Flow
+//!
+//! 1. **Init**: Initiator sends a fresh ephemeral ML-KEM public key
+//! 2. **Response**: Responder encapsulates to it and returns the ciphertext
+//!    with its identity, sealed under the derived responder key
+//! 3. **Finish**: Initiator verifies the responder and returns its own
+//!    identity, sealed under the derived initiator key
+//!
+//! Each identity carries an Ed25519 signature over the transcript so far,
+//! binding it to this handshake's ephemeral keys. Only the peer can open
+//! it, so identities and signatures never appear on the wire.
+//!
+//! ## Privacy Properties
+//!
+//! - **Passive observers**: See one random handshake ID and three frames of
+//!   `frame_len` bytes; no identity, key or certificate
+//! - **Initiator identity**: Revealed only after the responder has proven
+//!   its own, so active probing cannot learn it
+//! - **Responder identity**: Revealed to anyone who sends an init frame;
+//!   keep responders behind `screen_handshake` admission
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::QRNG;
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//! use quantum_forge_secure_comms::private_handshake::*;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = PrivateHandshakeConfig::default();
+//! let mut qrng = QRNG::from_seed(rand::random());
+//! let mut keys = KeyManager::new();
+//! keys.generate_key("identity", KeyPurpose::Signing, &mut qrng)?;
+//!
+//! let alice = LocalIdentity::new("alice", &keys, "identity");
+//! let bob = LocalIdentity::new("bob", &keys, "identity");
+//! let (initiator, init) = PrivateInitiator::start(&config, &mut qrng)?;
+//! let (responder, response) = PrivateResponder::respond(&config, &init, &bob, &mut qrng)?;
+//! let (at_alice, finish) = initiator.finish(&response, &alice, &mut qrng)?;
+//! let at_bob = responder.complete(&finish)?;
+//! assert_eq!(at_alice.peer.peer_id, "bob");
+//! assert_eq!(at_bob.session_key.expose(), at_alice.session_key.expose());
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashMap;
+
+use crate::consensus_verify::verify_commit_signature;
+use crate::crypto_protocols::{PQCAlgorithm, PQC, QRNG};
+use crate::hw_accel::{CryptoDispatch, NONCE_LEN, TAG_LEN};
+use crate::key_manager::KeyManager;
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Default size every handshake frame is padded to
+pub const DEFAULT_FRAME_LEN: usize = 4096;
+/// Size identities are padded to before sealing
+const IDENTITY_BLOCK_LEN: usize = 512;
+/// Random handshake ID length
+const HANDSHAKE_ID_LEN: usize = 16;
+
+/// Private handshake settings; both peers must agree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateHandshakeConfig {
+    /// ML-KEM parameter set of the ephemeral key
+    pub algorithm: PQCAlgorithm,
+    /// Size every handshake frame is padded to
+    pub frame_len: usize,
+    /// Pending handshakes are dropped after this long without their next frame
+    #[serde(default = "default_pending_timeout_ms")]
+    pub pending_timeout_ms: u64,
+    /// Most handshakes awaiting a frame at once, initiated and answered
+    #[serde(default = "default_max_pending")]
+    pub max_pending: usize,
+}
+
+impl Default for PrivateHandshakeConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: PQCAlgorithm::Kyber768,
+            frame_len: DEFAULT_FRAME_LEN,
+            pending_timeout_ms: default_pending_timeout_ms(),
+            max_pending: default_max_pending(),
+        }
+    }
+}
+
+fn default_pending_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_max_pending() -> usize {
+    256
+}
+
+impl PrivateHandshakeConfig {
+    /// Check the algorithm is a KEM and its largest frame fits `frame_len`
+    pub fn validate(&self) -> Result<()> {
+        if self.pending_timeout_ms == 0 || self.max_pending == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Private handshake timeout and pending limit must be positive".to_string(),
+            ));
+        }
+        let (public_key_len, ciphertext_len) = match self.algorithm {
+            PQCAlgorithm::Kyber512 => (800, 768),
+            PQCAlgorithm::Kyber768 => (1184, 1088),
+            PQCAlgorithm::Kyber1024 => (1568, 1568),
+            other => {
+                return Err(SecureCommsError::Configuration(format!(
+                    "{:?} is not a KEM",
+                    other
+                )))
+            }
+        };
+        let sealed_identity = 1 + NONCE_LEN + IDENTITY_BLOCK_LEN + TAG_LEN;
+        let largest_body = (2 + public_key_len).max(2 + ciphertext_len + 2 + sealed_identity);
+        if self.frame_len < 2 + largest_body {
+            return Err(SecureCommsError::Configuration(format!(
+                "Frame length {} is below the {} bytes {:?} needs",
+                self.frame_len,
+                2 + largest_body,
+                self.algorithm
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One padded handshake frame
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateFrame {
+    /// Random ID correlating the frames of one handshake
+    pub handshake_id: Vec<u8>,
+    /// Frame body padded to the configured length
+    pub frame: Vec<u8>,
+}
+
+/// Local identity proven inside the handshake
+#[derive(Debug, Clone, Copy)]
+pub struct LocalIdentity<'a> {
+    peer_id: &'a str,
+    keys: &'a KeyManager,
+    key_id: &'a str,
+}
+
+impl<'a> LocalIdentity<'a> {
+    /// Identify as `peer_id` with the Ed25519 signing key `key_id`
+    pub fn new(peer_id: &'a str, keys: &'a KeyManager, key_id: &'a str) -> Self {
+        Self {
+            peer_id,
+            keys,
+            key_id,
+        }
+    }
+
+    /// Identity block signed over `transcript`, padded to a fixed size
+    fn block(&self, transcript: &[u8; 32]) -> Result<SecretBytes> {
+        let public_key = self.keys.public_key(self.key_id).ok_or_else(|| {
+            SecureCommsError::Configuration(format!("Unknown identity key {}", self.key_id))
+        })?;
+        let signature = self.keys.sign(self.key_id, transcript)?;
+        let mut block = Vec::with_capacity(IDENTITY_BLOCK_LEN);
+        for field in [self.peer_id.as_bytes(), public_key, signature.as_slice()] {
+            push_field(&mut block, field)?;
+        }
+        if block.len() > IDENTITY_BLOCK_LEN {
+            return Err(SecureCommsError::Validation(format!(
+                "Peer ID {} is too long to hide",
+                self.peer_id
+            )));
+        }
+        block.resize(IDENTITY_BLOCK_LEN, 0);
+        Ok(SecretBytes::new(SensitiveKind::Plaintext, block))
+    }
+}
+
+/// Identity the peer proved inside the handshake
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerIdentity {
+    /// Peer identifier
+    pub peer_id: String,
+    /// Peer's Ed25519 identity public key
+    pub public_key: Vec<u8>,
+}
+
+/// Authenticated peer and the agreed session key
+#[derive(Debug)]
+pub struct PrivateHandshakeOutcome {
+    /// Identity the peer proved
+    pub peer: PeerIdentity,
+    /// Session key for the new channel
+    pub session_key: SecretBytes,
+}
+
+/// Initiator waiting for the response frame
+#[derive(Debug)]
+pub struct PrivateInitiator {
+    config: PrivateHandshakeConfig,
+    handshake_id: Vec<u8>,
+    ephemeral_secret: SecretBytes,
+    init_hash: [u8; 32],
+}
+
+impl PrivateInitiator {
+    /// Generate an ephemeral KEM key and the init frame carrying it
+    pub fn start(config: &PrivateHandshakeConfig, qrng: &mut QRNG) -> Result<(Self, PrivateFrame)> {
+        config.validate()?;
+        let keypair = PQC::keygen(config.algorithm)?;
+        let handshake_id = qrng.generate_bytes(HANDSHAKE_ID_LEN)?;
+
+        let mut body = Vec::new();
+        push_field(&mut body, &keypair.public_key)?;
+        let frame = pad_frame(&body, config.frame_len, qrng)?;
+        let init_hash = digest(b"private_handshake_init_v1", &[&handshake_id, &frame]);
+
+        Ok((
+            Self {
+                config: config.clone(),
+                handshake_id: handshake_id.clone(),
+                ephemeral_secret: SecretBytes::new(SensitiveKind::Key, keypair.private_key),
+                init_hash,
+            },
+            PrivateFrame {
+                handshake_id,
+                frame,
+            },
+        ))
+    }
+
+    /// Random ID of this handshake
+    pub fn handshake_id(&self) -> &[u8] {
+        &self.handshake_id
+    }
+
+    /// Verify the responder and produce the finish frame with our identity
+    pub fn finish(
+        self,
+        response: &PrivateFrame,
+        local: &LocalIdentity<'_>,
+        qrng: &mut QRNG,
+    ) -> Result<(PrivateHandshakeOutcome, PrivateFrame)> {
+        check_frame(response, &self.handshake_id, self.config.frame_len)?;
+        let body = unpad_frame(&response.frame)?;
+        let mut fields = FieldReader::new(body);
+        let ciphertext = fields.next()?;
+        let sealed_responder = fields.next()?;
+
+        let mut pqc = PQC::new(self.config.algorithm, QRNG::from_seed(seed(qrng)?));
+        let shared = SecretBytes::new(
+            SensitiveKind::Key,
+            pqc.decapsulate(self.ephemeral_secret.expose(), ciphertext)
+                .map_err(|_| SecureCommsError::AuthenticationFailed)?,
+        );
+        let keys = KeySchedule::derive(&shared, &self.init_hash, ciphertext);
+
+        let responder_transcript = digest(
+            b"private_handshake_responder_sig_v1",
+            &[&self.init_hash, ciphertext],
+        );
+        let peer = open_identity(
+            &keys.responder,
+            &self.handshake_id,
+            sealed_responder,
+            &responder_transcript,
+        )?;
+
+        let response_hash = digest(
+            b"private_handshake_response_v1",
+            &[&self.init_hash, &response.frame],
+        );
+        let initiator_transcript = digest(b"private_handshake_initiator_sig_v1", &[&response_hash]);
+        let sealed = seal_identity(
+            &keys.initiator,
+            &self.handshake_id,
+            &local.block(&initiator_transcript)?,
+            qrng,
+        )?;
+        let mut body = Vec::new();
+        push_field(&mut body, &sealed)?;
+        let frame = pad_frame(&body, self.config.frame_len, qrng)?;
+
+        Ok((
+            PrivateHandshakeOutcome {
+                peer,
+                session_key: keys.session(&response_hash),
+            },
+            PrivateFrame {
+                handshake_id: self.handshake_id,
+                frame,
+            },
+        ))
+    }
+}
+
+/// Responder waiting for the finish frame
+#[derive(Debug)]
+pub struct PrivateResponder {
+    config: PrivateHandshakeConfig,
+    handshake_id: Vec<u8>,
+    keys: KeySchedule,
+    response_hash: [u8; 32],
+}
+
+impl PrivateResponder {
+    /// Answer an init frame with the KEM ciphertext and our sealed identity
+    pub fn respond(
+        config: &PrivateHandshakeConfig,
+        init: &PrivateFrame,
+        local: &LocalIdentity<'_>,
+        qrng: &mut QRNG,
+    ) -> Result<(Self, PrivateFrame)> {
+        config.validate()?;
+        if init.handshake_id.len() != HANDSHAKE_ID_LEN {
+            return Err(SecureCommsError::Validation(
+                "Malformed private handshake ID".to_string(),
+            ));
+        }
+        check_frame(init, &init.handshake_id, config.frame_len)?;
+        let mut fields = FieldReader::new(unpad_frame(&init.frame)?);
+        let ephemeral_public = fields.next()?;
+        let init_hash = hash_init(init);
+
+        let mut pqc = PQC::new(config.algorithm, QRNG::from_seed(seed(qrng)?));
+        let (ciphertext, shared) = pqc.encapsulate(ephemeral_public)?;
+        let shared = SecretBytes::new(SensitiveKind::Key, shared);
+        let keys = KeySchedule::derive(&shared, &init_hash, &ciphertext);
+
+        let transcript = digest(
+            b"private_handshake_responder_sig_v1",
+            &[&init_hash, &ciphertext],
+        );
+        let sealed = seal_identity(
+            &keys.responder,
+            &init.handshake_id,
+            &local.block(&transcript)?,
+            qrng,
+        )?;
+        let mut body = Vec::new();
+        push_field(&mut body, &ciphertext)?;
+        push_field(&mut body, &sealed)?;
+        let frame = pad_frame(&body, config.frame_len, qrng)?;
+        let response_hash = digest(b"private_handshake_response_v1", &[&init_hash, &frame]);
+
+        Ok((
+            Self {
+                config: config.clone(),
+                handshake_id: init.handshake_id.clone(),
+                keys,
+                response_hash,
+            },
+            PrivateFrame {
+                handshake_id: init.handshake_id.clone(),
+                frame,
+            },
+        ))
+    }
+
+    /// Random ID of this handshake
+    pub fn handshake_id(&self) -> &[u8] {
+        &self.handshake_id
+    }
+
+    /// Verify the initiator's identity from the finish frame
+    pub fn complete(self, finish: &PrivateFrame) -> Result<PrivateHandshakeOutcome> {
+        check_frame(finish, &self.handshake_id, self.config.frame_len)?;
+        let mut fields = FieldReader::new(unpad_frame(&finish.frame)?);
+        let sealed_initiator = fields.next()?;
+
+        let transcript = digest(
+            b"private_handshake_initiator_sig_v1",
+            &[&self.response_hash],
+        );
+        let peer = open_identity(
+            &self.keys.initiator,
+            &self.handshake_id,
+            sealed_initiator,
+            &transcript,
+        )?;
+        Ok(PrivateHandshakeOutcome {
+            peer,
+            session_key: self.keys.session(&self.response_hash),
+        })
+    }
+}
+
+/// Handshakes awaiting their next frame
+///
+/// Entries older than `pending_timeout_ms` are dropped, and no more than
+/// `max_pending` are held, so abandoned handshakes cannot pile up. A
+/// retransmitted init frame is answered with the response already sent:
+/// answering it afresh would replace the responder the initiator's finish
+/// frame was computed against.
+#[derive(Debug, Default)]
+pub struct PendingHandshakes {
+    initiators: HashMap<Vec<u8>, (PrivateInitiator, u64)>,
+    responders: HashMap<Vec<u8>, PendingResponder>,
+}
+
+#[derive(Debug)]
+struct PendingResponder {
+    responder: PrivateResponder,
+    init_hash: [u8; 32],
+    response: PrivateFrame,
+    started_ms: u64,
+}
+
+impl PendingHandshakes {
+    /// Empty set of pending handshakes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handshakes awaiting a frame, initiated and answered
+    pub fn len(&self) -> usize {
+        self.initiators.len() + self.responders.len()
+    }
+
+    /// Whether no handshake is awaiting a frame
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop handshakes that timed out by `now_ms`, returning how many
+    pub fn expire(&mut self, config: &PrivateHandshakeConfig, now_ms: u64) -> usize {
+        let before = self.len();
+        let live = |started_ms: u64| now_ms < started_ms.saturating_add(config.pending_timeout_ms);
+        self.initiators
+            .retain(|_, (_, started_ms)| live(*started_ms));
+        self.responders
+            .retain(|_, pending| live(pending.started_ms));
+        before - self.len()
+    }
+
+    /// Fail with `ResourceExhausted` if no further handshake may start
+    pub fn check_capacity(&self, config: &PrivateHandshakeConfig) -> Result<()> {
+        if self.len() >= config.max_pending {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "{} private handshakes already pending",
+                self.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Track a handshake we initiated
+    pub fn insert_initiator(&mut self, initiator: PrivateInitiator, now_ms: u64) {
+        self.initiators
+            .insert(initiator.handshake_id.clone(), (initiator, now_ms));
+    }
+
+    /// Track a handshake we answered with `response`
+    pub fn insert_responder(
+        &mut self,
+        responder: PrivateResponder,
+        init: &PrivateFrame,
+        response: &PrivateFrame,
+        now_ms: u64,
+    ) {
+        self.responders.insert(
+            responder.handshake_id.clone(),
+            PendingResponder {
+                responder,
+                init_hash: hash_init(init),
+                response: response.clone(),
+                started_ms: now_ms,
+            },
+        );
+    }
+
+    /// Response already sent for `init`, if its handshake is pending
+    ///
+    /// A different init frame reusing a pending handshake ID is refused.
+    pub fn answered(&self, init: &PrivateFrame) -> Option<Result<PrivateFrame>> {
+        let pending = self.responders.get(&init.handshake_id)?;
+        if pending.init_hash != hash_init(init) {
+            return Some(Err(SecureCommsError::Validation(
+                "Init frame conflicts with a pending handshake".to_string(),
+            )));
+        }
+        Some(Ok(pending.response.clone()))
+    }
+
+    /// Remove the initiator of `handshake_id` unless it timed out
+    pub fn take_initiator(
+        &mut self,
+        config: &PrivateHandshakeConfig,
+        handshake_id: &[u8],
+        now_ms: u64,
+    ) -> Option<PrivateInitiator> {
+        self.expire(config, now_ms);
+        self.initiators
+            .remove(handshake_id)
+            .map(|(initiator, _)| initiator)
+    }
+
+    /// Remove the responder of `handshake_id` unless it timed out
+    pub fn take_responder(
+        &mut self,
+        config: &PrivateHandshakeConfig,
+        handshake_id: &[u8],
+        now_ms: u64,
+    ) -> Option<PrivateResponder> {
+        self.expire(config, now_ms);
+        self.responders
+            .remove(handshake_id)
+            .map(|pending| pending.responder)
+    }
+}
+
+fn hash_init(init: &PrivateFrame) -> [u8; 32] {
+    digest(
+        b"private_handshake_init_v1",
+        &[&init.handshake_id, &init.frame],
+    )
+}
+
+/// Keys derived from the KEM shared secret and the init transcript
+#[derive(Debug)]
+struct KeySchedule {
+    base: SecretBytes,
+    responder: SecretBytes,
+    initiator: SecretBytes,
+}
+
+impl KeySchedule {
+    fn derive(shared: &SecretBytes, init_hash: &[u8; 32], ciphertext: &[u8]) -> Self {
+        let base = digest(
+            b"private_handshake_keys_v1",
+            &[shared.expose(), init_hash, ciphertext],
+        );
+        let key =
+            |label: &[u8]| SecretBytes::new(SensitiveKind::Key, digest(label, &[&base]).to_vec());
+        Self {
+            responder: key(b"private_handshake_responder_key_v1"),
+            initiator: key(b"private_handshake_initiator_key_v1"),
+            base: SecretBytes::new(SensitiveKind::Key, base.to_vec()),
+        }
+    }
+
+    fn session(&self, response_hash: &[u8; 32]) -> SecretBytes {
+        SecretBytes::new(
+            SensitiveKind::Key,
+            digest(
+                b"private_handshake_session_v1",
+                &[self.base.expose(), response_hash],
+            )
+            .to_vec(),
+        )
+    }
+}
+
+fn seal_identity(
+    key: &SecretBytes,
+    handshake_id: &[u8],
+    block: &SecretBytes,
+    qrng: &mut QRNG,
+) -> Result<Vec<u8>> {
+    let nonce = qrng.generate_bytes(NONCE_LEN)?;
+    CryptoDispatch::default().seal(key.expose(), &nonce, handshake_id, block.expose())
+}
+
+/// Decrypt a sealed identity block and verify its transcript signature
+fn open_identity(
+    key: &SecretBytes,
+    handshake_id: &[u8],
+    sealed: &[u8],
+    transcript: &[u8; 32],
+) -> Result<PeerIdentity> {
+    let block = SecretBytes::new(
+        SensitiveKind::Plaintext,
+        CryptoDispatch::default()
+            .open(key.expose(), handshake_id, sealed)
+            .map_err(|_| SecureCommsError::AuthenticationFailed)?,
+    );
+    let mut fields = FieldReader::new(block.expose());
+    let peer_id = String::from_utf8(fields.next()?.to_vec())
+        .map_err(|_| SecureCommsError::Validation("Peer ID is not UTF-8".to_string()))?;
+    let public_key = fields.next()?.to_vec();
+    let signature = fields.next()?;
+    if !verify_commit_signature(&public_key, transcript, signature) {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    Ok(PeerIdentity {
+        peer_id,
+        public_key,
+    })
+}
+
+fn check_frame(frame: &PrivateFrame, handshake_id: &[u8], frame_len: usize) -> Result<()> {
+    if frame.handshake_id != handshake_id {
+        return Err(SecureCommsError::Validation(
+            "Frame belongs to another handshake".to_string(),
+        ));
+    }
+    if frame.frame.len() != frame_len {
+        return Err(SecureCommsError::Validation(format!(
+            "Handshake frame is {} bytes, expected {}",
+            frame.frame.len(),
+            frame_len
+        )));
+    }
+    Ok(())
+}
+
+/// Body behind a length prefix, followed by random padding to `frame_len`
+fn pad_frame(body: &[u8], frame_len: usize, qrng: &mut QRNG) -> Result<Vec<u8>> {
+    let mut frame = Vec::with_capacity(frame_len);
+    push_field(&mut frame, body)?;
+    let padding = frame_len.checked_sub(frame.len()).ok_or_else(|| {
+        SecureCommsError::Configuration(format!(
+            "Handshake body of {} bytes exceeds frame length {}",
+            body.len(),
+            frame_len
+        ))
+    })?;
+    frame.extend_from_slice(&qrng.generate_bytes(padding)?);
+    Ok(frame)
+}
+
+fn unpad_frame(frame: &[u8]) -> Result<&[u8]> {
+    FieldReader::new(frame).next()
+}
+
+fn push_field(out: &mut Vec<u8>, field: &[u8]) -> Result<()> {
+    let len = u16::try_from(field.len()).map_err(|_| {
+        SecureCommsError::Validation(format!("Handshake field of {} bytes", field.len()))
+    })?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(field);
+    Ok(())
+}
+
+/// Reads u16 length-prefixed fields
+struct FieldReader<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { rest: bytes }
+    }
+
+    fn next(&mut self) -> Result<&'a [u8]> {
+        let malformed = || SecureCommsError::Validation("Malformed handshake frame".to_string());
+        if self.rest.len() < 2 {
+            return Err(malformed());
+        }
+        let len = u16::from_be_bytes([self.rest[0], self.rest[1]]) as usize;
+        let field = self.rest.get(2..2 + len).ok_or_else(malformed)?;
+        self.rest = &self.rest[2 + len..];
+        Ok(field)
+    }
+}
+
+fn seed(qrng: &mut QRNG) -> Result<[u8; 32]> {
+    let bytes = qrng.generate_bytes(32)?;
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&bytes);
+    Ok(seed)
+}
+
+/// SHA3-256 over a label and length-prefixed parts
+fn digest(label: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(label);
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_manager::KeyPurpose;
+
+    fn identity_keys(seed: u8) -> KeyManager {
+        let mut keys = KeyManager::new();
+        keys.import_key("identity", KeyPurpose::Signing, [seed; 32])
+            .unwrap();
+        keys
+    }
+
+    #[test]
+    fn test_handshake_agrees_and_hides_identities() {
+        let config = PrivateHandshakeConfig::default();
+        let mut qrng = QRNG::from_seed([1u8; 32]);
+        let (alice_keys, bob_keys) = (identity_keys(1), identity_keys(2));
+        let alice = LocalIdentity::new("alice", &alice_keys, "identity");
+        let bob = LocalIdentity::new("bob", &bob_keys, "identity");
+
+        let (initiator, init) = PrivateInitiator::start(&config, &mut qrng).unwrap();
+        let (responder, response) =
+            PrivateResponder::respond(&config, &init, &bob, &mut qrng).unwrap();
+        let (at_alice, finish) = initiator.finish(&response, &alice, &mut qrng).unwrap();
+        let at_bob = responder.complete(&finish).unwrap();
+
+        assert_eq!(at_alice.peer.peer_id, "bob");
+        assert_eq!(
+            at_alice.peer.public_key,
+            bob_keys.public_key("identity").unwrap()
+        );
+        assert_eq!(at_bob.peer.peer_id, "alice");
+        assert_eq!(at_bob.session_key.expose(), at_alice.session_key.expose());
+
+        // Uniform frames with no identity material in the clear
+        for frame in [&init, &response, &finish] {
+            assert_eq!(frame.frame.len(), DEFAULT_FRAME_LEN);
+            assert_eq!(frame.handshake_id, init.handshake_id);
+            for secret in [
+                b"alice".as_slice(),
+                b"bob".as_slice(),
+                alice_keys.public_key("identity").unwrap(),
+                bob_keys.public_key("identity").unwrap(),
+            ] {
+                assert!(!frame
+                    .frame
+                    .windows(secret.len())
+                    .any(|window| window == secret));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampering_and_substitution_rejected() {
+        let config = PrivateHandshakeConfig::default();
+        let mut qrng = QRNG::from_seed([2u8; 32]);
+        let (alice_keys, bob_keys) = (identity_keys(1), identity_keys(2));
+        let alice = LocalIdentity::new("alice", &alice_keys, "identity");
+        let bob = LocalIdentity::new("bob", &bob_keys, "identity");
+
+        // Responder answering a different init cannot be spliced in
+        let (initiator, _) = PrivateInitiator::start(&config, &mut qrng).unwrap();
+        let (_, other_init) = PrivateInitiator::start(&config, &mut qrng).unwrap();
+        let (_, mut response) =
+            PrivateResponder::respond(&config, &other_init, &bob, &mut qrng).unwrap();
+        response.handshake_id = initiator.handshake_id().to_vec();
+        assert!(matches!(
+            initiator.finish(&response, &alice, &mut qrng),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // A flipped bit in the sealed identity fails authentication
+        let (initiator, init) = PrivateInitiator::start(&config, &mut qrng).unwrap();
+        let (responder, response) =
+            PrivateResponder::respond(&config, &init, &bob, &mut qrng).unwrap();
+        let (_, mut finish) = initiator.finish(&response, &alice, &mut qrng).unwrap();
+        finish.frame[40] ^= 1;
+        assert!(matches!(
+            responder.complete(&finish),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_config_and_frame_validation() {
+        assert!(PrivateHandshakeConfig::default().validate().is_ok());
+        assert!(PrivateHandshakeConfig {
+            algorithm: PQCAlgorithm::Dilithium2,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(PrivateHandshakeConfig {
+            algorithm: PQCAlgorithm::Kyber1024,
+            frame_len: 2048,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+
+        let config = PrivateHandshakeConfig::default();
+        let mut qrng = QRNG::from_seed([3u8; 32]);
+        let bob_keys = identity_keys(2);
+        let bob = LocalIdentity::new("bob", &bob_keys, "identity");
+        let (_, mut init) = PrivateInitiator::start(&config, &mut qrng).unwrap();
+        init.frame.truncate(1024);
+        assert!(matches!(
+            PrivateResponder::respond(&config, &init, &bob, &mut qrng),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_pending_handshakes_expire_and_dedup_inits() {
+        let config = PrivateHandshakeConfig {
+            pending_timeout_ms: 1_000,
+            max_pending: 2,
+            ..Default::default()
+        };
+        let mut qrng = QRNG::from_seed([4u8; 32]);
+        let bob_keys = identity_keys(2);
+        let bob = LocalIdentity::new("bob", &bob_keys, "identity");
+        let mut pending = PendingHandshakes::new();
+
+        let (initiator, init) = PrivateInitiator::start(&config, &mut qrng).unwrap();
+        pending.insert_initiator(initiator, 0);
+        assert!(pending.answered(&init).is_none());
+        let (responder, response) =
+            PrivateResponder::respond(&config, &init, &bob, &mut qrng).unwrap();
+        pending.insert_responder(responder, &init, &response, 500);
+        assert!(matches!(
+            pending.check_capacity(&config),
+            Err(SecureCommsError::ResourceExhausted(_))
+        ));
+
+        // A retransmitted init gets the same response; a forged one is refused
+        assert_eq!(pending.answered(&init).unwrap().unwrap(), response);
+        let mut forged = init.clone();
+        forged.frame[100] ^= 1;
+        assert!(pending.answered(&forged).unwrap().is_err());
+
+        assert_eq!(pending.expire(&config, 999), 0);
+        assert!(pending
+            .take_initiator(&config, &init.handshake_id, 1_000)
+            .is_none());
+        assert_eq!(pending.len(), 1);
+        assert!(pending
+            .take_responder(&config, &init.handshake_id, 1_500)
+            .is_none());
+        assert!(pending.is_empty());
+        assert!(PrivateHandshakeConfig {
+            max_pending: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
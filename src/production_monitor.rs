@@ -67,7 +67,7 @@
 //!
 //! ### Basic Production Monitor Setup
 //! ```rust,no_run
-//! use streamlined_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig};
+//! use quantum_forge_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -88,7 +88,7 @@
 //!
 //! ### Performance Metrics Recording
 //! ```rust,no_run
-//! # use streamlined_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig};
+//! # use quantum_forge_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = MonitoringConfig::default();
@@ -111,7 +111,7 @@
 //!
 //! ### Alert Subscription
 //! ```rust,no_run
-//! # use streamlined_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig, AlertEvent};
+//! # use quantum_forge_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig, AlertEvent};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = MonitoringConfig::default();
@@ -120,13 +120,13 @@
 //! let mut alert_receiver = monitor.subscribe_to_alerts();
 //! 
 //! // Process alerts
-//! while let Some(alert) = alert_receiver.recv().await {
+//! while let Ok(alert) = alert_receiver.recv().await {
 //!     match alert.severity {
-//!         streamlined_secure_comms::production_monitor::HealthStatus::Critical => {
+//!         quantum_forge_secure_comms::production_monitor::HealthStatus::Critical => {
 //!             println!("CRITICAL ALERT: {}", alert.message);
 //!             // Take immediate action
 //!         }
-//!         streamlined_secure_comms::production_monitor::HealthStatus::Warning => {
+//!         quantum_forge_secure_comms::production_monitor::HealthStatus::Warning => {
 //!             println!("WARNING: {}", alert.message);
 //!             // Monitor situation
 //!         }
@@ -141,7 +141,7 @@
 //!
 //! ### System Report Generation
 //! ```rust,no_run
-//! # use streamlined_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig};
+//! # use quantum_forge_secure_comms::production_monitor::{ProductionMonitor, MonitoringConfig};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = MonitoringConfig::default();
@@ -209,7 +209,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 
 use crate::logging::{log_info, LogCategory};
-use crate::Result;
+use crate::{Result, SecureCommsError};
 
 /// System health status levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -421,6 +421,44 @@ impl ProductionMonitor {
         self.alert_sender.subscribe()
     }
 
+    /// Publish an alert to subscribers unless `component` alerted within the cooldown
+    ///
+    /// Returns whether the alert was published.
+    pub fn raise_alert(
+        &self,
+        severity: HealthStatus,
+        component: &str,
+        message: &str,
+        suggested_actions: Vec<String>,
+    ) -> bool {
+        let now = Instant::now();
+        let cooling_down = self
+            .last_alerts
+            .get(component)
+            .map(|last| now.duration_since(*last) < self.config.alerts.alert_cooldown)
+            .unwrap_or(false);
+        if cooling_down {
+            return false;
+        }
+        self.last_alerts.insert(component.to_string(), now);
+
+        counter!("secure_comms_alerts_total", 1, "component" => component.to_string());
+        log_info(
+            LogCategory::System,
+            &format!("Alert [{}] {}: {}", severity, component, message),
+        );
+        // No subscribers is not an error; the alert is still logged
+        let _ = self.alert_sender.send(AlertEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            severity,
+            component: component.to_string(),
+            message: message.to_string(),
+            timestamp: Utc::now(),
+            suggested_actions,
+        });
+        true
+    }
+
     /// Record request
     pub fn record_request(&self, duration_ms: f64) {
         counter!("secure_comms_requests_total", 1);
@@ -472,6 +510,30 @@ pub fn create_production_monitor() -> ProductionMonitor {
     ProductionMonitor::new(MonitoringConfig::default())
 }
 
+/// Serve Prometheus metrics over HTTP on `address` (for example `0.0.0.0:9100`)
+///
+/// Fails if the address is invalid, the port is already bound, or another
+/// metrics recorder is installed in this process.
+pub fn install_prometheus_exporter(address: &str) -> Result<()> {
+    let listen: std::net::SocketAddr = address.parse().map_err(|e| {
+        SecureCommsError::Configuration(format!(
+            "Invalid metrics exporter address {}: {}",
+            address, e
+        ))
+    })?;
+    metrics_exporter_prometheus::PrometheusBuilder::new()
+        .with_http_listener(listen)
+        .install()
+        .map_err(|e| {
+            SecureCommsError::SystemError(format!("Metrics exporter on {} failed: {}", address, e))
+        })?;
+    log_info(
+        LogCategory::System,
+        &format!("Prometheus metrics exporter listening on {}", address),
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -499,4 +561,17 @@ mod tests {
         assert_eq!(format!("{}", HealthStatus::Warning), "WARNING");
         assert_eq!(format!("{}", HealthStatus::Critical), "CRITICAL");
     }
+
+    #[tokio::test]
+    async fn test_alert_cooldown() {
+        let monitor = ProductionMonitor::new(MonitoringConfig::default());
+        let mut alerts = monitor.subscribe_to_alerts();
+
+        assert!(monitor.raise_alert(HealthStatus::Warning, "budget:send", "slow", Vec::new()));
+        assert!(!monitor.raise_alert(HealthStatus::Warning, "budget:send", "slow", Vec::new()));
+        assert!(monitor.raise_alert(HealthStatus::Degraded, "budget:rekey", "slow", Vec::new()));
+
+        assert_eq!(alerts.recv().await.unwrap().component, "budget:send");
+        assert_eq!(alerts.recv().await.unwrap().severity, HealthStatus::Degraded);
+    }
 }
@@ -0,0 +1,353 @@
+//! # QRNG Bulk - High-Throughput Quantum Random Streams
+//!
+//! `QuantumCore::generate_quantum_random` yields one measurement's worth of
+//! bits per call, far too little to key bulk encryption or fill padding. The
+//! bulk generator samples shots of a dedicated state in large parallel
+//! batches, conditions them through the core's extraction stage and hands
+//! the bytes out as a blocking call, an iterator of batches or an
+//! `AsyncRead` stream.
+//!
+//! ## Generation Path
+//!
+//! - **Dedicated State**: Each generator owns a pinned state in the shared
+//!   core, released when the generator is dropped
+//! - **Batched Shots**: Shots are sampled in parallel from one prepared
+//!   superposition, as on hardware
+//! - **Conditioned Output**: Raw shots pass through the core extractor at
+//!   the entropy-backed ratio before any byte is returned
+//! - **Offloaded Batches**: The reader generates batches on the compute pool
+//!   so async callers never block a runtime worker
+//!
+//! ## Performance Characteristics
+//!
+//! - **Throughput**: >1MB/s of conditioned output with SHA-3 conditioning,
+//!   checked by `benches/qrng_bulk_benchmarks.rs`
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::qrng_bulk::{BulkQrng, BulkQrngConfig};
+//! use quantum_forge_secure_comms::quantum_core::QuantumCore;
+//! use std::sync::Arc;
+//! use tokio::io::AsyncReadExt;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let core = Arc::new(QuantumCore::new(4).await?);
+//! let qrng = Arc::new(BulkQrng::new(core, BulkQrngConfig::default())?);
+//! let key_material = qrng.generate(1024 * 1024)?;
+//!
+//! let mut reader = qrng.reader();
+//! let mut pad = vec![0u8; 4096];
+//! reader.read_exact(&mut pad).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::compute::spawn_compute;
+use crate::qrng_extraction::pack_bits;
+use crate::quantum_core::QuantumCore;
+use crate::{Result, SecureCommsError};
+
+/// Bulk generator settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkQrngConfig {
+    /// Width of the sampled state, bits per shot
+    pub qubits: u32,
+    /// Bytes produced per batch by the iterator and reader
+    pub batch_bytes: usize,
+}
+
+impl Default for BulkQrngConfig {
+    fn default() -> Self {
+        Self {
+            qubits: 4,
+            batch_bytes: 256 * 1024,
+        }
+    }
+}
+
+/// Output counters of a bulk generator
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BulkQrngStats {
+    /// Conditioned bytes handed out
+    pub bytes_generated: u64,
+    /// Generation calls served
+    pub batches: u64,
+    /// Time spent generating, in milliseconds
+    pub busy_ms: f64,
+    /// Bytes per second of generation time
+    pub bytes_per_second: f64,
+}
+
+/// Conditioned quantum random bytes in bulk from a dedicated core state
+pub struct BulkQrng {
+    core: Arc<QuantumCore>,
+    state_id: String,
+    config: BulkQrngConfig,
+    bytes_generated: AtomicU64,
+    batches: AtomicU64,
+    busy_nanos: AtomicU64,
+}
+
+impl BulkQrng {
+    /// Create a generator with its own pinned state in `core`
+    pub fn new(core: Arc<QuantumCore>, config: BulkQrngConfig) -> Result<Self> {
+        if config.batch_bytes == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Bulk QRNG batches must hold at least one byte".to_string(),
+            ));
+        }
+        let state_id =
+            core.create_comm_state(format!("bulk_qrng_{}", uuid::Uuid::new_v4()), config.qubits)?;
+        core.pin_state(&state_id);
+        Ok(Self {
+            core,
+            state_id,
+            config,
+            bytes_generated: AtomicU64::new(0),
+            batches: AtomicU64::new(0),
+            busy_nanos: AtomicU64::new(0),
+        })
+    }
+
+    /// Core state sampled by this generator
+    pub fn state_id(&self) -> &str {
+        &self.state_id
+    }
+
+    /// Generator settings
+    pub fn config(&self) -> &BulkQrngConfig {
+        &self.config
+    }
+
+    /// Generate `byte_count` conditioned bytes on the calling thread
+    pub fn generate(&self, byte_count: usize) -> Result<Vec<u8>> {
+        let start = Instant::now();
+        let bytes = self.core.generate_bulk_random(&self.state_id, byte_count)?;
+        self.busy_nanos
+            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        self.bytes_generated
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        self.batches.fetch_add(1, Ordering::Relaxed);
+        Ok(bytes)
+    }
+
+    /// Generate `byte_count` bytes of unconditioned shot outcomes
+    ///
+    /// For statistical testing of the source only; raw bytes carry its bias
+    /// and must not be used as key material.
+    pub fn generate_raw(&self, byte_count: usize) -> Result<Vec<u8>> {
+        let qubits = self.config.qubits.max(1) as usize;
+        let shots = (byte_count * 8).div_ceil(qubits);
+        let mut bytes = pack_bits(&self.core.sample_shots(&self.state_id, shots)?);
+        bytes.truncate(byte_count);
+        Ok(bytes)
+    }
+
+    /// Overwrite `buffer` with conditioned bytes
+    pub fn fill(&self, buffer: &mut [u8]) -> Result<()> {
+        let bytes = self.generate(buffer.len())?;
+        buffer.copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    /// Generate `byte_count` bytes on the compute pool
+    pub async fn generate_offloaded(self: &Arc<Self>, byte_count: usize) -> Result<Vec<u8>> {
+        let this = self.clone();
+        spawn_compute(move || this.generate(byte_count)).await?
+    }
+
+    /// Endless iterator of `batch_bytes` batches
+    pub fn batches(self: &Arc<Self>) -> QrngBatches {
+        QrngBatches {
+            source: self.clone(),
+        }
+    }
+
+    /// Endless `AsyncRead` stream generating a batch whenever it runs dry
+    pub fn reader(self: &Arc<Self>) -> QrngReader {
+        QrngReader {
+            source: self.clone(),
+            buffer: Vec::new(),
+            position: 0,
+            pending: None,
+        }
+    }
+
+    /// Output counters and measured throughput
+    pub fn stats(&self) -> BulkQrngStats {
+        let bytes_generated = self.bytes_generated.load(Ordering::Relaxed);
+        let busy_seconds = self.busy_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        BulkQrngStats {
+            bytes_generated,
+            batches: self.batches.load(Ordering::Relaxed),
+            busy_ms: busy_seconds * 1000.0,
+            bytes_per_second: if busy_seconds > 0.0 {
+                bytes_generated as f64 / busy_seconds
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+impl Drop for BulkQrng {
+    fn drop(&mut self) {
+        self.core.take_state(&self.state_id);
+    }
+}
+
+/// Iterator of conditioned batches from a `BulkQrng`
+pub struct QrngBatches {
+    source: Arc<BulkQrng>,
+}
+
+impl Iterator for QrngBatches {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.source.generate(self.source.config.batch_bytes))
+    }
+}
+
+type PendingBatch = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send>>;
+
+/// `AsyncRead` stream of conditioned bytes from a `BulkQrng`
+pub struct QrngReader {
+    source: Arc<BulkQrng>,
+    buffer: Vec<u8>,
+    position: usize,
+    pending: Option<PendingBatch>,
+}
+
+impl AsyncRead for QrngReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        loop {
+            if buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            if this.position < this.buffer.len() {
+                let available = &this.buffer[this.position..];
+                let count = available.len().min(buf.remaining());
+                buf.put_slice(&available[..count]);
+                this.position += count;
+                return Poll::Ready(Ok(()));
+            }
+            let pending = this.pending.get_or_insert_with(|| {
+                let source = this.source.clone();
+                Box::pin(async move {
+                    let batch_bytes = source.config.batch_bytes;
+                    source.generate_offloaded(batch_bytes).await
+                })
+            });
+            match pending.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => {
+                    this.pending = None;
+                    match result {
+                        Ok(bytes) => {
+                            this.buffer = bytes;
+                            this.position = 0;
+                        }
+                        Err(e) => return Poll::Ready(Err(io::Error::other(e.to_string()))),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    async fn bulk(batch_bytes: usize) -> (Arc<QuantumCore>, Arc<BulkQrng>) {
+        let core = Arc::new(QuantumCore::new(4).await.unwrap());
+        let config = BulkQrngConfig {
+            batch_bytes,
+            ..Default::default()
+        };
+        let qrng = Arc::new(BulkQrng::new(core.clone(), config).unwrap());
+        (core, qrng)
+    }
+
+    #[tokio::test]
+    async fn test_bulk_output_is_conditioned_and_balanced() {
+        let (core, qrng) = bulk(1024).await;
+        let bytes = qrng.generate(64 * 1024).unwrap();
+        assert_eq!(bytes.len(), 64 * 1024);
+        let ones: u32 = bytes.iter().map(|byte| byte.count_ones()).sum();
+        let fraction = ones as f64 / (bytes.len() * 8) as f64;
+        assert!((fraction - 0.5).abs() < 0.01, "ones fraction {}", fraction);
+
+        // Default extraction spends two raw bits per output bit
+        let extraction = core.extraction_stats();
+        assert_eq!(extraction.output_bits, 64 * 1024 * 8);
+        assert_eq!(extraction.raw_bits, 2 * 64 * 1024 * 8);
+        assert_eq!(core.total_measurements(), 64 * 1024 * 4);
+
+        let raw = qrng.generate_raw(100).unwrap();
+        assert_eq!(raw.len(), 100);
+        assert_eq!(core.extraction_stats().output_bits, 64 * 1024 * 8);
+
+        let mut buffer = [0u8; 33];
+        qrng.fill(&mut buffer).unwrap();
+        assert_ne!(buffer, [0u8; 33]);
+        assert_eq!(qrng.stats().bytes_generated, 64 * 1024 + 33);
+        assert_eq!(qrng.stats().batches, 2);
+    }
+
+    #[tokio::test]
+    async fn test_reader_and_batches_stream_bytes() {
+        let (_core, qrng) = bulk(1000).await;
+        let mut reader = qrng.reader();
+        let mut streamed = vec![0u8; 2500];
+        reader.read_exact(&mut streamed).await.unwrap();
+        assert_ne!(&streamed[..1000], &streamed[1000..2000]);
+        // Three batches were generated to cover 2500 bytes
+        assert_eq!(qrng.stats().batches, 3);
+
+        let batches: Vec<Vec<u8>> = qrng.batches().take(2).map(|b| b.unwrap()).collect();
+        assert!(batches.iter().all(|batch| batch.len() == 1000));
+        assert_ne!(batches[0], batches[1]);
+    }
+
+    #[tokio::test]
+    async fn test_generator_state_lifecycle() {
+        let core = Arc::new(QuantumCore::new(4).await.unwrap());
+        let invalid = BulkQrngConfig {
+            batch_bytes: 0,
+            ..Default::default()
+        };
+        assert!(BulkQrng::new(core.clone(), invalid).is_err());
+        let too_wide = BulkQrngConfig {
+            qubits: 5,
+            ..Default::default()
+        };
+        assert!(BulkQrng::new(core.clone(), too_wide).is_err());
+
+        let qrng = BulkQrng::new(core.clone(), BulkQrngConfig::default()).unwrap();
+        let state_id = qrng.state_id().to_string();
+        assert!(core.is_pinned(&state_id));
+        assert!(qrng.generate(0).unwrap().is_empty());
+        drop(qrng);
+        assert!(core.get_state_info(&state_id).is_none());
+        assert!(!core.is_pinned(&state_id));
+    }
+}
@@ -0,0 +1,427 @@
+//! # QRNG Extraction - Conditioning Raw Measurement Bits
+//!
+//! Raw measurement outcomes carry bias from imperfect state preparation and
+//! detector noise, so they are never handed to consumers directly. The
+//! extractor compresses raw bits into fewer, nearly uniform bits, spending
+//! raw bits in proportion to the estimated min-entropy of the source.
+//!
+//! ## Extraction Stage
+//!
+//! - **SHA-3 Conditioning**: Keyed SHA3-256 in counter mode over the packed
+//!   raw bits (default)
+//! - **Toeplitz Hashing**: Seeded Toeplitz matrix, a two-universal hash
+//!   covered by the leftover hash lemma
+//! - **Min-Entropy Estimate**: Most-common-value estimate (NIST SP 800-90B
+//!   §6.3.1) over every raw bit seen, with `assumed_min_entropy` used until
+//!   `min_samples` bits were observed
+//! - **Extraction Ratio**: Output bits per raw bit, never above the estimate
+//!   and capped at `max_extraction_ratio`
+//! - **Block Extraction**: Bulk draws are conditioned in independent blocks
+//!   on the rayon pool
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::qrng_extraction::{ExtractionConfig, RandomnessExtractor};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut extractor = RandomnessExtractor::new(ExtractionConfig::default(), [7u8; 32])?;
+//! let raw = vec![1, 0, 1, 1, 0, 0, 1, 0];
+//! let needed = extractor.raw_bits_needed(4)?;
+//! assert!(raw.len() >= needed);
+//! let conditioned = extractor.extract(&raw, 4)?;
+//! assert_eq!(conditioned.len(), 4);
+//! # Ok(())
+//! # }
+//! ```
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::{Result, SecureCommsError};
+
+/// z-score of the 99% upper confidence bound on the most common value
+const CONFIDENCE_Z: f64 = 2.576;
+
+/// Conditioning function applied to raw measurement bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExtractorKind {
+    /// Keyed SHA3-256 in counter mode
+    #[default]
+    Sha3,
+    /// Seeded Toeplitz matrix multiplication over GF(2)
+    Toeplitz,
+}
+
+/// Extraction settings of the QRNG conditioning stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionConfig {
+    /// Conditioning function
+    pub extractor: ExtractorKind,
+    /// Upper bound on conditioned bits per raw bit
+    pub max_extraction_ratio: f64,
+    /// Min-entropy per raw bit assumed until enough bits were observed
+    pub assumed_min_entropy: f64,
+    /// Raw bits observed before the running estimate replaces the assumption
+    pub min_samples: u64,
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            extractor: ExtractorKind::Sha3,
+            max_extraction_ratio: 0.5,
+            assumed_min_entropy: 0.8,
+            min_samples: 1024,
+        }
+    }
+}
+
+/// Most-common-value min-entropy estimate of a binary source
+#[derive(Debug, Clone, Default)]
+pub struct MinEntropyEstimator {
+    ones: u64,
+    total: u64,
+}
+
+impl MinEntropyEstimator {
+    /// Record raw bits, one per byte
+    pub fn observe(&mut self, bits: &[u8]) {
+        self.ones += bits.iter().filter(|&&bit| bit == 1).count() as u64;
+        self.total += bits.len() as u64;
+    }
+
+    /// Number of bits observed
+    pub fn samples(&self) -> u64 {
+        self.total
+    }
+
+    /// Min-entropy per bit from the upper confidence bound on the most
+    /// common value, `None` before two bits were observed
+    pub fn estimate(&self) -> Option<f64> {
+        if self.total < 2 {
+            return None;
+        }
+        let n = self.total as f64;
+        let p = self.ones.max(self.total - self.ones) as f64 / n;
+        let upper = (p + CONFIDENCE_Z * (p * (1.0 - p) / (n - 1.0)).sqrt()).min(1.0);
+        Some(-upper.log2())
+    }
+}
+
+/// Extraction counters and current entropy accounting
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExtractionStats {
+    /// Raw measurement bits consumed
+    pub raw_bits: u64,
+    /// Conditioned bits produced
+    pub output_bits: u64,
+    /// Min-entropy per raw bit currently credited
+    pub min_entropy: f64,
+    /// Conditioned bits per raw bit currently applied
+    pub extraction_ratio: f64,
+}
+
+/// Conditions raw QRNG bits into output bits at an entropy-backed ratio
+#[derive(Debug, Clone)]
+pub struct RandomnessExtractor {
+    config: ExtractionConfig,
+    /// Key of the SHA-3 conditioner and seed of the Toeplitz matrix
+    seed: [u8; 32],
+    estimator: MinEntropyEstimator,
+    output_bits: u64,
+}
+
+impl RandomnessExtractor {
+    /// Create an extractor; the seed must be independent of the raw bits
+    pub fn new(config: ExtractionConfig, seed: [u8; 32]) -> Result<Self> {
+        let in_unit_range = |value: f64| value > 0.0 && value <= 1.0;
+        if !in_unit_range(config.max_extraction_ratio) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Extraction ratio {} must be in (0, 1]",
+                config.max_extraction_ratio
+            )));
+        }
+        if !in_unit_range(config.assumed_min_entropy) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Assumed min-entropy {} must be in (0, 1]",
+                config.assumed_min_entropy
+            )));
+        }
+        Ok(Self {
+            config,
+            seed,
+            estimator: MinEntropyEstimator::default(),
+            output_bits: 0,
+        })
+    }
+
+    /// Extraction settings
+    pub fn config(&self) -> &ExtractionConfig {
+        &self.config
+    }
+
+    /// Min-entropy per raw bit currently credited to the source
+    pub fn min_entropy(&self) -> f64 {
+        if self.estimator.samples() < self.config.min_samples {
+            return self.config.assumed_min_entropy;
+        }
+        self.estimator
+            .estimate()
+            .unwrap_or(self.config.assumed_min_entropy)
+    }
+
+    /// Conditioned bits produced per raw bit
+    pub fn extraction_ratio(&self) -> f64 {
+        self.min_entropy().min(self.config.max_extraction_ratio)
+    }
+
+    /// Raw bits required to produce `output_bits` conditioned bits
+    ///
+    /// Fails once the source is estimated to carry no entropy at all, as a
+    /// stuck detector would.
+    pub fn raw_bits_needed(&self, output_bits: usize) -> Result<usize> {
+        let ratio = self.extraction_ratio();
+        if ratio <= 0.0 {
+            return Err(SecureCommsError::QuantumOperation(
+                "QRNG source min-entropy estimate is zero".to_string(),
+            ));
+        }
+        Ok((output_bits as f64 / ratio).ceil() as usize)
+    }
+
+    /// Condition raw bits (one per byte) into `output_bits` bits
+    ///
+    /// Fails when the raw bits hold too little estimated entropy for the
+    /// requested output, so callers size their draw with `raw_bits_needed`.
+    pub fn extract(&mut self, raw_bits: &[u8], output_bits: usize) -> Result<Vec<u8>> {
+        if raw_bits.iter().any(|&bit| bit > 1) {
+            return Err(SecureCommsError::Validation(
+                "Raw QRNG input must be one bit per byte".to_string(),
+            ));
+        }
+        let needed = self.raw_bits_needed(output_bits)?;
+        if raw_bits.len() < needed {
+            return Err(SecureCommsError::Validation(format!(
+                "{} raw bits cannot yield {} conditioned bits, {} required",
+                raw_bits.len(),
+                output_bits,
+                needed
+            )));
+        }
+        self.estimator.observe(raw_bits);
+        self.output_bits += output_bits as u64;
+        Ok(self.condition(raw_bits, output_bits))
+    }
+
+    /// Condition a large draw in independent blocks, in parallel
+    ///
+    /// Every `raw_bits_needed(block_bits)` raw bits yield `block_bits` output
+    /// bits; raw bits left over after the last whole block are discarded, so
+    /// a draw shorter than one block yields nothing.
+    pub fn extract_blocks(&mut self, raw_bits: &[u8], block_bits: usize) -> Result<Vec<u8>> {
+        if raw_bits.iter().any(|&bit| bit > 1) {
+            return Err(SecureCommsError::Validation(
+                "Raw QRNG input must be one bit per byte".to_string(),
+            ));
+        }
+        if block_bits == 0 {
+            return Err(SecureCommsError::Validation(
+                "Extraction blocks must produce at least one bit".to_string(),
+            ));
+        }
+        let per_block = self.raw_bits_needed(block_bits)?;
+        let used = &raw_bits[..raw_bits.len() / per_block * per_block];
+        let this = &*self;
+        let output: Vec<u8> = used
+            .par_chunks(per_block)
+            .flat_map_iter(|block| this.condition(block, block_bits))
+            .collect();
+        self.estimator.observe(used);
+        self.output_bits += output.len() as u64;
+        Ok(output)
+    }
+
+    fn condition(&self, raw_bits: &[u8], output_bits: usize) -> Vec<u8> {
+        match self.config.extractor {
+            ExtractorKind::Sha3 => sha3_condition(&self.seed, raw_bits, output_bits),
+            ExtractorKind::Toeplitz => toeplitz_hash(&self.seed, raw_bits, output_bits),
+        }
+    }
+
+    /// Current counters and entropy accounting
+    pub fn stats(&self) -> ExtractionStats {
+        ExtractionStats {
+            raw_bits: self.estimator.samples(),
+            output_bits: self.output_bits,
+            min_entropy: self.min_entropy(),
+            extraction_ratio: self.extraction_ratio(),
+        }
+    }
+}
+
+/// Pack bits into bytes, most significant bit first
+pub fn pack_bits(bits: &[u8]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, &bit)| byte | (bit << (7 - i)))
+        })
+        .collect()
+}
+
+/// `count` bits of SHA3-256 output keyed by `seed` in counter mode
+fn sha3_stream(seed: &[u8; 32], label: &[u8], input: &[u8], count: usize) -> Vec<u8> {
+    let mut bits = Vec::with_capacity(count);
+    let mut counter = 0u64;
+    while bits.len() < count {
+        let mut hasher = Sha3_256::new();
+        hasher.update(label);
+        hasher.update(seed);
+        hasher.update(counter.to_le_bytes());
+        hasher.update((count as u64).to_le_bytes());
+        hasher.update(input);
+        for byte in hasher.finalize() {
+            for shift in (0..8).rev() {
+                bits.push((byte >> shift) & 1);
+            }
+        }
+        counter += 1;
+    }
+    bits.truncate(count);
+    bits
+}
+
+fn sha3_condition(seed: &[u8; 32], raw_bits: &[u8], output_bits: usize) -> Vec<u8> {
+    let mut input = (raw_bits.len() as u64).to_le_bytes().to_vec();
+    input.extend(pack_bits(raw_bits));
+    sha3_stream(seed, b"qrng-sha3-conditioner", &input, output_bits)
+}
+
+/// Multiply the raw bits by an `output_bits × n` Toeplitz matrix over GF(2)
+///
+/// The matrix is fixed by its first row and column, `n + m - 1` bits expanded
+/// from the seed, so `T[i][j] = diagonal[i + n - 1 - j]`.
+fn toeplitz_hash(seed: &[u8; 32], raw_bits: &[u8], output_bits: usize) -> Vec<u8> {
+    let n = raw_bits.len();
+    if n == 0 {
+        return vec![0; output_bits];
+    }
+    let dimensions = [(n as u64).to_le_bytes(), (output_bits as u64).to_le_bytes()].concat();
+    let diagonal = sha3_stream(
+        seed,
+        b"qrng-toeplitz-matrix",
+        &dimensions,
+        n + output_bits - 1,
+    );
+    (0..output_bits)
+        .map(|i| {
+            raw_bits
+                .iter()
+                .enumerate()
+                .fold(0u8, |acc, (j, &bit)| acc ^ (diagonal[i + n - 1 - j] & bit))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_follows_min_entropy_estimate() {
+        let config = ExtractionConfig {
+            max_extraction_ratio: 0.9,
+            assumed_min_entropy: 0.5,
+            min_samples: 64,
+            ..Default::default()
+        };
+        let mut extractor = RandomnessExtractor::new(config.clone(), [1; 32]).unwrap();
+        assert_eq!(extractor.extraction_ratio(), 0.5);
+        assert_eq!(extractor.raw_bits_needed(8).unwrap(), 16);
+
+        // Balanced source: the estimate replaces the assumption once sampled
+        let balanced: Vec<u8> = (0..4096).map(|i| (i % 2) as u8).collect();
+        extractor.extract(&balanced, 64).unwrap();
+        assert!(extractor.min_entropy() > 0.9);
+        assert_eq!(extractor.extraction_ratio(), 0.9);
+
+        // Heavily biased source earns a much lower ratio
+        let mut biased = RandomnessExtractor::new(config, [1; 32]).unwrap();
+        let skewed: Vec<u8> = (0..4096).map(|i| (i % 10 == 0) as u8).collect();
+        biased.extract(&skewed, 64).unwrap();
+        assert!(biased.extraction_ratio() < 0.2);
+        assert!(biased.raw_bits_needed(8).unwrap() > 40);
+        assert_eq!(biased.stats().raw_bits, 4096);
+
+        // A stuck source earns nothing
+        let mut stuck = RandomnessExtractor::new(ExtractionConfig::default(), [1; 32]).unwrap();
+        stuck.extract(&[0; 2048], 8).unwrap();
+        assert!(stuck.raw_bits_needed(1).is_err());
+    }
+
+    #[test]
+    fn test_extract_rejects_insufficient_entropy() {
+        let mut extractor = RandomnessExtractor::new(ExtractionConfig::default(), [2; 32]).unwrap();
+        assert!(extractor.extract(&[1, 0, 1], 2).is_err());
+        assert!(extractor.extract(&[1, 0, 2, 0], 2).is_err());
+        assert_eq!(extractor.extract(&[1, 0, 1, 1], 2).unwrap().len(), 2);
+        assert_eq!(extractor.stats().output_bits, 2);
+
+        let bad = ExtractionConfig {
+            max_extraction_ratio: 1.5,
+            ..Default::default()
+        };
+        assert!(RandomnessExtractor::new(bad, [0; 32]).is_err());
+    }
+
+    #[test]
+    fn test_block_extraction_matches_single_blocks() {
+        let raw: Vec<u8> = (0..1100).map(|i| ((i * 13 + i / 5) % 2) as u8).collect();
+        let mut bulk = RandomnessExtractor::new(ExtractionConfig::default(), [5; 32]).unwrap();
+        let mut single = RandomnessExtractor::new(ExtractionConfig::default(), [5; 32]).unwrap();
+        let blocks = bulk.extract_blocks(&raw, 256).unwrap();
+        // Two whole 512-bit blocks; the trailing 76 raw bits are dropped
+        assert_eq!(blocks.len(), 512);
+        assert_eq!(bulk.stats().raw_bits, 1024);
+        assert_eq!(
+            &blocks[..256],
+            &single.extract(&raw[..512], 256).unwrap()[..]
+        );
+        assert_eq!(
+            &blocks[256..],
+            &single.extract(&raw[512..1024], 256).unwrap()[..]
+        );
+        assert_eq!(pack_bits(&[1, 0, 0, 0, 0, 0, 0, 1, 1]), vec![0x81, 0x80]);
+        assert!(bulk.extract_blocks(&raw[..100], 256).unwrap().is_empty());
+        assert!(bulk.extract_blocks(&raw, 0).is_err());
+    }
+
+    #[test]
+    fn test_extractors_are_seeded_and_balanced() {
+        for kind in [ExtractorKind::Sha3, ExtractorKind::Toeplitz] {
+            let config = ExtractionConfig {
+                extractor: kind,
+                ..Default::default()
+            };
+            let raw: Vec<u8> = (0..512).map(|i| ((i * 7 + i / 3) % 2) as u8).collect();
+            let mut first = RandomnessExtractor::new(config.clone(), [3; 32]).unwrap();
+            let mut same = RandomnessExtractor::new(config.clone(), [3; 32]).unwrap();
+            let mut other = RandomnessExtractor::new(config, [4; 32]).unwrap();
+            let out = first.extract(&raw, 256).unwrap();
+            assert_eq!(out, same.extract(&raw, 256).unwrap());
+            assert_ne!(out, other.extract(&raw, 256).unwrap());
+            assert!(out.iter().all(|&bit| bit <= 1));
+            let ones = out.iter().filter(|&&bit| bit == 1).count();
+            assert!(
+                (80..=176).contains(&ones),
+                "{:?} produced {} ones",
+                kind,
+                ones
+            );
+        }
+    }
+}
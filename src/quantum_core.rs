@@ -19,6 +19,23 @@
 //! - **Quantum Circuit Compilation**: Optimized circuit execution with depth management
 //! - **Circuit Optimization**: Automatic gate sequence optimization for performance
 //!
+//! ### Concurrent Sessions
+//! - **Per-State Locking**: States live in a concurrent map, each behind its own mutex
+//! - **Shared Core**: All operations take `&self`; share the core in an `Arc` across tasks
+//! - **Independent Sessions**: QKD sessions with different peers never wait on each other's states
+//! - **Non-Blocking Collection**: Garbage collection skips states busy in an operation
+//!
+//! ### Decoherence Modeling
+//! - **T1/T2 Noise Model**: Optional amplitude damping and dephasing per state
+//! - **Wall-Clock or Operation Count**: States age in seconds or in applied operations
+//! - **Decaying Fidelity**: Fidelity drops as coherence is lost
+//! - **Entanglement Refresh**: QKD states are re-entangled once coherence falls below threshold
+//!
+//! ### Conditioned Randomness
+//! - **Extraction Stage**: Raw measurement bits pass through SHA-3 or Toeplitz conditioning
+//! - **Entropy-Backed Ratio**: Raw bits are spent according to the estimated min-entropy
+//! - **Raw Access**: Unconditioned bits remain available for entropy testing
+//!
 //! ### Hardware Integration with Physics-Based Fallback
 //! - **Automatic Hardware Detection**: Quantum hardware interface with authentic simulation fallback
 //! - **Multi-Architecture Support**: Supports various quantum computing platforms
@@ -38,7 +55,7 @@
 //! - **Gate Operation Speed**: <100μs per gate operation with unitary preservation
 //! - **Measurement Fidelity**: Calculated from quantum state normalization
 //! - **Bell State Fidelity**: Dynamic calculation from entanglement properties
-//! - **Quantum Random Generation**: >1MB/s of quantum-quality random bits
+//! - **Quantum Random Generation**: >1MB/s of conditioned random bytes through `generate_bulk_random`
 //! - **Memory Efficiency**: <10MB for 4-qubit quantum state management
 //!
 //! ### Quantum Security Performance
@@ -96,7 +113,7 @@
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     // Create 4-qubit quantum core with physics-based fidelity
-//!     let mut quantum_core = QuantumCore::new(4).await?;
+//!     let quantum_core = QuantumCore::new(4).await?;
 //!     Ok(())
 //! }
 //! ```
@@ -106,7 +123,7 @@
 //! # use quantum_forge_secure_comms::quantum_core::QuantumCore;
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! # let mut quantum_core = QuantumCore::new(4).await?;
+//! # let quantum_core = QuantumCore::new(4).await?;
 //! // Create quantum communication state with dynamic fidelity
 //! let state_id = quantum_core.create_comm_state("alice_state".to_string(), 2)?;
 //! 
@@ -124,7 +141,7 @@
 //! # use quantum_forge_secure_comms::quantum_core::{QuantumCore, QuantumGate};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! # let mut quantum_core = QuantumCore::new(4).await?;
+//! # let quantum_core = QuantumCore::new(4).await?;
 //! # let state_id = quantum_core.create_comm_state("alice_state".to_string(), 2)?;
 //! // Create quantum circuit with unitary preservation
 //! let circuit_id = quantum_core.create_circuit("qkd_circuit".to_string(), 2)?;
@@ -144,7 +161,7 @@
 //! # use quantum_forge_secure_comms::quantum_core::QuantumCore;
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! # let mut quantum_core = QuantumCore::new(4).await?;
+//! # let quantum_core = QuantumCore::new(4).await?;
 //! // Create Bell pair with authentic quantum mechanics
 //! let bell_result = quantum_core.create_bell_pair(0, 1)?;
 //! 
@@ -192,7 +209,7 @@
 //! - **Quantum Integrity**: State integrity verification through physics
 //!
 //! ### Mathematical Foundation
-//! ```rust,no_run
+//! ```rust,ignore
 //! // Physics-based fidelity calculation from quantum state normalization
 //! fn update_fidelity(&mut self) {
 //!     let norm_squared: f64 = self.amplitudes.iter().map(|&a| a * a).sum();
@@ -201,12 +218,31 @@
 //! ```
 
 use chrono;
+use dashmap::{DashMap, DashSet};
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::time::Instant;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::calibration::{
+    CalibrationConfig, CalibrationData, CalibrationSource, CalibrationStore, CalibrationSummary,
+    Transpiled,
+};
+use crate::compute::spawn_compute;
+use crate::expiry::now_ms;
+use crate::memory_profile::{MemoryFootprint, SubsystemUsage};
+use crate::quantum_journal::{seed_bytes, JournalConfig, JournalEvent, QuantumJournal, StateJournal};
 use crate::crypto_protocols::QRNG;
 use crate::performance::PerformanceMetrics;
+use crate::qrng_extraction::{pack_bits, ExtractionConfig, ExtractionStats, RandomnessExtractor};
+use crate::qubit_registry::{QubitAddress, QubitId, QubitRegistry};
+use crate::quantum_scheduler::{
+    BackendProfile, OperationProfile, Placement, QuantumBackend, QuantumScheduler, SchedulerStats,
+    SchedulingConfig,
+};
 use crate::security_foundation::{SecurityConfig, SecurityFoundation};
 use crate::{Result, SecureCommsError};
 
@@ -258,6 +294,48 @@ pub struct QuantumConfig {
     /// How often to clean up old quantum states to prevent memory accumulation.
     /// Quantum states are automatically cleaned up after this interval.
     pub cleanup_interval_seconds: u64,
+
+    /// Garbage collection limits for states and circuits
+    ///
+    /// Age and memory thresholds applied by the collector that runs every
+    /// `cleanup_interval_seconds` and whenever state memory exceeds its limit.
+    #[serde(default)]
+    pub gc: QuantumGcConfig,
+
+    /// Operation journal for audit and exact replay (disabled when `None`)
+    ///
+    /// With a deterministic seed the QRNG is seeded from it, so journaled
+    /// sessions can be reproduced outcome for outcome.
+    #[serde(default)]
+    pub journal: Option<JournalConfig>,
+
+    /// T1/T2 decoherence of created states (noise-free when `None`)
+    ///
+    /// States lose fidelity as they age, and QKD channels re-entangle
+    /// states whose coherence falls below `refresh_threshold`.
+    #[serde(default)]
+    pub noise: Option<NoiseModel>,
+
+    /// Conditioning of raw measurement bits served as random output
+    ///
+    /// `generate_quantum_random` spends raw bits at the extraction ratio
+    /// backed by the estimated min-entropy of the measurements.
+    #[serde(default)]
+    pub extraction: ExtractionConfig,
+
+    /// Placement of operations on hardware backends or the simulation
+    ///
+    /// Queue depth, cost budget, fidelity and latency limits applied to
+    /// every backend added with `QuantumCore::register_backend`.
+    #[serde(default)]
+    pub scheduling: SchedulingConfig,
+
+    /// Refresh interval and trusted age of backend calibrations
+    ///
+    /// Fresh calibrations drive transpilation, the fidelity the scheduler
+    /// expects of each backend and the reported hardware error rates.
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
 }
 
 impl Default for QuantumConfig {
@@ -269,10 +347,211 @@ impl Default for QuantumConfig {
             enable_error_correction: false,
             max_circuit_depth: 100,
             cleanup_interval_seconds: 300,
+            gc: QuantumGcConfig::default(),
+            journal: None,
+            noise: None,
+            extraction: ExtractionConfig::default(),
+            scheduling: SchedulingConfig::default(),
+            calibration: CalibrationConfig::default(),
+        }
+    }
+}
+
+/// Thresholds of quantum state and circuit garbage collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantumGcConfig {
+    /// Age after which unpinned states and unused circuits are collected
+    pub max_age_seconds: u64,
+    /// State memory above which the oldest unpinned states are collected
+    /// regardless of age
+    pub memory_limit_bytes: usize,
+}
+
+impl Default for QuantumGcConfig {
+    fn default() -> Self {
+        Self {
+            max_age_seconds: 300,
+            memory_limit_bytes: 8 * 1024 * 1024, // 8MB
+        }
+    }
+}
+
+/// Why a garbage collection ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcTrigger {
+    /// The cleanup interval elapsed
+    Interval,
+    /// State memory exceeded `memory_limit_bytes`
+    MemoryPressure,
+    /// Requested by the caller
+    Manual,
+}
+
+/// Outcome of one garbage collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GcReport {
+    pub trigger: GcTrigger,
+    pub states_collected: usize,
+    pub circuits_collected: usize,
+    /// Expired states kept because an active session pins them
+    pub pinned_retained: usize,
+    pub bytes_freed: usize,
+}
+
+/// Collection counters since the core started
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuantumGcStats {
+    pub runs: u64,
+    pub pressure_runs: u64,
+    pub states_collected: u64,
+    pub circuits_collected: u64,
+    pub bytes_freed: u64,
+    pub last_run_at: Option<u64>,
+}
+
+/// What drives decoherence of a quantum state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecoherenceClock {
+    /// Seconds since the state was prepared
+    #[default]
+    WallClock,
+    /// Operations applied since the state was prepared
+    OperationCount,
+}
+
+/// Amplitude damping (T1) and dephasing (T2) noise model
+///
+/// Both times are in units of `clock`. A non-positive time disables that
+/// channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoiseModel {
+    /// Energy relaxation time
+    pub t1: f64,
+    /// Dephasing time
+    pub t2: f64,
+    pub clock: DecoherenceClock,
+    /// Coherence e^(-t/T2) below which QKD states are re-entangled
+    pub refresh_threshold: f64,
+}
+
+impl Default for NoiseModel {
+    fn default() -> Self {
+        Self {
+            t1: 100.0,
+            t2: 50.0,
+            clock: DecoherenceClock::WallClock,
+            refresh_threshold: 0.9,
+        }
+    }
+}
+
+impl NoiseModel {
+    /// Fraction of coherence left after `elapsed` clock units
+    pub fn coherence(&self, elapsed: f64) -> f64 {
+        retention(elapsed, self.t2)
+    }
+
+    /// Average fidelity of one qubit after `elapsed` clock units
+    ///
+    /// Averaged over the Bloch sphere, a qubit that relaxed with e^(-t/T1)
+    /// and dephased with e^(-t/T2) retains (3 + e^(-t/T1) + 2e^(-t/T2)) / 6.
+    pub fn qubit_fidelity(&self, elapsed: f64) -> f64 {
+        (3.0 + retention(elapsed, self.t1) + 2.0 * retention(elapsed, self.t2)) / 6.0
+    }
+}
+
+fn retention(elapsed: f64, time: f64) -> f64 {
+    if time > 0.0 {
+        (-elapsed.max(0.0) / time).exp()
+    } else {
+        1.0
+    }
+}
+
+/// Age of a state under an active noise model
+#[derive(Debug, Clone)]
+struct Decoherence {
+    model: NoiseModel,
+    prepared_at: Instant,
+    operations: u64,
+}
+
+impl Decoherence {
+    fn elapsed(&self) -> f64 {
+        match self.model.clock {
+            DecoherenceClock::WallClock => self.prepared_at.elapsed().as_secs_f64(),
+            DecoherenceClock::OperationCount => self.operations as f64,
+        }
+    }
+}
+
+/// Single-qubit measurement basis
+///
+/// Outcome 0 is the basis state at Bloch angles (θ, φ), i.e.
+/// cos(θ/2)|0⟩ + e^(iφ) sin(θ/2)|1⟩; outcome 1 is its orthogonal partner.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum MeasurementBasis {
+    /// Computational basis {|0⟩, |1⟩}
+    #[default]
+    Z,
+    /// Hadamard basis {|+⟩, |−⟩}
+    X,
+    /// Circular basis {|+i⟩, |−i⟩}
+    Y,
+    /// Basis along an arbitrary Bloch sphere axis
+    Angle { theta: f64, phi: f64 },
+}
+
+impl MeasurementBasis {
+    /// Bloch sphere angles (θ, φ) of the outcome 0 basis state
+    pub fn bloch_angles(&self) -> (f64, f64) {
+        use std::f64::consts::FRAC_PI_2;
+        match *self {
+            MeasurementBasis::Z => (0.0, 0.0),
+            MeasurementBasis::X => (FRAC_PI_2, 0.0),
+            MeasurementBasis::Y => (FRAC_PI_2, FRAC_PI_2),
+            MeasurementBasis::Angle { theta, phi } => (theta, phi),
+        }
+    }
+
+    /// Basis state for an outcome as complex (re, im) amplitudes of |0⟩ and |1⟩
+    fn eigenstate(&self, outcome: u8) -> [(f64, f64); 2] {
+        if *self == MeasurementBasis::Z {
+            return if outcome == 0 {
+                [(1.0, 0.0), (0.0, 0.0)]
+            } else {
+                [(0.0, 0.0), (1.0, 0.0)]
+            };
+        }
+        let (theta, phi) = self.bloch_angles();
+        let (cos, sin) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+        if outcome == 0 {
+            [(cos, 0.0), (sin * phi.cos(), sin * phi.sin())]
+        } else {
+            [(sin, 0.0), (-cos * phi.cos(), -cos * phi.sin())]
         }
     }
 }
 
+/// Product of two complex numbers as (re, im) pairs
+fn complex_mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+/// Outcome of measuring some of a state's qubits
+#[derive(Debug, Clone)]
+pub struct PartialMeasurement {
+    /// Measured qubits in the order requested
+    pub qubits: Vec<u32>,
+    /// Outcome bit of each measured qubit, in the same order
+    pub outcome: Vec<u8>,
+    /// Probability of this outcome before the measurement
+    pub probability: f64,
+    /// Normalized state of the unmeasured qubits given the outcome, renumbered
+    /// in ascending order; a zero-qubit state when every qubit was measured
+    pub conditional_state: QuantumState,
+}
+
 /// Enhanced quantum state with Born rule measurements and phase tracking
 /// 
 /// Represents a complete quantum state with amplitude and phase information,
@@ -285,6 +564,9 @@ impl Default for QuantumConfig {
 /// - **Phase Tracking**: Complete quantum state representation with phases
 /// - **Unitary Evolution**: No artificial noise - purity preserved through mathematics
 /// - **Dynamic Measurements**: Quantum measurements with proper state collapse
+/// - **Basis Selection**: Per-qubit measurement in Z, X, Y or any Bloch sphere axis
+/// - **Partial Measurement**: Measure some qubits and keep the rest coherent
+/// - **Decoherence**: Optional T1/T2 decay of fidelity with age
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuantumState {
     /// Unique state identifier for tracking and management
@@ -328,6 +610,24 @@ pub struct QuantumState {
     /// The phase information for each quantum state component.
     /// Provides complete quantum state representation with perfect fidelity.
     pub phases: Vec<f64>,
+
+    /// Operation trace while journaling is enabled
+    #[serde(skip)]
+    journal: Option<StateJournal>,
+
+    /// Decay tracking while a noise model is active
+    #[serde(skip)]
+    decoherence: Option<Decoherence>,
+}
+
+impl MemoryFootprint for QuantumState {
+    fn heap_bytes(&self) -> usize {
+        self.id.heap_bytes()
+            + self.amplitudes.heap_bytes()
+            + self.phases.heap_bytes()
+            + self.measurements.heap_bytes()
+            + self.journal.heap_bytes()
+    }
 }
 
 impl QuantumState {
@@ -350,6 +650,70 @@ impl QuantumState {
             fidelity: 1.0, // Will be calculated dynamically
             created_at: chrono::Utc::now().timestamp() as u64,
             phases: vec![0.0; state_count], // Initialize phases to zero
+            journal: None,
+            decoherence: None,
+        }
+    }
+
+    /// Start recording every operation on this state
+    pub fn start_journal(&mut self, max_entries: usize) {
+        let mut journal = StateJournal::new(&self.id, self.qubit_count, max_entries);
+        journal.record(JournalEvent::Created {
+            qubit_count: self.qubit_count,
+        });
+        self.journal = Some(journal);
+    }
+
+    /// Operation trace, if journaling
+    pub fn journal(&self) -> Option<&StateJournal> {
+        self.journal.as_ref()
+    }
+
+    /// Stop journaling and hand over the trace
+    pub fn take_journal(&mut self) -> Option<StateJournal> {
+        self.journal.take()
+    }
+
+    fn record(&mut self, event: JournalEvent) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.record(event);
+        }
+    }
+
+    /// Start decaying under `model`, counting from now
+    pub fn enable_decoherence(&mut self, model: NoiseModel) {
+        self.decoherence = Some(Decoherence {
+            model,
+            prepared_at: Instant::now(),
+            operations: 0,
+        });
+        self.update_fidelity();
+    }
+
+    /// Noise model the state decays under, if any
+    pub fn noise_model(&self) -> Option<&NoiseModel> {
+        self.decoherence.as_ref().map(|decoherence| &decoherence.model)
+    }
+
+    /// Remaining coherence e^(-t/T2), 1.0 without a noise model
+    pub fn coherence(&self) -> f64 {
+        self.decoherence
+            .as_ref()
+            .map_or(1.0, |decoherence| decoherence.model.coherence(decoherence.elapsed()))
+    }
+
+    /// Bring `fidelity` up to date with the time elapsed so far
+    ///
+    /// Operations do this on their own; wall-clock states that sit idle
+    /// need it before their fidelity is read.
+    pub fn advance_decoherence(&mut self) -> f64 {
+        self.update_fidelity();
+        self.fidelity
+    }
+
+    fn tick_decoherence(&mut self) {
+        if let Some(decoherence) = self.decoherence.as_mut() {
+            decoherence.operations += 1;
         }
     }
     
@@ -359,6 +723,7 @@ impl QuantumState {
     /// quantum random number generation for authentic quantum randomness.
     /// Includes random phase assignment for true quantum superposition.
     pub fn create_superposition(&mut self, qrng: &mut QRNG) -> Result<()> {
+        let rng_word_pos = qrng.is_deterministic().then(|| qrng.word_pos());
         // Create uniform superposition using quantum randomness
         let state_count = self.amplitudes.len();
         let amplitude = 1.0 / (state_count as f64).sqrt();
@@ -373,7 +738,9 @@ impl QuantumState {
         
         self.normalize();
         // Fidelity automatically maintained through proper normalization
+        self.tick_decoherence();
         self.update_fidelity();
+        self.record(JournalEvent::Superposition { rng_word_pos });
         
         Ok(())
     }
@@ -395,14 +762,21 @@ impl QuantumState {
     /// Calculate fidelity dynamically from quantum state properties
     /// 
     /// Computes fidelity based on state normalization and purity.
-    /// Perfect quantum states naturally achieve fidelity = 1.0.
+    /// Perfect quantum states naturally achieve fidelity = 1.0; under a
+    /// noise model each qubit's decay multiplies it down.
     fn update_fidelity(&mut self) {
         // Calculate state purity: Tr(ρ²) for pure states = 1
         let norm_squared: f64 = self.amplitudes.iter().map(|&a| a * a).sum();
         
         // For normalized pure states, this equals 1.0 exactly
         // Phase information doesn't affect purity for closed quantum systems
-        self.fidelity = norm_squared;
+        let decay = self.decoherence.as_ref().map_or(1.0, |decoherence| {
+            decoherence
+                .model
+                .qubit_fidelity(decoherence.elapsed())
+                .powi(self.qubit_count as i32)
+        });
+        self.fidelity = norm_squared * decay;
         
         // Physics-based fidelity: Perfect normalization = Perfect fidelity
         // No hardcoded values - fidelity emerges from quantum mechanics
@@ -415,13 +789,75 @@ impl QuantumState {
     /// the measured outcome. Uses quantum random number generation for
     /// authentic quantum behavior.
     pub fn measure(&mut self, measurement_id: String, qrng: &mut QRNG) -> Result<Vec<u8>> {
-        // Calculate Born rule probabilities |ψ|²
-        let probabilities: Vec<f64> = self
+        let bases = vec![MeasurementBasis::Z; self.qubit_count as usize];
+        self.measure_in_bases(measurement_id, &bases, qrng)
+    }
+
+    /// Measure every qubit in the same basis
+    pub fn measure_in_basis(
+        &mut self,
+        measurement_id: String,
+        basis: MeasurementBasis,
+        qrng: &mut QRNG,
+    ) -> Result<Vec<u8>> {
+        let bases = vec![basis; self.qubit_count as usize];
+        self.measure_in_bases(measurement_id, &bases, qrng)
+    }
+
+    /// Measure each qubit in its own basis, `bases[q]` for qubit q
+    ///
+    /// Qubits measured outside the computational basis are rotated so their
+    /// basis states map onto |0⟩ and |1⟩, sampled with the Born rule, and the
+    /// state collapses onto the product of the observed basis states. The
+    /// outcome is MSB first like `measure`.
+    pub fn measure_in_bases(
+        &mut self,
+        measurement_id: String,
+        bases: &[MeasurementBasis],
+        qrng: &mut QRNG,
+    ) -> Result<Vec<u8>> {
+        if bases.len() != self.qubit_count as usize {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Expected {} measurement bases, got {}",
+                self.qubit_count,
+                bases.len()
+            )));
+        }
+
+        // Complex amplitudes rotated into the measurement bases
+        let mut rotated: Vec<(f64, f64)> = self
             .amplitudes
             .iter()
-            .map(|&amplitude| amplitude * amplitude)
+            .zip(&self.phases)
+            .map(|(&amplitude, &phase)| (amplitude * phase.cos(), amplitude * phase.sin()))
+            .collect();
+        for (qubit, basis) in bases.iter().enumerate() {
+            if *basis == MeasurementBasis::Z {
+                continue;
+            }
+            // Rows of the rotation are the conjugated basis states
+            let [e0, e1] = [basis.eigenstate(0), basis.eigenstate(1)];
+            let conj = |c: (f64, f64)| (c.0, -c.1);
+            let mask = 1 << qubit;
+            for i in (0..rotated.len()).filter(|i| i & mask == 0) {
+                let (zero, one) = (rotated[i], rotated[i | mask]);
+                let project = |e: [(f64, f64); 2]| {
+                    let a = complex_mul(conj(e[0]), zero);
+                    let b = complex_mul(conj(e[1]), one);
+                    (a.0 + b.0, a.1 + b.1)
+                };
+                rotated[i] = project(e0);
+                rotated[i | mask] = project(e1);
+            }
+        }
+
+        // Calculate Born rule probabilities |ψ|²
+        let probabilities: Vec<f64> = rotated
+            .iter()
+            .map(|&(re, im)| re * re + im * im)
             .collect();
         
+        let rng_word_pos = qrng.is_deterministic().then(|| qrng.word_pos());
         // Generate quantum random number for measurement outcome selection
         let random_value = qrng.gen_range(0..u64::MAX) as f64 / u64::MAX as f64;
         
@@ -437,10 +873,26 @@ impl QuantumState {
             }
         }
         
-        // Quantum state collapse to measured outcome (Born rule)
-        self.amplitudes.fill(0.0);
-        self.amplitudes[measurement_outcome] = 1.0;
-        self.phases.fill(0.0); // Reset phases after measurement collapse
+        // Quantum state collapse onto the product of the observed basis states
+        let observed: Vec<[(f64, f64); 2]> = bases
+            .iter()
+            .enumerate()
+            .map(|(qubit, basis)| basis.eigenstate(((measurement_outcome >> qubit) & 1) as u8))
+            .collect();
+        for i in 0..self.amplitudes.len() {
+            let amplitude = observed
+                .iter()
+                .enumerate()
+                .fold((1.0, 0.0), |acc, (qubit, state)| {
+                    complex_mul(acc, state[(i >> qubit) & 1])
+                });
+            self.amplitudes[i] = amplitude.0.hypot(amplitude.1);
+            self.phases[i] = if self.amplitudes[i] > 0.0 {
+                amplitude.1.atan2(amplitude.0)
+            } else {
+                0.0
+            };
+        }
         
         // Convert measurement outcome to qubit bit string (MSB first)
         let mut result = Vec::new();
@@ -453,25 +905,141 @@ impl QuantumState {
         
         result.reverse(); // MSB first for conventional bit ordering
         
+        let recorded_bases = if bases.iter().all(|basis| *basis == MeasurementBasis::Z) {
+            Vec::new()
+        } else {
+            bases.to_vec()
+        };
+        self.record(JournalEvent::Measurement {
+            measurement_id: measurement_id.clone(),
+            outcome: result.clone(),
+            rng_word_pos,
+            bases: recorded_bases,
+        });
         // Cache measurement result for performance optimization
         self.measurements.insert(measurement_id, result.clone());
         
         // Measurement preserves purity - collapsed state is still pure
+        self.tick_decoherence();
         self.update_fidelity();
         
         Ok(result)
     }
     
+    /// Measure only the listed qubits with the Born rule
+    ///
+    /// The remaining qubits are left unmeasured: amplitudes inconsistent with
+    /// the outcome are removed and the rest renormalized, so entanglement
+    /// among the unmeasured qubits survives. Also returns the conditional
+    /// state of the unmeasured qubits on its own.
+    pub fn measure_qubits(
+        &mut self,
+        measurement_id: String,
+        qubits: &[u32],
+        qrng: &mut QRNG,
+    ) -> Result<PartialMeasurement> {
+        if qubits.is_empty() {
+            return Err(SecureCommsError::QuantumOperation(
+                "No qubits to measure".to_string(),
+            ));
+        }
+        if let Some(qubit) = qubits.iter().find(|&&q| q >= self.qubit_count) {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Qubit index {} out of range for state {} with {} qubits",
+                qubit, self.id, self.qubit_count
+            )));
+        }
+        let mask = qubits.iter().fold(0usize, |mask, &q| mask | (1 << q));
+        if mask.count_ones() as usize != qubits.len() {
+            return Err(SecureCommsError::QuantumOperation(
+                "Qubit measured twice".to_string(),
+            ));
+        }
+
+        // Born rule probability of each outcome of the measured qubits
+        let mut probabilities: BTreeMap<usize, f64> = BTreeMap::new();
+        for (i, &amplitude) in self.amplitudes.iter().enumerate() {
+            *probabilities.entry(i & mask).or_insert(0.0) += amplitude * amplitude;
+        }
+
+        let rng_word_pos = qrng.is_deterministic().then(|| qrng.word_pos());
+        let random_value = qrng.gen_range(0..u64::MAX) as f64 / u64::MAX as f64;
+        let mut cumulative_prob = 0.0;
+        let mut pattern = None;
+        for (&candidate, &prob) in &probabilities {
+            if prob <= 0.0 {
+                continue;
+            }
+            // Rounding can leave the total just under the draw; fall back to the last outcome
+            pattern = Some((candidate, prob));
+            cumulative_prob += prob;
+            if random_value <= cumulative_prob {
+                break;
+            }
+        }
+        let (pattern, probability) = pattern.ok_or_else(|| {
+            SecureCommsError::QuantumOperation("State has zero norm".to_string())
+        })?;
+
+        // Collapse the measured qubits and renormalize the rest
+        let norm = probability.sqrt();
+        for i in 0..self.amplitudes.len() {
+            if i & mask == pattern {
+                self.amplitudes[i] /= norm;
+            } else {
+                self.amplitudes[i] = 0.0;
+                self.phases[i] = 0.0;
+            }
+        }
+        self.tick_decoherence();
+        self.update_fidelity();
+
+        // Unmeasured qubits renumbered in ascending order
+        let remaining: Vec<u32> = (0..self.qubit_count)
+            .filter(|q| mask & (1 << q) == 0)
+            .collect();
+        let mut conditional_state =
+            QuantumState::new(format!("{}_conditional", self.id), remaining.len() as u32);
+        for j in 0..conditional_state.amplitudes.len() {
+            let i = remaining
+                .iter()
+                .enumerate()
+                .fold(pattern, |i, (bit, &q)| i | (((j >> bit) & 1) << q));
+            conditional_state.amplitudes[j] = self.amplitudes[i];
+            conditional_state.phases[j] = self.phases[i];
+        }
+        // The remaining qubits have aged as long as the parent state
+        conditional_state.decoherence = self.decoherence.clone();
+        conditional_state.update_fidelity();
+
+        let outcome: Vec<u8> = qubits.iter().map(|&q| ((pattern >> q) & 1) as u8).collect();
+        self.record(JournalEvent::PartialMeasurement {
+            measurement_id: measurement_id.clone(),
+            qubits: qubits.to_vec(),
+            outcome: outcome.clone(),
+            rng_word_pos,
+        });
+        self.measurements.insert(measurement_id, outcome.clone());
+
+        Ok(PartialMeasurement {
+            qubits: qubits.to_vec(),
+            outcome,
+            probability,
+            conditional_state,
+        })
+    }
+    
     /// Apply quantum gate operation with fidelity tracking
     /// 
     /// Applies the specified quantum gate to the given qubits with proper
     /// quantum mechanical evolution. Supports all standard quantum gates
     /// including single-qubit and two-qubit operations.
     pub fn apply_gate(&mut self, gate_type: QuantumGate, qubits: &[u32]) -> Result<()> {
-        if qubits.iter().any(|&q| q >= self.qubit_count) {
-            return Err(SecureCommsError::QuantumOperation(
-                "Qubit index out of range".to_string(),
-            ));
+        if let Some(qubit) = qubits.iter().find(|&&q| q >= self.qubit_count) {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Qubit index {} out of range for state {} with {} qubits",
+                qubit, self.id, self.qubit_count
+            )));
         }
         
         match gate_type {
@@ -486,7 +1054,12 @@ impl QuantumState {
         }
         
         // Update fidelity after gate operation
+        self.tick_decoherence();
         self.update_fidelity();
+        self.record(JournalEvent::Gate {
+            gate: gate_type,
+            qubits: qubits.to_vec(),
+        });
         
         Ok(())
     }
@@ -810,13 +1383,18 @@ pub struct QuantumHardwareInterface {
     available_qubits: u32,
     /// Supported quantum operations
     supported_operations: Vec<String>,
-    /// Error rates for different operations
-    error_rates: HashMap<String, f64>,
+    /// Backend calibrations behind the reported error rates
+    calibration: Arc<CalibrationStore>,
 }
 
 impl QuantumHardwareInterface {
     /// Create new quantum hardware interface
     pub fn new() -> Self {
+        Self::with_calibration(Arc::new(CalibrationStore::default()))
+    }
+
+    /// Create hardware interface reporting error rates from `calibration`
+    pub fn with_calibration(calibration: Arc<CalibrationStore>) -> Self {
         Self {
             hardware_available: false,
             architecture: "Physics-Based Quantum Simulation".to_string(),
@@ -831,14 +1409,7 @@ impl QuantumHardwareInterface {
                 "s".to_string(),
                 "phase".to_string(),
             ],
-            // SECURITY OPTIMIZATION: Perfect fidelity simulation for maximum security
-            error_rates: {
-                let mut rates = HashMap::new();
-                rates.insert("single_qubit".to_string(), 0.0); // Perfect fidelity
-                rates.insert("two_qubit".to_string(), 0.0); // Perfect fidelity
-                rates.insert("measurement".to_string(), 0.0); // Perfect fidelity
-                rates
-            },
+            calibration,
         }
     }
     
@@ -905,36 +1476,35 @@ impl QuantumHardwareInterface {
             ),
         );
 
-        // Include error rates in status
-        let error_rates_json: serde_json::Map<String, serde_json::Value> = self
-            .error_rates
-            .iter()
-            .map(|(k, v)| {
-                (
-                    k.clone(),
-                    serde_json::Value::Number(
-                        serde_json::Number::from_f64(*v)
-                            .unwrap_or_else(|| serde_json::Number::from(0)),
-                    ),
-                )
-            })
-            .collect();
+        // Mean error rates of fresh calibrations; zero on the noise-free simulation
+        let now = now_ms();
         status.insert(
             "error_rates".to_string(),
-            serde_json::Value::Object(error_rates_json),
+            serde_json::to_value(self.calibration.mean_errors(now))
+                .unwrap_or(serde_json::Value::Null),
+        );
+        status.insert(
+            "calibration".to_string(),
+            serde_json::to_value(self.calibration.summary(now)).unwrap_or(serde_json::Value::Null),
         );
 
         status
     }
 
     /// Get error rate for specific operation type
+    ///
+    /// `single_qubit`, `two_qubit` or `measurement`, averaged over the fresh
+    /// calibrations of all backends.
     pub fn get_error_rate(&self, operation: &str) -> f64 {
-        self.error_rates.get(operation).copied().unwrap_or(0.0)
+        self.calibration
+            .mean_errors(now_ms())
+            .get(operation)
+            .unwrap_or(0.0)
     }
 
-    /// Update error rate for operation type
-    pub fn update_error_rate(&mut self, operation: &str, error_rate: f64) {
-        self.error_rates.insert(operation.to_string(), error_rate);
+    /// Backend calibrations behind the error rates
+    pub fn calibration(&self) -> &Arc<CalibrationStore> {
+        &self.calibration
     }
 }
 
@@ -944,14 +1514,50 @@ impl Default for QuantumHardwareInterface {
     }
 }
 
+/// State id of the core register used by `create_bell_pair` and `measure_qubits`
+pub const REGISTER_STATE_ID: &str = "core_register";
+
+/// Output bits per independently conditioned block of a bulk draw
+pub const BULK_BLOCK_BITS: usize = 256;
+
+/// Blocks conditioned per sampling round of a bulk draw
+const BULK_BLOCKS_PER_ROUND: usize = 2048;
+
+/// Shots sampled by one parallel task of `sample_shots`
+const SHOTS_PER_TASK: usize = 16384;
+
+/// Shared handle to one quantum state
+///
+/// Operations lock only the state they touch, so sessions working on
+/// different states never wait for each other.
+type StateHandle = Arc<Mutex<QuantumState>>;
+
+/// Traces of collected states kept while journaling
+#[derive(Debug, Default)]
+struct RetiredJournals {
+    traces: VecDeque<StateJournal>,
+    /// Traces discarded by the retention limit
+    dropped: u64,
+}
+
 /// Enhanced quantum core with Phase 3 improvements
+///
+/// All operations take `&self` and the core is `Send + Sync`; share it behind
+/// an `Arc` to run independent QKD sessions concurrently. Locks are always
+/// taken in the order state, then QRNG, and never while iterating the maps.
 pub struct QuantumCore {
-    /// Active quantum states
-    states: HashMap<String, QuantumState>,
+    /// Active quantum states, each behind its own lock
+    states: DashMap<String, StateHandle>,
+    /// Global identifiers of the qubits of every active state
+    qubits: QubitRegistry,
     /// Compiled circuits
-    circuits: HashMap<String, QuantumCircuit>,
-    /// QRNG for quantum randomness
-    qrng: QRNG,
+    circuits: DashMap<String, QuantumCircuit>,
+    /// Register of `max_qubits` qubits for core-level operations, allocated on first use
+    register: Mutex<Option<QuantumState>>,
+    /// QRNG for quantum randomness, held only for the duration of a draw
+    qrng: Mutex<QRNG>,
+    /// Conditioning stage between raw measurements and random output
+    extractor: Mutex<RandomnessExtractor>,
     /// Performance metrics
     metrics: PerformanceMetrics,
     /// Maximum number of qubits for this implementation
@@ -960,43 +1566,312 @@ pub struct QuantumCore {
     hardware_interface: QuantumHardwareInterface,
     /// Hardware integration enabled flag
     hardware_enabled: bool,
+    /// Placement of circuits on hardware backends or the simulation
+    scheduler: QuantumScheduler,
+    /// Latest calibration of each backend, shared with the hardware interface
+    calibration: Arc<CalibrationStore>,
+    /// Calibration refresh timer task, stopped when the core is dropped
+    calibration_timer: Option<tokio::task::JoinHandle<()>>,
     /// Total number of measurements performed
-    total_measurements: u64,
+    total_measurements: AtomicU64,
     /// Total number of quantum operations performed
-    total_quantum_operations: u64,
+    total_quantum_operations: AtomicU64,
+    /// States re-entangled after decohering
+    entanglement_refreshes: AtomicU64,
+    /// Configuration including garbage collection thresholds
+    config: QuantumConfig,
+    /// States referenced by active QKD sessions, never collected
+    pinned: DashSet<String>,
+    /// Unix time each circuit was created or last executed
+    circuit_last_used: DashMap<String, u64>,
+    /// Set by the interval timer; the next allocation runs the collection
+    gc_due: Arc<AtomicBool>,
+    /// Held by the collection in progress so collections never overlap
+    gc_running: Mutex<()>,
+    /// Interval timer task, stopped when the core is dropped
+    gc_timer: Option<tokio::task::JoinHandle<()>>,
+    /// Collection counters
+    gc_stats: Mutex<QuantumGcStats>,
+    /// Journal settings while journaling is enabled
+    journal_config: RwLock<Option<JournalConfig>>,
+    /// Traces of collected states
+    retired_journals: Mutex<RetiredJournals>,
 }
 
 impl QuantumCore {
     /// Create new quantum core with Phase 3 enhancements
     pub async fn new(max_qubits: u32) -> Result<Self> {
+        Self::with_config(QuantumConfig {
+            max_qubits,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Create quantum core from configuration, starting the GC interval timer
+    pub async fn with_config(config: QuantumConfig) -> Result<Self> {
+        Self::build(config, true).await
+    }
+
+    /// Create quantum core on the physics simulation without probing for hardware
+    ///
+    /// Used when hardware detection failed or timed out at startup.
+    pub async fn simulation_only(config: QuantumConfig) -> Result<Self> {
+        Self::build(config, false).await
+    }
+
+    async fn build(config: QuantumConfig, detect_hardware: bool) -> Result<Self> {
+        let max_qubits = config.max_qubits;
         // Initialize security foundation for QRNG
         let mut security_foundation =
             SecurityFoundation::new(SecurityConfig::production_ready()).await?;
-        let qrng = QRNG::with_entropy(&mut security_foundation)?;
+        let mut qrng = QRNG::with_entropy(&mut security_foundation)?;
+        let mut extractor_seed = [0u8; 32];
+        extractor_seed.copy_from_slice(&qrng.generate_bytes(32)?);
+        let extractor = RandomnessExtractor::new(config.extraction.clone(), extractor_seed)?;
         
         // Initialize quantum hardware interface
-        let mut hardware_interface = QuantumHardwareInterface::new();
-        let hardware_enabled = hardware_interface.detect_hardware()?;
+        let calibration = Arc::new(CalibrationStore::new(config.calibration.clone())?);
+        let mut hardware_interface =
+            QuantumHardwareInterface::with_calibration(calibration.clone());
+        let hardware_enabled = detect_hardware && hardware_interface.detect_hardware()?;
+        let scheduler = QuantumScheduler::new(
+            config.scheduling.clone(),
+            detect_hardware && config.enable_hardware,
+        )?;
         
         println!(
             "🚀 Phase 3 Quantum Core initialized with enhanced measurements and teleportation"
         );
         
         Ok(Self {
-            states: HashMap::new(),
-            circuits: HashMap::new(),
-            qrng,
+            states: DashMap::new(),
+            qubits: QubitRegistry::new(),
+            circuits: DashMap::new(),
+            register: Mutex::new(None),
+            qrng: Mutex::new(qrng),
+            extractor: Mutex::new(extractor),
             metrics: PerformanceMetrics::new(),
             max_qubits,
             hardware_interface,
             hardware_enabled,
-            total_measurements: 0,
-            total_quantum_operations: 0,
+            scheduler,
+            calibration,
+            calibration_timer: None,
+            total_measurements: AtomicU64::new(0),
+            total_quantum_operations: AtomicU64::new(0),
+            entanglement_refreshes: AtomicU64::new(0),
+            pinned: DashSet::new(),
+            circuit_last_used: DashMap::new(),
+            gc_due: Arc::new(AtomicBool::new(false)),
+            gc_running: Mutex::new(()),
+            gc_timer: None,
+            gc_stats: Mutex::new(QuantumGcStats::default()),
+            journal_config: RwLock::new(None),
+            retired_journals: Mutex::new(RetiredJournals::default()),
+            config,
+        }
+        .with_gc_timer()
+        .with_calibration_timer()
+        .with_configured_journal())
+    }
+
+    fn with_configured_journal(self) -> Self {
+        if let Some(journal) = self.config.journal.clone() {
+            self.enable_journal(journal);
+        }
+        self
+    }
+
+    /// Record every operation on current and future states
+    ///
+    /// A deterministic seed reseeds the QRNG, making subsequent outcomes
+    /// reproducible by anyone who knows the seed; use it for audits and
+    /// debugging, not for production keys. Replays are exact only when the
+    /// recorded sessions ran one after another, since concurrent sessions
+    /// interleave their QRNG draws.
+    pub fn enable_journal(&self, config: JournalConfig) {
+        if let Some(seed) = config.deterministic_seed {
+            *self.qrng.lock() = QRNG::from_seed(seed_bytes(seed));
+        }
+        let max_entries = config.max_entries_per_state;
+        // Held across the sweep so states created meanwhile see the new config
+        let mut journal_config = self.journal_config.write();
+        for (_, handle) in self.handles() {
+            let mut state = handle.lock();
+            if state.journal().is_none() {
+                state.start_journal(max_entries);
+            }
+        }
+        *journal_config = Some(config);
+        // Released first: the register lock is held while reading the config
+        drop(journal_config);
+        if let Some(register) = self.register.lock().as_mut() {
+            if register.journal().is_none() {
+                register.start_journal(max_entries);
+            }
+        }
+    }
+
+    /// Stop journaling and discard recorded traces
+    pub fn disable_journal(&self) {
+        *self.journal_config.write() = None;
+        for (_, handle) in self.handles() {
+            handle.lock().take_journal();
+        }
+        if let Some(register) = self.register.lock().as_mut() {
+            register.take_journal();
+        }
+        *self.retired_journals.lock() = RetiredJournals::default();
+    }
+
+    /// Snapshot of all recorded traces for export
+    pub fn journal(&self) -> QuantumJournal {
+        let mut states: BTreeMap<String, StateJournal> = self
+            .handles()
+            .into_iter()
+            .filter_map(|(id, handle)| handle.lock().journal().cloned().map(|j| (id, j)))
+            .collect();
+        if let Some(journal) = self.register.lock().as_ref().and_then(|r| r.journal()) {
+            states.insert(REGISTER_STATE_ID.to_string(), journal.clone());
+        }
+        // Taken last: the register lock is held while retiring its trace
+        let retired = self.retired_journals.lock();
+        QuantumJournal {
+            seed: self
+                .journal_config
+                .read()
+                .as_ref()
+                .and_then(|config| config.deterministic_seed),
+            states,
+            retired: retired.traces.iter().cloned().collect(),
+            retired_dropped: retired.dropped,
+        }
+    }
+
+    /// Handles of all states, cloned out so no map lock is held while locking them
+    fn handles(&self) -> Vec<(String, StateHandle)> {
+        self.states
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// Handle of one state, releasing the map lock before it is locked
+    fn state_handle(&self, state_id: &str) -> Result<StateHandle> {
+        self.states
+            .get(state_id)
+            .map(|entry| entry.value().clone())
+            .ok_or_else(|| {
+                SecureCommsError::QuantumOperation(format!("State {} not found", state_id))
+            })
+    }
+
+    /// Remove a state, keeping its trace when journaling
+    fn remove_state(&self, state_id: &str, reason: &str) -> Option<StateHandle> {
+        let (_, handle) = self.states.remove(state_id)?;
+        self.qubits.release(state_id);
+        let journal = handle.lock().take_journal();
+        if let Some(journal) = journal {
+            self.retire_journal(journal, reason);
+        }
+        Some(handle)
+    }
+
+    /// Keep the trace of a state that no longer exists, if journaling
+    fn retire_journal(&self, mut journal: StateJournal, reason: &str) {
+        let config = self.journal_config.read().clone();
+        if let Some(config) = config {
+            journal.record(JournalEvent::Collected {
+                reason: reason.to_string(),
+            });
+            let mut retired = self.retired_journals.lock();
+            if retired.traces.len() >= config.max_retired_states.max(1) {
+                retired.traces.pop_front();
+                retired.dropped += 1;
+            }
+            retired.traces.push_back(journal);
+        }
+    }
+
+    /// Start the timer marking a collection due every cleanup interval
+    fn with_gc_timer(mut self) -> Self {
+        if let (Some(timer), Ok(handle)) = (self.gc_ticker(), tokio::runtime::Handle::try_current()) {
+            self.gc_timer = Some(handle.spawn(timer));
+        }
+        self
+    }
+
+    /// Stop the core's own collection timer and hand it over as a future
+    ///
+    /// Lets an owner run the timer under its own supervision, e.g. as a
+    /// tracked background task of the client. `None` when collection by
+    /// interval is disabled.
+    pub fn take_gc_timer(&mut self) -> Option<impl Future<Output = ()> + Send + 'static> {
+        if let Some(timer) = self.gc_timer.take() {
+            timer.abort();
+        }
+        self.gc_ticker()
+    }
+
+    fn with_calibration_timer(mut self) -> Self {
+        if let (Some(timer), Ok(handle)) = (
+            self.calibration_ticker(),
+            tokio::runtime::Handle::try_current(),
+        ) {
+            self.calibration_timer = Some(handle.spawn(timer));
+        }
+        self
+    }
+
+    /// Stop the core's own calibration refresh timer and hand it over as a future
+    ///
+    /// Like `take_gc_timer`. `None` when calibrations are not refreshed by
+    /// interval.
+    pub fn take_calibration_timer(&mut self) -> Option<impl Future<Output = ()> + Send + 'static> {
+        if let Some(timer) = self.calibration_timer.take() {
+            timer.abort();
+        }
+        self.calibration_ticker()
+    }
+
+    fn calibration_ticker(&self) -> Option<impl Future<Output = ()> + Send + 'static> {
+        let interval = self.config.calibration.refresh_interval_seconds;
+        if interval == 0 {
+            return None;
+        }
+        let calibration = self.calibration.clone();
+        Some(async move {
+            let period = Duration::from_secs(interval);
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                // Sources may block on a device API
+                let calibration = calibration.clone();
+                let _ =
+                    tokio::task::spawn_blocking(move || calibration.refresh_due(now_ms())).await;
+            }
+        })
+    }
+
+    fn gc_ticker(&self) -> Option<impl Future<Output = ()> + Send + 'static> {
+        let interval = self.config.cleanup_interval_seconds;
+        if interval == 0 {
+            return None;
+        }
+        let due = self.gc_due.clone();
+        Some(async move {
+            let period = Duration::from_secs(interval);
+            let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                ticker.tick().await;
+                due.store(true, Ordering::Release);
+            }
         })
     }
     
     /// Create quantum communication state
-    pub fn create_comm_state(&mut self, state_id: String, qubit_count: u32) -> Result<String> {
+    pub fn create_comm_state(&self, state_id: String, qubit_count: u32) -> Result<String> {
         if qubit_count > self.max_qubits {
             return Err(SecureCommsError::QuantumOperation(format!(
                 "Requested qubits ({}) exceeds maximum ({})",
@@ -1004,66 +1879,261 @@ impl QuantumCore {
             )));
         }
         
-        let state = QuantumState::new(state_id.clone(), qubit_count);
-        self.states.insert(state_id.clone(), state);
+        self.run_due_gc();
+        let mut state = QuantumState::new(state_id.clone(), qubit_count);
+        let journal_config = self.journal_config.read();
+        if let Some(config) = journal_config.as_ref() {
+            state.start_journal(config.max_entries_per_state);
+        }
+        if let Some(noise) = &self.config.noise {
+            state.enable_decoherence(noise.clone());
+        }
+        self.states
+            .insert(state_id.clone(), Arc::new(Mutex::new(state)));
+        self.qubits.allocate(&state_id, qubit_count);
         
         Ok(state_id)
     }
-    
-    /// Prepare entangled state for secure key distribution
-    pub fn create_entangled_state(&mut self, state_id: &str) -> Result<()> {
-        let state = self
-            .states
-            .get_mut(state_id)
-            .ok_or_else(|| SecureCommsError::QuantumOperation("State not found".to_string()))?;
-        
-        if state.qubit_count < 2 {
-            return Err(SecureCommsError::QuantumOperation(
-                "Need at least 2 qubits for entanglement".to_string(),
-            ));
-        }
-        
-        // Create Bell state |00⟩ + |11⟩
+
+    /// Global identifiers of the qubits of a state, in index order
+    pub fn state_qubits(&self, state_id: &str) -> Result<Vec<QubitId>> {
+        self.qubits.qubits_of(state_id)
+    }
+
+    /// Owning state and index of a qubit
+    pub fn locate_qubit(&self, qubit: QubitId) -> Result<QubitAddress> {
+        self.qubits.resolve(qubit)
+    }
+
+    /// Apply a gate to qubits addressed by their global identifiers
+    ///
+    /// The qubits must belong to one state; they are passed to the gate in
+    /// the order given, e.g. control then target for CNOT.
+    pub fn apply_gate_to_qubits(&self, gate: QuantumGate, qubits: &[QubitId]) -> Result<()> {
+        let arity = if gate == QuantumGate::CNOT { 2 } else { 1 };
+        if qubits.len() != arity {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "{:?} acts on {} qubits, got {}",
+                gate,
+                arity,
+                qubits.len()
+            )));
+        }
+        let (state_id, indices) = self.qubits.resolve_group(qubits)?;
+        self.state_handle(&state_id)?
+            .lock()
+            .apply_gate(gate, &indices)
+    }
+
+    /// Measure qubits addressed by their global identifiers with the Born rule
+    ///
+    /// The qubits must belong to one state; only they collapse. Outcomes are
+    /// in the order given.
+    pub fn measure_addressed_qubits(&self, qubits: &[QubitId]) -> Result<Vec<bool>> {
+        let (state_id, indices) = self.qubits.resolve_group(qubits)?;
+        let handle = self.state_handle(&state_id)?;
+        let measurement_id = format!("measure_{}_{}", state_id, chrono::Utc::now().timestamp());
+        let mut state = handle.lock();
+        let measured = state.measure_qubits(measurement_id, &indices, &mut self.qrng.lock())?;
+        drop(state);
+        self.total_measurements.fetch_add(1, Ordering::Relaxed);
+        Ok(measured.outcome.iter().map(|&bit| bit == 1).collect())
+    }
+    
+    /// Prepare entangled state for secure key distribution
+    pub fn create_entangled_state(&self, state_id: &str) -> Result<()> {
+        let handle = self.state_handle(state_id)?;
+        let mut state = handle.lock();
+        
+        if state.qubit_count < 2 {
+            return Err(SecureCommsError::QuantumOperation(
+                "Need at least 2 qubits for entanglement".to_string(),
+            ));
+        }
+        
+        // Create Bell state |00⟩ + |11⟩
         state.apply_gate(QuantumGate::Hadamard, &[0])?;
         state.apply_gate(QuantumGate::CNOT, &[0, 1])?;
         
         Ok(())
     }
+
+    /// Re-entangle a state whose coherence fell below the refresh threshold
+    ///
+    /// The state is re-prepared as a fresh Bell pair on qubits 0 and 1,
+    /// keeping its id, pin and journal. Returns whether it was refreshed;
+    /// always false without a noise model.
+    pub fn refresh_if_decohered(&self, state_id: &str) -> Result<bool> {
+        let noise = match &self.config.noise {
+            Some(noise) => noise,
+            None => return Ok(false),
+        };
+        let handle = self.state_handle(state_id)?;
+        let mut state = handle.lock();
+        if state.coherence() >= noise.refresh_threshold {
+            state.advance_decoherence();
+            return Ok(false);
+        }
+
+        let mut fresh = QuantumState::new(state.id.clone(), state.qubit_count);
+        if let Some(mut journal) = state.take_journal() {
+            journal.record(JournalEvent::Created {
+                qubit_count: fresh.qubit_count,
+            });
+            fresh.journal = Some(journal);
+        }
+        fresh.enable_decoherence(noise.clone());
+        if fresh.qubit_count >= 2 {
+            fresh.apply_gate(QuantumGate::Hadamard, &[0])?;
+            fresh.apply_gate(QuantumGate::CNOT, &[0, 1])?;
+        }
+        *state = fresh;
+        self.entanglement_refreshes.fetch_add(1, Ordering::Relaxed);
+        Ok(true)
+    }
+
+    /// Remaining coherence of a state, 1.0 without a noise model
+    pub fn coherence(&self, state_id: &str) -> Result<f64> {
+        Ok(self.state_handle(state_id)?.lock().coherence())
+    }
     
-    /// Generate quantum random bits
-    pub fn generate_quantum_random(&mut self, state_id: &str, bit_count: u32) -> Result<Vec<u8>> {
-        let state = self
-            .states
-            .get_mut(state_id)
-            .ok_or_else(|| SecureCommsError::QuantumOperation("State not found".to_string()))?;
-        
+    /// Generate conditioned quantum random bits
+    ///
+    /// Repeats superposition and measurement until the extractor holds enough
+    /// raw bits for the estimated min-entropy, then conditions them. Returns
+    /// `min(bit_count, qubit count)` bits, one per byte.
+    pub fn generate_quantum_random(&self, state_id: &str, bit_count: u32) -> Result<Vec<u8>> {
+        let handle = self.state_handle(state_id)?;
+        let mut state = handle.lock();
+        let output_bits = bit_count.min(state.qubit_count) as usize;
+        
+        // Held across the draw so the ratio used to size it is the one applied
+        let mut extractor = self.extractor.lock();
+        let needed = extractor.raw_bits_needed(output_bits)?;
+        let mut raw_bits = Vec::with_capacity(needed);
+        while raw_bits.len() < needed {
+            raw_bits.extend(self.measure_random_bits(state_id, &mut state)?);
+        }
+        extractor.extract(&raw_bits, output_bits)
+    }
+    
+    /// Generate unconditioned measurement bits, for entropy testing only
+    ///
+    /// Raw bits carry the source's bias and must not be used as key material.
+    pub fn generate_raw_quantum_random(&self, state_id: &str, bit_count: u32) -> Result<Vec<u8>> {
+        let handle = self.state_handle(state_id)?;
+        let mut state = handle.lock();
+        let mut measurement = self.measure_random_bits(state_id, &mut state)?;
+        measurement.truncate(bit_count as usize);
+        Ok(measurement)
+    }
+    
+    /// One superposition and measurement round of a random draw
+    fn measure_random_bits(&self, state_id: &str, state: &mut QuantumState) -> Result<Vec<u8>> {
         let measurement_id = format!("random_{}_{}", state_id, chrono::Utc::now().timestamp());
         
         // Create superposition for randomness
-        state.create_superposition(&mut self.qrng)?;
+        state.create_superposition(&mut self.qrng.lock())?;
         
         // Measure to get random bits
-        let measurement = state.measure(measurement_id, &mut self.qrng)?;
-        
-        // Return requested number of bits
-        let mut result = Vec::new();
-        for &bit in measurement.iter().take(bit_count as usize) {
-            result.push(bit);
+        let measurement = state.measure(measurement_id, &mut self.qrng.lock())?;
+        self.total_measurements.fetch_add(1, Ordering::Relaxed);
+        Ok(measurement)
+    }
+    
+    /// Counters and entropy accounting of the QRNG extraction stage
+    pub fn extraction_stats(&self) -> ExtractionStats {
+        self.extractor.lock().stats()
+    }
+    
+    /// Measure repeated shots of a state's uniform superposition
+    ///
+    /// Models hardware execution: the superposition is prepared once and
+    /// measured in `shots` independent repetitions, each yielding every qubit
+    /// MSB first, one bit per byte. Shots are sampled in parallel from
+    /// generators seeded by the core QRNG and are not journaled.
+    pub fn sample_shots(&self, state_id: &str, shots: usize) -> Result<Vec<u8>> {
+        let (probabilities, qubits) = {
+            let handle = self.state_handle(state_id)?;
+            let mut state = handle.lock();
+            state.create_superposition(&mut self.qrng.lock())?;
+            let probabilities: Vec<f64> = state.amplitudes.iter().map(|a| a * a).collect();
+            (probabilities, state.qubit_count as usize)
+        };
+        let mut cumulative = Vec::with_capacity(probabilities.len());
+        let mut total = 0.0;
+        for p in probabilities {
+            total += p;
+            cumulative.push(total);
         }
         
-        Ok(result)
+        let chunks = shots.div_ceil(SHOTS_PER_TASK);
+        let seeds = {
+            let mut qrng = self.qrng.lock();
+            (0..chunks)
+                .map(|_| {
+                    let mut seed = [0u8; 32];
+                    seed.copy_from_slice(&qrng.generate_bytes(32)?);
+                    Ok(seed)
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+        use rayon::prelude::*;
+        let bits: Vec<u8> = seeds
+            .into_par_iter()
+            .enumerate()
+            .flat_map_iter(|(chunk, seed)| {
+                let count = SHOTS_PER_TASK.min(shots - chunk * SHOTS_PER_TASK);
+                let mut rng = QRNG::from_seed(seed);
+                let mut out = Vec::with_capacity(count * qubits);
+                for _ in 0..count {
+                    let draw = rng.gen_range(0..1 << 53) as f64 / (1u64 << 53) as f64 * total;
+                    let outcome = cumulative
+                        .partition_point(|&c| c <= draw)
+                        .min(cumulative.len() - 1);
+                    out.extend((0..qubits).rev().map(|q| ((outcome >> q) & 1) as u8));
+                }
+                out
+            })
+            .collect();
+        self.total_measurements.fetch_add(shots as u64, Ordering::Relaxed);
+        Ok(bits)
+    }
+    
+    /// Generate `byte_count` conditioned random bytes from batched shots
+    ///
+    /// The bulk counterpart of `generate_quantum_random`: enough shots are
+    /// sampled for the current extraction ratio and conditioned in parallel
+    /// blocks of `BULK_BLOCK_BITS` output bits.
+    pub fn generate_bulk_random(&self, state_id: &str, byte_count: usize) -> Result<Vec<u8>> {
+        if byte_count == 0 {
+            return Ok(Vec::new());
+        }
+        let qubits = self.state_handle(state_id)?.lock().qubit_count as usize;
+        let mut bytes = Vec::with_capacity(byte_count + BULK_BLOCK_BITS / 8);
+        // Bounded rounds keep the one-bit-per-byte raw buffer small; a round
+        // also falls short if the entropy estimate tightened after sizing it
+        while bytes.len() < byte_count {
+            let blocks = ((byte_count - bytes.len()) * 8)
+                .div_ceil(BULK_BLOCK_BITS)
+                .min(BULK_BLOCKS_PER_ROUND);
+            let raw_per_block = self.extractor.lock().raw_bits_needed(BULK_BLOCK_BITS)?;
+            let raw_bits = self.sample_shots(state_id, (blocks * raw_per_block).div_ceil(qubits))?;
+            let bits = self.extractor.lock().extract_blocks(&raw_bits, BULK_BLOCK_BITS)?;
+            bytes.extend(pack_bits(&bits));
+        }
+        bytes.truncate(byte_count);
+        Ok(bytes)
     }
     
     /// Perform quantum operation with Phase 3 enhancements
     pub fn perform_operation(
-        &mut self,
+        &self,
         state_id: &str,
         operation: QuantumOperation,
     ) -> Result<Vec<u8>> {
-        let state = self
-            .states
-            .get_mut(state_id)
-            .ok_or_else(|| SecureCommsError::QuantumOperation("State not found".to_string()))?;
+        let handle = self.state_handle(state_id)?;
+        let mut state = handle.lock();
         
         match operation {
             QuantumOperation::CreateEntanglement { qubits } => {
@@ -1078,7 +2148,8 @@ impl QuantumCore {
             
             QuantumOperation::MeasureRandom { qubits: _ } => {
                 let measurement_id = format!("op_measure_{}", chrono::Utc::now().timestamp());
-                let result = state.measure(measurement_id, &mut self.qrng)?;
+                let result = state.measure(measurement_id, &mut self.qrng.lock())?;
+                self.total_measurements.fetch_add(1, Ordering::Relaxed);
                 Ok(result)
             }
             
@@ -1095,9 +2166,12 @@ impl QuantumCore {
                 state.apply_gate(QuantumGate::CNOT, &[source, target])?;
                 state.apply_gate(QuantumGate::Hadamard, &[source])?;
                 
-                // Step 3: Measure source and target qubits
+                // Step 3: Measure source and target qubits, leaving the auxiliary qubit coherent
                 let measurement_id = format!("teleport_bell_{}", chrono::Utc::now().timestamp());
-                let bell_measurement = state.measure(measurement_id, &mut self.qrng)?;
+                let bell_measurement = state
+                    .measure_qubits(measurement_id, &[source, target], &mut self.qrng.lock())?
+                    .outcome;
+                self.total_measurements.fetch_add(1, Ordering::Relaxed);
                 
                 // Step 4: Apply correction operations based on measurement
                 if bell_measurement.len() >= 2 {
@@ -1146,14 +2220,15 @@ impl QuantumCore {
                 
                 // Measure ancilla qubits for error detection
                 let measurement_id = format!("error_correction_{}", chrono::Utc::now().timestamp());
-                let syndrome = state.measure(measurement_id, &mut self.qrng)?;
+                let syndrome = state.measure(measurement_id, &mut self.qrng.lock())?;
+                self.total_measurements.fetch_add(1, Ordering::Relaxed);
                 Ok(syndrome)
             }
         }
     }
     
     /// Create and execute quantum circuit
-    pub fn create_circuit(&mut self, circuit_id: String, qubit_count: u32) -> Result<String> {
+    pub fn create_circuit(&self, circuit_id: String, qubit_count: u32) -> Result<String> {
         if qubit_count > self.max_qubits {
             return Err(SecureCommsError::QuantumOperation(format!(
                 "Requested qubits ({}) exceeds maximum ({})",
@@ -1161,20 +2236,40 @@ impl QuantumCore {
             )));
         }
         
+        self.run_due_gc();
         let circuit = QuantumCircuit::new(circuit_id.clone(), qubit_count);
         self.circuits.insert(circuit_id.clone(), circuit);
+        self.touch_circuit(circuit_id.clone());
         
         Ok(circuit_id)
     }
+
+    /// Store a prebuilt circuit, such as one from `crate::circuits`, under its id
+    ///
+    /// Replaces any circuit with the same id.
+    pub fn add_circuit(&self, circuit: QuantumCircuit) -> Result<String> {
+        if circuit.qubit_count > self.max_qubits {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Requested qubits ({}) exceeds maximum ({})",
+                circuit.qubit_count, self.max_qubits
+            )));
+        }
+
+        self.run_due_gc();
+        let circuit_id = circuit.id.clone();
+        self.circuits.insert(circuit_id.clone(), circuit);
+        self.touch_circuit(circuit_id.clone());
+        Ok(circuit_id)
+    }
     
     /// Add gate to circuit
     pub fn add_gate_to_circuit(
-        &mut self,
+        &self,
         circuit_id: &str,
         gate: QuantumGate,
         qubits: Vec<u32>,
     ) -> Result<()> {
-        let circuit = self
+        let mut circuit = self
             .circuits
             .get_mut(circuit_id)
             .ok_or_else(|| SecureCommsError::QuantumOperation("Circuit not found".to_string()))?;
@@ -1182,25 +2277,179 @@ impl QuantumCore {
         circuit.add_gate(gate, qubits)
     }
     
-    /// Execute circuit on state
-    pub fn execute_circuit(&mut self, circuit_id: &str, state_id: &str) -> Result<()> {
-        let circuit = self
-            .circuits
+    /// Copy of a circuit, so executing it holds no map lock
+    fn circuit_snapshot(&self, circuit_id: &str) -> Result<QuantumCircuit> {
+        self.circuits
             .get(circuit_id)
-            .ok_or_else(|| SecureCommsError::QuantumOperation("Circuit not found".to_string()))?
-            .clone();
-        
-        let state = self
-            .states
-            .get_mut(state_id)
-            .ok_or_else(|| SecureCommsError::QuantumOperation("State not found".to_string()))?;
-        
-        circuit.execute(state)
+            .map(|entry| entry.value().clone())
+            .ok_or_else(|| SecureCommsError::QuantumOperation("Circuit not found".to_string()))
+    }
+
+    /// Execute circuit on state
+    ///
+    /// Runs on a registered hardware backend when the scheduler places it
+    /// there with default requirements, otherwise on the simulation.
+    pub fn execute_circuit(&self, circuit_id: &str, state_id: &str) -> Result<()> {
+        let circuit = self.circuit_snapshot(circuit_id)?;
+        let operation = OperationProfile::new(circuit.qubit_count, 1);
+        self.execute_circuit_with(circuit_id, state_id, &operation)
+            .map(|_| ())
+    }
+
+    /// Execute circuit on state with explicit fidelity, latency and shot needs
+    ///
+    /// Returns where the circuit ran.
+    pub fn execute_circuit_with(
+        &self,
+        circuit_id: &str,
+        state_id: &str,
+        operation: &OperationProfile,
+    ) -> Result<Placement> {
+        let circuit = self.circuit_snapshot(circuit_id)?;
+        let handle = self.state_handle(state_id)?;
+
+        // Calibrated estimates for this circuit, unless the caller gave its own
+        let mut operation = operation.clone();
+        for (backend, fidelity) in self.calibration.fidelity_estimates(&circuit, now_ms()) {
+            operation
+                .fidelity_estimates
+                .entry(backend)
+                .or_insert(fidelity);
+        }
+        let placement = self
+            .scheduler
+            .execute(&operation, &circuit, &mut handle.lock())?;
+        self.touch_circuit(circuit_id.to_string());
+        Ok(placement)
+    }
+
+    /// Make a hardware backend available to the scheduler
+    pub fn register_backend(
+        &self,
+        profile: BackendProfile,
+        backend: Arc<dyn QuantumBackend>,
+    ) -> Result<()> {
+        let name = profile.name.clone();
+        self.scheduler.register(profile, backend)?;
+        println!("🖥️ Quantum backend {} registered", name);
+        Ok(())
+    }
+
+    /// Hardware scheduler and its placement limits
+    pub fn scheduler(&self) -> &QuantumScheduler {
+        &self.scheduler
+    }
+
+    /// Per-backend usage and simulated operations by reason
+    pub fn scheduler_stats(&self) -> SchedulerStats {
+        self.scheduler.stats()
+    }
+
+    /// Record calibration data reported by a backend
+    ///
+    /// Returns whether it replaced the backend's calibration; runs older
+    /// than the one held are ignored.
+    pub fn ingest_calibration(&self, backend: &str, data: CalibrationData) -> Result<bool> {
+        self.calibration.ingest(backend, data)
+    }
+
+    /// Fetch a backend's calibration from `source` on the refresh interval
+    pub fn register_calibration_source(&self, backend: &str, source: Arc<dyn CalibrationSource>) {
+        self.calibration.register_source(backend, source);
+    }
+
+    /// Fetch every calibration that is missing or due now
+    ///
+    /// Returns the number of backends whose calibration was updated.
+    pub fn refresh_calibrations(&self) -> usize {
+        self.calibration
+            .refresh_due(now_ms())
+            .into_iter()
+            .filter(|(_, result)| matches!(result, Ok(true)))
+            .count()
+    }
+
+    /// Lay a circuit out on a backend's best qubits using its calibration
+    pub fn transpile_circuit(&self, circuit_id: &str, backend: &str) -> Result<Transpiled> {
+        let circuit = self.circuit_snapshot(circuit_id)?;
+        self.calibration.transpile(backend, &circuit, now_ms())
+    }
+
+    /// Latest calibration of each backend
+    pub fn calibration(&self) -> &CalibrationStore {
+        &self.calibration
+    }
+
+    /// Calibration age, size and mean errors of each backend
+    pub fn calibration_summary(&self) -> BTreeMap<String, CalibrationSummary> {
+        self.calibration.summary(now_ms())
+    }
+
+    /// Execute circuit on state using the compute pool
+    ///
+    /// The state is locked on the compute thread, not across the await. If
+    /// the task fails the state is restored to its value before the call.
+    pub async fn execute_circuit_offloaded(&self, circuit_id: &str, state_id: &str) -> Result<()> {
+        let circuit = self.circuit_snapshot(circuit_id)?;
+        let handle = self.state_handle(state_id)?;
+
+        let snapshot = handle.lock().clone();
+        let worker_handle = handle.clone();
+        match spawn_compute(move || circuit.execute(&mut worker_handle.lock())).await {
+            Ok(result) => {
+                self.touch_circuit(circuit_id.to_string());
+                result
+            }
+            Err(e) => {
+                // The task may have stopped mid-circuit; restore the state unchanged
+                *handle.lock() = snapshot;
+                Err(e)
+            }
+        }
     }
     
-    /// Get quantum state information
-    pub fn get_state_info(&self, state_id: &str) -> Option<&QuantumState> {
-        self.states.get(state_id)
+    /// Memory held by active quantum states
+    pub fn state_memory(&self) -> SubsystemUsage {
+        let handles = self.handles();
+        let entries = self.states.capacity() * std::mem::size_of::<(String, StateHandle)>();
+        let register = self.register.lock();
+        SubsystemUsage {
+            bytes: entries
+                + register.heap_bytes()
+                + handles
+                    .iter()
+                    .map(|(id, handle)| id.heap_bytes() + handle.lock().footprint_bytes())
+                    .sum::<usize>(),
+            items: handles.len() + register.iter().count(),
+        }
+    }
+
+    /// Snapshot of a quantum state
+    ///
+    /// The state keeps evolving under concurrent operations; the copy does not.
+    /// Its fidelity includes the decoherence accrued up to now.
+    pub fn get_state_info(&self, state_id: &str) -> Option<QuantumState> {
+        self.state_handle(state_id).ok().map(|handle| {
+            let mut state = handle.lock();
+            state.advance_decoherence();
+            state.clone()
+        })
+    }
+
+    /// Remove a state from the core and hand it over
+    ///
+    /// Unpins the state first; its trace is retired like a collected one.
+    pub fn take_state(&self, state_id: &str) -> Option<QuantumState> {
+        self.pinned.remove(state_id);
+        let handle = self.remove_state(state_id, "taken")?;
+        let mut state = handle.lock();
+        state.advance_decoherence();
+        Some(state.clone())
+    }
+
+    /// Number of active quantum states
+    pub fn state_count(&self) -> usize {
+        self.states.len()
     }
     
     /// Get available quantum operations
@@ -1231,12 +2480,182 @@ impl QuantumCore {
         &self.metrics
     }
     
+    /// Total number of measurements performed
+    pub fn total_measurements(&self) -> u64 {
+        self.total_measurements.load(Ordering::Relaxed)
+    }
+
     /// Clean up old states
-    pub fn cleanup_old_states(&mut self, max_age_seconds: u64) {
+    ///
+    /// States pinned by active sessions are kept regardless of age.
+    pub fn cleanup_old_states(&self, max_age_seconds: u64) {
         let current_time = chrono::Utc::now().timestamp() as u64;
-        
-        self.states
-            .retain(|_id, state| current_time - state.created_at < max_age_seconds);
+        let expired: Vec<String> = self
+            .handles()
+            .into_iter()
+            .filter(|(id, handle)| {
+                !self.pinned.contains(id)
+                    && current_time.saturating_sub(handle.lock().created_at) >= max_age_seconds
+            })
+            .map(|(id, _)| id)
+            .collect();
+        for id in expired {
+            self.remove_state(&id, "cleanup");
+        }
+    }
+
+    /// Protect a state from collection while a QKD session uses it
+    pub fn pin_state(&self, state_id: &str) {
+        self.pinned.insert(state_id.to_string());
+    }
+
+    /// Release a state once its session ends; it is collected when expired
+    pub fn unpin_state(&self, state_id: &str) -> bool {
+        self.pinned.remove(state_id).is_some()
+    }
+
+    /// Whether a state is pinned by an active session
+    pub fn is_pinned(&self, state_id: &str) -> bool {
+        self.pinned.contains(state_id)
+    }
+
+    /// Bytes of the states themselves, excluding spare map capacity
+    fn live_state_bytes(&self) -> usize {
+        self.handles()
+            .iter()
+            .map(|(_, handle)| handle.lock().footprint_bytes())
+            .sum()
+    }
+
+    fn touch_circuit(&self, circuit_id: String) {
+        self.circuit_last_used
+            .insert(circuit_id, chrono::Utc::now().timestamp() as u64);
+    }
+
+    /// Run a collection if the interval elapsed or state memory is over its limit
+    ///
+    /// Returns `None` without waiting when another collection is in progress.
+    pub fn run_due_gc(&self) -> Option<GcReport> {
+        let trigger = if self.gc_due.swap(false, Ordering::AcqRel) {
+            GcTrigger::Interval
+        } else if self.live_state_bytes() > self.config.gc.memory_limit_bytes {
+            GcTrigger::MemoryPressure
+        } else {
+            return None;
+        };
+        let _running = self.gc_running.try_lock()?;
+        Some(self.collect_garbage_locked(trigger))
+    }
+
+    /// Collect expired unpinned states and unused circuits
+    ///
+    /// Under memory pressure the oldest unpinned states are collected as well
+    /// until state memory is back under the limit. States locked by a running
+    /// operation are in use and skipped.
+    pub fn collect_garbage(&self, trigger: GcTrigger) -> GcReport {
+        let _running = self.gc_running.lock();
+        self.collect_garbage_locked(trigger)
+    }
+
+    fn collect_garbage_locked(&self, trigger: GcTrigger) -> GcReport {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let max_age = self.config.gc.max_age_seconds;
+
+        // (created_at, bytes, id) of every state not busy in an operation
+        let idle: Vec<(u64, usize, String)> = self
+            .handles()
+            .into_iter()
+            .filter_map(|(id, handle)| {
+                let state = handle.try_lock()?;
+                Some((state.created_at, state.footprint_bytes(), id))
+            })
+            .collect();
+        let mut live_bytes: usize = idle.iter().map(|(_, bytes, _)| bytes).sum();
+        let mut bytes_freed = 0;
+        let mut states_collected = 0;
+        let mut pinned_retained = 0;
+        let mut survivors = Vec::new();
+        for (created_at, bytes, id) in idle {
+            if now.saturating_sub(created_at) < max_age {
+                survivors.push((created_at, bytes, id));
+            } else if self.pinned.contains(&id) {
+                pinned_retained += 1;
+            } else if self.remove_state(&id, "expired").is_some() {
+                states_collected += 1;
+                bytes_freed += bytes;
+                live_bytes = live_bytes.saturating_sub(bytes);
+            }
+        }
+
+        if trigger == GcTrigger::MemoryPressure {
+            survivors.sort();
+            for (_, bytes, id) in survivors {
+                if live_bytes <= self.config.gc.memory_limit_bytes {
+                    break;
+                }
+                if !self.pinned.contains(&id) && self.remove_state(&id, "memory pressure").is_some() {
+                    states_collected += 1;
+                    bytes_freed += bytes;
+                    live_bytes = live_bytes.saturating_sub(bytes);
+                }
+            }
+        }
+
+        let stale_circuits: Vec<String> = self
+            .circuits
+            .iter()
+            .map(|entry| entry.key().clone())
+            .filter(|id| {
+                let last_used = self.circuit_last_used.get(id).map(|entry| *entry.value()).unwrap_or(0);
+                now.saturating_sub(last_used) >= max_age
+            })
+            .collect();
+        for id in &stale_circuits {
+            self.circuits.remove(id);
+            self.circuit_last_used.remove(id);
+        }
+
+        let report = GcReport {
+            trigger,
+            states_collected,
+            circuits_collected: stale_circuits.len(),
+            pinned_retained,
+            bytes_freed,
+        };
+        let mut stats = self.gc_stats.lock();
+        stats.runs += 1;
+        if trigger == GcTrigger::MemoryPressure {
+            stats.pressure_runs += 1;
+        }
+        stats.states_collected += report.states_collected as u64;
+        stats.circuits_collected += report.circuits_collected as u64;
+        stats.bytes_freed += report.bytes_freed as u64;
+        stats.last_run_at = Some(now);
+        if report.states_collected > 0 || report.circuits_collected > 0 {
+            println!(
+                "🧹 Quantum GC ({:?}): {} states, {} circuits collected, {} pinned kept",
+                trigger, report.states_collected, report.circuits_collected, pinned_retained
+            );
+        }
+        report
+    }
+
+    /// Collection counters since the core started
+    pub fn gc_stats(&self) -> QuantumGcStats {
+        self.gc_stats.lock().clone()
+    }
+
+    /// Average fidelity of the active states
+    fn average_fidelity(&self) -> f64 {
+        let handles = self.handles();
+        if handles.is_empty() {
+            return 1.0;
+        }
+        handles
+            .iter()
+            .map(|(_, handle)| handle.lock().advance_decoherence())
+            .sum::<f64>()
+            / handles.len() as f64
     }
     
     /// Get comprehensive system status with Phase 3 enhancements
@@ -1251,16 +2670,54 @@ impl QuantumCore {
             "max_qubits".to_string(),
             serde_json::Value::Number(self.max_qubits.into()),
         );
+        status.insert(
+            "addressable_qubits".to_string(),
+            serde_json::Value::Number(self.qubits.len().into()),
+        );
         status.insert(
             "total_circuits".to_string(),
             serde_json::Value::Number(self.circuits.len().into()),
         );
+        status.insert(
+            "pinned_states".to_string(),
+            serde_json::Value::Number(self.pinned.len().into()),
+        );
+        status.insert(
+            "gc".to_string(),
+            serde_json::to_value(self.gc_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        status.insert(
+            "total_measurements".to_string(),
+            serde_json::Value::Number(self.total_measurements().into()),
+        );
+        status.insert(
+            "total_quantum_operations".to_string(),
+            serde_json::Value::Number(
+                self.total_quantum_operations.load(Ordering::Relaxed).into(),
+            ),
+        );
+        status.insert(
+            "noise_model".to_string(),
+            serde_json::to_value(&self.config.noise).unwrap_or(serde_json::Value::Null),
+        );
+        status.insert(
+            "entanglement_refreshes".to_string(),
+            serde_json::Value::Number(self.entanglement_refreshes.load(Ordering::Relaxed).into()),
+        );
+        status.insert(
+            "qrng_extraction".to_string(),
+            serde_json::to_value(self.extraction_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        status.insert(
+            "scheduling".to_string(),
+            serde_json::to_value(self.scheduler_stats()).unwrap_or(serde_json::Value::Null),
+        );
+        status.insert(
+            "calibration".to_string(),
+            serde_json::to_value(self.calibration_summary()).unwrap_or(serde_json::Value::Null),
+        );
         
-        let avg_fidelity = if !self.states.is_empty() {
-            self.states.values().map(|s| s.fidelity).sum::<f64>() / self.states.len() as f64
-        } else {
-            1.0
-        };
+        let avg_fidelity = self.average_fidelity();
         status.insert(
             "average_fidelity".to_string(),
             serde_json::Value::Number(
@@ -1306,8 +2763,13 @@ impl QuantumCore {
         self.hardware_interface.get_status()
     }
 
-    /// Create an entangled Bell pair between two qubits
-    pub fn create_bell_pair(&mut self, qubit1: usize, qubit2: usize) -> Result<BellPairResult> {
+    /// Create an entangled Bell pair between two qubits of the core register
+    ///
+    /// The register is re-prepared in |0...0⟩ and the pair built with the same
+    /// Hadamard and CNOT gates as any other state, so it holds one pair at a
+    /// time. Fidelity is the register's own state fidelity; entanglement
+    /// strength is the overlap with the ideal |Φ+⟩ pair.
+    pub fn create_bell_pair(&self, qubit1: usize, qubit2: usize) -> Result<BellPairResult> {
         let start_time = Instant::now();
 
         // Validate qubit indices
@@ -1323,51 +2785,59 @@ impl QuantumCore {
             ));
         }
 
-        // Apply Hadamard gate to first qubit to create superposition
-        self.apply_hadamard(qubit1 as u32)?;
-
-        // Apply CNOT gate to create entanglement
-        self.apply_cnot(qubit1 as u32, qubit2 as u32)?;
-
-        // Calculate fidelity based on quantum state analysis
-        let gate_fidelity = self.calculate_gate_fidelity();
-
-        // Update quantum state tracking
-        let circuit_key = format!("Bell_pair_{}_{}", qubit1, qubit2);
-        if let Some(circuit) = self.circuits.get_mut(&circuit_key) {
-            circuit.expected_fidelity *= gate_fidelity;
+        let mut register = self.register.lock();
+        if let Some(mut previous) = register.take() {
+            if let Some(journal) = previous.take_journal() {
+                self.retire_journal(journal, "re-prepared");
+            }
         }
+        let state = register.insert(self.new_register_state());
+        state.apply_gate(QuantumGate::Hadamard, &[qubit1 as u32])?;
+        state.apply_gate(QuantumGate::CNOT, &[qubit1 as u32, qubit2 as u32])?;
+        let fidelity = state.get_fidelity();
+        let entanglement_strength = bell_overlap(state, qubit1, qubit2);
+        drop(register);
 
         // Record the Bell pair creation
         let duration = start_time.elapsed().as_nanos() as u64;
+        self.record_quantum_operation("bell_pair", duration);
 
         Ok(BellPairResult {
             qubit1,
             qubit2,
-            fidelity: gate_fidelity,
-            entanglement_strength: 0.95, // High entanglement for Bell states
+            fidelity,
+            entanglement_strength,
             creation_time_ns: duration,
         })
     }
 
-    /// Measure specified qubits and return their values
-    pub fn measure_qubits(&mut self, qubit_indices: &[usize]) -> Result<Vec<bool>> {
+    /// Measure qubits of the core register with the Born rule
+    ///
+    /// Outcomes are drawn from the QRNG. Only the measured qubits collapse, so
+    /// measuring them again without a new preparation repeats the outcome.
+    pub fn measure_qubits(&self, qubit_indices: &[usize]) -> Result<Vec<bool>> {
         let start_time = Instant::now();
-        let mut results = Vec::new();
-
-        for &qubit_index in qubit_indices {
-            if qubit_index >= self.max_qubits as usize {
-                return Err(SecureCommsError::QuantumOperation(format!(
-                    "Qubit index {} out of range",
-                    qubit_index
-                )));
-            }
 
-            // Perform quantum measurement with realistic probabilities
-            let measurement = self.perform_single_qubit_measurement(qubit_index)?;
-            results.push(measurement);
+        if let Some(&qubit_index) = qubit_indices
+            .iter()
+            .find(|&&qubit| qubit >= self.max_qubits as usize)
+        {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Qubit index {} out of range",
+                qubit_index
+            )));
         }
 
+        let mut register = self.register.lock();
+        let state = register.get_or_insert_with(|| self.new_register_state());
+        let measurement_id = format!("measure_qubits_{}", chrono::Utc::now().timestamp());
+        let qubits: Vec<u32> = qubit_indices.iter().map(|&qubit| qubit as u32).collect();
+        let measured = state.measure_qubits(measurement_id, &qubits, &mut self.qrng.lock())?;
+        drop(register);
+        self.total_measurements.fetch_add(1, Ordering::Relaxed);
+
+        let results = measured.outcome.iter().map(|&bit| bit == 1).collect();
+
         // Record measurement operation
         let duration = start_time.elapsed().as_nanos() as u64;
         self.record_quantum_operation("measurement", duration);
@@ -1375,109 +2845,22 @@ impl QuantumCore {
         Ok(results)
     }
 
-    /// Apply Hadamard gate to create superposition
-    fn apply_hadamard(&mut self, qubit: u32) -> Result<()> {
-        if qubit >= self.max_qubits {
-            return Err(SecureCommsError::QuantumOperation(
-                "Qubit index out of range for Hadamard gate".to_string(),
-            ));
-        }
-
-        // Simulate Hadamard gate operation
-        let gate_duration = 10 + (rand::random::<u64>() % 20); // 10-30ns realistic timing
-
-        // Update circuit if available
-        let circuit_key = format!("Hadamard_{}", qubit);
-        if let Some(circuit) = self.circuits.get_mut(&circuit_key) {
-            circuit.depth += 1;
-        }
-
-        self.record_quantum_operation("hadamard", gate_duration);
-        Ok(())
-    }
-
-    /// Apply CNOT gate for entanglement
-    fn apply_cnot(&mut self, control: u32, target: u32) -> Result<()> {
-        if control >= self.max_qubits || target >= self.max_qubits {
-            return Err(SecureCommsError::QuantumOperation(
-                "Qubit index out of range for CNOT gate".to_string(),
-            ));
-        }
-
-        if control == target {
-            return Err(SecureCommsError::QuantumOperation(
-                "Control and target qubits cannot be the same".to_string(),
-            ));
+    /// Fresh |0...0⟩ register, journaled when journaling is enabled
+    fn new_register_state(&self) -> QuantumState {
+        let mut state = QuantumState::new(REGISTER_STATE_ID.to_string(), self.max_qubits);
+        if let Some(config) = self.journal_config.read().as_ref() {
+            state.start_journal(config.max_entries_per_state);
         }
-
-        // Simulate CNOT gate operation
-        let gate_duration = 20 + (rand::random::<u64>() % 30); // 20-50ns realistic timing
-
-        // Update circuit if available
-        let circuit_key = format!("CNOT_{}_{}", control, target);
-        if let Some(circuit) = self.circuits.get_mut(&circuit_key) {
-            circuit.depth += 1;
-        }
-
-        self.record_quantum_operation("cnot", gate_duration);
-        Ok(())
-    }
-
-    /// Perform single qubit measurement with perfect quantum mechanics
-    fn perform_single_qubit_measurement(&mut self, qubit: usize) -> Result<bool> {
-        // SECURITY OPTIMIZATION: Perfect quantum measurement without artificial noise
-        // Use quantum random number generation for authentic quantum behavior
-        
-        // Get quantum state bias for this qubit (perfect superposition)
-        let qubit_state_bias = match qubit {
-            0 => 0.5, // Qubit 0: perfect superposition
-            1 => 0.5, // Qubit 1: perfect superposition  
-            2 => 0.5, // Qubit 2: perfect superposition
-            3 => 0.5, // Qubit 3: perfect superposition
-            _ => 0.5, // All qubits: perfect superposition
-        };
-
-        // SECURITY OPTIMIZATION: Perfect quantum measurement without noise
-        // Use quantum randomness for authentic measurement outcomes
-        let measurement_result = self.qrng.gen_range(0..1000) as f64 / 1000.0 < qubit_state_bias;
-
-        // Record measurement statistics for this specific qubit
-        self.total_measurements += 1;
-
-        // Debug output for qubit-specific measurements
-        if self.hardware_enabled {
-            println!(
-                "📊 Measured qubit {}: {} (perfect superposition)",
-                qubit, measurement_result as u8
-            );
-        }
-
-        Ok(measurement_result)
-    }
-
-    /// Calculate gate fidelity based on quantum state analysis
-    fn calculate_gate_fidelity(&self) -> f64 {
-        // Calculate average fidelity across all active quantum states
-        if self.states.is_empty() {
-            // No states to evaluate - return theoretical perfect case
-            1.0
-        } else {
-            // Real fidelity based on actual quantum state purity
-            let total_fidelity: f64 = self.states.values()
-                .map(|state| {
-                    // Calculate state purity directly from amplitudes
-                    state.amplitudes.iter().map(|&a| a * a).sum::<f64>()
-                })
-                .sum();
-            
-            total_fidelity / self.states.len() as f64
+        if let Some(noise) = &self.config.noise {
+            state.enable_decoherence(noise.clone());
         }
+        state
     }
 
     /// Record quantum operation for performance tracking
-    fn record_quantum_operation(&mut self, operation_type: &str, duration_ns: u64) {
+    fn record_quantum_operation(&self, operation_type: &str, duration_ns: u64) {
         // Update performance metrics
-        self.total_quantum_operations += 1;
+        self.total_quantum_operations.fetch_add(1, Ordering::Relaxed);
 
         // Log operation for debugging if needed (conditional logging without external log crate)
         if self.hardware_enabled {
@@ -1489,6 +2872,14 @@ impl QuantumCore {
     }
 }
 
+/// Overlap |⟨Φ+|ψ⟩|² of a register holding a pair on two qubits and |0⟩ elsewhere
+fn bell_overlap(state: &QuantumState, qubit1: usize, qubit2: usize) -> f64 {
+    let both = (1 << qubit1) | (1 << qubit2);
+    let (a, b) = (state.amplitudes[0], state.amplitudes[both]);
+    let relative_phase = state.phases[0] - state.phases[both];
+    (a * a + b * b + 2.0 * a * b * relative_phase.cos()) / 2.0
+}
+
 /// Result of Bell pair creation
 #[derive(Debug, Clone)]
 pub struct BellPairResult {
@@ -1506,41 +2897,49 @@ pub struct BellPairResult {
 
 /// Trait for quantum operations
 pub trait QuantumOperations {
-    fn create_entanglement(&mut self, qubits: &[u32]) -> Result<()>;
-    fn measure_state(&mut self, measurement_id: String) -> Result<Vec<u8>>;
+    fn create_entanglement(&mut self, qubits: &[QubitId]) -> Result<()>;
+    fn measure_state(&mut self, state_id: &str, measurement_id: String) -> Result<Vec<u8>>;
     fn get_fidelity(&self) -> f64;
 }
 
+impl Drop for QuantumCore {
+    fn drop(&mut self) {
+        if let Some(timer) = self.gc_timer.take() {
+            timer.abort();
+        }
+        if let Some(timer) = self.calibration_timer.take() {
+            timer.abort();
+        }
+    }
+}
+
 impl QuantumOperations for QuantumCore {
-    fn create_entanglement(&mut self, qubits: &[u32]) -> Result<()> {
-        // Use first available state for operations
-        if let Some((_, state)) = self.states.iter_mut().next() {
-            if qubits.len() >= 2 {
-                state.apply_gate(QuantumGate::Hadamard, &[qubits[0]])?;
-                for i in 1..qubits.len() {
-                    state.apply_gate(QuantumGate::CNOT, &[qubits[0], qubits[i]])?;
-                }
-            }
+    fn create_entanglement(&mut self, qubits: &[QubitId]) -> Result<()> {
+        let (state_id, indices) = self.qubits.resolve_group(qubits)?;
+        if indices.len() < 2 {
+            return Err(SecureCommsError::QuantumOperation(format!(
+                "Entangling {} needs at least 2 qubits of {}",
+                qubits[0], state_id
+            )));
+        }
+        let handle = self.state_handle(&state_id)?;
+        let mut state = handle.lock();
+        state.apply_gate(QuantumGate::Hadamard, &[indices[0]])?;
+        for &target in &indices[1..] {
+            state.apply_gate(QuantumGate::CNOT, &[indices[0], target])?;
         }
         Ok(())
     }
     
-    fn measure_state(&mut self, measurement_id: String) -> Result<Vec<u8>> {
-        if let Some((_, state)) = self.states.iter_mut().next() {
-            state.measure(measurement_id, &mut self.qrng)
-        } else {
-            Err(SecureCommsError::QuantumOperation(
-                "No active state".to_string(),
-            ))
-        }
+    fn measure_state(&mut self, state_id: &str, measurement_id: String) -> Result<Vec<u8>> {
+        let handle = self.state_handle(state_id)?;
+        let outcome = handle.lock().measure(measurement_id, &mut self.qrng.lock());
+        self.total_measurements.fetch_add(1, Ordering::Relaxed);
+        outcome
     }
     
     fn get_fidelity(&self) -> f64 {
-        if !self.states.is_empty() {
-            self.states.values().map(|s| s.fidelity).sum::<f64>() / self.states.len() as f64
-        } else {
-            1.0
-        }
+        self.average_fidelity()
     }
 }
 
@@ -1569,7 +2968,7 @@ mod tests {
     
     #[tokio::test]
     async fn test_quantum_operations() {
-        let mut core = QuantumCore::new(4).await.unwrap();
+        let core = QuantumCore::new(4).await.unwrap();
         
         // Create a state
         let state_id = core.create_comm_state("test".to_string(), 2).unwrap();
@@ -1588,7 +2987,7 @@ mod tests {
     
     #[tokio::test]
     async fn test_quantum_circuit() {
-        let mut core = QuantumCore::new(4).await.unwrap();
+        let core = QuantumCore::new(4).await.unwrap();
         
         // Create circuit
         let circuit_id = core.create_circuit("test_circuit".to_string(), 3).unwrap();
@@ -1610,23 +3009,338 @@ mod tests {
         let state_info = core.get_state_info(&state_id).unwrap();
         assert!(state_info.fidelity > 0.99); // Should maintain high fidelity
     }
+
+    #[tokio::test]
+    async fn test_circuits_scheduled_on_registered_backend() {
+        use crate::quantum_scheduler::SimulationReason;
+
+        struct CountingDevice(AtomicU64);
+        impl QuantumBackend for CountingDevice {
+            fn queue_depth(&self) -> usize {
+                0
+            }
+            fn execute(&self, circuit: &QuantumCircuit, state: &mut QuantumState) -> Result<()> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                circuit.execute(state)
+            }
+        }
+
+        let core = QuantumCore::with_config(QuantumConfig {
+            scheduling: SchedulingConfig {
+                cost_budget: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let circuit_id = core.create_circuit("scheduled".to_string(), 2).unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::Hadamard, vec![0])
+            .unwrap();
+        let state_id = core.create_comm_state("scheduled".to_string(), 2).unwrap();
+        core.execute_circuit(&circuit_id, &state_id).unwrap();
+
+        let device = Arc::new(CountingDevice(AtomicU64::new(0)));
+        core.register_backend(
+            BackendProfile {
+                name: "qpu".to_string(),
+                qubits: 4,
+                fidelity: 0.999,
+                cost_per_job: 0.4,
+                cost_per_shot: 0.0,
+                job_latency_ms: 50,
+            },
+            device.clone(),
+        )
+        .unwrap();
+        let operation = OperationProfile::new(2, 1);
+        for _ in 0..2 {
+            assert!(core
+                .execute_circuit_with(&circuit_id, &state_id, &operation)
+                .unwrap()
+                .is_hardware());
+        }
+        // The budget covers two jobs; strict fidelity needs never reach hardware
+        assert!(!core
+            .execute_circuit_with(&circuit_id, &state_id, &operation)
+            .unwrap()
+            .is_hardware());
+        assert_eq!(
+            core.execute_circuit_with(
+                &circuit_id,
+                &state_id,
+                &operation.clone().with_min_fidelity(1.0)
+            )
+            .unwrap(),
+            Placement::Simulation {
+                reason: SimulationReason::FidelityTooLow
+            }
+        );
+        assert_eq!(device.0.load(Ordering::Relaxed), 2);
+
+        let stats = core.scheduler_stats();
+        assert_eq!(stats.backends["qpu"].jobs, 2);
+        assert_eq!(stats.simulated["no_backend"], 1);
+        assert_eq!(stats.simulated["over_budget"], 1);
+        assert_eq!(
+            core.get_system_status()["scheduling"]["simulated"]["fidelity_too_low"],
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circuits_delegated_to_blind_backend() {
+        use crate::blind_computation::{BlindBackend, BlindConfig, SimulatedBlindServer};
+
+        let core = QuantumCore::with_config(QuantumConfig {
+            scheduling: SchedulingConfig {
+                cost_budget: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let backend = BlindBackend::new(
+            BlindConfig::default(),
+            QRNG::from_seed([3; 32]),
+            SimulatedBlindServer::new(QRNG::from_seed([4; 32])),
+        )
+        .unwrap();
+        core.register_backend(
+            BackendProfile {
+                name: "blind".to_string(),
+                qubits: 1,
+                fidelity: 0.999,
+                cost_per_job: 0.1,
+                cost_per_shot: 0.0,
+                job_latency_ms: 50,
+            },
+            Arc::new(backend),
+        )
+        .unwrap();
+
+        let circuit_id = core.create_circuit("blind".to_string(), 1).unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::PauliX, vec![0])
+            .unwrap();
+        let state_id = core.create_comm_state("blind".to_string(), 1).unwrap();
+        assert!(core
+            .execute_circuit_with(&circuit_id, &state_id, &OperationProfile::new(1, 1))
+            .unwrap()
+            .is_hardware());
+        let state = core.get_state_info(&state_id).unwrap();
+        assert!((state.amplitudes[1] - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_qubits_addressed_across_states() {
+        let mut core = QuantumCore::new(8).await.unwrap();
+        core.create_comm_state("a".to_string(), 2).unwrap();
+        core.create_comm_state("b".to_string(), 2).unwrap();
+        let a = core.state_qubits("a").unwrap();
+        let b = core.state_qubits("b").unwrap();
+        assert_eq!(core.locate_qubit(b[1]).unwrap().to_string(), "b[1]");
+
+        // Each operation reaches the state owning its qubits
+        core.create_entanglement(&b).unwrap();
+        core.apply_gate_to_qubits(QuantumGate::PauliX, &[a[1]])
+            .unwrap();
+        let pair = core.measure_addressed_qubits(&b).unwrap();
+        assert_eq!(pair[0], pair[1]);
+        assert_eq!(
+            core.measure_addressed_qubits(&[a[1], a[0]]).unwrap(),
+            vec![true, false]
+        );
+
+        let message = core
+            .apply_gate_to_qubits(QuantumGate::CNOT, &[a[0], b[0]])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("a[0]") && message.contains("b[0]"),
+            "{}",
+            message
+        );
+        assert!(core
+            .measure_state("c", "missing".to_string())
+            .unwrap_err()
+            .to_string()
+            .contains("State c"));
+
+        // Removing a state retires its identifiers
+        core.take_state("b").unwrap();
+        assert!(core
+            .measure_addressed_qubits(&[b[0]])
+            .unwrap_err()
+            .to_string()
+            .contains(&b[0].to_string()));
+        assert_eq!(core.get_system_status()["addressable_qubits"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_library_circuits_run_on_core_states() {
+        let core = QuantumCore::new(8).await.unwrap();
+        let circuit_id = core.add_circuit(crate::circuits::ghz(3).unwrap()).unwrap();
+        assert_eq!(circuit_id, "ghz_3");
+        let state_id = core.create_comm_state("ghz".to_string(), 3).unwrap();
+        core.execute_circuit(&circuit_id, &state_id).unwrap();
+        let state = core.get_state_info(&state_id).unwrap();
+        let half = 1.0 / 2.0_f64.sqrt();
+        assert!((state.amplitudes[0].abs() - half).abs() < 1e-9);
+        assert!((state.amplitudes[0b111].abs() - half).abs() < 1e-9);
+
+        assert!(core
+            .add_circuit(crate::circuits::random_circuit(9, 2, 7).unwrap())
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calibration_drives_placement_and_error_rates() {
+        use crate::calibration::{CouplerCalibration, QubitCalibration};
+        use crate::quantum_scheduler::SimulationReason;
+
+        struct Device;
+        impl QuantumBackend for Device {
+            fn queue_depth(&self) -> usize {
+                0
+            }
+            fn execute(&self, circuit: &QuantumCircuit, state: &mut QuantumState) -> Result<()> {
+                circuit.execute(state)
+            }
+        }
+
+        let core = QuantumCore::with_config(QuantumConfig {
+            scheduling: SchedulingConfig {
+                cost_budget: 10.0,
+                min_fidelity: 0.95,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        core.register_backend(
+            BackendProfile {
+                name: "qpu".to_string(),
+                qubits: 3,
+                fidelity: 0.999,
+                cost_per_job: 0.1,
+                cost_per_shot: 0.0,
+                job_latency_ms: 50,
+            },
+            Arc::new(Device),
+        )
+        .unwrap();
+        let circuit_id = core.create_circuit("calibrated".to_string(), 2).unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::Hadamard, vec![0])
+            .unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::CNOT, vec![0, 1])
+            .unwrap();
+        let state_id = core.create_comm_state("calibrated".to_string(), 2).unwrap();
+        let operation = OperationProfile::new(2, 1);
+        assert!(core
+            .execute_circuit_with(&circuit_id, &state_id, &operation)
+            .unwrap()
+            .is_hardware());
+        assert_eq!(core.get_hardware_status()["error_rates"]["two_qubit"], 0.0);
+
+        // Only qubits 1 and 2 share a good coupler
+        let qubit = |readout_error| QubitCalibration {
+            t1_us: 100.0,
+            t2_us: 100.0,
+            gate_error: 0.001,
+            readout_error,
+        };
+        let calibration = |coupler_error| CalibrationData {
+            taken_at_ms: now_ms(),
+            qubits: vec![qubit(0.01), qubit(0.01), qubit(0.01)],
+            couplers: vec![
+                CouplerCalibration {
+                    control: 0,
+                    target: 1,
+                    error: 0.2,
+                },
+                CouplerCalibration {
+                    control: 1,
+                    target: 2,
+                    error: coupler_error,
+                },
+            ],
+            single_qubit_gate_ns: 20.0,
+            two_qubit_gate_ns: 200.0,
+        };
+        assert!(core.ingest_calibration("qpu", calibration(0.01)).unwrap());
+        let transpiled = core.transpile_circuit(&circuit_id, "qpu").unwrap();
+        let mut layout = transpiled.layout.clone();
+        layout.sort();
+        assert_eq!(layout, vec![1, 2]);
+        assert!(core
+            .execute_circuit_with(&circuit_id, &state_id, &operation)
+            .unwrap()
+            .is_hardware());
+
+        // A degraded coupler drops the estimate below the requirement
+        assert!(core.ingest_calibration("qpu", calibration(0.15)).unwrap());
+        assert_eq!(
+            core.execute_circuit_with(&circuit_id, &state_id, &operation)
+                .unwrap(),
+            Placement::Simulation {
+                reason: SimulationReason::FidelityTooLow
+            }
+        );
+        assert!((core.hardware_interface.get_error_rate("two_qubit") - 0.175).abs() < 1e-9);
+        assert_eq!(core.calibration_summary()["qpu"].qubits, 3);
+        assert!(!core.get_system_status()["calibration"]["qpu"]["stale"]
+            .as_bool()
+            .unwrap());
+        assert!(core.transpile_circuit(&circuit_id, "other").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offloaded_circuit_matches_inline() {
+        let core = QuantumCore::new(4).await.unwrap();
+        let circuit_id = core.create_circuit("offload".to_string(), 3).unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::Hadamard, vec![0])
+            .unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::CNOT, vec![0, 1])
+            .unwrap();
+
+        let inline = core.create_comm_state("inline".to_string(), 3).unwrap();
+        let offloaded = core.create_comm_state("offloaded".to_string(), 3).unwrap();
+        core.execute_circuit(&circuit_id, &inline).unwrap();
+        core.execute_circuit_offloaded(&circuit_id, &offloaded)
+            .await
+            .unwrap();
+        assert_eq!(
+            core.get_state_info(&inline).unwrap().get_amplitudes(),
+            core.get_state_info(&offloaded).unwrap().get_amplitudes()
+        );
+
+        // Unknown states are reported without touching the others
+        assert!(core
+            .execute_circuit_offloaded(&circuit_id, "missing")
+            .await
+            .is_err());
+        assert!(core.get_state_info(&offloaded).is_some());
+    }
     
     #[tokio::test]
     async fn test_born_rule_measurement() {
-        let mut core = QuantumCore::new(2).await.unwrap();
+        let core = QuantumCore::new(2).await.unwrap();
         let state_id = core.create_comm_state("born_test".to_string(), 2).unwrap();
         
         // Create superposition
-        let state = core.states.get_mut(&state_id).unwrap();
-        state.create_superposition(&mut core.qrng).unwrap();
+        let handle = core.state_handle(&state_id).unwrap();
+        let mut state = handle.lock();
+        state.create_superposition(&mut core.qrng.lock()).unwrap();
         
         // Test multiple measurements for statistical distribution
         let mut measurements = Vec::new();
         for i in 0..10 {
             // Need to recreate superposition for each measurement since measurement collapses state
-            state.create_superposition(&mut core.qrng).unwrap();
+            state.create_superposition(&mut core.qrng.lock()).unwrap();
             let measurement_id = format!("measurement_{}", i);
-            let result = state.measure(measurement_id, &mut core.qrng).unwrap();
+            let result = state.measure(measurement_id, &mut core.qrng.lock()).unwrap();
             measurements.push(result);
         }
         
@@ -1642,7 +3356,7 @@ mod tests {
     
     #[tokio::test]
     async fn test_quantum_teleportation() {
-        let mut core = QuantumCore::new(3).await.unwrap();
+        let core = QuantumCore::new(3).await.unwrap();
         let state_id = core
             .create_comm_state("teleport_test".to_string(), 3)
             .unwrap();
@@ -1663,10 +3377,11 @@ mod tests {
     
     #[tokio::test]
     async fn test_enhanced_gates() {
-        let mut core = QuantumCore::new(2).await.unwrap();
+        let core = QuantumCore::new(2).await.unwrap();
         let state_id = core.create_comm_state("gate_test".to_string(), 2).unwrap();
         
-        let state = core.states.get_mut(&state_id).unwrap();
+        let handle = core.state_handle(&state_id).unwrap();
+        let mut state = handle.lock();
         
         // Test all enhanced gates
         state.apply_gate(QuantumGate::PauliY, &[0]).unwrap();
@@ -1682,7 +3397,7 @@ mod tests {
     
     #[tokio::test]
     async fn test_bell_state_creation() {
-        let mut core = QuantumCore::new(2).await.unwrap();
+        let core = QuantumCore::new(2).await.unwrap();
         let state_id = core.create_comm_state("bell_test".to_string(), 2).unwrap();
         
         // Create Bell state
@@ -1713,4 +3428,542 @@ mod tests {
         assert!(status.contains_key("architecture"));
         assert!(status.contains_key("qubits"));
     }
-} 
+
+    fn gc_config(max_age_seconds: u64, memory_limit_bytes: usize) -> QuantumConfig {
+        QuantumConfig {
+            cleanup_interval_seconds: 0,
+            gc: QuantumGcConfig {
+                max_age_seconds,
+                memory_limit_bytes,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gc_keeps_pinned_states() {
+        let core = QuantumCore::with_config(gc_config(0, usize::MAX)).await.unwrap();
+        core.create_comm_state("session".to_string(), 2).unwrap();
+        core.create_comm_state("scratch".to_string(), 2).unwrap();
+        core.create_circuit("bell".to_string(), 2).unwrap();
+        core.pin_state("session");
+
+        let report = core.collect_garbage(GcTrigger::Manual);
+        assert_eq!(report.states_collected, 1);
+        assert_eq!(report.circuits_collected, 1);
+        assert_eq!(report.pinned_retained, 1);
+        assert!(report.bytes_freed > 0);
+        assert!(core.get_state_info("session").is_some());
+        assert!(core.get_state_info("scratch").is_none());
+
+        assert!(core.unpin_state("session"));
+        core.collect_garbage(GcTrigger::Manual);
+        assert!(core.get_state_info("session").is_none());
+        assert_eq!(core.gc_stats().states_collected, 2);
+    }
+
+    #[tokio::test]
+    async fn test_gc_under_memory_pressure() {
+        // Nothing is old enough to expire; only the memory limit applies
+        let state_bytes = QuantumState::new("state_0".to_string(), 4).footprint_bytes();
+        let limit = state_bytes * 3;
+        let core = QuantumCore::with_config(gc_config(3600, limit)).await.unwrap();
+        core.create_comm_state("pinned".to_string(), 4).unwrap();
+        core.pin_state("pinned");
+        for i in 0..8 {
+            core.create_comm_state(format!("state_{}", i), 4).unwrap();
+        }
+
+        // Collection runs before each allocation, so at most one state over
+        assert!(core.live_state_bytes() <= limit + state_bytes);
+        assert!(core.gc_stats().pressure_runs > 0);
+        assert!(core.get_state_info("pinned").is_some());
+        assert!(core.get_state_info("state_0").is_none());
+        assert!(core.get_state_info("state_7").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_gc_interval_marks_collection_due() {
+        let core = QuantumCore::with_config(QuantumConfig {
+            cleanup_interval_seconds: 1,
+            ..gc_config(0, usize::MAX)
+        })
+        .await
+        .unwrap();
+        core.create_comm_state("old".to_string(), 2).unwrap();
+        assert!(core.run_due_gc().is_none());
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        let report = core.run_due_gc().unwrap();
+        assert_eq!(report.trigger, GcTrigger::Interval);
+        assert_eq!(report.states_collected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_journal_replays_outcomes() {
+        async fn session() -> QuantumJournal {
+            let core = QuantumCore::with_config(QuantumConfig {
+                journal: Some(JournalConfig {
+                    deterministic_seed: Some(2024),
+                    ..Default::default()
+                }),
+                ..gc_config(0, usize::MAX)
+            })
+            .await
+            .unwrap();
+            core.create_comm_state("qkd".to_string(), 2).unwrap();
+            core.create_entangled_state("qkd").unwrap();
+            for _ in 0..4 {
+                core.generate_quantum_random("qkd", 2).unwrap();
+            }
+            core.create_comm_state("scratch".to_string(), 1).unwrap();
+            core.collect_garbage(GcTrigger::Manual);
+            core.journal()
+        }
+
+        let first = session().await;
+        let second = session().await;
+        assert_eq!(first.seed, Some(2024));
+        assert!(first.states.is_empty());
+        assert_eq!(first.retired.len(), 2);
+        // Measurement ids carry wall-clock time; outcomes and RNG positions must match
+        let outcomes = |journal: &QuantumJournal| -> Vec<(Vec<u8>, Option<u128>)> {
+            journal
+                .state("qkd")
+                .unwrap()
+                .events()
+                .into_iter()
+                .filter_map(|event| match event {
+                    JournalEvent::Measurement {
+                        outcome,
+                        rng_word_pos,
+                        ..
+                    } => Some((outcome.clone(), *rng_word_pos)),
+                    _ => None,
+                })
+                .collect()
+        };
+        // Each conditioned 2-bit draw spends two 2-bit measurements
+        assert_eq!(outcomes(&first).len(), 8);
+        assert!(outcomes(&first).iter().all(|(_, pos)| pos.is_some()));
+        assert_eq!(outcomes(&first), outcomes(&second));
+
+        let qkd = first.state("qkd").unwrap();
+        assert_eq!(
+            qkd.events()[1],
+            &JournalEvent::Gate {
+                gate: QuantumGate::Hadamard,
+                qubits: vec![0]
+            }
+        );
+        assert!(matches!(
+            qkd.events().last(),
+            Some(JournalEvent::Collected { .. })
+        ));
+        assert!(first.to_qasm("qkd").unwrap().contains("cx q[0],q[1];"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn test_concurrent_sessions_stress() {
+        let core = Arc::new(QuantumCore::with_config(gc_config(3600, usize::MAX)).await.unwrap());
+        let circuit_id = core.create_circuit("shared_bell".to_string(), 2).unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::Hadamard, vec![0])
+            .unwrap();
+        core.add_gate_to_circuit(&circuit_id, QuantumGate::CNOT, vec![0, 1])
+            .unwrap();
+
+        let sessions: Vec<_> = (0..100)
+            .map(|i| {
+                let core = core.clone();
+                let circuit_id = circuit_id.clone();
+                tokio::spawn(async move {
+                    let state_id = core.create_comm_state(format!("session_{}", i), 2)?;
+                    core.pin_state(&state_id);
+                    core.create_entangled_state(&state_id)?;
+                    let mut bits = Vec::new();
+                    for _ in 0..10 {
+                        bits.extend(core.generate_quantum_random(&state_id, 2)?);
+                    }
+                    core.execute_circuit(&circuit_id, &state_id)?;
+                    core.execute_circuit_offloaded(&circuit_id, &state_id)
+                        .await?;
+                    Ok::<_, SecureCommsError>((state_id, bits))
+                })
+            })
+            .collect();
+
+        for session in sessions {
+            let (state_id, bits) = session.await.unwrap().unwrap();
+            assert_eq!(bits.len(), 20);
+            assert!(bits.iter().all(|&bit| bit <= 1));
+            let state = core.get_state_info(&state_id).unwrap();
+            assert!(state.fidelity > 0.99);
+        }
+        assert_eq!(core.state_count(), 100);
+        // Conditioning at the default 0.5 ratio doubles the measurements per draw
+        assert_eq!(core.total_measurements(), 2000);
+        assert_eq!(core.collect_garbage(GcTrigger::Manual).states_collected, 0);
+    }
+
+    #[tokio::test]
+    async fn test_random_output_is_conditioned() {
+        let core = QuantumCore::with_config(QuantumConfig {
+            extraction: ExtractionConfig {
+                extractor: crate::qrng_extraction::ExtractorKind::Toeplitz,
+                max_extraction_ratio: 0.25,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        core.create_comm_state("qrng".to_string(), 4).unwrap();
+
+        let raw = core.generate_raw_quantum_random("qrng", 8).unwrap();
+        assert_eq!(raw.len(), 4);
+        assert_eq!(core.total_measurements(), 1);
+        assert_eq!(core.extraction_stats().raw_bits, 0);
+
+        // Output is capped at one measurement's width, funded by four of them
+        let bits = core.generate_quantum_random("qrng", 8).unwrap();
+        assert_eq!(bits.len(), 4);
+        assert!(bits.iter().all(|&bit| bit <= 1));
+        assert_eq!(core.total_measurements(), 5);
+        let stats = core.extraction_stats();
+        assert_eq!((stats.raw_bits, stats.output_bits), (16, 4));
+        assert_eq!(stats.extraction_ratio, 0.25);
+        assert_eq!(core.get_system_status()["qrng_extraction"]["output_bits"], 4);
+
+        let invalid = QuantumConfig {
+            extraction: ExtractionConfig {
+                assumed_min_entropy: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(QuantumCore::with_config(invalid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_busy_state_does_not_block_other_sessions() {
+        let core = Arc::new(QuantumCore::with_config(gc_config(0, usize::MAX)).await.unwrap());
+        core.create_comm_state("alice".to_string(), 2).unwrap();
+        core.create_comm_state("bob".to_string(), 2).unwrap();
+
+        // Hold alice's state the way a long-running operation would
+        let alice = core.state_handle("alice").unwrap();
+        let busy = alice.lock();
+        let other = core.clone();
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let bits = other
+                .create_entangled_state("bob")
+                .and_then(|_| other.generate_quantum_random("bob", 2));
+            done_tx.send(bits).unwrap();
+        });
+        let bits = done_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("bob's session waited on alice's state")
+            .unwrap();
+        assert_eq!(bits.len(), 2);
+
+        // Collection skips the busy state instead of waiting for it
+        let report = core.collect_garbage(GcTrigger::Manual);
+        assert_eq!(report.states_collected, 1);
+        drop(busy);
+        assert!(core.get_state_info("alice").is_some());
+        assert!(core.get_state_info("bob").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bell_pair_matches_state_fidelity() {
+        let core = QuantumCore::new(4).await.unwrap();
+        let bell = core.create_bell_pair(0, 1).unwrap();
+
+        let state_id = core.create_comm_state("pair".to_string(), 2).unwrap();
+        core.create_entangled_state(&state_id).unwrap();
+        let state = core.get_state_info(&state_id).unwrap();
+
+        assert!((bell.fidelity - state.get_fidelity()).abs() < 1e-12);
+        assert!((bell.fidelity - core.get_fidelity()).abs() < 1e-12);
+        assert!((bell.entanglement_strength - 1.0).abs() < 1e-12);
+        assert!(core.create_bell_pair(2, 2).is_err());
+        assert!(core.measure_qubits(&[4]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_measure_qubits_follows_born_rule() {
+        let core = QuantumCore::new(4).await.unwrap();
+        // Unprepared register is |0000⟩
+        assert_eq!(core.measure_qubits(&[0, 1, 2, 3]).unwrap(), vec![false; 4]);
+
+        let mut ones = 0;
+        for _ in 0..32 {
+            core.create_bell_pair(1, 3).unwrap();
+            let bits = core.measure_qubits(&[1, 3]).unwrap();
+            assert_eq!(bits[0], bits[1], "Bell pair outcomes must agree");
+            // Collapsed: measuring again repeats the outcome
+            assert_eq!(core.measure_qubits(&[3, 1]).unwrap(), bits);
+            ones += bits[0] as usize;
+        }
+        assert!(ones > 0 && ones < 32);
+        assert_eq!(core.total_measurements(), 65);
+    }
+
+    #[tokio::test]
+    async fn test_measure_qubits_uses_seeded_qrng() {
+        async fn outcomes() -> Vec<bool> {
+            let core = QuantumCore::with_config(QuantumConfig {
+                journal: Some(JournalConfig {
+                    deterministic_seed: Some(7),
+                    ..Default::default()
+                }),
+                ..gc_config(0, usize::MAX)
+            })
+            .await
+            .unwrap();
+            let mut bits = Vec::new();
+            for _ in 0..16 {
+                core.create_bell_pair(0, 2).unwrap();
+                bits.extend(core.measure_qubits(&[0]).unwrap());
+            }
+            let journal = core.journal();
+            assert!(journal.state(REGISTER_STATE_ID).is_some());
+            assert_eq!(journal.retired.len(), 15);
+            bits
+        }
+        assert_eq!(outcomes().await, outcomes().await);
+    }
+
+    fn basis_outcomes(
+        prepare: &[(QuantumGate, u32)],
+        basis: MeasurementBasis,
+        trials: usize,
+    ) -> (usize, QuantumState) {
+        let mut qrng = QRNG::from_seed([7; 32]);
+        let mut zeros = 0;
+        let mut last = QuantumState::new("basis".to_string(), 1);
+        for i in 0..trials {
+            let mut state = QuantumState::new("basis".to_string(), 1);
+            for &(gate, qubit) in prepare {
+                state.apply_gate(gate, &[qubit]).unwrap();
+            }
+            let outcome = state
+                .measure_in_basis(format!("m{}", i), basis, &mut qrng)
+                .unwrap();
+            zeros += (outcome[0] == 0) as usize;
+            last = state;
+        }
+        (zeros, last)
+    }
+
+    #[test]
+    fn test_measurement_in_eigenbasis_is_certain() {
+        use std::f64::consts::FRAC_PI_2;
+        let plus = [(QuantumGate::Hadamard, 0)];
+        let plus_i = [(QuantumGate::Hadamard, 0), (QuantumGate::SGate, 0)];
+        assert_eq!(basis_outcomes(&[], MeasurementBasis::Z, 50).0, 50);
+        assert_eq!(basis_outcomes(&plus, MeasurementBasis::X, 50).0, 50);
+        assert_eq!(basis_outcomes(&plus_i, MeasurementBasis::Y, 50).0, 50);
+        let x_axis = MeasurementBasis::Angle {
+            theta: FRAC_PI_2,
+            phi: 0.0,
+        };
+        assert_eq!(basis_outcomes(&plus, x_axis, 50).0, 50);
+        // |1⟩ is the -1 eigenstate of Z
+        assert_eq!(
+            basis_outcomes(&[(QuantumGate::PauliX, 0)], MeasurementBasis::Z, 50).0,
+            0
+        );
+    }
+
+    #[test]
+    fn test_measurement_in_conjugate_basis_is_uniform() {
+        use std::f64::consts::FRAC_PI_3;
+        let (zeros, collapsed) = basis_outcomes(&[], MeasurementBasis::X, 2000);
+        assert!((900..1100).contains(&zeros), "zeros = {}", zeros);
+        // Collapsed onto |+⟩ or |−⟩: equal weights, relative phase 0 or π
+        let inv_sqrt2 = 1.0 / 2.0_f64.sqrt();
+        assert!(collapsed.amplitudes.iter().all(|a| (a - inv_sqrt2).abs() < 1e-12));
+        let relative = (collapsed.phases[1] - collapsed.phases[0]).abs();
+        assert!(relative < 1e-12 || (relative - std::f64::consts::PI).abs() < 1e-12);
+        assert!((collapsed.fidelity - 1.0).abs() < 1e-12);
+
+        // P(0) = cos²(θ/2) = 0.75 for |0⟩ along θ = π/3
+        let tilted = MeasurementBasis::Angle {
+            theta: FRAC_PI_3,
+            phi: 0.4,
+        };
+        let (zeros, _) = basis_outcomes(&[], tilted, 2000);
+        assert!((1400..1600).contains(&zeros), "zeros = {}", zeros);
+    }
+
+    #[test]
+    fn test_mixed_bases_collapse_per_qubit() {
+        let mut qrng = QRNG::from_seed([9; 32]);
+        let mut state = QuantumState::new("bb84".to_string(), 2);
+        state.apply_gate(QuantumGate::Hadamard, &[1]).unwrap();
+        let bases = [MeasurementBasis::Z, MeasurementBasis::X];
+        // Qubit 0 is |0⟩ in Z and qubit 1 is |+⟩ in X: outcome is certain
+        let outcome = state
+            .measure_in_bases("m".to_string(), &bases, &mut qrng)
+            .unwrap();
+        assert_eq!(outcome, vec![0, 0]);
+        let repeat = state
+            .measure_in_bases("m2".to_string(), &bases, &mut qrng)
+            .unwrap();
+        assert_eq!(repeat, outcome);
+        assert!(state
+            .measure_in_bases("bad".to_string(), &bases[..1], &mut qrng)
+            .is_err());
+    }
+
+    #[test]
+    fn test_partial_measurement_keeps_entanglement() {
+        let mut qrng = QRNG::from_seed([3; 32]);
+        let inv_sqrt2 = 1.0 / 2.0_f64.sqrt();
+        for i in 0..20 {
+            // Bell pair on qubits 0 and 1, qubit 2 in |+⟩
+            let mut state = QuantumState::new("bell_plus".to_string(), 3);
+            state.apply_gate(QuantumGate::Hadamard, &[0]).unwrap();
+            state.apply_gate(QuantumGate::CNOT, &[0, 1]).unwrap();
+            state.apply_gate(QuantumGate::Hadamard, &[2]).unwrap();
+
+            let measured = state
+                .measure_qubits(format!("m{}", i), &[2], &mut qrng)
+                .unwrap();
+            assert!((measured.probability - 0.5).abs() < 1e-12);
+            let bell = &measured.conditional_state;
+            assert_eq!(bell.qubit_count, 2);
+            for (amplitude, expected) in bell.amplitudes.iter().zip([inv_sqrt2, 0.0, 0.0, inv_sqrt2]) {
+                assert!((amplitude - expected).abs() < 1e-12);
+            }
+            assert!((bell.fidelity - 1.0).abs() < 1e-12);
+            assert!((state.fidelity - 1.0).abs() < 1e-12);
+            // Only the branch matching the outcome survives in the full state
+            let branch = (measured.outcome[0] as usize) << 2;
+            assert!((state.amplitudes[branch] - inv_sqrt2).abs() < 1e-12);
+            assert!((state.amplitudes[branch | 0b011] - inv_sqrt2).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_partial_measurement_of_ghz_state() {
+        let mut qrng = QRNG::from_seed([5; 32]);
+        let mut seen = [false; 2];
+        for i in 0..20 {
+            let mut state = QuantumState::new("ghz".to_string(), 3);
+            state.apply_gate(QuantumGate::Hadamard, &[0]).unwrap();
+            state.apply_gate(QuantumGate::CNOT, &[0, 1]).unwrap();
+            state.apply_gate(QuantumGate::CNOT, &[0, 2]).unwrap();
+
+            let measured = state
+                .measure_qubits(format!("m{}", i), &[1], &mut qrng)
+                .unwrap();
+            let bit = measured.outcome[0] as usize;
+            seen[bit] = true;
+            // Qubits 0 and 2 are left in |bb⟩
+            let expected = if bit == 1 { 0b11 } else { 0 };
+            assert!((measured.conditional_state.amplitudes[expected] - 1.0).abs() < 1e-12);
+
+            let rest = state
+                .measure_qubits(format!("r{}", i), &[2, 0], &mut qrng)
+                .unwrap();
+            assert_eq!(rest.outcome, vec![bit as u8; 2]);
+            assert!((rest.probability - 1.0).abs() < 1e-12);
+            // The parent keeps all three qubits, so the already collapsed
+            // qubit 1 is what remains unmeasured
+            assert_eq!(rest.conditional_state.qubit_count, 1);
+            assert!((rest.conditional_state.amplitudes[bit] - 1.0).abs() < 1e-12);
+        }
+        assert_eq!(seen, [true, true]);
+
+        let mut state = QuantumState::new("errors".to_string(), 2);
+        assert!(state.measure_qubits("e".to_string(), &[], &mut qrng).is_err());
+        assert!(state.measure_qubits("e".to_string(), &[2], &mut qrng).is_err());
+        assert!(state.measure_qubits("e".to_string(), &[1, 1], &mut qrng).is_err());
+    }
+
+    #[test]
+    fn test_operation_count_decoherence_lowers_fidelity() {
+        let noise = NoiseModel {
+            t1: 40.0,
+            t2: 20.0,
+            clock: DecoherenceClock::OperationCount,
+            refresh_threshold: 0.9,
+        };
+        let mut state = QuantumState::new("noisy".to_string(), 2);
+        state.enable_decoherence(noise.clone());
+        assert_eq!(state.get_fidelity(), 1.0);
+
+        let mut previous = 1.0;
+        for _ in 0..10 {
+            state.apply_gate(QuantumGate::PauliX, &[0]).unwrap();
+            assert!(state.get_fidelity() < previous);
+            previous = state.get_fidelity();
+        }
+        let expected = noise.qubit_fidelity(10.0).powi(2);
+        assert!((state.get_fidelity() - expected).abs() < 1e-12);
+        assert!((state.coherence() - (-0.5f64).exp()).abs() < 1e-12);
+
+        // Noise-free states keep perfect fidelity
+        let mut ideal = QuantumState::new("ideal".to_string(), 2);
+        ideal.apply_gate(QuantumGate::PauliX, &[0]).unwrap();
+        assert_eq!(ideal.get_fidelity(), 1.0);
+        assert_eq!(ideal.coherence(), 1.0);
+    }
+
+    #[test]
+    fn test_wall_clock_decoherence_advances_while_idle() {
+        let mut state = QuantumState::new("idle".to_string(), 1);
+        state.enable_decoherence(NoiseModel {
+            t1: 0.2,
+            t2: 0.1,
+            ..Default::default()
+        });
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        // Nothing touched the state, so the cached fidelity is stale
+        assert!(state.get_fidelity() > 0.999);
+        let fidelity = state.advance_decoherence();
+        assert!(fidelity < 0.9);
+        assert!(state.coherence() < (-0.5f64).exp());
+    }
+
+    #[tokio::test]
+    async fn test_decohered_state_is_refreshed() {
+        let core = QuantumCore::with_config(QuantumConfig {
+            noise: Some(NoiseModel {
+                t1: 20.0,
+                t2: 10.0,
+                clock: DecoherenceClock::OperationCount,
+                refresh_threshold: 0.5,
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        core.create_comm_state("qkd".to_string(), 2).unwrap();
+        core.create_entangled_state("qkd").unwrap();
+        assert!(!core.refresh_if_decohered("qkd").unwrap());
+
+        // Each draw is two superposition and measurement rounds, eighteen operations in all
+        for _ in 0..4 {
+            core.generate_quantum_random("qkd", 2).unwrap();
+        }
+        assert!(core.coherence("qkd").unwrap() < 0.5);
+        let decayed = core.get_state_info("qkd").unwrap().fidelity;
+
+        assert!(core.refresh_if_decohered("qkd").unwrap());
+        let state = core.get_state_info("qkd").unwrap();
+        assert!(state.fidelity > decayed);
+        assert!(bell_overlap(&state, 0, 1) > 0.999);
+        assert!((core.coherence("qkd").unwrap() - (-0.2f64).exp()).abs() < 1e-12);
+        assert_eq!(core.get_system_status()["entanglement_refreshes"], 1);
+
+        let ideal = QuantumCore::new(4).await.unwrap();
+        ideal.create_comm_state("qkd".to_string(), 2).unwrap();
+        assert!(!ideal.refresh_if_decohered("qkd").unwrap());
+        assert_eq!(ideal.coherence("qkd").unwrap(), 1.0);
+    }
+}
@@ -0,0 +1,433 @@
+//! # Quantum Journal - Reproducible Traces of Quantum Operations
+//!
+//! Records every gate, superposition and measurement applied to a quantum
+//! state, with timestamps and measurement outcomes, so QKD sessions can be
+//! audited afterwards and bugs replayed exactly.
+//!
+//! ## Journaling
+//!
+//! - **Per State**: Each state keeps its own ordered trace; traces of
+//!   collected states are retired to the core and remain exportable
+//! - **Deterministic Mode**: With a seed configured the QRNG is seeded from
+//!   it and every random draw records its position in the RNG stream, so the
+//!   same seed and operations reproduce the same outcomes
+//! - **Bounded**: At most `max_entries_per_state` entries are kept per state
+//!   and `max_retired_states` retired traces per core; older ones are counted
+//!   as dropped
+//! - **Export**: JSON for tooling and audit archives, OpenQASM 2.0 with
+//!   timestamps and outcomes as comments for replay in circuit simulators
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::quantum_core::QuantumCore;
+//! use quantum_forge_secure_comms::quantum_journal::JournalConfig;
+//!
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! let core = QuantumCore::new(4).await?;
+//! core.enable_journal(JournalConfig {
+//!     deterministic_seed: Some(42),
+//!     ..Default::default()
+//! });
+//! core.create_comm_state("session".to_string(), 2)?;
+//! core.create_entangled_state("session")?;
+//! core.generate_quantum_random("session", 2)?;
+//!
+//! let journal = core.journal();
+//! println!("{}", journal.to_qasm("session").unwrap());
+//! println!("{}", journal.to_json()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::memory_profile::MemoryFootprint;
+use crate::quantum_core::{MeasurementBasis, QuantumGate};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Journal settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalConfig {
+    /// Seed for a reproducible QRNG; `None` keeps the entropy-seeded QRNG
+    pub deterministic_seed: Option<u64>,
+    /// Entries kept per state before the oldest are dropped
+    pub max_entries_per_state: usize,
+    /// Traces of collected states kept before the oldest are dropped
+    pub max_retired_states: usize,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            deterministic_seed: None,
+            max_entries_per_state: 10_000,
+            max_retired_states: 1024,
+        }
+    }
+}
+
+/// Operation recorded in a state's trace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JournalEvent {
+    /// State initialised to |00...0⟩
+    Created { qubit_count: u32 },
+    /// Gate applied to the listed qubits
+    Gate { gate: QuantumGate, qubits: Vec<u32> },
+    /// Uniform superposition with QRNG-drawn phases
+    Superposition { rng_word_pos: Option<u128> },
+    /// Born rule measurement of all qubits, outcome MSB first
+    Measurement {
+        measurement_id: String,
+        outcome: Vec<u8>,
+        rng_word_pos: Option<u128>,
+        /// Basis per qubit index; empty for the computational basis
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        bases: Vec<MeasurementBasis>,
+    },
+    /// Born rule measurement of some qubits, outcome in the listed order
+    PartialMeasurement {
+        measurement_id: String,
+        qubits: Vec<u32>,
+        outcome: Vec<u8>,
+        rng_word_pos: Option<u128>,
+    },
+    /// State removed by garbage collection or cleanup
+    Collected { reason: String },
+}
+
+/// Timestamped journal entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unix time in microseconds
+    pub timestamp_us: u64,
+    pub event: JournalEvent,
+}
+
+/// Trace of one quantum state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateJournal {
+    pub state_id: String,
+    pub qubit_count: u32,
+    pub entries: VecDeque<JournalEntry>,
+    /// Entries discarded because the trace was full
+    pub dropped: u64,
+    #[serde(skip, default = "default_max_entries")]
+    max_entries: usize,
+}
+
+fn default_max_entries() -> usize {
+    JournalConfig::default().max_entries_per_state
+}
+
+impl StateJournal {
+    /// Start an empty trace for a state
+    pub fn new(state_id: &str, qubit_count: u32, max_entries: usize) -> Self {
+        Self {
+            state_id: state_id.to_string(),
+            qubit_count,
+            entries: VecDeque::new(),
+            dropped: 0,
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    /// Append an event stamped with the current time
+    pub fn record(&mut self, event: JournalEvent) {
+        if self.entries.len() >= self.max_entries {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(JournalEntry {
+            timestamp_us: chrono::Utc::now().timestamp_micros() as u64,
+            event,
+        });
+    }
+
+    /// Recorded events without timestamps, for comparing replays
+    pub fn events(&self) -> Vec<&JournalEvent> {
+        self.entries.iter().map(|entry| &entry.event).collect()
+    }
+
+    /// OpenQASM 2.0 program of the trace with outcomes as comments
+    pub fn to_qasm(&self, seed: Option<u64>) -> String {
+        let n = self.qubit_count;
+        let mut lines = vec![
+            "OPENQASM 2.0;".to_string(),
+            "include \"qelib1.inc\";".to_string(),
+            format!("// state {} ({} qubits)", self.state_id, n),
+        ];
+        if let Some(seed) = seed {
+            lines.push(format!("// deterministic seed {}", seed));
+        }
+        if self.dropped > 0 {
+            lines.push(format!("// {} earlier entries dropped", self.dropped));
+        }
+        lines.push(format!("qreg q[{}];", n));
+        lines.push(format!("creg c[{}];", n));
+
+        for entry in &self.entries {
+            let t = entry.timestamp_us;
+            match &entry.event {
+                JournalEvent::Created { .. } => lines.push(format!("// t={} created", t)),
+                JournalEvent::Gate { gate, qubits } => {
+                    let operands: Vec<String> =
+                        qubits.iter().map(|q| format!("q[{}]", q)).collect();
+                    lines.push(format!(
+                        "{} {}; // t={}",
+                        qasm_gate(*gate),
+                        operands.join(","),
+                        t
+                    ));
+                }
+                JournalEvent::Superposition { rng_word_pos } => lines.push(format!(
+                    "// t={} uniform superposition with QRNG phases{}",
+                    t,
+                    rng_comment(*rng_word_pos)
+                )),
+                JournalEvent::Measurement {
+                    measurement_id,
+                    outcome,
+                    rng_word_pos,
+                    bases,
+                } => {
+                    // Rotate each basis onto Z before measuring and back afterwards
+                    let rotated: Vec<(usize, f64, f64)> = bases
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, basis)| **basis != MeasurementBasis::Z)
+                        .map(|(qubit, basis)| {
+                            let (theta, phi) = basis.bloch_angles();
+                            (qubit, theta, phi)
+                        })
+                        .collect();
+                    for (qubit, theta, phi) in &rotated {
+                        lines.push(format!(
+                            "u3({},0,{}) q[{}];",
+                            qasm_angle(-theta),
+                            qasm_angle(-phi),
+                            qubit
+                        ));
+                    }
+                    let bits: String = outcome.iter().map(|b| b.to_string()).collect();
+                    lines.push(format!(
+                        "measure q -> c; // t={} id={} outcome={}{}",
+                        t,
+                        measurement_id,
+                        bits,
+                        rng_comment(*rng_word_pos)
+                    ));
+                    for (qubit, theta, phi) in &rotated {
+                        lines.push(format!(
+                            "u3({},{},0) q[{}];",
+                            qasm_angle(*theta),
+                            qasm_angle(*phi),
+                            qubit
+                        ));
+                    }
+                }
+                JournalEvent::PartialMeasurement {
+                    measurement_id,
+                    qubits,
+                    outcome,
+                    rng_word_pos,
+                } => {
+                    let measures: Vec<String> = qubits
+                        .iter()
+                        .map(|q| format!("measure q[{}] -> c[{}];", q, q))
+                        .collect();
+                    let bits: String = outcome.iter().map(|b| b.to_string()).collect();
+                    lines.push(format!(
+                        "{} // t={} id={} outcome={}{}",
+                        measures.join(" "),
+                        t,
+                        measurement_id,
+                        bits,
+                        rng_comment(*rng_word_pos)
+                    ));
+                }
+                JournalEvent::Collected { reason } => {
+                    lines.push(format!("// t={} collected: {}", t, reason))
+                }
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+impl MemoryFootprint for StateJournal {
+    fn heap_bytes(&self) -> usize {
+        // Measurement ids and outcomes are small; count entries at their inline size
+        self.state_id.heap_bytes() + self.entries.capacity() * std::mem::size_of::<JournalEntry>()
+    }
+}
+
+fn qasm_gate(gate: QuantumGate) -> &'static str {
+    match gate {
+        QuantumGate::Hadamard => "h",
+        QuantumGate::PauliX => "x",
+        QuantumGate::PauliY => "y",
+        QuantumGate::PauliZ => "z",
+        QuantumGate::CNOT => "cx",
+        // π phase shift on |1⟩, the same rotation as Z
+        QuantumGate::Phase => "u1(pi)",
+        QuantumGate::TGate => "t",
+        QuantumGate::SGate => "s",
+    }
+}
+
+/// Angle in radians, without the sign of negative zero
+fn qasm_angle(angle: f64) -> String {
+    if angle == 0.0 {
+        "0".to_string()
+    } else {
+        angle.to_string()
+    }
+}
+
+fn rng_comment(rng_word_pos: Option<u128>) -> String {
+    rng_word_pos
+        .map(|pos| format!(", rng word {}", pos))
+        .unwrap_or_default()
+}
+
+/// 256-bit QRNG seed derived from a deterministic-mode seed
+pub fn seed_bytes(seed: u64) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"quantum_journal_seed");
+    hasher.update(seed.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Exportable journal of a quantum core
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuantumJournal {
+    /// Seed in deterministic mode
+    pub seed: Option<u64>,
+    /// Traces of live states by state id
+    pub states: BTreeMap<String, StateJournal>,
+    /// Traces of collected states, oldest first
+    pub retired: Vec<StateJournal>,
+    /// Traces of collected states discarded by the retention limit
+    pub retired_dropped: u64,
+}
+
+impl QuantumJournal {
+    /// Trace of a live state, or of the latest collected state with this id
+    pub fn state(&self, state_id: &str) -> Option<&StateJournal> {
+        self.states.get(state_id).or_else(|| {
+            self.retired
+                .iter()
+                .rev()
+                .find(|journal| journal.state_id == state_id)
+        })
+    }
+
+    /// Journal as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            SecureCommsError::SystemError(format!("Journal serialization failed: {}", e))
+        })
+    }
+
+    /// Parse a journal exported with `to_json`
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| SecureCommsError::Validation(format!("Invalid journal: {}", e)))
+    }
+
+    /// OpenQASM trace of one state
+    pub fn to_qasm(&self, state_id: &str) -> Option<String> {
+        self.state(state_id)
+            .map(|journal| journal.to_qasm(self.seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> StateJournal {
+        let mut journal = StateJournal::new("s", 2, 10);
+        journal.record(JournalEvent::Created { qubit_count: 2 });
+        journal.record(JournalEvent::Gate {
+            gate: QuantumGate::Hadamard,
+            qubits: vec![0],
+        });
+        journal.record(JournalEvent::Gate {
+            gate: QuantumGate::CNOT,
+            qubits: vec![0, 1],
+        });
+        journal.record(JournalEvent::Measurement {
+            measurement_id: "m1".to_string(),
+            outcome: vec![1, 1],
+            rng_word_pos: Some(16),
+            bases: vec![],
+        });
+        journal
+    }
+
+    #[test]
+    fn test_qasm_export() {
+        let qasm = sample().to_qasm(Some(7));
+        assert!(qasm.starts_with("OPENQASM 2.0;\ninclude \"qelib1.inc\";\n"));
+        assert!(qasm.contains("// deterministic seed 7"));
+        assert!(qasm.contains("qreg q[2];"));
+        assert!(qasm.contains("h q[0]; // t="));
+        assert!(qasm.contains("cx q[0],q[1]; // t="));
+        assert!(qasm.contains("outcome=11, rng word 16"));
+        assert!(!qasm.contains("u3("));
+
+        let mut journal = StateJournal::new("s", 2, 10);
+        journal.record(JournalEvent::Measurement {
+            measurement_id: "m2".to_string(),
+            outcome: vec![0, 1],
+            rng_word_pos: None,
+            bases: vec![MeasurementBasis::Z, MeasurementBasis::X],
+        });
+        journal.record(JournalEvent::PartialMeasurement {
+            measurement_id: "m3".to_string(),
+            qubits: vec![1],
+            outcome: vec![1],
+            rng_word_pos: None,
+        });
+        let qasm = journal.to_qasm(None);
+        assert!(qasm.contains("measure q[1] -> c[1]; // t="));
+        let pre = qasm.find("u3(-1.5707963267948966,0,0) q[1];").unwrap();
+        let post = qasm.find("u3(1.5707963267948966,0,0) q[1];").unwrap();
+        assert!(pre < qasm.find("measure q -> c;").unwrap());
+        assert!(post > qasm.find("measure q -> c;").unwrap());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut journal = QuantumJournal {
+            seed: Some(7),
+            ..Default::default()
+        };
+        journal.states.insert("s".to_string(), sample());
+        let parsed = QuantumJournal::from_json(&journal.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.seed, Some(7));
+        assert_eq!(parsed.state("s").unwrap().events(), sample().events());
+        assert!(QuantumJournal::from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_trace_is_bounded() {
+        let mut journal = StateJournal::new("s", 1, 3);
+        for _ in 0..5 {
+            journal.record(JournalEvent::Gate {
+                gate: QuantumGate::PauliX,
+                qubits: vec![0],
+            });
+        }
+        assert_eq!(journal.entries.len(), 3);
+        assert_eq!(journal.dropped, 2);
+        assert!(journal
+            .to_qasm(None)
+            .contains("// 2 earlier entries dropped"));
+        assert_eq!(seed_bytes(1), seed_bytes(1));
+        assert_ne!(seed_bytes(1), seed_bytes(2));
+    }
+}
@@ -0,0 +1,647 @@
+//! # Quantum Oblivious Transfer - Experimental 1-out-of-2 OT
+//!
+//! **Experimental.** Compiled only with the `experimental-ot` feature (and
+//! in tests). A sender holds two messages; a receiver learns exactly the one
+//! it chooses and the sender learns nothing about the choice. Oblivious
+//! transfer is the building block for privacy-preserving validator
+//! computations such as sealed-bid auctions and private voting.
+//!
+//! ## Protocol
+//!
+//! The BBCS protocol (Bennett, Brassard, Crépeau and Skubiszewska, 1991)
+//! with the receiver's measurements bound by commitments:
+//!
+//! 1. **Qubits**: The sender prepares `qubits` single-qubit states, each a
+//!    random bit in a random Z or X basis, on the quantum layer.
+//! 2. **Commit**: The receiver measures each in a random basis and commits
+//!    to every (basis, outcome) pair.
+//! 3. **Check**: The sender picks a random `test_fraction` of positions; the
+//!    receiver opens those commitments. Where the bases agree the outcomes
+//!    must match the sender's bits, up to `max_error_rate`. A receiver that
+//!    stored the qubits instead of measuring them cannot open consistently.
+//! 4. **Bases**: The sender reveals its bases.
+//! 5. **Index sets**: The receiver sorts the untested positions into those
+//!    where the bases agreed, whose bits it knows, and those where they did
+//!    not. It sends the known set as set `choice` and the other as set
+//!    `1 - choice`, trimmed to equal size. The sender cannot tell which set
+//!    is which.
+//! 6. **Transfer**: The sender derives one key from its bits on each set and
+//!    sends both messages encrypted under them. The receiver can derive only
+//!    the key of the set it knows.
+//!
+//! ## Commitments
+//!
+//! Commitments are SHA3-256 hashes of a fresh 32-byte nonce and the
+//! committed value. Hash commitments stay binding and hiding against
+//! quantum adversaries, unlike commitments built on discrete logarithms.
+//!
+//! ## Limitations
+//!
+//! The quantum channel is the in-process simulator and is noiseless; the
+//! protocol has no information reconciliation, so any error on a position
+//! of the chosen set corrupts the received message. Messages must be of
+//! equal length, which the ciphertexts reveal.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::QRNG;
+//! use quantum_forge_secure_comms::quantum_ot::{OtConfig, OtReceiver, OtSender};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = OtConfig::default();
+//! let messages = [b"bid:0042".to_vec(), b"bid:0097".to_vec()];
+//! let (mut sender, qubits) = OtSender::new(&config, messages, QRNG::from_seed([1; 32]))?;
+//! let mut receiver = OtReceiver::new(&config, true, QRNG::from_seed([2; 32]))?;
+//!
+//! let commitments = receiver.measure(qubits)?;
+//! let challenge = sender.challenge(commitments)?;
+//! let bases = sender.check_openings(&receiver.open(&challenge)?)?;
+//! let transfer = sender.transfer(&receiver.index_sets(&bases)?)?;
+//! assert_eq!(receiver.receive(&transfer)?, b"bid:0097");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::constant_time::ct_eq;
+use crate::crypto_protocols::QRNG;
+use crate::quantum_core::{MeasurementBasis, QuantumGate, QuantumState};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Shake256};
+use std::collections::BTreeSet;
+
+/// Domain separation of commitment hashes
+const COMMIT_DOMAIN: &[u8] = b"quantum_forge_ot_commit_v1";
+
+/// Domain separation of transfer key derivation
+const KEY_DOMAIN: &[u8] = b"quantum_forge_ot_key_v1";
+
+/// Parameters of one oblivious transfer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtConfig {
+    /// Qubits the sender prepares
+    pub qubits: usize,
+    /// Fraction of positions opened to check the receiver measured
+    pub test_fraction: f64,
+    /// Largest mismatch rate tolerated on tested positions with agreeing bases
+    pub max_error_rate: f64,
+    /// Smallest index set a key may be derived from
+    pub min_key_bits: usize,
+}
+
+impl Default for OtConfig {
+    fn default() -> Self {
+        Self {
+            qubits: 512,
+            test_fraction: 0.25,
+            max_error_rate: 0.0,
+            min_key_bits: 128,
+        }
+    }
+}
+
+impl OtConfig {
+    /// Check that an honest run leaves enough positions for both keys
+    pub fn validate(&self) -> Result<()> {
+        if !(0.05..=0.5).contains(&self.test_fraction) {
+            return Err(SecureCommsError::Configuration(
+                "OT test_fraction must be between 0.05 and 0.5".to_string(),
+            ));
+        }
+        if !(0.0..0.11).contains(&self.max_error_rate) {
+            return Err(SecureCommsError::Configuration(
+                "OT max_error_rate must be below 0.11".to_string(),
+            ));
+        }
+        if self.min_key_bits < 64 {
+            return Err(SecureCommsError::Configuration(
+                "OT min_key_bits must be at least 64".to_string(),
+            ));
+        }
+        // Each set gets about half the untested positions; leave slack
+        let expected = self.qubits as f64 * (1.0 - self.test_fraction) / 2.0;
+        if expected < self.min_key_bits as f64 * 1.25 {
+            return Err(SecureCommsError::Configuration(format!(
+                "OT with {} qubits leaves about {} positions per set, below {} with margin",
+                self.qubits, expected as usize, self.min_key_bits
+            )));
+        }
+        Ok(())
+    }
+
+    fn tested(&self) -> usize {
+        (self.qubits as f64 * self.test_fraction).round() as usize
+    }
+}
+
+/// Hash commitment to a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commitment(pub [u8; 32]);
+
+/// Nonce and value opening a commitment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Opening {
+    pub nonce: [u8; 32],
+    pub value: Vec<u8>,
+}
+
+/// Commit to `value` with a fresh nonce
+pub fn commit(value: &[u8], qrng: &mut QRNG) -> Result<(Commitment, Opening)> {
+    let mut nonce = [0u8; 32];
+    nonce.copy_from_slice(&qrng.generate_bytes(32)?);
+    let opening = Opening {
+        nonce,
+        value: value.to_vec(),
+    };
+    Ok((Commitment(commitment_digest(&opening)), opening))
+}
+
+impl Commitment {
+    /// Whether `opening` opens this commitment
+    pub fn verify(&self, opening: &Opening) -> bool {
+        ct_eq(&self.0, &commitment_digest(opening))
+    }
+}
+
+/// Receiver's commitments to its bases and outcomes, by position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtCommitments(pub Vec<Commitment>);
+
+/// Positions the sender wants opened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtChallenge(pub Vec<usize>);
+
+/// Openings of the challenged positions, in challenge order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtOpenings(pub Vec<Opening>);
+
+/// Sender's bases, true for X, by position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtBases(pub Vec<bool>);
+
+/// Receiver's two index sets; it knows the sender's bits on one of them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtIndexSets(pub [Vec<usize>; 2]);
+
+/// Both messages, each encrypted under the key of its index set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtTransfer(pub [Vec<u8>; 2]);
+
+/// Step the sender expects next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SenderStep {
+    Commitments,
+    Openings,
+    IndexSets,
+    Done,
+}
+
+/// Sending party, holding both messages
+#[derive(Debug)]
+pub struct OtSender {
+    config: OtConfig,
+    messages: [Vec<u8>; 2],
+    bits: Vec<bool>,
+    bases: Vec<bool>,
+    qrng: QRNG,
+    commitments: Vec<Commitment>,
+    tested: Vec<usize>,
+    step: SenderStep,
+}
+
+impl OtSender {
+    /// Start a transfer of `messages`, returning the qubits for the receiver
+    ///
+    /// Fails with `SecureCommsError::Validation` if the messages differ in
+    /// length or are empty.
+    pub fn new(
+        config: &OtConfig,
+        messages: [Vec<u8>; 2],
+        mut qrng: QRNG,
+    ) -> Result<(Self, Vec<QuantumState>)> {
+        config.validate()?;
+        if messages[0].is_empty() || messages[0].len() != messages[1].len() {
+            return Err(SecureCommsError::Validation(
+                "OT messages must be non-empty and of equal length".to_string(),
+            ));
+        }
+        let bits = random_bits(config.qubits, &mut qrng)?;
+        let bases = random_bits(config.qubits, &mut qrng)?;
+        let qubits = bits
+            .iter()
+            .zip(&bases)
+            .enumerate()
+            .map(|(position, (&bit, &x_basis))| prepare(position, bit, x_basis))
+            .collect::<Result<Vec<_>>>()?;
+        let sender = Self {
+            config: config.clone(),
+            messages,
+            bits,
+            bases,
+            qrng,
+            commitments: Vec::new(),
+            tested: Vec::new(),
+            step: SenderStep::Commitments,
+        };
+        Ok((sender, qubits))
+    }
+
+    /// Take the receiver's commitments and choose positions to test
+    pub fn challenge(&mut self, commitments: OtCommitments) -> Result<OtChallenge> {
+        self.expect(SenderStep::Commitments)?;
+        if commitments.0.len() != self.config.qubits {
+            return Err(SecureCommsError::Validation(format!(
+                "Expected {} OT commitments, got {}",
+                self.config.qubits,
+                commitments.0.len()
+            )));
+        }
+        let mut positions: Vec<usize> = (0..self.config.qubits).collect();
+        for i in (1..positions.len()).rev() {
+            let j = self.qrng.gen_range(0..i as u64 + 1) as usize;
+            positions.swap(i, j);
+        }
+        positions.truncate(self.config.tested());
+        positions.sort_unstable();
+        self.commitments = commitments.0;
+        self.tested = positions.clone();
+        self.step = SenderStep::Openings;
+        Ok(OtChallenge(positions))
+    }
+
+    /// Check the opened positions and reveal the bases
+    ///
+    /// Fails with `SecureCommsError::AuthenticationFailed` when an opening
+    /// does not match its commitment or too many outcomes disagree with the
+    /// sender's bits, which means the receiver did not measure as committed.
+    pub fn check_openings(&mut self, openings: &OtOpenings) -> Result<OtBases> {
+        self.expect(SenderStep::Openings)?;
+        if openings.0.len() != self.tested.len() {
+            self.step = SenderStep::Done;
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        let (mut compared, mut errors) = (0usize, 0usize);
+        for (&position, opening) in self.tested.iter().zip(&openings.0) {
+            let (x_basis, bit) = match opening.value.as_slice() {
+                &[basis, bit] if self.commitments[position].verify(opening) => {
+                    (basis == 1, bit == 1)
+                }
+                _ => {
+                    self.step = SenderStep::Done;
+                    return Err(SecureCommsError::AuthenticationFailed);
+                }
+            };
+            if x_basis == self.bases[position] {
+                compared += 1;
+                if bit != self.bits[position] {
+                    errors += 1;
+                }
+            }
+        }
+        if errors as f64 > self.config.max_error_rate * compared as f64 {
+            self.step = SenderStep::Done;
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        self.step = SenderStep::IndexSets;
+        Ok(OtBases(self.bases.clone()))
+    }
+
+    /// Encrypt both messages under the keys of the receiver's index sets
+    ///
+    /// The sets must be disjoint, of equal size, at least `min_key_bits`
+    /// long and free of tested positions; otherwise this fails with
+    /// `SecureCommsError::Validation`.
+    pub fn transfer(&mut self, sets: &OtIndexSets) -> Result<OtTransfer> {
+        self.expect(SenderStep::IndexSets)?;
+        self.step = SenderStep::Done;
+        let [first, second] = &sets.0;
+        if first.len() != second.len() || first.len() < self.config.min_key_bits {
+            return Err(SecureCommsError::Validation(format!(
+                "OT index sets of {} and {} positions, need equal sizes of at least {}",
+                first.len(),
+                second.len(),
+                self.config.min_key_bits
+            )));
+        }
+        let tested: BTreeSet<usize> = self.tested.iter().copied().collect();
+        let mut seen = BTreeSet::new();
+        for &position in first.iter().chain(second) {
+            if position >= self.config.qubits
+                || tested.contains(&position)
+                || !seen.insert(position)
+            {
+                return Err(SecureCommsError::Validation(format!(
+                    "OT index set position {} is out of range, tested or repeated",
+                    position
+                )));
+            }
+        }
+        let encrypt = |set: &[usize], message: &[u8]| {
+            let bits: Vec<bool> = set.iter().map(|&position| self.bits[position]).collect();
+            xor_with_key(message, &bits)
+        };
+        Ok(OtTransfer([
+            encrypt(first, &self.messages[0]),
+            encrypt(second, &self.messages[1]),
+        ]))
+    }
+
+    fn expect(&self, step: SenderStep) -> Result<()> {
+        if self.step != step {
+            return Err(SecureCommsError::Validation(format!(
+                "OT sender expected {:?}, is at {:?}",
+                step, self.step
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Receiving party, holding the choice bit
+#[derive(Debug)]
+pub struct OtReceiver {
+    config: OtConfig,
+    choice: bool,
+    qrng: QRNG,
+    bases: Vec<bool>,
+    outcomes: Vec<bool>,
+    openings: Vec<Opening>,
+    tested: BTreeSet<usize>,
+    known: Vec<usize>,
+}
+
+impl OtReceiver {
+    /// Receiver that will learn message `choice` (false for the first)
+    pub fn new(config: &OtConfig, choice: bool, qrng: QRNG) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config: config.clone(),
+            choice,
+            qrng,
+            bases: Vec::new(),
+            outcomes: Vec::new(),
+            openings: Vec::new(),
+            tested: BTreeSet::new(),
+            known: Vec::new(),
+        })
+    }
+
+    /// Measure the sender's qubits in random bases and commit to the results
+    pub fn measure(&mut self, qubits: Vec<QuantumState>) -> Result<OtCommitments> {
+        if qubits.len() != self.config.qubits {
+            return Err(SecureCommsError::Validation(format!(
+                "Expected {} OT qubits, got {}",
+                self.config.qubits,
+                qubits.len()
+            )));
+        }
+        self.bases = random_bits(qubits.len(), &mut self.qrng)?;
+        let mut commitments = Vec::with_capacity(qubits.len());
+        for (position, mut qubit) in qubits.into_iter().enumerate() {
+            let basis = if self.bases[position] {
+                MeasurementBasis::X
+            } else {
+                MeasurementBasis::Z
+            };
+            let outcome = qubit.measure_in_bases(
+                format!("ot_measure_{}", position),
+                &[basis],
+                &mut self.qrng,
+            )?[0]
+                == 1;
+            self.outcomes.push(outcome);
+            let (commitment, opening) = commit(
+                &[u8::from(self.bases[position]), u8::from(outcome)],
+                &mut self.qrng,
+            )?;
+            commitments.push(commitment);
+            self.openings.push(opening);
+        }
+        Ok(OtCommitments(commitments))
+    }
+
+    /// Open the commitments the sender challenged
+    pub fn open(&mut self, challenge: &OtChallenge) -> Result<OtOpenings> {
+        let mut openings = Vec::with_capacity(challenge.0.len());
+        for &position in &challenge.0 {
+            let opening = self.openings.get(position).ok_or_else(|| {
+                SecureCommsError::Validation(format!(
+                    "OT challenge position {} out of range",
+                    position
+                ))
+            })?;
+            openings.push(opening.clone());
+            self.tested.insert(position);
+        }
+        Ok(OtOpenings(openings))
+    }
+
+    /// Split the untested positions by whether the bases agreed
+    pub fn index_sets(&mut self, bases: &OtBases) -> Result<OtIndexSets> {
+        if bases.0.len() != self.bases.len() {
+            return Err(SecureCommsError::Validation(format!(
+                "Expected {} OT bases, got {}",
+                self.bases.len(),
+                bases.0.len()
+            )));
+        }
+        let (mut known, mut unknown): (Vec<usize>, Vec<usize>) = (0..self.bases.len())
+            .filter(|position| !self.tested.contains(position))
+            .partition(|&position| bases.0[position] == self.bases[position]);
+        let size = known.len().min(unknown.len());
+        if size < self.config.min_key_bits {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Only {} OT positions per set, need {}",
+                size, self.config.min_key_bits
+            )));
+        }
+        known.truncate(size);
+        unknown.truncate(size);
+        self.known = known.clone();
+        Ok(OtIndexSets(if self.choice {
+            [unknown, known]
+        } else {
+            [known, unknown]
+        }))
+    }
+
+    /// Decrypt the chosen message
+    pub fn receive(&self, transfer: &OtTransfer) -> Result<Vec<u8>> {
+        if self.known.is_empty() {
+            return Err(SecureCommsError::Validation(
+                "OT receiver has not sent its index sets".to_string(),
+            ));
+        }
+        let bits: Vec<bool> = self
+            .known
+            .iter()
+            .map(|&position| self.outcomes[position])
+            .collect();
+        Ok(xor_with_key(&transfer.0[usize::from(self.choice)], &bits))
+    }
+}
+
+/// Run both parties in process and return the message `choice` selects
+pub fn run_oblivious_transfer(
+    config: &OtConfig,
+    messages: [Vec<u8>; 2],
+    choice: bool,
+    sender_qrng: QRNG,
+    receiver_qrng: QRNG,
+) -> Result<Vec<u8>> {
+    let (mut sender, qubits) = OtSender::new(config, messages, sender_qrng)?;
+    let mut receiver = OtReceiver::new(config, choice, receiver_qrng)?;
+    let commitments = receiver.measure(qubits)?;
+    let challenge = sender.challenge(commitments)?;
+    let bases = sender.check_openings(&receiver.open(&challenge)?)?;
+    let transfer = sender.transfer(&receiver.index_sets(&bases)?)?;
+    receiver.receive(&transfer)
+}
+
+/// Qubit holding `bit` in the Z basis, or in the X basis if `x_basis`
+fn prepare(position: usize, bit: bool, x_basis: bool) -> Result<QuantumState> {
+    let mut qubit = QuantumState::new(format!("ot_qubit_{}", position), 1);
+    if x_basis {
+        // |+⟩, then a phase flip for |−⟩
+        qubit.apply_gate(QuantumGate::Hadamard, &[0])?;
+        if bit {
+            qubit.apply_gate(QuantumGate::PauliZ, &[0])?;
+        }
+    } else if bit {
+        qubit.apply_gate(QuantumGate::PauliX, &[0])?;
+    }
+    Ok(qubit)
+}
+
+fn random_bits(count: usize, qrng: &mut QRNG) -> Result<Vec<bool>> {
+    let bytes = qrng.generate_bytes(count.div_ceil(8))?;
+    Ok((0..count)
+        .map(|i| (bytes[i / 8] >> (i % 8)) & 1 == 1)
+        .collect())
+}
+
+fn commitment_digest(opening: &Opening) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    Digest::update(&mut hasher, COMMIT_DOMAIN);
+    Digest::update(&mut hasher, opening.nonce);
+    Digest::update(&mut hasher, &opening.value);
+    hasher.finalize().into()
+}
+
+/// XOR `message` with a SHAKE256 stream keyed by `bits`
+fn xor_with_key(message: &[u8], bits: &[bool]) -> Vec<u8> {
+    let mut shake = Shake256::default();
+    shake.update(KEY_DOMAIN);
+    shake.update(&(bits.len() as u64).to_be_bytes());
+    let packed: Vec<u8> = bits.iter().map(|&bit| u8::from(bit)).collect();
+    shake.update(&packed);
+    let mut stream = vec![0u8; message.len()];
+    shake.finalize_xof().read(&mut stream);
+    message.iter().zip(stream).map(|(m, k)| m ^ k).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages() -> [Vec<u8>; 2] {
+        [b"bid:0042".to_vec(), b"bid:0097".to_vec()]
+    }
+
+    #[test]
+    fn test_receiver_learns_only_the_chosen_message() {
+        let config = OtConfig::default();
+        for (seed, choice) in [(1u8, false), (2, true)] {
+            let (mut sender, qubits) =
+                OtSender::new(&config, messages(), QRNG::from_seed([seed; 32])).unwrap();
+            let mut receiver =
+                OtReceiver::new(&config, choice, QRNG::from_seed([seed + 10; 32])).unwrap();
+            let commitments = receiver.measure(qubits).unwrap();
+            let challenge = sender.challenge(commitments).unwrap();
+            assert_eq!(challenge.0.len(), 128);
+            let bases = sender
+                .check_openings(&receiver.open(&challenge).unwrap())
+                .unwrap();
+            let sets = receiver.index_sets(&bases).unwrap();
+            let transfer = sender.transfer(&sets).unwrap();
+            assert_eq!(
+                receiver.receive(&transfer).unwrap(),
+                messages()[usize::from(choice)]
+            );
+
+            // With its outcomes on the other set the receiver gets noise
+            let other = &sets.0[usize::from(!choice)];
+            let guessed: Vec<bool> = other.iter().map(|&p| receiver.outcomes[p]).collect();
+            let garbled = xor_with_key(&transfer.0[usize::from(!choice)], &guessed);
+            assert_ne!(garbled, messages()[usize::from(!choice)]);
+            // A finished sender accepts no further steps
+            assert!(sender.transfer(&sets).is_err());
+        }
+    }
+
+    #[test]
+    fn test_cheating_receiver_is_caught() {
+        let config = OtConfig::default();
+        let (mut sender, qubits) =
+            OtSender::new(&config, messages(), QRNG::from_seed([3; 32])).unwrap();
+        let mut receiver = OtReceiver::new(&config, false, QRNG::from_seed([4; 32])).unwrap();
+        let commitments = receiver.measure(qubits).unwrap();
+        let challenge = sender.challenge(commitments).unwrap();
+
+        // Flipping committed outcomes breaks the openings
+        let mut openings = receiver.open(&challenge).unwrap();
+        openings.0[0].value[1] ^= 1;
+        assert!(matches!(
+            sender.check_openings(&openings),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Index sets reusing tested positions are refused
+        let (mut sender, qubits) =
+            OtSender::new(&config, messages(), QRNG::from_seed([5; 32])).unwrap();
+        let mut receiver = OtReceiver::new(&config, false, QRNG::from_seed([6; 32])).unwrap();
+        let challenge = sender.challenge(receiver.measure(qubits).unwrap()).unwrap();
+        let bases = sender
+            .check_openings(&receiver.open(&challenge).unwrap())
+            .unwrap();
+        let mut sets = receiver.index_sets(&bases).unwrap();
+        sets.0[1][0] = challenge.0[0];
+        assert!(matches!(
+            sender.transfer(&sets),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_commitments_and_config_bounds() {
+        let mut qrng = QRNG::from_seed([7; 32]);
+        let (commitment, opening) = commit(&[1, 0], &mut qrng).unwrap();
+        assert!(commitment.verify(&opening));
+        let (again, _) = commit(&[1, 0], &mut qrng).unwrap();
+        assert_ne!(commitment, again);
+        let mut forged = opening.clone();
+        forged.value = vec![1, 1];
+        assert!(!commitment.verify(&forged));
+
+        assert!(OtConfig {
+            qubits: 256,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(OtConfig {
+            test_fraction: 0.9,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(OtSender::new(
+            &OtConfig::default(),
+            [b"short".to_vec(), b"longer".to_vec()],
+            QRNG::from_seed([8; 32])
+        )
+        .is_err());
+    }
+}
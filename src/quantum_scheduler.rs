@@ -0,0 +1,782 @@
+//! # Quantum Scheduler - Cost and Queue Aware Hardware Placement
+//!
+//! Decides for each quantum operation whether it runs on a hardware backend
+//! or on the physics simulation. Hardware is used when a registered backend
+//! can serve the operation right now: enough qubits, the required fidelity,
+//! a queue short enough to answer within the latency target and room left
+//! in the cost budget. Anything else runs on the simulation, which is always
+//! available and noise-free, and the reason is counted.
+//!
+//! ## Placement
+//!
+//! Each backend is checked in this order; when none accepts, the operation
+//! is simulated for the reason of the backend that got furthest:
+//!
+//! - **Qubits**: At least as many qubits as the operation uses
+//! - **Fidelity**: Calibrated fidelity at or above the requirement, taken
+//!   from the operation's per-backend estimates when it carries them
+//! - **Queue**: Fewer jobs waiting than `max_queue_depth`
+//! - **Latency**: `(queue + 1) × job_latency_ms` within the latency target
+//! - **Budget**: The job's cost fits what is left of the window's budget
+//!
+//! Of the backends that accept, the cheapest wins, then the quickest. Cost
+//! is reserved at placement and refunded if the backend fails, in which
+//! case the operation falls back to the simulation.
+//!
+//! ## Accounting
+//!
+//! [`BackendUsage`] tracks each backend's jobs, shots, spend, failures and
+//! latency; simulated operations are counted by [`SimulationReason`]. The
+//! budget starts afresh every `budget_window_seconds`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::quantum_core::{QuantumCircuit, QuantumState};
+//! use quantum_forge_secure_comms::quantum_scheduler::{
+//!     BackendProfile, OperationProfile, QuantumBackend, QuantumScheduler, SchedulingConfig,
+//! };
+//! use std::sync::Arc;
+//!
+//! struct Device;
+//!
+//! impl QuantumBackend for Device {
+//!     fn queue_depth(&self) -> usize {
+//!         2
+//!     }
+//!
+//!     fn execute(
+//!         &self,
+//!         circuit: &QuantumCircuit,
+//!         state: &mut QuantumState,
+//!     ) -> quantum_forge_secure_comms::Result<()> {
+//!         circuit.execute(state)
+//!     }
+//! }
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = SchedulingConfig {
+//!     cost_budget: 10.0,
+//!     ..Default::default()
+//! };
+//! let scheduler = QuantumScheduler::new(config, true)?;
+//! scheduler.register(
+//!     BackendProfile {
+//!         name: "ion_trap".to_string(),
+//!         qubits: 8,
+//!         fidelity: 0.995,
+//!         cost_per_job: 0.5,
+//!         cost_per_shot: 0.001,
+//!         job_latency_ms: 200,
+//!     },
+//!     Arc::new(Device),
+//! )?;
+//! let placement = scheduler.place(&OperationProfile::new(4, 100));
+//! println!("running on {:?}", placement);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::quantum_core::{QuantumCircuit, QuantumState};
+use crate::{Result, SecureCommsError};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Hardware placement limits shared by all operations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulingConfig {
+    /// Hardware spend allowed per budget window, in backend cost units
+    pub cost_budget: f64,
+    /// Length of the budget window
+    pub budget_window_seconds: u64,
+    /// Fidelity an operation needs unless it sets its own
+    pub min_fidelity: f64,
+    /// Longest expected wait for a hardware result unless an operation sets its own
+    pub latency_target_ms: u64,
+    /// Backends with this many jobs queued are passed over
+    pub max_queue_depth: usize,
+}
+
+impl Default for SchedulingConfig {
+    fn default() -> Self {
+        Self {
+            cost_budget: 0.0,
+            budget_window_seconds: 3_600,
+            min_fidelity: 0.99,
+            latency_target_ms: 1_000,
+            max_queue_depth: 16,
+        }
+    }
+}
+
+impl SchedulingConfig {
+    /// Check the budget, window and targets are usable
+    pub fn validate(&self) -> Result<()> {
+        if !(self.cost_budget >= 0.0 && self.cost_budget.is_finite()) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Quantum cost budget must be a non-negative amount, got {}",
+                self.cost_budget
+            )));
+        }
+        if !(self.min_fidelity > 0.0 && self.min_fidelity <= 1.0) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Quantum scheduling fidelity must be in (0, 1], got {}",
+                self.min_fidelity
+            )));
+        }
+        if self.budget_window_seconds == 0
+            || self.latency_target_ms == 0
+            || self.max_queue_depth == 0
+        {
+            return Err(SecureCommsError::Configuration(
+                "Quantum budget window, latency target and queue depth must be positive"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Static description of a hardware backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendProfile {
+    /// Unique backend name
+    pub name: String,
+    /// Qubits available to one job
+    pub qubits: u32,
+    /// Expected fidelity of a job's result
+    pub fidelity: f64,
+    /// Fixed cost of submitting a job
+    pub cost_per_job: f64,
+    /// Cost of each shot of a job
+    pub cost_per_shot: f64,
+    /// Time one queued job takes to run
+    pub job_latency_ms: u64,
+}
+
+impl BackendProfile {
+    /// Cost of a job of `shots` shots
+    pub fn cost(&self, shots: u32) -> f64 {
+        self.cost_per_job + self.cost_per_shot * shots as f64
+    }
+
+    /// Expected time to a result with `queue_depth` jobs ahead
+    pub fn expected_latency_ms(&self, queue_depth: usize) -> u64 {
+        self.job_latency_ms.saturating_mul(queue_depth as u64 + 1)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.name.is_empty() || self.qubits == 0 {
+            return Err(SecureCommsError::Configuration(
+                "Quantum backend needs a name and at least one qubit".to_string(),
+            ));
+        }
+        if !(self.fidelity > 0.0
+            && self.fidelity <= 1.0
+            && self.cost_per_job >= 0.0
+            && self.cost_per_job.is_finite()
+            && self.cost_per_shot >= 0.0
+            && self.cost_per_shot.is_finite())
+        {
+            return Err(SecureCommsError::Configuration(format!(
+                "Quantum backend {} needs a fidelity in (0, 1] and non-negative costs",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Quantum hardware able to run circuits
+pub trait QuantumBackend: Send + Sync {
+    /// Jobs queued on the device ahead of a new submission
+    fn queue_depth(&self) -> usize;
+
+    /// Run `circuit` on the device and write the result into `state`
+    ///
+    /// On error `state` must be left as it was, so the simulation can run
+    /// the circuit instead.
+    fn execute(&self, circuit: &QuantumCircuit, state: &mut QuantumState) -> Result<()>;
+}
+
+/// Requirements of one quantum operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationProfile {
+    /// Qubits the operation uses
+    pub qubits: u32,
+    /// Shots the operation needs
+    pub shots: u32,
+    /// Fidelity required instead of `SchedulingConfig::min_fidelity`
+    pub min_fidelity: Option<f64>,
+    /// Latency target instead of `SchedulingConfig::latency_target_ms`
+    pub latency_target_ms: Option<u64>,
+    /// Calibrated fidelity of this operation by backend, used instead of
+    /// the backend's static `BackendProfile::fidelity`
+    #[serde(default)]
+    pub fidelity_estimates: BTreeMap<String, f64>,
+}
+
+impl OperationProfile {
+    /// Operation on `qubits` qubits taking `shots` shots, with default targets
+    pub fn new(qubits: u32, shots: u32) -> Self {
+        Self {
+            qubits,
+            shots,
+            min_fidelity: None,
+            latency_target_ms: None,
+            fidelity_estimates: BTreeMap::new(),
+        }
+    }
+
+    /// Require at least `fidelity`
+    pub fn with_min_fidelity(mut self, fidelity: f64) -> Self {
+        self.min_fidelity = Some(fidelity);
+        self
+    }
+
+    /// Expect a result within `latency_ms`
+    pub fn with_latency_target(mut self, latency_ms: u64) -> Self {
+        self.latency_target_ms = Some(latency_ms);
+        self
+    }
+
+    /// Judge backends by calibrated fidelity estimates for this operation
+    pub fn with_fidelity_estimates(mut self, estimates: BTreeMap<String, f64>) -> Self {
+        self.fidelity_estimates = estimates;
+        self
+    }
+
+    /// Fidelity expected from `profile`'s backend
+    pub fn fidelity_on(&self, profile: &BackendProfile) -> f64 {
+        self.fidelity_estimates
+            .get(&profile.name)
+            .copied()
+            .unwrap_or(profile.fidelity)
+    }
+}
+
+/// Why an operation ran on the simulation, in placement check order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationReason {
+    /// Hardware use is switched off
+    HardwareDisabled,
+    /// No backend is registered
+    NoBackend,
+    TooFewQubits,
+    FidelityTooLow,
+    QueueTooDeep,
+    LatencyTooHigh,
+    OverBudget,
+    /// The chosen backend returned an error
+    BackendFailed,
+}
+
+impl SimulationReason {
+    /// Every reason
+    pub const ALL: [SimulationReason; 8] = [
+        SimulationReason::HardwareDisabled,
+        SimulationReason::NoBackend,
+        SimulationReason::TooFewQubits,
+        SimulationReason::FidelityTooLow,
+        SimulationReason::QueueTooDeep,
+        SimulationReason::LatencyTooHigh,
+        SimulationReason::OverBudget,
+        SimulationReason::BackendFailed,
+    ];
+
+    /// Name used in statistics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimulationReason::HardwareDisabled => "hardware_disabled",
+            SimulationReason::NoBackend => "no_backend",
+            SimulationReason::TooFewQubits => "too_few_qubits",
+            SimulationReason::FidelityTooLow => "fidelity_too_low",
+            SimulationReason::QueueTooDeep => "queue_too_deep",
+            SimulationReason::LatencyTooHigh => "latency_too_high",
+            SimulationReason::OverBudget => "over_budget",
+            SimulationReason::BackendFailed => "backend_failed",
+        }
+    }
+}
+
+/// Where an operation runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    /// On a hardware backend, with the cost reserved for it
+    Hardware {
+        backend: String,
+        cost: f64,
+        expected_latency_ms: u64,
+    },
+    /// On the physics simulation
+    Simulation { reason: SimulationReason },
+}
+
+impl Placement {
+    /// Whether the operation was given to hardware
+    pub fn is_hardware(&self) -> bool {
+        matches!(self, Placement::Hardware { .. })
+    }
+}
+
+/// Usage of one hardware backend
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendUsage {
+    /// Jobs placed on the backend
+    pub jobs: u64,
+    /// Shots of those jobs
+    pub shots: u64,
+    /// Cost of completed jobs
+    pub cost: f64,
+    /// Jobs the backend failed
+    pub failures: u64,
+    /// Time spent in completed jobs, in milliseconds
+    pub total_latency_ms: u64,
+    /// Queue depth seen at the last placement
+    pub queue_depth: usize,
+}
+
+impl BackendUsage {
+    /// Mean time of a completed job
+    pub fn average_latency_ms(&self) -> f64 {
+        let completed = self.jobs - self.failures;
+        if completed == 0 {
+            return 0.0;
+        }
+        self.total_latency_ms as f64 / completed as f64
+    }
+}
+
+/// Scheduler usage across backends and the simulation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerStats {
+    /// Usage by backend name
+    pub backends: BTreeMap<String, BackendUsage>,
+    /// Simulated operations by `SimulationReason::as_str`
+    pub simulated: BTreeMap<String, u64>,
+    /// Cost reserved in the current budget window
+    pub window_spend: f64,
+    /// Budget left in the current window
+    pub remaining_budget: f64,
+}
+
+struct RegisteredBackend {
+    profile: BackendProfile,
+    device: Arc<dyn QuantumBackend>,
+}
+
+struct Accounting {
+    window_started: Instant,
+    window_spend: f64,
+    backends: BTreeMap<String, BackendUsage>,
+    simulated: BTreeMap<String, u64>,
+}
+
+/// Places quantum operations on hardware or simulation and accounts for them
+pub struct QuantumScheduler {
+    config: SchedulingConfig,
+    hardware_enabled: bool,
+    backends: RwLock<Vec<RegisteredBackend>>,
+    accounting: Mutex<Accounting>,
+}
+
+impl QuantumScheduler {
+    /// Scheduler with no backends; with `hardware_enabled` false everything is simulated
+    pub fn new(config: SchedulingConfig, hardware_enabled: bool) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            hardware_enabled,
+            backends: RwLock::new(Vec::new()),
+            accounting: Mutex::new(Accounting {
+                window_started: Instant::now(),
+                window_spend: 0.0,
+                backends: BTreeMap::new(),
+                simulated: BTreeMap::new(),
+            }),
+        })
+    }
+
+    /// Placement limits in force
+    pub fn config(&self) -> &SchedulingConfig {
+        &self.config
+    }
+
+    /// Add a backend, replacing any registered under the same name
+    pub fn register(&self, profile: BackendProfile, device: Arc<dyn QuantumBackend>) -> Result<()> {
+        profile.validate()?;
+        let mut backends = self.backends.write();
+        backends.retain(|backend| backend.profile.name != profile.name);
+        self.accounting
+            .lock()
+            .backends
+            .entry(profile.name.clone())
+            .or_default();
+        backends.push(RegisteredBackend { profile, device });
+        Ok(())
+    }
+
+    /// Remove a backend, returning whether it was registered
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut backends = self.backends.write();
+        let before = backends.len();
+        backends.retain(|backend| backend.profile.name != name);
+        backends.len() < before
+    }
+
+    /// Profiles of the registered backends
+    pub fn backends(&self) -> Vec<BackendProfile> {
+        self.backends
+            .read()
+            .iter()
+            .map(|backend| backend.profile.clone())
+            .collect()
+    }
+
+    /// Decide where an operation runs, reserving its cost on hardware
+    pub fn place(&self, operation: &OperationProfile) -> Placement {
+        let backends = self.backends.read();
+        if !self.hardware_enabled {
+            return self.simulate(SimulationReason::HardwareDisabled);
+        }
+        if backends.is_empty() {
+            return self.simulate(SimulationReason::NoBackend);
+        }
+        let min_fidelity = operation.min_fidelity.unwrap_or(self.config.min_fidelity);
+        let latency_target_ms = operation
+            .latency_target_ms
+            .unwrap_or(self.config.latency_target_ms);
+
+        let mut accounting = self.accounting.lock();
+        accounting.roll_window(self.config.budget_window_seconds);
+        let remaining = self.config.cost_budget - accounting.window_spend;
+
+        let mut closest = SimulationReason::NoBackend;
+        let mut best: Option<(&BackendProfile, f64, u64, usize)> = None;
+        for backend in backends.iter() {
+            let profile = &backend.profile;
+            let queue_depth = backend.device.queue_depth();
+            let cost = profile.cost(operation.shots);
+            let latency_ms = profile.expected_latency_ms(queue_depth);
+            let rejection = if profile.qubits < operation.qubits {
+                Some(SimulationReason::TooFewQubits)
+            } else if operation.fidelity_on(profile) < min_fidelity {
+                Some(SimulationReason::FidelityTooLow)
+            } else if queue_depth >= self.config.max_queue_depth {
+                Some(SimulationReason::QueueTooDeep)
+            } else if latency_ms > latency_target_ms {
+                Some(SimulationReason::LatencyTooHigh)
+            } else if cost > remaining {
+                Some(SimulationReason::OverBudget)
+            } else {
+                None
+            };
+            match rejection {
+                Some(reason) => closest = closest.max(reason),
+                None => {
+                    let better = best.is_none_or(|(_, best_cost, best_latency, _)| {
+                        (cost, latency_ms) < (best_cost, best_latency)
+                    });
+                    if better {
+                        best = Some((profile, cost, latency_ms, queue_depth));
+                    }
+                }
+            }
+        }
+
+        match best {
+            Some((profile, cost, expected_latency_ms, queue_depth)) => {
+                accounting.window_spend += cost;
+                let usage = accounting.backends.entry(profile.name.clone()).or_default();
+                usage.jobs += 1;
+                usage.shots += operation.shots as u64;
+                usage.queue_depth = queue_depth;
+                Placement::Hardware {
+                    backend: profile.name.clone(),
+                    cost,
+                    expected_latency_ms,
+                }
+            }
+            None => {
+                drop(accounting);
+                self.simulate(closest)
+            }
+        }
+    }
+
+    /// Run `circuit` on `state` wherever `operation` is placed
+    ///
+    /// A failing backend is charged nothing and the circuit runs on the
+    /// simulation instead.
+    pub fn execute(
+        &self,
+        operation: &OperationProfile,
+        circuit: &QuantumCircuit,
+        state: &mut QuantumState,
+    ) -> Result<Placement> {
+        let placement = self.place(operation);
+        let (backend, cost) = match &placement {
+            Placement::Hardware { backend, cost, .. } => (backend.clone(), *cost),
+            Placement::Simulation { .. } => {
+                circuit.execute(state)?;
+                return Ok(placement);
+            }
+        };
+        let device = self
+            .backends
+            .read()
+            .iter()
+            .find(|registered| registered.profile.name == backend)
+            .map(|registered| registered.device.clone());
+        let started = Instant::now();
+        let result = match device {
+            Some(device) => device.execute(circuit, state),
+            None => Err(SecureCommsError::QuantumOperation(format!(
+                "Quantum backend {} was unregistered",
+                backend
+            ))),
+        };
+        self.complete(&backend, cost, started.elapsed(), result.is_ok());
+        match result {
+            Ok(()) => Ok(placement),
+            Err(e) => {
+                println!("⚠️ Quantum backend {} failed, simulating: {}", backend, e);
+                circuit.execute(state)?;
+                Ok(self.simulate(SimulationReason::BackendFailed))
+            }
+        }
+    }
+
+    /// Record the end of a hardware job, refunding its cost if it failed
+    pub fn complete(&self, backend: &str, cost: f64, elapsed: Duration, succeeded: bool) {
+        let mut accounting = self.accounting.lock();
+        if !succeeded {
+            accounting.window_spend = (accounting.window_spend - cost).max(0.0);
+        }
+        let usage = accounting.backends.entry(backend.to_string()).or_default();
+        if succeeded {
+            usage.cost += cost;
+            usage.total_latency_ms += elapsed.as_millis() as u64;
+        } else {
+            usage.failures += 1;
+        }
+    }
+
+    /// Usage of every backend and the simulation
+    pub fn stats(&self) -> SchedulerStats {
+        let mut accounting = self.accounting.lock();
+        accounting.roll_window(self.config.budget_window_seconds);
+        SchedulerStats {
+            backends: accounting.backends.clone(),
+            simulated: accounting.simulated.clone(),
+            window_spend: accounting.window_spend,
+            remaining_budget: (self.config.cost_budget - accounting.window_spend).max(0.0),
+        }
+    }
+
+    fn simulate(&self, reason: SimulationReason) -> Placement {
+        *self
+            .accounting
+            .lock()
+            .simulated
+            .entry(reason.as_str().to_string())
+            .or_default() += 1;
+        Placement::Simulation { reason }
+    }
+}
+
+impl Accounting {
+    fn roll_window(&mut self, window_seconds: u64) {
+        if self.window_started.elapsed() >= Duration::from_secs(window_seconds) {
+            self.window_started = Instant::now();
+            self.window_spend = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockDevice {
+        queue_depth: AtomicUsize,
+        fail: bool,
+    }
+
+    impl MockDevice {
+        fn new(queue_depth: usize) -> Arc<Self> {
+            Arc::new(Self {
+                queue_depth: AtomicUsize::new(queue_depth),
+                fail: false,
+            })
+        }
+    }
+
+    impl QuantumBackend for MockDevice {
+        fn queue_depth(&self) -> usize {
+            self.queue_depth.load(Ordering::Relaxed)
+        }
+
+        fn execute(&self, circuit: &QuantumCircuit, state: &mut QuantumState) -> Result<()> {
+            if self.fail {
+                return Err(SecureCommsError::QuantumOperation(
+                    "device offline".to_string(),
+                ));
+            }
+            circuit.execute(state)
+        }
+    }
+
+    fn profile(name: &str, qubits: u32, fidelity: f64, cost_per_job: f64) -> BackendProfile {
+        BackendProfile {
+            name: name.to_string(),
+            qubits,
+            fidelity,
+            cost_per_job,
+            cost_per_shot: 0.01,
+            job_latency_ms: 100,
+        }
+    }
+
+    fn budget(cost_budget: f64) -> SchedulingConfig {
+        SchedulingConfig {
+            cost_budget,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_placement_checks_each_limit() {
+        let scheduler = QuantumScheduler::new(budget(4.0), true).unwrap();
+        let operation = OperationProfile::new(4, 100);
+        assert_eq!(
+            scheduler.place(&operation),
+            Placement::Simulation {
+                reason: SimulationReason::NoBackend
+            }
+        );
+
+        let device = MockDevice::new(0);
+        scheduler
+            .register(profile("small", 2, 0.999, 0.0), device.clone())
+            .unwrap();
+        let reason = |operation: &OperationProfile| match scheduler.place(operation) {
+            Placement::Simulation { reason } => Some(reason),
+            Placement::Hardware { .. } => None,
+        };
+        assert_eq!(reason(&operation), Some(SimulationReason::TooFewQubits));
+        scheduler
+            .register(profile("small", 8, 0.95, 0.0), device.clone())
+            .unwrap();
+        assert_eq!(reason(&operation), Some(SimulationReason::FidelityTooLow));
+        assert_eq!(reason(&operation.clone().with_min_fidelity(0.9)), None);
+
+        scheduler
+            .register(profile("small", 8, 0.999, 0.0), device.clone())
+            .unwrap();
+        device.queue_depth.store(16, Ordering::Relaxed);
+        assert_eq!(reason(&operation), Some(SimulationReason::QueueTooDeep));
+        // Ten queued jobs ahead answer in 1.1s, past the default 1s target
+        device.queue_depth.store(10, Ordering::Relaxed);
+        assert_eq!(reason(&operation), Some(SimulationReason::LatencyTooHigh));
+        assert_eq!(reason(&operation.clone().with_latency_target(1_100)), None);
+
+        // Each job of 100 shots costs 1.0 of the 4.0 budget
+        device.queue_depth.store(0, Ordering::Relaxed);
+        assert_eq!(reason(&operation), None);
+        assert_eq!(reason(&operation), None);
+        assert_eq!(reason(&operation), Some(SimulationReason::OverBudget));
+        let stats = scheduler.stats();
+        assert_eq!(stats.backends["small"].jobs, 4);
+        assert_eq!(stats.window_spend, 4.0);
+        assert_eq!(stats.simulated["over_budget"], 1);
+    }
+
+    #[test]
+    fn test_cheapest_backend_wins_and_failures_refund() {
+        let scheduler = QuantumScheduler::new(budget(100.0), true).unwrap();
+        scheduler
+            .register(profile("premium", 8, 0.999, 5.0), MockDevice::new(0))
+            .unwrap();
+        scheduler
+            .register(profile("budget", 8, 0.999, 1.0), MockDevice::new(3))
+            .unwrap();
+        scheduler
+            .register(
+                profile("flaky", 8, 0.999, 0.5),
+                Arc::new(MockDevice {
+                    queue_depth: AtomicUsize::new(0),
+                    fail: true,
+                }),
+            )
+            .unwrap();
+
+        let circuit = QuantumCircuit::new("bell".to_string(), 2);
+        let mut state = QuantumState::new("bell".to_string(), 2);
+        let placement = scheduler
+            .execute(&OperationProfile::new(2, 10), &circuit, &mut state)
+            .unwrap();
+        assert_eq!(
+            placement,
+            Placement::Simulation {
+                reason: SimulationReason::BackendFailed
+            }
+        );
+        assert_eq!(scheduler.stats().window_spend, 0.0);
+
+        scheduler.unregister("flaky");
+        let placement = scheduler
+            .execute(&OperationProfile::new(2, 10), &circuit, &mut state)
+            .unwrap();
+        assert_eq!(
+            placement,
+            Placement::Hardware {
+                backend: "budget".to_string(),
+                cost: 1.1,
+                expected_latency_ms: 400,
+            }
+        );
+        let stats = scheduler.stats();
+        assert_eq!(stats.backends["flaky"].failures, 1);
+        assert_eq!(stats.backends["budget"].cost, 1.1);
+        assert_eq!(stats.backends["premium"].jobs, 0);
+        assert_eq!(stats.simulated["backend_failed"], 1);
+        assert!((stats.remaining_budget - 98.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_disabled_hardware_and_validation() {
+        let scheduler = QuantumScheduler::new(budget(100.0), false).unwrap();
+        scheduler
+            .register(profile("qpu", 8, 0.999, 0.0), MockDevice::new(0))
+            .unwrap();
+        assert_eq!(
+            scheduler.place(&OperationProfile::new(2, 1)),
+            Placement::Simulation {
+                reason: SimulationReason::HardwareDisabled
+            }
+        );
+        assert!(scheduler
+            .register(profile("", 8, 0.999, 0.0), MockDevice::new(0))
+            .is_err());
+        assert!(scheduler
+            .register(profile("qpu", 8, 1.5, 0.0), MockDevice::new(0))
+            .is_err());
+        assert!(QuantumScheduler::new(budget(-1.0), true).is_err());
+        assert!(QuantumScheduler::new(
+            SchedulingConfig {
+                max_queue_depth: 0,
+                ..Default::default()
+            },
+            true
+        )
+        .is_err());
+        assert_eq!(scheduler.backends().len(), 1);
+    }
+}
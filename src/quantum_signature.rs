@@ -0,0 +1,789 @@
+//! # Quantum Digital Signatures - Experimental, Research Only
+//!
+//! **Experimental.** This module is compiled only with the
+//! `experimental-qds` feature; run its tests with
+//! `cargo test --features experimental-qds`. It exists so researchers can
+//! compare a quantum digital signature (QDS) against ML-DSA on the same
+//! messages and through the same client pipeline. It is not a replacement
+//! for `hybrid_signature` and must not protect production traffic: the
+//! quantum distribution stage is simulated, keys are one-time and only
+//! short messages can be signed.
+//!
+//! ## Scheme
+//!
+//! The three-party scheme built from QKD components (Wallden, Dunjko,
+//! Kent and Andersson, 2015). A signer distributes signature elements to
+//! two recipients:
+//!
+//! - **Distribution**: For every bit position of the signed block and both
+//!   possible bit values, the signer runs a BB84-style exchange with each
+//!   recipient, stopping after sifting. The recipient's copy is correlated
+//!   with the signer's string but keeps the channel's errors; there is no
+//!   error correction or privacy amplification.
+//! - **Symmetrisation**: Each recipient keeps a random half of its elements
+//!   and forwards the other half, with their positions, to the other
+//!   recipient over an authenticated channel. The signer does not learn
+//!   which half either recipient holds.
+//! - **Signing**: For each bit of the message the signer reveals its full
+//!   strings for the bit value it signs.
+//! - **Verification**: A recipient compares the revealed strings against
+//!   both halves it holds. A bit is accepted when the mismatch rate of each
+//!   half stays below the authentication threshold for a message from the
+//!   signer, or below the higher verification threshold for a message
+//!   forwarded by the other recipient. The gap between the two thresholds
+//!   is what makes signatures transferable.
+//!
+//! Messages are signed as a block of a length byte followed by the message
+//! zero-padded to `max_message_bytes`, so a signature cannot be truncated
+//! into one on a prefix.
+//!
+//! ## Comparison with ML-DSA
+//!
+//! `compare_with_ml_dsa` signs and verifies one message with both schemes
+//! and reports key material, signature size and timings. A QDS signature
+//! grows with the message and its key is spent after one use; an ML-DSA
+//! key signs any number of messages of any length.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::QRNG;
+//! use quantum_forge_secure_comms::quantum_signature::{distribute, QdsConfig, VerificationLevel};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut qrng = QRNG::from_seed([7; 32]);
+//! let config = QdsConfig::default();
+//! let (mut signing_key, [bob_key, charlie_key]) =
+//!     distribute(&config, "alice", ["bob", "charlie"], &mut qrng)?;
+//!
+//! let signature = signing_key.sign(b"pay 10")?;
+//! bob_key.verify(b"pay 10", &signature, VerificationLevel::Direct)?;
+//! // Bob forwards the message; Charlie accepts it with the looser threshold
+//! charlie_key.verify(b"pay 10", &signature, VerificationLevel::Forwarded)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::crypto_protocols::QRNG;
+use crate::hybrid_signature::{ml_dsa_verify, MlDsaKeyPair, MlDsaLevel};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+
+/// Header carrying a quantum signature on a secure message
+pub const QDS_SIGNATURE_HEADER: &str = "x-qds-signature";
+
+/// Resolution of the channel error rate when sampling errors
+const ERROR_RATE_SCALE: u64 = 1_000_000;
+
+/// Parameters of the quantum signature scheme
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QdsConfig {
+    /// Sifted elements per recipient for each bit position and value;
+    /// each recipient keeps half and forwards half
+    pub elements_per_bit: usize,
+    /// Error rate of the simulated quantum channel
+    pub channel_error_rate: f64,
+    /// Largest mismatch rate accepted on a message from the signer
+    pub authentication_threshold: f64,
+    /// Largest mismatch rate accepted on a forwarded message
+    pub verification_threshold: f64,
+    /// Longest message one key can sign
+    pub max_message_bytes: usize,
+}
+
+impl Default for QdsConfig {
+    fn default() -> Self {
+        Self {
+            elements_per_bit: 512,
+            channel_error_rate: 0.01,
+            authentication_threshold: 0.06,
+            verification_threshold: 0.12,
+            max_message_bytes: 8,
+        }
+    }
+}
+
+impl QdsConfig {
+    /// Check that honest signatures pass and the thresholds are ordered
+    pub fn validate(&self) -> Result<()> {
+        if self.elements_per_bit < 64
+            || !self.elements_per_bit.is_multiple_of(2)
+            || self.elements_per_bit > usize::from(u16::MAX)
+        {
+            return Err(SecureCommsError::Configuration(
+                "QDS elements_per_bit must be even and between 64 and 65535".to_string(),
+            ));
+        }
+        if !(0.0..self.authentication_threshold).contains(&self.channel_error_rate)
+            || self.authentication_threshold >= self.verification_threshold
+            || self.verification_threshold >= 0.25
+        {
+            return Err(SecureCommsError::Configuration(
+                "QDS thresholds must satisfy error rate < authentication < verification < 0.25"
+                    .to_string(),
+            ));
+        }
+        if !(1..=usize::from(u8::MAX)).contains(&self.max_message_bytes) {
+            return Err(SecureCommsError::Configuration(
+                "QDS max_message_bytes must be between 1 and 255".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Bits in a signed block: the length byte and the padded message
+    fn block_bits(&self) -> usize {
+        (1 + self.max_message_bytes) * 8
+    }
+}
+
+/// Threshold a verification is held to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationLevel {
+    /// Received from the signer, authentication threshold
+    Direct,
+    /// Forwarded by the other recipient, verification threshold
+    Forwarded,
+}
+
+/// Cost of distributing one key set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistributionStats {
+    /// Quantum states sent to both recipients before sifting
+    pub pulses_sent: u64,
+    /// Elements left after sifting
+    pub sifted_elements: u64,
+    /// Elements exchanged between the recipients in symmetrisation
+    pub forwarded_elements: u64,
+}
+
+/// Signer's one-time key
+#[derive(Clone)]
+pub struct QdsSigningKey {
+    key_id: String,
+    signer_id: String,
+    recipients: [String; 2],
+    max_message_bytes: usize,
+    /// Packed strings by block bit, bit value and recipient
+    strings: Vec<[[Vec<u8>; 2]; 2]>,
+    used: bool,
+    stats: DistributionStats,
+}
+
+impl fmt::Debug for QdsSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The strings are the secret; never print them
+        f.debug_struct("QdsSigningKey")
+            .field("key_id", &self.key_id)
+            .field("signer_id", &self.signer_id)
+            .field("recipients", &self.recipients)
+            .field("used", &self.used)
+            .finish_non_exhaustive()
+    }
+}
+
+impl QdsSigningKey {
+    /// Identifier shared with the recipients' verification keys
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Recipients holding the matching verification keys
+    pub fn recipients(&self) -> &[String; 2] {
+        &self.recipients
+    }
+
+    /// Whether the key has signed its one message
+    pub fn is_used(&self) -> bool {
+        self.used
+    }
+
+    /// Cost of the key's distribution
+    pub fn stats(&self) -> DistributionStats {
+        self.stats
+    }
+
+    /// Sign `message`, spending the key
+    ///
+    /// Fails with `SecureCommsError::Validation` if the key was used
+    /// before or the message exceeds `max_message_bytes`.
+    pub fn sign(&mut self, message: &[u8]) -> Result<QuantumSignature> {
+        if self.used {
+            return Err(SecureCommsError::Validation(format!(
+                "Quantum signature key {} was already used",
+                self.key_id
+            )));
+        }
+        let block = signed_block(message, self.max_message_bytes)?;
+        self.used = true;
+        let elements = self
+            .strings
+            .iter()
+            .enumerate()
+            .map(|(bit, strings)| strings[usize::from(get_bit(&block, bit))].clone())
+            .collect();
+        Ok(QuantumSignature {
+            signer_id: self.signer_id.clone(),
+            key_id: self.key_id.clone(),
+            elements,
+        })
+    }
+}
+
+/// Element positions with the bit measured at each
+type Elements = Vec<(u16, bool)>;
+
+/// Elements a recipient holds for one bit position and value
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HeldElements {
+    /// Kept half of the recipient's own string
+    own: Elements,
+    /// Half of the other recipient's string it forwarded
+    forwarded: Elements,
+}
+
+/// Recipient's key for one signer's one-time key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QdsVerificationKey {
+    pub key_id: String,
+    pub signer_id: String,
+    /// Recipient holding this key
+    pub holder_id: String,
+    /// The other recipient, source of the forwarded half
+    pub other_id: String,
+    /// Position of the holder in the signer's recipient pair
+    holder_index: usize,
+    max_message_bytes: usize,
+    authentication_threshold: f64,
+    verification_threshold: f64,
+    /// Held elements by block bit and bit value
+    held: Vec<[HeldElements; 2]>,
+}
+
+impl QdsVerificationKey {
+    /// Verify `signature` on `message`
+    ///
+    /// Returns the worst mismatch rate seen over all bits and both halves.
+    /// Fails with `SecureCommsError::AuthenticationFailed` when it exceeds
+    /// the threshold of `level`, and with `SecureCommsError::Validation`
+    /// when the signature was made with another key or is malformed.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &QuantumSignature,
+        level: VerificationLevel,
+    ) -> Result<f64> {
+        if signature.key_id != self.key_id || signature.signer_id != self.signer_id {
+            return Err(SecureCommsError::Validation(format!(
+                "Quantum signature from {} key {} does not match key {} of {}",
+                signature.signer_id, signature.key_id, self.key_id, self.signer_id
+            )));
+        }
+        let block = signed_block(message, self.max_message_bytes)?;
+        if signature.elements.len() != self.held.len() {
+            return Err(SecureCommsError::Validation(format!(
+                "Quantum signature covers {} bits, key {} expects {}",
+                signature.elements.len(),
+                self.key_id,
+                self.held.len()
+            )));
+        }
+        let threshold = match level {
+            VerificationLevel::Direct => self.authentication_threshold,
+            VerificationLevel::Forwarded => self.verification_threshold,
+        };
+        let mut worst: f64 = 0.0;
+        for (bit, revealed) in signature.elements.iter().enumerate() {
+            let held = &self.held[bit][usize::from(get_bit(&block, bit))];
+            let own = mismatch_rate(&held.own, &revealed[self.holder_index])?;
+            let forwarded = mismatch_rate(&held.forwarded, &revealed[1 - self.holder_index])?;
+            worst = worst.max(own).max(forwarded);
+        }
+        if worst >= threshold {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        Ok(worst)
+    }
+}
+
+/// Signature revealing the signer's strings for each signed bit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuantumSignature {
+    pub signer_id: String,
+    pub key_id: String,
+    /// Packed strings by block bit and recipient
+    pub elements: Vec<[Vec<u8>; 2]>,
+}
+
+impl QuantumSignature {
+    /// Size of the revealed strings
+    pub fn len(&self) -> usize {
+        self.elements
+            .iter()
+            .map(|strings| strings[0].len() + strings[1].len())
+            .sum()
+    }
+
+    /// Whether the signature reveals nothing
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Header value carrying the signature
+    pub fn encode(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| {
+            SecureCommsError::Validation(format!("Cannot encode quantum signature: {}", e))
+        })
+    }
+
+    /// Parse a header value written by `encode`
+    pub fn decode(encoded: &str) -> Result<Self> {
+        serde_json::from_str(encoded).map_err(|e| {
+            SecureCommsError::Validation(format!("Malformed quantum signature: {}", e))
+        })
+    }
+}
+
+/// Distribute a one-time key from `signer_id` to two recipients
+///
+/// Simulates the quantum exchange with each recipient and the
+/// recipients' symmetrisation. Returns the signer's key and the
+/// verification keys of `recipients[0]` and `recipients[1]`, in order.
+pub fn distribute(
+    config: &QdsConfig,
+    signer_id: &str,
+    recipients: [&str; 2],
+    qrng: &mut QRNG,
+) -> Result<(QdsSigningKey, [QdsVerificationKey; 2])> {
+    config.validate()?;
+    if recipients[0] == recipients[1] || recipients.contains(&signer_id) {
+        return Err(SecureCommsError::Validation(
+            "QDS needs a signer and two distinct recipients".to_string(),
+        ));
+    }
+    let key_id = format!("qds_{}", hex(&qrng.generate_bytes(8)?));
+    let mut stats = DistributionStats::default();
+    let block_bits = config.block_bits();
+    let mut strings = Vec::with_capacity(block_bits);
+    let mut held: [Vec<[HeldElements; 2]>; 2] = [
+        Vec::with_capacity(block_bits),
+        Vec::with_capacity(block_bits),
+    ];
+
+    for _ in 0..block_bits {
+        let mut signer_strings: [[Vec<u8>; 2]; 2] = Default::default();
+        let mut held_bit: [[HeldElements; 2]; 2] = Default::default();
+        for value in 0..2 {
+            let mut kept: [Elements; 2] = Default::default();
+            let mut sent: [Elements; 2] = Default::default();
+            for recipient in 0..2 {
+                let (signer_string, received) = sifted_exchange(config, qrng, &mut stats)?;
+                signer_strings[value][recipient] = signer_string;
+                let (keep, forward) = split_halves(received, qrng);
+                kept[recipient] = keep;
+                sent[recipient] = forward;
+            }
+            stats.forwarded_elements += (sent[0].len() + sent[1].len()) as u64;
+            for recipient in 0..2 {
+                held_bit[recipient][value] = HeldElements {
+                    own: std::mem::take(&mut kept[recipient]),
+                    forwarded: sent[1 - recipient].clone(),
+                };
+            }
+        }
+        strings.push(signer_strings);
+        let [first, second] = held_bit;
+        held[0].push(first);
+        held[1].push(second);
+    }
+
+    let [held_first, held_second] = held;
+    let verification_key = |holder_index: usize, held: Vec<[HeldElements; 2]>| QdsVerificationKey {
+        key_id: key_id.clone(),
+        signer_id: signer_id.to_string(),
+        holder_id: recipients[holder_index].to_string(),
+        other_id: recipients[1 - holder_index].to_string(),
+        holder_index,
+        max_message_bytes: config.max_message_bytes,
+        authentication_threshold: config.authentication_threshold,
+        verification_threshold: config.verification_threshold,
+        held,
+    };
+    let verification_keys = [
+        verification_key(0, held_first),
+        verification_key(1, held_second),
+    ];
+    let signing_key = QdsSigningKey {
+        key_id: key_id.clone(),
+        signer_id: signer_id.to_string(),
+        recipients: recipients.map(str::to_string),
+        max_message_bytes: config.max_message_bytes,
+        strings,
+        used: false,
+        stats,
+    };
+    Ok((signing_key, verification_keys))
+}
+
+/// One-time signing keys and received verification keys of a client
+#[derive(Debug, Default)]
+pub struct QdsKeyring {
+    signing: Vec<QdsSigningKey>,
+    verification: HashMap<String, QdsVerificationKey>,
+}
+
+impl QdsKeyring {
+    /// Keep a freshly distributed signing key
+    pub fn add_signing_key(&mut self, key: QdsSigningKey) {
+        self.signing.push(key);
+    }
+
+    /// Keep a verification key received from a signer
+    pub fn add_verification_key(&mut self, key: QdsVerificationKey) {
+        self.verification.insert(key.key_id.clone(), key);
+    }
+
+    /// Remove and return the oldest unused signing key naming `peer_id`
+    pub fn take_signing_key(&mut self, peer_id: &str) -> Option<QdsSigningKey> {
+        let index = self
+            .signing
+            .iter()
+            .position(|key| !key.used && key.recipients.iter().any(|r| r == peer_id))?;
+        Some(self.signing.remove(index))
+    }
+
+    /// Unused signing keys naming `peer_id`
+    pub fn signing_keys_for(&self, peer_id: &str) -> usize {
+        self.signing
+            .iter()
+            .filter(|key| !key.used && key.recipients.iter().any(|r| r == peer_id))
+            .count()
+    }
+
+    /// Verify a signature that `sender_id` delivered, with the matching key
+    ///
+    /// A direct signature must come from the signer and a forwarded one from
+    /// the other recipient of the key; otherwise, or without a matching key,
+    /// this fails with `SecureCommsError::AuthenticationFailed`.
+    pub fn verify(
+        &self,
+        sender_id: &str,
+        message: &[u8],
+        signature: &QuantumSignature,
+        level: VerificationLevel,
+    ) -> Result<f64> {
+        let key = self
+            .verification
+            .get(&signature.key_id)
+            .filter(|key| match level {
+                VerificationLevel::Direct => key.signer_id == sender_id,
+                VerificationLevel::Forwarded => key.other_id == sender_id,
+            })
+            .ok_or(SecureCommsError::AuthenticationFailed)?;
+        key.verify(message, signature, level)
+    }
+}
+
+/// Sizes and timings of one scheme signing one message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemeMetrics {
+    pub scheme: String,
+    /// Key material a verifier holds
+    pub verification_key_bytes: usize,
+    pub signature_bytes: usize,
+    /// Key generation, or key distribution for QDS
+    pub setup_us: u64,
+    pub sign_us: u64,
+    pub verify_us: u64,
+    /// Whether the key is spent after one signature
+    pub one_time: bool,
+    pub verified: bool,
+}
+
+/// Sign and verify `message` with QDS and with ML-DSA at `level`
+///
+/// Returns the QDS metrics first. Timings of the QDS distribution cover
+/// the simulation only, not a physical quantum channel.
+pub fn compare_with_ml_dsa(
+    config: &QdsConfig,
+    level: MlDsaLevel,
+    message: &[u8],
+    qrng: &mut QRNG,
+) -> Result<[SchemeMetrics; 2]> {
+    let start = Instant::now();
+    let (mut signing_key, [verification_key, _]) =
+        distribute(config, "signer", ["verifier", "witness"], qrng)?;
+    let setup_us = elapsed_us(start);
+    let start = Instant::now();
+    let signature = signing_key.sign(message)?;
+    let sign_us = elapsed_us(start);
+    let start = Instant::now();
+    let verified = verification_key
+        .verify(message, &signature, VerificationLevel::Direct)
+        .is_ok();
+    let verify_us = elapsed_us(start);
+    let held: usize = verification_key
+        .held
+        .iter()
+        .flatten()
+        .map(|held| held.own.len() + held.forwarded.len())
+        .sum();
+    let qds = SchemeMetrics {
+        scheme: "QDS (experimental)".to_string(),
+        // A position and a bit per held element
+        verification_key_bytes: held * 3,
+        signature_bytes: signature.len(),
+        setup_us,
+        sign_us,
+        verify_us,
+        one_time: true,
+        verified,
+    };
+
+    let start = Instant::now();
+    let keypair = MlDsaKeyPair::generate(level)?;
+    let setup_us = elapsed_us(start);
+    let start = Instant::now();
+    let signature = keypair.sign(message)?;
+    let sign_us = elapsed_us(start);
+    let public_key = keypair.public_key();
+    let start = Instant::now();
+    let verified = ml_dsa_verify(&public_key, message, &signature);
+    let verify_us = elapsed_us(start);
+    let ml_dsa = SchemeMetrics {
+        scheme: format!("{:?}", level),
+        verification_key_bytes: public_key.bytes.len(),
+        signature_bytes: signature.len(),
+        setup_us,
+        sign_us,
+        verify_us,
+        one_time: false,
+        verified,
+    };
+    Ok([qds, ml_dsa])
+}
+
+/// Length byte followed by the message zero-padded to `max_message_bytes`
+fn signed_block(message: &[u8], max_message_bytes: usize) -> Result<Vec<u8>> {
+    if message.len() > max_message_bytes {
+        return Err(SecureCommsError::Validation(format!(
+            "Message of {} bytes exceeds the {} bytes a quantum signature covers",
+            message.len(),
+            max_message_bytes
+        )));
+    }
+    let mut block = Vec::with_capacity(1 + max_message_bytes);
+    block.push(message.len() as u8);
+    block.extend_from_slice(message);
+    block.resize(1 + max_message_bytes, 0);
+    Ok(block)
+}
+
+/// Sifted BB84 exchange with one recipient, without error correction
+///
+/// Returns the signer's packed string and the recipient's elements with
+/// their positions.
+fn sifted_exchange(
+    config: &QdsConfig,
+    qrng: &mut QRNG,
+    stats: &mut DistributionStats,
+) -> Result<(Vec<u8>, Elements)> {
+    let length = config.elements_per_bit;
+    let mut signer_string = vec![0u8; length.div_ceil(8)];
+    let mut received = Vec::with_capacity(length);
+    let error_threshold = (config.channel_error_rate * ERROR_RATE_SCALE as f64) as u64;
+    while received.len() < length {
+        // One byte of bases agreement and one of signer bits per 8 pulses
+        let pulses = qrng.generate_bytes(2)?;
+        for i in 0..8 {
+            if received.len() == length {
+                break;
+            }
+            stats.pulses_sent += 1;
+            if (pulses[0] >> i) & 1 == 0 {
+                continue;
+            }
+            let position = received.len();
+            let bit = (pulses[1] >> i) & 1 == 1;
+            if bit {
+                signer_string[position / 8] |= 0x80 >> (position % 8);
+            }
+            let flipped = qrng.gen_range(0..ERROR_RATE_SCALE) < error_threshold;
+            received.push((position as u16, bit ^ flipped));
+        }
+    }
+    stats.sifted_elements += length as u64;
+    Ok((signer_string, received))
+}
+
+/// Randomly split elements into a kept half and a forwarded half
+fn split_halves(mut elements: Elements, qrng: &mut QRNG) -> (Elements, Elements) {
+    for i in (1..elements.len()).rev() {
+        let j = qrng.gen_range(0..i as u64 + 1) as usize;
+        elements.swap(i, j);
+    }
+    let forwarded = elements.split_off(elements.len() / 2);
+    (elements, forwarded)
+}
+
+/// Fraction of held elements that disagree with a revealed string
+fn mismatch_rate(held: &[(u16, bool)], revealed: &[u8]) -> Result<f64> {
+    let mut mismatches = 0usize;
+    for &(position, bit) in held {
+        let position = usize::from(position);
+        if position / 8 >= revealed.len() {
+            return Err(SecureCommsError::Validation(
+                "Quantum signature string is too short".to_string(),
+            ));
+        }
+        if (get_bit(revealed, position) == 1) != bit {
+            mismatches += 1;
+        }
+    }
+    Ok(mismatches as f64 / held.len().max(1) as f64)
+}
+
+/// Bit `index` of `bytes`, most significant bit first
+fn get_bit(bytes: &[u8], index: usize) -> u8 {
+    (bytes[index / 8] >> (7 - index % 8)) & 1
+}
+
+fn elapsed_us(start: Instant) -> u64 {
+    start.elapsed().as_micros() as u64
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(seed: u8) -> (QdsSigningKey, [QdsVerificationKey; 2]) {
+        let mut qrng = QRNG::from_seed([seed; 32]);
+        distribute(
+            &QdsConfig::default(),
+            "alice",
+            ["bob", "charlie"],
+            &mut qrng,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_signature_verifies_directly_and_forwarded() {
+        let (mut signing_key, [bob, charlie]) = keys(1);
+        assert_eq!(bob.holder_id, "bob");
+        assert_eq!(charlie.other_id, "bob");
+        let stats = signing_key.stats();
+        // About half the pulses survive sifting
+        assert!(stats.pulses_sent > stats.sifted_elements * 3 / 2);
+        assert_eq!(stats.forwarded_elements, stats.sifted_elements / 2);
+
+        let signature = signing_key.sign(b"pay 10").unwrap();
+        assert!(signing_key.is_used());
+        let encoded = signature.encode().unwrap();
+        let signature = QuantumSignature::decode(&encoded).unwrap();
+        let rate = bob
+            .verify(b"pay 10", &signature, VerificationLevel::Direct)
+            .unwrap();
+        assert!(rate < QdsConfig::default().authentication_threshold);
+        charlie
+            .verify(b"pay 10", &signature, VerificationLevel::Forwarded)
+            .unwrap();
+
+        // One-time key, bounded message length
+        assert!(matches!(
+            signing_key.sign(b"pay 10"),
+            Err(SecureCommsError::Validation(_))
+        ));
+        let (mut fresh, _) = keys(2);
+        assert!(fresh.sign(&[0u8; 9]).is_err());
+    }
+
+    #[test]
+    fn test_forgeries_and_truncations_are_rejected() {
+        let (mut signing_key, [bob, charlie]) = keys(3);
+        let signature = signing_key.sign(b"pay 10").unwrap();
+
+        // Another message under the same signature
+        assert!(matches!(
+            bob.verify(b"pay 99", &signature, VerificationLevel::Direct),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        // The signature does not cover a prefix of the signed message
+        assert!(matches!(
+            charlie.verify(b"pay", &signature, VerificationLevel::Forwarded),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Bob reveals his own elements as the signature on another message
+        // for Charlie; he lacks the half Charlie kept
+        let mut forged = signature.clone();
+        let block = signed_block(b"pay 99", 8).unwrap();
+        for (bit, strings) in forged.elements.iter_mut().enumerate() {
+            let held = &bob.held[bit][usize::from(get_bit(&block, bit))];
+            let mut own = vec![0u8; strings[0].len()];
+            for &(position, value) in &held.own {
+                if value {
+                    own[usize::from(position) / 8] |= 0x80 >> (position % 8);
+                }
+            }
+            strings[0] = own;
+        }
+        assert!(charlie
+            .verify(b"pay 99", &forged, VerificationLevel::Forwarded)
+            .is_err());
+
+        // Keys of another distribution do not verify the signature
+        let (_, [other_bob, _]) = keys(4);
+        assert!(matches!(
+            other_bob.verify(b"pay 10", &signature, VerificationLevel::Direct),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_comparison_with_ml_dsa_and_config_bounds() {
+        let mut qrng = QRNG::from_seed([5; 32]);
+        let [qds, ml_dsa] = compare_with_ml_dsa(
+            &QdsConfig::default(),
+            MlDsaLevel::MlDsa65,
+            b"pay 10",
+            &mut qrng,
+        )
+        .unwrap();
+        assert!(qds.verified && ml_dsa.verified);
+        assert!(qds.one_time && !ml_dsa.one_time);
+        // 72 block bits, two 512-element strings each
+        assert_eq!(qds.signature_bytes, 72 * 2 * 64);
+        assert!(qds.signature_bytes > ml_dsa.signature_bytes);
+
+        for config in [
+            QdsConfig {
+                elements_per_bit: 63,
+                ..Default::default()
+            },
+            QdsConfig {
+                channel_error_rate: 0.07,
+                ..Default::default()
+            },
+            QdsConfig {
+                verification_threshold: 0.05,
+                ..Default::default()
+            },
+            QdsConfig {
+                max_message_bytes: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(config.validate().is_err());
+        }
+        let mut qrng = QRNG::from_seed([6; 32]);
+        assert!(distribute(&QdsConfig::default(), "alice", ["bob", "bob"], &mut qrng).is_err());
+    }
+}
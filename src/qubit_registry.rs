@@ -0,0 +1,259 @@
+//! # Qubit Registry - Globally Addressable Qubits
+//!
+//! Gives every qubit of every quantum state held by the core a global
+//! identifier, so operations name the qubits they act on instead of relying
+//! on whichever state happens to come first.
+//!
+//! ## Addressing
+//!
+//! - **Identifiers**: A [`QubitId`] is allocated when its state is created
+//!   and is never reused, so a stale id cannot silently refer to a newer
+//!   state created under the same name.
+//! - **Resolution**: A qubit resolves to a [`QubitAddress`], its owning
+//!   state and index within that state. Operations on several qubits
+//!   require them to share one state, since each state is simulated on its
+//!   own.
+//! - **Errors**: Failed resolutions name the qubit and, where known, the
+//!   states involved.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::qubit_registry::QubitRegistry;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let registry = QubitRegistry::new();
+//! let qubits = registry.allocate("alice_bob", 2);
+//! let (state_id, indices) = registry.resolve_group(&qubits)?;
+//! assert_eq!((state_id.as_str(), indices), ("alice_bob", vec![0, 1]));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Result, SecureCommsError};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Global identifier of one qubit held by the core
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct QubitId(u64);
+
+impl QubitId {
+    /// Raw identifier
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for QubitId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "q{}", self.0)
+    }
+}
+
+/// Location of a qubit within its owning state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QubitAddress {
+    /// State holding the qubit
+    pub state_id: String,
+    /// Index of the qubit within the state
+    pub index: u32,
+}
+
+impl fmt::Display for QubitAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.state_id, self.index)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RegistryInner {
+    next: u64,
+    qubits: HashMap<QubitId, QubitAddress>,
+    states: HashMap<String, Vec<QubitId>>,
+}
+
+/// Mapping of global qubit identifiers to their owning states
+#[derive(Debug, Default)]
+pub struct QubitRegistry {
+    inner: RwLock<RegistryInner>,
+}
+
+impl QubitRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocate fresh identifiers for the `count` qubits of `state_id`
+    ///
+    /// Identifiers of a previous state with the same id are released, as
+    /// that state has been replaced.
+    pub fn allocate(&self, state_id: &str, count: u32) -> Vec<QubitId> {
+        let mut inner = self.inner.write();
+        release_locked(&mut inner, state_id);
+        let qubits: Vec<QubitId> = (0..count)
+            .map(|index| {
+                let qubit = QubitId(inner.next);
+                inner.next += 1;
+                inner.qubits.insert(
+                    qubit,
+                    QubitAddress {
+                        state_id: state_id.to_string(),
+                        index,
+                    },
+                );
+                qubit
+            })
+            .collect();
+        inner.states.insert(state_id.to_string(), qubits.clone());
+        qubits
+    }
+
+    /// Release the identifiers of a removed state, returning them
+    pub fn release(&self, state_id: &str) -> Vec<QubitId> {
+        release_locked(&mut self.inner.write(), state_id)
+    }
+
+    /// Owning state and index of `qubit`
+    pub fn resolve(&self, qubit: QubitId) -> Result<QubitAddress> {
+        self.inner
+            .read()
+            .qubits
+            .get(&qubit)
+            .cloned()
+            .ok_or_else(|| {
+                SecureCommsError::QuantumOperation(format!(
+                    "Qubit {} is not held by any state",
+                    qubit
+                ))
+            })
+    }
+
+    /// Common owning state of `qubits` and their indices in order
+    ///
+    /// Fails if the list is empty, names a qubit twice, or spans states.
+    pub fn resolve_group(&self, qubits: &[QubitId]) -> Result<(String, Vec<u32>)> {
+        let first = *qubits
+            .first()
+            .ok_or_else(|| SecureCommsError::QuantumOperation("No qubits addressed".to_string()))?;
+        let owner = self.resolve(first)?;
+        let mut indices = vec![owner.index];
+        for (position, &qubit) in qubits.iter().enumerate().skip(1) {
+            if qubits[..position].contains(&qubit) {
+                return Err(SecureCommsError::QuantumOperation(format!(
+                    "Qubit {} is addressed twice",
+                    qubit
+                )));
+            }
+            let address = self.resolve(qubit)?;
+            if address.state_id != owner.state_id {
+                return Err(SecureCommsError::QuantumOperation(format!(
+                    "Qubits {} ({}) and {} ({}) belong to different states",
+                    first, owner, qubit, address
+                )));
+            }
+            indices.push(address.index);
+        }
+        Ok((owner.state_id, indices))
+    }
+
+    /// Identifiers of the qubits of `state_id`, in index order
+    pub fn qubits_of(&self, state_id: &str) -> Result<Vec<QubitId>> {
+        self.inner
+            .read()
+            .states
+            .get(state_id)
+            .cloned()
+            .ok_or_else(|| {
+                SecureCommsError::QuantumOperation(format!("State {} holds no qubits", state_id))
+            })
+    }
+
+    /// Number of qubits currently addressable
+    pub fn len(&self) -> usize {
+        self.inner.read().qubits.len()
+    }
+
+    /// Whether no qubit is addressable
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn release_locked(inner: &mut RegistryInner, state_id: &str) -> Vec<QubitId> {
+    let qubits = inner.states.remove(state_id).unwrap_or_default();
+    for qubit in &qubits {
+        inner.qubits.remove(qubit);
+    }
+    qubits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qubits_resolve_to_their_states() {
+        let registry = QubitRegistry::new();
+        let a = registry.allocate("a", 2);
+        let b = registry.allocate("b", 3);
+        assert_eq!(registry.len(), 5);
+        assert_eq!(
+            registry.resolve(b[2]).unwrap(),
+            QubitAddress {
+                state_id: "b".to_string(),
+                index: 2
+            }
+        );
+        assert_eq!(
+            registry.resolve_group(&[b[2], b[0]]).unwrap(),
+            ("b".to_string(), vec![2, 0])
+        );
+        assert_eq!(registry.qubits_of("a").unwrap(), a);
+        assert!(a.iter().all(|qubit| !b.contains(qubit)));
+    }
+
+    #[test]
+    fn test_errors_name_qubits_and_states() {
+        let registry = QubitRegistry::new();
+        let a = registry.allocate("a", 2);
+        let b = registry.allocate("b", 1);
+        let message = registry
+            .resolve_group(&[a[1], b[0]])
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains(&format!("{} (a[1])", a[1])), "{}", message);
+        assert!(message.contains(&format!("{} (b[0])", b[0])), "{}", message);
+        let message = registry
+            .resolve_group(&[a[0], a[0]])
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains(&a[0].to_string()), "{}", message);
+        assert!(registry.resolve_group(&[]).is_err());
+        assert!(registry
+            .qubits_of("c")
+            .unwrap_err()
+            .to_string()
+            .contains("c"));
+    }
+
+    #[test]
+    fn test_replaced_and_released_states_invalidate_ids() {
+        let registry = QubitRegistry::new();
+        let old = registry.allocate("a", 2);
+        let new = registry.allocate("a", 2);
+        assert!(old.iter().all(|qubit| !new.contains(qubit)));
+        assert!(registry
+            .resolve(old[0])
+            .unwrap_err()
+            .to_string()
+            .contains(&old[0].to_string()));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.release("a"), new);
+        assert!(registry.resolve(new[1]).is_err());
+        assert!(registry.is_empty());
+    }
+}
@@ -0,0 +1,405 @@
+//! # Quorum Rules - Weighted Voting and Supermajority Configuration
+//!
+//! Makes the consensus quorum explicit. Validators carry voting weights,
+//! decisions require a configurable supermajority of the weight cast plus a
+//! minimum participation, and each proposal type may use its own rule (for
+//! example a stricter rule for validator set changes than for data blocks).
+//!
+//! ## Rule Evaluation
+//!
+//! - **Participation**: Cast weight must reach `participation` of the total validator weight
+//! - **Minimum Voters**: At least `min_voters` distinct validators must have voted
+//! - **Approval**: Approving weight must reach `approval` of the cast weight
+//! - **Rejection**: Approving weight below `1 - approval` of the cast weight rejects
+//!
+//! Fractions are exact rationals, so every node evaluates the same tally the
+//! same way regardless of floating-point rounding.
+//!
+//! ## Validation
+//!
+//! `QuorumConfig::validate` runs when the consensus engine loads its
+//! configuration. Approval fractions must be a strict majority, otherwise two
+//! conflicting decisions could both reach quorum.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::quorum::{Fraction, QuorumConfig, QuorumRule};
+//!
+//! let mut quorum = QuorumConfig::default();
+//! quorum.weights.insert("validator_1".to_string(), 3);
+//! quorum.type_rules.insert(
+//!     "validator_set_change".to_string(),
+//!     QuorumRule {
+//!         approval: Fraction::new(3, 4),
+//!         participation: Fraction::new(2, 3),
+//!         min_voters: 3,
+//!     },
+//! );
+//! quorum.validate().unwrap();
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Exact fraction `numerator / denominator`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fraction {
+    /// Numerator
+    pub numerator: u64,
+    /// Denominator, never zero in a valid fraction
+    pub denominator: u64,
+}
+
+impl Fraction {
+    /// Create a fraction
+    pub const fn new(numerator: u64, denominator: u64) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Closest fraction in ten-thousandths to a legacy floating-point threshold
+    pub fn from_f64(value: f64) -> Self {
+        Self::new((value.clamp(0.0, 1.0) * 10_000.0).round() as u64, 10_000)
+    }
+
+    /// Fraction as a float, for reporting
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator.max(1) as f64
+    }
+
+    /// Whether `part / whole` is at least this fraction
+    pub fn reached_by(&self, part: u64, whole: u64) -> bool {
+        part as u128 * self.denominator as u128 >= self.numerator as u128 * whole as u128
+    }
+
+    /// One minus this fraction
+    pub fn complement(&self) -> Self {
+        Self::new(
+            self.denominator.saturating_sub(self.numerator),
+            self.denominator,
+        )
+    }
+
+    fn is_valid(&self) -> bool {
+        self.denominator > 0 && self.numerator <= self.denominator
+    }
+
+    /// Whether the fraction is strictly greater than one half
+    pub fn is_strict_majority(&self) -> bool {
+        self.numerator as u128 * 2 > self.denominator as u128
+    }
+}
+
+/// Quorum requirements for one kind of decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumRule {
+    /// Approving weight needed, as a fraction of the weight cast
+    pub approval: Fraction,
+    /// Weight that must vote, as a fraction of the total validator weight
+    pub participation: Fraction,
+    /// Minimum number of distinct voters
+    pub min_voters: u32,
+}
+
+impl Default for QuorumRule {
+    fn default() -> Self {
+        Self {
+            approval: Fraction::new(2, 3),
+            participation: Fraction::new(0, 1),
+            min_voters: 1,
+        }
+    }
+}
+
+impl QuorumRule {
+    /// Rule equivalent to a legacy vote-count threshold
+    pub fn from_threshold(min_voters: u32, threshold: f64) -> Self {
+        Self {
+            approval: Fraction::from_f64(threshold),
+            participation: Fraction::new(0, 1),
+            min_voters,
+        }
+    }
+
+    /// Check the rule is well formed
+    pub fn validate(&self) -> Result<()> {
+        if !self.approval.is_valid() || !self.participation.is_valid() {
+            return Err(SecureCommsError::Configuration(
+                "Quorum fractions need a non-zero denominator and may not exceed 1".to_string(),
+            ));
+        }
+        if !self.approval.is_strict_majority() {
+            return Err(SecureCommsError::Configuration(format!(
+                "Approval fraction {}/{} must be a strict majority",
+                self.approval.numerator, self.approval.denominator
+            )));
+        }
+        Ok(())
+    }
+
+    /// Evaluate a tally against the total validator weight
+    ///
+    /// Returns `None` while the outcome is still open.
+    pub fn evaluate(&self, tally: &QuorumTally, total_weight: u64) -> Option<QuorumOutcome> {
+        let cast = tally.cast_weight();
+        if tally.voters < self.min_voters as usize
+            || cast == 0
+            || !self.participation.reached_by(cast, total_weight)
+        {
+            return None;
+        }
+
+        if self.approval.reached_by(tally.approve_weight, cast) {
+            Some(QuorumOutcome::Approved)
+        } else if !self.approval.complement().reached_by(tally.approve_weight, cast) {
+            Some(QuorumOutcome::Rejected)
+        } else {
+            None
+        }
+    }
+}
+
+/// Outcome of a quorum evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuorumOutcome {
+    /// Approval fraction reached
+    Approved,
+    /// Approval can no longer be reached under the rule
+    Rejected,
+}
+
+/// Weighted vote totals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumTally {
+    /// Weight of approving votes
+    pub approve_weight: u64,
+    /// Weight of rejecting votes
+    pub reject_weight: u64,
+    /// Weight of abstaining votes
+    pub abstain_weight: u64,
+    /// Distinct voters
+    pub voters: usize,
+}
+
+impl QuorumTally {
+    /// Total weight cast, including abstentions
+    pub fn cast_weight(&self) -> u64 {
+        self.approve_weight
+            .saturating_add(self.reject_weight)
+            .saturating_add(self.abstain_weight)
+    }
+}
+
+/// Quorum configuration: weights, default rule and per-type rules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuorumConfig {
+    /// Rule for proposal types without their own rule
+    pub default_rule: QuorumRule,
+    /// Rules per proposal type
+    pub type_rules: HashMap<String, QuorumRule>,
+    /// Voting weight per validator
+    pub weights: HashMap<String, u64>,
+    /// Weight of validators not listed in `weights`
+    pub default_weight: u64,
+}
+
+impl Default for QuorumConfig {
+    fn default() -> Self {
+        Self {
+            default_rule: QuorumRule::default(),
+            type_rules: HashMap::new(),
+            weights: HashMap::new(),
+            default_weight: 1,
+        }
+    }
+}
+
+impl QuorumConfig {
+    /// Check every rule and the weight table
+    pub fn validate(&self) -> Result<()> {
+        self.default_rule.validate()?;
+        for (proposal_type, rule) in &self.type_rules {
+            rule.validate().map_err(|e| {
+                SecureCommsError::Configuration(format!(
+                    "Quorum rule for {}: {}",
+                    proposal_type, e
+                ))
+            })?;
+        }
+        if self.default_weight == 0 && self.weights.values().all(|&weight| weight == 0) {
+            return Err(SecureCommsError::Configuration(
+                "At least one validator must have non-zero voting weight".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rule for a proposal type
+    pub fn rule_for(&self, proposal_type: &str) -> &QuorumRule {
+        self.type_rules
+            .get(proposal_type)
+            .unwrap_or(&self.default_rule)
+    }
+
+    /// Voting weight of a validator
+    pub fn weight_of(&self, validator_id: &str) -> u64 {
+        self.weights
+            .get(validator_id)
+            .copied()
+            .unwrap_or(self.default_weight)
+    }
+
+    /// Combined weight of a set of validators
+    pub fn total_weight<'a, I>(&self, validator_ids: I) -> u64
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        validator_ids
+            .into_iter()
+            .map(|id| self.weight_of(id))
+            .fold(0u64, u64::saturating_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn tally(approve: u64, reject: u64, abstain: u64, voters: usize) -> QuorumTally {
+        QuorumTally {
+            approve_weight: approve,
+            reject_weight: reject,
+            abstain_weight: abstain,
+            voters,
+        }
+    }
+
+    #[test]
+    fn test_weighted_supermajority() {
+        let rule = QuorumRule {
+            approval: Fraction::new(2, 3),
+            participation: Fraction::new(1, 2),
+            min_voters: 2,
+        };
+
+        // 7 of 10 total weight voted, 5 approving: 5/7 >= 2/3
+        assert_eq!(rule.evaluate(&tally(5, 2, 0, 3), 10), Some(QuorumOutcome::Approved));
+        // Participation below one half keeps the outcome open
+        assert_eq!(rule.evaluate(&tally(4, 0, 0, 2), 10), None);
+        // A single heavy voter is not enough
+        assert_eq!(rule.evaluate(&tally(9, 0, 0, 1), 10), None);
+        // 1/6 approving is below 1 - 2/3
+        assert_eq!(rule.evaluate(&tally(1, 5, 0, 3), 10), Some(QuorumOutcome::Rejected));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(QuorumConfig::default().validate().is_ok());
+
+        let mut config = QuorumConfig::default();
+        config.default_rule.approval = Fraction::new(1, 2);
+        assert!(config.validate().is_err());
+
+        let mut config = QuorumConfig::default();
+        config.type_rules.insert(
+            "upgrade".to_string(),
+            QuorumRule {
+                approval: Fraction::new(3, 0),
+                ..QuorumRule::default()
+            },
+        );
+        assert!(config.validate().is_err());
+
+        let config = QuorumConfig {
+            default_weight: 0,
+            ..QuorumConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_per_type_rules_and_weights() {
+        let mut config = QuorumConfig::default();
+        config.weights.insert("heavy".to_string(), 5);
+        config.type_rules.insert(
+            "upgrade".to_string(),
+            QuorumRule {
+                approval: Fraction::new(9, 10),
+                ..QuorumRule::default()
+            },
+        );
+
+        assert_eq!(config.weight_of("heavy"), 5);
+        assert_eq!(config.weight_of("other"), 1);
+        assert_eq!(config.total_weight(["heavy", "a", "b"]), 7);
+        assert_eq!(config.rule_for("upgrade").approval, Fraction::new(9, 10));
+        assert_eq!(config.rule_for("block"), &config.default_rule);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_never_both_approved_and_rejected(
+            numerator in 51u64..=100,
+            approve in 0u64..1_000,
+            reject in 0u64..1_000,
+            abstain in 0u64..1_000,
+        ) {
+            let rule = QuorumRule {
+                approval: Fraction::new(numerator, 100),
+                ..QuorumRule::default()
+            };
+            let cast = approve + reject + abstain;
+            let approved = rule.approval.reached_by(approve, cast);
+            let rejected = !rule.approval.complement().reached_by(approve, cast);
+            prop_assert!(!(approved && rejected));
+        }
+
+        #[test]
+        fn prop_more_approval_never_flips_to_rejected(
+            approve in 0u64..500,
+            reject in 0u64..500,
+            extra in 1u64..500,
+        ) {
+            let rule = QuorumRule::default();
+            let before = rule.evaluate(&tally(approve, reject, 0, 5), 0);
+            let after = rule.evaluate(&tally(approve + extra, reject, 0, 5), 0);
+            if before == Some(QuorumOutcome::Approved) {
+                prop_assert_eq!(after, Some(QuorumOutcome::Approved));
+            }
+            if after == Some(QuorumOutcome::Rejected) {
+                prop_assert_eq!(before, Some(QuorumOutcome::Rejected));
+            }
+        }
+
+        #[test]
+        fn prop_unit_weights_match_legacy_count(
+            approve in 0usize..50,
+            reject in 0usize..50,
+            min_voters in 1u32..10,
+        ) {
+            let threshold = 0.67;
+            let rule = QuorumRule::from_threshold(min_voters, threshold);
+            let total = approve + reject;
+
+            let legacy = if total >= min_voters as usize && total > 0 {
+                let ratio = approve as f64 / total as f64;
+                if ratio >= threshold {
+                    Some(QuorumOutcome::Approved)
+                } else if ratio < 1.0 - threshold {
+                    Some(QuorumOutcome::Rejected)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+            let weighted = rule.evaluate(&tally(approve as u64, reject as u64, 0, total), 0);
+            prop_assert_eq!(weighted, legacy);
+        }
+    }
+}
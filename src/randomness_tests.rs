@@ -0,0 +1,462 @@
+//! # Randomness Tests - Statistical Quality Checks of QRNG Output
+//!
+//! A subset of the NIST SP 800-22 statistical test suite plus a min-entropy
+//! estimate, run in-process on raw or conditioned QRNG output. A failing
+//! source is caught in production rather than after keys were issued: the
+//! report is exported as Prometheus metrics and raises monitor alerts when
+//! quality degrades.
+//!
+//! ## Tests
+//!
+//! - **Monobit**: Proportion of ones against the ideal one half (SP 800-22 §2.1)
+//! - **Runs**: Number of uninterrupted runs of identical bits (§2.3)
+//! - **Serial**: Frequencies of every overlapping m-bit pattern (§2.11),
+//!   reporting both p-values
+//! - **Min-Entropy**: Most-common-value estimate per bit (SP 800-90B §6.3.1)
+//!   against a configured floor
+//!
+//! A single test failing at significance α happens to a perfect source with
+//! probability about α, so one failure raises a warning and several raise a
+//! critical alert.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::randomness_tests::{RandomnessTestConfig, RandomnessTester};
+//!
+//! # fn example(sample: &[u8]) -> quantum_forge_secure_comms::Result<()> {
+//! let tester = RandomnessTester::new(RandomnessTestConfig::default());
+//! let report = tester.test_bytes(sample)?;
+//! for result in &report.results {
+//!     println!("{}: p = {:?}, passed = {}", result.name, result.p_value, result.passed);
+//! }
+//! report.export_metrics();
+//! # Ok(())
+//! # }
+//! ```
+
+use metrics::{counter, gauge};
+use serde::{Deserialize, Serialize};
+
+use crate::production_monitor::{HealthStatus, ProductionMonitor};
+use crate::qrng_extraction::MinEntropyEstimator;
+use crate::{Result, SecureCommsError};
+
+/// Alert component of randomness quality alerts
+pub const QUALITY_ALERT_COMPONENT: &str = "qrng_quality";
+
+/// QRNG output a quality check samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RandomnessSample {
+    /// Shot outcomes before conditioning, exposing source bias
+    Raw,
+    /// Extractor output as served to consumers
+    #[default]
+    Conditioned,
+}
+
+/// Statistical test settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RandomnessTestConfig {
+    /// Significance level α; a p-value below it fails the test
+    pub significance: f64,
+    /// Smallest sample accepted, in bits
+    pub min_bits: usize,
+    /// Pattern length m of the serial test
+    pub serial_pattern_bits: u32,
+    /// Min-entropy per bit below which the entropy test fails
+    pub min_entropy_per_bit: f64,
+}
+
+impl Default for RandomnessTestConfig {
+    fn default() -> Self {
+        Self {
+            significance: 0.01,
+            min_bits: 8192,
+            serial_pattern_bits: 3,
+            min_entropy_per_bit: 0.8,
+        }
+    }
+}
+
+/// Outcome of one statistical test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestResult {
+    /// Test name, also the metric label
+    pub name: String,
+    /// Test statistic
+    pub statistic: f64,
+    /// p-value, `None` for threshold tests
+    pub p_value: Option<f64>,
+    /// Whether the sample passed
+    pub passed: bool,
+}
+
+/// Results of the full suite on one sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RandomnessReport {
+    /// Sample size in bits
+    pub bits: usize,
+    /// Per-test outcomes
+    pub results: Vec<TestResult>,
+    /// Estimated min-entropy per bit
+    pub min_entropy: f64,
+}
+
+impl RandomnessReport {
+    /// Whether every test passed
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|result| result.passed)
+    }
+
+    /// Tests that failed
+    pub fn failures(&self) -> Vec<&TestResult> {
+        self.results
+            .iter()
+            .filter(|result| !result.passed)
+            .collect()
+    }
+
+    /// Outcome of the named test
+    pub fn result(&self, name: &str) -> Option<&TestResult> {
+        self.results.iter().find(|result| result.name == name)
+    }
+
+    /// Severity of the report: one failure is a warning, more are critical
+    pub fn health(&self) -> HealthStatus {
+        match self.failures().len() {
+            0 => HealthStatus::Healthy,
+            1 => HealthStatus::Warning,
+            _ => HealthStatus::Critical,
+        }
+    }
+
+    /// Publish p-values, statistics and failures as Prometheus metrics
+    pub fn export_metrics(&self) {
+        gauge!("qrng_quality_min_entropy_bits", self.min_entropy);
+        gauge!("qrng_quality_sample_bits", self.bits as f64);
+        for result in &self.results {
+            gauge!("qrng_quality_statistic", result.statistic, "test" => result.name.clone());
+            if let Some(p_value) = result.p_value {
+                gauge!("qrng_quality_p_value", p_value, "test" => result.name.clone());
+            }
+            if !result.passed {
+                counter!("qrng_quality_failures_total", 1, "test" => result.name.clone());
+            }
+        }
+    }
+
+    /// Raise a monitor alert when any test failed; returns whether one was published
+    pub fn raise_alerts(&self, monitor: &ProductionMonitor) -> bool {
+        let failures = self.failures();
+        if failures.is_empty() {
+            return false;
+        }
+        let names: Vec<&str> = failures.iter().map(|result| result.name.as_str()).collect();
+        let message = format!(
+            "QRNG output failed {} of {} randomness tests ({}) over {} bits, min-entropy {:.3}",
+            failures.len(),
+            self.results.len(),
+            names.join(", "),
+            self.bits,
+            self.min_entropy
+        );
+        monitor.raise_alert(
+            self.health(),
+            QUALITY_ALERT_COMPONENT,
+            &message,
+            vec![
+                "Re-run the suite on a larger sample to rule out chance failures".to_string(),
+                "Check quantum hardware calibration and detector bias".to_string(),
+                "Stop issuing keys from this source until the tests pass".to_string(),
+            ],
+        )
+    }
+}
+
+/// Runs the statistical test suite on QRNG samples
+#[derive(Debug, Clone, Default)]
+pub struct RandomnessTester {
+    config: RandomnessTestConfig,
+}
+
+impl RandomnessTester {
+    /// Create a tester
+    pub fn new(config: RandomnessTestConfig) -> Self {
+        Self { config }
+    }
+
+    /// Test settings
+    pub fn config(&self) -> &RandomnessTestConfig {
+        &self.config
+    }
+
+    /// Run the suite on packed bytes, most significant bit first
+    pub fn test_bytes(&self, bytes: &[u8]) -> Result<RandomnessReport> {
+        let bits: Vec<u8> = bytes
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1))
+            .collect();
+        self.test_bits(&bits)
+    }
+
+    /// Run the suite on bits, one per byte
+    pub fn test_bits(&self, bits: &[u8]) -> Result<RandomnessReport> {
+        if bits.len() < self.config.min_bits {
+            return Err(SecureCommsError::Validation(format!(
+                "Randomness tests need at least {} bits, got {}",
+                self.config.min_bits,
+                bits.len()
+            )));
+        }
+        if bits.iter().any(|&bit| bit > 1) {
+            return Err(SecureCommsError::Validation(
+                "Randomness test input must be one bit per byte".to_string(),
+            ));
+        }
+        let alpha = self.config.significance;
+        let p_test = |name: &str, (statistic, p_value): (f64, f64)| TestResult {
+            name: name.to_string(),
+            statistic,
+            p_value: Some(p_value),
+            passed: p_value >= alpha,
+        };
+
+        let mut estimator = MinEntropyEstimator::default();
+        estimator.observe(bits);
+        let min_entropy = estimator.estimate().unwrap_or(0.0);
+        let (serial_1, serial_2) = serial(bits, self.config.serial_pattern_bits);
+
+        Ok(RandomnessReport {
+            bits: bits.len(),
+            results: vec![
+                p_test("monobit", monobit(bits)),
+                p_test("runs", runs(bits)),
+                p_test("serial_1", serial_1),
+                p_test("serial_2", serial_2),
+                TestResult {
+                    name: "min_entropy".to_string(),
+                    statistic: min_entropy,
+                    p_value: None,
+                    passed: min_entropy >= self.config.min_entropy_per_bit,
+                },
+            ],
+            min_entropy,
+        })
+    }
+}
+
+/// Frequency (monobit) test: normalised partial sum and p-value
+fn monobit(bits: &[u8]) -> (f64, f64) {
+    let n = bits.len() as f64;
+    let sum: i64 = bits.iter().map(|&bit| 2 * bit as i64 - 1).sum();
+    let statistic = sum.unsigned_abs() as f64 / n.sqrt();
+    (statistic, erfc(statistic / std::f64::consts::SQRT_2))
+}
+
+/// Runs test: observed run count and p-value
+///
+/// A sample failing the frequency prerequisite gets p-value 0.
+fn runs(bits: &[u8]) -> (f64, f64) {
+    let n = bits.len() as f64;
+    let pi = bits.iter().filter(|&&bit| bit == 1).count() as f64 / n;
+    let observed = 1 + bits.windows(2).filter(|pair| pair[0] != pair[1]).count();
+    let observed = observed as f64;
+    if (pi - 0.5).abs() >= 2.0 / n.sqrt() {
+        return (observed, 0.0);
+    }
+    let expected = 2.0 * n * pi * (1.0 - pi);
+    let p_value = erfc((observed - expected).abs() / (2.0 * (2.0 * n).sqrt() * pi * (1.0 - pi)));
+    (observed, p_value)
+}
+
+/// Serial test: (∇ψ², p₁) and (∇²ψ², p₂) for patterns of `m` bits
+fn serial(bits: &[u8], m: u32) -> ((f64, f64), (f64, f64)) {
+    let m = m.max(2);
+    let psi_m = psi_squared(bits, m);
+    let psi_m1 = psi_squared(bits, m - 1);
+    let psi_m2 = psi_squared(bits, m - 2);
+    let first = psi_m - psi_m1;
+    let second = psi_m - 2.0 * psi_m1 + psi_m2;
+    (
+        (first, igamc(2f64.powi(m as i32 - 2), first / 2.0)),
+        (second, igamc(2f64.powi(m as i32 - 3), second / 2.0)),
+    )
+}
+
+/// ψ²ₘ over overlapping m-bit patterns of the sequence extended cyclically
+fn psi_squared(bits: &[u8], m: u32) -> f64 {
+    if m == 0 {
+        return 0.0;
+    }
+    let n = bits.len();
+    let mask = (1usize << m) - 1;
+    let mut counts = vec![0u64; 1 << m];
+    let mut pattern = 0usize;
+    for i in 0..n + m as usize - 1 {
+        pattern = ((pattern << 1) | bits[i % n] as usize) & mask;
+        if i + 1 >= m as usize {
+            counts[pattern] += 1;
+        }
+    }
+    let sum: f64 = counts.iter().map(|&count| (count * count) as f64).sum();
+    (1u64 << m) as f64 / n as f64 * sum - n as f64
+}
+
+/// Complementary error function for x ≥ 0
+fn erfc(x: f64) -> f64 {
+    igamc(0.5, x * x)
+}
+
+/// Regularized upper incomplete gamma function Q(a, x)
+fn igamc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series for P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..1000 {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        1.0 - sum * prefactor
+    } else {
+        // Lentz continued fraction for Q(a, x)
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        prefactor * h
+    }
+}
+
+/// ln Γ(x) for x ≥ 0.5 by the Lanczos approximation (g = 7)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(pattern: &str) -> Vec<u8> {
+        pattern.bytes().map(|c| c - b'0').collect()
+    }
+
+    #[test]
+    fn test_matches_nist_worked_examples() {
+        // Examples from NIST SP 800-22 rev 1a, §2.1.4, §2.3.4 and §2.11.4
+        let (_, p) = monobit(&bits("1011010101"));
+        assert!((p - 0.527089).abs() < 1e-6);
+        let (observed, p) = runs(&bits("1001101011"));
+        assert_eq!(observed, 7.0);
+        assert!((p - 0.147232).abs() < 1e-6);
+        let ((_, p1), (_, p2)) = serial(&bits("0011011101"), 3);
+        assert!((p1 - 0.808792).abs() < 1e-6);
+        assert!((p2 - 0.670320).abs() < 1e-6);
+        assert!((ln_gamma(5.0) - 24f64.ln()).abs() < 1e-12);
+    }
+
+    /// Deterministic, well-mixed bytes: SHA3-256 of a counter
+    fn hash_stream(bytes: usize) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        (0..bytes.div_ceil(32) as u64)
+            .flat_map(|i| Sha3_256::digest(i.to_le_bytes()).to_vec())
+            .take(bytes)
+            .collect()
+    }
+
+    #[test]
+    fn test_suite_separates_good_and_biased_sources() {
+        let tester = RandomnessTester::default();
+
+        let good = hash_stream(16384);
+        let report = tester.test_bytes(&good).unwrap();
+        assert_eq!(report.bits, 16384 * 8);
+        assert!(report.passed(), "{:?}", report.failures());
+        assert_eq!(report.health(), HealthStatus::Healthy);
+        assert!(report.min_entropy > 0.95);
+
+        // About 60% ones fails frequency, runs and entropy
+        let biased: Vec<u8> = hash_stream(65536)
+            .into_iter()
+            .map(|byte| (byte < 154) as u8)
+            .collect();
+        let report = tester.test_bits(&biased).unwrap();
+        assert!(!report.result("monobit").unwrap().passed);
+        assert_eq!(report.result("runs").unwrap().p_value, Some(0.0));
+        assert!(!report.result("min_entropy").unwrap().passed);
+        assert_eq!(report.health(), HealthStatus::Critical);
+
+        // Alternating bits are balanced but wildly non-random
+        let alternating: Vec<u8> = (0..8192).map(|i| (i % 2) as u8).collect();
+        let report = tester.test_bits(&alternating).unwrap();
+        assert!(report.result("monobit").unwrap().passed);
+        assert!(!report.result("runs").unwrap().passed);
+        assert!(!report.result("serial_1").unwrap().passed);
+
+        assert!(tester.test_bits(&alternating[..100]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_degraded_report_raises_alert() {
+        let monitor = crate::production_monitor::create_production_monitor();
+        let mut alerts = monitor.subscribe_to_alerts();
+        let tester = RandomnessTester::default();
+
+        let stuck = vec![0u8; 8192];
+        let report = tester.test_bits(&stuck).unwrap();
+        report.export_metrics();
+        assert!(report.raise_alerts(&monitor));
+        let alert = alerts.recv().await.unwrap();
+        assert_eq!(alert.component, QUALITY_ALERT_COMPONENT);
+        assert_eq!(alert.severity, HealthStatus::Critical);
+        assert!(alert.message.contains("monobit"));
+    }
+}
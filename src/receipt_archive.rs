@@ -0,0 +1,688 @@
+//! # Receipt Archive - Non-Repudiation Evidence for Dispute Resolution
+//!
+//! Keeps signed messages and the delivery receipts acknowledging them in a
+//! `Storage` backend, so that long after a channel is gone either side can
+//! show a third party what was said and that it arrived.
+//!
+//! ## Records
+//!
+//! Every message carrying a transcript signature is archived once, sent or
+//! received, exactly as it was signed. A delivery receipt for the message
+//! is attached when it arrives (for sent messages) or is issued (for
+//! received ones). Each record also holds a [`KeyValidityProof`] for every
+//! key involved: the archiving node's signed statement that, when the
+//! message was archived, the key was the one pinned for that peer and was
+//! trusted.
+//!
+//! ## Evidence Bundles
+//!
+//! [`ReceiptArchive::evidence_bundle`] returns the message, its signature,
+//! the receipt and the key proofs as one self-contained
+//! [`EvidenceBundle`]. [`EvidenceBundle::verify`] checks it without access
+//! to the archive or either client: the transcript signature against the
+//! signer's proven keys, the receipt's signature and binding to the message,
+//! and every proof's attestation.
+//!
+//! ## Storage Layout
+//!
+//! - `m/<message ID>`: Archived record, sealed when a cipher is configured
+//! - `t/<archived at>/<message ID>`: Empty marker ordering records by age
+//!
+//! ## Retention
+//!
+//! `apply_retention` deletes records older than `max_age_seconds` and then
+//! the oldest records beyond `max_records`, both entries of a record in one
+//! batch.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//!
+//! # async fn example(client: &StreamlinedSecureClient) -> quantum_forge_secure_comms::Result<()> {
+//! let bundle = client.evidence_bundle("message-id")?;
+//! bundle.verify()?;
+//! println!(
+//!     "{} signed by {}, delivery proven: {}",
+//!     bundle.message.message_id,
+//!     bundle.message.sender_id,
+//!     bundle.receipt.is_some()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::address_book::TrustState;
+use crate::consensus_verify::verify_commit_signature;
+use crate::envelope::{Envelope, EnvelopeCipher};
+use crate::hybrid_signature::{verify_transcript, MlDsaPublicKey, SignatureMode};
+use crate::key_manager::KeyManager;
+use crate::receipts::{payload_hash, DeliveryReceipt};
+use crate::storage::{Storage, StorageBackend, WriteBatch};
+use crate::streamlined_client::{message_transcript, SecureMessage};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Envelope context binding sealed records to the archive
+const ARCHIVE_CONTEXT: &str = "receipt_archive";
+
+/// Key prefix of archived records
+const RECORD_PREFIX: &str = "m/";
+
+/// Key prefix of the age index
+const TIME_PREFIX: &str = "t/";
+
+/// Domain separator for key validity attestations
+const KEY_PROOF_DOMAIN: &[u8] = b"quantum-forge/key-validity/v1";
+
+/// How long archived evidence is kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveRetention {
+    /// Age after which records are deleted (None keeps them forever)
+    pub max_age_seconds: Option<u64>,
+    /// Most records kept, oldest deleted first (None for no limit)
+    pub max_records: Option<usize>,
+}
+
+impl Default for ArchiveRetention {
+    fn default() -> Self {
+        Self {
+            max_age_seconds: Some(365 * 24 * 3600),
+            max_records: None,
+        }
+    }
+}
+
+/// Where and for how long evidence is archived
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    /// Storage backend holding the archive
+    pub backend: StorageBackend,
+    /// Retention applied by `apply_retention`
+    #[serde(default)]
+    pub retention: ArchiveRetention,
+}
+
+impl ArchiveConfig {
+    /// Reject retention limits that would delete everything
+    pub fn validate(&self) -> Result<()> {
+        if self.retention.max_age_seconds == Some(0) || self.retention.max_records == Some(0) {
+            return Err(SecureCommsError::Configuration(
+                "Archive retention limits must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether the archiving node sent or received a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveDirection {
+    Sent,
+    Received,
+}
+
+/// Signed statement that a peer's key was pinned and trusted at a time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyValidityProof {
+    /// Peer the key belongs to
+    pub peer_id: String,
+    /// Ed25519 identity key, which also signs receipts
+    pub ed25519_key: Vec<u8>,
+    /// ML-DSA key verifying hybrid signatures, if known
+    pub ml_dsa_key: Option<MlDsaPublicKey>,
+    /// Trust state of the key when attested
+    pub trust: TrustState,
+    /// When the key was first pinned (Unix seconds)
+    pub pinned_since: u64,
+    /// When the key was attested (Unix seconds)
+    pub attested_at: u64,
+    /// Node making the statement
+    pub attester_id: String,
+    /// Attester's Ed25519 key
+    pub attester_key: Vec<u8>,
+    /// Ed25519 signature over the other fields
+    pub attestation: Vec<u8>,
+}
+
+impl KeyValidityProof {
+    /// Attest a trusted key with the attester key `key_id` held by `keys`
+    ///
+    /// Keys awaiting review or revoked cannot be attested.
+    #[allow(clippy::too_many_arguments)]
+    pub fn attest(
+        peer_id: &str,
+        ed25519_key: Vec<u8>,
+        ml_dsa_key: Option<MlDsaPublicKey>,
+        trust: TrustState,
+        pinned_since: u64,
+        attester_id: &str,
+        keys: &KeyManager,
+        key_id: &str,
+    ) -> Result<Self> {
+        if !matches!(trust, TrustState::Pinned | TrustState::Verified) {
+            return Err(SecureCommsError::Validation(format!(
+                "Key of {} is {:?} and cannot be attested",
+                peer_id, trust
+            )));
+        }
+        let attester_key = keys
+            .public_key(key_id)
+            .ok_or_else(|| SecureCommsError::Validation(format!("Unknown key {}", key_id)))?
+            .to_vec();
+        let mut proof = Self {
+            peer_id: peer_id.to_string(),
+            ed25519_key,
+            ml_dsa_key,
+            trust,
+            pinned_since,
+            attested_at: chrono::Utc::now().timestamp() as u64,
+            attester_id: attester_id.to_string(),
+            attester_key,
+            attestation: Vec::new(),
+        };
+        proof.attestation = keys.sign(key_id, &proof.signing_payload())?;
+        Ok(proof)
+    }
+
+    /// Bytes covered by the attestation
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let ml_dsa_key = serde_json::to_vec(&self.ml_dsa_key).unwrap_or_default();
+        let trust = format!("{:?}", self.trust);
+        let mut payload = KEY_PROOF_DOMAIN.to_vec();
+        for field in [
+            self.peer_id.as_bytes(),
+            self.ed25519_key.as_slice(),
+            ml_dsa_key.as_slice(),
+            trust.as_bytes(),
+            self.attester_id.as_bytes(),
+            self.attester_key.as_slice(),
+        ] {
+            payload.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            payload.extend_from_slice(field);
+        }
+        payload.extend_from_slice(&self.pinned_since.to_be_bytes());
+        payload.extend_from_slice(&self.attested_at.to_be_bytes());
+        payload
+    }
+
+    /// Check the attestation and that the key was trusted
+    pub fn verify(&self) -> Result<()> {
+        if !matches!(self.trust, TrustState::Pinned | TrustState::Verified)
+            || !verify_commit_signature(
+                &self.attester_key,
+                &self.signing_payload(),
+                &self.attestation,
+            )
+        {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+}
+
+/// One archived message with its receipt and key proofs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    /// Message as signed, with its transcript signature
+    pub message: SecureMessage,
+    pub direction: ArchiveDirection,
+    /// When the message was archived (Unix seconds)
+    pub archived_at: u64,
+    /// Receipt acknowledging the message, once known
+    pub receipt: Option<DeliveryReceipt>,
+    /// Proofs for the signer's and the receipt signer's keys
+    pub key_proofs: Vec<KeyValidityProof>,
+}
+
+/// Self-contained evidence for one message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceBundle {
+    /// Message as signed; its `transcript_signature` is the signature
+    pub message: SecureMessage,
+    pub direction: ArchiveDirection,
+    /// When the message was archived (Unix seconds)
+    pub archived_at: u64,
+    /// Recipient's signed delivery receipt, if one was received or issued
+    pub receipt: Option<DeliveryReceipt>,
+    /// Proofs for the keys the signature and receipt were checked against
+    pub key_proofs: Vec<KeyValidityProof>,
+    /// When the bundle was produced (Unix seconds)
+    pub produced_at: u64,
+}
+
+impl EvidenceBundle {
+    /// Check the bundle without the archive or either client
+    ///
+    /// Every key proof must verify. The transcript signature must verify
+    /// against the proven keys of the message's sender, and a receipt must
+    /// be signed by the proven key of the message's recipient and name the
+    /// same message, sender and recipient. Any failure is
+    /// `SecureCommsError::AuthenticationFailed`.
+    pub fn verify(&self) -> Result<()> {
+        for proof in &self.key_proofs {
+            proof.verify()?;
+        }
+        let signer = self
+            .proof_for(&self.message.sender_id)
+            .ok_or(SecureCommsError::AuthenticationFailed)?;
+        let signature = self
+            .message
+            .transcript_signature
+            .as_ref()
+            .ok_or(SecureCommsError::AuthenticationFailed)?;
+        verify_transcript(
+            SignatureMode::Classical,
+            &signer.ed25519_key,
+            signer.ml_dsa_key.as_ref(),
+            &message_transcript(&self.message),
+            Some(signature),
+        )
+        .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+
+        if let Some(receipt) = &self.receipt {
+            let receipt_signer = self
+                .proof_for(&self.message.recipient_id)
+                .ok_or(SecureCommsError::AuthenticationFailed)?;
+            if receipt.message_id != self.message.message_id
+                || receipt.sender_id != self.message.sender_id
+                || receipt.recipient_id != self.message.recipient_id
+                || receipt.signer_public_key != receipt_signer.ed25519_key
+            {
+                return Err(SecureCommsError::AuthenticationFailed);
+            }
+            receipt.verify()?;
+        }
+        Ok(())
+    }
+
+    /// Whether the receipt's payload hash matches the signed payload
+    ///
+    /// Receipts hash the payload the recipient's application saw, so an
+    /// interceptor rewriting payloads (compression, for one) makes this
+    /// false even for a genuine receipt.
+    pub fn payload_matches(&self) -> bool {
+        self.receipt
+            .as_ref()
+            .map(|receipt| receipt.payload_hash == payload_hash(&self.message.payload))
+            .unwrap_or(false)
+    }
+
+    fn proof_for(&self, peer_id: &str) -> Option<&KeyValidityProof> {
+        self.key_proofs
+            .iter()
+            .find(|proof| proof.peer_id == peer_id)
+    }
+}
+
+/// Storage-backed archive of signed messages and receipts
+#[derive(Debug)]
+pub struct ReceiptArchive {
+    storage: Arc<dyn Storage>,
+    cipher: Option<Arc<EnvelopeCipher>>,
+    retention: ArchiveRetention,
+    records: usize,
+}
+
+impl ReceiptArchive {
+    /// Open the configured backend, sealing records when a cipher is given
+    pub fn open(config: &ArchiveConfig, cipher: Option<Arc<EnvelopeCipher>>) -> Result<Self> {
+        config.validate()?;
+        Self::with_storage(config.backend.open()?, cipher, config.retention.clone())
+    }
+
+    /// Archive in any storage backend
+    pub fn with_storage(
+        storage: Arc<dyn Storage>,
+        cipher: Option<Arc<EnvelopeCipher>>,
+        retention: ArchiveRetention,
+    ) -> Result<Self> {
+        let records = storage.scan_prefix(TIME_PREFIX.as_bytes())?.len();
+        Ok(Self {
+            storage,
+            cipher,
+            retention,
+            records,
+        })
+    }
+
+    /// Archive a signed message with the proof for its signer's key
+    ///
+    /// Returns false without writing if the message is already archived.
+    /// Unsigned messages fail with `SecureCommsError::Validation`.
+    pub fn archive_message(
+        &mut self,
+        message: &SecureMessage,
+        direction: ArchiveDirection,
+        signer_proof: KeyValidityProof,
+    ) -> Result<bool> {
+        if message.transcript_signature.is_none() {
+            return Err(SecureCommsError::Validation(format!(
+                "Message {} carries no transcript signature",
+                message.message_id
+            )));
+        }
+        if self
+            .storage
+            .get(&record_key(&message.message_id))?
+            .is_some()
+        {
+            return Ok(false);
+        }
+        let record = ArchiveRecord {
+            message: message.clone(),
+            direction,
+            archived_at: chrono::Utc::now().timestamp() as u64,
+            receipt: None,
+            key_proofs: vec![signer_proof],
+        };
+        let mut batch = WriteBatch::new();
+        batch
+            .put(&record_key(&message.message_id), &self.encode(&record)?)
+            .put(&time_key(record.archived_at, &message.message_id), &[]);
+        self.storage.write_batch(batch)?;
+        self.records += 1;
+        Ok(true)
+    }
+
+    /// Attach a receipt and the proof for its signer's key
+    ///
+    /// Returns false if the receipt's message is not archived.
+    pub fn attach_receipt(
+        &mut self,
+        receipt: &DeliveryReceipt,
+        signer_proof: KeyValidityProof,
+    ) -> Result<bool> {
+        let Some(mut record) = self.record(&receipt.message_id)? else {
+            return Ok(false);
+        };
+        record.receipt = Some(receipt.clone());
+        record
+            .key_proofs
+            .retain(|proof| proof.peer_id != signer_proof.peer_id);
+        record.key_proofs.push(signer_proof);
+        self.storage
+            .put(&record_key(&receipt.message_id), &self.encode(&record)?)?;
+        Ok(true)
+    }
+
+    /// Archived record of a message
+    pub fn record(&self, message_id: &str) -> Result<Option<ArchiveRecord>> {
+        match self.storage.get(&record_key(message_id))? {
+            Some(value) => {
+                let record = serde_json::from_slice(&self.decode(&value)?).map_err(|e| {
+                    SecureCommsError::SystemError(format!(
+                        "Corrupt archive record {}: {}",
+                        message_id, e
+                    ))
+                })?;
+                Ok(Some(record))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Evidence bundle for an archived message
+    pub fn evidence_bundle(&self, message_id: &str) -> Result<EvidenceBundle> {
+        let record = self.record(message_id)?.ok_or_else(|| {
+            SecureCommsError::Validation(format!("Message {} is not archived", message_id))
+        })?;
+        Ok(EvidenceBundle {
+            message: record.message,
+            direction: record.direction,
+            archived_at: record.archived_at,
+            receipt: record.receipt,
+            key_proofs: record.key_proofs,
+            produced_at: chrono::Utc::now().timestamp() as u64,
+        })
+    }
+
+    /// Delete records past the retention limits as of `now` (Unix seconds)
+    ///
+    /// Returns the number of records deleted.
+    pub fn apply_retention(&mut self, now: u64) -> Result<usize> {
+        let index = self.storage.scan_prefix(TIME_PREFIX.as_bytes())?;
+        let cutoff = self
+            .retention
+            .max_age_seconds
+            .map(|max_age| now.saturating_sub(max_age));
+        let excess = self
+            .retention
+            .max_records
+            .map(|max_records| index.len().saturating_sub(max_records))
+            .unwrap_or(0);
+
+        let mut batch = WriteBatch::new();
+        let mut deleted = 0;
+        // The index scans oldest first
+        for (position, (key, _)) in index.iter().enumerate() {
+            let (archived_at, message_id) = parse_time_key(key)?;
+            let expired = cutoff.map(|cutoff| archived_at < cutoff).unwrap_or(false);
+            if !expired && position >= excess {
+                break;
+            }
+            batch.delete(key).delete(&record_key(&message_id));
+            deleted += 1;
+        }
+        self.storage.write_batch(batch)?;
+        self.records -= deleted;
+        Ok(deleted)
+    }
+
+    /// Number of archived records
+    pub fn len(&self) -> usize {
+        self.records
+    }
+
+    /// Whether the archive holds no records
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+    }
+
+    fn encode(&self, record: &ArchiveRecord) -> Result<Vec<u8>> {
+        let value = serde_json::to_vec(record).map_err(|e| {
+            SecureCommsError::SystemError(format!("Archive serialization failed: {}", e))
+        })?;
+        match &self.cipher {
+            Some(cipher) => cipher.seal_bytes(ARCHIVE_CONTEXT, &value),
+            None => Ok(value),
+        }
+    }
+
+    fn decode(&self, value: &[u8]) -> Result<Vec<u8>> {
+        let Some(envelope) = Envelope::from_bytes(value) else {
+            return Ok(value.to_vec());
+        };
+        let cipher = self.cipher.as_ref().ok_or_else(|| {
+            SecureCommsError::Configuration(
+                "Sealed archive record but no cipher configured".to_string(),
+            )
+        })?;
+        cipher.open(ARCHIVE_CONTEXT, &envelope)
+    }
+}
+
+fn record_key(message_id: &str) -> Vec<u8> {
+    format!("{}{}", RECORD_PREFIX, message_id).into_bytes()
+}
+
+fn time_key(archived_at: u64, message_id: &str) -> Vec<u8> {
+    format!("{}{:020}/{}", TIME_PREFIX, archived_at, message_id).into_bytes()
+}
+
+fn parse_time_key(key: &[u8]) -> Result<(u64, String)> {
+    let corrupt = || {
+        SecureCommsError::SystemError(format!(
+            "Corrupt archive index key {}",
+            String::from_utf8_lossy(key)
+        ))
+    };
+    let entry = std::str::from_utf8(&key[TIME_PREFIX.len()..]).map_err(|_| corrupt())?;
+    let (archived_at, message_id) = entry.split_once('/').ok_or_else(corrupt)?;
+    Ok((
+        archived_at.parse().map_err(|_| corrupt())?,
+        message_id.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hybrid_signature::{sign_transcript, MlDsaKeyPair, MlDsaLevel};
+    use crate::key_manager::KeyPurpose;
+    use crate::storage::MemoryStorage;
+
+    struct Party {
+        id: &'static str,
+        keys: KeyManager,
+        ml_dsa: MlDsaKeyPair,
+    }
+
+    impl Party {
+        fn new(id: &'static str, seed: u8) -> Self {
+            let mut keys = KeyManager::new();
+            keys.import_key("identity", KeyPurpose::Signing, [seed; 32])
+                .unwrap();
+            Self {
+                id,
+                keys,
+                ml_dsa: MlDsaKeyPair::generate(MlDsaLevel::MlDsa44).unwrap(),
+            }
+        }
+
+        fn proof_by(&self, attester: &Party, trust: TrustState) -> Result<KeyValidityProof> {
+            KeyValidityProof::attest(
+                self.id,
+                self.keys.public_key("identity").unwrap().to_vec(),
+                Some(self.ml_dsa.public_key()),
+                trust,
+                1_700_000_000,
+                attester.id,
+                &attester.keys,
+                "identity",
+            )
+        }
+    }
+
+    fn signed_message(from: &Party, to: &Party, payload: &[u8]) -> SecureMessage {
+        let mut message =
+            SecureMessage::new(from.id.to_string(), to.id.to_string(), payload.to_vec());
+        message.transcript_signature = Some(
+            sign_transcript(
+                SignatureMode::Hybrid,
+                &message_transcript(&message),
+                &from.keys,
+                "identity",
+                &from.ml_dsa,
+            )
+            .unwrap(),
+        );
+        message
+    }
+
+    fn archive(retention: ArchiveRetention) -> ReceiptArchive {
+        ReceiptArchive::with_storage(Arc::new(MemoryStorage::new()), None, retention).unwrap()
+    }
+
+    #[test]
+    fn test_evidence_bundle_verifies_signature_and_receipt() {
+        let (alice, bob) = (Party::new("alice", 1), Party::new("bob", 2));
+        let mut archive = archive(ArchiveRetention::default());
+        let message = signed_message(&alice, &bob, b"transfer 10");
+        let proof = alice.proof_by(&alice, TrustState::Verified).unwrap();
+        assert!(archive
+            .archive_message(&message, ArchiveDirection::Sent, proof.clone())
+            .unwrap());
+        assert!(!archive
+            .archive_message(&message, ArchiveDirection::Sent, proof)
+            .unwrap());
+
+        let bundle = archive.evidence_bundle(&message.message_id).unwrap();
+        bundle.verify().unwrap();
+        assert!(!bundle.payload_matches());
+
+        let receipt = DeliveryReceipt::sign(
+            &message.message_id,
+            "alice",
+            "bob",
+            b"transfer 10",
+            &bob.keys,
+            "identity",
+        )
+        .unwrap();
+        let bob_proof = bob.proof_by(&alice, TrustState::Pinned).unwrap();
+        assert!(archive.attach_receipt(&receipt, bob_proof).unwrap());
+        let bundle = archive.evidence_bundle(&message.message_id).unwrap();
+        bundle.verify().unwrap();
+        assert!(bundle.payload_matches());
+        assert_eq!(archive.len(), 1);
+        assert!(archive.evidence_bundle("unknown").is_err());
+    }
+
+    #[test]
+    fn test_tampered_evidence_is_rejected() {
+        let (alice, bob) = (Party::new("alice", 1), Party::new("bob", 2));
+        let mut archive = archive(ArchiveRetention::default());
+        let message = signed_message(&bob, &alice, b"vote yes");
+        let proof = bob.proof_by(&alice, TrustState::Pinned).unwrap();
+        archive
+            .archive_message(&message, ArchiveDirection::Received, proof)
+            .unwrap();
+        let bundle = archive.evidence_bundle(&message.message_id).unwrap();
+        bundle.verify().unwrap();
+
+        let mut altered = bundle.clone();
+        altered.message.payload = b"vote no".to_vec();
+        assert!(altered.verify().is_err());
+
+        // A proof naming another key for the sender does not verify the signature
+        let mallory = Party::new("bob", 3);
+        let mut substituted = bundle.clone();
+        substituted.key_proofs = vec![mallory.proof_by(&alice, TrustState::Pinned).unwrap()];
+        assert!(substituted.verify().is_err());
+
+        let mut forged_proof = bundle;
+        forged_proof.key_proofs[0].trust = TrustState::Verified;
+        assert!(forged_proof.verify().is_err());
+
+        // Revoked keys cannot be attested and unsigned messages are not archived
+        assert!(bob.proof_by(&alice, TrustState::Revoked).is_err());
+        let unsigned = SecureMessage::new("bob".to_string(), "alice".to_string(), b"x".to_vec());
+        let proof = bob.proof_by(&alice, TrustState::Pinned).unwrap();
+        assert!(archive
+            .archive_message(&unsigned, ArchiveDirection::Received, proof)
+            .is_err());
+    }
+
+    #[test]
+    fn test_retention_deletes_oldest_records() {
+        let (alice, bob) = (Party::new("alice", 1), Party::new("bob", 2));
+        let mut archive = archive(ArchiveRetention {
+            max_age_seconds: Some(3600),
+            max_records: Some(2),
+        });
+        let mut ids = Vec::new();
+        for payload in [b"one", b"two", b"six"] {
+            let message = signed_message(&alice, &bob, payload);
+            let proof = alice.proof_by(&alice, TrustState::Verified).unwrap();
+            archive
+                .archive_message(&message, ArchiveDirection::Sent, proof)
+                .unwrap();
+            ids.push(message.message_id);
+        }
+        assert_eq!(archive.len(), 3);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        assert_eq!(archive.apply_retention(now).unwrap(), 1);
+        assert_eq!(archive.len(), 2);
+        assert_eq!(archive.apply_retention(now).unwrap(), 0);
+        // Everything is past the age limit an hour and a bit later
+        assert_eq!(archive.apply_retention(now + 3_700).unwrap(), 2);
+        assert!(archive.is_empty());
+        assert!(ids
+            .iter()
+            .all(|message_id| archive.record(message_id).unwrap().is_none()));
+    }
+}
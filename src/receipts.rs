@@ -0,0 +1,431 @@
+//! # Delivery Receipts - Signed End-to-End Acknowledgements
+//!
+//! A sender can ask the recipient to acknowledge a message. The recipient
+//! answers with a receipt signed by its long-term Ed25519 receipt key and
+//! bound to the message ID and a SHA3 hash of the payload it received, so
+//! the sender gets cryptographic proof that exactly this message reached
+//! the application of exactly this peer.
+//!
+//! ## Receipt Flow
+//!
+//! - **Request**: The sender marks the message with [`RECEIPT_REQUEST_HEADER`]
+//!   and starts tracking it
+//! - **Acknowledge**: The receiving client signs a [`DeliveryReceipt`] when
+//!   the message is delivered and returns it marked with [`RECEIPT_HEADER`]
+//! - **Confirm**: The sender checks signature, message binding, payload hash
+//!   and the peer's pinned receipt key before marking the message delivered
+//!
+//! ## Trust Model
+//!
+//! The first valid receipt from a peer pins its receipt key; receipts signed
+//! by any other key are rejected afterwards. Keys can also be pinned up front
+//! with [`ReceiptTracker::pin_peer_key`].
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! client.establish_secure_channel("peer_1").await?;
+//!
+//! // Block until the peer acknowledges
+//! let receipt = client
+//!     .send_with_receipt("peer_1", b"settle batch 42", Duration::from_secs(5))
+//!     .await?;
+//! println!("Delivered at {}", receipt.received_at);
+//!
+//! // Or fire now and check later
+//! let message = client.send_requesting_receipt("peer_1", b"audit").await?;
+//! println!("{:?}", client.receipt_status(&message.message_id));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::consensus_verify::verify_commit_signature;
+use crate::constant_time::ct_eq;
+use crate::key_manager::KeyManager;
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Header marking a message whose recipient should return a receipt
+pub const RECEIPT_REQUEST_HEADER: &str = "x-receipt-request";
+
+/// Header marking a message that carries a serialized receipt
+pub const RECEIPT_HEADER: &str = "x-delivery-receipt";
+
+/// Key ID of the receipt signing key in the client's key manager
+pub const RECEIPT_KEY_ID: &str = "delivery_receipts";
+
+/// Default time a sender waits for a receipt before giving up
+pub const DEFAULT_RECEIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Domain separator for receipt signatures
+const RECEIPT_DOMAIN: &[u8] = b"quantum-forge/delivery-receipt/v1";
+
+/// SHA3-256 hash of a message payload
+pub fn payload_hash(payload: &[u8]) -> Vec<u8> {
+    use sha3::{Digest, Sha3_256};
+    Sha3_256::digest(payload).to_vec()
+}
+
+/// Signed acknowledgement that a message reached its recipient
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryReceipt {
+    /// Acknowledged message
+    pub message_id: String,
+    /// Sender of the acknowledged message
+    pub sender_id: String,
+    /// Recipient that signed the receipt
+    pub recipient_id: String,
+    /// SHA3-256 hash of the payload as delivered
+    pub payload_hash: Vec<u8>,
+    /// Delivery time (Unix seconds)
+    pub received_at: u64,
+    /// Recipient's Ed25519 receipt public key
+    pub signer_public_key: Vec<u8>,
+    /// Ed25519 signature over the receipt fields
+    pub signature: Vec<u8>,
+}
+
+impl DeliveryReceipt {
+    /// Create and sign a receipt with a key held by `keys`
+    pub fn sign(
+        message_id: &str,
+        sender_id: &str,
+        recipient_id: &str,
+        payload: &[u8],
+        keys: &KeyManager,
+        key_id: &str,
+    ) -> Result<Self> {
+        let signer_public_key = keys
+            .public_key(key_id)
+            .ok_or_else(|| SecureCommsError::Validation(format!("Unknown key {}", key_id)))?
+            .to_vec();
+        let mut receipt = Self {
+            message_id: message_id.to_string(),
+            sender_id: sender_id.to_string(),
+            recipient_id: recipient_id.to_string(),
+            payload_hash: payload_hash(payload),
+            received_at: chrono::Utc::now().timestamp() as u64,
+            signer_public_key,
+            signature: Vec::new(),
+        };
+        receipt.signature = keys.sign(key_id, &receipt.signing_payload())?;
+        Ok(receipt)
+    }
+
+    /// Bytes covered by the signature
+    ///
+    /// Every variable-length field is length-prefixed so that no two
+    /// receipts share a signing payload.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = RECEIPT_DOMAIN.to_vec();
+        for field in [
+            self.message_id.as_bytes(),
+            self.sender_id.as_bytes(),
+            self.recipient_id.as_bytes(),
+            self.payload_hash.as_slice(),
+            self.signer_public_key.as_slice(),
+        ] {
+            payload.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            payload.extend_from_slice(field);
+        }
+        payload.extend_from_slice(&self.received_at.to_be_bytes());
+        payload
+    }
+
+    /// Check the signature against the embedded public key
+    pub fn verify(&self) -> Result<()> {
+        if verify_commit_signature(
+            &self.signer_public_key,
+            &self.signing_payload(),
+            &self.signature,
+        ) {
+            Ok(())
+        } else {
+            Err(SecureCommsError::AuthenticationFailed)
+        }
+    }
+
+    /// Serialize for transport
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|e| SecureCommsError::Validation(format!("Receipt encoding failed: {}", e)))
+    }
+
+    /// Deserialize from transport
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| SecureCommsError::Validation(format!("Malformed receipt: {}", e)))
+    }
+}
+
+/// Delivery state of a message that requested a receipt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReceiptStatus {
+    /// Waiting for the recipient's receipt
+    Pending,
+    /// Valid receipt received
+    Delivered(DeliveryReceipt),
+    /// No receipt arrived before the deadline
+    TimedOut,
+}
+
+#[derive(Debug, Clone)]
+struct PendingReceipt {
+    peer_id: String,
+    payload_hash: Vec<u8>,
+    deadline_ms: u64,
+}
+
+/// Sender-side bookkeeping of requested and confirmed receipts
+#[derive(Debug, Clone, Default)]
+pub struct ReceiptTracker {
+    pending: HashMap<String, PendingReceipt>,
+    delivered: HashMap<String, DeliveryReceipt>,
+    timed_out: HashMap<String, String>,
+    peer_keys: HashMap<String, Vec<u8>>,
+    rejected: u64,
+}
+
+impl ReceiptTracker {
+    /// Create empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start waiting for the receipt of a sent message
+    pub fn track(&mut self, message_id: &str, peer_id: &str, payload: &[u8], timeout: Duration) {
+        self.pending.insert(
+            message_id.to_string(),
+            PendingReceipt {
+                peer_id: peer_id.to_string(),
+                payload_hash: payload_hash(payload),
+                deadline_ms: crate::expiry::deadline_after(timeout),
+            },
+        );
+    }
+
+    /// Pin the receipt key expected from peer
+    pub fn pin_peer_key(&mut self, peer_id: &str, public_key: Vec<u8>) {
+        self.peer_keys.insert(peer_id.to_string(), public_key);
+    }
+
+    /// Forget the receipt key pinned for peer
+    pub fn unpin_peer_key(&mut self, peer_id: &str) -> Option<Vec<u8>> {
+        self.peer_keys.remove(peer_id)
+    }
+
+    /// Pinned receipt key of peer
+    pub fn peer_key(&self, peer_id: &str) -> Option<&[u8]> {
+        self.peer_keys.get(peer_id).map(|key| key.as_slice())
+    }
+
+    /// Validate a receipt from `from_peer` and mark its message delivered
+    ///
+    /// A repeated receipt for an already delivered message is ignored, so
+    /// retransmitted messages do not count as rejected receipts.
+    pub fn accept(&mut self, from_peer: &str, receipt: DeliveryReceipt) -> Result<()> {
+        if self
+            .delivered
+            .get(&receipt.message_id)
+            .map(|delivered| delivered.recipient_id == from_peer)
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+
+        let result = self.check(from_peer, &receipt);
+        if result.is_err() {
+            self.rejected += 1;
+            return result;
+        }
+
+        self.pending.remove(&receipt.message_id);
+        self.peer_keys
+            .entry(from_peer.to_string())
+            .or_insert_with(|| receipt.signer_public_key.clone());
+        self.delivered.insert(receipt.message_id.clone(), receipt);
+        Ok(())
+    }
+
+    fn check(&self, from_peer: &str, receipt: &DeliveryReceipt) -> Result<()> {
+        let pending = self.pending.get(&receipt.message_id).ok_or_else(|| {
+            SecureCommsError::Validation(format!(
+                "No receipt pending for message {}",
+                receipt.message_id
+            ))
+        })?;
+        if receipt.recipient_id != from_peer || pending.peer_id != from_peer {
+            return Err(SecureCommsError::Validation(format!(
+                "Receipt for {} not signed by its recipient",
+                receipt.message_id
+            )));
+        }
+        if !ct_eq(&receipt.payload_hash, &pending.payload_hash) {
+            return Err(SecureCommsError::Validation(format!(
+                "Receipt for {} acknowledges a different payload",
+                receipt.message_id
+            )));
+        }
+        if let Some(pinned) = self.peer_keys.get(from_peer) {
+            if !ct_eq(pinned, &receipt.signer_public_key) {
+                return Err(SecureCommsError::AuthenticationFailed);
+            }
+        }
+        receipt.verify()
+    }
+
+    /// Move pending receipts past their deadline to timed out
+    pub fn expire_overdue(&mut self, now_ms: u64) -> Vec<String> {
+        let overdue: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| now_ms > pending.deadline_ms)
+            .map(|(message_id, _)| message_id.clone())
+            .collect();
+        for message_id in &overdue {
+            if let Some(pending) = self.pending.remove(message_id) {
+                self.timed_out.insert(message_id.clone(), pending.peer_id);
+            }
+        }
+        overdue
+    }
+
+    /// Delivery state of a tracked message
+    ///
+    /// Pending receipts past their deadline report `TimedOut` even before
+    /// `expire_overdue` has moved them.
+    pub fn status(&self, message_id: &str) -> Option<ReceiptStatus> {
+        if let Some(receipt) = self.delivered.get(message_id) {
+            return Some(ReceiptStatus::Delivered(receipt.clone()));
+        }
+        if self.timed_out.contains_key(message_id) {
+            return Some(ReceiptStatus::TimedOut);
+        }
+        let now_ms = crate::expiry::now_ms();
+        self.pending.get(message_id).map(|pending| {
+            if now_ms > pending.deadline_ms {
+                ReceiptStatus::TimedOut
+            } else {
+                ReceiptStatus::Pending
+            }
+        })
+    }
+
+    /// Forget a tracked message, returning its last state
+    pub fn forget(&mut self, message_id: &str) -> Option<ReceiptStatus> {
+        let status = self.status(message_id);
+        self.pending.remove(message_id);
+        self.delivered.remove(message_id);
+        self.timed_out.remove(message_id);
+        status
+    }
+
+    /// Tracker statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert("pending".to_string(), serde_json::json!(self.pending.len()));
+        stats.insert(
+            "delivered".to_string(),
+            serde_json::json!(self.delivered.len()),
+        );
+        stats.insert(
+            "timed_out".to_string(),
+            serde_json::json!(self.timed_out.len()),
+        );
+        stats.insert("rejected".to_string(), serde_json::json!(self.rejected));
+        stats.insert(
+            "pinned_keys".to_string(),
+            serde_json::json!(self.peer_keys.len()),
+        );
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_manager::KeyPurpose;
+
+    fn signer(seed: u8) -> KeyManager {
+        let mut keys = KeyManager::new();
+        keys.import_key(RECEIPT_KEY_ID, KeyPurpose::Signing, [seed; 32])
+            .unwrap();
+        keys
+    }
+
+    fn receipt(keys: &KeyManager, payload: &[u8]) -> DeliveryReceipt {
+        DeliveryReceipt::sign("msg_1", "alice", "bob", payload, keys, RECEIPT_KEY_ID).unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keys = signer(7);
+        let mut receipt = receipt(&keys, b"payload");
+        assert!(receipt.verify().is_ok());
+
+        let decoded = DeliveryReceipt::from_bytes(&receipt.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, receipt);
+
+        // Rebinding to another message breaks the signature
+        receipt.message_id = "msg_2".to_string();
+        assert!(matches!(
+            receipt.verify(),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_tracker_accepts_matching_receipt() {
+        let keys = signer(7);
+        let mut tracker = ReceiptTracker::new();
+        tracker.track("msg_1", "bob", b"payload", Duration::from_secs(5));
+        assert_eq!(tracker.status("msg_1"), Some(ReceiptStatus::Pending));
+
+        // Wrong payload and wrong peer are rejected without resolving
+        assert!(tracker.accept("bob", receipt(&keys, b"other")).is_err());
+        assert!(tracker
+            .accept("mallory", receipt(&keys, b"payload"))
+            .is_err());
+        assert_eq!(tracker.status("msg_1"), Some(ReceiptStatus::Pending));
+
+        let valid = receipt(&keys, b"payload");
+        tracker.accept("bob", valid.clone()).unwrap();
+        assert_eq!(
+            tracker.status("msg_1"),
+            Some(ReceiptStatus::Delivered(valid.clone()))
+        );
+        // Duplicate receipts for a retransmitted message are harmless
+        assert!(tracker.accept("bob", valid).is_ok());
+        assert_eq!(tracker.peer_key("bob"), keys.public_key(RECEIPT_KEY_ID));
+        assert_eq!(tracker.get_stats()["rejected"], serde_json::json!(2));
+    }
+
+    #[test]
+    fn test_pinned_key_and_timeouts() {
+        let mut tracker = ReceiptTracker::new();
+        tracker.pin_peer_key(
+            "bob",
+            signer(7).public_key(RECEIPT_KEY_ID).unwrap().to_vec(),
+        );
+        tracker.track("msg_1", "bob", b"payload", Duration::from_secs(5));
+
+        // Validly signed, but not by the pinned key
+        assert!(matches!(
+            tracker.accept("bob", receipt(&signer(9), b"payload")),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        let overdue = tracker.expire_overdue(u64::MAX);
+        assert_eq!(overdue, vec!["msg_1".to_string()]);
+        assert_eq!(tracker.status("msg_1"), Some(ReceiptStatus::TimedOut));
+        assert_eq!(tracker.forget("msg_1"), Some(ReceiptStatus::TimedOut));
+        assert_eq!(tracker.status("msg_1"), None);
+    }
+}
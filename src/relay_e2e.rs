@@ -0,0 +1,431 @@
+//! # Relay E2E - End-to-End Encryption Through Untrusted Relays
+//!
+//! Messages routed over relay nodes are sealed for the final recipient, so
+//! each relay sees only the routing header it needs to forward them. The
+//! sender encapsulates a fresh secret to the recipient's pinned ML-KEM key
+//! and seals the payload under a key derived from it; relays hold no
+//! secret that opens it.
+//!
+//! ## Envelope Layout
+//!
+//! - **header**: Sender, recipient, message ID, relay route and optional
+//!   delivery deadline, visible to every hop
+//! - **kem_ciphertext**: ML-KEM encapsulation to the recipient's key
+//! - **sealed**: Payload sealed with the header as associated data
+//! - **signature**: Sender's Ed25519 identity signature over the header and
+//!   both ciphertexts
+//!
+//! Relays check the signature against the sender's pinned identity key
+//! before forwarding, so forged or altered envelopes are dropped at the
+//! first honest hop without any relay being able to read them. The hop
+//! position is the only field relays change and is not signed.
+//!
+//! ## Key Separation
+//!
+//! The recipient key is the semi-static ML-KEM key also used for key
+//! updates. Content keys are derived with their own label over the
+//! encapsulated secret and the header, so they never coincide with a
+//! session key.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::{PQCAlgorithm, QRNG};
+//! use quantum_forge_secure_comms::crypto_provider::BuiltinKem;
+//! use quantum_forge_secure_comms::hw_accel::{CryptoDispatch, DispatchPolicy, NONCE_LEN};
+//! use quantum_forge_secure_comms::key_manager::{KeyManager, KeyPurpose};
+//! use quantum_forge_secure_comms::key_update::SemiStaticKemKey;
+//! use quantum_forge_secure_comms::relay_e2e::{RelayEnvelope, RoutingHeader};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut qrng = QRNG::from_seed(rand::random());
+//! let mut keys = KeyManager::new();
+//! keys.generate_key("identity", KeyPurpose::Signing, &mut qrng)?;
+//! let kem = BuiltinKem::new(PQCAlgorithm::Kyber768);
+//! let aead = CryptoDispatch::new(DispatchPolicy::Auto);
+//!
+//! let bob_kem = SemiStaticKemKey::generate(&kem)?;
+//! let bob_public = bob_kem.signed_public_key("bob", &keys, "identity")?;
+//! let header = RoutingHeader::new("alice", "bob", vec!["relay_1".to_string()]);
+//! let nonce = qrng.generate_bytes(NONCE_LEN)?;
+//! let mut envelope =
+//!     RelayEnvelope::seal(header, &bob_public, b"hello", &kem, &aead, &keys, "identity", &nonce)?;
+//!
+//! // The relay authenticates and forwards without decrypting
+//! envelope.verify_origin(keys.public_key("identity").unwrap())?;
+//! assert_eq!(envelope.advance("relay_1")?, "bob");
+//! assert_eq!(envelope.open("bob", &bob_kem, &kem, &aead)?, b"hello");
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::time::Duration;
+
+use crate::consensus_verify::verify_commit_signature;
+use crate::crypto_provider::{Aead, Kem};
+use crate::expiry::deadline_after;
+use crate::hw_accel::NONCE_LEN;
+use crate::key_manager::KeyManager;
+use crate::key_update::{KeyUpdatePublicKey, SemiStaticKemKey};
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Routing information visible to every relay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingHeader {
+    /// Originating client
+    pub sender_id: String,
+    /// Final recipient
+    pub recipient_id: String,
+    /// Unique message identifier
+    pub message_id: String,
+    /// Relays to traverse in order, excluding sender and recipient
+    pub route: Vec<String>,
+    /// Delivery deadline in Unix milliseconds
+    #[serde(default)]
+    pub expires_at_ms: Option<u64>,
+}
+
+impl RoutingHeader {
+    /// Header for a new message over `route`
+    pub fn new(sender_id: &str, recipient_id: &str, route: Vec<String>) -> Self {
+        Self {
+            sender_id: sender_id.to_string(),
+            recipient_id: recipient_id.to_string(),
+            message_id: uuid::Uuid::new_v4().to_string(),
+            route,
+            expires_at_ms: None,
+        }
+    }
+
+    /// Set a delivery deadline `ttl` from now
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.expires_at_ms = Some(deadline_after(ttl));
+        self
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        digest(
+            b"relay_e2e_header_v1",
+            &[&serde_json::to_vec(self).unwrap_or_default()],
+        )
+    }
+}
+
+/// Message sealed for its final recipient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayEnvelope {
+    /// Routing information
+    pub header: RoutingHeader,
+    /// Relays already traversed
+    pub hop: usize,
+    /// Fingerprint of the recipient key the secret is encapsulated to
+    pub recipient_key_id: String,
+    /// ML-KEM ciphertext
+    pub kem_ciphertext: Vec<u8>,
+    /// Payload sealed under the derived content key
+    pub sealed: Vec<u8>,
+    /// Sender's identity signature
+    pub signature: Vec<u8>,
+}
+
+impl RelayEnvelope {
+    /// Seal `payload` for the owner of `recipient_key` under a fresh nonce
+    ///
+    /// The recipient key must already be verified and pinned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn seal(
+        header: RoutingHeader,
+        recipient_key: &KeyUpdatePublicKey,
+        payload: &[u8],
+        kem: &dyn Kem,
+        aead: &dyn Aead,
+        keys: &KeyManager,
+        identity_key_id: &str,
+        nonce: &[u8],
+    ) -> Result<Self> {
+        if recipient_key.owner_id != header.recipient_id {
+            return Err(SecureCommsError::Validation(format!(
+                "Key of {} cannot seal for {}",
+                recipient_key.owner_id, header.recipient_id
+            )));
+        }
+        if nonce.len() != NONCE_LEN {
+            return Err(SecureCommsError::Validation(format!(
+                "Nonce is {} bytes, expected {}",
+                nonce.len(),
+                NONCE_LEN
+            )));
+        }
+        if kem.algorithm() != recipient_key.algorithm {
+            return Err(SecureCommsError::Configuration(format!(
+                "KEM provider implements {:?}, recipient key is {:?}",
+                kem.algorithm(),
+                recipient_key.algorithm
+            )));
+        }
+        let (kem_ciphertext, shared) = kem.encapsulate(&recipient_key.public_key)?;
+        let shared = SecretBytes::new(SensitiveKind::Key, shared);
+        let header_digest = header.digest();
+        let content_key = content_key(&shared, &header_digest);
+        let sealed = aead.seal(content_key.expose(), nonce, &header_digest, payload)?;
+
+        let mut envelope = Self {
+            header,
+            hop: 0,
+            recipient_key_id: recipient_key.key_id(),
+            kem_ciphertext,
+            sealed,
+            signature: Vec::new(),
+        };
+        envelope.signature = keys.sign(identity_key_id, &envelope.signed_payload())?;
+        Ok(envelope)
+    }
+
+    /// Check the sender's signature; relays call this before forwarding
+    pub fn verify_origin(&self, sender_identity_key: &[u8]) -> Result<()> {
+        if !verify_commit_signature(sender_identity_key, &self.signed_payload(), &self.signature) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+
+    /// Client the envelope should be delivered to next
+    pub fn next_hop(&self) -> &str {
+        self.header
+            .route
+            .get(self.hop)
+            .unwrap_or(&self.header.recipient_id)
+    }
+
+    /// Whether every relay has been traversed
+    pub fn at_recipient(&self) -> bool {
+        self.hop >= self.header.route.len()
+    }
+
+    /// Record the hop through `relay_id` and return the following hop
+    pub fn advance(&mut self, relay_id: &str) -> Result<String> {
+        if self.at_recipient() || self.next_hop() != relay_id {
+            return Err(SecureCommsError::Validation(format!(
+                "{} is not the next hop of message {}",
+                relay_id, self.header.message_id
+            )));
+        }
+        self.hop += 1;
+        Ok(self.next_hop().to_string())
+    }
+
+    /// Open the payload as its recipient
+    ///
+    /// A payload or header altered in flight fails with
+    /// `SecureCommsError::AuthenticationFailed`.
+    pub fn open(
+        &self,
+        local_id: &str,
+        local_key: &SemiStaticKemKey,
+        kem: &dyn Kem,
+        aead: &dyn Aead,
+    ) -> Result<Vec<u8>> {
+        if self.header.recipient_id != local_id || self.recipient_key_id != local_key.key_id() {
+            return Err(SecureCommsError::Validation(format!(
+                "Message {} is not sealed for this client",
+                self.header.message_id
+            )));
+        }
+        let shared = local_key.decapsulate(&self.kem_ciphertext, kem)?;
+        let header_digest = self.header.digest();
+        let content_key = content_key(&shared, &header_digest);
+        aead.open(content_key.expose(), &header_digest, &self.sealed)
+            .map_err(|_| SecureCommsError::AuthenticationFailed)
+    }
+
+    fn signed_payload(&self) -> Vec<u8> {
+        digest(
+            b"relay_e2e_envelope_v1",
+            &[
+                &self.header.digest(),
+                self.recipient_key_id.as_bytes(),
+                &self.kem_ciphertext,
+                &self.sealed,
+            ],
+        )
+        .to_vec()
+    }
+}
+
+/// Payload delivered end to end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayedMessage {
+    /// Originating client
+    pub sender_id: String,
+    /// Unique message identifier
+    pub message_id: String,
+    /// Relays the message traversed
+    pub route: Vec<String>,
+    /// Decrypted payload
+    pub payload: Vec<u8>,
+}
+
+/// What a client does with a received envelope
+#[derive(Debug, Clone)]
+pub enum RelayStep {
+    /// Pass the envelope on to `next_hop`
+    Forward {
+        /// Client to deliver the envelope to
+        next_hop: String,
+        /// Envelope with the hop recorded
+        envelope: RelayEnvelope,
+    },
+    /// The envelope was for this client
+    Delivered(RelayedMessage),
+}
+
+fn content_key(shared: &SecretBytes, header_digest: &[u8; 32]) -> SecretBytes {
+    SecretBytes::new(
+        SensitiveKind::Key,
+        digest(b"relay_e2e_content_v1", &[shared.expose(), header_digest]).to_vec(),
+    )
+}
+
+fn digest(label: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(label);
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto_protocols::{PQCAlgorithm, QRNG};
+    use crate::crypto_provider::BuiltinKem;
+    use crate::hw_accel::{CryptoDispatch, DispatchPolicy};
+    use crate::key_manager::KeyPurpose;
+
+    const KEM: BuiltinKem = BuiltinKem::new(PQCAlgorithm::Kyber768);
+
+    struct Fixture {
+        qrng: QRNG,
+        keys: KeyManager,
+        aead: CryptoDispatch,
+        bob_kem: SemiStaticKemKey,
+        bob_public: KeyUpdatePublicKey,
+    }
+
+    fn fixture() -> Fixture {
+        let mut qrng = QRNG::from_seed([7u8; 32]);
+        let mut keys = KeyManager::new();
+        keys.generate_key("alice", KeyPurpose::Signing, &mut qrng)
+            .unwrap();
+        keys.generate_key("bob", KeyPurpose::Signing, &mut qrng)
+            .unwrap();
+        let bob_kem = SemiStaticKemKey::generate(&KEM).unwrap();
+        let bob_public = bob_kem.signed_public_key("bob", &keys, "bob").unwrap();
+        Fixture {
+            qrng,
+            keys,
+            aead: CryptoDispatch::new(DispatchPolicy::Auto),
+            bob_kem,
+            bob_public,
+        }
+    }
+
+    fn seal(f: &mut Fixture, route: &[&str]) -> RelayEnvelope {
+        let route = route.iter().map(|relay| relay.to_string()).collect();
+        RelayEnvelope::seal(
+            RoutingHeader::new("alice", "bob", route),
+            &f.bob_public,
+            b"transfer 10",
+            &KEM,
+            &f.aead,
+            &f.keys,
+            "alice",
+            &f.qrng.generate_bytes(NONCE_LEN).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_envelope_crosses_relays_end_to_end() {
+        let mut f = fixture();
+        let mut envelope = seal(&mut f, &["relay_1", "relay_2"]);
+        let alice_key = f.keys.public_key("alice").unwrap().to_vec();
+
+        assert_eq!(envelope.next_hop(), "relay_1");
+        for relay in ["relay_1", "relay_2"] {
+            envelope.verify_origin(&alice_key).unwrap();
+            // Relays see the route but never the payload
+            assert!(!envelope
+                .sealed
+                .windows(b"transfer".len())
+                .any(|w| w == b"transfer"));
+            envelope.advance(relay).unwrap();
+        }
+        assert!(envelope.at_recipient());
+        assert_eq!(envelope.next_hop(), "bob");
+        assert_eq!(
+            envelope.open("bob", &f.bob_kem, &KEM, &f.aead).unwrap(),
+            b"transfer 10"
+        );
+    }
+
+    #[test]
+    fn test_relays_refuse_altered_envelopes() {
+        let mut f = fixture();
+        let alice_key = f.keys.public_key("alice").unwrap().to_vec();
+        let bob_key = f.keys.public_key("bob").unwrap().to_vec();
+        let envelope = seal(&mut f, &["relay_1"]);
+
+        // Rerouting, tampering or a wrong claimed sender fails authentication
+        let mut rerouted = envelope.clone();
+        rerouted.header.route = vec!["eavesdropper".to_string()];
+        let mut tampered = envelope.clone();
+        let last = tampered.sealed.len() - 1;
+        tampered.sealed[last] ^= 0x01;
+        for altered in [&rerouted, &tampered] {
+            assert!(matches!(
+                altered.verify_origin(&alice_key),
+                Err(SecureCommsError::AuthenticationFailed)
+            ));
+        }
+        assert!(envelope.verify_origin(&bob_key).is_err());
+        assert!(matches!(
+            tampered.open("bob", &f.bob_kem, &KEM, &f.aead),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Only the listed relay may forward
+        let mut misrouted = envelope.clone();
+        assert!(misrouted.advance("relay_2").is_err());
+        assert_eq!(misrouted.hop, 0);
+    }
+
+    #[test]
+    fn test_only_recipient_key_opens() {
+        let mut f = fixture();
+        let envelope = seal(&mut f, &[]);
+        assert!(envelope.at_recipient());
+
+        let other_kem = SemiStaticKemKey::generate(&KEM).unwrap();
+        assert!(envelope.open("bob", &other_kem, &KEM, &f.aead).is_err());
+        assert!(envelope.open("relay_1", &f.bob_kem, &KEM, &f.aead).is_err());
+
+        let bob_public = f.bob_public.clone();
+        assert!(RelayEnvelope::seal(
+            RoutingHeader::new("alice", "carol", Vec::new()),
+            &bob_public,
+            b"misaddressed",
+            &KEM,
+            &f.aead,
+            &f.keys,
+            "alice",
+            &[0u8; NONCE_LEN],
+        )
+        .is_err());
+    }
+}
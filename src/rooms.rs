@@ -0,0 +1,838 @@
+//! # Rooms - Named Group Chats over Secure Channels
+//!
+//! A room is a named group with controlled membership, its own symmetric
+//! key and a bounded message history. Like the gossip engine, the
+//! [`RoomManager`] is transport-agnostic: it returns [`RoomAction`]s which
+//! the client delivers over the pairwise secure channels, marked with
+//! [`ROOM_FRAME_HEADER`].
+//!
+//! ## Membership
+//!
+//! The creator owns the room. The owner and admins invite and remove
+//! members; an admin cannot remove the owner or another admin. Frames that
+//! change membership are only accepted from the owner or an admin of the
+//! receiver's current view of the room.
+//!
+//! ## Room Keys
+//!
+//! Every membership change starts a new key epoch: the new key is sent in a
+//! [`RoomFrame::Welcome`] to each remaining member over their own secure
+//! channel, so a new member cannot read earlier messages and a removed
+//! member cannot read later ones. The key of the previous epoch is kept to
+//! open messages that were in flight during the change, but only from
+//! senders who are still members.
+//!
+//! ## Ordering
+//!
+//! Messages from each sender are delivered in the order they were sent. A
+//! message that arrives ahead of a gap is held back until the gap fills, or
+//! until [`MAX_HELD_BACK`] messages from that sender are waiting, at which
+//! point the missing ones are skipped. The first message seen from a sender
+//! sets where its sequence starts.
+//!
+//! ## History
+//!
+//! Each room keeps delivered messages, including the local member's own,
+//! under its [`RetentionPolicy`], which the owner sets when creating the
+//! room and every member applies.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::hw_accel::{CryptoDispatch, KEY_LEN, NONCE_LEN};
+//! use quantum_forge_secure_comms::rooms::{RetentionPolicy, RoomManager, RoomRole};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let aead = CryptoDispatch::default();
+//! let mut alice = RoomManager::new("alice");
+//! let mut bob = RoomManager::new("bob");
+//!
+//! alice.create_room("validators", RetentionPolicy::default(), &[1u8; KEY_LEN])?;
+//! for action in alice.invite("validators", "bob", RoomRole::Member, &[2u8; KEY_LEN])? {
+//!     bob.handle_frame("alice", action.frame, &aead)?;
+//! }
+//!
+//! let (_, actions) = alice.send("validators", b"block #42 finalized", &[0u8; NONCE_LEN], &aead)?;
+//! for action in actions {
+//!     let delivered = bob.handle_frame("alice", action.frame, &aead)?;
+//!     assert_eq!(delivered[0].payload, b"block #42 finalized");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::crypto_provider::Aead;
+use crate::expiry::now_ms;
+use crate::hw_accel::{KEY_LEN, NONCE_LEN};
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Header marking a secure message that carries a [`RoomFrame`]
+pub const ROOM_FRAME_HEADER: &str = "x-room-frame";
+
+/// Messages held back per sender before a gap is skipped
+pub const MAX_HELD_BACK: usize = 64;
+
+/// Role of a room member
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoomRole {
+    /// Creator; may invite and remove anyone
+    Owner,
+    /// May invite members and remove plain members
+    Admin,
+    /// May send and read
+    Member,
+}
+
+impl RoomRole {
+    /// Whether the role may change membership
+    pub fn can_manage(&self) -> bool {
+        matches!(self, RoomRole::Owner | RoomRole::Admin)
+    }
+}
+
+/// How much history a room keeps
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Most recent messages kept
+    pub max_messages: usize,
+    /// Messages older than this are dropped, if set
+    pub max_age_seconds: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_messages: 1000,
+            max_age_seconds: None,
+        }
+    }
+}
+
+/// Shared view of a room
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomInfo {
+    /// Room name, unique per owner
+    pub name: String,
+    /// Member that created the room
+    pub owner: String,
+    /// Members and their roles
+    pub members: BTreeMap<String, RoomRole>,
+    /// Current key epoch
+    pub epoch: u64,
+    /// History retention every member applies
+    pub retention: RetentionPolicy,
+}
+
+impl RoomInfo {
+    /// Role of `member`, if it belongs to the room
+    pub fn role(&self, member: &str) -> Option<RoomRole> {
+        self.members.get(member).copied()
+    }
+}
+
+/// Message sealed under a room key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomMessage {
+    pub room: String,
+    pub sender: String,
+    /// Key epoch the message was sealed under
+    pub epoch: u64,
+    /// Sender-local sequence number
+    pub sequence: u64,
+    pub sealed: Vec<u8>,
+}
+
+impl RoomMessage {
+    /// Header fields bound into the ciphertext
+    fn associated_data(room: &str, sender: &str, epoch: u64, sequence: u64) -> Vec<u8> {
+        let mut aad = b"qf-room-v1".to_vec();
+        for field in [room.as_bytes(), sender.as_bytes()] {
+            aad.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            aad.extend_from_slice(field);
+        }
+        aad.extend_from_slice(&epoch.to_be_bytes());
+        aad.extend_from_slice(&sequence.to_be_bytes());
+        aad
+    }
+}
+
+/// Frame exchanged between room members
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomFrame {
+    /// Membership and key of a new epoch, sent to each member
+    Welcome {
+        info: RoomInfo,
+        /// Room key of `info.epoch`; only ever sent over the member's
+        /// own secure channel
+        key: Vec<u8>,
+    },
+    /// The receiver was removed from the room
+    Removed { room: String, epoch: u64 },
+    /// Message for the room
+    Message(RoomMessage),
+}
+
+impl std::fmt::Debug for RoomFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoomFrame::Welcome { info, .. } => f
+                .debug_struct("Welcome")
+                .field("info", info)
+                .finish_non_exhaustive(),
+            RoomFrame::Removed { room, epoch } => f
+                .debug_struct("Removed")
+                .field("room", room)
+                .field("epoch", epoch)
+                .finish(),
+            RoomFrame::Message(message) => f.debug_tuple("Message").field(message).finish(),
+        }
+    }
+}
+
+/// Frame to be sent to a member over its secure channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomAction {
+    /// Destination member
+    pub peer_id: String,
+    /// Frame to deliver
+    pub frame: RoomFrame,
+}
+
+/// Message delivered to the local member
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomEntry {
+    pub room: String,
+    pub sender: String,
+    /// Sender-local sequence number
+    pub sequence: u64,
+    /// Position in the local room history
+    pub position: u64,
+    /// Unix time of delivery in milliseconds
+    pub delivered_at_ms: u64,
+    pub payload: Vec<u8>,
+}
+
+/// Local state of one room
+struct Room {
+    info: RoomInfo,
+    /// Keys of the current and previous epoch
+    keys: BTreeMap<u64, SecretBytes>,
+    next_sequence: u64,
+    /// Next expected sequence per sender
+    expected: HashMap<String, u64>,
+    held_back: HashMap<String, BTreeMap<u64, Vec<u8>>>,
+    history: VecDeque<RoomEntry>,
+    next_position: u64,
+}
+
+impl Room {
+    fn new(info: RoomInfo, key: &[u8]) -> Self {
+        let mut room = Self {
+            info,
+            keys: BTreeMap::new(),
+            next_sequence: 0,
+            expected: HashMap::new(),
+            held_back: HashMap::new(),
+            history: VecDeque::new(),
+            next_position: 0,
+        };
+        room.install_key(room.info.epoch, key);
+        room
+    }
+
+    fn install_key(&mut self, epoch: u64, key: &[u8]) {
+        self.keys
+            .insert(epoch, SecretBytes::from_slice(SensitiveKind::Key, key));
+        self.keys.retain(|&kept, _| kept + 1 >= epoch);
+    }
+
+    fn current_key(&self) -> Result<&[u8]> {
+        self.keys
+            .get(&self.info.epoch)
+            .map(SecretBytes::expose)
+            .ok_or_else(|| {
+                SecureCommsError::CryptoProtocol(format!("No key for room {}", self.info.name))
+            })
+    }
+
+    fn record(&mut self, sender: &str, sequence: u64, payload: Vec<u8>) -> RoomEntry {
+        let entry = RoomEntry {
+            room: self.info.name.clone(),
+            sender: sender.to_string(),
+            sequence,
+            position: self.next_position,
+            delivered_at_ms: now_ms(),
+            payload,
+        };
+        self.next_position += 1;
+        self.history.push_back(entry.clone());
+        self.apply_retention(entry.delivered_at_ms);
+        entry
+    }
+
+    fn apply_retention(&mut self, now: u64) {
+        let retention = &self.info.retention;
+        while self.history.len() > retention.max_messages {
+            self.history.pop_front();
+        }
+        if let Some(max_age) = retention.max_age_seconds {
+            let cutoff = now.saturating_sub(max_age * 1000);
+            while self
+                .history
+                .front()
+                .map(|entry| entry.delivered_at_ms < cutoff)
+                .unwrap_or(false)
+            {
+                self.history.pop_front();
+            }
+        }
+    }
+
+    /// Deliver `sequence` from `sender` and whatever it unblocks
+    fn deliver_in_order(
+        &mut self,
+        sender: &str,
+        sequence: u64,
+        payload: Vec<u8>,
+    ) -> Vec<RoomEntry> {
+        let expected = *self.expected.entry(sender.to_string()).or_insert(sequence);
+        if sequence < expected {
+            return Vec::new();
+        }
+        let held = self.held_back.entry(sender.to_string()).or_default();
+        held.insert(sequence, payload);
+
+        let mut next = expected;
+        if held.len() > MAX_HELD_BACK {
+            // Give up on the gap
+            next = *held.keys().next().expect("held back messages");
+        }
+        let mut ready = Vec::new();
+        while let Some(payload) = held.remove(&next) {
+            ready.push((next, payload));
+            next += 1;
+        }
+        self.expected.insert(sender.to_string(), next);
+        ready
+            .into_iter()
+            .map(|(sequence, payload)| self.record(sender, sequence, payload))
+            .collect()
+    }
+}
+
+/// Rooms the local node belongs to
+pub struct RoomManager {
+    local_id: String,
+    rooms: HashMap<String, Room>,
+}
+
+impl RoomManager {
+    /// Manager for the member `local_id`
+    pub fn new(local_id: &str) -> Self {
+        Self {
+            local_id: local_id.to_string(),
+            rooms: HashMap::new(),
+        }
+    }
+
+    /// Create a room owned by the local member, keyed with `key`
+    pub fn create_room(
+        &mut self,
+        name: &str,
+        retention: RetentionPolicy,
+        key: &[u8],
+    ) -> Result<()> {
+        check_key(key)?;
+        if name.is_empty() {
+            return Err(SecureCommsError::Validation(
+                "Room name is empty".to_string(),
+            ));
+        }
+        if self.rooms.contains_key(name) {
+            return Err(SecureCommsError::Validation(format!(
+                "Room {} already exists",
+                name
+            )));
+        }
+        let mut members = BTreeMap::new();
+        members.insert(self.local_id.clone(), RoomRole::Owner);
+        let info = RoomInfo {
+            name: name.to_string(),
+            owner: self.local_id.clone(),
+            members,
+            epoch: 0,
+            retention,
+        };
+        self.rooms.insert(name.to_string(), Room::new(info, key));
+        Ok(())
+    }
+
+    /// Add `member` with `role` and move the room to a new epoch keyed
+    /// with `new_key`
+    pub fn invite(
+        &mut self,
+        room: &str,
+        member: &str,
+        role: RoomRole,
+        new_key: &[u8],
+    ) -> Result<Vec<RoomAction>> {
+        check_key(new_key)?;
+        if role == RoomRole::Owner {
+            return Err(SecureCommsError::Validation(
+                "A room has exactly one owner".to_string(),
+            ));
+        }
+        let local_id = self.local_id.clone();
+        let state = self.managed_room(room)?;
+        if state.info.members.contains_key(member) {
+            return Err(SecureCommsError::Validation(format!(
+                "{} is already a member of {}",
+                member, room
+            )));
+        }
+        state.info.members.insert(member.to_string(), role);
+        Ok(rekey(state, &local_id, new_key))
+    }
+
+    /// Remove `member` and move the room to a new epoch keyed with
+    /// `new_key`, which the removed member never receives
+    pub fn remove_member(
+        &mut self,
+        room: &str,
+        member: &str,
+        new_key: &[u8],
+    ) -> Result<Vec<RoomAction>> {
+        check_key(new_key)?;
+        let local_id = self.local_id.clone();
+        let state = self.managed_room(room)?;
+        let local_role = state.info.role(&local_id);
+        match state.info.role(member) {
+            None => {
+                return Err(SecureCommsError::Validation(format!(
+                    "{} is not a member of {}",
+                    member, room
+                )))
+            }
+            Some(RoomRole::Owner) => {
+                return Err(SecureCommsError::Security(
+                    "The room owner cannot be removed".to_string(),
+                ))
+            }
+            Some(RoomRole::Admin) if local_role != Some(RoomRole::Owner) => {
+                return Err(SecureCommsError::Security(
+                    "Only the owner may remove an admin".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        state.info.members.remove(member);
+        state.expected.remove(member);
+        state.held_back.remove(member);
+
+        let mut actions = rekey(state, &local_id, new_key);
+        actions.push(RoomAction {
+            peer_id: member.to_string(),
+            frame: RoomFrame::Removed {
+                room: room.to_string(),
+                epoch: state.info.epoch,
+            },
+        });
+        Ok(actions)
+    }
+
+    /// Seal `payload` for the room and record it in the local history
+    pub fn send(
+        &mut self,
+        room: &str,
+        payload: &[u8],
+        nonce: &[u8],
+        aead: &dyn Aead,
+    ) -> Result<(RoomEntry, Vec<RoomAction>)> {
+        if nonce.len() != NONCE_LEN {
+            return Err(SecureCommsError::Validation(format!(
+                "Room nonce must be {} bytes",
+                NONCE_LEN
+            )));
+        }
+        let local_id = self.local_id.clone();
+        let state = self.room_mut(room)?;
+        let sequence = state.next_sequence;
+        let epoch = state.info.epoch;
+        let aad = RoomMessage::associated_data(room, &local_id, epoch, sequence);
+        let sealed = aead.seal(state.current_key()?, nonce, &aad, payload)?;
+        state.next_sequence += 1;
+        state.expected.insert(local_id.clone(), sequence + 1);
+
+        let message = RoomMessage {
+            room: room.to_string(),
+            sender: local_id.clone(),
+            epoch,
+            sequence,
+            sealed,
+        };
+        let actions = state
+            .info
+            .members
+            .keys()
+            .filter(|member| **member != local_id)
+            .map(|member| RoomAction {
+                peer_id: member.clone(),
+                frame: RoomFrame::Message(message.clone()),
+            })
+            .collect();
+        let entry = state.record(&local_id, sequence, payload.to_vec());
+        Ok((entry, actions))
+    }
+
+    /// Handle a frame received from `from_peer`
+    ///
+    /// Returns the messages delivered, in order; frames that change
+    /// membership deliver none.
+    pub fn handle_frame(
+        &mut self,
+        from_peer: &str,
+        frame: RoomFrame,
+        aead: &dyn Aead,
+    ) -> Result<Vec<RoomEntry>> {
+        match frame {
+            RoomFrame::Welcome { info, key } => {
+                self.handle_welcome(from_peer, info, &key)?;
+                Ok(Vec::new())
+            }
+            RoomFrame::Removed { room, epoch } => {
+                let state = self.room_mut(&room)?;
+                check_manager(&state.info, from_peer)?;
+                if epoch <= state.info.epoch {
+                    return Err(SecureCommsError::Validation(format!(
+                        "Stale removal from room {}",
+                        room
+                    )));
+                }
+                self.rooms.remove(&room);
+                Ok(Vec::new())
+            }
+            RoomFrame::Message(message) => self.handle_message(from_peer, message, aead),
+        }
+    }
+
+    /// Delivered messages of a room still within its retention policy,
+    /// oldest first
+    pub fn history(&mut self, room: &str) -> Result<Vec<RoomEntry>> {
+        let state = self.room_mut(room)?;
+        state.apply_retention(now_ms());
+        Ok(state.history.iter().cloned().collect())
+    }
+
+    /// Shared view of a room the local member belongs to
+    pub fn room(&self, name: &str) -> Option<&RoomInfo> {
+        self.rooms.get(name).map(|room| &room.info)
+    }
+
+    /// Names of the rooms the local member belongs to, sorted
+    pub fn room_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.rooms.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn handle_welcome(&mut self, from_peer: &str, info: RoomInfo, key: &[u8]) -> Result<()> {
+        check_key(key)?;
+        match self.rooms.get_mut(&info.name) {
+            Some(state) => {
+                check_manager(&state.info, from_peer)?;
+                if info.epoch <= state.info.epoch || info.owner != state.info.owner {
+                    return Err(SecureCommsError::Validation(format!(
+                        "Stale or conflicting welcome for room {}",
+                        info.name
+                    )));
+                }
+                if info.role(&self.local_id).is_none() {
+                    self.rooms.remove(&info.name);
+                    return Ok(());
+                }
+                state.install_key(info.epoch, key);
+                state
+                    .expected
+                    .retain(|member, _| info.members.contains_key(member));
+                state.info = info;
+                state.apply_retention(now_ms());
+            }
+            None => {
+                check_manager(&info, from_peer)?;
+                if info.role(&self.local_id).is_none()
+                    || info.role(&info.owner) != Some(RoomRole::Owner)
+                {
+                    return Err(SecureCommsError::Validation(format!(
+                        "Malformed welcome for room {}",
+                        info.name
+                    )));
+                }
+                self.rooms.insert(info.name.clone(), Room::new(info, key));
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_message(
+        &mut self,
+        from_peer: &str,
+        message: RoomMessage,
+        aead: &dyn Aead,
+    ) -> Result<Vec<RoomEntry>> {
+        let state = self.room_mut(&message.room)?;
+        if message.sender != from_peer || state.info.role(&message.sender).is_none() {
+            return Err(SecureCommsError::Security(format!(
+                "{} may not send to room {} as {}",
+                from_peer, message.room, message.sender
+            )));
+        }
+        let key = state.keys.get(&message.epoch).ok_or_else(|| {
+            SecureCommsError::CryptoProtocol(format!(
+                "No key for epoch {} of room {}",
+                message.epoch, message.room
+            ))
+        })?;
+        let aad = RoomMessage::associated_data(
+            &message.room,
+            &message.sender,
+            message.epoch,
+            message.sequence,
+        );
+        let payload = aead.open(key.expose(), &aad, &message.sealed)?;
+        Ok(state.deliver_in_order(&message.sender, message.sequence, payload))
+    }
+
+    fn room_mut(&mut self, room: &str) -> Result<&mut Room> {
+        self.rooms
+            .get_mut(room)
+            .ok_or_else(|| SecureCommsError::Validation(format!("Not a member of room {}", room)))
+    }
+
+    /// Room the local member may manage
+    fn managed_room(&mut self, room: &str) -> Result<&mut Room> {
+        let local_id = self.local_id.clone();
+        let state = self.room_mut(room)?;
+        check_manager(&state.info, &local_id)?;
+        Ok(state)
+    }
+}
+
+impl std::fmt::Debug for RoomManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoomManager")
+            .field("local_id", &self.local_id)
+            .field("rooms", &self.room_names())
+            .finish()
+    }
+}
+
+/// Start a new epoch keyed with `new_key` and welcome every other member
+fn rekey(state: &mut Room, local_id: &str, new_key: &[u8]) -> Vec<RoomAction> {
+    state.info.epoch += 1;
+    state.install_key(state.info.epoch, new_key);
+    state
+        .info
+        .members
+        .keys()
+        .filter(|member| *member != local_id)
+        .map(|member| RoomAction {
+            peer_id: member.clone(),
+            frame: RoomFrame::Welcome {
+                info: state.info.clone(),
+                key: new_key.to_vec(),
+            },
+        })
+        .collect()
+}
+
+fn check_manager(info: &RoomInfo, member: &str) -> Result<()> {
+    if info
+        .role(member)
+        .map(|role| role.can_manage())
+        .unwrap_or(false)
+    {
+        Ok(())
+    } else {
+        Err(SecureCommsError::Security(format!(
+            "{} may not manage room {}",
+            member, info.name
+        )))
+    }
+}
+
+fn check_key(key: &[u8]) -> Result<()> {
+    if key.len() == KEY_LEN {
+        Ok(())
+    } else {
+        Err(SecureCommsError::Validation(format!(
+            "Room key must be {} bytes",
+            KEY_LEN
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hw_accel::CryptoDispatch;
+
+    fn deliver(
+        to: &mut RoomManager,
+        from: &str,
+        actions: &[RoomAction],
+        aead: &dyn Aead,
+    ) -> Vec<RoomEntry> {
+        let local_id = to.local_id.clone();
+        let mut delivered = Vec::new();
+        for action in actions.iter().filter(|action| action.peer_id == local_id) {
+            delivered.extend(to.handle_frame(from, action.frame.clone(), aead).unwrap());
+        }
+        delivered
+    }
+
+    fn room_of_three() -> (RoomManager, RoomManager, RoomManager, CryptoDispatch) {
+        let aead = CryptoDispatch::default();
+        let mut alice = RoomManager::new("alice");
+        let mut bob = RoomManager::new("bob");
+        let mut carol = RoomManager::new("carol");
+        alice
+            .create_room("validators", RetentionPolicy::default(), &[1u8; KEY_LEN])
+            .unwrap();
+        let actions = alice
+            .invite("validators", "bob", RoomRole::Admin, &[2u8; KEY_LEN])
+            .unwrap();
+        deliver(&mut bob, "alice", &actions, &aead);
+        let actions = alice
+            .invite("validators", "carol", RoomRole::Member, &[3u8; KEY_LEN])
+            .unwrap();
+        deliver(&mut bob, "alice", &actions, &aead);
+        deliver(&mut carol, "alice", &actions, &aead);
+        (alice, bob, carol, aead)
+    }
+
+    #[test]
+    fn test_members_exchange_messages_and_keep_history() {
+        let (mut alice, mut bob, mut carol, aead) = room_of_three();
+        assert_eq!(bob.room("validators").unwrap().epoch, 2);
+        assert_eq!(
+            carol.room("validators").unwrap().role("bob"),
+            Some(RoomRole::Admin)
+        );
+
+        let (own, actions) = bob
+            .send("validators", b"block #42", &[7u8; NONCE_LEN], &aead)
+            .unwrap();
+        assert_eq!(own.position, 0);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(
+            deliver(&mut alice, "bob", &actions, &aead)[0].payload,
+            b"block #42"
+        );
+        assert_eq!(deliver(&mut carol, "bob", &actions, &aead)[0].sender, "bob");
+
+        // Only the sender's own channel may carry its messages
+        let RoomFrame::Message(message) = actions[0].frame.clone() else {
+            panic!("expected a room message");
+        };
+        assert!(alice
+            .handle_frame("carol", RoomFrame::Message(message), &aead)
+            .is_err());
+        assert_eq!(alice.history("validators").unwrap().len(), 1);
+
+        // Members cannot manage the room
+        assert!(carol
+            .invite("validators", "mallory", RoomRole::Member, &[4u8; KEY_LEN])
+            .is_err());
+    }
+
+    #[test]
+    fn test_removed_member_loses_access_to_new_epoch() {
+        let (mut alice, mut bob, mut carol, aead) = room_of_three();
+        // An admin cannot remove the owner
+        assert!(bob
+            .remove_member("validators", "alice", &[5u8; KEY_LEN])
+            .is_err());
+
+        let actions = bob
+            .remove_member("validators", "carol", &[5u8; KEY_LEN])
+            .unwrap();
+        deliver(&mut alice, "bob", &actions, &aead);
+        deliver(&mut carol, "bob", &actions, &aead);
+        assert!(carol.room("validators").is_none());
+        assert!(actions.iter().all(|action| match &action.frame {
+            RoomFrame::Welcome { .. } => action.peer_id != "carol",
+            _ => true,
+        }));
+        assert!(!alice
+            .room("validators")
+            .unwrap()
+            .members
+            .contains_key("carol"));
+
+        // A message sealed by the removed member under the old epoch key
+        let aad = RoomMessage::associated_data("validators", "carol", 2, 0);
+        let forged = RoomMessage {
+            room: "validators".to_string(),
+            sender: "carol".to_string(),
+            epoch: 2,
+            sequence: 0,
+            sealed: aead
+                .seal(&[3u8; KEY_LEN], &[0u8; NONCE_LEN], &aad, b"still here")
+                .unwrap(),
+        };
+        assert!(matches!(
+            alice.handle_frame("carol", RoomFrame::Message(forged), &aead),
+            Err(SecureCommsError::Security(_))
+        ));
+    }
+
+    #[test]
+    fn test_out_of_order_messages_are_held_back_and_retention_applies() {
+        let (mut alice, mut bob, _, aead) = room_of_three();
+        let mut frames = Vec::new();
+        for (i, text) in ["one", "two", "three"].iter().enumerate() {
+            let (_, actions) = alice
+                .send("validators", text.as_bytes(), &[i as u8; NONCE_LEN], &aead)
+                .unwrap();
+            frames.push(actions);
+        }
+
+        assert_eq!(deliver(&mut bob, "alice", &frames[0], &aead).len(), 1);
+        assert!(deliver(&mut bob, "alice", &frames[2], &aead).is_empty());
+        let delivered = deliver(&mut bob, "alice", &frames[1], &aead);
+        let texts: Vec<&[u8]> = delivered
+            .iter()
+            .map(|entry| entry.payload.as_slice())
+            .collect();
+        assert_eq!(texts, vec![b"two".as_slice(), b"three".as_slice()]);
+        // Replays are dropped
+        assert!(deliver(&mut bob, "alice", &frames[1], &aead).is_empty());
+
+        let mut small = RoomManager::new("dave");
+        small
+            .create_room(
+                "log",
+                RetentionPolicy {
+                    max_messages: 2,
+                    max_age_seconds: None,
+                },
+                &[9u8; KEY_LEN],
+            )
+            .unwrap();
+        for i in 0..5u8 {
+            small.send("log", &[i], &[i; NONCE_LEN], &aead).unwrap();
+        }
+        let history = small.history("log").unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| entry.position)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! # Secret Memory - Zeroizing Buffers and Memory Hygiene Audits
+//!
+//! `SecretBytes` holds key material and plaintexts and wipes them before the
+//! memory is freed or handed back to a `MemoryPool`. The hygiene audit is an
+//! instrumentation mode that tracks every sensitive buffer and checks, at
+//! the moment it is freed or pooled, that its bytes were zeroized first.
+//!
+//! ## Hygiene Audit
+//!
+//! - **Tracking**: Sensitive buffers register their address on creation
+//! - **Release Check**: Freeing or pooling a tracked buffer that still holds
+//!   non-zero bytes records a `HygieneViolation`
+//! - **Outstanding**: Buffers still tracked when the report is taken
+//!
+//! Audits are scoped to the thread that begins them and cost a thread-local
+//! flag check when inactive. Buffers freed on another thread are reported as
+//! outstanding rather than checked.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::secret_memory::{HygieneAudit, SecretBytes, SensitiveKind};
+//!
+//! let audit = HygieneAudit::begin();
+//! let key = SecretBytes::new(SensitiveKind::Key, vec![7u8; 32]);
+//! assert_eq!(key.len(), 32);
+//! drop(key);
+//!
+//! let report = audit.finish();
+//! assert!(report.violations.is_empty());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// What a sensitive buffer holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SensitiveKind {
+    /// Key material or seeds
+    Key,
+    /// Decrypted message content
+    Plaintext,
+}
+
+/// Sensitive buffer released without being zeroized
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HygieneViolation {
+    /// What the buffer held
+    pub kind: SensitiveKind,
+    /// Buffer length in bytes
+    pub len: usize,
+    /// Bytes still non-zero on release
+    pub nonzero_bytes: usize,
+    /// Release path that found the buffer dirty
+    pub release_path: String,
+}
+
+/// Result of a hygiene audit
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HygieneReport {
+    /// Sensitive buffers tracked during the audit
+    pub tracked: usize,
+    /// Tracked buffers released after zeroization
+    pub released_clean: usize,
+    /// Tracked buffers released with data left in them
+    pub violations: Vec<HygieneViolation>,
+    /// Tracked buffers not yet released
+    pub outstanding: usize,
+}
+
+impl HygieneReport {
+    /// Panic with every violation, for use at the end of tests
+    pub fn assert_clean(&self) {
+        assert!(
+            self.violations.is_empty(),
+            "{} sensitive buffers released without zeroization: {:?}",
+            self.violations.len(),
+            self.violations
+        );
+    }
+}
+
+#[derive(Default)]
+struct AuditState {
+    tracked: HashMap<usize, (SensitiveKind, usize)>,
+    report: HygieneReport,
+}
+
+thread_local! {
+    static AUDIT_ACTIVE: Cell<bool> = const { Cell::new(false) };
+    static AUDIT: RefCell<AuditState> = RefCell::new(AuditState::default());
+}
+
+/// Active hygiene audit on the current thread, ended by `finish` or drop
+#[derive(Debug)]
+pub struct HygieneAudit {
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl HygieneAudit {
+    /// Start tracking sensitive buffers on this thread
+    pub fn begin() -> Self {
+        AUDIT.with(|audit| *audit.borrow_mut() = AuditState::default());
+        AUDIT_ACTIVE.with(|active| active.set(true));
+        Self {
+            _not_send: std::marker::PhantomData,
+        }
+    }
+
+    /// Report so far without ending the audit
+    pub fn report(&self) -> HygieneReport {
+        AUDIT.with(|audit| {
+            let audit = audit.borrow();
+            HygieneReport {
+                outstanding: audit.tracked.len(),
+                ..audit.report.clone()
+            }
+        })
+    }
+
+    /// End the audit and return its report
+    pub fn finish(self) -> HygieneReport {
+        self.report()
+    }
+}
+
+impl Drop for HygieneAudit {
+    fn drop(&mut self) {
+        AUDIT_ACTIVE.with(|active| active.set(false));
+        AUDIT.with(|audit| *audit.borrow_mut() = AuditState::default());
+    }
+}
+
+/// Whether a hygiene audit is running on this thread
+pub fn audit_active() -> bool {
+    AUDIT_ACTIVE.with(|active| active.get())
+}
+
+/// Register a sensitive buffer with the running audit
+pub fn track_sensitive(kind: SensitiveKind, buffer: &[u8]) {
+    if !audit_active() || buffer.is_empty() {
+        return;
+    }
+    AUDIT.with(|audit| {
+        let mut audit = audit.borrow_mut();
+        audit
+            .tracked
+            .insert(buffer.as_ptr() as usize, (kind, buffer.len()));
+        audit.report.tracked += 1;
+    });
+}
+
+/// Check a buffer about to be freed or pooled, returning whether it was clean
+///
+/// Untracked buffers are always clean. A tracked buffer is untracked and,
+/// if any of its bytes are non-zero, recorded as a violation of `release_path`.
+pub fn check_released(buffer: &[u8], release_path: &str) -> bool {
+    if !audit_active() || buffer.is_empty() {
+        return true;
+    }
+    AUDIT.with(|audit| {
+        let mut audit = audit.borrow_mut();
+        let (kind, len) = match audit.tracked.remove(&(buffer.as_ptr() as usize)) {
+            Some(entry) => entry,
+            None => return true,
+        };
+        let nonzero_bytes = buffer[..len.min(buffer.len())]
+            .iter()
+            .filter(|byte| **byte != 0)
+            .count();
+        if nonzero_bytes == 0 {
+            audit.report.released_clean += 1;
+            return true;
+        }
+        audit.report.violations.push(HygieneViolation {
+            kind,
+            len,
+            nonzero_bytes,
+            release_path: release_path.to_string(),
+        });
+        false
+    })
+}
+
+/// Byte buffer holding secrets, zeroized before it is freed
+///
+/// Debug output never shows the contents.
+pub struct SecretBytes {
+    bytes: Vec<u8>,
+    kind: SensitiveKind,
+}
+
+impl SecretBytes {
+    /// Take ownership of sensitive bytes
+    pub fn new(kind: SensitiveKind, bytes: Vec<u8>) -> Self {
+        track_sensitive(kind, &bytes);
+        Self { bytes, kind }
+    }
+
+    /// Copy sensitive bytes into a new buffer
+    pub fn from_slice(kind: SensitiveKind, bytes: &[u8]) -> Self {
+        Self::new(kind, bytes.to_vec())
+    }
+
+    /// What the buffer holds
+    pub fn kind(&self) -> SensitiveKind {
+        self.kind
+    }
+
+    /// Borrow the secret
+    pub fn expose(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Borrow the secret mutably; the length cannot change
+    pub fn expose_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    /// Length in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Zeroize and give up the allocation, e.g. to return it to a pool
+    pub fn into_wiped(mut self) -> Vec<u8> {
+        self.wipe();
+        std::mem::take(&mut self.bytes)
+    }
+
+    fn wipe(&mut self) {
+        // Zeroize in place first so the release check still sees every byte
+        self.bytes.as_mut_slice().zeroize();
+        check_released(&self.bytes, "secret_bytes");
+        // Also clears spare capacity left by earlier, longer contents
+        self.bytes.zeroize();
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl Clone for SecretBytes {
+    fn clone(&self) -> Self {
+        Self::from_slice(self.kind, &self.bytes)
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SecretBytes({:?}, {} bytes)",
+            self.kind,
+            self.bytes.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performance::{MemoryPool, MemoryPoolConfig};
+
+    #[test]
+    fn test_secret_bytes_released_clean() {
+        let audit = HygieneAudit::begin();
+        let key = SecretBytes::from_slice(SensitiveKind::Key, &[0xaa; 32]);
+        let copy = key.clone();
+        assert_eq!(copy.expose(), key.expose());
+        assert_eq!(format!("{:?}", key), "SecretBytes(Key, 32 bytes)");
+        drop(key);
+        let wiped = copy.into_wiped();
+        assert!(wiped.is_empty() && wiped.capacity() >= 32);
+
+        let report = audit.finish();
+        assert_eq!(report.tracked, 2);
+        assert_eq!(report.released_clean, 2);
+        assert_eq!(report.outstanding, 0);
+        report.assert_clean();
+    }
+
+    #[test]
+    fn test_dirty_pool_return_is_a_violation() {
+        let pool = MemoryPool::new(MemoryPoolConfig::default());
+        let audit = HygieneAudit::begin();
+
+        // A plaintext returned through the plain path keeps its contents
+        let mut plaintext = pool.get_buffer(1024);
+        plaintext.fill(0x41);
+        track_sensitive(SensitiveKind::Plaintext, &plaintext);
+        pool.return_buffer(plaintext);
+
+        // The secret path wipes before pooling
+        let mut secret = pool.get_secret_buffer(SensitiveKind::Key, 1024);
+        secret.expose_mut().fill(0x42);
+        pool.return_secret_buffer(secret);
+
+        let report = audit.finish();
+        assert_eq!(report.tracked, 2);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].kind, SensitiveKind::Plaintext);
+        assert_eq!(report.violations[0].nonzero_bytes, 1024);
+        assert_eq!(report.violations[0].release_path, "memory_pool");
+    }
+
+    #[test]
+    fn test_untracked_without_audit() {
+        assert!(!audit_active());
+        let key = SecretBytes::new(SensitiveKind::Key, vec![1u8; 16]);
+        assert!(check_released(&[1u8; 16], "test"));
+        drop(key);
+
+        let audit = HygieneAudit::begin();
+        assert!(audit_active());
+        drop(audit);
+        assert!(!audit_active());
+    }
+}
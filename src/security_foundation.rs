@@ -12,6 +12,7 @@
 //! - **QuantumSimulated**: Physics-based quantum entropy generation with authentic mechanics
 //! - **TimingJitter**: Hardware timing variations for additional entropy
 //! - **Environmental**: Environmental noise collection for maximum entropy
+//! - **Hardware**: Attested HSM or TPM device (see `hsm_entropy`)
 //!
 //! ### Real-Time Threat Detection
 //! - **Timing Analysis Protection**: Statistical analysis of operation timings
@@ -63,7 +64,7 @@
 //!
 //! ### Production Configuration
 //! ```rust,no_run
-//! use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -76,7 +77,7 @@
 //!
 //! ### Maximum Security Setup
 //! ```rust,no_run
-//! # use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! # use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Maximum security for critical applications
@@ -88,7 +89,7 @@
 //!
 //! ### Secure Random Generation
 //! ```rust,no_run
-//! # use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! # use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = SecurityConfig::production_ready();
@@ -104,7 +105,7 @@
 //!
 //! ### Threat Monitoring
 //! ```rust,no_run
-//! # use streamlined_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
+//! # use quantum_forge_secure_comms::security_foundation::{SecurityConfig, SecurityFoundation};
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let config = SecurityConfig::production_ready();
@@ -139,6 +140,9 @@
 //! - **Performance Optimization**: Minimal latency with maximum security
 //! - **Scalability**: Efficient resource usage with linear scaling
 
+use crate::hsm_entropy::{
+    AttestedEntropySource, EntropyProvenance, HardwareEntropyRecord, SourceContribution,
+};
 use crate::performance::PerformanceMetrics;
 use crate::Result;
 use rand::{SeedableRng, RngCore, Rng};
@@ -151,7 +155,9 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 /// Defines three security levels with different entropy mixing rounds and
 /// threat detection sensitivity. Higher levels provide stronger security
 /// at the cost of slightly increased computational overhead.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub enum SecurityLevel {
     /// Standard security for typical applications - 3 entropy rounds, 70% sensitivity
     Standard,
@@ -185,6 +191,25 @@ impl SecurityLevel {
             Self::Maximum => 0.95,
         }
     }
+
+    /// Post-quantum security in bits required of key exchanges at this level
+    ///
+    /// Standard: 128 (ML-KEM-512), High: 192 (ML-KEM-768), Maximum: 256 (ML-KEM-1024)
+    pub fn key_exchange_bits(&self) -> u16 {
+        match self {
+            Self::Standard => 128,
+            Self::High => 192,
+            Self::Maximum => 256,
+        }
+    }
+
+    /// Next stronger level, or this one at Maximum
+    pub fn escalated(&self) -> Self {
+        match self {
+            Self::Standard => Self::High,
+            Self::High | Self::Maximum => Self::Maximum,
+        }
+    }
 }
 
 /// Comprehensive security configuration for the foundation layer
@@ -268,6 +293,8 @@ pub enum EntropySource {
     TimingJitter,
     /// Environmental noise - Ambient electromagnetic and thermal variations
     Environmental,
+    /// Hardware entropy - Attested HSM or TPM random number generator
+    Hardware,
 }
 
 /// Types of security threats that can be detected by the monitoring system
@@ -287,6 +314,8 @@ pub enum ThreatType {
     AdversarialInput,
     /// Replay attack - Retransmission of captured communications
     ReplayAttack,
+    /// Key substitution - Peer presented a different identity key than the pinned one
+    KeySubstitution,
 }
 
 /// Security event detected by the threat monitoring system
@@ -323,6 +352,10 @@ pub struct EntropyService {
     mixing_rounds: usize,
     /// Real-time health scores for each entropy source (0.0-1.0)
     health_scores: HashMap<EntropySource, f64>,
+    /// Attested hardware device backing `EntropySource::Hardware`
+    hardware: Option<AttestedEntropySource>,
+    /// Provenance of the most recent output
+    last_provenance: Option<EntropyProvenance>,
 }
 
 impl EntropyService {
@@ -348,15 +381,65 @@ impl EntropyService {
             sources,
             mixing_rounds,
             health_scores,
+            hardware: None,
+            last_provenance: None,
         }
     }
 
+    /// Mix an attested hardware device into all subsequent output
+    ///
+    /// The DRBG is reseeded with device output straight away, and the device
+    /// is collected first so that it always contributes to the pool.
+    pub fn attach_hardware(&mut self, source: AttestedEntropySource) -> Result<()> {
+        use sha3::{Digest, Sha3_256};
+
+        let mut device_bytes = [0u8; 32];
+        source.device.fill(&mut device_bytes)?;
+        let mut current = [0u8; 32];
+        self.rng.fill_bytes(&mut current);
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"hardware_reseed_v1");
+        hasher.update(current);
+        hasher.update(device_bytes);
+        self.rng = ChaCha20Rng::from_seed(hasher.finalize().into());
+
+        self.sources.retain(|existing| *existing != EntropySource::Hardware);
+        self.sources.insert(0, EntropySource::Hardware);
+        self.health_scores.insert(EntropySource::Hardware, 1.0);
+        self.hardware = Some(source);
+        Ok(())
+    }
+
+    /// Attested hardware device, if one is attached
+    pub fn hardware(&self) -> Option<&AttestedEntropySource> {
+        self.hardware.as_ref()
+    }
+
+    /// Provenance of the most recent output
+    pub fn last_provenance(&self) -> Option<&EntropyProvenance> {
+        self.last_provenance.as_ref()
+    }
+
+    /// Change the number of SHA-3 mixing rounds for subsequent output
+    pub fn set_mixing_rounds(&mut self, mixing_rounds: usize) {
+        self.mixing_rounds = mixing_rounds;
+    }
+
     /// Generate secure random bytes with optimized performance
     pub fn generate_bytes(&mut self, count: usize) -> Result<Vec<u8>> {
-        // Fast-path for small requests (common case optimization)
-        if count <= 32 {
+        // Fast-path for small requests (common case optimization); an
+        // attached hardware device must contribute, so it takes the full path
+        if count <= 32 && self.hardware.is_none() {
             let mut bytes = vec![0u8; count];
             self.rng.fill_bytes(&mut bytes);
+            self.last_provenance = Some(EntropyProvenance {
+                generated_at: self.get_timestamp() / 1_000_000_000,
+                output_bytes: count,
+                mixing_rounds: 0,
+                drbg_weight: 1.0,
+                sources: Vec::new(),
+                hardware: None,
+            });
             return Ok(bytes);
         }
 
@@ -366,11 +449,13 @@ impl EntropyService {
 
         // Collect entropy from configured sources with optimized collection
         let mut entropy_pool = Vec::with_capacity(count + 128);
+        let mut collected = Vec::with_capacity(self.sources.len());
         
         // Fast collection for active sources only
         for source in &self.sources.clone() {
             let source_entropy = self.collect_source_entropy(*source);
             entropy_pool.extend_from_slice(&source_entropy);
+            collected.push((*source, source_entropy.len()));
             
             // Early termination if we have enough entropy
             if entropy_pool.len() >= count + 64 {
@@ -383,6 +468,7 @@ impl EntropyService {
 
         // Optimized mixing with fewer rounds for better performance
         let mixed_entropy = self.mix_entropy_optimized(&entropy_pool, count);
+        self.record_provenance(count, &collected, base_bytes.len(), entropy_pool.len());
 
         // Update health scores periodically, not on every generation
         if rand::random::<f64>() < 0.1 { // 10% sampling rate
@@ -392,6 +478,33 @@ impl EntropyService {
         Ok(mixed_entropy)
     }
 
+    /// Record which sources fed an output and in what proportion
+    fn record_provenance(
+        &mut self,
+        count: usize,
+        collected: &[(EntropySource, usize)],
+        drbg_bytes: usize,
+        pool_bytes: usize,
+    ) {
+        let pool_bytes = pool_bytes.max(1) as f64;
+        let sources = collected
+            .iter()
+            .map(|(source, bytes)| SourceContribution {
+                source: *source,
+                weight: *bytes as f64 / pool_bytes,
+                health: self.health_scores.get(source).copied().unwrap_or(0.0),
+            })
+            .collect();
+        self.last_provenance = Some(EntropyProvenance {
+            generated_at: self.get_timestamp() / 1_000_000_000,
+            output_bytes: count,
+            mixing_rounds: self.mixing_rounds,
+            drbg_weight: drbg_bytes as f64 / pool_bytes,
+            sources,
+            hardware: self.hardware.as_ref().map(AttestedEntropySource::record),
+        });
+    }
+
     /// Enhanced entropy mixing with statistical validation
     fn mix_entropy_optimized(&mut self, data: &[u8], output_size: usize) -> Vec<u8> {
         use sha3::{Digest, Sha3_256};
@@ -498,6 +611,21 @@ impl EntropyService {
                 
                 env_bytes
             }
+            EntropySource::Hardware => {
+                // A failing device contributes nothing and drops to zero health
+                let hardware = match &self.hardware {
+                    Some(hardware) => hardware,
+                    None => return Vec::new(),
+                };
+                let mut device_bytes = vec![0u8; 32];
+                match hardware.device.fill(&mut device_bytes) {
+                    Ok(()) => device_bytes,
+                    Err(_) => {
+                        self.health_scores.insert(EntropySource::Hardware, 0.0);
+                        Vec::new()
+                    }
+                }
+            }
         }
     }
 
@@ -590,6 +718,11 @@ impl ThreatDetector {
         }
     }
 
+    /// Change the detection sensitivity applied to later analysis
+    pub fn set_sensitivity(&mut self, sensitivity: f64) {
+        self.sensitivity = sensitivity;
+    }
+
     /// Record a timing measurement
     pub fn record_timing(&mut self, operation: &str, duration_ns: u64) {
         self.timing_measurements.push(duration_ns);
@@ -754,6 +887,11 @@ impl SecurityFoundation {
         }
     }
 
+    /// Record a security event raised outside the built-in detectors
+    pub fn report_security_event(&mut self, event: SecurityEvent) {
+        self.detector.record_event(event);
+    }
+
     /// Get current threat level
     pub fn get_threat_level(&self) -> f64 {
         self.detector.get_threat_level()
@@ -769,6 +907,23 @@ impl SecurityFoundation {
         self.entropy.health_scores.clone()
     }
 
+    /// Mix an attested hardware entropy device into all subsequent output
+    ///
+    /// The attestation must already have been verified by the caller.
+    pub fn attach_hardware_entropy(&mut self, source: AttestedEntropySource) -> Result<()> {
+        self.entropy.attach_hardware(source)
+    }
+
+    /// Audit summary of the attached hardware entropy device
+    pub fn hardware_entropy(&self) -> Option<HardwareEntropyRecord> {
+        self.entropy.hardware().map(AttestedEntropySource::record)
+    }
+
+    /// Provenance of the most recently generated secure bytes
+    pub fn entropy_provenance(&self) -> Option<&EntropyProvenance> {
+        self.entropy.last_provenance()
+    }
+
     /// Get performance metrics
     pub fn get_metrics(&self) -> &PerformanceMetrics {
         &self.metrics
@@ -779,6 +934,18 @@ impl SecurityFoundation {
         &self.config
     }
 
+    /// Switch to another security level at runtime
+    ///
+    /// Entropy mixing rounds and detection sensitivity follow the new level
+    /// from the next operation on. Returns the previous level.
+    pub fn set_level(&mut self, level: SecurityLevel) -> SecurityLevel {
+        let previous = self.config.level;
+        self.config.level = level;
+        self.entropy.set_mixing_rounds(level.entropy_rounds());
+        self.detector.set_sensitivity(level.detection_sensitivity());
+        previous
+    }
+
     /// Perform security self-test
     pub async fn self_test(&mut self) -> Result<bool> {
         // Test entropy generation
@@ -801,6 +968,7 @@ impl SecurityFoundation {
                 EntropySource::QuantumSimulated => 0.3, // Simulated source, lower threshold
                 EntropySource::TimingJitter => 0.4,     // Variable quality from timing
                 EntropySource::Environmental => 0.3,    // Simulated environmental data
+                EntropySource::Hardware => 0.7,         // Attested device, high quality expected
             };
 
             if health < min_threshold {
@@ -895,5 +1063,21 @@ mod tests {
             SecurityLevel::Maximum.detection_sensitivity()
                 > SecurityLevel::Standard.detection_sensitivity()
         );
+        assert!(SecurityLevel::Standard < SecurityLevel::Maximum);
+        assert_eq!(SecurityLevel::Standard.escalated(), SecurityLevel::High);
+        assert_eq!(SecurityLevel::Maximum.escalated(), SecurityLevel::Maximum);
+        assert_eq!(SecurityLevel::High.key_exchange_bits(), 192);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_level_change() {
+        let mut foundation = SecurityFoundation::new(SecurityConfig::production_ready())
+            .await
+            .unwrap();
+        assert_eq!(foundation.set_level(SecurityLevel::Maximum), SecurityLevel::High);
+        assert_eq!(foundation.get_config().level, SecurityLevel::Maximum);
+        assert_eq!(foundation.entropy.mixing_rounds, 7);
+        assert_eq!(foundation.detector.sensitivity, 0.95);
+        assert_eq!(foundation.generate_secure_bytes(64).unwrap().len(), 64);
     }
 }
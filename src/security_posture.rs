@@ -0,0 +1,335 @@
+//! # Security Posture - Runtime Security Level Transitions
+//!
+//! Tracks the effective security level of a client after startup. The level
+//! is raised on operator command or, when enabled, in response to confident
+//! threat detections; every transition is kept in a bounded history and
+//! written to the audit log.
+//!
+//! ## Transitions
+//!
+//! - **Operator**: Any level, up or down, through `set_security_level`
+//! - **Threat**: One step up per health check that saw a qualifying threat,
+//!   never past the configured ceiling and never down
+//! - **Upgrade**: Existing channels are rekeyed with the stronger KEM
+//! - **Downgrade**: Applies to new key exchanges only; existing keys stay
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::security_foundation::SecurityLevel;
+//! use quantum_forge_secure_comms::security_posture::ThreatEscalationConfig;
+//! use quantum_forge_secure_comms::{StreamlinedConfig, StreamlinedSecureClient};
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = StreamlinedConfig {
+//!     threat_escalation: Some(ThreatEscalationConfig::default()),
+//!     ..Default::default()
+//! };
+//! let mut client = StreamlinedSecureClient::with_config(config).await?;
+//! client.establish_secure_channel("peer_1").await?;
+//!
+//! if let Some(transition) = client
+//!     .set_security_level(SecurityLevel::Maximum, "incident response")
+//!     .await
+//! {
+//!     println!("Rekeyed {:?}", transition.channels_rekeyed);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::expiry::now_ms;
+use crate::security_foundation::{SecurityEvent, SecurityLevel, ThreatType};
+
+/// Transitions kept in the posture history
+pub const MAX_TRANSITION_HISTORY: usize = 256;
+
+/// Automatic escalation on threat detections
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreatEscalationConfig {
+    /// Detection confidence at or above which a threat escalates
+    pub min_confidence: f64,
+    /// Threat types that escalate; empty means every type
+    pub threat_types: Vec<ThreatType>,
+    /// Highest level reached through escalation
+    pub ceiling: SecurityLevel,
+}
+
+impl Default for ThreatEscalationConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.9,
+            threat_types: Vec::new(),
+            ceiling: SecurityLevel::Maximum,
+        }
+    }
+}
+
+impl ThreatEscalationConfig {
+    /// Whether a security event warrants escalation
+    pub fn matches(&self, event: &SecurityEvent) -> bool {
+        event.confidence >= self.min_confidence
+            && (self.threat_types.is_empty() || self.threat_types.contains(&event.threat_type))
+    }
+}
+
+/// Cause of a security level transition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TransitionTrigger {
+    /// Requested by an operator
+    Operator {
+        /// Reason given with the command
+        reason: String,
+    },
+    /// Escalated after a threat detection
+    Threat {
+        /// Threat classification
+        threat_type: ThreatType,
+        /// Detection confidence (0.0 - 1.0)
+        confidence: f64,
+        /// Component that raised the event
+        component: String,
+    },
+}
+
+impl TransitionTrigger {
+    /// One-line description for logs and events
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Operator { reason } => format!("operator: {}", reason),
+            Self::Threat {
+                threat_type,
+                confidence,
+                component,
+            } => format!(
+                "threat: {:?} in {} ({:.2})",
+                threat_type, component, confidence
+            ),
+        }
+    }
+}
+
+/// One recorded change of the effective security level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityTransition {
+    /// Level before the change
+    pub from: SecurityLevel,
+    /// Level after the change
+    pub to: SecurityLevel,
+    /// What caused the change
+    pub trigger: TransitionTrigger,
+    /// Unix time of the change in milliseconds
+    pub timestamp_ms: u64,
+    /// Peers whose channels were rekeyed with the new parameters
+    pub channels_rekeyed: Vec<String>,
+    /// Peers whose rekey failed and still use the previous key
+    pub channels_failed: Vec<String>,
+}
+
+impl SecurityTransition {
+    /// Create a transition stamped with the current time
+    pub fn new(from: SecurityLevel, to: SecurityLevel, trigger: TransitionTrigger) -> Self {
+        Self {
+            from,
+            to,
+            trigger,
+            timestamp_ms: now_ms(),
+            channels_rekeyed: Vec::new(),
+            channels_failed: Vec::new(),
+        }
+    }
+
+    /// Whether the change strengthened the security level
+    pub fn is_upgrade(&self) -> bool {
+        self.to > self.from
+    }
+}
+
+/// Escalation policy state and transition history of one client
+#[derive(Debug, Default)]
+pub struct SecurityPosture {
+    escalation: Option<ThreatEscalationConfig>,
+    pending: Option<TransitionTrigger>,
+    history: VecDeque<SecurityTransition>,
+}
+
+impl SecurityPosture {
+    /// Create a posture, escalating on threats only when configured
+    pub fn new(escalation: Option<ThreatEscalationConfig>) -> Self {
+        Self {
+            escalation,
+            pending: None,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Escalation settings, if threat-driven escalation is enabled
+    pub fn escalation(&self) -> Option<&ThreatEscalationConfig> {
+        self.escalation.as_ref()
+    }
+
+    /// Note a threat detection, returning whether it queued an escalation
+    ///
+    /// Several threats before the next `take_escalation` escalate once; the
+    /// most confident one is kept as the trigger.
+    pub fn observe_threat(&mut self, event: &SecurityEvent, current: SecurityLevel) -> bool {
+        let config = match &self.escalation {
+            Some(config) => config,
+            None => return false,
+        };
+        if current >= config.ceiling || !config.matches(event) {
+            return false;
+        }
+        let stronger = match &self.pending {
+            Some(TransitionTrigger::Threat { confidence, .. }) => event.confidence > *confidence,
+            _ => true,
+        };
+        if stronger {
+            self.pending = Some(TransitionTrigger::Threat {
+                threat_type: event.threat_type,
+                confidence: event.confidence,
+                component: event.component.clone(),
+            });
+        }
+        true
+    }
+
+    /// Take the queued escalation as a target level and trigger
+    pub fn take_escalation(
+        &mut self,
+        current: SecurityLevel,
+    ) -> Option<(SecurityLevel, TransitionTrigger)> {
+        let trigger = self.pending.take()?;
+        let ceiling = self.escalation.as_ref()?.ceiling;
+        let target = current.escalated().min(ceiling);
+        (target > current).then_some((target, trigger))
+    }
+
+    /// Append a transition to the history and the audit log
+    pub fn record(&mut self, transition: SecurityTransition) {
+        crate::logging::log_audit(
+            "Security level changed",
+            serde_json::json!({
+                "from": transition.from,
+                "to": transition.to,
+                "trigger": transition.trigger,
+                "timestamp_ms": transition.timestamp_ms,
+                "channels_rekeyed": transition.channels_rekeyed,
+                "channels_failed": transition.channels_failed,
+            }),
+        );
+        if self.history.len() == MAX_TRANSITION_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(transition);
+    }
+
+    /// Recorded transitions, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &SecurityTransition> {
+        self.history.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn threat(threat_type: ThreatType, confidence: f64) -> SecurityEvent {
+        SecurityEvent {
+            timestamp: 1,
+            threat_type,
+            confidence,
+            component: "test".to_string(),
+            details: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_threats_escalate_one_step_up_to_ceiling() {
+        let mut posture = SecurityPosture::new(Some(ThreatEscalationConfig::default()));
+        assert!(!posture.observe_threat(
+            &threat(ThreatType::TimingAnalysis, 0.5),
+            SecurityLevel::Standard
+        ));
+        assert!(posture.observe_threat(
+            &threat(ThreatType::TimingAnalysis, 0.92),
+            SecurityLevel::Standard
+        ));
+        assert!(posture.observe_threat(
+            &threat(ThreatType::ReplayAttack, 0.97),
+            SecurityLevel::Standard
+        ));
+
+        let (target, trigger) = posture.take_escalation(SecurityLevel::Standard).unwrap();
+        assert_eq!(target, SecurityLevel::High);
+        assert!(matches!(
+            trigger,
+            TransitionTrigger::Threat {
+                threat_type: ThreatType::ReplayAttack,
+                ..
+            }
+        ));
+        assert!(posture.take_escalation(SecurityLevel::High).is_none());
+        assert!(!posture.observe_threat(
+            &threat(ThreatType::ReplayAttack, 0.99),
+            SecurityLevel::Maximum
+        ));
+    }
+
+    #[test]
+    fn test_escalation_respects_config() {
+        let mut disabled = SecurityPosture::default();
+        assert!(!disabled.observe_threat(
+            &threat(ThreatType::SideChannel, 1.0),
+            SecurityLevel::Standard
+        ));
+
+        let mut posture = SecurityPosture::new(Some(ThreatEscalationConfig {
+            threat_types: vec![ThreatType::KeySubstitution],
+            ceiling: SecurityLevel::High,
+            ..Default::default()
+        }));
+        assert!(!posture.observe_threat(
+            &threat(ThreatType::SideChannel, 1.0),
+            SecurityLevel::Standard
+        ));
+        assert!(!posture.observe_threat(
+            &threat(ThreatType::KeySubstitution, 1.0),
+            SecurityLevel::High
+        ));
+        assert!(posture.observe_threat(
+            &threat(ThreatType::KeySubstitution, 1.0),
+            SecurityLevel::Standard
+        ));
+        assert_eq!(
+            posture
+                .take_escalation(SecurityLevel::Standard)
+                .map(|(level, _)| level),
+            Some(SecurityLevel::High)
+        );
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_audited() {
+        let mut posture = SecurityPosture::default();
+        for i in 0..MAX_TRANSITION_HISTORY + 2 {
+            posture.record(SecurityTransition::new(
+                SecurityLevel::High,
+                SecurityLevel::Maximum,
+                TransitionTrigger::Operator {
+                    reason: format!("drill {}", i),
+                },
+            ));
+        }
+        assert_eq!(posture.history().count(), MAX_TRANSITION_HISTORY);
+        assert!(posture.history().next().unwrap().is_upgrade());
+        assert!(crate::logging::LOGGER
+            .get_audit_trail()
+            .iter()
+            .any(|entry| entry.message == "Security level changed"));
+    }
+}
@@ -0,0 +1,430 @@
+//! # Session Transcript - Exportable Record of Channel Key Agreement
+//!
+//! Records, per channel, every event that set or changed its session key
+//! together with the negotiated algorithms and parameters, so an auditor can
+//! later check which cryptography a channel actually used. Transcripts hold
+//! no secrets: session keys appear only as key confirmation hashes, and
+//! public keys and handshake messages only as hashes or fingerprints.
+//!
+//! ## Structure
+//!
+//! - **Entries**: One per full key exchange, rekey, private handshake or
+//!   key update, numbered from 0
+//! - **Hash chain**: Each entry commits to the digest of the one before, so
+//!   entries cannot be dropped or reordered without detection
+//! - **Key confirmation**: SHA3-256 over both client IDs and the session
+//!   key; both peers of a channel record the same value, so their
+//!   transcripts can be cross-checked
+//!
+//! ## Export and Verification
+//!
+//! `SignedTranscript::sign` signs a transcript's head digest with the
+//! client's identity key. `SignedTranscript::verify` re-checks the chain and
+//! signature, optionally against a pinned identity key, and returns the
+//! transcript for inspection of its `NegotiatedParameters`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! client.establish_secure_channel("bob").await?;
+//!
+//! let export = client.export_session_transcript("bob")?;
+//! let json = serde_json::to_string(&export)?;
+//!
+//! // Later, at the auditor
+//! let export: quantum_forge_secure_comms::session_transcript::SignedTranscript =
+//!     serde_json::from_str(&json)?;
+//! let transcript = export.verify(None)?;
+//! println!("KEM: {:?}", transcript.parameters().map(|p| p.kem));
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::consensus_verify::verify_commit_signature;
+use crate::constant_time::ct_eq;
+use crate::crypto_protocols::{PQCAlgorithm, QKDProtocol};
+use crate::hw_accel::AeadSuite;
+use crate::key_manager::KeyManager;
+use crate::suite_negotiation::NegotiatedSuite;
+use crate::{Result, SecureCommsError};
+
+/// Algorithms and parameters agreed by a key exchange
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NegotiatedParameters {
+    /// Key encapsulation mechanism
+    pub kem: PQCAlgorithm,
+    /// Quantum key distribution protocol
+    pub qkd_protocol: QKDProtocol,
+    /// Authenticated encryption suite for sealed payloads
+    pub aead: AeadSuite,
+    /// Channel security level in bits
+    pub security_level: u16,
+    /// Measured QKD fidelity
+    pub qkd_fidelity: f64,
+    /// Measured QKD error rate
+    pub qkd_error_rate: f64,
+}
+
+/// Event that set or changed a channel's session key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TranscriptEvent {
+    /// Full key exchange establishing the channel
+    Established {
+        /// Negotiated algorithms and parameters
+        parameters: NegotiatedParameters,
+        /// Fingerprint of the KEM public key used
+        kem_public_key: String,
+    },
+    /// Full key exchange replacing the session key
+    Rekeyed {
+        /// Negotiated algorithms and parameters
+        parameters: NegotiatedParameters,
+        /// Fingerprint of the KEM public key used
+        kem_public_key: String,
+    },
+    /// Identity-hiding handshake replacing the session key
+    PrivateHandshake {
+        /// Hash of the random handshake ID
+        handshake_hash: Vec<u8>,
+        /// Fingerprint of the peer identity key the handshake authenticated
+        peer_identity: String,
+    },
+    /// Re-encapsulation key update
+    KeyUpdated {
+        /// Epoch the update moved to
+        epoch: u64,
+        /// Whether this client sent the update
+        initiated: bool,
+        /// Hash of the serialized `KeyUpdate` message
+        message_hash: Vec<u8>,
+    },
+    /// Suite negotiation bound into the session key
+    SuiteNegotiated {
+        /// Agreed suite
+        suite: NegotiatedSuite,
+        /// Whether this client sent the offer
+        initiated: bool,
+        /// Negotiation transcript hash
+        transcript_hash: Vec<u8>,
+    },
+}
+
+/// One event with the key confirmation of the resulting session key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    /// Position in the transcript, starting at 0
+    pub sequence: u64,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: u64,
+    /// What happened
+    pub event: TranscriptEvent,
+    /// Confirmation hash of the session key in force after the event
+    pub key_confirmation: Vec<u8>,
+    /// Digest of the previous entry (empty for the first)
+    pub prev_digest: Vec<u8>,
+}
+
+impl TranscriptEntry {
+    /// Digest the next entry commits to
+    pub fn digest(&self) -> Vec<u8> {
+        self.digest_after(&self.prev_digest)
+    }
+
+    /// Digest of the entry's contents chained onto `prev_digest`
+    fn digest_after(&self, prev_digest: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"session_transcript_entry_v1");
+        hasher.update(self.sequence.to_be_bytes());
+        hasher.update(self.timestamp_ms.to_be_bytes());
+        let event = serde_json::to_vec(&self.event).unwrap_or_default();
+        for field in [&event, &self.key_confirmation, prev_digest] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        }
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Hash-chained key agreement history of one channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTranscript {
+    /// Client that recorded the transcript
+    pub local_id: String,
+    /// Peer at the other end of the channel
+    pub peer_id: String,
+    /// Entries in the order they happened
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl SessionTranscript {
+    /// Create empty transcript for a channel
+    pub fn new(local_id: &str, peer_id: &str) -> Self {
+        Self {
+            local_id: local_id.to_string(),
+            peer_id: peer_id.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Append an event with the session key now in force
+    pub fn record(&mut self, event: TranscriptEvent, session_key: &[u8]) -> &TranscriptEntry {
+        let entry = TranscriptEntry {
+            sequence: self.entries.len() as u64,
+            timestamp_ms: chrono::Utc::now().timestamp_millis() as u64,
+            event,
+            key_confirmation: key_confirmation(&self.local_id, &self.peer_id, session_key),
+            prev_digest: self.entries.last().map(|e| e.digest()).unwrap_or_default(),
+        };
+        self.entries.push(entry);
+        self.entries.last().unwrap()
+    }
+
+    /// Digest committing to the contents of every entry
+    ///
+    /// Recomputed from the entries rather than taken from the stored
+    /// `prev_digest` links, so editing any entry changes it.
+    pub fn head_digest(&self) -> Vec<u8> {
+        let chain_head = self.entries.iter().fold(Vec::new(), |prev_digest, entry| {
+            entry.digest_after(&prev_digest)
+        });
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"session_transcript_v1");
+        for field in [self.local_id.as_bytes(), self.peer_id.as_bytes()] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        }
+        hasher.update(chain_head);
+        hasher.finalize().to_vec()
+    }
+
+    /// Check sequence numbers and the hash chain
+    pub fn verify_chain(&self) -> Result<()> {
+        let mut prev_digest = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.sequence != index as u64 || entry.prev_digest != prev_digest {
+                return Err(SecureCommsError::Validation(format!(
+                    "Session transcript chain broken at entry {}",
+                    index
+                )));
+            }
+            prev_digest = entry.digest();
+        }
+        Ok(())
+    }
+
+    /// Parameters of the latest full key exchange
+    pub fn parameters(&self) -> Option<&NegotiatedParameters> {
+        self.entries
+            .iter()
+            .rev()
+            .find_map(|entry| match &entry.event {
+                TranscriptEvent::Established { parameters, .. }
+                | TranscriptEvent::Rekeyed { parameters, .. } => Some(parameters),
+                _ => None,
+            })
+    }
+
+    /// Key confirmation of the session key currently in force
+    pub fn key_confirmation(&self) -> Option<&[u8]> {
+        self.entries
+            .last()
+            .map(|entry| entry.key_confirmation.as_slice())
+    }
+}
+
+/// Key confirmation hash shared by both ends of a channel
+///
+/// Independent of which side computes it, so matching values in both
+/// peers' transcripts show they agreed on the session key.
+pub fn key_confirmation(local_id: &str, peer_id: &str, session_key: &[u8]) -> Vec<u8> {
+    let (first, second) = if local_id <= peer_id {
+        (local_id, peer_id)
+    } else {
+        (peer_id, local_id)
+    };
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"session_key_confirmation_v1");
+    for field in [first.as_bytes(), second.as_bytes(), session_key] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field);
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Transcript signed by the recording client's identity key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedTranscript {
+    /// Exported transcript
+    pub transcript: SessionTranscript,
+    /// Unix timestamp of the export in milliseconds
+    pub exported_at_ms: u64,
+    /// Ed25519 identity key of the recording client
+    pub signer_key: Vec<u8>,
+    /// Signature over `signing_payload`
+    pub signature: Vec<u8>,
+}
+
+impl SignedTranscript {
+    /// Sign a transcript with a key held by `keys`
+    pub fn sign(transcript: SessionTranscript, keys: &KeyManager, key_id: &str) -> Result<Self> {
+        let signer_key = keys
+            .public_key(key_id)
+            .ok_or_else(|| SecureCommsError::Configuration(format!("Unknown key {}", key_id)))?
+            .to_vec();
+        let mut export = Self {
+            transcript,
+            exported_at_ms: chrono::Utc::now().timestamp_millis() as u64,
+            signer_key,
+            signature: Vec::new(),
+        };
+        export.signature = keys.sign(key_id, &export.signing_payload())?;
+        Ok(export)
+    }
+
+    /// Bytes covered by the signature
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = b"session_transcript_export_v1".to_vec();
+        payload.extend_from_slice(&self.exported_at_ms.to_be_bytes());
+        payload.extend_from_slice(&self.transcript.head_digest());
+        payload
+    }
+
+    /// Check the chain and signature, returning the transcript
+    ///
+    /// With `trusted_key` set, the transcript must be signed by that
+    /// identity key. Fails with `SecureCommsError::AuthenticationFailed` for
+    /// a bad signature or untrusted signer and `SecureCommsError::Validation`
+    /// for a broken chain. Edited entries no longer match the signed chain
+    /// head, so they are reported as `AuthenticationFailed` even though they
+    /// also break the chain.
+    pub fn verify(&self, trusted_key: Option<&[u8]>) -> Result<&SessionTranscript> {
+        let chain = self.transcript.verify_chain();
+        if let Some(trusted_key) = trusted_key {
+            if !ct_eq(trusted_key, &self.signer_key) {
+                return Err(SecureCommsError::AuthenticationFailed);
+            }
+        }
+        if !verify_commit_signature(&self.signer_key, &self.signing_payload(), &self.signature) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        chain?;
+        Ok(&self.transcript)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_manager::KeyPurpose;
+
+    fn parameters() -> NegotiatedParameters {
+        NegotiatedParameters {
+            kem: PQCAlgorithm::Kyber768,
+            qkd_protocol: QKDProtocol::BB84,
+            aead: AeadSuite::Aes256Gcm,
+            security_level: 192,
+            qkd_fidelity: 0.98,
+            qkd_error_rate: 0.01,
+        }
+    }
+
+    fn transcript() -> SessionTranscript {
+        let mut transcript = SessionTranscript::new("alice", "bob");
+        transcript.record(
+            TranscriptEvent::Established {
+                parameters: parameters(),
+                kem_public_key: "aa:bb".to_string(),
+            },
+            &[1u8; 32],
+        );
+        transcript.record(
+            TranscriptEvent::KeyUpdated {
+                epoch: 1,
+                initiated: true,
+                message_hash: vec![3u8; 32],
+            },
+            &[2u8; 32],
+        );
+        transcript
+    }
+
+    #[test]
+    fn test_key_confirmation_matches_across_peers() {
+        assert_eq!(
+            key_confirmation("alice", "bob", &[1u8; 32]),
+            key_confirmation("bob", "alice", &[1u8; 32])
+        );
+        assert_ne!(
+            key_confirmation("alice", "bob", &[1u8; 32]),
+            key_confirmation("alice", "bob", &[2u8; 32])
+        );
+
+        let transcript = transcript();
+        assert_eq!(transcript.parameters(), Some(&parameters()));
+        assert_eq!(
+            transcript.key_confirmation(),
+            Some(key_confirmation("bob", "alice", &[2u8; 32]).as_slice())
+        );
+    }
+
+    #[test]
+    fn test_signed_export_verifies() {
+        let mut keys = KeyManager::new();
+        keys.import_key("identity", KeyPurpose::Signing, [4u8; 32])
+            .unwrap();
+        let export = SignedTranscript::sign(transcript(), &keys, "identity").unwrap();
+        let json = serde_json::to_string(&export).unwrap();
+        let export: SignedTranscript = serde_json::from_str(&json).unwrap();
+
+        let pinned = keys.public_key("identity").unwrap();
+        assert_eq!(export.verify(Some(pinned)).unwrap().entries.len(), 2);
+        assert!(matches!(
+            export.verify(Some(&[9u8; 32])),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let mut keys = KeyManager::new();
+        keys.import_key("identity", KeyPurpose::Signing, [4u8; 32])
+            .unwrap();
+        let export = SignedTranscript::sign(transcript(), &keys, "identity").unwrap();
+
+        // Claiming a stronger KEM breaks the signature
+        let mut upgraded = export.clone();
+        if let TranscriptEvent::Established { parameters, .. } =
+            &mut upgraded.transcript.entries[0].event
+        {
+            parameters.kem = PQCAlgorithm::Kyber1024;
+        }
+        assert!(matches!(
+            upgraded.verify(None),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Editing the latest entry, whose digest no link covers, is caught too
+        let mut rekeyed = export.clone();
+        rekeyed.transcript.entries[1].key_confirmation = vec![0u8; 32];
+        assert!(matches!(
+            rekeyed.verify(None),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Dropping an entry breaks the chain even when re-signed
+        let mut truncated = export.transcript.clone();
+        truncated.entries.remove(0);
+        let resigned = SignedTranscript::sign(truncated, &keys, "identity").unwrap();
+        assert!(matches!(
+            resigned.verify(None),
+            Err(SecureCommsError::Validation(_))
+        ));
+    }
+}
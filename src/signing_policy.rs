@@ -0,0 +1,614 @@
+//! # Signing Policy - Per-Channel Signature Cadence with Chained MACs
+//!
+//! Post-quantum transcript signatures are slow to produce and large on the
+//! wire. A signing policy trades the granularity of non-repudiation for
+//! latency: instead of signing every message, a channel can sign only some
+//! of them and bind the rest into a MAC chain that the signatures commit to.
+//!
+//! ## Policies
+//!
+//! - **Always**: Every message carries a transcript signature
+//! - **EveryNth**: Every `interval`-th message is signed; the messages in
+//!   between carry only a chained MAC
+//! - **Checkpoint**: Messages carry only chained MACs; after every
+//!   `interval` messages a separate signed checkpoint covers the chain head
+//!
+//! ## MAC Chain
+//!
+//! Each chained message carries a [`ChainLink`] in the
+//! [`SIGNATURE_CHAIN_HEADER`] header: its position in the chain, the
+//! previous link's MAC and a SHA3-256 MAC keyed from the session key over
+//! the position, the previous MAC and the message transcript. The header is
+//! part of the transcript a signature covers, so a signed message or
+//! checkpoint commits to every message before it. Receivers refuse a link
+//! whose MAC does not verify, a link that forks the chain, and an unsigned
+//! message at a position the policy requires to be signed.
+//!
+//! ## Negotiation
+//!
+//! Each side offers its local policy with a `SigningOffer` once the channel
+//! is established; both adopt the policy leaving the shorter run of
+//! messages without a signature. The result is symmetric, so the peers
+//! agree without a further round trip. Channels without a negotiated policy
+//! sign every message.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::signing_policy::{SigningChannels, SigningPolicy};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let session_key = [7u8; 32];
+//! let mut alice = SigningChannels::new(SigningPolicy::EveryNth { interval: 8 });
+//! let mut bob = SigningChannels::new(SigningPolicy::Checkpoint { interval: 16 });
+//! let agreed = alice.negotiate("bob", &bob.local_policy(), &session_key)?;
+//! assert_eq!(agreed, bob.negotiate("alice", &alice.local_policy(), &session_key)?);
+//!
+//! let outbound = alice.next_link("bob", b"transcript").expect("policy negotiated");
+//! bob.accept_link("alice", &outbound.link, b"transcript", outbound.sign)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::constant_time::ct_eq;
+use crate::hybrid_signature::HybridSignature;
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, HashMap};
+
+/// Header carrying a message's chain link
+pub const SIGNATURE_CHAIN_HEADER: &str = "x-signature-chain";
+
+/// Header marking a signed checkpoint frame
+pub const SIGNED_CHECKPOINT_HEADER: &str = "x-signed-checkpoint";
+
+/// Largest signing interval a policy may use
+pub const MAX_SIGNING_INTERVAL: u32 = 4096;
+
+/// How often a channel's messages are signed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningPolicy {
+    /// Sign every message
+    #[default]
+    Always,
+    /// Sign every `interval`-th message, chaining MACs in between
+    EveryNth {
+        /// Messages per signed message
+        interval: u32,
+    },
+    /// Chain MACs only, with a signed checkpoint every `interval` messages
+    Checkpoint {
+        /// Messages per signed checkpoint
+        interval: u32,
+    },
+}
+
+impl SigningPolicy {
+    /// Check the interval is usable
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            SigningPolicy::Always => Ok(()),
+            SigningPolicy::EveryNth { interval } | SigningPolicy::Checkpoint { interval } => {
+                if *interval == 0 || *interval > MAX_SIGNING_INTERVAL {
+                    return Err(SecureCommsError::Configuration(format!(
+                        "Signing interval must be between 1 and {}, got {}",
+                        MAX_SIGNING_INTERVAL, interval
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Longest run of messages a signature does not yet cover
+    pub fn max_unsigned_run(&self) -> u32 {
+        match self {
+            SigningPolicy::Always => 0,
+            SigningPolicy::EveryNth { interval } => interval - 1,
+            SigningPolicy::Checkpoint { interval } => *interval,
+        }
+    }
+
+    /// Policy both peers adopt from their offers
+    ///
+    /// The one with the shorter unsigned run wins; on a tie, signing the
+    /// messages themselves beats separate checkpoints.
+    pub fn negotiate(&self, other: &SigningPolicy) -> SigningPolicy {
+        let strength = |policy: &SigningPolicy| {
+            let rank = match policy {
+                SigningPolicy::Always => 0,
+                SigningPolicy::EveryNth { .. } => 1,
+                SigningPolicy::Checkpoint { .. } => 2,
+            };
+            (policy.max_unsigned_run(), rank)
+        };
+        if strength(other) < strength(self) {
+            *other
+        } else {
+            *self
+        }
+    }
+
+    /// Whether the message at chain position `index` must be signed
+    pub fn signs_message(&self, index: u64) -> bool {
+        match self {
+            SigningPolicy::Always => true,
+            SigningPolicy::EveryNth { interval } => index.is_multiple_of(u64::from(*interval)),
+            SigningPolicy::Checkpoint { .. } => false,
+        }
+    }
+
+    /// Whether a signed checkpoint follows the message at `index`
+    pub fn checkpoint_due(&self, index: u64) -> bool {
+        match self {
+            SigningPolicy::Checkpoint { interval } => index.is_multiple_of(u64::from(*interval)),
+            _ => false,
+        }
+    }
+}
+
+/// Position of a message in its channel's MAC chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainLink {
+    /// Position in the chain, starting at 1
+    pub index: u64,
+    /// MAC of the previous link (zero for the first)
+    pub prev: [u8; 32],
+    /// MAC over the position, `prev` and the message transcript
+    pub mac: [u8; 32],
+}
+
+impl ChainLink {
+    /// Header value, as `index:prev:mac` with hex MACs
+    pub fn encode(&self) -> String {
+        format!("{}:{}:{}", self.index, hex(&self.prev), hex(&self.mac))
+    }
+
+    /// Parse a header value written by `encode`
+    pub fn decode(encoded: &str) -> Result<Self> {
+        let malformed =
+            || SecureCommsError::Validation(format!("Malformed chain link {:?}", encoded));
+        let mut parts = encoded.split(':');
+        let index = parts
+            .next()
+            .and_then(|index| index.parse().ok())
+            .ok_or_else(malformed)?;
+        let mac = |part: Option<&str>| -> Result<[u8; 32]> {
+            part.and_then(unhex)
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(malformed)
+        };
+        let link = Self {
+            index,
+            prev: mac(parts.next())?,
+            mac: mac(parts.next())?,
+        };
+        if parts.next().is_some() {
+            return Err(malformed());
+        }
+        Ok(link)
+    }
+}
+
+/// Link to attach to an outbound message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboundLink {
+    pub link: ChainLink,
+    /// Whether the policy requires signing this message
+    pub sign: bool,
+    /// Whether a signed checkpoint must follow this message
+    pub checkpoint: bool,
+}
+
+/// Signed commitment to a chain head, sent under `Checkpoint` policies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedCheckpoint {
+    /// Chain position the checkpoint covers
+    pub index: u64,
+    /// MAC of the link at `index`
+    pub chain_head: [u8; 32],
+    /// Transcript signature over `checkpoint_transcript`
+    pub signature: HybridSignature,
+}
+
+/// Bytes a checkpoint's signature covers
+pub fn checkpoint_transcript(
+    sender_id: &str,
+    recipient_id: &str,
+    index: u64,
+    chain_head: &[u8; 32],
+) -> Vec<u8> {
+    let mut transcript = b"signed_checkpoint_v1".to_vec();
+    for field in [
+        sender_id.as_bytes(),
+        recipient_id.as_bytes(),
+        &index.to_be_bytes(),
+        chain_head,
+    ] {
+        transcript.extend_from_slice(&(field.len() as u64).to_be_bytes());
+        transcript.extend_from_slice(field);
+    }
+    transcript
+}
+
+/// Signing counters of one channel
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningStats {
+    /// Chained messages sent with a signature
+    pub signed_sent: u64,
+    /// Chained messages sent with only a MAC
+    pub mac_only_sent: u64,
+    /// Signed checkpoints sent
+    pub checkpoints_sent: u64,
+    /// Chained messages accepted
+    pub links_received: u64,
+    /// Signed checkpoints verified
+    pub checkpoints_verified: u64,
+    /// Chain positions skipped by lost or dropped messages
+    pub gaps: u64,
+}
+
+/// Chain state of one channel
+struct ChannelSigning {
+    policy: SigningPolicy,
+    key: SecretBytes,
+    sent: u64,
+    sent_head: [u8; 32],
+    received: u64,
+    received_head: [u8; 32],
+    /// Heads received since the last verified checkpoint
+    pending: BTreeMap<u64, [u8; 32]>,
+    checkpointed: u64,
+    stats: SigningStats,
+}
+
+/// Negotiated signing policies and MAC chains by peer
+pub struct SigningChannels {
+    local: SigningPolicy,
+    channels: HashMap<String, ChannelSigning>,
+}
+
+impl SigningChannels {
+    /// Manager offering `local` to every peer
+    pub fn new(local: SigningPolicy) -> Self {
+        Self {
+            local,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Policy this node offers
+    pub fn local_policy(&self) -> SigningPolicy {
+        self.local
+    }
+
+    /// Adopt the stronger of our policy and a peer's offer
+    ///
+    /// Starts fresh MAC chains keyed from `session_key`, which must be the
+    /// key both ends hold for the channel.
+    pub fn negotiate(
+        &mut self,
+        peer_id: &str,
+        offer: &SigningPolicy,
+        session_key: &[u8],
+    ) -> Result<SigningPolicy> {
+        offer.validate()?;
+        let policy = self.local.negotiate(offer);
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"signature_chain_key_v1");
+        hasher.update(session_key);
+        let key = hasher.finalize();
+        self.channels.insert(
+            peer_id.to_string(),
+            ChannelSigning {
+                policy,
+                key: SecretBytes::from_slice(SensitiveKind::Key, &key),
+                sent: 0,
+                sent_head: [0u8; 32],
+                received: 0,
+                received_head: [0u8; 32],
+                pending: BTreeMap::new(),
+                checkpointed: 0,
+                stats: SigningStats::default(),
+            },
+        );
+        Ok(policy)
+    }
+
+    /// Negotiated policy of the channel with a peer
+    pub fn policy(&self, peer_id: &str) -> Option<SigningPolicy> {
+        self.channels.get(peer_id).map(|channel| channel.policy)
+    }
+
+    /// Signing counters of the channel with a peer
+    pub fn stats(&self, peer_id: &str) -> Option<SigningStats> {
+        self.channels
+            .get(peer_id)
+            .map(|channel| channel.stats.clone())
+    }
+
+    /// Extend the outbound chain with a message's transcript
+    ///
+    /// Returns `None` when no policy was negotiated or the policy is
+    /// `Always`, in which case the message is signed without a link.
+    pub fn next_link(&mut self, peer_id: &str, transcript: &[u8]) -> Option<OutboundLink> {
+        let channel = self
+            .channels
+            .get_mut(peer_id)
+            .filter(|channel| channel.policy != SigningPolicy::Always)?;
+        let index = channel.sent + 1;
+        let link = ChainLink {
+            index,
+            prev: channel.sent_head,
+            mac: link_mac(channel.key.expose(), index, &channel.sent_head, transcript),
+        };
+        channel.sent = index;
+        channel.sent_head = link.mac;
+        let sign = channel.policy.signs_message(index);
+        let checkpoint = channel.policy.checkpoint_due(index);
+        if sign {
+            channel.stats.signed_sent += 1;
+        } else {
+            channel.stats.mac_only_sent += 1;
+        }
+        if checkpoint {
+            channel.stats.checkpoints_sent += 1;
+        }
+        Some(OutboundLink {
+            link,
+            sign,
+            checkpoint,
+        })
+    }
+
+    /// Position and head of the outbound chain, for a signed checkpoint
+    pub fn outbound_head(&self, peer_id: &str) -> Option<(u64, [u8; 32])> {
+        self.channels
+            .get(peer_id)
+            .map(|channel| (channel.sent, channel.sent_head))
+    }
+
+    /// Whether an inbound message at `index` may arrive without a signature
+    pub fn allows_unsigned(&self, peer_id: &str, index: u64) -> bool {
+        self.channels
+            .get(peer_id)
+            .map(|channel| !channel.policy.signs_message(index))
+            .unwrap_or(false)
+    }
+
+    /// Check an inbound link and extend the peer's chain with it
+    ///
+    /// `signed` says whether the message carried a verified signature.
+    /// Forged or forking links and unsigned messages the policy requires
+    /// to be signed fail with `SecureCommsError::AuthenticationFailed`, as
+    /// do unsigned messages more than two checkpoint intervals past the
+    /// last verified checkpoint; the link is still recorded so a late
+    /// checkpoint can catch up.
+    pub fn accept_link(
+        &mut self,
+        peer_id: &str,
+        link: &ChainLink,
+        transcript: &[u8],
+        signed: bool,
+    ) -> Result<()> {
+        let channel = self.channels.get_mut(peer_id).ok_or_else(|| {
+            SecureCommsError::Validation(format!("No signing policy negotiated with {}", peer_id))
+        })?;
+        let expected = link_mac(channel.key.expose(), link.index, &link.prev, transcript);
+        if link.index == 0 || !ct_eq(&expected, &link.mac) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        if !signed && channel.policy.signs_message(link.index) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        if link.index == channel.received + 1 && link.prev != channel.received_head {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+        // Retransmissions verify but do not move the chain
+        if link.index > channel.received {
+            channel.stats.gaps += link.index - channel.received - 1;
+            channel.received = link.index;
+            channel.received_head = link.mac;
+            channel.stats.links_received += 1;
+            if let SigningPolicy::Checkpoint { interval } = channel.policy {
+                channel.pending.insert(link.index, link.mac);
+                if !signed && link.index > channel.checkpointed + 2 * u64::from(interval) {
+                    return Err(SecureCommsError::AuthenticationFailed);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Accept a checkpoint whose signature the caller has verified
+    ///
+    /// The checkpoint must name the head this node recorded at its
+    /// position; a checkpoint for a position never received fails with
+    /// `SecureCommsError::Validation`.
+    pub fn accept_checkpoint(
+        &mut self,
+        peer_id: &str,
+        checkpoint: &SignedCheckpoint,
+    ) -> Result<()> {
+        let channel = self.channels.get_mut(peer_id).ok_or_else(|| {
+            SecureCommsError::Validation(format!("No signing policy negotiated with {}", peer_id))
+        })?;
+        match channel.pending.get(&checkpoint.index) {
+            Some(head) if ct_eq(head, &checkpoint.chain_head) => {
+                channel.checkpointed = checkpoint.index;
+                channel.pending = channel.pending.split_off(&(checkpoint.index + 1));
+                channel.stats.checkpoints_verified += 1;
+                Ok(())
+            }
+            Some(_) => Err(SecureCommsError::AuthenticationFailed),
+            None => Err(SecureCommsError::Validation(format!(
+                "Checkpoint {} from {} covers no received message",
+                checkpoint.index, peer_id
+            ))),
+        }
+    }
+
+    /// Forget the chains of a closed channel
+    pub fn remove_peer(&mut self, peer_id: &str) {
+        self.channels.remove(peer_id);
+    }
+}
+
+fn link_mac(key: &[u8], index: u64, prev: &[u8; 32], transcript: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"signature_chain_v1");
+    hasher.update(key);
+    hasher.update(index.to_be_bytes());
+    hasher.update(prev);
+    hasher.update(transcript);
+    hasher.finalize().into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(encoded: &str) -> Option<Vec<u8>> {
+    if !encoded.len().is_multiple_of(2) || !encoded.is_ascii() {
+        return None;
+    }
+    (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [9u8; 32];
+
+    fn pair(local: SigningPolicy, remote: SigningPolicy) -> (SigningChannels, SigningChannels) {
+        let mut alice = SigningChannels::new(local);
+        let mut bob = SigningChannels::new(remote);
+        let agreed = alice.negotiate("bob", &remote, &KEY).unwrap();
+        assert_eq!(bob.negotiate("alice", &local, &KEY).unwrap(), agreed);
+        (alice, bob)
+    }
+
+    #[test]
+    fn test_negotiation_picks_shorter_unsigned_run() {
+        let every_4 = SigningPolicy::EveryNth { interval: 4 };
+        let checkpoint_4 = SigningPolicy::Checkpoint { interval: 4 };
+        let checkpoint_3 = SigningPolicy::Checkpoint { interval: 3 };
+        assert_eq!(every_4.negotiate(&checkpoint_4), every_4);
+        assert_eq!(checkpoint_4.negotiate(&every_4), every_4);
+        // EveryNth(4) and Checkpoint(3) both leave three messages unsigned
+        assert_eq!(checkpoint_3.negotiate(&every_4), every_4);
+        assert_eq!(
+            every_4.negotiate(&SigningPolicy::Always),
+            SigningPolicy::Always
+        );
+
+        assert!(SigningPolicy::EveryNth { interval: 0 }.validate().is_err());
+        assert!(SigningPolicy::Checkpoint {
+            interval: MAX_SIGNING_INTERVAL + 1
+        }
+        .validate()
+        .is_err());
+        let (mut alice, _) = pair(SigningPolicy::Always, SigningPolicy::Always);
+        assert!(alice.next_link("bob", b"m").is_none());
+    }
+
+    #[test]
+    fn test_every_nth_chain_and_downgrade() {
+        let policy = SigningPolicy::EveryNth { interval: 3 };
+        let (mut alice, mut bob) = pair(policy, policy);
+        let links: Vec<OutboundLink> = (0..3)
+            .map(|i| alice.next_link("bob", &[i]).unwrap())
+            .collect();
+        assert_eq!(
+            links.iter().map(|link| link.sign).collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+        assert_eq!(links[1].link.prev, links[0].link.mac);
+        let decoded = ChainLink::decode(&links[0].link.encode()).unwrap();
+        assert_eq!(decoded, links[0].link);
+        assert!(ChainLink::decode("1:zz:00").is_err());
+
+        bob.accept_link("alice", &links[0].link, &[0], false)
+            .unwrap();
+        // A tampered transcript or a stripped signature is refused
+        assert!(bob
+            .accept_link("alice", &links[1].link, &[7], false)
+            .is_err());
+        bob.accept_link("alice", &links[1].link, &[1], false)
+            .unwrap();
+        assert!(matches!(
+            bob.accept_link("alice", &links[2].link, &[2], false),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        bob.accept_link("alice", &links[2].link, &[2], true)
+            .unwrap();
+
+        // A correctly keyed link forking the chain at the next position is refused
+        let key = bob.channels["alice"].key.expose().to_vec();
+        let forged = ChainLink {
+            index: 4,
+            prev: links[0].link.mac,
+            mac: link_mac(&key, 4, &links[0].link.mac, &[3]),
+        };
+        assert!(bob.accept_link("alice", &forged, &[3], false).is_err());
+        assert_eq!(bob.stats("alice").unwrap().links_received, 3);
+    }
+
+    #[test]
+    fn test_checkpoints_cover_chain_and_gaps() {
+        let policy = SigningPolicy::Checkpoint { interval: 2 };
+        let (mut alice, mut bob) = pair(policy, policy);
+        let links: Vec<OutboundLink> = (0..6)
+            .map(|i| alice.next_link("bob", &[i]).unwrap())
+            .collect();
+        assert!(links.iter().all(|link| !link.sign));
+        assert_eq!(
+            links.iter().map(|link| link.checkpoint).collect::<Vec<_>>(),
+            vec![false, true, false, true, false, true]
+        );
+
+        // The second message is lost
+        bob.accept_link("alice", &links[0].link, &[0], false)
+            .unwrap();
+        bob.accept_link("alice", &links[2].link, &[2], false)
+            .unwrap();
+        bob.accept_link("alice", &links[3].link, &[3], false)
+            .unwrap();
+        let checkpoint = |index: u64, head: [u8; 32]| SignedCheckpoint {
+            index,
+            chain_head: head,
+            signature: HybridSignature {
+                ed25519: Vec::new(),
+                ml_dsa: None,
+            },
+        };
+        assert!(matches!(
+            bob.accept_checkpoint("alice", &checkpoint(2, links[1].link.mac)),
+            Err(SecureCommsError::Validation(_))
+        ));
+        // With no verified checkpoint, unsigned messages past two intervals are refused
+        assert!(bob
+            .accept_link("alice", &links[4].link, &[4], false)
+            .is_err());
+        assert!(bob
+            .accept_checkpoint("alice", &checkpoint(4, links[2].link.mac))
+            .is_err());
+        bob.accept_checkpoint("alice", &checkpoint(4, links[3].link.mac))
+            .unwrap();
+        bob.accept_link("alice", &links[5].link, &[5], false)
+            .unwrap();
+
+        let stats = bob.stats("alice").unwrap();
+        assert_eq!(stats.gaps, 1);
+        assert_eq!(stats.checkpoints_verified, 1);
+        assert_eq!(alice.stats("bob").unwrap().checkpoints_sent, 3);
+        assert_eq!(alice.outbound_head("bob"), Some((6, links[5].link.mac)));
+    }
+}
@@ -0,0 +1,357 @@
+//! # Startup - Ordered Initialization Stages and Degraded Mode
+//!
+//! Describes the stages the client runs while starting, the order they
+//! depend on each other in, and which of them the client can live without.
+//! When an optional stage fails (quantum hardware detection times out, the
+//! metrics exporter port is already taken) the client starts anyway in a
+//! degraded mode and records why, instead of failing construction; the
+//! record is surfaced in `StreamlinedSecureClient::health_report`.
+//!
+//! ## Stages
+//!
+//! | Stage | Depends on | Required |
+//! |-------|------------|----------|
+//! | security_foundation | - | yes |
+//! | crypto_protocols | security_foundation | yes |
+//! | quantum_core | security_foundation | no: falls back to simulation |
+//! | network_comms | - | yes |
+//! | consensus | crypto_protocols | yes |
+//! | storage | crypto_protocols | yes |
+//! | metrics_exporter | - | no: skipped |
+//!
+//! Optional stages can be made mandatory with `StartupConfig::required`, and
+//! degraded startup can be switched off entirely with `allow_degraded`.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::startup::{StartupConfig, StartupStage};
+//! use quantum_forge_secure_comms::{StreamlinedConfig, StreamlinedSecureClient};
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let config = StreamlinedConfig {
+//!     metrics_exporter: Some("0.0.0.0:9100".to_string()),
+//!     startup: StartupConfig::default().require(StartupStage::QuantumCore),
+//!     ..Default::default()
+//! };
+//! let client = StreamlinedSecureClient::with_config(config).await?;
+//! for stage in client.startup_report().degraded() {
+//!     println!("⚠️ {} degraded: {:?}", stage.stage.name(), stage.status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// Stage of client startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupStage {
+    /// Entropy sources and threat detection
+    SecurityFoundation,
+    /// Post-quantum cryptography and identity keys
+    CryptoProtocols,
+    /// Quantum core and hardware detection
+    QuantumCore,
+    /// Network communications
+    NetworkComms,
+    /// Consensus and verification engine
+    Consensus,
+    /// Delivery log and address book
+    Storage,
+    /// Prometheus metrics exporter
+    MetricsExporter,
+}
+
+impl StartupStage {
+    /// Every stage, in the order startup runs them
+    pub const ORDER: [StartupStage; 7] = [
+        StartupStage::SecurityFoundation,
+        StartupStage::CryptoProtocols,
+        StartupStage::QuantumCore,
+        StartupStage::NetworkComms,
+        StartupStage::Consensus,
+        StartupStage::Storage,
+        StartupStage::MetricsExporter,
+    ];
+
+    /// Name used in reports and logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            StartupStage::SecurityFoundation => "security_foundation",
+            StartupStage::CryptoProtocols => "crypto_protocols",
+            StartupStage::QuantumCore => "quantum_core",
+            StartupStage::NetworkComms => "network_comms",
+            StartupStage::Consensus => "consensus",
+            StartupStage::Storage => "storage",
+            StartupStage::MetricsExporter => "metrics_exporter",
+        }
+    }
+
+    /// Stages that must be ready before this one runs
+    pub fn dependencies(&self) -> &'static [StartupStage] {
+        match self {
+            StartupStage::SecurityFoundation
+            | StartupStage::NetworkComms
+            | StartupStage::MetricsExporter => &[],
+            StartupStage::CryptoProtocols | StartupStage::QuantumCore => {
+                &[StartupStage::SecurityFoundation]
+            }
+            StartupStage::Consensus | StartupStage::Storage => &[StartupStage::CryptoProtocols],
+        }
+    }
+
+    /// Whether the client can start without this stage fully working
+    pub fn is_optional(&self) -> bool {
+        matches!(
+            self,
+            StartupStage::QuantumCore | StartupStage::MetricsExporter
+        )
+    }
+}
+
+/// Degraded startup policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupConfig {
+    /// Start in degraded mode when an optional stage fails
+    pub allow_degraded: bool,
+    /// Optional stages that must nevertheless succeed
+    pub required: BTreeSet<StartupStage>,
+}
+
+impl Default for StartupConfig {
+    fn default() -> Self {
+        Self {
+            allow_degraded: true,
+            required: BTreeSet::new(),
+        }
+    }
+}
+
+impl StartupConfig {
+    /// Make an optional stage mandatory
+    pub fn require(mut self, stage: StartupStage) -> Self {
+        self.required.insert(stage);
+        self
+    }
+
+    /// Whether a failure of `stage` may be tolerated
+    pub fn tolerates(&self, stage: StartupStage) -> bool {
+        self.allow_degraded && stage.is_optional() && !self.required.contains(&stage)
+    }
+}
+
+/// Result of one startup stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    /// Stage completed normally
+    Ready,
+    /// Stage runs with reduced function, such as simulation instead of hardware
+    Degraded {
+        /// Why the stage could not start normally
+        reason: String,
+    },
+    /// Stage does not run
+    Skipped {
+        /// Why the stage was skipped
+        reason: String,
+    },
+}
+
+/// Recorded outcome of one stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageOutcome {
+    /// Stage
+    pub stage: StartupStage,
+    /// How it ended
+    pub status: StageStatus,
+    /// Time the stage took in milliseconds
+    pub elapsed_ms: u64,
+}
+
+/// Outcome of every stage run during startup
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupReport {
+    /// Policy the client started under
+    pub config: StartupConfig,
+    /// Stages in the order they ran
+    pub stages: Vec<StageOutcome>,
+}
+
+impl StartupReport {
+    /// Empty report for a startup under `config`
+    pub fn new(config: StartupConfig) -> Self {
+        Self {
+            config,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Record a stage that completed normally
+    pub fn ready(&mut self, stage: StartupStage, elapsed: Duration) -> Result<()> {
+        self.record(stage, StageStatus::Ready, elapsed)
+    }
+
+    /// Record a stage that runs with reduced function, if the policy allows it
+    pub fn degrade(
+        &mut self,
+        stage: StartupStage,
+        reason: String,
+        elapsed: Duration,
+    ) -> Result<()> {
+        self.tolerate(stage, &reason)?;
+        self.record(stage, StageStatus::Degraded { reason }, elapsed)
+    }
+
+    /// Record a stage that does not run, if the policy allows it
+    pub fn skip(&mut self, stage: StartupStage, reason: String, elapsed: Duration) -> Result<()> {
+        self.tolerate(stage, &reason)?;
+        self.record(stage, StageStatus::Skipped { reason }, elapsed)
+    }
+
+    /// Outcome of a stage, if it ran
+    pub fn stage(&self, stage: StartupStage) -> Option<&StageOutcome> {
+        self.stages.iter().find(|outcome| outcome.stage == stage)
+    }
+
+    /// Stages that did not complete normally
+    pub fn degraded(&self) -> Vec<&StageOutcome> {
+        self.stages
+            .iter()
+            .filter(|outcome| outcome.status != StageStatus::Ready)
+            .collect()
+    }
+
+    /// Whether the client started with any stage degraded or skipped
+    pub fn is_degraded(&self) -> bool {
+        !self.degraded().is_empty()
+    }
+
+    fn tolerate(&self, stage: StartupStage, reason: &str) -> Result<()> {
+        if self.config.tolerates(stage) {
+            Ok(())
+        } else {
+            Err(SecureCommsError::SystemError(format!(
+                "Startup stage {} failed: {}",
+                stage.name(),
+                reason
+            )))
+        }
+    }
+
+    fn record(
+        &mut self,
+        stage: StartupStage,
+        status: StageStatus,
+        elapsed: Duration,
+    ) -> Result<()> {
+        if let Some(missing) = stage
+            .dependencies()
+            .iter()
+            .find(|dependency| self.stage(**dependency).is_none())
+        {
+            return Err(SecureCommsError::SystemError(format!(
+                "Startup stage {} ran before its dependency {}",
+                stage.name(),
+                missing.name()
+            )));
+        }
+        self.stages.push(StageOutcome {
+            stage,
+            status,
+            elapsed_ms: elapsed.as_millis() as u64,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_respects_dependencies() {
+        for (position, stage) in StartupStage::ORDER.iter().enumerate() {
+            for dependency in stage.dependencies() {
+                let before = StartupStage::ORDER
+                    .iter()
+                    .position(|candidate| candidate == dependency)
+                    .unwrap();
+                assert!(before < position, "{:?} before {:?}", dependency, stage);
+            }
+        }
+
+        let mut report = StartupReport::new(StartupConfig::default());
+        assert!(report
+            .ready(StartupStage::Consensus, Duration::ZERO)
+            .is_err());
+        for stage in StartupStage::ORDER {
+            report.ready(stage, Duration::from_millis(3)).unwrap();
+        }
+        assert!(!report.is_degraded());
+    }
+
+    #[test]
+    fn test_only_optional_stages_degrade() {
+        let mut report = StartupReport::new(StartupConfig::default());
+        report
+            .ready(StartupStage::SecurityFoundation, Duration::ZERO)
+            .unwrap();
+        report
+            .degrade(
+                StartupStage::QuantumCore,
+                "hardware detection timed out".to_string(),
+                Duration::from_millis(10),
+            )
+            .unwrap();
+        report
+            .skip(
+                StartupStage::MetricsExporter,
+                "address in use".to_string(),
+                Duration::ZERO,
+            )
+            .unwrap();
+        assert!(report
+            .skip(
+                StartupStage::NetworkComms,
+                "bind failed".to_string(),
+                Duration::ZERO
+            )
+            .is_err());
+
+        assert!(report.is_degraded());
+        let degraded: Vec<StartupStage> = report.degraded().iter().map(|o| o.stage).collect();
+        assert_eq!(
+            degraded,
+            vec![StartupStage::QuantumCore, StartupStage::MetricsExporter]
+        );
+        assert_eq!(
+            report.stage(StartupStage::QuantumCore).unwrap().elapsed_ms,
+            10
+        );
+    }
+
+    #[test]
+    fn test_policy_can_require_optional_stages() {
+        let strict = StartupConfig::default().require(StartupStage::QuantumCore);
+        assert!(!strict.tolerates(StartupStage::QuantumCore));
+        assert!(strict.tolerates(StartupStage::MetricsExporter));
+
+        let disabled = StartupConfig {
+            allow_degraded: false,
+            ..Default::default()
+        };
+        assert!(!disabled.tolerates(StartupStage::MetricsExporter));
+
+        let json = serde_json::to_value(&strict).unwrap();
+        assert_eq!(json["required"][0], "quantum_core");
+        let parsed: StartupConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, strict);
+    }
+}
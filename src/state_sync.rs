@@ -0,0 +1,585 @@
+//! # State Sync - Catch-Up for Lagging Validators
+//!
+//! Lets a validator that fell behind (after downtime or a partition) fetch
+//! the consensus decisions it missed from its peers and rejoin consensus.
+//! Every decision travels with its commit certificate, so a syncing node
+//! trusts nothing but the validator set: entries whose certificate does not
+//! verify are refused, whichever peer served them.
+//!
+//! ## Sync Modes
+//!
+//! - **Incremental Log**: Small gaps are filled with a batch of certified decisions
+//! - **Snapshot**: Large gaps fetch a signed snapshot of all certified decisions,
+//!   streamed as bulk traffic through the chunked file transfer subsystem
+//!
+//! ## Verification
+//!
+//! - **Commit Certificates**: Each decision's certificate must verify against the validator set
+//! - **Vote Proofs**: Decision vote proofs must match their digest
+//! - **Snapshot Signature**: Snapshots are Ed25519-signed by a validator in the set
+//! - **Transfer Integrity**: Snapshot chunks and content are SHA-3 verified on arrival
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::consensus_verify::{ConsensusConfig, ConsensusEngine};
+//! use quantum_forge_secure_comms::state_sync::{StateSyncConfig, StateSyncManager};
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let engine = ConsensusEngine::new("validator_3".to_string(), ConsensusConfig::default()).await?;
+//! let mut sync = StateSyncManager::new(StateSyncConfig::default());
+//!
+//! sync.record_peer_height("validator_1", 1_250);
+//! if let Some((peer, request)) = sync.plan_sync(engine.sync_height()) {
+//!     println!("Requesting {:?} from {}", request, peer);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::consensus_verify::{
+    verify_commit_signature, CommitCertificate, ConsensusEngine, ConsensusProposal,
+    ProposalDecision, ValidatorSet,
+};
+use crate::file_transfer::{prepare_transfer, FileChunk, TransferManifest, TransferReceiver};
+use crate::key_manager::KeyManager;
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// State sync configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSyncConfig {
+    /// Height gap that triggers a sync
+    pub lag_threshold: u64,
+    /// Largest gap served as an incremental log; larger gaps use a snapshot
+    pub max_log_entries: usize,
+    /// Snapshot transfer chunk size in bytes
+    pub chunk_size: usize,
+}
+
+impl Default for StateSyncConfig {
+    fn default() -> Self {
+        Self {
+            lag_threshold: 8,
+            max_log_entries: 500,
+            chunk_size: 256 * 1024,
+        }
+    }
+}
+
+/// A decided proposal with the certificate proving its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncEntry {
+    /// Decided proposal
+    pub proposal: ConsensusProposal,
+    /// Decision with its vote proof
+    pub decision: ProposalDecision,
+    /// Commit certificate for the decision
+    pub certificate: CommitCertificate,
+}
+
+impl SyncEntry {
+    /// Check the entry is internally consistent and certified by the set
+    pub fn verify(&self, validator_set: &ValidatorSet) -> Result<()> {
+        let proposal_id = &self.proposal.proposal_id;
+        if self.decision.proposal_id != *proposal_id || self.certificate.proposal_id != *proposal_id
+        {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Sync entry for {} mixes proposals",
+                proposal_id
+            )));
+        }
+        if self.certificate.status != self.decision.status {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Sync entry for {} has status {:?} but certificate {:?}",
+                proposal_id, self.decision.status, self.certificate.status
+            )));
+        }
+        if !self.decision.vote_proof.verify() {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Vote proof for {} does not match its digest",
+                proposal_id
+            )));
+        }
+        self.certificate
+            .verify_data(&self.proposal.data, validator_set)
+    }
+}
+
+/// Signed snapshot of all certified decisions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// Number of certified decisions included
+    pub height: u64,
+    /// Certified decisions in ledger order
+    pub entries: Vec<SyncEntry>,
+    /// Validator set the entries were certified by
+    pub validator_set_hash: Vec<u8>,
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+    /// Validator that produced the snapshot
+    pub signer_id: String,
+    /// Ed25519 signature over `digest`
+    pub signature: Vec<u8>,
+}
+
+impl StateSnapshot {
+    /// Capture and sign a snapshot of an engine's certified decisions
+    pub fn capture(
+        engine: &ConsensusEngine,
+        signer_id: &str,
+        keys: &KeyManager,
+        signing_key_id: &str,
+    ) -> Result<Self> {
+        let entries = engine.export_sync_entries(0, usize::MAX);
+        let mut snapshot = Self {
+            height: entries.len() as u64,
+            entries,
+            validator_set_hash: engine.validator_set().hash(),
+            created_at: chrono::Utc::now().timestamp() as u64,
+            signer_id: signer_id.to_string(),
+            signature: Vec::new(),
+        };
+        snapshot.signature = keys.sign(signing_key_id, &snapshot.digest())?;
+        Ok(snapshot)
+    }
+
+    /// Digest covered by the snapshot signature
+    pub fn digest(&self) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"state_snapshot_v1");
+        hasher.update(self.height.to_le_bytes());
+        hasher.update(&self.validator_set_hash);
+        hasher.update(self.created_at.to_le_bytes());
+        hasher.update(self.signer_id.as_bytes());
+        for entry in &self.entries {
+            hasher.update(entry.proposal.proposal_id.as_bytes());
+            hasher.update(&entry.certificate.data_hash);
+            hasher.update(&entry.decision.vote_proof.digest);
+        }
+        hasher.finalize().to_vec()
+    }
+
+    /// Verify signer, signature and every certified entry
+    pub fn verify(&self, validator_set: &ValidatorSet) -> Result<()> {
+        if self.validator_set_hash != validator_set.hash() {
+            return Err(SecureCommsError::ConsensusVerify(
+                "Snapshot was built for a different validator set".to_string(),
+            ));
+        }
+        if self.height != self.entries.len() as u64 {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Snapshot claims height {} but holds {} entries",
+                self.height,
+                self.entries.len()
+            )));
+        }
+        let signer_key = validator_set.public_key(&self.signer_id).ok_or_else(|| {
+            SecureCommsError::ConsensusVerify(format!(
+                "Snapshot signer {} is not a validator",
+                self.signer_id
+            ))
+        })?;
+        if !verify_commit_signature(signer_key, &self.digest(), &self.signature) {
+            return Err(SecureCommsError::ConsensusVerify(format!(
+                "Invalid snapshot signature from {}",
+                self.signer_id
+            )));
+        }
+        for entry in &self.entries {
+            entry.verify(validator_set)?;
+        }
+        Ok(())
+    }
+}
+
+/// State sync protocol messages, carried over secure channels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SyncMessage {
+    /// Ask a peer for its height
+    StatusRequest,
+    /// Report the local height
+    Status { height: u64 },
+    /// Ask for certified decisions starting at a height
+    LogRequest {
+        from_height: u64,
+        max_entries: usize,
+    },
+    /// Certified decisions starting at `from_height`
+    LogEntries {
+        from_height: u64,
+        entries: Vec<SyncEntry>,
+    },
+    /// Ask for a full snapshot
+    SnapshotRequest,
+    /// Announce a snapshot transfer
+    SnapshotOffer(TransferManifest),
+    /// One chunk of a snapshot transfer
+    SnapshotChunk(FileChunk),
+}
+
+/// Drives state sync for the local validator
+#[derive(Debug, Default)]
+pub struct StateSyncManager {
+    config: StateSyncConfig,
+    /// Last reported height per peer
+    peer_heights: HashMap<String, u64>,
+    /// Snapshot transfers in progress, per serving peer
+    transfers: HashMap<String, TransferReceiver>,
+    /// Entries applied through sync
+    entries_applied: u64,
+    /// Snapshots applied
+    snapshots_applied: u64,
+}
+
+impl StateSyncManager {
+    /// Create state sync manager
+    pub fn new(config: StateSyncConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Remember a peer's reported height
+    pub fn record_peer_height(&mut self, peer_id: &str, height: u64) {
+        self.peer_heights.insert(peer_id.to_string(), height);
+    }
+
+    /// Whether the local node trails the best known peer by the lag threshold
+    pub fn is_lagging(&self, local_height: u64) -> bool {
+        self.plan_sync(local_height).is_some()
+    }
+
+    /// Choose a peer and request to catch up from, if lagging
+    pub fn plan_sync(&self, local_height: u64) -> Option<(String, SyncMessage)> {
+        let (peer_id, peer_height) = self
+            .peer_heights
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))?;
+        let gap = peer_height.saturating_sub(local_height);
+        if gap < self.config.lag_threshold.max(1) {
+            return None;
+        }
+
+        let request = if gap > self.config.max_log_entries as u64 {
+            SyncMessage::SnapshotRequest
+        } else {
+            SyncMessage::LogRequest {
+                from_height: local_height,
+                max_entries: self.config.max_log_entries,
+            }
+        };
+        Some((peer_id.clone(), request))
+    }
+
+    /// Peers far enough ahead to serve a catch-up, highest first
+    ///
+    /// Any of them can answer the request from `plan_sync`, so the caller
+    /// may hedge across them; see `crate::hedging`.
+    pub fn sync_sources(&self, local_height: u64) -> Vec<String> {
+        let threshold = self.config.lag_threshold.max(1);
+        let mut sources: Vec<(&String, &u64)> = self
+            .peer_heights
+            .iter()
+            .filter(|(_, height)| height.saturating_sub(local_height) >= threshold)
+            .collect();
+        sources.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        sources
+            .into_iter()
+            .map(|(peer_id, _)| peer_id.clone())
+            .collect()
+    }
+
+    /// Answer a sync request from a peer (serving side)
+    ///
+    /// Snapshots are signed with the local validator's signing key and split
+    /// into a manifest followed by its chunks.
+    pub fn serve(
+        &self,
+        engine: &ConsensusEngine,
+        request: &SyncMessage,
+        keys: &KeyManager,
+        signing_key_id: &str,
+    ) -> Result<Vec<SyncMessage>> {
+        match request {
+            SyncMessage::StatusRequest => Ok(vec![SyncMessage::Status {
+                height: engine.sync_height(),
+            }]),
+            SyncMessage::LogRequest {
+                from_height,
+                max_entries,
+            } => Ok(vec![SyncMessage::LogEntries {
+                from_height: *from_height,
+                entries: engine.export_sync_entries(
+                    *from_height,
+                    (*max_entries).min(self.config.max_log_entries),
+                ),
+            }]),
+            SyncMessage::SnapshotRequest => {
+                let snapshot = StateSnapshot::capture(
+                    engine,
+                    engine.get_local_validator_id(),
+                    keys,
+                    signing_key_id,
+                )?;
+                let bytes = serde_json::to_vec(&snapshot).map_err(|e| {
+                    SecureCommsError::SystemError(format!("Snapshot serialization failed: {}", e))
+                })?;
+                let (manifest, chunks) =
+                    prepare_transfer("consensus_snapshot", &bytes, self.config.chunk_size);
+
+                let mut messages = vec![SyncMessage::SnapshotOffer(manifest)];
+                messages.extend(chunks.into_iter().map(SyncMessage::SnapshotChunk));
+                Ok(messages)
+            }
+            other => Err(SecureCommsError::Validation(format!(
+                "{:?} is not a sync request",
+                other
+            ))),
+        }
+    }
+
+    /// Handle a sync response (syncing side)
+    ///
+    /// Verified entries are applied to the engine; returns how many new
+    /// decisions were applied.
+    pub fn handle_response(
+        &mut self,
+        peer_id: &str,
+        message: SyncMessage,
+        engine: &mut ConsensusEngine,
+    ) -> Result<usize> {
+        let validator_set = engine.validator_set();
+        match message {
+            SyncMessage::Status { height } => {
+                self.record_peer_height(peer_id, height);
+                Ok(0)
+            }
+            SyncMessage::LogEntries { entries, .. } => {
+                let applied = engine.apply_sync_entries(&entries, &validator_set)?;
+                self.entries_applied += applied as u64;
+                Ok(applied)
+            }
+            SyncMessage::SnapshotOffer(manifest) => {
+                self.transfers
+                    .insert(peer_id.to_string(), TransferReceiver::new(manifest)?);
+                Ok(0)
+            }
+            SyncMessage::SnapshotChunk(chunk) => {
+                let receiver = self.transfers.get_mut(peer_id).ok_or_else(|| {
+                    SecureCommsError::Validation(format!(
+                        "Unexpected snapshot chunk from {}",
+                        peer_id
+                    ))
+                })?;
+                if !receiver.accept_chunk(chunk)? {
+                    return Ok(0);
+                }
+
+                let bytes = receiver.assemble();
+                self.transfers.remove(peer_id);
+                let snapshot: StateSnapshot = serde_json::from_slice(&bytes?).map_err(|e| {
+                    SecureCommsError::Validation(format!("Invalid snapshot: {}", e))
+                })?;
+                snapshot.verify(&validator_set)?;
+
+                let applied = engine.apply_sync_entries(&snapshot.entries, &validator_set)?;
+                self.entries_applied += applied as u64;
+                self.snapshots_applied += 1;
+                println!(
+                    "📦 Applied snapshot from {} at height {} ({} new decisions)",
+                    peer_id, snapshot.height, applied
+                );
+                Ok(applied)
+            }
+            other => Err(SecureCommsError::Validation(format!(
+                "{:?} is not a sync response",
+                other
+            ))),
+        }
+    }
+
+    /// Snapshot transfers still in progress, with their missing chunks
+    pub fn pending_transfers(&self) -> Vec<(String, Vec<usize>)> {
+        self.transfers
+            .iter()
+            .map(|(peer, receiver)| (peer.clone(), receiver.missing_chunks()))
+            .collect()
+    }
+
+    /// Get state sync statistics
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "known_peers".to_string(),
+            serde_json::Value::Number(self.peer_heights.len().into()),
+        );
+        stats.insert(
+            "transfers_in_progress".to_string(),
+            serde_json::Value::Number(self.transfers.len().into()),
+        );
+        stats.insert(
+            "entries_applied".to_string(),
+            serde_json::Value::Number(self.entries_applied.into()),
+        );
+        stats.insert(
+            "snapshots_applied".to_string(),
+            serde_json::Value::Number(self.snapshots_applied.into()),
+        );
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_verify::{
+        sign_commit_vote, ConsensusConfig, ValidatorInfo, VerificationResult, VoteType,
+    };
+    use crate::key_manager::KeyPurpose;
+
+    const VALIDATORS: u8 = 3;
+
+    async fn engine_with_validators(local_id: &str) -> ConsensusEngine {
+        let config = ConsensusConfig {
+            min_validators: 2,
+            consensus_threshold: 0.6,
+            ..ConsensusConfig::default()
+        };
+        let mut engine = ConsensusEngine::new(local_id.to_string(), config)
+            .await
+            .unwrap();
+        for i in 0..VALIDATORS {
+            engine.register_validator(ValidatorInfo {
+                validator_id: format!("v{}", i),
+                public_key: signing_keys(i).public_key("sig").unwrap().to_vec(),
+                trust_score: 1.0,
+                is_active: true,
+                last_activity: 0,
+            });
+        }
+        engine
+    }
+
+    fn signing_keys(index: u8) -> KeyManager {
+        let mut keys = KeyManager::new();
+        keys.import_key("sig", KeyPurpose::Signing, [index + 1; 32])
+            .unwrap();
+        keys
+    }
+
+    async fn decide(engine: &mut ConsensusEngine, data: &[u8]) {
+        let proposal_id = engine
+            .create_proposal("v0".to_string(), data.to_vec(), vec![1u8; 64])
+            .unwrap();
+        for i in 0..2u8 {
+            let key = ed25519_dalek::SigningKey::from_bytes(&[i + 1; 32]);
+            let signature = sign_commit_vote(&key, &proposal_id, data, VoteType::Approve);
+            engine
+                .submit_signed_vote(
+                    &proposal_id,
+                    format!("v{}", i),
+                    VoteType::Approve,
+                    VerificationResult::custom(true, 1.0, 0),
+                    signature,
+                )
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incremental_log_sync() {
+        let mut ahead = engine_with_validators("v0").await;
+        for i in 0..3u8 {
+            decide(&mut ahead, &[i; 16]).await;
+        }
+        let mut behind = engine_with_validators("v2").await;
+
+        let mut sync = StateSyncManager::new(StateSyncConfig {
+            lag_threshold: 2,
+            ..StateSyncConfig::default()
+        });
+        sync.record_peer_height("v0", ahead.sync_height());
+        let (peer, request) = sync.plan_sync(behind.sync_height()).unwrap();
+        assert_eq!(peer, "v0");
+        assert!(matches!(request, SyncMessage::LogRequest { .. }));
+
+        let responses = sync
+            .serve(&ahead, &request, &signing_keys(0), "sig")
+            .unwrap();
+        for response in responses {
+            sync.handle_response(&peer, response, &mut behind).unwrap();
+        }
+        assert_eq!(behind.sync_height(), 3);
+        assert!(!sync.is_lagging(behind.sync_height()));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_sync_over_chunks() {
+        let mut ahead = engine_with_validators("v0").await;
+        for i in 0..4u8 {
+            decide(&mut ahead, &[i; 4096]).await;
+        }
+        let mut behind = engine_with_validators("v2").await;
+
+        let mut sync = StateSyncManager::new(StateSyncConfig {
+            chunk_size: 1024,
+            ..StateSyncConfig::default()
+        });
+        let responses = sync
+            .serve(
+                &ahead,
+                &SyncMessage::SnapshotRequest,
+                &signing_keys(0),
+                "sig",
+            )
+            .unwrap();
+        assert!(responses.len() > 2);
+
+        let mut applied = 0;
+        for response in responses {
+            applied += sync.handle_response("v0", response, &mut behind).unwrap();
+        }
+        assert_eq!(applied, 4);
+        assert_eq!(behind.sync_height(), ahead.sync_height());
+        assert!(sync.pending_transfers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_uncertified_entries_rejected() {
+        let mut ahead = engine_with_validators("v0").await;
+        decide(&mut ahead, b"block").await;
+        let mut behind = engine_with_validators("v2").await;
+
+        let mut entries = ahead.export_sync_entries(0, 10);
+        entries[0].certificate.signatures.pop();
+        let validator_set = behind.validator_set();
+        assert!(behind.apply_sync_entries(&entries, &validator_set).is_err());
+
+        // A snapshot signed by a non-validator is refused
+        let mut outsider = KeyManager::new();
+        outsider
+            .import_key("sig", KeyPurpose::Signing, [99u8; 32])
+            .unwrap();
+        let snapshot = StateSnapshot::capture(&ahead, "v9", &outsider, "sig").unwrap();
+        assert!(snapshot.verify(&validator_set).is_err());
+        assert_eq!(behind.sync_height(), 0);
+    }
+
+    #[test]
+    fn test_sync_sources_are_peers_ahead_by_threshold() {
+        let mut sync = StateSyncManager::new(StateSyncConfig {
+            lag_threshold: 5,
+            ..StateSyncConfig::default()
+        });
+        sync.record_peer_height("v0", 120);
+        sync.record_peer_height("v1", 104);
+        sync.record_peer_height("v2", 130);
+        sync.record_peer_height("v3", 120);
+        assert_eq!(sync.sync_sources(100), vec!["v2", "v0", "v3"]);
+        assert!(sync.sync_sources(130).is_empty());
+    }
+}
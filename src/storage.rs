@@ -0,0 +1,682 @@
+//! # Storage - Key-Value Persistence Behind One Trait
+//!
+//! Components that keep state on disk go through the `Storage` trait rather
+//! than handling files themselves. Every backend offers point reads, prefix
+//! scans and atomic batch writes, and refuses to return data it cannot
+//! verify.
+//!
+//! ## Backends
+//!
+//! - **MemoryStorage**: Ordered map for tests and ephemeral nodes
+//! - **FileStorage**: Append-only log of checksummed batches, replayed on
+//!   open; needs no extra dependencies
+//! - **SledStorage**: Embedded sled tree (`storage-sled` feature)
+//! - **RocksDbStorage**: Embedded RocksDB database (`storage-rocksdb`
+//!   feature)
+//!
+//! `StorageBackend` selects one from configuration.
+//!
+//! ## Atomicity and Corruption Detection
+//!
+//! A `WriteBatch` is applied entirely or not at all. `FileStorage` writes
+//! each batch as one synced line carrying a SHA3 checksum; a torn final
+//! line left by a crash is dropped on open, a bad checksum anywhere else is
+//! reported as corruption. The sled and RocksDB backends frame every value
+//! with a checksum of its key and contents, so bit rot or a value moved to
+//! another key is detected on read.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::storage::{StorageBackend, WriteBatch};
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let storage = StorageBackend::File("data/state.log".into()).open()?;
+//! let mut batch = WriteBatch::new();
+//! batch.put(b"peer/validator_1", b"pinned");
+//! batch.delete(b"peer/validator_2");
+//! storage.write_batch(batch)?;
+//!
+//! for (key, value) in storage.scan_prefix(b"peer/")? {
+//!     println!("{} = {} bytes", String::from_utf8_lossy(&key), value.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::{Result, SecureCommsError};
+
+/// Bytes of SHA3-256 kept as a checksum
+const CHECKSUM_LEN: usize = 8;
+
+/// Ordered key-value store with atomic batch writes
+pub trait Storage: Send + Sync + std::fmt::Debug {
+    /// Backend name for logs and status reports
+    fn backend(&self) -> &'static str;
+
+    /// Value stored under `key`
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Apply every operation in `batch` atomically
+    fn write_batch(&self, batch: WriteBatch) -> Result<()>;
+
+    /// Entries whose key starts with `prefix`, in key order
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Store `value` under `key`
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        batch.put(key, value);
+        self.write_batch(batch)
+    }
+
+    /// Remove `key`
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        let mut batch = WriteBatch::new();
+        batch.delete(key);
+        self.write_batch(batch)
+    }
+
+    /// Reclaim space held by overwritten and deleted entries
+    fn compact(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// One operation of a `WriteBatch`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchOp {
+    /// Store a value
+    Put {
+        /// Entry key
+        key: Vec<u8>,
+        /// Entry value
+        value: Vec<u8>,
+    },
+    /// Remove an entry
+    Delete {
+        /// Entry key
+        key: Vec<u8>,
+    },
+}
+
+/// Operations applied together or not at all
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl WriteBatch {
+    /// Empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a put
+    pub fn put(&mut self, key: &[u8], value: &[u8]) -> &mut Self {
+        self.ops.push(BatchOp::Put {
+            key: key.to_vec(),
+            value: value.to_vec(),
+        });
+        self
+    }
+
+    /// Add a delete
+    pub fn delete(&mut self, key: &[u8]) -> &mut Self {
+        self.ops.push(BatchOp::Delete { key: key.to_vec() });
+        self
+    }
+
+    /// Operations in order
+    pub fn ops(&self) -> &[BatchOp] {
+        &self.ops
+    }
+
+    /// Number of operations
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether the batch has no operations
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    fn apply_to(self, map: &mut BTreeMap<Vec<u8>, Vec<u8>>) {
+        for op in self.ops {
+            match op {
+                BatchOp::Put { key, value } => {
+                    map.insert(key, value);
+                }
+                BatchOp::Delete { key } => {
+                    map.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Storage backend selected by configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageBackend {
+    /// In-memory map, lost on restart
+    Memory,
+    /// Append-only batch log at the path
+    File(PathBuf),
+    /// sled database directory at the path
+    Sled(PathBuf),
+    /// RocksDB database directory at the path
+    RocksDb(PathBuf),
+}
+
+impl StorageBackend {
+    /// Open the selected backend
+    ///
+    /// Backends whose feature is not compiled in fail with
+    /// `SecureCommsError::Configuration`.
+    pub fn open(&self) -> Result<Arc<dyn Storage>> {
+        match self {
+            StorageBackend::Memory => Ok(Arc::new(MemoryStorage::new())),
+            StorageBackend::File(path) => Ok(Arc::new(FileStorage::open(path)?)),
+            #[cfg(feature = "storage-sled")]
+            StorageBackend::Sled(path) => Ok(Arc::new(SledStorage::open(path)?)),
+            #[cfg(feature = "storage-rocksdb")]
+            StorageBackend::RocksDb(path) => Ok(Arc::new(RocksDbStorage::open(path)?)),
+            #[allow(unreachable_patterns)]
+            other => Err(SecureCommsError::Configuration(format!(
+                "Storage backend {:?} is not compiled in",
+                other
+            ))),
+        }
+    }
+}
+
+/// In-memory storage
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: RwLock<BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    /// Empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.read().get(key).cloned())
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        batch.apply_to(&mut self.entries.write());
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        Ok(scan(&self.entries.read(), prefix))
+    }
+}
+
+/// Batch line written by `FileStorage`
+#[derive(Serialize, Deserialize)]
+struct FileBatch {
+    ops: Vec<BatchOp>,
+    checksum: Vec<u8>,
+}
+
+/// Append-only log of checksummed batches
+///
+/// The whole store is held in memory and rebuilt from the log on open, so
+/// it suits the modest state of a single node: journals, pins and queues.
+#[derive(Debug)]
+pub struct FileStorage {
+    path: PathBuf,
+    file: Mutex<File>,
+    entries: RwLock<BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl FileStorage {
+    /// Open (or create) a log and replay it
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    SecureCommsError::SystemError(format!(
+                        "Failed to create storage directory: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+        let entries = Self::replay(&path)?;
+        let file = Self::open_append(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            entries: RwLock::new(entries),
+        })
+    }
+
+    /// Log file location
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn open_append(path: &Path) -> Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                SecureCommsError::SystemError(format!("Failed to open storage {:?}: {}", path, e))
+            })
+    }
+
+    fn encode(ops: Vec<BatchOp>) -> Result<Vec<u8>> {
+        let checksum = checksum(&[&serde_json::to_vec(&ops).unwrap_or_default()]);
+        let mut line = serde_json::to_vec(&FileBatch { ops, checksum }).map_err(|e| {
+            SecureCommsError::SystemError(format!("Storage serialization failed: {}", e))
+        })?;
+        line.push(b'\n');
+        Ok(line)
+    }
+
+    fn replay(path: &Path) -> Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+        let mut entries = BTreeMap::new();
+        if !path.exists() {
+            return Ok(entries);
+        }
+        let file = File::open(path).map_err(|e| {
+            SecureCommsError::SystemError(format!("Failed to open storage {:?}: {}", path, e))
+        })?;
+        let lines: Vec<String> = BufReader::new(file)
+            .lines()
+            .collect::<std::io::Result<_>>()
+            .map_err(|e| SecureCommsError::SystemError(format!("Storage read failed: {}", e)))?;
+
+        let last_index = lines.len().saturating_sub(1);
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let batch = serde_json::from_str::<FileBatch>(line)
+                .ok()
+                .filter(|batch| {
+                    checksum(&[&serde_json::to_vec(&batch.ops).unwrap_or_default()])
+                        == batch.checksum
+                });
+            match batch {
+                Some(batch) => WriteBatch { ops: batch.ops }.apply_to(&mut entries),
+                None if index == last_index => break, // torn write from a crash
+                None => {
+                    return Err(SecureCommsError::SystemError(format!(
+                        "Corrupt storage batch at line {} of {:?}",
+                        index + 1,
+                        path
+                    )))
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl Storage for FileStorage {
+    fn backend(&self) -> &'static str {
+        "file"
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.read().get(key).cloned())
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let line = Self::encode(batch.ops.clone())?;
+        // Hold the file lock while applying so readers never see a batch
+        // ahead of the log
+        let mut file = self.file.lock();
+        file.write_all(&line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| SecureCommsError::SystemError(format!("Storage write failed: {}", e)))?;
+        batch.apply_to(&mut self.entries.write());
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        Ok(scan(&self.entries.read(), prefix))
+    }
+
+    /// Rewrite the log as a single batch of the live entries
+    fn compact(&self) -> Result<()> {
+        let mut file = self.file.lock();
+        let ops = self
+            .entries
+            .read()
+            .iter()
+            .map(|(key, value)| BatchOp::Put {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect::<Vec<_>>();
+        let contents = if ops.is_empty() {
+            Vec::new()
+        } else {
+            Self::encode(ops)?
+        };
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".compact");
+        let tmp_path = PathBuf::from(tmp_path);
+        File::create(&tmp_path)
+            .and_then(|mut tmp| {
+                tmp.write_all(&contents)?;
+                tmp.sync_data()
+            })
+            .and_then(|_| fs::rename(&tmp_path, &self.path))
+            .map_err(|e| {
+                SecureCommsError::SystemError(format!("Storage compaction failed: {}", e))
+            })?;
+        *file = Self::open_append(&self.path)?;
+        Ok(())
+    }
+}
+
+/// sled-backed storage
+#[cfg(feature = "storage-sled")]
+#[derive(Debug)]
+pub struct SledStorage {
+    tree: sled::Db,
+}
+
+#[cfg(feature = "storage-sled")]
+impl SledStorage {
+    /// Open (or create) a sled database directory
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let tree = sled::open(path.as_ref()).map_err(|e| {
+            SecureCommsError::SystemError(format!("Failed to open sled storage: {}", e))
+        })?;
+        Ok(Self { tree })
+    }
+}
+
+#[cfg(feature = "storage-sled")]
+impl Storage for SledStorage {
+    fn backend(&self) -> &'static str {
+        "sled"
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let value = self
+            .tree
+            .get(key)
+            .map_err(|e| SecureCommsError::SystemError(format!("sled read failed: {}", e)))?;
+        value.map(|framed| unframe(key, &framed)).transpose()
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        let mut sled_batch = sled::Batch::default();
+        for op in batch.ops {
+            match op {
+                BatchOp::Put { key, value } => {
+                    let framed = frame(&key, &value);
+                    sled_batch.insert(key, framed);
+                }
+                BatchOp::Delete { key } => sled_batch.remove(key),
+            }
+        }
+        self.tree
+            .apply_batch(sled_batch)
+            .and_then(|_| self.tree.flush().map(|_| ()))
+            .map_err(|e| SecureCommsError::SystemError(format!("sled write failed: {}", e)))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.tree
+            .scan_prefix(prefix)
+            .map(|entry| {
+                let (key, framed) = entry.map_err(|e| {
+                    SecureCommsError::SystemError(format!("sled scan failed: {}", e))
+                })?;
+                Ok((key.to_vec(), unframe(&key, &framed)?))
+            })
+            .collect()
+    }
+}
+
+/// RocksDB-backed storage
+#[cfg(feature = "storage-rocksdb")]
+#[derive(Debug)]
+pub struct RocksDbStorage {
+    db: rocksdb::DB,
+}
+
+#[cfg(feature = "storage-rocksdb")]
+impl RocksDbStorage {
+    /// Open (or create) a RocksDB database directory
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut options = rocksdb::Options::default();
+        options.create_if_missing(true);
+        options.set_paranoid_checks(true);
+        let db = rocksdb::DB::open(&options, path.as_ref()).map_err(|e| {
+            SecureCommsError::SystemError(format!("Failed to open RocksDB storage: {}", e))
+        })?;
+        Ok(Self { db })
+    }
+}
+
+#[cfg(feature = "storage-rocksdb")]
+impl Storage for RocksDbStorage {
+    fn backend(&self) -> &'static str {
+        "rocksdb"
+    }
+
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let value = self
+            .db
+            .get(key)
+            .map_err(|e| SecureCommsError::SystemError(format!("RocksDB read failed: {}", e)))?;
+        value.map(|framed| unframe(key, &framed)).transpose()
+    }
+
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        let mut rocks_batch = rocksdb::WriteBatch::default();
+        for op in batch.ops {
+            match op {
+                BatchOp::Put { key, value } => rocks_batch.put(&key, frame(&key, &value)),
+                BatchOp::Delete { key } => rocks_batch.delete(&key),
+            }
+        }
+        let mut options = rocksdb::WriteOptions::default();
+        options.set_sync(true);
+        self.db
+            .write_opt(rocks_batch, &options)
+            .map_err(|e| SecureCommsError::SystemError(format!("RocksDB write failed: {}", e)))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.db
+            .prefix_iterator(prefix)
+            .take_while(|entry| {
+                entry
+                    .as_ref()
+                    .map(|(key, _)| key.starts_with(prefix))
+                    .unwrap_or(true)
+            })
+            .map(|entry| {
+                let (key, framed) = entry.map_err(|e| {
+                    SecureCommsError::SystemError(format!("RocksDB scan failed: {}", e))
+                })?;
+                Ok((key.to_vec(), unframe(&key, &framed)?))
+            })
+            .collect()
+    }
+
+    fn compact(&self) -> Result<()> {
+        self.db.compact_range::<&[u8], &[u8]>(None, None);
+        Ok(())
+    }
+}
+
+fn scan(entries: &BTreeMap<Vec<u8>, Vec<u8>>, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    entries
+        .range(prefix.to_vec()..)
+        .take_while(|(key, _)| key.starts_with(prefix))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+fn checksum(parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"storage_checksum_v1");
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize()[..CHECKSUM_LEN].to_vec()
+}
+
+/// Prefix a value with a checksum over its key and contents
+#[cfg_attr(
+    not(any(test, feature = "storage-sled", feature = "storage-rocksdb")),
+    allow(dead_code)
+)]
+fn frame(key: &[u8], value: &[u8]) -> Vec<u8> {
+    let mut framed = checksum(&[key, value]);
+    framed.extend_from_slice(value);
+    framed
+}
+
+/// Strip and verify the checksum added by `frame`
+#[cfg_attr(
+    not(any(test, feature = "storage-sled", feature = "storage-rocksdb")),
+    allow(dead_code)
+)]
+fn unframe(key: &[u8], framed: &[u8]) -> Result<Vec<u8>> {
+    if framed.len() < CHECKSUM_LEN {
+        return Err(SecureCommsError::SystemError(format!(
+            "Corrupt storage value under {}",
+            String::from_utf8_lossy(key)
+        )));
+    }
+    let (stored, value) = framed.split_at(CHECKSUM_LEN);
+    if checksum(&[key, value]) != stored {
+        return Err(SecureCommsError::SystemError(format!(
+            "Corrupt storage value under {}",
+            String::from_utf8_lossy(key)
+        )));
+    }
+    Ok(value.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercise(storage: &dyn Storage) {
+        storage.put(b"peer/a", b"1").unwrap();
+        let mut batch = WriteBatch::new();
+        batch
+            .put(b"peer/b", b"2")
+            .put(b"queue/x", b"3")
+            .delete(b"peer/a");
+        storage.write_batch(batch).unwrap();
+
+        assert_eq!(storage.get(b"peer/a").unwrap(), None);
+        assert_eq!(storage.get(b"peer/b").unwrap(), Some(b"2".to_vec()));
+        assert_eq!(
+            storage.scan_prefix(b"peer/").unwrap(),
+            vec![(b"peer/b".to_vec(), b"2".to_vec())]
+        );
+        storage.delete(b"queue/x").unwrap();
+        assert!(storage.scan_prefix(b"queue/").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backends_share_semantics() {
+        exercise(&MemoryStorage::new());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.log");
+        exercise(&FileStorage::open(&path).unwrap());
+
+        // The file backend replays to the same state, before and after compaction
+        let storage = FileStorage::open(&path).unwrap();
+        assert_eq!(storage.get(b"peer/b").unwrap(), Some(b"2".to_vec()));
+        storage.compact().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        let storage = StorageBackend::File(path).open().unwrap();
+        assert_eq!(storage.backend(), "file");
+        assert_eq!(storage.scan_prefix(b"").unwrap().len(), 1);
+
+        #[cfg(not(feature = "storage-sled"))]
+        assert!(matches!(
+            StorageBackend::Sled(dir.path().join("sled")).open(),
+            Err(SecureCommsError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_torn_batch_dropped_and_corruption_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.log");
+        let storage = FileStorage::open(&path).unwrap();
+        storage.put(b"a", b"1").unwrap();
+        let mut batch = WriteBatch::new();
+        batch.put(b"b", b"2").put(b"c", b"3");
+        storage.write_batch(batch).unwrap();
+        drop(storage);
+
+        // A crash mid-write leaves half a batch, which is dropped whole
+        let contents = fs::read_to_string(&path).unwrap();
+        let torn = &contents[..contents.len() - 10];
+        fs::write(&path, torn).unwrap();
+        let storage = FileStorage::open(&path).unwrap();
+        assert_eq!(storage.get(b"a").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(storage.get(b"b").unwrap(), None);
+        assert_eq!(storage.get(b"c").unwrap(), None);
+        drop(storage);
+
+        // A flipped bit in an earlier batch is corruption, not a torn write
+        let first = contents.lines().next().unwrap().replacen("49", "50", 1);
+        fs::write(
+            &path,
+            format!("{}\n{}", first, contents.lines().nth(1).unwrap()),
+        )
+        .unwrap();
+        assert!(matches!(
+            FileStorage::open(&path),
+            Err(SecureCommsError::SystemError(_))
+        ));
+    }
+
+    #[test]
+    fn test_framed_values_detect_corruption() {
+        let framed = frame(b"key", b"value");
+        assert_eq!(unframe(b"key", &framed).unwrap(), b"value");
+
+        let mut flipped = framed.clone();
+        *flipped.last_mut().unwrap() ^= 0x01;
+        assert!(unframe(b"key", &flipped).is_err());
+        // A value copied under another key is refused too
+        assert!(unframe(b"other", &framed).is_err());
+        assert!(unframe(b"key", &framed[..4]).is_err());
+    }
+}
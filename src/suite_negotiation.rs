@@ -0,0 +1,388 @@
+//! # Suite Negotiation - Downgrade-Resistant Algorithm Agreement
+//!
+//! Peers agree on the KEM, AEAD suite and transcript signature mode of an
+//! established channel from the options each offers. An on-path attacker
+//! who strips the strongest options from an offer would otherwise push
+//! both sides onto the weakest common suite without either noticing.
+//!
+//! ## Transcript Binding
+//!
+//! The negotiation transcript covers both endpoints, the initiator's nonce,
+//! both offers exactly as each side saw them, the selected suite and a hash
+//! of the current session key:
+//!
+//! - **New key**: SHA3-256 over the current key and the transcript, so the
+//!   agreed suite is bound into every key derived afterwards
+//! - **Confirmation**: SHA3-256 over the new key and the transcript, sent
+//!   by the responder
+//!
+//! If either offer was altered in flight the two transcripts differ, the
+//! confirmation does not match and the initiator refuses the result with
+//! `SecureCommsError::AuthenticationFailed`. The initiator also recomputes
+//! the selection, so a responder cannot pick a weaker suite than both
+//! sides support.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::suite_negotiation::*;
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let session_key = [7u8; 32];
+//! let hello = SuiteHello::new("alice", "bob", vec![1u8; 32], SuiteOffer::default());
+//! let (accept, at_bob) = accept_suite_offer(&session_key, &hello, &SuiteOffer::default())?;
+//! let (suite, at_alice) = finish_suite_negotiation(&session_key, &hello, &accept)?;
+//! assert_eq!(at_alice.expose(), at_bob.expose());
+//! println!("Agreed on {:?}", suite);
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::constant_time::ct_eq;
+use crate::crypto_protocols::PQCAlgorithm;
+use crate::hw_accel::AeadSuite;
+use crate::hybrid_signature::SignatureMode;
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Options a peer supports, most preferred first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiteOffer {
+    /// ML-KEM parameter sets
+    pub kems: Vec<PQCAlgorithm>,
+    /// Authenticated encryption suites
+    pub aeads: Vec<AeadSuite>,
+    /// Transcript signature modes
+    pub signatures: Vec<SignatureMode>,
+}
+
+impl Default for SuiteOffer {
+    fn default() -> Self {
+        Self {
+            kems: vec![
+                PQCAlgorithm::Kyber1024,
+                PQCAlgorithm::Kyber768,
+                PQCAlgorithm::Kyber512,
+            ],
+            aeads: vec![AeadSuite::Aes256Gcm, AeadSuite::ChaCha20Poly1305],
+            signatures: vec![
+                SignatureMode::Hybrid,
+                SignatureMode::Classical,
+                SignatureMode::None,
+            ],
+        }
+    }
+}
+
+impl SuiteOffer {
+    /// Reject offers that cannot be negotiated
+    pub fn validate(&self) -> Result<()> {
+        if self.kems.is_empty() || self.aeads.is_empty() || self.signatures.is_empty() {
+            return Err(SecureCommsError::Configuration(
+                "Suite offer needs at least one KEM, AEAD suite and signature mode".to_string(),
+            ));
+        }
+        if let Some(kem) = self.kems.iter().find(|kem| {
+            !matches!(
+                kem,
+                PQCAlgorithm::Kyber512 | PQCAlgorithm::Kyber768 | PQCAlgorithm::Kyber1024
+            )
+        }) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Suite offer KEM {:?} is not an ML-KEM parameter set",
+                kem
+            )));
+        }
+        Ok(())
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        digest(
+            b"suite_offer_v1",
+            &[&serde_json::to_vec(self).unwrap_or_default()],
+        )
+    }
+}
+
+/// Suite both peers agreed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegotiatedSuite {
+    /// ML-KEM parameter set
+    pub kem: PQCAlgorithm,
+    /// Authenticated encryption suite
+    pub aead: AeadSuite,
+    /// Minimum transcript signature mode on the channel
+    pub signature: SignatureMode,
+}
+
+/// Pick the initiator's most preferred option of each kind the responder
+/// also supports
+pub fn select_suite(initiator: &SuiteOffer, responder: &SuiteOffer) -> Result<NegotiatedSuite> {
+    fn first_common<T: Copy + PartialEq>(preferred: &[T], supported: &[T]) -> Option<T> {
+        preferred
+            .iter()
+            .copied()
+            .find(|option| supported.contains(option))
+    }
+    let no_common =
+        |kind: &str| SecureCommsError::Validation(format!("No common {} offered", kind));
+    Ok(NegotiatedSuite {
+        kem: first_common(&initiator.kems, &responder.kems).ok_or_else(|| no_common("KEM"))?,
+        aead: first_common(&initiator.aeads, &responder.aeads)
+            .ok_or_else(|| no_common("AEAD suite"))?,
+        signature: first_common(&initiator.signatures, &responder.signatures)
+            .ok_or_else(|| no_common("signature mode"))?,
+    })
+}
+
+/// Initiator's offer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuiteHello {
+    /// Initiating client
+    pub sender_id: String,
+    /// Responding client
+    pub recipient_id: String,
+    /// Fresh random nonce
+    pub nonce: Vec<u8>,
+    /// Initiator's options
+    pub offer: SuiteOffer,
+}
+
+impl SuiteHello {
+    /// Offer from `sender_id` to `recipient_id`
+    pub fn new(sender_id: &str, recipient_id: &str, nonce: Vec<u8>, offer: SuiteOffer) -> Self {
+        Self {
+            sender_id: sender_id.to_string(),
+            recipient_id: recipient_id.to_string(),
+            nonce,
+            offer,
+        }
+    }
+}
+
+/// Responder's selection with key confirmation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuiteAccept {
+    /// Responding client
+    pub sender_id: String,
+    /// Initiating client
+    pub recipient_id: String,
+    /// Nonce of the answered `SuiteHello`
+    pub nonce: Vec<u8>,
+    /// Responder's options
+    pub offer: SuiteOffer,
+    /// Selected suite
+    pub suite: NegotiatedSuite,
+    /// Key confirmation over the new key and transcript
+    pub confirmation: Vec<u8>,
+}
+
+/// Select a suite for a peer's offer and derive the bound session key
+///
+/// Returns the acceptance to deliver to the initiator and the new key.
+pub fn accept_suite_offer(
+    current_key: &[u8],
+    hello: &SuiteHello,
+    local_offer: &SuiteOffer,
+) -> Result<(SuiteAccept, SecretBytes)> {
+    let suite = select_suite(&hello.offer, local_offer)?;
+    let mut accept = SuiteAccept {
+        sender_id: hello.recipient_id.clone(),
+        recipient_id: hello.sender_id.clone(),
+        nonce: hello.nonce.clone(),
+        offer: local_offer.clone(),
+        suite,
+        confirmation: Vec::new(),
+    };
+    let transcript = transcript(current_key, hello, &accept);
+    let next_key = next_session_key(current_key, &transcript);
+    accept.confirmation = confirmation(&next_key, &transcript).to_vec();
+    Ok((accept, next_key))
+}
+
+/// Check the responder's selection against the hello as sent
+///
+/// Fails with `SecureCommsError::AuthenticationFailed` if either offer was
+/// altered in flight or the responder selected a weaker suite than both
+/// offers allow.
+pub fn finish_suite_negotiation(
+    current_key: &[u8],
+    hello: &SuiteHello,
+    accept: &SuiteAccept,
+) -> Result<(NegotiatedSuite, SecretBytes)> {
+    if accept.sender_id != hello.recipient_id
+        || accept.recipient_id != hello.sender_id
+        || accept.nonce != hello.nonce
+    {
+        return Err(SecureCommsError::Validation(
+            "Suite acceptance does not answer this offer".to_string(),
+        ));
+    }
+    let transcript = transcript(current_key, hello, accept);
+    let next_key = next_session_key(current_key, &transcript);
+    if !ct_eq(&confirmation(&next_key, &transcript), &accept.confirmation) {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    if select_suite(&hello.offer, &accept.offer)? != accept.suite {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    Ok((accept.suite, next_key))
+}
+
+/// Transcript hash binding the negotiation to the current session key
+pub fn negotiation_transcript(
+    current_key: &[u8],
+    hello: &SuiteHello,
+    accept: &SuiteAccept,
+) -> Vec<u8> {
+    transcript(current_key, hello, accept).to_vec()
+}
+
+fn transcript(current_key: &[u8], hello: &SuiteHello, accept: &SuiteAccept) -> [u8; 32] {
+    digest(
+        b"suite_negotiation_transcript_v1",
+        &[
+            hello.sender_id.as_bytes(),
+            hello.recipient_id.as_bytes(),
+            &hello.nonce,
+            &hello.offer.digest(),
+            &accept.offer.digest(),
+            &serde_json::to_vec(&accept.suite).unwrap_or_default(),
+            &digest(b"suite_negotiation_current_key_v1", &[current_key]),
+        ],
+    )
+}
+
+fn next_session_key(current_key: &[u8], transcript: &[u8; 32]) -> SecretBytes {
+    SecretBytes::new(
+        SensitiveKind::Key,
+        digest(b"suite_negotiation_session_v1", &[current_key, transcript]).to_vec(),
+    )
+}
+
+fn confirmation(next_key: &SecretBytes, transcript: &[u8; 32]) -> [u8; 32] {
+    digest(
+        b"suite_negotiation_confirm_v1",
+        &[next_key.expose(), transcript],
+    )
+}
+
+fn digest(label: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(label);
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hello(offer: SuiteOffer) -> SuiteHello {
+        SuiteHello::new("alice", "bob", vec![1u8; 32], offer)
+    }
+
+    #[test]
+    fn test_negotiation_agrees_on_strongest_common_suite() {
+        let session_key = [7u8; 32];
+        let bob_offer = SuiteOffer {
+            kems: vec![PQCAlgorithm::Kyber768, PQCAlgorithm::Kyber1024],
+            aeads: vec![AeadSuite::ChaCha20Poly1305],
+            signatures: vec![SignatureMode::Classical, SignatureMode::Hybrid],
+        };
+        let hello = hello(SuiteOffer::default());
+        let (accept, at_bob) = accept_suite_offer(&session_key, &hello, &bob_offer).unwrap();
+        let (suite, at_alice) = finish_suite_negotiation(&session_key, &hello, &accept).unwrap();
+
+        // The initiator's preference order wins among common options
+        assert_eq!(
+            suite,
+            NegotiatedSuite {
+                kem: PQCAlgorithm::Kyber1024,
+                aead: AeadSuite::ChaCha20Poly1305,
+                signature: SignatureMode::Hybrid,
+            }
+        );
+        assert_eq!(at_alice.expose(), at_bob.expose());
+        assert_ne!(at_alice.expose(), &session_key[..]);
+    }
+
+    #[test]
+    fn test_stripped_offer_fails_key_confirmation() {
+        let session_key = [7u8; 32];
+        let sent = hello(SuiteOffer::default());
+
+        // An on-path attacker strips the strong options from the offer
+        let mut stripped = sent.clone();
+        stripped.offer.kems = vec![PQCAlgorithm::Kyber512];
+        stripped.offer.signatures = vec![SignatureMode::None];
+        let (accept, _) =
+            accept_suite_offer(&session_key, &stripped, &SuiteOffer::default()).unwrap();
+        assert_eq!(accept.suite.kem, PQCAlgorithm::Kyber512);
+        assert!(matches!(
+            finish_suite_negotiation(&session_key, &sent, &accept),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // Stripping the responder's offer or rewriting its selection fails too
+        let (mut accept, _) =
+            accept_suite_offer(&session_key, &sent, &SuiteOffer::default()).unwrap();
+        accept.offer.kems = vec![PQCAlgorithm::Kyber512];
+        accept.suite.kem = PQCAlgorithm::Kyber512;
+        assert!(matches!(
+            finish_suite_negotiation(&session_key, &sent, &accept),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        // A responder holding the key still cannot select below the common best
+        let bob_offer = SuiteOffer::default();
+        let mut weak = SuiteAccept {
+            sender_id: "bob".to_string(),
+            recipient_id: "alice".to_string(),
+            nonce: sent.nonce.clone(),
+            offer: bob_offer,
+            suite: NegotiatedSuite {
+                kem: PQCAlgorithm::Kyber512,
+                aead: AeadSuite::ChaCha20Poly1305,
+                signature: SignatureMode::None,
+            },
+            confirmation: Vec::new(),
+        };
+        let transcript = transcript(&session_key, &sent, &weak);
+        weak.confirmation =
+            confirmation(&next_session_key(&session_key, &transcript), &transcript).to_vec();
+        assert!(matches!(
+            finish_suite_negotiation(&session_key, &sent, &weak),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_offers_without_common_options_are_refused() {
+        let only_768 = SuiteOffer {
+            kems: vec![PQCAlgorithm::Kyber768],
+            ..Default::default()
+        };
+        let only_1024 = SuiteOffer {
+            kems: vec![PQCAlgorithm::Kyber1024],
+            ..Default::default()
+        };
+        assert!(matches!(
+            select_suite(&only_768, &only_1024),
+            Err(SecureCommsError::Validation(_))
+        ));
+        assert!(SuiteOffer {
+            kems: vec![PQCAlgorithm::Dilithium3],
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(SuiteOffer::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,314 @@
+//! # Tasks - Supervised Background Tasks
+//!
+//! Owns every long-lived task a client spawns (entanglement pool refill,
+//! the quantum core's garbage collection timer) in one `JoinSet`, so none
+//! of them outlives the client unnoticed. Each task is registered under a
+//! name and its fate is recorded: an error it returns or a panic is kept as
+//! a failure and surfaced in `StreamlinedSecureClient::health_report`.
+//!
+//! ## Shutdown
+//!
+//! `BackgroundTasks::shutdown` cancels the supervisor's token, which stops
+//! each task at its next await point, then joins them within a deadline.
+//! Tasks still running when the deadline passes are aborted and logged by
+//! name; the returned `TaskShutdown` lists which ones finished and which
+//! had to be aborted.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::cancellation::CancellationToken;
+//! use quantum_forge_secure_comms::tasks::BackgroundTasks;
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let mut tasks = BackgroundTasks::new(&CancellationToken::new());
+//! tasks.spawn("heartbeat", async {
+//!     let mut ticker = tokio::time::interval(Duration::from_secs(1));
+//!     loop {
+//!         ticker.tick().await;
+//!     }
+//! });
+//! let summary = tasks.shutdown(Duration::from_secs(5)).await;
+//! assert!(summary.aborted.is_empty());
+//! # }
+//! ```
+
+use crate::cancellation::CancellationToken;
+use crate::logging::{log_warn, LogCategory};
+use crate::Result;
+use futures::FutureExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// Lifecycle of one background task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// Still running
+    Running,
+    /// Returned normally or stopped at shutdown
+    Finished,
+    /// Returned an error
+    Failed {
+        /// Error the task returned
+        error: String,
+    },
+    /// Panicked
+    Panicked {
+        /// Panic message, if it was a string
+        message: String,
+    },
+    /// Did not stop within the shutdown deadline
+    Aborted,
+}
+
+impl TaskStatus {
+    /// Whether the task ended abnormally
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Failed { .. } | TaskStatus::Panicked { .. }
+        )
+    }
+}
+
+/// Record of one background task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskRecord {
+    /// Name the task was spawned under
+    pub name: String,
+    /// Current status
+    pub status: TaskStatus,
+    /// Unix time the task was spawned in milliseconds
+    pub started_at: u64,
+    /// Unix time the task ended in milliseconds
+    pub ended_at: Option<u64>,
+}
+
+/// Outcome of shutting the tasks down
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskShutdown {
+    /// Tasks that stopped within the deadline
+    pub joined: Vec<String>,
+    /// Tasks that were aborted after the deadline
+    pub aborted: Vec<String>,
+}
+
+/// Supervisor of a client's background tasks
+#[derive(Debug)]
+pub struct BackgroundTasks {
+    set: JoinSet<()>,
+    records: Arc<Mutex<BTreeMap<String, TaskRecord>>>,
+    token: CancellationToken,
+}
+
+impl BackgroundTasks {
+    /// Supervisor whose tasks stop when `parent` is cancelled
+    pub fn new(parent: &CancellationToken) -> Self {
+        Self {
+            set: JoinSet::new(),
+            records: Arc::new(Mutex::new(BTreeMap::new())),
+            token: parent.child_token(),
+        }
+    }
+
+    /// Spawn a task under `name`, replacing the record of an earlier one
+    ///
+    /// The task is dropped at its next await point once the supervisor is
+    /// cancelled; an error it returns or a panic is recorded as a failure.
+    pub fn spawn<F>(&mut self, name: &str, task: F)
+    where
+        F: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.records.lock().insert(
+            name.to_string(),
+            TaskRecord {
+                name: name.to_string(),
+                status: TaskStatus::Running,
+                started_at: crate::expiry::now_ms(),
+                ended_at: None,
+            },
+        );
+        let records = self.records.clone();
+        let token = self.token.clone();
+        let name = name.to_string();
+        self.set.spawn(async move {
+            let status = tokio::select! {
+                _ = token.cancelled() => TaskStatus::Finished,
+                outcome = AssertUnwindSafe(task).catch_unwind() => match outcome {
+                    Ok(Ok(())) => TaskStatus::Finished,
+                    Ok(Err(e)) => TaskStatus::Failed { error: e.to_string() },
+                    Err(panic) => TaskStatus::Panicked {
+                        message: panic
+                            .downcast_ref::<&str>()
+                            .map(|message| message.to_string())
+                            .or_else(|| panic.downcast_ref::<String>().cloned())
+                            .unwrap_or_default(),
+                    },
+                },
+            };
+            if status.is_failure() {
+                log_warn(
+                    LogCategory::System,
+                    &format!("Background task {} ended: {:?}", name, status),
+                );
+            }
+            finish(&records, &name, status);
+        });
+    }
+
+    /// Record of a task, by name
+    pub fn record(&self, name: &str) -> Option<TaskRecord> {
+        self.records.lock().get(name).cloned()
+    }
+
+    /// Records of every task, ordered by name
+    pub fn records(&self) -> Vec<TaskRecord> {
+        self.records.lock().values().cloned().collect()
+    }
+
+    /// Names of the tasks still running
+    pub fn running(&self) -> Vec<String> {
+        self.with_status(|status| *status == TaskStatus::Running)
+    }
+
+    /// Tasks that failed or panicked
+    pub fn failures(&self) -> Vec<TaskRecord> {
+        self.records()
+            .into_iter()
+            .filter(|record| record.status.is_failure())
+            .collect()
+    }
+
+    /// Stop every task, aborting those that outlast `deadline`
+    pub async fn shutdown(&mut self, deadline: Duration) -> TaskShutdown {
+        let running = self.running();
+        self.token.cancel();
+        let set = &mut self.set;
+        let drained =
+            tokio::time::timeout(deadline, async { while set.join_next().await.is_some() {} })
+                .await;
+
+        let mut aborted = Vec::new();
+        if drained.is_err() {
+            aborted = self.running();
+            self.set.abort_all();
+            while self.set.join_next().await.is_some() {}
+            for name in &aborted {
+                log_warn(
+                    LogCategory::System,
+                    &format!(
+                        "Background task {} aborted after {}ms shutdown deadline",
+                        name,
+                        deadline.as_millis()
+                    ),
+                );
+                finish(&self.records, name, TaskStatus::Aborted);
+            }
+        }
+        TaskShutdown {
+            joined: running
+                .into_iter()
+                .filter(|name| !aborted.contains(name))
+                .collect(),
+            aborted,
+        }
+    }
+
+    fn with_status(&self, keep: impl Fn(&TaskStatus) -> bool) -> Vec<String> {
+        self.records
+            .lock()
+            .values()
+            .filter(|record| keep(&record.status))
+            .map(|record| record.name.clone())
+            .collect()
+    }
+}
+
+fn finish(records: &Mutex<BTreeMap<String, TaskRecord>>, name: &str, status: TaskStatus) {
+    if let Some(record) = records.lock().get_mut(name) {
+        record.status = status;
+        record.ended_at = Some(crate::expiry::now_ms());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SecureCommsError;
+
+    #[tokio::test]
+    async fn test_failures_and_panics_are_recorded() {
+        let mut tasks = BackgroundTasks::new(&CancellationToken::new());
+        tasks.spawn("ok", async { Ok(()) });
+        tasks.spawn("failing", async {
+            Err(SecureCommsError::QuantumOperation(
+                "refill failed".to_string(),
+            ))
+        });
+        tasks.spawn("panicking", async { panic!("gc timer exploded") });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(tasks.record("ok").unwrap().status, TaskStatus::Finished);
+        let failures = tasks.failures();
+        assert_eq!(failures.len(), 2);
+        assert!(matches!(
+            &failures[0].status,
+            TaskStatus::Failed { error } if error.contains("refill failed")
+        ));
+        assert_eq!(
+            failures[1].status,
+            TaskStatus::Panicked {
+                message: "gc timer exploded".to_string()
+            }
+        );
+        assert!(failures.iter().all(|record| record.ended_at.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_joins_cooperative_tasks() {
+        let parent = CancellationToken::new();
+        let mut tasks = BackgroundTasks::new(&parent);
+        for name in ["refill", "gc_timer"] {
+            tasks.spawn(name, async {
+                let mut ticker = tokio::time::interval(Duration::from_millis(5));
+                loop {
+                    ticker.tick().await;
+                }
+            });
+        }
+        assert_eq!(tasks.running(), vec!["gc_timer", "refill"]);
+
+        let summary = tasks.shutdown(Duration::from_secs(5)).await;
+        assert_eq!(summary.joined, vec!["gc_timer", "refill"]);
+        assert!(summary.aborted.is_empty());
+        assert!(tasks.running().is_empty());
+        // Stopping at shutdown is not a failure
+        assert!(tasks.failures().is_empty());
+        assert!(!parent.is_cancelled());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_stuck_task_is_aborted_at_deadline() {
+        let mut tasks = BackgroundTasks::new(&CancellationToken::new());
+        tasks.spawn("cooperative", std::future::pending());
+        // Never reaches an await point, so cancellation cannot stop it
+        tasks.spawn("stuck", async {
+            tokio::task::block_in_place(|| std::thread::sleep(Duration::from_millis(200)));
+            Ok(())
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let summary = tasks.shutdown(Duration::from_millis(20)).await;
+        assert_eq!(summary.joined, vec!["cooperative"]);
+        assert_eq!(summary.aborted, vec!["stuck"]);
+        assert_eq!(tasks.record("stuck").unwrap().status, TaskStatus::Aborted);
+    }
+}
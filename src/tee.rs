@@ -0,0 +1,550 @@
+//! # TEE - Trusted Execution Environment Backends and Attestation
+//!
+//! Key operations that must not expose private keys to the host - ML-KEM
+//! decapsulation and signing - run behind the `TeeBackend` trait. Hardware
+//! enclaves (SGX, SEV, TrustZone) implement it outside this crate; when none
+//! is available the `SoftwareTee` fallback keeps keys in process memory.
+//!
+//! ## Key Handles
+//!
+//! - **Generate**: Keys are created inside the backend and never leave it;
+//!   callers get a `TeeKeyHandle` with the key id and public key
+//! - **Use**: `decapsulate` and `sign` take the key id
+//! - **Destroy**: `destroy_key` wipes the key inside the backend
+//!
+//! ## Attestation
+//!
+//! `attest` returns `AttestationEvidence`: the backend kind, its code
+//! measurement, the verifier's nonce and caller-chosen report data, signed
+//! by the backend's attestation key. Peers exchange evidence through the
+//! `AttestationRequest`/`AttestationResponse` network messages and check it
+//! with `verify_evidence` against an `AttestationPolicy`. Checking a hardware
+//! attestation key against the vendor's certificate chain is the backend
+//! integration's job; the policy can additionally pin attestation keys and
+//! measurements.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::crypto_protocols::PQCAlgorithm;
+//! use quantum_forge_secure_comms::tee::{
+//!     verify_evidence, AttestationPolicy, SoftwareTee, TeeBackend,
+//! };
+//!
+//! # fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let tee = SoftwareTee::new();
+//! let handle = tee.generate_kem_key("session", PQCAlgorithm::Kyber768)?;
+//! println!("KEM public key: {} bytes", handle.public_key.len());
+//!
+//! let evidence = tee.attest(b"verifier nonce", b"report data")?;
+//! verify_evidence(
+//!     &evidence,
+//!     b"verifier nonce",
+//!     Some(b"report data".as_slice()),
+//!     &AttestationPolicy::default(),
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::constant_time::ct_eq;
+use crate::crypto_protocols::{PQCAlgorithm, PQC, QRNG};
+use crate::secret_memory::{SecretBytes, SensitiveKind};
+use crate::{Result, SecureCommsError};
+
+/// Execution environment a backend runs key operations in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TeeKind {
+    /// Intel SGX enclave
+    Sgx,
+    /// AMD SEV confidential VM
+    Sev,
+    /// Arm TrustZone secure world
+    TrustZone,
+    /// Process memory; no hardware isolation
+    Software,
+}
+
+impl TeeKind {
+    /// Whether keys are isolated from the host by hardware
+    pub fn is_hardware(&self) -> bool {
+        !matches!(self, TeeKind::Software)
+    }
+}
+
+/// What a backend key is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TeeKeyAlgorithm {
+    /// ML-KEM key pair for decapsulation
+    Kem(PQCAlgorithm),
+    /// Ed25519 signing key
+    Ed25519,
+}
+
+/// Reference to a key held inside a backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeeKeyHandle {
+    /// Identifier used for later operations
+    pub key_id: String,
+    /// Key algorithm
+    pub algorithm: TeeKeyAlgorithm,
+    /// Public half of the key
+    pub public_key: Vec<u8>,
+}
+
+/// Signed statement about a backend, bound to a verifier nonce
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationEvidence {
+    /// Environment that produced the evidence
+    pub tee: TeeKind,
+    /// Hash of the code running in the environment
+    pub measurement: Vec<u8>,
+    /// Verifier nonce the evidence answers
+    pub nonce: Vec<u8>,
+    /// Caller data bound into the evidence, e.g. an identity key hash
+    pub report_data: Vec<u8>,
+    /// Unix time the evidence was produced (seconds)
+    pub timestamp: u64,
+    /// Ed25519 key that signed the evidence
+    pub attestation_key: Vec<u8>,
+    /// Signature over `signed_payload`
+    pub signature: Vec<u8>,
+}
+
+impl AttestationEvidence {
+    /// Bytes covered by the signature
+    pub fn signed_payload(&self) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"tee_attestation_v1");
+        hasher.update(serde_json::to_vec(&self.tee).unwrap_or_default());
+        for field in [&self.measurement, &self.nonce, &self.report_data] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        }
+        hasher.update(self.timestamp.to_be_bytes());
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Requirements on a peer's attestation evidence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationPolicy {
+    /// Accept evidence from the software fallback
+    pub allow_software: bool,
+    /// Accepted measurements; empty accepts any
+    pub trusted_measurements: Vec<Vec<u8>>,
+    /// Accepted attestation keys; empty accepts any
+    pub trusted_attestation_keys: Vec<Vec<u8>>,
+    /// Oldest evidence accepted, in seconds
+    pub max_age_seconds: u64,
+}
+
+impl Default for AttestationPolicy {
+    fn default() -> Self {
+        Self {
+            allow_software: true,
+            trusted_measurements: Vec::new(),
+            trusted_attestation_keys: Vec::new(),
+            max_age_seconds: 300,
+        }
+    }
+}
+
+/// Client settings for TEE key operations and attestation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TeeConfig {
+    /// Refuse to attest from the software fallback
+    pub require_hardware: bool,
+    /// Requirements on peers' evidence
+    pub policy: AttestationPolicy,
+    /// Require peers to attest a verified startup integrity check
+    #[serde(default)]
+    pub require_peer_integrity: bool,
+}
+
+/// Key operations isolated in a trusted execution environment
+///
+/// Implementations must keep private keys inside the environment; only
+/// public keys, shared secrets, signatures and evidence leave it.
+pub trait TeeBackend: Send + Sync {
+    /// Environment kind
+    fn kind(&self) -> TeeKind;
+
+    /// Whether the environment can be used on this machine
+    fn is_available(&self) -> bool;
+
+    /// Hash of the code running in the environment
+    fn measurement(&self) -> Vec<u8>;
+
+    /// Generate an ML-KEM key pair inside the environment
+    fn generate_kem_key(&self, key_id: &str, algorithm: PQCAlgorithm) -> Result<TeeKeyHandle>;
+
+    /// Generate an Ed25519 signing key inside the environment
+    fn generate_signing_key(&self, key_id: &str) -> Result<TeeKeyHandle>;
+
+    /// Recover the shared secret of a ciphertext for a KEM key
+    fn decapsulate(&self, key_id: &str, ciphertext: &[u8]) -> Result<SecretBytes>;
+
+    /// Sign data with a signing key
+    fn sign(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>>;
+
+    /// Produce evidence binding `nonce` and `report_data` to the environment
+    fn attest(&self, nonce: &[u8], report_data: &[u8]) -> Result<AttestationEvidence>;
+
+    /// Wipe a key, returning whether it existed
+    fn destroy_key(&self, key_id: &str) -> bool;
+}
+
+enum SoftwareKey {
+    Kem {
+        algorithm: PQCAlgorithm,
+        private_key: SecretBytes,
+    },
+    Signing(SigningKey),
+}
+
+/// Fallback backend keeping keys in process memory
+///
+/// Offers the same interface as hardware backends but no isolation from the
+/// host; its evidence is self-signed with a per-instance attestation key and
+/// reports `TeeKind::Software`.
+pub struct SoftwareTee {
+    attestation_key: SigningKey,
+    keys: Mutex<HashMap<String, SoftwareKey>>,
+}
+
+impl SoftwareTee {
+    /// Create a backend with a fresh attestation key
+    pub fn new() -> Self {
+        Self {
+            attestation_key: SigningKey::from_bytes(&rand::random::<[u8; 32]>()),
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn insert(&self, key_id: &str, key: SoftwareKey) -> Result<()> {
+        let mut keys = self.keys.lock();
+        if keys.contains_key(key_id) {
+            return Err(SecureCommsError::Validation(format!(
+                "TEE key '{}' already exists",
+                key_id
+            )));
+        }
+        keys.insert(key_id.to_string(), key);
+        Ok(())
+    }
+}
+
+impl Default for SoftwareTee {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SoftwareTee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftwareTee")
+            .field("keys", &self.keys.lock().len())
+            .finish()
+    }
+}
+
+fn unknown_key(key_id: &str) -> SecureCommsError {
+    SecureCommsError::Validation(format!("Unknown TEE key '{}'", key_id))
+}
+
+impl TeeBackend for SoftwareTee {
+    fn kind(&self) -> TeeKind {
+        TeeKind::Software
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn measurement(&self) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"software_tee_v1");
+        hasher.update(env!("CARGO_PKG_NAME").as_bytes());
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    fn generate_kem_key(&self, key_id: &str, algorithm: PQCAlgorithm) -> Result<TeeKeyHandle> {
+        let mut pqc = PQC::new(algorithm, QRNG::from_seed(rand::random()));
+        let keypair = pqc.generate_keypair()?;
+        self.insert(
+            key_id,
+            SoftwareKey::Kem {
+                algorithm,
+                private_key: SecretBytes::new(SensitiveKind::Key, keypair.private_key),
+            },
+        )?;
+        Ok(TeeKeyHandle {
+            key_id: key_id.to_string(),
+            algorithm: TeeKeyAlgorithm::Kem(algorithm),
+            public_key: keypair.public_key,
+        })
+    }
+
+    fn generate_signing_key(&self, key_id: &str) -> Result<TeeKeyHandle> {
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let public_key = key.verifying_key().to_bytes().to_vec();
+        self.insert(key_id, SoftwareKey::Signing(key))?;
+        Ok(TeeKeyHandle {
+            key_id: key_id.to_string(),
+            algorithm: TeeKeyAlgorithm::Ed25519,
+            public_key,
+        })
+    }
+
+    fn decapsulate(&self, key_id: &str, ciphertext: &[u8]) -> Result<SecretBytes> {
+        let keys = self.keys.lock();
+        match keys.get(key_id) {
+            Some(SoftwareKey::Kem {
+                algorithm,
+                private_key,
+            }) => {
+                let mut pqc = PQC::new(*algorithm, QRNG::from_seed(rand::random()));
+                let shared = pqc.decapsulate(private_key.expose(), ciphertext)?;
+                Ok(SecretBytes::new(SensitiveKind::Key, shared))
+            }
+            Some(SoftwareKey::Signing(_)) => Err(SecureCommsError::Validation(format!(
+                "TEE key '{}' is not a KEM key",
+                key_id
+            ))),
+            None => Err(unknown_key(key_id)),
+        }
+    }
+
+    fn sign(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let keys = self.keys.lock();
+        match keys.get(key_id) {
+            Some(SoftwareKey::Signing(key)) => Ok(key.sign(data).to_bytes().to_vec()),
+            Some(SoftwareKey::Kem { .. }) => Err(SecureCommsError::Validation(format!(
+                "TEE key '{}' is not a signing key",
+                key_id
+            ))),
+            None => Err(unknown_key(key_id)),
+        }
+    }
+
+    fn attest(&self, nonce: &[u8], report_data: &[u8]) -> Result<AttestationEvidence> {
+        let mut evidence = AttestationEvidence {
+            tee: self.kind(),
+            measurement: self.measurement(),
+            nonce: nonce.to_vec(),
+            report_data: report_data.to_vec(),
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            attestation_key: self.attestation_key.verifying_key().to_bytes().to_vec(),
+            signature: Vec::new(),
+        };
+        evidence.signature = self
+            .attestation_key
+            .sign(&evidence.signed_payload())
+            .to_bytes()
+            .to_vec();
+        Ok(evidence)
+    }
+
+    fn destroy_key(&self, key_id: &str) -> bool {
+        // Dropping the entry zeroizes KEM keys; SigningKey zeroizes on drop
+        self.keys.lock().remove(key_id).is_some()
+    }
+}
+
+/// First available backend, or the software fallback when none is
+pub fn select_backend(candidates: Vec<Arc<dyn TeeBackend>>) -> Arc<dyn TeeBackend> {
+    candidates
+        .into_iter()
+        .find(|backend| backend.is_available())
+        .unwrap_or_else(|| Arc::new(SoftwareTee::new()))
+}
+
+/// Check evidence against the expected nonce, report data and policy
+///
+/// Fails with `SecureCommsError::AuthenticationFailed` when the evidence is
+/// forged, stale, answers another nonce or binds other report data, and
+/// with `SecureCommsError::Validation` when it is genuine but the policy
+/// rejects the environment.
+pub fn verify_evidence(
+    evidence: &AttestationEvidence,
+    expected_nonce: &[u8],
+    expected_report_data: Option<&[u8]>,
+    policy: &AttestationPolicy,
+) -> Result<()> {
+    let key_bytes: [u8; 32] = evidence
+        .attestation_key
+        .as_slice()
+        .try_into()
+        .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+    let signature_bytes: [u8; 64] = evidence
+        .signature
+        .as_slice()
+        .try_into()
+        .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+    let key =
+        VerifyingKey::from_bytes(&key_bytes).map_err(|_| SecureCommsError::AuthenticationFailed)?;
+    key.verify(
+        &evidence.signed_payload(),
+        &Signature::from_bytes(&signature_bytes),
+    )
+    .map_err(|_| SecureCommsError::AuthenticationFailed)?;
+
+    if !ct_eq(&evidence.nonce, expected_nonce) {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+    if let Some(report_data) = expected_report_data {
+        if !ct_eq(&evidence.report_data, report_data) {
+            return Err(SecureCommsError::AuthenticationFailed);
+        }
+    }
+    let now = chrono::Utc::now().timestamp() as u64;
+    if now.saturating_sub(evidence.timestamp) > policy.max_age_seconds {
+        return Err(SecureCommsError::AuthenticationFailed);
+    }
+
+    if !evidence.tee.is_hardware() && !policy.allow_software {
+        return Err(SecureCommsError::Validation(
+            "Peer attests from the software fallback".to_string(),
+        ));
+    }
+    if !policy.trusted_measurements.is_empty()
+        && !policy.trusted_measurements.contains(&evidence.measurement)
+    {
+        return Err(SecureCommsError::Validation(
+            "Peer measurement is not trusted".to_string(),
+        ));
+    }
+    if !policy.trusted_attestation_keys.is_empty()
+        && !policy
+            .trusted_attestation_keys
+            .contains(&evidence.attestation_key)
+    {
+        return Err(SecureCommsError::Validation(
+            "Peer attestation key is not trusted".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AbsentEnclave;
+
+    impl TeeBackend for AbsentEnclave {
+        fn kind(&self) -> TeeKind {
+            TeeKind::Sgx
+        }
+        fn is_available(&self) -> bool {
+            false
+        }
+        fn measurement(&self) -> Vec<u8> {
+            Vec::new()
+        }
+        fn generate_kem_key(&self, _: &str, _: PQCAlgorithm) -> Result<TeeKeyHandle> {
+            Err(SecureCommsError::SystemError("no enclave".to_string()))
+        }
+        fn generate_signing_key(&self, _: &str) -> Result<TeeKeyHandle> {
+            Err(SecureCommsError::SystemError("no enclave".to_string()))
+        }
+        fn decapsulate(&self, _: &str, _: &[u8]) -> Result<SecretBytes> {
+            Err(SecureCommsError::SystemError("no enclave".to_string()))
+        }
+        fn sign(&self, _: &str, _: &[u8]) -> Result<Vec<u8>> {
+            Err(SecureCommsError::SystemError("no enclave".to_string()))
+        }
+        fn attest(&self, _: &[u8], _: &[u8]) -> Result<AttestationEvidence> {
+            Err(SecureCommsError::SystemError("no enclave".to_string()))
+        }
+        fn destroy_key(&self, _: &str) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_software_tee_key_operations() {
+        let tee = SoftwareTee::new();
+        let kem = tee.generate_kem_key("kem", PQCAlgorithm::Kyber768).unwrap();
+        assert!(tee.generate_kem_key("kem", PQCAlgorithm::Kyber768).is_err());
+
+        let mut pqc = PQC::new(PQCAlgorithm::Kyber768, QRNG::from_seed([1u8; 32]));
+        let (ciphertext, shared) = pqc.encapsulate(&kem.public_key).unwrap();
+        let recovered = tee.decapsulate("kem", &ciphertext).unwrap();
+        assert_eq!(recovered.expose(), shared.as_slice());
+
+        let signer = tee.generate_signing_key("sig").unwrap();
+        let signature = tee.sign("sig", b"payload").unwrap();
+        let key = VerifyingKey::from_bytes(&signer.public_key.clone().try_into().unwrap()).unwrap();
+        assert!(key
+            .verify(
+                b"payload",
+                &Signature::from_bytes(&signature.try_into().unwrap())
+            )
+            .is_ok());
+
+        assert!(tee.sign("kem", b"payload").is_err());
+        assert!(tee.decapsulate("sig", &ciphertext).is_err());
+        assert!(tee.destroy_key("kem"));
+        assert!(tee.decapsulate("kem", &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_evidence_verification() {
+        let tee = SoftwareTee::new();
+        let evidence = tee.attest(b"nonce", b"report").unwrap();
+        let policy = AttestationPolicy::default();
+        assert!(verify_evidence(&evidence, b"nonce", Some(b"report".as_slice()), &policy).is_ok());
+        assert!(verify_evidence(&evidence, b"nonce", None, &policy).is_ok());
+
+        assert!(matches!(
+            verify_evidence(&evidence, b"other", None, &policy),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        assert!(matches!(
+            verify_evidence(&evidence, b"nonce", Some(b"other".as_slice()), &policy),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+        let mut forged = evidence.clone();
+        forged.tee = TeeKind::Sgx;
+        assert!(matches!(
+            verify_evidence(&forged, b"nonce", None, &policy),
+            Err(SecureCommsError::AuthenticationFailed)
+        ));
+
+        let strict = AttestationPolicy {
+            allow_software: false,
+            ..Default::default()
+        };
+        assert!(matches!(
+            verify_evidence(&evidence, b"nonce", None, &strict),
+            Err(SecureCommsError::Validation(_))
+        ));
+        let pinned = AttestationPolicy {
+            trusted_measurements: vec![vec![0u8; 32]],
+            ..Default::default()
+        };
+        assert!(verify_evidence(&evidence, b"nonce", None, &pinned).is_err());
+    }
+
+    #[test]
+    fn test_select_backend_falls_back_to_software() {
+        let absent: Arc<dyn TeeBackend> = Arc::new(AbsentEnclave);
+        let backend = select_backend(vec![absent.clone()]);
+        assert_eq!(backend.kind(), TeeKind::Software);
+        assert!(backend.is_available());
+
+        let software: Arc<dyn TeeBackend> = Arc::new(SoftwareTee::new());
+        let backend = select_backend(vec![absent, software.clone()]);
+        assert!(Arc::ptr_eq(&backend, &software));
+    }
+}
@@ -0,0 +1,431 @@
+//! # Multi-Tenancy - Isolated Tenants in One Process
+//!
+//! `ClientManager` hosts several tenants side by side. Each tenant owns its
+//! own `StreamlinedSecureClient`, key store, peer set, metrics namespace and
+//! resource quota, and every operation goes through the owning `Tenant`, so
+//! one tenant has no path to another tenant's keys or channels.
+//!
+//! ## Isolation
+//!
+//! - **Key Stores**: Each tenant has its own `KeyManager`; key IDs only resolve within it
+//! - **Channels**: Each tenant has its own client, channels and crypto state
+//! - **Peer Sets**: Optional allow-list of peers a tenant may open channels to
+//! - **Metrics**: Statistics are reported under `tenant.<id>.` keys
+//! - **Quotas**: Channel, key and traffic limits enforced per tenant
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::tenancy::{ClientManager, TenantConfig};
+//!
+//! # async fn run() -> quantum_forge_secure_comms::Result<()> {
+//! let mut manager = ClientManager::new();
+//! manager.create_tenant(TenantConfig::new("acme", 9101)).await?;
+//! manager.create_tenant(TenantConfig::new("globex", 9102)).await?;
+//!
+//! let acme = manager.tenant_mut("acme").unwrap();
+//! acme.establish_channel("acme_peer").await?;
+//! acme.send_message("acme_peer", b"hello").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::key_manager::{KeyInfo, KeyManager, KeyPurpose};
+use crate::streamlined_client::{
+    SecureChannel, SecureMessage, StreamlinedConfig, StreamlinedSecureClient,
+};
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Resource limits for one tenant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantQuota {
+    /// Maximum open channels
+    pub max_channels: usize,
+    /// Maximum keys in the tenant key store
+    pub max_keys: usize,
+    /// Maximum size of a single message in bytes
+    pub max_message_bytes: usize,
+    /// Maximum total bytes sent over the tenant's lifetime
+    pub max_total_bytes: u64,
+}
+
+impl Default for TenantQuota {
+    fn default() -> Self {
+        Self {
+            max_channels: 32,
+            max_keys: 64,
+            max_message_bytes: 1024 * 1024,
+            max_total_bytes: 10 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// Tenant configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// Tenant identifier
+    pub tenant_id: String,
+    /// Client configuration; the client ID defaults to one derived from the tenant
+    pub client: StreamlinedConfig,
+    /// Resource limits
+    pub quota: TenantQuota,
+    /// Peers the tenant may open channels to; `None` allows any peer
+    pub allowed_peers: Option<Vec<String>>,
+}
+
+impl TenantConfig {
+    /// Tenant with default client settings bound to `bind_port`
+    pub fn new(tenant_id: &str, bind_port: u16) -> Self {
+        Self {
+            tenant_id: tenant_id.to_string(),
+            client: StreamlinedConfig {
+                bind_port,
+                ..StreamlinedConfig::default()
+            },
+            quota: TenantQuota::default(),
+            allowed_peers: None,
+        }
+    }
+}
+
+/// Resources consumed by a tenant
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TenantUsage {
+    /// Messages sent
+    pub messages_sent: u64,
+    /// Payload bytes sent
+    pub bytes_sent: u64,
+}
+
+/// An isolated tenant with its own client and key store
+pub struct Tenant {
+    tenant_id: String,
+    client: StreamlinedSecureClient,
+    keys: KeyManager,
+    allowed_peers: Option<HashSet<String>>,
+    quota: TenantQuota,
+    usage: TenantUsage,
+}
+
+impl Tenant {
+    /// Tenant identifier
+    pub fn tenant_id(&self) -> &str {
+        &self.tenant_id
+    }
+
+    /// Tenant's client, read-only
+    pub fn client(&self) -> &StreamlinedSecureClient {
+        &self.client
+    }
+
+    /// Tenant's key store, read-only
+    pub fn keys(&self) -> &KeyManager {
+        &self.keys
+    }
+
+    /// Resource limits
+    pub fn quota(&self) -> &TenantQuota {
+        &self.quota
+    }
+
+    /// Resources consumed so far
+    pub fn usage(&self) -> &TenantUsage {
+        &self.usage
+    }
+
+    /// Whether the tenant may talk to `peer_id`
+    pub fn is_peer_allowed(&self, peer_id: &str) -> bool {
+        self.allowed_peers.is_none()
+            || self
+                .allowed_peers
+                .as_ref()
+                .map(|peers| peers.contains(peer_id))
+                .unwrap_or(false)
+    }
+
+    /// Establish a channel within the tenant's peer set and channel quota
+    pub async fn establish_channel(&mut self, peer_id: &str) -> Result<SecureChannel> {
+        if !self.is_peer_allowed(peer_id) {
+            return Err(SecureCommsError::Security(format!(
+                "Peer {} is outside the peer set of tenant {}",
+                peer_id, self.tenant_id
+            )));
+        }
+        let open = self.client.list_secure_channels().len();
+        if self.client.get_secure_channel(peer_id).is_none() && open >= self.quota.max_channels {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Tenant {} reached its limit of {} channels",
+                self.tenant_id, self.quota.max_channels
+            )));
+        }
+        self.client.establish_secure_channel(peer_id).await
+    }
+
+    /// Send a message over one of the tenant's own channels
+    pub async fn send_message(&mut self, peer_id: &str, data: &[u8]) -> Result<SecureMessage> {
+        if data.len() > self.quota.max_message_bytes {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Message of {} bytes exceeds tenant {} limit of {} bytes",
+                data.len(),
+                self.tenant_id,
+                self.quota.max_message_bytes
+            )));
+        }
+        if self.usage.bytes_sent + data.len() as u64 > self.quota.max_total_bytes {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Tenant {} traffic quota of {} bytes exhausted",
+                self.tenant_id, self.quota.max_total_bytes
+            )));
+        }
+
+        let message = self.client.send_secure_message(peer_id, data).await?;
+        self.usage.messages_sent += 1;
+        self.usage.bytes_sent += data.len() as u64;
+        Ok(message)
+    }
+
+    /// Generate a key in the tenant's key store from the tenant client's QRNG
+    pub fn generate_key(&mut self, key_id: &str, purpose: KeyPurpose) -> Result<KeyInfo> {
+        self.check_key_quota()?;
+        self.keys.generate_key(key_id, purpose, self.client.qrng())
+    }
+
+    /// Import a key into the tenant's key store
+    pub fn import_key(&mut self, key_id: &str, purpose: KeyPurpose, seed: [u8; 32]) -> Result<KeyInfo> {
+        self.check_key_quota()?;
+        self.keys.import_key(key_id, purpose, seed)
+    }
+
+    /// Remove a key from the tenant's key store
+    pub fn remove_key(&mut self, key_id: &str) -> bool {
+        self.keys.remove_key(key_id)
+    }
+
+    /// Sign with one of the tenant's signing keys
+    pub fn sign(&self, key_id: &str, message: &[u8]) -> Result<Vec<u8>> {
+        self.keys.sign(key_id, message)
+    }
+
+    fn check_key_quota(&self) -> Result<()> {
+        if self.keys.key_ids().len() >= self.quota.max_keys {
+            return Err(SecureCommsError::ResourceExhausted(format!(
+                "Tenant {} reached its limit of {} keys",
+                self.tenant_id, self.quota.max_keys
+            )));
+        }
+        Ok(())
+    }
+
+    /// Tenant statistics under the `tenant.<id>.` namespace
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let prefix = format!("tenant.{}", self.tenant_id);
+        let mut stats = HashMap::new();
+        let mut insert = |name: &str, value: serde_json::Value| {
+            stats.insert(format!("{}.{}", prefix, name), value);
+        };
+
+        insert(
+            "channels",
+            serde_json::Value::Number(self.client.list_secure_channels().len().into()),
+        );
+        insert(
+            "keys",
+            serde_json::Value::Number(self.keys.key_ids().len().into()),
+        );
+        insert(
+            "messages_sent",
+            serde_json::Value::Number(self.usage.messages_sent.into()),
+        );
+        insert(
+            "bytes_sent",
+            serde_json::Value::Number(self.usage.bytes_sent.into()),
+        );
+        insert(
+            "setup_time_ms",
+            serde_json::Value::Number(self.client.get_performance_metrics().total_setup_ms.into()),
+        );
+        stats
+    }
+}
+
+/// Hosts isolated tenants in one process
+#[derive(Default)]
+pub struct ClientManager {
+    tenants: HashMap<String, Tenant>,
+}
+
+impl ClientManager {
+    /// Create manager without tenants
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tenant with its own client and key store
+    pub async fn create_tenant(&mut self, config: TenantConfig) -> Result<()> {
+        if config.tenant_id.is_empty() || config.tenant_id.contains('.') {
+            return Err(SecureCommsError::Configuration(format!(
+                "Invalid tenant ID {:?}",
+                config.tenant_id
+            )));
+        }
+        if self.tenants.contains_key(&config.tenant_id) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Tenant {} already exists",
+                config.tenant_id
+            )));
+        }
+
+        let mut client_config = config.client;
+        let client_id = client_config.client_id.clone().unwrap_or_else(|| {
+            format!("{}-{}", config.tenant_id, uuid::Uuid::new_v4().simple())
+        });
+        if let Some(other) = self.tenants.values().find(|tenant| {
+            let existing = tenant.client.get_config();
+            tenant.client.get_client_id() == client_id
+                || (existing.bind_address == client_config.bind_address
+                    && existing.bind_port == client_config.bind_port)
+        }) {
+            return Err(SecureCommsError::Configuration(format!(
+                "Tenant {} would share a client identity or listener with tenant {}",
+                config.tenant_id, other.tenant_id
+            )));
+        }
+        client_config.client_id = Some(client_id);
+        // Governor quotas are charged to this tenant unless configured otherwise
+        client_config
+            .quotas
+            .tenant_id
+            .get_or_insert_with(|| config.tenant_id.clone());
+
+        let client = StreamlinedSecureClient::with_config(client_config).await?;
+        println!("🏢 Tenant {} ready", config.tenant_id);
+
+        self.tenants.insert(
+            config.tenant_id.clone(),
+            Tenant {
+                tenant_id: config.tenant_id,
+                client,
+                keys: KeyManager::new(),
+                allowed_peers: config
+                    .allowed_peers
+                    .map(|peers| peers.into_iter().collect()),
+                quota: config.quota,
+                usage: TenantUsage::default(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Look up a tenant
+    pub fn tenant(&self, tenant_id: &str) -> Option<&Tenant> {
+        self.tenants.get(tenant_id)
+    }
+
+    /// Look up a tenant for operations
+    pub fn tenant_mut(&mut self, tenant_id: &str) -> Option<&mut Tenant> {
+        self.tenants.get_mut(tenant_id)
+    }
+
+    /// Shut down and remove a tenant; its keys are zeroized on drop
+    pub async fn remove_tenant(&mut self, tenant_id: &str) -> Result<bool> {
+        match self.tenants.remove(tenant_id) {
+            Some(mut tenant) => {
+                tenant.client.shutdown().await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Tenant IDs, sorted
+    pub fn tenant_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.tenants.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Statistics of all tenants, each under its own namespace
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "tenants".to_string(),
+            serde_json::Value::Number(self.tenants.len().into()),
+        );
+        for tenant in self.tenants.values() {
+            stats.extend(tenant.get_stats());
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn manager() -> ClientManager {
+        let mut manager = ClientManager::new();
+        let mut acme = TenantConfig::new("acme", 9301);
+        acme.quota.max_channels = 1;
+        acme.allowed_peers = Some(vec!["acme_peer".to_string(), "acme_backup".to_string()]);
+        manager.create_tenant(acme).await.unwrap();
+        manager
+            .create_tenant(TenantConfig::new("globex", 9302))
+            .await
+            .unwrap();
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_key_stores_are_isolated() {
+        let mut manager = manager().await;
+        let acme_key = manager
+            .tenant_mut("acme")
+            .unwrap()
+            .import_key("signing", KeyPurpose::Signing, [1u8; 32])
+            .unwrap();
+
+        let globex = manager.tenant_mut("globex").unwrap();
+        assert!(globex.sign("signing", b"payload").is_err());
+        let globex_key = globex.generate_key("signing", KeyPurpose::Signing).unwrap();
+        assert_ne!(acme_key.public_key, globex_key.public_key);
+    }
+
+    #[tokio::test]
+    async fn test_channels_peers_and_quotas() {
+        let mut manager = manager().await;
+
+        let acme = manager.tenant_mut("acme").unwrap();
+        assert!(acme.establish_channel("stranger").await.is_err());
+        acme.establish_channel("acme_peer").await.unwrap();
+        assert!(acme.establish_channel("acme_backup").await.is_err());
+        acme.send_message("acme_peer", b"hello").await.unwrap();
+        assert_eq!(acme.usage().bytes_sent, 5);
+
+        // Another tenant cannot use acme's channel
+        let globex = manager.tenant_mut("globex").unwrap();
+        assert!(globex.send_message("acme_peer", b"hello").await.is_err());
+
+        let stats = manager.get_stats();
+        assert_eq!(stats["tenants"], 2);
+        assert_eq!(stats["tenant.acme.channels"], 1);
+        assert_eq!(stats["tenant.globex.channels"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_conflicts_rejected() {
+        let mut manager = manager().await;
+        assert!(manager
+            .create_tenant(TenantConfig::new("acme", 9303))
+            .await
+            .is_err());
+        assert!(manager
+            .create_tenant(TenantConfig::new("initech", 9301))
+            .await
+            .is_err());
+
+        assert!(manager.remove_tenant("globex").await.unwrap());
+        assert_eq!(manager.tenant_ids(), vec!["acme".to_string()]);
+    }
+}
@@ -0,0 +1,198 @@
+//! # Timeouts - Single Configuration Surface for Protocol Deadlines
+//!
+//! Collects every deadline the client enforces while talking to peers in one
+//! validated struct, so operators tune them in one place and each subsystem
+//! reads the same values instead of its own hardcoded durations.
+//!
+//! ## Deadlines
+//!
+//! - **connect_ms**: TCP connect to a peer
+//! - **latency_probe_ms**: Connect used only to measure round-trip latency
+//! - **handshake_round_ms**: One channel establishment attempt, from the first
+//!   handshake message to verification; retries get a fresh round
+//! - **rekey_ms**: Key exchange and installation of one rekey
+//! - **health_check_ms**: A full `health_check`; overrunning it reports unhealthy
+//! - **consensus_round_ms**: Voting window of a consensus proposal
+//! - **startup_stage_ms**: One optional startup stage, such as quantum
+//!   hardware detection, before the client continues without it
+//! - **shutdown_ms**: Joining background tasks in `shutdown`; tasks still
+//!   running afterwards are aborted
+//!
+//! ## Validation
+//!
+//! Every deadline must be non-zero and at most `MAX_TIMEOUT_MS`, and a
+//! handshake round must leave room for its TCP connect.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::timeouts::Timeouts;
+//!
+//! let timeouts = Timeouts {
+//!     connect_ms: 2_000,
+//!     handshake_round_ms: 15_000,
+//!     ..Default::default()
+//! };
+//! timeouts.validate().unwrap();
+//! assert_eq!(timeouts.connect().as_secs(), 2);
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Upper bound for any configured deadline (one hour)
+pub const MAX_TIMEOUT_MS: u64 = 3_600_000;
+
+/// Deadlines enforced across the client, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Timeouts {
+    /// TCP connect to a peer
+    pub connect_ms: u64,
+    /// Connect used to measure latency; a miss falls back to an estimate
+    pub latency_probe_ms: u64,
+    /// One channel establishment attempt
+    pub handshake_round_ms: u64,
+    /// One session key rotation
+    pub rekey_ms: u64,
+    /// One full health check
+    pub health_check_ms: u64,
+    /// Voting window of a consensus proposal
+    pub consensus_round_ms: u64,
+    /// One optional startup stage
+    pub startup_stage_ms: u64,
+    /// Joining background tasks at shutdown
+    pub shutdown_ms: u64,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            connect_ms: 500,
+            latency_probe_ms: 100,
+            handshake_round_ms: 10_000,
+            rekey_ms: 10_000,
+            health_check_ms: 30_000,
+            consensus_round_ms: 5_000,
+            startup_stage_ms: 10_000,
+            shutdown_ms: 5_000,
+        }
+    }
+}
+
+impl Timeouts {
+    /// TCP connect deadline
+    pub fn connect(&self) -> Duration {
+        Duration::from_millis(self.connect_ms)
+    }
+
+    /// Latency probe deadline
+    pub fn latency_probe(&self) -> Duration {
+        Duration::from_millis(self.latency_probe_ms)
+    }
+
+    /// Deadline of one establishment attempt
+    pub fn handshake_round(&self) -> Duration {
+        Duration::from_millis(self.handshake_round_ms)
+    }
+
+    /// Deadline of one rekey
+    pub fn rekey(&self) -> Duration {
+        Duration::from_millis(self.rekey_ms)
+    }
+
+    /// Deadline of one health check
+    pub fn health_check(&self) -> Duration {
+        Duration::from_millis(self.health_check_ms)
+    }
+
+    /// Voting window of a consensus proposal
+    pub fn consensus_round(&self) -> Duration {
+        Duration::from_millis(self.consensus_round_ms)
+    }
+
+    /// Deadline of one optional startup stage
+    pub fn startup_stage(&self) -> Duration {
+        Duration::from_millis(self.startup_stage_ms)
+    }
+
+    /// Deadline for background tasks to stop at shutdown
+    pub fn shutdown(&self) -> Duration {
+        Duration::from_millis(self.shutdown_ms)
+    }
+
+    /// Check every deadline is usable
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("connect_ms", self.connect_ms),
+            ("latency_probe_ms", self.latency_probe_ms),
+            ("handshake_round_ms", self.handshake_round_ms),
+            ("rekey_ms", self.rekey_ms),
+            ("health_check_ms", self.health_check_ms),
+            ("consensus_round_ms", self.consensus_round_ms),
+            ("startup_stage_ms", self.startup_stage_ms),
+            ("shutdown_ms", self.shutdown_ms),
+        ] {
+            if value == 0 || value > MAX_TIMEOUT_MS {
+                return Err(SecureCommsError::Configuration(format!(
+                    "Timeout {} must be between 1 and {} ms, got {}",
+                    name, MAX_TIMEOUT_MS, value
+                )));
+            }
+        }
+        if self.handshake_round_ms < self.connect_ms {
+            return Err(SecureCommsError::Configuration(format!(
+                "Handshake round of {} ms cannot fit a {} ms connect",
+                self.handshake_round_ms, self.connect_ms
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let timeouts = Timeouts::default();
+        timeouts.validate().unwrap();
+        assert_eq!(timeouts.connect(), Duration::from_millis(500));
+        assert_eq!(timeouts.consensus_round(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_validation_rejects_unusable_deadlines() {
+        let zero = Timeouts {
+            rekey_ms: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            zero.validate(),
+            Err(SecureCommsError::Configuration(message)) if message.contains("rekey_ms")
+        ));
+        let huge = Timeouts {
+            health_check_ms: MAX_TIMEOUT_MS + 1,
+            ..Default::default()
+        };
+        assert!(huge.validate().is_err());
+        let cramped = Timeouts {
+            connect_ms: 5_000,
+            handshake_round_ms: 1_000,
+            ..Default::default()
+        };
+        assert!(cramped.validate().is_err());
+    }
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let timeouts: Timeouts = serde_json::from_str(r#"{"connect_ms": 2000}"#).unwrap();
+        assert_eq!(timeouts.connect_ms, 2_000);
+        assert_eq!(
+            timeouts.handshake_round_ms,
+            Timeouts::default().handshake_round_ms
+        );
+    }
+}
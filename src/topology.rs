@@ -0,0 +1,452 @@
+//! # Network Topology Manager - Mesh, Star, Ring and Linear Presets
+//!
+//! Plans, tracks and repairs the channel graph of a validator network. Given a
+//! node list and a [`NetworkTopology`] preset, the manager derives the set of
+//! links that must exist, tracks the health of each link, and recomputes the
+//! plan over the surviving nodes when links or nodes fail.
+//!
+//! ## Topology Presets
+//!
+//! - **FullMesh**: Every node links to every other node (n·(n-1)/2 links)
+//! - **Star**: The first healthy node acts as hub and links to every other node
+//! - **Ring**: Each node links to its successor, the last node closes the ring
+//! - **Linear**: Each node links to its successor, no wrap-around
+//!
+//! ## Repair Strategy
+//!
+//! Links that go down are retried while both endpoints are healthy. Once a node
+//! exceeds the failure threshold it is excluded and the plan is rebuilt over the
+//! remaining nodes: ring neighbours are bridged, a new star hub is elected and
+//! linear chains are re-stitched.
+//!
+//! ## Visualization
+//!
+//! [`TopologyManager::snapshot`] returns a serializable graph and
+//! [`TopologyManager::to_dot`] renders Graphviz DOT output.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::topology::TopologyManager;
+//! use quantum_forge_secure_comms::NetworkTopology;
+//!
+//! let nodes = vec!["v1".to_string(), "v2".to_string(), "v3".to_string(), "v4".to_string()];
+//! let mut manager = TopologyManager::new(NetworkTopology::Ring, nodes).unwrap();
+//!
+//! for (a, b) in manager.pending_links() {
+//!     manager.mark_link_up(&a, &b);
+//! }
+//!
+//! // v3 goes away - bridge v2 and v4
+//! manager.mark_node_failed("v3");
+//! let repairs = manager.repair_plan();
+//! assert!(repairs.contains(&("v2".to_string(), "v4".to_string())));
+//!
+//! println!("{}", manager.to_dot());
+//! ```
+
+use crate::streamlined_client::NetworkTopology;
+use crate::{Result, SecureCommsError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Health status of a single topology link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkStatus {
+    /// Link is planned but not yet established
+    Pending,
+    /// Secure channel is established and healthy
+    Up,
+    /// Link failed or channel was lost
+    Down,
+}
+
+/// Undirected link between two nodes of the topology
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyLink {
+    /// First endpoint (lexicographically ordered as planned)
+    pub from: String,
+    /// Second endpoint
+    pub to: String,
+    /// Current link status
+    pub status: LinkStatus,
+    /// Consecutive failures observed on this link
+    pub failure_count: u32,
+    /// Unix timestamp of the last status change
+    pub last_checked: u64,
+}
+
+impl TopologyLink {
+    fn new(from: String, to: String) -> Self {
+        Self {
+            from,
+            to,
+            status: LinkStatus::Pending,
+            failure_count: 0,
+            last_checked: chrono::Utc::now().timestamp() as u64,
+        }
+    }
+
+    /// Check whether this link connects the two given nodes (in either direction)
+    pub fn connects(&self, a: &str, b: &str) -> bool {
+        (self.from == a && self.to == b) || (self.from == b && self.to == a)
+    }
+
+    /// Check whether the node is an endpoint of this link
+    pub fn touches(&self, node: &str) -> bool {
+        self.from == node || self.to == node
+    }
+}
+
+/// Serializable view of the current topology graph for visualization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologySnapshot {
+    /// Topology preset in use
+    pub topology: NetworkTopology,
+    /// Healthy nodes in planning order
+    pub nodes: Vec<String>,
+    /// Nodes excluded after repeated failures
+    pub failed_nodes: Vec<String>,
+    /// All planned links with status
+    pub links: Vec<TopologyLink>,
+    /// Current hub for star topologies
+    pub hub: Option<String>,
+    /// Number of links currently up
+    pub healthy_links: usize,
+}
+
+/// Topology manager planning and repairing the channel graph
+#[derive(Debug, Clone)]
+pub struct TopologyManager {
+    /// Topology preset
+    topology: NetworkTopology,
+    /// All nodes in planning order
+    nodes: Vec<String>,
+    /// Nodes excluded from the plan
+    failed_nodes: HashSet<String>,
+    /// Planned links over healthy nodes
+    links: Vec<TopologyLink>,
+    /// Consecutive link failures before the remote node is declared failed
+    failure_threshold: u32,
+}
+
+impl TopologyManager {
+    /// Create topology manager for the given preset and node list
+    pub fn new(topology: NetworkTopology, nodes: Vec<String>) -> Result<Self> {
+        let mut unique = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            if !unique.contains(&node) {
+                unique.push(node);
+            }
+        }
+
+        if unique.len() < 2 {
+            return Err(SecureCommsError::Configuration(
+                "Topology requires at least two distinct nodes".to_string(),
+            ));
+        }
+
+        let mut manager = Self {
+            topology,
+            nodes: unique,
+            failed_nodes: HashSet::new(),
+            links: Vec::new(),
+            failure_threshold: 3,
+        };
+        manager.rebuild();
+        Ok(manager)
+    }
+
+    /// Set the number of consecutive link failures tolerated before a node is excluded
+    pub fn with_failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+
+    /// Compute the links required by a topology preset over the given nodes
+    pub fn plan_links(topology: NetworkTopology, nodes: &[String]) -> Vec<(String, String)> {
+        let mut links = Vec::new();
+        if nodes.len() < 2 {
+            return links;
+        }
+
+        match topology {
+            NetworkTopology::FullMesh => {
+                for i in 0..nodes.len() {
+                    for j in (i + 1)..nodes.len() {
+                        links.push((nodes[i].clone(), nodes[j].clone()));
+                    }
+                }
+            }
+            NetworkTopology::Star => {
+                let hub = &nodes[0];
+                for spoke in &nodes[1..] {
+                    links.push((hub.clone(), spoke.clone()));
+                }
+            }
+            NetworkTopology::Ring | NetworkTopology::Linear => {
+                for pair in nodes.windows(2) {
+                    links.push((pair[0].clone(), pair[1].clone()));
+                }
+                // A ring of two is just a single link
+                if topology == NetworkTopology::Ring && nodes.len() > 2 {
+                    links.push((nodes[nodes.len() - 1].clone(), nodes[0].clone()));
+                }
+            }
+        }
+
+        links
+    }
+
+    /// Get topology preset
+    pub fn topology(&self) -> NetworkTopology {
+        self.topology
+    }
+
+    /// Get healthy nodes in planning order
+    pub fn healthy_nodes(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter(|node| !self.failed_nodes.contains(*node))
+            .cloned()
+            .collect()
+    }
+
+    /// Get current hub for star topologies
+    pub fn hub(&self) -> Option<String> {
+        match self.topology {
+            NetworkTopology::Star => self.healthy_nodes().into_iter().next(),
+            _ => None,
+        }
+    }
+
+    /// Get all planned links
+    pub fn links(&self) -> &[TopologyLink] {
+        &self.links
+    }
+
+    /// Get neighbours of a node in the current plan
+    pub fn neighbors(&self, node: &str) -> Vec<String> {
+        self.links
+            .iter()
+            .filter(|link| link.touches(node))
+            .map(|link| {
+                if link.from == node {
+                    link.to.clone()
+                } else {
+                    link.from.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Links that still need to be established
+    pub fn pending_links(&self) -> Vec<(String, String)> {
+        self.links
+            .iter()
+            .filter(|link| link.status == LinkStatus::Pending)
+            .map(|link| (link.from.clone(), link.to.clone()))
+            .collect()
+    }
+
+    /// Mark a link as established
+    pub fn mark_link_up(&mut self, a: &str, b: &str) {
+        if let Some(link) = self.links.iter_mut().find(|link| link.connects(a, b)) {
+            link.status = LinkStatus::Up;
+            link.failure_count = 0;
+            link.last_checked = chrono::Utc::now().timestamp() as u64;
+        }
+    }
+
+    /// Mark a link as failed
+    ///
+    /// Returns `true` when the link has reached the failure threshold and the
+    /// caller should consider the remote endpoint failed.
+    pub fn mark_link_down(&mut self, a: &str, b: &str) -> bool {
+        let threshold = self.failure_threshold;
+        if let Some(link) = self.links.iter_mut().find(|link| link.connects(a, b)) {
+            link.status = LinkStatus::Down;
+            link.failure_count += 1;
+            link.last_checked = chrono::Utc::now().timestamp() as u64;
+            link.failure_count >= threshold
+        } else {
+            false
+        }
+    }
+
+    /// Exclude a node from the topology and rebuild the plan
+    pub fn mark_node_failed(&mut self, node: &str) {
+        if self.nodes.iter().any(|n| n == node) {
+            self.failed_nodes.insert(node.to_string());
+            self.rebuild();
+        }
+    }
+
+    /// Re-admit a previously failed node and rebuild the plan
+    pub fn restore_node(&mut self, node: &str) {
+        if self.failed_nodes.remove(node) {
+            self.rebuild();
+        }
+    }
+
+    /// Compute links that must be (re-)established to restore the topology
+    ///
+    /// Includes newly planned bridge links and links that are down between
+    /// healthy nodes.
+    pub fn repair_plan(&mut self) -> Vec<(String, String)> {
+        self.rebuild();
+        self.links
+            .iter()
+            .filter(|link| link.status != LinkStatus::Up)
+            .map(|link| (link.from.clone(), link.to.clone()))
+            .collect()
+    }
+
+    /// Whether every planned link is up
+    pub fn is_healthy(&self) -> bool {
+        self.links.iter().all(|link| link.status == LinkStatus::Up)
+    }
+
+    /// Get serializable snapshot of the graph
+    pub fn snapshot(&self) -> TopologySnapshot {
+        let mut failed_nodes: Vec<String> = self.failed_nodes.iter().cloned().collect();
+        failed_nodes.sort();
+
+        TopologySnapshot {
+            topology: self.topology,
+            nodes: self.healthy_nodes(),
+            failed_nodes,
+            links: self.links.clone(),
+            hub: self.hub(),
+            healthy_links: self
+                .links
+                .iter()
+                .filter(|link| link.status == LinkStatus::Up)
+                .count(),
+        }
+    }
+
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("graph {:?} {{\n", self.topology);
+        for node in &self.nodes {
+            let style = if self.failed_nodes.contains(node) {
+                " [style=dashed, color=red]"
+            } else {
+                ""
+            };
+            dot.push_str(&format!("    \"{}\"{};\n", node, style));
+        }
+        for link in &self.links {
+            let color = match link.status {
+                LinkStatus::Up => "green",
+                LinkStatus::Pending => "gray",
+                LinkStatus::Down => "red",
+            };
+            dot.push_str(&format!(
+                "    \"{}\" -- \"{}\" [color={}];\n",
+                link.from, link.to, color
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Rebuild the planned links over healthy nodes, preserving known link state
+    fn rebuild(&mut self) {
+        let planned = Self::plan_links(self.topology, &self.healthy_nodes());
+        let previous = std::mem::take(&mut self.links);
+
+        self.links = planned
+            .into_iter()
+            .map(|(from, to)| {
+                previous
+                    .iter()
+                    .find(|link| link.connects(&from, &to))
+                    .cloned()
+                    .unwrap_or_else(|| TopologyLink::new(from, to))
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("v{}", i)).collect()
+    }
+
+    #[test]
+    fn test_preset_link_counts() {
+        let five = nodes(5);
+        assert_eq!(TopologyManager::plan_links(NetworkTopology::FullMesh, &five).len(), 10);
+        assert_eq!(TopologyManager::plan_links(NetworkTopology::Star, &five).len(), 4);
+        assert_eq!(TopologyManager::plan_links(NetworkTopology::Ring, &five).len(), 5);
+        assert_eq!(TopologyManager::plan_links(NetworkTopology::Linear, &five).len(), 4);
+        assert_eq!(TopologyManager::plan_links(NetworkTopology::Ring, &nodes(2)).len(), 1);
+    }
+
+    #[test]
+    fn test_requires_two_nodes() {
+        assert!(TopologyManager::new(NetworkTopology::FullMesh, vec!["solo".to_string()]).is_err());
+        assert!(TopologyManager::new(
+            NetworkTopology::FullMesh,
+            vec!["dup".to_string(), "dup".to_string()]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_ring_repair_bridges_neighbours() {
+        let mut manager = TopologyManager::new(NetworkTopology::Ring, nodes(4)).unwrap();
+        for (a, b) in manager.pending_links() {
+            manager.mark_link_up(&a, &b);
+        }
+        assert!(manager.is_healthy());
+
+        manager.mark_node_failed("v3");
+        let repairs = manager.repair_plan();
+        assert_eq!(repairs, vec![("v2".to_string(), "v4".to_string())]);
+        assert!(!manager.neighbors("v2").contains(&"v3".to_string()));
+    }
+
+    #[test]
+    fn test_star_hub_failover() {
+        let mut manager = TopologyManager::new(NetworkTopology::Star, nodes(4)).unwrap();
+        assert_eq!(manager.hub().as_deref(), Some("v1"));
+
+        manager.mark_node_failed("v1");
+        assert_eq!(manager.hub().as_deref(), Some("v2"));
+        assert_eq!(manager.repair_plan().len(), 2);
+        assert_eq!(manager.neighbors("v2").len(), 2);
+    }
+
+    #[test]
+    fn test_link_failure_threshold() {
+        let mut manager = TopologyManager::new(NetworkTopology::Linear, nodes(3))
+            .unwrap()
+            .with_failure_threshold(2);
+        manager.mark_link_up("v1", "v2");
+
+        assert!(!manager.mark_link_down("v2", "v1"));
+        assert!(manager.mark_link_down("v1", "v2"));
+        assert!(manager.repair_plan().contains(&("v1".to_string(), "v2".to_string())));
+    }
+
+    #[test]
+    fn test_snapshot_and_dot_export() {
+        let mut manager = TopologyManager::new(NetworkTopology::FullMesh, nodes(3)).unwrap();
+        manager.mark_link_up("v1", "v2");
+
+        let snapshot = manager.snapshot();
+        assert_eq!(snapshot.links.len(), 3);
+        assert_eq!(snapshot.healthy_links, 1);
+        assert!(serde_json::to_string(&snapshot).is_ok());
+
+        let dot = manager.to_dot();
+        assert!(dot.starts_with("graph FullMesh {"));
+        assert!(dot.contains("\"v1\" -- \"v2\" [color=green];"));
+    }
+}
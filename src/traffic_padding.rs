@@ -0,0 +1,343 @@
+//! # Traffic Padding - Per-Channel Padding and Timing Obfuscation
+//!
+//! Encryption hides what validators say but not when or how much. Padding
+//! policies blunt traffic analysis on sensitive links by making frame sizes,
+//! send times and idle periods uninformative.
+//!
+//! ## Policy Elements
+//!
+//! - **Fixed-size frames**: Frames are padded up to a multiple of
+//!   `frame_size`, so lengths reveal only a coarse size bucket
+//! - **Cover traffic**: Idle channels send dummy frames at
+//!   `cover_rate_per_sec` on average, with jittered intervals
+//! - **Batching delays**: Outbound frames are held for a random delay within
+//!   the batching window, decoupling send times from application events
+//!
+//! ## Negotiation
+//!
+//! Each side offers its local policy with a `PaddingOffer`; both adopt the
+//! stronger value of every element. The result is symmetric, so the peers
+//! agree without a further round trip. Channels without a negotiated policy
+//! are not padded.
+//!
+//! ## Overhead
+//!
+//! Padding bytes and cover frames are charged to the peer's egress bucket
+//! and reported as `padding_bytes` and `cover_messages` in bandwidth usage.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::traffic_padding::{PaddingPolicy, TrafficPadder};
+//!
+//! let mut padder = TrafficPadder::new(PaddingPolicy::validator());
+//! let agreed = padder.negotiate("validator_2", &PaddingPolicy::none());
+//! assert_eq!(agreed, PaddingPolicy::validator());
+//! assert_eq!(agreed.padded_len(100), 1024);
+//! ```
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::{Result, SecureCommsError};
+
+/// Bytes of the length prefix inside a padded frame
+const LENGTH_PREFIX_LEN: usize = 4;
+
+/// Padding and timing obfuscation settings for a channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaddingPolicy {
+    /// Frames are padded to a multiple of this size (0 disables padding)
+    pub frame_size: usize,
+    /// Average cover frames per second on an idle channel (0 disables)
+    pub cover_rate_per_sec: f64,
+    /// Shortest random hold before an outbound frame is sent
+    pub batch_delay_min_ms: u64,
+    /// Longest random hold before an outbound frame is sent (0 disables)
+    pub batch_delay_max_ms: u64,
+}
+
+impl Default for PaddingPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl PaddingPolicy {
+    /// No padding, cover traffic or batching
+    pub fn none() -> Self {
+        Self {
+            frame_size: 0,
+            cover_rate_per_sec: 0.0,
+            batch_delay_min_ms: 0,
+            batch_delay_max_ms: 0,
+        }
+    }
+
+    /// Preset for validator links: 1 KiB frames, 2 cover frames/s, 5-25ms batching
+    pub fn validator() -> Self {
+        Self {
+            frame_size: 1024,
+            cover_rate_per_sec: 2.0,
+            batch_delay_min_ms: 5,
+            batch_delay_max_ms: 25,
+        }
+    }
+
+    /// Whether any element of the policy is active
+    pub fn is_enabled(&self) -> bool {
+        self.frame_size > 0 || self.cover_rate_per_sec > 0.0 || self.batch_delay_max_ms > 0
+    }
+
+    /// Stronger of two policies, element by element
+    ///
+    /// Symmetric, so both peers reach the same policy from the same offers.
+    pub fn negotiate(&self, other: &PaddingPolicy) -> PaddingPolicy {
+        let batch_delay_max_ms = self.batch_delay_max_ms.max(other.batch_delay_max_ms);
+        PaddingPolicy {
+            frame_size: self.frame_size.max(other.frame_size),
+            cover_rate_per_sec: self.cover_rate_per_sec.max(other.cover_rate_per_sec),
+            batch_delay_min_ms: self
+                .batch_delay_min_ms
+                .max(other.batch_delay_min_ms)
+                .min(batch_delay_max_ms),
+            batch_delay_max_ms,
+        }
+    }
+
+    /// Size on the wire of a frame carrying `len` bytes
+    pub fn padded_len(&self, len: usize) -> usize {
+        if self.frame_size == 0 {
+            return len;
+        }
+        len.div_ceil(self.frame_size).max(1) * self.frame_size
+    }
+
+    /// Pad a payload behind a length prefix to the policy's frame size
+    pub fn pad(&self, payload: &[u8]) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(self.padded_len(LENGTH_PREFIX_LEN + payload.len()));
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame.resize(self.padded_len(frame.len()), 0);
+        frame
+    }
+
+    /// Recover the payload of a frame produced by `pad`
+    pub fn unpad(frame: &[u8]) -> Result<&[u8]> {
+        let malformed = || SecureCommsError::Validation("Malformed padded frame".to_string());
+        let prefix: [u8; LENGTH_PREFIX_LEN] = frame
+            .get(..LENGTH_PREFIX_LEN)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(malformed)?;
+        let len = u32::from_be_bytes(prefix) as usize;
+        frame
+            .get(LENGTH_PREFIX_LEN..LENGTH_PREFIX_LEN + len)
+            .ok_or_else(malformed)
+    }
+
+    /// Random hold for the next outbound frame
+    pub fn batch_delay<R: Rng>(&self, rng: &mut R) -> Duration {
+        if self.batch_delay_max_ms == 0 {
+            return Duration::ZERO;
+        }
+        let min = self.batch_delay_min_ms.min(self.batch_delay_max_ms);
+        Duration::from_millis(rng.gen_range(min..=self.batch_delay_max_ms))
+    }
+
+    /// Jittered wait before the next cover frame, between 0.5 and 1.5 mean intervals
+    pub fn cover_interval<R: Rng>(&self, rng: &mut R) -> Option<Duration> {
+        if self.cover_rate_per_sec <= 0.0 {
+            return None;
+        }
+        let mean = 1.0 / self.cover_rate_per_sec;
+        Some(Duration::from_secs_f64(mean * rng.gen_range(0.5..1.5)))
+    }
+}
+
+/// Negotiated policy and cover schedule of one channel
+#[derive(Debug, Clone)]
+struct ChannelPadding {
+    policy: PaddingPolicy,
+    next_cover_at: Option<Instant>,
+}
+
+/// Per-channel padding policies and cover traffic scheduling
+#[derive(Debug, Clone, Default)]
+pub struct TrafficPadder {
+    /// Policy offered to peers
+    local: PaddingPolicy,
+    /// Negotiated policies by peer
+    channels: HashMap<String, ChannelPadding>,
+}
+
+impl TrafficPadder {
+    /// Create a padder offering `local` to peers
+    pub fn new(local: PaddingPolicy) -> Self {
+        Self {
+            local,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Policy offered to peers
+    pub fn local_policy(&self) -> &PaddingPolicy {
+        &self.local
+    }
+
+    /// Change the policy offered to peers; negotiated channels keep theirs
+    pub fn set_local_policy(&mut self, policy: PaddingPolicy) {
+        self.local = policy;
+    }
+
+    /// Adopt the stronger of our policy and the peer's offer for its channel
+    pub fn negotiate(&mut self, peer_id: &str, offer: &PaddingPolicy) -> PaddingPolicy {
+        self.negotiate_at(peer_id, offer, Instant::now())
+    }
+
+    /// `negotiate` with an explicit clock
+    pub fn negotiate_at(
+        &mut self,
+        peer_id: &str,
+        offer: &PaddingPolicy,
+        now: Instant,
+    ) -> PaddingPolicy {
+        let policy = self.local.negotiate(offer);
+        let next_cover_at = policy
+            .cover_interval(&mut rand::thread_rng())
+            .map(|interval| now + interval);
+        self.channels.insert(
+            peer_id.to_string(),
+            ChannelPadding {
+                policy: policy.clone(),
+                next_cover_at,
+            },
+        );
+        policy
+    }
+
+    /// Negotiated policy of a channel, if any
+    pub fn policy(&self, peer_id: &str) -> Option<&PaddingPolicy> {
+        self.channels.get(peer_id).map(|channel| &channel.policy)
+    }
+
+    /// Note a frame sent to the peer, pushing back its next cover frame
+    pub fn record_send_at(&mut self, peer_id: &str, now: Instant) {
+        if let Some(channel) = self.channels.get_mut(peer_id) {
+            channel.next_cover_at = channel
+                .policy
+                .cover_interval(&mut rand::thread_rng())
+                .map(|interval| now + interval);
+        }
+    }
+
+    /// Peers whose channel has been idle long enough to need a cover frame
+    pub fn due_cover_at(&self, now: Instant) -> Vec<String> {
+        let mut due: Vec<String> = self
+            .channels
+            .iter()
+            .filter(|(_, channel)| channel.next_cover_at.is_some_and(|at| at <= now))
+            .map(|(peer_id, _)| peer_id.clone())
+            .collect();
+        due.sort();
+        due
+    }
+
+    /// Forget a peer's negotiated policy
+    pub fn remove_peer(&mut self, peer_id: &str) {
+        self.channels.remove(peer_id);
+    }
+
+    /// Get padding statistics for metrics export
+    pub fn get_stats(&self) -> HashMap<String, serde_json::Value> {
+        let mut stats = HashMap::new();
+        stats.insert(
+            "local_policy".to_string(),
+            serde_json::to_value(&self.local).unwrap_or(serde_json::Value::Null),
+        );
+        stats.insert(
+            "padded_channels".to_string(),
+            serde_json::json!(self
+                .channels
+                .values()
+                .filter(|channel| channel.policy.is_enabled())
+                .count()),
+        );
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiation_takes_stronger_policy() {
+        let offer = PaddingPolicy {
+            frame_size: 512,
+            cover_rate_per_sec: 5.0,
+            batch_delay_min_ms: 40,
+            batch_delay_max_ms: 20,
+        };
+        let agreed = PaddingPolicy::validator().negotiate(&offer);
+        assert_eq!(agreed, offer.negotiate(&PaddingPolicy::validator()));
+        assert_eq!(agreed.frame_size, 1024);
+        assert_eq!(agreed.cover_rate_per_sec, 5.0);
+        assert_eq!(agreed.batch_delay_max_ms, 25);
+        assert_eq!(agreed.batch_delay_min_ms, 25);
+
+        assert!(!PaddingPolicy::none().is_enabled());
+        assert_eq!(
+            PaddingPolicy::none().negotiate(&PaddingPolicy::none()),
+            PaddingPolicy::none()
+        );
+    }
+
+    #[test]
+    fn test_padding_roundtrip_and_delays() {
+        let policy = PaddingPolicy::validator();
+        assert_eq!(policy.padded_len(0), 1024);
+        assert_eq!(policy.padded_len(1024), 1024);
+        assert_eq!(policy.padded_len(1025), 2048);
+        assert_eq!(PaddingPolicy::none().padded_len(77), 77);
+
+        for payload in [&b""[..], b"vote", &[7u8; 1020][..], &[7u8; 1021][..]] {
+            let frame = policy.pad(payload);
+            assert_eq!(frame.len() % 1024, 0);
+            assert_eq!(PaddingPolicy::unpad(&frame).unwrap(), payload);
+        }
+        assert!(PaddingPolicy::unpad(&[0, 0, 1]).is_err());
+        assert!(PaddingPolicy::unpad(&[0, 0, 0, 9, 1]).is_err());
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let delay = policy.batch_delay(&mut rng).as_millis();
+            assert!((5..=25).contains(&delay));
+            let interval = policy.cover_interval(&mut rng).unwrap().as_secs_f64();
+            assert!((0.25..0.75).contains(&interval));
+        }
+        assert!(PaddingPolicy::none().batch_delay(&mut rng).is_zero());
+        assert!(PaddingPolicy::none().cover_interval(&mut rng).is_none());
+    }
+
+    #[test]
+    fn test_cover_traffic_schedule() {
+        let mut padder = TrafficPadder::new(PaddingPolicy::validator());
+        let start = Instant::now();
+        padder.negotiate_at("validator_2", &PaddingPolicy::none(), start);
+        padder.negotiate_at("observer", &PaddingPolicy::none(), start);
+        padder.set_local_policy(PaddingPolicy::none());
+        padder.negotiate_at("client", &PaddingPolicy::none(), start);
+
+        assert!(padder.due_cover_at(start).is_empty());
+        let later = start + Duration::from_secs(1);
+        assert_eq!(padder.due_cover_at(later), vec!["observer", "validator_2"]);
+
+        // Real traffic pushes the next cover frame back
+        padder.record_send_at("validator_2", later);
+        assert_eq!(padder.due_cover_at(later), vec!["observer"]);
+        assert!(padder.policy("client").is_some_and(|p| !p.is_enabled()));
+        assert_eq!(padder.get_stats()["padded_channels"], 2);
+    }
+}
@@ -0,0 +1,505 @@
+//! # Typed Messaging - Schema-Identified Messages with Version Negotiation
+//!
+//! Lets applications exchange serde types instead of hand-rolled byte
+//! protocols on top of `send_secure_message`. Every message type carries a
+//! schema ID and a version range; the registry encodes outgoing values at
+//! the highest version both peers support, validates incoming values and
+//! routes them to typed handlers.
+//!
+//! ## Wire Format
+//!
+//! - **Headers**: [`SCHEMA_ID_HEADER`] and [`SCHEMA_VERSION_HEADER`] identify
+//!   the type and the encoded version
+//! - **Payload**: JSON body produced by [`TypedMessage::encode_version`]
+//! - **Advertisement**: A message marked with [`SCHEMA_ADVERTISEMENT_HEADER`]
+//!   carries the sender's [`SchemaAdvertisement`]
+//!
+//! ## Version Negotiation
+//!
+//! Until a peer has advertised its schemas, values are sent at the local
+//! current version. Afterwards the highest version in both ranges is used,
+//! and sending a type the peer does not support fails instead of producing
+//! messages it would drop.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::typed_messaging::TypedMessage;
+//! use quantum_forge_secure_comms::StreamlinedSecureClient;
+//! use serde::{Deserialize, Serialize};
+//! use std::sync::Arc;
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! struct Transfer {
+//!     from: String,
+//!     to: String,
+//!     amount: u64,
+//! }
+//!
+//! impl TypedMessage for Transfer {
+//!     const SCHEMA_ID: &'static str = "ledger.transfer";
+//!     const VERSION: u16 = 2;
+//! }
+//!
+//! # async fn example() -> quantum_forge_secure_comms::Result<()> {
+//! let mut client = StreamlinedSecureClient::new().await?;
+//! client.on_typed_message::<Transfer>(Arc::new(|peer: &str, transfer: Transfer| {
+//!     println!("{} sent {:?}", peer, transfer);
+//!     Ok(())
+//! }))?;
+//!
+//! client.establish_secure_channel("peer_1").await?;
+//! client.advertise_schemas("peer_1").await?;
+//! let transfer = Transfer { from: "a".into(), to: "b".into(), amount: 5 };
+//! client.send_typed("peer_1", &transfer).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Result, SecureCommsError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Header carrying the schema ID of a typed message
+pub const SCHEMA_ID_HEADER: &str = "x-schema-id";
+
+/// Header carrying the encoded schema version
+pub const SCHEMA_VERSION_HEADER: &str = "x-schema-version";
+
+/// Header marking a schema advertisement
+pub const SCHEMA_ADVERTISEMENT_HEADER: &str = "x-schema-advertisement";
+
+/// Application message type with a stable schema ID
+///
+/// `MIN_VERSION..=VERSION` is the range of versions the type can encode and
+/// decode. Types that support older versions override `encode_version` and
+/// `decode_version` to migrate between layouts.
+pub trait TypedMessage: Serialize + DeserializeOwned + Send + 'static {
+    /// Globally unique schema identifier
+    const SCHEMA_ID: &'static str;
+    /// Current schema version
+    const VERSION: u16 = 1;
+    /// Oldest supported schema version
+    const MIN_VERSION: u16 = 1;
+
+    /// Check invariants of a decoded value
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Encode the value at `version`
+    fn encode_version(&self, _version: u16) -> Result<serde_json::Value> {
+        serde_json::to_value(self).map_err(|e| {
+            SecureCommsError::Validation(format!("Failed to encode {}: {}", Self::SCHEMA_ID, e))
+        })
+    }
+
+    /// Decode a value encoded at `version`
+    fn decode_version(value: serde_json::Value, _version: u16) -> Result<Self> {
+        serde_json::from_value(value).map_err(|e| {
+            SecureCommsError::Validation(format!("Malformed {}: {}", Self::SCHEMA_ID, e))
+        })
+    }
+}
+
+/// Supported version range of one schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionRange {
+    /// Oldest supported version
+    pub min: u16,
+    /// Newest supported version
+    pub max: u16,
+}
+
+impl VersionRange {
+    /// Highest version contained in both ranges
+    pub fn negotiate(&self, other: &VersionRange) -> Option<u16> {
+        let max = self.max.min(other.max);
+        let min = self.min.max(other.min);
+        if min <= max {
+            Some(max)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `version` lies in the range
+    pub fn contains(&self, version: u16) -> bool {
+        self.min <= version && version <= self.max
+    }
+}
+
+/// Schemas a node can exchange, sent to peers for version negotiation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaAdvertisement {
+    /// Version range by schema ID
+    pub schemas: BTreeMap<String, VersionRange>,
+}
+
+impl SchemaAdvertisement {
+    /// Serialize for transport
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| {
+            SecureCommsError::Validation(format!("Advertisement encoding failed: {}", e))
+        })
+    }
+
+    /// Deserialize from transport
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| SecureCommsError::Validation(format!("Malformed advertisement: {}", e)))
+    }
+}
+
+/// Handler receiving decoded values of one type along with the sending peer
+pub type TypedHandler<T> = Arc<dyn Fn(&str, T) -> Result<()> + Send + Sync>;
+
+type ErasedHandler = Arc<dyn Fn(&str, u16, &[u8]) -> Result<()> + Send + Sync>;
+
+#[derive(Debug, Clone)]
+struct SchemaInfo {
+    type_name: &'static str,
+    versions: VersionRange,
+}
+
+/// Registered message types, typed handlers and negotiated peer schemas
+#[derive(Default)]
+pub struct TypedRegistry {
+    schemas: HashMap<String, SchemaInfo>,
+    handlers: HashMap<String, ErasedHandler>,
+    peer_schemas: HashMap<String, SchemaAdvertisement>,
+}
+
+impl std::fmt::Debug for TypedRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedRegistry")
+            .field("schemas", &self.schemas.keys().collect::<Vec<_>>())
+            .field("handlers", &self.handlers.len())
+            .field("peers", &self.peer_schemas.len())
+            .finish()
+    }
+}
+
+impl TypedRegistry {
+    /// Create empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a message type
+    ///
+    /// Registering the same type twice is a no-op; reusing a schema ID for a
+    /// different type is refused.
+    pub fn register<T: TypedMessage>(&mut self) -> Result<()> {
+        if T::MIN_VERSION > T::VERSION {
+            return Err(SecureCommsError::Configuration(format!(
+                "Schema {} has MIN_VERSION above VERSION",
+                T::SCHEMA_ID
+            )));
+        }
+        let type_name = std::any::type_name::<T>();
+        if let Some(existing) = self.schemas.get(T::SCHEMA_ID) {
+            if existing.type_name != type_name {
+                return Err(SecureCommsError::Configuration(format!(
+                    "Schema {} already registered for {}",
+                    T::SCHEMA_ID,
+                    existing.type_name
+                )));
+            }
+            return Ok(());
+        }
+        self.schemas.insert(
+            T::SCHEMA_ID.to_string(),
+            SchemaInfo {
+                type_name,
+                versions: VersionRange {
+                    min: T::MIN_VERSION,
+                    max: T::VERSION,
+                },
+            },
+        );
+        Ok(())
+    }
+
+    /// Register a type and route its inbound messages to `handler`
+    ///
+    /// Replaces any handler previously registered for the type.
+    pub fn on<T: TypedMessage>(&mut self, handler: TypedHandler<T>) -> Result<()> {
+        self.register::<T>()?;
+        let erased: ErasedHandler = Arc::new(move |peer_id: &str, version: u16, payload: &[u8]| {
+            let value = decode_payload::<T>(version, payload)?;
+            handler(peer_id, value)
+        });
+        self.handlers.insert(T::SCHEMA_ID.to_string(), erased);
+        Ok(())
+    }
+
+    /// Whether a schema ID is registered
+    pub fn is_registered(&self, schema_id: &str) -> bool {
+        self.schemas.contains_key(schema_id)
+    }
+
+    /// Schemas of all registered types
+    pub fn advertisement(&self) -> SchemaAdvertisement {
+        SchemaAdvertisement {
+            schemas: self
+                .schemas
+                .iter()
+                .map(|(schema_id, info)| (schema_id.clone(), info.versions))
+                .collect(),
+        }
+    }
+
+    /// Record the schemas advertised by peer
+    pub fn set_peer_schemas(&mut self, peer_id: &str, advertisement: SchemaAdvertisement) {
+        self.peer_schemas.insert(peer_id.to_string(), advertisement);
+    }
+
+    /// Forget the schemas of peer
+    pub fn remove_peer(&mut self, peer_id: &str) {
+        self.peer_schemas.remove(peer_id);
+    }
+
+    /// Version used for `schema_id` with peer
+    ///
+    /// `None` when the type is not registered, or the peer has advertised
+    /// its schemas and shares no version of it.
+    pub fn negotiated_version(&self, peer_id: &str, schema_id: &str) -> Option<u16> {
+        let local = self.schemas.get(schema_id)?.versions;
+        match self.peer_schemas.get(peer_id) {
+            Some(advertisement) => advertisement
+                .schemas
+                .get(schema_id)
+                .and_then(|remote| local.negotiate(remote)),
+            None => Some(local.max),
+        }
+    }
+
+    /// Encode a value for peer, returning the headers and payload
+    pub fn encode<T: TypedMessage>(
+        &self,
+        peer_id: &str,
+        value: &T,
+    ) -> Result<(BTreeMap<String, String>, Vec<u8>)> {
+        if !self.is_registered(T::SCHEMA_ID) {
+            return Err(SecureCommsError::Validation(format!(
+                "Schema {} is not registered",
+                T::SCHEMA_ID
+            )));
+        }
+        let version = self
+            .negotiated_version(peer_id, T::SCHEMA_ID)
+            .ok_or_else(|| {
+                SecureCommsError::Validation(format!(
+                    "Peer {} supports no common version of {}",
+                    peer_id,
+                    T::SCHEMA_ID
+                ))
+            })?;
+        value.validate()?;
+        let body = serde_json::to_vec(&value.encode_version(version)?).map_err(|e| {
+            SecureCommsError::Validation(format!("Failed to encode {}: {}", T::SCHEMA_ID, e))
+        })?;
+
+        let mut headers = BTreeMap::new();
+        headers.insert(SCHEMA_ID_HEADER.to_string(), T::SCHEMA_ID.to_string());
+        headers.insert(SCHEMA_VERSION_HEADER.to_string(), version.to_string());
+        Ok((headers, body))
+    }
+
+    /// Decode a typed message payload
+    pub fn decode<T: TypedMessage>(
+        &self,
+        headers: &BTreeMap<String, String>,
+        payload: &[u8],
+    ) -> Result<T> {
+        let (schema_id, version) = schema_of(headers)?;
+        if schema_id != T::SCHEMA_ID {
+            return Err(SecureCommsError::Validation(format!(
+                "Expected schema {}, found {}",
+                T::SCHEMA_ID,
+                schema_id
+            )));
+        }
+        decode_payload::<T>(version, payload)
+    }
+
+    /// Route a typed message to its handler
+    ///
+    /// Returns `None` when the message is untyped or no handler is
+    /// registered for its schema.
+    pub fn dispatch(
+        &self,
+        peer_id: &str,
+        headers: &BTreeMap<String, String>,
+        payload: &[u8],
+    ) -> Option<Result<()>> {
+        let schema_id = headers.get(SCHEMA_ID_HEADER)?;
+        let handler = self.handlers.get(schema_id)?;
+        Some(schema_of(headers).and_then(|(_, version)| handler(peer_id, version, payload)))
+    }
+}
+
+/// Schema ID and version from message headers
+pub fn schema_of(headers: &BTreeMap<String, String>) -> Result<(&str, u16)> {
+    let schema_id = headers
+        .get(SCHEMA_ID_HEADER)
+        .ok_or_else(|| SecureCommsError::Validation("Message is not typed".to_string()))?;
+    let version = headers
+        .get(SCHEMA_VERSION_HEADER)
+        .and_then(|version| version.parse::<u16>().ok())
+        .ok_or_else(|| {
+            SecureCommsError::Validation(format!("Missing version for schema {}", schema_id))
+        })?;
+    Ok((schema_id.as_str(), version))
+}
+
+fn decode_payload<T: TypedMessage>(version: u16, payload: &[u8]) -> Result<T> {
+    if version < T::MIN_VERSION || version > T::VERSION {
+        return Err(SecureCommsError::Validation(format!(
+            "Unsupported version {} of {}",
+            version,
+            T::SCHEMA_ID
+        )));
+    }
+    let value: serde_json::Value = serde_json::from_slice(payload)
+        .map_err(|e| SecureCommsError::Validation(format!("Malformed {}: {}", T::SCHEMA_ID, e)))?;
+    let decoded = T::decode_version(value, version)?;
+    decoded.validate()?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Transfer {
+        amount: u64,
+        #[serde(default)]
+        memo: String,
+    }
+
+    impl TypedMessage for Transfer {
+        const SCHEMA_ID: &'static str = "test.transfer";
+        const VERSION: u16 = 2;
+
+        fn validate(&self) -> Result<()> {
+            if self.amount == 0 {
+                return Err(SecureCommsError::Validation("Zero transfer".to_string()));
+            }
+            Ok(())
+        }
+
+        fn encode_version(&self, version: u16) -> Result<serde_json::Value> {
+            // Version 1 has no memo field
+            if version == 1 {
+                return Ok(serde_json::json!({ "amount": self.amount }));
+            }
+            Ok(serde_json::to_value(self).unwrap())
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Imposter;
+
+    impl TypedMessage for Imposter {
+        const SCHEMA_ID: &'static str = "test.transfer";
+    }
+
+    fn transfer() -> Transfer {
+        Transfer {
+            amount: 7,
+            memo: "rent".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let mut registry = TypedRegistry::new();
+        registry.register::<Transfer>().unwrap();
+        assert!(registry.register::<Transfer>().is_ok());
+        assert!(registry.register::<Imposter>().is_err());
+
+        let (headers, payload) = registry.encode("peer_1", &transfer()).unwrap();
+        assert_eq!(schema_of(&headers).unwrap(), ("test.transfer", 2));
+        assert_eq!(
+            registry.decode::<Transfer>(&headers, &payload).unwrap(),
+            transfer()
+        );
+
+        // Invalid values are refused on both sides
+        let zero = Transfer {
+            amount: 0,
+            memo: String::new(),
+        };
+        assert!(registry.encode("peer_1", &zero).is_err());
+        assert!(registry
+            .decode::<Transfer>(&headers, b"{\"amount\":0}")
+            .is_err());
+    }
+
+    #[test]
+    fn test_version_negotiation() {
+        let mut registry = TypedRegistry::new();
+        registry.register::<Transfer>().unwrap();
+        assert_eq!(
+            registry.negotiated_version("old_peer", "test.transfer"),
+            Some(2)
+        );
+
+        let mut old = SchemaAdvertisement::default();
+        old.schemas
+            .insert("test.transfer".to_string(), VersionRange { min: 1, max: 1 });
+        registry.set_peer_schemas("old_peer", old);
+        let (headers, payload) = registry.encode("old_peer", &transfer()).unwrap();
+        assert_eq!(schema_of(&headers).unwrap().1, 1);
+        assert_eq!(
+            registry
+                .decode::<Transfer>(&headers, &payload)
+                .unwrap()
+                .memo,
+            ""
+        );
+
+        // A peer without the schema cannot be sent the type
+        registry.set_peer_schemas("other_peer", SchemaAdvertisement::default());
+        assert!(registry.encode("other_peer", &transfer()).is_err());
+        assert_eq!(
+            VersionRange { min: 3, max: 4 }.negotiate(&VersionRange { min: 1, max: 2 }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dispatch_to_handler() {
+        let mut registry = TypedRegistry::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        registry
+            .on::<Transfer>(Arc::new(move |peer_id: &str, value: Transfer| {
+                sink.lock()
+                    .unwrap()
+                    .push((peer_id.to_string(), value.amount));
+                Ok(())
+            }))
+            .unwrap();
+
+        let (headers, payload) = registry.encode("peer_1", &transfer()).unwrap();
+        assert!(matches!(
+            registry.dispatch("peer_1", &headers, &payload),
+            Some(Ok(()))
+        ));
+        assert!(registry
+            .dispatch("peer_1", &BTreeMap::new(), &payload)
+            .is_none());
+        assert!(matches!(
+            registry.dispatch("peer_1", &headers, b"not json"),
+            Some(Err(_))
+        ));
+        assert_eq!(*seen.lock().unwrap(), vec![("peer_1".to_string(), 7)]);
+    }
+}
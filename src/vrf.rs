@@ -0,0 +1,351 @@
+//! # Verifiable Random Function - ECVRF over Edwards25519
+//!
+//! Elliptic-curve VRF following the structure of RFC 9381
+//! (ECVRF-EDWARDS25519-TAI) with SHA3-512 as the hash function. The holder of
+//! a VRF secret key can compute a pseudorandom output for any input together
+//! with a proof; anyone holding the public key can check that the output is
+//! the unique, correct one for that input without learning the secret key.
+//!
+//! ## Properties
+//!
+//! - **Uniqueness**: Each (public key, input) pair has exactly one valid output
+//! - **Pseudorandomness**: Outputs are unpredictable without the secret key
+//! - **Verifiability**: 80-byte proofs check against the 32-byte public key
+//!
+//! Unlike signature-derived randomness, the output cannot be ground by the
+//! key holder, which makes it suitable for leader election.
+//!
+//! ## Usage Examples
+//!
+//! ```rust,no_run
+//! use quantum_forge_secure_comms::vrf::VrfSecretKey;
+//!
+//! let secret = VrfSecretKey::from_seed([7u8; 32]);
+//! let public = secret.public_key();
+//!
+//! let (output, proof) = secret.prove(b"round 42");
+//! let verified = public.verify(b"round 42", &proof).unwrap();
+//! assert_eq!(output, verified);
+//! ```
+
+use crate::{Result, SecureCommsError};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_512};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Domain separation tag for this VRF suite
+const SUITE: &[u8] = b"QF-ECVRF-EDWARDS25519-SHA3-512-TAI";
+
+/// Encoded proof length in bytes
+pub const VRF_PROOF_LENGTH: usize = 80;
+
+/// Output length in bytes
+pub const VRF_OUTPUT_LENGTH: usize = 64;
+
+/// VRF secret key
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct VrfSecretKey {
+    seed: [u8; 32],
+}
+
+impl std::fmt::Debug for VrfSecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VrfSecretKey").finish_non_exhaustive()
+    }
+}
+
+impl VrfSecretKey {
+    /// Derive a secret key from a 32-byte seed
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self { seed }
+    }
+
+    /// Secret scalar and nonce prefix expanded from the seed
+    fn expand(&self) -> (Scalar, [u8; 32]) {
+        let mut expanded = Sha3_512::digest(self.seed);
+        let mut scalar_bytes = [0u8; 32];
+        let mut nonce_prefix = [0u8; 32];
+        scalar_bytes.copy_from_slice(&expanded[..32]);
+        nonce_prefix.copy_from_slice(&expanded[32..]);
+        expanded.as_mut_slice().zeroize();
+
+        let scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+        scalar_bytes.zeroize();
+        (scalar, nonce_prefix)
+    }
+
+    /// Public key matching this secret key
+    pub fn public_key(&self) -> VrfPublicKey {
+        let (scalar, _) = self.expand();
+        VrfPublicKey::from_point(ED25519_BASEPOINT_POINT * scalar)
+    }
+
+    /// Compute the VRF output for `alpha` and a proof of its correctness
+    pub fn prove(&self, alpha: &[u8]) -> (VrfOutput, VrfProof) {
+        let (secret, nonce_prefix) = self.expand();
+        let public = VrfPublicKey::from_point(ED25519_BASEPOINT_POINT * secret);
+
+        // Hashing to the curve fails with probability 2^-256
+        let h = hash_to_curve(&public.bytes, alpha).expect("hash to curve exhausted");
+        let gamma = h * secret;
+
+        let mut hasher = Sha3_512::new();
+        hasher.update(nonce_prefix);
+        hasher.update(h.compress().as_bytes());
+        let mut nonce_bytes = [0u8; 64];
+        nonce_bytes.copy_from_slice(&hasher.finalize());
+        let nonce = Scalar::from_bytes_mod_order_wide(&nonce_bytes);
+        nonce_bytes.zeroize();
+
+        let challenge = challenge_bytes(
+            &public.bytes,
+            &h,
+            &gamma,
+            &(ED25519_BASEPOINT_POINT * nonce),
+            &(h * nonce),
+        );
+        let response = nonce + challenge_scalar(&challenge) * secret;
+
+        let proof = VrfProof {
+            gamma: gamma.compress().to_bytes(),
+            challenge,
+            response: response.to_bytes(),
+        };
+        (proof_to_output(&gamma), proof)
+    }
+}
+
+/// VRF public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfPublicKey {
+    point: EdwardsPoint,
+    bytes: [u8; 32],
+}
+
+impl VrfPublicKey {
+    fn from_point(point: EdwardsPoint) -> Self {
+        Self {
+            point,
+            bytes: point.compress().to_bytes(),
+        }
+    }
+
+    /// Parse a compressed public key, rejecting invalid and small-order points
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| {
+            SecureCommsError::CryptoProtocol("VRF public key must be 32 bytes".to_string())
+        })?;
+        let point = CompressedEdwardsY(bytes)
+            .decompress()
+            .filter(|point| !point.is_small_order())
+            .ok_or_else(|| {
+                SecureCommsError::CryptoProtocol("Invalid VRF public key".to_string())
+            })?;
+        Ok(Self { point, bytes })
+    }
+
+    /// Compressed public key bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Verify a proof for `alpha`, returning the VRF output on success
+    pub fn verify(&self, alpha: &[u8], proof: &VrfProof) -> Result<VrfOutput> {
+        let invalid = || SecureCommsError::CryptoProtocol("Invalid VRF proof".to_string());
+
+        let gamma = CompressedEdwardsY(proof.gamma)
+            .decompress()
+            .ok_or_else(invalid)?;
+        let response: Option<Scalar> = Scalar::from_canonical_bytes(proof.response).into();
+        let response = response.ok_or_else(invalid)?;
+        let challenge = challenge_scalar(&proof.challenge);
+        let h = hash_to_curve(&self.bytes, alpha).ok_or_else(invalid)?;
+
+        let u = ED25519_BASEPOINT_POINT * response - self.point * challenge;
+        let v = h * response - gamma * challenge;
+        if challenge_bytes(&self.bytes, &h, &gamma, &u, &v) != proof.challenge {
+            return Err(invalid());
+        }
+        Ok(proof_to_output(&gamma))
+    }
+}
+
+/// VRF proof: Gamma point, 128-bit challenge and response scalar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VrfProof {
+    /// Compressed Gamma point
+    pub gamma: [u8; 32],
+    /// Truncated challenge
+    pub challenge: [u8; 16],
+    /// Response scalar
+    pub response: [u8; 32],
+}
+
+impl VrfProof {
+    /// Encode as `gamma || challenge || response`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(VRF_PROOF_LENGTH);
+        bytes.extend_from_slice(&self.gamma);
+        bytes.extend_from_slice(&self.challenge);
+        bytes.extend_from_slice(&self.response);
+        bytes
+    }
+
+    /// Decode from `gamma || challenge || response`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != VRF_PROOF_LENGTH {
+            return Err(SecureCommsError::CryptoProtocol(format!(
+                "VRF proof must be {} bytes, got {}",
+                VRF_PROOF_LENGTH,
+                bytes.len()
+            )));
+        }
+        let mut proof = Self {
+            gamma: [0u8; 32],
+            challenge: [0u8; 16],
+            response: [0u8; 32],
+        };
+        proof.gamma.copy_from_slice(&bytes[..32]);
+        proof.challenge.copy_from_slice(&bytes[32..48]);
+        proof.response.copy_from_slice(&bytes[48..]);
+        Ok(proof)
+    }
+}
+
+/// VRF output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfOutput([u8; VRF_OUTPUT_LENGTH]);
+
+impl VrfOutput {
+    /// Output bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Output mapped uniformly onto [0, 1)
+    pub fn to_unit_interval(&self) -> f64 {
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&self.0[..8]);
+        // 53 bits fill an f64 mantissa exactly
+        (u64::from_be_bytes(prefix) >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Try-and-increment hash onto the prime-order subgroup
+fn hash_to_curve(public_key: &[u8; 32], alpha: &[u8]) -> Option<EdwardsPoint> {
+    for counter in 0u8..=255 {
+        let mut hasher = Sha3_512::new();
+        hasher.update(SUITE);
+        hasher.update([0x01]);
+        hasher.update(public_key);
+        hasher.update(alpha);
+        hasher.update([counter, 0x00]);
+        let digest = hasher.finalize();
+
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&digest[..32]);
+        if let Some(point) = CompressedEdwardsY(candidate).decompress() {
+            let point = point.mul_by_cofactor();
+            if !point.is_identity() {
+                return Some(point);
+            }
+        }
+    }
+    None
+}
+
+fn challenge_bytes(
+    public_key: &[u8; 32],
+    h: &EdwardsPoint,
+    gamma: &EdwardsPoint,
+    u: &EdwardsPoint,
+    v: &EdwardsPoint,
+) -> [u8; 16] {
+    let mut hasher = Sha3_512::new();
+    hasher.update(SUITE);
+    hasher.update([0x02]);
+    hasher.update(public_key);
+    for point in [h, gamma, u, v] {
+        hasher.update(point.compress().as_bytes());
+    }
+    hasher.update([0x00]);
+
+    let mut challenge = [0u8; 16];
+    challenge.copy_from_slice(&hasher.finalize()[..16]);
+    challenge
+}
+
+fn challenge_scalar(challenge: &[u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(challenge);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+fn proof_to_output(gamma: &EdwardsPoint) -> VrfOutput {
+    let mut hasher = Sha3_512::new();
+    hasher.update(SUITE);
+    hasher.update([0x03]);
+    hasher.update(gamma.mul_by_cofactor().compress().as_bytes());
+    hasher.update([0x00]);
+
+    let mut output = [0u8; VRF_OUTPUT_LENGTH];
+    output.copy_from_slice(&hasher.finalize());
+    VrfOutput(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prove_and_verify() {
+        let secret = VrfSecretKey::from_seed([7u8; 32]);
+        let public = secret.public_key();
+
+        let (output, proof) = secret.prove(b"round 1");
+        assert_eq!(public.verify(b"round 1", &proof).unwrap(), output);
+
+        // Deterministic: the same input always yields the same output
+        let (again, _) = secret.prove(b"round 1");
+        assert_eq!(output, again);
+    }
+
+    #[test]
+    fn test_wrong_input_or_key_rejected() {
+        let secret = VrfSecretKey::from_seed([7u8; 32]);
+        let other = VrfSecretKey::from_seed([8u8; 32]).public_key();
+        let (_, proof) = secret.prove(b"round 1");
+
+        assert!(secret.public_key().verify(b"round 2", &proof).is_err());
+        assert!(other.verify(b"round 1", &proof).is_err());
+    }
+
+    #[test]
+    fn test_tampered_proof_rejected() {
+        let secret = VrfSecretKey::from_seed([7u8; 32]);
+        let (_, proof) = secret.prove(b"round 1");
+
+        let mut tampered = proof;
+        tampered.response[0] ^= 1;
+        assert!(secret.public_key().verify(b"round 1", &tampered).is_err());
+    }
+
+    #[test]
+    fn test_encoding_roundtrip() {
+        let secret = VrfSecretKey::from_seed([7u8; 32]);
+        let public = secret.public_key();
+        let (output, proof) = secret.prove(b"round 1");
+
+        let decoded = VrfProof::from_bytes(&proof.to_bytes()).unwrap();
+        let public = VrfPublicKey::from_bytes(&public.to_bytes()).unwrap();
+        assert_eq!(public.verify(b"round 1", &decoded).unwrap(), output);
+        assert!(VrfProof::from_bytes(&[0u8; 10]).is_err());
+
+        let unit = output.to_unit_interval();
+        assert!((0.0..1.0).contains(&unit));
+    }
+}
@@ -1,6 +1,5 @@
 use quantum_forge_secure_comms::StreamlinedSecureClient;
 use tokio::time::{sleep, Duration, Instant};
-use rand;
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -292,12 +291,12 @@ async fn test_distributed_ledger_consensus() -> Result<()> {
     
     // Ensure both sides of all connections are established
     for i in 0..nodes.len() {
-        for j in (i + 1)..nodes.len() {
+        for (j, node) in nodes.iter_mut().enumerate().skip(i + 1) {
             let peer_id_i = format!("node_{}", i);
             let _peer_id_j = format!("node_{}", j);
             
             // Establish connection from j to i as well
-            let _reverse_connection = nodes[j].establish_secure_channel(&peer_id_i).await?;
+            let _reverse_connection = node.establish_secure_channel(&peer_id_i).await?;
         }
     }
     
@@ -312,7 +311,7 @@ async fn test_distributed_ledger_consensus() -> Result<()> {
     println!("✅ Full mesh connectivity verified");
     
     // Test ledger consensus scenario
-    let ledger_entries = vec![
+    let ledger_entries = [
         b"block_1:hash_abc123:txs_5" as &[u8],
         b"block_2:hash_def456:txs_8",
         b"block_3:hash_ghi789:txs_12",
@@ -331,14 +330,14 @@ async fn test_distributed_ledger_consensus() -> Result<()> {
         
         // Other nodes validate and vote
         let mut validation_responses = Vec::new();
-        for i in 1..nodes.len() {
+        for node in nodes.iter_mut().skip(1) {
             let vote_data: &[u8] = if rand::random::<f32>() > 0.1 { // 90% honest voting
                 b"VOTE_APPROVE"
             } else {
                 b"VOTE_REJECT"
             };
             
-            let vote_response = nodes[i].send_secure_message("node_0", vote_data).await?;
+            let vote_response = node.send_secure_message("node_0", vote_data).await?;
             validation_responses.push(vote_response);
         }
         
@@ -507,8 +506,8 @@ async fn test_blockchain_performance_under_load() -> Result<()> {
     
     // Verify transaction integrity
     for (i, tx) in processed_transactions.iter().enumerate() {
-        assert!(tx.message_id.len() > 0);
-        assert!(tx.payload.len() > 0);
+        assert!(!tx.message_id.is_empty());
+        assert!(!tx.payload.is_empty());
         assert!(String::from_utf8_lossy(&tx.payload).contains(&format!("high_load_tx_{}", i)));
     }
     
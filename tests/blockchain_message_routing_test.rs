@@ -94,6 +94,7 @@ async fn test_blockchain_consensus_message_validation() -> Result<()> {
     let _v1_v2 = validator_1.establish_secure_channel("validator_2").await?;
     let _v1_v3 = validator_1.establish_secure_channel("validator_3").await?;
     let _v2_v3 = validator_2.establish_secure_channel("validator_3").await?;
+    let _v3_v1 = validator_3.establish_secure_channel("validator_1").await?;
     let _client_v1 = client.establish_secure_channel("validator_1").await?;
     let _client_v2 = client.establish_secure_channel("validator_2").await?;
     let _client_v3 = client.establish_secure_channel("validator_3").await?;
@@ -375,7 +376,7 @@ async fn test_blockchain_message_ordering_and_sequencing() -> Result<()> {
     println!("✅ Established sequencer → processors topology");
     
     // Send ordered sequence of blockchain messages
-    let message_sequence = vec![
+    let message_sequence = [
         b"SEQ_1:BLOCK_PROPOSAL:height_100" as &[u8],
         b"SEQ_2:TRANSACTION_BATCH:txs_50",
         b"SEQ_3:CONSENSUS_VOTE:approve",
@@ -282,7 +282,7 @@ async fn test_protocol_edge_cases() -> Result<(), SecureCommsError> {
         // Use character-aware slicing for Unicode safety
         let display_text = message.chars().take(20).collect::<String>();
         println!("✅ Special encoding: {} ({} bytes)", 
-            display_text, message.as_bytes().len());
+            display_text, message.len());
     }
     
     println!("✅ Protocol edge cases test completed successfully");
@@ -108,7 +108,7 @@ async fn test_multi_user_group_messaging() -> Result<()> {
     let msg1_3 = alice.send_secure_message("diana", "Hey everyone! Let's plan our quantum computing meetup.".as_bytes()).await?;
     message_count += 3;
     println!("📤 Alice → All: {} ({}, {}, {})", 
-        "Hey everyone! Let's plan our quantum computing meetup."[..30].to_string(),
+        &"Hey everyone! Let's plan our quantum computing meetup."[..30],
         &msg1_1.message_id[..8], &msg1_2.message_id[..8], &msg1_3.message_id[..8]);
     
     // Bob's message
@@ -117,7 +117,7 @@ async fn test_multi_user_group_messaging() -> Result<()> {
     let msg2_3 = bob.send_secure_message("diana", "Great idea Alice! I can present on post-quantum cryptography.".as_bytes()).await?;
     message_count += 3;
     println!("📤 Bob → All: {} ({}, {}, {})", 
-        "Great idea Alice! I can present on post-quantum cryptography."[..30].to_string(),
+        &"Great idea Alice! I can present on post-quantum cryptography."[..30],
         &msg2_1.message_id[..8], &msg2_2.message_id[..8], &msg2_3.message_id[..8]);
     
     // Charlie's message
@@ -126,7 +126,7 @@ async fn test_multi_user_group_messaging() -> Result<()> {
     let msg3_3 = charlie.send_secure_message("diana", "I'll cover quantum key distribution protocols.".as_bytes()).await?;
     message_count += 3;
     println!("📤 Charlie → All: {} ({}, {}, {})", 
-        "I'll cover quantum key distribution protocols."[..30].to_string(),
+        &"I'll cover quantum key distribution protocols."[..30],
         &msg3_1.message_id[..8], &msg3_2.message_id[..8], &msg3_3.message_id[..8]);
     
     // Diana's message
@@ -135,7 +135,7 @@ async fn test_multi_user_group_messaging() -> Result<()> {
     let msg4_3 = diana.send_secure_message("charlie", "Perfect! I'll handle the quantum random number generation topic.".as_bytes()).await?;
     message_count += 3;
     println!("📤 Diana → All: {} ({}, {}, {})", 
-        "Perfect! I'll handle the quantum random number generation topic."[..30].to_string(),
+        &"Perfect! I'll handle the quantum random number generation topic."[..30],
         &msg4_1.message_id[..8], &msg4_2.message_id[..8], &msg4_3.message_id[..8]);
     
     // Final Alice message
@@ -144,7 +144,7 @@ async fn test_multi_user_group_messaging() -> Result<()> {
     let msg5_3 = alice.send_secure_message("diana", "Excellent! Meeting scheduled for next Friday at 2 PM.".as_bytes()).await?;
     message_count += 3;
     println!("📤 Alice → All: {} ({}, {}, {})", 
-        "Excellent! Meeting scheduled for next Friday at 2 PM."[..30].to_string(),
+        &"Excellent! Meeting scheduled for next Friday at 2 PM."[..30],
         &msg5_1.message_id[..8], &msg5_2.message_id[..8], &msg5_3.message_id[..8]);
     
     println!("✅ Sent {} messages in group conversation", message_count);
@@ -382,7 +382,7 @@ async fn test_message_ordering_and_delivery() -> Result<()> {
     println!("✅ Established bidirectional channels");
     
     // Send ordered sequence of messages
-    let message_sequence = vec![
+    let message_sequence = [
         "Message 1: Starting sequence",
         "Message 2: This is the second message",
         "Message 3: Third message in sequence",
@@ -451,7 +451,7 @@ async fn test_error_recovery_and_resilience() -> Result<()> {
     
     // Test 5: Rapid successive messages
     println!("🔍 Test 5: Rapid successive messages");
-    let rapid_count = 20;
+    let rapid_count: usize = 20;
     let mut rapid_messages = Vec::new();
     
     for i in 0..rapid_count {
@@ -460,7 +460,7 @@ async fn test_error_recovery_and_resilience() -> Result<()> {
         rapid_messages.push(sent_msg);
         
         if i % 5 == 0 {
-            let start_range = if i >= 4 { i - 4 } else { 0 };
+            let start_range = i.saturating_sub(4);
             println!("📤 Sent rapid message batch: {}-{}", start_range, i + 1);
         }
     }
@@ -491,19 +491,19 @@ async fn test_real_world_usage_patterns() -> Result<()> {
     // Business communication flow - sequential to avoid borrowing issues
     let msg1 = manager.send_secure_message("employee_1", "Please review the Q4 financial report by EOD.".as_bytes()).await?;
     println!("💼 Manager → Employee1: {} ({})", 
-        "Please review the Q4 financial report by EOD."[..30].to_string(), &msg1.message_id[..8]);
+        &"Please review the Q4 financial report by EOD."[..30], &msg1.message_id[..8]);
     
     let msg2 = manager.send_secure_message("employee_2", "Can you prepare the client presentation for tomorrow?".as_bytes()).await?;
     println!("💼 Manager → Employee2: {} ({})", 
-        "Can you prepare the client presentation for tomorrow?"[..30].to_string(), &msg2.message_id[..8]);
+        &"Can you prepare the client presentation for tomorrow?"[..30], &msg2.message_id[..8]);
     
     let msg3 = employee1.send_secure_message("manager", "Financial report reviewed. Found 3 discrepancies to discuss.".as_bytes()).await?;
     println!("💼 Employee1 → Manager: {} ({})", 
-        "Financial report reviewed. Found 3 discrepancies to discuss."[..30].to_string(), &msg3.message_id[..8]);
+        &"Financial report reviewed. Found 3 discrepancies to discuss."[..30], &msg3.message_id[..8]);
     
     let msg4 = employee2.send_secure_message("manager", "Presentation ready. Sent to your secure folder.".as_bytes()).await?;
     println!("💼 Employee2 → Manager: {} ({})", 
-        "Presentation ready. Sent to your secure folder."[..30].to_string(), &msg4.message_id[..8]);
+        &"Presentation ready. Sent to your secure folder."[..30], &msg4.message_id[..8]);
     
     // Scenario 2: File Transfer Simulation
     println!("📋 Scenario 2: Secure File Transfer");
@@ -135,49 +135,49 @@ async fn test_business_team_collaboration() -> Result<(), SecureCommsError> {
     // Sequential message sending to avoid borrowing issues
     let msg1 = manager.send_secure_message("developer", "Please provide estimates for the new feature implementation.".as_bytes()).await?;
     println!("💼 Manager → Developer: {} ({})", 
-        "Please provide estimates for the new feature implementation."[..40].to_string(), &msg1.message_id[..8]);
+        &"Please provide estimates for the new feature implementation."[..40], &msg1.message_id[..8]);
     assert_eq!(msg1.payload, "Please provide estimates for the new feature implementation.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg2 = developer.send_secure_message("manager", "Initial estimate: 2 weeks for backend, 1 week for API integration.".as_bytes()).await?;
     println!("💼 Developer → Manager: {} ({})", 
-        "Initial estimate: 2 weeks for backend, 1 week for API integration."[..40].to_string(), &msg2.message_id[..8]);
+        &"Initial estimate: 2 weeks for backend, 1 week for API integration."[..40], &msg2.message_id[..8]);
     assert_eq!(msg2.payload, "Initial estimate: 2 weeks for backend, 1 week for API integration.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg3 = manager.send_secure_message("designer", "Can you create mockups for the new user interface?".as_bytes()).await?;
     println!("💼 Manager → Designer: {} ({})", 
-        "Can you create mockups for the new user interface?"[..40].to_string(), &msg3.message_id[..8]);
+        &"Can you create mockups for the new user interface?"[..40], &msg3.message_id[..8]);
     assert_eq!(msg3.payload, "Can you create mockups for the new user interface?".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg4 = designer.send_secure_message("manager", "I'll have the UI mockups ready by tomorrow morning.".as_bytes()).await?;
     println!("💼 Designer → Manager: {} ({})", 
-        "I'll have the UI mockups ready by tomorrow morning."[..40].to_string(), &msg4.message_id[..8]);
+        &"I'll have the UI mockups ready by tomorrow morning."[..40], &msg4.message_id[..8]);
     assert_eq!(msg4.payload, "I'll have the UI mockups ready by tomorrow morning.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg5 = manager.send_secure_message("qa_engineer", "Please prepare test cases for the new feature.".as_bytes()).await?;
     println!("💼 Manager → QA Engineer: {} ({})", 
-        "Please prepare test cases for the new feature."[..40].to_string(), &msg5.message_id[..8]);
+        &"Please prepare test cases for the new feature."[..40], &msg5.message_id[..8]);
     assert_eq!(msg5.payload, "Please prepare test cases for the new feature.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg6 = qa_engineer.send_secure_message("manager", "Test plan will be ready by Friday. Need feature specs first.".as_bytes()).await?;
     println!("💼 QA Engineer → Manager: {} ({})", 
-        "Test plan will be ready by Friday. Need feature specs first."[..40].to_string(), &msg6.message_id[..8]);
+        &"Test plan will be ready by Friday. Need feature specs first."[..40], &msg6.message_id[..8]);
     assert_eq!(msg6.payload, "Test plan will be ready by Friday. Need feature specs first.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg7 = developer.send_secure_message("designer", "Need to coordinate on the API data structure for UI.".as_bytes()).await?;
     println!("💼 Developer → Designer: {} ({})", 
-        "Need to coordinate on the API data structure for UI."[..40].to_string(), &msg7.message_id[..8]);
+        &"Need to coordinate on the API data structure for UI."[..40], &msg7.message_id[..8]);
     assert_eq!(msg7.payload, "Need to coordinate on the API data structure for UI.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
     let msg8 = designer.send_secure_message("developer", "Let's schedule a sync meeting for API-UI alignment.".as_bytes()).await?;
     println!("💼 Designer → Developer: {} ({})", 
-        "Let's schedule a sync meeting for API-UI alignment."[..40].to_string(), &msg8.message_id[..8]);
+        &"Let's schedule a sync meeting for API-UI alignment."[..40], &msg8.message_id[..8]);
     assert_eq!(msg8.payload, "Let's schedule a sync meeting for API-UI alignment.".as_bytes());
     sleep(Duration::from_millis(100)).await;
     
@@ -261,19 +261,19 @@ async fn test_customer_support_scenario() -> Result<(), SecureCommsError> {
     
     let msg1 = customer.send_secure_message("support_agent", "Hello, I'm having trouble with secure file transfers. The system keeps failing.".as_bytes()).await?;
     println!("🎧 Customer → Support: {} ({})", 
-        "Hello, I'm having trouble with secure file transfers. The system keeps failing."[..50].to_string(), &msg1.message_id[..8]);
+        &"Hello, I'm having trouble with secure file transfers. The system keeps failing."[..50], &msg1.message_id[..8]);
     
     let msg2 = support_agent.send_secure_message("customer", "Hi! I'm here to help. Can you tell me what error message you're seeing?".as_bytes()).await?;
     println!("🎧 Support → Customer: {} ({})", 
-        "Hi! I'm here to help. Can you tell me what error message you're seeing?"[..50].to_string(), &msg2.message_id[..8]);
+        &"Hi! I'm here to help. Can you tell me what error message you're seeing?"[..50], &msg2.message_id[..8]);
     
     let msg3 = customer.send_secure_message("support_agent", "The error says 'Channel not established' but I'm sure I set up the connection correctly.".as_bytes()).await?;
     println!("🎧 Customer → Support: {} ({})", 
-        "The error says 'Channel not established' but I'm sure I set up the connection correctly."[..50].to_string(), &msg3.message_id[..8]);
+        &"The error says 'Channel not established' but I'm sure I set up the connection correctly."[..50], &msg3.message_id[..8]);
     
     let msg4 = support_agent.send_secure_message("customer", "Let me check your account settings. Can you try sending a small test file?".as_bytes()).await?;
     println!("🎧 Support → Customer: {} ({})", 
-        "Let me check your account settings. Can you try sending a small test file?"[..50].to_string(), &msg4.message_id[..8]);
+        &"Let me check your account settings. Can you try sending a small test file?"[..50], &msg4.message_id[..8]);
     
     // Escalation to technical specialist
     println!("🔧 Escalating to technical specialist");
@@ -286,19 +286,19 @@ async fn test_customer_support_scenario() -> Result<(), SecureCommsError> {
     // Technical specialist conversation - sequential
     let tech_msg1 = support_agent.send_secure_message("technical_specialist", "Customer having issues with large file transfers. Need diagnostic assistance.".as_bytes()).await?;
     println!("🔧 Support → Tech: {} ({})", 
-        "Customer having issues with large file transfers. Need diagnostic assistance."[..50].to_string(), &tech_msg1.message_id[..8]);
+        &"Customer having issues with large file transfers. Need diagnostic assistance."[..50], &tech_msg1.message_id[..8]);
     
     let tech_msg2 = technical_specialist.send_secure_message("support_agent", "I'll handle this. Let me run some diagnostics and test the customer's setup.".as_bytes()).await?;
     println!("🔧 Tech → Support: {} ({})", 
-        "I'll handle this. Let me run some diagnostics and test the customer's setup."[..50].to_string(), &tech_msg2.message_id[..8]);
+        &"I'll handle this. Let me run some diagnostics and test the customer's setup."[..50], &tech_msg2.message_id[..8]);
     
     let tech_msg3 = technical_specialist.send_secure_message("customer", "Hi! I'm a technical specialist. Let's test your file transfer capability step by step.".as_bytes()).await?;
     println!("🔧 Tech → Customer: {} ({})", 
-        "Hi! I'm a technical specialist. Let's test your file transfer capability step by step."[..50].to_string(), &tech_msg3.message_id[..8]);
+        &"Hi! I'm a technical specialist. Let's test your file transfer capability step by step."[..50], &tech_msg3.message_id[..8]);
     
     let tech_msg4 = customer.send_secure_message("technical_specialist", "Great! I'm ready to test. What should I try first?".as_bytes()).await?;
     println!("🔧 Customer → Tech: {} ({})", 
-        "Great! I'm ready to test. What should I try first?"[..50].to_string(), &tech_msg4.message_id[..8]);
+        &"Great! I'm ready to test. What should I try first?"[..50], &tech_msg4.message_id[..8]);
     
     // File transfer testing simulation
     println!("📁 Testing file transfer capabilities");
@@ -335,19 +335,19 @@ async fn test_customer_support_scenario() -> Result<(), SecureCommsError> {
     
     let resolution_msg1 = technical_specialist.send_secure_message("customer", "All file transfers are working correctly. The issue was with your initial channel setup.".as_bytes()).await?;
     println!("✅ Tech → Customer: {} ({})", 
-        "All file transfers are working correctly. The issue was with your initial channel setup."[..50].to_string(), &resolution_msg1.message_id[..8]);
+        &"All file transfers are working correctly. The issue was with your initial channel setup."[..50], &resolution_msg1.message_id[..8]);
     
     let resolution_msg2 = customer.send_secure_message("technical_specialist", "Thank you! Everything is working perfectly now. I really appreciate the help.".as_bytes()).await?;
     println!("✅ Customer → Tech: {} ({})", 
-        "Thank you! Everything is working perfectly now. I really appreciate the help."[..50].to_string(), &resolution_msg2.message_id[..8]);
+        &"Thank you! Everything is working perfectly now. I really appreciate the help."[..50], &resolution_msg2.message_id[..8]);
     
     let resolution_msg3 = technical_specialist.send_secure_message("support_agent", "Issue resolved. Customer's file transfer capability is fully functional.".as_bytes()).await?;
     println!("✅ Tech → Support: {} ({})", 
-        "Issue resolved. Customer's file transfer capability is fully functional."[..50].to_string(), &resolution_msg3.message_id[..8]);
+        &"Issue resolved. Customer's file transfer capability is fully functional."[..50], &resolution_msg3.message_id[..8]);
     
     let resolution_msg4 = support_agent.send_secure_message("customer", "Glad we could help! Please don't hesitate to contact us if you need further assistance.".as_bytes()).await?;
     println!("✅ Support → Customer: {} ({})", 
-        "Glad we could help! Please don't hesitate to contact us if you need further assistance."[..50].to_string(), &resolution_msg4.message_id[..8]);
+        &"Glad we could help! Please don't hesitate to contact us if you need further assistance."[..50], &resolution_msg4.message_id[..8]);
     
     // Final status check
     println!("📊 Final support session status");
@@ -406,32 +406,32 @@ async fn test_remote_work_collaboration() -> Result<(), SecureCommsError> {
     
     let update1 = remote_dev1.send_secure_message("team_lead", "Yesterday: Completed user authentication module. Today: Working on API integration. Blockers: None.".as_bytes()).await?;
     println!("🗣️ Remote Dev 1 → Team Lead: {} ({})", 
-        "Yesterday: Completed user authentication module. Today: Working on API integration. Blockers: None."[..50].to_string(), &update1.message_id[..8]);
+        &"Yesterday: Completed user authentication module. Today: Working on API integration. Blockers: None."[..50], &update1.message_id[..8]);
     sleep(Duration::from_millis(100)).await;
     
     let update2 = remote_dev2.send_secure_message("team_lead", "Yesterday: Fixed database connection issues. Today: Implementing data validation. Blockers: Need schema review.".as_bytes()).await?;
     println!("🗣️ Remote Dev 2 → Team Lead: {} ({})", 
-        "Yesterday: Fixed database connection issues. Today: Implementing data validation. Blockers: Need schema review."[..50].to_string(), &update2.message_id[..8]);
+        &"Yesterday: Fixed database connection issues. Today: Implementing data validation. Blockers: Need schema review."[..50], &update2.message_id[..8]);
     sleep(Duration::from_millis(100)).await;
     
     let update3 = project_manager.send_secure_message("team_lead", "Yesterday: Updated project timeline. Today: Client meeting at 2 PM. Blockers: Waiting for QA feedback.".as_bytes()).await?;
     println!("🗣️ Project Manager → Team Lead: {} ({})", 
-        "Yesterday: Updated project timeline. Today: Client meeting at 2 PM. Blockers: Waiting for QA feedback."[..50].to_string(), &update3.message_id[..8]);
+        &"Yesterday: Updated project timeline. Today: Client meeting at 2 PM. Blockers: Waiting for QA feedback."[..50], &update3.message_id[..8]);
     sleep(Duration::from_millis(100)).await;
     
     let update4 = team_lead.send_secure_message("remote_dev1", "Great progress on auth! Let me know if you need any help with API integration.".as_bytes()).await?;
     println!("🗣️ Team Lead → Remote Dev 1: {} ({})", 
-        "Great progress on auth! Let me know if you need any help with API integration."[..50].to_string(), &update4.message_id[..8]);
+        &"Great progress on auth! Let me know if you need any help with API integration."[..50], &update4.message_id[..8]);
     sleep(Duration::from_millis(100)).await;
     
     let update5 = team_lead.send_secure_message("remote_dev2", "I'll review the schema today. Can you send me the current draft?".as_bytes()).await?;
     println!("🗣️ Team Lead → Remote Dev 2: {} ({})", 
-        "I'll review the schema today. Can you send me the current draft?"[..50].to_string(), &update5.message_id[..8]);
+        &"I'll review the schema today. Can you send me the current draft?"[..50], &update5.message_id[..8]);
     sleep(Duration::from_millis(100)).await;
     
     let update6 = team_lead.send_secure_message("project_manager", "I'll prepare QA feedback before your client meeting.".as_bytes()).await?;
     println!("🗣️ Team Lead → Project Manager: {} ({})", 
-        "I'll prepare QA feedback before your client meeting."[..50].to_string(), &update6.message_id[..8]);
+        &"I'll prepare QA feedback before your client meeting."[..50], &update6.message_id[..8]);
     sleep(Duration::from_millis(100)).await;
     
     // Code review process
@@ -577,32 +577,32 @@ async fn test_emergency_communication_protocol() -> Result<(), SecureCommsError>
     
     let response1 = security_team.send_secure_message("incident_commander", "Security team responding. Initiating threat analysis and access review.".as_bytes()).await?;
     println!("🚨 Security → Commander: {} ({})", 
-        "Security team responding. Initiating threat analysis and access review."[..50].to_string(), &response1.message_id[..8]);
+        &"Security team responding. Initiating threat analysis and access review."[..50], &response1.message_id[..8]);
     sleep(Duration::from_millis(50)).await;
     
     let response2 = technical_team.send_secure_message("incident_commander", "Technical team online. Beginning system integrity check and log analysis.".as_bytes()).await?;
     println!("🚨 Technical → Commander: {} ({})", 
-        "Technical team online. Beginning system integrity check and log analysis."[..50].to_string(), &response2.message_id[..8]);
+        &"Technical team online. Beginning system integrity check and log analysis."[..50], &response2.message_id[..8]);
     sleep(Duration::from_millis(50)).await;
     
     let response3 = management.send_secure_message("incident_commander", "Management notified. Preparing stakeholder communication. What's the severity?".as_bytes()).await?;
     println!("🚨 Management → Commander: {} ({})", 
-        "Management notified. Preparing stakeholder communication. What's the severity?"[..50].to_string(), &response3.message_id[..8]);
+        &"Management notified. Preparing stakeholder communication. What's the severity?"[..50], &response3.message_id[..8]);
     sleep(Duration::from_millis(50)).await;
     
     let response4 = incident_commander.send_secure_message("security_team", "Priority 1 incident. Isolate affected systems immediately.".as_bytes()).await?;
     println!("🚨 Commander → Security: {} ({})", 
-        "Priority 1 incident. Isolate affected systems immediately."[..50].to_string(), &response4.message_id[..8]);
+        &"Priority 1 incident. Isolate affected systems immediately."[..50], &response4.message_id[..8]);
     sleep(Duration::from_millis(50)).await;
     
     let response5 = incident_commander.send_secure_message("technical_team", "Run full security scan and provide status in 5 minutes.".as_bytes()).await?;
     println!("🚨 Commander → Technical: {} ({})", 
-        "Run full security scan and provide status in 5 minutes."[..50].to_string(), &response5.message_id[..8]);
+        &"Run full security scan and provide status in 5 minutes."[..50], &response5.message_id[..8]);
     sleep(Duration::from_millis(50)).await;
     
     let response6 = incident_commander.send_secure_message("management", "Severity: High. Potential data exposure. Recommend immediate action.".as_bytes()).await?;
     println!("🚨 Commander → Management: {} ({})", 
-        "Severity: High. Potential data exposure. Recommend immediate action."[..50].to_string(), &response6.message_id[..8]);
+        &"Severity: High. Potential data exposure. Recommend immediate action."[..50], &response6.message_id[..8]);
     sleep(Duration::from_millis(50)).await;
     
     // Critical data transmission